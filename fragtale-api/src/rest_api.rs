@@ -25,31 +25,86 @@
 //! TODO: WebTransport over HTTP/3 like [wtransport](https://github.com/BiagioFesta/wtransport)
 //! when K8s Gateway API w h3 support is standard.
 
+mod admin_resources {
+    //! Administrative API resources.
+
+    pub mod capabilities_resource;
+    pub mod consumer_definitions_resource;
+    pub mod consumption_audit_resource;
+    pub mod dead_letters_resource;
+    pub mod diagnostic_query_resource;
+    pub mod duplicate_events_resource;
+    pub mod group_members_resource;
+    pub mod publish_rejections_resource;
+    pub mod rejected_events_resource;
+    pub mod resource_grants_resource;
+    pub mod retention_preview_resource;
+    pub mod shared_schemas_resource;
+    pub mod subscription_health_resource;
+    pub mod topic_bulk_ingest_resource;
+    pub mod topic_counts_resource;
+    pub mod topic_index_rebuild_resource;
+    pub mod topic_inspection_resource;
+    pub mod topic_mirror_resource;
+    pub mod topic_overview_resource;
+    pub mod topic_purge_resource;
+    pub mod topic_snapshot_resource;
+    pub mod topic_statistics_resource;
+    pub mod web_socket_sessions_resource;
+}
 mod http_resources {
     //! API resources
 
     pub mod confirm_delivery;
+    pub mod consumer_position_resource;
+    pub mod event_annotation_resource;
     pub mod event_by_correlation_resource;
     pub mod event_by_id_resource;
     pub mod event_description_resource;
     pub mod event_ids_by_index_resource;
+    pub mod event_locations_resource;
+    pub mod event_peek_resource;
     pub mod event_poll_resource;
+    pub mod event_range_resource;
+    pub mod publish_grant_resource;
     pub mod publish_resource;
+    pub mod reply_topic_resource;
+    pub mod topic_access_resource;
 }
 mod common {
     //! Common RESP API resources and utils.
 
     mod api_error_mapper;
     mod bearer_token_authentication_checker;
+    mod client_certificate_authentication;
+    mod keep_alive_query_params;
+    mod member_query_params;
     mod next_query_params;
+    mod protocol_negotiation;
+    mod request_timeout;
+    mod shared_document_body;
     mod utoipa_security_scheme_modifier;
+    mod web_socket_session_registry;
 
     pub use api_error_mapper::*;
     pub use bearer_token_authentication_checker::*;
+    pub use client_certificate_authentication::ClientCertificateAuthentication;
+    pub use keep_alive_query_params::KeepAliveQueryParams;
+    pub use member_query_params::MemberQueryParams;
     pub use next_query_params::NextQueryParams;
+    pub use protocol_negotiation::NegotiatedProtocolVersion;
+    pub use protocol_negotiation::ProtocolNegotiation;
+    pub use request_timeout::RequestTimeout;
+    pub use shared_document_body::SharedDocumentBody;
     pub use utoipa_security_scheme_modifier::*;
+    pub use web_socket_session_registry::WebSocketSessionRegistry;
 }
 //mod health_resources;
+mod ui_resources {
+    //! Embedded operator web UI.
+
+    pub mod web_ui_resource;
+}
 mod ws_resources {
     //! WebSocket resources.
     //!
@@ -59,21 +114,29 @@ mod ws_resources {
     //! connection pools with multiple connections per client.
 
     pub mod ws_confirm_resource;
+    pub mod ws_multiplex_resource;
     pub mod ws_publish_resource;
     pub mod ws_subscribe_resource;
 }
 
 use self::common::BearerTokenAuthenticationChecker;
+use self::common::ClientCertificateAuthentication;
+use self::common::ProtocolNegotiation;
+use self::common::RequestTimeout;
 use self::common::UtopiaSecuritySchemeModifier;
+use self::common::WebSocketSessionRegistry;
 use actix_web::App;
 use actix_web::HttpResponse;
 use actix_web::HttpServer;
 use actix_web::Responder;
 use actix_web::get;
 use actix_web::http::header::ContentType;
+use actix_web::middleware::Condition;
+use actix_web::middleware::Logger;
 use actix_web::web;
 use fragtale_core::conf::AppConfig;
 use fragtale_core::mb::MessageBroker;
+use fragtale_core::mb::auth::ClientAuthenticationMode;
 use std::sync::Arc;
 use tyst_api_rest_health::AppHealth;
 use tyst_api_rest_health::health_resources;
@@ -85,8 +148,10 @@ const WORKERS_PER_CORE: usize = 1024;
 /// Shared state between requests.
 #[derive(Clone)]
 struct AppState {
+    app_config: Arc<AppConfig>,
     mb: Arc<MessageBroker>,
     auth: Arc<BearerTokenAuthenticationChecker>,
+    ws_sessions: Arc<WebSocketSessionRegistry>,
 }
 
 /// Simple health check that gets the provider instance.
@@ -116,55 +181,203 @@ pub async fn run_http_server(
     mb: &Arc<MessageBroker>,
 ) -> Result<(), Box<dyn core::error::Error>> {
     let app_config = Arc::clone(app_config);
-    let auth = BearerTokenAuthenticationChecker::new(app_config.api.audience()).await?;
+    let client_authentication_mode =
+        ClientAuthenticationMode::from_name(app_config.api.authentication()).ok_or_else(|| {
+            format!(
+                "Unsupported API authentication '{}'. Use 'bearer', 'mtls' or 'any'.",
+                app_config.api.authentication()
+            )
+        })?;
+    // Client certificates are only available when the API is served over TLS
+    let tls_server_config = if client_authentication_mode.accepts_client_certificate() {
+        Some(ClientCertificateAuthentication::server_config(
+            &app_config,
+            client_authentication_mode,
+        )?)
+    } else {
+        None
+    };
+    let scheme = if tls_server_config.is_some() {
+        "https"
+    } else {
+        "http"
+    };
+    let auth = BearerTokenAuthenticationChecker::new(
+        app_config.api.audience(),
+        &app_config.api.oidc_issuers(),
+        app_config.api.oidc_trust_anchors_file(),
+        app_config.dev.token(),
+        client_authentication_mode,
+    )
+    .await?;
     let workers = app_config.limits.available_parallelism();
     let max_connections = WORKERS_PER_CORE * workers;
-    log::info!(
-        "API described by http://{}:{}/openapi.json allows {max_connections} concurrent connections.",
-        &app_config.api.bind_address(),
-        &app_config.api.bind_port(),
-    );
+    // Worker instances only serve health and metrics
+    let api_enabled = mb.is_serving_api();
+    if api_enabled {
+        log::info!(
+            "API described by {scheme}://{}:{}/openapi.json allows {max_connections} concurrent connections.",
+            &app_config.api.bind_address(),
+            &app_config.api.bind_port(),
+        );
+    } else {
+        log::info!(
+            "Only health and metrics are served at {scheme}://{}:{}/.",
+            &app_config.api.bind_address(),
+            &app_config.api.bind_port(),
+        );
+    }
+    let ws_sessions = WebSocketSessionRegistry::new(&app_config);
     let app_state: AppState = AppState {
+        app_config: Arc::clone(&app_config),
         mb: Arc::clone(mb),
         auth,
+        ws_sessions,
     };
     let app_data = web::Data::<AppState>::new(app_state);
     let app_health = web::Data::<Arc<dyn AppHealth>>::new(MessageBrokerHealth::with_app(mb));
+    let ui_enabled = api_enabled && app_config.api.ui_enabled();
+    // Log every request to aid local development
+    let dev_enabled = app_config.dev.enabled();
+    if dev_enabled {
+        if app_config.dev.token().is_some() {
+            log::warn!(
+                "Developer mode accepts the configured static bearer token with unrestricted access."
+            );
+        } else {
+            log::warn!(
+                "Developer mode accepts no static bearer token, since none has been configured."
+            );
+        }
+    }
+    if ui_enabled {
+        log::info!(
+            "Operator web UI is served at {scheme}://{}:{}/ui/",
+            &app_config.api.bind_address(),
+            &app_config.api.bind_port(),
+        );
+    }
 
-    HttpServer::new(move || {
+    let http_server = HttpServer::new(move || {
         let scope = web::scope("/api/v1")
             .service(get_openapi)
             .service(http_resources::event_description_resource::topic_event_description_upsert)
+            .service(http_resources::event_description_resource::topic_event_description_by_topic)
+            .service(http_resources::event_description_resource::topic_event_description_history)
+            .service(http_resources::event_description_resource::topic_event_description_diff)
+            .service(http_resources::event_description_resource::topic_event_description_fixtures)
             .service(http_resources::publish_resource::publish_event_to_topic)
+            .service(http_resources::publish_resource::publish_events_to_topic)
+            .service(http_resources::publish_grant_resource::create_publish_grant)
+            .service(http_resources::publish_grant_resource::publish_event_with_grant)
             .service(http_resources::event_poll_resource::next_event_by_topic_and_consumer)
+            .service(http_resources::event_peek_resource::peek_events_by_topic_and_consumer)
+            .service(http_resources::event_range_resource::get_events_by_time_range)
             .service(http_resources::confirm_delivery::confirm_event_delivery)
+            .service(http_resources::confirm_delivery::prepare_event_delivery)
+            .service(http_resources::confirm_delivery::commit_event_delivery)
+            .service(http_resources::consumer_position_resource::commit_consumer_position)
+            .service(http_resources::consumer_position_resource::seek_consumer_position)
             .service(http_resources::event_by_correlation_resource::by_topic_and_correlation_token)
             .service(http_resources::event_by_id_resource::event_by_topic_and_id)
+            .service(http_resources::event_annotation_resource::event_annotation_append)
+            .service(http_resources::event_annotation_resource::event_annotations_by_event_id)
+            .service(http_resources::event_locations_resource::event_locations_by_event_id)
             .service(http_resources::event_ids_by_index_resource::event_ids_by_topic_and_index)
+            .service(http_resources::reply_topic_resource::reply_topic_register)
+            .service(http_resources::reply_topic_resource::reply_topic_by_topic)
+            .service(http_resources::topic_access_resource::topic_ownership_by_topic)
+            .service(http_resources::topic_access_resource::topic_ownership_transfer)
+            .service(http_resources::topic_access_resource::topic_access_update)
             .service(ws_resources::ws_subscribe_resource::subscribe_to_topic)
             .service(ws_resources::ws_confirm_resource::confirm_event_delivery)
-            .service(ws_resources::ws_publish_resource::publish_event_to_topic);
+            .service(ws_resources::ws_publish_resource::publish_event_to_topic)
+            .service(ws_resources::ws_multiplex_resource::multiplex_topics)
+            .service(admin_resources::capabilities_resource::capabilities)
+            .service(admin_resources::topic_overview_resource::topics)
+            .service(admin_resources::topic_overview_resource::topic_consumers_lag)
+            .service(admin_resources::topic_statistics_resource::topic_statistics)
+            .service(admin_resources::topic_counts_resource::topic_counts)
+            .service(admin_resources::topic_counts_resource::reset_topic_counts)
+            .service(admin_resources::duplicate_events_resource::duplicate_events)
+            .service(admin_resources::publish_rejections_resource::publish_rejections)
+            .service(admin_resources::dead_letters_resource::dead_letters)
+            .service(admin_resources::dead_letters_resource::replay_dead_letter)
+            .service(admin_resources::rejected_events_resource::rejected_events)
+            .service(admin_resources::rejected_events_resource::replay_rejected_event)
+            .service(admin_resources::rejected_events_resource::discard_rejected_event)
+            .service(admin_resources::topic_inspection_resource::topic_shelves)
+            .service(admin_resources::topic_inspection_resource::topic_buckets)
+            .service(admin_resources::topic_inspection_resource::bucket_entries)
+            .service(admin_resources::group_members_resource::topic_group_members)
+            .service(admin_resources::subscription_health_resource::topic_subscriptions_health)
+            .service(admin_resources::subscription_health_resource::resume_subscription)
+            .service(admin_resources::diagnostic_query_resource::diagnostic_queries)
+            .service(admin_resources::diagnostic_query_resource::diagnostic_query)
+            .service(admin_resources::topic_mirror_resource::topic_mirror)
+            .service(admin_resources::topic_mirror_resource::update_topic_mirror)
+            .service(admin_resources::topic_bulk_ingest_resource::topic_bulk_ingest)
+            .service(admin_resources::topic_bulk_ingest_resource::open_topic_bulk_ingest)
+            .service(admin_resources::topic_bulk_ingest_resource::close_topic_bulk_ingest)
+            .service(admin_resources::topic_index_rebuild_resource::topic_index_rebuild)
+            .service(admin_resources::topic_index_rebuild_resource::start_topic_index_rebuild)
+            .service(admin_resources::retention_preview_resource::retention_preview)
+            .service(admin_resources::topic_purge_resource::topic_purge)
+            .service(admin_resources::consumption_audit_resource::consumption_audit)
+            .service(admin_resources::consumption_audit_resource::consumption_audit_verify)
+            .service(admin_resources::resource_grants_resource::resource_grants_export)
+            .service(admin_resources::resource_grants_resource::resource_grants_import)
+            .service(admin_resources::topic_snapshot_resource::topic_snapshot)
+            .service(admin_resources::consumer_definitions_resource::consumer_definitions)
+            .service(admin_resources::consumer_definitions_resource::upsert_consumer_definition)
+            .service(admin_resources::consumer_definitions_resource::delete_consumer_definition)
+            .service(admin_resources::web_socket_sessions_resource::web_socket_sessions)
+            .service(admin_resources::web_socket_sessions_resource::kill_web_socket_session)
+            .service(admin_resources::shared_schemas_resource::shared_schemas)
+            .service(admin_resources::shared_schemas_resource::register_shared_schema);
         App::new()
+            .wrap_fn(RequestTimeout::call)
+            .wrap_fn(ProtocolNegotiation::call)
+            .wrap(Condition::new(dev_enabled, Logger::default()))
             .app_data(app_data.clone())
             .app_data(app_health.clone())
-            .service(web::redirect("/openapi", "/api/v1/openapi.json"))
-            .service(web::redirect("/openapi.json", "/api/v1/openapi.json"))
-            .service(scope)
+            .configure(|service_config| {
+                if api_enabled {
+                    service_config
+                        .service(web::redirect("/openapi", "/api/v1/openapi.json"))
+                        .service(web::redirect("/openapi.json", "/api/v1/openapi.json"))
+                        .service(scope);
+                }
+            })
             .service(health_resources::health)
             .service(health_resources::health_live)
             .service(health_resources::health_ready)
             .service(health_resources::health_started)
             .service(fragtale_metrics::http_metrics_resource::metrics)
+            .configure(|service_config| {
+                if ui_enabled {
+                    service_config
+                        .service(ui_resources::web_ui_resource::web_ui)
+                        .service(ui_resources::web_ui_resource::web_ui_asset);
+                }
+            })
     })
+    .on_connect(ClientCertificateAuthentication::on_connect)
     .workers(workers)
     .backlog(u32::try_from(max_connections / 2).unwrap()) // Default is 2048
     .worker_max_blocking_threads(max_connections)
-    .max_connections(max_connections)
-    .bind_auto_h2c((app_config.api.bind_address(), app_config.api.bind_port()))?
-    .disable_signals()
-    .shutdown_timeout(5) // Default 30
-    .run()
-    .await?;
+    .max_connections(max_connections);
+    let bind_address = (app_config.api.bind_address(), app_config.api.bind_port());
+    let http_server = if let Some(tls_server_config) = tls_server_config {
+        http_server.bind_rustls_0_23(bind_address, tls_server_config)?
+    } else {
+        http_server.bind_auto_h2c(bind_address)?
+    };
+    http_server
+        .disable_signals()
+        .shutdown_timeout(5) // Default 30
+        .run()
+        .await?;
     Ok(())
 }
 
@@ -184,15 +397,79 @@ pub fn openapi_as_string() -> String {
         modifiers(&UtopiaSecuritySchemeModifier),
         paths(
             http_resources::event_description_resource::topic_event_description_upsert,
+            http_resources::event_description_resource::topic_event_description_by_topic,
+            http_resources::event_description_resource::topic_event_description_history,
+            http_resources::event_description_resource::topic_event_description_diff,
+            http_resources::event_description_resource::topic_event_description_fixtures,
             http_resources::publish_resource::publish_event_to_topic,
+            http_resources::publish_resource::publish_events_to_topic,
+            http_resources::publish_grant_resource::create_publish_grant,
+            http_resources::publish_grant_resource::publish_event_with_grant,
             http_resources::event_poll_resource::next_event_by_topic_and_consumer,
+            http_resources::event_peek_resource::peek_events_by_topic_and_consumer,
+            http_resources::event_range_resource::get_events_by_time_range,
             http_resources::confirm_delivery::confirm_event_delivery,
+            http_resources::confirm_delivery::prepare_event_delivery,
+            http_resources::confirm_delivery::commit_event_delivery,
+            http_resources::consumer_position_resource::commit_consumer_position,
+            http_resources::consumer_position_resource::seek_consumer_position,
             http_resources::event_by_correlation_resource::by_topic_and_correlation_token,
             http_resources::event_by_id_resource::event_by_topic_and_id,
+            http_resources::event_annotation_resource::event_annotation_append,
+            http_resources::event_annotation_resource::event_annotations_by_event_id,
+            http_resources::event_locations_resource::event_locations_by_event_id,
             http_resources::event_ids_by_index_resource::event_ids_by_topic_and_index,
+            http_resources::reply_topic_resource::reply_topic_register,
+            http_resources::reply_topic_resource::reply_topic_by_topic,
+            http_resources::topic_access_resource::topic_ownership_by_topic,
+            http_resources::topic_access_resource::topic_ownership_transfer,
+            http_resources::topic_access_resource::topic_access_update,
             ws_resources::ws_subscribe_resource::subscribe_to_topic,
             ws_resources::ws_confirm_resource::confirm_event_delivery,
             ws_resources::ws_publish_resource::publish_event_to_topic,
+            ws_resources::ws_multiplex_resource::multiplex_topics,
+            admin_resources::capabilities_resource::capabilities,
+            admin_resources::topic_overview_resource::topics,
+            admin_resources::topic_overview_resource::topic_consumers_lag,
+            admin_resources::topic_statistics_resource::topic_statistics,
+            admin_resources::topic_counts_resource::topic_counts,
+            admin_resources::topic_counts_resource::reset_topic_counts,
+            admin_resources::duplicate_events_resource::duplicate_events,
+            admin_resources::publish_rejections_resource::publish_rejections,
+            admin_resources::dead_letters_resource::dead_letters,
+            admin_resources::dead_letters_resource::replay_dead_letter,
+            admin_resources::rejected_events_resource::rejected_events,
+            admin_resources::rejected_events_resource::replay_rejected_event,
+            admin_resources::rejected_events_resource::discard_rejected_event,
+            admin_resources::topic_inspection_resource::topic_shelves,
+            admin_resources::topic_inspection_resource::topic_buckets,
+            admin_resources::topic_inspection_resource::bucket_entries,
+            admin_resources::group_members_resource::topic_group_members,
+            admin_resources::subscription_health_resource::topic_subscriptions_health,
+            admin_resources::subscription_health_resource::resume_subscription,
+            admin_resources::diagnostic_query_resource::diagnostic_queries,
+            admin_resources::diagnostic_query_resource::diagnostic_query,
+            admin_resources::topic_mirror_resource::topic_mirror,
+            admin_resources::topic_mirror_resource::update_topic_mirror,
+            admin_resources::topic_bulk_ingest_resource::topic_bulk_ingest,
+            admin_resources::topic_bulk_ingest_resource::open_topic_bulk_ingest,
+            admin_resources::topic_bulk_ingest_resource::close_topic_bulk_ingest,
+            admin_resources::topic_index_rebuild_resource::topic_index_rebuild,
+            admin_resources::topic_index_rebuild_resource::start_topic_index_rebuild,
+            admin_resources::retention_preview_resource::retention_preview,
+            admin_resources::topic_purge_resource::topic_purge,
+            admin_resources::consumption_audit_resource::consumption_audit,
+            admin_resources::consumption_audit_resource::consumption_audit_verify,
+            admin_resources::resource_grants_resource::resource_grants_export,
+            admin_resources::resource_grants_resource::resource_grants_import,
+            admin_resources::topic_snapshot_resource::topic_snapshot,
+            admin_resources::consumer_definitions_resource::consumer_definitions,
+            admin_resources::consumer_definitions_resource::upsert_consumer_definition,
+            admin_resources::consumer_definitions_resource::delete_consumer_definition,
+            admin_resources::web_socket_sessions_resource::web_socket_sessions,
+            admin_resources::web_socket_sessions_resource::kill_web_socket_session,
+            admin_resources::shared_schemas_resource::shared_schemas,
+            admin_resources::shared_schemas_resource::register_shared_schema,
             health_resources::health,
             health_resources::health_live,
             health_resources::health_ready,
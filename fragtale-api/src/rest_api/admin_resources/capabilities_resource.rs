@@ -0,0 +1,90 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource for retrieving capabilities and limits of the message broker.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use crate::rest_api::http_resources::publish_resource::MAX_DOCUMENT_SIZE;
+use crate::rest_api::ws_resources::ws_publish_resource::MAX_MESSAGE_SIZE;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use fragtale_client::mb::capabilities::ApiCapabilities;
+use fragtale_client::mb::capabilities::Capabilities;
+
+/// Version of the exposed API.
+const API_VERSION: &str = "v1";
+
+/// Retrieve capabilities and limits of the message broker.
+///
+/// This includes the enabled features (metrics, tracing, database backend,
+/// integrity protection and supported algorithms), the API version, the
+/// authentication providers and the limits enforced by the API, like the
+/// maximum document size and sessions per identity.
+///
+/// It also includes the limits enforced when a topic's event descriptor is
+/// registered or updated, like the maximum number of extractors, schema size
+/// and indexed columns per topic, and the configured delivery limits.
+///
+/// Requires authorization to the administrative function `capabilities`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "capabilities",
+    responses(
+        (
+            status = 200,
+            description = "Return the capabilities.",
+            body = inline(Capabilities),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/capabilities")]
+pub async fn capabilities(
+    app_state: Data<AppState>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let capabilities = app_state
+        .mb
+        .get_capabilities(&identity)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?
+        .with_api(ApiCapabilities::new(
+            API_VERSION,
+            app_state.auth.get_provider_names(),
+            MAX_DOCUMENT_SIZE,
+            MAX_MESSAGE_SIZE,
+            app_state.app_config.api.max_sessions_per_identity(),
+            app_state.app_config.api.session_idle_timeout_micros(),
+        ));
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(capabilities.as_string()))
+}
@@ -0,0 +1,172 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for declaring named consumers of a topic.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::delete;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Json;
+use actix_web::web::Path;
+use fragtale_client::mb::consumer_definitions::ConsumerDefinition;
+use fragtale_client::mb::consumer_definitions::ConsumerDefinitions;
+
+/// Get the declared consumers of the topic.
+///
+/// Requires authorization to the administrative function `consumers`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "consumer_definitions",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the declared consumers of the topic.",
+            body = inline(ConsumerDefinitions),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/consumers")]
+pub async fn consumer_definitions(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let consumer_definitions = app_state
+        .mb
+        .get_consumer_definitions(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(consumer_definitions.as_string()))
+}
+
+/// Declare a named consumer of the topic or replace the existing declaration.
+///
+/// Webhook consumers get events POSTed by the server. Subscriber consumers
+/// apply the declared filter, starting position and rate limit to the
+/// clients that connect as the principal.
+///
+/// Requires authorization to the administrative function `consumers`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "upsert_consumer_definition",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("name", description = "Name of the declared consumer."),
+    ),
+    request_body = inline(ConsumerDefinition),
+    responses(
+        (
+            status = 200,
+            description = "Return the declared consumer.",
+            body = inline(ConsumerDefinition),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/admin/topics/{topic_id}/consumers/{name}")]
+pub async fn upsert_consumer_definition(
+    app_state: Data<AppState>,
+    path: Path<(String, String)>,
+    consumer_definition: Json<ConsumerDefinition>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, name) = path.into_inner();
+    let consumer_definition = app_state
+        .mb
+        .upsert_consumer_definition(
+            &identity,
+            &topic_id,
+            &name,
+            consumer_definition.into_inner(),
+        )
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(consumer_definition.as_string()))
+}
+
+/// Remove the declaration of a named consumer of the topic.
+///
+/// The delivery progress of the consumer is retained.
+///
+/// Requires authorization to the administrative function `consumers`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "delete_consumer_definition",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("name", description = "Name of the declared consumer."),
+    ),
+    responses(
+        (status = 204, description = "No content. Declaration was removed."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/admin/topics/{topic_id}/consumers/{name}")]
+pub async fn delete_consumer_definition(
+    app_state: Data<AppState>,
+    path: Path<(String, String)>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, name) = path.into_inner();
+    app_state
+        .mb
+        .delete_consumer_definition(&identity, &topic_id, &name)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
+}
@@ -0,0 +1,161 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for exporting and verifying signed consumption audits.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::error::ErrorBadRequest;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::post;
+use actix_web::web::Data;
+use actix_web::web::Json;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::consumption_audit::ConsumptionAudit;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct ConsumptionAuditQuery {
+    /// Start of the publication time window in epoch microseconds.
+    from: u64,
+    /// End (inclusive) of the publication time window in epoch microseconds.
+    to: u64,
+    /// Limit the report to a single event identifier.
+    event_id: Option<String>,
+}
+
+/// Export a signed report of which consumers retrieved events of the topic.
+///
+/// The report covers events published in the time window and lists each
+/// consumer identifier with the claims of the authenticated identity behind
+/// it, the delivery time and if the delivery was confirmed. Events retrieved
+/// by identifier or correlation token are included.
+///
+/// Use the `event_id` parameter to prove who retrieved a specific event.
+///
+/// Requires authorization to the administrative function `audit`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "consumption_audit",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        (
+            "from" = u64,
+            Query,
+            description = "Start of the publication time window in epoch microseconds."
+        ),
+        (
+            "to" = u64,
+            Query,
+            description = "End (inclusive) of the publication time window in epoch microseconds."
+        ),
+        (
+            "event_id" = Option<String>,
+            Query,
+            description = "Limit the report to a single event identifier."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the signed consumption audit.",
+            body = inline(ConsumptionAudit),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/audit/consumption")]
+pub async fn consumption_audit(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<ConsumptionAuditQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let consumption_audit = app_state
+        .mb
+        .get_consumption_audit(
+            &identity,
+            &topic_id,
+            query.from,
+            query.to,
+            query.event_id.as_deref(),
+        )
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(consumption_audit.as_string()))
+}
+
+/// Verify the signature of a previously exported consumption audit.
+///
+/// Reports signed before the last rotation of the integrity protection
+/// secrets can still be verified.
+///
+/// Requires authorization to the administrative function `audit`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "consumption_audit_verify",
+    request_body = inline(ConsumptionAudit),
+    responses(
+        (status = 204, description = "No content. The report is unmodified since it was signed."),
+        (status = 400, description = "Bad Request: The report signature is missing or invalid."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/admin/audit/consumption/verify")]
+pub async fn consumption_audit_verify(
+    app_state: Data<AppState>,
+    consumption_audit: Json<ConsumptionAudit>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let valid = app_state
+        .mb
+        .verify_consumption_audit(&identity, &consumption_audit)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if valid {
+        Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
+    } else {
+        Err(ErrorBadRequest(
+            "The consumption audit signature is missing or invalid.",
+        ))
+    }
+}
@@ -0,0 +1,151 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for inspecting and replaying events that were moved to the
+//! dead-letter topic of a topic.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::dead_letters::DeadLetters;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct DeadLettersQuery {
+    /// Return dead-lettered events with a unique time after this one.
+    from: Option<u64>,
+}
+
+/// List events that were moved to the dead-letter topic of the topic.
+///
+/// Events are only dead-lettered for topics where this has been enabled in
+/// the event descriptor. Results are ordered by the time the events were
+/// dead-lettered and paged using the `from` parameter.
+///
+/// Requires authorization to the administrative function `dead_letters`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "dead_letters",
+    params(
+        ("topic_id", description = "Topic identifier of the source topic."),
+        (
+            "from" = Option<u64>,
+            Query,
+            description = "Only return dead-lettered events with a unique time after this one."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return a page of dead-lettered events.",
+            body = inline(DeadLetters),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/deadletters")]
+pub async fn dead_letters(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<DeadLettersQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let dead_letters = app_state
+        .mb
+        .get_dead_letters(&identity, &topic_id, query.from)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(dead_letters.as_string()))
+}
+
+/// Publish a dead-lettered event to the source topic again.
+///
+/// The event is published as a new event and will be delivered to all
+/// consumers of the source topic. The dead-lettered event is annotated as
+/// replayed, but is kept in the dead-letter topic.
+///
+/// Requires authorization to the administrative function `dead_letters`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "replay_dead_letter",
+    params(
+        ("topic_id", description = "Topic identifier of the source topic."),
+        ("event_id", description = "Event identifier in the dead-letter topic."),
+    ),
+    responses(
+        (
+            status = 204,
+            description = "No content. Successfully published event.",
+            headers(
+                (
+                    "correlation-token" = String,
+                    description = "Opaque token that can be used to correlate events."
+                ),
+            ),
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "No such dead-lettered event."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/admin/topics/{topic_id}/deadletters/{event_id}/replay")]
+pub async fn replay_dead_letter(
+    app_state: Data<AppState>,
+    path: Path<(String, String)>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, event_id) = path.into_inner();
+    let correlation_token_opt = app_state
+        .mb
+        .replay_dead_letter(&identity, &topic_id, &event_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(correlation_token) = correlation_token_opt {
+        Ok(HttpResponse::build(StatusCode::NO_CONTENT)
+            .append_header(("correlation-token", correlation_token))
+            .finish())
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
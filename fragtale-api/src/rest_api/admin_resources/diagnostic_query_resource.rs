@@ -0,0 +1,156 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for whitelisted read-only diagnostic queries.
+//!
+//! These are disabled by default and intended for emergencies, so operators
+//! rarely need direct database access to production.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::diagnostic_queries::DiagnosticQueryResult;
+use fragtale_client::mb::diagnostic_queries::DiagnosticQueryTemplates;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct DiagnosticQueryParams {
+    /// Consumer identifier.
+    consumer_id: Option<String>,
+    /// Start of the range in encoded unique time (inclusive).
+    from: Option<u64>,
+    /// End of the range in encoded unique time (inclusive).
+    to: Option<u64>,
+}
+
+/// List the whitelisted read-only diagnostic queries.
+///
+/// Requires diagnostics to be enabled in the configuration and authorization
+/// to the administrative function `diagnostics`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "diagnostic_queries",
+    responses(
+        (
+            status = 200,
+            description = "Return the available diagnostic queries.",
+            body = inline(DiagnosticQueryTemplates),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure or diagnostics are disabled."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/diagnostics")]
+pub async fn diagnostic_queries(
+    app_state: Data<AppState>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let diagnostic_query_templates = app_state
+        .mb
+        .get_diagnostic_query_templates(&identity)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(diagnostic_query_templates.as_string()))
+}
+
+/// Run a whitelisted read-only diagnostic query against the storage of a
+/// topic.
+///
+/// Every query is audit logged.
+///
+/// Requires diagnostics to be enabled in the configuration and authorization
+/// to the administrative function `diagnostics`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "diagnostic_query",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("query", description = "Name of the diagnostic query."),
+        (
+            "consumer_id" = Option<String>,
+            Query,
+            description = "Consumer identifier for queries that require it."
+        ),
+        (
+            "from" = Option<u64>,
+            Query,
+            description = "Start of the range in encoded unique time (inclusive) for queries that require it."
+        ),
+        (
+            "to" = Option<u64>,
+            Query,
+            description = "End of the range in encoded unique time (inclusive) for queries that require it."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the result rows of the diagnostic query.",
+            body = inline(DiagnosticQueryResult),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad request: Unknown query or missing parameters."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure or diagnostics are disabled."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/diagnostics/{query}")]
+pub async fn diagnostic_query(
+    app_state: Data<AppState>,
+    path: Path<(String, String)>,
+    query: Query<DiagnosticQueryParams>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, query_name) = path.into_inner();
+    let diagnostic_query_result = app_state
+        .mb
+        .run_diagnostic_query(
+            &identity,
+            &topic_id,
+            &query_name,
+            query.consumer_id.as_deref(),
+            query.from.zip(query.to),
+        )
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(diagnostic_query_result.as_string()))
+}
@@ -0,0 +1,78 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource for tracking down producers that publish duplicate events.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use fragtale_client::mb::duplicate_events::DuplicateEvents;
+
+/// Report duplicate events recently published to a topic on this instance.
+///
+/// An event is a duplicate when an event with the same `event_id` was
+/// published to the topic within the detection window. The publishers of the
+/// most duplicates within the window are listed first, which helps to track
+/// down producer retry storms.
+///
+/// Requires authorization to the administrative function `stats`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "duplicate_events",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the duplicate detection report.",
+            body = inline(DuplicateEvents),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/stats/duplicates")]
+pub async fn duplicate_events(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let duplicate_events = app_state
+        .mb
+        .get_duplicate_events(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(duplicate_events.as_string()))
+}
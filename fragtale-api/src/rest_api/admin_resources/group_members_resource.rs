@@ -0,0 +1,94 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource for inspecting the members of consumer groups.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::group_members::TopicGroupMembers;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct GroupMembersQuery {
+    /// Window of confirmation statistics in minutes.
+    minutes: Option<u64>,
+}
+
+/// List the members of the consumer groups of the topic connected to this
+/// instance.
+///
+/// Each client instance identifies itself within its consumer group when
+/// connecting. The number of open subscriber connections and of events
+/// confirmed within the window is reported for each member, so members that
+/// are connected but not processing events stand out.
+///
+/// Requires authorization to the administrative function `subscriptions`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_group_members",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        (
+            "minutes" = Option<u64>,
+            Query,
+            description = "Window of confirmation statistics in minutes (1-60, default 5)."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the members of each consumer group.",
+            body = inline(TopicGroupMembers),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/subscriptions/members")]
+pub async fn topic_group_members(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<GroupMembersQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let group_members = app_state
+        .mb
+        .get_topic_group_members(&identity, &topic_id, query.minutes)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(group_members.as_string()))
+}
@@ -0,0 +1,88 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource for diagnosing recently rejected publishes.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Query;
+use fragtale_client::mb::publish_rejections::PublishRejections;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct PublishRejectionsQuery {
+    /// Only return rejected publishes to this topic.
+    topic: Option<String>,
+}
+
+/// List the most recently rejected publishes on this instance.
+///
+/// Each rejection has a reason code (`authz`, `schema`, `rate_limit`,
+/// `time_not_trusted`, `integrity` or `backend`) and the error message that
+/// was returned to the publisher. Only a limited number of rejections are
+/// kept and the oldest are dropped first.
+///
+/// Requires authorization to the administrative function `rejections`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "publish_rejections",
+    params(
+        (
+            "topic" = Option<String>,
+            Query,
+            description = "Only return rejected publishes to this topic."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the recently rejected publishes.",
+            body = inline(PublishRejections),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/rejections")]
+pub async fn publish_rejections(
+    app_state: Data<AppState>,
+    query: Query<PublishRejectionsQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let publish_rejections = app_state
+        .mb
+        .get_publish_rejections(&identity, query.topic.as_deref())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(publish_rejections.as_string()))
+}
@@ -0,0 +1,194 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for inspecting, replaying and discarding events rejected by
+//! validation during publishing.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::delete;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::rejected_events::RejectedEvents;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct RejectedEventsQuery {
+    /// Return rejected events with an event identifier after this one.
+    from: Option<String>,
+}
+
+/// List events rejected by validation during publishing to the topic.
+///
+/// Rejected events are only kept for topics where the reject store has been
+/// enabled in the event descriptor. Results are ordered by event identifier
+/// and paged using the `from` parameter.
+///
+/// Requires authorization to the administrative function `rejected`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "rejected_events",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        (
+            "from" = Option<String>,
+            Query,
+            description = "Only return rejected events with an event identifier after this one."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return a page of rejected events.",
+            body = inline(RejectedEvents),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/rejected")]
+pub async fn rejected_events(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<RejectedEventsQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let rejected_events = app_state
+        .mb
+        .get_rejected_events(&identity, &topic_id, &query.from)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(rejected_events.as_string()))
+}
+
+/// Publish a rejected event again on behalf of the original publisher.
+///
+/// This is intended to be used after the event descriptor of the topic has
+/// been fixed. The rejected event is removed when it was successfully
+/// published. If it is rejected again, the stored reason is updated.
+///
+/// Requires authorization to the administrative function `rejected`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "replay_rejected_event",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("event_id", description = "Event identifier."),
+    ),
+    responses(
+        (
+            status = 204,
+            description = "No content. Successfully published event.",
+            headers(
+                (
+                    "correlation-token" = String,
+                    description = "Opaque token that can be used to correlate events."
+                ),
+            ),
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "No such rejected event."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/admin/topics/{topic_id}/rejected/{event_id}/replay")]
+pub async fn replay_rejected_event(
+    app_state: Data<AppState>,
+    path: Path<(String, String)>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, event_id) = path.into_inner();
+    let correlation_token_opt = app_state
+        .mb
+        .replay_rejected_event(&identity, &topic_id, &event_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(correlation_token) = correlation_token_opt {
+        Ok(HttpResponse::build(StatusCode::NO_CONTENT)
+            .append_header(("correlation-token", correlation_token))
+            .finish())
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
+
+/// Discard a rejected event.
+///
+/// Requires authorization to the administrative function `rejected`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "discard_rejected_event",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("event_id", description = "Event identifier."),
+    ),
+    responses(
+        (status = 204, description = "No content. Rejected event was discarded."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "No such rejected event."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/admin/topics/{topic_id}/rejected/{event_id}")]
+pub async fn discard_rejected_event(
+    app_state: Data<AppState>,
+    path: Path<(String, String)>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, event_id) = path.into_inner();
+    let discarded = app_state
+        .mb
+        .discard_rejected_event(&identity, &topic_id, &event_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if discarded {
+        Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
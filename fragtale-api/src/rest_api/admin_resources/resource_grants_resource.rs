@@ -0,0 +1,196 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for bulk export and import of resource authorization grants.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::error::ErrorBadRequest;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::CONTENT_TYPE;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Bytes;
+use actix_web::web::Data;
+use actix_web::web::Query;
+use fragtale_client::mb::resource_grants::ResourceGrants;
+use fragtale_client::mb::resource_grants::ResourceGrantsImport;
+use serde::Deserialize;
+
+/// Media type of YAML serialized grants.
+const CONTENT_TYPE_YAML: &str = "application/yaml";
+
+#[derive(Debug, Deserialize)]
+pub struct ResourceGrantsExportQuery {
+    /// Serialization format. One of `json` (default) or `yaml`.
+    format: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResourceGrantsImportQuery {
+    /// Revoke existing grants that are not part of the import.
+    prune: Option<bool>,
+    /// Only report the changes that would be applied.
+    dry_run: Option<bool>,
+}
+
+/// Export all explicit resource authorization grants.
+///
+/// The export can be kept under version control and later applied using the
+/// import.
+///
+/// Requires authorization to the administrative function `acl`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "resource_grants_export",
+    params(
+        (
+            "format" = Option<String>,
+            Query,
+            description = "Serialization format. One of `json` (default) or `yaml`."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return all explicit grants as JSON or YAML (`application/yaml`).",
+            body = inline(ResourceGrants),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/acl")]
+pub async fn resource_grants_export(
+    app_state: Data<AppState>,
+    query: Query<ResourceGrantsExportQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let resource_grants = app_state
+        .mb
+        .get_resource_grants(&identity)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    match query.format.as_deref() {
+        None | Some("json") => Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(resource_grants.as_string())),
+        Some("yaml") => Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(CONTENT_TYPE_YAML)
+            .body(serde_yaml::to_string(&resource_grants).unwrap())),
+        Some(format) => Err(ErrorBadRequest(format!(
+            "Unsupported format '{format}'. Expected 'json' or 'yaml'."
+        ))),
+    }
+}
+
+/// Import resource authorization grants.
+///
+/// The request body is a JSON or YAML (`Content-Type: application/yaml`)
+/// document in the same format as the export. Every grant is validated
+/// before any change is applied and a single invalid grant rejects the whole
+/// import.
+///
+/// Missing grants are added. With `prune=true` existing grants that are not
+/// part of the import are revoked, making the import the full grant set.
+/// With `dry_run=true` the changes are only reported.
+///
+/// Applied changes are published as an audit event to the `fragtale_audit`
+/// topic.
+///
+/// Requires authorization to the administrative function `acl`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "resource_grants_import",
+    params(
+        (
+            "prune" = Option<bool>,
+            Query,
+            description = "Revoke existing grants that are not part of the import. (Default: false)"
+        ),
+        (
+            "dry_run" = Option<bool>,
+            Query,
+            description = "Only report the changes that would be applied. (Default: false)"
+        ),
+    ),
+    request_body(
+        content = inline(ResourceGrants),
+        description = "Grants as JSON or YAML (`application/yaml`).",
+        content_type = "application/json",
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the applied (or for a dry run, proposed) changes.",
+            body = inline(ResourceGrantsImport),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/admin/acl")]
+pub async fn resource_grants_import(
+    app_state: Data<AppState>,
+    query: Query<ResourceGrantsImportQuery>,
+    body: Bytes,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let is_yaml = http_request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with(CONTENT_TYPE_YAML));
+    let resource_grants: ResourceGrants = if is_yaml {
+        serde_yaml::from_slice(&body).map_err(|e| ErrorBadRequest(e.to_string()))?
+    } else {
+        serde_json::from_slice(&body).map_err(|e| ErrorBadRequest(e.to_string()))?
+    };
+    let resource_grants_import = app_state
+        .mb
+        .import_resource_grants(
+            &identity,
+            resource_grants,
+            query.prune.unwrap_or(false),
+            query.dry_run.unwrap_or(false),
+        )
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(resource_grants_import.as_string()))
+}
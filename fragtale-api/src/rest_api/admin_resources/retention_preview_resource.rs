@@ -0,0 +1,102 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource for previewing the effect of a proposed retention on a topic.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::retention_preview::RetentionPreview;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct RetentionPreviewQuery {
+    /// Proposed retention in seconds.
+    max_age: u64,
+    /// Continue the preview from this bucket.
+    from: Option<u64>,
+}
+
+/// Dry-run report of what a proposed retention would delete from a topic.
+///
+/// Buckets older than the proposed retention are walked to report event
+/// counts, an estimate of the document sizes and the oldest surviving event
+/// for the topic and for each consumer's watermark. Nothing is deleted.
+///
+/// Large topics are reported in pages of buckets where `next_bucket` of the
+/// response is used as the `from` parameter of the next request.
+///
+/// Requires authorization to the administrative function `retention`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "retention_preview",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        (
+            "max_age" = u64,
+            Query,
+            description = "Proposed retention in seconds."
+        ),
+        (
+            "from" = Option<u64>,
+            Query,
+            description = "Continue the preview from this bucket."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the retention preview.",
+            body = inline(RetentionPreview),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/retention/preview")]
+pub async fn retention_preview(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<RetentionPreviewQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let preview = app_state
+        .mb
+        .get_retention_preview(&identity, &topic_id, query.max_age, query.from)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(preview.as_string()))
+}
@@ -0,0 +1,115 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for the registry of shared schema components.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Json;
+use fragtale_client::mb::shared_schemas::SharedSchema;
+use fragtale_client::mb::shared_schemas::SharedSchemas;
+
+/// Get the latest version of all shared schema components.
+///
+/// Requires authorization to the administrative function `schemas`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "shared_schemas",
+    responses(
+        (
+            status = 200,
+            description = "Return the latest version of all shared schema components.",
+            body = inline(SharedSchemas),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/schemas")]
+pub async fn shared_schemas(
+    app_state: Data<AppState>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let shared_schemas = app_state
+        .mb
+        .get_shared_schemas(&identity)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(shared_schemas.as_string()))
+}
+
+/// Register a new version of a shared schema component.
+///
+/// Event schemas of any topic can `$ref`erence the component by its schema
+/// identifier. Once registered, the new version is used when validating
+/// events of all topics that reference it.
+///
+/// Requires authorization to the administrative function `schemas`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "register_shared_schema",
+    request_body = inline(SharedSchema),
+    responses(
+        (
+            status = 200,
+            description = "Return the registered component with its assigned version.",
+            body = inline(SharedSchema),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/admin/schemas")]
+pub async fn register_shared_schema(
+    app_state: Data<AppState>,
+    shared_schema: Json<SharedSchema>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let shared_schema = app_state
+        .mb
+        .register_shared_schema(&identity, shared_schema.into_inner())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(shared_schema.as_string()))
+}
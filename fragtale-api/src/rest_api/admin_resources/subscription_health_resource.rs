@@ -0,0 +1,125 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for inspecting and resuming paused topic subscriptions.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Json;
+use actix_web::web::Path;
+use fragtale_client::mb::subscription_health::SubscriptionResume;
+use fragtale_client::mb::subscription_health::TopicSubscriptionsHealth;
+
+/// List the health of the subscriptions to the topic served by this instance.
+///
+/// A subscription is paused when the same event has been redelivered too many
+/// times without being confirmed.
+///
+/// Requires authorization to the administrative function `subscriptions`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_subscriptions_health",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the health of each subscription.",
+            body = inline(TopicSubscriptionsHealth),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/subscriptions")]
+pub async fn topic_subscriptions_health(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let topic_subscriptions_health = app_state
+        .mb
+        .get_topic_subscriptions_health(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(topic_subscriptions_health.as_string()))
+}
+
+/// Resume delivery to a paused subscription.
+///
+/// The event that caused the pause will be redelivered again.
+///
+/// Requires authorization to the administrative function `subscriptions`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "resume_subscription",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    request_body = inline(SubscriptionResume),
+    responses(
+        (status = 204, description = "No content. Successfully resumed delivery."),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "No paused subscription on this instance."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/admin/topics/{topic_id}/subscriptions/resume")]
+pub async fn resume_subscription(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    subscription_resume: Json<SubscriptionResume>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let resumed = app_state
+        .mb
+        .resume_subscription(&identity, &topic_id, subscription_resume.into_inner())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if resumed {
+        Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
@@ -0,0 +1,189 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for bulk ingest windows that speed up backfilling topics.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::delete;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Json;
+use actix_web::web::Path;
+use fragtale_client::mb::bulk_ingest::BulkIngestWindow;
+use fragtale_client::mb::bulk_ingest::BulkIngestWindowRequest;
+
+/// Get the latest bulk ingest window of the topic.
+///
+/// Requires authorization to the administrative function `bulk_ingest`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_bulk_ingest",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the latest bulk ingest window of the topic.",
+            body = inline(BulkIngestWindow),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "The topic never had a bulk ingest window."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/bulk-ingest")]
+pub async fn topic_bulk_ingest(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let window_opt = app_state
+        .mb
+        .get_bulk_ingest_window(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(window) = window_opt {
+        Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(window.as_string()))
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
+
+/// Open a bulk ingest window of the topic, or extend the current one.
+///
+/// Events published to the topic during the window are persisted without
+/// their indexed columns and integrity consolidation of the topic is paused.
+/// Once the window has ended, the indexed columns are populated in the
+/// background. Lookups by index will not find events of the window until
+/// then.
+///
+/// Requires authorization to the administrative function `bulk_ingest`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "open_topic_bulk_ingest",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    request_body = inline(BulkIngestWindowRequest),
+    responses(
+        (
+            status = 200,
+            description = "Return the opened bulk ingest window of the topic.",
+            body = inline(BulkIngestWindow),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/admin/topics/{topic_id}/bulk-ingest")]
+pub async fn open_topic_bulk_ingest(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    bulk_ingest_window_request: Json<BulkIngestWindowRequest>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let window = app_state
+        .mb
+        .open_bulk_ingest_window(
+            &identity,
+            &topic_id,
+            bulk_ingest_window_request.into_inner(),
+        )
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(window.as_string()))
+}
+
+/// End the bulk ingest window of the topic now.
+///
+/// Reconciliation of the events published during the window starts shortly
+/// after.
+///
+/// Requires authorization to the administrative function `bulk_ingest`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "close_topic_bulk_ingest",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the ended bulk ingest window of the topic.",
+            body = inline(BulkIngestWindow),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "The topic never had a bulk ingest window."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/admin/topics/{topic_id}/bulk-ingest")]
+pub async fn close_topic_bulk_ingest(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let window_opt = app_state
+        .mb
+        .close_bulk_ingest_window(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(window) = window_opt {
+        Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(window.as_string()))
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
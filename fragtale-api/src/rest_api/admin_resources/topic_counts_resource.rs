@@ -0,0 +1,117 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for retrieving and resetting object counts of a topic.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::delete;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use fragtale_client::mb::topic_counts::TopicCounts;
+
+/// Retrieve the number of published events and delivery intents of a topic
+/// across all instances.
+///
+/// The counts are kept across broker restarts until they are reset.
+///
+/// Requires authorization to the administrative function `stats`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_counts",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the topic counts.",
+            body = inline(TopicCounts),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/stats/counts")]
+pub async fn topic_counts(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let topic_counts = app_state
+        .mb
+        .get_topic_counts(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(topic_counts.as_string()))
+}
+
+/// Reset the number of published events and delivery intents of a topic on
+/// all instances.
+///
+/// Other instances than the one serving the request reset their counts when
+/// they take their next snapshot (within a minute).
+///
+/// Requires authorization to the administrative function `stats`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "reset_topic_counts",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (status = 204, description = "No content. Successfully reset the counts."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/admin/topics/{topic_id}/stats/counts")]
+pub async fn reset_topic_counts(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    app_state
+        .mb
+        .reset_topic_counts(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
+}
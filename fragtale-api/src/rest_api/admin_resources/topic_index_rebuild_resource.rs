@@ -0,0 +1,129 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for online rebuilds of the indexed columns of topics.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use fragtale_client::mb::index_rebuild::IndexRebuild;
+
+/// Get the progress of the latest rebuild of the indexed columns of the
+/// topic.
+///
+/// Requires authorization to the administrative function `index_rebuild`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_index_rebuild",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the progress of the latest index rebuild of the topic.",
+            body = inline(IndexRebuild),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "The topic never had an index rebuild."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/index-rebuild")]
+pub async fn topic_index_rebuild(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let index_rebuild_opt = app_state
+        .mb
+        .get_index_rebuild(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(index_rebuild) = index_rebuild_opt {
+        Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(index_rebuild.as_string()))
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
+
+/// Start a rebuild of the indexed columns of all existing events of the
+/// topic.
+///
+/// The extractors of the latest event description are re-run over historical
+/// events in the background and any rebuild in progress is restarted. Lookups
+/// by index keep working during the rebuild and become complete once it has
+/// completed.
+///
+/// Requires authorization to the administrative function `index_rebuild`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "start_topic_index_rebuild",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 202,
+            description = "Return the progress of the started index rebuild of the topic.",
+            body = inline(IndexRebuild),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/admin/topics/{topic_id}/index-rebuild")]
+pub async fn start_topic_index_rebuild(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let index_rebuild = app_state
+        .mb
+        .start_index_rebuild(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::ACCEPTED)
+        .content_type(ContentType::json())
+        .body(index_rebuild.as_string()))
+}
@@ -0,0 +1,192 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for browsing the raw storage layout of a topic.
+//!
+//! Events are stored in time based buckets that are grouped into shelves.
+//! These resources allow operators to investigate delivery gaps without
+//! direct database access.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::topic_buckets::BucketEntries;
+use fragtale_client::mb::topic_buckets::TopicBuckets;
+use fragtale_client::mb::topic_buckets::TopicShelves;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct InspectionQuery {
+    /// Return entries after this one.
+    from: Option<u64>,
+}
+
+/// List the shelves of a topic that contain at least one bucket of events.
+///
+/// Requires authorization to the administrative function `inspect`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_shelves",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the shelves of the topic.",
+            body = inline(TopicShelves),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/shelves")]
+pub async fn topic_shelves(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let topic_shelves = app_state
+        .mb
+        .get_topic_shelves(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(topic_shelves.as_string()))
+}
+
+/// List the used buckets in a shelf of a topic with event counts.
+///
+/// Results are ordered by bucket and paged using the `from` parameter.
+///
+/// Requires authorization to the administrative function `inspect`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_buckets",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("shelf", description = "Shelf of the buckets."),
+        (
+            "from" = Option<u64>,
+            Query,
+            description = "Only return buckets after this one."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return a page of buckets.",
+            body = inline(TopicBuckets),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/shelves/{shelf}/buckets")]
+pub async fn topic_buckets(
+    app_state: Data<AppState>,
+    path: Path<(String, u16)>,
+    query: Query<InspectionQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, shelf) = path.into_inner();
+    let topic_buckets = app_state
+        .mb
+        .get_topic_buckets(&identity, &topic_id, shelf, query.from)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(topic_buckets.as_string()))
+}
+
+/// List the raw entries of a bucket with event identifier, unique time and
+/// descriptor version.
+///
+/// Results are ordered by unique time and paged using the `from` parameter.
+///
+/// Requires authorization to the administrative function `inspect`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "bucket_entries",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("bucket", description = "Bucket identifier."),
+        (
+            "from" = Option<u64>,
+            Query,
+            description = "Only return entries with an encoded unique time after this one."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return a page of bucket entries.",
+            body = inline(BucketEntries),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/buckets/{bucket}")]
+pub async fn bucket_entries(
+    app_state: Data<AppState>,
+    path: Path<(String, u64)>,
+    query: Query<InspectionQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, bucket) = path.into_inner();
+    let bucket_entries = app_state
+        .mb
+        .get_bucket_entries(&identity, &topic_id, bucket, query.from)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(bucket_entries.as_string()))
+}
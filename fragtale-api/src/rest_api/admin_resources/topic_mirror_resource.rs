@@ -0,0 +1,127 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for mirroring published events to local files for
+//! debugging.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Json;
+use actix_web::web::Path;
+use fragtale_client::mb::event_mirror::TopicMirror;
+use fragtale_client::mb::event_mirror::TopicMirrorUpdate;
+
+/// Get the mirroring status of the topic on the instance that serves the
+/// request.
+///
+/// Requires authorization to the administrative function `mirror`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_mirror",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the mirroring status of the topic.",
+            body = inline(TopicMirror),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/mirror")]
+pub async fn topic_mirror(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let mirror_status = app_state
+        .mb
+        .get_topic_mirror(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(mirror_status.as_string()))
+}
+
+/// Enable or disable mirroring of events published to the topic.
+///
+/// Events accepted by the instance that serves the request are written to
+/// rotating NDJSON files on its local disk.
+///
+/// Requires authorization to the administrative function `mirror`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "update_topic_mirror",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    request_body = inline(TopicMirrorUpdate),
+    responses(
+        (
+            status = 200,
+            description = "Return the updated mirroring status of the topic.",
+            body = inline(TopicMirror),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/admin/topics/{topic_id}/mirror")]
+pub async fn update_topic_mirror(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    topic_mirror_update: Json<TopicMirrorUpdate>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let mirror_status = app_state
+        .mb
+        .update_topic_mirror(&identity, &topic_id, topic_mirror_update.into_inner())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(mirror_status.as_string()))
+}
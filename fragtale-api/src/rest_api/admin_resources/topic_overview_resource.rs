@@ -0,0 +1,134 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for an overview of topics and their consumers.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::topic_overview::TopicConsumersLag;
+use fragtale_client::mb::topic_overview::TopicList;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct TopicListQuery {
+    /// Return topics after this one.
+    from: Option<String>,
+}
+
+/// List known topics.
+///
+/// Results are ordered by topic identifier and paged using the `from`
+/// parameter.
+///
+/// Requires authorization to the administrative function `inspect`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topics",
+    params(
+        (
+            "from" = Option<String>,
+            Query,
+            description = "Only return topics after this topic identifier."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return a page of topic identifiers.",
+            body = inline(TopicList),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics")]
+pub async fn topics(
+    app_state: Data<AppState>,
+    query: Query<TopicListQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_list = app_state
+        .mb
+        .get_topic_list(&identity, query.into_inner().from)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(topic_list.as_string()))
+}
+
+/// List how far each consumer of a topic is behind.
+///
+/// The lag is the time between the latest confirmed delivery to the consumer
+/// and the time of the request.
+///
+/// Requires authorization to the administrative function `inspect`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_consumers_lag",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the lag of each consumer.",
+            body = inline(TopicConsumersLag),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/lag")]
+pub async fn topic_consumers_lag(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let topic_consumers_lag = app_state
+        .mb
+        .get_topic_consumers_lag(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(topic_consumers_lag.as_string()))
+}
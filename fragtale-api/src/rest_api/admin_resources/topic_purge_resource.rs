@@ -0,0 +1,93 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource for purging events by index key from a topic.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::post;
+use actix_web::web::Data;
+use actix_web::web::Json;
+use actix_web::web::Path;
+use fragtale_client::mb::event_purge::EventPurge;
+use fragtale_client::mb::event_purge::EventPurgeRequest;
+
+/// Purge or redact all events of the topic that match the key of an indexed
+/// column. E.g. for erasure of a data subject's events.
+///
+/// Without `expected_count` the request is a dry-run that only reports the
+/// number of matching events. Provide the reported `matched_count` as
+/// `expected_count` to execute the purge. The request is rejected if the
+/// number of matching events has changed since the dry-run.
+///
+/// Mode `purge` removes the events together with their index entries,
+/// correlation lookups and annotations. Mode `redact` replaces the documents
+/// by the masked version from the masking rules of the topic's event
+/// descriptor. Digests in the group level integrity protections are kept, so
+/// the integrity of the remaining events can still be verified.
+///
+/// The index key is read from the request body to keep it out of access logs
+/// and is not part of the audit trail.
+///
+/// Requires authorization to the administrative function `event_purge`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_purge",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    request_body = inline(EventPurgeRequest),
+    responses(
+        (
+            status = 200,
+            description = "Return the number of matching and purged events.",
+            body = inline(EventPurge),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request. E.g. the expected count is outdated."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/admin/topics/{topic_id}/purge")]
+pub async fn topic_purge(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    event_purge_request: Json<EventPurgeRequest>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let event_purge = app_state
+        .mb
+        .purge_events_by_index(&identity, &topic_id, event_purge_request.into_inner())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(event_purge.as_string()))
+}
@@ -0,0 +1,110 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource for exporting a consistent snapshot of a topic.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::web::Bytes;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct TopicSnapshotQuery {
+    /// Encoded UniqueTime of the last event to include.
+    as_of: u64,
+    /// Return only the latest event per value of this extracted key.
+    key: Option<String>,
+}
+
+/// Export a consistent snapshot of a topic as of a point in time.
+///
+/// All events up to and including the encoded UniqueTime `as_of` are streamed
+/// in order as NDJSON (one JSON object with `event_id`, `unique_time` and
+/// `document` per line). Since persisted events never change, repeating the
+/// export yields the same result as long as retention has not removed any of
+/// the events.
+///
+/// With `key` set to the result name of an extractor of the topic's event
+/// descriptor, the topic is treated as compacted: only the latest event for
+/// each extracted key is returned and events without the key are left out.
+///
+/// Requires authorization to the administrative function `snapshot`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_snapshot",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        (
+            "as_of" = u64,
+            Query,
+            description = "Encoded UniqueTime of the last event to include."
+        ),
+        (
+            "key" = Option<String>,
+            Query,
+            description = "Return only the latest event per value of this extracted key."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Stream of events as NDJSON.",
+            body = String,
+            content_type = "application/x-ndjson",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/snapshot")]
+pub async fn topic_snapshot(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<TopicSnapshotQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let lines = app_state
+        .mb
+        .get_topic_snapshot(&identity, &topic_id, query.as_of, query.key.as_deref())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let body = futures::stream::unfold(lines, |mut lines| async move {
+        lines
+            .recv()
+            .await
+            .map(|line| (Ok::<_, Error>(Bytes::from(line)), lines))
+    });
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type("application/x-ndjson")
+        .streaming(body))
+}
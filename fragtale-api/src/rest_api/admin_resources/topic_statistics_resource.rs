@@ -0,0 +1,85 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource for retrieving event statistics of a topic.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use fragtale_client::mb::topic_statistics::TopicStatistics;
+
+/// Retrieve rolling event size and field-level statistics of a topic.
+///
+/// Statistics are collected in memory by each instance since it was started,
+/// so the result only reflects events published through the instance that
+/// serves this request.
+///
+/// Requires authorization to the administrative function `stats`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "topic_statistics",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the topic statistics.",
+            body = inline(TopicStatistics),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (
+            status = 404,
+            description = "No events have been published to the topic via this instance.",
+        ),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/topics/{topic_id}/stats")]
+pub async fn topic_statistics(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let topic_statistics_opt = app_state
+        .mb
+        .get_topic_statistics(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(topic_statistics) = topic_statistics_opt {
+        Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(topic_statistics.as_string()))
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
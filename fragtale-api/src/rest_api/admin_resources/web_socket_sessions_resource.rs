@@ -0,0 +1,114 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for listing and killing WebSocket sessions.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::delete;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use fragtale_client::mb::web_socket_sessions::WebSocketSessions;
+
+/// List the WebSocket sessions open on this instance.
+///
+/// Requires authorization to the administrative function `sessions`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "web_socket_sessions",
+    responses(
+        (
+            status = 200,
+            description = "Return the open WebSocket sessions.",
+            body = inline(WebSocketSessions),
+            content_type = "application/json",
+        ),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/admin/sessions")]
+pub async fn web_socket_sessions(
+    app_state: Data<AppState>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let web_socket_sessions = app_state
+        .mb
+        .get_web_socket_sessions(&identity, app_state.ws_sessions.get_sessions())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(web_socket_sessions.as_string()))
+}
+
+/// Kill a WebSocket session open on this instance.
+///
+/// The session is closed the next time it is polled for incoming messages.
+///
+/// Requires authorization to the administrative function `sessions`.
+#[utoipa::path(
+    tag = "admin",
+    //operation_id = "kill_web_socket_session",
+    params(
+        ("session_id", description = "Session identifier on this instance."),
+    ),
+    responses(
+        (status = 204, description = "No content. Successfully killed the session."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "No such session on this instance."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[delete("/admin/sessions/{session_id}")]
+pub async fn kill_web_socket_session(
+    app_state: Data<AppState>,
+    path: Path<u64>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let session_id = path.into_inner();
+    let killed = app_state
+        .mb
+        .kill_web_socket_session(&identity, session_id, |session_id| {
+            app_state.ws_sessions.kill(session_id)
+        })
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if killed {
+        Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
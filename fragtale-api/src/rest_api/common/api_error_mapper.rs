@@ -34,7 +34,8 @@ impl ApiErrorMapper {
         }
         match e.kind() {
             MessageBrokerErrorKind::MalformedIdentifier
-            | MessageBrokerErrorKind::EvenDescriptorError => {
+            | MessageBrokerErrorKind::EvenDescriptorError
+            | MessageBrokerErrorKind::MalformedRequest => {
                 // HTTP 400
                 error::ErrorBadRequest(e.to_string())
             }
@@ -46,6 +47,18 @@ impl ApiErrorMapper {
                 // HTTP 403
                 error::ErrorForbidden(e.to_string())
             }
+            MessageBrokerErrorKind::QuotaExceeded => {
+                // HTTP 429
+                error::ErrorTooManyRequests(e.to_string())
+            }
+            MessageBrokerErrorKind::DeadlineExceeded => {
+                // HTTP 504
+                error::ErrorGatewayTimeout(e.to_string())
+            }
+            MessageBrokerErrorKind::PersistenceFailure => {
+                // HTTP 503
+                error::ErrorServiceUnavailable(e.to_string())
+            }
             _other => {
                 // HTTP 500
                 error::ErrorInternalServerError(e.to_string())
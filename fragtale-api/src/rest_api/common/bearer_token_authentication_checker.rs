@@ -19,15 +19,19 @@
 
 mod jwks_cache;
 mod kubernetes_integration;
+mod oidc_jwks_cache;
 
 use self::jwks_cache::JwksCache;
 use self::kubernetes_integration::KubernetesIntegration;
+use self::oidc_jwks_cache::OidcJwksCache;
+use super::ClientCertificateAuthentication;
 use actix_web::HttpRequest;
 use actix_web::http::header::HeaderValue;
 use crossbeam_skiplist::SkipMap;
 use crossbeam_skiplist::map::Entry;
 use fragtale_core::mb::MessageBrokerError;
 use fragtale_core::mb::MessageBrokerErrorKind;
+use fragtale_core::mb::auth::ClientAuthenticationMode;
 use fragtale_core::mb::auth::ClientIdentity;
 use jsonwebtoken::DecodingKey;
 use jsonwebtoken::TokenData;
@@ -44,27 +48,91 @@ use std::sync::Arc;
 /// This is tailored for Kubernetes environments where the other microservice
 /// can use a projected service account token to authenticate to fragtale in the
 /// same cluster with very little configuration.
+///
+/// Tokens from configured OpenID Connect issuers (e.g. corporate identity
+/// providers) are accepted as well, which also allows use outside of
+/// Kubernetes.
+///
+/// In developer mode, a static token is accepted as an identity with
+/// unrestricted access.
+///
+/// When enabled by the [ClientAuthenticationMode], the identity derived from a
+/// verified client certificate is used instead. See
+/// [ClientCertificateAuthentication].
 pub struct BearerTokenAuthenticationChecker {
     client_identity_by_bearer_token: SkipMap<String, (u64, Arc<ClientIdentity>)>,
-    jwks_cache: Arc<JwksCache>,
+    jwks_cache: Option<Arc<JwksCache>>,
+    oidc_jwks_cache: Option<Arc<OidcJwksCache>>,
     aud: String,
-    local_service_account_token_sub: String,
+    local_service_account_token_sub: Option<String>,
+    dev_token_and_identity: Option<(String, Arc<ClientIdentity>)>,
+    client_authentication_mode: ClientAuthenticationMode,
 }
 
 impl BearerTokenAuthenticationChecker {
     const BEARER_TOKEN: &str = "Bearer";
+    /// Issuer of the identity of the developer mode token.
+    const DEV_ISSUER: &str = "dev";
+    /// Subject of the identity of the developer mode token.
+    const DEV_SUBJECT: &str = "developer";
 
-    pub async fn new(aud: &str) -> Result<Arc<Self>, Box<dyn core::error::Error>> {
-        let jwks_cache = JwksCache::new().await?;
-        let (iss, jwks) = jwks_cache.get_iss_and_jwks()?;
-        let local_service_account_token_sub = Self::get_local_subject(&jwks, &iss, aud).await?;
-        let iss_and_jwks_cache = SkipMap::default();
-        iss_and_jwks_cache.insert((), (iss, Arc::new(jwks)));
+    /// Return a new instance.
+    ///
+    /// The Kubernetes cluster's JWKS is used when running in Kubernetes and
+    /// the JWKS of each of the `oidc_issuers` is found using OpenID Connect
+    /// discovery. The `dev_token` is only accepted in developer mode.
+    pub async fn new(
+        aud: &str,
+        oidc_issuers: &[&str],
+        oidc_trust_anchors_file: Option<&str>,
+        dev_token: Option<&str>,
+        client_authentication_mode: ClientAuthenticationMode,
+    ) -> Result<Arc<Self>, Box<dyn core::error::Error>> {
+        let (jwks_cache, local_service_account_token_sub) = if KubernetesIntegration::is_available()
+        {
+            let jwks_cache = JwksCache::new().await?;
+            let (iss, jwks) = jwks_cache.get_iss_and_jwks()?;
+            let local_service_account_token_sub = Self::get_local_subject(&jwks, &iss, aud).await?;
+            (Some(jwks_cache), Some(local_service_account_token_sub))
+        } else {
+            (None, None)
+        };
+        let oidc_jwks_cache = if oidc_issuers.is_empty() {
+            None
+        } else {
+            Some(OidcJwksCache::new(oidc_issuers, oidc_trust_anchors_file).await?)
+        };
+        let dev_token_and_identity = dev_token
+            .map(|dev_token| {
+                let claims = HashMap::from([
+                    ("iss".to_owned(), Value::String(Self::DEV_ISSUER.to_owned())),
+                    (
+                        "sub".to_owned(),
+                        Value::String(Self::DEV_SUBJECT.to_owned()),
+                    ),
+                ]);
+                // Local identities are allowed access to anything
+                ClientIdentity::from_bearer_token_claims(claims, true)
+                    .map(|identity| (dev_token.to_owned(), Arc::new(identity)))
+            })
+            .transpose()?;
+        if client_authentication_mode == ClientAuthenticationMode::Bearer
+            && jwks_cache.is_none()
+            && oidc_jwks_cache.is_none()
+            && dev_token_and_identity.is_none()
+        {
+            Err(
+                "Bearer tokens can't be validated outside of Kubernetes without OpenID Connect issuers.",
+            )?;
+        }
         Ok(Arc::new(Self {
             client_identity_by_bearer_token: SkipMap::default(),
             jwks_cache,
+            oidc_jwks_cache,
             aud: aud.to_string(),
             local_service_account_token_sub,
+            dev_token_and_identity,
+            client_authentication_mode,
         })
         .init()
         .await)
@@ -148,11 +216,100 @@ impl BearerTokenAuthenticationChecker {
             })
     }
 
-    /// Return the bearer token's (`iss`,`sub`) or `error::ErrorUnauthorized` (401)
+    /// Return the names of the supported authentication providers.
+    ///
+    /// Each OpenID Connect issuer is named `oidc:` followed by the issuer.
+    /// The developer mode token is named `dev`.
+    pub fn get_provider_names(&self) -> Vec<String> {
+        self.jwks_cache
+            .iter()
+            .map(|_| "kubernetes".to_owned())
+            .chain(
+                self.oidc_jwks_cache
+                    .iter()
+                    .flat_map(|oidc_jwks_cache| oidc_jwks_cache.get_issuers())
+                    .map(|issuer| format!("oidc:{issuer}")),
+            )
+            .chain(
+                self.dev_token_and_identity
+                    .iter()
+                    .map(|_| Self::DEV_ISSUER.to_owned()),
+            )
+            .collect()
+    }
+
+    /// Return the `iss` claim of the bearer token without validating it.
+    ///
+    /// This is only used to select the JWKS to validate the token with.
+    fn peek_issuer(token: &str) -> Option<String> {
+        let claims = tyst::encdec::base64::decode_url(token.split('.').nth(1)?).ok()?;
+        serde_json::from_slice::<Value>(&claims)
+            .ok()?
+            .get("iss")?
+            .as_str()
+            .map(str::to_owned)
+    }
+
+    /// Return `true` if the bearer token is signed with a key that isn't in
+    /// the JWKS.
+    fn is_unknown_kid(jwks: &JwkSet, token: &str) -> bool {
+        jsonwebtoken::decode_header(token)
+            .ok()
+            .and_then(|header| header.kid)
+            .is_some_and(|kid| jwks.find(&kid).is_none())
+    }
+
+    /// Validate the bearer token using the JWKS of its issuer.
+    ///
+    /// Return the token data and `true` if the token was issued by the
+    /// Kubernetes cluster.
+    fn validate_by_issuer(
+        &self,
+        bearer_token: &str,
+    ) -> Result<(TokenData<HashMap<String, Value>>, bool), MessageBrokerError> {
+        if let Some(oidc_jwks_cache) = &self.oidc_jwks_cache
+            && let Some(iss) = Self::peek_issuer(bearer_token)
+            && let Some(jwks) = oidc_jwks_cache.get_jwks(&iss)
+        {
+            return Self::validate_bearer_token(&iss, &jwks, &self.aud, bearer_token)
+                .inspect_err(|_e| {
+                    // The issuer might have rotated its keys
+                    if Self::is_unknown_kid(&jwks, bearer_token) {
+                        oidc_jwks_cache.reload_on_kid_miss(&iss);
+                    }
+                })
+                .map(|token_data| (token_data, false));
+        }
+        let (iss, jwks) = self
+            .jwks_cache
+            .as_ref()
+            .ok_or_else(|| {
+                MessageBrokerErrorKind::AuthenticationFailure
+                    .error_with_msg("Bearer token is not from a trusted issuer.")
+            })?
+            .get_iss_and_jwks()?;
+        Self::validate_bearer_token(&iss, &jwks, &self.aud, bearer_token)
+            .map(|token_data| (token_data, true))
+    }
+
+    /// Return the client certificate's or bearer token's identity or
+    /// `error::ErrorUnauthorized` (401)
     pub fn get_identity(
         &self,
         http_request: &HttpRequest,
     ) -> Result<Arc<ClientIdentity>, MessageBrokerError> {
+        if self.client_authentication_mode.accepts_client_certificate()
+            && let Some(client_certificate_authentication) =
+                http_request.conn_data::<ClientCertificateAuthentication>()
+        {
+            return Ok(Arc::clone(
+                client_certificate_authentication.get_client_identity(),
+            ));
+        }
+        if !self.client_authentication_mode.accepts_bearer_token() {
+            return Err(MessageBrokerErrorKind::AuthenticationFailure
+                .error_with_msg("Missing client certificate."));
+        }
         let authorization_header = http_request
             .headers()
             .get(actix_web::http::header::AUTHORIZATION)
@@ -188,6 +345,11 @@ impl BearerTokenAuthenticationChecker {
                 .collect::<String>();
             log::trace!("Bearer: {bearer_token} -> {decoded}");
         }
+        if let Some((dev_token, dev_identity)) = &self.dev_token_and_identity
+            && dev_token == bearer_token
+        {
+            return Ok(Arc::clone(dev_identity));
+        }
         let now_micros = fragtale_client::time::get_timestamp_micros();
         let mut delete_from_cache_on_fail = false;
         if let Some((expires_micros, client_identity)) = self
@@ -203,14 +365,17 @@ impl BearerTokenAuthenticationChecker {
             }
         }
         // Parse and validate JWT
-        let (iss, jwks) = self.jwks_cache.get_iss_and_jwks()?;
-        let token_data = Self::validate_bearer_token(&iss, &jwks, &self.aud, bearer_token)?;
+        let (token_data, is_kubernetes) = self.validate_by_issuer(bearer_token)?;
         if log::log_enabled!(log::Level::Trace) {
             log::trace!("token_data: {token_data:?}");
         }
         let sub = Self::extract_sub(&token_data)?;
         let exp_seconds = Self::extract_exp_seconds(&token_data)?;
-        let local_authentication = self.local_service_account_token_sub.eq(&sub);
+        let local_authentication = is_kubernetes
+            && self
+                .local_service_account_token_sub
+                .as_ref()
+                .is_some_and(|local_sub| local_sub.eq(&sub));
         let ret = Arc::new(
             ClientIdentity::from_bearer_token_claims(token_data.claims, local_authentication)
                 .map_err(|e| {
@@ -36,6 +36,11 @@ impl KubernetesIntegration {
     /// Kubernetes API server HTTPS trunt anchor.
     const FILE_K8S_TRUST_ANCHORS_PEM: &str = "/var/run/secrets/kubernetes.io/serviceaccount/ca.crt";
 
+    /// Return `true` when running in a Kubernetes cluster.
+    pub fn is_available() -> bool {
+        std::env::var(Self::ENV_K8S_HOST).is_ok()
+    }
+
     /// Returns the URL `https://${KUBERNETES_SERVICE_HOST}:${KUBERNETES_SERVICE_PORT_HTTPS}/.well-known/openid-configuration`
     pub fn build_openid_config_url() -> Result<String, std::env::VarError> {
         // Get KUBERNETES_SERVICE_HOST and KUBERNETES_SERVICE_PORT_HTTPS
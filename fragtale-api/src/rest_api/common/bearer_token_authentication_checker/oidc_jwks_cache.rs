@@ -0,0 +1,257 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! JSON Web Key Set cache of OpenID Connect issuers.
+
+use crossbeam_skiplist::SkipMap;
+use crossbeam_skiplist::map::Entry;
+use jsonwebtoken::jwk::JwkSet;
+use reqwest::Client;
+use serde_json::Value;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+
+/// The JSON Web Key Sets
+/// ([RFC 7517 5](https://www.rfc-editor.org/rfc/rfc7517#section-5)) of
+/// OpenID Connect issuers outside of Kubernetes (e.g. Keycloak or Entra ID).
+///
+/// The `jwks_uri` of each issuer is found using
+/// [OpenID Connect Discovery](https://openid.net/specs/openid-connect-discovery-1_0.html)
+/// and the key sets are reloaded periodically. A token signed with a key that
+/// is not in the cached key set triggers an early reload, so rotated keys are
+/// picked up without waiting for the next periodic reload.
+pub struct OidcJwksCache {
+    client: Client,
+    jwks_by_issuer: SkipMap<String, Arc<JwkSet>>,
+    /// Epoch microseconds of the latest reload of each issuer's key set.
+    reloaded_micros_by_issuer: SkipMap<String, AtomicU64>,
+}
+
+impl OidcJwksCache {
+    /// Time between periodic reloads of each issuer's key set.
+    const RELOAD_INTERVAL_MICROS: u64 = 300_000_000;
+    /// Min time between reloads of the same issuer's key set triggered by
+    /// tokens signed with unknown keys.
+    const RELOAD_ON_MISS_INTERVAL_MICROS: u64 = 10_000_000;
+
+    /// Return a new instance for the `issuers`.
+    ///
+    /// The HTTPS endpoints of the issuers are trusted using the trust anchors
+    /// in the PEM file `trust_anchors_file`.
+    pub async fn new(
+        issuers: &[&str],
+        trust_anchors_file: Option<&str>,
+    ) -> Result<Arc<Self>, Box<dyn core::error::Error>> {
+        let trust_anchors_file =
+            trust_anchors_file.ok_or("OpenID Connect issuers require configured trust anchors.")?;
+        let client = Self::new_client(trust_anchors_file).await?;
+        let jwks_by_issuer = SkipMap::default();
+        let reloaded_micros_by_issuer = SkipMap::default();
+        // Initial population of cached values that fail fast.
+        for issuer in issuers {
+            let jwks = Self::retrieve_jwks(&client, issuer).await?;
+            jwks_by_issuer.insert(issuer.to_string(), Arc::new(jwks));
+            reloaded_micros_by_issuer.insert(
+                issuer.to_string(),
+                AtomicU64::new(fragtale_client::time::get_timestamp_micros()),
+            );
+            log::info!("OAuth2 tokens will also be validated using issuer '{issuer}'.");
+        }
+        Ok(Arc::new(Self {
+            client,
+            jwks_by_issuer,
+            reloaded_micros_by_issuer,
+        })
+        .init()
+        .await)
+    }
+
+    async fn init(self: Arc<Self>) -> Arc<Self> {
+        let self_clone = Arc::clone(&self);
+        tokio::spawn(async move { self_clone.background_reload_of_jwks().await });
+        self
+    }
+
+    /// Return the issuers of this cache.
+    pub fn get_issuers(&self) -> Vec<String> {
+        self.jwks_by_issuer
+            .iter()
+            .map(|entry| entry.key().to_owned())
+            .collect()
+    }
+
+    /// Get the cached JWKS of the `issuer` or `None` if this is not one of
+    /// the issuers of this cache.
+    pub fn get_jwks(&self, issuer: &str) -> Option<Arc<JwkSet>> {
+        self.jwks_by_issuer
+            .get(issuer)
+            .as_ref()
+            .map(Entry::value)
+            .cloned()
+    }
+
+    /// Reload the key set of the `issuer` in the background, since a token
+    /// was signed with a key that isn't in the cached key set.
+    ///
+    /// Reloads are rate limited to protect the issuer from floods of tokens
+    /// with unknown keys.
+    pub fn reload_on_kid_miss(self: &Arc<Self>, issuer: &str) {
+        let Some(entry) = self.reloaded_micros_by_issuer.get(issuer) else {
+            return;
+        };
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let reloaded_micros = entry.value().load(Ordering::Relaxed);
+        if reloaded_micros + Self::RELOAD_ON_MISS_INTERVAL_MICROS > now_micros
+            || entry
+                .value()
+                .compare_exchange(
+                    reloaded_micros,
+                    now_micros,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_err()
+        {
+            return;
+        }
+        let self_clone = Arc::clone(self);
+        let issuer = issuer.to_owned();
+        tokio::spawn(async move { self_clone.reload_jwks(&issuer).await });
+    }
+
+    /// Background reloads of JWKS
+    async fn background_reload_of_jwks(&self) {
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_micros(
+                Self::RELOAD_INTERVAL_MICROS,
+            ))
+            .await;
+            for issuer in self.get_issuers() {
+                if let Some(entry) = self.reloaded_micros_by_issuer.get(&issuer) {
+                    entry.value().store(
+                        fragtale_client::time::get_timestamp_micros(),
+                        Ordering::Relaxed,
+                    );
+                }
+                self.reload_jwks(&issuer).await;
+            }
+        }
+    }
+
+    /// Reload the key set of the `issuer`.
+    async fn reload_jwks(&self, issuer: &str) {
+        match Self::retrieve_jwks(&self.client, issuer).await {
+            Ok(jwks) => {
+                self.jwks_by_issuer
+                    .insert(issuer.to_owned(), Arc::new(jwks));
+            }
+            Err(e) => {
+                log::warn!(
+                    "Failed to reload JWKS of '{issuer}' (last successfully loaded will be used still): {e}"
+                );
+            }
+        }
+    }
+
+    /// Return the URL of the issuer's OpenID Provider Configuration.
+    fn build_openid_config_url(issuer: &str) -> String {
+        format!(
+            "{}/.well-known/openid-configuration",
+            issuer.trim_end_matches('/')
+        )
+    }
+
+    /// Load JWKS of the `issuer` using OpenID Connect Discovery.
+    async fn retrieve_jwks(
+        client: &Client,
+        issuer: &str,
+    ) -> Result<JwkSet, Box<dyn core::error::Error>> {
+        let openid_config_url = Self::build_openid_config_url(issuer);
+        let openid_config = Self::http_get(client, &openid_config_url).await?;
+        if log::log_enabled!(log::Level::Trace) {
+            log::trace!("openid-configuration at '{openid_config_url}': {openid_config}");
+        }
+        let json_value = serde_json::from_str::<Value>(&openid_config)?;
+        // The issuer of the configuration must be exactly the one configured
+        let discovered_issuer = Self::extract_string_from_json(&json_value, "/issuer")?;
+        if discovered_issuer != issuer {
+            Err(format!(
+                "Issuer '{discovered_issuer}' at '{openid_config_url}' does not match '{issuer}'."
+            ))?;
+        }
+        let jwks_uri = Self::extract_string_from_json(&json_value, "/jwks_uri")?;
+        Ok(serde_json::from_str(
+            &Self::http_get(client, &jwks_uri).await?,
+        )?)
+    }
+
+    /// Extract String value at JSON Pointer from document.
+    fn extract_string_from_json(
+        json_value: &Value,
+        json_pointer: &str,
+    ) -> Result<String, Box<dyn core::error::Error>> {
+        Ok(json_value
+            .pointer(json_pointer)
+            .ok_or(format!(
+                "Failed to extract '{json_pointer}' from {json_value}."
+            ))?
+            .as_str()
+            .ok_or(format!(
+                "Failed to parse value of '{json_pointer}' from {json_value} as String."
+            ))
+            .map(str::to_string)?)
+    }
+
+    /// Return a new REST API client for talking to the issuers.
+    async fn new_client(trust_anchors_file: &str) -> Result<Client, Box<dyn core::error::Error>> {
+        let mut file = File::open(trust_anchors_file).await?;
+        let mut trust_anchors_pem = vec![];
+        file.read_to_end(&mut trust_anchors_pem).await?;
+        let mut client_builder = reqwest::ClientBuilder::new()
+            .use_rustls_tls()
+            .tls_built_in_root_certs(false)
+            .https_only(true);
+        for cert in reqwest::Certificate::from_pem_bundle(&trust_anchors_pem)? {
+            client_builder = client_builder.add_root_certificate(cert);
+        }
+        let res = client_builder
+            .user_agent("fragtale/0.0.0")
+            .referer(false)
+            .redirect(reqwest::redirect::Policy::none())
+            .pool_max_idle_per_host(1)
+            .timeout(core::time::Duration::from_secs(10))
+            .build()?;
+        Ok(res)
+    }
+
+    /// Make request to the issuer using client.
+    async fn http_get(client: &Client, url: &str) -> Result<String, Box<dyn core::error::Error>> {
+        let response = client.get(url).send().await?;
+        if response.status() == reqwest::StatusCode::OK {
+            let content = response.text().await?;
+            if log::log_enabled!(log::Level::Trace) {
+                log::trace!("'{url}' -> '{content}'");
+            }
+            Ok(content)
+        } else {
+            Err(format!("Get '{url}' failed: {response:?}").as_str().into())
+        }
+    }
+}
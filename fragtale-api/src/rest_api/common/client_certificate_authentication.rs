@@ -0,0 +1,147 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Authentication of API clients with client certificates (mTLS).
+
+use actix_tls::accept::rustls_0_23::TlsStream;
+use actix_web::dev::Extensions;
+use actix_web::rt::net::TcpStream;
+use fragtale_core::conf::AppConfig;
+use fragtale_core::mb::auth::ClientAuthenticationMode;
+use fragtale_core::mb::auth::ClientIdentity;
+use rustls::RootCertStore;
+use rustls::ServerConfig;
+use rustls::server::WebPkiClientVerifier;
+use rustls_pki_types::CertificateDer;
+use rustls_pki_types::PrivateKeyDer;
+use rustls_pki_types::pem::PemObject;
+use std::any::Any;
+use std::sync::Arc;
+
+/** Authenticates API clients using the certificate presented during the TLS
+handshake.
+
+The certificate chain is verified against the configured trust anchors by
+the TLS server, so the client identity can be derived from the subject of
+the certificate when the connection is established. The identity is then
+available to each request on the connection as connection data.
+*/
+pub struct ClientCertificateAuthentication {
+    client_identity: Arc<ClientIdentity>,
+}
+
+impl ClientCertificateAuthentication {
+    /// Return the identity derived from the client certificate.
+    pub fn get_client_identity(&self) -> &Arc<ClientIdentity> {
+        &self.client_identity
+    }
+
+    /// Derive the client identity from the certificate presented by the
+    /// client (if any) when a new connection is established.
+    ///
+    /// Intended for use with `HttpServer::on_connect`.
+    pub fn on_connect(connection: &dyn Any, extensions: &mut Extensions) {
+        let Some(tls_stream) = connection.downcast_ref::<TlsStream<TcpStream>>() else {
+            return;
+        };
+        let (_tcp_stream, server_connection) = tls_stream.get_ref();
+        let Some(end_entity) = server_connection
+            .peer_certificates()
+            .and_then(|certificates| certificates.first())
+        else {
+            return;
+        };
+        match Self::client_identity_from_certificate(end_entity) {
+            Ok(client_identity) => {
+                extensions.insert(Self {
+                    client_identity: Arc::new(client_identity),
+                });
+            }
+            Err(e) => log::info!("Unable to derive client identity from certificate: {e}"),
+        }
+    }
+
+    /// Derive the client identity from the subject of the DER encoded
+    /// certificate.
+    fn client_identity_from_certificate(
+        certificate: &CertificateDer,
+    ) -> Result<ClientIdentity, String> {
+        let (_rest, x509_certificate) =
+            x509_parser::parse_x509_certificate(certificate.as_ref())
+                .map_err(|e| format!("Malformed client certificate: {e}"))?;
+        let subject = x509_certificate.subject();
+        let organizational_units = subject
+            .iter_organizational_unit()
+            .filter_map(|attribute| attribute.as_str().ok())
+            .map(str::to_owned)
+            .collect();
+        ClientIdentity::from_certificate_subject(&subject.to_string(), organizational_units)
+            .map_err(|e| e.to_string())
+    }
+
+    /// Return the TLS server configuration that verifies client certificates
+    /// against the configured trust anchors.
+    ///
+    /// Clients without a certificate are only allowed to connect unless the
+    /// `client_authentication_mode` requires a certificate.
+    pub fn server_config(
+        app_config: &AppConfig,
+        client_authentication_mode: ClientAuthenticationMode,
+    ) -> Result<ServerConfig, Box<dyn core::error::Error>> {
+        let (Some(certificate_file), Some(key_file), Some(trust_anchors_file)) = (
+            app_config.api.tls_certificate_file(),
+            app_config.api.tls_key_file(),
+            app_config.api.tls_client_trust_anchors_file(),
+        ) else {
+            return Err(
+                "Client certificate authentication requires a TLS certificate, key and client trust anchors.".into(),
+            );
+        };
+        let certificate_chain = CertificateDer::pem_file_iter(certificate_file)
+            .and_then(|pem_iter| pem_iter.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| {
+                format!("Failed to read TLS certificate from '{certificate_file}': {e}")
+            })?;
+        let private_key = PrivateKeyDer::from_pem_file(key_file)
+            .map_err(|e| format!("Failed to read TLS key from '{key_file}': {e}"))?;
+        let mut root_cert_store = RootCertStore::empty();
+        for trust_anchor in CertificateDer::pem_file_iter(trust_anchors_file)
+            .and_then(|pem_iter| pem_iter.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| {
+                format!("Failed to read client trust anchors from '{trust_anchors_file}': {e}")
+            })?
+        {
+            root_cert_store.add(trust_anchor)?;
+        }
+        let crypto_provider = Arc::new(rustls::crypto::ring::default_provider());
+        let client_verifier_builder = WebPkiClientVerifier::builder_with_provider(
+            Arc::new(root_cert_store),
+            Arc::clone(&crypto_provider),
+        );
+        let client_verifier = if client_authentication_mode.requires_client_certificate() {
+            client_verifier_builder.build()?
+        } else {
+            client_verifier_builder.allow_unauthenticated().build()?
+        };
+        let mut server_config = ServerConfig::builder_with_provider(crypto_provider)
+            .with_safe_default_protocol_versions()?
+            .with_client_cert_verifier(client_verifier)
+            .with_single_cert(certificate_chain, private_key)?;
+        server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(server_config)
+    }
+}
@@ -0,0 +1,45 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! WebSocket keep-alive query parameters.
+
+use fragtale_core::conf::AppConfig;
+use serde::Deserialize;
+
+/// Keep-alive settings requested by a WebSocket client.
+///
+/// The requested values are negotiated against the server-side caps, so a
+/// client on a high-latency link can ask for a longer ping interval or a
+/// larger tolerance before the connection is considered stale.
+#[derive(Debug, Deserialize)]
+pub struct KeepAliveQueryParams {
+    /// Requested interval between client pings in milliseconds.
+    #[serde(rename = "ping_interval")]
+    ping_interval_millis: Option<u64>,
+    /// Requested tolerated delay of a client ping in milliseconds.
+    #[serde(rename = "ping_tolerance")]
+    ping_tolerance_millis: Option<u64>,
+}
+
+impl KeepAliveQueryParams {
+    /// Return the negotiated ping interval and tolerance in microseconds.
+    pub fn negotiate_ping_micros(&self, app_config: &AppConfig) -> (u64, u64) {
+        app_config
+            .api
+            .negotiate_ping_micros(self.ping_interval_millis, self.ping_tolerance_millis)
+    }
+}
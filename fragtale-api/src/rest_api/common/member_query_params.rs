@@ -0,0 +1,36 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Consumer group member query parameters.
+
+use serde::Deserialize;
+
+/// Identity of the client instance within its consumer group.
+#[derive(Debug, Deserialize)]
+pub struct MemberQueryParams {
+    /// Client instance identifier within the consumer group.
+    #[serde(rename = "member")]
+    member_id: Option<String>,
+}
+
+impl MemberQueryParams {
+    /// Get the client instance identifier within the consumer group, if
+    /// present.
+    pub fn get_member_id(&self) -> Option<&str> {
+        self.member_id.as_deref()
+    }
+}
@@ -19,6 +19,8 @@
 
 use actix_web::Error;
 use actix_web::error::ErrorBadRequest;
+use fragtale_client::DeliveryBatching;
+use fragtale_client::WireFormat;
 use fragtale_client::mb::event_descriptor::DescriptorVersion;
 use serde::Deserialize;
 use std::num::ParseIntError;
@@ -32,6 +34,23 @@ pub struct NextQueryParams {
     /// Event Descriptor SemVer that the client prefers.
     #[serde(rename = "version")]
     event_descriptor_semver: Option<String>,
+    /// Comma separated kinds of events of interest in a multi-type topic.
+    #[serde(rename = "type")]
+    event_types: Option<String>,
+    /// Lowest priority of events of interest.
+    min_priority: Option<u8>,
+    /// Comma separated JSON Pointers of the fields of interest.
+    fields: Option<String>,
+    /// Filter expression over the values extracted from each event document.
+    filter: Option<String>,
+    /// Time in milliseconds to wait for confirmation before redelivery.
+    visibility_timeout: Option<u64>,
+    /// Routing key of the events that this consumer prefers.
+    sticky: Option<String>,
+    /// Serialization format of WebSocket messages sent to the client.
+    format: Option<String>,
+    /// Max number of events in a single WebSocket message.
+    batch: Option<usize>,
 }
 
 impl NextQueryParams {
@@ -86,4 +105,77 @@ impl NextQueryParams {
     pub fn get_descriptor_version(&self) -> Result<Option<DescriptorVersion>, Error> {
         Self::as_descriptor_version(&self.event_descriptor_semver)
     }
+
+    /// Get the kinds of events of interest in a multi-type topic, if present.
+    pub fn get_event_types(&self) -> Option<Vec<String>> {
+        self.event_types.as_ref().map(|event_types| {
+            event_types
+                .split(',')
+                .map(str::trim)
+                .filter(|event_type| !event_type.is_empty())
+                .map(str::to_owned)
+                .collect()
+        })
+    }
+
+    /// Get the lowest priority of events of interest, if present.
+    pub fn get_min_priority(&self) -> Option<u8> {
+        self.min_priority
+    }
+
+    /// Get the JSON Pointers of the fields of interest in each event
+    /// document, if present.
+    pub fn get_fields(&self) -> Option<Vec<String>> {
+        self.fields.as_ref().map(|fields| {
+            fields
+                .split(',')
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .map(str::to_owned)
+                .collect()
+        })
+    }
+
+    /// Get the filter expression that events of interest match, if present.
+    pub fn get_filter(&self) -> Option<&str> {
+        self.filter.as_deref()
+    }
+
+    /// Get the time in milliseconds that the broker should wait for
+    /// confirmation of a delivered event before it is considered for
+    /// redelivery, if present.
+    pub fn get_visibility_timeout_millis(&self) -> Option<u64> {
+        self.visibility_timeout
+    }
+
+    /// Get the routing key of the events that this member of the consumer
+    /// group prefers, if present.
+    pub fn get_sticky_key(&self) -> Option<&str> {
+        self.sticky.as_deref()
+    }
+
+    /// Get the negotiated serialization format of WebSocket messages sent to
+    /// the client.
+    ///
+    /// Errors out with HTTP 400 Bad Request if the format is unknown.
+    pub fn get_wire_format(&self) -> Result<WireFormat, Error> {
+        self.format
+            .as_ref()
+            .map_or(Ok(WireFormat::default()), |format| {
+                WireFormat::from_name(format).ok_or_else(|| {
+                    ErrorBadRequest(format!(
+                        "Unknown 'format' query parameter '{format}'. Use 'json' or 'msgpack'."
+                    ))
+                })
+            })
+    }
+
+    /// Get the negotiated max number of events sent to the client in a single
+    /// WebSocket message.
+    ///
+    /// The requested size is capped to [DeliveryBatching::MAX_BATCH_SIZE] and
+    /// events are not batched unless requested.
+    pub fn get_delivery_batching(&self) -> DeliveryBatching {
+        DeliveryBatching::new(self.batch.unwrap_or(1))
+    }
 }
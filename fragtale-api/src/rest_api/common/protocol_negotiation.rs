@@ -0,0 +1,127 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Protocol version negotiation of API requests.
+
+use actix_web::Error;
+use actix_web::HttpMessage;
+use actix_web::dev::Service;
+use actix_web::dev::ServiceRequest;
+use actix_web::dev::ServiceResponse;
+use actix_web::error;
+use actix_web::http::header::HeaderMap;
+use actix_web::http::header::HeaderName;
+use actix_web::http::header::HeaderValue;
+use fragtale_client::protocol;
+use futures::FutureExt;
+use futures::future::LocalBoxFuture;
+
+/** Negotiates the protocol version used to serve each API request.
+
+Clients announce the highest protocol version they speak using the
+`protocol-version` header. Clients that don't send the header are assumed to
+only speak the oldest supported protocol version, so older clients keep
+working while the cluster is being upgraded.
+
+The negotiated version is returned in the `protocol-version` response header
+and is available to handlers as a [NegotiatedProtocolVersion] request
+extension. Requests announcing a protocol version older than what this
+instance still understands are rejected with HTTP 400.
+*/
+pub struct ProtocolNegotiation {}
+
+/// The protocol version negotiated for a request.
+#[derive(Clone, Copy, Debug)]
+pub struct NegotiatedProtocolVersion {
+    protocol_version: u32,
+    announced: bool,
+}
+
+impl NegotiatedProtocolVersion {
+    /// Return the negotiated protocol version.
+    pub fn get_protocol_version(&self) -> u32 {
+        self.protocol_version
+    }
+
+    /// Return `true` if the client announced its protocol version.
+    pub fn is_announced(&self) -> bool {
+        self.announced
+    }
+}
+
+impl ProtocolNegotiation {
+    /// Serve the request using `service` with the negotiated protocol version.
+    pub fn call<S, B>(
+        service_request: ServiceRequest,
+        service: &S,
+    ) -> LocalBoxFuture<'static, Result<ServiceResponse<B>, Error>>
+    where
+        S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+        S::Future: 'static,
+        B: 'static,
+    {
+        let negotiated = match Self::negotiate(service_request.headers()) {
+            Ok(negotiated) => negotiated,
+            Err(e) => return futures::future::ready(Err(e)).boxed_local(),
+        };
+        service_request.extensions_mut().insert(negotiated);
+        service
+            .call(service_request)
+            .map(move |result| {
+                result.map(|mut service_response| {
+                    service_response.headers_mut().insert(
+                        HeaderName::from_static(protocol::HEADER_NAME),
+                        HeaderValue::from(negotiated.protocol_version),
+                    );
+                    service_response
+                })
+            })
+            .boxed_local()
+    }
+
+    /// Return the protocol version to use for the request.
+    fn negotiate(headers: &HeaderMap) -> Result<NegotiatedProtocolVersion, Error> {
+        let Some(value) = headers.get(protocol::HEADER_NAME) else {
+            return Ok(NegotiatedProtocolVersion {
+                protocol_version: protocol::MIN_PROTOCOL_VERSION,
+                announced: false,
+            });
+        };
+        let requested = value
+            .to_str()
+            .ok()
+            .and_then(|value| value.trim().parse::<u32>().ok())
+            .ok_or_else(|| {
+                error::ErrorBadRequest(format!(
+                    "The '{}' header must be a protocol version number.",
+                    protocol::HEADER_NAME
+                ))
+            })?;
+        // Clients only announce their highest protocol version
+        protocol::negotiate(Some(requested), 0)
+            .map(|protocol_version| NegotiatedProtocolVersion {
+                protocol_version,
+                announced: true,
+            })
+            .ok_or_else(|| {
+                error::ErrorBadRequest(format!(
+                    "Protocol version {requested} is no longer supported. The oldest supported version is {}.",
+                    protocol::MIN_PROTOCOL_VERSION
+                ))
+            })
+    }
+}
@@ -0,0 +1,120 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Deadlines of API requests.
+
+use crate::rest_api::AppState;
+use actix_web::Error;
+use actix_web::dev::Service;
+use actix_web::dev::ServiceRequest;
+use actix_web::dev::ServiceResponse;
+use actix_web::error;
+use actix_web::http::StatusCode;
+use actix_web::http::header::HeaderMap;
+use actix_web::web::Data;
+use fragtale_core::util::RequestDeadline;
+use futures::FutureExt;
+use futures::future::LocalBoxFuture;
+use tokio::time::Duration;
+use tokio::time::Instant;
+
+/** Applies a deadline to each API request.
+
+Clients can request a timeout in milliseconds using the `request-timeout-ms`
+header. The timeout is capped by the configured maximum and the configured
+default applies when the header is absent.
+
+The deadline is propagated to database calls made while serving the request,
+which will fail with HTTP 504 when the deadline passes. Handlers that are still
+running shortly after the deadline are aborted.
+*/
+pub struct RequestTimeout {}
+
+impl RequestTimeout {
+    /// Name of the HTTP header with the requested timeout in milliseconds.
+    pub const HEADER_NAME: &str = "request-timeout-ms";
+    /// Time after the deadline before a still running handler is aborted.
+    const ABORT_GRACE: Duration = Duration::from_millis(250);
+
+    /// Serve the request using `service` within the deadline of the request.
+    pub fn call<S, B>(
+        service_request: ServiceRequest,
+        service: &S,
+    ) -> LocalBoxFuture<'static, Result<ServiceResponse<B>, Error>>
+    where
+        S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+        S::Future: 'static,
+        B: 'static,
+    {
+        let Some(app_state) = service_request.app_data::<Data<AppState>>().cloned() else {
+            return service.call(service_request).boxed_local();
+        };
+        let requested_millis = match Self::requested_timeout_millis(service_request.headers()) {
+            Ok(requested_millis) => requested_millis,
+            Err(e) => return futures::future::ready(Err(e)).boxed_local(),
+        };
+        let Some(timeout_micros) = app_state
+            .app_config
+            .api
+            .negotiate_request_timeout_micros(requested_millis)
+        else {
+            return service.call(service_request).boxed_local();
+        };
+        let endpoint = service_request
+            .match_pattern()
+            .unwrap_or_else(|| "unmatched".to_owned());
+        let deadline = Instant::now() + Duration::from_micros(timeout_micros);
+        let response_future = RequestDeadline::scope(deadline, service.call(service_request));
+        async move {
+            let result = tokio::time::timeout_at(deadline + Self::ABORT_GRACE, response_future)
+                .await
+                .unwrap_or_else(|_elapsed| {
+                    Err(error::ErrorGatewayTimeout(
+                        "The request deadline passed before the request was served.",
+                    ))
+                });
+            let status_code = match &result {
+                Ok(service_response) => service_response.status(),
+                Err(e) => e.as_response_error().status_code(),
+            };
+            if status_code == StatusCode::GATEWAY_TIMEOUT {
+                app_state.mb.report_deadline_exceeded(&endpoint);
+            }
+            result
+        }
+        .boxed_local()
+    }
+
+    /// Return the timeout in milliseconds requested by the client (if any).
+    fn requested_timeout_millis(headers: &HeaderMap) -> Result<Option<u64>, Error> {
+        headers
+            .get(Self::HEADER_NAME)
+            .map(|value| {
+                value
+                    .to_str()
+                    .ok()
+                    .and_then(|value| value.trim().parse::<u64>().ok())
+                    .ok_or_else(|| {
+                        error::ErrorBadRequest(format!(
+                            "The '{}' header must be a number of milliseconds.",
+                            Self::HEADER_NAME
+                        ))
+                    })
+            })
+            .transpose()
+    }
+}
@@ -0,0 +1,40 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Response body of a shared event document.
+
+use actix_web::web::Bytes;
+use std::sync::Arc;
+
+/// Response body of a shared event document.
+///
+/// The body references the document instead of copying it, which matters for
+/// large payloads that are also held by caches.
+pub struct SharedDocumentBody(Arc<str>);
+
+impl SharedDocumentBody {
+    /// Return the document as response body without copying it.
+    pub fn from_document(document: Arc<str>) -> Bytes {
+        Bytes::from_owner(Self(document))
+    }
+}
+
+impl AsRef<[u8]> for SharedDocumentBody {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
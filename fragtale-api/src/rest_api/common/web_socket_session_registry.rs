@@ -0,0 +1,480 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Tracking, caps and metrics of open WebSocket sessions.
+
+use actix_ws::AggregatedMessage;
+use actix_ws::AggregatedMessageStream;
+use actix_ws::ProtocolError;
+use crossbeam_skiplist::SkipMap;
+use fragtale_client::mb::web_socket_sessions::WebSocketSession;
+use fragtale_core::conf::AppConfig;
+use fragtale_core::util::TimerWheel;
+use fragtale_metrics::metric::Metric;
+use fragtale_metrics::metric::MetricLabeledValue;
+use fragtale_metrics::metric::MetricType;
+use fragtale_metrics::registry::MetricsProvider;
+use fragtale_metrics::registry::MetricsProviderRegistry;
+use fragtale_metrics::registry::MetricsResult;
+use fragtale_metrics::registry::MetricsResultFuture;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use tokio::time::Duration;
+use tokio::time::timeout;
+
+/// State of a single open WebSocket session.
+struct SessionState {
+    kind: &'static str,
+    topic_id: Option<String>,
+    consumer_id: String,
+    opened_ts_micros: u64,
+    last_activity_ts_micros: AtomicU64,
+    rtt_micros: AtomicU64,
+    sent_bytes: AtomicU64,
+    killed: AtomicBool,
+    /// Epoch microseconds when the client must have pinged again or `0`.
+    ping_deadline_micros: AtomicU64,
+    /// Set when the client failed to ping before the deadline.
+    stale: AtomicBool,
+}
+
+/** Tracks open WebSocket sessions of this instance.
+
+Subscriber connections that are never closed by the client (or a proxy in
+between) would otherwise accumulate until the instance is restarted. Sessions
+are capped per identity, closed when idle for too long and can be killed by an
+administrator.
+*/
+pub struct WebSocketSessionRegistry {
+    max_sessions_per_identity: Option<usize>,
+    idle_timeout_micros: Option<u64>,
+    session_id_counter: AtomicU64,
+    sessions: SkipMap<u64, Arc<SessionState>>,
+    opened: SkipMap<&'static str, AtomicU64>,
+    closed: SkipMap<&'static str, AtomicU64>,
+    killed: SkipMap<&'static str, AtomicU64>,
+    refused: SkipMap<&'static str, AtomicU64>,
+    /// Session identifier and ping deadline by ping deadline.
+    ping_deadlines: TimerWheel<(u64, u64)>,
+}
+
+impl WebSocketSessionRegistry {
+    /// Session that delivers events of a topic.
+    pub const KIND_SUBSCRIBE: &str = "subscribe";
+    /// Session that receives delivery confirmations for a topic.
+    pub const KIND_CONFIRM: &str = "confirm";
+    /// Session that receives events published to a topic.
+    pub const KIND_PUBLISH: &str = "publish";
+    /// Session that multiplexes streams of many topics.
+    pub const KIND_MULTIPLEX: &str = "multiplex";
+
+    /// Interval between checks of idle and killed sessions while waiting for
+    /// the next message from the client.
+    const CHECK_INTERVAL_MICROS: u64 = 1_000_000;
+    /// Resolution of the ping deadlines.
+    const PING_DEADLINE_TICK_MICROS: u64 = 64_000;
+
+    const METRIC_COMPONENT_NAME: &str = "ws";
+    const METRIC_NAME_PING_RTT: &str = "ping_rtt_micros";
+    const METRIC_NAME_SENT_BYTES: &str = "session_sent_bytes";
+    const METRIC_NAME_OPEN: &str = "sessions_open";
+    const METRIC_NAME_OPENED: &str = "sessions_opened_count";
+    const METRIC_NAME_CLOSED: &str = "sessions_closed_count";
+    const METRIC_NAME_KILLED: &str = "sessions_killed_count";
+    const METRIC_NAME_REFUSED: &str = "sessions_refused_count";
+    const METRIC_LABEL_TOPIC: &str = "topic";
+    const METRIC_LABEL_CONSUMER: &str = "consumer";
+    const METRIC_LABEL_CONNECTION: &str = "connection";
+    const METRIC_LABEL_KIND: &str = "kind";
+
+    /// Return a new instance.
+    pub fn new(app_config: &AppConfig) -> Arc<Self> {
+        let instance = Arc::new(Self {
+            max_sessions_per_identity: app_config.api.max_sessions_per_identity(),
+            idle_timeout_micros: app_config.api.session_idle_timeout_micros(),
+            session_id_counter: AtomicU64::default(),
+            sessions: SkipMap::default(),
+            opened: SkipMap::default(),
+            closed: SkipMap::default(),
+            killed: SkipMap::default(),
+            refused: SkipMap::default(),
+            ping_deadlines: TimerWheel::new(Self::PING_DEADLINE_TICK_MICROS),
+        });
+        MetricsProviderRegistry::register_metrics(
+            app_config.app_name_lowercase(),
+            Self::METRIC_COMPONENT_NAME,
+            Arc::clone(&instance) as Arc<dyn MetricsProvider>,
+        );
+        let self_clone = Arc::clone(&instance);
+        tokio::spawn(async move { self_clone.expire_ping_deadlines().await });
+        instance
+    }
+
+    /// Mark sessions as stale when the client has not pinged before the
+    /// deadline.
+    ///
+    /// Only the deadlines that have passed are visited, so there is no need
+    /// for each session to poll its own deadline.
+    async fn expire_ping_deadlines(&self) {
+        loop {
+            tokio::time::sleep(Duration::from_micros(Self::PING_DEADLINE_TICK_MICROS)).await;
+            let now_micros = fragtale_client::time::get_timestamp_micros();
+            for (session_id, ping_deadline_micros) in self.ping_deadlines.expire(now_micros) {
+                let Some(entry) = self.sessions.get(&session_id) else {
+                    continue;
+                };
+                let session = entry.value();
+                // Ignore deadlines that were renewed by a later ping
+                if session.ping_deadline_micros.load(Ordering::Relaxed) == ping_deadline_micros
+                    && !session.stale.swap(true, Ordering::Relaxed)
+                    && log::log_enabled!(log::Level::Debug)
+                {
+                    log::debug!(
+                        "Last ping on {} session {session_id} of '{}' was too old.",
+                        session.kind,
+                        session.consumer_id
+                    );
+                }
+            }
+        }
+    }
+
+    /// Require the client of the session to ping again before
+    /// `ping_deadline_micros` epoch microseconds or the session is no longer
+    /// considered open.
+    pub fn renew_ping_deadline(&self, session_id: u64, ping_deadline_micros: u64) {
+        if let Some(entry) = self.sessions.get(&session_id) {
+            entry
+                .value()
+                .ping_deadline_micros
+                .store(ping_deadline_micros, Ordering::Relaxed);
+            self.ping_deadlines
+                .schedule(ping_deadline_micros, (session_id, ping_deadline_micros));
+        }
+    }
+
+    /// Increment the counter of the session `kind`.
+    fn inc(map: &SkipMap<&'static str, AtomicU64>, kind: &'static str) {
+        map.get_or_insert_with(kind, AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Start tracking a new session and return its local identifier.
+    ///
+    /// Return `None` if the identity already has the max number of open
+    /// sessions on this instance.
+    pub fn open(
+        &self,
+        kind: &'static str,
+        topic_id: Option<&str>,
+        consumer_id: &str,
+    ) -> Option<u64> {
+        if let Some(max_sessions) = self.max_sessions_per_identity {
+            let open_sessions = self
+                .sessions
+                .iter()
+                .filter(|entry| entry.value().consumer_id == consumer_id)
+                .count();
+            if open_sessions >= max_sessions {
+                log::info!(
+                    "Refused {kind} session of '{consumer_id}' that already has {open_sessions} open sessions."
+                );
+                Self::inc(&self.refused, kind);
+                return None;
+            }
+        }
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let session_id = self.session_id_counter.fetch_add(1, Ordering::Relaxed);
+        self.sessions.insert(
+            session_id,
+            Arc::new(SessionState {
+                kind,
+                topic_id: topic_id.map(str::to_owned),
+                consumer_id: consumer_id.to_owned(),
+                opened_ts_micros: now_micros,
+                last_activity_ts_micros: AtomicU64::new(now_micros),
+                rtt_micros: AtomicU64::default(),
+                sent_bytes: AtomicU64::default(),
+                killed: AtomicBool::default(),
+                ping_deadline_micros: AtomicU64::default(),
+                stale: AtomicBool::default(),
+            }),
+        );
+        Self::inc(&self.opened, kind);
+        Some(session_id)
+    }
+
+    /// Stop tracking a closed session.
+    pub fn close(&self, session_id: u64) {
+        if let Some(entry) = self.sessions.remove(&session_id) {
+            Self::inc(&self.closed, entry.value().kind);
+        }
+    }
+
+    /// Signal that the session should be closed.
+    ///
+    /// Return the identity string and topic of the session if it was open.
+    pub fn kill(&self, session_id: u64) -> Option<(String, Option<String>)> {
+        let entry = self.sessions.get(&session_id)?;
+        let session = entry.value();
+        if !session.killed.swap(true, Ordering::Relaxed) {
+            Self::inc(&self.killed, session.kind);
+        }
+        Some((session.consumer_id.to_owned(), session.topic_id.to_owned()))
+    }
+
+    /// Return `true` if the session is tracked, has not been killed and the
+    /// client has pinged in time.
+    pub fn is_open(&self, session_id: u64) -> bool {
+        self.sessions.get(&session_id).is_some_and(|entry| {
+            !entry.value().killed.load(Ordering::Relaxed)
+                && !entry.value().stale.load(Ordering::Relaxed)
+        })
+    }
+
+    /// Count bytes sent to the client of the session.
+    pub fn add_sent_bytes(&self, session_id: u64, bytes: usize) {
+        if let Some(entry) = self.sessions.get(&session_id) {
+            entry
+                .value()
+                .sent_bytes
+                .fetch_add(u64::try_from(bytes).unwrap_or_default(), Ordering::Relaxed);
+        }
+    }
+
+    /// Update the last measured ping round-trip time of a session.
+    pub fn update_rtt(&self, session_id: u64, rtt_micros: u64) {
+        if let Some(entry) = self.sessions.get(&session_id) {
+            entry
+                .value()
+                .rtt_micros
+                .store(rtt_micros, Ordering::Relaxed);
+        }
+    }
+
+    /// Wait for the next message from the client of the session.
+    ///
+    /// Return `None` when the stream has ended, the session has been killed,
+    /// the client missed its ping deadline or has been idle for longer than
+    /// the configured timeout.
+    pub async fn next_message(
+        &self,
+        session_id: u64,
+        stream: &mut AggregatedMessageStream,
+    ) -> Option<Result<AggregatedMessage, ProtocolError>> {
+        let session = Arc::clone(self.sessions.get(&session_id)?.value());
+        loop {
+            if session.killed.load(Ordering::Relaxed) {
+                log::info!(
+                    "Closing killed {} session {session_id} of '{}'.",
+                    session.kind,
+                    session.consumer_id
+                );
+                return None;
+            }
+            if session.stale.load(Ordering::Relaxed) {
+                log::info!(
+                    "Closing stale {} session {session_id} of '{}'.",
+                    session.kind,
+                    session.consumer_id
+                );
+                return None;
+            }
+            let now_micros = fragtale_client::time::get_timestamp_micros();
+            if self.idle_timeout_micros.is_some_and(|idle_timeout_micros| {
+                session.last_activity_ts_micros.load(Ordering::Relaxed)
+                    < now_micros.saturating_sub(idle_timeout_micros)
+            }) {
+                log::info!(
+                    "Closing idle {} session {session_id} of '{}'.",
+                    session.kind,
+                    session.consumer_id
+                );
+                return None;
+            }
+            if let Ok(msg) = timeout(
+                Duration::from_micros(Self::CHECK_INTERVAL_MICROS),
+                stream.next(),
+            )
+            .await
+            {
+                session.last_activity_ts_micros.store(
+                    fragtale_client::time::get_timestamp_micros(),
+                    Ordering::Relaxed,
+                );
+                return msg;
+            }
+        }
+    }
+
+    /// Return all open sessions.
+    pub fn get_sessions(&self) -> Vec<WebSocketSession> {
+        self.sessions
+            .iter()
+            .map(|entry| {
+                let session = entry.value();
+                WebSocketSession::new(
+                    *entry.key(),
+                    session.kind,
+                    session.topic_id.as_deref(),
+                    &session.consumer_id,
+                    session.opened_ts_micros,
+                    session.last_activity_ts_micros.load(Ordering::Relaxed),
+                    session.sent_bytes.load(Ordering::Relaxed),
+                )
+            })
+            .collect()
+    }
+
+    /// Return a value of each open session with labels that identify it.
+    fn mlvs_from_sessions<F>(&self, kind: Option<&str>, value: F) -> Vec<MetricLabeledValue>
+    where
+        F: Fn(&SessionState) -> u64,
+    {
+        let mut mlvs = vec![];
+        for entry in self.sessions.iter() {
+            let session = entry.value();
+            if kind.is_some_and(|kind| kind != session.kind) {
+                continue;
+            }
+            mlvs.push(
+                MetricLabeledValue::new(value(session) as f64)
+                    .add_label(
+                        Self::METRIC_LABEL_TOPIC,
+                        session.topic_id.to_owned().unwrap_or_default(),
+                    )
+                    .add_label(Self::METRIC_LABEL_CONSUMER, session.consumer_id.to_owned())
+                    .add_label(Self::METRIC_LABEL_CONNECTION, entry.key().to_string()),
+            );
+        }
+        if mlvs.is_empty() {
+            mlvs.push(MetricLabeledValue::new(0f64));
+        }
+        mlvs
+    }
+
+    /// Return the number of open sessions of each kind.
+    fn mlvs_from_open_by_kind(&self) -> Vec<MetricLabeledValue> {
+        [
+            Self::KIND_SUBSCRIBE,
+            Self::KIND_CONFIRM,
+            Self::KIND_PUBLISH,
+            Self::KIND_MULTIPLEX,
+        ]
+        .iter()
+        .map(|kind| {
+            let count = self
+                .sessions
+                .iter()
+                .filter(|entry| entry.value().kind == *kind)
+                .count();
+            MetricLabeledValue::new(count as f64)
+                .add_label(Self::METRIC_LABEL_KIND, kind.to_string())
+        })
+        .collect()
+    }
+
+    /// Return the counter of each session kind.
+    fn mlvs_from_by_kind_count(map: &SkipMap<&'static str, AtomicU64>) -> Vec<MetricLabeledValue> {
+        let mut mlvs = map
+            .iter()
+            .map(|entry| {
+                MetricLabeledValue::new(entry.value().load(Ordering::Relaxed) as f64)
+                    .add_label(Self::METRIC_LABEL_KIND, entry.key().to_string())
+            })
+            .collect::<Vec<_>>();
+        if mlvs.is_empty() {
+            mlvs.push(MetricLabeledValue::new(0f64));
+        }
+        mlvs
+    }
+}
+
+impl MetricsProvider for WebSocketSessionRegistry {
+    fn metrics(self: Arc<Self>, template: MetricsResult) -> MetricsResultFuture {
+        let self_clone = Arc::clone(&self);
+        MetricsResultFuture::from_future(async move {
+            template
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_PING_RTT,
+                        &self_clone.mlvs_from_sessions(Some(Self::KIND_SUBSCRIBE), |session| {
+                            session.rtt_micros.load(Ordering::Relaxed)
+                        }),
+                    )
+                    .set_help("Last measured ping round-trip time of each subscriber connection.")
+                    .set_type(MetricType::Gauge),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_SENT_BYTES,
+                        &self_clone.mlvs_from_sessions(None, |session| {
+                            session.sent_bytes.load(Ordering::Relaxed)
+                        }),
+                    )
+                    .set_help("Bytes sent to the client of each open WebSocket session.")
+                    .set_type(MetricType::Gauge),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_OPEN,
+                        &self_clone.mlvs_from_open_by_kind(),
+                    )
+                    .set_help("Open WebSocket sessions of each kind.")
+                    .set_type(MetricType::Gauge),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_OPENED,
+                        &Self::mlvs_from_by_kind_count(&self_clone.opened),
+                    )
+                    .set_help("Opened WebSocket sessions of each kind.")
+                    .set_type(MetricType::Counter),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_CLOSED,
+                        &Self::mlvs_from_by_kind_count(&self_clone.closed),
+                    )
+                    .set_help("Closed WebSocket sessions of each kind.")
+                    .set_type(MetricType::Counter),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_KILLED,
+                        &Self::mlvs_from_by_kind_count(&self_clone.killed),
+                    )
+                    .set_help("WebSocket sessions of each kind killed by an administrator.")
+                    .set_type(MetricType::Counter),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_REFUSED,
+                        &Self::mlvs_from_by_kind_count(&self_clone.refused),
+                    )
+                    .set_help(
+                        "WebSocket sessions of each kind refused due to the per identity cap.",
+                    )
+                    .set_type(MetricType::Counter),
+                )
+        })
+    }
+}
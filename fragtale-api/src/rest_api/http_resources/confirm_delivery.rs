@@ -25,7 +25,9 @@ use actix_web::HttpResponse;
 use actix_web::http::StatusCode;
 use actix_web::route;
 use actix_web::web::Data;
+use actix_web::web::Json;
 use actix_web::web::Path;
+use fragtale_client::mb::delivery_preparation::DeliveryPreparation;
 use fragtale_core::util::LogScopeDuration;
 
 /// Confirm successful delivery of an event.
@@ -72,3 +74,99 @@ pub async fn confirm_event_delivery(
         .map_err(ApiErrorMapper::from_message_broker_error)?;
     Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
 }
+
+/// Prepare confirmation of an event delivery.
+///
+/// First step of a two-phase confirmation for consumers that hand off events
+/// to an external sink. The consumer's external transaction identifier is
+/// recorded, so that a redelivery of the event (e.g. after a consumer crash)
+/// will carry the prepared transaction identifier in the
+/// `prepared-transaction-id` header.
+///
+/// Consumer identifier is derived from authentication.
+#[utoipa::path(
+    put,
+    path = "/topics/{topic_id}/confirm/{unique_time}/{instance_id}/prepare",
+    tag = "http",
+    //operation_id = "prepare_event_delivery",
+    request_body = inline(DeliveryPreparation),
+    responses(
+        (status = 204, description = "Successfully prepared event delivery confirmation."),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[route(
+    "/topics/{topic_id}/confirm/{unique_time}/{instance_id}/prepare",
+    method = "PUT",
+    name = "prepare_event_delivery"
+)]
+pub async fn prepare_event_delivery(
+    app_state: Data<AppState>,
+    path: Path<(String, u64, u16)>,
+    preparation: Json<DeliveryPreparation>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, encoded_unique_time, _instance_id) = path.into_inner();
+    app_state
+        .mb
+        .prepare_event_delivery(
+            &identity,
+            &topic_id,
+            encoded_unique_time,
+            preparation.get_transaction_id(),
+        )
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
+}
+
+/// Commit a prepared confirmation of an event delivery.
+///
+/// Second step of a two-phase confirmation. This has the same effect as
+/// confirming the event delivery and removes the prepared transaction
+/// identifier.
+///
+/// Consumer identifier is derived from authentication.
+#[utoipa::path(
+    put,
+    path = "/topics/{topic_id}/confirm/{unique_time}/{instance_id}/commit",
+    tag = "http",
+    //operation_id = "commit_event_delivery",
+    responses(
+        (status = 204, description = "Successfully committed event delivery confirmation."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[route(
+    "/topics/{topic_id}/confirm/{unique_time}/{instance_id}/commit",
+    method = "PUT",
+    name = "commit_event_delivery"
+)]
+pub async fn commit_event_delivery(
+    app_state: Data<AppState>,
+    path: Path<(String, u64, u16)>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, encoded_unique_time, instance_id) = path.into_inner();
+    app_state
+        .mb
+        .commit_event_delivery(&identity, &topic_id, encoded_unique_time, instance_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
+}
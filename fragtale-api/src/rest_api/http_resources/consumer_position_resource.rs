@@ -0,0 +1,140 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for explicitly committing or moving a consumer's position in
+//! a topic.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::http::StatusCode;
+use actix_web::post;
+use actix_web::put;
+use actix_web::web;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use fragtale_client::mb::consumer_position::ConsumerPosition;
+use fragtale_client::mb::consumer_position::ConsumerSeek;
+
+/// Commit consumer's position in the topic.
+///
+/// All events up to and including the position will be considered delivered
+/// to the consumer. This allows consumers to manage their own progress (e.g.
+/// batch-commit after writing to an external sink) instead of confirming the
+/// delivery of each individual event.
+///
+/// A position at or before the already committed position is ignored.
+///
+/// The consumer identifier must match the identity derived from
+/// authentication.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "commit_consumer_position",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("consumer_id", description = "Consumer identifier."),
+    ),
+    request_body = inline(ConsumerPosition),
+    responses(
+        (
+            status = 204,
+            description = "Successfully committed consumer's position."
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/topics/{topic_id}/consumers/{consumer_id}/position")]
+pub async fn commit_consumer_position(
+    app_state: Data<AppState>,
+    path: Path<(String, String)>,
+    position: web::Json<ConsumerPosition>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, consumer_id) = path.into_inner();
+    app_state
+        .mb
+        .commit_consumer_position(
+            &identity,
+            &topic_id,
+            &consumer_id,
+            position.get_unique_time(),
+        )
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)
+        .map(|_| HttpResponse::build(StatusCode::NO_CONTENT).finish())
+}
+
+/// Move consumer's position in the topic.
+///
+/// Rewind the consumer to replay history or move it forward to skip a
+/// backlog. The position is either given as the encoded unique time of the
+/// last event that should be considered processed or as a timestamp in epoch
+/// microseconds from where events will be delivered. Events after the new
+/// position are delivered again, even if they were delivered before.
+///
+/// The consumer may move its own position. Moving the position of another
+/// consumer requires authorization to the administrative function `seek`.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "seek_consumer_position",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("consumer_id", description = "Consumer identifier."),
+    ),
+    request_body = inline(ConsumerSeek),
+    responses(
+        (
+            status = 204,
+            description = "Successfully moved consumer's position."
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+        (status = 503, description = "Service Unavailable. The position could not be persisted."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/topics/{topic_id}/consumers/{consumer_id}/seek")]
+pub async fn seek_consumer_position(
+    app_state: Data<AppState>,
+    path: Path<(String, String)>,
+    consumer_seek: web::Json<ConsumerSeek>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, consumer_id) = path.into_inner();
+    app_state
+        .mb
+        .seek_consumer_position(&identity, &topic_id, &consumer_id, &consumer_seek)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)
+        .map(|_| HttpResponse::build(StatusCode::NO_CONTENT).finish())
+}
@@ -0,0 +1,136 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for append-only annotations of events.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::post;
+use actix_web::web::Data;
+use actix_web::web::Json;
+use actix_web::web::Path;
+use fragtale_client::mb::event_annotations::EventAnnotation;
+use fragtale_client::mb::event_annotations::EventAnnotations;
+
+/// Append an annotation to an existing event.
+///
+/// Annotations are small label keys, free text notes or processing statuses
+/// (`label`, `note` or `status`) that are stored separately and never alter
+/// the event itself.
+///
+/// The author is derived from authentication and must be allowed to annotate
+/// events in the topic.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "event_annotation_append",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("event_id", description = "Event identifier."),
+    ),
+    request_body = inline(EventAnnotation),
+    responses(
+        (status = 204, description = "Successfully annotated the event."),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (
+            status = 404,
+            description = "No event with the event identifier was found.",
+        ),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/topics/{topic_id}/events/by_event_id/{event_id}/annotations")]
+pub async fn event_annotation_append(
+    app_state: Data<AppState>,
+    path: Path<(String, String)>,
+    event_annotation: Json<EventAnnotation>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, event_id) = path.into_inner();
+    let annotated = app_state
+        .mb
+        .annotate_event(
+            &identity,
+            &topic_id,
+            &event_id,
+            event_annotation.into_inner(),
+        )
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if annotated {
+        Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
+
+/// Get all annotations of an event ordered by time of annotation.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "event_annotations_by_event_id",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("event_id", description = "Event identifier."),
+    ),
+    responses(
+        (status = 200, description = "Ok.", body = inline(EventAnnotations)),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (
+            status = 404,
+            description = "No event with the event identifier was found.",
+        ),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/topics/{topic_id}/events/by_event_id/{event_id}/annotations")]
+pub async fn event_annotations_by_event_id(
+    app_state: Data<AppState>,
+    path: Path<(String, String)>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let (topic_id, event_id) = path.into_inner();
+    let event_annotations = app_state
+        .mb
+        .get_event_annotations(&identity, &topic_id, &event_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(event_annotations) = event_annotations {
+        Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(event_annotations.as_string()))
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
@@ -19,6 +19,7 @@
 
 use crate::rest_api::AppState;
 use crate::rest_api::common::ApiErrorMapper;
+use crate::rest_api::common::SharedDocumentBody;
 use actix_web::Error;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
@@ -78,7 +79,8 @@ pub async fn by_topic_and_correlation_token(
         .await
         .map_err(ApiErrorMapper::from_message_broker_error)?;
     if let Some(event_document) = event_document_opt {
-        Ok(HttpResponse::build(StatusCode::OK).body(event_document))
+        Ok(HttpResponse::build(StatusCode::OK)
+            .body(SharedDocumentBody::from_document(event_document)))
     } else {
         Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
     }
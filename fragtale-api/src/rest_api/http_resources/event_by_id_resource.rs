@@ -19,6 +19,7 @@
 
 use crate::rest_api::AppState;
 use crate::rest_api::common::ApiErrorMapper;
+use crate::rest_api::common::SharedDocumentBody;
 use actix_web::Error;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
@@ -30,6 +31,9 @@ use actix_web::web::Path;
 /// Retrieve an event document by its identifier.
 ///
 /// Consumer identifier is derived from authentication.
+///
+/// Events that are no longer present in the database are read from the
+/// archive tier (if enabled), which is much slower.
 #[utoipa::path(
     tag = "http",
     //operation_id = "event_by_topic_and_id",
@@ -46,6 +50,12 @@ use actix_web::web::Path;
             status = 200,
             description = "Return the event document.",
             content_type = "application/json",
+            headers(
+                (
+                    "storage-tier" = String,
+                    description = "Storage tier the event was read from: 'database' or 'archive'."
+                ),
+            ),
         ),
         (status = 401, description = "Unauthorized: Authentication failure."),
         (status = 403, description = "Forbidden: Authorization failure."),
@@ -73,8 +83,10 @@ pub async fn event_by_topic_and_id(
         .get_event_by_id(&identity, &topic_id, &event_id)
         .await
         .map_err(ApiErrorMapper::from_message_broker_error)?;
-    if let Some(event_document) = event_document_opt {
-        Ok(HttpResponse::build(StatusCode::OK).body(event_document))
+    if let Some((event_document, storage_tier)) = event_document_opt {
+        Ok(HttpResponse::build(StatusCode::OK)
+            .append_header(("storage-tier", storage_tier.as_str()))
+            .body(SharedDocumentBody::from_document(event_document)))
     } else {
         Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
     }
@@ -15,19 +15,28 @@
     limitations under the License.
 */
 
-//! API resource for topic pre-registration.
+//! API resources for topic pre-registration and event description lookup.
 
 use crate::rest_api::AppState;
 use crate::rest_api::common::ApiErrorMapper;
 use actix_web::Error;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
+use actix_web::error;
+use actix_web::get;
 use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
 use actix_web::put;
 use actix_web::web;
 use actix_web::web::Data;
 use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::contract_fixtures::ContractFixtures;
+use fragtale_client::mb::descriptor_diff::DescriptorDiff;
+use fragtale_client::mb::event_descriptor::DescriptorVersion;
 use fragtale_client::mb::event_descriptor::EventDescriptor;
+use fragtale_client::mb::topic_overview::DescriptorHistory;
+use serde::Deserialize;
 
 /// Upsert topic's event description.
 ///
@@ -74,3 +83,228 @@ pub async fn topic_event_description_upsert(
         .map_err(ApiErrorMapper::from_message_broker_error)
         .map(|_| HttpResponse::build(StatusCode::NO_CONTENT).finish())
 }
+
+/// Get topic's latest event description.
+///
+/// Consumers can use this to validate incoming event documents with the same
+/// schema as publishers and to detect when the description has changed.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "topic_description_by_topic",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (status = 200, description = "Ok.", body = inline(EventDescriptor)),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "Not Found. No event description has been registered."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/topics/{topic_id}/description")]
+pub async fn topic_event_description_by_topic(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let event_descriptor = app_state
+        .mb
+        .get_topic_event_descriptor(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(event_descriptor) = event_descriptor {
+        Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(event_descriptor.as_string()))
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
+
+/// List all registered versions of a topic's event description.
+///
+/// Versions are ordered from oldest to latest, so consecutive versions can be
+/// compared using the `descriptions/diff` resource.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "topic_description_history",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (status = 200, description = "Ok.", body = inline(DescriptorHistory)),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/topics/{topic_id}/descriptions")]
+pub async fn topic_event_description_history(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let descriptor_history = app_state
+        .mb
+        .get_topic_event_descriptor_history(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(descriptor_history.as_string()))
+}
+
+/// Query parameters of [topic_event_description_diff].
+#[derive(Debug, Deserialize)]
+pub struct DescriptionDiffQuery {
+    /// Older version in the form `major[.minor[.patch]]`.
+    from: String,
+    /// Newer version in the form `major[.minor[.patch]]`.
+    to: Option<String>,
+}
+
+/// Get the differences between two versions of a topic's event description.
+///
+/// The result holds changed settings, event schema locations and extractors,
+/// so consumers can assess the impact of an upcoming version before opting in
+/// to it.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "topic_description_diff",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("from" = String, Query, description = "Older version in the form 'major[.minor[.patch]]'."),
+        ("to" = Option<String>, Query, description = "Newer version in the form 'major[.minor[.patch]]'. Defaults to the latest version."),
+    ),
+    responses(
+        (status = 200, description = "Ok.", body = inline(DescriptorDiff)),
+        (status = 400, description = "Bad Request. Invalid version format."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "Not Found. One of the versions has not been registered."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/topics/{topic_id}/descriptions/diff")]
+pub async fn topic_event_description_diff(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<DescriptionDiffQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let from = parse_descriptor_version("from", &query.from)?;
+    let to = query
+        .to
+        .as_deref()
+        .map(|to| parse_descriptor_version("to", to))
+        .transpose()?;
+    let descriptor_diff = app_state
+        .mb
+        .get_topic_event_descriptor_diff(&identity, &topic_id, &from, to.as_ref())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(descriptor_diff) = descriptor_diff {
+        Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(descriptor_diff.as_string()))
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
+
+/// Query parameters of [topic_event_description_fixtures].
+#[derive(Debug, Deserialize)]
+pub struct DescriptionFixturesQuery {
+    /// Version in the form `major[.minor[.patch]]`.
+    version: Option<String>,
+}
+
+/// Get contract test fixtures generated from a topic's event description.
+///
+/// The result holds valid and invalid sample documents with the expected
+/// extraction results, so producer and consumer implementations in any
+/// language can be verified against the same canonical examples.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "topic_description_fixtures",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        ("version" = Option<String>, Query, description = "Version in the form 'major[.minor[.patch]]'. Defaults to the latest version."),
+    ),
+    responses(
+        (status = 200, description = "Ok.", body = inline(ContractFixtures)),
+        (status = 400, description = "Bad Request. Invalid version format."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "Not Found. The version has not been registered."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/topics/{topic_id}/descriptions/fixtures")]
+pub async fn topic_event_description_fixtures(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<DescriptionFixturesQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let version = query
+        .version
+        .as_deref()
+        .map(|version| parse_descriptor_version("version", version))
+        .transpose()?;
+    let contract_fixtures = app_state
+        .mb
+        .get_topic_contract_fixtures(&identity, &topic_id, version.as_ref())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(contract_fixtures) = contract_fixtures {
+        Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(contract_fixtures.as_string()))
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
+
+/// Parse a version in the form `major[.minor[.patch]]`.
+fn parse_descriptor_version(name: &str, value: &str) -> Result<DescriptorVersion, Error> {
+    let parts = value
+        .trim()
+        .split('.')
+        .map(str::parse::<u16>)
+        .collect::<Result<Vec<_>, _>>()
+        .ok()
+        .filter(|parts| parts.len() <= 3)
+        .ok_or_else(|| {
+            error::ErrorBadRequest(format!(
+                "Invalid format of '{name}' query parameter. Use 'major[.minor[.patch]]'."
+            ))
+        })?;
+    let part = |index: usize| parts.get(index).copied().unwrap_or(0);
+    Ok(DescriptorVersion::new(part(0), part(1), part(2)))
+}
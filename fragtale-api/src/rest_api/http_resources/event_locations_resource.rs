@@ -0,0 +1,69 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource for locating the topics that contain an event.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use fragtale_client::mb::event_locations::EventLocations;
+
+/// Locate the topics that contain an event with the event identifier.
+///
+/// Only topics that the authenticated client is allowed to read are
+/// included. An empty list of locations is returned when the event is not
+/// found in any readable topic.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "event_locations_by_event_id",
+    params(
+        ("event_id", description = "Event identifier."),
+    ),
+    responses(
+        (status = 200, description = "Ok.", body = inline(EventLocations)),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/events/{event_id}")]
+pub async fn event_locations_by_event_id(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let event_id = path.into_inner();
+    let event_locations = app_state
+        .mb
+        .get_event_locations(&identity, &event_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(event_locations.as_string()))
+}
@@ -0,0 +1,100 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource to peek at events pending delivery.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use crate::rest_api::common::NextQueryParams;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::peeked_events::PeekedEvents;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct PeekQuery {
+    /// Max number of events to return.
+    count: Option<usize>,
+    /// Event Descriptor SemVer that the client prefers (major.minor).
+    version: Option<String>,
+}
+
+/// Peek at the events next in line for delivery to the consumer.
+///
+/// The events are not reserved for delivery and the consumer's position is
+/// left as is, so the returned events will still be delivered as usual. Only
+/// events known to the instance serving the request are returned.
+///
+/// Consumer identifier is derived from authentication.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "peek_events_by_topic_and_consumer",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        (
+            "count" = Option<usize>,
+            Query,
+            description = "Max number of events to return (1-100, default 10)."
+        ),
+        (
+            "version" = Option<String>,
+            Query,
+            description = "Event Descriptor SemVer that the client prefers (major.minor)."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the events next in line for delivery in delivery order.",
+            body = inline(PeekedEvents),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/topics/{topic_id}/peek")]
+pub async fn peek_events_by_topic_and_consumer(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<PeekQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let descriptor_version = NextQueryParams::as_descriptor_version(&query.version)?;
+    let peeked_events = app_state
+        .mb
+        .peek_events_for_consumer(&identity, &topic_id, query.count, descriptor_version)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(peeked_events.as_string()))
+}
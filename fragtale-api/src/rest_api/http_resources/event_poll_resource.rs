@@ -20,10 +20,10 @@
 use crate::rest_api::AppState;
 use crate::rest_api::common::ApiErrorMapper;
 use crate::rest_api::common::NextQueryParams;
+use crate::rest_api::common::SharedDocumentBody;
 use actix_web::Error;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
-use actix_web::error;
 use actix_web::get;
 use actix_web::http::StatusCode;
 use actix_web::web::Data;
@@ -52,11 +52,71 @@ use fragtale_core::util::LogScopeDuration;
             Query,
             description = "Event Descriptor SemVer that the client prefers (major.minor)."
         ),
+        (
+            "type" = Option<String>,
+            Query,
+            description = "Comma separated kinds of events of interest in a multi-type topic. Events of other kinds are skipped."
+        ),
+        (
+            "min_priority" = Option<u8>,
+            Query,
+            description = "Lowest priority of events of interest. Events published with a lower priority are skipped."
+        ),
+        (
+            "fields" = Option<String>,
+            Query,
+            description = "Comma separated JSON Pointers (e.g. '/id,/customer/name'). Only these fields of each event document are delivered."
+        ),
+        (
+            "filter" = Option<String>,
+            Query,
+            description = "Filter expression over the extracted values of each event (e.g. 'region == \"eu\" && amount > 100' or the shorthand 'doc_status=eq:open,amount=gt:100'). Events that do not match are skipped."
+        ),
+        (
+            "visibility_timeout" = Option<u64>,
+            Query,
+            description = "Milliseconds to wait for confirmation of a delivered event before it is considered for redelivery. Clamped to the bounds set by the administrator."
+        ),
+        (
+            "sticky" = Option<String>,
+            Query,
+            description = "Routing key that this member of the consumer group prefers (e.g. a region or shard hint). Events with another routing key are left to the members that prefer them for a short while."
+        ),
     ),
     responses(
         (
             status = 200,
             description = "A new event is delivered in the response body.",
+            headers(
+                (
+                    "correlation-token" = String,
+                    description = "Opaque token that can be used to correlate events."
+                ),
+                (
+                    "priority" = Option<u8>,
+                    description = "The priority the event was published with, when known."
+                ),
+                (
+                    "topic-id" = String,
+                    description = "Topic identifier of the event."
+                ),
+                (
+                    "event-id" = String,
+                    description = "Event identifier."
+                ),
+                (
+                    "descriptor-version" = Option<String>,
+                    description = "Event Descriptor SemVer (major.minor.patch) the event was published with, when known."
+                ),
+                (
+                    "schema-id" = Option<String>,
+                    description = "Identifier of the event schema the event was published with, when known."
+                ),
+                (
+                    "prepared-transaction-id" = Option<String>,
+                    description = "The consumer's prepared, but not committed, transaction identifier when this is a redelivery."
+                ),
+            ),
             links(
                 (
                     "Location" = (
@@ -100,12 +160,35 @@ pub async fn next_event_by_topic_and_consumer(
     let baseline_micros = next_query_params.get_from_epoch_micros();
     // Respect consumers version support to avoid (too new) incompatibel messages
     let descriptor_version = next_query_params.get_descriptor_version()?;
+    let event_types = next_query_params.get_event_types();
+    let min_priority = next_query_params.get_min_priority();
+    let fields = next_query_params.get_fields();
     let event_opt = app_state
         .mb
-        .get_event_by_consumer_and_topic(&identity, &topic_id, baseline_micros, descriptor_version)
+        .get_event_by_consumer_and_topic(
+            &identity,
+            &topic_id,
+            baseline_micros,
+            descriptor_version,
+            event_types.as_deref(),
+            min_priority,
+            fields.as_deref(),
+            next_query_params.get_filter(),
+            next_query_params.get_visibility_timeout_millis(),
+            next_query_params.get_sticky_key(),
+        )
         .await
-        .map_err(|e| error::ErrorInternalServerError(e.to_string()))?;
-    if let Some((unique_time, event_document, correlation_token, instance_id)) = event_opt {
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some((
+        unique_time,
+        event_document,
+        correlation_token,
+        instance_id,
+        prepared_transaction_id,
+        priority,
+        delivery_envelope,
+    )) = event_opt
+    {
         let confirmation_url = http_request
             .url_for(
                 "confirm_event_delivery",
@@ -114,13 +197,28 @@ pub async fn next_event_by_topic_and_consumer(
             .unwrap();
         // TODO: Work-around apparent bug where the 2nd and 3rd path args are dropped.
         let confirmation_url = format!("{confirmation_url}/{unique_time}/{instance_id}");
-        Ok(HttpResponse::build(StatusCode::OK)
+        let mut builder = HttpResponse::build(StatusCode::OK);
+        builder
             .append_header((
                 "Link",
                 format!(r#"<{confirmation_url}>;rel="confirm-delivery""#),
             ))
             .append_header(("correlation-token", correlation_token))
-            .body(event_document))
+            .append_header(("topic-id", delivery_envelope.get_topic_id()))
+            .append_header(("event-id", delivery_envelope.get_event_id()));
+        if let Some(descriptor_version) = delivery_envelope.get_descriptor_version() {
+            builder.append_header(("descriptor-version", descriptor_version));
+        }
+        if let Some(schema_id) = delivery_envelope.get_schema_id() {
+            builder.append_header(("schema-id", schema_id));
+        }
+        if let Some(priority) = priority {
+            builder.append_header(("priority", priority.to_string()));
+        }
+        if let Some(prepared_transaction_id) = prepared_transaction_id {
+            builder.append_header(("prepared-transaction-id", prepared_transaction_id));
+        }
+        Ok(builder.body(SharedDocumentBody::from_document(event_document)))
     } else {
         Ok(HttpResponse::build(StatusCode::NO_CONTENT).finish())
     }
@@ -0,0 +1,122 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource to replay events published in a time range.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Query;
+use fragtale_client::mb::replayed_events::ReplayedEvents;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct RangeQuery {
+    /// Start of the time range (inclusive) in epoch microseconds.
+    from: u64,
+    /// End of the time range (exclusive) in epoch microseconds.
+    to: u64,
+    /// Encoded UniqueTime to continue after, as returned by the previous page.
+    after: Option<u64>,
+    /// Max number of events to return.
+    count: Option<usize>,
+}
+
+/// Replay the events published to the topic in a time range.
+///
+/// Events are returned in order of publication and each document is
+/// integrity validated before it is returned. Pass the returned `next` as
+/// `after` to get the next page. The consumer's position is left as is.
+///
+/// Events that are no longer present in the database are read from the
+/// archive tier (if enabled), which is much slower, and have `storage_tier`
+/// set to `archive`.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "get_events_by_time_range",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        (
+            "from" = u64,
+            Query,
+            description = "Start of the time range (inclusive) in epoch microseconds."
+        ),
+        (
+            "to" = u64,
+            Query,
+            description = "End of the time range (exclusive) in epoch microseconds."
+        ),
+        (
+            "after" = Option<u64>,
+            Query,
+            description = "Encoded UniqueTime to continue after, as returned in `next` of the previous page."
+        ),
+        (
+            "count" = Option<usize>,
+            Query,
+            description = "Max number of events to return (1-1000, default 100)."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the events published in the time range in order of publication.",
+            body = inline(ReplayedEvents),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/topics/{topic_id}/events/range")]
+pub async fn get_events_by_time_range(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<RangeQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let replayed_events = app_state
+        .mb
+        .get_events_by_time_range(
+            &identity,
+            &topic_id,
+            query.from,
+            query.to,
+            query.after,
+            query.count,
+        )
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(replayed_events.as_string()))
+}
@@ -0,0 +1,174 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for publishing with pre-signed grants.
+
+use super::publish_resource::MAX_DOCUMENT_SIZE;
+use super::publish_resource::assert_declared_content_length;
+use super::publish_resource::read_full_body_text;
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::post;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Path;
+use actix_web::web::Payload;
+use actix_web::web::Query;
+use fragtale_client::mb::publish_grant::IssuedPublishGrant;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct PublishGrantCreateQuery {
+    /// Time to live of the grant in seconds.
+    ttl: Option<u64>,
+    /// Max number of events that may be published with the grant.
+    events: Option<u32>,
+    /// Max size in bytes of each published event document.
+    bytes: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublishGrantQuery {
+    /// The grant in serialized form.
+    grant: String,
+}
+
+/// Issue a pre-signed grant to publish to the topic.
+///
+/// The returned relative URL can be handed to a third party, like an external
+/// partner, that doesn't hold any cluster credentials. The holder of the URL
+/// can publish a limited number of events of limited size to the topic
+/// before the grant expires. Events are published on behalf of the issuer of
+/// the grant.
+///
+/// Requires authorization to publish to the topic.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "create_publish_grant",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        (
+            "ttl" = Option<u64>,
+            Query,
+            description = "Time to live of the grant in seconds. Defaults to 900 and may not exceed 86400."
+        ),
+        (
+            "events" = Option<u32>,
+            Query,
+            description = "Max number of events that may be published with the grant. Defaults to 1 and may not exceed 1000."
+        ),
+        (
+            "bytes" = Option<u64>,
+            Query,
+            description = "Max size in bytes of each published event document."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Return the issued grant and the URL to publish to with it.",
+            body = inline(IssuedPublishGrant),
+            content_type = "application/json",
+        ),
+        (status = 400, description = "Bad Request. The limits of the grant are out of bounds."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[post("/topics/{topic_id}/grants")]
+pub async fn create_publish_grant(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<PublishGrantCreateQuery>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let issued_publish_grant = app_state
+        .mb
+        .create_publish_grant(&identity, &topic_id, query.ttl, query.events, query.bytes)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(issued_publish_grant.as_string()))
+}
+
+/// Publish event document using a pre-signed grant.
+///
+/// No other authentication is required, but the issuer of the grant must
+/// still be authorized to publish to the topic. Each request consumes one of
+/// the uses of the grant, even if the event is rejected.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "publish_event_with_grant",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        (
+            "grant" = String,
+            Query,
+            description = "Pre-signed grant issued for the topic."
+        ),
+    ),
+    responses(
+        (
+            status = 204,
+            description = "No content. Successfully published event.",
+            headers(
+                (
+                    "correlation-token" = String,
+                    description = "Opaque token that can be used to correlate events."
+                ),
+            ),
+        ),
+        (status = 400, description = "Bad Request. E.g. the document exceeds the size allowed by the grant."),
+        (status = 401, description = "Unauthorized: The grant is invalid or has expired."),
+        (status = 403, description = "Forbidden: All uses of the grant have been consumed or the issuer is no longer authorized."),
+        (status = 429, description = "Too Many Requests. The issuer's tenant exceeded its quota or low priority events are shed due to a critical backlog."),
+        (status = 500, description = "Internal server error."),
+    ),
+)]
+#[put("/topics/{topic_id}/events/granted")]
+pub async fn publish_event_with_grant(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<PublishGrantQuery>,
+    payload: Payload,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let topic_id = path.into_inner();
+    let content_length_estimate = assert_declared_content_length(&http_request, MAX_DOCUMENT_SIZE)?;
+    let event_document = read_full_body_text(&topic_id, content_length_estimate, payload).await?;
+    let correlation_token = app_state
+        .mb
+        .publish_event_with_grant(&query.grant, &topic_id, &event_document)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::NO_CONTENT)
+        .append_header(("correlation-token", correlation_token))
+        .finish())
+}
@@ -20,11 +20,13 @@
 use crate::rest_api::AppState;
 use crate::rest_api::common::ApiErrorMapper;
 use crate::rest_api::common::NextQueryParams;
+use crate::rest_api::common::SharedDocumentBody;
 use actix_web::Error;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
 use actix_web::error;
 use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
 use actix_web::put;
 use actix_web::web;
 use actix_web::web::Data;
@@ -32,14 +34,25 @@ use actix_web::web::Path;
 use actix_web::web::Payload;
 use actix_web::web::Query;
 use fragtale_client::mb::event_descriptor::DescriptorVersion;
+use fragtale_client::mb::publish_acknowledgement::PublishAcknowledgement;
+use fragtale_client::mb::published_events::PublishedEvents;
 use fragtale_core::util::LogScopeDuration;
 use futures::StreamExt;
 use serde::Deserialize;
+use serde_json::value::RawValue;
 
 #[derive(Debug, Deserialize)]
 pub struct PublishQuery {
     /// Event priority
     priority: Option<u8>,
+    /// Deadline in epoch microseconds after which the event should no longer
+    /// be delivered.
+    #[serde(rename = "expires")]
+    expires_ts: Option<u64>,
+    /// Time in epoch microseconds before which the event should not be
+    /// delivered.
+    #[serde(rename = "deliver_after")]
+    deliver_after_micros: Option<u64>,
     /// Event Descriptor SemVer of the event.
     #[serde(rename = "version")]
     event_descriptor_semver: Option<String>,
@@ -47,6 +60,13 @@ pub struct PublishQuery {
     /// result of correlated request.
     #[serde(rename = "target")]
     result_topic_id: Option<String>,
+    /// Await the correlated result in the reply topic registered by the
+    /// responding service when no `target` is specified.
+    #[serde(rename = "reply")]
+    await_reply: Option<bool>,
+    /// How far the publishing must have progressed before the response.
+    #[serde(rename = "ack")]
+    acknowledgement: Option<String>,
 }
 
 impl PublishQuery {
@@ -58,16 +78,35 @@ impl PublishQuery {
     pub fn get_descriptor_version(&self) -> Result<Option<DescriptorVersion>, Error> {
         NextQueryParams::as_descriptor_version(&self.event_descriptor_semver)
     }
+
+    /// Return the requested acknowledgement level or `persisted` by default.
+    ///
+    /// Errors out with HTTP 400 Bad Request if the level is unknown.
+    pub fn get_acknowledgement(&self) -> Result<PublishAcknowledgement, Error> {
+        self.acknowledgement
+            .as_deref()
+            .map_or(Ok(PublishAcknowledgement::default()), |name| {
+                PublishAcknowledgement::from_name(name).ok_or_else(|| {
+                    error::ErrorBadRequest(format!(
+                        "Unknown 'ack' level '{name}'. Use 'received', 'persisted' or 'indexed'."
+                    ))
+                })
+            })
+    }
 }
 
 /// Cassandra practical max column size is 5 MiB.
-const MAX_DOCUMENT_SIZE: usize = 5 * 1024 * 1024;
+pub const MAX_DOCUMENT_SIZE: usize = 5 * 1024 * 1024;
 
 /// Publish event document.
 ///
 /// Please note the `correlation-token` and `location` header if you need to
 /// find an event from a different topic that is the result of processing this event.
 ///
+/// When enabled, the `backlog-state` header signals if the primary consumer of
+/// the topic keeps up, so producers can shed load before the broker is
+/// overwhelmed.
+///
 /// Publisher identifier is derived from authentication.
 #[utoipa::path(
     tag = "http",
@@ -77,7 +116,17 @@ const MAX_DOCUMENT_SIZE: usize = 5 * 1024 * 1024;
         (
             "priority" = Option<u8>,
             Query,
-            description = "Importance of the published event. 0-100 where 100 is most important."
+            description = "Importance of the published event. 0-100 where 100 is most important. Correlated replies inherit the priority of the request when omitted. Lower priority events are delivered up to the configured priority aging duration (450 ms by default) after events of priority 100 published at the same time."
+        ),
+        (
+            "expires" = Option<u64>,
+            Query,
+            description = "Deadline in epoch microseconds. The event is marked as expired instead of being delivered after this."
+        ),
+        (
+            "deliver_after" = Option<u64>,
+            Query,
+            description = "Time in epoch microseconds before which the event is not delivered. (E.g. for reminders.)"
         ),
         (
             "version" = Option<String>,
@@ -89,6 +138,16 @@ const MAX_DOCUMENT_SIZE: usize = 5 * 1024 * 1024;
             Query,
             description = "Expected target topic of correlated event processing."
         ),
+        (
+            "reply" = Option<bool>,
+            Query,
+            description = "Await the result of correlated event processing in the reply topic registered by the responding service. Ignored when 'target' is specified."
+        ),
+        (
+            "ack" = Option<String>,
+            Query,
+            description = "When the publishing is acknowledged: 'received' (validated and queued), 'persisted' (default, the database acknowledged the write) or 'indexed' (visible to consumers)."
+        ),
     ),
     responses(
         (
@@ -104,6 +163,10 @@ const MAX_DOCUMENT_SIZE: usize = 5 * 1024 * 1024;
                     "correlation-token" = String,
                     description = "Opaque token that can be used to correlate events."
                 ),
+                (
+                    "backlog-state" = String,
+                    description = "Backlog of the topic's primary consumer: 'ok', 'warn' or 'critical'. Only present when enabled."
+                ),
             ),
         ),
         (
@@ -121,10 +184,12 @@ const MAX_DOCUMENT_SIZE: usize = 5 * 1024 * 1024;
                 ),
             ),
         ),
-        (status = 400, description = "Bad Request."),
+        (status = 400, description = "Bad Request. E.g. no reply topic has been registered for the topic."),
         (status = 401, description = "Unauthorized: Authentication failure."),
         (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 429, description = "Too Many Requests. The tenant exceeded its quota or low priority events are shed due to a critical backlog."),
         (status = 500, description = "Internal server error."),
+        (status = 504, description = "Gateway Timeout. The event was persisted, but did not become visible to consumers in time for an 'indexed' acknowledgement."),
     ),
     security(("bearer_auth" = [])),
 )]
@@ -146,11 +211,29 @@ pub async fn publish_event_to_topic(
     let publish_query = query.into_inner();
     let priority = publish_query.priority;
     let descriptor_version = publish_query.get_descriptor_version()?;
+    let acknowledgement = publish_query.get_acknowledgement()?;
     let http_headers = http_request.headers();
     let identity = app_state
         .auth
         .get_identity(&http_request)
         .map_err(ApiErrorMapper::from_message_broker_error)?;
+    // Resolve the reply topic before publishing to fail fast
+    let result_topic_id = match publish_query.result_topic_id {
+        Some(result_topic_id) => Some(result_topic_id),
+        None if publish_query.await_reply.unwrap_or(false) => Some(
+            app_state
+                .mb
+                .get_reply_topic(&identity, &topic_id)
+                .await
+                .map_err(ApiErrorMapper::from_message_broker_error)?
+                .ok_or_else(|| {
+                    error::ErrorBadRequest(format!(
+                        "No reply topic has been registered for topic '{topic_id}'."
+                    ))
+                })?,
+        ),
+        None => None,
+    };
     let content_length_estimate = assert_declared_content_length(&http_request, MAX_DOCUMENT_SIZE)?;
     let event_document = read_full_body_text(&topic_id, content_length_estimate, payload).await?;
     let correlation_token_opt = http_headers
@@ -165,12 +248,16 @@ pub async fn publish_event_to_topic(
             &topic_id,
             &event_document,
             priority,
+            publish_query.expires_ts,
+            publish_query.deliver_after_micros,
             descriptor_version,
             correlation_token_opt,
+            acknowledgement,
         )
         .await
         .map_err(ApiErrorMapper::from_message_broker_error)?;
-    if let Some(result_topic_id) = publish_query.result_topic_id {
+    let backlog_state_opt = app_state.mb.get_backlog_state(&topic_id);
+    if let Some(result_topic_id) = result_topic_id {
         if let Some(result_document) = app_state
             .mb
             .get_event_by_correlation_token(
@@ -181,7 +268,11 @@ pub async fn publish_event_to_topic(
             .await
             .map_err(ApiErrorMapper::from_message_broker_error)?
         {
-            Ok(HttpResponse::build(StatusCode::OK).body(result_document))
+            let mut builder = HttpResponse::build(StatusCode::OK);
+            if let Some(backlog_state) = backlog_state_opt {
+                builder.append_header(("backlog-state", backlog_state));
+            }
+            Ok(builder.body(SharedDocumentBody::from_document(result_document)))
         } else {
             let result_poll_url = http_request
                 .url_for(
@@ -190,9 +281,12 @@ pub async fn publish_event_to_topic(
                 )
                 .unwrap();
             // Revert to Post-Redirect-Get pattern if this takes to long
-            Ok(HttpResponse::build(StatusCode::SEE_OTHER)
-                .append_header(("Location", result_poll_url.as_str()))
-                .finish())
+            let mut builder = HttpResponse::build(StatusCode::SEE_OTHER);
+            builder.append_header(("Location", result_poll_url.as_str()));
+            if let Some(backlog_state) = backlog_state_opt {
+                builder.append_header(("backlog-state", backlog_state));
+            }
+            Ok(builder.finish())
         }
     } else {
         let mut builder = HttpResponse::build(StatusCode::NO_CONTENT);
@@ -200,13 +294,138 @@ pub async fn publish_event_to_topic(
             // Return the correlation (only really matters first time when it was generated)
             builder.append_header(("correlation-token", persisted_correlation_token));
         }
+        if let Some(backlog_state) = backlog_state_opt {
+            builder.append_header(("backlog-state", backlog_state));
+        }
         Ok(builder.finish())
     }
 }
 
+/// Publish a batch of event documents.
+///
+/// The request body is a JSON array of event documents and is limited to the
+/// same size as a single document. Nothing is published unless all documents
+/// are valid, but a failure to persist the batch may leave a leading part of
+/// it published. The events are ordered as in the request and get new
+/// correlation tokens.
+///
+/// Publisher identifier is derived from authentication.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "publish_events_to_topic",
+    params(
+        ("topic_id", description = "Topic identifier."),
+        (
+            "priority" = Option<u8>,
+            Query,
+            description = "Importance of the published events. 0-100 where 100 is most important."
+        ),
+        (
+            "expires" = Option<u64>,
+            Query,
+            description = "Deadline in epoch microseconds. The events are marked as expired instead of being delivered after this."
+        ),
+        (
+            "deliver_after" = Option<u64>,
+            Query,
+            description = "Time in epoch microseconds before which the events are not delivered."
+        ),
+        (
+            "version" = Option<String>,
+            Query,
+            description = "Event Descriptor SemVer the events are expected to comply to. (E.g. major.minor)."
+        ),
+        (
+            "ack" = Option<String>,
+            Query,
+            description = "When the publishing is acknowledged: 'received' (validated and queued), 'persisted' (default, the database acknowledged the write) or 'indexed' (visible to consumers)."
+        ),
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Ok. Successfully published all events.",
+            body = inline(PublishedEvents),
+            content_type = "application/json",
+            headers(
+                (
+                    "backlog-state" = String,
+                    description = "Backlog of the topic's primary consumer: 'ok', 'warn' or 'critical'. Only present when enabled."
+                ),
+            ),
+        ),
+        (status = 400, description = "Bad Request. E.g. a document was invalid or the batch too large."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 429, description = "Too Many Requests. The tenant exceeded its quota or low priority events are shed due to a critical backlog."),
+        (status = 500, description = "Internal server error."),
+        (status = 504, description = "Gateway Timeout. The events were persisted, but did not become visible to consumers in time for an 'indexed' acknowledgement."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/topics/{topic_id}/events/batch")]
+pub async fn publish_events_to_topic(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    query: Query<PublishQuery>,
+    payload: Payload,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let _ = LogScopeDuration::new(
+        log::Level::Trace,
+        module_path!(),
+        "publish_events_to_topic",
+        0,
+    );
+    let topic_id = path.into_inner();
+    let publish_query = query.into_inner();
+    if publish_query.result_topic_id.is_some() || publish_query.await_reply.is_some() {
+        Err(error::ErrorBadRequest(
+            "Awaiting correlated results is not supported for batches.",
+        ))?;
+    }
+    let descriptor_version = publish_query.get_descriptor_version()?;
+    let acknowledgement = publish_query.get_acknowledgement()?;
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let content_length_estimate = assert_declared_content_length(&http_request, MAX_DOCUMENT_SIZE)?;
+    let body = read_full_body_text(&topic_id, content_length_estimate, payload).await?;
+    let event_documents = serde_json::from_str::<Vec<Box<RawValue>>>(&body)
+        .map_err(|e| {
+            log::info!("Failed to parse batch of documents for topic {topic_id}: {e:?}");
+            error::ErrorBadRequest("invalid_batch")
+        })?
+        .into_iter()
+        .map(|event_document| event_document.get().to_owned())
+        .collect::<Vec<_>>();
+    let correlation_tokens = app_state
+        .mb
+        .publish_events_to_topic(
+            &identity,
+            &topic_id,
+            &event_documents,
+            publish_query.priority,
+            publish_query.expires_ts,
+            publish_query.deliver_after_micros,
+            descriptor_version,
+            acknowledgement,
+        )
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let mut builder = HttpResponse::build(StatusCode::OK);
+    if let Some(backlog_state) = app_state.mb.get_backlog_state(&topic_id) {
+        builder.append_header(("backlog-state", backlog_state));
+    }
+    Ok(builder
+        .content_type(ContentType::json())
+        .body(PublishedEvents::new(correlation_tokens).as_string()))
+}
+
 /// Assert that the declared content-length header (if present) is within the
 /// max_size limit.
-fn assert_declared_content_length(
+pub(crate) fn assert_declared_content_length(
     http_request: &HttpRequest,
     max_size: usize,
 ) -> Result<usize, Error> {
@@ -223,7 +442,7 @@ fn assert_declared_content_length(
     }
 }
 
-async fn read_full_body_text(
+pub(crate) async fn read_full_body_text(
     topic_id: &str,
     content_length_estimate: usize,
     mut payload: Payload,
@@ -0,0 +1,119 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resource for registration of a topic's reply topic.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Json;
+use actix_web::web::Path;
+use fragtale_client::mb::reply_topic::ReplyTopic;
+
+/// Register the topic where correlated results of events in this topic are
+/// published.
+///
+/// This is used by the responding service, so that publishers can await the
+/// correlated result of a request without knowing the reply topic.
+///
+/// The responding service must be allowed to read from this topic and write
+/// to the reply topic. Replacing a registration made by another identity
+/// requires ownership of this topic or administration of topics.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "reply_topic_register",
+    params(
+        ("topic_id", description = "Topic identifier of requests."),
+    ),
+    request_body = inline(ReplyTopic),
+    responses(
+        (status = 204, description = "Successfully registered the reply topic."),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/topics/{topic_id}/reply")]
+pub async fn reply_topic_register(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    reply_topic: Json<ReplyTopic>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    app_state
+        .mb
+        .register_reply_topic(&identity, &topic_id, reply_topic.get_reply_topic_id())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)
+        .map(|_| HttpResponse::build(StatusCode::NO_CONTENT).finish())
+}
+
+/// Get the topic where correlated results of events in this topic are
+/// published.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "reply_topic_by_topic",
+    params(
+        ("topic_id", description = "Topic identifier of requests."),
+    ),
+    responses(
+        (status = 200, description = "Ok.", body = inline(ReplyTopic)),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 404, description = "Not Found. No reply topic has been registered."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/topics/{topic_id}/reply")]
+pub async fn reply_topic_by_topic(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let reply_topic_id = app_state
+        .mb
+        .get_reply_topic(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    if let Some(reply_topic_id) = reply_topic_id {
+        Ok(HttpResponse::build(StatusCode::OK)
+            .content_type(ContentType::json())
+            .body(ReplyTopic::new(&reply_topic_id).as_string()))
+    } else {
+        Ok(HttpResponse::build(StatusCode::NOT_FOUND).finish())
+    }
+}
@@ -0,0 +1,156 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! API resources for topic ownership and access grants.
+
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header::ContentType;
+use actix_web::put;
+use actix_web::web::Data;
+use actix_web::web::Json;
+use actix_web::web::Path;
+use fragtale_client::mb::topic_access::TopicAccessGrant;
+use fragtale_client::mb::topic_access::TopicOwnership;
+use fragtale_client::mb::topic_access::TopicOwnershipTransfer;
+
+/// Get the principals that own the topic.
+///
+/// The owner is recorded when the topic is first claimed and is either an
+/// identity string or a group string (`group;` + group name).
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "topic_ownership_by_topic",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    responses(
+        (status = 200, description = "Ok.", body = inline(TopicOwnership)),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/topics/{topic_id}/owner")]
+pub async fn topic_ownership_by_topic(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    let topic_ownership = app_state
+        .mb
+        .get_topic_ownership(&identity, &topic_id)
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    Ok(HttpResponse::build(StatusCode::OK)
+        .content_type(ContentType::json())
+        .body(topic_ownership.as_string()))
+}
+
+/// Transfer the ownership of the topic to a new principal.
+///
+/// Only owners of the topic and identities allowed to administer topics may
+/// transfer ownership. The transfer is published as an audit event to the
+/// `fragtale_audit` topic.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "topic_ownership_transfer",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    request_body = inline(TopicOwnershipTransfer),
+    responses(
+        (status = 204, description = "Successfully transferred the ownership."),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/topics/{topic_id}/owner")]
+pub async fn topic_ownership_transfer(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    topic_ownership_transfer: Json<TopicOwnershipTransfer>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    app_state
+        .mb
+        .transfer_topic_ownership(&identity, &topic_id, topic_ownership_transfer.into_inner())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)
+        .map(|_| HttpResponse::build(StatusCode::NO_CONTENT).finish())
+}
+
+/// Grant or revoke access to write to, annotate or receive unmasked events in
+/// the topic.
+///
+/// Only owners of the topic and identities allowed to administer topics may
+/// edit access. The change is published as an audit event to the
+/// `fragtale_audit` topic.
+#[utoipa::path(
+    tag = "http",
+    //operation_id = "topic_access_update",
+    params(
+        ("topic_id", description = "Topic identifier."),
+    ),
+    request_body = inline(TopicAccessGrant),
+    responses(
+        (status = 204, description = "Successfully updated access."),
+        (status = 400, description = "Bad Request."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 500, description = "Internal server error."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[put("/topics/{topic_id}/access")]
+pub async fn topic_access_update(
+    app_state: Data<AppState>,
+    path: Path<String>,
+    topic_access_grant: Json<TopicAccessGrant>,
+    http_request: HttpRequest,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let topic_id = path.into_inner();
+    app_state
+        .mb
+        .update_topic_access(&identity, &topic_id, topic_access_grant.into_inner())
+        .await
+        .map_err(ApiErrorMapper::from_message_broker_error)
+        .map(|_| HttpResponse::build(StatusCode::NO_CONTENT).finish())
+}
@@ -0,0 +1,63 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Static assets of the embedded operator web UI.
+//!
+//! The assets are compiled into the binary, so no files need to be deployed
+//! with the broker. The UI requests all data from the REST API using the
+//! operator's own bearer token, so no data is exposed by these resources.
+
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::http::StatusCode;
+use actix_web::http::header;
+use actix_web::http::header::ContentType;
+use actix_web::mime;
+use actix_web::web::Path;
+
+const INDEX_HTML: &str = include_str!("../../../ui/index.html");
+const FRAGTALE_UI_JS: &str = include_str!("../../../ui/fragtale-ui.js");
+const FRAGTALE_UI_CSS: &str = include_str!("../../../ui/fragtale-ui.css");
+
+/// Only allow scripts, styles and requests from the broker itself.
+const CONTENT_SECURITY_POLICY: &str =
+    "default-src 'self'; object-src 'none'; base-uri 'none'; frame-ancestors 'none'";
+
+/// Redirect to the start page of the web UI.
+#[get("/ui")]
+pub async fn web_ui() -> HttpResponse {
+    HttpResponse::build(StatusCode::TEMPORARY_REDIRECT)
+        .insert_header((header::LOCATION, "/ui/"))
+        .finish()
+}
+
+/// Serve an asset of the web UI.
+#[get("/ui/{asset:[^/]*}")]
+pub async fn web_ui_asset(path: Path<String>) -> HttpResponse {
+    let (content_type, body) = match path.into_inner().as_str() {
+        "" | "index.html" => (ContentType::html(), INDEX_HTML),
+        "fragtale-ui.js" => (ContentType(mime::TEXT_JAVASCRIPT), FRAGTALE_UI_JS),
+        "fragtale-ui.css" => (ContentType(mime::TEXT_CSS_UTF_8), FRAGTALE_UI_CSS),
+        _ => return HttpResponse::build(StatusCode::NOT_FOUND).finish(),
+    };
+    HttpResponse::build(StatusCode::OK)
+        .content_type(content_type)
+        .insert_header((header::CONTENT_SECURITY_POLICY, CONTENT_SECURITY_POLICY))
+        .insert_header((header::X_CONTENT_TYPE_OPTIONS, "nosniff"))
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .body(body)
+}
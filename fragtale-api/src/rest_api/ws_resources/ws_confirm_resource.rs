@@ -19,20 +19,25 @@
 
 use crate::rest_api::AppState;
 use crate::rest_api::common::ApiErrorMapper;
+use crate::rest_api::common::MemberQueryParams;
+use crate::rest_api::common::WebSocketSessionRegistry;
 use actix_web::Error;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
+use actix_web::error::ErrorTooManyRequests;
 use actix_web::get;
 use actix_web::rt;
 use actix_web::web::Data;
 use actix_web::web::Path;
 use actix_web::web::Payload;
+use actix_web::web::Query;
 use actix_ws::AggregatedMessage;
 use actix_ws::AggregatedMessageStream;
 use actix_ws::Session;
 use fragtale_client::SubscriberCommand;
+use fragtale_client::WireFormat;
+use fragtale_core::mb::MessageBroker;
 use fragtale_core::mb::auth::ClientIdentity;
-use futures::StreamExt;
 
 /// Open a WebSocket connection for sending event delivery confirmation messages.
 ///
@@ -41,10 +46,13 @@ use futures::StreamExt;
     tag = "web_socket",
     params(
         ("topic_id", description = "Topic identifier."),
+        ("member" = Option<String>, Query, description = "Identifier of the client instance within its consumer group (1-64 characters of 'A-Za-z0-9-_.')."),
     ),
     responses(
         (status = 101, description = "Switching protocols to websocket."),
+        (status = 400, description = "Bad Request."),
         (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 429, description = "Too Many Requests: The identity has too many open sessions."),
     ),
     security(("bearer_auth" = [])),
 )]
@@ -53,6 +61,7 @@ pub async fn confirm_event_delivery(
     http_request: HttpRequest,
     app_state: Data<AppState>,
     path: Path<String>,
+    member_query: Query<MemberQueryParams>,
     stream: Payload,
 ) -> Result<HttpResponse, Error> {
     let identity = app_state
@@ -61,15 +70,41 @@ pub async fn confirm_event_delivery(
         .map_err(ApiErrorMapper::from_message_broker_error)?;
     let consumer_id = identity.identity_string();
     let topic_id = path.into_inner();
+    let member_id = member_query.get_member_id().map(str::to_owned);
+    if let Some(member_id) = &member_id {
+        MessageBroker::assert_well_formed_member_id(member_id)
+            .map_err(ApiErrorMapper::from_message_broker_error)?;
+    }
+    let session_id = app_state
+        .ws_sessions
+        .open(
+            WebSocketSessionRegistry::KIND_CONFIRM,
+            Some(&topic_id),
+            consumer_id,
+        )
+        .ok_or_else(|| ErrorTooManyRequests("Too many open sessions."))?;
     log::info!("Consumer '{consumer_id}' opened a confirm connection for topic '{topic_id}'.");
-    let (http_upgrade_response, session, stream) = actix_ws::handle(&http_request, stream)?;
+    let (http_upgrade_response, session, stream) = actix_ws::handle(&http_request, stream)
+        .inspect_err(|_| {
+            app_state.ws_sessions.close(session_id);
+        })?;
     let stream = stream
         .aggregate_continuations()
         // aggregate continuation frames up to 1 MiB
         .max_continuation_size(2_usize.pow(20));
     // Pull messages from this steam
     rt::spawn(async move {
-        pull_messages_from_stream(&identity, app_state, session, stream, topic_id).await;
+        pull_messages_from_stream(
+            &identity,
+            app_state.clone(),
+            session,
+            session_id,
+            stream,
+            topic_id,
+            member_id,
+        )
+        .await;
+        app_state.ws_sessions.close(session_id);
     });
     // Respond immediately with with WebSocket upgrade response
     Ok(http_upgrade_response)
@@ -80,54 +115,32 @@ async fn pull_messages_from_stream(
     identity: &ClientIdentity,
     app_state: Data<AppState>,
     mut session: Session,
+    session_id: u64,
     mut stream: AggregatedMessageStream,
     topic_id: String,
+    member_id: Option<String>,
 ) {
     if log::log_enabled!(log::Level::Debug) {
         log::debug!("Starting to process confirmation messages");
     }
-    while let Some(msg) = stream.next().await {
-        match msg {
+    while let Some(msg) = app_state
+        .ws_sessions
+        .next_message(session_id, &mut stream)
+        .await
+    {
+        // JSON is sent in text frames and MessagePack in binary frames
+        let command: Result<SubscriberCommand, String> = match msg {
             Ok(AggregatedMessage::Text(text)) => {
-                // Parse text as "command" and match
                 if log::log_enabled!(log::Level::Trace) {
                     log::trace!("Got msg: {text}");
                 }
-                match serde_json::from_str(&text) {
-                    Ok(SubscriberCommand::AckDelivery {
-                        encoded_unique_time,
-                        delivery_instance_id,
-                    }) => {
-                        let app_state = app_state.clone();
-                        let identity = identity.to_owned();
-                        let topic_id = topic_id.to_owned();
-                        rt::spawn(async move {
-                            app_state
-                                .mb
-                                .confirm_event_delivery(
-                                    &identity,
-                                    &topic_id,
-                                    encoded_unique_time,
-                                    delivery_instance_id,
-                                )
-                                .await
-                                .map_err(|e| log::info!("Failed to confirm delivery: {e}"))
-                                .ok();
-                        });
-                    }
-                    _ => {
-                        if log::log_enabled!(log::Level::Debug) {
-                            log::debug!("Ignoring text message: {text}");
-                        }
-                    }
-                }
+                WireFormat::Json.decode(text.as_bytes())
             }
-            Ok(AggregatedMessage::Binary(_bin)) => {
-                // echo binary message
-                if log::log_enabled!(log::Level::Debug) {
-                    log::debug!("Ignoring binary message");
+            Ok(AggregatedMessage::Binary(bin)) => {
+                if log::log_enabled!(log::Level::Trace) {
+                    log::trace!("Got binary message of {} bytes.", bin.len());
                 }
-                //session.binary(bin).await.unwrap();
+                WireFormat::MessagePack.decode(&bin)
             }
             Ok(AggregatedMessage::Ping(msg)) => {
                 // respond to PING frame with PONG frame
@@ -135,20 +148,147 @@ async fn pull_messages_from_stream(
                     log::trace!("Sending pong message {msg:?}");
                 }
                 session.pong(&msg).await.unwrap();
+                continue;
             }
             Err(e) => {
                 if log::log_enabled!(log::Level::Debug) {
                     log::debug!("Confirm error: {e:?}");
                 }
+                continue;
             }
             r => {
                 if log::log_enabled!(log::Level::Debug) {
                     log::debug!("Unknown result: {r:?}");
                 }
+                continue;
+            }
+        };
+        match command {
+            Ok(SubscriberCommand::AckDelivery {
+                encoded_unique_time,
+                delivery_instance_id,
+            }) => {
+                confirm_deliveries(
+                    identity,
+                    &app_state,
+                    &topic_id,
+                    &member_id,
+                    vec![(encoded_unique_time, delivery_instance_id)],
+                );
+            }
+            Ok(command @ SubscriberCommand::AckDeliveryBatch { .. }) => {
+                let deliveries = command
+                    .unbatch()
+                    .into_iter()
+                    .filter_map(|command| match command {
+                        SubscriberCommand::AckDelivery {
+                            encoded_unique_time,
+                            delivery_instance_id,
+                        } => Some((encoded_unique_time, delivery_instance_id)),
+                        _ => None,
+                    })
+                    .collect();
+                confirm_deliveries(identity, &app_state, &topic_id, &member_id, deliveries);
+            }
+            Ok(SubscriberCommand::PrepareDelivery {
+                encoded_unique_time,
+                transaction_id,
+            }) => {
+                let app_state = app_state.clone();
+                let identity = identity.to_owned();
+                let topic_id = topic_id.to_owned();
+                rt::spawn(async move {
+                    app_state
+                        .mb
+                        .prepare_event_delivery(
+                            &identity,
+                            &topic_id,
+                            encoded_unique_time,
+                            &transaction_id,
+                        )
+                        .await
+                        .map_err(|e| log::info!("Failed to prepare delivery: {e}"))
+                        .ok();
+                });
+            }
+            Ok(SubscriberCommand::CommitDelivery {
+                encoded_unique_time,
+                delivery_instance_id,
+            }) => {
+                let app_state = app_state.clone();
+                let identity = identity.to_owned();
+                let topic_id = topic_id.to_owned();
+                let member_id = member_id.to_owned();
+                rt::spawn(async move {
+                    let result = app_state
+                        .mb
+                        .commit_event_delivery(
+                            &identity,
+                            &topic_id,
+                            encoded_unique_time,
+                            delivery_instance_id,
+                        )
+                        .await
+                        .map_err(|e| log::info!("Failed to commit delivery: {e}"));
+                    if result.is_ok()
+                        && let Some(member_id) = &member_id
+                    {
+                        app_state
+                            .mb
+                            .report_group_member_confirmation(&identity, &topic_id, member_id);
+                    }
+                });
+            }
+            command => {
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!("Ignoring message: {command:?}");
+                }
             }
         }
     }
+    session
+        .close(None)
+        .await
+        .map_err(|e| {
+            log::debug!("Failed to close session: {e:?}");
+        })
+        .ok();
     if log::log_enabled!(log::Level::Debug) {
         log::debug!("Stopping processing of confirmation messages");
     }
 }
+
+/// Confirm the deliveries of events in the background.
+fn confirm_deliveries(
+    identity: &ClientIdentity,
+    app_state: &Data<AppState>,
+    topic_id: &str,
+    member_id: &Option<String>,
+    deliveries: Vec<(u64, u16)>,
+) {
+    let app_state = app_state.clone();
+    let identity = identity.to_owned();
+    let topic_id = topic_id.to_owned();
+    let member_id = member_id.to_owned();
+    rt::spawn(async move {
+        for (encoded_unique_time, delivery_instance_id) in deliveries {
+            let result = app_state
+                .mb
+                .confirm_event_delivery(
+                    &identity,
+                    &topic_id,
+                    encoded_unique_time,
+                    delivery_instance_id,
+                )
+                .await
+                .map_err(|e| log::info!("Failed to confirm delivery: {e}"));
+            if result.is_ok()
+                && let Some(member_id) = &member_id
+            {
+                app_state
+                    .mb
+                    .report_group_member_confirmation(&identity, &topic_id, member_id);
+            }
+        }
+    });
+}
@@ -0,0 +1,637 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! WebSocket API resource for multiplexing event streams of many topics and
+//! correlation waits over a single connection.
+
+use super::ws_subscribe_resource::send_hello;
+use super::ws_subscribe_resource::send_response;
+use crate::rest_api::AppState;
+use crate::rest_api::common::ApiErrorMapper;
+use crate::rest_api::common::KeepAliveQueryParams;
+use crate::rest_api::common::MemberQueryParams;
+use crate::rest_api::common::NegotiatedProtocolVersion;
+use crate::rest_api::common::NextQueryParams;
+use crate::rest_api::common::WebSocketSessionRegistry;
+use actix_web::Error;
+use actix_web::HttpMessage;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::error::ErrorTooManyRequests;
+use actix_web::get;
+use actix_web::rt;
+use actix_web::web;
+use actix_web::web::Data;
+use actix_web::web::Query;
+use actix_ws::AggregatedMessage;
+use actix_ws::AggregatedMessageStream;
+use actix_ws::Session;
+use crossbeam_skiplist::SkipMap;
+use fragtale_client::SubscriberCommand;
+use fragtale_client::SubscriberResponse;
+use fragtale_client::WireFormat;
+use fragtale_client::mb::event_descriptor::DescriptorVersion;
+use fragtale_core::mb::MessageBroker;
+use fragtale_core::mb::auth::ClientIdentity;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use tokio::time::Duration;
+use tokio::time::sleep;
+
+/// Open a multiplexed WebSocket connection for subscribing to events of many
+/// topics and waiting for correlated events.
+///
+/// The client opens streams with `subscribe` commands carrying a client
+/// assigned stream identifier. Deliveries of each stream are tagged with the
+/// stream identifier and are confirmed with `ack_stream_delivery` over the
+/// same connection. An `await_correlation` command is answered with a
+/// `correlated` response tagged with its stream identifier. A `peek` command
+/// is answered with a `peeked` response listing the events next in line for
+/// delivery, without reserving them.
+///
+/// Consumer identifier is derived from authentication.
+#[utoipa::path(
+    tag = "web_socket",
+    params(
+        ("format" = Option<String>, Query, description = "Serialization of messages sent to the client: 'json' (default) in text frames or 'msgpack' in binary frames. Commands from the client may use either."),
+        ("ping_interval" = Option<u64>, Query, description = "Interval between client pings in milliseconds. Capped by the server."),
+        ("ping_tolerance" = Option<u64>, Query, description = "Tolerated delay of a client ping in milliseconds before the connection is considered stale. Capped by the server."),
+        ("member" = Option<String>, Query, description = "Identifier of the client instance within its consumer group (1-64 characters of 'A-Za-z0-9-_.')."),
+    ),
+    responses(
+        (status = 101, description = "Switching protocols to websocket."),
+        (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 400, description = "Bad Request."),
+        (status = 429, description = "Too Many Requests: The identity has too many open sessions."),
+    ),
+    security(("bearer_auth" = [])),
+)]
+#[get("/multiplex")]
+pub async fn multiplex_topics(
+    http_request: HttpRequest,
+    query: Query<NextQueryParams>,
+    keep_alive_query: Query<KeepAliveQueryParams>,
+    member_query: Query<MemberQueryParams>,
+    app_state: Data<AppState>,
+    stream: web::Payload,
+) -> Result<HttpResponse, Error> {
+    let identity = app_state
+        .auth
+        .get_identity(&http_request)
+        .map_err(ApiErrorMapper::from_message_broker_error)?;
+    let wire_format = query.get_wire_format()?;
+    let member_id = member_query.get_member_id().map(str::to_owned);
+    if let Some(member_id) = &member_id {
+        MessageBroker::assert_well_formed_member_id(member_id)
+            .map_err(ApiErrorMapper::from_message_broker_error)?;
+    }
+    let (ping_interval_micros, ping_tolerance_micros) =
+        keep_alive_query.negotiate_ping_micros(&app_state.app_config);
+    log::info!(
+        "Consumer '{}' opened a multiplexed connection with ping interval {ping_interval_micros} and tolerance {ping_tolerance_micros} micros.",
+        identity.identity_string()
+    );
+    let session_id = app_state
+        .ws_sessions
+        .open(
+            WebSocketSessionRegistry::KIND_MULTIPLEX,
+            None,
+            identity.identity_string(),
+        )
+        .ok_or_else(|| ErrorTooManyRequests("Too many open sessions."))?;
+    let (http_upgrade_response, session, stream) = actix_ws::handle(&http_request, stream)
+        .inspect_err(|_| {
+            app_state.ws_sessions.close(session_id);
+        })?;
+    let stream = stream
+        .aggregate_continuations()
+        // aggregate continuation frames up to 1 MiB
+        .max_continuation_size(2_usize.pow(20));
+    let connection = Arc::new(MultiplexedConnection {
+        identity,
+        app_state,
+        session,
+        session_id,
+        wire_format,
+        member_id,
+        ping_timeout_micros: ping_interval_micros + ping_tolerance_micros,
+        open: AtomicBool::new(true),
+        streams: SkipMap::default(),
+    });
+    let connection_clone = Arc::clone(&connection);
+    let negotiated = http_request
+        .extensions()
+        .get::<NegotiatedProtocolVersion>()
+        .copied();
+    rt::spawn(async move {
+        send_hello(
+            &mut connection_clone.session.clone(),
+            connection_clone.wire_format,
+            negotiated,
+            &connection_clone.app_state.ws_sessions,
+            connection_clone.session_id,
+        )
+        .await;
+        connection_clone.keep_alive(ping_interval_micros).await;
+    });
+    rt::spawn(async move {
+        connection.pull_commands_from_stream(stream).await;
+    });
+    // Respond immediately with with WebSocket upgrade response
+    Ok(http_upgrade_response)
+}
+
+/// State of a multiplexed connection.
+struct MultiplexedConnection {
+    identity: Arc<ClientIdentity>,
+    app_state: Data<AppState>,
+    session: Session,
+    /// Identifier of the session in the [WebSocketSessionRegistry].
+    session_id: u64,
+    wire_format: WireFormat,
+    member_id: Option<String>,
+    /// Max time between client pings.
+    ping_timeout_micros: u64,
+    open: AtomicBool,
+    /// Topic identifier and `active` flag by stream identifier.
+    ///
+    /// Closed streams are kept to allow late confirmations of their
+    /// deliveries.
+    streams: SkipMap<u32, (String, Arc<AtomicBool>)>,
+}
+
+impl MultiplexedConnection {
+    /// Return `true` while the connection is open.
+    fn is_open(&self) -> bool {
+        self.open.load(Ordering::Relaxed)
+    }
+
+    /// Require the client to ping again within the negotiated interval and
+    /// tolerance.
+    fn renew_ping_deadline(&self) {
+        self.app_state.ws_sessions.renew_ping_deadline(
+            self.session_id,
+            fragtale_client::time::get_timestamp_micros() + self.ping_timeout_micros,
+        );
+    }
+
+    /// Close the connection and all its streams.
+    async fn close(&self) {
+        if self.open.swap(false, Ordering::Relaxed) {
+            self.app_state.ws_sessions.close(self.session_id);
+            for entry in self.streams.iter() {
+                entry.value().1.store(false, Ordering::Relaxed);
+            }
+            self.session
+                .clone()
+                .close(None)
+                .await
+                .map_err(|e| {
+                    log::debug!("Failed to close session: {e:?}");
+                })
+                .ok();
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!(
+                    "Consumer '{}' lost a multiplexed connection.",
+                    self.identity.identity_string()
+                );
+            }
+        }
+    }
+
+    /// Ping the client while there is no other traffic and close the
+    /// connection when the client's pings are too old or the instance is no
+    /// longer serving consumers.
+    async fn keep_alive(&self, ping_interval_micros: u64) {
+        let mut counter = 0u64;
+        let delay_micros: u64 = 64_000;
+        let mut session = self.session.clone();
+        self.renew_ping_deadline();
+        while self.is_open() {
+            let now = fragtale_client::time::get_timestamp_micros();
+            // Killed by an administrator or last ping was too old
+            if !self.app_state.ws_sessions.is_open(self.session_id) {
+                break;
+            }
+            // Tell the consumer to move on to another instance
+            if !self.app_state.mb.is_serving_consumers() {
+                let response = SubscriberResponse::Rebalance {
+                    reason: "Instance is no longer serving consumers.".to_owned(),
+                };
+                if let Err(e) = send_response(
+                    &mut session,
+                    self.wire_format,
+                    &response,
+                    &self.app_state.ws_sessions,
+                    self.session_id,
+                )
+                .await
+                    && log::log_enabled!(log::Level::Debug)
+                {
+                    log::debug!("Rebalance notification failed with: {e:?}");
+                }
+                break;
+            }
+            if counter.is_multiple_of(std::cmp::max(1, ping_interval_micros / delay_micros))
+                && let Err(e) = session.ping(&now.to_be_bytes()).await
+            {
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!("Ping failed with: {e:?}");
+                }
+                break;
+            }
+            sleep(Duration::from_micros(delay_micros)).await;
+            counter += 1;
+        }
+        self.close().await;
+    }
+
+    /// Pull commands from the stream until the connection is closed.
+    async fn pull_commands_from_stream(self: Arc<Self>, mut stream: AggregatedMessageStream) {
+        while let Some(msg) = self
+            .app_state
+            .ws_sessions
+            .next_message(self.session_id, &mut stream)
+            .await
+        {
+            // JSON is sent in text frames and MessagePack in binary frames
+            let command: Result<SubscriberCommand, String> = match msg {
+                Ok(AggregatedMessage::Text(text)) => WireFormat::Json.decode(text.as_bytes()),
+                Ok(AggregatedMessage::Binary(bin)) => WireFormat::MessagePack.decode(&bin),
+                Ok(AggregatedMessage::Ping(msg)) => {
+                    self.renew_ping_deadline();
+                    if let Err(e) = self.session.clone().pong(&msg).await {
+                        if log::log_enabled!(log::Level::Debug) {
+                            log::debug!("Pong failed with: {e:?}");
+                        }
+                        break;
+                    }
+                    continue;
+                }
+                Ok(AggregatedMessage::Pong(_msg)) => continue,
+                Ok(AggregatedMessage::Close(reason)) => {
+                    if log::log_enabled!(log::Level::Debug) {
+                        log::debug!("Client closed the connection: {reason:?}");
+                    }
+                    break;
+                }
+                Err(e) => {
+                    if log::log_enabled!(log::Level::Debug) {
+                        log::debug!("Failed to get next message: {e:?}");
+                    }
+                    break;
+                }
+            };
+            match command {
+                Ok(SubscriberCommand::Subscribe {
+                    stream_id,
+                    topic_id,
+                    from_epoch_millis,
+                    version,
+                    event_types,
+                    min_priority,
+                    fields,
+                    visibility_timeout_millis,
+                    sticky_key,
+                }) => {
+                    self.subscribe(
+                        stream_id,
+                        topic_id,
+                        from_epoch_millis,
+                        version,
+                        event_types,
+                        min_priority,
+                        fields,
+                        visibility_timeout_millis,
+                        sticky_key,
+                    )
+                    .await;
+                }
+                Ok(SubscriberCommand::Unsubscribe { stream_id }) => {
+                    if let Some(entry) = self.streams.get(&stream_id) {
+                        entry.value().1.store(false, Ordering::Relaxed);
+                    }
+                }
+                Ok(SubscriberCommand::AckStreamDelivery {
+                    stream_id,
+                    encoded_unique_time,
+                    delivery_instance_id,
+                }) => {
+                    self.confirm(stream_id, encoded_unique_time, delivery_instance_id);
+                }
+                Ok(SubscriberCommand::AwaitCorrelation {
+                    stream_id,
+                    topic_id,
+                    correlation_token,
+                }) => {
+                    self.await_correlation(stream_id, topic_id, correlation_token);
+                }
+                Ok(SubscriberCommand::Peek {
+                    stream_id,
+                    topic_id,
+                    count,
+                    version,
+                }) => {
+                    self.peek(stream_id, topic_id, count, version);
+                }
+                Ok(command) => {
+                    if log::log_enabled!(log::Level::Debug) {
+                        log::debug!("Ignoring message: {command:?}");
+                    }
+                }
+                Err(e) => {
+                    log::info!("Ignoring malformed message: {e}");
+                }
+            }
+        }
+        self.close().await;
+    }
+
+    /// Open (or replace) a stream of events from a topic.
+    #[allow(clippy::too_many_arguments)]
+    async fn subscribe(
+        self: &Arc<Self>,
+        stream_id: u32,
+        topic_id: String,
+        from_epoch_millis: Option<u64>,
+        version: Option<String>,
+        event_types: Option<Vec<String>>,
+        min_priority: Option<u8>,
+        fields: Option<Vec<String>>,
+        visibility_timeout_millis: Option<u64>,
+        sticky_key: Option<String>,
+    ) {
+        let descriptor_version = match NextQueryParams::as_descriptor_version(&version) {
+            Ok(descriptor_version) => descriptor_version,
+            Err(e) => {
+                log::info!("Refusing stream '{stream_id}' for topic '{topic_id}': {e}");
+                return;
+            }
+        };
+        if let Some(member_id) = &self.member_id
+            && let Err(e) = self
+                .app_state
+                .mb
+                .register_group_member(&self.identity, &topic_id, member_id)
+                .await
+        {
+            log::info!("Refusing stream '{stream_id}' for topic '{topic_id}': {e}");
+            return;
+        }
+        let active = Arc::new(AtomicBool::new(true));
+        if let Some(entry) = self.streams.get(&stream_id) {
+            entry.value().1.store(false, Ordering::Relaxed);
+        }
+        self.streams
+            .insert(stream_id, (topic_id.to_owned(), Arc::clone(&active)));
+        let self_clone = Arc::clone(self);
+        rt::spawn(async move {
+            self_clone
+                .ship_events_to_stream(
+                    stream_id,
+                    &topic_id,
+                    &active,
+                    from_epoch_millis.map(|ms| ms * 1000),
+                    descriptor_version,
+                    event_types,
+                    min_priority,
+                    fields,
+                    visibility_timeout_millis,
+                    sticky_key,
+                )
+                .await;
+            if let Some(member_id) = &self_clone.member_id {
+                self_clone.app_state.mb.unregister_group_member(
+                    &self_clone.identity,
+                    &topic_id,
+                    member_id,
+                );
+            }
+        });
+    }
+
+    /// Ship events to the stream while it and the connection are open.
+    #[allow(clippy::too_many_arguments)]
+    async fn ship_events_to_stream(
+        &self,
+        stream_id: u32,
+        topic_id: &str,
+        active: &AtomicBool,
+        baseline_micros: Option<u64>,
+        descriptor_version: Option<DescriptorVersion>,
+        event_types: Option<Vec<String>>,
+        min_priority: Option<u8>,
+        fields: Option<Vec<String>>,
+        visibility_timeout_millis: Option<u64>,
+        sticky_key: Option<String>,
+    ) {
+        let mut session = self.session.clone();
+        while self.is_open() && active.load(Ordering::Relaxed) {
+            let res = self
+                .app_state
+                .mb
+                .get_event_by_consumer_and_topic(
+                    &self.identity,
+                    topic_id,
+                    baseline_micros,
+                    descriptor_version,
+                    event_types.as_deref(),
+                    min_priority,
+                    fields.as_deref(),
+                    None,
+                    visibility_timeout_millis,
+                    sticky_key.as_deref(),
+                )
+                .await;
+            match res {
+                Ok(Some((
+                    encoded_unique_time,
+                    event_document,
+                    correlation_token,
+                    delivery_instance_id,
+                    prepared_transaction_id,
+                    priority,
+                    delivery_envelope,
+                ))) => {
+                    let response = SubscriberResponse::Next {
+                        encoded_unique_time,
+                        delivery_instance_id,
+                        correlation_token,
+                        event_document,
+                        prepared_transaction_id,
+                        priority,
+                        stream_id: Some(stream_id),
+                        topic_id: Some(delivery_envelope.get_topic_id().to_owned()),
+                        event_id: Some(delivery_envelope.get_event_id().to_owned()),
+                        descriptor_version: delivery_envelope
+                            .get_descriptor_version()
+                            .map(str::to_owned),
+                        schema_id: delivery_envelope.get_schema_id().map(str::to_owned),
+                    };
+                    if let Err(e) = send_response(
+                        &mut session,
+                        self.wire_format,
+                        &response,
+                        &self.app_state.ws_sessions,
+                        self.session_id,
+                    )
+                    .await
+                    {
+                        if log::log_enabled!(log::Level::Debug) {
+                            log::debug!("Send failed with: {e:?}");
+                        }
+                        self.close().await;
+                        break;
+                    }
+                }
+                Ok(None) => {
+                    sleep(Duration::from_micros(64_000)).await;
+                }
+                Err(e) => {
+                    log::info!(
+                        "Closing stream '{stream_id}' for topic '{topic_id}' due to error: {e}"
+                    );
+                    active.store(false, Ordering::Relaxed);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Confirm the delivery of an event over a stream.
+    fn confirm(
+        self: &Arc<Self>,
+        stream_id: u32,
+        encoded_unique_time: u64,
+        delivery_instance_id: u16,
+    ) {
+        let Some(topic_id) = self
+            .streams
+            .get(&stream_id)
+            .map(|entry| entry.value().0.to_owned())
+        else {
+            log::info!("Ignoring confirmation of delivery over unknown stream '{stream_id}'.");
+            return;
+        };
+        let self_clone = Arc::clone(self);
+        rt::spawn(async move {
+            let result = self_clone
+                .app_state
+                .mb
+                .confirm_event_delivery(
+                    &self_clone.identity,
+                    &topic_id,
+                    encoded_unique_time,
+                    delivery_instance_id,
+                )
+                .await
+                .map_err(|e| log::info!("Failed to confirm delivery: {e}"));
+            if result.is_ok()
+                && let Some(member_id) = &self_clone.member_id
+            {
+                self_clone.app_state.mb.report_group_member_confirmation(
+                    &self_clone.identity,
+                    &topic_id,
+                    member_id,
+                );
+            }
+        });
+    }
+
+    /// Wait for a correlated event and respond with the result.
+    fn await_correlation(
+        self: &Arc<Self>,
+        stream_id: u32,
+        topic_id: String,
+        correlation_token: String,
+    ) {
+        let self_clone = Arc::clone(self);
+        rt::spawn(async move {
+            let event_document = self_clone
+                .app_state
+                .mb
+                .get_event_by_correlation_token(&self_clone.identity, &topic_id, &correlation_token)
+                .await
+                .map_err(|e| log::info!("Failed to await correlated event: {e}"))
+                .ok()
+                .flatten();
+            let response = SubscriberResponse::Correlated {
+                stream_id,
+                event_document,
+            };
+            if let Err(e) = send_response(
+                &mut self_clone.session.clone(),
+                self_clone.wire_format,
+                &response,
+                &self_clone.app_state.ws_sessions,
+                self_clone.session_id,
+            )
+            .await
+                && log::log_enabled!(log::Level::Debug)
+            {
+                log::debug!("Send failed with: {e:?}");
+            }
+        });
+    }
+
+    /// Respond with the events next in line for delivery to the consumer
+    /// without reserving them.
+    fn peek(
+        self: &Arc<Self>,
+        stream_id: u32,
+        topic_id: String,
+        count: Option<usize>,
+        version: Option<String>,
+    ) {
+        let self_clone = Arc::clone(self);
+        rt::spawn(async move {
+            let events = match NextQueryParams::as_descriptor_version(&version) {
+                Ok(descriptor_version) => self_clone
+                    .app_state
+                    .mb
+                    .peek_events_for_consumer(
+                        &self_clone.identity,
+                        &topic_id,
+                        count,
+                        descriptor_version,
+                    )
+                    .await
+                    .map_err(|e| log::info!("Failed to peek at events in '{topic_id}': {e}"))
+                    .map(|peeked_events| peeked_events.get_events().to_vec())
+                    .unwrap_or_default(),
+                Err(e) => {
+                    log::info!("Refusing peek at events in '{topic_id}': {e}");
+                    vec![]
+                }
+            };
+            let response = SubscriberResponse::Peeked { stream_id, events };
+            if let Err(e) = send_response(
+                &mut self_clone.session.clone(),
+                self_clone.wire_format,
+                &response,
+                &self_clone.app_state.ws_sessions,
+                self_clone.session_id,
+            )
+            .await
+                && log::log_enabled!(log::Level::Debug)
+            {
+                log::debug!("Send failed with: {e:?}");
+            }
+        });
+    }
+}
@@ -21,9 +21,11 @@ use std::sync::Arc;
 
 use crate::rest_api::AppState;
 use crate::rest_api::common::ApiErrorMapper;
+use crate::rest_api::common::WebSocketSessionRegistry;
 use actix_web::Error;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
+use actix_web::error::ErrorTooManyRequests;
 use actix_web::get;
 use actix_web::rt;
 use actix_web::web::Data;
@@ -33,9 +35,13 @@ use actix_ws::AggregatedMessage;
 use actix_ws::AggregatedMessageStream;
 use actix_ws::Session;
 use fragtale_client::SubscriberCommand;
+use fragtale_client::WireFormat;
 use fragtale_client::mb::event_descriptor::DescriptorVersion;
+use fragtale_client::mb::publish_acknowledgement::PublishAcknowledgement;
 use fragtale_core::mb::auth::ClientIdentity;
-use futures::StreamExt;
+
+/// Aggregate continuation frames up to 4 MiB.
+pub const MAX_MESSAGE_SIZE: usize = 2_usize.pow(22);
 
 /// Open a WebSocket connection for publishing events.
 ///
@@ -48,6 +54,7 @@ use futures::StreamExt;
     responses(
         (status = 101, description = "Switching protocols to websocket."),
         (status = 401, description = "Unauthorized: Authentication failure."),
+        (status = 429, description = "Too Many Requests: The identity has too many open sessions."),
     ),
     security(("bearer_auth" = [])),
 )]
@@ -63,15 +70,34 @@ pub async fn publish_event_to_topic(
         .get_identity(&http_request)
         .map_err(ApiErrorMapper::from_message_broker_error)?;
     let topic_id = path.into_inner();
+    let session_id = app_state
+        .ws_sessions
+        .open(
+            WebSocketSessionRegistry::KIND_PUBLISH,
+            Some(&topic_id),
+            identity.identity_string(),
+        )
+        .ok_or_else(|| ErrorTooManyRequests("Too many open sessions."))?;
     log::info!("Publisher '{identity}' opened a publish connection for topic '{topic_id}'.");
-    let (http_upgrade_response, session, stream) = actix_ws::handle(&http_request, stream)?;
+    let (http_upgrade_response, session, stream) = actix_ws::handle(&http_request, stream)
+        .inspect_err(|_| {
+            app_state.ws_sessions.close(session_id);
+        })?;
     let stream = stream
         .aggregate_continuations()
-        // aggregate continuation frames up to 4 MiB
-        .max_continuation_size(2_usize.pow(22));
+        .max_continuation_size(MAX_MESSAGE_SIZE);
     // Pull messages from this steam
     rt::spawn(async move {
-        pull_messages_from_stream(identity, app_state, session, stream, topic_id).await;
+        pull_messages_from_stream(
+            identity,
+            app_state.clone(),
+            session,
+            session_id,
+            stream,
+            topic_id,
+        )
+        .await;
+        app_state.ws_sessions.close(session_id);
     });
     // Respond immediately with with WebSocket upgrade response
     Ok(http_upgrade_response)
@@ -82,58 +108,31 @@ async fn pull_messages_from_stream(
     identity: Arc<ClientIdentity>,
     app_state: Data<AppState>,
     mut session: Session,
+    session_id: u64,
     mut stream: AggregatedMessageStream,
     topic_id: String,
 ) {
     if log::log_enabled!(log::Level::Debug) {
         log::debug!("Starting to process publish messages");
     }
-    while let Some(msg) = stream.next().await {
-        match msg {
+    while let Some(msg) = app_state
+        .ws_sessions
+        .next_message(session_id, &mut stream)
+        .await
+    {
+        // JSON is sent in text frames and MessagePack in binary frames
+        let command: Result<SubscriberCommand, String> = match msg {
             Ok(AggregatedMessage::Text(text)) => {
-                // Parse text as "command" and match
                 if log::log_enabled!(log::Level::Trace) {
                     log::trace!("Got msg: {text}");
                 }
-                match serde_json::from_str(&text) {
-                    Ok(SubscriberCommand::Publish {
-                        priority,
-                        event_document,
-                        correlation_token,
-                        descriptor_version,
-                    }) => {
-                        let app_state = app_state.clone();
-                        let identity = Arc::clone(&identity);
-                        let topic_id = topic_id.to_owned();
-                        let descriptor_version =
-                            descriptor_version.map(DescriptorVersion::from_encoded);
-                        rt::spawn(async move {
-                            app_state
-                                .mb
-                                .publish_event_to_topic(
-                                    &identity,
-                                    &topic_id,
-                                    &event_document,
-                                    priority,
-                                    descriptor_version,
-                                    correlation_token,
-                                )
-                                .await
-                                .map_err(|e| log::info!("Failed to publish event: {e}"))
-                                .ok();
-                        });
-                    }
-                    _ => {
-                        if log::log_enabled!(log::Level::Debug) {
-                            log::debug!("Ignoring text message: {text}");
-                        }
-                    }
-                }
+                WireFormat::Json.decode(text.as_bytes())
             }
-            Ok(AggregatedMessage::Binary(_bin)) => {
-                if log::log_enabled!(log::Level::Debug) {
-                    log::debug!("Ignoring binary message");
+            Ok(AggregatedMessage::Binary(bin)) => {
+                if log::log_enabled!(log::Level::Trace) {
+                    log::trace!("Got binary message of {} bytes.", bin.len());
                 }
+                WireFormat::MessagePack.decode(&bin)
             }
             Ok(AggregatedMessage::Ping(msg)) => {
                 // respond to PING frame with PONG frame
@@ -141,19 +140,66 @@ async fn pull_messages_from_stream(
                     log::trace!("Sending pong message");
                 }
                 session.pong(&msg).await.unwrap();
+                continue;
             }
             Err(e) => {
                 if log::log_enabled!(log::Level::Debug) {
                     log::debug!("Publish error: {e:?}");
                 }
+                continue;
             }
             r => {
                 if log::log_enabled!(log::Level::Debug) {
                     log::debug!("Unknown result: {r:?}");
                 }
+                continue;
+            }
+        };
+        match command {
+            Ok(SubscriberCommand::Publish {
+                priority,
+                event_document,
+                correlation_token,
+                descriptor_version,
+                expires_ts,
+            }) => {
+                let app_state = app_state.clone();
+                let identity = Arc::clone(&identity);
+                let topic_id = topic_id.to_owned();
+                let descriptor_version = descriptor_version.map(DescriptorVersion::from_encoded);
+                rt::spawn(async move {
+                    app_state
+                        .mb
+                        .publish_event_to_topic(
+                            &identity,
+                            &topic_id,
+                            &event_document,
+                            priority,
+                            expires_ts,
+                            None,
+                            descriptor_version,
+                            correlation_token,
+                            PublishAcknowledgement::default(),
+                        )
+                        .await
+                        .map_err(|e| log::info!("Failed to publish event: {e}"))
+                        .ok();
+                });
+            }
+            command => {
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!("Ignoring message: {command:?}");
+                }
             }
         }
     }
+    session
+        .close(None)
+        .await
+        .map_err(|e| {
+            log::debug!("Failed to close session: {e:?}");
+        })
+        .ok();
     if log::log_enabled!(log::Level::Debug) {
         log::debug!("Stopping processing of publish messages");
     }
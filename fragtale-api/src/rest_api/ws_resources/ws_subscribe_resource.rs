@@ -19,10 +19,16 @@
 
 use crate::rest_api::AppState;
 use crate::rest_api::common::ApiErrorMapper;
+use crate::rest_api::common::KeepAliveQueryParams;
+use crate::rest_api::common::MemberQueryParams;
+use crate::rest_api::common::NegotiatedProtocolVersion;
 use crate::rest_api::common::NextQueryParams;
+use crate::rest_api::common::WebSocketSessionRegistry;
 use actix_web::Error;
+use actix_web::HttpMessage;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
+use actix_web::error::ErrorTooManyRequests;
 use actix_web::get;
 use actix_web::rt;
 use actix_web::web;
@@ -32,14 +38,13 @@ use actix_web::web::Query;
 use actix_ws::AggregatedMessage;
 use actix_ws::AggregatedMessageStream;
 use actix_ws::Session;
-use fragtale_client::EventClient;
+use fragtale_client::DeliveryBatching;
 use fragtale_client::SubscriberResponse;
+use fragtale_client::WireFormat;
 use fragtale_client::mb::event_descriptor::DescriptorVersion;
+use fragtale_client::protocol;
 use fragtale_core::mb::auth::ClientIdentity;
-use futures::StreamExt;
 use std::sync::Arc;
-use std::sync::atomic::AtomicU64;
-use std::sync::atomic::Ordering;
 use tokio::time::Duration;
 use tokio::time::sleep;
 use tyst::encdec::hex::ToHex;
@@ -53,11 +58,24 @@ use tyst::encdec::hex::ToHex;
         ("topic_id", description = "Topic identifier."),
         ("from" = Option<u64>, Query, description = "Only consider events newer than this in epoch milliseconds."),
         ("version" = Option<String>, Query, description = "Event Descriptor SemVer that the client prefers (major.minor)."),
+        ("type" = Option<String>, Query, description = "Comma separated kinds of events of interest in a multi-type topic. Events of other kinds are skipped."),
+        ("min_priority" = Option<u8>, Query, description = "Lowest priority of events of interest. Events published with a lower priority are skipped."),
+        ("fields" = Option<String>, Query, description = "Comma separated JSON Pointers (e.g. '/id,/customer/name'). Only these fields of each event document are delivered."),
+        ("filter" = Option<String>, Query, description = "Filter expression over the extracted values of each event (e.g. 'region == \"eu\" && amount > 100' or the shorthand 'doc_status=eq:open,amount=gt:100'). Events that do not match are skipped."),
+        ("visibility_timeout" = Option<u64>, Query, description = "Milliseconds to wait for confirmation of a delivered event before it is considered for redelivery. Clamped to the bounds set by the administrator."),
+        ("sticky" = Option<String>, Query, description = "Routing key that this member of the consumer group prefers (e.g. a region or shard hint). Events with another routing key are left to the members that prefer them for a short while."),
+        ("format" = Option<String>, Query, description = "Serialization of messages sent to the client: 'json' (default) in text frames or 'msgpack' in binary frames. Commands from the client may use either."),
+        ("batch" = Option<usize>, Query, description = "Max number of events already available for delivery to send in a single message. Capped by the server. Batched events are confirmed individually or in batches."),
+        ("ping_interval" = Option<u64>, Query, description = "Interval between client pings in milliseconds. Capped by the server."),
+        ("ping_tolerance" = Option<u64>, Query, description = "Tolerated delay of a client ping in milliseconds before the connection is considered stale. Capped by the server."),
+        ("member" = Option<String>, Query, description = "Identifier of the client instance within its consumer group (1-64 characters of 'A-Za-z0-9-_.')."),
     ),
     responses(
         (status = 101, description = "Switching protocols to websocket."),
         (status = 401, description = "Unauthorized: Authentication failure."),
         (status = 400, description = "Bad Request."),
+        (status = 403, description = "Forbidden: Authorization failure."),
+        (status = 429, description = "Too Many Requests: The identity has too many open sessions."),
     ),
     security(("bearer_auth" = [])),
 )]
@@ -66,6 +84,8 @@ pub async fn subscribe_to_topic(
     http_request: HttpRequest,
     path: Path<String>,
     query: Query<NextQueryParams>,
+    keep_alive_query: Query<KeepAliveQueryParams>,
+    member_query: Query<MemberQueryParams>,
     app_state: Data<AppState>,
     stream: web::Payload,
 ) -> Result<HttpResponse, Error> {
@@ -78,54 +98,148 @@ pub async fn subscribe_to_topic(
     let next_query_params = query.into_inner();
     let baseline_micros = next_query_params.get_from_epoch_micros();
     let descriptor_version = next_query_params.get_descriptor_version()?;
-    log::info!("Consumer '{consumer_id}' opened a subscriber connection for topic '{topic_id}'.");
-    let (http_upgrade_response, session, stream) = actix_ws::handle(&http_request, stream)?;
+    let event_types = next_query_params.get_event_types();
+    let min_priority = next_query_params.get_min_priority();
+    let fields = next_query_params.get_fields();
+    let filter = next_query_params.get_filter().map(str::to_owned);
+    let visibility_timeout_millis = next_query_params.get_visibility_timeout_millis();
+    let sticky_key = next_query_params.get_sticky_key().map(str::to_owned);
+    let wire_format = next_query_params.get_wire_format()?;
+    let delivery_batching = next_query_params.get_delivery_batching();
+    let (ping_interval_micros, ping_tolerance_micros) =
+        keep_alive_query.negotiate_ping_micros(&app_state.app_config);
+    let member_id = member_query.get_member_id().map(str::to_owned);
+    let ws_sessions = Arc::clone(&app_state.ws_sessions);
+    let session_id = ws_sessions
+        .open(
+            WebSocketSessionRegistry::KIND_SUBSCRIBE,
+            Some(&topic_id),
+            consumer_id,
+        )
+        .ok_or_else(|| ErrorTooManyRequests("Too many open sessions."))?;
+    if let Some(member_id) = &member_id
+        && let Err(e) = app_state
+            .mb
+            .register_group_member(&identity, &topic_id, member_id)
+            .await
+    {
+        ws_sessions.close(session_id);
+        Err(ApiErrorMapper::from_message_broker_error(e))?;
+    }
+    log::info!(
+        "Consumer '{consumer_id}' opened a subscriber connection for topic '{topic_id}' with ping interval {ping_interval_micros} and tolerance {ping_tolerance_micros} micros."
+    );
+    let (http_upgrade_response, session, stream) = actix_ws::handle(&http_request, stream)
+        .inspect_err(|_| {
+            ws_sessions.close(session_id);
+        })?;
     let stream = stream
         .aggregate_continuations()
         // aggregate continuation frames up to 1 MiB
         .max_continuation_size(2_usize.pow(20));
-    let last_ping = Arc::new(AtomicU64::new(fragtale_client::time::get_timestamp_micros()));
-    let last_ping_clone = Arc::clone(&last_ping);
+    let ping_timeout_micros = ping_interval_micros + ping_tolerance_micros;
+    ws_sessions.renew_ping_deadline(
+        session_id,
+        fragtale_client::time::get_timestamp_micros() + ping_timeout_micros,
+    );
+    let negotiated = http_request
+        .extensions()
+        .get::<NegotiatedProtocolVersion>()
+        .copied();
     // Ship events to this stream
     rt::spawn(async move {
+        let mut session = session;
+        send_hello(
+            &mut session,
+            wire_format,
+            negotiated,
+            &app_state.ws_sessions,
+            session_id,
+        )
+        .await;
         ship_events_to_stream(
             &identity,
-            app_state,
+            app_state.clone(),
             session,
-            last_ping,
-            topic_id,
+            session_id,
+            topic_id.to_owned(),
             baseline_micros,
             descriptor_version,
+            event_types,
+            min_priority,
+            fields,
+            filter,
+            visibility_timeout_millis,
+            sticky_key,
+            wire_format,
+            delivery_batching,
+            ping_interval_micros,
         )
         .await;
+        if let Some(member_id) = &member_id {
+            app_state
+                .mb
+                .unregister_group_member(&identity, &topic_id, member_id);
+        }
     });
     // Pull messages from this steam (none are expected, except pings)
-    rt::spawn(async move { pull_messages_from_stream(stream, last_ping_clone).await });
+    rt::spawn(async move {
+        pull_messages_from_stream(stream, ping_timeout_micros, &ws_sessions, session_id).await;
+        ws_sessions.close(session_id);
+    });
     // Respond immediately with with WebSocket upgrade response
     Ok(http_upgrade_response)
 }
 
 /// Ship events to the subscribed consumer.
+#[allow(clippy::too_many_arguments)]
 async fn ship_events_to_stream(
     identity: &ClientIdentity,
     app_state: Data<AppState>,
     mut session: Session,
-    last_ping: Arc<AtomicU64>,
+    session_id: u64,
     topic_id: String,
     baseline_micros: Option<u64>,
     descriptor_version: Option<DescriptorVersion>,
+    event_types: Option<Vec<String>>,
+    min_priority: Option<u8>,
+    fields: Option<Vec<String>>,
+    filter: Option<String>,
+    visibility_timeout_millis: Option<u64>,
+    sticky_key: Option<String>,
+    wire_format: WireFormat,
+    delivery_batching: DeliveryBatching,
+    ping_interval_micros: u64,
 ) {
     let mut counter = 0u64;
+    // Deliveries of events that were available right away
+    let mut batch = Vec::with_capacity(delivery_batching.get_max_batch_size());
     let mut exhausted_ts = None;
+    // Last catch-up status reported to the client
+    let mut reported_catching_up = false;
     let consumer_id = identity.identity_string();
     loop {
         let start_ts = fragtale_client::time::get_timestamp_micros();
-        // Check that last ping was withing acceptable threshold
-        if last_ping.load(Ordering::Relaxed)
-            < start_ts - (EventClient::PING_INTERVAL_MICROS + 1_000_000)
-        {
-            if log::log_enabled!(log::Level::Trace) {
-                log::trace!("Last ping on this web-socket connection was too old.");
+        // Killed by an administrator, last ping was too old or the client is gone
+        if !app_state.ws_sessions.is_open(session_id) {
+            break;
+        }
+        // Tell the consumer to move on to another instance
+        if !app_state.mb.is_serving_consumers() {
+            let response = SubscriberResponse::Rebalance {
+                reason: "Instance is no longer serving consumers.".to_owned(),
+            };
+            if let Err(e) = send_response(
+                &mut session,
+                wire_format,
+                &response,
+                &app_state.ws_sessions,
+                session_id,
+            )
+            .await
+                && log::log_enabled!(log::Level::Debug)
+            {
+                log::debug!("Rebalance notification failed with: {e:?}");
             }
             break;
         }
@@ -136,6 +250,12 @@ async fn ship_events_to_stream(
                 &topic_id,
                 baseline_micros,
                 descriptor_version,
+                event_types.as_deref(),
+                min_priority,
+                fields.as_deref(),
+                filter.as_deref(),
+                visibility_timeout_millis,
+                sticky_key.as_deref(),
             )
             .await;
         match res {
@@ -144,18 +264,40 @@ async fn ship_events_to_stream(
                 event_document,
                 correlation_token,
                 delivery_instance_id,
+                prepared_transaction_id,
+                priority,
+                delivery_envelope,
             ))) => {
-                let text = serde_json::to_string(&SubscriberResponse::Next {
+                let response = SubscriberResponse::Next {
                     encoded_unique_time,
                     delivery_instance_id,
                     correlation_token,
                     event_document,
-                })
-                .unwrap();
-                if log::log_enabled!(log::Level::Trace) {
-                    log::trace!("Sending text: {text}");
+                    prepared_transaction_id,
+                    priority,
+                    stream_id: None,
+                    topic_id: Some(delivery_envelope.get_topic_id().to_owned()),
+                    event_id: Some(delivery_envelope.get_event_id().to_owned()),
+                    descriptor_version: delivery_envelope
+                        .get_descriptor_version()
+                        .map(str::to_owned),
+                    schema_id: delivery_envelope.get_schema_id().map(str::to_owned),
+                };
+                exhausted_ts = None;
+                batch.push(response);
+                // Keep collecting events while they are available right away
+                if batch.len() < delivery_batching.get_max_batch_size() {
+                    continue;
                 }
-                if let Err(e) = session.text(text).await {
+                if let Err(e) = send_deliveries(
+                    &mut session,
+                    wire_format,
+                    std::mem::take(&mut batch),
+                    &app_state.ws_sessions,
+                    session_id,
+                )
+                .await
+                {
                     if log::log_enabled!(log::Level::Debug) {
                         log::debug!("Send failed with: {e:?}");
                     }
@@ -183,19 +325,56 @@ async fn ship_events_to_stream(
                     }
                 }
                 */
-                exhausted_ts = None;
             }
             Ok(None) => {
+                if !batch.is_empty()
+                    && let Err(e) = send_deliveries(
+                        &mut session,
+                        wire_format,
+                        std::mem::take(&mut batch),
+                        &app_state.ws_sessions,
+                        session_id,
+                    )
+                    .await
+                {
+                    if log::log_enabled!(log::Level::Debug) {
+                        log::debug!("Send failed with: {e:?}");
+                    }
+                    break;
+                }
                 if exhausted_ts.is_none() {
                     exhausted_ts = Some(start_ts);
                 }
+                // Let the client know why nothing is delivered yet
+                let catching_up = app_state.mb.is_consumer_catching_up(identity, &topic_id);
+                if catching_up != reported_catching_up {
+                    reported_catching_up = catching_up;
+                    let response = SubscriberResponse::Status { catching_up };
+                    if let Err(e) = send_response(
+                        &mut session,
+                        wire_format,
+                        &response,
+                        &app_state.ws_sessions,
+                        session_id,
+                    )
+                    .await
+                    {
+                        if log::log_enabled!(log::Level::Debug) {
+                            log::debug!("Status notification failed with: {e:?}");
+                        }
+                        break;
+                    }
+                }
                 // Only ping when there is no other traffic
                 let delay_micros: u64 = 64_000;
-                if counter % (EventClient::PING_INTERVAL_MICROS / delay_micros) == 0 {
+                if counter.is_multiple_of(std::cmp::max(1, ping_interval_micros / delay_micros)) {
                     if log::log_enabled!(log::Level::Trace) {
                         log::trace!("Sending ping");
                     }
-                    if let Err(e) = session.ping("ping".as_bytes()).await {
+                    // Send the current time to measure the round-trip time
+                    // when the client responds with a pong.
+                    let ping_ts = fragtale_client::time::get_timestamp_micros();
+                    if let Err(e) = session.ping(&ping_ts.to_be_bytes()).await {
                         if log::log_enabled!(log::Level::Debug) {
                             log::debug!("Ping failed with: {e:?}");
                         }
@@ -211,6 +390,18 @@ async fn ship_events_to_stream(
             }
         }
     }
+    // Events of an incomplete batch would otherwise wait for redelivery
+    if !batch.is_empty() {
+        send_deliveries(
+            &mut session,
+            wire_format,
+            batch,
+            &app_state.ws_sessions,
+            session_id,
+        )
+        .await
+        .ok();
+    }
     session
         .close(None)
         .await
@@ -223,11 +414,84 @@ async fn ship_events_to_stream(
     }
 }
 
+/// Send a response in the negotiated serialization format and count the sent
+/// bytes of the session.
+pub async fn send_response(
+    session: &mut Session,
+    wire_format: WireFormat,
+    response: &SubscriberResponse,
+    ws_sessions: &WebSocketSessionRegistry,
+    session_id: u64,
+) -> Result<(), actix_ws::Closed> {
+    let data = wire_format.encode(response);
+    let bytes = data.len();
+    if wire_format.is_binary() {
+        session.binary(data).await?;
+    } else {
+        session
+            .text(String::from_utf8(data).unwrap_or_default())
+            .await?;
+    }
+    ws_sessions.add_sent_bytes(session_id, bytes);
+    Ok(())
+}
+
+/// Send deliveries as a single batch when possible.
+async fn send_deliveries(
+    session: &mut Session,
+    wire_format: WireFormat,
+    deliveries: Vec<SubscriberResponse>,
+    ws_sessions: &WebSocketSessionRegistry,
+    session_id: u64,
+) -> Result<(), actix_ws::Closed> {
+    let deliveries = match SubscriberResponse::batch(deliveries) {
+        Ok(response) => vec![response],
+        Err(deliveries) => deliveries,
+    };
+    for response in &deliveries {
+        if log::log_enabled!(log::Level::Trace) {
+            log::trace!("Sending: {response:?}");
+        }
+        send_response(session, wire_format, response, ws_sessions, session_id).await?;
+    }
+    Ok(())
+}
+
+/// Tell the client which protocol version is used for the connection.
+///
+/// Clients that did not announce their protocol version predate the hello
+/// message and will not be sent one.
+pub async fn send_hello(
+    session: &mut Session,
+    wire_format: WireFormat,
+    negotiated: Option<NegotiatedProtocolVersion>,
+    ws_sessions: &WebSocketSessionRegistry,
+    session_id: u64,
+) {
+    let Some(negotiated) = negotiated.filter(NegotiatedProtocolVersion::is_announced) else {
+        return;
+    };
+    let response = SubscriberResponse::Hello {
+        protocol_version: negotiated.get_protocol_version(),
+        min_protocol_version: protocol::MIN_PROTOCOL_VERSION,
+    };
+    if let Err(e) = send_response(session, wire_format, &response, ws_sessions, session_id).await
+        && log::log_enabled!(log::Level::Debug)
+    {
+        log::debug!("Hello failed with: {e:?}");
+    }
+}
+
 /// Pull messages from this steam (none are expected, except pings)
-async fn pull_messages_from_stream(mut stream: AggregatedMessageStream, last_ping: Arc<AtomicU64>) {
+async fn pull_messages_from_stream(
+    mut stream: AggregatedMessageStream,
+    ping_timeout_micros: u64,
+    ws_sessions: &WebSocketSessionRegistry,
+    session_id: u64,
+) {
     let mut ping_id = None;
     loop {
-        match stream.next().await {
+        match ws_sessions.next_message(session_id, &mut stream).await {
             Some(Ok(AggregatedMessage::Text(text))) => {
                 // Parse text as "command" and match
                 if log::log_enabled!(log::Level::Debug) {
@@ -258,12 +522,20 @@ async fn pull_messages_from_stream(mut stream: AggregatedMessageStream, last_pin
                 }
                 if ping_id.as_ref().is_some_and(|ping_id| ping_id.eq(&msg)) {
                     let ping_ts = fragtale_client::time::get_timestamp_micros();
-                    last_ping.store(ping_ts, Ordering::Relaxed);
+                    ws_sessions.renew_ping_deadline(session_id, ping_ts + ping_timeout_micros);
                 }
                 //session.pong(&msg).await.unwrap();
             }
-            Some(Ok(AggregatedMessage::Pong(_msg))) => {
-                if log::log_enabled!(log::Level::Trace) {
+            Some(Ok(AggregatedMessage::Pong(msg))) => {
+                // Pong echoes the server ping payload with the time it was sent
+                if let Ok(ping_ts_bytes) = <[u8; 8]>::try_from(msg.as_ref()) {
+                    let rtt_micros = fragtale_client::time::get_timestamp_micros()
+                        .saturating_sub(u64::from_be_bytes(ping_ts_bytes));
+                    if log::log_enabled!(log::Level::Trace) {
+                        log::trace!("Got pong message. Round-trip time: {rtt_micros} micros.");
+                    }
+                    ws_sessions.update_rtt(session_id, rtt_micros);
+                } else if log::log_enabled!(log::Level::Trace) {
                     log::trace!("Ignoring pong message");
                 }
             }
@@ -280,8 +552,9 @@ async fn pull_messages_from_stream(mut stream: AggregatedMessageStream, last_pin
             }
             None => {
                 if log::log_enabled!(log::Level::Debug) {
-                    log::debug!("No message.");
+                    log::debug!("No more messages.");
                 }
+                break;
             }
         }
     }
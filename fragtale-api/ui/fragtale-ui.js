@@ -0,0 +1,293 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+// Operator web UI backed by the REST API of the broker.
+//
+// All data is requested with the bearer token of the operator, so the API
+// enforces the same authorization as for any other client. The token is only
+// kept in the session storage of the browser tab.
+
+"use strict";
+
+const API = "/api/v1";
+const TOKEN_KEY = "fragtale.token";
+// Number of recent events to show for a topic
+const RECENT_EVENTS = 20;
+// Max number of pages to follow when looking for the latest bucket
+const MAX_PAGES = 16;
+
+const byId = (id) => document.getElementById(id);
+
+function token() {
+    return sessionStorage.getItem(TOKEN_KEY);
+}
+
+function setStatus(message) {
+    byId("status").textContent = message || "";
+}
+
+// Request a resource from the API and return the response body as text.
+async function apiText(path) {
+    const headers = {};
+    if (token()) {
+        headers["Authorization"] = "Bearer " + token();
+    }
+    const response = await fetch(API + path, { headers });
+    if (!response.ok) {
+        throw new Error(response.status + " " + response.statusText + " for " + path);
+    }
+    return response.text();
+}
+
+// Request a JSON resource from the API.
+//
+// Encoded unique times and bucket identifiers might not fit in a JavaScript
+// number, so these are kept as strings.
+async function apiJson(path) {
+    const text = await apiText(path);
+    return JSON.parse(text.replace(/"(unique_time|bucket)":(\d+)/g, "\"$1\":\"$2\""));
+}
+
+function formatMicros(micros) {
+    if (micros === undefined || micros === null) {
+        return "-";
+    }
+    return new Date(micros / 1000).toISOString();
+}
+
+function formatLag(micros) {
+    if (micros === undefined || micros === null) {
+        return "-";
+    }
+    const seconds = micros / 1000000;
+    if (seconds < 120) {
+        return seconds.toFixed(1) + " s";
+    }
+    if (seconds < 7200) {
+        return (seconds / 60).toFixed(1) + " min";
+    }
+    return (seconds / 3600).toFixed(1) + " h";
+}
+
+function cell(row, text) {
+    const td = document.createElement("td");
+    td.textContent = text;
+    row.appendChild(td);
+    return td;
+}
+
+function link(text, onClick) {
+    const a = document.createElement("a");
+    a.textContent = text;
+    a.addEventListener("click", (event) => {
+        event.preventDefault();
+        onClick().catch((e) => setStatus(e.message));
+    });
+    return a;
+}
+
+async function loadHealth() {
+    const health = byId("health");
+    health.replaceChildren();
+    for (const probe of ["live", "ready", "started"]) {
+        const response = await fetch("/health/" + probe);
+        const dt = document.createElement("dt");
+        dt.textContent = probe;
+        const dd = document.createElement("dd");
+        dd.textContent = response.ok ? "UP" : "DOWN";
+        dd.className = response.ok ? "ok" : "failed";
+        health.append(dt, dd);
+    }
+    try {
+        const capabilities = await apiJson("/admin/capabilities");
+        byId("capabilities").textContent = JSON.stringify(capabilities, null, 2);
+    } catch (e) {
+        byId("capabilities").textContent = e.message;
+    }
+}
+
+async function loadTopics(from) {
+    const list = byId("topic-list");
+    if (!from) {
+        list.replaceChildren();
+    }
+    const query = from ? "?from=" + encodeURIComponent(from) : "";
+    const topicList = await apiJson("/admin/topics" + query);
+    for (const topicId of topicList.topic_ids) {
+        const li = document.createElement("li");
+        li.appendChild(link(topicId, () => loadTopic(topicId)));
+        list.appendChild(li);
+    }
+    const more = byId("topic-more");
+    more.hidden = !topicList.more;
+    more.dataset.from = topicList.topic_ids[topicList.topic_ids.length - 1] || "";
+}
+
+async function loadTopic(topicId) {
+    setStatus("");
+    byId("topic").hidden = false;
+    byId("topic-title").textContent = topicId;
+    byId("descriptor-diff").hidden = true;
+    byId("event-document").hidden = true;
+    const topicPath = encodeURIComponent(topicId);
+    const results = await Promise.allSettled([
+        loadConsumers(topicPath),
+        loadDescriptors(topicPath),
+        loadRecentEvents(topicPath),
+    ]);
+    const failures = results.filter((result) => result.status === "rejected");
+    setStatus(failures.map((result) => result.reason.message).join(" "));
+}
+
+async function loadConsumers(topicPath) {
+    const consumers = byId("consumers");
+    consumers.replaceChildren();
+    const lag = await apiJson("/admin/topics/" + topicPath + "/lag");
+    // Subscription health is only known for subscriptions to this instance
+    const subscriptions = new Map();
+    try {
+        const health = await apiJson("/admin/topics/" + topicPath + "/subscriptions");
+        for (const subscription of health.subscriptions) {
+            subscriptions.set(subscription.consumer_id, subscription);
+        }
+    } catch (e) {
+        // Not authorized or unavailable
+    }
+    for (const consumer of lag.consumers) {
+        const row = document.createElement("tr");
+        cell(row, consumer.consumer_id);
+        cell(row, formatLag(consumer.lag_micros));
+        cell(row, formatMicros(consumer.done_ts_micros));
+        cell(row, formatMicros(consumer.attempted_ts_micros));
+        const subscription = subscriptions.get(consumer.consumer_id);
+        if (!subscription) {
+            cell(row, "-");
+        } else if (subscription.paused) {
+            cell(row, "paused at " + subscription.paused_event_id).className = "failed";
+        } else {
+            cell(row, subscription.redelivered_events + " redeliveries").className = "ok";
+        }
+        consumers.appendChild(row);
+    }
+}
+
+async function loadDescriptors(topicPath) {
+    const descriptors = byId("descriptors");
+    descriptors.replaceChildren();
+    const history = await apiJson("/topics/" + topicPath + "/descriptions");
+    history.versions.forEach((version, index) => {
+        const li = document.createElement("li");
+        li.textContent = version + " ";
+        if (index > 0) {
+            const previous = history.versions[index - 1];
+            li.appendChild(link("changes since " + previous, async () => {
+                const diff = await apiJson("/topics/" + topicPath + "/descriptions/diff?from="
+                    + encodeURIComponent(previous) + "&to=" + encodeURIComponent(version));
+                const pre = byId("descriptor-diff");
+                pre.textContent = JSON.stringify(diff, null, 2);
+                pre.hidden = false;
+            }));
+        }
+        descriptors.appendChild(li);
+    });
+}
+
+async function loadRecentEvents(topicPath) {
+    const events = byId("events");
+    events.replaceChildren();
+    const shelves = (await apiJson("/admin/topics/" + topicPath + "/shelves")).shelves;
+    if (shelves.length === 0) {
+        return;
+    }
+    const shelf = shelves[shelves.length - 1];
+    const bucket = await lastPage(
+        (from) => "/admin/topics/" + topicPath + "/shelves/" + shelf + "/buckets" + from,
+        (page) => page.buckets,
+        (bucket) => bucket.bucket,
+    );
+    if (!bucket) {
+        return;
+    }
+    const entries = [];
+    await lastPage(
+        (from) => "/admin/topics/" + topicPath + "/buckets/" + bucket.bucket + from,
+        (page) => {
+            entries.push(...page.entries);
+            return page.entries;
+        },
+        (entry) => entry.unique_time,
+    );
+    for (const entry of entries.slice(-RECENT_EVENTS).reverse()) {
+        const row = document.createElement("tr");
+        const td = cell(row, "");
+        td.appendChild(link(entry.event_id, async () => {
+            const eventDocument = await apiText("/topics/" + topicPath + "/events/by_event_id/"
+                + encodeURIComponent(entry.event_id));
+            const pre = byId("event-document");
+            try {
+                pre.textContent = JSON.stringify(JSON.parse(eventDocument), null, 2);
+            } catch (e) {
+                pre.textContent = eventDocument;
+            }
+            pre.hidden = false;
+        }));
+        cell(row, entry.descriptor_version === undefined ? "-" : String(entry.descriptor_version));
+        events.appendChild(row);
+    }
+}
+
+// Follow paged results and return the last item of the last page.
+async function lastPage(pathFn, itemsFn, cursorFn) {
+    let last = null;
+    let from = "";
+    for (let i = 0; i < MAX_PAGES; i++) {
+        const page = await apiJson(pathFn(from));
+        const items = itemsFn(page);
+        if (items.length > 0) {
+            last = items[items.length - 1];
+        }
+        if (!page.more || items.length === 0) {
+            break;
+        }
+        from = "?from=" + encodeURIComponent(cursorFn(last));
+    }
+    return last;
+}
+
+function reload() {
+    setStatus("");
+    loadHealth().catch((e) => setStatus(e.message));
+    loadTopics().catch((e) => setStatus(e.message));
+}
+
+window.addEventListener("DOMContentLoaded", () => {
+    byId("token-form").addEventListener("submit", (event) => {
+        event.preventDefault();
+        sessionStorage.setItem(TOKEN_KEY, byId("token").value.trim());
+        byId("token").value = "";
+        reload();
+    });
+    byId("token-clear").addEventListener("click", () => {
+        sessionStorage.removeItem(TOKEN_KEY);
+        byId("topic").hidden = true;
+        reload();
+    });
+    byId("topic-more").addEventListener("click", () => {
+        loadTopics(byId("topic-more").dataset.from).catch((e) => setStatus(e.message));
+    });
+    reload();
+});
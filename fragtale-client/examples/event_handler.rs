@@ -88,9 +88,17 @@ impl EventProcessor for Application {
                 println!("event_document: '{event_document}'");
                 let mut klingon_kill_count = 1;
                 // Query indexed events as a document database
-                let event_ids = event_source
+                let event_ids = match event_source
                     .event_ids_by_indexed_column("starfleet_captains", "user_id", "kirk")
-                    .await;
+                    .await
+                {
+                    Ok(event_ids) => event_ids,
+                    Err(e) if e.is_retryable() => {
+                        println!("Temporary failure, giving up on this event: {e}");
+                        return None;
+                    }
+                    Err(e) => panic!("Unexpected: Failed to query index: {e}"),
+                };
                 // Get latest event doc if there are multiple updates matched by the index
                 if let Some(event_id) = event_ids.first() {
                     let event_document = event_source
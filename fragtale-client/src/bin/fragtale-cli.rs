@@ -79,12 +79,15 @@ async fn event_by_topic_and_event_id(
     topic_id: &str,
     event_id: &str,
 ) -> ExitCode {
-    let event_opt = client.event_by_topic_and_event_id(topic_id, event_id).await;
-    if let Some(document) = event_opt {
-        // Show relevant info
-        log::info!("Event document: '{document}'");
-        return ExitCode::SUCCESS;
+    match client.event_by_topic_and_event_id(topic_id, event_id).await {
+        Ok(document) => {
+            // Show relevant info
+            log::info!("Event document: '{document}'");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            log::warn!("Failed to retrieve event: {e}");
+            ExitCode::FAILURE
+        }
     }
-    log::warn!("Failed to retrieve event!");
-    ExitCode::FAILURE
 }
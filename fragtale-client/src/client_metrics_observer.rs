@@ -0,0 +1,64 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Observer of client side metrics.
+
+use std::time::Duration;
+
+/// Observer of client side broker health.
+///
+/// Useful for exporting client side metrics to the caller's own metrics
+/// registry without wrapping every call. All methods do nothing by default,
+/// so implementations only need to provide the ones of interest.
+///
+/// See [crate::RestApiClient::with_metrics_observer] and
+/// [crate::EventClient::connect_with_metrics_observer].
+pub trait ClientMetricsObserver: Send + Sync {
+    /// Invoked when an event from `topic_id` has been received.
+    fn on_event_received(&self, topic_id: &str) {
+        let _ = topic_id;
+    }
+
+    /// Invoked when the delivery of an event from `topic_id` has been
+    /// confirmed `latency` after it was received.
+    fn on_delivery_confirmed(&self, topic_id: &str, latency: Duration) {
+        let _ = (topic_id, latency);
+    }
+
+    /// Invoked before the subscription to `topic_id` is re-established.
+    ///
+    /// `attempt` is the number of attempts since the connection was lost
+    /// starting from `1`.
+    fn on_reconnect(&self, topic_id: &str, attempt: u32) {
+        let _ = (topic_id, attempt);
+    }
+
+    /// Invoked before a failed publish to `topic_id` is retried.
+    ///
+    /// `attempt` is the number of the upcoming retry starting from `1`.
+    fn on_publish_retry(&self, topic_id: &str, attempt: u32) {
+        let _ = (topic_id, attempt);
+    }
+
+    /// Invoked when the caller has waited `wait` for the correlated result of
+    /// an event published to `topic_id`.
+    ///
+    /// `found` is `false` if the wait ended without a result.
+    fn on_correlation_wait(&self, topic_id: &str, wait: Duration, found: bool) {
+        let _ = (topic_id, wait, found);
+    }
+}
@@ -17,17 +17,35 @@
 
 //! Client abstraction for only dealing with processing of event documents.
 
+mod event_deduplicator;
 mod event_processor;
 mod event_source;
+mod event_validator;
+mod multiplexed_pool;
 mod web_socket_pool;
 
+pub use self::event_deduplicator::EventDeduplicator;
 pub use self::event_processor::EventProcessor;
 pub use self::event_source::EventSource;
+pub use self::event_validator::EventValidator;
+pub use self::multiplexed_pool::MultiplexedPool;
+pub use self::multiplexed_pool::MultiplexedSubscription;
+pub use self::web_socket_pool::BatchedEvent;
+pub use self::web_socket_pool::ConnectionState;
+pub use self::web_socket_pool::DeliveryBatching;
+pub use self::web_socket_pool::KeepAliveSettings;
 pub use self::web_socket_pool::SubscriberCommand;
 pub use self::web_socket_pool::SubscriberResponse;
 use self::web_socket_pool::WebSocketPool;
+pub use self::web_socket_pool::WireFormat;
+use crate::ClientError;
+use crate::ClientMetricsObserver;
 use crate::RestApiClient;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Instant;
+use tyst::Tyst;
+use tyst::encdec::hex::ToHex;
 
 /// Abstraction for client that is only dealing with event messages.
 pub struct EventClient {
@@ -35,12 +53,22 @@ pub struct EventClient {
     web_socket_pool_subscribe: Arc<WebSocketPool>,
     web_socket_pool_ack: Arc<WebSocketPool>,
     web_socket_pool_publish: Arc<WebSocketPool>,
+    multiplexed_subscription: Option<MultiplexedSubscription>,
     event_processor: Arc<dyn EventProcessor>,
+    event_deduplicator: Option<Arc<EventDeduplicator>>,
+    event_validator: Option<Arc<EventValidator>>,
+    metrics_observer: Option<Arc<dyn ClientMetricsObserver>>,
+    delivery_batching: DeliveryBatching,
+    pending_confirmations: Mutex<Vec<SubscriberCommand>>,
 }
 
 #[async_trait::async_trait]
 impl EventSource for EventClient {
-    async fn event_by_topic_and_event_id(&self, topic_id: &str, event_id: &str) -> Option<String> {
+    async fn event_by_topic_and_event_id(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+    ) -> Result<String, ClientError> {
         self.rest_api_client
             .event_by_topic_and_event_id(topic_id, event_id)
             .await
@@ -51,7 +79,7 @@ impl EventSource for EventClient {
         topic_id: &str,
         index_name: &str,
         index_key: &str,
-    ) -> Vec<String> {
+    ) -> Result<Vec<String>, ClientError> {
         self.rest_api_client
             .event_ids_by_topic_and_index(topic_id, index_name, index_key)
             .await
@@ -64,9 +92,34 @@ impl EventClient {
     /// Package version reported by Cargo at build time.
     const CARGO_PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-    /// Interval between ping at the client to keep-alive the connection.
+    /// Default interval between ping at the client to keep-alive the
+    /// connection.
+    ///
+    /// See [KeepAliveSettings] for how to request another interval.
     pub const PING_INTERVAL_MICROS: u64 = WebSocketPool::PING_INTERVAL_MICROS;
 
+    /// Environment variable with comma separated kinds of events to consume
+    /// from a multi-type topic.
+    const ENV_EVENT_TYPES: &str = "EVENT_TYPES";
+
+    /// Environment variable with the lowest priority of events to consume.
+    const ENV_MIN_PRIORITY: &str = "MIN_PRIORITY";
+
+    /// Environment variable with comma separated JSON Pointers of the fields
+    /// of interest in each event document.
+    const ENV_FIELDS: &str = "FIELDS";
+
+    /// Environment variable with the identifier of this client instance within
+    /// its consumer group.
+    const ENV_MEMBER_ID: &str = "MEMBER_ID";
+
+    /// Environment variable with the host name (e.g. the Kubernetes Pod name).
+    const ENV_HOSTNAME: &str = "HOSTNAME";
+
+    /// Environment variable that enables sharing of WebSocket connections
+    /// between subscriptions when set to `true`.
+    const ENV_SHARED_CONNECTIONS: &str = "SHARED_CONNECTIONS";
+
     /// Connect a new instance.
     ///
     /// This will spawn off background jobs for consuming events and deliver
@@ -79,40 +132,308 @@ impl EventClient {
         publish_to_topic_id: &str,
         event_processor: Box<Arc<dyn EventProcessor>>,
         concurrency: usize,
+    ) -> Arc<Self> {
+        Self::connect_internal(
+            event_service_base_url,
+            consume_from_topic_id,
+            publish_to_topic_id,
+            event_processor,
+            concurrency,
+            None,
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Connect a new instance that skips redelivered events that have already
+    /// been processed.
+    ///
+    /// See [Self::connect] and [EventDeduplicator].
+    pub async fn connect_with_deduplication(
+        event_service_base_url: &str,
+        consume_from_topic_id: &str,
+        publish_to_topic_id: &str,
+        event_processor: Box<Arc<dyn EventProcessor>>,
+        concurrency: usize,
+        event_deduplicator: Arc<EventDeduplicator>,
+    ) -> Arc<Self> {
+        Self::connect_internal(
+            event_service_base_url,
+            consume_from_topic_id,
+            publish_to_topic_id,
+            event_processor,
+            concurrency,
+            Some(event_deduplicator),
+            None,
+            None,
+        )
+        .await
+    }
+
+    /// Connect a new instance that validates incoming event documents against
+    /// the topic's event description before processing.
+    ///
+    /// Documents that fail validation are logged and skipped.
+    ///
+    /// See [Self::connect] and [EventValidator].
+    pub async fn connect_with_validation(
+        event_service_base_url: &str,
+        consume_from_topic_id: &str,
+        publish_to_topic_id: &str,
+        event_processor: Box<Arc<dyn EventProcessor>>,
+        concurrency: usize,
+        event_validator: Arc<EventValidator>,
+    ) -> Arc<Self> {
+        Self::connect_internal(
+            event_service_base_url,
+            consume_from_topic_id,
+            publish_to_topic_id,
+            event_processor,
+            concurrency,
+            None,
+            Some(event_validator),
+            None,
+        )
+        .await
+    }
+
+    /// Connect a new instance that notifies the provided
+    /// [ClientMetricsObserver] about received and confirmed events,
+    /// reconnects, publish retries and correlation waits.
+    ///
+    /// See [Self::connect].
+    pub async fn connect_with_metrics_observer(
+        event_service_base_url: &str,
+        consume_from_topic_id: &str,
+        publish_to_topic_id: &str,
+        event_processor: Box<Arc<dyn EventProcessor>>,
+        concurrency: usize,
+        metrics_observer: Arc<dyn ClientMetricsObserver>,
+    ) -> Arc<Self> {
+        Self::connect_internal(
+            event_service_base_url,
+            consume_from_topic_id,
+            publish_to_topic_id,
+            event_processor,
+            concurrency,
+            None,
+            None,
+            Some(metrics_observer),
+        )
+        .await
+    }
+
+    /// Return the comma separated kinds of events of interest from the
+    /// environment variable `EVENT_TYPES` (if any).
+    fn event_types_from_env() -> Option<String> {
+        std::env::var(Self::ENV_EVENT_TYPES)
+            .ok()
+            .map(|event_types| event_types.replace(' ', ""))
+            .filter(|event_types| !event_types.is_empty())
+    }
+
+    /// Return the lowest priority of events of interest from the environment
+    /// variable `MIN_PRIORITY` (if any).
+    fn min_priority_from_env() -> Option<u8> {
+        std::env::var(Self::ENV_MIN_PRIORITY)
+            .ok()
+            .and_then(|min_priority| min_priority.trim().parse::<u8>().ok())
+    }
+
+    /// Return the comma separated JSON Pointers of the fields of interest from
+    /// the environment variable `FIELDS` (if any).
+    fn fields_from_env() -> Option<String> {
+        std::env::var(Self::ENV_FIELDS)
+            .ok()
+            .map(|fields| fields.replace(' ', ""))
+            .filter(|fields| !fields.is_empty())
+    }
+
+    /// Append the JSON Pointers of the fields of interest from the environment
+    /// variable `FIELDS` (if any) as a query parameter to `url`.
+    ///
+    /// Only these fields of each event document will be delivered to this
+    /// consumer.
+    fn append_fields_to_url(url: &str) -> String {
+        match Self::fields_from_env() {
+            Some(fields) if url.contains('?') => format!("{url}&fields={fields}"),
+            Some(fields) => format!("{url}?fields={fields}"),
+            None => url.to_owned(),
+        }
+    }
+
+    /// Append the kinds of events of interest from the environment variable
+    /// `EVENT_TYPES` (if any) as a query parameter to `url`.
+    ///
+    /// Events of other kinds in a multi-type topic will not be delivered to
+    /// this consumer.
+    fn append_event_types_to_url(url: &str) -> String {
+        match Self::event_types_from_env() {
+            Some(event_types) if url.contains('?') => format!("{url}&type={event_types}"),
+            Some(event_types) => format!("{url}?type={event_types}"),
+            None => url.to_owned(),
+        }
+    }
+
+    /// Append the lowest priority of events of interest from the environment
+    /// variable `MIN_PRIORITY` (if any) as a query parameter to `url`.
+    ///
+    /// Events published with a lower priority will not be delivered to this
+    /// consumer.
+    fn append_min_priority_to_url(url: &str) -> String {
+        match Self::min_priority_from_env() {
+            Some(min_priority) if url.contains('?') => format!("{url}&min_priority={min_priority}"),
+            Some(min_priority) => format!("{url}?min_priority={min_priority}"),
+            None => url.to_owned(),
+        }
+    }
+
+    /// Return the identifier of this client instance within its consumer
+    /// group.
+    ///
+    /// This is the environment variable `MEMBER_ID` or `HOSTNAME` with any
+    /// characters that are not URL safe removed. A random identifier is
+    /// used if neither is present.
+    fn member_id() -> String {
+        [Self::ENV_MEMBER_ID, Self::ENV_HOSTNAME]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .map(|value| {
+                value
+                    .chars()
+                    .filter(|c| c.is_ascii_alphanumeric() || ['-', '_', '.'].contains(c))
+                    .take(64)
+                    .collect::<String>()
+            })
+            .find(|member_id| !member_id.is_empty())
+            .unwrap_or_else(|| {
+                Tyst::instance()
+                    .prng_get_random_bytes(None, 8)
+                    .as_slice()
+                    .to_hex()
+            })
+    }
+
+    /// Append the identifier of this client instance within its consumer
+    /// group as a query parameter to `url`.
+    fn append_member_id_to_url(url: &str, member_id: &str) -> String {
+        if url.contains('?') {
+            format!("{url}&member={member_id}")
+        } else {
+            format!("{url}?member={member_id}")
+        }
+    }
+
+    async fn connect_internal(
+        event_service_base_url: &str,
+        consume_from_topic_id: &str,
+        publish_to_topic_id: &str,
+        event_processor: Box<Arc<dyn EventProcessor>>,
+        concurrency: usize,
+        event_deduplicator: Option<Arc<EventDeduplicator>>,
+        event_validator: Option<Arc<EventValidator>>,
+        metrics_observer: Option<Arc<dyn ClientMetricsObserver>>,
     ) -> Arc<Self> {
         let max_pool_size_multiplier = std::cmp::max(1, concurrency);
-        let rest_api_client = RestApiClient::new(
+        let mut rest_api_client = RestApiClient::new(
             event_service_base_url,
             Self::CARGO_PKG_NAME,
             Self::CARGO_PKG_VERSION,
             max_pool_size_multiplier,
         )
         .await;
+        if let Some(metrics_observer) = &metrics_observer {
+            rest_api_client = rest_api_client.with_metrics_observer(Arc::clone(metrics_observer));
+        }
+        let keep_alive_settings = KeepAliveSettings::from_env();
+        let ping_interval_micros = keep_alive_settings.ping_interval_micros();
+        let wire_format = WireFormat::from_env();
+        let delivery_batching = DeliveryBatching::from_env();
+        let member_id = Self::member_id();
         let web_socket_pool_subscribe = WebSocketPool::new(
-            &format!("{event_service_base_url}/topics/{consume_from_topic_id}/subscribe"),
+            &Self::append_member_id_to_url(
+                &delivery_batching.append_to_url(&wire_format.append_to_url(
+                    &keep_alive_settings.append_to_url(&Self::append_fields_to_url(
+                        &Self::append_min_priority_to_url(&Self::append_event_types_to_url(
+                            &format!(
+                                "{event_service_base_url}/topics/{consume_from_topic_id}/subscribe"
+                            ),
+                        )),
+                    )),
+                )),
+                &member_id,
+            ),
             max_pool_size_multiplier * 16,
             1,
+            ping_interval_micros,
+            wire_format,
         )
         .await;
+        let event_processor_clone = Arc::clone(&event_processor);
+        let subscribed_topic_id = consume_from_topic_id.to_owned();
+        let metrics_observer_clone = metrics_observer.clone();
+        web_socket_pool_subscribe.set_connection_state_listener(move |connection_state| {
+            if let Some(metrics_observer) = &metrics_observer_clone
+                && let ConnectionState::Reconnecting { attempt, .. } = connection_state
+            {
+                metrics_observer.on_reconnect(&subscribed_topic_id, attempt);
+            }
+            event_processor_clone.connection_state_hook(&subscribed_topic_id, connection_state)
+        });
         let web_socket_pool_ack = WebSocketPool::new(
-            &format!("{event_service_base_url}/topics/{consume_from_topic_id}/confirm"),
+            &Self::append_member_id_to_url(
+                &format!("{event_service_base_url}/topics/{consume_from_topic_id}/confirm"),
+                &member_id,
+            ),
             max_pool_size_multiplier,
             1,
+            ping_interval_micros,
+            wire_format,
         )
         .await;
         let web_socket_pool_publish = WebSocketPool::new(
             &format!("{event_service_base_url}/topics/{publish_to_topic_id}/events"),
             max_pool_size_multiplier,
             1,
+            ping_interval_micros,
+            wire_format,
         )
         .await;
+        // The dedicated subscribe and confirm pools connect lazily and are
+        // left unused when the connections are shared.
+        let multiplexed_subscription = if std::env::var(Self::ENV_SHARED_CONNECTIONS)
+            .is_ok_and(|value| value.trim().eq_ignore_ascii_case("true"))
+        {
+            Some(
+                MultiplexedPool::shared(event_service_base_url)
+                    .await
+                    .subscribe(
+                        consume_from_topic_id,
+                        Self::event_types_from_env()
+                            .map(|event_types| event_types.split(',').map(str::to_owned).collect()),
+                        Self::min_priority_from_env(),
+                        Self::fields_from_env()
+                            .map(|fields| fields.split(',').map(str::to_owned).collect()),
+                    )
+                    .await,
+            )
+        } else {
+            None
+        };
         Arc::new(Self {
             rest_api_client,
             //event_client_config,
             web_socket_pool_subscribe,
             web_socket_pool_ack,
             web_socket_pool_publish,
+            multiplexed_subscription,
             event_processor: Arc::clone(&event_processor),
+            event_deduplicator,
+            event_validator,
+            metrics_observer,
+            delivery_batching,
+            pending_confirmations: Mutex::new(Vec::new()),
         })
         .init(
             max_pool_size_multiplier * 16 * 4,
@@ -129,9 +450,32 @@ impl EventClient {
         publish_to_topic_id: &str,
         subscribed_topic_id: &str,
     ) -> Arc<Self> {
-        self.rest_api_client
+        if let Err(e) = self
+            .rest_api_client
             .register_topic(publish_to_topic_id, None)
-            .await;
+            .await
+        {
+            log::info!("Failed to register topic '{publish_to_topic_id}': {e}");
+        }
+        // Pair the topics, so publishers can await the correlated result of
+        // their requests without knowing where the results are published.
+        if let Err(e) = self
+            .rest_api_client
+            .register_reply_topic(subscribed_topic_id, publish_to_topic_id)
+            .await
+        {
+            log::info!(
+                "Failed to register '{publish_to_topic_id}' as reply topic for '{subscribed_topic_id}': {e}"
+            );
+        }
+        if self.event_validator.is_some() {
+            self.refresh_event_descriptor(subscribed_topic_id).await;
+        }
+        // Confirm deliveries that linger in an incomplete batch
+        if self.delivery_batching.is_enabled() && self.multiplexed_subscription.is_none() {
+            let self_clone = Arc::clone(&self);
+            tokio::spawn(async move { self_clone.flush_confirmations_periodically().await });
+        }
         // Start N concurrent tasks polling for new messages
         for i in 0..task_count {
             let self_clone = Arc::clone(&self);
@@ -149,26 +493,44 @@ impl EventClient {
             event_document,
             correlation_token,
             delivery_instance_id,
-        }) = self.web_socket_pool_subscribe.next().await
+            ..
+        }) = self.next_delivery().await
         {
             if log::log_enabled!(log::Level::Trace) {
                 log::trace!("event_document: {event_document:?}");
             }
+            let received = Instant::now();
+            if let Some(metrics_observer) = &self.metrics_observer {
+                metrics_observer.on_event_received(subscribed_topic_id);
+            }
             self.confirm_delivery_ws(encoded_unique_time, delivery_instance_id)
                 .await;
+            if let Some(metrics_observer) = &self.metrics_observer {
+                metrics_observer.on_delivery_confirmed(subscribed_topic_id, received.elapsed());
+            }
             if log::log_enabled!(log::Level::Trace) {
                 log::trace!("Confirmed: {event_document}");
             }
-            let subscribed_topic_id = subscribed_topic_id.to_owned();
+            if let Some(event_deduplicator) = &self.event_deduplicator
+                && event_deduplicator
+                    .is_processed(subscribed_topic_id, encoded_unique_time)
+                    .await
+            {
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!("Skipping already processed event {encoded_unique_time}.");
+                }
+                continue;
+            }
+            if let Err(e) = self.validate(subscribed_topic_id, &event_document).await {
+                log::warn!("Skipping invalid event {encoded_unique_time}: {e}");
+                continue;
+            }
+            let topic_id = subscribed_topic_id.to_owned();
             let event_processor = Arc::clone(&self.event_processor);
             let event_source = Arc::clone(self) as Arc<dyn EventSource>;
             let result_document = tokio::task::spawn(async move {
                 event_processor
-                    .process_message(
-                        subscribed_topic_id,
-                        event_document.to_owned(),
-                        event_source.as_ref(),
-                    )
+                    .process_message(topic_id, event_document.to_string(), event_source.as_ref())
                     .await
             })
             .await
@@ -183,23 +545,149 @@ impl EventClient {
             } else if log::log_enabled!(log::Level::Debug) {
                 log::debug!("Failed to process event.");
             }
+            if let Some(event_deduplicator) = &self.event_deduplicator {
+                event_deduplicator
+                    .mark_processed(subscribed_topic_id, encoded_unique_time)
+                    .await;
+            }
         }
         if log::log_enabled!(log::Level::Debug) {
             log::debug!("Will not handle additional messages.");
         }
     }
 
+    /// Validate the event document if validation is enabled.
+    ///
+    /// A failed validation might be due to a newer event description, so it
+    /// will be fetched again (at most once per refresh interval) before the
+    /// document is considered invalid.
+    async fn validate(&self, topic_id: &str, event_document: &str) -> Result<(), String> {
+        let Some(event_validator) = &self.event_validator else {
+            return Ok(());
+        };
+        let result = event_validator.validate(event_document).await;
+        if result.is_err() && event_validator.is_refresh_due() {
+            self.refresh_event_descriptor(topic_id).await;
+            return event_validator.validate(event_document).await;
+        }
+        result
+    }
+
+    /// Fetch and compile the latest event description of the topic and notify
+    /// the [EventProcessor] if its version is unexpected.
+    async fn refresh_event_descriptor(&self, topic_id: &str) {
+        let Some(event_validator) = &self.event_validator else {
+            return;
+        };
+        let event_descriptor = match self
+            .rest_api_client
+            .event_descriptor_by_topic(topic_id)
+            .await
+        {
+            Ok(Some(event_descriptor)) => event_descriptor,
+            Ok(None) => {
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!("No event description available for topic '{topic_id}'.");
+                }
+                return;
+            }
+            Err(e) => {
+                log::info!("Failed to get event description of topic '{topic_id}': {e}");
+                return;
+            }
+        };
+        match event_validator.update(&event_descriptor).await {
+            Ok(Some(actual_version)) => {
+                if let Some(expected_version) = event_validator.get_expected_version() {
+                    self.event_processor.descriptor_version_mismatch_hook(
+                        topic_id,
+                        expected_version,
+                        actual_version,
+                    );
+                }
+            }
+            Ok(None) => {}
+            Err(e) => {
+                log::warn!("Unable to use event description of topic '{topic_id}': {e}");
+            }
+        }
+    }
+
+    /// Get the next delivered event from the dedicated or shared
+    /// connections.
+    async fn next_delivery(&self) -> Option<SubscriberResponse> {
+        if let Some(multiplexed_subscription) = &self.multiplexed_subscription {
+            multiplexed_subscription.next().await
+        } else {
+            self.web_socket_pool_subscribe.next().await
+        }
+    }
+
     /// Confirm that the even was recieved.
+    ///
+    /// A reconnected subscription will resume after the latest confirmed
+    /// event.
     async fn confirm_delivery_ws(&self, encoded_unique_time: u64, delivery_instance_id: u16) {
-        Arc::clone(&self.web_socket_pool_ack)
-            .send(
-                &SubscriberCommand::AckDelivery {
-                    encoded_unique_time,
-                    delivery_instance_id,
-                },
-                false,
-            )
+        if let Some(multiplexed_subscription) = &self.multiplexed_subscription {
+            multiplexed_subscription
+                .confirm(encoded_unique_time, delivery_instance_id)
+                .await;
+            return;
+        }
+        let command = SubscriberCommand::AckDelivery {
+            encoded_unique_time,
+            delivery_instance_id,
+        };
+        if self.delivery_batching.is_enabled() {
+            let full_batch = {
+                let mut pending_confirmations = self.pending_confirmations.lock().unwrap();
+                pending_confirmations.push(command);
+                (pending_confirmations.len() >= self.delivery_batching.get_max_batch_size())
+                    .then(|| std::mem::take(&mut *pending_confirmations))
+            };
+            if let Some(commands) = full_batch {
+                self.send_confirmations(commands).await;
+            }
+        } else {
+            Arc::clone(&self.web_socket_pool_ack)
+                .send(&command, false)
+                .await;
+        }
+        self.web_socket_pool_subscribe
+            .resume_from(encoded_unique_time);
+    }
+
+    /// Send pending confirmations that did not fill up a batch at regular
+    /// intervals.
+    async fn flush_confirmations_periodically(&self) {
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_millis(
+                DeliveryBatching::CONFIRMATION_LINGER_MILLIS,
+            ))
             .await;
+            let commands = std::mem::take(&mut *self.pending_confirmations.lock().unwrap());
+            if !commands.is_empty() {
+                self.send_confirmations(commands).await;
+            }
+        }
+    }
+
+    /// Send confirmations as a single batch when possible.
+    async fn send_confirmations(&self, commands: Vec<SubscriberCommand>) {
+        match SubscriberCommand::batch(commands) {
+            Ok(command) => {
+                Arc::clone(&self.web_socket_pool_ack)
+                    .send(&command, false)
+                    .await;
+            }
+            Err(commands) => {
+                for command in &commands {
+                    Arc::clone(&self.web_socket_pool_ack)
+                        .send(command, false)
+                        .await;
+                }
+            }
+        }
     }
 
     /// Publish the result of the processing.
@@ -219,6 +707,7 @@ impl EventClient {
                     event_document: document.to_owned(),
                     correlation_token,
                     descriptor_version: None,
+                    expires_ts: None,
                 },
                 false,
             )
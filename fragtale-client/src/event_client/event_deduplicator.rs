@@ -0,0 +1,222 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Persistent deduplication of redelivered events.
+
+use crossbeam_skiplist::SkipMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs::File;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/** Bounded and persistent set of recently processed events per topic.
+
+Event delivery is at-least-once, so an event might be redelivered if the
+confirmation of the delivery was lost or the process restarted before it was
+sent. Events are identified by their encoded `UniqueTime` and the most recently
+processed ones are appended to a file per topic in the state directory, so
+redeliveries can be skipped even across process restarts.
+*/
+pub struct EventDeduplicator {
+    state_directory: PathBuf,
+    capacity: usize,
+    by_topic: SkipMap<String, Arc<Mutex<ProcessedEvents>>>,
+}
+
+/// Recently processed events of a single topic.
+struct ProcessedEvents {
+    file: Option<File>,
+    file_records: usize,
+    order: VecDeque<u64>,
+    lookup: HashSet<u64>,
+}
+
+impl EventDeduplicator {
+    /// Size of each persisted record.
+    const RECORD_SIZE: usize = 8;
+
+    /// Return a new instance that keeps up to `capacity` processed events per
+    /// topic in files under `state_directory`.
+    pub async fn new(state_directory: &str, capacity: usize) -> Arc<Self> {
+        let state_directory = PathBuf::from(state_directory);
+        if let Err(e) = tokio::fs::create_dir_all(&state_directory).await {
+            log::warn!(
+                "Unable to create deduplication state directory '{}': {e}",
+                state_directory.display()
+            );
+        }
+        Arc::new(Self {
+            state_directory,
+            capacity: std::cmp::max(1, capacity),
+            by_topic: SkipMap::default(),
+        })
+    }
+
+    /// Return `true` if the event has already been processed.
+    pub async fn is_processed(&self, topic_id: &str, encoded_unique_time: u64) -> bool {
+        self.by_topic_id(topic_id)
+            .await
+            .lock()
+            .await
+            .lookup
+            .contains(&encoded_unique_time)
+    }
+
+    /// Remember that the event has been processed.
+    pub async fn mark_processed(&self, topic_id: &str, encoded_unique_time: u64) {
+        let processed_events = self.by_topic_id(topic_id).await;
+        let mut processed_events = processed_events.lock().await;
+        if !processed_events.lookup.insert(encoded_unique_time) {
+            return;
+        }
+        processed_events.order.push_back(encoded_unique_time);
+        while processed_events.order.len() > self.capacity {
+            if let Some(evicted) = processed_events.order.pop_front() {
+                processed_events.lookup.remove(&evicted);
+            }
+        }
+        if processed_events.file_records >= self.capacity * 2 {
+            self.compact(topic_id, &mut processed_events).await;
+        } else if let Some(file) = processed_events.file.as_mut() {
+            // Flush right away, since tokio might otherwise still be writing
+            // when the file is dropped.
+            let bytes = encoded_unique_time.to_be_bytes();
+            if let Err(e) = async {
+                file.write_all(&bytes).await?;
+                file.flush().await
+            }
+            .await
+            {
+                log::warn!("Failed to persist processed event for topic '{topic_id}': {e}");
+            }
+            processed_events.file_records += 1;
+        }
+    }
+
+    /// Return the processed events of a topic, loading them from the state
+    /// directory the first time.
+    async fn by_topic_id(&self, topic_id: &str) -> Arc<Mutex<ProcessedEvents>> {
+        if let Some(entry) = self.by_topic.get(topic_id) {
+            return Arc::clone(entry.value());
+        }
+        let processed_events = Arc::new(Mutex::new(self.load(topic_id).await));
+        Arc::clone(
+            self.by_topic
+                .get_or_insert(topic_id.to_owned(), processed_events)
+                .value(),
+        )
+    }
+
+    fn file_path(&self, topic_id: &str) -> PathBuf {
+        self.state_directory.join(format!("{topic_id}.processed"))
+    }
+
+    /// Load persisted records and open the file for appending new ones.
+    async fn load(&self, topic_id: &str) -> ProcessedEvents {
+        let file_path = self.file_path(topic_id);
+        let content = tokio::fs::read(&file_path).await.unwrap_or_default();
+        let records = content
+            .chunks_exact(Self::RECORD_SIZE)
+            .map(|chunk| u64::from_be_bytes(chunk.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        let mut processed_events = ProcessedEvents {
+            file: None,
+            file_records: records.len(),
+            order: VecDeque::with_capacity(self.capacity),
+            lookup: HashSet::with_capacity(self.capacity),
+        };
+        for encoded_unique_time in records.into_iter().rev() {
+            if processed_events.order.len() >= self.capacity {
+                break;
+            }
+            if processed_events.lookup.insert(encoded_unique_time) {
+                processed_events.order.push_front(encoded_unique_time);
+            }
+        }
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "Loaded {} recently processed events of topic '{topic_id}'.",
+                processed_events.order.len()
+            );
+        }
+        // Rewrite to drop any partial record and evicted entries
+        self.compact(topic_id, &mut processed_events).await;
+        processed_events
+    }
+
+    /// Replace the persisted records with the ones currently retained.
+    async fn compact(&self, topic_id: &str, processed_events: &mut ProcessedEvents) {
+        let file_path = self.file_path(topic_id);
+        let temp_file_path = file_path.with_extension("compacting");
+        let content = processed_events
+            .order
+            .iter()
+            .flat_map(|encoded_unique_time| encoded_unique_time.to_be_bytes())
+            .collect::<Vec<_>>();
+        processed_events.file = None;
+        let result = async {
+            tokio::fs::write(&temp_file_path, &content).await?;
+            tokio::fs::rename(&temp_file_path, &file_path).await?;
+            OpenOptions::new().append(true).open(&file_path).await
+        }
+        .await;
+        match result {
+            Ok(file) => {
+                processed_events.file = Some(file);
+                processed_events.file_records = processed_events.order.len();
+            }
+            Err(e) => {
+                log::warn!(
+                    "Failed to persist processed events for topic '{topic_id}' to '{}': {e}",
+                    file_path.display()
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn survives_restart_and_stays_bounded() {
+        let state_directory = std::env::temp_dir().join(format!(
+            "fragtale-dedup-test-{}",
+            crate::time::get_timestamp_micros()
+        ));
+        let state_directory = state_directory.to_str().unwrap();
+        let deduplicator = EventDeduplicator::new(state_directory, 4).await;
+        for encoded_unique_time in 0..10 {
+            deduplicator
+                .mark_processed("topic", encoded_unique_time)
+                .await;
+        }
+        assert!(!deduplicator.is_processed("topic", 5).await);
+        assert!(deduplicator.is_processed("topic", 6).await);
+        drop(deduplicator);
+        let deduplicator = EventDeduplicator::new(state_directory, 4).await;
+        assert!(deduplicator.is_processed("topic", 9).await);
+        assert!(!deduplicator.is_processed("topic", 5).await);
+        assert!(!deduplicator.is_processed("other", 9).await);
+        tokio::fs::remove_dir_all(state_directory).await.unwrap();
+    }
+}
@@ -17,7 +17,9 @@
 
 //! Processor of event documents.
 
+use super::ConnectionState;
 use super::EventSource;
+use crate::mb::event_descriptor::DescriptorVersion;
 
 /// Process event documents.
 #[async_trait::async_trait]
@@ -36,4 +38,29 @@ pub trait EventProcessor: Send + Sync + 'static {
     fn post_subscribed_hook(&self, topic_id: &str) {
         let _ = topic_id;
     }
+
+    /// Invoked when the state of the subscription's connections changes.
+    ///
+    /// Lost connections are re-established automatically and delivery
+    /// resumes after the last confirmed event. Not invoked when connections
+    /// are shared between subscriptions (see
+    /// [MultiplexedPool](super::MultiplexedPool)).
+    fn connection_state_hook(&self, topic_id: &str, connection_state: ConnectionState) {
+        let _ = (topic_id, connection_state);
+    }
+
+    /// Invoked when validation is enabled and the topic's latest event
+    /// description is incompatible with the expected version.
+    ///
+    /// See [EventValidator](super::EventValidator).
+    fn descriptor_version_mismatch_hook(
+        &self,
+        topic_id: &str,
+        expected_version: DescriptorVersion,
+        actual_version: DescriptorVersion,
+    ) {
+        log::warn!(
+            "Event description of topic '{topic_id}' has version {actual_version:?}, but {expected_version:?} was expected."
+        );
+    }
 }
@@ -15,11 +15,19 @@
     limitations under the License.
 */
 
+use crate::ClientError;
+
 /// Query interface for the event source.
 #[async_trait::async_trait]
 pub trait EventSource: Send + Sync {
     /// Get an event document by its identifier.
-    async fn event_by_topic_and_event_id(&self, topic_id: &str, event_id: &str) -> Option<String>;
+    ///
+    /// Return [ClientError::NotFound] if no such event exists.
+    async fn event_by_topic_and_event_id(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+    ) -> Result<String, ClientError>;
 
     /// Get all event identifiers where `index_name` exactly has `index_key`
     /// entries.
@@ -30,5 +38,5 @@ pub trait EventSource: Send + Sync {
         topic_id: &str,
         index_name: &str,
         index_key: &str,
-    ) -> Vec<String>;
+    ) -> Result<Vec<String>, ClientError>;
 }
@@ -0,0 +1,256 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Consumer side validation of event documents.
+
+use crate::mb::event_descriptor::DescriptorVersion;
+use crate::mb::event_descriptor::EventDescriptor;
+use crate::mb::event_descriptor::EventSchema;
+use jsonschema::Draft;
+use jsonschema::Validator;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use tokio::sync::Mutex;
+
+/** Validation of incoming event documents before processing.
+
+The topic's [EventDescriptor] is fetched from `fragtale` and its JSON schemas
+(including the ones of each kind of event in multi-type topics) are compiled
+locally. Documents that don't comply are never handed to the
+[EventProcessor](super::EventProcessor).
+
+When the expected [DescriptorVersion] is provided, the
+[EventProcessor::descriptor_version_mismatch_hook](super::EventProcessor::descriptor_version_mismatch_hook)
+is invoked whenever the topic's latest description is of an incompatible
+major version or newer than expected.
+*/
+pub struct EventValidator {
+    expected_version: Option<DescriptorVersion>,
+    compiled: Mutex<Option<Arc<CompiledEventDescriptor>>>,
+    updated_micros: AtomicU64,
+}
+
+/// Locally compiled schemas of an [EventDescriptor].
+struct CompiledEventDescriptor {
+    version: DescriptorVersion,
+    event_type_field: Option<String>,
+    validator: Option<Validator>,
+    validators_by_event_type: HashMap<String, Option<Validator>>,
+}
+
+impl EventValidator {
+    /// Minimum time between fetching a new description after a failed
+    /// validation.
+    const REFRESH_INTERVAL_MICROS: u64 = 10_000_000;
+
+    /// Return a new instance.
+    ///
+    /// `expected_version` is the description version that the consumer was
+    /// built for. Use `None` to skip version checks.
+    pub fn new(expected_version: Option<DescriptorVersion>) -> Arc<Self> {
+        Arc::new(Self {
+            expected_version,
+            compiled: Mutex::default(),
+            updated_micros: AtomicU64::default(),
+        })
+    }
+
+    /// Return the version of the currently used description (if any).
+    pub async fn get_version(&self) -> Option<DescriptorVersion> {
+        self.compiled
+            .lock()
+            .await
+            .as_ref()
+            .map(|compiled| compiled.version)
+    }
+
+    /// Compile and start using a (new) description of the topic's events.
+    ///
+    /// Return the version of the new description if it is different from the
+    /// previously used one and incompatible with the expected version.
+    pub async fn update(
+        &self,
+        event_descriptor: &EventDescriptor,
+    ) -> Result<Option<DescriptorVersion>, String> {
+        self.updated_micros
+            .store(crate::time::get_timestamp_micros(), Ordering::Relaxed);
+        let compiled = Arc::new(CompiledEventDescriptor::compile(event_descriptor)?);
+        let version = compiled.version;
+        let previous = self.compiled.lock().await.replace(compiled);
+        if previous.is_some_and(|previous| previous.version == version) {
+            return Ok(None);
+        }
+        Ok(self
+            .expected_version
+            .filter(|expected| Self::is_version_mismatch(expected, &version))
+            .map(|_| version))
+    }
+
+    /// Return the version the consumer was built for (if any).
+    pub fn get_expected_version(&self) -> Option<DescriptorVersion> {
+        self.expected_version
+    }
+
+    /// Return `true` if enough time has passed since the last update to fetch
+    /// the description again.
+    pub(super) fn is_refresh_due(&self) -> bool {
+        self.updated_micros.load(Ordering::Relaxed) + Self::REFRESH_INTERVAL_MICROS
+            < crate::time::get_timestamp_micros()
+    }
+
+    /// Return `true` if `actual` is of another major version or newer than
+    /// `expected`.
+    fn is_version_mismatch(expected: &DescriptorVersion, actual: &DescriptorVersion) -> bool {
+        expected.get_major() != actual.get_major() || actual > expected
+    }
+
+    /// Validate the event document using the current description.
+    ///
+    /// Documents are always considered valid until a description is known.
+    pub async fn validate(&self, event_document: &str) -> Result<(), String> {
+        let Some(compiled) = self.compiled.lock().await.as_ref().map(Arc::clone) else {
+            return Ok(());
+        };
+        compiled.validate(event_document)
+    }
+}
+
+impl CompiledEventDescriptor {
+    /// Compile all schemas of the description.
+    fn compile(event_descriptor: &EventDescriptor) -> Result<Self, String> {
+        let validators_by_event_type = event_descriptor
+            .get_event_types()
+            .iter()
+            .flatten()
+            .map(|event_type_descriptor| {
+                Self::compile_schema(event_type_descriptor.get_event_schema())
+                    .map(|validator| (event_type_descriptor.get_event_type().to_owned(), validator))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+        Ok(Self {
+            version: DescriptorVersion::from_encoded(event_descriptor.get_version()),
+            event_type_field: event_descriptor.get_event_type_field().to_owned(),
+            validator: Self::compile_schema(event_descriptor.get_event_schema())?,
+            validators_by_event_type,
+        })
+    }
+
+    /// Compile a single schema (if present).
+    fn compile_schema(event_schema_opt: &Option<EventSchema>) -> Result<Option<Validator>, String> {
+        let Some(event_schema) = event_schema_opt else {
+            return Ok(None);
+        };
+        match event_schema.get_schema_type() {
+            "https://json-schema.org/draft/2020-12/schema" => {
+                let schema = serde_json::from_str(event_schema.get_schema_data())
+                    .map_err(|e| format!("Failed to parse schema as JSON: {e:?}"))?;
+                jsonschema::options()
+                    .with_draft(Draft::Draft202012)
+                    .build(&schema)
+                    .map(Some)
+                    .map_err(|e| format!("Failed to compile JSONSchema: {e:?}"))
+            }
+            schema_type => Err(format!("Unsupported schema type: '{schema_type}'")),
+        }
+    }
+
+    /// Validate document against the topic's schema and the schema of the
+    /// kind of event (if any).
+    fn validate(&self, event_document: &str) -> Result<(), String> {
+        let document = serde_json::from_str::<Value>(event_document)
+            .map_err(|e| format!("Failed to parse document as JSON: {e:?}"))?;
+        if let Some(validator) = &self.validator {
+            Self::validate_with(validator, &document)?;
+        }
+        if let Some(event_type_field) = &self.event_type_field {
+            let event_type = document
+                .pointer(event_type_field)
+                .and_then(Value::as_str)
+                .ok_or_else(|| {
+                    format!("The document has no text event type at '{event_type_field}'.")
+                })?;
+            match self.validators_by_event_type.get(event_type) {
+                Some(Some(validator)) => Self::validate_with(validator, &document)?,
+                Some(None) => {}
+                None => Err(format!("The event type '{event_type}' is not described."))?,
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_with(validator: &Validator, document: &Value) -> Result<(), String> {
+        validator
+            .validate(document)
+            .map_err(|e| format!("Validation error at '{}': {e}", e.instance_path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mb::event_descriptor::EventTypeDescriptor;
+
+    #[tokio::test]
+    async fn validates_by_event_type_and_detects_version_mismatch() {
+        let schema = |required: &str| {
+            Some(EventSchema::new(
+                "id".to_owned(),
+                "https://json-schema.org/draft/2020-12/schema".to_owned(),
+                format!(r#"{{"type":"object","required":["{required}"]}}"#),
+            ))
+        };
+        let event_descriptor = EventDescriptor::new(
+            DescriptorVersion::new(2, 0, 0).as_encoded(),
+            None,
+            schema("kind"),
+            None,
+        )
+        .with_event_types(
+            "/kind",
+            vec![EventTypeDescriptor::new("order", schema("order_id"), None)],
+        );
+        let event_validator = EventValidator::new(Some(DescriptorVersion::from_major(1)));
+        assert!(event_validator.validate("not json").await.is_ok());
+        assert_eq!(
+            event_validator.update(&event_descriptor).await,
+            Ok(Some(DescriptorVersion::new(2, 0, 0)))
+        );
+        assert_eq!(event_validator.update(&event_descriptor).await, Ok(None));
+        assert!(
+            event_validator
+                .validate(r#"{"kind":"order","order_id":1}"#)
+                .await
+                .is_ok()
+        );
+        assert!(
+            event_validator
+                .validate(r#"{"kind":"order"}"#)
+                .await
+                .is_err()
+        );
+        assert!(
+            event_validator
+                .validate(r#"{"kind":"other"}"#)
+                .await
+                .is_err()
+        );
+        assert!(event_validator.validate(r#"{"order_id":1}"#).await.is_err());
+    }
+}
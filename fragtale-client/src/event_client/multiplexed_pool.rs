@@ -0,0 +1,316 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Process-wide sharing of WebSocket connections between subscriptions.
+
+use super::EventClient;
+use super::web_socket_pool::KeepAliveSettings;
+use super::web_socket_pool::SubscriberCommand;
+use super::web_socket_pool::SubscriberResponse;
+use super::web_socket_pool::WebSocketPool;
+use super::web_socket_pool::WireFormat;
+use crate::mb::peeked_events::PeekedEvent;
+use crossbeam_skiplist::SkipMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Shared pools by multiplexing URL.
+static SHARED_POOLS: LazyLock<Mutex<HashMap<String, Arc<MultiplexedPool>>>> =
+    LazyLock::new(Mutex::default);
+
+/** Small set of WebSocket connections shared by all subscriptions and
+correlation waits of a process.
+
+Each subscription or correlation wait is a stream with an identifier that the
+server tags its responses with. This keeps the number of sockets low for
+services that subscribe to many topics.
+
+```no_run
+# async fn example(event_service_base_url: &str, correlation_token: &str) {
+use fragtale_client::MultiplexedPool;
+
+let multiplexed_pool = MultiplexedPool::shared(event_service_base_url).await;
+let subscription = multiplexed_pool.subscribe("orders", None, None, None).await;
+let correlated_document = multiplexed_pool
+    .await_correlation("order_results", correlation_token)
+    .await;
+# }
+```
+*/
+pub struct MultiplexedPool {
+    web_socket_pool: Arc<WebSocketPool>,
+    stream_id_counter: AtomicU32,
+    streams: SkipMap<u32, UnboundedSender<SubscriberResponse>>,
+}
+
+impl MultiplexedPool {
+    /// Number of shared WebSocket connections.
+    const CONNECTIONS: usize = 2;
+    /// Max time to wait for the server to respond to a correlation wait.
+    const CORRELATION_TIMEOUT_MICROS: u64 = 30_000_000;
+
+    /// Return the pool shared by all users in this process of the event
+    /// service at `event_service_base_url`.
+    ///
+    /// Keep-alive settings, the wire format and the member identifier are
+    /// taken from the environment the first time the pool is used.
+    pub async fn shared(event_service_base_url: &str) -> Arc<Self> {
+        let mut shared_pools = SHARED_POOLS.lock().await;
+        if let Some(multiplexed_pool) = shared_pools.get(event_service_base_url) {
+            return Arc::clone(multiplexed_pool);
+        }
+        let keep_alive_settings = KeepAliveSettings::from_env();
+        let wire_format = WireFormat::from_env();
+        let url = EventClient::append_member_id_to_url(
+            &wire_format.append_to_url(
+                &keep_alive_settings.append_to_url(&format!("{event_service_base_url}/multiplex")),
+            ),
+            &EventClient::member_id(),
+        );
+        let multiplexed_pool = Arc::new(Self {
+            web_socket_pool: WebSocketPool::new(
+                &url,
+                Self::CONNECTIONS,
+                Self::CONNECTIONS,
+                keep_alive_settings.ping_interval_micros(),
+                wire_format,
+            )
+            .await,
+            stream_id_counter: AtomicU32::default(),
+            streams: SkipMap::new(),
+        })
+        .init();
+        shared_pools.insert(
+            event_service_base_url.to_owned(),
+            Arc::clone(&multiplexed_pool),
+        );
+        multiplexed_pool
+    }
+
+    /// Start routing of responses to their streams.
+    fn init(self: Arc<Self>) -> Arc<Self> {
+        let self_clone = Arc::clone(&self);
+        tokio::spawn(async move { self_clone.route_responses().await });
+        self
+    }
+
+    /// Route responses from any connection to the stream they are tagged
+    /// with.
+    async fn route_responses(&self) {
+        while let Some(response) = self.web_socket_pool.next().await {
+            let stream_id = match &response {
+                SubscriberResponse::Next {
+                    stream_id: Some(stream_id),
+                    ..
+                } => *stream_id,
+                SubscriberResponse::Correlated { stream_id, .. } => *stream_id,
+                SubscriberResponse::Peeked { stream_id, .. } => *stream_id,
+                response => {
+                    if log::log_enabled!(log::Level::Debug) {
+                        log::debug!("Ignoring untagged response: {response:?}");
+                    }
+                    continue;
+                }
+            };
+            if let Some(entry) = self.streams.get(&stream_id) {
+                entry.value().send(response).ok();
+            } else if log::log_enabled!(log::Level::Debug) {
+                // Unconfirmed deliveries will be redelivered
+                log::debug!("Dropping response for closed stream '{stream_id}'.");
+            }
+        }
+    }
+
+    /// Open a new stream and return its identifier and receiver.
+    fn open_stream(&self) -> (u32, UnboundedReceiver<SubscriberResponse>) {
+        let stream_id = self.stream_id_counter.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.streams.insert(stream_id, tx);
+        (stream_id, rx)
+    }
+
+    /// Subscribe to events of a topic.
+    ///
+    /// `event_types` limits deliveries to some kinds of events in a
+    /// multi-type topic and `min_priority` to events of at least this
+    /// priority. `fields` limits each delivered event document to the values
+    /// at these JSON Pointers. The subscription is closed when dropped.
+    pub async fn subscribe(
+        self: &Arc<Self>,
+        topic_id: &str,
+        event_types: Option<Vec<String>>,
+        min_priority: Option<u8>,
+        fields: Option<Vec<String>>,
+    ) -> MultiplexedSubscription {
+        let (stream_id, rx) = self.open_stream();
+        let command = SubscriberCommand::Subscribe {
+            stream_id,
+            topic_id: topic_id.to_owned(),
+            from_epoch_millis: None,
+            version: None,
+            event_types,
+            min_priority,
+            fields,
+            visibility_timeout_millis: None,
+            sticky_key: None,
+        };
+        // Retain first, so connections opened meanwhile (or reconnected) get
+        // the stream as well. A duplicate subscribe replaces the stream.
+        self.web_socket_pool
+            .retain_on_connect(stream_id, command.clone());
+        self.web_socket_pool.send_to_all(&command).await;
+        MultiplexedSubscription {
+            multiplexed_pool: Arc::clone(self),
+            stream_id,
+            rx: Mutex::new(rx),
+        }
+    }
+
+    /// Close the stream of a subscription.
+    async fn unsubscribe(&self, stream_id: u32) {
+        self.web_socket_pool.forget_on_connect(stream_id);
+        self.streams.remove(&stream_id);
+        self.web_socket_pool
+            .send_to_all(&SubscriberCommand::Unsubscribe { stream_id })
+            .await;
+    }
+
+    /// Wait for the event correlated to a previously published event in
+    /// `topic_id`.
+    ///
+    /// Return `None` if no correlated event appeared before the server or
+    /// client gave up waiting.
+    pub async fn await_correlation(
+        self: &Arc<Self>,
+        topic_id: &str,
+        correlation_token: &str,
+    ) -> Option<String> {
+        let (stream_id, mut rx) = self.open_stream();
+        self.web_socket_pool
+            .send(
+                &SubscriberCommand::AwaitCorrelation {
+                    stream_id,
+                    topic_id: topic_id.to_owned(),
+                    correlation_token: correlation_token.to_owned(),
+                },
+                true,
+            )
+            .await;
+        let response = tokio::time::timeout(
+            tokio::time::Duration::from_micros(Self::CORRELATION_TIMEOUT_MICROS),
+            rx.recv(),
+        )
+        .await;
+        self.streams.remove(&stream_id);
+        match response {
+            Ok(Some(SubscriberResponse::Correlated { event_document, .. })) => {
+                event_document.as_deref().map(str::to_owned)
+            }
+            _ => {
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!("No correlated event in '{topic_id}' for '{correlation_token}'.");
+                }
+                None
+            }
+        }
+    }
+
+    /// Peek at up to `count` of the events in `topic_id` next in line for
+    /// delivery to this client without reserving them.
+    ///
+    /// Return an empty list if the server did not respond in time.
+    pub async fn peek(
+        self: &Arc<Self>,
+        topic_id: &str,
+        count: Option<usize>,
+        version: Option<&str>,
+    ) -> Vec<PeekedEvent> {
+        let (stream_id, mut rx) = self.open_stream();
+        self.web_socket_pool
+            .send(
+                &SubscriberCommand::Peek {
+                    stream_id,
+                    topic_id: topic_id.to_owned(),
+                    count,
+                    version: version.map(str::to_owned),
+                },
+                true,
+            )
+            .await;
+        let response = tokio::time::timeout(
+            tokio::time::Duration::from_micros(Self::CORRELATION_TIMEOUT_MICROS),
+            rx.recv(),
+        )
+        .await;
+        self.streams.remove(&stream_id);
+        match response {
+            Ok(Some(SubscriberResponse::Peeked { events, .. })) => events,
+            _ => {
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!("No peeked events in '{topic_id}'.");
+                }
+                vec![]
+            }
+        }
+    }
+}
+
+/// Subscription to events of a topic over a [MultiplexedPool].
+pub struct MultiplexedSubscription {
+    multiplexed_pool: Arc<MultiplexedPool>,
+    stream_id: u32,
+    rx: Mutex<UnboundedReceiver<SubscriberResponse>>,
+}
+
+impl MultiplexedSubscription {
+    /// Get the next delivered event of the subscription.
+    pub async fn next(&self) -> Option<SubscriberResponse> {
+        self.rx.lock().await.recv().await
+    }
+
+    /// Confirm that an event delivered over this subscription was recieved.
+    pub async fn confirm(&self, encoded_unique_time: u64, delivery_instance_id: u16) {
+        self.multiplexed_pool
+            .web_socket_pool
+            .send(
+                &SubscriberCommand::AckStreamDelivery {
+                    stream_id: self.stream_id,
+                    encoded_unique_time,
+                    delivery_instance_id,
+                },
+                false,
+            )
+            .await;
+    }
+}
+
+impl Drop for MultiplexedSubscription {
+    fn drop(&mut self) {
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            let multiplexed_pool = Arc::clone(&self.multiplexed_pool);
+            let stream_id = self.stream_id;
+            runtime.spawn(async move { multiplexed_pool.unsubscribe(stream_id).await });
+        }
+    }
+}
@@ -17,19 +17,33 @@
 
 //! WebSocket connection pool.
 
+mod batched_event;
+mod connection_state;
+mod delivery_batching;
+mod keep_alive_settings;
+mod reconnect_backoff;
 mod subscriber_command;
 mod subscriber_response;
 mod web_socket_connection;
+mod wire_format;
 
 use crate::authentication::BearerTokenCache;
 
+pub use self::batched_event::BatchedEvent;
+pub use self::connection_state::ConnectionState;
+pub use self::delivery_batching::DeliveryBatching;
+pub use self::keep_alive_settings::KeepAliveSettings;
+use self::reconnect_backoff::ReconnectBackoff;
 pub use self::subscriber_command::SubscriberCommand;
 pub use self::subscriber_response::SubscriberResponse;
 use self::web_socket_connection::WebSocketConnection;
+pub use self::wire_format::WireFormat;
 use crossbeam_skiplist::SkipMap;
 use std::sync::Arc;
+use std::sync::OnceLock;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
@@ -51,14 +65,27 @@ pub struct WebSocketPool {
     min_pool_size: u64,
     initialized: AtomicBool,
     last_get_next_ts: AtomicU64,
+    ping_interval_micros: u64,
+    wire_format: WireFormat,
+    on_connect_commands: SkipMap<u32, SubscriberCommand>,
+    reconnect_backoff: ReconnectBackoff,
+    connected_count: AtomicUsize,
+    resume_from: AtomicU64,
+    connection_state_listener: OnceLock<Box<dyn Fn(ConnectionState) + Send + Sync>>,
 }
 
 impl WebSocketPool {
-    /// Ping interval
-    pub const PING_INTERVAL_MICROS: u64 = 5_000_000;
+    /// Default ping interval
+    pub const PING_INTERVAL_MICROS: u64 = KeepAliveSettings::DEFAULT_PING_INTERVAL_MICROS;
 
     /// Return a new instance.
-    pub async fn new(url: &str, pool_size: usize, min_pool_size: usize) -> Arc<Self> {
+    pub async fn new(
+        url: &str,
+        pool_size: usize,
+        min_pool_size: usize,
+        ping_interval_micros: u64,
+        wire_format: WireFormat,
+    ) -> Arc<Self> {
         let bearer_token_cache = BearerTokenCache::new().await;
         let (tx, rx) = mpsc::unbounded_channel();
         Arc::new(Self {
@@ -73,6 +100,13 @@ impl WebSocketPool {
             min_pool_size: u64::try_from(min_pool_size).unwrap(),
             initialized: AtomicBool::new(false),
             last_get_next_ts: AtomicU64::new(u64::MAX),
+            ping_interval_micros,
+            wire_format,
+            on_connect_commands: SkipMap::new(),
+            reconnect_backoff: ReconnectBackoff::from_env(),
+            connected_count: AtomicUsize::new(0),
+            resume_from: AtomicU64::new(0),
+            connection_state_listener: OnceLock::new(),
         })
     }
 
@@ -86,10 +120,62 @@ impl WebSocketPool {
         }
     }
 
+    /// Invoke `listener` whenever the [ConnectionState] of the pool changes.
+    ///
+    /// Only the first listener is used.
+    pub fn set_connection_state_listener(
+        &self,
+        listener: impl Fn(ConnectionState) + Send + Sync + 'static,
+    ) {
+        if self
+            .connection_state_listener
+            .set(Box::new(listener))
+            .is_err()
+        {
+            log::debug!("Ignoring additional connection state listener.");
+        }
+    }
+
+    /// Notify the listener (if any) of a [ConnectionState] change.
+    fn notify_connection_state(&self, connection_state: ConnectionState) {
+        if let Some(listener) = self.connection_state_listener.get() {
+            listener(connection_state);
+        }
+    }
+
+    /// Track the encoded unique time of a confirmed event, so a new connection
+    /// resumes delivery after the latest confirmed event.
+    pub fn resume_from(&self, encoded_unique_time: u64) {
+        self.resume_from
+            .fetch_max(encoded_unique_time, Ordering::Relaxed);
+    }
+
+    /// Return the URL to connect to, with the point in time to resume
+    /// delivery from (if any) as a query parameter.
+    ///
+    /// The server only uses this when it has no delivery state for the
+    /// consumer, like after the consumer was removed.
+    fn connect_url(&self) -> String {
+        let resume_from = self.resume_from.load(Ordering::Relaxed);
+        if resume_from == 0 {
+            return self.url.to_owned();
+        }
+        let from_millis = crate::time::micros_from_encoded_unique_time(resume_from) / 1000;
+        if self.url.contains('?') {
+            format!("{}&from={from_millis}", self.url)
+        } else {
+            format!("{}?from={from_millis}", self.url)
+        }
+    }
+
     /// Start (and restart) message handling if this instance should be kept
     /// alive.
+    ///
+    /// Failed connection attempts and lost keep-alive connections are retried
+    /// with exponential backoff.
     async fn maintain_ws_instance(&self, ws_connection_id: u64) {
         let keep_alive = ws_connection_id < self.min_pool_size;
+        let mut attempt = 0u32;
         loop {
             if !keep_alive {
                 // while no incoming messages
@@ -100,15 +186,25 @@ impl WebSocketPool {
                     log::debug!("Will fire up web socket connection '{ws_connection_id}' shortly.");
                 }
             }
+            let mut connected = false;
             if let Some(ws_connection) = WebSocketConnection::connect(
-                &self.url,
+                &self.connect_url(),
                 &self.bearer_token_cache.current_as_header_value().await,
                 &self.tx.clone(),
+                self.wire_format,
             )
             .await
             {
+                for entry in self.on_connect_commands.iter() {
+                    ws_connection.send(entry.value(), true).await;
+                }
                 self.ws_connections
                     .insert(ws_connection_id, Arc::clone(&ws_connection));
+                connected = true;
+                attempt = 0;
+                if self.connected_count.fetch_add(1, Ordering::Relaxed) == 0 {
+                    self.notify_connection_state(ConnectionState::Connected);
+                }
                 if log::log_enabled!(log::Level::Debug) {
                     log::debug!(
                         "Added web socket connection '{ws_connection_id}'. Connection count is now {}.",
@@ -116,26 +212,37 @@ impl WebSocketPool {
                     );
                 }
                 ws_connection
-                    .handle_messages(Self::PING_INTERVAL_MICROS)
+                    .handle_messages(self.ping_interval_micros)
                     .await;
                 // wait for any kind of failure or termination..
                 ws_connection.await_termination().await;
             }
             log::info!("Removing web socket connection {ws_connection_id}.");
             self.ws_connections.remove(&ws_connection_id);
+            if connected && self.connected_count.fetch_sub(1, Ordering::Relaxed) == 1 {
+                self.notify_connection_state(ConnectionState::Disconnected);
+            }
             if log::log_enabled!(log::Level::Debug) {
                 log::debug!(
                     "Removed web socket connection '{ws_connection_id}'. Connection count esitmate is now {}.",
                     self.ws_connections.len()
                 );
             }
-            if keep_alive {
+            if keep_alive || !connected {
+                attempt = attempt.saturating_add(1);
+                let delay = self.reconnect_backoff.delay(attempt);
                 if log::log_enabled!(log::Level::Debug) {
                     log::debug!(
-                        "Will try to recover web socket connection '{ws_connection_id}' shortly."
+                        "Will try to recover web socket connection '{ws_connection_id}' in {delay:?} (attempt {attempt})."
                     );
                 }
-                tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+                if keep_alive {
+                    self.notify_connection_state(ConnectionState::Reconnecting {
+                        attempt,
+                        delay_millis: u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                    });
+                }
+                tokio::time::sleep(delay).await;
             }
         }
     }
@@ -154,6 +261,25 @@ impl WebSocketPool {
         }
     }
 
+    /// Send command over all open WebSocket connections.
+    pub async fn send_to_all(self: &Arc<Self>, command: &SubscriberCommand) {
+        self.lazy_init().await;
+        for entry in self.ws_connections.iter() {
+            entry.value().send(command, true).await;
+        }
+    }
+
+    /// Send the command over every WebSocket connection that is opened from
+    /// now on, until it is forgotten.
+    pub fn retain_on_connect(&self, key: u32, command: SubscriberCommand) {
+        self.on_connect_commands.insert(key, command);
+    }
+
+    /// Stop sending the command retained under `key` when connecting.
+    pub fn forget_on_connect(&self, key: u32) {
+        self.on_connect_commands.remove(&key);
+    }
+
     /// Return next available [WebSocketConnection].
     async fn get_available_ws_connection(self: &Arc<Self>) -> Arc<WebSocketConnection> {
         self.lazy_init().await;
@@ -216,7 +342,7 @@ impl WebSocketPool {
             if last_get_next_ts == u64::MAX {
                 self.last_get_next_ts.store(now, Ordering::Relaxed);
                 tokio::time::sleep(tokio::time::Duration::from_millis(32)).await;
-            } else if last_get_next_ts < now - Self::PING_INTERVAL_MICROS {
+            } else if last_get_next_ts < now.saturating_sub(self.ping_interval_micros) {
                 // Terminate another non-keep-alive instance
                 for ws_connection_id in self.min_pool_size..self.pool_size {
                     if let Some(entry) = self.ws_connections.get(&ws_connection_id) {
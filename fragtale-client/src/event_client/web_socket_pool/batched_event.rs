@@ -0,0 +1,73 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Event delivered as part of a batch.
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Event delivered as part of a [super::SubscriberResponse::NextBatch].
+///
+/// Fields shared by all events of the batch are only sent once in the
+/// enclosing message.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BatchedEvent {
+    /// Difference between the UniqueTime of this event and the previous event
+    /// in the batch (or the base UniqueTime of the batch for the first event).
+    pub unique_time_delta: i64,
+    /// The event document.
+    pub event_document: Arc<str>,
+    /// Correlation token of the event.
+    pub correlation_token: String,
+    /// The instance id responsilble for the delivery.
+    pub delivery_instance_id: u16,
+    /// The consumer's prepared, but not committed, external transaction
+    /// identifier when this is a redelivery.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prepared_transaction_id: Option<String>,
+    /// The priority the event was published with, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    /// Event identifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    /// Event Descriptor SemVer (major.minor.patch) the event was published
+    /// with, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descriptor_version: Option<String>,
+    /// Identifier of the event schema the event was published with, when
+    /// known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_id: Option<String>,
+}
+
+impl BatchedEvent {
+    /// Return the difference between two encoded unique times.
+    ///
+    /// Events in a batch are usually, but not always, in ascending order, so
+    /// the difference is signed.
+    pub fn unique_time_delta(previous: u64, encoded_unique_time: u64) -> i64 {
+        encoded_unique_time.wrapping_sub(previous) as i64
+    }
+
+    /// Return the encoded unique time from the previous one and the
+    /// difference.
+    pub fn apply_unique_time_delta(previous: u64, unique_time_delta: i64) -> u64 {
+        previous.wrapping_add(unique_time_delta as u64)
+    }
+}
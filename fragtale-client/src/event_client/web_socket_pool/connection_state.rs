@@ -0,0 +1,39 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! State of the connections of a subscription.
+
+/// State of the WebSocket connections of a subscription.
+///
+/// See [EventProcessor::connection_state_hook](crate::EventProcessor::connection_state_hook).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// At least one connection to the server is established.
+    Connected,
+    /// All connections to the server are lost and events will not be
+    /// delivered until a connection is re-established.
+    Disconnected,
+    /// The connection will be re-established after a delay.
+    ///
+    /// Delivery resumes after the last confirmed event.
+    Reconnecting {
+        /// Number of attempts since the connection was lost (starting at `1`).
+        attempt: u32,
+        /// Delay before the attempt in milliseconds.
+        delay_millis: u64,
+    },
+}
@@ -0,0 +1,95 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! WebSocket delivery batching settings.
+
+/// Client side WebSocket delivery batching settings.
+///
+/// Tiny events at high rates spend most of the time in per message framing.
+/// Set the environment variable `WIRE_BATCH_SIZE` to let the server send up
+/// to this many events already available for delivery in a single message and
+/// to confirm deliveries in batches of the same size.
+///
+/// Batching is disabled by default.
+#[derive(Debug, Clone, Copy)]
+pub struct DeliveryBatching {
+    max_batch_size: usize,
+}
+
+impl DeliveryBatching {
+    const ENV_WIRE_BATCH_SIZE: &str = "WIRE_BATCH_SIZE";
+
+    /// Largest number of events in a single batch.
+    pub const MAX_BATCH_SIZE: usize = 1024;
+
+    /// Max time in milliseconds that a confirmation is held back waiting for
+    /// the batch to fill up.
+    pub const CONFIRMATION_LINGER_MILLIS: u64 = 10;
+
+    /// Return a new instance that batches up to `max_batch_size` events.
+    ///
+    /// The size is capped to [Self::MAX_BATCH_SIZE] and a size of `1`
+    /// disables batching.
+    pub fn new(max_batch_size: usize) -> Self {
+        Self {
+            max_batch_size: max_batch_size.clamp(1, Self::MAX_BATCH_SIZE),
+        }
+    }
+
+    /// Return a new instance with settings from the environment.
+    pub fn from_env() -> Self {
+        let max_batch_size = std::env::var(Self::ENV_WIRE_BATCH_SIZE)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .and_then(|value| {
+                value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|e| {
+                        log::warn!(
+                            "Ignoring environment variable '{}' value '{value}': {e}",
+                            Self::ENV_WIRE_BATCH_SIZE
+                        )
+                    })
+                    .ok()
+            })
+            .unwrap_or(1);
+        Self::new(max_batch_size)
+    }
+
+    /// Max number of events in a single batch.
+    pub fn get_max_batch_size(&self) -> usize {
+        self.max_batch_size
+    }
+
+    /// Return `true` if events are batched.
+    pub fn is_enabled(&self) -> bool {
+        self.max_batch_size > 1
+    }
+
+    /// Append the requested batch size as a query parameter to `url` unless
+    /// batching is disabled.
+    pub fn append_to_url(&self, url: &str) -> String {
+        if !self.is_enabled() {
+            url.to_owned()
+        } else if url.contains('?') {
+            format!("{url}&batch={}", self.max_batch_size)
+        } else {
+            format!("{url}?batch={}", self.max_batch_size)
+        }
+    }
+}
@@ -0,0 +1,87 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! WebSocket keep-alive settings.
+
+/// Client side WebSocket keep-alive settings.
+///
+/// By default the server's ping interval and tolerance are used. On
+/// high-latency links, set the environment variables `PING_INTERVAL_MILLIS`
+/// and `PING_TOLERANCE_MILLIS` to request other values. The server will cap
+/// the requested values to its configured limits.
+#[derive(Debug, Clone)]
+pub struct KeepAliveSettings {
+    ping_interval_millis: Option<u64>,
+    ping_tolerance_millis: Option<u64>,
+}
+
+impl KeepAliveSettings {
+    const ENV_PING_INTERVAL: &str = "PING_INTERVAL_MILLIS";
+    const ENV_PING_TOLERANCE: &str = "PING_TOLERANCE_MILLIS";
+
+    /// Default interval between pings.
+    pub const DEFAULT_PING_INTERVAL_MICROS: u64 = 5_000_000;
+
+    /// Return a new instance with settings from the environment.
+    pub fn from_env() -> Self {
+        Self {
+            ping_interval_millis: Self::parse_env(Self::ENV_PING_INTERVAL),
+            ping_tolerance_millis: Self::parse_env(Self::ENV_PING_TOLERANCE),
+        }
+    }
+
+    /// Parse an optional number of milliseconds from the environment.
+    fn parse_env(name: &str) -> Option<u64> {
+        std::env::var(name)
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .and_then(|value| {
+                value
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|e| {
+                        log::warn!("Ignoring environment variable '{name}' value '{value}': {e}")
+                    })
+                    .ok()
+            })
+    }
+
+    /// Interval between pings sent by the client.
+    pub fn ping_interval_micros(&self) -> u64 {
+        self.ping_interval_millis
+            .map(|ms| ms * 1000)
+            .unwrap_or(Self::DEFAULT_PING_INTERVAL_MICROS)
+    }
+
+    /// Append the requested keep-alive settings as query parameters to `url`.
+    pub fn append_to_url(&self, url: &str) -> String {
+        let params = [
+            ("ping_interval", self.ping_interval_millis),
+            ("ping_tolerance", self.ping_tolerance_millis),
+        ]
+        .iter()
+        .filter_map(|(name, value)| value.map(|value| format!("{name}={value}")))
+        .collect::<Vec<_>>();
+        if params.is_empty() {
+            url.to_owned()
+        } else if url.contains('?') {
+            format!("{url}&{}", params.join("&"))
+        } else {
+            format!("{url}?{}", params.join("&"))
+        }
+    }
+}
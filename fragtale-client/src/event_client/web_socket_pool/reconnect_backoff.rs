@@ -0,0 +1,110 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Backoff between WebSocket reconnect attempts.
+
+use tokio::time::Duration;
+
+/// Exponential backoff between attempts to reconnect a lost WebSocket
+/// connection.
+///
+/// The first attempt is delayed by 250 milliseconds and each following attempt
+/// doubles the delay up to 30 seconds. Set the environment variables
+/// `RECONNECT_INITIAL_MILLIS` and `RECONNECT_MAX_MILLIS` to use other values.
+/// Each delay is randomly shortened by up to half to spread out the
+/// reconnects of many clients after a server restart.
+#[derive(Debug, Clone)]
+pub struct ReconnectBackoff {
+    initial_millis: u64,
+    max_millis: u64,
+}
+
+impl ReconnectBackoff {
+    const ENV_RECONNECT_INITIAL: &str = "RECONNECT_INITIAL_MILLIS";
+    const ENV_RECONNECT_MAX: &str = "RECONNECT_MAX_MILLIS";
+
+    /// Default delay before the first reconnect attempt.
+    pub const DEFAULT_INITIAL_MILLIS: u64 = 250;
+    /// Default upper bound of the delay between reconnect attempts.
+    pub const DEFAULT_MAX_MILLIS: u64 = 30_000;
+
+    /// Return a new instance with settings from the environment.
+    pub fn from_env() -> Self {
+        let initial_millis = Self::parse_env(Self::ENV_RECONNECT_INITIAL)
+            .unwrap_or(Self::DEFAULT_INITIAL_MILLIS)
+            .max(1);
+        let max_millis = Self::parse_env(Self::ENV_RECONNECT_MAX)
+            .unwrap_or(Self::DEFAULT_MAX_MILLIS)
+            .max(initial_millis);
+        Self {
+            initial_millis,
+            max_millis,
+        }
+    }
+
+    /// Parse an optional number of milliseconds from the environment.
+    fn parse_env(name: &str) -> Option<u64> {
+        std::env::var(name)
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .and_then(|value| {
+                value
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|e| {
+                        log::warn!("Ignoring environment variable '{name}' value '{value}': {e}")
+                    })
+                    .ok()
+            })
+    }
+
+    /// Delay before reconnect attempt number `attempt` (starting at `1`).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let full_millis = self
+            .initial_millis
+            .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_millis);
+        let half_millis = full_millis / 2;
+        // Sub-second clock noise is random enough to spread out reconnects
+        let noise = u64::from(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .subsec_nanos(),
+        );
+        Duration::from_millis(full_millis - noise % (half_millis + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_grows_exponentially_up_to_max() {
+        let reconnect_backoff = ReconnectBackoff {
+            initial_millis: 100,
+            max_millis: 1_000,
+        };
+        for (attempt, full_millis) in [(1, 100), (2, 200), (3, 400), (4, 800), (5, 1_000)] {
+            let delay = reconnect_backoff.delay(attempt).as_millis();
+            assert!(delay <= full_millis, "attempt {attempt}: {delay}");
+            assert!(delay >= full_millis / 2, "attempt {attempt}: {delay}");
+        }
+        assert!(reconnect_backoff.delay(u32::MAX).as_millis() <= 1_000);
+    }
+}
@@ -17,11 +17,12 @@
 
 //! WebSocket messages sent from client to server.
 
+use super::BatchedEvent;
 use serde::Deserialize;
 use serde::Serialize;
 
 /// WebSocket messages sent from client to server.
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SubscriberCommand {
     /// Acknowledge (confirm) that an event has been recieved by the client.
@@ -31,6 +32,30 @@ pub enum SubscriberCommand {
         /// The instance id responsilble for the acknowledged delivery.
         delivery_instance_id: u16,
     },
+    /// Acknowledge (confirm) that several events have been recieved by the
+    /// client in a single message.
+    AckDeliveryBatch {
+        /// UniqueTime that the first event's delta is relative to.
+        base_unique_time: u64,
+        /// Difference between the UniqueTime of each event and the previous
+        /// one and the instance id responsible for the acknowledged delivery.
+        deliveries: Vec<(i64, u16)>,
+    },
+    /// Prepare the confirmation of an event delivery by recording the
+    /// consumer's external transaction identifier.
+    PrepareDelivery {
+        /// UniqueTime of the event.
+        encoded_unique_time: u64,
+        /// The consumer's transaction identifier in the external sink.
+        transaction_id: String,
+    },
+    /// Commit a previously prepared confirmation of an event delivery.
+    CommitDelivery {
+        /// UniqueTime of the event.
+        encoded_unique_time: u64,
+        /// The instance id responsilble for the committed delivery.
+        delivery_instance_id: u16,
+    },
     /// Publish a new event to the server.
     Publish {
         /// Relative priority of the message. 0-100 (100 is highest priority).
@@ -43,5 +68,147 @@ pub enum SubscriberCommand {
         correlation_token: Option<String>,
         /// Event descriptor version the event document adheres to.
         descriptor_version: Option<u64>,
+        /// Deadline in epoch microseconds after which the event should no
+        /// longer be delivered.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expires_ts: Option<u64>,
+    },
+    /// Open a stream of events from a topic over a multiplexed connection.
+    ///
+    /// Reusing the identifier of an open stream replaces the stream.
+    Subscribe {
+        /// Client assigned identifier of the stream.
+        stream_id: u32,
+        /// Topic identifier.
+        topic_id: String,
+        /// Only consider events newer than this in epoch milliseconds.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from_epoch_millis: Option<u64>,
+        /// Event Descriptor SemVer that the client prefers (major.minor).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version: Option<String>,
+        /// Kinds of events of interest in a multi-type topic.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        event_types: Option<Vec<String>>,
+        /// Lowest priority of events of interest.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min_priority: Option<u8>,
+        /// JSON Pointers of the fields of interest in each event document.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fields: Option<Vec<String>>,
+        /// Milliseconds to wait for confirmation of a delivered event before
+        /// it is considered for redelivery.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        visibility_timeout_millis: Option<u64>,
+        /// Routing key of the events that this member of the consumer group
+        /// prefers.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sticky_key: Option<String>,
+    },
+    /// Close a stream of a multiplexed connection.
+    Unsubscribe {
+        /// Client assigned identifier of the stream.
+        stream_id: u32,
     },
+    /// Acknowledge (confirm) that an event delivered over a stream of a
+    /// multiplexed connection has been recieved by the client.
+    AckStreamDelivery {
+        /// Client assigned identifier of the stream.
+        stream_id: u32,
+        /// UniqueTime of the event.
+        encoded_unique_time: u64,
+        /// The instance id responsilble for the acknowledged delivery.
+        delivery_instance_id: u16,
+    },
+    /// Wait for an event correlated to a previously published event over a
+    /// multiplexed connection.
+    AwaitCorrelation {
+        /// Client assigned identifier of the wait.
+        stream_id: u32,
+        /// Topic where the correlated event is expected.
+        topic_id: String,
+        /// Correlation token of the previously published event.
+        correlation_token: String,
+    },
+    /// Peek at the events next in line for delivery to the consumer over a
+    /// multiplexed connection without reserving them.
+    Peek {
+        /// Client assigned identifier of the peek.
+        stream_id: u32,
+        /// Topic identifier.
+        topic_id: String,
+        /// Max number of events to return.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        count: Option<usize>,
+        /// Event Descriptor SemVer that the client prefers (major.minor).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version: Option<String>,
+    },
+}
+
+impl SubscriberCommand {
+    /// Combine [Self::AckDelivery] acknowledgements into a single
+    /// [Self::AckDeliveryBatch].
+    ///
+    /// The commands are returned unchanged if there are fewer than two or if
+    /// they can't be combined.
+    pub fn batch(commands: Vec<Self>) -> Result<Self, Vec<Self>> {
+        if commands.len() < 2
+            || !commands
+                .iter()
+                .all(|command| matches!(command, Self::AckDelivery { .. }))
+        {
+            return Err(commands);
+        }
+        let base_unique_time = match commands[0] {
+            Self::AckDelivery {
+                encoded_unique_time,
+                ..
+            } => encoded_unique_time,
+            _ => 0,
+        };
+        let mut previous = base_unique_time;
+        let deliveries = commands
+            .into_iter()
+            .filter_map(|command| match command {
+                Self::AckDelivery {
+                    encoded_unique_time,
+                    delivery_instance_id,
+                } => {
+                    let unique_time_delta =
+                        BatchedEvent::unique_time_delta(previous, encoded_unique_time);
+                    previous = encoded_unique_time;
+                    Some((unique_time_delta, delivery_instance_id))
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(Self::AckDeliveryBatch {
+            base_unique_time,
+            deliveries,
+        })
+    }
+
+    /// Return the individual [Self::AckDelivery] acknowledgements of a
+    /// [Self::AckDeliveryBatch] or just this command otherwise.
+    pub fn unbatch(self) -> Vec<Self> {
+        let Self::AckDeliveryBatch {
+            base_unique_time,
+            deliveries,
+        } = self
+        else {
+            return vec![self];
+        };
+        let mut previous = base_unique_time;
+        deliveries
+            .into_iter()
+            .map(|(unique_time_delta, delivery_instance_id)| {
+                previous = BatchedEvent::apply_unique_time_delta(previous, unique_time_delta);
+                Self::AckDelivery {
+                    encoded_unique_time: previous,
+                    delivery_instance_id,
+                }
+            })
+            .collect()
+    }
 }
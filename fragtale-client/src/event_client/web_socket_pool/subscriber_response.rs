@@ -17,8 +17,11 @@
 
 //! WebSocket messages sent from server to client.
 
+use super::BatchedEvent;
+use crate::mb::peeked_events::PeekedEvent;
 use serde::Deserialize;
 use serde::Serialize;
+use std::sync::Arc;
 
 /// WebSocket messages sent from server to client.
 #[derive(Debug, Deserialize, Serialize)]
@@ -28,11 +31,221 @@ pub enum SubscriberResponse {
     Next {
         ///  UniqueTime of the event.
         encoded_unique_time: u64,
-        /// todo
-        event_document: String,
+        /// The event document.
+        ///
+        /// This is shared to avoid copying large documents on delivery.
+        event_document: Arc<str>,
         /// todo
         correlation_token: String,
         /// todo
         delivery_instance_id: u16,
+        /// The consumer's prepared, but not committed, external transaction
+        /// identifier when this is a redelivery.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prepared_transaction_id: Option<String>,
+        /// The priority the event was published with, when known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        priority: Option<u8>,
+        /// The stream the event was delivered over, when the connection is
+        /// multiplexed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stream_id: Option<u32>,
+        /// Topic identifier of the event.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        topic_id: Option<String>,
+        /// Event identifier.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        event_id: Option<String>,
+        /// Event Descriptor SemVer (major.minor.patch) the event was published
+        /// with, when known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        descriptor_version: Option<String>,
+        /// Identifier of the event schema the event was published with, when
+        /// known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        schema_id: Option<String>,
+    },
+    /// Delivery of several new events from the same topic to the client in a
+    /// single message.
+    ///
+    /// This is only sent when batching was negotiated when subscribing.
+    NextBatch {
+        /// UniqueTime that the first event's delta is relative to.
+        base_unique_time: u64,
+        /// Topic identifier of all events in the batch.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        topic_id: Option<String>,
+        /// The events in delivery order.
+        events: Vec<BatchedEvent>,
+    },
+    /// Result of waiting for a correlated event over a multiplexed
+    /// connection.
+    Correlated {
+        /// Client assigned identifier of the wait.
+        stream_id: u32,
+        /// The correlated event document, unless the wait timed out.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        event_document: Option<Arc<str>>,
+    },
+    /// Result of peeking at the events next in line for delivery over a
+    /// multiplexed connection.
+    Peeked {
+        /// Client assigned identifier of the peek.
+        stream_id: u32,
+        /// The events next in line for delivery in delivery order.
+        ///
+        /// This is empty when peeking failed.
+        events: Vec<PeekedEvent>,
+    },
+    /// Status of the subscription.
+    ///
+    /// This is sent when no event was available for delivery while the
+    /// server is still catching up after the client connected and again once
+    /// catch-up has completed.
+    Status {
+        /// `true` while the server is still catching up on events to deliver.
+        catching_up: bool,
+    },
+    /// Protocol versions spoken by the server instance.
+    ///
+    /// This is sent first on a new connection when the client announced its
+    /// protocol version using the [crate::protocol::HEADER_NAME] header.
+    Hello {
+        /// The protocol version negotiated for this connection.
+        protocol_version: u32,
+        /// The oldest protocol version still understood by the server
+        /// instance.
+        min_protocol_version: u32,
     },
+    /// Control message telling the client that the server instance will no
+    /// longer deliver events over this connection.
+    ///
+    /// This is sent when the instance is shutting down or has lost its
+    /// claimed instance identity. The client should close the connection and
+    /// reconnect, to be served by another instance.
+    Rebalance {
+        /// Reason for the rebalancing.
+        reason: String,
+    },
+}
+
+impl SubscriberResponse {
+    /// Combine [Self::Next] deliveries of events from the same topic into a
+    /// single [Self::NextBatch].
+    ///
+    /// The deliveries are returned unchanged if there are fewer than two or if
+    /// they can't be combined.
+    pub fn batch(deliveries: Vec<Self>) -> Result<Self, Vec<Self>> {
+        let batchable = deliveries.len() > 1
+            && deliveries.iter().all(|delivery| {
+                matches!(
+                    delivery,
+                    Self::Next {
+                        stream_id: None,
+                        ..
+                    }
+                ) && delivery.get_topic_id() == deliveries[0].get_topic_id()
+            });
+        if !batchable {
+            return Err(deliveries);
+        }
+        let topic_id = deliveries[0].get_topic_id().map(str::to_owned);
+        let base_unique_time = deliveries[0].get_encoded_unique_time();
+        let mut previous = base_unique_time;
+        let events = deliveries
+            .into_iter()
+            .filter_map(|delivery| match delivery {
+                Self::Next {
+                    encoded_unique_time,
+                    event_document,
+                    correlation_token,
+                    delivery_instance_id,
+                    prepared_transaction_id,
+                    priority,
+                    event_id,
+                    descriptor_version,
+                    schema_id,
+                    ..
+                } => {
+                    let unique_time_delta =
+                        BatchedEvent::unique_time_delta(previous, encoded_unique_time);
+                    previous = encoded_unique_time;
+                    Some(BatchedEvent {
+                        unique_time_delta,
+                        event_document,
+                        correlation_token,
+                        delivery_instance_id,
+                        prepared_transaction_id,
+                        priority,
+                        event_id,
+                        descriptor_version,
+                        schema_id,
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(Self::NextBatch {
+            base_unique_time,
+            topic_id,
+            events,
+        })
+    }
+
+    /// Return the individual [Self::Next] deliveries of a [Self::NextBatch]
+    /// or just this message otherwise.
+    pub fn unbatch(self) -> Vec<Self> {
+        let Self::NextBatch {
+            base_unique_time,
+            topic_id,
+            events,
+        } = self
+        else {
+            return vec![self];
+        };
+        let mut previous = base_unique_time;
+        events
+            .into_iter()
+            .map(|event| {
+                let encoded_unique_time =
+                    BatchedEvent::apply_unique_time_delta(previous, event.unique_time_delta);
+                previous = encoded_unique_time;
+                Self::Next {
+                    encoded_unique_time,
+                    event_document: event.event_document,
+                    correlation_token: event.correlation_token,
+                    delivery_instance_id: event.delivery_instance_id,
+                    prepared_transaction_id: event.prepared_transaction_id,
+                    priority: event.priority,
+                    stream_id: None,
+                    topic_id: topic_id.clone(),
+                    event_id: event.event_id,
+                    descriptor_version: event.descriptor_version,
+                    schema_id: event.schema_id,
+                }
+            })
+            .collect()
+    }
+
+    /// Return the topic identifier of a delivery, when known.
+    fn get_topic_id(&self) -> Option<&str> {
+        match self {
+            Self::Next { topic_id, .. } | Self::NextBatch { topic_id, .. } => topic_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Return the encoded unique time of the (first) delivered event.
+    fn get_encoded_unique_time(&self) -> u64 {
+        match self {
+            Self::Next {
+                encoded_unique_time,
+                ..
+            } => *encoded_unique_time,
+            Self::NextBatch {
+                base_unique_time, ..
+            } => *base_unique_time,
+            _ => 0,
+        }
+    }
 }
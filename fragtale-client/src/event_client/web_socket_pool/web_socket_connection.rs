@@ -38,6 +38,7 @@ use tyst::encdec::hex::ToHex;
 
 use super::SubscriberCommand;
 use super::SubscriberResponse;
+use super::WireFormat;
 
 pub struct WebSocketConnection {
     ws_write_stream: Arc<Mutex<SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>>>,
@@ -45,6 +46,7 @@ pub struct WebSocketConnection {
     tx: UnboundedSender<SubscriberResponse>,
     termination_semaphore: Semaphore,
     feed_counter: AtomicU64,
+    wire_format: WireFormat,
 }
 
 impl WebSocketConnection {
@@ -55,6 +57,7 @@ impl WebSocketConnection {
         url: &str,
         authorization_header_value: &str,
         tx: &UnboundedSender<SubscriberResponse>,
+        wire_format: WireFormat,
     ) -> Option<Arc<Self>> {
         let url = if url.starts_with("http") {
             url.replacen("http", "ws", 1)
@@ -64,6 +67,10 @@ impl WebSocketConnection {
         let uri: Uri = url.parse().unwrap();
         let builder = ClientRequestBuilder::new(uri)
             .with_header("Authorization", authorization_header_value)
+            .with_header(
+                crate::protocol::HEADER_NAME,
+                crate::protocol::PROTOCOL_VERSION.to_string(),
+            )
             //.with_sub_protocol("fragtale_ws")
             ;
         if let Ok((ws_stream, _res)) = tokio_tungstenite::connect_async_with_config(
@@ -88,6 +95,7 @@ impl WebSocketConnection {
                     tx: tx.clone(),
                     termination_semaphore: Semaphore::new(0),
                     feed_counter: AtomicU64::new(0),
+                    wire_format,
                 })
                 .initialize()
                 .await,
@@ -177,16 +185,18 @@ impl WebSocketConnection {
                 let mut web_socket_mutex = self.ws_read_stream.lock().await;
                 web_socket_mutex.next().await
             };
-            match res {
+            let message = match res {
                 Some(Ok(Message::Text(text))) => {
                     if log::log_enabled!(log::Level::Trace) {
                         log::trace!("Got text: {text}");
                     }
-                    let message = serde_json::from_str(&text).unwrap();
-                    if let Err(e) = self.tx.send(message) {
-                        log::info!("Unable to write to queue: {e:?}");
-                        break;
+                    Some(WireFormat::Json.decode(text.as_bytes()))
+                }
+                Some(Ok(Message::Binary(bin))) => {
+                    if log::log_enabled!(log::Level::Trace) {
+                        log::trace!("Got binary message of {} bytes.", bin.len());
                     }
+                    Some(WireFormat::MessagePack.decode(&bin))
                 }
                 // Respond to ping with pong right away.
                 Some(Ok(Message::Ping(text))) => {
@@ -204,15 +214,18 @@ impl WebSocketConnection {
                         log::debug!("Pong send failed: {e:?}");
                         break;
                     }
+                    None
                 }
                 Some(Ok(Message::Pong(text))) => {
                     if log::log_enabled!(log::Level::Trace) {
                         log::trace!("Got pong: {text:?}");
                     }
+                    None
                 }
                 None => {
                     log::debug!("None next() message result.");
                     tokio::time::sleep(tokio::time::Duration::from_millis(32)).await;
+                    None
                 }
                 Some(Err(e)) => {
                     log::info!("recv_next: {e:?}");
@@ -222,7 +235,52 @@ impl WebSocketConnection {
                     log::info!("Unhandled result: {r:?}");
                     //break;
                     tokio::time::sleep(tokio::time::Duration::from_millis(32)).await;
+                    None
+                }
+            };
+            match message {
+                Some(Ok(SubscriberResponse::Rebalance { reason })) => {
+                    log::info!("Reconnecting on server request: {reason}");
+                    break;
+                }
+                Some(Ok(SubscriberResponse::Hello {
+                    protocol_version,
+                    min_protocol_version,
+                })) => {
+                    if crate::protocol::negotiate(Some(protocol_version), min_protocol_version)
+                        .is_none()
+                    {
+                        log::warn!(
+                            "Server speaks protocol version {min_protocol_version}..={protocol_version} which is incompatible with this client ({}..={}).",
+                            crate::protocol::MIN_PROTOCOL_VERSION,
+                            crate::protocol::PROTOCOL_VERSION,
+                        );
+                        break;
+                    }
+                    if log::log_enabled!(log::Level::Debug) {
+                        log::debug!("Using protocol version {protocol_version}.");
+                    }
+                }
+                Some(Ok(SubscriberResponse::Status { catching_up })) => {
+                    if log::log_enabled!(log::Level::Debug) {
+                        log::debug!("Server is catching up: {catching_up}");
+                    }
+                }
+                Some(Ok(message)) => {
+                    // Batched deliveries are queued as individual events
+                    if let Err(e) = message
+                        .unbatch()
+                        .into_iter()
+                        .try_for_each(|message| self.tx.send(message))
+                    {
+                        log::info!("Unable to write to queue: {e:?}");
+                        break;
+                    }
+                }
+                Some(Err(e)) => {
+                    log::info!("Ignoring malformed message: {e}");
                 }
+                None => {}
             }
         }
         if log::log_enabled!(log::Level::Trace) {
@@ -233,7 +291,12 @@ impl WebSocketConnection {
 
     /// Send all commands to the WebSocket and flush afterwards
     pub async fn send(&self, command: &SubscriberCommand, flush: bool) {
-        let msg = Message::Text(serde_json::to_string(&command).unwrap().into());
+        let data = self.wire_format.encode(command);
+        let msg = if self.wire_format.is_binary() {
+            Message::Binary(data.into())
+        } else {
+            Message::Text(String::from_utf8(data).unwrap_or_default().into())
+        };
         let mut web_socket = self.ws_write_stream.lock().await;
         let res = if flush {
             web_socket.send(msg).await
@@ -0,0 +1,237 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Serialization of WebSocket messages.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Serialization of [super::SubscriberResponse] and
+/// [super::SubscriberCommand] messages over WebSocket.
+///
+/// JSON is sent in text frames and MessagePack in binary frames, so the
+/// receiver can always tell the format from the kind of frame. The format of
+/// the server's responses is negotiated when subscribing using the `format`
+/// query parameter.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WireFormat {
+    /// JSON in text frames.
+    #[default]
+    Json,
+    /// MessagePack in binary frames.
+    MessagePack,
+}
+
+impl WireFormat {
+    /// Environment variable with the client's preferred format.
+    const ENV_WIRE_FORMAT: &str = "WIRE_FORMAT";
+
+    /// Return the format with the provided name, if known.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(Self::Json),
+            "msgpack" => Some(Self::MessagePack),
+            _ => None,
+        }
+    }
+
+    /// Return the name used for content negotiation.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::MessagePack => "msgpack",
+        }
+    }
+
+    /// Return the format from the environment variable `WIRE_FORMAT` or JSON
+    /// if unset or unknown.
+    pub fn from_env() -> Self {
+        std::env::var(Self::ENV_WIRE_FORMAT)
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .and_then(|value| {
+                let wire_format = Self::from_name(value.trim());
+                if wire_format.is_none() {
+                    log::warn!(
+                        "Ignoring environment variable '{}' value '{value}'.",
+                        Self::ENV_WIRE_FORMAT
+                    );
+                }
+                wire_format
+            })
+            .unwrap_or_default()
+    }
+
+    /// Return `true` if messages in this format are sent in binary frames.
+    pub fn is_binary(&self) -> bool {
+        *self == Self::MessagePack
+    }
+
+    /// Append this format as a query parameter to `url` unless it is the
+    /// default.
+    pub fn append_to_url(&self, url: &str) -> String {
+        match self {
+            Self::Json => url.to_owned(),
+            _ if url.contains('?') => format!("{url}&format={}", self.get_name()),
+            _ => format!("{url}?format={}", self.get_name()),
+        }
+    }
+
+    /// Serialize a message.
+    pub fn encode<T: Serialize>(&self, value: &T) -> Vec<u8> {
+        match self {
+            Self::Json => serde_json::to_vec(value).unwrap(),
+            // Use field names to allow optional fields to be omitted
+            Self::MessagePack => rmp_serde::to_vec_named(value).unwrap(),
+        }
+    }
+
+    /// Deserialize a message.
+    pub fn decode<T: DeserializeOwned>(&self, data: &[u8]) -> Result<T, String> {
+        match self {
+            Self::Json => serde_json::from_slice(data).map_err(|e| e.to_string()),
+            Self::MessagePack => rmp_serde::from_slice(data).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SubscriberCommand;
+    use crate::SubscriberResponse;
+
+    #[test]
+    fn round_trips_messages_in_both_formats() {
+        for wire_format in [WireFormat::Json, WireFormat::MessagePack] {
+            let response = wire_format.encode(&SubscriberResponse::Next {
+                encoded_unique_time: 42,
+                event_document: "{}".into(),
+                correlation_token: "token".to_owned(),
+                delivery_instance_id: 7,
+                prepared_transaction_id: None,
+                priority: Some(50),
+                stream_id: None,
+                topic_id: Some("topic".to_owned()),
+                event_id: Some("abc".to_owned()),
+                descriptor_version: Some("1.2.3".to_owned()),
+                schema_id: None,
+            });
+            match wire_format.decode(&response) {
+                Ok(SubscriberResponse::Next {
+                    encoded_unique_time,
+                    prepared_transaction_id,
+                    priority,
+                    topic_id,
+                    descriptor_version,
+                    schema_id,
+                    ..
+                }) => {
+                    assert_eq!(encoded_unique_time, 42);
+                    assert_eq!(prepared_transaction_id, None);
+                    assert_eq!(priority, Some(50));
+                    assert_eq!(topic_id.as_deref(), Some("topic"));
+                    assert_eq!(descriptor_version.as_deref(), Some("1.2.3"));
+                    assert_eq!(schema_id, None);
+                }
+                other => panic!("Unexpected {other:?}"),
+            }
+            let command = wire_format.encode(&SubscriberCommand::AckDelivery {
+                encoded_unique_time: 42,
+                delivery_instance_id: 7,
+            });
+            assert!(matches!(
+                wire_format.decode(&command),
+                Ok(SubscriberCommand::AckDelivery {
+                    encoded_unique_time: 42,
+                    delivery_instance_id: 7,
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn round_trips_batches_in_both_formats() {
+        let deliveries = || {
+            [42u64, 40, 1_000_042]
+                .iter()
+                .map(|encoded_unique_time| SubscriberResponse::Next {
+                    encoded_unique_time: *encoded_unique_time,
+                    event_document: "{}".into(),
+                    correlation_token: format!("token{encoded_unique_time}"),
+                    delivery_instance_id: 7,
+                    prepared_transaction_id: None,
+                    priority: None,
+                    stream_id: None,
+                    topic_id: Some("topic".to_owned()),
+                    event_id: None,
+                    descriptor_version: None,
+                    schema_id: None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let acks = [42u64, 40, 1_000_042]
+            .iter()
+            .map(|encoded_unique_time| SubscriberCommand::AckDelivery {
+                encoded_unique_time: *encoded_unique_time,
+                delivery_instance_id: 7,
+            })
+            .collect::<Vec<_>>();
+        for wire_format in [WireFormat::Json, WireFormat::MessagePack] {
+            let response = SubscriberResponse::batch(deliveries()).unwrap();
+            let decoded: SubscriberResponse =
+                wire_format.decode(&wire_format.encode(&response)).unwrap();
+            let unbatched = decoded
+                .unbatch()
+                .into_iter()
+                .map(|response| match response {
+                    SubscriberResponse::Next {
+                        encoded_unique_time,
+                        correlation_token,
+                        topic_id,
+                        ..
+                    } => (encoded_unique_time, correlation_token, topic_id),
+                    other => panic!("Unexpected {other:?}"),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                unbatched,
+                [42u64, 40, 1_000_042]
+                    .iter()
+                    .map(|t| (*t, format!("token{t}"), Some("topic".to_owned())))
+                    .collect::<Vec<_>>()
+            );
+            let command = SubscriberCommand::batch(acks.clone()).unwrap();
+            let decoded: SubscriberCommand =
+                wire_format.decode(&wire_format.encode(&command)).unwrap();
+            let unbatched = decoded
+                .unbatch()
+                .into_iter()
+                .map(|command| match command {
+                    SubscriberCommand::AckDelivery {
+                        encoded_unique_time,
+                        ..
+                    } => encoded_unique_time,
+                    other => panic!("Unexpected {other:?}"),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(unbatched, vec![42u64, 40, 1_000_042]);
+        }
+        // A single delivery is left as is
+        assert!(SubscriberResponse::batch(deliveries().split_off(2)).is_err());
+    }
+}
@@ -27,17 +27,70 @@ pub(crate) mod authentication {
 pub mod mb {
     //! Message broker objects.
 
+    pub mod bulk_ingest;
+    pub mod capabilities;
+    pub mod consumer_definitions;
+    pub mod consumer_position;
+    pub mod consumption_audit;
+    pub mod contract_fixtures;
     pub mod correlation_token;
+    pub mod dead_letters;
+    pub mod delivery_envelope;
+    pub mod delivery_preparation;
+    pub mod delivery_receipts;
+    pub mod descriptor_diff;
+    pub mod diagnostic_queries;
+    pub mod duplicate_events;
+    pub mod event_annotations;
     pub mod event_descriptor;
+    pub mod event_locations;
+    pub mod event_mirror;
+    pub mod event_purge;
+    pub mod group_members;
+    pub mod index_rebuild;
+    pub mod peeked_events;
+    pub mod publish_acknowledgement;
+    pub mod publish_grant;
+    pub mod publish_rejections;
+    pub mod published_events;
+    pub mod rejected_events;
+    pub mod replayed_events;
+    pub mod reply_topic;
+    pub mod resource_grants;
+    pub mod retention_preview;
+    pub mod shared_schemas;
+    pub mod storage_tier;
+    pub mod subscription_health;
+    pub mod topic_access;
+    pub mod topic_buckets;
+    pub mod topic_counts;
+    pub mod topic_overview;
+    pub mod topic_statistics;
+    pub mod web_socket_sessions;
 }
+mod client_metrics_observer;
 mod event_client;
+pub mod protocol;
 mod rest_api_client;
 pub mod time;
 
+pub use client_metrics_observer::ClientMetricsObserver;
+pub use event_client::BatchedEvent;
+pub use event_client::ConnectionState;
+pub use event_client::DeliveryBatching;
 pub use event_client::EventClient;
+pub use event_client::EventDeduplicator;
 pub use event_client::EventProcessor;
 pub use event_client::EventSource;
+pub use event_client::EventValidator;
+pub use event_client::KeepAliveSettings;
+pub use event_client::MultiplexedPool;
+pub use event_client::MultiplexedSubscription;
+pub use rest_api_client::ClientError;
 pub use rest_api_client::RestApiClient;
+pub use rest_api_client::RetryObserver;
+pub use rest_api_client::RetryPolicy;
 
 pub use self::event_client::SubscriberCommand;
 pub use self::event_client::SubscriberResponse;
+pub use self::event_client::WireFormat;
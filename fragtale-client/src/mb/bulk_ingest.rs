@@ -0,0 +1,109 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Bulk ingest windows for backfilling topics.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Bulk ingest window of a topic.
+///
+/// Events published to the topic during the window are persisted without
+/// their indexed columns and integrity consolidation of the topic is paused.
+/// Once the window has ended, the indexed columns of these events are
+/// populated (reconciled) in the background.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BulkIngestWindow {
+    /// Topic identifier.
+    topic_id: String,
+    /// Start of the window in epoch microseconds.
+    start_ts_micros: u64,
+    /// End of the window in epoch microseconds.
+    end_ts_micros: u64,
+    /// `true` while publishing to the topic defers work.
+    active: bool,
+    /// `true` once the deferred work of the window has been completed.
+    reconciled: bool,
+}
+
+impl BulkIngestWindow {
+    /// Return a new instance.
+    pub fn new(
+        topic_id: &str,
+        start_ts_micros: u64,
+        end_ts_micros: u64,
+        active: bool,
+        reconciled: bool,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            start_ts_micros,
+            end_ts_micros,
+            active,
+            reconciled,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Start of the window in epoch microseconds.
+    pub fn get_start_ts_micros(&self) -> u64 {
+        self.start_ts_micros
+    }
+
+    /// End of the window in epoch microseconds.
+    pub fn get_end_ts_micros(&self) -> u64 {
+        self.end_ts_micros
+    }
+
+    /// `true` while publishing to the topic defers work.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// `true` once the deferred work of the window has been completed.
+    pub fn is_reconciled(&self) -> bool {
+        self.reconciled
+    }
+}
+
+/// Request to open (or extend) a bulk ingest window of a topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BulkIngestWindowRequest {
+    /// Duration of the window from now in milliseconds.
+    duration_millis: u64,
+}
+
+impl BulkIngestWindowRequest {
+    /// Return a new instance.
+    pub fn new(duration_millis: u64) -> Self {
+        Self { duration_millis }
+    }
+
+    /// Duration of the window from now in milliseconds.
+    pub fn get_duration_millis(&self) -> u64 {
+        self.duration_millis
+    }
+}
@@ -0,0 +1,353 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Capabilities and limits of the message broker.
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Limits enforced when a topic's event descriptor is registered or updated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DescriptorLimits {
+    /// Maximum number of extractors in a single event descriptor, including
+    /// the extractors of each kind of event.
+    max_extractors: usize,
+    /// Maximum size in bytes of each schema in an event descriptor.
+    max_schema_size: usize,
+    /// Maximum number of distinct indexed columns of a topic across all
+    /// versions of its event descriptor.
+    max_index_columns: usize,
+}
+
+impl DescriptorLimits {
+    /// Return a new instance.
+    pub fn new(max_extractors: usize, max_schema_size: usize, max_index_columns: usize) -> Self {
+        Self {
+            max_extractors,
+            max_schema_size,
+            max_index_columns,
+        }
+    }
+
+    /// Maximum number of extractors in a single event descriptor, including
+    /// the extractors of each kind of event.
+    pub fn get_max_extractors(&self) -> usize {
+        self.max_extractors
+    }
+
+    /// Maximum size in bytes of each schema in an event descriptor.
+    pub fn get_max_schema_size(&self) -> usize {
+        self.max_schema_size
+    }
+
+    /// Maximum number of distinct indexed columns of a topic across all
+    /// versions of its event descriptor.
+    pub fn get_max_index_columns(&self) -> usize {
+        self.max_index_columns
+    }
+}
+
+/// Optional features and supported algorithms of the message broker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Features {
+    /// `true` if metrics are collected and exposed.
+    metrics: bool,
+    /// `true` if the application was built with tracing.
+    tracing: bool,
+    /// `true` if whitelisted diagnostic queries are allowed.
+    diagnostics: bool,
+    /// Persistence backend implementation. Example: "cassandra" or "mem".
+    backend: String,
+    /// OID of the MAC currently used for integrity protection of events.
+    integrity_protection: String,
+    /// Supported message digest algorithms for deriving event identifiers.
+    event_id_algorithms: Vec<String>,
+    /// Supported canonicalizations of event documents.
+    canonicalizations: Vec<String>,
+    /// Protocol version currently active in the cluster.
+    ///
+    /// All broker instances in the cluster can parse data encoded using this
+    /// protocol version.
+    #[serde(default)]
+    cluster_protocol_version: u32,
+}
+
+impl Features {
+    /// Return a new instance.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        metrics: bool,
+        tracing: bool,
+        diagnostics: bool,
+        backend: &str,
+        integrity_protection: &str,
+        event_id_algorithms: Vec<String>,
+        canonicalizations: Vec<String>,
+        cluster_protocol_version: u32,
+    ) -> Self {
+        Self {
+            metrics,
+            tracing,
+            diagnostics,
+            backend: backend.to_owned(),
+            integrity_protection: integrity_protection.to_owned(),
+            event_id_algorithms,
+            canonicalizations,
+            cluster_protocol_version,
+        }
+    }
+
+    /// `true` if metrics are collected and exposed.
+    pub fn is_metrics_enabled(&self) -> bool {
+        self.metrics
+    }
+
+    /// `true` if the application was built with tracing.
+    pub fn is_tracing_enabled(&self) -> bool {
+        self.tracing
+    }
+
+    /// `true` if whitelisted diagnostic queries are allowed.
+    pub fn is_diagnostics_enabled(&self) -> bool {
+        self.diagnostics
+    }
+
+    /// Persistence backend implementation. Example: "cassandra" or "mem".
+    pub fn get_backend(&self) -> &str {
+        &self.backend
+    }
+
+    /// OID of the MAC currently used for integrity protection of events.
+    pub fn get_integrity_protection(&self) -> &str {
+        &self.integrity_protection
+    }
+
+    /// Supported message digest algorithms for deriving event identifiers.
+    pub fn get_event_id_algorithms(&self) -> &[String] {
+        &self.event_id_algorithms
+    }
+
+    /// Supported canonicalizations of event documents.
+    pub fn get_canonicalizations(&self) -> &[String] {
+        &self.canonicalizations
+    }
+
+    /// Protocol version currently active in the cluster.
+    pub fn get_cluster_protocol_version(&self) -> u32 {
+        self.cluster_protocol_version
+    }
+}
+
+/// Limits of event delivery to consumers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DeliveryLimits {
+    /// Redeliveries of the same event before a subscription is paused. `0`
+    /// means unlimited.
+    max_redeliveries: u32,
+    /// Maximum number of unconfirmed deliveries each consumer group may hold
+    /// by topic.
+    concurrency_limits: BTreeMap<String, u32>,
+}
+
+impl DeliveryLimits {
+    /// Return a new instance.
+    pub fn new(max_redeliveries: u32, concurrency_limits: BTreeMap<String, u32>) -> Self {
+        Self {
+            max_redeliveries,
+            concurrency_limits,
+        }
+    }
+
+    /// Redeliveries of the same event before a subscription is paused. `0`
+    /// means unlimited.
+    pub fn get_max_redeliveries(&self) -> u32 {
+        self.max_redeliveries
+    }
+
+    /// Maximum number of unconfirmed deliveries each consumer group may hold
+    /// by topic.
+    pub fn get_concurrency_limits(&self) -> &BTreeMap<String, u32> {
+        &self.concurrency_limits
+    }
+}
+
+/// Version, authentication and limits of the API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ApiCapabilities {
+    /// API version. Example: "v1"
+    version: String,
+    /// Supported authentication providers. Example: "kubernetes"
+    auth_providers: Vec<String>,
+    /// Maximum size in bytes of a published event document.
+    max_document_size: usize,
+    /// Maximum size in bytes of an aggregated WebSocket publish message.
+    max_ws_message_size: usize,
+    /// Max number of open WebSocket sessions of a single identity on an
+    /// instance (if limited).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_sessions_per_identity: Option<usize>,
+    /// Time in microseconds without any message from the client before a
+    /// WebSocket session is closed (if limited).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    session_idle_timeout_micros: Option<u64>,
+    /// The highest protocol version spoken by the instance.
+    #[serde(default)]
+    protocol_version: u32,
+    /// The oldest protocol version still understood by the instance.
+    #[serde(default)]
+    min_protocol_version: u32,
+}
+
+impl ApiCapabilities {
+    /// Return a new instance.
+    pub fn new(
+        version: &str,
+        auth_providers: Vec<String>,
+        max_document_size: usize,
+        max_ws_message_size: usize,
+        max_sessions_per_identity: Option<usize>,
+        session_idle_timeout_micros: Option<u64>,
+    ) -> Self {
+        Self {
+            version: version.to_owned(),
+            auth_providers,
+            max_document_size,
+            max_ws_message_size,
+            max_sessions_per_identity,
+            session_idle_timeout_micros,
+            protocol_version: crate::protocol::PROTOCOL_VERSION,
+            min_protocol_version: crate::protocol::MIN_PROTOCOL_VERSION,
+        }
+    }
+
+    /// API version. Example: "v1"
+    pub fn get_version(&self) -> &str {
+        &self.version
+    }
+
+    /// Supported authentication providers. Example: "kubernetes"
+    pub fn get_auth_providers(&self) -> &[String] {
+        &self.auth_providers
+    }
+
+    /// Maximum size in bytes of a published event document.
+    pub fn get_max_document_size(&self) -> usize {
+        self.max_document_size
+    }
+
+    /// Maximum size in bytes of an aggregated WebSocket publish message.
+    pub fn get_max_ws_message_size(&self) -> usize {
+        self.max_ws_message_size
+    }
+
+    /// Max number of open WebSocket sessions of a single identity on an
+    /// instance (if limited).
+    pub fn get_max_sessions_per_identity(&self) -> Option<usize> {
+        self.max_sessions_per_identity
+    }
+
+    /// Time in microseconds without any message from the client before a
+    /// WebSocket session is closed (if limited).
+    pub fn get_session_idle_timeout_micros(&self) -> Option<u64> {
+        self.session_idle_timeout_micros
+    }
+
+    /// The highest protocol version spoken by the instance.
+    pub fn get_protocol_version(&self) -> u32 {
+        self.protocol_version
+    }
+
+    /// The oldest protocol version still understood by the instance.
+    pub fn get_min_protocol_version(&self) -> u32 {
+        self.min_protocol_version
+    }
+}
+
+/// Capabilities and limits of the message broker.
+///
+/// This allows client libraries and platform tooling to adapt their behavior
+/// without out-of-band configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Capabilities {
+    /// Version of the message broker application.
+    app_version: String,
+    /// Version, authentication and limits of the API.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    api: Option<ApiCapabilities>,
+    /// Optional features and supported algorithms.
+    features: Features,
+    /// Limits of topic event descriptors.
+    descriptor_limits: DescriptorLimits,
+    /// Limits of event delivery to consumers.
+    delivery_limits: DeliveryLimits,
+}
+
+impl Capabilities {
+    /// Return a new instance.
+    pub fn new(
+        app_version: &str,
+        features: Features,
+        descriptor_limits: DescriptorLimits,
+        delivery_limits: DeliveryLimits,
+    ) -> Self {
+        Self {
+            app_version: app_version.to_owned(),
+            api: None,
+            features,
+            descriptor_limits,
+            delivery_limits,
+        }
+    }
+
+    /// Return this instance with the capabilities of the API that served the
+    /// request.
+    pub fn with_api(mut self, api: ApiCapabilities) -> Self {
+        self.api = Some(api);
+        self
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Version of the message broker application.
+    pub fn get_app_version(&self) -> &str {
+        &self.app_version
+    }
+
+    /// Version, authentication and limits of the API.
+    pub fn get_api(&self) -> &Option<ApiCapabilities> {
+        &self.api
+    }
+
+    /// Optional features and supported algorithms.
+    pub fn get_features(&self) -> &Features {
+        &self.features
+    }
+
+    /// Limits of topic event descriptors.
+    pub fn get_descriptor_limits(&self) -> &DescriptorLimits {
+        &self.descriptor_limits
+    }
+
+    /// Limits of event delivery to consumers.
+    pub fn get_delivery_limits(&self) -> &DeliveryLimits {
+        &self.delivery_limits
+    }
+}
@@ -0,0 +1,205 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Declarative definitions of named consumers of a topic.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Where the events of a declared consumer are delivered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConsumerDefinitionTarget {
+    /// Events are delivered by the server as HTTP POST requests to the URL.
+    Webhook {
+        /// `http` or `https` URL that accepts the event document.
+        url: String,
+    },
+    /// Events are delivered to the clients that connect as the principal.
+    Subscriber {
+        /// Identity string of the expected subscriber.
+        principal: String,
+    },
+}
+
+/// Declared consumer of a topic that is managed by the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConsumerDefinition {
+    /// Name of the consumer that is unique within the topic.
+    name: String,
+    /// Where events are delivered.
+    target: ConsumerDefinitionTarget,
+    /// Only deliver events of these types in a multi-type topic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_types: Option<Vec<String>>,
+    /// Only deliver events of at least this priority.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_priority: Option<u8>,
+    /// Starting position in epoch microseconds when the consumer is first
+    /// set up. `0` replays the full history and absence means from now on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    baseline_ts: Option<u64>,
+    /// Maximum number of events delivered per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_events_per_second: Option<u32>,
+    /// Only deliver the fields at these JSON Pointers of each event document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fields: Option<Vec<String>>,
+    /// Only deliver events where this filter expression matches the values
+    /// extracted from the event document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    filter: Option<String>,
+}
+
+impl ConsumerDefinition {
+    /// Prefix of consumer identifiers of webhook consumers.
+    const WEBHOOK_CONSUMER_PREFIX: &str = "webhook;";
+
+    /// Return a new instance.
+    pub fn new(
+        name: &str,
+        target: ConsumerDefinitionTarget,
+        event_types: Option<Vec<String>>,
+        min_priority: Option<u8>,
+        baseline_ts: Option<u64>,
+        max_events_per_second: Option<u32>,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            target,
+            event_types,
+            min_priority,
+            baseline_ts,
+            max_events_per_second,
+            fields: None,
+            filter: None,
+        }
+    }
+
+    /// Only deliver the fields at the JSON Pointers of each event document.
+    pub fn with_fields(mut self, fields: Vec<String>) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Only deliver events where the filter expression matches the values
+    /// extracted from the event document.
+    pub fn with_filter(mut self, filter: &str) -> Self {
+        self.filter = Some(filter.to_owned());
+        self
+    }
+
+    /// Return a new instance from a JSON serialized String.
+    pub fn from_string(value: &str) -> Option<Self> {
+        serde_json::from_str(value).ok()
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Name of the consumer that is unique within the topic.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Where events are delivered.
+    pub fn get_target(&self) -> &ConsumerDefinitionTarget {
+        &self.target
+    }
+
+    /// Only deliver events of these types in a multi-type topic.
+    pub fn get_event_types(&self) -> Option<&[String]> {
+        self.event_types.as_deref()
+    }
+
+    /// Only deliver events of at least this priority.
+    pub fn get_min_priority(&self) -> Option<u8> {
+        self.min_priority
+    }
+
+    /// Starting position in epoch microseconds when the consumer is first
+    /// set up.
+    pub fn get_baseline_ts(&self) -> Option<u64> {
+        self.baseline_ts
+    }
+
+    /// Maximum number of events delivered per second.
+    pub fn get_max_events_per_second(&self) -> Option<u32> {
+        self.max_events_per_second
+    }
+
+    /// Only deliver the fields at these JSON Pointers of each event document.
+    pub fn get_fields(&self) -> Option<&[String]> {
+        self.fields.as_deref()
+    }
+
+    /// Only deliver events where this filter expression matches the values
+    /// extracted from the event document.
+    pub fn get_filter(&self) -> Option<&str> {
+        self.filter.as_deref()
+    }
+
+    /// Return the identifier that deliveries to this consumer are tracked by.
+    ///
+    /// Webhook consumers are tracked by their name and subscribers by the
+    /// principal they connect as.
+    pub fn get_consumer_id(&self) -> String {
+        match &self.target {
+            ConsumerDefinitionTarget::Webhook { url: _ } => {
+                Self::WEBHOOK_CONSUMER_PREFIX.to_string() + &self.name
+            }
+            ConsumerDefinitionTarget::Subscriber { principal } => principal.to_owned(),
+        }
+    }
+}
+
+/// Declared consumers of a topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConsumerDefinitions {
+    /// Topic identifier.
+    topic_id: String,
+    /// Declared consumers ordered by name.
+    definitions: Vec<ConsumerDefinition>,
+}
+
+impl ConsumerDefinitions {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, mut definitions: Vec<ConsumerDefinition>) -> Self {
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        Self {
+            topic_id: topic_id.to_owned(),
+            definitions,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Declared consumers ordered by name.
+    pub fn get_definitions(&self) -> &[ConsumerDefinition] {
+        &self.definitions
+    }
+}
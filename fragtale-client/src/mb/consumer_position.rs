@@ -0,0 +1,100 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Explicitly committed or moved consumer position.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Position in a topic up to which a consumer has completed processing.
+///
+/// Committing a position moves the consumer's delivery baseline, so that no
+/// event at or before this point will be delivered (or retried) again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConsumerPosition {
+    /// Encoded unique time of the last event that the consumer has processed.
+    unique_time: u64,
+}
+
+impl ConsumerPosition {
+    /// Return a new instance.
+    pub fn new(unique_time: u64) -> Self {
+        Self { unique_time }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Return the encoded unique time of the last processed event.
+    pub fn get_unique_time(&self) -> u64 {
+        self.unique_time
+    }
+}
+
+/// Position in a topic that a consumer should be moved to.
+///
+/// Unlike a committed [ConsumerPosition], the consumer can be moved backwards
+/// to replay history. Either `unique_time` or `timestamp` must be present.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConsumerSeek {
+    /// Encoded unique time of the last event that the consumer should be
+    /// considered to have processed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unique_time: Option<u64>,
+    /// Epoch microseconds. Events published at or after this time will be
+    /// delivered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp: Option<u64>,
+}
+
+impl ConsumerSeek {
+    /// Return a new instance that moves the consumer to right after the
+    /// event with the encoded `unique_time`.
+    pub fn by_unique_time(unique_time: u64) -> Self {
+        Self {
+            unique_time: Some(unique_time),
+            timestamp: None,
+        }
+    }
+
+    /// Return a new instance that moves the consumer to the first event
+    /// published at or after `timestamp` in epoch microseconds.
+    pub fn by_timestamp(timestamp: u64) -> Self {
+        Self {
+            unique_time: None,
+            timestamp: Some(timestamp),
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Return the encoded unique time of the last event that the consumer
+    /// should be considered to have processed.
+    pub fn get_unique_time(&self) -> Option<u64> {
+        self.unique_time
+    }
+
+    /// Return the epoch microseconds from where events will be delivered.
+    pub fn get_timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
+}
@@ -0,0 +1,245 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Audit report of which consumers retrieved events of a topic.
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Record of a consumer retrieving an event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConsumptionRecord {
+    /// Consumer identifier.
+    consumer_id: String,
+    /// Claims of the authenticated identity behind the consumer identifier
+    /// (when known).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    identity_claims: BTreeMap<String, String>,
+    /// Event identifier.
+    event_id: String,
+    /// Encoded UniqueTime of the event.
+    unique_time: u64,
+    /// Time of the (latest) delivery in epoch microseconds.
+    delivery_ts_micros: u64,
+    /// `true` if the consumer confirmed the delivery.
+    done: bool,
+    /// Number of times the delivery was retried.
+    retry_count: u32,
+}
+
+impl ConsumptionRecord {
+    /// Return a new instance.
+    pub fn new(
+        consumer_id: &str,
+        identity_claims: BTreeMap<String, String>,
+        event_id: &str,
+        unique_time: u64,
+        delivery_ts_micros: u64,
+        done: bool,
+        retry_count: u32,
+    ) -> Self {
+        Self {
+            consumer_id: consumer_id.to_owned(),
+            identity_claims,
+            event_id: event_id.to_owned(),
+            unique_time,
+            delivery_ts_micros,
+            done,
+            retry_count,
+        }
+    }
+
+    /// Consumer identifier.
+    pub fn get_consumer_id(&self) -> &str {
+        &self.consumer_id
+    }
+
+    /// Claims of the authenticated identity behind the consumer identifier
+    /// (when known).
+    pub fn get_identity_claims(&self) -> &BTreeMap<String, String> {
+        &self.identity_claims
+    }
+
+    /// Event identifier.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Encoded UniqueTime of the event.
+    pub fn get_unique_time(&self) -> u64 {
+        self.unique_time
+    }
+
+    /// Time of the (latest) delivery in epoch microseconds.
+    pub fn get_delivery_ts_micros(&self) -> u64 {
+        self.delivery_ts_micros
+    }
+
+    /// `true` if the consumer confirmed the delivery.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Number of times the delivery was retried.
+    pub fn get_retry_count(&self) -> u32 {
+        self.retry_count
+    }
+}
+
+/// Signed report of which consumers retrieved events of a topic that were
+/// published in a time window.
+///
+/// The signature covers the serialized report without the signature, so the
+/// report can be handed to a third party and verified later.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConsumptionAudit {
+    /// Topic identifier.
+    topic_id: String,
+    /// Event identifier that the report is limited to (if any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_id: Option<String>,
+    /// Start of the publication time window in epoch microseconds.
+    from_ts_micros: u64,
+    /// End (inclusive) of the publication time window in epoch
+    /// microseconds.
+    to_ts_micros: u64,
+    /// Time the report was generated in epoch microseconds.
+    generated_ts_micros: u64,
+    /// Consumption records ordered by event UniqueTime.
+    records: Vec<ConsumptionRecord>,
+    /// `true` if the report was truncated and a narrower time window should
+    /// be used to get all records.
+    truncated: bool,
+    /// Integrity protection of the report.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+}
+
+impl ConsumptionAudit {
+    /// Return a new unsigned instance.
+    pub fn new(
+        topic_id: &str,
+        event_id: Option<&str>,
+        from_ts_micros: u64,
+        to_ts_micros: u64,
+        generated_ts_micros: u64,
+        records: Vec<ConsumptionRecord>,
+        truncated: bool,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            event_id: event_id.map(str::to_owned),
+            from_ts_micros,
+            to_ts_micros,
+            generated_ts_micros,
+            records,
+            truncated,
+            signature: None,
+        }
+    }
+
+    /// Return a signed copy of the report.
+    pub fn with_signature(self, signature: String) -> Self {
+        Self {
+            signature: Some(signature),
+            ..self
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Return the JSON serialized String that the signature covers.
+    pub fn as_signed_string(&self) -> String {
+        serde_json::to_string(&Self {
+            signature: None,
+            ..self.clone()
+        })
+        .unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Event identifier that the report is limited to (if any).
+    pub fn get_event_id(&self) -> Option<&str> {
+        self.event_id.as_deref()
+    }
+
+    /// Start of the publication time window in epoch microseconds.
+    pub fn get_from_ts_micros(&self) -> u64 {
+        self.from_ts_micros
+    }
+
+    /// End (inclusive) of the publication time window in epoch
+    /// microseconds.
+    pub fn get_to_ts_micros(&self) -> u64 {
+        self.to_ts_micros
+    }
+
+    /// Time the report was generated in epoch microseconds.
+    pub fn get_generated_ts_micros(&self) -> u64 {
+        self.generated_ts_micros
+    }
+
+    /// Consumption records ordered by event UniqueTime.
+    pub fn get_records(&self) -> &[ConsumptionRecord] {
+        &self.records
+    }
+
+    /// `true` if the report was truncated and a narrower time window should
+    /// be used to get all records.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Integrity protection of the report.
+    pub fn get_signature(&self) -> Option<&str> {
+        self.signature.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_is_not_part_of_signed_string() {
+        let record = ConsumptionRecord::new(
+            "bearer;https_issuer_example;alice",
+            BTreeMap::from([("sub".to_string(), "alice".to_string())]),
+            "event1",
+            1 << 40,
+            1_700_000_000_000_000,
+            true,
+            0,
+        );
+        let audit = ConsumptionAudit::new("topic", None, 1, 2, 3, vec![record], false);
+        let signed_string = audit.as_signed_string();
+        assert_eq!(signed_string, audit.as_string());
+        let audit = audit.with_signature("signature".to_string());
+        assert_eq!(audit.as_signed_string(), signed_string);
+        let parsed: ConsumptionAudit = serde_json::from_str(&audit.as_string()).unwrap();
+        assert_eq!(parsed.get_signature(), Some("signature"));
+        assert_eq!(parsed.as_signed_string(), signed_string);
+    }
+}
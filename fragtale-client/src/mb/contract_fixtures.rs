@@ -0,0 +1,145 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Contract test fixtures generated from a topic's event descriptor.
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Sample event document and the expected outcome of publishing it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ContractFixture {
+    /// Short description of the fixture. Example: "missing required property
+    /// 'amount'"
+    name: String,
+    /// Kind of event in a multi-type topic (absent for the topic's schema).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_type: Option<String>,
+    /// Sample event document.
+    document: Value,
+    /// `true` if the document is expected to be accepted when published.
+    valid: bool,
+    /// Expected extraction results by result name. Text results are JSON
+    /// strings and bigint results are JSON numbers.
+    ///
+    /// Always empty for invalid documents.
+    extractions: BTreeMap<String, Value>,
+}
+
+impl ContractFixture {
+    /// Return a new instance of a document that is expected to be accepted.
+    pub fn valid(
+        name: &str,
+        event_type: Option<&str>,
+        document: Value,
+        extractions: BTreeMap<String, Value>,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            event_type: event_type.map(str::to_owned),
+            document,
+            valid: true,
+            extractions,
+        }
+    }
+
+    /// Return a new instance of a document that is expected to be rejected.
+    pub fn invalid(name: &str, event_type: Option<&str>, document: Value) -> Self {
+        Self {
+            name: name.to_owned(),
+            event_type: event_type.map(str::to_owned),
+            document,
+            valid: false,
+            extractions: BTreeMap::new(),
+        }
+    }
+
+    /// Short description of the fixture.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Kind of event in a multi-type topic (if any).
+    pub fn get_event_type(&self) -> &Option<String> {
+        &self.event_type
+    }
+
+    /// Sample event document.
+    pub fn get_document(&self) -> &Value {
+        &self.document
+    }
+
+    /// `true` if the document is expected to be accepted when published.
+    pub fn is_valid(&self) -> bool {
+        self.valid
+    }
+
+    /// Expected extraction results by result name.
+    pub fn get_extractions(&self) -> &BTreeMap<String, Value> {
+        &self.extractions
+    }
+}
+
+/// Language-agnostic contract test fixtures of a topic.
+///
+/// Producers can verify that their documents are shaped like the valid
+/// fixtures and consumers can verify that they extract the same values from
+/// the same canonical examples.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ContractFixtures {
+    /// Topic identifier.
+    topic_id: String,
+    /// Encoded version of the event descriptor the fixtures were generated
+    /// from.
+    descriptor_version: u64,
+    /// Generated fixtures.
+    #[schema(inline)]
+    fixtures: Vec<ContractFixture>,
+}
+
+impl ContractFixtures {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, descriptor_version: u64, fixtures: Vec<ContractFixture>) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            descriptor_version,
+            fixtures,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Encoded version of the event descriptor the fixtures were generated
+    /// from.
+    pub fn get_descriptor_version(&self) -> u64 {
+        self.descriptor_version
+    }
+
+    /// Generated fixtures.
+    pub fn get_fixtures(&self) -> &[ContractFixture] {
+        &self.fixtures
+    }
+}
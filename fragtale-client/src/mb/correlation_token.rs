@@ -44,12 +44,29 @@ valid correlation token could be attached to unrelated events.
 The integrity protection will only ensure that a correlation token was not
 tampered with during processing to prevent that a rouge or poorly written client
 could fill up the server with waiters for bogus correlation tokens.
+
+Each token is bound to the topic where it was issued. The protection key is
+derived with HKDF from the master secret and the `topic_id`, so a token can't be
+relabeled to claim that it was issued in a different topic.
+
+Legacy tokens issued before this binding have no topic and are still verified
+with the master secret, so they remain valid until they are no longer in use.
+
+## Priority inheritance
+
+The token carries the explicitly requested priority of the original request (if
+any), so correlated replies can inherit it unless they request a priority of
+their own.
 */
 #[serde_as]
 #[derive(Clone, Deserialize, Serialize)]
 pub struct CorrelationToken {
     uid: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    topic: String,
     timestamp: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<u8>,
     #[serde_as(as = "Base64")]
     integrity: Vec<u8>,
 }
@@ -71,17 +88,35 @@ impl CorrelationToken {
 }
 
 impl CorrelationToken {
-    /// New correlation
-    pub fn new(oid: &[u32], secret: &[u8], timestamp: u64) -> Self {
+    /// Label used as HKDF `info` prefix when deriving per topic secrets.
+    const HKDF_INFO_PREFIX: &[u8] = b"fragtale correlation token ";
+
+    /// New correlation of a request published to `topic_id`.
+    pub fn new(oid: &[u32], master_secret: &[u8], topic_id: &str, timestamp: u64) -> Self {
+        Self::new_with_priority(oid, master_secret, topic_id, timestamp, None)
+    }
+
+    /// New correlation of a request published to `topic_id` with the provided
+    /// priority.
+    pub fn new_with_priority(
+        oid: &[u32],
+        master_secret: &[u8],
+        topic_id: &str,
+        timestamp: u64,
+        priority: Option<u8>,
+    ) -> Self {
         // UID might be assumed elsewhere to be hard to guess (-> 256 bits)
         let uid = tyst::encdec::base64::encode_url(
             &Tyst::instance().prng_get_random_bytes(None, 32),
             false,
         );
-        let integrity = Self::protect(oid, secret, &uid, timestamp);
+        let secret = Self::derive_topic_secret(oid, master_secret, topic_id);
+        let integrity = Self::protect(oid, &secret, &uid, timestamp, priority);
         Self {
             uid,
+            topic: topic_id.to_owned(),
             timestamp,
+            priority,
             integrity,
         }
     }
@@ -91,27 +126,148 @@ impl CorrelationToken {
         &self.uid
     }
 
+    /// Return the topic where the original request was published
+    ///
+    /// This is empty for legacy tokens that are not bound to a topic.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic
+    }
+
     /// Return the timestamp of the original request
     pub fn get_timestamp_micros(&self) -> u64 {
         self.timestamp
     }
 
-    fn protect(oid: &[u32], secret: &[u8], uid: &str, timestamp: u64) -> Vec<u8> {
+    /// Return the explicitly requested priority of the original request
+    pub fn get_priority(&self) -> Option<u8> {
+        self.priority
+    }
+
+    fn protect(
+        oid: &[u32],
+        secret: &[u8],
+        uid: &str,
+        timestamp: u64,
+        priority: Option<u8>,
+    ) -> Vec<u8> {
+        let timestamp_bytes = u64::to_be_bytes(timestamp);
+        let priority_bytes = priority.map(|priority| [priority]);
+        let mut parts: Vec<&[u8]> = vec![uid.as_bytes(), &timestamp_bytes];
+        // The priority is only protected when present, so tokens issued
+        // before priority inheritance keep the same protection
+        if let Some(priority_bytes) = &priority_bytes {
+            parts.push(priority_bytes);
+        }
+        Self::mac(oid, secret, &parts)
+    }
+
+    /// Derive the per topic secret from the `master_secret` using HKDF
+    /// (RFC 5869) with the MAC identified by `oid` as the HMAC.
+    ///
+    /// The derived secret has the same length as the MAC output.
+    fn derive_topic_secret(oid: &[u32], master_secret: &[u8], topic_id: &str) -> Vec<u8> {
+        Self::derive_labeled_topic_secret(oid, master_secret, Self::HKDF_INFO_PREFIX, topic_id)
+    }
+
+    /// Derive a per topic secret for the purpose described by `info_prefix`
+    /// from the `master_secret` using HKDF (RFC 5869).
+    pub(crate) fn derive_labeled_topic_secret(
+        oid: &[u32],
+        master_secret: &[u8],
+        info_prefix: &[u8],
+        topic_id: &str,
+    ) -> Vec<u8> {
+        // HKDF-Extract without salt (a string of zeros of MAC output length)
+        let salt = vec![0u8; Self::mac_size_bytes(oid)];
+        let prk = Self::mac(oid, &salt, &[master_secret]);
+        // HKDF-Expand of a single block: T(1) = HMAC(PRK, info | 0x01)
+        Self::mac(oid, &prk, &[info_prefix, topic_id.as_bytes(), &[0x01]])
+    }
+
+    /// Return the output size in bytes of the MAC identified by `oid`.
+    fn mac_size_bytes(oid: &[u32]) -> usize {
+        Tyst::instance()
+            .macs()
+            .by_oid(&tyst::encdec::oid::as_string(oid))
+            .unwrap()
+            .get_mac_size_bits()
+            >> 3
+    }
+
+    /// Return the MAC of the concatenated `parts` using `key`.
+    pub(crate) fn mac(oid: &[u32], key: &[u8], parts: &[&[u8]]) -> Vec<u8> {
         let mut mac = Tyst::instance()
             .macs()
             .by_oid(&tyst::encdec::oid::as_string(oid))
             .unwrap();
-        mac.init(secret.to_mac_key().as_ref());
-        mac.update(uid.as_bytes());
-        mac.update(&u64::to_be_bytes(timestamp));
+        mac.init(key.to_mac_key().as_ref());
+        for part in parts {
+            mac.update(part);
+        }
         let mut out = vec![0u8; mac.get_mac_size_bits() >> 3];
         mac.finalize(&mut out);
         out
     }
 
-    /// Verify the correlation token's integrity protection.
-    pub fn verify(&self, oid: &[u32], secret: &[u8]) -> bool {
-        let out = Self::protect(oid, secret, &self.uid, self.timestamp);
+    /// Verify the correlation token's integrity protection using the secret
+    /// derived for the topic where the token was issued.
+    ///
+    /// Legacy tokens without a topic are verified using the master secret.
+    pub fn verify(&self, oid: &[u32], master_secret: &[u8]) -> bool {
+        let secret = if self.topic.is_empty() {
+            master_secret.to_vec()
+        } else {
+            Self::derive_topic_secret(oid, master_secret, &self.topic)
+        };
+        let out = Self::protect(oid, &secret, &self.uid, self.timestamp, self.priority);
         tyst::util::external_constant_time_equals(&self.integrity, &out)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn correlation_token_is_bound_to_topic() {
+        let oid = tyst::oids::mac::HMAC_SHA3_256;
+        let master_secret = Tyst::instance().prng_get_random_bytes(None, 32);
+        let ct = CorrelationToken::new_with_priority(oid, &master_secret, "topic_a", 42, Some(7));
+        let ct = CorrelationToken::from_string(ct.as_string()).unwrap();
+        assert_eq!(ct.get_topic_id(), "topic_a");
+        assert_eq!(ct.get_priority(), Some(7));
+        assert!(ct.verify(oid, &master_secret));
+        // Relabel the token to another topic
+        let mut relabeled = ct.clone();
+        relabeled.topic = "topic_b".to_owned();
+        assert!(!relabeled.verify(oid, &master_secret));
+        // Different master secret
+        let other_secret = Tyst::instance().prng_get_random_bytes(None, 32);
+        assert!(!ct.verify(oid, &other_secret));
+    }
+
+    #[test]
+    fn legacy_correlation_token_is_verified_with_master_secret() {
+        let oid = tyst::oids::mac::HMAC_SHA3_256;
+        let master_secret = Tyst::instance().prng_get_random_bytes(None, 32);
+        // Tokens issued before topic binding had no topic and used the master secret
+        let legacy = CorrelationToken {
+            uid: "uid".to_owned(),
+            topic: String::new(),
+            timestamp: 42,
+            priority: None,
+            integrity: CorrelationToken::protect(oid, &master_secret, "uid", 42, None),
+        };
+        let legacy_json =
+            String::from_utf8(tyst::encdec::base64::decode_url(&legacy.as_string()).unwrap())
+                .unwrap();
+        assert!(!legacy_json.contains("topic"));
+        let ct = CorrelationToken::from_string(legacy.as_string()).unwrap();
+        assert_eq!(ct.get_topic_id(), "");
+        assert!(ct.verify(oid, &master_secret));
+        // Relabeling a legacy token to a topic breaks it
+        let mut relabeled = ct.clone();
+        relabeled.topic = "topic_a".to_owned();
+        assert!(!relabeled.verify(oid, &master_secret));
+    }
+}
@@ -0,0 +1,177 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Events that consumers failed to process.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An event that could not be delivered to a consumer and was published to
+/// the dead-letter topic of the event's topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DeadLetter {
+    /// Topic of the original event.
+    topic_id: String,
+    /// Consumer that failed to process the event.
+    consumer_id: String,
+    /// Encoded unique time of the original event.
+    unique_time: u64,
+    /// Identifier of the original event (if known).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_id: Option<String>,
+    /// Reason for dead-lettering the event.
+    reason: String,
+    /// Time of the dead-lettering in epoch microseconds.
+    dead_lettered_ts_micros: u64,
+    /// The original event document.
+    document: String,
+}
+
+impl DeadLetter {
+    /// Return a new instance.
+    pub fn new(
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: u64,
+        event_id: Option<&str>,
+        reason: &str,
+        dead_lettered_ts_micros: u64,
+        document: &str,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            consumer_id: consumer_id.to_owned(),
+            unique_time,
+            event_id: event_id.map(str::to_owned),
+            reason: reason.to_owned(),
+            dead_lettered_ts_micros,
+            document: document.to_owned(),
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic of the original event.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Consumer that failed to process the event.
+    pub fn get_consumer_id(&self) -> &str {
+        &self.consumer_id
+    }
+
+    /// Encoded unique time of the original event.
+    pub fn get_unique_time(&self) -> u64 {
+        self.unique_time
+    }
+
+    /// Identifier of the original event (if known).
+    pub fn get_event_id(&self) -> &Option<String> {
+        &self.event_id
+    }
+
+    /// Reason for dead-lettering the event.
+    pub fn get_reason(&self) -> &str {
+        &self.reason
+    }
+
+    /// Time of the dead-lettering in epoch microseconds.
+    pub fn get_dead_lettered_ts_micros(&self) -> u64 {
+        self.dead_lettered_ts_micros
+    }
+
+    /// The original event document.
+    pub fn get_document(&self) -> &str {
+        &self.document
+    }
+}
+
+/// A [DeadLetter] as an event in the dead-letter topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DeadLetteredEvent {
+    /// Identifier of the event in the dead-letter topic.
+    event_id: String,
+    /// Encoded unique time of the event in the dead-letter topic.
+    unique_time: u64,
+    /// The dead-lettered event.
+    dead_letter: DeadLetter,
+}
+
+impl DeadLetteredEvent {
+    /// Return a new instance.
+    pub fn new(event_id: &str, unique_time: u64, dead_letter: DeadLetter) -> Self {
+        Self {
+            event_id: event_id.to_owned(),
+            unique_time,
+            dead_letter,
+        }
+    }
+
+    /// Identifier of the event in the dead-letter topic.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Encoded unique time of the event in the dead-letter topic.
+    pub fn get_unique_time(&self) -> u64 {
+        self.unique_time
+    }
+
+    /// The dead-lettered event.
+    pub fn get_dead_letter(&self) -> &DeadLetter {
+        &self.dead_letter
+    }
+}
+
+/// A page of dead-lettered events ordered by the time of dead-lettering.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DeadLetters {
+    /// Dead-lettered events.
+    dead_lettered_events: Vec<DeadLetteredEvent>,
+    /// `true` if there are more dead-lettered events after the last one
+    /// returned.
+    more: bool,
+}
+
+impl DeadLetters {
+    /// Return a new instance.
+    pub fn new(dead_lettered_events: Vec<DeadLetteredEvent>, more: bool) -> Self {
+        Self {
+            dead_lettered_events,
+            more,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Dead-lettered events.
+    pub fn get_dead_lettered_events(&self) -> &[DeadLetteredEvent] {
+        &self.dead_lettered_events
+    }
+
+    /// `true` if there are more dead-lettered events after the last one
+    /// returned.
+    pub fn has_more(&self) -> bool {
+        self.more
+    }
+}
@@ -0,0 +1,78 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Metadata that describes a delivered event.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Metadata that describes a delivered event.
+///
+/// This allows consumers of multiple topics or descriptor versions to handle
+/// the event document without deriving the context from the document itself.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeliveryEnvelope {
+    /// Topic identifier.
+    topic_id: String,
+    /// Event descriptor version the event was published with (SemVer).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    descriptor_version: Option<String>,
+    /// Identifier of the event schema of the descriptor version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema_id: Option<String>,
+    /// Event identifier.
+    event_id: String,
+}
+
+impl DeliveryEnvelope {
+    /// Return a new instance.
+    pub fn new(
+        topic_id: &str,
+        descriptor_version: Option<String>,
+        schema_id: Option<String>,
+        event_id: String,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            descriptor_version,
+            schema_id,
+            event_id,
+        }
+    }
+
+    /// Return the topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Return the event descriptor version (SemVer) the event was published
+    /// with, when known.
+    pub fn get_descriptor_version(&self) -> Option<&str> {
+        self.descriptor_version.as_deref()
+    }
+
+    /// Return the identifier of the event schema the event was published
+    /// with, when known.
+    pub fn get_schema_id(&self) -> Option<&str> {
+        self.schema_id.as_deref()
+    }
+
+    /// Return the event identifier.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+}
@@ -0,0 +1,50 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Prepared confirmation of an event delivery.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// First step of a two-phase confirmation of an event delivery.
+///
+/// Ties the consumer's external transaction identifier to the delivery until
+/// the confirmation is committed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DeliveryPreparation {
+    /// The consumer's transaction identifier in the external sink.
+    transaction_id: String,
+}
+
+impl DeliveryPreparation {
+    /// Return a new instance.
+    pub fn new(transaction_id: &str) -> Self {
+        Self {
+            transaction_id: transaction_id.to_owned(),
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Return the consumer's transaction identifier in the external sink.
+    pub fn get_transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+}
@@ -0,0 +1,156 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Receipts of confirmed event deliveries for producers.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Where receipts of confirmed deliveries of a topic's events are emitted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeliveryReceiptTarget {
+    /// Receipts are published as events to the topic's receipt topic.
+    ///
+    /// See [DeliveryReceipt::receipt_topic_id].
+    Topic,
+    /// Receipts are sent as HTTP POST requests to the URL.
+    ///
+    /// Unless the host is configured as an allowed receipt webhook host, only
+    /// clients allowed to declare webhook consumers may set the URL.
+    Webhook {
+        /// `http` or `https` URL that accepts the receipt document.
+        url: String,
+    },
+}
+
+/// Receipt of a confirmed delivery of an event to a consumer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DeliveryReceipt {
+    /// Topic identifier of the delivered event.
+    topic_id: String,
+    /// Identifier of the delivered event.
+    event_id: String,
+    /// Encoded UniqueTime of the delivered event.
+    unique_time: u64,
+    /// Identifier of the consumer (group) that confirmed the delivery.
+    consumer_id: String,
+    /// Epoch timestamp in microseconds when the delivery was confirmed.
+    confirmed_ts: u64,
+    /// Microseconds from the event was accepted until the delivery was
+    /// confirmed.
+    latency_micros: u64,
+}
+
+impl DeliveryReceipt {
+    /// Suffix of the topic that receipts of a topic are published to.
+    const RECEIPT_TOPIC_SUFFIX: &str = "_receipts";
+
+    /// Return a new instance.
+    pub fn new(
+        topic_id: &str,
+        event_id: &str,
+        unique_time: u64,
+        consumer_id: &str,
+        confirmed_ts: u64,
+        latency_micros: u64,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            event_id: event_id.to_owned(),
+            unique_time,
+            consumer_id: consumer_id.to_owned(),
+            confirmed_ts,
+            latency_micros,
+        }
+    }
+
+    /// Return the topic that receipts of `topic_id` are published to when
+    /// the [DeliveryReceiptTarget::Topic] is used.
+    pub fn receipt_topic_id(topic_id: &str) -> String {
+        topic_id.to_owned() + Self::RECEIPT_TOPIC_SUFFIX
+    }
+
+    /// Return `true` if `topic_id` is a topic that receipts are published to.
+    pub fn is_receipt_topic_id(topic_id: &str) -> bool {
+        topic_id.ends_with(Self::RECEIPT_TOPIC_SUFFIX)
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Return a new instance from JSON serialization.
+    pub fn from_string<S: AsRef<str>>(value: S) -> Self {
+        serde_json::from_str(value.as_ref()).unwrap()
+    }
+
+    /// Topic identifier of the delivered event.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Identifier of the delivered event.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Encoded UniqueTime of the delivered event.
+    pub fn get_unique_time(&self) -> u64 {
+        self.unique_time
+    }
+
+    /// Identifier of the consumer (group) that confirmed the delivery.
+    pub fn get_consumer_id(&self) -> &str {
+        &self.consumer_id
+    }
+
+    /// Epoch timestamp in microseconds when the delivery was confirmed.
+    pub fn get_confirmed_ts(&self) -> u64 {
+        self.confirmed_ts
+    }
+
+    /// Microseconds from the event was accepted until the delivery was
+    /// confirmed.
+    pub fn get_latency_micros(&self) -> u64 {
+        self.latency_micros
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receipt_target_serialization() {
+        let target: DeliveryReceiptTarget = serde_json::from_str(r#"{"type":"topic"}"#).unwrap();
+        assert_eq!(target, DeliveryReceiptTarget::Topic);
+        let target: DeliveryReceiptTarget =
+            serde_json::from_str(r#"{"type":"webhook","url":"https://example.com/r"}"#).unwrap();
+        assert_eq!(
+            target,
+            DeliveryReceiptTarget::Webhook {
+                url: "https://example.com/r".to_owned()
+            }
+        );
+        let receipt_topic_id = DeliveryReceipt::receipt_topic_id("orders");
+        assert_eq!(receipt_topic_id, "orders_receipts");
+        assert!(DeliveryReceipt::is_receipt_topic_id(&receipt_topic_id));
+        assert!(!DeliveryReceipt::is_receipt_topic_id("orders"));
+    }
+}
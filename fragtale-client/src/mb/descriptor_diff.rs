@@ -0,0 +1,470 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Differences between two versions of a topic's event descriptor.
+
+use super::event_descriptor::EventDescriptor;
+use super::event_descriptor::EventSchema;
+use super::event_descriptor::Extractor;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Change of a topic level setting of the event descriptor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SettingChange {
+    /// Name of the setting. Example: "canonicalization"
+    name: String,
+    /// Value in the older version (absent when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+    /// Value in the newer version (absent when unset).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to: Option<String>,
+}
+
+impl SettingChange {
+    /// Name of the setting.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Value in the older version (if set).
+    pub fn get_from(&self) -> &Option<String> {
+        &self.from
+    }
+
+    /// Value in the newer version (if set).
+    pub fn get_to(&self) -> &Option<String> {
+        &self.to
+    }
+}
+
+/// Change of an event schema.
+///
+/// The schema is compared as a JSON object with the properties `schema_id`,
+/// `schema_type` and `schema_data`, where `schema_data` is the parsed schema
+/// document (or the raw text if it isn't JSON).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SchemaChange {
+    /// Kind of event in a multi-type topic (absent for the topic's schema).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_type: Option<String>,
+    /// JSON Pointer to the changed location. Example:
+    /// "/schema_data/properties/amount/type"
+    path: String,
+    /// Value in the older version (absent when added).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    from: Option<Value>,
+    /// Value in the newer version (absent when removed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to: Option<Value>,
+}
+
+impl SchemaChange {
+    /// Kind of event in a multi-type topic (if any).
+    pub fn get_event_type(&self) -> &Option<String> {
+        &self.event_type
+    }
+
+    /// JSON Pointer to the changed location.
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /// Value in the older version (if any).
+    pub fn get_from(&self) -> &Option<Value> {
+        &self.from
+    }
+
+    /// Value in the newer version (if any).
+    pub fn get_to(&self) -> &Option<Value> {
+        &self.to
+    }
+}
+
+/// Change of an extractor identified by its result name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ExtractorChange {
+    /// Kind of event in a multi-type topic (absent for the topic's
+    /// extractors).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_type: Option<String>,
+    /// Name of the extracted result.
+    result_name: String,
+    /// Extractor in the older version (absent when added).
+    #[schema(inline)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    from: Option<Extractor>,
+    /// Extractor in the newer version (absent when removed).
+    #[schema(inline)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to: Option<Extractor>,
+}
+
+impl ExtractorChange {
+    /// Kind of event in a multi-type topic (if any).
+    pub fn get_event_type(&self) -> &Option<String> {
+        &self.event_type
+    }
+
+    /// Name of the extracted result.
+    pub fn get_result_name(&self) -> &str {
+        &self.result_name
+    }
+
+    /// Extractor in the older version (if any).
+    pub fn get_from(&self) -> &Option<Extractor> {
+        &self.from
+    }
+
+    /// Extractor in the newer version (if any).
+    pub fn get_to(&self) -> &Option<Extractor> {
+        &self.to
+    }
+}
+
+/// Structured differences between two versions of a topic's event
+/// descriptor.
+///
+/// This allows consumers to assess the impact of an upcoming version before
+/// opting in to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DescriptorDiff {
+    /// Topic identifier.
+    topic_id: String,
+    /// Encoded version of the older descriptor.
+    from_version: u64,
+    /// Encoded version of the newer descriptor.
+    to_version: u64,
+    /// Changed topic level settings.
+    settings: Vec<SettingChange>,
+    /// Changed event schemas.
+    schema_changes: Vec<SchemaChange>,
+    /// Changed extractors.
+    extractor_changes: Vec<ExtractorChange>,
+    /// Kinds of events that were added in the newer version.
+    added_event_types: Vec<String>,
+    /// Kinds of events that were removed in the newer version.
+    removed_event_types: Vec<String>,
+}
+
+impl DescriptorDiff {
+    /// Return the differences going from the `from` to the `to`
+    /// [EventDescriptor] of the topic.
+    pub fn between(topic_id: &str, from: &EventDescriptor, to: &EventDescriptor) -> Self {
+        let mut diff = Self {
+            topic_id: topic_id.to_owned(),
+            from_version: from.get_version(),
+            to_version: to.get_version(),
+            settings: vec![],
+            schema_changes: vec![],
+            extractor_changes: vec![],
+            added_event_types: vec![],
+            removed_event_types: vec![],
+        };
+        diff.diff_settings(from, to);
+        diff.diff_schema(None, from.get_event_schema(), to.get_event_schema());
+        diff.diff_extractors(None, from.get_extractors(), to.get_extractors());
+        let event_types = |ed: &EventDescriptor| {
+            ed.get_event_types()
+                .iter()
+                .flatten()
+                .map(|etd| etd.get_event_type().to_owned())
+                .collect::<BTreeSet<_>>()
+        };
+        let (from_types, to_types) = (event_types(from), event_types(to));
+        diff.added_event_types = to_types.difference(&from_types).cloned().collect();
+        diff.removed_event_types = from_types.difference(&to_types).cloned().collect();
+        for event_type in from_types.intersection(&to_types) {
+            let from_etd = from.get_event_type_descriptor(event_type).unwrap();
+            let to_etd = to.get_event_type_descriptor(event_type).unwrap();
+            diff.diff_schema(
+                Some(event_type),
+                from_etd.get_event_schema(),
+                to_etd.get_event_schema(),
+            );
+            diff.diff_extractors(
+                Some(event_type),
+                from_etd.get_extractors(),
+                to_etd.get_extractors(),
+            );
+        }
+        diff
+    }
+
+    fn diff_settings(&mut self, from: &EventDescriptor, to: &EventDescriptor) {
+        let settings = |ed: &EventDescriptor| {
+            [
+                ("version_min", ed.get_version_min().map(|v| v.to_string())),
+                (
+                    "reject_store",
+                    Some(ed.is_reject_store_enabled().to_string()),
+                ),
+                ("dead_letter", Some(ed.is_dead_letter_enabled().to_string())),
+                ("event_id_algorithm", ed.get_event_id_algorithm().to_owned()),
+                (
+                    "event_id_collision_policy",
+                    ed.get_event_id_collision_policy().to_owned(),
+                ),
+                ("canonicalization", ed.get_canonicalization().to_owned()),
+                ("event_type_field", ed.get_event_type_field().to_owned()),
+                ("routing_key_field", ed.get_routing_key_field().to_owned()),
+                (
+                    "delivery_receipts",
+                    ed.get_delivery_receipts()
+                        .as_ref()
+                        .map(|target| serde_json::to_string(target).unwrap()),
+                ),
+                (
+                    "default_values",
+                    ed.get_default_values()
+                        .as_ref()
+                        .map(|default_values| serde_json::to_string(default_values).unwrap()),
+                ),
+                (
+                    "masking_rules",
+                    ed.get_masking_rules()
+                        .as_ref()
+                        .map(|masking_rules| serde_json::to_string(masking_rules).unwrap()),
+                ),
+            ]
+        };
+        for ((name, from), (_, to)) in settings(from).into_iter().zip(settings(to)) {
+            if from != to {
+                self.settings.push(SettingChange {
+                    name: name.to_owned(),
+                    from,
+                    to,
+                });
+            }
+        }
+    }
+
+    fn diff_schema(
+        &mut self,
+        event_type: Option<&str>,
+        from: &Option<EventSchema>,
+        to: &Option<EventSchema>,
+    ) {
+        let mut changes = vec![];
+        Self::diff_json(
+            String::new(),
+            from.as_ref().map(Self::schema_as_value).as_ref(),
+            to.as_ref().map(Self::schema_as_value).as_ref(),
+            &mut changes,
+        );
+        self.schema_changes
+            .extend(changes.into_iter().map(|(path, from, to)| SchemaChange {
+                event_type: event_type.map(str::to_owned),
+                path,
+                from,
+                to,
+            }));
+    }
+
+    /// Return the schema as a JSON object for comparison.
+    fn schema_as_value(event_schema: &EventSchema) -> Value {
+        let schema_data = serde_json::from_str(event_schema.get_schema_data())
+            .unwrap_or_else(|_| Value::String(event_schema.get_schema_data().to_owned()));
+        serde_json::json!({
+            "schema_id": event_schema.get_schema_id(),
+            "schema_type": event_schema.get_schema_type(),
+            "schema_data": schema_data,
+        })
+    }
+
+    /// Recursively collect changed locations of two JSON values.
+    ///
+    /// Objects are compared property by property, while other values
+    /// (including arrays) are compared as a whole.
+    fn diff_json(
+        path: String,
+        from: Option<&Value>,
+        to: Option<&Value>,
+        changes: &mut Vec<(String, Option<Value>, Option<Value>)>,
+    ) {
+        match (from, to) {
+            (Some(Value::Object(from)), Some(Value::Object(to))) => {
+                let keys = from.keys().chain(to.keys()).collect::<BTreeSet<_>>();
+                for key in keys {
+                    // Escape the reference token as described in RFC 6901
+                    let token = key.replace('~', "~0").replace('/', "~1");
+                    Self::diff_json(
+                        format!("{path}/{token}"),
+                        from.get(key),
+                        to.get(key),
+                        changes,
+                    );
+                }
+            }
+            (from, to) if from != to => {
+                changes.push((path, from.cloned(), to.cloned()));
+            }
+            _ => {}
+        }
+    }
+
+    fn diff_extractors(
+        &mut self,
+        event_type: Option<&str>,
+        from: &Option<Vec<Extractor>>,
+        to: &Option<Vec<Extractor>>,
+    ) {
+        let find = |extractors: &Option<Vec<Extractor>>, result_name: &str| {
+            extractors
+                .iter()
+                .flatten()
+                .find(|extractor| extractor.get_result_name() == result_name)
+                .cloned()
+        };
+        let result_names = from
+            .iter()
+            .flatten()
+            .chain(to.iter().flatten())
+            .map(Extractor::get_result_name)
+            .collect::<BTreeSet<_>>();
+        for result_name in result_names {
+            let (from, to) = (find(from, result_name), find(to, result_name));
+            if from != to {
+                self.extractor_changes.push(ExtractorChange {
+                    event_type: event_type.map(str::to_owned),
+                    result_name: result_name.to_owned(),
+                    from,
+                    to,
+                });
+            }
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Encoded version of the older descriptor.
+    pub fn get_from_version(&self) -> u64 {
+        self.from_version
+    }
+
+    /// Encoded version of the newer descriptor.
+    pub fn get_to_version(&self) -> u64 {
+        self.to_version
+    }
+
+    /// Changed topic level settings.
+    pub fn get_settings(&self) -> &[SettingChange] {
+        &self.settings
+    }
+
+    /// Changed event schemas.
+    pub fn get_schema_changes(&self) -> &[SchemaChange] {
+        &self.schema_changes
+    }
+
+    /// Changed extractors.
+    pub fn get_extractor_changes(&self) -> &[ExtractorChange] {
+        &self.extractor_changes
+    }
+
+    /// Kinds of events that were added in the newer version.
+    pub fn get_added_event_types(&self) -> &[String] {
+        &self.added_event_types
+    }
+
+    /// Kinds of events that were removed in the newer version.
+    pub fn get_removed_event_types(&self) -> &[String] {
+        &self.removed_event_types
+    }
+
+    /// Return `true` if the versions are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.settings.is_empty()
+            && self.schema_changes.is_empty()
+            && self.extractor_changes.is_empty()
+            && self.added_event_types.is_empty()
+            && self.removed_event_types.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mb::event_descriptor::DescriptorVersion;
+
+    fn schema(schema_data: &str) -> Option<EventSchema> {
+        Some(EventSchema::new(
+            "order".to_owned(),
+            "https://json-schema.org/draft/2020-12/schema".to_owned(),
+            schema_data.to_owned(),
+        ))
+    }
+
+    #[test]
+    fn diff_of_schema_extractors_and_settings() {
+        let from = EventDescriptor::new(
+            DescriptorVersion::new(1, 0, 0).as_encoded(),
+            None,
+            schema(r#"{"type":"object","properties":{"id":{"type":"string"},"a/b":{}}}"#),
+            Some(vec![
+                Extractor::from_string_root_property("id"),
+                Extractor::from_string_root_property("customer"),
+            ]),
+        );
+        let to = EventDescriptor::new(
+            DescriptorVersion::new(1, 1, 0).as_encoded(),
+            Some(DescriptorVersion::new(1, 0, 0).as_encoded()),
+            schema(r#"{"type":"object","properties":{"id":{"type":"integer"}}}"#),
+            Some(vec![Extractor::from_string_root_property("id")]),
+        )
+        .with_canonicalization("JCS");
+        let diff = DescriptorDiff::between("orders", &from, &to);
+        assert_eq!(diff.get_from_version(), from.get_version());
+        assert_eq!(diff.get_to_version(), to.get_version());
+        let settings = diff
+            .get_settings()
+            .iter()
+            .map(SettingChange::get_name)
+            .collect::<Vec<_>>();
+        assert_eq!(settings, vec!["version_min", "canonicalization"]);
+        let paths = diff
+            .get_schema_changes()
+            .iter()
+            .map(SchemaChange::get_path)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                "/schema_data/properties/a~1b",
+                "/schema_data/properties/id/type"
+            ]
+        );
+        assert_eq!(diff.get_extractor_changes().len(), 1);
+        assert_eq!(
+            diff.get_extractor_changes()[0].get_result_name(),
+            "customer"
+        );
+        assert!(diff.get_extractor_changes()[0].get_to().is_none());
+        assert!(DescriptorDiff::between("orders", &to, &to).is_empty());
+    }
+}
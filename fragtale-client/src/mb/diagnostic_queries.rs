@@ -0,0 +1,145 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Whitelisted read-only diagnostic queries for emergencies.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Description of an available diagnostic query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DiagnosticQueryTemplate {
+    /// Name of the diagnostic query.
+    name: String,
+    /// Human readable description of the diagnostic query.
+    description: String,
+    /// `true` if the `consumer_id` parameter is required.
+    requires_consumer_id: bool,
+    /// `true` if the `from` and `to` parameters are required.
+    requires_range: bool,
+}
+
+impl DiagnosticQueryTemplate {
+    /// Return a new instance.
+    pub fn new(
+        name: &str,
+        description: &str,
+        requires_consumer_id: bool,
+        requires_range: bool,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            description: description.to_owned(),
+            requires_consumer_id,
+            requires_range,
+        }
+    }
+
+    /// Name of the diagnostic query.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Human readable description of the diagnostic query.
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+
+    /// `true` if the `consumer_id` parameter is required.
+    pub fn requires_consumer_id(&self) -> bool {
+        self.requires_consumer_id
+    }
+
+    /// `true` if the `from` and `to` parameters are required.
+    pub fn requires_range(&self) -> bool {
+        self.requires_range
+    }
+}
+
+/// All available diagnostic queries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DiagnosticQueryTemplates {
+    /// Available diagnostic queries.
+    queries: Vec<DiagnosticQueryTemplate>,
+}
+
+impl DiagnosticQueryTemplates {
+    /// Return a new instance.
+    pub fn new(queries: Vec<DiagnosticQueryTemplate>) -> Self {
+        Self { queries }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Available diagnostic queries.
+    pub fn get_queries(&self) -> &[DiagnosticQueryTemplate] {
+        &self.queries
+    }
+}
+
+/// Result of a diagnostic query.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DiagnosticQueryResult {
+    /// Topic identifier.
+    topic_id: String,
+    /// Name of the diagnostic query.
+    query: String,
+    /// Result rows as JSON objects.
+    rows: Vec<serde_json::Value>,
+    /// `true` if there might be more rows than returned.
+    more: bool,
+}
+
+impl DiagnosticQueryResult {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, query: &str, rows: Vec<serde_json::Value>, more: bool) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            query: query.to_owned(),
+            rows,
+            more,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Name of the diagnostic query.
+    pub fn get_query(&self) -> &str {
+        &self.query
+    }
+
+    /// Result rows as JSON objects.
+    pub fn get_rows(&self) -> &[serde_json::Value] {
+        &self.rows
+    }
+
+    /// `true` if there might be more rows than returned.
+    pub fn has_more(&self) -> bool {
+        self.more
+    }
+}
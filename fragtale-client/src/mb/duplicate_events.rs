@@ -0,0 +1,137 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Recently detected duplicate events of a topic.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Number of duplicate events published by a single publisher.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DuplicatePublisher {
+    /// Identity of the publisher.
+    publisher: String,
+    /// Number of duplicates published within the detection window.
+    duplicates: u64,
+}
+
+impl DuplicatePublisher {
+    /// Return a new instance.
+    pub fn new(publisher: &str, duplicates: u64) -> Self {
+        Self {
+            publisher: publisher.to_owned(),
+            duplicates,
+        }
+    }
+
+    /// Identity of the publisher.
+    pub fn get_publisher(&self) -> &str {
+        &self.publisher
+    }
+
+    /// Number of duplicates published within the detection window.
+    pub fn get_duplicates(&self) -> u64 {
+        self.duplicates
+    }
+}
+
+/// Duplicate detection of a topic on a broker instance.
+///
+/// An event is a duplicate when its event identifier was already published
+/// to the topic within the detection window.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DuplicateEvents {
+    /// Topic identifier.
+    topic_id: String,
+    /// Identifier of the broker instance that detected the duplicates.
+    instance_id: u16,
+    /// Duration of the detection window in microseconds.
+    window_micros: u64,
+    /// Number of event identifiers currently tracked for detection.
+    tracked_event_ids: u64,
+    /// Number of duplicates detected since the instance started.
+    total_duplicates: u64,
+    /// Number of duplicates detected within the detection window.
+    window_duplicates: u64,
+    /// Publishers of the most duplicates within the detection window, with
+    /// the most duplicates first.
+    top_publishers: Vec<DuplicatePublisher>,
+}
+
+impl DuplicateEvents {
+    /// Return a new instance.
+    pub fn new(
+        topic_id: &str,
+        instance_id: u16,
+        window_micros: u64,
+        tracked_event_ids: u64,
+        total_duplicates: u64,
+        window_duplicates: u64,
+        top_publishers: Vec<DuplicatePublisher>,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            instance_id,
+            window_micros,
+            tracked_event_ids,
+            total_duplicates,
+            window_duplicates,
+            top_publishers,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Identifier of the broker instance that detected the duplicates.
+    pub fn get_instance_id(&self) -> u16 {
+        self.instance_id
+    }
+
+    /// Duration of the detection window in microseconds.
+    pub fn get_window_micros(&self) -> u64 {
+        self.window_micros
+    }
+
+    /// Number of event identifiers currently tracked for detection.
+    pub fn get_tracked_event_ids(&self) -> u64 {
+        self.tracked_event_ids
+    }
+
+    /// Number of duplicates detected since the instance started.
+    pub fn get_total_duplicates(&self) -> u64 {
+        self.total_duplicates
+    }
+
+    /// Number of duplicates detected within the detection window.
+    pub fn get_window_duplicates(&self) -> u64 {
+        self.window_duplicates
+    }
+
+    /// Publishers of the most duplicates within the detection window, with
+    /// the most duplicates first.
+    pub fn get_top_publishers(&self) -> &[DuplicatePublisher] {
+        &self.top_publishers
+    }
+}
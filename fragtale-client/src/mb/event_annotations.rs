@@ -0,0 +1,137 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Append-only annotations of events.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A small annotation to attach to an existing event.
+///
+/// The event itself is never altered by annotations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EventAnnotation {
+    /// Kind of annotation. One of `label`, `note` or `status`.
+    kind: String,
+    /// Value of the annotation. E.g. a label key, a free text note or a
+    /// processing status.
+    value: String,
+}
+
+impl EventAnnotation {
+    /// Annotation kind for a label key.
+    pub const KIND_LABEL: &str = "label";
+    /// Annotation kind for a free text note.
+    pub const KIND_NOTE: &str = "note";
+    /// Annotation kind for a processing status.
+    pub const KIND_STATUS: &str = "status";
+
+    /// Return a new instance.
+    pub fn new(kind: &str, value: &str) -> Self {
+        Self {
+            kind: kind.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Kind of annotation.
+    pub fn get_kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Value of the annotation.
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+}
+
+/// An annotation of an event with information about when and by whom it was
+/// added.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EventAnnotationEntry {
+    /// Time of the annotation in epoch microseconds.
+    annotated_ts_micros: u64,
+    /// Identity of the author.
+    author: String,
+    /// The annotation.
+    annotation: EventAnnotation,
+}
+
+impl EventAnnotationEntry {
+    /// Return a new instance.
+    pub fn new(annotated_ts_micros: u64, author: &str, annotation: EventAnnotation) -> Self {
+        Self {
+            annotated_ts_micros,
+            author: author.to_owned(),
+            annotation,
+        }
+    }
+
+    /// Time of the annotation in epoch microseconds.
+    pub fn get_annotated_ts_micros(&self) -> u64 {
+        self.annotated_ts_micros
+    }
+
+    /// Identity of the author.
+    pub fn get_author(&self) -> &str {
+        &self.author
+    }
+
+    /// The annotation.
+    pub fn get_annotation(&self) -> &EventAnnotation {
+        &self.annotation
+    }
+}
+
+/// All annotations of an event ordered by time of annotation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EventAnnotations {
+    /// Event identifier.
+    event_id: String,
+    /// Annotations of the event.
+    annotations: Vec<EventAnnotationEntry>,
+}
+
+impl EventAnnotations {
+    /// Return a new instance.
+    pub fn new(event_id: &str, annotations: Vec<EventAnnotationEntry>) -> Self {
+        Self {
+            event_id: event_id.to_owned(),
+            annotations,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Event identifier.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Annotations of the event.
+    pub fn get_annotations(&self) -> &[EventAnnotationEntry] {
+        &self.annotations
+    }
+}
@@ -17,13 +17,21 @@
 
 //! Event schema, schema versioning and indexed column extraction.
 
+mod default_value;
 mod descriptor_version;
 mod event_schema;
+mod event_type_descriptor;
 mod extractor;
+mod masking_rule;
 
+pub use self::default_value::DefaultValue;
 pub use self::descriptor_version::DescriptorVersion;
 pub use self::event_schema::EventSchema;
+pub use self::event_type_descriptor::EventTypeDescriptor;
 pub use self::extractor::Extractor;
+pub use self::masking_rule::MaskingAction;
+pub use self::masking_rule::MaskingRule;
+use crate::mb::delivery_receipts::DeliveryReceiptTarget;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -52,6 +60,68 @@ pub struct EventDescriptor {
     /// See [Self::get_extractors].
     #[schema(inline)]
     extractors: Option<Vec<Extractor>>,
+    /// Keep published documents that fail validation for later replay.
+    ///
+    /// See [Self::is_reject_store_enabled].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reject_store: Option<bool>,
+    /// Route events that consumers fail to process to a dead-letter topic.
+    ///
+    /// See [Self::is_dead_letter_enabled].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dead_letter: Option<bool>,
+    /// Message digest algorithm used to derive event identifiers.
+    ///
+    /// See [Self::get_event_id_algorithm].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_id_algorithm: Option<String>,
+    /// Handling of different documents that map to the same event identifier.
+    ///
+    /// See [Self::get_event_id_collision_policy].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_id_collision_policy: Option<String>,
+    /// Canonicalization of event documents before they are processed.
+    ///
+    /// See [Self::get_canonicalization].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    canonicalization: Option<String>,
+    /// JSON Pointer to the event type discriminator of multi-type topics.
+    ///
+    /// See [Self::get_event_type_field].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_type_field: Option<String>,
+    /// Validation and extraction for each kind of event in a multi-type
+    /// topic.
+    ///
+    /// See [Self::get_event_types].
+    #[schema(inline)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_types: Option<Vec<EventTypeDescriptor>>,
+    /// JSON Pointer to the routing key used for consumer stickiness.
+    ///
+    /// See [Self::get_routing_key_field].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    routing_key_field: Option<String>,
+    /// Emit receipts when deliveries of the topic's events are confirmed.
+    ///
+    /// See [Self::get_delivery_receipts].
+    #[schema(inline)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    delivery_receipts: Option<DeliveryReceiptTarget>,
+    /// Values of fields added in this version for events published with an
+    /// older version.
+    ///
+    /// See [Self::get_default_values].
+    #[schema(inline)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_values: Option<Vec<DefaultValue>>,
+    /// Masking of fields in documents delivered to consumers that are not
+    /// allowed to see unmasked events.
+    ///
+    /// See [Self::get_masking_rules].
+    #[schema(inline)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    masking_rules: Option<Vec<MaskingRule>>,
 }
 
 impl EventDescriptor {
@@ -67,9 +137,91 @@ impl EventDescriptor {
             version_min,
             event_schema,
             extractors,
+            reject_store: None,
+            dead_letter: None,
+            event_id_algorithm: None,
+            event_id_collision_policy: None,
+            canonicalization: None,
+            event_type_field: None,
+            event_types: None,
+            routing_key_field: None,
+            delivery_receipts: None,
+            default_values: None,
+            masking_rules: None,
         }
     }
 
+    /// Return this instance with the reject store enabled or disabled.
+    pub fn with_reject_store(mut self, enabled: bool) -> Self {
+        self.reject_store = Some(enabled);
+        self
+    }
+
+    /// Return this instance with the dead-letter topic enabled or disabled.
+    pub fn with_dead_letter(mut self, enabled: bool) -> Self {
+        self.dead_letter = Some(enabled);
+        self
+    }
+
+    /// Return this instance with the message digest algorithm used to derive
+    /// event identifiers.
+    pub fn with_event_id_algorithm(mut self, event_id_algorithm: &str) -> Self {
+        self.event_id_algorithm = Some(event_id_algorithm.to_owned());
+        self
+    }
+
+    /// Return this instance with the event identifier collision policy.
+    pub fn with_event_id_collision_policy(mut self, event_id_collision_policy: &str) -> Self {
+        self.event_id_collision_policy = Some(event_id_collision_policy.to_owned());
+        self
+    }
+
+    /// Return this instance with canonicalization of event documents.
+    pub fn with_canonicalization(mut self, canonicalization: &str) -> Self {
+        self.canonicalization = Some(canonicalization.to_owned());
+        self
+    }
+
+    /// Return this instance as a multi-type topic descriptor where the kind
+    /// of event is determined by the value at the JSON Pointer
+    /// `event_type_field`.
+    pub fn with_event_types(
+        mut self,
+        event_type_field: &str,
+        event_types: Vec<EventTypeDescriptor>,
+    ) -> Self {
+        self.event_type_field = Some(event_type_field.to_owned());
+        self.event_types = Some(event_types);
+        self
+    }
+
+    /// Return this instance with the routing key at the JSON Pointer
+    /// `routing_key_field`.
+    pub fn with_routing_key_field(mut self, routing_key_field: &str) -> Self {
+        self.routing_key_field = Some(routing_key_field.to_owned());
+        self
+    }
+
+    /// Return this instance with delivery receipts emitted to the `target`.
+    pub fn with_delivery_receipts(mut self, target: DeliveryReceiptTarget) -> Self {
+        self.delivery_receipts = Some(target);
+        self
+    }
+
+    /// Return this instance with default values of fields for events
+    /// published with an older version.
+    pub fn with_default_values(mut self, default_values: Vec<DefaultValue>) -> Self {
+        self.default_values = Some(default_values);
+        self
+    }
+
+    /// Return this instance with masking of fields in documents delivered to
+    /// consumers that are not allowed to see unmasked events.
+    pub fn with_masking_rules(mut self, masking_rules: Vec<MaskingRule>) -> Self {
+        self.masking_rules = Some(masking_rules);
+        self
+    }
+
     /// Return as a JSON serialized String.
     pub fn as_string(&self) -> String {
         serde_json::to_string(self).unwrap()
@@ -112,4 +264,141 @@ impl EventDescriptor {
     pub fn get_extractors(&self) -> &Option<Vec<Extractor>> {
         &self.extractors
     }
+
+    /// Return `true` if published documents that fail validation should be
+    /// kept in the topic's reject store instead of being discarded.
+    ///
+    /// Rejected documents can be listed and replayed by an administrator once
+    /// the cause of the rejection has been fixed.
+    pub fn is_reject_store_enabled(&self) -> bool {
+        self.reject_store.unwrap_or(false)
+    }
+
+    /// Return `true` if events that fail integrity validation or exhaust the
+    /// redelivery budget of a consumer should be published to the topic's
+    /// dead-letter topic `{topic_id}_dlq` instead of being dropped or pausing
+    /// delivery.
+    ///
+    /// Dead-lettered events can be listed and replayed by an administrator.
+    pub fn is_dead_letter_enabled(&self) -> bool {
+        self.dead_letter.unwrap_or(false)
+    }
+
+    /// Message digest algorithm used to derive event identifiers from event
+    /// documents.
+    ///
+    /// Example: "SHA3-256" or "SHA3-512" (default when absent)
+    ///
+    /// The algorithm is a property of the topic and cannot be changed once
+    /// the topic has an event descriptor or events. Events published before
+    /// the first event descriptor use the default.
+    pub fn get_event_id_algorithm(&self) -> &Option<String> {
+        &self.event_id_algorithm
+    }
+
+    /// Handling of different event documents that map to the same event
+    /// identifier.
+    ///
+    /// One of "ignore" (default when absent), "alert" to raise an integrity
+    /// alert or "reject" to also refuse the publishing of the event.
+    pub fn get_event_id_collision_policy(&self) -> &Option<String> {
+        &self.event_id_collision_policy
+    }
+
+    /// Canonicalization of published event documents.
+    ///
+    /// One of "none" (default when absent) or "JCS" to rewrite documents
+    /// into the JSON Canonicalization Scheme (RFC 8785) before the event
+    /// identifier is derived and integrity protection is applied. This makes
+    /// documents that only differ in key order or whitespace logical
+    /// duplicates with the same event identifier.
+    pub fn get_canonicalization(&self) -> &Option<String> {
+        &self.canonicalization
+    }
+
+    /// JSON Pointer to the event type discriminator of multi-type topics.
+    ///
+    /// Example: "/type"
+    ///
+    /// When present, each published document must have a text value at this
+    /// location that matches one of [Self::get_event_types].
+    pub fn get_event_type_field(&self) -> &Option<String> {
+        &self.event_type_field
+    }
+
+    /// Validation and extraction for each kind of event in a multi-type
+    /// topic.
+    pub fn get_event_types(&self) -> &Option<Vec<EventTypeDescriptor>> {
+        &self.event_types
+    }
+
+    /// JSON Pointer to the routing key of each event document.
+    ///
+    /// Example: "/region"
+    ///
+    /// Consumers that subscribe with a stickiness key are preferred for
+    /// events with a matching text value at this location. Events without a
+    /// routing key are delivered to any consumer.
+    pub fn get_routing_key_field(&self) -> &Option<String> {
+        &self.routing_key_field
+    }
+
+    /// Where receipts are emitted when deliveries of the topic's events are
+    /// confirmed (if anywhere).
+    ///
+    /// Each receipt holds the event identifier, the consumer that confirmed
+    /// the delivery and the delivery latency. Receipts are emitted on a best
+    /// effort basis and a lost receipt never affects the delivery.
+    pub fn get_delivery_receipts(&self) -> &Option<DeliveryReceiptTarget> {
+        &self.delivery_receipts
+    }
+
+    /// Values of fields for events published with an older version (if any).
+    ///
+    /// When an event published with an older version is delivered to a
+    /// consumer of this version, each absent field is added with its default
+    /// value. Fields present in the document are never replaced. This allows
+    /// a new version to require fields without republishing older events.
+    ///
+    /// Only the default values of the consumer's version are applied, so
+    /// newer versions should keep the default values of older versions.
+    pub fn get_default_values(&self) -> &Option<Vec<DefaultValue>> {
+        &self.default_values
+    }
+
+    /// Masking of fields in documents delivered to consumers (if any).
+    ///
+    /// Consumers that are not allowed to see unmasked events of the topic get
+    /// documents where each rule has been applied. The stored events are never
+    /// modified, so integrity validation is unaffected.
+    pub fn get_masking_rules(&self) -> &Option<Vec<MaskingRule>> {
+        &self.masking_rules
+    }
+
+    /// Return the descriptor for a kind of event in a multi-type topic.
+    pub fn get_event_type_descriptor(&self, event_type: &str) -> Option<&EventTypeDescriptor> {
+        self.event_types.as_ref().and_then(|event_types| {
+            event_types
+                .iter()
+                .find(|event_type_descriptor| event_type_descriptor.get_event_type() == event_type)
+        })
+    }
+
+    /// Return all extractors of the topic including the extractors of each
+    /// kind of event.
+    pub fn get_all_extractors(&self) -> Vec<&Extractor> {
+        self.extractors
+            .iter()
+            .flatten()
+            .chain(
+                self.event_types
+                    .iter()
+                    .flatten()
+                    .filter_map(|event_type_descriptor| {
+                        event_type_descriptor.get_extractors().as_ref()
+                    })
+                    .flatten(),
+            )
+            .collect()
+    }
 }
@@ -0,0 +1,54 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Default value of a field added in a newer event descriptor version.
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Default value of a field added in a newer event descriptor version.
+///
+/// Events published with an older descriptor version are augmented with the
+/// default value when delivered to consumers of a newer version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DefaultValue {
+    /// JSON Pointer to the location of the field. E.g. "/currency".
+    path: String,
+    /// The JSON value used when the field is absent.
+    value: Value,
+}
+
+impl DefaultValue {
+    /// Return a new instance.
+    pub fn new(path: &str, value: Value) -> Self {
+        Self {
+            path: path.to_owned(),
+            value,
+        }
+    }
+
+    /// JSON Pointer to the location of the field.
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /// The JSON value used when the field is absent.
+    pub fn get_value(&self) -> &Value {
+        &self.value
+    }
+}
@@ -78,4 +78,14 @@ impl DescriptorVersion {
     pub fn get_patch(&self) -> u16 {
         u16::try_from(self.0 & 0xffff).unwrap()
     }
+
+    /// Return the version in the `major.minor.patch` form.
+    pub fn as_semver(&self) -> String {
+        format!(
+            "{}.{}.{}",
+            self.get_major(),
+            self.get_minor(),
+            self.get_patch()
+        )
+    }
 }
@@ -23,7 +23,8 @@ use serde::Serialize;
 /// The even schema for each topic is an optional feature to ensure that
 /// documents are well formed.
 ///
-/// Schemas must be self-contained.
+/// Schemas must be self-contained, except for `$ref`erences to shared schema
+/// components registered with the server by their schema identifier.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct EventSchema {
     schema_id: String,
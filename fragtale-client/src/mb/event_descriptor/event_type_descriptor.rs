@@ -0,0 +1,75 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Validation and extraction for one kind of event in a multi-type topic.
+
+use super::EventSchema;
+use super::Extractor;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Validation and extraction for one kind of event in a multi-type topic.
+///
+/// The kind of an event is determined by the value of the topic's event type
+/// field. See [super::EventDescriptor::get_event_type_field].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EventTypeDescriptor {
+    /// Value of the event type field for this kind of event.
+    event_type: String,
+    /// Optional event schema used to validate event documents of this type.
+    #[schema(inline)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_schema: Option<EventSchema>,
+    /// Optional extractors for indexing document values of this type.
+    #[schema(inline)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    extractors: Option<Vec<Extractor>>,
+}
+
+impl EventTypeDescriptor {
+    /// Return a new instance.
+    pub fn new(
+        event_type: &str,
+        event_schema: Option<EventSchema>,
+        extractors: Option<Vec<Extractor>>,
+    ) -> Self {
+        Self {
+            event_type: event_type.to_owned(),
+            event_schema,
+            extractors,
+        }
+    }
+
+    /// Value of the event type field for this kind of event.
+    pub fn get_event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    /// Optional event schema for this kind of event.
+    ///
+    /// This is applied in addition to the topic's event schema.
+    pub fn get_event_schema(&self) -> &Option<EventSchema> {
+        &self.event_schema
+    }
+
+    /// Extractors of document values for indexing this kind of event.
+    ///
+    /// These are applied in addition to the topic's extractors.
+    pub fn get_extractors(&self) -> &Option<Vec<Extractor>> {
+        &self.extractors
+    }
+}
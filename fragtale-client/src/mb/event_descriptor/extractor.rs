@@ -28,12 +28,23 @@ pub struct Extractor {
     /// One of a subset of data types defined by Cassandra.
     ///
     /// Example: "text" or "bigint"
+    ///
+    /// Arrays in the document are extracted with a collection type like
+    /// "set<text>" or "list<bigint>", which allows events to be found by any
+    /// of the members.
     result_type: String,
     /// Type of extraction: "jsonpointer"
     extraction_type: String,
     /// When extraction_type is "jsonpointer", this points to the value to extract.
     /// E.g. "/property-of-document-root".
     extraction_path: String,
+    /// Filter expression over the values extracted by unconditional
+    /// extractors (and earlier conditional extractors) that must match for
+    /// this value to be extracted.
+    ///
+    /// Example: `kind == "order" && has(amount)`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    condition: Option<String>,
 }
 
 impl Extractor {
@@ -52,9 +63,17 @@ impl Extractor {
             result_type,
             extraction_type,
             extraction_path,
+            condition: None,
         }
     }
 
+    /// Only extract the value when the filter expression matches the values
+    /// extracted before.
+    pub fn with_condition(mut self, condition: &str) -> Self {
+        self.condition = Some(condition.to_owned());
+        self
+    }
+
     /// Return a new instance for extracting text values from the
     /// `root_property` property in the root of the JSON document.
     pub fn from_string_root_property<S: AsRef<str>>(root_property: S) -> Self {
@@ -63,6 +82,7 @@ impl Extractor {
             result_type: "text".to_string(),
             extraction_type: "jsonpointer".to_string(),
             extraction_path: "/".to_string() + root_property.as_ref(),
+            condition: None,
         }
     }
 
@@ -86,4 +106,10 @@ impl Extractor {
     pub fn get_extraction_path(&self) -> &str {
         &self.extraction_path
     }
+
+    /// Filter expression that must match the values extracted before for this
+    /// value to be extracted (if any).
+    pub fn get_condition(&self) -> Option<&str> {
+        self.condition.as_deref()
+    }
 }
@@ -0,0 +1,66 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Masking of a field in event documents delivered to consumers.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// How the value of a masked field is replaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MaskingAction {
+    /// The field is removed from the document.
+    Redact,
+    /// The value is replaced by the hex encoded SHA3-256 digest of its JSON
+    /// serialization.
+    ///
+    /// Equal values yield equal digests, so masked values can still be
+    /// correlated. The digest is not keyed, so values from a small set of
+    /// possible values (e.g. a birth date) can be recovered by guessing.
+    Hash,
+}
+
+/// Masking of a field in event documents delivered to consumers that are not
+/// allowed to see unmasked events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MaskingRule {
+    /// JSON Pointer to the location of the field. E.g. "/customer/email".
+    path: String,
+    /// How the value of the field is replaced.
+    action: MaskingAction,
+}
+
+impl MaskingRule {
+    /// Return a new instance.
+    pub fn new(path: &str, action: MaskingAction) -> Self {
+        Self {
+            path: path.to_owned(),
+            action,
+        }
+    }
+
+    /// JSON Pointer to the location of the field.
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /// How the value of the field is replaced.
+    pub fn get_action(&self) -> MaskingAction {
+        self.action
+    }
+}
@@ -0,0 +1,87 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Topics that contain an event with a specific event identifier.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Location of an event in a topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EventLocation {
+    /// Topic identifier.
+    topic_id: String,
+    /// Encoded unique time of the latest event with the identifier in the
+    /// topic.
+    unique_time: u64,
+}
+
+impl EventLocation {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, unique_time: u64) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            unique_time,
+        }
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Encoded unique time of the latest event with the identifier in the
+    /// topic.
+    pub fn get_unique_time(&self) -> u64 {
+        self.unique_time
+    }
+}
+
+/// All readable topics that contain an event with a specific event
+/// identifier ordered by topic identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EventLocations {
+    /// Event identifier.
+    event_id: String,
+    /// Locations of the event.
+    locations: Vec<EventLocation>,
+}
+
+impl EventLocations {
+    /// Return a new instance.
+    pub fn new(event_id: &str, locations: Vec<EventLocation>) -> Self {
+        Self {
+            event_id: event_id.to_owned(),
+            locations,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Event identifier.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Locations of the event.
+    pub fn get_locations(&self) -> &[EventLocation] {
+        &self.locations
+    }
+}
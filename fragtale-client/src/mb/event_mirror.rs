@@ -0,0 +1,90 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Mirroring of published events to local files for debugging.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Mirroring status of a topic on the instance that served the request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicMirror {
+    /// Topic identifier.
+    topic_id: String,
+    /// `true` if published events are mirrored to local files.
+    enabled: bool,
+    /// Identifier of the instance that served the request.
+    instance_id: u16,
+    /// Local directory of the mirror files of the topic.
+    directory: String,
+}
+
+impl TopicMirror {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, enabled: bool, instance_id: u16, directory: &str) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            enabled,
+            instance_id,
+            directory: directory.to_owned(),
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// `true` if published events are mirrored to local files.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Identifier of the instance that served the request.
+    pub fn get_instance_id(&self) -> u16 {
+        self.instance_id
+    }
+
+    /// Local directory of the mirror files of the topic.
+    pub fn get_directory(&self) -> &str {
+        &self.directory
+    }
+}
+
+/// Request to enable or disable mirroring of a topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicMirrorUpdate {
+    /// `true` to mirror published events to local files.
+    enabled: bool,
+}
+
+impl TopicMirrorUpdate {
+    /// Return a new instance.
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// `true` to mirror published events to local files.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+}
@@ -0,0 +1,163 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Administrative purge of events by index key.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// How matching events are removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EventPurgeMode {
+    /// The events are permanently removed together with their index entries,
+    /// correlation lookups and annotations.
+    Purge,
+    /// The documents are replaced by the masked version from the masking
+    /// rules of the topic's event descriptor, while the events keep their
+    /// place in the topic.
+    Redact,
+}
+
+/// Outcome of a purge of all events matching an indexed key of a topic.
+///
+/// A dry-run only reports the number of matching events, which is then
+/// provided as the expected count when the purge is executed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EventPurge {
+    /// Topic identifier.
+    topic_id: String,
+    /// Name of the indexed column that was matched.
+    index_name: String,
+    /// How matching events were removed.
+    mode: EventPurgeMode,
+    /// `true` if no events were modified.
+    dry_run: bool,
+    /// Number of events matching the index key.
+    matched_count: u64,
+    /// Number of events that were purged or redacted.
+    purged_count: u64,
+}
+
+impl EventPurge {
+    /// Return a new instance.
+    pub fn new(
+        topic_id: &str,
+        index_name: &str,
+        mode: EventPurgeMode,
+        dry_run: bool,
+        matched_count: u64,
+        purged_count: u64,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            index_name: index_name.to_owned(),
+            mode,
+            dry_run,
+            matched_count,
+            purged_count,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Name of the indexed column that was matched.
+    pub fn get_index_name(&self) -> &str {
+        &self.index_name
+    }
+
+    /// How matching events were removed.
+    pub fn get_mode(&self) -> EventPurgeMode {
+        self.mode
+    }
+
+    /// `true` if no events were modified.
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Number of events matching the index key.
+    pub fn get_matched_count(&self) -> u64 {
+        self.matched_count
+    }
+
+    /// Number of events that were purged or redacted.
+    pub fn get_purged_count(&self) -> u64 {
+        self.purged_count
+    }
+}
+
+/// Request to purge all events matching an indexed key of a topic.
+///
+/// Without an expected count, the request is a dry-run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct EventPurgeRequest {
+    /// Name of the indexed column to match.
+    index_name: String,
+    /// Key of the indexed column to match.
+    index_key: String,
+    /// How matching events are removed.
+    mode: EventPurgeMode,
+    /// Number of matching events reported by a preceding dry-run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expected_count: Option<u64>,
+}
+
+impl EventPurgeRequest {
+    /// Return a new instance.
+    pub fn new(
+        index_name: &str,
+        index_key: &str,
+        mode: EventPurgeMode,
+        expected_count: Option<u64>,
+    ) -> Self {
+        Self {
+            index_name: index_name.to_owned(),
+            index_key: index_key.to_owned(),
+            mode,
+            expected_count,
+        }
+    }
+
+    /// Name of the indexed column to match.
+    pub fn get_index_name(&self) -> &str {
+        &self.index_name
+    }
+
+    /// Key of the indexed column to match.
+    pub fn get_index_key(&self) -> &str {
+        &self.index_key
+    }
+
+    /// How matching events are removed.
+    pub fn get_mode(&self) -> EventPurgeMode {
+        self.mode
+    }
+
+    /// Number of matching events reported by a preceding dry-run.
+    pub fn get_expected_count(&self) -> Option<u64> {
+        self.expected_count
+    }
+}
@@ -0,0 +1,157 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Members of consumer groups.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Connection and delivery statistics of a member of a consumer group.
+///
+/// A consumer group is all clients that share the same consumer identifier and
+/// each member is a client instance that identifies itself when connecting.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct GroupMember {
+    /// Consumer identifier of the group.
+    consumer_id: String,
+    /// Client instance identifier of the member.
+    member_id: String,
+    /// Number of open subscriber connections to this broker instance.
+    connections: u64,
+    /// Last time the member opened a subscriber connection in epoch
+    /// microseconds.
+    last_connected_ts_micros: u64,
+    /// Last time the member confirmed the delivery of an event in epoch
+    /// microseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_confirmed_ts_micros: Option<u64>,
+    /// Number of events the member confirmed within the window.
+    confirmed_events: u64,
+}
+
+impl GroupMember {
+    /// Return a new instance.
+    pub fn new(
+        consumer_id: &str,
+        member_id: &str,
+        connections: u64,
+        last_connected_ts_micros: u64,
+        last_confirmed_ts_micros: Option<u64>,
+        confirmed_events: u64,
+    ) -> Self {
+        Self {
+            consumer_id: consumer_id.to_owned(),
+            member_id: member_id.to_owned(),
+            connections,
+            last_connected_ts_micros,
+            last_confirmed_ts_micros,
+            confirmed_events,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Consumer identifier of the group.
+    pub fn get_consumer_id(&self) -> &str {
+        &self.consumer_id
+    }
+
+    /// Client instance identifier of the member.
+    pub fn get_member_id(&self) -> &str {
+        &self.member_id
+    }
+
+    /// Number of open subscriber connections to this broker instance.
+    pub fn get_connections(&self) -> u64 {
+        self.connections
+    }
+
+    /// Last time the member opened a subscriber connection in epoch
+    /// microseconds.
+    pub fn get_last_connected_ts_micros(&self) -> u64 {
+        self.last_connected_ts_micros
+    }
+
+    /// Last time the member confirmed the delivery of an event in epoch
+    /// microseconds.
+    pub fn get_last_confirmed_ts_micros(&self) -> Option<u64> {
+        self.last_confirmed_ts_micros
+    }
+
+    /// Number of events the member confirmed within the window.
+    pub fn get_confirmed_events(&self) -> u64 {
+        self.confirmed_events
+    }
+}
+
+/// Members of the consumer groups of a topic served by a broker instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicGroupMembers {
+    /// Topic identifier.
+    topic_id: String,
+    /// Identifier of the broker instance the members are connected to.
+    instance_id: u16,
+    /// Window of confirmation statistics in minutes.
+    window_minutes: u64,
+    /// Members of each consumer group.
+    members: Vec<GroupMember>,
+}
+
+impl TopicGroupMembers {
+    /// Return a new instance.
+    pub fn new(
+        topic_id: &str,
+        instance_id: u16,
+        window_minutes: u64,
+        members: Vec<GroupMember>,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            instance_id,
+            window_minutes,
+            members,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Identifier of the broker instance the members are connected to.
+    pub fn get_instance_id(&self) -> u16 {
+        self.instance_id
+    }
+
+    /// Window of confirmation statistics in minutes.
+    pub fn get_window_minutes(&self) -> u64 {
+        self.window_minutes
+    }
+
+    /// Members of each consumer group.
+    pub fn get_members(&self) -> &[GroupMember] {
+        &self.members
+    }
+}
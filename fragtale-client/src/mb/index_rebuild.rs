@@ -0,0 +1,103 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Online rebuild of the indexed columns of existing events.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Progress of the rebuild of the indexed columns of a topic.
+///
+/// The extractors of the latest event descriptor are applied to all events
+/// published until shortly after the rebuild was requested. Lookups by index
+/// are complete for these events once the rebuild has completed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IndexRebuild {
+    /// Topic identifier.
+    topic_id: String,
+    /// Time the rebuild was requested in epoch microseconds.
+    requested_ts_micros: u64,
+    /// Events published until this time in epoch microseconds are covered
+    /// by the rebuild.
+    covered_until_ts_micros: u64,
+    /// All events published until this time in epoch microseconds have been
+    /// processed.
+    position_ts_micros: u64,
+    /// Number of events where indexed columns were populated.
+    rebuilt_count: u64,
+    /// Time the rebuild completed in epoch microseconds (if completed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    completed_ts_micros: Option<u64>,
+}
+
+impl IndexRebuild {
+    /// Return a new instance.
+    pub fn new(
+        topic_id: &str,
+        requested_ts_micros: u64,
+        covered_until_ts_micros: u64,
+        position_ts_micros: u64,
+        rebuilt_count: u64,
+        completed_ts_micros: Option<u64>,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            requested_ts_micros,
+            covered_until_ts_micros,
+            position_ts_micros,
+            rebuilt_count,
+            completed_ts_micros,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Time the rebuild was requested in epoch microseconds.
+    pub fn get_requested_ts_micros(&self) -> u64 {
+        self.requested_ts_micros
+    }
+
+    /// Events published until this time in epoch microseconds are covered by
+    /// the rebuild.
+    pub fn get_covered_until_ts_micros(&self) -> u64 {
+        self.covered_until_ts_micros
+    }
+
+    /// All events published until this time in epoch microseconds have been
+    /// processed.
+    pub fn get_position_ts_micros(&self) -> u64 {
+        self.position_ts_micros
+    }
+
+    /// Number of events where indexed columns were populated.
+    pub fn get_rebuilt_count(&self) -> u64 {
+        self.rebuilt_count
+    }
+
+    /// Time the rebuild completed in epoch microseconds (if completed).
+    pub fn get_completed_ts_micros(&self) -> Option<u64> {
+        self.completed_ts_micros
+    }
+}
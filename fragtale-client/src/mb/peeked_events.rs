@@ -0,0 +1,149 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Events next in line for delivery to a consumer.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An event next in line for delivery to a consumer that has not been
+/// reserved for delivery.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PeekedEvent {
+    /// UniqueTime of the event.
+    encoded_unique_time: u64,
+    /// Event identifier.
+    event_id: String,
+    /// Event Descriptor SemVer (major.minor.patch) the event was published
+    /// with, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    descriptor_version: Option<String>,
+    /// Identifier of the event schema the event was published with, when
+    /// known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema_id: Option<String>,
+    /// The priority the event was published with, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<u8>,
+    /// `true` when an earlier delivery of the event to the consumer failed.
+    redelivery: bool,
+    /// The event document as it would be delivered.
+    event_document: String,
+}
+
+impl PeekedEvent {
+    /// Return a new instance.
+    pub fn new(
+        encoded_unique_time: u64,
+        event_id: &str,
+        descriptor_version: Option<String>,
+        schema_id: Option<String>,
+        priority: Option<u8>,
+        redelivery: bool,
+        event_document: &str,
+    ) -> Self {
+        Self {
+            encoded_unique_time,
+            event_id: event_id.to_owned(),
+            descriptor_version,
+            schema_id,
+            priority,
+            redelivery,
+            event_document: event_document.to_owned(),
+        }
+    }
+
+    /// UniqueTime of the event.
+    pub fn get_encoded_unique_time(&self) -> u64 {
+        self.encoded_unique_time
+    }
+
+    /// Event identifier.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Event Descriptor SemVer (major.minor.patch) the event was published
+    /// with, when known.
+    pub fn get_descriptor_version(&self) -> Option<&str> {
+        self.descriptor_version.as_deref()
+    }
+
+    /// Identifier of the event schema the event was published with, when
+    /// known.
+    pub fn get_schema_id(&self) -> Option<&str> {
+        self.schema_id.as_deref()
+    }
+
+    /// The priority the event was published with, when known.
+    pub fn get_priority(&self) -> Option<u8> {
+        self.priority
+    }
+
+    /// `true` when an earlier delivery of the event to the consumer failed.
+    pub fn is_redelivery(&self) -> bool {
+        self.redelivery
+    }
+
+    /// The event document as it would be delivered.
+    pub fn get_event_document(&self) -> &str {
+        &self.event_document
+    }
+}
+
+/// Events next in line for delivery to a consumer of a topic, in delivery
+/// order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PeekedEvents {
+    /// Topic identifier.
+    topic_id: String,
+    /// Consumer identifier.
+    consumer_id: String,
+    /// The events next in line for delivery.
+    events: Vec<PeekedEvent>,
+}
+
+impl PeekedEvents {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, consumer_id: &str, events: Vec<PeekedEvent>) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            consumer_id: consumer_id.to_owned(),
+            events,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Consumer identifier.
+    pub fn get_consumer_id(&self) -> &str {
+        &self.consumer_id
+    }
+
+    /// The events next in line for delivery.
+    pub fn get_events(&self) -> &[PeekedEvent] {
+        &self.events
+    }
+}
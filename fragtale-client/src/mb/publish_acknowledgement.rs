@@ -0,0 +1,77 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Level of acknowledgement of published events.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// When the publishing of an event is acknowledged to the producer.
+///
+/// Each level returns later than the previous, trading latency for stronger
+/// guarantees of delivery visibility.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishAcknowledgement {
+    /// The event has been validated and is queued for persistence.
+    ///
+    /// An event might be lost if the instance fails before it is persisted.
+    Received,
+    /// The database has acknowledged the write of the event.
+    #[default]
+    Persisted,
+    /// The event is visible to consumers of the topic.
+    Indexed,
+}
+
+impl PublishAcknowledgement {
+    /// Return the acknowledgement level with the `name` if supported.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "received" => Some(Self::Received),
+            "persisted" => Some(Self::Persisted),
+            "indexed" => Some(Self::Indexed),
+            _ => None,
+        }
+    }
+
+    /// Return the name of the acknowledgement level.
+    pub fn as_name(&self) -> &'static str {
+        match self {
+            Self::Received => "received",
+            Self::Persisted => "persisted",
+            Self::Indexed => "indexed",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for ack in [
+            PublishAcknowledgement::Received,
+            PublishAcknowledgement::Persisted,
+            PublishAcknowledgement::Indexed,
+        ] {
+            assert_eq!(PublishAcknowledgement::from_name(ack.as_name()), Some(ack));
+        }
+        assert_eq!(PublishAcknowledgement::from_name("durable"), None);
+    }
+}
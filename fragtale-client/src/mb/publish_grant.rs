@@ -0,0 +1,319 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Pre-signed grants to publish to a topic without cluster credentials.
+
+use super::correlation_token::CorrelationToken;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_with::base64::Base64;
+use serde_with::serde_as;
+use tyst::Tyst;
+use tyst::encdec::DecodingError;
+
+/** Pre-signed grant to publish a limited number of events to a topic.
+
+A grant is minted by a client that is allowed to publish to the topic and can
+be handed to a third party (like an external partner) that doesn't hold any
+cluster credentials. The holder of the grant can publish up to `max_events`
+events of at most `max_event_bytes` each to the topic until the grant expires.
+
+## Security
+
+The grant is integrity protected with a MAC using a secret that is derived
+with HKDF from the master secret and the `topic_id`, so the grant can't be
+modified or relabeled to another topic. Each use is counted across all broker
+instances, so a grant can't be replayed more times than allowed.
+
+Anyone holding the grant can use it, so it should only be shared over
+confidential channels.
+*/
+#[serde_as]
+#[derive(Clone, Deserialize, Serialize)]
+pub struct PublishGrant {
+    uid: String,
+    topic: String,
+    expires: u64,
+    max_events: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_event_bytes: Option<u64>,
+    issuer: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    issuer_groups: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    issuer_tenant: Option<String>,
+    #[serde_as(as = "Base64")]
+    integrity: Vec<u8>,
+}
+
+impl PublishGrant {
+    /// Label used as HKDF `info` prefix when deriving per topic secrets.
+    const HKDF_INFO_PREFIX: &[u8] = b"fragtale publish grant ";
+
+    /// Return a new grant to publish up to `max_events` events of at most
+    /// `max_event_bytes` (if limited) each to `topic_id` until `expires`
+    /// (epoch microseconds) issued by the `issuer` identity.
+    ///
+    /// The `issuer_groups` and `issuer_tenant` are kept, so the issuer's
+    /// access can be authorized again when the grant is used.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        oid: &[u32],
+        master_secret: &[u8],
+        topic_id: &str,
+        expires: u64,
+        max_events: u32,
+        max_event_bytes: Option<u64>,
+        issuer: &str,
+        issuer_groups: &[String],
+        issuer_tenant: Option<&str>,
+    ) -> Self {
+        let uid = tyst::encdec::base64::encode_url(
+            &Tyst::instance().prng_get_random_bytes(None, 32),
+            false,
+        );
+        let mut ret = Self {
+            uid,
+            topic: topic_id.to_owned(),
+            expires,
+            max_events,
+            max_event_bytes,
+            issuer: issuer.to_owned(),
+            issuer_groups: issuer_groups.to_vec(),
+            issuer_tenant: issuer_tenant.map(str::to_owned),
+            integrity: Vec::new(),
+        };
+        ret.integrity = ret.protect(oid, master_secret);
+        ret
+    }
+
+    /// Return a new instance from the serialized form.
+    pub fn from_string<S: AsRef<str>>(value: S) -> Result<Self, DecodingError> {
+        let json_string = String::from_utf8(tyst::encdec::base64::decode_url(value.as_ref())?)
+            .map_err(|e| DecodingError::with_msg(&e.to_string()))?;
+        serde_json::from_str(json_string.as_str())
+            .map_err(|e| DecodingError::with_msg(&e.to_string()))
+    }
+
+    /// Return the grant in String serialized form.
+    pub fn as_string(&self) -> String {
+        let json_string = serde_json::to_string(self).unwrap();
+        tyst::encdec::base64::encode_url(json_string.as_bytes(), false)
+    }
+
+    /// Return the grant's unique identifier.
+    pub fn get_uid(&self) -> &str {
+        &self.uid
+    }
+
+    /// Return the topic the holder of the grant may publish to.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic
+    }
+
+    /// Return the expiration time of the grant in epoch microseconds.
+    pub fn get_expires_micros(&self) -> u64 {
+        self.expires
+    }
+
+    /// Return the max number of events that may be published with the grant.
+    pub fn get_max_events(&self) -> u32 {
+        self.max_events
+    }
+
+    /// Return the max size in bytes of each published event document (if
+    /// limited).
+    pub fn get_max_event_bytes(&self) -> Option<u64> {
+        self.max_event_bytes
+    }
+
+    /// Return the identity that issued the grant.
+    pub fn get_issuer(&self) -> &str {
+        &self.issuer
+    }
+
+    /// Return the groups of the identity that issued the grant.
+    pub fn get_issuer_groups(&self) -> &[String] {
+        &self.issuer_groups
+    }
+
+    /// Return the tenant of the identity that issued the grant (if any).
+    pub fn get_issuer_tenant(&self) -> Option<&str> {
+        self.issuer_tenant.as_deref()
+    }
+
+    fn protect(&self, oid: &[u32], master_secret: &[u8]) -> Vec<u8> {
+        let secret = CorrelationToken::derive_labeled_topic_secret(
+            oid,
+            master_secret,
+            Self::HKDF_INFO_PREFIX,
+            &self.topic,
+        );
+        let expires_bytes = u64::to_be_bytes(self.expires);
+        let max_events_bytes = u32::to_be_bytes(self.max_events);
+        // Distinguish unlimited size from any limit
+        let max_event_bytes_bytes = self
+            .max_event_bytes
+            .map(|max_event_bytes| [&[1u8][..], &u64::to_be_bytes(max_event_bytes)].concat())
+            .unwrap_or_else(|| vec![0u8]);
+        let issuer_len_bytes = u64::to_be_bytes(self.issuer.len() as u64);
+        // Grants without groups or tenant of the issuer keep their protection
+        let mut issuer_scope_bytes = Vec::new();
+        if !self.issuer_groups.is_empty() || self.issuer_tenant.is_some() {
+            issuer_scope_bytes
+                .extend_from_slice(&u64::to_be_bytes(self.issuer_groups.len() as u64));
+            for group in &self.issuer_groups {
+                issuer_scope_bytes.extend_from_slice(&u64::to_be_bytes(group.len() as u64));
+                issuer_scope_bytes.extend_from_slice(group.as_bytes());
+            }
+            if let Some(issuer_tenant) = &self.issuer_tenant {
+                issuer_scope_bytes.push(1u8);
+                issuer_scope_bytes.extend_from_slice(issuer_tenant.as_bytes());
+            } else {
+                issuer_scope_bytes.push(0u8);
+            }
+        }
+        CorrelationToken::mac(
+            oid,
+            &secret,
+            &[
+                self.uid.as_bytes(),
+                &expires_bytes,
+                &max_events_bytes,
+                &max_event_bytes_bytes,
+                &issuer_len_bytes,
+                self.issuer.as_bytes(),
+                &issuer_scope_bytes,
+            ],
+        )
+    }
+
+    /// Verify the grant's integrity protection using the secret derived for
+    /// the topic the grant was issued for.
+    pub fn verify(&self, oid: &[u32], master_secret: &[u8]) -> bool {
+        tyst::util::external_constant_time_equals(
+            &self.integrity,
+            &self.protect(oid, master_secret),
+        )
+    }
+}
+
+/// A newly issued [PublishGrant] and how to use it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IssuedPublishGrant {
+    /// Topic identifier.
+    topic_id: String,
+    /// The grant in serialized form.
+    grant: String,
+    /// Expiration time of the grant in epoch microseconds.
+    expires_micros: u64,
+    /// Max number of events that may be published with the grant.
+    max_events: u32,
+    /// Max size in bytes of each published event document (if limited).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_event_bytes: Option<u64>,
+    /// Relative URL to publish to with the grant.
+    url: String,
+}
+
+impl IssuedPublishGrant {
+    /// Return a new instance.
+    pub fn new(publish_grant: &PublishGrant, url: &str) -> Self {
+        Self {
+            topic_id: publish_grant.get_topic_id().to_owned(),
+            grant: publish_grant.as_string(),
+            expires_micros: publish_grant.get_expires_micros(),
+            max_events: publish_grant.get_max_events(),
+            max_event_bytes: publish_grant.get_max_event_bytes(),
+            url: url.to_owned(),
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// The grant in serialized form.
+    pub fn get_grant(&self) -> &str {
+        &self.grant
+    }
+
+    /// Expiration time of the grant in epoch microseconds.
+    pub fn get_expires_micros(&self) -> u64 {
+        self.expires_micros
+    }
+
+    /// Max number of events that may be published with the grant.
+    pub fn get_max_events(&self) -> u32 {
+        self.max_events
+    }
+
+    /// Max size in bytes of each published event document (if limited).
+    pub fn get_max_event_bytes(&self) -> Option<u64> {
+        self.max_event_bytes
+    }
+
+    /// Relative URL to publish to with the grant.
+    pub fn get_url(&self) -> &str {
+        &self.url
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn publish_grant_is_bound_to_topic_and_limits() {
+        let oid = tyst::oids::mac::HMAC_SHA3_256;
+        let master_secret = Tyst::instance().prng_get_random_bytes(None, 32);
+        let grant = PublishGrant::new(
+            oid,
+            &master_secret,
+            "topic_a",
+            42,
+            1,
+            Some(1024),
+            "me",
+            &["group;a".to_owned()],
+            Some("team1"),
+        );
+        let grant = PublishGrant::from_string(grant.as_string()).unwrap();
+        assert!(grant.verify(oid, &master_secret));
+        let mut relabeled = grant.clone();
+        relabeled.topic = "topic_b".to_owned();
+        assert!(!relabeled.verify(oid, &master_secret));
+        let mut extended = grant.clone();
+        extended.max_events = 2;
+        assert!(!extended.verify(oid, &master_secret));
+        let mut unlimited = grant.clone();
+        unlimited.max_event_bytes = None;
+        assert!(!unlimited.verify(oid, &master_secret));
+        let mut regrouped = grant.clone();
+        regrouped.issuer_groups.push("group;b".to_owned());
+        assert!(!regrouped.verify(oid, &master_secret));
+        let mut retenanted = grant.clone();
+        retenanted.issuer_tenant = None;
+        assert!(!retenanted.verify(oid, &master_secret));
+    }
+}
@@ -0,0 +1,150 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Recently rejected publishes of a broker instance.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Cause of a rejected publish.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PublishRejectionReason {
+    /// The publisher is not allowed to write to the topic or to create it.
+    Authz,
+    /// The document is malformed or does not comply with the topic's event
+    /// descriptor.
+    Schema,
+    /// Events were published faster than they could be given unique time.
+    RateLimit,
+    /// The broker's time could not be trusted.
+    TimeNotTrusted,
+    /// A different document with the same event identifier was refused.
+    Integrity,
+    /// Failure in the database backend.
+    Backend,
+    /// Low priority events were shed due to the backlog of the topic.
+    Backlog,
+}
+
+impl PublishRejectionReason {
+    /// Return the reason code. Example: "rate_limit"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Authz => "authz",
+            Self::Schema => "schema",
+            Self::RateLimit => "rate_limit",
+            Self::TimeNotTrusted => "time_not_trusted",
+            Self::Integrity => "integrity",
+            Self::Backend => "backend",
+            Self::Backlog => "backlog",
+        }
+    }
+}
+
+/// A publish that was rejected by the broker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PublishRejection {
+    /// Topic identifier.
+    topic_id: String,
+    /// Cause of the rejection.
+    reason: PublishRejectionReason,
+    /// Time of the rejection in epoch microseconds.
+    rejected_ts_micros: u64,
+    /// Identity of the publisher.
+    publisher: String,
+    /// Error message returned to the publisher.
+    error_message: String,
+}
+
+impl PublishRejection {
+    /// Return a new instance.
+    pub fn new(
+        topic_id: &str,
+        reason: PublishRejectionReason,
+        rejected_ts_micros: u64,
+        publisher: &str,
+        error_message: &str,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            reason,
+            rejected_ts_micros,
+            publisher: publisher.to_owned(),
+            error_message: error_message.to_owned(),
+        }
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Cause of the rejection.
+    pub fn get_reason(&self) -> PublishRejectionReason {
+        self.reason
+    }
+
+    /// Time of the rejection in epoch microseconds.
+    pub fn get_rejected_ts_micros(&self) -> u64 {
+        self.rejected_ts_micros
+    }
+
+    /// Identity of the publisher.
+    pub fn get_publisher(&self) -> &str {
+        &self.publisher
+    }
+
+    /// Error message returned to the publisher.
+    pub fn get_error_message(&self) -> &str {
+        &self.error_message
+    }
+}
+
+/// Recently rejected publishes of a broker instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PublishRejections {
+    /// Identifier of the broker instance that rejected the publishes.
+    instance_id: u16,
+    /// Rejections ordered with the most recent first.
+    rejections: Vec<PublishRejection>,
+}
+
+impl PublishRejections {
+    /// Return a new instance.
+    pub fn new(instance_id: u16, rejections: Vec<PublishRejection>) -> Self {
+        Self {
+            instance_id,
+            rejections,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Identifier of the broker instance that rejected the publishes.
+    pub fn get_instance_id(&self) -> u16 {
+        self.instance_id
+    }
+
+    /// Rejections ordered with the most recent first.
+    pub fn get_rejections(&self) -> &[PublishRejection] {
+        &self.rejections
+    }
+}
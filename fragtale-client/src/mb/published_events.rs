@@ -0,0 +1,47 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Outcome of publishing a batch of events.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Correlation tokens of a published batch of events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PublishedEvents {
+    /// Opaque tokens that can be used to correlate events in the order the
+    /// events were published in.
+    correlation_tokens: Vec<String>,
+}
+
+impl PublishedEvents {
+    /// Return a new instance.
+    pub fn new(correlation_tokens: Vec<String>) -> Self {
+        Self { correlation_tokens }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Return the correlation tokens in the order the events were published
+    /// in.
+    pub fn get_correlation_tokens(&self) -> &[String] {
+        &self.correlation_tokens
+    }
+}
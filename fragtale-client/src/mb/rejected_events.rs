@@ -0,0 +1,134 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Events rejected by validation during publishing.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An event document that was rejected by validation during publishing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RejectedEvent {
+    /// Event identifier.
+    event_id: String,
+    /// Time of the rejection in epoch microseconds.
+    rejected_ts_micros: u64,
+    /// The rejected event document.
+    document: String,
+    /// Requested event priority.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    priority: Option<u8>,
+    /// Requested Event Descriptor version in encoded form.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    descriptor_version: Option<u64>,
+    /// Identity of the publisher.
+    publisher: String,
+    /// Reason for the rejection.
+    error_message: String,
+}
+
+impl RejectedEvent {
+    /// Return a new instance.
+    pub fn new(
+        event_id: &str,
+        rejected_ts_micros: u64,
+        document: &str,
+        priority: Option<u8>,
+        descriptor_version: Option<u64>,
+        publisher: &str,
+        error_message: &str,
+    ) -> Self {
+        Self {
+            event_id: event_id.to_owned(),
+            rejected_ts_micros,
+            document: document.to_owned(),
+            priority,
+            descriptor_version,
+            publisher: publisher.to_owned(),
+            error_message: error_message.to_owned(),
+        }
+    }
+
+    /// Event identifier.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Time of the rejection in epoch microseconds.
+    pub fn get_rejected_ts_micros(&self) -> u64 {
+        self.rejected_ts_micros
+    }
+
+    /// The rejected event document.
+    pub fn get_document(&self) -> &str {
+        &self.document
+    }
+
+    /// Requested event priority.
+    pub fn get_priority(&self) -> Option<u8> {
+        self.priority
+    }
+
+    /// Requested Event Descriptor version in encoded form.
+    pub fn get_descriptor_version(&self) -> Option<u64> {
+        self.descriptor_version
+    }
+
+    /// Identity of the publisher.
+    pub fn get_publisher(&self) -> &str {
+        &self.publisher
+    }
+
+    /// Reason for the rejection.
+    pub fn get_error_message(&self) -> &str {
+        &self.error_message
+    }
+}
+
+/// A page of rejected events ordered by event identifier.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RejectedEvents {
+    /// Rejected events.
+    rejected_events: Vec<RejectedEvent>,
+    /// `true` if there are more rejected events after the last one returned.
+    more: bool,
+}
+
+impl RejectedEvents {
+    /// Return a new instance.
+    pub fn new(rejected_events: Vec<RejectedEvent>, more: bool) -> Self {
+        Self {
+            rejected_events,
+            more,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Rejected events.
+    pub fn get_rejected_events(&self) -> &[RejectedEvent] {
+        &self.rejected_events
+    }
+
+    /// `true` if there are more rejected events after the last one returned.
+    pub fn has_more(&self) -> bool {
+        self.more
+    }
+}
@@ -0,0 +1,143 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Replay of events published to a topic in a time range.
+
+use super::storage_tier::StorageTier;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An event published to a topic in the requested time range.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ReplayedEvent {
+    /// UniqueTime of the event.
+    encoded_unique_time: u64,
+    /// Event identifier.
+    event_id: String,
+    /// Event Descriptor SemVer (major.minor.patch) the event was published
+    /// with, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    descriptor_version: Option<String>,
+    /// Identifier of the event schema the event was published with, when
+    /// known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schema_id: Option<String>,
+    /// The integrity validated event document.
+    event_document: String,
+    /// Storage tier the event was read from.
+    #[serde(default)]
+    storage_tier: StorageTier,
+}
+
+impl ReplayedEvent {
+    /// Return a new instance.
+    pub fn new(
+        encoded_unique_time: u64,
+        event_id: &str,
+        descriptor_version: Option<String>,
+        schema_id: Option<String>,
+        event_document: &str,
+        storage_tier: StorageTier,
+    ) -> Self {
+        Self {
+            encoded_unique_time,
+            event_id: event_id.to_owned(),
+            descriptor_version,
+            schema_id,
+            event_document: event_document.to_owned(),
+            storage_tier,
+        }
+    }
+
+    /// UniqueTime of the event.
+    pub fn get_encoded_unique_time(&self) -> u64 {
+        self.encoded_unique_time
+    }
+
+    /// Event identifier.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Event Descriptor SemVer (major.minor.patch) the event was published
+    /// with, when known.
+    pub fn get_descriptor_version(&self) -> Option<&str> {
+        self.descriptor_version.as_deref()
+    }
+
+    /// Identifier of the event schema the event was published with, when
+    /// known.
+    pub fn get_schema_id(&self) -> Option<&str> {
+        self.schema_id.as_deref()
+    }
+
+    /// The integrity validated event document.
+    pub fn get_event_document(&self) -> &str {
+        &self.event_document
+    }
+
+    /// Storage tier the event was read from.
+    pub fn get_storage_tier(&self) -> StorageTier {
+        self.storage_tier
+    }
+}
+
+/// A page of the events published to a topic in a time range, in order of
+/// publication.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ReplayedEvents {
+    /// Topic identifier.
+    topic_id: String,
+    /// The events of the page.
+    events: Vec<ReplayedEvent>,
+    /// Encoded UniqueTime to continue the replay after, if there might be
+    /// more events in the time range.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    next: Option<u64>,
+}
+
+impl ReplayedEvents {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, events: Vec<ReplayedEvent>, next: Option<u64>) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            events,
+            next,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// The events of the page.
+    pub fn get_events(&self) -> &[ReplayedEvent] {
+        &self.events
+    }
+
+    /// Encoded UniqueTime to continue the replay after, if there might be
+    /// more events in the time range.
+    pub fn get_next(&self) -> Option<u64> {
+        self.next
+    }
+}
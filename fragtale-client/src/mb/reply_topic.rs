@@ -0,0 +1,48 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Reply topic registration for correlated requests.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Topic where the responding service publishes correlated results of events
+/// in a request topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ReplyTopic {
+    /// Topic identifier of correlated results.
+    reply_topic_id: String,
+}
+
+impl ReplyTopic {
+    /// Return a new instance.
+    pub fn new(reply_topic_id: &str) -> Self {
+        Self {
+            reply_topic_id: reply_topic_id.to_owned(),
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Return the topic identifier of correlated results.
+    pub fn get_reply_topic_id(&self) -> &str {
+        &self.reply_topic_id
+    }
+}
@@ -0,0 +1,134 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Bulk export and import of resource authorization grants.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Authorization of a principal to a resource.
+#[derive(
+    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, utoipa::ToSchema,
+)]
+pub struct ResourceGrant {
+    /// The resource where authorization is granted. E.g.
+    /// `/topic/my_topic/write` or `/admin/topics/execute`.
+    resource: String,
+    /// An identity string or a group string.
+    principal: String,
+}
+
+impl ResourceGrant {
+    /// Return a new instance.
+    pub fn new(resource: &str, principal: &str) -> Self {
+        Self {
+            resource: resource.to_owned(),
+            principal: principal.to_owned(),
+        }
+    }
+
+    /// The resource where authorization is granted.
+    pub fn get_resource(&self) -> &str {
+        &self.resource
+    }
+
+    /// An identity string or a group string.
+    pub fn get_principal(&self) -> &str {
+        &self.principal
+    }
+}
+
+/// The full set of explicit resource authorization grants.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ResourceGrants {
+    /// Granted authorizations sorted by resource and principal.
+    grants: Vec<ResourceGrant>,
+}
+
+impl ResourceGrants {
+    /// Return a new instance.
+    pub fn new(mut grants: Vec<ResourceGrant>) -> Self {
+        grants.sort();
+        grants.dedup();
+        Self { grants }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Granted authorizations sorted by resource and principal.
+    pub fn get_grants(&self) -> &[ResourceGrant] {
+        &self.grants
+    }
+}
+
+/// Outcome of a bulk import of resource authorization grants.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ResourceGrantsImport {
+    /// `true` if the changes were only computed and not applied.
+    dry_run: bool,
+    /// Grants that were (or would be) added.
+    granted: Vec<ResourceGrant>,
+    /// Grants that were (or would be) revoked.
+    revoked: Vec<ResourceGrant>,
+    /// Number of grants that were already in place.
+    unchanged: usize,
+}
+
+impl ResourceGrantsImport {
+    /// Return a new instance.
+    pub fn new(
+        dry_run: bool,
+        granted: Vec<ResourceGrant>,
+        revoked: Vec<ResourceGrant>,
+        unchanged: usize,
+    ) -> Self {
+        Self {
+            dry_run,
+            granted,
+            revoked,
+            unchanged,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// `true` if the changes were only computed and not applied.
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Grants that were (or would be) added.
+    pub fn get_granted(&self) -> &[ResourceGrant] {
+        &self.granted
+    }
+
+    /// Grants that were (or would be) revoked.
+    pub fn get_revoked(&self) -> &[ResourceGrant] {
+        &self.revoked
+    }
+
+    /// Number of grants that were already in place.
+    pub fn get_unchanged(&self) -> usize {
+        self.unchanged
+    }
+}
@@ -0,0 +1,189 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Dry-run preview of what a proposed retention would delete.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Effect of a proposed retention on a consumer of the topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConsumerRetentionPreview {
+    /// Consumer identifier.
+    consumer_id: String,
+    /// Encoded UniqueTime up to which all events are done for the consumer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    watermark: Option<u64>,
+    /// Number of events in the previewed buckets that are newer than the
+    /// consumer's watermark and would be deleted before being delivered.
+    undelivered_event_count: u64,
+    /// Encoded UniqueTime of the oldest event that would survive the
+    /// retention and is newer than the consumer's watermark.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    oldest_surviving_unique_time: Option<u64>,
+}
+
+impl ConsumerRetentionPreview {
+    /// Return a new instance.
+    pub fn new(
+        consumer_id: &str,
+        watermark: Option<u64>,
+        undelivered_event_count: u64,
+        oldest_surviving_unique_time: Option<u64>,
+    ) -> Self {
+        Self {
+            consumer_id: consumer_id.to_owned(),
+            watermark,
+            undelivered_event_count,
+            oldest_surviving_unique_time,
+        }
+    }
+
+    /// Consumer identifier.
+    pub fn get_consumer_id(&self) -> &str {
+        &self.consumer_id
+    }
+
+    /// Encoded UniqueTime up to which all events are done for the consumer.
+    pub fn get_watermark(&self) -> Option<u64> {
+        self.watermark
+    }
+
+    /// Number of events in the previewed buckets that are newer than the
+    /// consumer's watermark and would be deleted before being delivered.
+    pub fn get_undelivered_event_count(&self) -> u64 {
+        self.undelivered_event_count
+    }
+
+    /// Encoded UniqueTime of the oldest event that would survive the
+    /// retention and is newer than the consumer's watermark.
+    pub fn get_oldest_surviving_unique_time(&self) -> Option<u64> {
+        self.oldest_surviving_unique_time
+    }
+}
+
+/// Report of what a proposed retention would delete from a topic.
+///
+/// Nothing is deleted when this is produced. Large topics are previewed in
+/// pages of buckets, where `next_bucket` is used to continue the walk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RetentionPreview {
+    /// Topic identifier.
+    topic_id: String,
+    /// Proposed retention in seconds.
+    max_age_seconds: u64,
+    /// Events before this point in epoch microseconds would be deleted.
+    cutoff_ts_micros: u64,
+    /// Number of previewed buckets that would be deleted.
+    bucket_count: u64,
+    /// Number of events in the previewed buckets.
+    event_count: u64,
+    /// Estimated size of the event documents in the previewed buckets in
+    /// bytes.
+    estimated_bytes: u64,
+    /// Encoded UniqueTime of the oldest event that would survive the
+    /// retention.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    oldest_surviving_unique_time: Option<u64>,
+    /// Effect on each consumer of the topic.
+    consumers: Vec<ConsumerRetentionPreview>,
+    /// Bucket to continue the preview from, when there are more buckets that
+    /// would be deleted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    next_bucket: Option<u64>,
+}
+
+impl RetentionPreview {
+    /// Return a new instance.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        topic_id: &str,
+        max_age_seconds: u64,
+        cutoff_ts_micros: u64,
+        bucket_count: u64,
+        event_count: u64,
+        estimated_bytes: u64,
+        oldest_surviving_unique_time: Option<u64>,
+        consumers: Vec<ConsumerRetentionPreview>,
+        next_bucket: Option<u64>,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            max_age_seconds,
+            cutoff_ts_micros,
+            bucket_count,
+            event_count,
+            estimated_bytes,
+            oldest_surviving_unique_time,
+            consumers,
+            next_bucket,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Proposed retention in seconds.
+    pub fn get_max_age_seconds(&self) -> u64 {
+        self.max_age_seconds
+    }
+
+    /// Events before this point in epoch microseconds would be deleted.
+    pub fn get_cutoff_ts_micros(&self) -> u64 {
+        self.cutoff_ts_micros
+    }
+
+    /// Number of previewed buckets that would be deleted.
+    pub fn get_bucket_count(&self) -> u64 {
+        self.bucket_count
+    }
+
+    /// Number of events in the previewed buckets.
+    pub fn get_event_count(&self) -> u64 {
+        self.event_count
+    }
+
+    /// Estimated size of the event documents in the previewed buckets in
+    /// bytes.
+    pub fn get_estimated_bytes(&self) -> u64 {
+        self.estimated_bytes
+    }
+
+    /// Encoded UniqueTime of the oldest event that would survive the
+    /// retention.
+    pub fn get_oldest_surviving_unique_time(&self) -> Option<u64> {
+        self.oldest_surviving_unique_time
+    }
+
+    /// Effect on each consumer of the topic.
+    pub fn get_consumers(&self) -> &[ConsumerRetentionPreview] {
+        &self.consumers
+    }
+
+    /// Bucket to continue the preview from, when there are more buckets that
+    /// would be deleted.
+    pub fn get_next_bucket(&self) -> Option<u64> {
+        self.next_bucket
+    }
+}
@@ -0,0 +1,117 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Shared schema components that event schemas of any topic can reference.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Centrally registered schema component.
+///
+/// Event schemas reference the component with `$ref` by its `schema_id`, so
+/// multiple topics can reuse common definitions. Registering a component with
+/// the same `schema_id` again creates a new version, which is then used by all
+/// referencing event schemas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SharedSchema {
+    /// Schema identifier used as `$ref` target.
+    ///
+    /// Example: `https://example.com/schemas/address.json`
+    schema_id: String,
+    /// Schema type.
+    ///
+    /// Example: `https://json-schema.org/draft/2020-12/schema`
+    schema_type: String,
+    /// The actual schema component.
+    schema_data: String,
+    /// Version of the component assigned by the server on registration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<u64>,
+}
+
+impl SharedSchema {
+    /// Return a new instance.
+    pub fn new(schema_id: &str, schema_type: &str, schema_data: &str) -> Self {
+        Self {
+            schema_id: schema_id.to_owned(),
+            schema_type: schema_type.to_owned(),
+            schema_data: schema_data.to_owned(),
+            version: None,
+        }
+    }
+
+    /// Return a new instance from JSON serialization.
+    pub fn from_string<S: AsRef<str>>(value: S) -> Self {
+        serde_json::from_str(value.as_ref()).unwrap()
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Return a copy of this component with the assigned `version`.
+    pub fn with_version(mut self, version: u64) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Return the schema identifier used as `$ref` target.
+    pub fn get_schema_id(&self) -> &str {
+        &self.schema_id
+    }
+
+    /// Return the schema type.
+    pub fn get_schema_type(&self) -> &str {
+        &self.schema_type
+    }
+
+    /// Return the actual schema component.
+    pub fn get_schema_data(&self) -> &str {
+        &self.schema_data
+    }
+
+    /// Return the version of the component assigned by the server.
+    pub fn get_version(&self) -> Option<u64> {
+        self.version
+    }
+}
+
+/// Latest versions of all registered shared schema components.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SharedSchemas {
+    /// Registered components ordered by schema identifier.
+    schemas: Vec<SharedSchema>,
+}
+
+impl SharedSchemas {
+    /// Return a new instance.
+    pub fn new(mut schemas: Vec<SharedSchema>) -> Self {
+        schemas.sort_by(|a, b| a.schema_id.cmp(&b.schema_id));
+        Self { schemas }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Registered components ordered by schema identifier.
+    pub fn get_schemas(&self) -> &[SharedSchema] {
+        &self.schemas
+    }
+}
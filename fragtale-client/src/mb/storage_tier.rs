@@ -0,0 +1,43 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Storage tier an event was read from.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Storage tier an event was read from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageTier {
+    /// The event was read from the database.
+    #[default]
+    Database,
+    /// The event is no longer present in the database and was read from the
+    /// archive tier, which is much slower.
+    Archive,
+}
+
+impl StorageTier {
+    /// Return the name of the storage tier.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Database => "database",
+            Self::Archive => "archive",
+        }
+    }
+}
@@ -0,0 +1,149 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Health of topic subscriptions.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Health of a consumer's subscription to a topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SubscriptionHealth {
+    /// Consumer identifier.
+    consumer_id: String,
+    /// `true` when delivery to the consumer has been paused.
+    paused: bool,
+    /// Event identifier of the event that caused the subscription to pause.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paused_event_id: Option<String>,
+    /// Number of times the event that caused the subscription to pause was
+    /// redelivered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    paused_redeliveries: Option<u32>,
+    /// Number of events currently being redelivered to the consumer.
+    redelivered_events: usize,
+}
+
+impl SubscriptionHealth {
+    /// Return a new instance.
+    pub fn new(
+        consumer_id: &str,
+        paused: bool,
+        paused_event_id: Option<String>,
+        paused_redeliveries: Option<u32>,
+        redelivered_events: usize,
+    ) -> Self {
+        Self {
+            consumer_id: consumer_id.to_owned(),
+            paused,
+            paused_event_id,
+            paused_redeliveries,
+            redelivered_events,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Consumer identifier.
+    pub fn get_consumer_id(&self) -> &str {
+        &self.consumer_id
+    }
+
+    /// `true` when delivery to the consumer has been paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Event identifier of the event that caused the subscription to pause.
+    pub fn get_paused_event_id(&self) -> &Option<String> {
+        &self.paused_event_id
+    }
+
+    /// Number of times the event that caused the subscription to pause was
+    /// redelivered.
+    pub fn get_paused_redeliveries(&self) -> Option<u32> {
+        self.paused_redeliveries
+    }
+
+    /// Number of events currently being redelivered to the consumer.
+    pub fn get_redelivered_events(&self) -> usize {
+        self.redelivered_events
+    }
+}
+
+/// Health of all subscriptions to a topic served by a broker instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicSubscriptionsHealth {
+    /// Topic identifier.
+    topic_id: String,
+    /// Health of each subscription.
+    subscriptions: Vec<SubscriptionHealth>,
+}
+
+impl TopicSubscriptionsHealth {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, subscriptions: Vec<SubscriptionHealth>) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            subscriptions,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Health of each subscription.
+    pub fn get_subscriptions(&self) -> &[SubscriptionHealth] {
+        &self.subscriptions
+    }
+}
+
+/// Request to resume delivery to a paused subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SubscriptionResume {
+    /// Consumer identifier.
+    consumer_id: String,
+}
+
+impl SubscriptionResume {
+    /// Return a new instance.
+    pub fn new(consumer_id: &str) -> Self {
+        Self {
+            consumer_id: consumer_id.to_owned(),
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Consumer identifier.
+    pub fn get_consumer_id(&self) -> &str {
+        &self.consumer_id
+    }
+}
@@ -0,0 +1,134 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Topic ownership and access grants.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Principals that own a topic.
+///
+/// A principal is either an identity string or a group string (`group;` +
+/// group name from the bearer token's `groups` claim).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicOwnership {
+    /// Topic identifier.
+    topic_id: String,
+    /// Principals that own the topic.
+    owners: Vec<String>,
+}
+
+impl TopicOwnership {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, owners: Vec<String>) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            owners,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Principals that own the topic.
+    pub fn get_owners(&self) -> &[String] {
+        &self.owners
+    }
+}
+
+/// Request to transfer the ownership of a topic to a new principal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicOwnershipTransfer {
+    /// The new owner. An identity string or a group string.
+    owner: String,
+}
+
+impl TopicOwnershipTransfer {
+    /// Return a new instance.
+    pub fn new(owner: &str) -> Self {
+        Self {
+            owner: owner.to_owned(),
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// The new owner.
+    pub fn get_owner(&self) -> &str {
+        &self.owner
+    }
+}
+
+/// Grant or revoke access to a topic operation for a principal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicAccessGrant {
+    /// An identity string or a group string.
+    principal: String,
+    /// The topic operation. One of `write`, `annotate` or `unmasked`.
+    operation: String,
+    /// `true` to grant access and `false` to revoke it.
+    granted: bool,
+}
+
+impl TopicAccessGrant {
+    /// Operation for publishing events to the topic.
+    pub const OPERATION_WRITE: &str = "write";
+    /// Operation for annotating events in the topic.
+    pub const OPERATION_ANNOTATE: &str = "annotate";
+    /// Operation for receiving events without the topic's masking rules
+    /// applied.
+    pub const OPERATION_UNMASKED: &str = "unmasked";
+
+    /// Return a new instance.
+    pub fn new(principal: &str, operation: &str, granted: bool) -> Self {
+        Self {
+            principal: principal.to_owned(),
+            operation: operation.to_owned(),
+            granted,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// An identity string or a group string.
+    pub fn get_principal(&self) -> &str {
+        &self.principal
+    }
+
+    /// The topic operation.
+    pub fn get_operation(&self) -> &str {
+        &self.operation
+    }
+
+    /// `true` to grant access and `false` to revoke it.
+    pub fn is_granted(&self) -> bool {
+        self.granted
+    }
+}
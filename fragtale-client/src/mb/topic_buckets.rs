@@ -0,0 +1,206 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Raw storage layout of a topic for diagnostics.
+//!
+//! Events are stored in time based "buckets" that are grouped in "shelves".
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Shelves of a topic that contain at least one bucket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicShelves {
+    /// Shelves in ascending order.
+    shelves: Vec<u16>,
+}
+
+impl TopicShelves {
+    /// Return a new instance.
+    pub fn new(shelves: Vec<u16>) -> Self {
+        Self { shelves }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Shelves in ascending order.
+    pub fn get_shelves(&self) -> &[u16] {
+        &self.shelves
+    }
+}
+
+/// A bucket of events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicBucket {
+    /// Bucket identifier.
+    bucket: u64,
+    /// Earliest possible event time in the bucket in epoch microseconds.
+    start_ts_micros: u64,
+    /// Number of events in the bucket.
+    event_count: u64,
+}
+
+impl TopicBucket {
+    /// Return a new instance.
+    pub fn new(bucket: u64, start_ts_micros: u64, event_count: u64) -> Self {
+        Self {
+            bucket,
+            start_ts_micros,
+            event_count,
+        }
+    }
+
+    /// Bucket identifier.
+    pub fn get_bucket(&self) -> u64 {
+        self.bucket
+    }
+
+    /// Earliest possible event time in the bucket in epoch microseconds.
+    pub fn get_start_ts_micros(&self) -> u64 {
+        self.start_ts_micros
+    }
+
+    /// Number of events in the bucket.
+    pub fn get_event_count(&self) -> u64 {
+        self.event_count
+    }
+}
+
+/// A page of used buckets in a shelf ordered by bucket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicBuckets {
+    /// Shelf of the buckets.
+    shelf: u16,
+    /// Buckets.
+    buckets: Vec<TopicBucket>,
+    /// `true` if there are more buckets after the last one returned.
+    more: bool,
+}
+
+impl TopicBuckets {
+    /// Return a new instance.
+    pub fn new(shelf: u16, buckets: Vec<TopicBucket>, more: bool) -> Self {
+        Self {
+            shelf,
+            buckets,
+            more,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Shelf of the buckets.
+    pub fn get_shelf(&self) -> u16 {
+        self.shelf
+    }
+
+    /// Buckets.
+    pub fn get_buckets(&self) -> &[TopicBucket] {
+        &self.buckets
+    }
+
+    /// `true` if there are more buckets after the last one returned.
+    pub fn has_more(&self) -> bool {
+        self.more
+    }
+}
+
+/// Raw entry of an event in a bucket.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BucketEntry {
+    /// Encoded UniqueTime of the event.
+    unique_time: u64,
+    /// Event identifier.
+    event_id: String,
+    /// Event Descriptor version in encoded form.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    descriptor_version: Option<u64>,
+}
+
+impl BucketEntry {
+    /// Return a new instance.
+    pub fn new(unique_time: u64, event_id: &str, descriptor_version: Option<u64>) -> Self {
+        Self {
+            unique_time,
+            event_id: event_id.to_owned(),
+            descriptor_version,
+        }
+    }
+
+    /// Encoded UniqueTime of the event.
+    pub fn get_unique_time(&self) -> u64 {
+        self.unique_time
+    }
+
+    /// Event identifier.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Event Descriptor version in encoded form.
+    pub fn get_descriptor_version(&self) -> Option<u64> {
+        self.descriptor_version
+    }
+}
+
+/// A page of raw entries in a bucket ordered by UniqueTime.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BucketEntries {
+    /// Bucket of the entries.
+    bucket: u64,
+    /// Entries.
+    entries: Vec<BucketEntry>,
+    /// `true` if there are more entries after the last one returned.
+    more: bool,
+}
+
+impl BucketEntries {
+    /// Return a new instance.
+    pub fn new(bucket: u64, entries: Vec<BucketEntry>, more: bool) -> Self {
+        Self {
+            bucket,
+            entries,
+            more,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Bucket of the entries.
+    pub fn get_bucket(&self) -> u64 {
+        self.bucket
+    }
+
+    /// Entries.
+    pub fn get_entries(&self) -> &[BucketEntry] {
+        &self.entries
+    }
+
+    /// `true` if there are more entries after the last one returned.
+    pub fn has_more(&self) -> bool {
+        self.more
+    }
+}
@@ -0,0 +1,80 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Object counts of a topic.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Number of published events and delivery intents of a topic across all
+/// instances.
+///
+/// The counts are kept across broker restarts until they are explicitly
+/// reset.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicCounts {
+    /// Topic identifier.
+    topic_id: String,
+    /// Number of published events.
+    events: u64,
+    /// Number of reserved delivery intents.
+    reserved_delivery_intents: u64,
+    /// Number of finished delivery intents.
+    done_delivery_intents: u64,
+}
+
+impl TopicCounts {
+    /// Return a new instance.
+    pub fn new(
+        topic_id: &str,
+        events: u64,
+        reserved_delivery_intents: u64,
+        done_delivery_intents: u64,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            events,
+            reserved_delivery_intents,
+            done_delivery_intents,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Number of published events.
+    pub fn get_events(&self) -> u64 {
+        self.events
+    }
+
+    /// Number of reserved delivery intents.
+    pub fn get_reserved_delivery_intents(&self) -> u64 {
+        self.reserved_delivery_intents
+    }
+
+    /// Number of finished delivery intents.
+    pub fn get_done_delivery_intents(&self) -> u64 {
+        self.done_delivery_intents
+    }
+}
@@ -0,0 +1,190 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Overview of topics, consumers and descriptor history for operators.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A page of known topic identifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicList {
+    /// Topic identifiers in ascending order.
+    topic_ids: Vec<String>,
+    /// `true` if there might be more topics after the last returned one.
+    more: bool,
+}
+
+impl TopicList {
+    /// Return a new instance.
+    pub fn new(topic_ids: Vec<String>, more: bool) -> Self {
+        Self { topic_ids, more }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifiers in ascending order.
+    pub fn get_topic_ids(&self) -> &[String] {
+        &self.topic_ids
+    }
+
+    /// `true` if there might be more topics after the last returned one.
+    pub fn has_more(&self) -> bool {
+        self.more
+    }
+}
+
+/// How far a consumer is behind the head of a topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ConsumerLag {
+    /// Consumer identifier.
+    consumer_id: String,
+    /// Epoch microseconds of the latest event confirmed as delivered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    done_ts_micros: Option<u64>,
+    /// Epoch microseconds of the latest event attempted for delivery.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    attempted_ts_micros: Option<u64>,
+    /// Microseconds between the latest confirmed delivery and the time of the
+    /// request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    lag_micros: Option<u64>,
+}
+
+impl ConsumerLag {
+    /// Return a new instance where the lag is relative to `now_micros`.
+    pub fn new(
+        consumer_id: &str,
+        done_ts_micros: Option<u64>,
+        attempted_ts_micros: Option<u64>,
+        now_micros: u64,
+    ) -> Self {
+        Self {
+            consumer_id: consumer_id.to_owned(),
+            done_ts_micros,
+            attempted_ts_micros,
+            lag_micros: done_ts_micros.map(|done_ts| now_micros.saturating_sub(done_ts)),
+        }
+    }
+
+    /// Consumer identifier.
+    pub fn get_consumer_id(&self) -> &str {
+        &self.consumer_id
+    }
+
+    /// Epoch microseconds of the latest event confirmed as delivered.
+    pub fn get_done_ts_micros(&self) -> Option<u64> {
+        self.done_ts_micros
+    }
+
+    /// Epoch microseconds of the latest event attempted for delivery.
+    pub fn get_attempted_ts_micros(&self) -> Option<u64> {
+        self.attempted_ts_micros
+    }
+
+    /// Microseconds between the latest confirmed delivery and the time of the
+    /// request.
+    pub fn get_lag_micros(&self) -> Option<u64> {
+        self.lag_micros
+    }
+}
+
+/// Lag of all consumers of a topic.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicConsumersLag {
+    /// Topic identifier.
+    topic_id: String,
+    /// Lag of each consumer.
+    consumers: Vec<ConsumerLag>,
+}
+
+impl TopicConsumersLag {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, consumers: Vec<ConsumerLag>) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            consumers,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Lag of each consumer.
+    pub fn get_consumers(&self) -> &[ConsumerLag] {
+        &self.consumers
+    }
+}
+
+/// All registered versions of a topic's event description.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DescriptorHistory {
+    /// Topic identifier.
+    topic_id: String,
+    /// Versions in the form `major.minor.patch` from oldest to latest.
+    versions: Vec<String>,
+}
+
+impl DescriptorHistory {
+    /// Return a new instance.
+    pub fn new(topic_id: &str, versions: Vec<String>) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            versions,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Versions in the form `major.minor.patch` from oldest to latest.
+    pub fn get_versions(&self) -> &[String] {
+        &self.versions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consumer_lag_is_relative_to_request_time() {
+        let lag = ConsumerLag::new("consumer", Some(1_000), Some(1_500), 4_000);
+        assert_eq!(lag.get_lag_micros(), Some(3_000));
+        let lag = ConsumerLag::new("consumer", Some(5_000), None, 4_000);
+        assert_eq!(lag.get_lag_micros(), Some(0));
+        let lag = ConsumerLag::new("consumer", None, None, 4_000);
+        assert_eq!(lag.get_lag_micros(), None);
+    }
+}
@@ -0,0 +1,128 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Event size and field-level statistics of a topic.
+
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Rolling statistics of events published to a topic.
+///
+/// The statistics are collected by each broker instance since it was started
+/// and are intended to inform schema/extraction design and capacity planning.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TopicStatistics {
+    /// Topic identifier.
+    topic_id: String,
+    /// Start of collection in epoch microseconds.
+    since_micros: u64,
+    /// Number of sampled events.
+    event_count: u64,
+    /// Smallest sampled event document size in bytes.
+    size_min: u64,
+    /// Largest sampled event document size in bytes.
+    size_max: u64,
+    /// Average sampled event document size in bytes.
+    size_mean: u64,
+    /// Number of event documents by (inclusive) upper size bound in bytes.
+    ///
+    /// Buckets are powers of two and empty buckets are omitted.
+    size_distribution: BTreeMap<u64, u64>,
+    /// Number of event documents where each top-level field was present.
+    field_presence: BTreeMap<String, u64>,
+    /// Estimated number of distinct values by extracted result name.
+    extracted_cardinality: BTreeMap<String, u64>,
+}
+
+impl TopicStatistics {
+    /// Return a new instance.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        topic_id: &str,
+        since_micros: u64,
+        event_count: u64,
+        size_min: u64,
+        size_max: u64,
+        size_mean: u64,
+        size_distribution: BTreeMap<u64, u64>,
+        field_presence: BTreeMap<String, u64>,
+        extracted_cardinality: BTreeMap<String, u64>,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            since_micros,
+            event_count,
+            size_min,
+            size_max,
+            size_mean,
+            size_distribution,
+            field_presence,
+            extracted_cardinality,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Start of collection in epoch microseconds.
+    pub fn get_since_micros(&self) -> u64 {
+        self.since_micros
+    }
+
+    /// Number of sampled events.
+    pub fn get_event_count(&self) -> u64 {
+        self.event_count
+    }
+
+    /// Smallest sampled event document size in bytes.
+    pub fn get_size_min(&self) -> u64 {
+        self.size_min
+    }
+
+    /// Largest sampled event document size in bytes.
+    pub fn get_size_max(&self) -> u64 {
+        self.size_max
+    }
+
+    /// Average sampled event document size in bytes.
+    pub fn get_size_mean(&self) -> u64 {
+        self.size_mean
+    }
+
+    /// Number of event documents by (inclusive) upper size bound in bytes.
+    pub fn get_size_distribution(&self) -> &BTreeMap<u64, u64> {
+        &self.size_distribution
+    }
+
+    /// Number of event documents where each top-level field was present.
+    pub fn get_field_presence(&self) -> &BTreeMap<String, u64> {
+        &self.field_presence
+    }
+
+    /// Estimated number of distinct values by extracted result name.
+    pub fn get_extracted_cardinality(&self) -> &BTreeMap<String, u64> {
+        &self.extracted_cardinality
+    }
+}
@@ -0,0 +1,139 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Open WebSocket sessions of a broker instance.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An open WebSocket session.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WebSocketSession {
+    /// Identifier of the session that is unique within the broker instance.
+    session_id: u64,
+    /// Kind of session: `subscribe`, `confirm`, `publish` or `multiplex`.
+    kind: String,
+    /// Topic identifier. Absent for multiplexed sessions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    topic_id: Option<String>,
+    /// Identity string of the client.
+    identity: String,
+    /// Time when the session was opened in epoch microseconds.
+    opened_ts_micros: u64,
+    /// Time of the last message from the client in epoch microseconds.
+    last_activity_ts_micros: u64,
+    /// Number of bytes sent to the client in messages.
+    sent_bytes: u64,
+}
+
+impl WebSocketSession {
+    /// Return a new instance.
+    pub fn new(
+        session_id: u64,
+        kind: &str,
+        topic_id: Option<&str>,
+        identity: &str,
+        opened_ts_micros: u64,
+        last_activity_ts_micros: u64,
+        sent_bytes: u64,
+    ) -> Self {
+        Self {
+            session_id,
+            kind: kind.to_owned(),
+            topic_id: topic_id.map(str::to_owned),
+            identity: identity.to_owned(),
+            opened_ts_micros,
+            last_activity_ts_micros,
+            sent_bytes,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Identifier of the session that is unique within the broker instance.
+    pub fn get_session_id(&self) -> u64 {
+        self.session_id
+    }
+
+    /// Kind of session: `subscribe`, `confirm`, `publish` or `multiplex`.
+    pub fn get_kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Topic identifier. Absent for multiplexed sessions.
+    pub fn get_topic_id(&self) -> Option<&str> {
+        self.topic_id.as_deref()
+    }
+
+    /// Identity string of the client.
+    pub fn get_identity(&self) -> &str {
+        &self.identity
+    }
+
+    /// Time when the session was opened in epoch microseconds.
+    pub fn get_opened_ts_micros(&self) -> u64 {
+        self.opened_ts_micros
+    }
+
+    /// Time of the last message from the client in epoch microseconds.
+    pub fn get_last_activity_ts_micros(&self) -> u64 {
+        self.last_activity_ts_micros
+    }
+
+    /// Number of bytes sent to the client in messages.
+    pub fn get_sent_bytes(&self) -> u64 {
+        self.sent_bytes
+    }
+}
+
+/// Open WebSocket sessions of a broker instance.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WebSocketSessions {
+    /// Identifier of the broker instance the sessions are connected to.
+    instance_id: u16,
+    /// Open sessions ordered by session identifier.
+    sessions: Vec<WebSocketSession>,
+}
+
+impl WebSocketSessions {
+    /// Return a new instance.
+    pub fn new(instance_id: u16, mut sessions: Vec<WebSocketSession>) -> Self {
+        sessions.sort_by_key(WebSocketSession::get_session_id);
+        Self {
+            instance_id,
+            sessions,
+        }
+    }
+
+    /// Return as a JSON serialized String.
+    pub fn as_string(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    /// Identifier of the broker instance the sessions are connected to.
+    pub fn get_instance_id(&self) -> u16 {
+        self.instance_id
+    }
+
+    /// Open sessions ordered by session identifier.
+    pub fn get_sessions(&self) -> &[WebSocketSession] {
+        &self.sessions
+    }
+}
@@ -0,0 +1,78 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Protocol version negotiation between clients and broker instances.
+//!
+//! During a rolling upgrade, a cluster might have broker instances and
+//! clients of different versions. Each side announces the highest protocol
+//! version it speaks and the oldest one it still understands, so that both
+//! sides can agree on an encoding (e.g. of UniqueTime or
+//! `SubscriberResponse`) that both can parse.
+
+/// HTTP header used to announce the protocol version of the sender.
+pub const HEADER_NAME: &str = "protocol-version";
+
+/// The highest protocol version spoken by this version of the library.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The oldest protocol version still understood by this version of the
+/// library.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// Return the highest protocol version understood by both this library and
+/// the peer or `None` if the supported version ranges do not overlap.
+///
+/// A peer that did not announce a protocol version is assumed to only speak
+/// the oldest protocol version.
+pub fn negotiate(
+    peer_protocol_version: Option<u32>,
+    peer_min_protocol_version: u32,
+) -> Option<u32> {
+    let peer_protocol_version = peer_protocol_version.unwrap_or(peer_min_protocol_version);
+    let version = std::cmp::min(PROTOCOL_VERSION, peer_protocol_version);
+    (version >= std::cmp::max(MIN_PROTOCOL_VERSION, peer_min_protocol_version)).then_some(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiate_overlapping_ranges() {
+        assert_eq!(
+            negotiate(Some(PROTOCOL_VERSION), MIN_PROTOCOL_VERSION),
+            Some(PROTOCOL_VERSION)
+        );
+        assert_eq!(
+            negotiate(Some(PROTOCOL_VERSION + 1), MIN_PROTOCOL_VERSION),
+            Some(PROTOCOL_VERSION)
+        );
+        assert_eq!(
+            negotiate(None, MIN_PROTOCOL_VERSION),
+            Some(MIN_PROTOCOL_VERSION)
+        );
+    }
+
+    #[test]
+    fn negotiate_disjoint_ranges() {
+        assert_eq!(
+            negotiate(Some(PROTOCOL_VERSION + 2), PROTOCOL_VERSION + 1),
+            None
+        );
+        assert_eq!(negotiate(Some(MIN_PROTOCOL_VERSION - 1), 0), None);
+    }
+}
@@ -17,17 +17,33 @@
 
 //! Interactions with `fragtale` using the REST API.
 
+mod client_error;
+mod retry_policy;
+
+pub use self::client_error::ClientError;
+pub use self::retry_policy::RetryObserver;
+pub use self::retry_policy::RetryPolicy;
+use crate::ClientMetricsObserver;
 use crate::authentication::BearerTokenCache;
+use crate::mb::consumer_position::ConsumerPosition;
+use crate::mb::consumer_position::ConsumerSeek;
+use crate::mb::delivery_preparation::DeliveryPreparation;
 use crate::mb::event_descriptor::EventDescriptor;
+use crate::mb::published_events::PublishedEvents;
+use crate::mb::reply_topic::ReplyTopic;
 use reqwest::Client;
 use reqwest::ClientBuilder;
 use reqwest::Error;
+use reqwest::RequestBuilder;
 use reqwest::Response;
 use reqwest::StatusCode;
 use reqwest::header::AUTHORIZATION;
 use reqwest::header::CONTENT_TYPE;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderValue;
+use serde::de::DeserializeOwned;
 use std::sync::Arc;
-use tokio::time::Duration;
+use std::time::Instant;
 use tokio::time::sleep;
 
 /// Client for interacting with `fragtale` using the REST API.
@@ -36,26 +52,53 @@ pub struct RestApiClient {
     // Client uses an Arc internally, so it doesn't need Arc<> wrapping here
     client: Client,
     bearer_token_cache: Arc<BearerTokenCache>,
+    retry_policy: RetryPolicy,
+    metrics_observer: Option<Arc<dyn ClientMetricsObserver>>,
 }
 impl RestApiClient {
     const MIME_APPLICATION_JSON: &'static str = "application/json";
 
-    /// Return a new instance.
+    /// Return a new instance using the default [RetryPolicy].
     pub async fn new(
         api_base_url: &str,
         app_name_lowercase: &str,
         app_version: &str,
         pool_size: usize,
+    ) -> Self {
+        Self::new_with_retry_policy(
+            api_base_url,
+            app_name_lowercase,
+            app_version,
+            pool_size,
+            RetryPolicy::default(),
+        )
+        .await
+    }
+
+    /// Return a new instance that applies the provided timeouts and retries.
+    pub async fn new_with_retry_policy(
+        api_base_url: &str,
+        app_name_lowercase: &str,
+        app_version: &str,
+        pool_size: usize,
+        retry_policy: RetryPolicy,
     ) -> Self {
         let bearer_token_cache = BearerTokenCache::new().await;
         let user_agent = format!("{app_name_lowercase}/{app_version}");
         log::debug!("user_agent: {user_agent}. pool_size: {pool_size}");
+        let mut default_headers = HeaderMap::new();
+        default_headers.insert(
+            crate::protocol::HEADER_NAME,
+            HeaderValue::from(crate::protocol::PROTOCOL_VERSION),
+        );
         let client = ClientBuilder::new()
             .user_agent(user_agent)
+            .default_headers(default_headers)
             .referer(false)
             .brotli(true)
             .pool_max_idle_per_host(pool_size)
-            .timeout(core::time::Duration::from_secs(10))
+            .connect_timeout(retry_policy.connect_timeout())
+            .timeout(retry_policy.request_timeout())
             //.http2_prior_knowledge()
             .build()
             .unwrap();
@@ -63,19 +106,33 @@ impl RestApiClient {
             api_base_url: api_base_url.to_owned(),
             client,
             bearer_token_cache,
+            retry_policy,
+            metrics_observer: None,
         }
     }
 
+    /// Set an observer that is notified about publish retries and the wait
+    /// for correlated results.
+    pub fn with_metrics_observer(
+        mut self,
+        metrics_observer: Arc<dyn ClientMetricsObserver>,
+    ) -> Self {
+        self.metrics_observer = Some(metrics_observer);
+        self
+    }
+
     /// Pre-register information about a topic.
     ///
     /// If the topic did not exist, it will be created.
     ///
     /// Only a single producer should "own" the topic and its description.
+    ///
+    /// Return the response body (if any) when successful.
     pub async fn register_topic(
         &self,
         topic_id: &str,
         topic_description: Option<EventDescriptor>,
-    ) -> Option<String> {
+    ) -> Result<Option<String>, ClientError> {
         let client = self.client.clone();
         let url = format!("{}/topics/{}/description", self.api_base_url, topic_id);
         let request_json_string = if let Some(event_descriptor) = topic_description {
@@ -101,29 +158,19 @@ impl RestApiClient {
             .send()
             .await;
         Self::get_http_20x_response_body_as_string(res, &url).await
-        //.and_then(|json| CountRangeResponse::from_json_string(&json).map(|crr| crr.counts()))
     }
 
-    /// Publish a document to a topic.
+    /// Get the latest description of a topic's events.
     ///
-    /// Return correlation-token when successful
-    pub async fn publish_document(
+    /// Return `None` if no description has been registered.
+    pub async fn event_descriptor_by_topic(
         &self,
-        publish_to_topic_id: &str,
-        document: &str,
-        correlation_token: &str,
-    ) -> Option<String> {
+        topic_id: &str,
+    ) -> Result<Option<EventDescriptor>, ClientError> {
         let client = self.client.clone();
-        let url = format!(
-            "{}/topics/{}/events?priority=50",
-            self.api_base_url, publish_to_topic_id
-        );
-        let request_json_string = document.to_owned();
-        log::trace!("Sending body: {request_json_string}");
+        let url = format!("{}/topics/{topic_id}/description", self.api_base_url);
         let result = client
-            .put(&url)
-            .body(request_json_string)
-            .header(&CONTENT_TYPE, Self::MIME_APPLICATION_JSON)
+            .get(&url)
             .header(
                 &AUTHORIZATION,
                 self.bearer_token_cache
@@ -131,102 +178,227 @@ impl RestApiClient {
                     .await
                     .as_str(),
             )
-            .header("correlation-token", correlation_token)
             .send()
             .await;
+        match Self::get_http_20x_response_body_as_string(result, &url).await {
+            Ok(Some(content)) => Self::parse_json(&content, &url).map(Some),
+            Ok(None) | Err(ClientError::NotFound) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Publish a document to a topic.
+    ///
+    /// Return correlation-token when successful
+    pub async fn publish_document(
+        &self,
+        publish_to_topic_id: &str,
+        document: &str,
+        correlation_token: &str,
+    ) -> Result<String, ClientError> {
+        self.publish_expiring_document(publish_to_topic_id, document, correlation_token, None)
+            .await
+    }
+
+    /// Publish a document to a topic that should not be delivered after the
+    /// `expires_ts` deadline in epoch microseconds.
+    ///
+    /// Return correlation-token when successful
+    pub async fn publish_expiring_document(
+        &self,
+        publish_to_topic_id: &str,
+        document: &str,
+        correlation_token: &str,
+        expires_ts: Option<u64>,
+    ) -> Result<String, ClientError> {
+        let client = self.client.clone();
+        let mut url = format!(
+            "{}/topics/{}/events?priority=50",
+            self.api_base_url, publish_to_topic_id
+        );
+        if let Some(expires_ts) = expires_ts {
+            url += &format!("&expires={expires_ts}");
+        }
+        log::trace!("Sending body: {document}");
+        let result = self
+            .send_publish_with_retry(publish_to_topic_id, &url, || {
+                client
+                    .put(&url)
+                    .body(document.to_owned())
+                    .header(&CONTENT_TYPE, Self::MIME_APPLICATION_JSON)
+                    .header("correlation-token", correlation_token)
+            })
+            .await;
         Self::handle_response_err(result, &url).and_then(|response| {
-            //if response.status() == StatusCode::NO_CONTENT {}
             Self::header_as_string(&response, "correlation-token")
+                .ok_or_else(|| ClientError::invalid_response("Missing correlation-token header."))
         })
     }
 
+    /// Publish a batch of documents to a topic with a single request.
+    ///
+    /// Nothing is published unless all documents are valid, but a failed
+    /// request may have published a leading part of the batch. The server
+    /// limits the number of documents in a batch.
+    ///
+    /// Return the correlation-tokens of the published documents in the order
+    /// of `documents` when successful.
+    pub async fn publish_documents(
+        &self,
+        publish_to_topic_id: &str,
+        documents: &[&str],
+    ) -> Result<Vec<String>, ClientError> {
+        let client = self.client.clone();
+        let url = format!(
+            "{}/topics/{}/events/batch?priority=50",
+            self.api_base_url, publish_to_topic_id
+        );
+        let body = format!("[{}]", documents.join(","));
+        log::trace!("Sending body: {body}");
+        let result = self
+            .send_publish_with_retry(publish_to_topic_id, &url, || {
+                client
+                    .put(&url)
+                    .body(body.to_owned())
+                    .header(&CONTENT_TYPE, Self::MIME_APPLICATION_JSON)
+            })
+            .await;
+        match Self::get_http_20x_response_body_as_string(result, &url).await? {
+            Some(content) => Self::parse_json::<PublishedEvents>(&content, &url)
+                .map(|published_events| published_events.get_correlation_tokens().to_vec()),
+            None => Err(ClientError::invalid_response(
+                "Missing correlation tokens of published documents.",
+            )),
+        }
+    }
+
     /// Publish a document to a topic (`publish_to_topic_id`) and wait for a
     /// correlated event to be consumed from another topic
     /// (`consume_from_topic_id`).
+    ///
+    /// When `consume_from_topic_id` is `None`, the reply topic registered by
+    /// the responding service is used. See [Self::register_reply_topic].
+    ///
+    /// Return [ClientError::Timeout] if no correlated result was available
+    /// within the polling budget of the [RetryPolicy].
     pub async fn publish_and_await_result(
         &self,
         publish_to_topic_id: &str,
-        consume_from_topic_id: &str,
+        consume_from_topic_id: Option<&str>,
         document: &str,
-    ) -> Option<String> {
+    ) -> Result<String, ClientError> {
         let client = self.client.clone();
-        let url = format!(
-            "{}/topics/{}/events?priority=50&target={}",
-            self.api_base_url, publish_to_topic_id, consume_from_topic_id,
-        );
-        let request_json_string = document.to_owned();
+        let url = if let Some(consume_from_topic_id) = consume_from_topic_id {
+            format!(
+                "{}/topics/{}/events?priority=50&target={}",
+                self.api_base_url, publish_to_topic_id, consume_from_topic_id,
+            )
+        } else {
+            format!(
+                "{}/topics/{}/events?priority=50&reply=true",
+                self.api_base_url, publish_to_topic_id,
+            )
+        };
+        let consume_from_topic_id = consume_from_topic_id.unwrap_or("registered reply topic");
+        let start = Instant::now();
         if log::log_enabled!(log::Level::Trace) {
-            log::trace!("Sending body: {request_json_string}");
+            log::trace!("Sending body: {document}");
         }
-        let result = client
-            .put(&url)
-            .body(request_json_string)
-            .header(&CONTENT_TYPE, Self::MIME_APPLICATION_JSON)
-            .header(
-                &AUTHORIZATION,
-                self.bearer_token_cache
-                    .current_as_header_value()
-                    .await
-                    .as_str(),
-            )
-            .send()
+        let result = self
+            .send_publish_with_retry(publish_to_topic_id, &url, || {
+                client
+                    .put(&url)
+                    .body(document.to_owned())
+                    .header(&CONTENT_TYPE, Self::MIME_APPLICATION_JSON)
+            })
             .await;
-        let mut location_header_content = None;
-        if let Ok(response) = result.map_err(|e| {
-            log::info!("Failed request to {url}: {:?}", e.without_url());
-        }) {
-            match response.status() {
-                StatusCode::OK => {
-                    return response
-                        .text()
-                        .await
-                        .map_err(|e| {
-                            log::info!(
-                            "Failed request to {url}: Failed to parse response body as text: {:?}",
-                            e.without_url()
-                        );
-                        })
-                        .ok();
-                }
-                StatusCode::SEE_OTHER => {
-                    location_header_content = Self::header_as_string(&response, "location");
-                    #[allow(clippy::question_mark)]
-                    if location_header_content.is_none() {
-                        return None;
-                    }
+        let response = Self::handle_response_err(result, &url)?;
+        let location_header_content = match response.status() {
+            StatusCode::OK => {
+                let result = Self::response_body_as_string(response, &url).await;
+                self.notify_correlation_wait(publish_to_topic_id, start, result.is_ok());
+                return result;
+            }
+            StatusCode::SEE_OTHER => Self::header_as_string(&response, "location")
+                .ok_or_else(|| ClientError::invalid_response("Missing location header."))?,
+            status_code => {
+                log::info!("Unhandled response from {url}: {status_code}");
+                return Err(ClientError::invalid_response(&format!(
+                    "Unexpected status {status_code}."
+                )));
+            }
+        };
+        // Poll for result
+        let max_polls = self.retry_policy.max_polls();
+        for i in 1..=max_polls {
+            let result = self
+                .send_with_retry(&location_header_content, true, || {
+                    client.get(&location_header_content)
+                })
+                .await;
+            match Self::get_http_20x_response_body_as_string(result, &url).await {
+                Ok(Some(document)) => {
+                    self.notify_correlation_wait(publish_to_topic_id, start, true);
+                    return Ok(document);
                 }
-                _ => {
-                    log::info!("Unhandled response from {url}: {}", response.status());
-                    return None;
+                Ok(None) => {}
+                Err(e) if e.is_retryable() => {}
+                Err(e) => {
+                    self.notify_correlation_wait(publish_to_topic_id, start, false);
+                    return Err(e);
                 }
             }
-        }
-        if let Some(location_header_content) = location_header_content {
-            // Poll for result
-            for i in 0..10 {
-                let result = client.get(&location_header_content).send().await;
-                if let Some(document) =
-                    Self::get_http_20x_response_body_as_string(result, &url).await
-                {
-                    return Some(document);
-                }
-                // Back off before polling again
-                sleep(Duration::from_millis(1000)).await;
-                log::info!(
-                    "Failed to get any result for correlation_token on topic '{consume_from_topic_id}'. Retry {i}/10 pending."
-                );
+            if i == max_polls {
+                break;
             }
+            // Back off before polling again
+            let delay = self.retry_policy.poll_delay();
+            log::info!(
+                "Failed to get any result for correlation_token on topic '{consume_from_topic_id}'. Retry {i}/{max_polls} pending."
+            );
+            self.retry_policy.notify_retry(
+                &location_header_content,
+                i,
+                delay,
+                "correlated result not available yet",
+            );
+            sleep(delay).await;
         }
+        self.retry_policy.notify_give_up(
+            &location_header_content,
+            max_polls,
+            "correlated result not available",
+        );
         log::info!("Failed to get any result on topic '{consume_from_topic_id}'.");
-        None
+        self.notify_correlation_wait(publish_to_topic_id, start, false);
+        Err(ClientError::Timeout)
     }
 
-    /// Get the next available document from a topic.
-    pub async fn get_next_document(&self, topic_id: &str) -> Option<(String, String, String)> {
-        let client = self.client.clone();
-        let url = format!("{}/topics/{topic_id}/next?from=0", self.api_base_url);
-        let result_res = client
-            .get(&url)
+    /// Notify any metrics observer about the wait since `start` for a
+    /// correlated result.
+    fn notify_correlation_wait(&self, publish_to_topic_id: &str, start: Instant, found: bool) {
+        if let Some(metrics_observer) = &self.metrics_observer {
+            metrics_observer.on_correlation_wait(publish_to_topic_id, start.elapsed(), found);
+        }
+    }
+
+    /// Register `reply_topic_id` as the topic where correlated results of
+    /// events in `topic_id` are published.
+    ///
+    /// This allows publishers to await the correlated result of a request
+    /// without knowing the reply topic.
+    pub async fn register_reply_topic(
+        &self,
+        topic_id: &str,
+        reply_topic_id: &str,
+    ) -> Result<(), ClientError> {
+        let url = format!("{}/topics/{topic_id}/reply", self.api_base_url);
+        let res = self
+            .client
+            .clone()
+            .put(&url)
+            .body(ReplyTopic::new(reply_topic_id).as_string())
+            .header(&CONTENT_TYPE, Self::MIME_APPLICATION_JSON)
             .header(
                 &AUTHORIZATION,
                 self.bearer_token_cache
@@ -236,55 +408,133 @@ impl RestApiClient {
             )
             .send()
             .await;
+        Self::is_no_content(res, &url)
+    }
+
+    /// Get the next available document from a topic.
+    ///
+    /// Return the document, the confirmation link, the correlation token and
+    /// the consumer's prepared transaction identifier if this is a redelivery
+    /// of an event with a prepared, but not committed, confirmation.
+    ///
+    /// Return `None` if no event is available.
+    pub async fn get_next_document(
+        &self,
+        topic_id: &str,
+    ) -> Result<Option<(String, String, String, Option<String>)>, ClientError> {
+        let client = self.client.clone();
+        let url = format!("{}/topics/{topic_id}/next?from=0", self.api_base_url);
+        let result_res = self.send_with_retry(&url, true, || client.get(&url)).await;
         if result_res.is_err() {
             // Back off a little on network failures
-            sleep(Duration::from_millis(512)).await;
+            sleep(self.retry_policy.backoff(1)).await;
         }
-        let result_opt = result_res
-            .map_err(|e| {
-                log::info!("Failed request to {url}: {:?}", e.without_url());
-            })
-            .ok();
-        if let Some(response) = result_opt
-            && response.status() == StatusCode::OK
-        {
-            let confirmation_link =
-                Self::header_as_string(&response, "link").and_then(|header_value| {
-                    log::trace!("Link: {header_value}");
-                    header_value
-                        .split(';')
-                        .next()
-                        .map(|link_str| link_str.replace(['<', '>'], "").to_owned())
-                });
-            let correlation_token = Self::header_as_string(&response, "correlation-token");
-            let document = response
-                .text()
-                .await
-                .map_err(|e| {
-                    log::info!(
-                        "Failed request to {url}: Failed to parse response body as text: {:?}",
-                        e.without_url()
-                    );
-                })
-                .ok();
-            if let Some(confirmation_link) = confirmation_link
-                && let Some(document) = document
-                && let Some(correlation_token) = correlation_token
-            {
-                return Some((document, confirmation_link, correlation_token));
-            }
+        let response = Self::handle_response_err(result_res, &url)?;
+        if response.status() != StatusCode::OK {
+            return Ok(None);
         }
-        None
+        let confirmation_link =
+            Self::header_as_string(&response, "link").and_then(|header_value| {
+                log::trace!("Link: {header_value}");
+                header_value
+                    .split(';')
+                    .next()
+                    .map(|link_str| link_str.replace(['<', '>'], "").to_owned())
+            });
+        let correlation_token = Self::header_as_string(&response, "correlation-token");
+        let prepared_transaction_id = Self::header_as_string(&response, "prepared-transaction-id");
+        let document = Self::response_body_as_string(response, &url).await?;
+        let confirmation_link = confirmation_link
+            .ok_or_else(|| ClientError::invalid_response("Missing link header."))?;
+        let correlation_token = correlation_token
+            .ok_or_else(|| ClientError::invalid_response("Missing correlation-token header."))?;
+        Ok(Some((
+            document,
+            confirmation_link,
+            correlation_token,
+            prepared_transaction_id,
+        )))
     }
 
     /// Confirm event delivery.
-    pub async fn confirm_delivery(&self, url: &str) {
+    pub async fn confirm_delivery(&self, url: &str) -> Result<(), ClientError> {
         if log::log_enabled!(log::Level::Trace) {
             log::trace!("Confirming delivery with PUT '{url}'.");
         }
-        self.client
+        let client = self.client.clone();
+        let result = self.send_with_retry(url, true, || client.put(url)).await;
+        Self::handle_response_err(result, url).map(|_response| ())
+    }
+
+    /// Prepare the confirmation of an event delivery by recording the
+    /// consumer's transaction identifier in an external sink.
+    ///
+    /// `url` is the confirmation link of the delivered event.
+    pub async fn prepare_delivery(
+        &self,
+        url: &str,
+        transaction_id: &str,
+    ) -> Result<(), ClientError> {
+        let url = format!("{url}/prepare");
+        let res = self
+            .client
+            .clone()
+            .put(&url)
+            .body(DeliveryPreparation::new(transaction_id).as_string())
+            .header(&CONTENT_TYPE, Self::MIME_APPLICATION_JSON)
+            .header(
+                &AUTHORIZATION,
+                self.bearer_token_cache
+                    .current_as_header_value()
+                    .await
+                    .as_str(),
+            )
+            .send()
+            .await;
+        Self::is_no_content(res, &url)
+    }
+
+    /// Commit a previously prepared confirmation of an event delivery.
+    ///
+    /// `url` is the confirmation link of the delivered event.
+    pub async fn commit_delivery(&self, url: &str) -> Result<(), ClientError> {
+        let url = format!("{url}/commit");
+        let res = self
+            .client
+            .clone()
+            .put(&url)
+            .header(
+                &AUTHORIZATION,
+                self.bearer_token_cache
+                    .current_as_header_value()
+                    .await
+                    .as_str(),
+            )
+            .send()
+            .await;
+        Self::is_no_content(res, &url)
+    }
+
+    /// Commit the consumer's position in the topic.
+    ///
+    /// All events up to and including `unique_time` will be considered
+    /// delivered to the consumer.
+    pub async fn commit_consumer_position(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: u64,
+    ) -> Result<(), ClientError> {
+        let url = format!(
+            "{}/topics/{}/consumers/{}/position",
+            self.api_base_url, topic_id, consumer_id
+        );
+        let res = self
+            .client
             .clone()
-            .put(url)
+            .put(&url)
+            .body(ConsumerPosition::new(unique_time).as_string())
+            .header(&CONTENT_TYPE, Self::MIME_APPLICATION_JSON)
             .header(
                 &AUTHORIZATION,
                 self.bearer_token_cache
@@ -293,19 +543,158 @@ impl RestApiClient {
                     .as_str(),
             )
             .send()
+            .await;
+        Self::is_no_content(res, &url)
+    }
+
+    /// Move the consumer's position in the topic backwards to replay history
+    /// or forward to skip a backlog.
+    pub async fn seek_consumer_position(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        consumer_seek: &ConsumerSeek,
+    ) -> Result<(), ClientError> {
+        let url = format!(
+            "{}/topics/{}/consumers/{}/seek",
+            self.api_base_url, topic_id, consumer_id
+        );
+        let res = self
+            .client
+            .clone()
+            .post(&url)
+            .body(consumer_seek.as_string())
+            .header(&CONTENT_TYPE, Self::MIME_APPLICATION_JSON)
+            .header(
+                &AUTHORIZATION,
+                self.bearer_token_cache
+                    .current_as_header_value()
+                    .await
+                    .as_str(),
+            )
+            .send()
+            .await;
+        Self::is_no_content(res, &url)
+    }
+
+    /// Send the request from `request_builder` with authorization and retry
+    /// transient failures according to the [RetryPolicy].
+    ///
+    /// Requests that are not `idempotent` are only retried if they never
+    /// reached the server, unless the policy allows it.
+    async fn send_with_retry<F>(
+        &self,
+        url: &str,
+        idempotent: bool,
+        request_builder: F,
+    ) -> Result<Response, Error>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        self.send_with_retry_internal(url, idempotent, None, request_builder)
             .await
-            .map_err(|e| {
-                log::info!("Failed request to {url}: {:?}", e.without_url());
-            })
-            .ok();
+    }
+
+    /// Send the publish request from `request_builder` like
+    /// [Self::send_with_retry] and notify any metrics observer about retries.
+    async fn send_publish_with_retry<F>(
+        &self,
+        publish_to_topic_id: &str,
+        url: &str,
+        request_builder: F,
+    ) -> Result<Response, Error>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        self.send_with_retry_internal(url, false, Some(publish_to_topic_id), request_builder)
+            .await
+    }
+
+    /// See [Self::send_with_retry].
+    async fn send_with_retry_internal<F>(
+        &self,
+        url: &str,
+        idempotent: bool,
+        publish_to_topic_id: Option<&str>,
+        request_builder: F,
+    ) -> Result<Response, Error>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let mut attempt = 0;
+        loop {
+            let result = request_builder()
+                .header(
+                    &AUTHORIZATION,
+                    self.bearer_token_cache
+                        .current_as_header_value()
+                        .await
+                        .as_str(),
+                )
+                .send()
+                .await;
+            let (reached_server, reason, retry_after) = match &result {
+                Ok(response) if Self::is_transient_status(response.status()) => (
+                    true,
+                    format!("status_code {}", response.status()),
+                    ClientError::retry_after_of(response),
+                ),
+                Ok(_) => return result,
+                Err(e) => (!e.is_connect(), e.to_string(), None),
+            };
+            if attempt >= self.retry_policy.max_retries()
+                || !self.retry_policy.is_retryable(idempotent, reached_server)
+            {
+                if attempt > 0 {
+                    self.retry_policy.notify_give_up(url, attempt + 1, &reason);
+                }
+                return result;
+            }
+            attempt += 1;
+            let delay = self.retry_policy.retry_delay(attempt, retry_after);
+            self.retry_policy.notify_retry(url, attempt, delay, &reason);
+            if let Some(metrics_observer) = &self.metrics_observer
+                && let Some(publish_to_topic_id) = publish_to_topic_id
+            {
+                metrics_observer.on_publish_retry(publish_to_topic_id, attempt);
+            }
+            sleep(delay).await;
+        }
+    }
+
+    /// Return `true` if the HTTP status code indicates a temporary condition
+    /// where the request could succeed later.
+    fn is_transient_status(status_code: StatusCode) -> bool {
+        matches!(
+            status_code,
+            StatusCode::TOO_MANY_REQUESTS
+                | StatusCode::BAD_GATEWAY
+                | StatusCode::SERVICE_UNAVAILABLE
+                | StatusCode::GATEWAY_TIMEOUT
+        )
+    }
+
+    /// Return `Ok` if the request was successful with an empty response.
+    fn is_no_content(res: Result<Response, Error>, url: &str) -> Result<(), ClientError> {
+        match Self::handle_response_err(res, url)?.status() {
+            StatusCode::NO_CONTENT => Ok(()),
+            status_code => {
+                log::info!("Failed request to {url}: status_code {status_code}.");
+                Err(ClientError::invalid_response(&format!(
+                    "Unexpected status {status_code}."
+                )))
+            }
+        }
     }
 
     /// Query topic for an event document with the specified event identifier.
+    ///
+    /// Return [ClientError::NotFound] if no such event exists.
     pub async fn event_by_topic_and_event_id(
         &self,
         topic_id: &str,
         event_id: &str,
-    ) -> Option<String> {
+    ) -> Result<String, ClientError> {
         let client = self.client.clone();
         let url = format!(
             "{}/topics/{topic_id}/events/by_event_id/{event_id}",
@@ -322,7 +711,9 @@ impl RestApiClient {
             )
             .send()
             .await;
-        Self::get_http_20x_response_body_as_string(result, &url).await
+        Self::get_http_20x_response_body_as_string(result, &url)
+            .await?
+            .ok_or(ClientError::NotFound)
     }
 
     /// Get all event identifiers where `index_name` exactly has `index_key`
@@ -332,7 +723,7 @@ impl RestApiClient {
         topic_id: &str,
         index_name: &str,
         index_key: &str,
-    ) -> Vec<String> {
+    ) -> Result<Vec<String>, ClientError> {
         let client = self.client.clone();
         let url = format!(
             "{}/topics/{topic_id}/events/ids_by_index/{index_name}/{index_key}",
@@ -349,43 +740,48 @@ impl RestApiClient {
             )
             .send()
             .await;
-        Self::get_http_20x_response_body_as_string(result, &url)
-            .await
-            .and_then(|content| {
-                serde_json::from_str(&content)
-                    .map_err(|e| {
-                        log::info!("Failed to parse JSON response from '{url}': {e:?}");
-                    })
-                    .ok()
-            })
-            .unwrap_or_default()
+        match Self::get_http_20x_response_body_as_string(result, &url).await? {
+            Some(content) => Self::parse_json(&content, &url),
+            None => Ok(Vec::default()),
+        }
     }
 
-    /// Return reposonse body as text when present if HTTP status code is 200 or 201.
+    /// Return reposonse body as text if HTTP status code is 200 or `None` if
+    /// the HTTP status code is 204.
     async fn get_http_20x_response_body_as_string(
         result: Result<Response, Error>,
         url: &str,
-    ) -> Option<String> {
-        match Self::handle_response_err(result, url).map(|response| (response.status(), response)) {
-            Some((StatusCode::OK, response)) => {
-                return response
-                    .text()
-                    .await
-                    .map_err(|e| {
-                        log::info!(
-                            "Failed request to '{url}': Failed to parse response body as text: {:?}",
-                            e.without_url()
-                        );
-                    })
-                    .ok();
-            }
-            Some((StatusCode::NO_CONTENT, _)) => (),
-            Some((status_code, _)) => {
+    ) -> Result<Option<String>, ClientError> {
+        let response = Self::handle_response_err(result, url)?;
+        match response.status() {
+            StatusCode::OK => Self::response_body_as_string(response, url).await.map(Some),
+            StatusCode::NO_CONTENT => Ok(None),
+            status_code => {
                 log::info!("Failed request to {url}: status_code {status_code}.");
+                Err(ClientError::invalid_response(&format!(
+                    "Unexpected status {status_code}."
+                )))
             }
-            _ => (),
         }
-        None
+    }
+
+    /// Return the response body as text.
+    async fn response_body_as_string(response: Response, url: &str) -> Result<String, ClientError> {
+        response.text().await.map_err(|e| {
+            let client_error = ClientError::from_transport(e);
+            log::info!(
+                "Failed request to '{url}': Failed to parse response body as text: {client_error}"
+            );
+            client_error
+        })
+    }
+
+    /// Parse JSON response body.
+    fn parse_json<T: DeserializeOwned>(content: &str, url: &str) -> Result<T, ClientError> {
+        serde_json::from_str(content).map_err(|e| {
+            log::info!("Failed to parse JSON response from '{url}': {e:?}");
+            ClientError::invalid_response(&e.to_string())
+        })
     }
 
     fn header_as_string(response: &Response, header_key: &str) -> Option<String> {
@@ -395,12 +791,29 @@ impl RestApiClient {
             .map(|header_value| header_value.to_str().unwrap_or("").to_owned())
     }
 
-    /// Log any error and return the response if present
-    fn handle_response_err(result: Result<Response, Error>, url: &str) -> Option<Response> {
-        result
-            .map_err(|e| {
-                log::info!("Failed request to '{url}': {:?}", e.without_url());
-            })
-            .ok()
+    /// Log any error and return the response if the request was successful.
+    fn handle_response_err(
+        result: Result<Response, Error>,
+        url: &str,
+    ) -> Result<Response, ClientError> {
+        match result {
+            Ok(response)
+                if response.status().is_success() || response.status().is_redirection() =>
+            {
+                Ok(response)
+            }
+            Ok(response) => {
+                log::info!(
+                    "Failed request to {url}: status_code {}.",
+                    response.status()
+                );
+                Err(ClientError::from_response(&response))
+            }
+            Err(e) => {
+                let client_error = ClientError::from_transport(e);
+                log::info!("Failed request to '{url}': {client_error}");
+                Err(client_error)
+            }
+        }
     }
 }
@@ -0,0 +1,211 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Typed errors of the REST API client.
+
+use reqwest::Response;
+use reqwest::StatusCode;
+use reqwest::header::RETRY_AFTER;
+use std::time::Duration;
+
+/// Failure of a request made by the REST API client.
+///
+/// Use [Self::is_retryable] and [Self::retry_after] to decide if and when a
+/// failed request should be attempted again, instead of treating every
+/// failure identically.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientError {
+    /// Authentication (HTTP 401) or authorization (HTTP 403) failure.
+    Auth {
+        /// HTTP status code of the response.
+        status_code: u16,
+    },
+    /// The requested resource was not found (HTTP 404).
+    NotFound,
+    /// The request conflicts with the current state of the resource (HTTP
+    /// 409).
+    Conflict,
+    /// Too many requests (HTTP 429).
+    RateLimited {
+        /// Time to wait before trying again as hinted by the server.
+        retry_after: Option<Duration>,
+    },
+    /// The request was rejected by the server (any other HTTP 4xx), e.g. due
+    /// to a malformed request or a document that failed validation.
+    Rejected {
+        /// HTTP status code of the response.
+        status_code: u16,
+    },
+    /// The server failed to process the request (HTTP 5xx).
+    Server {
+        /// HTTP status code of the response.
+        status_code: u16,
+        /// Time to wait before trying again as hinted by the server.
+        retry_after: Option<Duration>,
+    },
+    /// The request never completed, e.g. due to a connection failure or a
+    /// request timeout.
+    Transport {
+        /// Description of the failure.
+        msg: String,
+    },
+    /// No outcome of a correlated request was available before the polling
+    /// budget of the [crate::RetryPolicy] was exhausted.
+    Timeout,
+    /// The response could not be interpreted, e.g. due to a missing header
+    /// or a malformed body.
+    InvalidResponse {
+        /// Description of the failure.
+        msg: String,
+    },
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auth { status_code } => {
+                write!(f, "Authentication failure (status {status_code}).")
+            }
+            Self::NotFound => write!(f, "Not found."),
+            Self::Conflict => write!(f, "Conflict."),
+            Self::RateLimited {
+                retry_after: Some(retry_after),
+            } => write!(f, "Rate limited. Retry after {retry_after:?}."),
+            Self::RateLimited { retry_after: None } => write!(f, "Rate limited."),
+            Self::Rejected { status_code } => write!(f, "Request rejected (status {status_code})."),
+            Self::Server { status_code, .. } => write!(f, "Server failure (status {status_code})."),
+            Self::Transport { msg } => write!(f, "Transport failure: {msg}"),
+            Self::Timeout => write!(f, "Timed out waiting for outcome."),
+            Self::InvalidResponse { msg } => write!(f, "Invalid response: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl ClientError {
+    /// Return a new instance from the HTTP status code of a failed request.
+    pub(crate) fn from_status_code(status_code: StatusCode, retry_after: Option<Duration>) -> Self {
+        match status_code {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth {
+                status_code: status_code.as_u16(),
+            },
+            StatusCode::NOT_FOUND => Self::NotFound,
+            StatusCode::CONFLICT => Self::Conflict,
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimited { retry_after },
+            status_code if status_code.is_server_error() => Self::Server {
+                status_code: status_code.as_u16(),
+                retry_after,
+            },
+            status_code if status_code.is_client_error() => Self::Rejected {
+                status_code: status_code.as_u16(),
+            },
+            status_code => Self::InvalidResponse {
+                msg: format!("Unexpected status {status_code}."),
+            },
+        }
+    }
+
+    /// Return a new instance from the response of a failed request.
+    pub(crate) fn from_response(response: &Response) -> Self {
+        Self::from_status_code(response.status(), Self::retry_after_of(response))
+    }
+
+    /// Return a new instance from a request that never completed.
+    pub(crate) fn from_transport(e: reqwest::Error) -> Self {
+        Self::Transport {
+            msg: e.without_url().to_string(),
+        }
+    }
+
+    /// Return a new instance for a response that could not be interpreted.
+    pub(crate) fn invalid_response(msg: &str) -> Self {
+        Self::InvalidResponse {
+            msg: msg.to_owned(),
+        }
+    }
+
+    /// Return the server's hint (if any) about when to try again.
+    pub(crate) fn retry_after_of(response: &Response) -> Option<Duration> {
+        response
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|header_value| header_value.to_str().ok())
+            .and_then(Self::parse_retry_after)
+    }
+
+    /// Parse the value of a `Retry-After` header in delay-seconds format.
+    ///
+    /// The HTTP-date format is not supported.
+    fn parse_retry_after(value: &str) -> Option<Duration> {
+        value.trim().parse::<u64>().ok().map(Duration::from_secs)
+    }
+
+    /// Return `true` if the same request could succeed when attempted later.
+    ///
+    /// Note that a request that is not idempotent (like publishing an event)
+    /// might have reached the server before the failure.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::RateLimited { .. } | Self::Server { .. } | Self::Transport { .. } | Self::Timeout
+        )
+    }
+
+    /// Time to wait before trying again as hinted by the server (if any).
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after } | Self::Server { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_codes_map_to_typed_errors() {
+        assert_eq!(
+            ClientError::from_status_code(StatusCode::FORBIDDEN, None),
+            ClientError::Auth { status_code: 403 }
+        );
+        assert_eq!(
+            ClientError::from_status_code(StatusCode::NOT_FOUND, None),
+            ClientError::NotFound
+        );
+        assert_eq!(
+            ClientError::from_status_code(StatusCode::BAD_REQUEST, None),
+            ClientError::Rejected { status_code: 400 }
+        );
+        let rate_limited = ClientError::from_status_code(
+            StatusCode::TOO_MANY_REQUESTS,
+            ClientError::parse_retry_after(" 3"),
+        );
+        assert!(rate_limited.is_retryable());
+        assert_eq!(rate_limited.retry_after(), Some(Duration::from_secs(3)));
+        let server = ClientError::from_status_code(StatusCode::SERVICE_UNAVAILABLE, None);
+        assert!(server.is_retryable());
+        assert_eq!(server.retry_after(), None);
+        assert!(!ClientError::Conflict.is_retryable());
+        assert_eq!(
+            ClientError::parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            None
+        );
+    }
+}
@@ -0,0 +1,273 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Retry and timeout policy of the REST API client.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tyst::Tyst;
+
+/// Observer of retries made by the REST API client.
+///
+/// Useful for exposing client side metrics or logging on the caller's terms.
+pub trait RetryObserver: Send + Sync {
+    /// Invoked before a failed request to `url` is retried after `delay`.
+    ///
+    /// `attempt` is the number of the upcoming retry starting from `1`.
+    fn on_retry(&self, url: &str, attempt: u32, delay: Duration, reason: &str);
+
+    /// Invoked when no more attempts will be made to `url` after `attempts`
+    /// failed attempts.
+    fn on_give_up(&self, url: &str, attempts: u32, reason: &str) {
+        let _ = (url, attempts, reason);
+    }
+}
+
+/// Retry and timeout policy of the REST API client.
+///
+/// Requests that are not idempotent (like publishing an event) are only
+/// retried when the connection could not be established, unless
+/// [Self::with_retry_non_idempotent] is enabled.
+///
+/// Example:
+///
+/// ```
+/// use fragtale_client::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let retry_policy = RetryPolicy::default()
+///     .with_request_timeout(Duration::from_secs(30))
+///     .with_max_retries(5)
+///     .with_backoff(Duration::from_millis(100), Duration::from_secs(2));
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    connect_timeout: Duration,
+    request_timeout: Duration,
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_non_idempotent: bool,
+    max_polls: u32,
+    poll_interval: Duration,
+    observer: Option<Arc<dyn RetryObserver>>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(5),
+            request_timeout: Duration::from_secs(10),
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(256),
+            max_backoff: Duration::from_secs(4),
+            jitter: true,
+            retry_non_idempotent: false,
+            max_polls: 10,
+            poll_interval: Duration::from_secs(1),
+            observer: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("connect_timeout", &self.connect_timeout)
+            .field("request_timeout", &self.request_timeout)
+            .field("max_retries", &self.max_retries)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("jitter", &self.jitter)
+            .field("retry_non_idempotent", &self.retry_non_idempotent)
+            .field("max_polls", &self.max_polls)
+            .field("poll_interval", &self.poll_interval)
+            .field("observer", &self.observer.is_some())
+            .finish()
+    }
+}
+
+impl RetryPolicy {
+    /// Set the max time to wait for a connection to be established.
+    pub fn with_connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Set the max time to wait for a single request to complete.
+    pub fn with_request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Set the max number of retries of a failed request.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the exponential backoff between retries, starting at `initial` and
+    /// doubling for each retry up to `max`.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = std::cmp::max(initial, max);
+        self
+    }
+
+    /// Enable or disable randomization of the backoff to avoid that many
+    /// clients retry in lockstep.
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Allow retries of requests that are not idempotent after the request
+    /// might have reached the server.
+    ///
+    /// This can lead to duplicate events unless the topic rejects or ignores
+    /// colliding event identifiers.
+    pub fn with_retry_non_idempotent(mut self, retry_non_idempotent: bool) -> Self {
+        self.retry_non_idempotent = retry_non_idempotent;
+        self
+    }
+
+    /// Set how many times and how often the outcome of a correlated request is
+    /// polled for.
+    pub fn with_polling(mut self, max_polls: u32, poll_interval: Duration) -> Self {
+        self.max_polls = max_polls;
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Set an observer that is notified on retries.
+    pub fn with_observer(mut self, observer: Arc<dyn RetryObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Max time to wait for a connection to be established.
+    pub fn connect_timeout(&self) -> Duration {
+        self.connect_timeout
+    }
+
+    /// Max time to wait for a single request to complete.
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
+    /// Max number of retries of a failed request.
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Max number of times the outcome of a correlated request is polled for.
+    pub fn max_polls(&self) -> u32 {
+        self.max_polls
+    }
+
+    /// Return `true` if a failed request should be retried.
+    pub(crate) fn is_retryable(&self, idempotent: bool, reached_server: bool) -> bool {
+        idempotent || !reached_server || self.retry_non_idempotent
+    }
+
+    /// Delay before the retry following `attempt` failed attempts.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_backoff);
+        self.apply_jitter(delay)
+    }
+
+    /// Delay before the retry following `attempt` failed attempts when the
+    /// server hinted when to try again.
+    ///
+    /// The hint is respected up to the max backoff.
+    pub(crate) fn retry_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let delay = self.backoff(attempt);
+        retry_after.map_or(delay, |retry_after| {
+            std::cmp::max(delay, std::cmp::min(retry_after, self.max_backoff))
+        })
+    }
+
+    /// Delay between polls for the outcome of a correlated request.
+    pub fn poll_delay(&self) -> Duration {
+        self.apply_jitter(self.poll_interval)
+    }
+
+    /// Randomize the delay to between half and the full `delay` when jitter
+    /// is enabled.
+    fn apply_jitter(&self, delay: Duration) -> Duration {
+        if !self.jitter || delay.is_zero() {
+            return delay;
+        }
+        let mut random = [0u8; 8];
+        Tyst::instance().prng_fill_with_random(None, &mut random);
+        let half_micros = u64::try_from(delay.as_micros() / 2).unwrap_or(u64::MAX / 2);
+        Duration::from_micros(half_micros + u64::from_be_bytes(random) % (half_micros + 1))
+    }
+
+    /// Notify any observer about an upcoming retry.
+    pub(crate) fn notify_retry(&self, url: &str, attempt: u32, delay: Duration, reason: &str) {
+        log::debug!(
+            "Retry {attempt}/{} of '{url}' in {delay:?}: {reason}",
+            self.max_retries
+        );
+        if let Some(observer) = &self.observer {
+            observer.on_retry(url, attempt, delay, reason);
+        }
+    }
+
+    /// Notify any observer that no more attempts will be made.
+    pub(crate) fn notify_give_up(&self, url: &str, attempts: u32, reason: &str) {
+        if let Some(observer) = &self.observer {
+            observer.on_give_up(url, attempts, reason);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let retry_policy = RetryPolicy::default()
+            .with_jitter(false)
+            .with_backoff(Duration::from_millis(100), Duration::from_millis(500));
+        assert_eq!(retry_policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(retry_policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(retry_policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(retry_policy.backoff(4), Duration::from_millis(500));
+        assert_eq!(retry_policy.backoff(64), Duration::from_millis(500));
+        assert_eq!(
+            retry_policy.retry_delay(1, Some(Duration::from_millis(300))),
+            Duration::from_millis(300)
+        );
+        assert_eq!(
+            retry_policy.retry_delay(1, Some(Duration::from_secs(60))),
+            Duration::from_millis(500)
+        );
+        let retry_policy = retry_policy.with_jitter(true);
+        for attempt in 1..10 {
+            let delay = retry_policy.backoff(attempt);
+            assert!(delay >= Duration::from_millis(50));
+            assert!(delay <= Duration::from_millis(500));
+        }
+    }
+}
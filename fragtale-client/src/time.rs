@@ -27,3 +27,9 @@ pub fn get_timestamp_micros() -> u64 {
     )
     .unwrap()
 }
+
+/// Microseconds since UNIX epoch of an encoded unique time (as delivered with
+/// each event).
+pub fn micros_from_encoded_unique_time(encoded_unique_time: u64) -> u64 {
+    (encoded_unique_time >> 10) & 0x001f_ffff_ffff_ffff
+}
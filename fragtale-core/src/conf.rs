@@ -18,10 +18,23 @@
 //! Parsing of application configuration.
 
 mod api_config;
+mod archive_config;
 mod backend_config;
+mod backpressure_config;
+mod cache_config;
+mod canary_config;
+mod delivery_config;
+mod deployment_config;
+mod descriptor_config;
+mod dev_config;
+mod diagnostics_config;
 pub mod integrity_config;
 mod limits_config;
 mod metrics_config;
+mod mirror_config;
+mod tenancy_config;
+mod topics_config;
+mod warmup_config;
 
 use config::Config;
 use config::ConfigBuilder;
@@ -32,10 +45,23 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use self::api_config::ApiConfig;
+use self::archive_config::ArchiveConfig;
 use self::backend_config::BackendConfig;
+use self::backpressure_config::BackpressureConfig;
+use self::cache_config::CacheConfig;
+use self::canary_config::CanaryConfig;
+use self::delivery_config::DeliveryConfig;
+use self::deployment_config::DeploymentConfig;
+use self::descriptor_config::DescriptorConfig;
+use self::dev_config::DevConfig;
+use self::diagnostics_config::DiagnosticsConfig;
 use self::integrity_config::IntegrityConfig;
 use self::limits_config::ResourceLimitsConfig;
 use self::metrics_config::MetricsConfig;
+use self::mirror_config::MirrorConfig;
+use self::tenancy_config::TenancyConfig;
+use self::topics_config::TopicsConfig;
+use self::warmup_config::WarmupConfig;
 
 /// Package name reported by Cargo at build time.
 const CARGO_PKG_NAME: &str = env!("CARGO_PKG_NAME");
@@ -66,14 +92,40 @@ Configuration will be loaded from
 pub struct AppConfig {
     /// Configuration of the exposed REST API.
     pub api: ApiConfig,
+    /// Configuration for the archive tier of events.
+    pub archive: ArchiveConfig,
     /// Configuration for persistence backend.
     pub backend: BackendConfig,
+    /// Configuration for signalling topic backlogs to producers.
+    pub backpressure: BackpressureConfig,
+    /// Configuration for in-process caching of read-mostly queries.
+    pub cache: CacheConfig,
+    /// Configuration for synthetic canary events.
+    pub canary: CanaryConfig,
+    /// Configuration for delivery of events to consumers.
+    pub delivery: DeliveryConfig,
+    /// Configuration for the role of instances in a deployment.
+    pub deployment: DeploymentConfig,
+    /// Configuration for limits of topic event descriptors.
+    pub descriptor: DescriptorConfig,
+    /// Configuration for local development.
+    pub dev: DevConfig,
+    /// Configuration for emergency diagnostics.
+    pub diagnostics: DiagnosticsConfig,
     /// Configuration for integrity protection of data at rest.
     pub integrity: IntegrityConfig,
     /// Resource detection and configuration overrides.
     pub limits: ResourceLimitsConfig,
     /// Configuration for the application's  metrics collection.
     pub metrics: MetricsConfig,
+    /// Configuration for mirroring of published events to local files.
+    pub mirror: MirrorConfig,
+    /// Configuration for isolation of tenants.
+    pub tenancy: TenancyConfig,
+    /// Configuration for creation of topics.
+    pub topics: TopicsConfig,
+    /// Configuration for warm-up of hot topics and consumers during startup.
+    pub warmup: WarmupConfig,
 
     /// Lower case application name. Ignored when loading configuration.
     #[serde(skip_deserializing)]
@@ -81,6 +133,9 @@ pub struct AppConfig {
     /// Time of application startup in epoch microseconds
     #[serde(skip_deserializing)]
     startup_ts_micros: u64,
+    /// `true` if the application was built with tracing enabled.
+    #[serde(skip_deserializing)]
+    tracing_enabled: bool,
 }
 
 impl Default for AppConfig {
@@ -121,6 +176,31 @@ impl AppConfig {
         self.startup_ts_micros
     }
 
+    /// Return this instance with tracing reported as enabled or disabled.
+    ///
+    /// Tracing is a build time feature of the application binary.
+    pub fn with_tracing_enabled(mut self, enabled: bool) -> Self {
+        self.tracing_enabled = enabled;
+        self
+    }
+
+    /// Return this instance in developer mode, optionally seeded from the
+    /// `fixtures_file`.
+    ///
+    /// Developer mode always uses the in-memory backend, so no database is
+    /// required to run the broker locally.
+    pub fn with_dev_mode(mut self, fixtures_file: Option<&str>) -> Self {
+        self.dev.enable(fixtures_file);
+        self.backend.use_in_memory();
+        log::warn!("Running in developer mode. Never use this in production!");
+        self
+    }
+
+    /// `true` if the application was built with tracing enabled.
+    pub fn is_tracing_enabled(&self) -> bool {
+        self.tracing_enabled
+    }
+
     /** Creates a new instance pre-populated with defaults, an optional
     configurations file and environment variable overrides.
 
@@ -132,10 +212,23 @@ impl AppConfig {
         let config_env_prefix = &app_name.to_uppercase();
         let mut config_builder = Config::builder();
         config_builder = ApiConfig::set_defaults(config_builder, "api");
+        config_builder = ArchiveConfig::set_defaults(config_builder, "archive");
         config_builder = BackendConfig::set_defaults(config_builder, "backend");
+        config_builder = BackpressureConfig::set_defaults(config_builder, "backpressure");
+        config_builder = CacheConfig::set_defaults(config_builder, "cache");
+        config_builder = CanaryConfig::set_defaults(config_builder, "canary");
+        config_builder = DeliveryConfig::set_defaults(config_builder, "delivery");
+        config_builder = DeploymentConfig::set_defaults(config_builder, "deployment");
+        config_builder = DescriptorConfig::set_defaults(config_builder, "descriptor");
+        config_builder = DevConfig::set_defaults(config_builder, "dev");
+        config_builder = DiagnosticsConfig::set_defaults(config_builder, "diagnostics");
         config_builder = IntegrityConfig::set_defaults(config_builder, "integrity");
         config_builder = ResourceLimitsConfig::set_defaults(config_builder, "limits");
         config_builder = MetricsConfig::set_defaults(config_builder, "metrics");
+        config_builder = MirrorConfig::set_defaults(config_builder, "mirror");
+        config_builder = TenancyConfig::set_defaults(config_builder, "tenancy");
+        config_builder = TopicsConfig::set_defaults(config_builder, "topics");
+        config_builder = WarmupConfig::set_defaults(config_builder, "warmup");
         let conf_file = std::env::current_dir().unwrap().join(config_filename);
         if log::log_enabled!(log::Level::Debug) {
             log::debug!(
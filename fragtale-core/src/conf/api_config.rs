@@ -32,6 +32,42 @@ pub struct ApiConfig {
     port: u16,
     /// See [Self::audience()].
     audience: String,
+    /// See [Self::oidc_issuers()].
+    oidcissuers: String,
+    /// See [Self::oidc_trust_anchors_file()].
+    oidctrustanchors: String,
+    /// See [Self::authentication()].
+    authentication: String,
+    /// See [Self::tls_certificate_file()].
+    tlscert: String,
+    /// See [Self::tls_key_file()].
+    tlskey: String,
+    /// See [Self::tls_client_trust_anchors_file()].
+    tlsclientcas: String,
+    /// Default WebSocket ping interval in milliseconds.
+    pinginterval: u64,
+    /// Lowest WebSocket ping interval in milliseconds a client may request.
+    pingintervalmin: u64,
+    /// Highest WebSocket ping interval in milliseconds a client may request.
+    pingintervalmax: u64,
+    /// Default tolerated WebSocket ping delay in milliseconds.
+    pingtolerance: u64,
+    /// Highest tolerated WebSocket ping delay in milliseconds a client may
+    /// request.
+    pingtolerancemax: u64,
+    /// Max number of open WebSocket sessions of a single identity on this
+    /// instance. `0` means unlimited.
+    maxsessionsperidentity: usize,
+    /// Time in milliseconds without any message from the client before a
+    /// WebSocket session is closed. `0` disables the timeout.
+    sessionidletimeout: u64,
+    /// See [Self::ui_enabled()].
+    ui: bool,
+    /// Default time in milliseconds a request may take when the client did
+    /// not request a timeout. `0` means that only the maximum applies.
+    requesttimeout: u64,
+    /// Highest time in milliseconds a request may take. `0` means unlimited.
+    requesttimeoutmax: u64,
 }
 
 impl AppConfigDefaults for ApiConfig {
@@ -47,6 +83,38 @@ impl AppConfigDefaults for ApiConfig {
             .unwrap()
             .set_default(prefix.to_string() + "." + "audience", "fragtale")
             .unwrap()
+            .set_default(prefix.to_string() + "." + "oidcissuers", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "oidctrustanchors", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "authentication", "bearer")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "tlscert", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "tlskey", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "tlsclientcas", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "pinginterval", "5000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "pingintervalmin", "1000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "pingintervalmax", "60000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "pingtolerance", "1000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "pingtolerancemax", "30000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "maxsessionsperidentity", "0")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "sessionidletimeout", "300000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "ui", "false")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "requesttimeout", "30000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "requesttimeoutmax", "300000")
+            .unwrap()
     }
 }
 
@@ -65,4 +133,242 @@ impl ApiConfig {
     pub fn audience(&self) -> &str {
         &self.audience
     }
+
+    /// Issuers of bearer tokens that are validated using OpenID Connect
+    /// discovery in addition to the Kubernetes cluster (if any).
+    ///
+    /// Configured as a comma separated list of issuer URLs (e.g.
+    /// `https://idp.example.com/realms/demo`). None by default.
+    pub fn oidc_issuers(&self) -> Vec<&str> {
+        self.oidcissuers
+            .split(',')
+            .map(str::trim)
+            .filter(|issuer| !issuer.is_empty())
+            .collect()
+    }
+
+    /// PEM file with the trust anchors of the OpenID Connect issuers' HTTPS
+    /// endpoints or `None` if not configured.
+    pub fn oidc_trust_anchors_file(&self) -> Option<&str> {
+        Some(self.oidctrustanchors.as_str()).filter(|filename| !filename.is_empty())
+    }
+
+    /// Accepted ways for API clients to authenticate. Defaults to `bearer`.
+    ///
+    /// * `bearer`: Only accept bearer tokens.
+    /// * `mtls`: Require a client certificate during the TLS handshake and
+    ///   derive the client identity from the certificate subject.
+    /// * `any`: Use the client certificate when presented and fall back to
+    ///   bearer tokens otherwise.
+    ///
+    /// Client certificates require the API to be served over TLS. See
+    /// [Self::tls_certificate_file()].
+    pub fn authentication(&self) -> &str {
+        &self.authentication
+    }
+
+    /// PEM file with the certificate chain of the API's TLS server
+    /// certificate or `None` if not configured.
+    pub fn tls_certificate_file(&self) -> Option<&str> {
+        Some(self.tlscert.as_str()).filter(|filename| !filename.is_empty())
+    }
+
+    /// PEM file with the private key of the API's TLS server certificate or
+    /// `None` if not configured.
+    pub fn tls_key_file(&self) -> Option<&str> {
+        Some(self.tlskey.as_str()).filter(|filename| !filename.is_empty())
+    }
+
+    /// PEM file with the trust anchors of accepted client certificates or
+    /// `None` if not configured.
+    pub fn tls_client_trust_anchors_file(&self) -> Option<&str> {
+        Some(self.tlsclientcas.as_str()).filter(|filename| !filename.is_empty())
+    }
+
+    /// Max number of open WebSocket sessions of a single identity on this
+    /// instance or `None` if unlimited. Unlimited by default.
+    pub fn max_sessions_per_identity(&self) -> Option<usize> {
+        (self.maxsessionsperidentity > 0).then_some(self.maxsessionsperidentity)
+    }
+
+    /// Time in microseconds without any message from the client before a
+    /// WebSocket session is closed or `None` if disabled. Defaults to 5
+    /// minutes.
+    pub fn session_idle_timeout_micros(&self) -> Option<u64> {
+        (self.sessionidletimeout > 0).then_some(self.sessionidletimeout * 1000)
+    }
+
+    /// Serve the embedded operator web UI under `/ui`. Disabled by default.
+    pub fn ui_enabled(&self) -> bool {
+        self.ui
+    }
+
+    /// Negotiate the time in microseconds a request may take from the
+    /// timeout requested by the client in milliseconds.
+    ///
+    /// The requested value is capped by the configured maximum and the
+    /// configured default is used when no value was requested. Returns `None`
+    /// if the request may take any time.
+    pub fn negotiate_request_timeout_micros(&self, requested_millis: Option<u64>) -> Option<u64> {
+        let timeout_millis = requested_millis
+            .or((self.requesttimeout > 0).then_some(self.requesttimeout))
+            .map(|timeout_millis| {
+                if self.requesttimeoutmax > 0 {
+                    std::cmp::min(timeout_millis, self.requesttimeoutmax)
+                } else {
+                    timeout_millis
+                }
+            })
+            .or((self.requesttimeoutmax > 0).then_some(self.requesttimeoutmax))?;
+        Some(timeout_millis * 1000)
+    }
+
+    /// Negotiate the WebSocket ping interval and tolerance in microseconds
+    /// from the values requested by a client in milliseconds.
+    ///
+    /// Requested values are clamped to the configured server-side caps and
+    /// the configured defaults are used when no value was requested.
+    pub fn negotiate_ping_micros(
+        &self,
+        requested_interval_millis: Option<u64>,
+        requested_tolerance_millis: Option<u64>,
+    ) -> (u64, u64) {
+        let interval_millis = requested_interval_millis
+            .unwrap_or(self.pinginterval)
+            .clamp(
+                self.pingintervalmin,
+                std::cmp::max(self.pingintervalmin, self.pingintervalmax),
+            );
+        let tolerance_millis = std::cmp::min(
+            requested_tolerance_millis.unwrap_or(self.pingtolerance),
+            self.pingtolerancemax,
+        );
+        (interval_millis * 1000, tolerance_millis * 1000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiate_ping_within_caps() {
+        let api_config = ApiConfig {
+            address: "0.0.0.0".to_string(),
+            port: 8081,
+            audience: "fragtale".to_string(),
+            oidcissuers: String::default(),
+            oidctrustanchors: String::default(),
+            authentication: "bearer".to_string(),
+            tlscert: String::default(),
+            tlskey: String::default(),
+            tlsclientcas: String::default(),
+            pinginterval: 5000,
+            pingintervalmin: 1000,
+            pingintervalmax: 60000,
+            pingtolerance: 1000,
+            pingtolerancemax: 30000,
+            maxsessionsperidentity: 0,
+            sessionidletimeout: 300000,
+            ui: false,
+            requesttimeout: 30000,
+            requesttimeoutmax: 300000,
+        };
+        assert_eq!(
+            api_config.negotiate_ping_micros(None, None),
+            (5_000_000, 1_000_000)
+        );
+        assert_eq!(
+            api_config.negotiate_ping_micros(Some(20_000), Some(10_000)),
+            (20_000_000, 10_000_000)
+        );
+        assert_eq!(
+            api_config.negotiate_ping_micros(Some(10), Some(3_600_000)),
+            (1_000_000, 30_000_000)
+        );
+        assert_eq!(
+            api_config.negotiate_ping_micros(Some(3_600_000), Some(0)),
+            (60_000_000, 0)
+        );
+    }
+
+    #[test]
+    fn negotiate_request_timeout_within_cap() {
+        let mut api_config = ApiConfig {
+            address: "0.0.0.0".to_string(),
+            port: 8081,
+            audience: "fragtale".to_string(),
+            oidcissuers: String::default(),
+            oidctrustanchors: String::default(),
+            authentication: "bearer".to_string(),
+            tlscert: String::default(),
+            tlskey: String::default(),
+            tlsclientcas: String::default(),
+            pinginterval: 5000,
+            pingintervalmin: 1000,
+            pingintervalmax: 60000,
+            pingtolerance: 1000,
+            pingtolerancemax: 30000,
+            maxsessionsperidentity: 0,
+            sessionidletimeout: 300000,
+            ui: false,
+            requesttimeout: 30000,
+            requesttimeoutmax: 300000,
+        };
+        assert_eq!(
+            api_config.negotiate_request_timeout_micros(None),
+            Some(30_000_000)
+        );
+        assert_eq!(
+            api_config.negotiate_request_timeout_micros(Some(500)),
+            Some(500_000)
+        );
+        assert_eq!(
+            api_config.negotiate_request_timeout_micros(Some(3_600_000)),
+            Some(300_000_000)
+        );
+        api_config.requesttimeout = 0;
+        assert_eq!(
+            api_config.negotiate_request_timeout_micros(None),
+            Some(300_000_000)
+        );
+        api_config.requesttimeoutmax = 0;
+        assert_eq!(api_config.negotiate_request_timeout_micros(None), None);
+        assert_eq!(
+            api_config.negotiate_request_timeout_micros(Some(3_600_000)),
+            Some(3_600_000_000)
+        );
+    }
+
+    #[test]
+    fn oidc_issuers_skip_blanks() {
+        let mut api_config = ApiConfig {
+            address: "0.0.0.0".to_string(),
+            port: 8081,
+            audience: "fragtale".to_string(),
+            oidcissuers: String::default(),
+            oidctrustanchors: String::default(),
+            authentication: "bearer".to_string(),
+            tlscert: String::default(),
+            tlskey: String::default(),
+            tlsclientcas: String::default(),
+            pinginterval: 5000,
+            pingintervalmin: 1000,
+            pingintervalmax: 60000,
+            pingtolerance: 1000,
+            pingtolerancemax: 30000,
+            maxsessionsperidentity: 0,
+            sessionidletimeout: 300000,
+            ui: false,
+            requesttimeout: 30000,
+            requesttimeoutmax: 300000,
+        };
+        assert!(api_config.oidc_issuers().is_empty());
+        assert_eq!(api_config.oidc_trust_anchors_file(), None);
+        api_config.oidcissuers = " https://a.example.com , ,https://b.example.com/".to_string();
+        assert_eq!(
+            api_config.oidc_issuers(),
+            vec!["https://a.example.com", "https://b.example.com/"]
+        );
+    }
 }
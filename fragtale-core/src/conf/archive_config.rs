@@ -0,0 +1,54 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for the archive tier of events.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for the archive tier of events.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ArchiveConfig {
+    /// See [Self::directory()].
+    directory: String,
+}
+
+impl AppConfigDefaults for ArchiveConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "directory", "")
+            .unwrap()
+    }
+}
+
+impl ArchiveConfig {
+    /// Directory shared by all instances where published events are
+    /// archived. Archiving is disabled when empty.
+    ///
+    /// This is typically an object storage bucket mounted into the container.
+    pub fn directory(&self) -> Option<&str> {
+        Some(self.directory.as_str()).filter(|directory| !directory.is_empty())
+    }
+}
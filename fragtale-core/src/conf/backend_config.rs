@@ -27,18 +27,24 @@ use super::AppConfigDefaults;
 /// Configuration for persistence backend.
 #[derive(Deserialize, Serialize)]
 pub struct BackendConfig {
-    /// Backend implementation
+    /// Backend implementation (`cassandra`, `postgres` or `mem`)
     implementation: String,
-    /// Comma separated list of cassandra backends (host:port).
+    /// Comma separated list of cassandra or postgres backends (host:port).
     endpoints: String,
-    /// Cassandra username
+    /// Cassandra or PostgreSQL username
     username: String,
-    /// Cassandra password
+    /// Cassandra or PostgreSQL password
     password: String,
-    /// Cassandra keyspace for common app tables
+    /// Cassandra keyspace or PostgreSQL schema for common app tables
     namespace: String,
     /// Cassandra keyspace replication factor
     replfactor: String,
+    /// Cassandra max number of concurrent queries on the shared session
+    concurrency: String,
+    /// Cassandra percentage of concurrent queries reserved from retry scans
+    freshshare: String,
+    /// Cassandra queries per second guaranteed to each topic under contention
+    topicrate: String,
 }
 
 impl std::fmt::Debug for BackendConfig {
@@ -50,6 +56,9 @@ impl std::fmt::Debug for BackendConfig {
             .field("password", &"*redacted*")
             .field("namespace", &self.namespace)
             .field("replfactor", &self.replfactor)
+            .field("concurrency", &self.concurrency)
+            .field("freshshare", &self.freshshare)
+            .field("topicrate", &self.topicrate)
             .finish()
     }
 }
@@ -73,6 +82,12 @@ impl AppConfigDefaults for BackendConfig {
             .unwrap()
             .set_default(prefix.to_string() + "." + "replfactor", "3")
             .unwrap()
+            .set_default(prefix.to_string() + "." + "concurrency", "256")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "freshshare", "50")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "topicrate", "1000")
+            .unwrap()
     }
 }
 
@@ -82,6 +97,11 @@ impl BackendConfig {
         &self.implementation
     }
 
+    /// Use the ephemeral in-memory backend regardless of configuration.
+    pub(super) fn use_in_memory(&mut self) {
+        self.implementation = "mem".to_owned();
+    }
+
     /// Comma separated list of hosts.
     pub fn endpoints(&self) -> Vec<String> {
         let mut ret = Vec::new();
@@ -95,17 +115,17 @@ impl BackendConfig {
         ret
     }
 
-    /// Cassandra username
+    /// Cassandra or PostgreSQL username
     pub fn username(&self) -> &str {
         &self.username
     }
 
-    /// Cassandra password
+    /// Cassandra or PostgreSQL password
     pub fn password(&self) -> &str {
         &self.password
     }
 
-    /// Cassandra keyspace for common app tables
+    /// Cassandra keyspace or PostgreSQL schema for common app tables
     pub fn keyspace(&self) -> &str {
         &self.namespace
     }
@@ -114,4 +134,22 @@ impl BackendConfig {
     pub fn replication_factor(&self) -> usize {
         self.replfactor.parse::<usize>().unwrap_or(3)
     }
+
+    /// Cassandra max number of concurrent queries on the shared session
+    pub fn max_concurrency(&self) -> usize {
+        self.concurrency.parse::<usize>().unwrap_or(256)
+    }
+
+    /// Cassandra percentage (1-100) of the concurrent queries that retry scans
+    /// can't use, to guarantee a minimum share of the session throughput for
+    /// delivery of fresh events.
+    pub fn fresh_min_share(&self) -> usize {
+        self.freshshare.parse::<usize>().unwrap_or(50).clamp(1, 100)
+    }
+
+    /// Cassandra queries per second each topic is guaranteed while other
+    /// topics are waiting for the shared session (0 disables the budget).
+    pub fn topic_query_rate(&self) -> u64 {
+        self.topicrate.parse::<u64>().unwrap_or(1000)
+    }
 }
@@ -0,0 +1,98 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for signalling topic backlogs to producers.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for signalling topic backlogs to producers.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BackpressureConfig {
+    /// See [Self::mode()].
+    mode: String,
+    /// See [Self::consumer()].
+    consumer: String,
+    /// See [Self::warn_micros()].
+    warn: u64,
+    /// See [Self::critical_micros()].
+    critical: u64,
+    /// See [Self::shed_priority()].
+    shedpriority: u8,
+}
+
+impl AppConfigDefaults for BackpressureConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "mode", "off")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "consumer", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "warn", "60000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "critical", "300000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "shedpriority", "50")
+            .unwrap()
+    }
+}
+
+impl BackpressureConfig {
+    /// Name of how producers are signalled about the backlog of a topic.
+    ///
+    /// * `off`: The backlog is not tracked.
+    /// * `header`: Publish responses carry a `backlog-state` header.
+    /// * `shed`: Like `header`, but low priority publishes are also rejected
+    ///   while the backlog is critical.
+    pub fn mode(&self) -> &str {
+        &self.mode
+    }
+
+    /// Identifier of the primary consumer (group) whose backlog is tracked in
+    /// every topic or `None` to track the consumer that is furthest behind.
+    pub fn consumer(&self) -> Option<&str> {
+        Some(self.consumer.trim()).filter(|consumer| !consumer.is_empty())
+    }
+
+    /// Age in microseconds of the oldest event not yet processed by the
+    /// primary consumer where the backlog is reported as `warn`. Configured
+    /// in milliseconds.
+    pub fn warn_micros(&self) -> u64 {
+        self.warn * 1000
+    }
+
+    /// Age in microseconds of the oldest event not yet processed by the
+    /// primary consumer where the backlog is reported as `critical`.
+    /// Configured in milliseconds.
+    pub fn critical_micros(&self) -> u64 {
+        std::cmp::max(self.critical, self.warn) * 1000
+    }
+
+    /// Events published with a lower priority than this are rejected while
+    /// the backlog is critical in `shed` mode.
+    pub fn shed_priority(&self) -> u8 {
+        std::cmp::min(self.shedpriority, 100)
+    }
+}
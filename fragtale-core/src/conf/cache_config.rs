@@ -0,0 +1,78 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for in-process caching of read-mostly queries.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for in-process caching of read-mostly queries.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CacheConfig {
+    /// See [Self::max_entries()].
+    size: usize,
+    /// See [Self::event_time_to_live_micros()].
+    eventttl: u64,
+    /// See [Self::index_time_to_live_micros()].
+    indexttl: u64,
+}
+
+impl AppConfigDefaults for CacheConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "size", "10000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "eventttl", "60000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "indexttl", "0")
+            .unwrap()
+    }
+}
+
+impl CacheConfig {
+    /// Maximum number of entries in each cache. `0` disables caching.
+    pub fn max_entries(&self) -> usize {
+        self.size
+    }
+
+    /// Time that an integrity validated event document is served from the
+    /// cache when queried by event identifier. `0` disables the cache.
+    ///
+    /// Event documents are only changed by purges and redactions, which drop
+    /// cached reads of the topic on all instances, so this only delays the
+    /// effect of integrity protection changes made outside of the application.
+    pub fn event_time_to_live_micros(&self) -> u64 {
+        self.eventttl * 1000
+    }
+
+    /// Time that event identifiers matching an indexed query are served from
+    /// the cache. `0` disables the cache.
+    ///
+    /// Events published after the result was cached will not be included
+    /// until the cached result expires.
+    pub fn index_time_to_live_micros(&self) -> u64 {
+        self.indexttl * 1000
+    }
+}
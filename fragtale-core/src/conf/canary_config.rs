@@ -0,0 +1,84 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for synthetic canary events.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for synthetic canary events.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CanaryConfig {
+    /// Comma separated list of topic identifiers.
+    topics: String,
+    /// See [Self::interval_micros()].
+    interval: u64,
+    /// See [Self::timeout_micros()].
+    timeout: u64,
+}
+
+impl AppConfigDefaults for CanaryConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "topics", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "interval", "10000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "timeout", "30000")
+            .unwrap()
+    }
+}
+
+impl CanaryConfig {
+    /// Lower bound of the configured interval in milliseconds.
+    const INTERVAL_MIN_MILLIS: u64 = 100;
+
+    /// Topic identifiers where canary events are published and consumed.
+    ///
+    /// Canary events are published without an event descriptor version, so
+    /// these topics should be dedicated to canaries. No canaries are sent when
+    /// this is empty.
+    pub fn topics(&self) -> Vec<String> {
+        self.topics
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    /// Time in microseconds between canary events published to each topic.
+    /// Configured in milliseconds.
+    pub fn interval_micros(&self) -> u64 {
+        std::cmp::max(self.interval, Self::INTERVAL_MIN_MILLIS) * 1000
+    }
+
+    /// Time in microseconds after publishing that a canary event that has not
+    /// been delivered and confirmed is considered lost. Configured in
+    /// milliseconds.
+    pub fn timeout_micros(&self) -> u64 {
+        self.timeout * 1000
+    }
+}
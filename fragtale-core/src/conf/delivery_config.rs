@@ -0,0 +1,236 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for delivery of events to consumers.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use super::AppConfigDefaults;
+
+/// Configuration for delivery of events to consumers.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeliveryConfig {
+    /// See [Self::max_redeliveries()].
+    maxredeliveries: u32,
+    /// Comma separated list of `topic_id=limit`.
+    concurrency: String,
+    /// See [Self::cache_budget_bytes()].
+    cachebudget: usize,
+    /// Comma separated list of `topic_id` or `topic_id/consumer_id`.
+    strictorder: String,
+    /// Comma separated list of delays in milliseconds.
+    retrybackoff: String,
+    /// See [Self::priority_aging_micros()].
+    priorityaging: u64,
+    /// See [Self::visibility_timeout_bounds_micros()].
+    visibilitytimeoutmin: u64,
+    /// See [Self::visibility_timeout_bounds_micros()].
+    visibilitytimeoutmax: u64,
+    /// Comma separated list of host names.
+    receiptwebhookhosts: String,
+}
+
+impl AppConfigDefaults for DeliveryConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "maxredeliveries", "16")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "concurrency", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "cachebudget", "256")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "strictorder", "")
+            .unwrap()
+            .set_default(
+                prefix.to_string() + "." + "retrybackoff",
+                "3000,30000,300000",
+            )
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "priorityaging", "450")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "visibilitytimeoutmin", "3000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "visibilitytimeoutmax", "900000")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "receiptwebhookhosts", "")
+            .unwrap()
+    }
+}
+
+impl DeliveryConfig {
+    /// Max time for the effective priority of an event to reach the highest
+    /// priority.
+    const PRIORITY_AGING_MAX_MILLIS: u64 = 60_000;
+
+    /// Maximum number of times the same event is redelivered to a consumer
+    /// before the subscription is paused.
+    ///
+    /// A consumer that repeatedly fails to confirm the same event (a poison
+    /// message) would otherwise get it redelivered forever. `0` disables
+    /// pausing.
+    pub fn max_redeliveries(&self) -> u32 {
+        self.maxredeliveries
+    }
+
+    /// Estimated memory in bytes that the delivery caches of all consumers on
+    /// this instance may use combined. Configured in MiB. `0` disables the
+    /// budget.
+    ///
+    /// Each delivery cache is entitled to a fair share. Once the budget is
+    /// exhausted, caches above their share evict their oldest entries.
+    pub fn cache_budget_bytes(&self) -> usize {
+        self.cachebudget.saturating_mul(1024 * 1024)
+    }
+
+    /// Maximum number of unconfirmed deliveries per topic that each consumer
+    /// group may hold across all instances.
+    ///
+    /// This protects downstream systems with hard concurrency limits. Topics
+    /// that are not listed are unlimited.
+    pub fn concurrency_limits(&self) -> HashMap<String, u32> {
+        self.concurrency
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|topic_and_limit| {
+                let parsed = topic_and_limit
+                    .split_once('=')
+                    .and_then(|(topic_id, limit)| {
+                        limit
+                            .trim()
+                            .parse::<u32>()
+                            .ok()
+                            .filter(|limit| *limit > 0)
+                            .map(|limit| (topic_id.trim().to_owned(), limit))
+                    });
+                if parsed.is_none() {
+                    log::warn!(
+                        "Ignoring malformed delivery concurrency limit '{topic_and_limit}'. Expected 'topic_id=limit'."
+                    );
+                }
+                parsed
+            })
+            .collect()
+    }
+
+    /// Topics (`topic_id`) or consumers of topics (`topic_id/consumer_id`)
+    /// where events are delivered strictly in published order.
+    ///
+    /// Delivery to such consumers holds at most one unconfirmed event and
+    /// halts on gaps until all earlier events have been delivered or marked
+    /// done. This trades throughput for a total order of delivery, e.g. for
+    /// ledger appliers.
+    pub fn strict_order(&self) -> HashSet<String> {
+        self.strictorder
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    /// Host names that delivery receipt webhooks of any topic owner may
+    /// point to.
+    ///
+    /// Webhooks are called from inside the cluster, so other receipt webhook
+    /// hosts may only be declared by clients allowed to declare webhook
+    /// consumers.
+    pub fn receipt_webhook_hosts(&self) -> HashSet<String> {
+        self.receiptwebhookhosts
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect()
+    }
+
+    /// Delay in microseconds before each retry of a failed delivery of the
+    /// same event. Configured in milliseconds.
+    ///
+    /// The last delay applies to all following retries, so persistent
+    /// failures don't keep consuming bandwidth at the same rate as fresh
+    /// traffic. A retry is never attempted before the delivery has timed out.
+    pub fn retry_backoff_micros(&self) -> Vec<u64> {
+        self.retrybackoff
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .filter_map(|delay_millis| {
+                let parsed = delay_millis.parse::<u64>().ok();
+                if parsed.is_none() {
+                    log::warn!("Ignoring malformed delivery retry backoff '{delay_millis}'.");
+                }
+                parsed
+            })
+            .map(|delay_millis| delay_millis.saturating_mul(1000))
+            .collect()
+    }
+
+    /// Time in microseconds for the effective delivery priority of an event
+    /// to improve from the lowest (0) to the highest (100). Configured in
+    /// milliseconds and capped at one minute.
+    ///
+    /// Delivery is ordered by publish time where lower priority events are
+    /// delayed by up to this duration, so an event that has waited this long
+    /// is never starved by fresh events of higher priority. A longer duration
+    /// favors important events more, but also delays delivery of low
+    /// priority events when there is no competition. `0` ignores priority
+    /// when ordering deliveries.
+    ///
+    /// All instances should use the same value for a consistent order.
+    pub fn priority_aging_micros(&self) -> u64 {
+        if self.priorityaging > Self::PRIORITY_AGING_MAX_MILLIS {
+            log::warn!(
+                "Capping delivery priority aging of {} ms to {} ms.",
+                self.priorityaging,
+                Self::PRIORITY_AGING_MAX_MILLIS
+            );
+        }
+        std::cmp::min(self.priorityaging, Self::PRIORITY_AGING_MAX_MILLIS) * 1000
+    }
+
+    /// Lowest and highest visibility timeout in microseconds that a
+    /// subscription may request. Configured in milliseconds.
+    ///
+    /// The visibility timeout is how long the broker waits for a confirmation
+    /// of a delivered event before it is considered for redelivery. Requested
+    /// values outside of the bounds are clamped. A highest value below the
+    /// lowest is raised to the lowest.
+    pub fn visibility_timeout_bounds_micros(&self) -> (u64, u64) {
+        let min_millis = std::cmp::max(self.visibilitytimeoutmin, 1);
+        if self.visibilitytimeoutmax < min_millis {
+            log::warn!(
+                "Raising max delivery visibility timeout of {} ms to the min of {min_millis} ms.",
+                self.visibilitytimeoutmax
+            );
+        }
+        let max_millis = std::cmp::max(self.visibilitytimeoutmax, min_millis);
+        (
+            min_millis.saturating_mul(1000),
+            max_millis.saturating_mul(1000),
+        )
+    }
+}
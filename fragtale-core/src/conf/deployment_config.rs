@@ -0,0 +1,62 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for the role of instances in a deployment.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for the role of instances in a deployment.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DeploymentConfig {
+    /// See [Self::mode()].
+    mode: String,
+}
+
+impl AppConfigDefaults for DeploymentConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "mode", "combined")
+            .unwrap()
+    }
+}
+
+impl DeploymentConfig {
+    /// Name of the workloads this instance performs.
+    ///
+    /// * `combined`: Serve the REST API and consumers and take part in
+    ///   background work like integrity consolidation.
+    /// * `broker`: Serve the REST API and consumers, but leave background
+    ///   work to `combined` or `worker` instances.
+    /// * `worker`: Only perform background work. Health and metrics are
+    ///   still served, so the instance can be probed and scraped.
+    ///
+    /// Background work is performed by a single elected instance out of the
+    /// ones that take part, so a deployment of `broker` instances needs at
+    /// least one `worker` (or `combined`) instance.
+    pub fn mode(&self) -> &str {
+        &self.mode
+    }
+}
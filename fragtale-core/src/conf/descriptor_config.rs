@@ -0,0 +1,74 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for limits of topic event descriptors.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for limits of topic event descriptors.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DescriptorConfig {
+    /// See [Self::max_extractors()].
+    maxextractors: usize,
+    /// See [Self::max_schema_size()].
+    maxschemasize: usize,
+    /// See [Self::max_index_columns()].
+    maxindexcolumns: usize,
+}
+
+impl AppConfigDefaults for DescriptorConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "maxextractors", "16")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "maxschemasize", "65536")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "maxindexcolumns", "16")
+            .unwrap()
+    }
+}
+
+impl DescriptorConfig {
+    /// Maximum number of extractors in a single event descriptor, including
+    /// the extractors of each kind of event.
+    pub fn max_extractors(&self) -> usize {
+        self.maxextractors
+    }
+
+    /// Maximum size in bytes of each schema in an event descriptor.
+    pub fn max_schema_size(&self) -> usize {
+        self.maxschemasize
+    }
+
+    /// Maximum number of distinct indexed columns of a topic across all
+    /// versions of its event descriptor.
+    ///
+    /// Each indexed column is backed by a secondary index in the database, so
+    /// this protects the cluster from excessive index maintenance.
+    pub fn max_index_columns(&self) -> usize {
+        self.maxindexcolumns
+    }
+}
@@ -0,0 +1,89 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for local development.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for local development.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DevConfig {
+    /// See [Self::enabled()].
+    ///
+    /// Never read from configuration, so a deployed broker can't be switched
+    /// into developer mode by its environment.
+    #[serde(skip)]
+    enabled: bool,
+    /// See [Self::token()].
+    token: String,
+    /// See [Self::fixtures_file()].
+    fixtures: String,
+}
+
+impl AppConfigDefaults for DevConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "token", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "fixtures", "")
+            .unwrap()
+    }
+}
+
+impl DevConfig {
+    /// Return `true` if the broker runs in developer mode.
+    ///
+    /// Developer mode can only be enabled from the command line (see
+    /// [super::AppConfig::with_dev_mode()]). It uses the in-memory backend,
+    /// accepts the static [Self::token()] as a bearer token with unrestricted
+    /// access and logs every API request. It must never be used in
+    /// production.
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Static bearer token accepted in developer mode.
+    ///
+    /// There is no default token, so no static token is accepted unless one
+    /// has been configured.
+    pub fn token(&self) -> Option<&str> {
+        (self.enabled && !self.token.is_empty()).then_some(self.token.as_str())
+    }
+
+    /// JSON file with topics, event descriptors and events to seed the broker
+    /// with in developer mode.
+    pub fn fixtures_file(&self) -> Option<&str> {
+        (self.enabled && !self.fixtures.is_empty()).then_some(self.fixtures.as_str())
+    }
+
+    /// Enable developer mode and optionally seed from the `fixtures_file`.
+    pub(super) fn enable(&mut self, fixtures_file: Option<&str>) {
+        self.enabled = true;
+        if let Some(fixtures_file) = fixtures_file {
+            self.fixtures = fixtures_file.to_owned();
+        }
+    }
+}
@@ -35,6 +35,8 @@ pub struct IntegrityConfig {
     previousoid: String,
     ntphost: Option<String>,
     tolerance: u64,
+    /// See [Self::tolerable_clock_stall_micros()].
+    stalltolerance: u64,
 }
 
 impl AppConfigDefaults for IntegrityConfig {
@@ -84,11 +86,13 @@ impl AppConfigDefaults for IntegrityConfig {
             .unwrap()
             .set_default(prefix.to_string() + "." + "tolerance", "1000000")
             .unwrap()
+            .set_default(prefix.to_string() + "." + "stalltolerance", "500000")
+            .unwrap()
     }
 }
 
 impl IntegrityConfig {
-    /// Return the correlation token protection OID and secret.
+    /// Return the correlation token protection OID and master secret.
     pub fn correlation_secret(&self) -> (Vec<u32>, Vec<u8>) {
         Self::get_oid_and_secret(&self.correlationoid, &self.correlationsecret)
     }
@@ -136,6 +140,12 @@ impl IntegrityConfig {
         self.tolerance
     }
 
+    /// How far ahead of the local clock unique time stamping may run while
+    /// the clock stalls or steps backwards, before publishing is refused.
+    pub fn tolerable_clock_stall_micros(&self) -> u64 {
+        self.stalltolerance
+    }
+
     /// Return the previous protection OID and secret.
     fn get_oid_and_secret(oid_filename: &str, secret_filename: &str) -> (Vec<u32>, Vec<u8>) {
         let oid = Self::get_oid(oid_filename);
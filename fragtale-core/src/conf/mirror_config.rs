@@ -0,0 +1,78 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for mirroring of published events to local files.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for mirroring of published events to local files.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct MirrorConfig {
+    /// See [Self::directory()].
+    directory: String,
+    /// See [Self::max_file_size()].
+    maxfilesize: u64,
+    /// See [Self::max_file_age_micros()].
+    maxfileage: u64,
+    /// See [Self::max_files()].
+    maxfiles: usize,
+}
+
+impl AppConfigDefaults for MirrorConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "directory", "/mirror")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "maxfilesize", "16777216")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "maxfileage", "3600")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "maxfiles", "8")
+            .unwrap()
+    }
+}
+
+impl MirrorConfig {
+    /// Local directory where mirrored events of each topic are written.
+    pub fn directory(&self) -> &str {
+        &self.directory
+    }
+
+    /// Size in bytes after which a mirror file is rotated.
+    pub fn max_file_size(&self) -> u64 {
+        self.maxfilesize
+    }
+
+    /// Age after which a mirror file is rotated.
+    pub fn max_file_age_micros(&self) -> u64 {
+        self.maxfileage * 1_000_000
+    }
+
+    /// Number of mirror files to keep for each topic.
+    pub fn max_files(&self) -> usize {
+        self.maxfiles.max(1)
+    }
+}
@@ -0,0 +1,62 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Parsing of configuration for isolation of tenants.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for isolation of tenants.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TenancyConfig {
+    /// See [Self::claim()].
+    claim: String,
+    /// See [Self::max_events_per_second()].
+    maxeventspersecond: u64,
+}
+
+impl AppConfigDefaults for TenancyConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "claim", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "maxeventspersecond", 0)
+            .unwrap()
+    }
+}
+
+impl TenancyConfig {
+    /// Bearer token claim that holds the tenant of an identity.
+    ///
+    /// Topics are isolated per tenant when present.
+    pub fn claim(&self) -> Option<&str> {
+        Some(self.claim.trim()).filter(|claim| !claim.is_empty())
+    }
+
+    /// Max number of events each tenant may publish per second to a single
+    /// instance or `None` if unlimited.
+    pub fn max_events_per_second(&self) -> Option<u64> {
+        Some(self.maxeventspersecond).filter(|max| *max > 0)
+    }
+}
@@ -0,0 +1,57 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for creation of topics.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for creation of topics.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TopicsConfig {
+    /// See [Self::creation()].
+    creation: String,
+}
+
+impl AppConfigDefaults for TopicsConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "creation", "auto")
+            .unwrap()
+    }
+}
+
+impl TopicsConfig {
+    /// Name of the policy for creating topics that have not been set up yet.
+    ///
+    /// * `auto`: Any identity creates topics on first use.
+    /// * `restricted`: Only identities granted the administrative function
+    ///   `create_topic` may create topics.
+    /// * `disabled`: Topics must be registered explicitly with an event
+    ///   descriptor before use.
+    pub fn creation(&self) -> &str {
+        &self.creation
+    }
+}
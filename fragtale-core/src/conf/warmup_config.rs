@@ -0,0 +1,105 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parsing of configuration for warm-up of hot topics and consumers.
+
+use config::ConfigBuilder;
+use config::builder::BuilderState;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::AppConfigDefaults;
+
+/// Configuration for warm-up of hot topics and consumers during startup.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct WarmupConfig {
+    /// Comma separated list of topic identifiers.
+    topics: String,
+    /// Comma separated list of `topic_id/consumer_id`.
+    consumers: String,
+    /// See [Self::learn()].
+    learn: bool,
+    /// See [Self::timeout_micros()].
+    timeout: u64,
+}
+
+impl AppConfigDefaults for WarmupConfig {
+    /// Provide defaults for this part of the configuration
+    fn set_defaults<T: BuilderState>(
+        config_builder: ConfigBuilder<T>,
+        prefix: &str,
+    ) -> ConfigBuilder<T> {
+        config_builder
+            .set_default(prefix.to_string() + "." + "topics", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "consumers", "")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "learn", "false")
+            .unwrap()
+            .set_default(prefix.to_string() + "." + "timeout", "30")
+            .unwrap()
+    }
+}
+
+impl WarmupConfig {
+    /// Topic identifiers to warm up before readiness is reported.
+    pub fn topics(&self) -> Vec<String> {
+        Self::split_list(&self.topics)
+    }
+
+    /// Topic and consumer identifier pairs to warm up before readiness is
+    /// reported.
+    ///
+    /// Since consumer identifiers may contain `/`, the topic identifier is
+    /// separated from the consumer identifier by the first `/`.
+    pub fn consumers(&self) -> Vec<(String, String)> {
+        Self::split_list(&self.consumers)
+            .iter()
+            .filter_map(|topic_and_consumer| {
+                let parsed = topic_and_consumer
+                    .split_once('/')
+                    .map(|(topic_id, consumer_id)| (topic_id.to_owned(), consumer_id.to_owned()));
+                if parsed.is_none() {
+                    log::warn!(
+                        "Ignoring malformed warm-up consumer '{topic_and_consumer}'. Expected 'topic_id/consumer_id'."
+                    );
+                }
+                parsed
+            })
+            .collect()
+    }
+
+    /// Return `true` if all topics known by the database should be warmed up.
+    pub fn learn(&self) -> bool {
+        self.learn
+    }
+
+    /// Maximum time to spend on warm-up before readiness is reported anyway.
+    pub fn timeout_micros(&self) -> u64 {
+        self.timeout * 1_000_000
+    }
+
+    /// Parse a comma separated list, ignoring empty entries.
+    fn split_list(value: &str) -> Vec<String> {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+}
@@ -27,13 +27,17 @@ pub mod util {
     mod bdtd_builder;
     mod lockless_caching_filter;
     mod log_scope_duration;
+    mod request_deadline;
     mod signal_awaiter;
+    mod timer_wheel;
     mod trusted_time;
 
     pub use self::bdtd_builder::*;
     pub use self::lockless_caching_filter::*;
     pub use self::log_scope_duration::*;
+    pub use self::request_deadline::*;
     pub use self::signal_awaiter::*;
+    pub use self::timer_wheel::*;
     pub use self::trusted_time::*;
 }
 
@@ -21,47 +21,198 @@ pub mod auth {
     //! Authorization
 
     mod access_control;
+    mod client_authentication_mode;
     mod client_identity;
+    mod publish_grants;
 
     pub use self::access_control::AccessControl;
+    pub use self::client_authentication_mode::ClientAuthenticationMode;
     pub use self::client_identity::ClientIdentity;
+    pub use self::publish_grants::PublishGrants;
 }
+mod backlog_monitor;
+mod bulk_ingest;
+mod canary_tracker;
+mod cluster_protocol;
 mod consumers;
+mod consumption_auditor;
 mod correlation_hotlist;
+mod dead_letter_reader;
+mod deployment_mode;
+mod dev_fixtures;
+mod document_canonicalization;
+mod document_masking;
+mod document_migration;
+mod document_projection;
+mod duplicate_tracker;
+mod event_archive;
 mod event_descriptor_cache;
+mod event_id_collision_policy;
+mod event_mirror;
+mod event_purger;
+mod event_statistics;
+mod filter_expression;
+mod filter_expression_cache;
+mod index_rebuilder;
 mod integrity;
+mod leader_election;
 mod mb_metrics;
 mod object_count_tracker;
 mod pre_storage_processor;
+mod publish_rejection_log;
+mod read_cache;
+mod retention_previewer;
+mod task_supervisor;
+mod tenant_quota;
+mod topic_creation_policy;
+mod topic_snapshotter;
 mod unique_time_stamper;
 
+use self::backlog_monitor::BacklogMonitor;
+use self::backlog_monitor::BacklogState;
+use self::bulk_ingest::BulkIngest;
+use self::canary_tracker::CanaryTracker;
+use self::cluster_protocol::ClusterProtocol;
+use self::consumers::ConsumerDefinitionRegistry;
 use self::consumers::Consumers;
+use self::consumers::GroupMembers;
+use self::consumers::StickyPreference;
+use self::consumers::WebhookSender;
+use self::consumption_auditor::ConsumptionAuditor;
 use self::correlation_hotlist::CorrelationHotlist;
+use self::dead_letter_reader::DeadLetterReader;
+use self::deployment_mode::DeploymentMode;
+use self::dev_fixtures::DevFixtures;
+use self::document_canonicalization::DocumentCanonicalization;
+use self::document_masking::DocumentMasking;
+use self::document_migration::DocumentMigration;
+use self::document_projection::DocumentProjection;
+use self::duplicate_tracker::DuplicateTracker;
+use self::event_archive::ArchivedEvent;
+use self::event_archive::EventArchive;
 use self::event_descriptor_cache::EventDescriptorCache;
+use self::event_id_collision_policy::EventIdCollisionPolicy;
+use self::event_mirror::EventMirror;
+use self::event_purger::EventPurger;
+use self::event_statistics::EventStatistics;
+use self::filter_expression_cache::FilterExpressionCache;
+use self::index_rebuilder::IndexRebuilder;
 use self::integrity::*;
+use self::leader_election::LeaderElection;
 use self::object_count_tracker::ObjectCountTracker;
 use self::pre_storage_processor::PreStorageProcessor;
+use self::publish_rejection_log::PublishRejectionLog;
+use self::read_cache::ReadCache;
+use self::retention_previewer::RetentionPreviewer;
+use self::task_supervisor::TaskSupervisor;
+use self::tenant_quota::TenantQuota;
+use self::topic_creation_policy::TopicCreationPolicy;
+use self::topic_snapshotter::TopicSnapshotter;
 use self::unique_time_stamper::UniqueTimeStamper;
 use crate::conf::AppConfig;
+use crate::util::RequestDeadline;
 use crate::util::TrustedTime;
 use auth::AccessControl;
 use auth::ClientIdentity;
+use auth::PublishGrants;
+use fragtale_client::mb::bulk_ingest::BulkIngestWindow;
+use fragtale_client::mb::bulk_ingest::BulkIngestWindowRequest;
+use fragtale_client::mb::capabilities::Capabilities;
+use fragtale_client::mb::capabilities::DeliveryLimits;
+use fragtale_client::mb::capabilities::DescriptorLimits;
+use fragtale_client::mb::capabilities::Features;
+use fragtale_client::mb::consumer_definitions::ConsumerDefinition;
+use fragtale_client::mb::consumer_definitions::ConsumerDefinitionTarget;
+use fragtale_client::mb::consumer_definitions::ConsumerDefinitions;
+use fragtale_client::mb::consumer_position::ConsumerSeek;
+use fragtale_client::mb::consumption_audit::ConsumptionAudit;
+use fragtale_client::mb::contract_fixtures::ContractFixtures;
+use fragtale_client::mb::dead_letters::DeadLetter;
+use fragtale_client::mb::dead_letters::DeadLetters;
+use fragtale_client::mb::delivery_envelope::DeliveryEnvelope;
+use fragtale_client::mb::delivery_receipts::DeliveryReceipt;
+use fragtale_client::mb::delivery_receipts::DeliveryReceiptTarget;
+use fragtale_client::mb::descriptor_diff::DescriptorDiff;
+use fragtale_client::mb::diagnostic_queries::DiagnosticQueryResult;
+use fragtale_client::mb::diagnostic_queries::DiagnosticQueryTemplate;
+use fragtale_client::mb::diagnostic_queries::DiagnosticQueryTemplates;
+use fragtale_client::mb::duplicate_events::DuplicateEvents;
+use fragtale_client::mb::event_annotations::EventAnnotation;
+use fragtale_client::mb::event_annotations::EventAnnotationEntry;
+use fragtale_client::mb::event_annotations::EventAnnotations;
 use fragtale_client::mb::event_descriptor::DescriptorVersion;
 use fragtale_client::mb::event_descriptor::EventDescriptor;
+use fragtale_client::mb::event_descriptor::EventTypeDescriptor;
+use fragtale_client::mb::event_descriptor::MaskingRule;
+use fragtale_client::mb::event_locations::EventLocation;
+use fragtale_client::mb::event_locations::EventLocations;
+use fragtale_client::mb::event_mirror::TopicMirror;
+use fragtale_client::mb::event_mirror::TopicMirrorUpdate;
+use fragtale_client::mb::event_purge::EventPurge;
+use fragtale_client::mb::event_purge::EventPurgeMode;
+use fragtale_client::mb::event_purge::EventPurgeRequest;
+use fragtale_client::mb::group_members::TopicGroupMembers;
+use fragtale_client::mb::index_rebuild::IndexRebuild;
+use fragtale_client::mb::peeked_events::PeekedEvent;
+use fragtale_client::mb::peeked_events::PeekedEvents;
+use fragtale_client::mb::publish_acknowledgement::PublishAcknowledgement;
+use fragtale_client::mb::publish_grant::IssuedPublishGrant;
+use fragtale_client::mb::publish_rejections::PublishRejection;
+use fragtale_client::mb::publish_rejections::PublishRejectionReason;
+use fragtale_client::mb::publish_rejections::PublishRejections;
+use fragtale_client::mb::rejected_events::RejectedEvents;
+use fragtale_client::mb::replayed_events::ReplayedEvent;
+use fragtale_client::mb::replayed_events::ReplayedEvents;
+use fragtale_client::mb::resource_grants::ResourceGrant;
+use fragtale_client::mb::resource_grants::ResourceGrants;
+use fragtale_client::mb::resource_grants::ResourceGrantsImport;
+use fragtale_client::mb::retention_preview::RetentionPreview;
+use fragtale_client::mb::shared_schemas::SharedSchema;
+use fragtale_client::mb::shared_schemas::SharedSchemas;
+use fragtale_client::mb::storage_tier::StorageTier;
+use fragtale_client::mb::subscription_health::SubscriptionResume;
+use fragtale_client::mb::subscription_health::TopicSubscriptionsHealth;
+use fragtale_client::mb::topic_access::TopicAccessGrant;
+use fragtale_client::mb::topic_access::TopicOwnership;
+use fragtale_client::mb::topic_access::TopicOwnershipTransfer;
+use fragtale_client::mb::topic_buckets::BucketEntries;
+use fragtale_client::mb::topic_buckets::BucketEntry;
+use fragtale_client::mb::topic_buckets::TopicBucket;
+use fragtale_client::mb::topic_buckets::TopicBuckets;
+use fragtale_client::mb::topic_buckets::TopicShelves;
+use fragtale_client::mb::topic_counts::TopicCounts;
+use fragtale_client::mb::topic_overview::ConsumerLag;
+use fragtale_client::mb::topic_overview::DescriptorHistory;
+use fragtale_client::mb::topic_overview::TopicConsumersLag;
+use fragtale_client::mb::topic_overview::TopicList;
+use fragtale_client::mb::topic_statistics::TopicStatistics;
+use fragtale_client::mb::web_socket_sessions::WebSocketSession;
+use fragtale_client::mb::web_socket_sessions::WebSocketSessions;
 use fragtale_dbp::dbp::DatabaseProvider;
 use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::DiagnosticQuery;
+use fragtale_dbp::mb::EventIdAlgorithm;
+use fragtale_dbp::mb::ExtractedValue;
+use fragtale_dbp::mb::IndexRebuildProgress;
 pub use fragtale_dbp::mb::MessageBrokerError;
 pub use fragtale_dbp::mb::MessageBrokerErrorKind;
 use fragtale_dbp::mb::ObjectCountType;
+use fragtale_dbp::mb::RejectedEvent;
 use fragtale_dbp::mb::TopicEvent;
 use fragtale_dbp::mb::UniqueTime;
 use fragtale_dbp::mb::consumers::EventDeliveryGist;
+use fragtale_dbp::mb::consumers::RetryBackoff;
 use fragtale_dbp_cassandra::CassandraProvider;
 use fragtale_dbp_mem::InMemoryDatabaseProvider;
+use fragtale_dbp_postgres::PostgresProvider;
 use integrity::common::IntegritySecretsHolder;
 use mb_metrics::MessageBrokerMetrics;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 use tokio::time::sleep;
 
@@ -73,6 +224,8 @@ delivery while maintaining intgrity.
 pub struct MessageBroker {
     /// Thread safe boolean used to indicate application readyness.
     health_ready: AtomicBool,
+    /// Thread safe boolean used to indicate that the instance is shutting down.
+    draining: AtomicBool,
     /// The database provider
     dbp: Arc<DatabaseProvider>,
     /// The trusted time montor.
@@ -89,32 +242,199 @@ pub struct MessageBroker {
     object_count_tracker: Arc<ObjectCountTracker>,
     // Performs tasks like extracting indexed data before the event is persisted.
     pre_storage_processor: Arc<PreStorageProcessor>,
+    // Rolling statistics of published events.
+    event_statistics: Arc<EventStatistics>,
     // Tracking outcomes of a request event.
     correlation_hotlist: Arc<CorrelationHotlist>,
     // Tracking of consumers and fairly ordered event delivery.
     consumers: Arc<Consumers>,
     // For checking authorization.
     access_control: Arc<AccessControl>,
+    // Pre-signed grants to publish without cluster credentials.
+    publish_grants: PublishGrants,
+    // Limits of what each tenant may publish.
+    tenant_quota: TenantQuota,
+    // Backlogs of topics signalled to producers.
+    backlog_monitor: Arc<BacklogMonitor>,
     // Metrics
     metrics: Option<Arc<MessageBrokerMetrics>>,
+    task_supervisor: Arc<TaskSupervisor>,
+    // Integrity validated event documents by topic and event identifier.
+    event_read_cache: Arc<ReadCache<(String, String), (UniqueTime, Arc<str>)>>,
+    // Event identifiers by topic, index column and index key.
+    index_read_cache: Arc<ReadCache<(String, String, String), Vec<String>>>,
+    // Limits enforced when a topic's event descriptor is upserted.
+    descriptor_limits: DescriptorLimits,
+    // Copies of persisted events on storage shared by all instances.
+    event_archive: Arc<EventArchive>,
+    // Capabilities and limits reported to client libraries and tooling.
+    capabilities: Capabilities,
+    // Allow whitelisted read-only diagnostic queries against the database.
+    diagnostics_enabled: bool,
+    // Debug sink of published events to local files.
+    event_mirror: Arc<EventMirror>,
+    // Dry-run reporting of what a proposed retention would delete.
+    retention_previewer: Arc<RetentionPreviewer>,
+    // Signed reports of which consumers retrieved events.
+    consumption_auditor: Arc<ConsumptionAuditor>,
+    // Consistent export of topics as of a point in time.
+    topic_snapshotter: Arc<TopicSnapshotter>,
+    // Listing of events in dead-letter topics.
+    dead_letter_reader: Arc<DeadLetterReader>,
+    // Declared consumers that survive client restarts.
+    consumer_definitions: Arc<ConsumerDefinitionRegistry>,
+    // Delivery of events to declared webhook consumers.
+    webhook_sender: WebhookSender,
+    // Handling of requests for topics that have not been set up yet.
+    topic_creation_policy: TopicCreationPolicy,
+    // Recently rejected publishes for diagnostics of producer integrations.
+    publish_rejection_log: PublishRejectionLog,
+    // Recently published duplicate events for diagnostics of producer retries.
+    duplicate_tracker: DuplicateTracker,
+    // Workloads performed by this instance.
+    deployment_mode: DeploymentMode,
+    // Election of the instance performing background work (if taking part).
+    background_work_election: Option<Arc<LeaderElection>>,
+    // Synthetic canary events in flight.
+    canary_tracker: CanaryTracker,
+    // Windows where publishing defers index population and consolidation.
+    bulk_ingest: Arc<BulkIngest>,
+    index_rebuilder: Arc<IndexRebuilder>,
+    // Administrative purge of events matching an indexed key.
+    event_purger: Arc<EventPurger>,
+    // Topics and events to seed the broker with in developer mode.
+    dev_fixtures: Option<DevFixtures>,
+}
+
+/// Published event that passed validation, but has no unique time yet.
+struct ValidatedEvent {
+    event_id: String,
+    event_document: String,
+    priority: u8,
+    correlation_token: String,
+    additional_columns: HashMap<String, ExtractedValue>,
+    event_descriptor_version: Option<DescriptorVersion>,
 }
 
 impl MessageBroker {
+    /// Max number of rejected events returned in a single listing.
+    const REJECTED_EVENTS_PAGE_SIZE: usize = 100;
+    /// Max number of events in a batch published to a topic.
+    pub const PUBLISH_BATCH_MAX_EVENTS: usize = 100;
+    /// Suffix of the topic that dead-lettered events of a topic are
+    /// published to.
+    const DEAD_LETTER_TOPIC_SUFFIX: &str = "_dlq";
+    /// Reason code of events dead-lettered after failed integrity validation.
+    const DEAD_LETTER_REASON_INTEGRITY: &str = "integrity";
+    /// Reason code of events dead-lettered after exhausting the redelivery
+    /// budget.
+    const DEAD_LETTER_REASON_REDELIVERIES: &str = "redeliveries";
+    /// Max number of buckets returned in a single listing.
+    const TOPIC_BUCKETS_PAGE_SIZE: usize = 32;
+    /// Max number of raw bucket entries returned in a single listing.
+    const BUCKET_ENTRIES_PAGE_SIZE: usize = 100;
+    /// Max number of rows returned by a single diagnostic query.
+    const DIAGNOSTIC_QUERY_MAX_ROWS: usize = 100;
+    /// Max number of events of other types, lower priority or not matching
+    /// the filter skipped in a single attempt to get the next event for a
+    /// filtered consumer.
+    const EVENT_TYPE_FILTER_MAX_SKIPPED: usize = 128;
+    /// Max size in bytes of the value of a single event annotation.
+    const EVENT_ANNOTATION_VALUE_MAX_BYTES: usize = 1024;
+    /// Max number of annotations returned for a single event.
+    const EVENT_ANNOTATIONS_MAX: usize = 1000;
+    /// Max number of topics returned when locating an event by identifier.
+    const EVENT_LOCATIONS_MAX: usize = 100;
+    /// Max number of events delivered to a single webhook consumer before
+    /// the other webhook consumers get their turn.
+    const WEBHOOK_BATCH_SIZE: usize = 32;
+    /// Time to wait between rounds of webhook deliveries when there was
+    /// nothing to deliver.
+    const WEBHOOK_IDLE_MICROS: u64 = 1_000_000;
+    /// Max length of the name of a declared consumer.
+    const CONSUMER_DEFINITION_NAME_MAX_LEN: usize = 64;
+    /// Namespace of system topics. Topic identifiers starting with
+    /// `{namespace}_` are reserved for this Pod and the namespace is never a
+    /// valid tenant.
+    pub const SYSTEM_TOPIC_NAMESPACE: &str = "fragtale";
+    /// Topic where audit events of ownership and access changes and of
+    /// diagnostic queries are published.
+    pub const AUDIT_TOPIC_ID: &str = "fragtale_audit";
+    /// Time given to connected consumers to be notified of an orderly
+    /// shutdown before the instance identity is freed.
+    const DRAIN_GRACE_MICROS: u64 = 500_000;
+    /// Publisher of synthetic canary events and prefix of the consumer
+    /// identifier of the built-in canary consumer.
+    const CANARY_PUBLISHER: &str = "fragtale_canary";
+    /// Time to wait before polling for canary events again when there was
+    /// nothing to deliver.
+    const CANARY_IDLE_MICROS: u64 = 64_000;
+    /// Prefix of the consumer identifier of the built-in consumer of audit
+    /// events that applies event purges to the read caches and archive files
+    /// of this instance.
+    const EVENT_PURGE_FOLLOWER: &str = "fragtale_event_purge";
+    /// Time to wait before polling for audit events of event purges again
+    /// when there was nothing to deliver.
+    const EVENT_PURGE_FOLLOWER_IDLE_MICROS: u64 = 1_000_000;
+    /// Default window of confirmation statistics of consumer group members.
+    const GROUP_MEMBERS_DEFAULT_WINDOW_MINUTES: u64 = 5;
+    /// Max number of recently rejected publishes kept on this instance.
+    const PUBLISH_REJECTIONS_KEPT: usize = 1024;
+    /// Publisher of rejected publishes with an invalid publish grant.
+    const PUBLISH_GRANT_PUBLISHER: &str = "publish_grant";
+    /// Window where an event published again with the same event identifier
+    /// is detected as a duplicate.
+    const DUPLICATE_WINDOW_MICROS: u64 = 300_000_000;
+    /// Max number of publishers reported as producing the most duplicates.
+    const DUPLICATE_TOP_PUBLISHERS: usize = 10;
+    /// Default number of events returned when peeking at pending deliveries.
+    const PEEK_DEFAULT_EVENTS: usize = 10;
+    /// Max number of events returned when peeking at pending deliveries.
+    const PEEK_MAX_EVENTS: usize = 100;
+    /// Default number of events returned per page when replaying a time
+    /// range.
+    const REPLAY_DEFAULT_EVENTS: usize = 100;
+    /// Max number of events returned per page when replaying a time range.
+    const REPLAY_MAX_EVENTS: usize = 1000;
+    /// Max time to wait for a published event to become visible to
+    /// consumers.
+    const INDEXED_ACK_MAX_WAIT_MICROS: u64 = 10_000_000;
+    /// Initial interval between looks for a published event to become
+    /// visible to consumers.
+    const INDEXED_ACK_POLL_MIN_MICROS: u64 = 1_000;
+    /// Max interval between looks for a published event to become visible to
+    /// consumers.
+    const INDEXED_ACK_POLL_MAX_MICROS: u64 = 100_000;
+
     /// Return a new instance.
     pub async fn new(app_config: &Arc<AppConfig>) -> Arc<Self> {
         // Setup persistence from config.
         let dbp = match app_config.backend.implementation() {
             "cassandra" => {
                 let cassandra_provider = CassandraProvider::new(
+                    app_config.app_name_lowercase(),
                     app_config.backend.keyspace(),
                     &app_config.backend.endpoints(),
                     app_config.backend.username(),
                     app_config.backend.password(),
                     app_config.backend.replication_factor(),
+                    app_config.backend.max_concurrency(),
+                    app_config.backend.fresh_min_share(),
+                    app_config.backend.topic_query_rate(),
                 )
                 .await;
                 Arc::new(cassandra_provider.as_database_provider())
             }
+            "postgres" => {
+                let postgres_provider = PostgresProvider::new(
+                    app_config.backend.keyspace(),
+                    &app_config.backend.endpoints(),
+                    app_config.backend.username(),
+                    app_config.backend.password(),
+                )
+                .await;
+                Arc::new(postgres_provider.as_database_provider())
+            }
             "mem" => {
                 let inmem_provider = InMemoryDatabaseProvider::new().await;
                 //DatabaseProvider2::new(Box::new(inmem_provider))
@@ -123,13 +443,28 @@ impl MessageBroker {
             unknown_provider => panic!("Unkown database provider type '{unknown_provider}'."),
         };
         // Establish a unique instance identifier using the shared database.
-        let unique_timer_stamper = UniqueTimeStamper::new(&dbp).await;
+        let unique_timer_stamper = UniqueTimeStamper::new(
+            &dbp,
+            app_config.integrity.tolerable_clock_stall_micros(),
+            app_config.delivery.priority_aging_micros(),
+        )
+        .await;
         let instance_id = unique_timer_stamper.get_instance_id();
+        // Refuse to run next to instances that write data we can't parse.
+        let cluster_protocol = ClusterProtocol::new(&dbp).await;
         let instance_start_ts = fragtale_client::time::get_timestamp_micros();
+        let metrics = app_config
+            .metrics
+            .enabled()
+            .then(|| MessageBrokerMetrics::new(app_config));
+        // Restart background tasks that die or get stuck.
+        let task_supervisor = TaskSupervisor::new(&metrics);
         // Start tracking schema and state of deliveries.
         let event_descriptor_cache = EventDescriptorCache::new(&dbp).await;
         let object_count_tracker = ObjectCountTracker::new(&dbp, instance_id).await;
-        let pre_storage_processor = PreStorageProcessor::new(&event_descriptor_cache);
+        let filter_expression_cache = FilterExpressionCache::new();
+        let pre_storage_processor =
+            PreStorageProcessor::new(&event_descriptor_cache, &filter_expression_cache);
         // Setup time monitoring, integrity protection and consolidation.
         let trusted_time = TrustedTime::new(
             app_config.integrity.ntp_host(),
@@ -140,26 +475,121 @@ impl MessageBroker {
         let integrity_protector = IntegrityProtector::new(&ish, &dbp, &unique_timer_stamper);
         let integrity_validator =
             IntegrityValidator::new(&ish, &dbp, instance_start_ts, &unique_timer_stamper);
-        IntegrityConsolidationService::new(
-            &ish,
-            &dbp,
-            &integrity_protector,
-            &integrity_validator,
-            &unique_timer_stamper,
-        )
-        .await;
+        let deployment_mode = DeploymentMode::from_name(app_config.deployment.mode())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Unknown deployment mode '{}'.",
+                    app_config.deployment.mode()
+                )
+            });
+        // Background work is only performed by the elected instance.
+        let background_work_election = if deployment_mode.runs_background_work() {
+            Some(
+                LeaderElection::new(
+                    &dbp,
+                    &unique_timer_stamper,
+                    LeaderElection::ROLE_BACKGROUND_WORK,
+                )
+                .await,
+            )
+        } else {
+            None
+        };
+        let bulk_ingest = BulkIngest::new(&dbp, &pre_storage_processor, &background_work_election);
+        let index_rebuilder =
+            IndexRebuilder::new(&dbp, &pre_storage_processor, &background_work_election);
+        if let Some(background_work_election) = &background_work_election {
+            IntegrityConsolidationService::new(
+                &ish,
+                &dbp,
+                &integrity_protector,
+                &integrity_validator,
+                background_work_election,
+                &bulk_ingest,
+                &task_supervisor,
+            )
+            .await;
+        }
         // Setup speedy delivery of correlation requests.
-        let correlation_hotlist = CorrelationHotlist::new(app_config, &dbp).await;
-        let consumers = Consumers::new(&dbp, &object_count_tracker, instance_id);
-        let access_control = AccessControl::new(&dbp).await;
-        let metrics = app_config
-            .metrics
-            .enabled()
-            .then(|| MessageBrokerMetrics::new(app_config));
+        let correlation_hotlist = CorrelationHotlist::new(app_config, &dbp, &task_supervisor).await;
+        let access_control = AccessControl::new(&dbp, app_config.tenancy.claim()).await;
+        // Setup caching of read-mostly queries.
+        let event_read_cache = ReadCache::new(
+            app_config.cache.event_time_to_live_micros(),
+            app_config.cache.max_entries(),
+        );
+        let index_read_cache = ReadCache::new(
+            app_config.cache.index_time_to_live_micros(),
+            app_config.cache.max_entries(),
+        );
+        let descriptor_limits = DescriptorLimits::new(
+            app_config.descriptor.max_extractors(),
+            app_config.descriptor.max_schema_size(),
+            app_config.descriptor.max_index_columns(),
+        );
+        let event_archive = EventArchive::new(app_config, instance_id);
+        let capabilities = Capabilities::new(
+            app_config.app_version(),
+            Features::new(
+                app_config.metrics.enabled(),
+                app_config.is_tracing_enabled(),
+                app_config.diagnostics.enabled(),
+                app_config.backend.implementation(),
+                &tyst::encdec::oid::as_string(ish.get_current_oid()),
+                EventIdAlgorithm::ALL
+                    .iter()
+                    .map(|event_id_algorithm| event_id_algorithm.get_name().to_owned())
+                    .collect(),
+                DocumentCanonicalization::NAMES
+                    .iter()
+                    .map(|name| name.to_string())
+                    .collect(),
+                cluster_protocol.get_protocol_version(),
+            ),
+            descriptor_limits.clone(),
+            DeliveryLimits::new(
+                app_config.delivery.max_redeliveries(),
+                app_config
+                    .delivery
+                    .concurrency_limits()
+                    .into_iter()
+                    .collect(),
+            ),
+        );
+        let consumers = Consumers::new(
+            &dbp,
+            &object_count_tracker,
+            &metrics,
+            &task_supervisor,
+            instance_id,
+            app_config.delivery.max_redeliveries(),
+            app_config.delivery.concurrency_limits(),
+            app_config.delivery.cache_budget_bytes(),
+            app_config.delivery.strict_order(),
+            RetryBackoff::new(app_config.delivery.retry_backoff_micros()),
+            app_config.delivery.visibility_timeout_bounds_micros(),
+        );
+        let retention_previewer = RetentionPreviewer::new(&dbp);
+        let event_purger = EventPurger::new(&dbp, &integrity_protector, &event_archive);
+        let consumption_auditor = ConsumptionAuditor::new(&dbp, &ish);
+        let topic_snapshotter = TopicSnapshotter::new(&dbp);
+        let dead_letter_reader = DeadLetterReader::new(&dbp);
+        let consumer_definitions = ConsumerDefinitionRegistry::new(&dbp);
+        let topic_creation_policy = TopicCreationPolicy::from_name(app_config.topics.creation())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Unknown topic creation policy '{}'.",
+                    app_config.topics.creation()
+                )
+            });
+        let dev_fixtures = app_config.dev.fixtures_file().map(|fixtures_file| {
+            DevFixtures::from_file(fixtures_file).unwrap_or_else(|e| panic!("{e}"))
+        });
         //let metrics = MessageBrokerMetrics::new(app_config);
         log::info!("Message broker dependencies has have been created.");
         Arc::new(Self {
             health_ready: AtomicBool::new(false),
+            draining: AtomicBool::new(false),
             dbp,
             trusted_time,
             unique_timer_stamper,
@@ -168,10 +598,38 @@ impl MessageBroker {
             integrity_validator,
             object_count_tracker,
             pre_storage_processor,
+            event_statistics: EventStatistics::new(),
             correlation_hotlist,
             consumers,
             access_control,
+            publish_grants: PublishGrants::new(app_config, &dbp),
+            tenant_quota: TenantQuota::new(app_config.tenancy.max_events_per_second()),
+            backlog_monitor: BacklogMonitor::new(&dbp, app_config),
             metrics,
+            task_supervisor,
+            event_read_cache,
+            index_read_cache,
+            descriptor_limits,
+            event_archive,
+            capabilities,
+            diagnostics_enabled: app_config.diagnostics.enabled(),
+            event_mirror: EventMirror::new(app_config),
+            retention_previewer,
+            consumption_auditor,
+            topic_snapshotter,
+            dead_letter_reader,
+            consumer_definitions,
+            webhook_sender: WebhookSender::new(app_config.delivery.receipt_webhook_hosts()),
+            topic_creation_policy,
+            publish_rejection_log: PublishRejectionLog::new(Self::PUBLISH_REJECTIONS_KEPT),
+            duplicate_tracker: DuplicateTracker::new(Self::DUPLICATE_WINDOW_MICROS),
+            deployment_mode,
+            background_work_election,
+            canary_tracker: CanaryTracker::new(instance_id),
+            bulk_ingest,
+            index_rebuilder,
+            event_purger,
+            dev_fixtures,
         })
         .init(app_config)
     }
@@ -181,6 +639,34 @@ impl MessageBroker {
         let self_clone = Arc::clone(&self);
         let app_config = Arc::clone(app_config);
         tokio::spawn(async move { self_clone.post_init(&app_config).await });
+        if self.deployment_mode.serves_api() {
+            let self_clone = Arc::clone(&self);
+            tokio::spawn(async move { self_clone.deliver_to_webhooks().await });
+        }
+        if (self.deployment_mode.serves_api()
+            && (self.event_read_cache.is_enabled() || self.index_read_cache.is_enabled()))
+            || self.event_archive.is_enabled()
+        {
+            let self_clone = Arc::clone(&self);
+            tokio::spawn(async move { self_clone.follow_event_purges().await });
+        }
+        let canary_topic_ids = app_config.canary.topics();
+        if !canary_topic_ids.is_empty() && self.deployment_mode.serves_api() {
+            for topic_id in &canary_topic_ids {
+                let self_clone = Arc::clone(&self);
+                let topic_id = topic_id.to_owned();
+                tokio::spawn(async move { self_clone.consume_canaries(topic_id).await });
+            }
+            let self_clone = Arc::clone(&self);
+            let interval_micros = app_config.canary.interval_micros();
+            let timeout_micros = app_config.canary.timeout_micros();
+            tokio::spawn(async move {
+                self_clone
+                    .publish_canaries(canary_topic_ids, interval_micros, timeout_micros)
+                    .await
+            });
+        }
+        log::info!("Running in {:?} deployment mode.", self.deployment_mode);
         self
     }
 
@@ -200,6 +686,13 @@ impl MessageBroker {
             }
             tokio::time::sleep(tokio::time::Duration::from_micros(500_000)).await;
         }
+        // Smoothen post-deploy latency spikes by preparing hot paths
+        if self.deployment_mode.serves_api() {
+            self.warm_up(app_config).await;
+        }
+        if let Some(dev_fixtures) = &self.dev_fixtures {
+            self.seed_dev_fixtures(dev_fixtures).await;
+        }
         let ready_ts_micros = fragtale_client::time::get_timestamp_micros();
         self.health_ready.store(true, Ordering::Relaxed);
         log::info!(
@@ -208,6 +701,496 @@ impl MessageBroker {
         );
     }
 
+    /// Perform topic setup checks and populate the event descriptor and
+    /// consumer caches for the configured (or learned) hot topics and
+    /// consumers.
+    ///
+    /// This will give up after the configured timeout, so readiness is never
+    /// blocked indefinitely.
+    async fn warm_up(&self, app_config: &AppConfig) {
+        let configured_topic_ids = app_config.warmup.topics();
+        let consumers = app_config.warmup.consumers();
+        if configured_topic_ids.is_empty() && consumers.is_empty() && !app_config.warmup.learn() {
+            return;
+        }
+        let start_ts_micros = fragtale_client::time::get_timestamp_micros();
+        let topic_count = AtomicU64::default();
+        let consumer_count = AtomicU64::default();
+        let warm_up_future = async {
+            let mut topic_ids = configured_topic_ids;
+            if app_config.warmup.learn() {
+                let mut from = None;
+                loop {
+                    let (known_topic_ids, more) =
+                        self.dbp.topic_facade().get_topic_ids(&from).await;
+                    from = known_topic_ids.last().cloned();
+                    topic_ids.extend(known_topic_ids);
+                    if !more {
+                        break;
+                    }
+                }
+            }
+            for topic_id in &topic_ids {
+                if self.warm_up_topic(topic_id).await {
+                    topic_count.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            for (topic_id, consumer_id) in &consumers {
+                if self
+                    .dbp
+                    .consumer_delivery_facade()
+                    .consumer_get_done_by_id(topic_id, consumer_id)
+                    .await
+                    .is_none()
+                {
+                    if log::log_enabled!(log::Level::Debug) {
+                        log::debug!(
+                            "Skipping warm up of unknown consumer '{consumer_id}' of topic '{topic_id}'."
+                        );
+                    }
+                    continue;
+                }
+                if !self.warm_up_topic(topic_id).await {
+                    continue;
+                }
+                if let Err(e) = self
+                    .consumers
+                    .by_topic_and_consumer_id(topic_id, consumer_id, None, None)
+                    .await
+                {
+                    log::warn!(
+                        "Failed to warm up consumer '{consumer_id}' of topic '{topic_id}': {e}"
+                    );
+                    continue;
+                }
+                consumer_count.fetch_add(1, Ordering::Relaxed);
+            }
+        };
+        let timeout = tokio::time::Duration::from_micros(app_config.warmup.timeout_micros());
+        let completed = tokio::time::timeout(timeout, warm_up_future).await.is_ok();
+        let duration_micros = fragtale_client::time::get_timestamp_micros() - start_ts_micros;
+        let topic_count = topic_count.load(Ordering::Relaxed);
+        let consumer_count = consumer_count.load(Ordering::Relaxed);
+        if completed {
+            log::info!(
+                "Warm-up of {topic_count} topics and {consumer_count} consumers completed after {duration_micros} micros."
+            );
+        } else {
+            log::warn!(
+                "Warm-up did not complete within {timeout:?} after warming up {topic_count} topics and {consumer_count} consumers. Proceeding anyway."
+            );
+        }
+    }
+
+    /// Perform topic setup checks and load the event descriptors of the
+    /// topic.
+    ///
+    /// Return `true` if the topic was warmed up.
+    async fn warm_up_topic(&self, topic_id: &str) -> bool {
+        if let Err(e) = self.dbp.topic_facade().ensure_topic_setup(topic_id).await {
+            log::warn!("Failed to warm up topic '{topic_id}': {e}");
+            return false;
+        }
+        self.event_descriptor_cache.reload_for_topic(topic_id).await;
+        true
+    }
+
+    /// Register the event descriptors and publish the events of the
+    /// developer mode fixtures.
+    ///
+    /// Failures are logged, so a broken fixture does not prevent local
+    /// development against the rest of them.
+    async fn seed_dev_fixtures(&self, dev_fixtures: &DevFixtures) {
+        let identity = ClientIdentity::Internal;
+        for topic_fixture in dev_fixtures.get_topics() {
+            let topic_id = topic_fixture.get_topic_id();
+            if let Some(event_descriptor) = topic_fixture.get_descriptor()
+                && let Err(e) = self
+                    .upsert_topic_event_descriptor(&identity, topic_id, event_descriptor.clone())
+                    .await
+            {
+                log::warn!("Failed to seed event descriptor of topic '{topic_id}': {e}");
+                continue;
+            }
+            let event_documents = topic_fixture.get_event_documents();
+            for event_document in &event_documents {
+                if let Err(e) = self
+                    .publish_event_to_topic(
+                        &identity,
+                        topic_id,
+                        event_document,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        PublishAcknowledgement::Persisted,
+                    )
+                    .await
+                {
+                    log::warn!("Failed to seed event of topic '{topic_id}': {e}");
+                }
+            }
+            log::info!(
+                "Seeded topic '{topic_id}' with {} events.",
+                event_documents.len()
+            );
+        }
+    }
+
+    /// Deliver events to all declared webhook consumers for as long as this
+    /// instance is serving consumers.
+    ///
+    /// Deliveries are coordinated with other instances through the same
+    /// delivery intents as for connected consumers, so each event is only
+    /// POSTed once to a webhook unless the delivery failed.
+    async fn deliver_to_webhooks(self: Arc<Self>) {
+        loop {
+            let mut delivered = 0;
+            if self.is_health_ready() && self.is_serving_consumers() {
+                let webhooks = self.consumer_definitions.webhooks().await;
+                delivered = futures::future::join_all(webhooks.iter().map(
+                    |(topic_id, consumer_definition)| {
+                        self.deliver_to_webhook(topic_id, consumer_definition)
+                    },
+                ))
+                .await
+                .into_iter()
+                .sum();
+            }
+            if delivered == 0 {
+                sleep(tokio::time::Duration::from_micros(
+                    Self::WEBHOOK_IDLE_MICROS,
+                ))
+                .await;
+            }
+        }
+    }
+
+    /// Publish synthetic canary events to each topic at the configured
+    /// interval and count canaries that are not delivered in time as lost.
+    async fn publish_canaries(
+        self: Arc<Self>,
+        topic_ids: Vec<String>,
+        interval_micros: u64,
+        timeout_micros: u64,
+    ) {
+        loop {
+            sleep(tokio::time::Duration::from_micros(interval_micros)).await;
+            if !(self.is_health_ready() && self.is_serving_consumers()) {
+                continue;
+            }
+            for topic_id in &topic_ids {
+                let now_micros = fragtale_client::time::get_timestamp_micros();
+                let (sequence, document) = self.canary_tracker.next_document(topic_id, now_micros);
+                let published = self
+                    .publish_event_to_topic_internal(
+                        Self::CANARY_PUBLISHER,
+                        topic_id,
+                        &document,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        PublishAcknowledgement::Persisted,
+                    )
+                    .await;
+                if let Err(e) = published {
+                    self.canary_tracker.forget(topic_id, sequence);
+                    log::info!("Failed to publish canary event to '{topic_id}': {e}");
+                    if let Some(metrics) = &self.metrics {
+                        metrics.inc_canary_errors(topic_id, "publish");
+                    }
+                } else if let Some(metrics) = &self.metrics {
+                    metrics.inc_canary_events(topic_id);
+                }
+            }
+            let cutoff_micros =
+                fragtale_client::time::get_timestamp_micros().saturating_sub(timeout_micros);
+            for topic_id in self.canary_tracker.expire(cutoff_micros) {
+                log::warn!(
+                    "Canary event in '{topic_id}' was not delivered within {} ms.",
+                    timeout_micros / 1000
+                );
+                if let Some(metrics) = &self.metrics {
+                    metrics.inc_canary_errors(&topic_id, "lost");
+                }
+            }
+        }
+    }
+
+    /// Consume canary events from the topic with the built-in consumer of
+    /// this instance and report the end-to-end latency of own canaries.
+    async fn consume_canaries(self: Arc<Self>, topic_id: String) {
+        let consumer_id = format!(
+            "{}_{}",
+            Self::CANARY_PUBLISHER,
+            self.unique_timer_stamper.get_instance_id()
+        );
+        // Ignore canaries published before this instance started
+        let baseline_ts = Some(fragtale_client::time::get_timestamp_micros());
+        loop {
+            if !(self.is_health_ready() && self.is_serving_consumers()) {
+                sleep(tokio::time::Duration::from_micros(Self::CANARY_IDLE_MICROS)).await;
+                continue;
+            }
+            let next = self
+                .next_event_for_consumer(
+                    &topic_id,
+                    &consumer_id,
+                    baseline_ts,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await;
+            match next {
+                Ok(Some((
+                    encoded_unique_time,
+                    document,
+                    _correlation_token,
+                    delivery_instance_id,
+                    _protected_ts,
+                    _priority,
+                    _delivery_envelope,
+                ))) => {
+                    let confirmed = self
+                        .confirm_event_delivery_by_consumer_id(
+                            &topic_id,
+                            &consumer_id,
+                            encoded_unique_time,
+                            delivery_instance_id,
+                        )
+                        .await;
+                    if let Err(e) = confirmed {
+                        log::info!("Failed to confirm canary event in '{topic_id}': {e}");
+                        if let Some(metrics) = &self.metrics {
+                            metrics.inc_canary_errors(&topic_id, "confirm");
+                        }
+                        continue;
+                    }
+                    let now_micros = fragtale_client::time::get_timestamp_micros();
+                    if let Some(latency_micros) = self
+                        .canary_tracker
+                        .complete(&topic_id, &document, now_micros)
+                        && let Some(metrics) = &self.metrics
+                    {
+                        metrics.report_canary_latency_micros(&topic_id, latency_micros);
+                    }
+                }
+                Ok(None) => {
+                    sleep(tokio::time::Duration::from_micros(Self::CANARY_IDLE_MICROS)).await;
+                }
+                Err(e) => {
+                    log::info!("Failed to consume canary event from '{topic_id}': {e}");
+                    if let Some(metrics) = &self.metrics {
+                        metrics.inc_canary_errors(&topic_id, "delivery");
+                    }
+                    sleep(tokio::time::Duration::from_micros(Self::CANARY_IDLE_MICROS)).await;
+                }
+            }
+        }
+    }
+
+    /// Drop cached reads and compact the archive files of this instance for
+    /// topics where events have been purged or redacted by any instance.
+    ///
+    /// Purges are announced to all instances by their audit event, so each
+    /// instance consumes the [Self::AUDIT_TOPIC_ID] topic for this purpose.
+    async fn follow_event_purges(self: Arc<Self>) {
+        let consumer_id = format!(
+            "{}_{}",
+            Self::EVENT_PURGE_FOLLOWER,
+            self.unique_timer_stamper.get_instance_id()
+        );
+        // Cached reads are never older than the start of this instance
+        let baseline_ts = Some(fragtale_client::time::get_timestamp_micros());
+        loop {
+            if !self.is_health_ready() {
+                sleep(tokio::time::Duration::from_micros(
+                    Self::EVENT_PURGE_FOLLOWER_IDLE_MICROS,
+                ))
+                .await;
+                continue;
+            }
+            let next = self
+                .next_event_for_consumer(
+                    Self::AUDIT_TOPIC_ID,
+                    &consumer_id,
+                    baseline_ts,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await;
+            match next {
+                Ok(Some((
+                    encoded_unique_time,
+                    document,
+                    _correlation_token,
+                    delivery_instance_id,
+                    _protected_ts,
+                    _priority,
+                    _delivery_envelope,
+                ))) => {
+                    let audit_event =
+                        serde_json::from_str::<serde_json::Value>(&document).unwrap_or_default();
+                    if audit_event["action"].as_str() == Some("event_purge")
+                        && let Some(topic_id) = audit_event["topic_id"].as_str()
+                    {
+                        self.event_read_cache
+                            .remove_matching(|(cached_topic_id, _)| cached_topic_id == topic_id);
+                        self.index_read_cache
+                            .remove_matching(|(cached_topic_id, _, _)| cached_topic_id == topic_id);
+                        self.event_archive.compact(topic_id).await;
+                    }
+                    if let Err(e) = self
+                        .confirm_event_delivery_by_consumer_id(
+                            Self::AUDIT_TOPIC_ID,
+                            &consumer_id,
+                            encoded_unique_time,
+                            delivery_instance_id,
+                        )
+                        .await
+                    {
+                        log::info!("Failed to confirm audit event of an event purge: {e}");
+                    }
+                }
+                Ok(None) => {
+                    sleep(tokio::time::Duration::from_micros(
+                        Self::EVENT_PURGE_FOLLOWER_IDLE_MICROS,
+                    ))
+                    .await;
+                }
+                Err(e) => {
+                    log::info!("Failed to consume audit events of event purges: {e}");
+                    sleep(tokio::time::Duration::from_micros(
+                        Self::EVENT_PURGE_FOLLOWER_IDLE_MICROS,
+                    ))
+                    .await;
+                }
+            }
+        }
+    }
+
+    /// Deliver a batch of events to a declared webhook consumer.
+    ///
+    /// A failed delivery ends the batch and the event is retried like any
+    /// other unconfirmed delivery.
+    ///
+    /// Return the number of delivered events.
+    async fn deliver_to_webhook(
+        &self,
+        topic_id: &str,
+        consumer_definition: &ConsumerDefinition,
+    ) -> usize {
+        let ConsumerDefinitionTarget::Webhook { url } = consumer_definition.get_target() else {
+            return 0;
+        };
+        let consumer_id = consumer_definition.get_consumer_id();
+        let document_projection = consumer_definition
+            .get_fields()
+            .and_then(|fields| DocumentProjection::new(fields).ok());
+        // Webhooks have no identity that can be allowed unmasked events
+        let masking_rules = self.get_masking_rules(None, topic_id).await;
+        let mut delivered = 0;
+        while delivered < Self::WEBHOOK_BATCH_SIZE {
+            if consumer_definition.get_max_events_per_second().is_some_and(
+                |max_events_per_second| {
+                    !self.consumer_definitions.try_acquire_delivery(
+                        topic_id,
+                        &consumer_id,
+                        max_events_per_second,
+                    )
+                },
+            ) {
+                break;
+            }
+            let next_event = self
+                .next_event_for_consumer(
+                    topic_id,
+                    &consumer_id,
+                    consumer_definition.get_baseline_ts(),
+                    None,
+                    consumer_definition.get_event_types(),
+                    consumer_definition.get_min_priority(),
+                    consumer_definition.get_filter(),
+                    None,
+                )
+                .await
+                .map(|next_event| {
+                    Self::apply_masking_and_projection(
+                        next_event,
+                        masking_rules.as_deref(),
+                        document_projection.as_ref(),
+                    )
+                });
+            let (
+                encoded_unique_time,
+                document,
+                correlation_token,
+                delivery_instance_id,
+                delivery_envelope,
+            ) = match next_event {
+                Ok(Some((
+                    encoded_unique_time,
+                    document,
+                    correlation_token,
+                    delivery_instance_id,
+                    _prepared_transaction_id,
+                    _priority,
+                    delivery_envelope,
+                ))) => (
+                    encoded_unique_time,
+                    document,
+                    correlation_token,
+                    delivery_instance_id,
+                    delivery_envelope,
+                ),
+                Ok(None) => break,
+                Err(e) => {
+                    log::info!("Failed to get next event for '{topic_id}/{consumer_id}': {e}");
+                    break;
+                }
+            };
+            if !self
+                .webhook_sender
+                .send(
+                    url,
+                    &delivery_envelope,
+                    encoded_unique_time,
+                    &correlation_token,
+                    &document,
+                )
+                .await
+            {
+                break;
+            }
+            if let Err(e) = self
+                .confirm_event_delivery_by_consumer_id(
+                    topic_id,
+                    &consumer_id,
+                    encoded_unique_time,
+                    delivery_instance_id,
+                )
+                .await
+            {
+                log::info!(
+                    "Failed to confirm webhook delivery for '{topic_id}/{consumer_id}': {e}"
+                );
+                break;
+            }
+            delivered += 1;
+        }
+        delivered
+    }
+
     /// Return `true` if the app has started.
     pub fn is_health_started(&self) -> bool {
         self.health_ready.load(Ordering::Relaxed)
@@ -215,7 +1198,27 @@ impl MessageBroker {
 
     /// Return `true` if the app is ready to recieve requests.
     pub fn is_health_ready(&self) -> bool {
-        self.health_ready.load(Ordering::Relaxed) && self.is_health_live()
+        self.health_ready.load(Ordering::Relaxed)
+            && !self.draining.load(Ordering::Relaxed)
+            && self.is_health_live()
+    }
+
+    /// Return `true` if this instance should keep delivering events to
+    /// connected consumers.
+    ///
+    /// When this returns `false`, consumers should be told to reconnect to
+    /// another instance.
+    pub fn is_serving_consumers(&self) -> bool {
+        self.deployment_mode.serves_api()
+            && !self.draining.load(Ordering::Relaxed)
+            && self.is_health_live()
+    }
+
+    /// Return `true` if this instance serves the REST API.
+    ///
+    /// Instances in the `worker` deployment mode only perform background work.
+    pub fn is_serving_api(&self) -> bool {
+        self.deployment_mode.serves_api()
     }
 
     /// Return `true` if the app is functioning as expected and `false` if it
@@ -223,6 +1226,15 @@ impl MessageBroker {
     pub fn is_health_live(&self) -> bool {
         self.trusted_time.is_local_time_within_tolerance()
             && self.unique_timer_stamper.is_instance_id_still_valid()
+            && self.task_supervisor.is_healthy()
+    }
+
+    /// Track a request to `endpoint` that was aborted since its deadline
+    /// passed.
+    pub fn report_deadline_exceeded(&self, endpoint: &str) {
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_deadline_exceeded(endpoint);
+        }
     }
 
     /// Failsafe that terminates the application if it returns.
@@ -253,6 +1265,13 @@ impl MessageBroker {
     /// This is not garanteed to run, so no code can rely on this clean-up to
     /// have happened.
     pub async fn exit_hook(&self) {
+        // Allow connected consumers to be told to reconnect elsewhere
+        self.draining.store(true, Ordering::Relaxed);
+        // Let another instance take over background work right away
+        if let Some(background_work_election) = &self.background_work_election {
+            background_work_election.resign().await;
+        }
+        sleep(tokio::time::Duration::from_micros(Self::DRAIN_GRACE_MICROS)).await;
         self.unique_timer_stamper.free_instance_id().await
     }
 
@@ -264,6 +1283,7 @@ impl MessageBroker {
         topic_id: &str,
         event_descriptor: EventDescriptor,
     ) -> Result<(), MessageBrokerError> {
+        self.ensure_topic_setup(identity, topic_id, true).await?;
         self.access_control
             .assert_allowed_topic_write(identity, topic_id)
             .await?;
@@ -271,12 +1291,122 @@ impl MessageBroker {
             "Event descriptor update of topic '{topic_id}' by '{}' descriptor: '{event_descriptor:?}'.",
             identity.identity_string()
         );
-        self.dbp.topic_facade().ensure_topic_setup(topic_id).await?;
         // Make sue we have the latest version
         self.event_descriptor_cache.reload_for_topic(topic_id).await;
         let latest_opt = self
             .event_descriptor_cache
             .get_event_descriptor_by_topic_latest(topic_id);
+        let event_id_algorithm = event_descriptor
+            .get_event_id_algorithm()
+            .as_deref()
+            .map(|name| {
+                EventIdAlgorithm::from_name(name).ok_or_else(|| {
+                    MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                        "Unsupported event_id algorithm '{name}' for topic '{topic_id}'."
+                    ))
+                })
+            })
+            .transpose()?
+            .unwrap_or_default();
+        if let Some(name) = event_descriptor.get_event_id_collision_policy().as_deref()
+            && EventIdCollisionPolicy::from_name(name).is_none()
+        {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Unsupported event_id collision policy '{name}' for topic '{topic_id}'."
+                )),
+            )?;
+        }
+        if let Some(name) = event_descriptor.get_canonicalization().as_deref()
+            && DocumentCanonicalization::from_name(name).is_none()
+        {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Unsupported canonicalization '{name}' for topic '{topic_id}'."
+                )),
+            )?;
+        }
+        match event_descriptor.get_delivery_receipts() {
+            Some(DeliveryReceiptTarget::Webhook { url })
+                if !url.starts_with("http://") && !url.starts_with("https://") =>
+            {
+                Err(
+                    MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                        "Delivery receipt webhook URL '{url}' of topic '{topic_id}' must use http or https."
+                    )),
+                )?;
+            }
+            Some(DeliveryReceiptTarget::Topic)
+                if DeliveryReceipt::is_receipt_topic_id(topic_id) =>
+            {
+                Err(
+                    MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                        "Receipts of receipt topic '{topic_id}' can't be published to a topic."
+                    )),
+                )?;
+            }
+            _ => {}
+        }
+        if let Some(DeliveryReceiptTarget::Webhook { url }) =
+            event_descriptor.get_delivery_receipts()
+            && !self.webhook_sender.is_allowed_receipt_url(url)
+            && latest_opt.as_ref().is_none_or(|latest| {
+                latest.get_delivery_receipts() != event_descriptor.get_delivery_receipts()
+            })
+        {
+            // Receipts are POSTed from inside the cluster like webhook consumers
+            self.access_control
+                .assert_allowed_admin(identity, "consumers")
+                .await?;
+        }
+        if let Some(event_types) = event_descriptor.get_event_types() {
+            if event_descriptor.get_event_type_field().is_none() {
+                Err(
+                    MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                        "Event types of topic '{topic_id}' require an event type field."
+                    )),
+                )?;
+            }
+            let mut seen = HashSet::new();
+            if let Some(duplicate) = event_types
+                .iter()
+                .map(EventTypeDescriptor::get_event_type)
+                .find(|event_type| !seen.insert(*event_type))
+            {
+                Err(MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Event type '{duplicate}' is described more than once for topic '{topic_id}'."
+                )))?;
+            }
+        }
+        for extractor in event_descriptor.get_all_extractors() {
+            if let Some(condition) = extractor.get_condition() {
+                self.pre_storage_processor
+                    .compile_filter(topic_id, condition)
+                    .map_err(|e| {
+                        MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                            "Condition of extractor '{}' of topic '{topic_id}' is invalid: {e}",
+                            extractor.get_result_name()
+                        ))
+                    })?;
+            }
+        }
+        if let Some(default_values) = event_descriptor.get_default_values() {
+            DocumentMigration::assert_valid_default_values(default_values)?;
+        }
+        if let Some(masking_rules) = event_descriptor.get_masking_rules() {
+            DocumentMasking::assert_valid_masking_rules(masking_rules)?;
+        }
+        self.assert_within_descriptor_limits(topic_id, &event_descriptor)
+            .await?;
+        // Event identifiers of persisted events are never recalculated, so
+        // events published before the first descriptor use the default.
+        if self.event_descriptor_cache.get_event_id_algorithm(topic_id) != event_id_algorithm
+            && (latest_opt.is_some() || self.has_topic_events(topic_id).await)
+        {
+            Err(MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                "Prevented upsert of topic '{topic_id}' event descriptor, since the event_id algorithm cannot be changed once the topic has a descriptor or events."
+            )))?;
+        }
         if let Some(latest) = latest_opt.as_deref() {
             if latest.eq(&event_descriptor) {
                 log::debug!("Event Descriptor already exists exactly as requested. All good.");
@@ -288,6 +1418,10 @@ impl MessageBroker {
                 )))?;
             }
         }
+        // Changing the event descriptor is reserved for owners of the topic
+        self.access_control
+            .assert_allowed_topic_owner_or_admin(identity, topic_id)
+            .await?;
         // Persist new event description
         let inserted = self
             .dbp
@@ -321,13 +1455,16 @@ impl MessageBroker {
             .await
             .into_iter()
             .map(EventDescriptor::from_string)
-            .filter_map(|ed| ed.get_extractors().clone())
-            .flatten()
-            .map(|extractor| {
-                (
-                    extractor.get_result_name().to_owned(),
-                    extractor.get_result_type().to_owned(),
-                )
+            .flat_map(|ed| {
+                ed.get_all_extractors()
+                    .into_iter()
+                    .map(|extractor| {
+                        (
+                            extractor.get_result_name().to_owned(),
+                            extractor.get_result_type().to_owned(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
             })
             .collect::<Vec<_>>();
         self.dbp
@@ -337,217 +1474,4322 @@ impl MessageBroker {
         Ok(())
     }
 
-    /// Publish event to a topic.
+    /// Return the latest version of all shared schema components in the
+    /// schema registry.
+    pub async fn get_shared_schemas(
+        &self,
+        identity: &ClientIdentity,
+    ) -> Result<SharedSchemas, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "schemas")
+            .await?;
+        self.event_descriptor_cache.reload_shared_schemas().await;
+        Ok(SharedSchemas::new(
+            self.event_descriptor_cache
+                .get_shared_schemas()
+                .iter()
+                .map(|shared_schema| shared_schema.as_ref().to_owned())
+                .collect(),
+        ))
+    }
+
+    /// Register a new version of a shared schema component that event schemas
+    /// of any topic can `$ref`erence by its schema identifier.
     ///
-    /// This will also validate event document schema (if any) and extract
-    /// indexed values.
+    /// All event schemas that reference the component are validated with the
+    /// new version once it is registered.
     ///
-    /// Return `CorrelationToken` in serialized form.
-    pub async fn publish_event_to_topic(
+    /// Return the component with the assigned version.
+    pub async fn register_shared_schema(
         &self,
         identity: &ClientIdentity,
-        topic_id: &str,
-        event_document: &str,
-        priority: Option<u8>,
-        descriptor_version: Option<DescriptorVersion>,
-        correlation_token_opt: Option<String>,
-    ) -> Result<String, MessageBrokerError> {
+        shared_schema: SharedSchema,
+    ) -> Result<SharedSchema, MessageBrokerError> {
         self.access_control
-            .assert_allowed_topic_write(identity, topic_id)
-            .await?;
-        let event_ts = self.trusted_time.get_timestamp_micros().ok_or_else(|| {
-            MessageBrokerErrorKind::TrustedTimeError.error_with_msg(format!(
-                "Refusing to accept published event to '{topic_id}' since time cannot be trusted."
-            ))
-        })?;
-        let correlation_token = self
-            .correlation_hotlist
-            .validate_or_protect(correlation_token_opt, event_ts);
-        self.dbp.topic_facade().ensure_topic_setup(topic_id).await?;
-        let priority = priority
-            .map(|priority| std::cmp::max(100, priority))
-            .unwrap_or(100);
-        // Validate schema (if present) and extract data into indexed columns (if available)
-        let (additional_columns, event_descriptor_version) = self
-            .pre_storage_processor
-            .validate_and_extract(topic_id, event_document, descriptor_version)
+            .assert_allowed_admin(identity, "schemas")
             .await?;
-        let unique_time = self
-            .unique_timer_stamper
-            .get_unique_timestamp(event_ts, priority);
-        // Derive integrity protection
-        let protection_ref = self
-            .integrity_protector
-            .derive_protection(topic_id, event_document, &unique_time)
-            .await
-            .as_string();
-        let ret = self
+        let schema_id = shared_schema.get_schema_id().to_owned();
+        if schema_id.is_empty() {
+            Err(MessageBrokerErrorKind::EvenDescriptorError
+                .error_with_msg("Shared schema identifier must not be empty."))?;
+        }
+        if shared_schema.get_schema_data().len() > self.descriptor_limits.get_max_schema_size() {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Shared schema '{schema_id}' exceeds the maximum size of {} bytes.",
+                    self.descriptor_limits.get_max_schema_size()
+                )),
+            )?;
+        }
+        self.pre_storage_processor
+            .assert_shared_schema_compliance(&shared_schema)
+            .map_err(|e| {
+                MessageBrokerErrorKind::EvenDescriptorError
+                    .error_with_msg(format!("Shared schema '{schema_id}' is not usable: {e}"))
+            })?;
+        let latest_opt = self
             .dbp
-            .event_facade()
-            .event_persist(
-                topic_id,
-                TopicEvent::new(
+            .topic_facade()
+            .shared_schema_latest_by_id(&schema_id)
+            .await;
+        if let Some((_version, latest)) = &latest_opt {
+            let latest = SharedSchema::from_string(latest);
+            if latest.get_schema_type() == shared_schema.get_schema_type()
+                && latest.get_schema_data() == shared_schema.get_schema_data()
+            {
+                log::debug!("Shared schema already exists exactly as requested. All good.");
+                return Ok(latest);
+            }
+        }
+        let version = latest_opt.map_or(1, |(version, _latest)| version + 1);
+        let shared_schema = shared_schema.with_version(version);
+        let inserted = self
+            .dbp
+            .topic_facade()
+            .shared_schema_persist(&schema_id, version, &shared_schema.as_string())
+            .await;
+        if !inserted {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Failed to register version {version} of shared schema '{schema_id}'."
+                )),
+            )?;
+        }
+        // Reload cache right away on this instance
+        self.event_descriptor_cache.reload_shared_schemas().await;
+        log::info!(
+            "'{}' registered version {version} of shared schema '{schema_id}'.",
+            identity.identity_string()
+        );
+        Ok(shared_schema)
+    }
+
+    /// Error out with [MessageBrokerErrorKind::EvenDescriptorError] if the
+    /// event descriptor exceeds any of the configured [DescriptorLimits].
+    async fn assert_within_descriptor_limits(
+        &self,
+        topic_id: &str,
+        event_descriptor: &EventDescriptor,
+    ) -> Result<(), MessageBrokerError> {
+        let limits = &self.descriptor_limits;
+        let extractors = event_descriptor.get_all_extractors();
+        if extractors.len() > limits.get_max_extractors() {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Event descriptor of topic '{topic_id}' has {} extractors, but at most {} are allowed.",
+                    extractors.len(),
+                    limits.get_max_extractors()
+                )),
+            )?;
+        }
+        if let Some(event_schema) = std::iter::once(event_descriptor.get_event_schema())
+            .chain(
+                event_descriptor
+                    .get_event_types()
+                    .iter()
+                    .flatten()
+                    .map(EventTypeDescriptor::get_event_schema),
+            )
+            .flatten()
+            .find(|event_schema| {
+                event_schema.get_schema_data().len() > limits.get_max_schema_size()
+            })
+        {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Schema '{}' of topic '{topic_id}' exceeds the maximum size of {} bytes.",
+                    event_schema.get_schema_id(),
+                    limits.get_max_schema_size()
+                )),
+            )?;
+        }
+        // Indexed columns of previous versions are never removed
+        let mut index_columns = self
+            .dbp
+            .topic_facade()
+            .event_descriptors_by_topic_id(topic_id, None)
+            .await
+            .into_iter()
+            .map(EventDescriptor::from_string)
+            .flat_map(|ed| {
+                ed.get_all_extractors()
+                    .into_iter()
+                    .map(|extractor| extractor.get_result_name().to_owned())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<HashSet<_>>();
+        index_columns.extend(
+            extractors
+                .iter()
+                .map(|extractor| extractor.get_result_name().to_owned()),
+        );
+        if index_columns.len() > limits.get_max_index_columns() {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Topic '{topic_id}' would have {} indexed columns, but at most {} are allowed.",
+                    index_columns.len(),
+                    limits.get_max_index_columns()
+                )),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Return the capabilities and limits of this message broker.
+    pub async fn get_capabilities(
+        &self,
+        identity: &ClientIdentity,
+    ) -> Result<Capabilities, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "capabilities")
+            .await?;
+        Ok(self.capabilities.clone())
+    }
+
+    /// Return the latest event description of a topic (if any).
+    ///
+    /// This allows consumers to validate incoming event documents using the
+    /// same schema as the publishers.
+    pub async fn get_topic_event_descriptor(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<Option<EventDescriptor>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        Ok(self
+            .event_descriptor_cache
+            .get_event_descriptor_by_topic_latest(topic_id)
+            .as_deref()
+            .cloned())
+    }
+
+    /// Return the differences between two versions of the event description
+    /// of a topic.
+    ///
+    /// The newer version defaults to the latest registered version when `to`
+    /// is absent. Returns `None` if either version is unknown.
+    pub async fn get_topic_event_descriptor_diff(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        from: &DescriptorVersion,
+        to: Option<&DescriptorVersion>,
+    ) -> Result<Option<DescriptorDiff>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        let Some(from_descriptor) = self
+            .event_descriptor_cache
+            .get_event_descriptor_by_topic_and_version(topic_id, from)
+            .await
+        else {
+            return Ok(None);
+        };
+        let to_descriptor = if let Some(to) = to {
+            self.event_descriptor_cache
+                .get_event_descriptor_by_topic_and_version(topic_id, to)
+                .await
+        } else {
+            self.event_descriptor_cache
+                .get_event_descriptor_by_topic_latest(topic_id)
+        };
+        Ok(to_descriptor.map(|to_descriptor| {
+            DescriptorDiff::between(topic_id, &from_descriptor, &to_descriptor)
+        }))
+    }
+
+    /// Return contract test fixtures generated from a version of the event
+    /// description of a topic.
+    ///
+    /// The version defaults to the latest registered version when absent.
+    /// Returns `None` if the version is unknown.
+    pub async fn get_topic_contract_fixtures(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        version: Option<&DescriptorVersion>,
+    ) -> Result<Option<ContractFixtures>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        let event_descriptor = if let Some(version) = version {
+            self.event_descriptor_cache
+                .get_event_descriptor_by_topic_and_version(topic_id, version)
+                .await
+        } else {
+            self.event_descriptor_cache
+                .get_event_descriptor_by_topic_latest(topic_id)
+        };
+        event_descriptor
+            .map(|event_descriptor| {
+                self.pre_storage_processor
+                    .generate_contract_fixtures(topic_id, &event_descriptor)
+            })
+            .transpose()
+    }
+
+    /// Return all registered versions of the event description of a topic
+    /// from oldest to latest.
+    pub async fn get_topic_event_descriptor_history(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<DescriptorHistory, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        let mut encoded_versions = self
+            .dbp
+            .topic_facade()
+            .event_descriptors_by_topic_id(topic_id, None)
+            .await
+            .into_iter()
+            .map(EventDescriptor::from_string)
+            .map(|event_descriptor| event_descriptor.get_version())
+            .collect::<Vec<_>>();
+        encoded_versions.sort_unstable();
+        encoded_versions.dedup();
+        Ok(DescriptorHistory::new(
+            topic_id,
+            encoded_versions
+                .into_iter()
+                .map(DescriptorVersion::from_encoded)
+                .map(|version| {
+                    format!(
+                        "{}.{}.{}",
+                        version.get_major(),
+                        version.get_minor(),
+                        version.get_patch()
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    /// Publish event to a topic.
+    ///
+    /// This will also validate event document schema (if any) and extract
+    /// indexed values.
+    ///
+    /// When `expires_ts` (epoch microseconds) is present, the event will not
+    /// be delivered to consumers after this deadline.
+    ///
+    /// When `deliver_after_micros` (epoch microseconds) is present, the event
+    /// will not be delivered to consumers before this time.
+    ///
+    /// The `acknowledgement` level determines how far the publishing has
+    /// progressed when this returns. See [PublishAcknowledgement].
+    ///
+    /// Return `CorrelationToken` in serialized form.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn publish_event_to_topic(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        event_document: &str,
+        priority: Option<u8>,
+        expires_ts: Option<u64>,
+        deliver_after_micros: Option<u64>,
+        descriptor_version: Option<DescriptorVersion>,
+        correlation_token_opt: Option<String>,
+        acknowledgement: PublishAcknowledgement,
+    ) -> Result<String, MessageBrokerError> {
+        let publisher = identity.identity_string();
+        self.assert_allowed_publish_within_quota(identity, topic_id, 1, priority)
+            .await?;
+        self.publish_event_to_topic_internal(
+            publisher,
+            topic_id,
+            event_document,
+            priority,
+            expires_ts,
+            deliver_after_micros,
+            descriptor_version,
+            correlation_token_opt,
+            acknowledgement,
+        )
+        .await
+    }
+
+    /// Publish a batch of events to a topic.
+    ///
+    /// Every event document is validated like in
+    /// [Self::publish_event_to_topic] and nothing is published unless all of
+    /// them are valid. The events get consecutive unique times in the order
+    /// of `event_documents` and are persisted together, but not atomically.
+    /// See [fragtale_dbp::dbp::facades::EventFacade::events_persist].
+    ///
+    /// A batch may hold at most [Self::PUBLISH_BATCH_MAX_EVENTS] events.
+    ///
+    /// Return the `CorrelationToken`s in serialized form in the order of
+    /// `event_documents`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn publish_events_to_topic(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        event_documents: &[String],
+        priority: Option<u8>,
+        expires_ts: Option<u64>,
+        deliver_after_micros: Option<u64>,
+        descriptor_version: Option<DescriptorVersion>,
+        acknowledgement: PublishAcknowledgement,
+    ) -> Result<Vec<String>, MessageBrokerError> {
+        let publisher = identity.identity_string();
+        Self::assert_deliverable_window(expires_ts, deliver_after_micros)?;
+        if event_documents.len() > Self::PUBLISH_BATCH_MAX_EVENTS {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "A batch of events may not hold more than {} events.",
+                    Self::PUBLISH_BATCH_MAX_EVENTS
+                )),
+            )?;
+        }
+        self.assert_allowed_publish_within_quota(
+            identity,
+            topic_id,
+            event_documents.len(),
+            priority,
+        )
+        .await?;
+        if event_documents.is_empty() {
+            return Ok(Vec::new());
+        }
+        let event_ts = self.get_trusted_event_ts(publisher, topic_id)?;
+        self.ensure_topic_setup_for_publish(publisher, topic_id)
+            .await?;
+        let mut validated_events = Vec::with_capacity(event_documents.len());
+        for event_document in event_documents {
+            validated_events.push(
+                self.validate_event(
+                    publisher,
+                    topic_id,
                     event_document,
                     priority,
-                    &protection_ref,
-                    &correlation_token,
-                    additional_columns,
-                    event_descriptor_version
-                        .as_ref()
-                        .map(DescriptorVersion::as_encoded),
-                    unique_time,
-                ),
+                    descriptor_version,
+                    None,
+                    event_ts,
+                )
+                .await?,
+            );
+        }
+        // Uncorrelated events of a batch all get the same priority
+        let (unique_times, is_clock_stall_fallback) = self
+            .unique_timer_stamper
+            .get_unique_timestamps(validated_events[0].priority, validated_events.len())
+            .inspect_err(|e| self.record_unique_time_refusal(publisher, topic_id, e))?;
+        if is_clock_stall_fallback && let Some(metrics) = &self.metrics {
+            metrics.inc_unique_time_fallbacks(topic_id);
+        }
+        let mut topic_events = Vec::with_capacity(validated_events.len());
+        for (validated_event, unique_time) in validated_events.iter().zip(unique_times.iter()) {
+            topic_events.push(
+                self.protect_event(
+                    topic_id,
+                    validated_event,
+                    expires_ts,
+                    deliver_after_micros,
+                    *unique_time,
+                )
+                .await,
+            );
+        }
+        let archived_events = if self.event_archive.is_enabled() {
+            topic_events
+                .iter()
+                .map(ArchivedEvent::from_topic_event)
+                .collect()
+        } else {
+            Vec::default()
+        };
+        let ret = if acknowledgement == PublishAcknowledgement::Received {
+            let dbp = Arc::clone(&self.dbp);
+            let event_archive = Arc::clone(&self.event_archive);
+            let metrics = self.metrics.clone();
+            let topic_id = topic_id.to_owned();
+            tokio::spawn(async move {
+                // The publisher has already been acknowledged
+                if Self::persist_topic_events(&dbp, &metrics, &topic_id, topic_events)
+                    .await
+                    .inspect_err(|e| log::warn!("Lost acknowledged batch of events: {e}"))
+                    .is_ok()
+                {
+                    event_archive.append(&topic_id, archived_events).await;
+                }
+            });
+            validated_events
+                .iter()
+                .map(|validated_event| validated_event.correlation_token.to_owned())
+                .collect()
+        } else {
+            let ret = Self::persist_topic_events(&self.dbp, &self.metrics, topic_id, topic_events)
+                .await
+                .inspect_err(|e| {
+                    self.record_publish_rejection(
+                        topic_id,
+                        publisher,
+                        PublishRejectionReason::Backend,
+                        e,
+                    )
+                })?;
+            self.event_archive.append(topic_id, archived_events).await;
+            ret
+        };
+        for (validated_event, unique_time) in validated_events.iter().zip(unique_times.iter()) {
+            self.track_published_event(publisher, topic_id, validated_event, *unique_time);
+        }
+        if acknowledgement == PublishAcknowledgement::Indexed {
+            for unique_time in unique_times {
+                self.await_event_indexed(topic_id, unique_time).await?;
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Issue a pre-signed grant that allows the holder to publish up to
+    /// `max_events` (default 1) events of at most `max_event_bytes` each to
+    /// the topic during `ttl_seconds` (default 15 minutes) without any other
+    /// credentials.
+    ///
+    /// Requires that the `identity` is allowed to publish to the topic.
+    pub async fn create_publish_grant(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        ttl_seconds: Option<u64>,
+        max_events: Option<u32>,
+        max_event_bytes: Option<u64>,
+    ) -> Result<IssuedPublishGrant, MessageBrokerError> {
+        self.assert_allowed_publish(identity, topic_id).await?;
+        let publish_grant = self.publish_grants.issue(
+            identity,
+            self.access_control.get_tenant(identity)?,
+            topic_id,
+            ttl_seconds,
+            max_events,
+            max_event_bytes,
+        )?;
+        self.publish_audit_event(
+            identity,
+            "publish_grant_created",
+            topic_id,
+            serde_json::json!({
+                "grant_uid": publish_grant.get_uid(),
+                "expires_micros": publish_grant.get_expires_micros(),
+                "max_events": publish_grant.get_max_events(),
+                "max_event_bytes": publish_grant.get_max_event_bytes(),
+            }),
+        )
+        .await;
+        let url = format!(
+            "/topics/{topic_id}/events/granted?grant={}",
+            publish_grant.as_string()
+        );
+        Ok(IssuedPublishGrant::new(&publish_grant, &url))
+    }
+
+    /// Publish an event to a topic using a pre-signed grant from
+    /// [Self::create_publish_grant] instead of a client identity.
+    ///
+    /// The event is published on behalf of the issuer of the grant and each
+    /// attempt consumes one of the uses of the grant. The issuer must still be
+    /// allowed to publish to the topic and the publish counts towards the
+    /// quota of the issuer's tenant, just like the issuer's own publishes.
+    ///
+    /// Return `CorrelationToken` in serialized form.
+    pub async fn publish_event_with_grant(
+        &self,
+        grant: &str,
+        topic_id: &str,
+        event_document: &str,
+    ) -> Result<String, MessageBrokerError> {
+        let publish_grant = self
+            .publish_grants
+            .consume(grant, topic_id, event_document.len())
+            .await
+            .inspect_err(|e| {
+                self.record_publish_rejection(
+                    topic_id,
+                    Self::PUBLISH_GRANT_PUBLISHER,
+                    PublishRejectionReason::Authz,
+                    e,
+                )
+            })?;
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "Publishing to '{topic_id}' with grant '{}' issued by '{}'.",
+                publish_grant.get_uid(),
+                publish_grant.get_issuer()
+            );
+        }
+        let issuer = ClientIdentity::from_publish_grant(&publish_grant);
+        self.assert_allowed_publish_within_quota(&issuer, topic_id, 1, None)
+            .await?;
+        self.publish_event_to_topic_internal(
+            publish_grant.get_issuer(),
+            topic_id,
+            event_document,
+            None,
+            None,
+            None,
+            None,
+            None,
+            PublishAcknowledgement::Persisted,
+        )
+        .await
+    }
+
+    /// Ensure that an event that may not be delivered before
+    /// `deliver_after_micros` can be delivered before it expires at
+    /// `expires_ts`.
+    fn assert_deliverable_window(
+        expires_ts: Option<u64>,
+        deliver_after_micros: Option<u64>,
+    ) -> Result<(), MessageBrokerError> {
+        if let Some((expires_ts, deliver_after_micros)) = expires_ts.zip(deliver_after_micros)
+            && deliver_after_micros >= expires_ts
+        {
+            Err(MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                "The event would expire at {expires_ts} before it may be delivered at {deliver_after_micros} epoch microseconds."
+            )))?;
+        }
+        Ok(())
+    }
+
+    /// Ensure that the `identity` is allowed to publish `count` events to the
+    /// topic, that the publisher's tenant has quota left for them and that
+    /// events of the `priority` are not shed due to the topic's backlog.
+    ///
+    /// Failures are recorded as publish rejections.
+    async fn assert_allowed_publish_within_quota(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        count: usize,
+        priority: Option<u8>,
+    ) -> Result<(), MessageBrokerError> {
+        let publisher = identity.identity_string();
+        if let Err(e) = self.assert_allowed_publish(identity, topic_id).await {
+            let reason = match e.kind() {
+                MessageBrokerErrorKind::AuthenticationFailure
+                | MessageBrokerErrorKind::Unauthorized
+                | MessageBrokerErrorKind::MalformedIdentifier => PublishRejectionReason::Authz,
+                _ => PublishRejectionReason::Backend,
+            };
+            self.record_publish_rejection(topic_id, publisher, reason, &e);
+            Err(e)?;
+        }
+        if let Some(tenant) = self.access_control.get_tenant(identity)?
+            && !self.tenant_quota.try_acquire(
+                tenant,
+                count as u64,
+                fragtale_client::time::get_timestamp_micros(),
             )
-            .await;
-        self.object_count_tracker
-            .inc(topic_id, &ObjectCountType::Events);
-        if let Some(metrics) = &self.metrics {
-            metrics.inc_published_events(topic_id, event_document.len());
+        {
+            let e = MessageBrokerErrorKind::QuotaExceeded.error_with_msg(format!(
+                "Tenant '{tenant}' exceeded the quota of published events per second."
+            ));
+            self.record_publish_rejection(
+                topic_id,
+                publisher,
+                PublishRejectionReason::RateLimit,
+                &e,
+            );
+            Err(e)?;
+        }
+        // Events published without a priority are most important and never shed
+        if self
+            .backlog_monitor
+            .is_shed(topic_id, priority.unwrap_or(100))
+        {
+            let e = MessageBrokerErrorKind::QuotaExceeded.error_with_msg(format!(
+                "Topic '{topic_id}' has a critical backlog and only accepts events of higher priority."
+            ));
+            self.record_publish_rejection(topic_id, publisher, PublishRejectionReason::Backlog, &e);
+            Err(e)?;
+        }
+        Ok(())
+    }
+
+    /// Return the name of the last known backlog state (`ok`, `warn` or
+    /// `critical`) of the topic's primary consumer or `None` if backlogs are
+    /// not signalled to producers.
+    pub fn get_backlog_state(&self, topic_id: &str) -> Option<&'static str> {
+        self.backlog_monitor
+            .get_backlog_state(topic_id)
+            .as_ref()
+            .map(BacklogState::as_str)
+    }
+
+    /// Ensure that the topic exists, or may be created, and that the
+    /// `identity` is allowed to publish to it.
+    async fn assert_allowed_publish(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        self.access_control
+            .assert_allowed_topic_write(identity, topic_id)
+            .await
+    }
+
+    /// Publish event on behalf of `publisher` without checking authorization.
+    #[allow(clippy::too_many_arguments)]
+    async fn publish_event_to_topic_internal(
+        &self,
+        publisher: &str,
+        topic_id: &str,
+        event_document: &str,
+        priority: Option<u8>,
+        expires_ts: Option<u64>,
+        deliver_after_micros: Option<u64>,
+        descriptor_version: Option<DescriptorVersion>,
+        correlation_token_opt: Option<String>,
+        acknowledgement: PublishAcknowledgement,
+    ) -> Result<String, MessageBrokerError> {
+        Self::assert_deliverable_window(expires_ts, deliver_after_micros)?;
+        let event_ts = self.get_trusted_event_ts(publisher, topic_id)?;
+        self.ensure_topic_setup_for_publish(publisher, topic_id)
+            .await?;
+        let validated_event = self
+            .validate_event(
+                publisher,
+                topic_id,
+                event_document,
+                priority,
+                descriptor_version,
+                correlation_token_opt,
+                event_ts,
+            )
+            .await?;
+        let (unique_time, is_clock_stall_fallback) = self
+            .unique_timer_stamper
+            .get_unique_timestamp(validated_event.priority)
+            .inspect_err(|e| self.record_unique_time_refusal(publisher, topic_id, e))?;
+        if is_clock_stall_fallback && let Some(metrics) = &self.metrics {
+            metrics.inc_unique_time_fallbacks(topic_id);
+        }
+        let topic_event = self
+            .protect_event(
+                topic_id,
+                &validated_event,
+                expires_ts,
+                deliver_after_micros,
+                unique_time,
+            )
+            .await;
+        let archived_events = self
+            .event_archive
+            .is_enabled()
+            .then(|| vec![ArchivedEvent::from_topic_event(&topic_event)])
+            .unwrap_or_default();
+        let ret = if acknowledgement == PublishAcknowledgement::Received {
+            let dbp = Arc::clone(&self.dbp);
+            let event_archive = Arc::clone(&self.event_archive);
+            let metrics = self.metrics.clone();
+            let topic_id = topic_id.to_owned();
+            tokio::spawn(async move {
+                // The publisher has already been acknowledged
+                if Self::persist_topic_event(&dbp, &metrics, &topic_id, topic_event)
+                    .await
+                    .inspect_err(|e| log::warn!("Lost acknowledged event: {e}"))
+                    .is_ok()
+                {
+                    event_archive.append(&topic_id, archived_events).await;
+                }
+            });
+            validated_event.correlation_token.to_owned()
+        } else {
+            let ret = Self::persist_topic_event(&self.dbp, &self.metrics, topic_id, topic_event)
+                .await
+                .inspect_err(|e| {
+                    self.record_publish_rejection(
+                        topic_id,
+                        publisher,
+                        PublishRejectionReason::Backend,
+                        e,
+                    )
+                })?;
+            self.event_archive.append(topic_id, archived_events).await;
+            ret
+        };
+        self.track_published_event(publisher, topic_id, &validated_event, unique_time);
+        if acknowledgement == PublishAcknowledgement::Indexed {
+            self.await_event_indexed(topic_id, unique_time).await?;
+        }
+        Ok(ret)
+    }
+
+    /// Return the current trusted time for a new event.
+    ///
+    /// Failures are recorded as publish rejections.
+    fn get_trusted_event_ts(
+        &self,
+        publisher: &str,
+        topic_id: &str,
+    ) -> Result<u64, MessageBrokerError> {
+        self.trusted_time
+            .get_timestamp_micros()
+            .ok_or_else(|| {
+                MessageBrokerErrorKind::TrustedTimeError.error_with_msg(format!(
+                    "Refusing to accept published event to '{topic_id}' since time cannot be trusted."
+                ))
+            })
+            .inspect_err(|e| {
+                self.record_publish_rejection(
+                    topic_id,
+                    publisher,
+                    PublishRejectionReason::TimeNotTrusted,
+                    e,
+                )
+            })
+    }
+
+    /// Ensure that the topic is set up in the database before publishing.
+    ///
+    /// Failures are recorded as publish rejections.
+    async fn ensure_topic_setup_for_publish(
+        &self,
+        publisher: &str,
+        topic_id: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.dbp
+            .topic_facade()
+            .ensure_topic_setup(topic_id)
+            .await
+            .inspect_err(|e| {
+                self.record_publish_rejection(
+                    topic_id,
+                    publisher,
+                    PublishRejectionReason::Backend,
+                    e,
+                )
+            })
+    }
+
+    /// Canonicalize and validate an event document, extract indexed values
+    /// and resolve its correlation token and priority.
+    ///
+    /// Failures are recorded as publish rejections and invalid documents are
+    /// kept in the reject store of the topic (if enabled).
+    #[allow(clippy::too_many_arguments)]
+    async fn validate_event(
+        &self,
+        publisher: &str,
+        topic_id: &str,
+        event_document: &str,
+        priority: Option<u8>,
+        descriptor_version: Option<DescriptorVersion>,
+        correlation_token_opt: Option<String>,
+        event_ts: u64,
+    ) -> Result<ValidatedEvent, MessageBrokerError> {
+        let requested_correlation_token = correlation_token_opt.clone();
+        let (correlation_token, request_priority) = self.correlation_hotlist.validate_or_protect(
+            topic_id,
+            correlation_token_opt,
+            event_ts,
+            priority,
+        );
+        // Canonicalize before anything is derived from the document
+        let event_document = self
+            .event_descriptor_cache
+            .get_canonicalization(topic_id)
+            .canonicalize(topic_id, event_document)
+            .inspect_err(|e| {
+                self.record_publish_rejection(
+                    topic_id,
+                    publisher,
+                    PublishRejectionReason::Schema,
+                    e,
+                )
+            })?
+            .into_owned();
+        let event_id = self
+            .event_descriptor_cache
+            .get_event_id_algorithm(topic_id)
+            .event_id_from_document(&event_document);
+        let requested_priority = priority;
+        // Correlated replies inherit the priority of the request unless overridden
+        let priority = priority
+            .or(request_priority)
+            .map(|priority| std::cmp::min(100, priority))
+            .unwrap_or(100);
+        // Validate schema (if present) and extract data into indexed columns (if available)
+        let (mut additional_columns, event_descriptor_version) = match self
+            .pre_storage_processor
+            .validate_and_extract(topic_id, &event_document, descriptor_version)
+            .await
+        {
+            Ok(validated) => validated,
+            Err(e) => {
+                self.record_publish_rejection(
+                    topic_id,
+                    publisher,
+                    PublishRejectionReason::Schema,
+                    &e,
+                );
+                self.reject_event(
+                    topic_id,
+                    RejectedEvent::new(
+                        &event_id,
+                        event_ts,
+                        &event_document,
+                        requested_priority,
+                        descriptor_version
+                            .as_ref()
+                            .map(DescriptorVersion::as_encoded),
+                        requested_correlation_token,
+                        publisher,
+                        &e.to_string(),
+                    ),
+                )
+                .await;
+                Err(e)?
+            }
+        };
+        self.assert_no_event_id_collision(topic_id, &event_id, &event_document)
+            .await
+            .inspect_err(|e| {
+                self.record_publish_rejection(
+                    topic_id,
+                    publisher,
+                    PublishRejectionReason::Integrity,
+                    e,
+                )
+            })?;
+        self.event_statistics
+            .record(topic_id, &event_document, &additional_columns);
+        // Indexed columns are populated after the bulk ingest window instead
+        if self.bulk_ingest.is_deferring(topic_id).await {
+            additional_columns.clear();
+        }
+        Ok(ValidatedEvent {
+            event_id,
+            event_document,
+            priority,
+            correlation_token,
+            additional_columns,
+            event_descriptor_version,
+        })
+    }
+
+    /// Track the refusal to stamp a new event with a unique time.
+    fn record_unique_time_refusal(&self, publisher: &str, topic_id: &str, e: &MessageBrokerError) {
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_unique_time_refusals(topic_id);
+        }
+        self.record_publish_rejection(topic_id, publisher, PublishRejectionReason::RateLimit, e);
+    }
+
+    /// Derive integrity protection of a validated event at its unique time.
+    async fn protect_event(
+        &self,
+        topic_id: &str,
+        validated_event: &ValidatedEvent,
+        expires_ts: Option<u64>,
+        deliver_after_micros: Option<u64>,
+        unique_time: UniqueTime,
+    ) -> TopicEvent {
+        let protection_ref = self
+            .integrity_protector
+            .derive_protection(topic_id, &validated_event.event_document, &unique_time)
+            .await
+            .as_string();
+        TopicEvent::new(
+            &validated_event.event_id,
+            &validated_event.event_document,
+            validated_event.priority,
+            expires_ts,
+            deliver_after_micros,
+            &protection_ref,
+            &validated_event.correlation_token,
+            validated_event.additional_columns.clone(),
+            validated_event
+                .event_descriptor_version
+                .as_ref()
+                .map(DescriptorVersion::as_encoded),
+            unique_time,
+        )
+    }
+
+    /// Account for a published event and mirror it (if enabled).
+    fn track_published_event(
+        &self,
+        publisher: &str,
+        topic_id: &str,
+        validated_event: &ValidatedEvent,
+        unique_time: UniqueTime,
+    ) {
+        self.object_count_tracker
+            .inc(topic_id, &ObjectCountType::Events);
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_published_events(topic_id, validated_event.event_document.len());
+        }
+        if self.duplicate_tracker.record(
+            topic_id,
+            publisher,
+            &validated_event.event_id,
+            fragtale_client::time::get_timestamp_micros(),
+        ) {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!(
+                    "'{publisher}' published event_id '{}' to '{topic_id}' again.",
+                    validated_event.event_id
+                );
+            }
+            if let Some(metrics) = &self.metrics {
+                metrics.inc_duplicate_events(topic_id);
+            }
+        }
+        self.event_mirror.mirror(
+            topic_id,
+            &validated_event.event_id,
+            unique_time.as_encoded(),
+            publisher,
+            &validated_event.event_document,
+        );
+    }
+
+    /// Persist the event and track if it was persisted on the first try,
+    /// after retries or not at all.
+    ///
+    /// Return the correlation token of the persisted event.
+    async fn persist_topic_event(
+        dbp: &Arc<DatabaseProvider>,
+        metrics: &Option<Arc<MessageBrokerMetrics>>,
+        topic_id: &str,
+        topic_event: TopicEvent,
+    ) -> Result<String, MessageBrokerError> {
+        let result = dbp
+            .event_facade()
+            .event_persist(topic_id, topic_event)
+            .await;
+        if let Some(metrics) = metrics {
+            metrics.inc_event_persists(topic_id, result.as_ref().ok().map(|(_, retries)| *retries));
+        }
+        result.map(|(correlation_token, _retries)| correlation_token)
+    }
+
+    /// Persist a batch of events and track the outcome for every event.
+    ///
+    /// Return the correlation tokens of the persisted events.
+    async fn persist_topic_events(
+        dbp: &Arc<DatabaseProvider>,
+        metrics: &Option<Arc<MessageBrokerMetrics>>,
+        topic_id: &str,
+        topic_events: Vec<TopicEvent>,
+    ) -> Result<Vec<String>, MessageBrokerError> {
+        let count = topic_events.len();
+        let result = dbp
+            .event_facade()
+            .events_persist(topic_id, topic_events)
+            .await;
+        if let Some(metrics) = metrics {
+            let retries = result.as_ref().ok().map(|(_, retries)| *retries);
+            for _ in 0..count {
+                metrics.inc_event_persists(topic_id, retries);
+            }
+        }
+        result.map(|(correlation_tokens, _retries)| correlation_tokens)
+    }
+
+    /// Wait until the persisted event shows up in its bucket, where
+    /// consumers of the topic look for new events.
+    ///
+    /// Fails with [MessageBrokerErrorKind::DeadlineExceeded] if the event
+    /// isn't visible before the request deadline or
+    /// [Self::INDEXED_ACK_MAX_WAIT_MICROS].
+    async fn await_event_indexed(
+        &self,
+        topic_id: &str,
+        unique_time: UniqueTime,
+    ) -> Result<(), MessageBrokerError> {
+        let observed = async {
+            let mut interval_micros = Self::INDEXED_ACK_POLL_MIN_MICROS;
+            loop {
+                let (entries, _more) = self
+                    .dbp
+                    .event_facade()
+                    .events_by_bucket(
+                        topic_id,
+                        unique_time.get_bucket(),
+                        Some(UniqueTime::from(unique_time.as_encoded().saturating_sub(1))),
+                        1,
+                    )
+                    .await;
+                if entries
+                    .iter()
+                    .any(|(other, _event_id, _descriptor_version)| *other == unique_time)
+                {
+                    return;
+                }
+                sleep(tokio::time::Duration::from_micros(interval_micros)).await;
+                interval_micros = (interval_micros * 2).min(Self::INDEXED_ACK_POLL_MAX_MICROS);
+            }
+        };
+        tokio::time::timeout(
+            tokio::time::Duration::from_micros(Self::INDEXED_ACK_MAX_WAIT_MICROS),
+            RequestDeadline::within(observed),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            Err(
+                MessageBrokerErrorKind::DeadlineExceeded.error_with_msg(format!(
+                    "Event was persisted in '{topic_id}', but did not become visible to consumers in time."
+                )),
+            )
+        })
+    }
+
+    /// Look for an already persisted event with the same event identifier, but
+    /// a different document, when required by the topic's collision policy.
+    async fn assert_no_event_id_collision(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        event_document: &str,
+    ) -> Result<(), MessageBrokerError> {
+        let policy = self
+            .event_descriptor_cache
+            .get_event_id_collision_policy(topic_id);
+        if policy == EventIdCollisionPolicy::Ignore {
+            return Ok(());
+        }
+        let collision = self
+            .dbp
+            .event_facade()
+            .event_by_id(topic_id, event_id)
+            .await
+            .is_some_and(|existing| existing.get_document() != event_document);
+        if !collision {
+            return Ok(());
+        }
+        log::error!(
+            "Integrity alert: Different documents map to event_id '{event_id}' in topic '{topic_id}'."
+        );
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_event_id_collisions(topic_id);
+        }
+        if policy == EventIdCollisionPolicy::Reject {
+            Err(
+                MessageBrokerErrorKind::IntegrityProtectionError.error_with_msg(format!(
+                    "Refusing to accept published event to '{topic_id}' since a different document has the same event_id."
+                )),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Count the rejected publish and keep it among the recent rejections.
+    fn record_publish_rejection(
+        &self,
+        topic_id: &str,
+        publisher: &str,
+        reason: PublishRejectionReason,
+        e: &MessageBrokerError,
+    ) {
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_publish_rejections(topic_id, reason.as_str());
+        }
+        self.publish_rejection_log.record(PublishRejection::new(
+            topic_id,
+            reason,
+            fragtale_client::time::get_timestamp_micros(),
+            publisher,
+            &e.to_string(),
+        ));
+    }
+
+    /// Return the most recently rejected publishes on this instance,
+    /// optionally limited to a single topic.
+    ///
+    /// Requires authorization to the administrative function `rejections`.
+    pub async fn get_publish_rejections(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: Option<&str>,
+    ) -> Result<PublishRejections, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "rejections")
+            .await?;
+        Ok(PublishRejections::new(
+            self.unique_timer_stamper.get_instance_id(),
+            self.publish_rejection_log.get_recent(topic_id),
+        ))
+    }
+
+    /// Return duplicate events recently published to the topic on this
+    /// instance and the publishers of the most duplicates.
+    ///
+    /// Requires authorization to the administrative function `stats`.
+    pub async fn get_duplicate_events(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<DuplicateEvents, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "stats")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let (tracked_event_ids, total_duplicates, window_duplicates, top_publishers) =
+            self.duplicate_tracker.get_report(
+                topic_id,
+                Self::DUPLICATE_TOP_PUBLISHERS,
+                fragtale_client::time::get_timestamp_micros(),
+            );
+        Ok(DuplicateEvents::new(
+            topic_id,
+            self.unique_timer_stamper.get_instance_id(),
+            self.duplicate_tracker.get_window_micros(),
+            tracked_event_ids,
+            total_duplicates,
+            window_duplicates,
+            top_publishers,
+        ))
+    }
+
+    /// Keep the rejected event for later inspection and replay if the topic
+    /// has the reject store enabled.
+    async fn reject_event(&self, topic_id: &str, rejected_event: RejectedEvent) {
+        let reject_store_enabled = self
+            .event_descriptor_cache
+            .get_event_descriptor_by_topic_latest(topic_id)
+            .is_some_and(|event_descriptor| event_descriptor.is_reject_store_enabled());
+        if !reject_store_enabled {
+            return;
+        }
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "Storing rejected event '{}' in topic '{topic_id}': {}",
+                rejected_event.get_event_id(),
+                rejected_event.get_error_message()
+            );
+        }
+        if !self
+            .dbp
+            .event_facade()
+            .rejected_event_persist(topic_id, rejected_event)
+            .await
+        {
+            log::warn!("Failed to store rejected event in topic '{topic_id}'.");
+        }
+    }
+
+    /// Get events that were rejected by validation during publishing to a
+    /// topic, ordered by event identifier and starting after `from`.
+    ///
+    /// Only topics with the reject store enabled in the event descriptor will
+    /// keep rejected events.
+    pub async fn get_rejected_events(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        from: &Option<String>,
+    ) -> Result<RejectedEvents, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "rejected")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let (rejected_events, more) = self
+            .dbp
+            .event_facade()
+            .rejected_events(topic_id, from, Self::REJECTED_EVENTS_PAGE_SIZE)
+            .await;
+        Ok(RejectedEvents::new(
+            rejected_events
+                .iter()
+                .map(|rejected_event| {
+                    fragtale_client::mb::rejected_events::RejectedEvent::new(
+                        rejected_event.get_event_id(),
+                        rejected_event.get_rejected_ts_micros(),
+                        rejected_event.get_document(),
+                        rejected_event.get_priority(),
+                        rejected_event.get_descriptor_version(),
+                        rejected_event.get_publisher(),
+                        rejected_event.get_error_message(),
+                    )
+                })
+                .collect(),
+            more,
+        ))
+    }
+
+    /// Publish a previously rejected event again on behalf of the original
+    /// publisher.
+    ///
+    /// This is intended to be used after the event descriptor has been fixed.
+    /// The rejected event is removed when it was successfully published.
+    ///
+    /// Return `None` if no such rejected event exists or the serialized
+    /// `CorrelationToken` of the published event.
+    pub async fn replay_rejected_event(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        event_id: &str,
+    ) -> Result<Option<String>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "rejected")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let Some(rejected_event) = self
+            .dbp
+            .event_facade()
+            .rejected_event_by_id(topic_id, event_id)
+            .await
+        else {
+            return Ok(None);
+        };
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "'{}' is replaying rejected event '{event_id}' in topic '{topic_id}'.",
+                identity.identity_string()
+            );
+        }
+        // A failed replay will update the stored rejection reason
+        let correlation_token = self
+            .publish_event_to_topic_internal(
+                rejected_event.get_publisher(),
+                topic_id,
+                rejected_event.get_document(),
+                rejected_event.get_priority(),
+                None,
+                None,
+                rejected_event
+                    .get_descriptor_version()
+                    .map(DescriptorVersion::from_encoded),
+                rejected_event.get_correlation_token().clone(),
+                PublishAcknowledgement::Persisted,
+            )
+            .await?;
+        self.dbp
+            .event_facade()
+            .rejected_event_delete(topic_id, event_id)
+            .await;
+        Ok(Some(correlation_token))
+    }
+
+    /// Discard a previously rejected event.
+    ///
+    /// Return `true` if the rejected event existed.
+    pub async fn discard_rejected_event(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        event_id: &str,
+    ) -> Result<bool, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "rejected")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        Ok(self
+            .dbp
+            .event_facade()
+            .rejected_event_delete(topic_id, event_id)
+            .await)
+    }
+
+    /// Return the topic that dead-lettered events of the topic are published
+    /// to.
+    fn dead_letter_topic_id(topic_id: &str) -> String {
+        topic_id.to_owned() + Self::DEAD_LETTER_TOPIC_SUFFIX
+    }
+
+    /// Return `true` if the latest event descriptor of the topic has
+    /// dead-lettering enabled.
+    fn is_dead_letter_enabled(&self, topic_id: &str) -> bool {
+        self.event_descriptor_cache
+            .get_event_descriptor_by_topic_latest(topic_id)
+            .is_some_and(|event_descriptor| event_descriptor.is_dead_letter_enabled())
+    }
+
+    /// Publish an event that could not be delivered to the dead-letter topic
+    /// of its topic.
+    ///
+    /// Return `true` if the event was dead-lettered.
+    async fn dead_letter_event(&self, reason_code: &str, dead_letter: DeadLetter) -> bool {
+        let topic_id = dead_letter.get_topic_id();
+        let dead_letter_topic_id = Self::dead_letter_topic_id(topic_id);
+        let published = self
+            .publish_event_to_topic_internal(
+                ClientIdentity::Internal.identity_string(),
+                &dead_letter_topic_id,
+                &dead_letter.as_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                PublishAcknowledgement::Persisted,
+            )
+            .await;
+        if let Err(e) = published {
+            log::warn!(
+                "Failed to dead-letter event {} in '{topic_id}' for '{}': {e}",
+                dead_letter.get_unique_time(),
+                dead_letter.get_consumer_id(),
+            );
+            return false;
+        }
+        log::info!(
+            "Dead-lettered event {} in '{topic_id}' for '{}' to '{dead_letter_topic_id}': {}",
+            dead_letter.get_unique_time(),
+            dead_letter.get_consumer_id(),
+            dead_letter.get_reason(),
+        );
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_dead_lettered_events(topic_id, reason_code);
+        }
+        true
+    }
+
+    /// Get events of a topic that were published to its dead-letter topic,
+    /// ordered by the time of dead-lettering and starting after the encoded
+    /// unique time `from` of the dead-letter topic.
+    ///
+    /// Only topics with dead-lettering enabled in the event descriptor will
+    /// dead-letter events.
+    pub async fn get_dead_letters(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        from: Option<u64>,
+    ) -> Result<DeadLetters, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "dead_letters")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        Ok(self
+            .dead_letter_reader
+            .list(
+                &Self::dead_letter_topic_id(topic_id),
+                from.map(UniqueTime::from),
+            )
+            .await)
+    }
+
+    /// Publish the document of a dead-lettered event to its topic again.
+    ///
+    /// This is intended to be used after the cause of the failed delivery has
+    /// been fixed. The replayed event is a new event that is delivered to all
+    /// consumers of the topic. The event in the dead-letter topic is annotated
+    /// with the status `replayed`.
+    ///
+    /// Return `None` if no such dead-lettered event exists or the serialized
+    /// `CorrelationToken` of the published event.
+    pub async fn replay_dead_letter(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        event_id: &str,
+    ) -> Result<Option<String>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "dead_letters")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let dead_letter_topic_id = Self::dead_letter_topic_id(topic_id);
+        let Some(dead_letter) = self
+            .dbp
+            .event_facade()
+            .event_by_id(&dead_letter_topic_id, event_id)
+            .await
+            .and_then(|event_delivery_gist| {
+                serde_json::from_str::<DeadLetter>(event_delivery_gist.get_document()).ok()
+            })
+            .filter(|dead_letter| dead_letter.get_topic_id() == topic_id)
+        else {
+            return Ok(None);
+        };
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "'{}' is replaying dead-lettered event '{event_id}' of topic '{topic_id}'.",
+                identity.identity_string()
+            );
+        }
+        let correlation_token = self
+            .publish_event_to_topic_internal(
+                identity.identity_string(),
+                topic_id,
+                dead_letter.get_document(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                PublishAcknowledgement::Persisted,
+            )
+            .await?;
+        let annotated = self
+            .dbp
+            .event_facade()
+            .event_annotation_persist(
+                &dead_letter_topic_id,
+                fragtale_dbp::mb::EventAnnotation::new(
+                    event_id,
+                    fragtale_client::time::get_timestamp_micros(),
+                    identity.identity_string(),
+                    EventAnnotation::KIND_STATUS,
+                    "replayed",
+                ),
+            )
+            .await;
+        if !annotated {
+            log::warn!(
+                "Failed to annotate replayed event '{event_id}' in topic '{dead_letter_topic_id}'."
+            );
+        }
+        Ok(Some(correlation_token))
+    }
+
+    /// Append an annotation (label key, note or processing status) to an
+    /// existing event without altering the event itself.
+    ///
+    /// Return `false` if no event with the identifier exists in the topic.
+    pub async fn annotate_event(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        event_id: &str,
+        event_annotation: EventAnnotation,
+    ) -> Result<bool, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_annotate(identity, topic_id)
+            .await?;
+        let kind = event_annotation.get_kind();
+        if ![
+            EventAnnotation::KIND_LABEL,
+            EventAnnotation::KIND_NOTE,
+            EventAnnotation::KIND_STATUS,
+        ]
+        .contains(&kind)
+        {
+            Err(MessageBrokerErrorKind::MalformedRequest
+                .error_with_msg(format!("Unsupported event annotation kind '{kind}'.")))?;
+        }
+        if event_annotation.get_value().len() > Self::EVENT_ANNOTATION_VALUE_MAX_BYTES {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Event annotation value exceeds {} bytes.",
+                    Self::EVENT_ANNOTATION_VALUE_MAX_BYTES
+                )),
+            )?;
+        }
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        if self
+            .dbp
+            .event_facade()
+            .event_by_id(topic_id, event_id)
+            .await
+            .is_none()
+        {
+            return Ok(false);
+        }
+        let persisted = self
+            .dbp
+            .event_facade()
+            .event_annotation_persist(
+                topic_id,
+                fragtale_dbp::mb::EventAnnotation::new(
+                    event_id,
+                    fragtale_client::time::get_timestamp_micros(),
+                    identity.identity_string(),
+                    kind,
+                    event_annotation.get_value(),
+                ),
+            )
+            .await;
+        if !persisted {
+            Err(MessageBrokerErrorKind::Unspecified.error_with_msg(format!(
+                "Failed to annotate event '{event_id}' in topic '{topic_id}'."
+            )))?;
+        }
+        Ok(true)
+    }
+
+    /// Get the annotations of an event ordered by time of annotation.
+    ///
+    /// Return `None` if no event with the identifier exists in the topic.
+    pub async fn get_event_annotations(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        event_id: &str,
+    ) -> Result<Option<EventAnnotations>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        if self
+            .dbp
+            .event_facade()
+            .event_by_id(topic_id, event_id)
+            .await
+            .is_none()
+        {
+            return Ok(None);
+        }
+        let annotations = self
+            .dbp
+            .event_facade()
+            .event_annotations_by_event_id(topic_id, event_id, Self::EVENT_ANNOTATIONS_MAX)
+            .await
+            .iter()
+            .map(|event_annotation| {
+                EventAnnotationEntry::new(
+                    event_annotation.get_annotated_ts_micros(),
+                    event_annotation.get_author(),
+                    EventAnnotation::new(event_annotation.get_kind(), event_annotation.get_value()),
+                )
+            })
+            .collect();
+        Ok(Some(EventAnnotations::new(event_id, annotations)))
+    }
+
+    /// Confirm that the delivery of an event has been recieved and should not
+    /// be resent again.
+    ///
+    /// Note that this does not mean that the event has been processed or that
+    /// the processing could crash after this confirmation.
+    ///
+    /// A client application could choose to wait with the confirmation until
+    /// after processing is done at the risk of redelivery.
+    ///
+    /// When the topic has a delivery concurrency limit, the confirmation also
+    /// frees the consumer group's delivery slot for the event.
+    pub async fn confirm_event_delivery(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        encoded_unique_time: u64,
+        delivery_instance_id: u16,
+    ) -> Result<(), MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        self.confirm_event_delivery_by_consumer_id(
+            topic_id,
+            identity.identity_string(),
+            encoded_unique_time,
+            delivery_instance_id,
+        )
+        .await
+    }
+
+    /// Confirm the delivery of an event to the consumer.
+    async fn confirm_event_delivery_by_consumer_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        encoded_unique_time: u64,
+        delivery_instance_id: u16,
+    ) -> Result<(), MessageBrokerError> {
+        if log::log_enabled!(log::Level::Trace) {
+            log::trace!(
+                "Receiving event confirmation for '{topic_id}/{consumer_id}/{encoded_unique_time}'."
+            );
+        }
+        self.dbp.topic_facade().ensure_topic_setup(topic_id).await?;
+        self.dbp
+            .consumer_delivery_facade()
+            .delivery_intent_mark_done(
+                topic_id,
+                consumer_id,
+                UniqueTime::from(encoded_unique_time),
+                delivery_instance_id,
+            )
+            .await;
+        if self
+            .consumers
+            .get_delivery_concurrency(topic_id, consumer_id)
+            .is_some()
+        {
+            self.dbp
+                .consumer_delivery_facade()
+                .delivery_slot_release(topic_id, consumer_id, UniqueTime::from(encoded_unique_time))
+                .await;
+        }
+        if let Some(topic_consumer) = self
+            .consumers
+            .get_by_topic_and_consumer_id(topic_id, consumer_id)
+        {
+            topic_consumer.report_confirmed(&UniqueTime::from(encoded_unique_time));
+        }
+        self.object_count_tracker
+            .inc(topic_id, &ObjectCountType::DoneDeliveryIntents);
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_delivered_events(topic_id);
+        }
+        if let Some(target) = self.event_descriptor_cache.get_delivery_receipts(topic_id) {
+            self.emit_delivery_receipt(topic_id, consumer_id, encoded_unique_time, &target)
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Emit a receipt of a confirmed delivery to the `target` of the topic.
+    ///
+    /// Failures are logged, since the delivery itself has already been
+    /// confirmed.
+    async fn emit_delivery_receipt(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        encoded_unique_time: u64,
+        target: &DeliveryReceiptTarget,
+    ) {
+        let unique_time = UniqueTime::from(encoded_unique_time);
+        let Some(event_id) = self
+            .dbp
+            .event_facade()
+            .events_by_bucket(
+                topic_id,
+                unique_time.get_bucket(),
+                Some(UniqueTime::from(encoded_unique_time.saturating_sub(1))),
+                1,
+            )
+            .await
+            .0
+            .into_iter()
+            .find(|(other, _, _)| other.as_encoded() == encoded_unique_time)
+            .map(|(_, event_id, _)| event_id)
+        else {
+            log::info!(
+                "No receipt for '{topic_id}/{consumer_id}/{encoded_unique_time}' since the event is gone."
+            );
+            return;
+        };
+        let confirmed_ts = fragtale_client::time::get_timestamp_micros();
+        let receipt = DeliveryReceipt::new(
+            topic_id,
+            &event_id,
+            encoded_unique_time,
+            consumer_id,
+            confirmed_ts,
+            confirmed_ts.saturating_sub(unique_time.get_time_micros()),
+        )
+        .as_string();
+        match target {
+            DeliveryReceiptTarget::Topic => {
+                if let Err(e) = self
+                    .publish_event_to_topic_internal(
+                        consumer_id,
+                        &DeliveryReceipt::receipt_topic_id(topic_id),
+                        &receipt,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        PublishAcknowledgement::Persisted,
+                    )
+                    .await
+                {
+                    log::warn!("Failed to publish delivery receipt '{receipt}': {e}");
+                }
+            }
+            DeliveryReceiptTarget::Webhook { url } => {
+                self.webhook_sender.send_receipt(url, topic_id, &receipt);
+            }
+        }
+    }
+
+    /// Prepare the confirmation of an event delivery by recording the
+    /// consumer's external transaction identifier.
+    ///
+    /// This is the first step of a two-phase confirmation for consumers that
+    /// hand off events to an external sink. If the consumer fails before the
+    /// confirmation is committed, the redelivered event will carry the
+    /// prepared transaction identifier so the consumer can resolve the
+    /// outcome idempotently against its sink.
+    pub async fn prepare_event_delivery(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        encoded_unique_time: u64,
+        transaction_id: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        let consumer_id = identity.identity_string();
+        if log::log_enabled!(log::Level::Trace) {
+            log::trace!(
+                "Receiving prepared confirmation for '{topic_id}/{consumer_id}/{encoded_unique_time}'."
+            );
+        }
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let prepared = self
+            .dbp
+            .consumer_delivery_facade()
+            .delivery_prepare(
+                topic_id,
+                consumer_id,
+                UniqueTime::from(encoded_unique_time),
+                transaction_id,
+                fragtale_client::time::get_timestamp_micros(),
+            )
+            .await;
+        if !prepared {
+            Err(MessageBrokerErrorKind::Unspecified.error_with_msg(format!(
+                "Failed to prepare confirmation of '{topic_id}/{consumer_id}/{encoded_unique_time}'."
+            )))?;
+        }
+        Ok(())
+    }
+
+    /// Commit a previously prepared confirmation of an event delivery.
+    ///
+    /// This is the second step of a two-phase confirmation and has the same
+    /// effect as [Self::confirm_event_delivery].
+    pub async fn commit_event_delivery(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        encoded_unique_time: u64,
+        delivery_instance_id: u16,
+    ) -> Result<(), MessageBrokerError> {
+        self.confirm_event_delivery(
+            identity,
+            topic_id,
+            encoded_unique_time,
+            delivery_instance_id,
+        )
+        .await?;
+        self.dbp
+            .consumer_delivery_facade()
+            .delivery_prepared_remove(
+                topic_id,
+                identity.identity_string(),
+                UniqueTime::from(encoded_unique_time),
+            )
+            .await;
+        Ok(())
+    }
+
+    /// Commit the position in the topic up to which the consumer has processed
+    /// all events.
+    ///
+    /// This allows advanced consumers to manage their own delivery baseline
+    /// (e.g. batch-commit after writing to an external sink) instead of having
+    /// it inferred from confirmations of individual event deliveries.
+    ///
+    /// Return `true` if the position was moved forward.
+    pub async fn commit_consumer_position(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        consumer_id: &str,
+        encoded_unique_time: u64,
+    ) -> Result<bool, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        if consumer_id != identity.identity_string() {
+            Err(MessageBrokerErrorKind::Unauthorized.error_with_msg(format!(
+                "'{}' is not allowed to commit the position of consumer '{consumer_id}'.",
+                identity.identity_string()
+            )))?;
+        }
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "Receiving position commit for '{topic_id}/{consumer_id}/{encoded_unique_time}'."
+            );
+        }
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let topic_consumer = self
+            .consumers
+            .by_topic_and_consumer_id(topic_id, consumer_id, None, None)
+            .await?;
+        Ok(topic_consumer
+            .commit_position(UniqueTime::from(encoded_unique_time))
+            .await)
+    }
+
+    /// Move a consumer's position in the topic backwards to replay history or
+    /// forward to skip a backlog.
+    ///
+    /// The position is either right after the event with the encoded unique
+    /// time or right before the first event published at the timestamp in
+    /// epoch microseconds.
+    ///
+    /// The consumer itself may move its position. Others require
+    /// authorization to the administrative function `seek`.
+    pub async fn seek_consumer_position(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        consumer_id: &str,
+        consumer_seek: &ConsumerSeek,
+    ) -> Result<(), MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        if consumer_id != identity.identity_string() {
+            self.access_control
+                .assert_allowed_admin(identity, "seek")
+                .await?;
+        }
+        let position = match (
+            consumer_seek.get_unique_time(),
+            consumer_seek.get_timestamp(),
+        ) {
+            (Some(encoded_unique_time), None) => UniqueTime::from(encoded_unique_time),
+            (None, Some(timestamp)) => {
+                UniqueTime::from(UniqueTime::min_encoded_for_micros(timestamp).saturating_sub(1))
+            }
+            _ => Err(MessageBrokerErrorKind::MalformedRequest
+                .error_with_msg("Exactly one of 'unique_time' and 'timestamp' must be present."))?,
+        };
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "Receiving seek for '{topic_id}/{consumer_id}/{}'.",
+                position.as_encoded()
+            );
+        }
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let topic_consumer = self
+            .consumers
+            .by_topic_and_consumer_id(topic_id, consumer_id, None, None)
+            .await?;
+        if !topic_consumer.seek(position).await {
+            Err(
+                MessageBrokerErrorKind::PersistenceFailure.error_with_msg(format!(
+                    "Failed to move the position of consumer '{consumer_id}' in '{topic_id}'."
+                )),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Get rolling statistics of events published to a topic.
+    ///
+    /// Statistics are collected in memory by each instance, so the result only
+    /// reflects events published through the instance serving this request.
+    ///
+    /// Return `None` if no event has been published to the topic via this
+    /// instance since it was started.
+    pub async fn get_topic_statistics(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<Option<TopicStatistics>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "stats")
+            .await?;
+        Ok(self.event_statistics.by_topic(topic_id))
+    }
+
+    /// Get the number of published events and delivery intents of a topic
+    /// across all instances.
+    ///
+    /// The counts are kept across restarts until they are reset using
+    /// [Self::reset_topic_counts].
+    pub async fn get_topic_counts(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<TopicCounts, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "stats")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        Ok(TopicCounts::new(
+            topic_id,
+            self.object_count_tracker
+                .get_total_object_count(topic_id, &ObjectCountType::Events)
+                .await,
+            self.object_count_tracker
+                .get_total_object_count(topic_id, &ObjectCountType::ReservedDeliveryIntents)
+                .await,
+            self.object_count_tracker
+                .get_total_object_count(topic_id, &ObjectCountType::DoneDeliveryIntents)
+                .await,
+        ))
+    }
+
+    /// Reset the number of published events and delivery intents of a topic
+    /// on all instances.
+    ///
+    /// Other instances reset their counts when they take their next snapshot.
+    pub async fn reset_topic_counts(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "stats")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        self.object_count_tracker.reset(topic_id).await;
+        self.publish_audit_event(
+            identity,
+            "topic_counts_reset",
+            topic_id,
+            serde_json::json!({}),
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Get a page of known topic identifiers after `from` (exclusive).
+    pub async fn get_topic_list(
+        &self,
+        identity: &ClientIdentity,
+        from: Option<String>,
+    ) -> Result<TopicList, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "inspect")
+            .await?;
+        let (mut topic_ids, more) = self.dbp.topic_facade().get_topic_ids(&from).await;
+        if let Some(tenant) = self.access_control.get_tenant(identity)? {
+            topic_ids.retain(|topic_id| AccessControl::is_tenant_topic(tenant, topic_id));
+        }
+        Ok(TopicList::new(topic_ids, more))
+    }
+
+    /// Get how far each consumer of a topic is behind in time.
+    ///
+    /// The positions are persisted, so the result covers consumers served by
+    /// any instance.
+    pub async fn get_topic_consumers_lag(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<TopicConsumersLag, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "inspect")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let mut consumers = Vec::new();
+        for consumer_id in self
+            .dbp
+            .consumer_delivery_facade()
+            .consumer_ids(topic_id)
+            .await
+        {
+            let done_ts_micros = self
+                .dbp
+                .consumer_delivery_facade()
+                .consumer_get_done_by_id(topic_id, &consumer_id)
+                .await
+                .as_ref()
+                .map(UniqueTime::get_time_micros);
+            let attempted_ts_micros = self
+                .dbp
+                .consumer_delivery_facade()
+                .consumer_get_attempted_by_id(topic_id, &consumer_id)
+                .await
+                .as_ref()
+                .map(UniqueTime::get_time_micros);
+            consumers.push(ConsumerLag::new(
+                &consumer_id,
+                done_ts_micros,
+                attempted_ts_micros,
+                now_micros,
+            ));
+        }
+        Ok(TopicConsumersLag::new(topic_id, consumers))
+    }
+
+    /// Return `true` if any events have been persisted to the topic.
+    async fn has_topic_events(&self, topic_id: &str) -> bool {
+        let now_shelf = UniqueTime::from(UniqueTime::min_encoded_for_micros(
+            fragtale_client::time::get_timestamp_micros(),
+        ))
+        .get_shelf();
+        // Recent shelves are most likely to hold events
+        for shelf in (0..=now_shelf).rev() {
+            let (buckets, _more) = self
+                .dbp
+                .event_facade()
+                .buckets_by_shelf(topic_id, shelf, None, 1)
+                .await;
+            if !buckets.is_empty() {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Get the shelves of a topic that contain at least one bucket of events.
+    pub async fn get_topic_shelves(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<TopicShelves, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "inspect")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let now_shelf = UniqueTime::from(UniqueTime::min_encoded_for_micros(
+            fragtale_client::time::get_timestamp_micros(),
+        ))
+        .get_shelf();
+        let mut shelves = Vec::new();
+        for shelf in 0..=now_shelf {
+            let (buckets, _more) = self
+                .dbp
+                .event_facade()
+                .buckets_by_shelf(topic_id, shelf, None, 1)
+                .await;
+            if !buckets.is_empty() {
+                shelves.push(shelf);
+            }
+        }
+        Ok(TopicShelves::new(shelves))
+    }
+
+    /// Get a page of used buckets in a shelf of a topic with the number of
+    /// events in each bucket.
+    pub async fn get_topic_buckets(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        shelf: u16,
+        from_bucket: Option<u64>,
+    ) -> Result<TopicBuckets, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "inspect")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let (buckets, more) = self
+            .dbp
+            .event_facade()
+            .buckets_by_shelf(topic_id, shelf, from_bucket, Self::TOPIC_BUCKETS_PAGE_SIZE)
+            .await;
+        let mut topic_buckets = Vec::with_capacity(buckets.len());
+        for bucket in buckets {
+            let event_count = self
+                .dbp
+                .event_facade()
+                .event_count_by_bucket(topic_id, bucket)
+                .await;
+            let start_ts_micros =
+                UniqueTime::from(UniqueTime::min_encoded_in_bucket(bucket)).get_time_micros();
+            topic_buckets.push(TopicBucket::new(bucket, start_ts_micros, event_count));
+        }
+        Ok(TopicBuckets::new(shelf, topic_buckets, more))
+    }
+
+    /// Get a page of raw entries in a bucket of a topic.
+    pub async fn get_bucket_entries(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        bucket: u64,
+        from_unique_time: Option<u64>,
+    ) -> Result<BucketEntries, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "inspect")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let (entries, more) = self
+            .dbp
+            .event_facade()
+            .events_by_bucket(
+                topic_id,
+                bucket,
+                from_unique_time.map(UniqueTime::from),
+                Self::BUCKET_ENTRIES_PAGE_SIZE,
+            )
+            .await;
+        Ok(BucketEntries::new(
+            bucket,
+            entries
+                .iter()
+                .map(|(unique_time, event_id, descriptor_version)| {
+                    BucketEntry::new(unique_time.as_encoded(), event_id, *descriptor_version)
+                })
+                .collect(),
+            more,
+        ))
+    }
+
+    /// Report what a retention of `max_age_seconds` would delete from a topic
+    /// without deleting anything.
+    ///
+    /// Buckets are previewed in pages, starting at `from_bucket` if present.
+    pub async fn get_retention_preview(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        max_age_seconds: u64,
+        from_bucket: Option<u64>,
+    ) -> Result<RetentionPreview, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "retention")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        Ok(self
+            .retention_previewer
+            .preview(topic_id, max_age_seconds, from_bucket)
+            .await)
+    }
+
+    /// Export a signed report of which consumers retrieved events published to
+    /// the topic in the time window `[from_ts_micros..=to_ts_micros]`.
+    ///
+    /// With `event_id` set, only retrievals of that event are reported.
+    pub async fn get_consumption_audit(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        from_ts_micros: u64,
+        to_ts_micros: u64,
+        event_id: Option<&str>,
+    ) -> Result<ConsumptionAudit, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "audit")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let consumption_audit = self
+            .consumption_auditor
+            .audit(topic_id, from_ts_micros, to_ts_micros, event_id)
+            .await?;
+        self.publish_audit_event(
+            identity,
+            "consumption_audit_export",
+            topic_id,
+            serde_json::json!({
+                "from_ts_micros": from_ts_micros,
+                "to_ts_micros": to_ts_micros,
+                "event_id": event_id,
+                "records": consumption_audit.get_records().len(),
+            }),
+        )
+        .await;
+        Ok(consumption_audit)
+    }
+
+    /// Return `true` if the consumption audit report was signed by this
+    /// installation and has not been modified since.
+    pub async fn verify_consumption_audit(
+        &self,
+        identity: &ClientIdentity,
+        consumption_audit: &ConsumptionAudit,
+    ) -> Result<bool, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "audit")
+            .await?;
+        Ok(self.consumption_auditor.verify(consumption_audit))
+    }
+
+    /// Stream a consistent snapshot of the topic as of the encoded
+    /// [UniqueTime] `as_of` as NDJSON lines.
+    ///
+    /// With `key` set to the result name of one of the extractors in the
+    /// latest event descriptor of the topic, only the latest event for each
+    /// extracted key is returned.
+    pub async fn get_topic_snapshot(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        as_of: u64,
+        key: Option<&str>,
+    ) -> Result<tokio::sync::mpsc::Receiver<String>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "snapshot")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let key_pointer = key
+            .map(|key| {
+                self.event_descriptor_cache
+                    .get_event_descriptor_by_topic_latest(topic_id)
+                    .as_ref()
+                    .and_then(|event_descriptor| event_descriptor.get_extractors().as_ref())
+                    .and_then(|extractors| {
+                        extractors.iter().find(|extractor| {
+                            extractor.get_result_name() == key
+                                && extractor.get_extraction_type() == "jsonpointer"
+                        })
+                    })
+                    .map(|extractor| extractor.get_extraction_path().to_owned())
+                    .ok_or_else(|| {
+                        MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                            "Topic '{topic_id}' has no JSON Pointer extractor named '{key}'."
+                        ))
+                    })
+            })
+            .transpose()?;
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "'{identity}' exports snapshot of topic '{topic_id}' as of {as_of} (key: {key:?})."
+            );
+        }
+        Ok(self
+            .topic_snapshotter
+            .snapshot(topic_id, UniqueTime::from(as_of), key_pointer))
+    }
+
+    /// List the whitelisted read-only diagnostic queries.
+    pub async fn get_diagnostic_query_templates(
+        &self,
+        identity: &ClientIdentity,
+    ) -> Result<DiagnosticQueryTemplates, MessageBrokerError> {
+        self.assert_diagnostics_enabled()?;
+        self.access_control
+            .assert_allowed_admin(identity, "diagnostics")
+            .await?;
+        Ok(DiagnosticQueryTemplates::new(
+            DiagnosticQuery::ALL
+                .iter()
+                .map(|diagnostic_query| {
+                    DiagnosticQueryTemplate::new(
+                        diagnostic_query.get_name(),
+                        diagnostic_query.get_description(),
+                        diagnostic_query.requires_consumer_id(),
+                        diagnostic_query.requires_range(),
+                    )
+                })
+                .collect(),
+        ))
+    }
+
+    /// Run a whitelisted read-only diagnostic query against the storage of a
+    /// topic.
+    ///
+    /// This is intended for emergencies, so operators rarely need direct
+    /// database access to production. Every query is audit logged.
+    pub async fn run_diagnostic_query(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        query_name: &str,
+        consumer_id: Option<&str>,
+        range: Option<(u64, u64)>,
+    ) -> Result<DiagnosticQueryResult, MessageBrokerError> {
+        self.assert_diagnostics_enabled()?;
+        self.access_control
+            .assert_allowed_admin(identity, "diagnostics")
+            .await?;
+        let diagnostic_query = DiagnosticQuery::from_name(query_name).ok_or_else(|| {
+            MessageBrokerErrorKind::MalformedRequest
+                .error_with_msg(format!("Unknown diagnostic query '{query_name}'."))
+        })?;
+        if diagnostic_query.requires_consumer_id() && consumer_id.is_none() {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Diagnostic query '{query_name}' requires a consumer identifier."
+                )),
+            )?;
+        }
+        if diagnostic_query.requires_range() && range.is_none_or(|(from, to)| from > to) {
+            Err(MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                "Diagnostic query '{query_name}' requires a range where 'from' is not after 'to'."
+            )))?;
+        }
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        self.publish_audit_event(
+            identity,
+            "diagnostic_query",
+            topic_id,
+            serde_json::json!({
+                "query": query_name,
+                "consumer_id": consumer_id,
+                "from": range.map(|(from, _to)| from),
+                "to": range.map(|(_from, to)| to),
+            }),
+        )
+        .await;
+        let (rows, more) = self
+            .dbp
+            .topic_facade()
+            .diagnostic_query(
+                topic_id,
+                diagnostic_query,
+                consumer_id,
+                range.map(|(from, to)| (UniqueTime::from(from), UniqueTime::from(to))),
+                Self::DIAGNOSTIC_QUERY_MAX_ROWS,
+            )
+            .await?;
+        Ok(DiagnosticQueryResult::new(
+            topic_id,
+            query_name,
+            rows.iter()
+                .map(|row| {
+                    serde_json::from_str(row)
+                        .unwrap_or_else(|_| serde_json::Value::String(row.to_owned()))
+                })
+                .collect(),
+            more,
+        ))
+    }
+
+    /// Ensure that the topic is set up before use.
+    ///
+    /// Creation of topics that do not exist yet is subject to the configured
+    /// [TopicCreationPolicy]. `explicit` is set when the request is an
+    /// intentional registration of the topic (upserting its descriptor).
+    async fn ensure_topic_setup(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        explicit: bool,
+    ) -> Result<(), MessageBrokerError> {
+        if !RequestDeadline::within(self.dbp.topic_facade().topic_exists(topic_id)).await? {
+            let allowed = match self.topic_creation_policy {
+                TopicCreationPolicy::Auto => Ok(()),
+                _ if identity.is_local() => Ok(()),
+                TopicCreationPolicy::Restricted => {
+                    self.access_control
+                        .assert_allowed_admin(identity, "create_topic")
+                        .await
+                }
+                TopicCreationPolicy::Disabled if explicit => Ok(()),
+                TopicCreationPolicy::Disabled => Err(MessageBrokerErrorKind::MalformedIdentifier
+                    .error_with_msg(format!(
+                        "Topic '{topic_id}' does not exist and must be registered explicitly."
+                    ))),
+            };
+            // Tenants may only create topics in their own namespace
+            let allowed = self
+                .access_control
+                .assert_tenant_topic(identity, topic_id)
+                .and(allowed);
+            if let Err(e) = allowed {
+                if let Some(metrics) = &self.metrics {
+                    metrics.inc_refused_topic_creations(topic_id);
+                }
+                log::info!(
+                    "Refused creation of topic '{topic_id}' by '{}': {e}",
+                    identity.identity_string()
+                );
+                Err(e)?;
+            }
+            self.dbp.topic_facade().ensure_topic_setup(topic_id).await?;
+            if !explicit && let Some(metrics) = &self.metrics {
+                metrics.inc_auto_created_topics(topic_id);
+            }
+            log::info!(
+                "Created topic '{topic_id}' on request by '{}'.",
+                identity.identity_string()
+            );
+            return Ok(());
+        }
+        self.dbp.topic_facade().ensure_topic_setup(topic_id).await
+    }
+
+    /// Fail unless diagnostic queries are enabled in the configuration.
+    fn assert_diagnostics_enabled(&self) -> Result<(), MessageBrokerError> {
+        if !self.diagnostics_enabled {
+            Err(MessageBrokerErrorKind::Unauthorized
+                .error_with_msg("Diagnostic queries are disabled in the configuration."))?;
+        }
+        Ok(())
+    }
+
+    /// Get next event to deliver.
+    ///
+    /// When `event_types` is present, events of other kinds in the
+    /// multi-type topic are marked as done for the consumer without delivery.
+    ///
+    /// When `min_priority` is present, events published with a lower priority
+    /// are marked as done for the consumer without delivery. Events persisted
+    /// without a known priority are always delivered.
+    ///
+    /// When `filter` is present, events where the filter expression does not
+    /// match the values extracted with the latest [EventDescriptor] of the
+    /// topic are marked as done for the consumer without delivery.
+    ///
+    /// Events published with an older version of the [EventDescriptor] than
+    /// the consumer's are augmented with the default values of absent fields
+    /// declared by the consumer's version.
+    ///
+    /// Events that have passed their deadline are marked as done for the
+    /// consumer without delivery.
+    ///
+    /// When the topic has dead-lettering enabled, events that fail integrity
+    /// validation or exhaust the redelivery budget are published to the
+    /// topic's dead-letter topic and marked as done for the consumer.
+    ///
+    /// When `fields` is present, only the values at these JSON Pointers of
+    /// the (integrity validated) event document are delivered.
+    ///
+    /// The topic's masking rules are applied to the delivered document unless
+    /// the identity is allowed to receive unmasked events.
+    ///
+    /// When the identity is a declared subscriber of the topic, the filter,
+    /// projection, starting position and rate limit of the declaration apply
+    /// unless overridden by the request. A rate limited consumer gets no event
+    /// until the next delivery is due.
+    ///
+    /// When `visibility_timeout_millis` is present, the broker waits this long
+    /// (within the configured bounds) for a confirmation before the delivered
+    /// event is considered for redelivery.
+    ///
+    /// When `sticky_key` is present, events with a matching routing key are
+    /// preferred for this member of the consumer group. Events with another
+    /// routing key are left for a short while to the members that prefer
+    /// them and are then delivered to any member.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_event_by_consumer_and_topic(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        baseline_ts: Option<u64>,
+        descriptor_version: Option<DescriptorVersion>,
+        event_types: Option<&[String]>,
+        min_priority: Option<u8>,
+        fields: Option<&[String]>,
+        filter: Option<&str>,
+        visibility_timeout_millis: Option<u64>,
+        sticky_key: Option<&str>,
+    ) -> Result<
+        Option<(
+            u64,
+            Arc<str>,
+            String,
+            u16,
+            Option<String>,
+            Option<u8>,
+            DeliveryEnvelope,
+        )>,
+        MessageBrokerError,
+    > {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        let consumer_id = identity.identity_string();
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let Some(consumer_definition) = self
+            .consumer_definitions
+            .subscriber_by_topic_and_consumer_id(topic_id, consumer_id)
+            .await
+        else {
+            let document_projection = fields.map(DocumentProjection::new).transpose()?;
+            let masking_rules = self.get_masking_rules(Some(identity), topic_id).await;
+            return self
+                .next_event_for_consumer(
+                    topic_id,
+                    consumer_id,
+                    baseline_ts,
+                    descriptor_version,
+                    event_types,
+                    min_priority,
+                    filter,
+                    visibility_timeout_millis,
+                    sticky_key,
+                )
+                .await
+                .map(|next_event| {
+                    Self::apply_masking_and_projection(
+                        next_event,
+                        masking_rules.as_deref(),
+                        document_projection.as_ref(),
+                    )
+                });
+        };
+        let document_projection = fields
+            .or(consumer_definition.get_fields())
+            .map(DocumentProjection::new)
+            .transpose()?;
+        let masking_rules = self.get_masking_rules(Some(identity), topic_id).await;
+        if consumer_definition
+            .get_max_events_per_second()
+            .is_some_and(|max_events_per_second| {
+                !self.consumer_definitions.try_acquire_delivery(
+                    topic_id,
+                    consumer_id,
+                    max_events_per_second,
+                )
+            })
+        {
+            return Ok(None);
+        }
+        self.next_event_for_consumer(
+            topic_id,
+            consumer_id,
+            baseline_ts.or(consumer_definition.get_baseline_ts()),
+            descriptor_version,
+            event_types.or(consumer_definition.get_event_types()),
+            min_priority.max(consumer_definition.get_min_priority()),
+            filter.or(consumer_definition.get_filter()),
+            visibility_timeout_millis,
+            sticky_key,
+        )
+        .await
+        .map(|next_event| {
+            Self::apply_masking_and_projection(
+                next_event,
+                masking_rules.as_deref(),
+                document_projection.as_ref(),
+            )
+        })
+    }
+
+    /// Replace the document of the next event with its masked projection (if
+    /// any).
+    ///
+    /// Masking is applied before the projection, so a projection can't select
+    /// the unmasked value of a field. Documents that are not JSON objects or
+    /// arrays are delivered unmodified.
+    #[allow(clippy::type_complexity)]
+    fn apply_masking_and_projection(
+        next_event: Option<(
+            u64,
+            Arc<str>,
+            String,
+            u16,
+            Option<String>,
+            Option<u8>,
+            DeliveryEnvelope,
+        )>,
+        masking_rules: Option<&[MaskingRule]>,
+        document_projection: Option<&DocumentProjection>,
+    ) -> Option<(
+        u64,
+        Arc<str>,
+        String,
+        u16,
+        Option<String>,
+        Option<u8>,
+        DeliveryEnvelope,
+    )> {
+        if masking_rules.is_none() && document_projection.is_none() {
+            return next_event;
+        }
+        next_event.map(
+            |(
+                encoded_unique_time,
+                document,
+                correlation_token,
+                delivery_instance_id,
+                prepared_transaction_id,
+                priority,
+                delivery_envelope,
+            )| {
+                let document = masking_rules
+                    .and_then(|masking_rules| DocumentMasking::mask(&document, masking_rules))
+                    .map(Arc::from)
+                    .unwrap_or(document);
+                (
+                    encoded_unique_time,
+                    document_projection
+                        .and_then(|document_projection| document_projection.project(&document))
+                        .map(Arc::from)
+                        .unwrap_or(document),
+                    correlation_token,
+                    delivery_instance_id,
+                    prepared_transaction_id,
+                    priority,
+                    delivery_envelope,
+                )
+            },
+        )
+    }
+
+    /// Return up to `count` of the events next in line for delivery to the
+    /// consumer without reserving them.
+    ///
+    /// No delivery intents are created and the consumer's position is left
+    /// as is, so the returned events will still be delivered as usual. Only
+    /// events already known to this instance's delivery cache of the consumer
+    /// are returned, so this is a best effort view of the backlog.
+    pub async fn peek_events_for_consumer(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        count: Option<usize>,
+        descriptor_version: Option<DescriptorVersion>,
+    ) -> Result<PeekedEvents, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        let consumer_id = identity.identity_string();
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let count = count
+            .unwrap_or(Self::PEEK_DEFAULT_EVENTS)
+            .clamp(1, Self::PEEK_MAX_EVENTS);
+        let topic_consumer = self
+            .consumers
+            .by_topic_and_consumer_id(topic_id, consumer_id, None, descriptor_version)
+            .await?;
+        let masking_rules = self.get_masking_rules(Some(identity), topic_id).await;
+        let mut events = vec![];
+        for (event_delivery_gist, event_descriptor_version, redelivery) in topic_consumer
+            .peek_delivery_intents(count, descriptor_version)
+            .await
+        {
+            let (unique_time, document, protection_ref, _correlation_token, priority) =
+                event_delivery_gist.into_parts();
+            if !self
+                .integrity_validator
+                .validate_protection_ref_of_event(
+                    topic_id,
+                    &document,
+                    &protection_ref,
+                    &unique_time,
+                )
+                .await
+            {
+                log::warn!(
+                    "Integrity protection validation failed for peeked event in '{topic_id}' with protection_id {protection_ref}."
+                );
+                continue;
+            }
+            let delivery_envelope = self
+                .get_delivery_envelope(topic_id, &document, event_descriptor_version)
+                .await;
+            let delivered_document = self
+                .migrate_to_consumer_version(
+                    topic_id,
+                    &document,
+                    event_descriptor_version,
+                    descriptor_version,
+                )
+                .unwrap_or(document);
+            let delivered_document = masking_rules
+                .as_ref()
+                .and_then(|masking_rules| DocumentMasking::mask(&delivered_document, masking_rules))
+                .map(Arc::from)
+                .unwrap_or(delivered_document);
+            events.push(PeekedEvent::new(
+                unique_time.as_encoded(),
+                delivery_envelope.get_event_id(),
+                delivery_envelope
+                    .get_descriptor_version()
+                    .map(str::to_owned),
+                delivery_envelope.get_schema_id().map(str::to_owned),
+                priority,
+                redelivery,
+                &delivered_document,
+            ));
+        }
+        Ok(PeekedEvents::new(topic_id, consumer_id, events))
+    }
+
+    /// Return up to `count` of the events published to the topic in the time
+    /// range `[from_ts_micros, to_ts_micros)`, in order of publication.
+    ///
+    /// Continue with the next page by passing the returned `next` as
+    /// `after`. Every document is integrity validated before it is returned
+    /// and masked according to the caller's grants. No delivery intents are
+    /// created and the consumer's position is left as is.
+    ///
+    /// Events that are no longer present in the database are read from the
+    /// archive tier (if enabled) and marked with [StorageTier::Archive].
+    pub async fn get_events_by_time_range(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        from_ts_micros: u64,
+        to_ts_micros: u64,
+        after: Option<u64>,
+        count: Option<usize>,
+    ) -> Result<ReplayedEvents, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        if from_ts_micros >= to_ts_micros {
+            Err(MessageBrokerErrorKind::MalformedRequest
+                .error_with_msg("The start of the time range must be before the end."))?;
+        }
+        let count = count
+            .unwrap_or(Self::REPLAY_DEFAULT_EVENTS)
+            .clamp(1, Self::REPLAY_MAX_EVENTS);
+        // The facade treats the lower bound as exclusive and the upper as inclusive
+        let from = UniqueTime::from(after.unwrap_or_else(|| {
+            UniqueTime::min_encoded_for_micros(from_ts_micros).saturating_sub(1)
+        }));
+        let to = UniqueTime::from(UniqueTime::min_encoded_for_micros(to_ts_micros) - 1);
+        let (mut entries, mut more) = RequestDeadline::within(
+            self.dbp
+                .event_facade()
+                .events_by_unique_time_range(topic_id, from, to, count),
+        )
+        .await?;
+        let (archived_events, archive_more) = self
+            .event_archive
+            .events_by_unique_time_range(topic_id, from, to, count)
+            .await;
+        let mut archived_by_unique_time = HashMap::with_capacity(archived_events.len());
+        if !archived_events.is_empty() {
+            let listed = entries
+                .iter()
+                .map(|(unique_time, _, _)| *unique_time)
+                .collect::<HashSet<_>>();
+            for archived_event in archived_events {
+                let unique_time = archived_event.get_unique_time();
+                if !listed.contains(&unique_time) {
+                    entries.push((
+                        unique_time,
+                        archived_event.get_event_id().to_owned(),
+                        archived_event.get_descriptor_version(),
+                    ));
+                }
+                archived_by_unique_time.insert(unique_time, archived_event);
+            }
+            entries.sort_by_key(|(unique_time, _, _)| *unique_time);
+            more |= archive_more || entries.len() > count;
+            entries.truncate(count);
+        }
+        let next = more
+            .then(|| {
+                entries
+                    .last()
+                    .map(|(unique_time, _, _)| unique_time.as_encoded())
+            })
+            .flatten();
+        let masking_rules = self.get_masking_rules(Some(identity), topic_id).await;
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let mut events = Vec::with_capacity(entries.len());
+        for (unique_time, event_id, descriptor_version) in entries {
+            let (unique_time, document, protection_ref, storage_tier) =
+                if let Some(event_delivery_gist) = self
+                    .dbp
+                    .event_facade()
+                    .event_by_id_and_unique_time(topic_id, &event_id, unique_time)
+                    .await
+                {
+                    let (unique_time, document, protection_ref, _correlation_token, _priority) =
+                        event_delivery_gist.into_parts();
+                    (unique_time, document, protection_ref, StorageTier::Database)
+                } else if let Some(archived_event) = archived_by_unique_time
+                    .remove(&unique_time)
+                    .filter(|archived_event| !archived_event.is_expired(now_micros))
+                {
+                    let (unique_time, document, protection_ref) = archived_event.into_parts();
+                    (unique_time, document, protection_ref, StorageTier::Archive)
+                } else {
+                    // The event might have been removed by retention since it was listed
+                    continue;
+                };
+            if !self
+                .integrity_validator
+                .validate_protection_ref_of_event(
+                    topic_id,
+                    &document,
+                    &protection_ref,
+                    &unique_time,
+                )
+                .await
+            {
+                Err(
+                    MessageBrokerErrorKind::IntegrityProtectionError.error_with_msg(format!(
+                        "Failed to verify integrity for event with id '{event_id}'."
+                    )),
+                )?;
+            }
+            let delivery_envelope = self
+                .get_delivery_envelope(topic_id, &document, descriptor_version)
+                .await;
+            let document = masking_rules
+                .as_ref()
+                .and_then(|masking_rules| DocumentMasking::mask(&document, masking_rules))
+                .map(Arc::from)
+                .unwrap_or(document);
+            events.push(ReplayedEvent::new(
+                unique_time.as_encoded(),
+                delivery_envelope.get_event_id(),
+                delivery_envelope
+                    .get_descriptor_version()
+                    .map(str::to_owned),
+                delivery_envelope.get_schema_id().map(str::to_owned),
+                &document,
+                storage_tier,
+            ));
+        }
+        Ok(ReplayedEvents::new(topic_id, events, next))
+    }
+
+    /// Get next event to deliver to the consumer.
+    ///
+    /// See [Self::get_event_by_consumer_and_topic] for filtering details.
+    #[allow(clippy::too_many_arguments)]
+    async fn next_event_for_consumer(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        baseline_ts: Option<u64>,
+        descriptor_version: Option<DescriptorVersion>,
+        event_types: Option<&[String]>,
+        min_priority: Option<u8>,
+        filter: Option<&str>,
+        visibility_timeout_millis: Option<u64>,
+        sticky_key: Option<&str>,
+    ) -> Result<
+        Option<(
+            u64,
+            Arc<str>,
+            String,
+            u16,
+            Option<String>,
+            Option<u8>,
+            DeliveryEnvelope,
+        )>,
+        MessageBrokerError,
+    > {
+        self.dbp.topic_facade().ensure_topic_setup(topic_id).await?;
+        let filter_expression = filter
+            .map(|filter| self.pre_storage_processor.compile_filter(topic_id, filter))
+            .transpose()?;
+        if event_types.is_some()
+            && self
+                .event_descriptor_cache
+                .get_event_descriptor_by_topic_latest(topic_id)
+                .is_none_or(|event_descriptor| event_descriptor.get_event_type_field().is_none())
+        {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Topic '{topic_id}' has no event type field to filter events by."
+                )),
+            )?;
+        }
+        let sticky_preference = sticky_key
+            .map(|sticky_key| {
+                self.event_descriptor_cache
+                    .get_event_descriptor_by_topic_latest(topic_id)
+                    .and_then(|event_descriptor| {
+                        event_descriptor
+                            .get_routing_key_field()
+                            .as_deref()
+                            .map(|routing_key_field| {
+                                StickyPreference::new(routing_key_field, sticky_key)
+                            })
+                    })
+                    .ok_or_else(|| {
+                        MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                            "Topic '{topic_id}' has no routing key field to match the stickiness key with."
+                        ))
+                    })
+            })
+            .transpose()?;
+        let topic_consumer = self
+            .consumers
+            .by_topic_and_consumer_id(topic_id, consumer_id, baseline_ts, descriptor_version)
+            .await?;
+        topic_consumer.set_visibility_timeout_micros(
+            self.consumers
+                .bounded_visibility_timeout_micros(visibility_timeout_millis),
+        );
+        let dead_letter = self.is_dead_letter_enabled(topic_id);
+        for _ in 0..Self::EVENT_TYPE_FILTER_MAX_SKIPPED {
+            let Some((event_delivery_gist, prepared_transaction_id, event_descriptor_version)) =
+                topic_consumer
+                    .reserve_delivery_intent(
+                        descriptor_version,
+                        sticky_preference.as_ref(),
+                        dead_letter,
+                    )
+                    .await
+            else {
+                return Ok(None);
+            };
+            let is_expired =
+                event_delivery_gist.is_expired(fragtale_client::time::get_timestamp_micros());
+            let (unique_time, document, protection_ref, correlation_token, priority) =
+                event_delivery_gist.into_parts();
+            if log::log_enabled!(log::Level::Trace) {
+                log::trace!("Got event_delivery_gist in '{topic_id}'.");
+            }
+            let delivery_instance_id = self.unique_timer_stamper.get_instance_id();
+            if is_expired {
+                // Delivering this late would only create wasted work downstream
+                self.dbp
+                    .consumer_delivery_facade()
+                    .delivery_intent_mark_done(
+                        topic_id,
+                        consumer_id,
+                        unique_time,
+                        delivery_instance_id,
+                    )
+                    .await;
+                self.object_count_tracker
+                    .inc(topic_id, &ObjectCountType::DoneDeliveryIntents);
+                if let Some(metrics) = &self.metrics {
+                    metrics.inc_expired_events(topic_id);
+                }
+                continue;
+            }
+            if dead_letter
+                && let Some((event_id, redeliveries)) =
+                    topic_consumer.exhausted_redelivery(&unique_time)
+                && self
+                    .dead_letter_event(
+                        Self::DEAD_LETTER_REASON_REDELIVERIES,
+                        DeadLetter::new(
+                            topic_id,
+                            consumer_id,
+                            unique_time.as_encoded(),
+                            Some(&event_id),
+                            &format!(
+                                "Delivery was not confirmed after {redeliveries} redeliveries."
+                            ),
+                            fragtale_client::time::get_timestamp_micros(),
+                            &document,
+                        ),
+                    )
+                    .await
+            {
+                // Dead-lettering failures leave the event for the next redelivery
+                self.dbp
+                    .consumer_delivery_facade()
+                    .delivery_intent_mark_done(
+                        topic_id,
+                        consumer_id,
+                        unique_time,
+                        delivery_instance_id,
+                    )
+                    .await;
+                self.object_count_tracker
+                    .inc(topic_id, &ObjectCountType::DoneDeliveryIntents);
+                continue;
+            }
+            if !self
+                .integrity_validator
+                .validate_protection_ref_of_event(
+                    topic_id,
+                    &document,
+                    &protection_ref,
+                    &unique_time,
+                )
+                .await
+            {
+                let msg = "Integrity protection validation failed for event in '{topic_id}' with protection_id {protection_ref}.";
+                log::warn!("{msg}");
+                if dead_letter {
+                    self.dead_letter_event(
+                        Self::DEAD_LETTER_REASON_INTEGRITY,
+                        DeadLetter::new(
+                            topic_id,
+                            consumer_id,
+                            unique_time.as_encoded(),
+                            None,
+                            "Integrity protection validation failed.",
+                            fragtale_client::time::get_timestamp_micros(),
+                            &document,
+                        ),
+                    )
+                    .await;
+                }
+                // This will never be delivered.. make sure it isn't attempted again!
+                self.dbp
+                    .consumer_delivery_facade()
+                    .delivery_intent_mark_done(
+                        topic_id,
+                        consumer_id,
+                        unique_time,
+                        delivery_instance_id,
+                    )
+                    .await;
+                self.object_count_tracker
+                    .inc(topic_id, &ObjectCountType::DoneDeliveryIntents);
+                Err(MessageBrokerErrorKind::IntegrityProtectionError.error_with_msg(msg))?;
+            }
+            if log::log_enabled!(log::Level::Trace) {
+                log::trace!("Validation of event_delivery_gist in '{topic_id}' done.");
+            }
+            let migrated_document = self.migrate_to_consumer_version(
+                topic_id,
+                &document,
+                event_descriptor_version,
+                descriptor_version,
+            );
+            let delivered_document = migrated_document.as_ref().unwrap_or(&document);
+            let is_below_min_priority = min_priority
+                .zip(priority)
+                .is_some_and(|(min_priority, priority)| priority < min_priority);
+            if is_below_min_priority
+                || event_types.is_some_and(|event_types| {
+                    !self.is_event_of_type(topic_id, delivered_document, event_types)
+                })
+                || filter_expression.as_ref().is_some_and(|filter_expression| {
+                    !self.pre_storage_processor.matches_filter(
+                        topic_id,
+                        delivered_document,
+                        filter_expression,
+                    )
+                })
+            {
+                // The consumer has no interest in this event.. skip it!
+                self.dbp
+                    .consumer_delivery_facade()
+                    .delivery_intent_mark_done(
+                        topic_id,
+                        consumer_id,
+                        unique_time,
+                        delivery_instance_id,
+                    )
+                    .await;
+                self.object_count_tracker
+                    .inc(topic_id, &ObjectCountType::DoneDeliveryIntents);
+                continue;
+            }
+            if let Some(metrics) = &self.metrics {
+                metrics.inc_delivered_bytes(topic_id, delivered_document.len());
+                let now = fragtale_client::time::get_timestamp_micros();
+                metrics.report_publish_to_delivery_latency_micros(
+                    topic_id,
+                    now - unique_time.get_time_micros(),
+                );
+            }
+            let delivery_envelope = self
+                .get_delivery_envelope(topic_id, &document, event_descriptor_version)
+                .await;
+            return Ok(Some((
+                unique_time.as_encoded(),
+                migrated_document.unwrap_or(document),
+                correlation_token,
+                delivery_instance_id,
+                prepared_transaction_id,
+                priority,
+                delivery_envelope,
+            )));
+        }
+        Ok(None)
+    }
+
+    /// Return the masking rules of the topic that apply to events delivered to
+    /// the identity (if any).
+    ///
+    /// The rules of the latest event descriptor apply to all events. Without
+    /// an identity, the masking rules always apply.
+    async fn get_masking_rules(
+        &self,
+        identity: Option<&ClientIdentity>,
+        topic_id: &str,
+    ) -> Option<Vec<MaskingRule>> {
+        let masking_rules = self
+            .event_descriptor_cache
+            .get_event_descriptor_by_topic_latest(topic_id)?
+            .get_masking_rules()
+            .clone()
+            .filter(|masking_rules| !masking_rules.is_empty())?;
+        if let Some(identity) = identity
+            && self
+                .access_control
+                .is_allowed_topic_unmasked(identity, topic_id)
+                .await
+        {
+            return None;
+        }
+        Some(masking_rules)
+    }
+
+    /// Return the document with the masking rules of the topic that apply to
+    /// the identity (if any).
+    async fn apply_masking(
+        &self,
+        identity: Option<&ClientIdentity>,
+        topic_id: &str,
+        document: Arc<str>,
+    ) -> Arc<str> {
+        self.get_masking_rules(identity, topic_id)
+            .await
+            .and_then(|masking_rules| DocumentMasking::mask(&document, &masking_rules))
+            .map(Arc::from)
+            .unwrap_or(document)
+    }
+
+    /// Return the metadata that describes an event on delivery.
+    ///
+    /// The event identifier is derived from the document as published.
+    async fn get_delivery_envelope(
+        &self,
+        topic_id: &str,
+        document: &str,
+        event_descriptor_version: Option<u64>,
+    ) -> DeliveryEnvelope {
+        let event_id = self
+            .event_descriptor_cache
+            .get_event_id_algorithm(topic_id)
+            .event_id_from_document(document);
+        let descriptor_version = event_descriptor_version.map(DescriptorVersion::from_encoded);
+        let schema_id = if let Some(descriptor_version) = &descriptor_version {
+            self.event_descriptor_cache
+                .get_event_descriptor_by_topic_and_version(topic_id, descriptor_version)
+                .await
+                .and_then(|event_descriptor| {
+                    event_descriptor
+                        .get_event_schema()
+                        .as_ref()
+                        .map(|event_schema| event_schema.get_schema_id().to_owned())
+                })
+        } else {
+            None
+        };
+        DeliveryEnvelope::new(
+            topic_id,
+            descriptor_version
+                .as_ref()
+                .map(DescriptorVersion::as_semver),
+            schema_id,
+            event_id,
+        )
+    }
+
+    /// Return the document with the default values of the consumer's version
+    /// of the [EventDescriptor] when the event was published with an older
+    /// version or `None` if there is nothing to add.
+    ///
+    /// Consumers that don't state a version get the latest version.
+    fn migrate_to_consumer_version(
+        &self,
+        topic_id: &str,
+        document: &str,
+        event_descriptor_version: Option<u64>,
+        consumer_descriptor_version: Option<DescriptorVersion>,
+    ) -> Option<Arc<str>> {
+        let event_descriptor_version = event_descriptor_version?;
+        let event_descriptor = match consumer_descriptor_version {
+            Some(consumer_descriptor_version) => self
+                .event_descriptor_cache
+                .get_event_descriptor_by_topic_at_most(topic_id, &consumer_descriptor_version),
+            None => self
+                .event_descriptor_cache
+                .get_event_descriptor_by_topic_latest(topic_id),
+        }?;
+        if event_descriptor.get_version() <= event_descriptor_version {
+            return None;
+        }
+        let default_values = event_descriptor.get_default_values().as_deref()?;
+        DocumentMigration::apply_default_values(document, default_values).map(Arc::from)
+    }
+
+    /// Return `true` if the event's type according to the latest
+    /// [EventDescriptor] of the topic is one of `event_types`.
+    fn is_event_of_type(&self, topic_id: &str, document: &str, event_types: &[String]) -> bool {
+        self.event_descriptor_cache
+            .get_event_descriptor_by_topic_latest(topic_id)
+            .and_then(|event_descriptor| {
+                PreStorageProcessor::extract_event_type(&event_descriptor, document)
+                    .ok()
+                    .flatten()
+            })
+            .is_some_and(|event_type| event_types.contains(&event_type))
+    }
+
+    /// Return an event by the correlation token or `None` if an event has not
+    /// appeared before the timeout.
+    pub async fn get_event_by_correlation_token(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        correlation_token_str: &str,
+    ) -> Result<Option<Arc<str>>, MessageBrokerError> {
+        let start_ts = fragtale_client::time::get_timestamp_micros();
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        let consumer_id = identity.identity_string();
+        // Create topic on the fly, if it did not exist.
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        // Create a Consumer if it did not exist.
+        self.consumers
+            .by_topic_and_consumer_id(topic_id, consumer_id, None, None)
+            .await?;
+        let ret = RequestDeadline::within(
+            self.correlation_hotlist
+                .get_event_by_correlation_token(topic_id, correlation_token_str),
+        )
+        .await?;
+        if let Some((unique_time, document, protection_ref, _correlation_token, _priority)) =
+            ret.map(EventDeliveryGist::into_parts)
+        {
+            if !self
+                .integrity_validator
+                .validate_protection_ref_of_event(
+                    topic_id,
+                    &document,
+                    &protection_ref,
+                    &unique_time,
+                )
+                .await
+            {
+                Err(MessageBrokerErrorKind::IntegrityProtectionError.error())
+            } else {
+                let event_id = self
+                    .event_descriptor_cache
+                    .get_event_id_algorithm(topic_id)
+                    .event_id_from_document(&document);
+                if log::log_enabled!(log::Level::Trace) {
+                    log::trace!(
+                        "correlation token: '{correlation_token_str}' -> event_id: {event_id}"
+                    );
+                }
+                let delivery_instance_id = self.unique_timer_stamper.get_instance_id();
+                let descriptor_version = None;
+                let intent_ts_micros = fragtale_client::time::get_timestamp_micros();
+                self.dbp
+                    .consumer_delivery_facade()
+                    .delivery_intent_insert_done(
+                        topic_id,
+                        consumer_id,
+                        &event_id,
+                        unique_time,
+                        delivery_instance_id,
+                        &descriptor_version,
+                        intent_ts_micros,
+                    )
+                    .await;
+                if let Some(metrics) = &self.metrics {
+                    metrics.inc_delivered_events(topic_id);
+                    metrics.inc_delivered_bytes(topic_id, document.len());
+                    metrics.report_correlated_wait(
+                        topic_id,
+                        fragtale_client::time::get_timestamp_micros() - start_ts,
+                    );
+                }
+                Ok(Some(
+                    self.apply_masking(Some(identity), topic_id, document).await,
+                ))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Register `reply_topic_id` as the topic where correlated results of
+    /// events in `topic_id` are published.
+    ///
+    /// This is intended to be used by the responding service, so requesting
+    /// clients can await the correlated result without knowing the reply
+    /// topic. The responding service must be allowed to read from `topic_id`
+    /// and write to `reply_topic_id`.
+    ///
+    /// A registration made by another identity can only be replaced by an
+    /// owner of `topic_id` or an identity allowed to administer topics.
+    pub async fn register_reply_topic(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        reply_topic_id: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        self.access_control
+            .assert_allowed_topic_write(identity, reply_topic_id)
+            .await?;
+        if let Some(registered_by) = self
+            .dbp
+            .topic_facade()
+            .reply_topic_registered_by(topic_id)
+            .await
+            && registered_by != identity.identity_string()
+        {
+            self.access_control
+                .assert_allowed_topic_owner_or_admin(identity, topic_id)
+                .await?;
+        }
+        // Create topics on the fly, if they did not exist.
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        self.ensure_topic_setup(identity, reply_topic_id, false)
+            .await?;
+        if !self
+            .dbp
+            .topic_facade()
+            .reply_topic_register(topic_id, reply_topic_id, identity.identity_string())
+            .await
+        {
+            Err(MessageBrokerErrorKind::Unspecified.error_with_msg(format!(
+                "Failed to register reply topic '{reply_topic_id}' for topic '{topic_id}'."
+            )))?;
+        }
+        log::info!(
+            "Consumer '{}' registered topic '{reply_topic_id}' for replies to events in topic '{topic_id}'.",
+            identity.identity_string()
+        );
+        Ok(())
+    }
+
+    /// Return the topic where correlated results of events in `topic_id` are
+    /// published, if registered by the responding service.
+    pub async fn get_reply_topic(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<Option<String>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_write(identity, topic_id)
+            .await?;
+        Ok(self
+            .dbp
+            .topic_facade()
+            .reply_topic_by_topic_id(topic_id)
+            .await)
+    }
+
+    /// Return the principals that own the topic.
+    pub async fn get_topic_ownership(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<TopicOwnership, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        Ok(TopicOwnership::new(
+            topic_id,
+            self.access_control.get_topic_owners(topic_id).await,
+        ))
+    }
+
+    /// Transfer the ownership of a topic to a new principal (identity or group
+    /// string).
+    ///
+    /// Only owners of the topic and identities allowed to administer topics
+    /// may transfer ownership. The change is recorded as an audit event.
+    pub async fn transfer_topic_ownership(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        topic_ownership_transfer: TopicOwnershipTransfer,
+    ) -> Result<(), MessageBrokerError> {
+        let owner = topic_ownership_transfer.get_owner();
+        Self::assert_well_formed_principal(owner)?;
+        self.access_control
+            .assert_allowed_topic_owner_or_admin(identity, topic_id)
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let previous_owners = self
+            .access_control
+            .transfer_topic_ownership(topic_id, owner)
+            .await?;
+        self.publish_audit_event(
+            identity,
+            "topic_ownership_transfer",
+            topic_id,
+            serde_json::json!({
+                "previous_owners": previous_owners,
+                "owner": owner,
+            }),
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Grant or revoke access to a topic operation for a principal (identity
+    /// or group string).
+    ///
+    /// Only owners of the topic and identities allowed to administer topics
+    /// may edit access. The change is recorded as an audit event.
+    pub async fn update_topic_access(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        topic_access_grant: TopicAccessGrant,
+    ) -> Result<(), MessageBrokerError> {
+        let principal = topic_access_grant.get_principal();
+        let operation = topic_access_grant.get_operation();
+        Self::assert_well_formed_principal(principal)?;
+        if ![
+            TopicAccessGrant::OPERATION_WRITE,
+            TopicAccessGrant::OPERATION_ANNOTATE,
+            TopicAccessGrant::OPERATION_UNMASKED,
+        ]
+        .contains(&operation)
+        {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Access to topic operation '{operation}' cannot be granted or revoked."
+                )),
+            )?;
+        }
+        self.access_control
+            .assert_allowed_topic_owner_or_admin(identity, topic_id)
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let action = if topic_access_grant.is_granted() {
+            self.access_control
+                .grant_topic_access_for_principal(principal, topic_id, operation)
+                .await?;
+            "topic_access_grant"
+        } else {
+            self.access_control
+                .revoke_topic_access_for_principal(principal, topic_id, operation)
+                .await?;
+            "topic_access_revoke"
+        };
+        self.publish_audit_event(
+            identity,
+            action,
+            topic_id,
+            serde_json::json!({
+                "principal": principal,
+                "operation": operation,
+            }),
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Return all explicit resource authorization grants.
+    pub async fn get_resource_grants(
+        &self,
+        identity: &ClientIdentity,
+    ) -> Result<ResourceGrants, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "acl")
+            .await?;
+        Ok(self.get_resource_grants_internal().await)
+    }
+
+    /// Return all explicit resource authorization grants without any
+    /// authorization checks.
+    async fn get_resource_grants_internal(&self) -> ResourceGrants {
+        ResourceGrants::new(
+            self.access_control
+                .get_resource_grants()
+                .await
+                .iter()
+                .map(|(resource, principal)| ResourceGrant::new(resource, principal))
+                .collect(),
+        )
+    }
+
+    /// Bring the explicit resource authorization grants in line with
+    /// `resource_grants`.
+    ///
+    /// Every grant is validated before any change is applied. Grants that are
+    /// missing are added and, if `prune` is set, existing grants that are not
+    /// part of `resource_grants` are revoked. With `dry_run` set the changes
+    /// are only computed.
+    pub async fn import_resource_grants(
+        &self,
+        identity: &ClientIdentity,
+        resource_grants: ResourceGrants,
+        prune: bool,
+        dry_run: bool,
+    ) -> Result<ResourceGrantsImport, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "acl")
+            .await?;
+        let desired = resource_grants
+            .get_grants()
+            .iter()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let validation_errors = desired
+            .iter()
+            .filter_map(|resource_grant| self.validate_resource_grant(resource_grant).err())
+            .collect::<Vec<_>>();
+        if !validation_errors.is_empty() {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Rejected {} invalid grant(s): {}",
+                    validation_errors.len(),
+                    validation_errors.join(" ")
+                )),
+            )?;
+        }
+        let existing = self
+            .get_resource_grants_internal()
+            .await
+            .get_grants()
+            .iter()
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        let granted = desired.difference(&existing).cloned().collect::<Vec<_>>();
+        let revoked = if prune {
+            existing.difference(&desired).cloned().collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+        let unchanged = desired.len() - granted.len();
+        if !dry_run {
+            for resource_grant in &granted {
+                self.access_control
+                    .grant_access_to_resource_for_principal(
+                        resource_grant.get_principal(),
+                        resource_grant.get_resource(),
+                    )
+                    .await?;
+            }
+            for resource_grant in &revoked {
+                self.access_control
+                    .revoke_access_to_resource_for_principal(
+                        resource_grant.get_principal(),
+                        resource_grant.get_resource(),
+                    )
+                    .await?;
+            }
+            if !granted.is_empty() || !revoked.is_empty() {
+                self.publish_audit_event(
+                    identity,
+                    "acl_import",
+                    "",
+                    serde_json::json!({
+                        "granted": granted,
+                        "revoked": revoked,
+                    }),
+                )
+                .await;
+            }
+        }
+        Ok(ResourceGrantsImport::new(
+            dry_run, granted, revoked, unchanged,
+        ))
+    }
+
+    /// Return a description of why the grant can't be explicitly granted or
+    /// revoked if this is the case.
+    fn validate_resource_grant(&self, resource_grant: &ResourceGrant) -> Result<(), String> {
+        let principal = resource_grant.get_principal();
+        let resource = resource_grant.get_resource();
+        if !Self::is_well_formed_principal(principal) {
+            Err(format!(
+                "Principal '{principal}' is neither an identity string nor a group string."
+            ))?;
+        }
+        self.access_control.validate_grantable_resource(resource)?;
+        if resource.starts_with("/admin/") && ClientIdentity::is_group_string(principal) {
+            Err(format!(
+                "Administrative function '{resource}' cannot be granted to group '{principal}'."
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Error out with [MessageBrokerErrorKind::MalformedRequest] if the
+    /// principal can't be an identity or group string.
+    fn assert_well_formed_principal(principal: &str) -> Result<(), MessageBrokerError> {
+        if !Self::is_well_formed_principal(principal) {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Principal '{principal}' is neither an identity string nor a group string."
+                )),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Return `true` if the principal can be an identity or group string.
+    fn is_well_formed_principal(principal: &str) -> bool {
+        principal.contains(';') && !principal.contains('|')
+    }
+
+    /// Publish an audit event of a change made by `identity` to the
+    /// [Self::AUDIT_TOPIC_ID] topic.
+    ///
+    /// Failures are logged, since the change itself has already been applied.
+    async fn publish_audit_event(
+        &self,
+        identity: &ClientIdentity,
+        action: &str,
+        topic_id: &str,
+        details: serde_json::Value,
+    ) {
+        let audit_event = serde_json::json!({
+            "action": action,
+            "topic_id": topic_id,
+            "actor": identity.identity_string(),
+            "details": details,
+        })
+        .to_string();
+        log::info!("Audit: {audit_event}");
+        if let Err(e) = self
+            .publish_event_to_topic_internal(
+                identity.identity_string(),
+                Self::AUDIT_TOPIC_ID,
+                &audit_event,
+                None,
+                None,
+                None,
+                None,
+                None,
+                PublishAcknowledgement::Persisted,
+            )
+            .await
+        {
+            log::warn!("Failed to publish audit event '{audit_event}': {e}");
+        }
+    }
+
+    /// Return the health of the subscriptions to a topic served by this
+    /// instance.
+    pub async fn get_topic_subscriptions_health(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<TopicSubscriptionsHealth, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "subscriptions")
+            .await?;
+        Ok(TopicSubscriptionsHealth::new(
+            topic_id,
+            self.consumers
+                .get_by_topic(topic_id)
+                .iter()
+                .map(|topic_consumer| topic_consumer.get_health())
+                .collect(),
+        ))
+    }
+
+    /// Return the members of the consumer groups of a topic connected to this
+    /// instance with the number of events each confirmed within the last
+    /// `window_minutes` (default 5).
+    pub async fn get_topic_group_members(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        window_minutes: Option<u64>,
+    ) -> Result<TopicGroupMembers, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "subscriptions")
+            .await?;
+        let window_minutes = window_minutes
+            .unwrap_or(Self::GROUP_MEMBERS_DEFAULT_WINDOW_MINUTES)
+            .clamp(1, GroupMembers::MAX_WINDOW_MINUTES);
+        Ok(TopicGroupMembers::new(
+            topic_id,
+            self.unique_timer_stamper.get_instance_id(),
+            window_minutes,
+            self.consumers
+                .get_group_members()
+                .get_by_topic(topic_id, window_minutes),
+        ))
+    }
+
+    /// Return the WebSocket `sessions` open on this instance.
+    ///
+    /// The sessions are tracked by the API layer and only wrapped here after
+    /// authorization to the administrative function `sessions`.
+    pub async fn get_web_socket_sessions(
+        &self,
+        identity: &ClientIdentity,
+        sessions: Vec<WebSocketSession>,
+    ) -> Result<WebSocketSessions, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "sessions")
+            .await?;
+        Ok(WebSocketSessions::new(
+            self.unique_timer_stamper.get_instance_id(),
+            sessions,
+        ))
+    }
+
+    /// Kill a WebSocket session open on this instance using the provided
+    /// `kill` function and audit the action.
+    ///
+    /// `kill` returns the identity and topic of the session or `None` if the
+    /// session is unknown.
+    ///
+    /// Return `true` if the session was killed.
+    pub async fn kill_web_socket_session(
+        &self,
+        identity: &ClientIdentity,
+        session_id: u64,
+        kill: impl FnOnce(u64) -> Option<(String, Option<String>)>,
+    ) -> Result<bool, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "sessions")
+            .await?;
+        let Some((session_identity, topic_id)) = kill(session_id) else {
+            return Ok(false);
+        };
+        self.publish_audit_event(
+            identity,
+            "kill_session",
+            topic_id.as_deref().unwrap_or_default(),
+            serde_json::json!({
+                "session_id": session_id,
+                "instance_id": self.unique_timer_stamper.get_instance_id(),
+                "identity": session_identity,
+            }),
+        )
+        .await;
+        Ok(true)
+    }
+
+    /// Error out with [MessageBrokerErrorKind::MalformedRequest] if the
+    /// consumer group member identifier isn't short and URL safe.
+    pub fn assert_well_formed_member_id(member_id: &str) -> Result<(), MessageBrokerError> {
+        if !GroupMembers::is_well_formed_member_id(member_id) {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Member identifier '{member_id}' must be 1 to {} characters of 'A-Za-z0-9-_.'.",
+                    GroupMembers::MEMBER_ID_MAX_LEN
+                )),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Start tracking a subscriber connection of a consumer group member.
+    pub async fn register_group_member(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        member_id: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.access_control
+            .assert_allowed_topic_read(identity, topic_id)
+            .await?;
+        Self::assert_well_formed_member_id(member_id)?;
+        self.consumers.get_group_members().connected(
+            topic_id,
+            identity.identity_string(),
+            member_id,
+        );
+        Ok(())
+    }
+
+    /// Stop tracking a closed subscriber connection of a consumer group
+    /// member.
+    pub fn unregister_group_member(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        member_id: &str,
+    ) {
+        self.consumers.get_group_members().disconnected(
+            topic_id,
+            identity.identity_string(),
+            member_id,
+        );
+    }
+
+    /// Count an event delivery confirmed by a consumer group member.
+    ///
+    /// Invoke this after a successful [Self::confirm_event_delivery] or
+    /// [Self::commit_event_delivery].
+    pub fn report_group_member_confirmation(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        member_id: &str,
+    ) {
+        self.consumers.get_group_members().confirmed(
+            topic_id,
+            identity.identity_string(),
+            member_id,
+        );
+    }
+
+    /// Return `true` while the delivery cache of the identity's consumer on
+    /// this instance is still catching up after connecting.
+    pub fn is_consumer_catching_up(&self, identity: &ClientIdentity, topic_id: &str) -> bool {
+        self.consumers
+            .get_by_topic_and_consumer_id(topic_id, identity.identity_string())
+            .is_some_and(|topic_consumer| topic_consumer.is_catching_up())
+    }
+
+    /// Resume delivery to a subscription that was paused due to repeated
+    /// redelivery of the same event.
+    ///
+    /// Return `false` if the subscription is not paused on this instance.
+    pub async fn resume_subscription(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        subscription_resume: SubscriptionResume,
+    ) -> Result<bool, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "subscriptions")
+            .await?;
+        Ok(self
+            .consumers
+            .get_by_topic_and_consumer_id(topic_id, subscription_resume.get_consumer_id())
+            .is_some_and(|topic_consumer| topic_consumer.resume()))
+    }
+
+    /// Return the declared consumers of a topic.
+    pub async fn get_consumer_definitions(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<ConsumerDefinitions, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "consumers")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        Ok(ConsumerDefinitions::new(
+            topic_id,
+            self.consumer_definitions
+                .by_topic(topic_id)
+                .await
+                .iter()
+                .map(|consumer_definition| consumer_definition.as_ref().to_owned())
+                .collect(),
+        ))
+    }
+
+    /// Declare a named consumer of a topic or replace an existing declaration
+    /// by the same name.
+    ///
+    /// The consumer is set up right away from the declared starting position,
+    /// so no events are missed before the first delivery. The starting
+    /// position of an already set up consumer is never moved.
+    pub async fn upsert_consumer_definition(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        name: &str,
+        consumer_definition: ConsumerDefinition,
+    ) -> Result<ConsumerDefinition, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "consumers")
+            .await?;
+        if consumer_definition.get_name() != name {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Consumer name '{}' does not match '{name}'.",
+                    consumer_definition.get_name()
+                )),
+            )?;
+        }
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        self.validate_consumer_definition(topic_id, &consumer_definition)
+            .map_err(|msg| MessageBrokerErrorKind::MalformedRequest.error_with_msg(msg))?;
+        let consumer_id = consumer_definition.get_consumer_id();
+        if self
+            .consumer_definitions
+            .by_topic(topic_id)
+            .await
+            .iter()
+            .any(|existing| {
+                existing.get_name() != consumer_definition.get_name()
+                    && existing.get_consumer_id() == consumer_id
+            })
+        {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Consumer '{consumer_id}' of topic '{topic_id}' is already declared by another name."
+                )),
+            )?;
+        }
+        self.consumers
+            .by_topic_and_consumer_id(
+                topic_id,
+                &consumer_id,
+                consumer_definition.get_baseline_ts(),
+                None,
+            )
+            .await?;
+        if !self
+            .consumer_definitions
+            .persist(topic_id, &consumer_definition)
+            .await
+        {
+            Err(MessageBrokerErrorKind::Unspecified.error_with_msg(format!(
+                "Failed to persist consumer '{}' of topic '{topic_id}'.",
+                consumer_definition.get_name()
+            )))?;
+        }
+        self.publish_audit_event(
+            identity,
+            "consumer_definition_upsert",
+            topic_id,
+            serde_json::json!({
+                "name": consumer_definition.get_name(),
+                "consumer_id": consumer_id,
+            }),
+        )
+        .await;
+        Ok(consumer_definition)
+    }
+
+    /// Remove the declaration of a named consumer of a topic.
+    ///
+    /// The delivery progress of the consumer is retained, so declaring it
+    /// again will resume where it left off.
+    pub async fn delete_consumer_definition(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        name: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "consumers")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        self.consumer_definitions.remove(topic_id, name).await;
+        self.publish_audit_event(
+            identity,
+            "consumer_definition_delete",
+            topic_id,
+            serde_json::json!({ "name": name }),
+        )
+        .await;
+        Ok(())
+    }
+
+    /// Validate a consumer declaration before it is persisted.
+    fn validate_consumer_definition(
+        &self,
+        topic_id: &str,
+        consumer_definition: &ConsumerDefinition,
+    ) -> Result<(), String> {
+        let name = consumer_definition.get_name();
+        if name.is_empty()
+            || name.len() > Self::CONSUMER_DEFINITION_NAME_MAX_LEN
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+        {
+            Err(format!(
+                "Consumer name '{name}' must be 1-{} characters of a-z, 0-9, '_' or '-'.",
+                Self::CONSUMER_DEFINITION_NAME_MAX_LEN
+            ))?;
+        }
+        match consumer_definition.get_target() {
+            ConsumerDefinitionTarget::Webhook { url } => {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    Err(format!("Webhook URL '{url}' must use http or https."))?;
+                }
+            }
+            ConsumerDefinitionTarget::Subscriber { principal } => {
+                if !Self::is_well_formed_principal(principal)
+                    || ClientIdentity::is_group_string(principal)
+                {
+                    Err(format!(
+                        "Subscriber '{principal}' must be the identity string of a client."
+                    ))?;
+                }
+            }
+        }
+        if consumer_definition.get_max_events_per_second() == Some(0) {
+            Err("The max events per second must be positive.".to_string())?;
+        }
+        if let Some(fields) = consumer_definition.get_fields() {
+            DocumentProjection::new(fields).map_err(|e| e.to_string())?;
+        }
+        if let Some(filter) = consumer_definition.get_filter() {
+            self.pre_storage_processor
+                .compile_filter(topic_id, filter)
+                .map_err(|e| e.to_string())?;
+        }
+        if consumer_definition.get_event_types().is_some()
+            && self
+                .event_descriptor_cache
+                .get_event_descriptor_by_topic_latest(topic_id)
+                .is_none_or(|event_descriptor| event_descriptor.get_event_type_field().is_none())
+        {
+            Err(format!(
+                "Topic '{topic_id}' has no event type field to filter events by."
+            ))?;
+        }
+        Ok(())
+    }
+
+    /// Return the mirroring status of a topic on this instance.
+    pub async fn get_topic_mirror(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<TopicMirror, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "mirror")
+            .await?;
+        Ok(self.topic_mirror(topic_id))
+    }
+
+    /// Enable or disable mirroring of published events of a topic to rotating
+    /// files on the local disk of this instance.
+    pub async fn update_topic_mirror(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        topic_mirror_update: TopicMirrorUpdate,
+    ) -> Result<TopicMirror, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "mirror")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let enabled = topic_mirror_update.is_enabled();
+        if enabled != self.event_mirror.is_enabled(topic_id) {
+            self.event_mirror.set_enabled(topic_id, enabled);
+            log::info!(
+                "Mirroring of topic '{topic_id}' was {} by '{}'.",
+                if enabled { "enabled" } else { "disabled" },
+                identity.identity_string()
+            );
+        }
+        Ok(self.topic_mirror(topic_id))
+    }
+
+    fn topic_mirror(&self, topic_id: &str) -> TopicMirror {
+        TopicMirror::new(
+            topic_id,
+            self.event_mirror.is_enabled(topic_id),
+            self.unique_timer_stamper.get_instance_id(),
+            &self
+                .event_mirror
+                .get_topic_directory(topic_id)
+                .to_string_lossy(),
+        )
+    }
+
+    /// Return the latest bulk ingest window of a topic (if any).
+    pub async fn get_bulk_ingest_window(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<Option<BulkIngestWindow>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "bulk_ingest")
+            .await?;
+        let window = self.bulk_ingest.get_window(topic_id).await;
+        Ok(window.map(|window| self.bulk_ingest_window(topic_id, window)))
+    }
+
+    /// Open a bulk ingest window of a topic, or extend the current one.
+    ///
+    /// See [BulkIngest] for details.
+    pub async fn open_bulk_ingest_window(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        bulk_ingest_window_request: BulkIngestWindowRequest,
+    ) -> Result<BulkIngestWindow, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "bulk_ingest")
+            .await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let duration_micros = bulk_ingest_window_request
+            .get_duration_millis()
+            .saturating_mul(1000);
+        if duration_micros == 0 || duration_micros > BulkIngest::MAX_DURATION_MICROS {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Bulk ingest window duration must be between 1 and {} ms.",
+                    BulkIngest::MAX_DURATION_MICROS / 1000
+                )),
+            )?;
+        }
+        let window = self
+            .bulk_ingest
+            .open(topic_id, duration_micros)
+            .await
+            .ok_or_else(|| {
+                MessageBrokerErrorKind::Unspecified.error_with_msg(format!(
+                    "Failed to open bulk ingest window of '{topic_id}'."
+                ))
+            })?;
+        log::info!(
+            "Bulk ingest window of topic '{topic_id}' was opened until {} by '{}'.",
+            window.1,
+            identity.identity_string()
+        );
+        Ok(self.bulk_ingest_window(topic_id, window))
+    }
+
+    /// End the bulk ingest window of a topic now, which starts reconciliation
+    /// of the events published during the window.
+    pub async fn close_bulk_ingest_window(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<Option<BulkIngestWindow>, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "bulk_ingest")
+            .await?;
+        let window = self.bulk_ingest.close(topic_id).await;
+        if window.is_some() {
+            log::info!(
+                "Bulk ingest window of topic '{topic_id}' was closed by '{}'.",
+                identity.identity_string()
+            );
         }
-        Ok(ret)
+        Ok(window.map(|window| self.bulk_ingest_window(topic_id, window)))
     }
 
-    /// Confirm that the delivery of an event has been recieved and should not
-    /// be resent again.
-    ///
-    /// Note that this does not mean that the event has been processed or that
-    /// the processing could crash after this confirmation.
-    ///
-    /// A client application could choose to wait with the confirmation until
-    /// after processing is done at the risk of redelivery.
-    pub async fn confirm_event_delivery(
+    fn bulk_ingest_window(&self, topic_id: &str, window: (u64, u64, bool)) -> BulkIngestWindow {
+        let (start_ts_micros, end_ts_micros, reconciled) = window;
+        BulkIngestWindow::new(
+            topic_id,
+            start_ts_micros,
+            end_ts_micros,
+            BulkIngest::is_active(&window, fragtale_client::time::get_timestamp_micros()),
+            reconciled,
+        )
+    }
+
+    /// Return the progress of the latest rebuild of the indexed columns of a
+    /// topic (if any).
+    pub async fn get_index_rebuild(
         &self,
         identity: &ClientIdentity,
         topic_id: &str,
-        encoded_unique_time: u64,
-        delivery_instance_id: u16,
-    ) -> Result<(), MessageBrokerError> {
+    ) -> Result<Option<IndexRebuild>, MessageBrokerError> {
         self.access_control
-            .assert_allowed_topic_read(identity, topic_id)
+            .assert_allowed_admin(identity, "index_rebuild")
             .await?;
-        let consumer_id = identity.identity_string();
-        if log::log_enabled!(log::Level::Trace) {
-            log::trace!(
-                "Receiving event confirmation for '{topic_id}/{consumer_id}/{encoded_unique_time}'."
-            );
-        }
-        self.dbp.topic_facade().ensure_topic_setup(topic_id).await?;
-        self.dbp
-            .consumer_delivery_facade()
-            .delivery_intent_mark_done(
-                topic_id,
-                consumer_id,
-                UniqueTime::from(encoded_unique_time),
-                delivery_instance_id,
-            )
-            .await;
-        self.object_count_tracker
-            .inc(topic_id, &ObjectCountType::DoneDeliveryIntents);
-        if let Some(metrics) = &self.metrics {
-            metrics.inc_delivered_events(topic_id);
-        }
-        Ok(())
+        let progress = self.index_rebuilder.get_progress(topic_id).await;
+        Ok(progress.map(|progress| Self::index_rebuild(topic_id, &progress)))
     }
 
-    /// Get next event to deliver.
-    pub async fn get_event_by_consumer_and_topic(
+    /// Request a rebuild of the indexed columns of all existing events of a
+    /// topic using the extractors of the latest event descriptor.
+    ///
+    /// See [IndexRebuilder] for details.
+    pub async fn start_index_rebuild(
         &self,
         identity: &ClientIdentity,
         topic_id: &str,
-        baseline_ts: Option<u64>,
-        descriptor_version: Option<DescriptorVersion>,
-    ) -> Result<Option<(u64, String, String, u16)>, MessageBrokerError> {
+    ) -> Result<IndexRebuild, MessageBrokerError> {
         self.access_control
-            .assert_allowed_topic_read(identity, topic_id)
+            .assert_allowed_admin(identity, "index_rebuild")
             .await?;
-        let consumer_id = identity.identity_string();
-        self.dbp.topic_facade().ensure_topic_setup(topic_id).await?;
-        let topic_consumer = self
-            .consumers
-            .by_topic_and_consumer_id(topic_id, consumer_id, baseline_ts, descriptor_version)
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let progress = self.index_rebuilder.start(topic_id).await.ok_or_else(|| {
+            MessageBrokerErrorKind::Unspecified
+                .error_with_msg(format!("Failed to start index rebuild of '{topic_id}'."))
+        })?;
+        log::info!(
+            "Index rebuild of topic '{topic_id}' was requested by '{}'.",
+            identity.identity_string()
+        );
+        Ok(Self::index_rebuild(topic_id, &progress))
+    }
+
+    fn index_rebuild(topic_id: &str, progress: &IndexRebuildProgress) -> IndexRebuild {
+        let position_ts_micros = if progress.get_position() == 0 {
+            0
+        } else {
+            UniqueTime::from(progress.get_position()).get_time_micros()
+        };
+        IndexRebuild::new(
+            topic_id,
+            progress.get_requested_ts_micros(),
+            IndexRebuilder::covered_until_ts_micros(progress),
+            position_ts_micros,
+            progress.get_rebuilt_count(),
+            progress.get_completed_ts_micros(),
+        )
+    }
+
+    /// Purge or redact all events of a topic that match the key of an
+    /// indexed column.
+    ///
+    /// Without an expected count this is a dry-run that only reports the
+    /// number of matching events. Events are only modified when the expected
+    /// count equals the current number of matching events, which confirms the
+    /// outcome of a recent dry-run.
+    ///
+    /// Redaction uses the masking rules of the latest event descriptor of the
+    /// topic. Cached reads of the topic are dropped by every instance once
+    /// the audit event of the purge reaches it. If the audit event could not
+    /// be published, other instances keep serving cached reads until they
+    /// expire.
+    ///
+    /// See [EventPurger] for details.
+    pub async fn purge_events_by_index(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+        event_purge_request: EventPurgeRequest,
+    ) -> Result<EventPurge, MessageBrokerError> {
+        self.access_control
+            .assert_allowed_admin(identity, "event_purge")
             .await?;
-        if let Some((unique_time, document, protection_ref, correlation_token)) = topic_consumer
-            .reserve_delivery_intent(descriptor_version)
-            .await
-            .map(EventDeliveryGist::into_parts)
-        {
-            if log::log_enabled!(log::Level::Trace) {
-                log::trace!("Got event_delivery_gist in '{topic_id}'.");
-            }
-            let delivery_instance_id = self.unique_timer_stamper.get_instance_id();
-            if !self
-                .integrity_validator
-                .validate_protection_ref_of_event(
-                    topic_id,
-                    &document,
-                    &protection_ref,
-                    &unique_time,
-                )
-                .await
-            {
-                let msg = "Integrity protection validation failed for event in '{topic_id}' with protection_id {protection_ref}.";
-                log::warn!("{msg}");
-                // This will never be delivered.. make sure it isn't attempted again!
-                self.dbp
-                    .consumer_delivery_facade()
-                    .delivery_intent_mark_done(
-                        topic_id,
-                        consumer_id,
-                        unique_time,
-                        delivery_instance_id,
-                    )
-                    .await;
-                self.object_count_tracker
-                    .inc(topic_id, &ObjectCountType::DoneDeliveryIntents);
-                Err(MessageBrokerErrorKind::IntegrityProtectionError.error_with_msg(msg))?;
-            }
-            if log::log_enabled!(log::Level::Trace) {
-                log::trace!("Validation of event_delivery_gist in '{topic_id}' done.");
+        let index_column = event_purge_request.get_index_name();
+        let index_key = event_purge_request.get_index_key();
+        let mode = event_purge_request.get_mode();
+        self.ensure_topic_setup(identity, topic_id, false).await?;
+        let masking_rules = match mode {
+            EventPurgeMode::Purge => vec![],
+            EventPurgeMode::Redact => {
+                self.get_masking_rules(None, topic_id)
+                    .await
+                    .ok_or_else(|| {
+                        MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                            "Topic '{topic_id}' has no masking rules to redact events with."
+                        ))
+                    })?
             }
-            if let Some(metrics) = &self.metrics {
-                metrics.inc_delivered_bytes(topic_id, document.len());
-                let now = fragtale_client::time::get_timestamp_micros();
-                metrics.report_publish_to_delivery_latency_micros(
-                    topic_id,
-                    now - unique_time.get_time_micros(),
-                );
+        };
+        let events = self
+            .event_purger
+            .matching_events(topic_id, index_column, index_key)
+            .await;
+        let matched_count = u64::try_from(events.len()).unwrap_or(u64::MAX);
+        let Some(expected_count) = event_purge_request.get_expected_count() else {
+            return Ok(EventPurge::new(
+                topic_id,
+                index_column,
+                mode,
+                true,
+                matched_count,
+                0,
+            ));
+        };
+        if expected_count != matched_count {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Expected {expected_count} matching events, but {matched_count} match now. Run a new dry-run."
+                )),
+            )?;
+        }
+        let purged_count = match mode {
+            EventPurgeMode::Purge => self.event_purger.purge(topic_id, &events).await,
+            EventPurgeMode::Redact => {
+                self.event_purger
+                    .redact(topic_id, &events, &masking_rules)
+                    .await
             }
-            Ok(Some((
-                unique_time.as_encoded(),
-                document.to_owned(),
-                correlation_token,
-                delivery_instance_id,
-            )))
-        } else {
-            Ok(None)
+        };
+        self.index_read_cache.remove(&(
+            topic_id.to_owned(),
+            index_column.to_owned(),
+            index_key.to_owned(),
+        ));
+        for (event_id, _unique_time) in &events {
+            self.event_read_cache
+                .remove(&(topic_id.to_owned(), event_id.to_owned()));
         }
+        log::info!(
+            "Purge ({mode:?}) of {purged_count}/{matched_count} events by index '{index_column}' in topic '{topic_id}' was requested by '{}'.",
+            identity.identity_string()
+        );
+        // The index key is likely personal data and is left out of the audit trail
+        self.publish_audit_event(
+            identity,
+            "event_purge",
+            topic_id,
+            serde_json::json!({
+                "index_name": index_column,
+                "mode": mode,
+                "matched_count": matched_count,
+                "purged_count": purged_count,
+            }),
+        )
+        .await;
+        Ok(EventPurge::new(
+            topic_id,
+            index_column,
+            mode,
+            false,
+            matched_count,
+            purged_count,
+        ))
     }
 
-    /// Return an event by the correlation token or `None` if an event has not
-    /// appeared before the timeout.
-    pub async fn get_event_by_correlation_token(
+    /// Return the event document by the provided event identifier and the
+    /// [StorageTier] it was read from.
+    ///
+    /// Events that are no longer present in the database are looked up in
+    /// the archive tier (if enabled), which is much slower.
+    pub async fn get_event_by_id(
         &self,
         identity: &ClientIdentity,
         topic_id: &str,
-        correlation_token_str: &str,
-    ) -> Result<Option<String>, MessageBrokerError> {
-        let start_ts = fragtale_client::time::get_timestamp_micros();
+        event_id: &str,
+    ) -> Result<Option<(Arc<str>, StorageTier)>, MessageBrokerError> {
         self.access_control
             .assert_allowed_topic_read(identity, topic_id)
             .await?;
         let consumer_id = identity.identity_string();
         // Create topic on the fly, if it did not exist.
-        self.dbp.topic_facade().ensure_topic_setup(topic_id).await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
         // Create a Consumer if it did not exist.
         self.consumers
             .by_topic_and_consumer_id(topic_id, consumer_id, None, None)
             .await?;
-        let ret = self
-            .correlation_hotlist
-            .get_event_by_correlation_token(topic_id, correlation_token_str)
-            .await;
-        if let Some((unique_time, document, protection_ref, _correlation_token)) =
-            ret.map(EventDeliveryGist::into_parts)
+        let cache_key = (topic_id.to_owned(), event_id.to_owned());
+        let ret_opt = if let Some((unique_time, document)) = self.event_read_cache.get(&cache_key) {
+            Some((unique_time, document, StorageTier::Database))
+        } else if let Some((unique_time, document, protection_ref, _correlation_token, _priority)) =
+            RequestDeadline::within(self.dbp.event_facade().event_by_id(topic_id, event_id))
+                .await?
+                .map(EventDeliveryGist::into_parts)
         {
             if !self
                 .integrity_validator
@@ -559,68 +5801,25 @@ impl MessageBroker {
                 )
                 .await
             {
-                Err(MessageBrokerErrorKind::IntegrityProtectionError.error())
-            } else {
-                let event_id = TopicEvent::event_id_from_document(&document);
-                if log::log_enabled!(log::Level::Trace) {
-                    log::trace!(
-                        "correlation token: '{correlation_token_str}' -> event_id: {event_id}"
-                    );
-                }
-                let delivery_instance_id = self.unique_timer_stamper.get_instance_id();
-                let descriptor_version = None;
-                let intent_ts_micros = fragtale_client::time::get_timestamp_micros();
-                self.dbp
-                    .consumer_delivery_facade()
-                    .delivery_intent_insert_done(
-                        topic_id,
-                        consumer_id,
-                        &event_id,
-                        unique_time,
-                        delivery_instance_id,
-                        &descriptor_version,
-                        intent_ts_micros,
-                    )
-                    .await;
-                if let Some(metrics) = &self.metrics {
-                    metrics.inc_delivered_events(topic_id);
-                    metrics.inc_delivered_bytes(topic_id, document.len());
-                    metrics.report_correlated_wait(
-                        topic_id,
-                        fragtale_client::time::get_timestamp_micros() - start_ts,
-                    );
-                }
-                Ok(Some(document.to_owned()))
+                Err(
+                    MessageBrokerErrorKind::IntegrityProtectionError.error_with_msg(format!(
+                        "Failed to verify integrity for event with id '{event_id}'."
+                    )),
+                )?;
             }
-        } else {
-            Ok(None)
-        }
-    }
-
-    /// Return the event document by the provided event identifier.
-    pub async fn get_event_by_id(
-        &self,
-        identity: &ClientIdentity,
-        topic_id: &str,
-        event_id: &str,
-    ) -> Result<Option<String>, MessageBrokerError> {
-        self.access_control
-            .assert_allowed_topic_read(identity, topic_id)
-            .await?;
-        let consumer_id = identity.identity_string();
-        // Create topic on the fly, if it did not exist.
-        self.dbp.topic_facade().ensure_topic_setup(topic_id).await?;
-        // Create a Consumer if it did not exist.
-        self.consumers
-            .by_topic_and_consumer_id(topic_id, consumer_id, None, None)
-            .await?;
-        let ret_opt = self
-            .dbp
-            .event_facade()
+            // Only integrity validated documents are cached
+            self.event_read_cache
+                .insert(cache_key, (unique_time, Arc::clone(&document)));
+            Some((unique_time, document, StorageTier::Database))
+        } else if let Some(archived_event) = self
+            .event_archive
             .event_by_id(topic_id, event_id)
             .await
-            .map(EventDeliveryGist::into_parts);
-        if let Some((unique_time, document, protection_ref, _correlation_token)) = ret_opt {
+            .filter(|archived_event| {
+                !archived_event.is_expired(fragtale_client::time::get_timestamp_micros())
+            })
+        {
+            let (unique_time, document, protection_ref) = archived_event.into_parts();
             if !self
                 .integrity_validator
                 .validate_protection_ref_of_event(
@@ -633,36 +5832,86 @@ impl MessageBroker {
             {
                 Err(
                     MessageBrokerErrorKind::IntegrityProtectionError.error_with_msg(format!(
-                        "Failed to verify integrity for event with id '{event_id}'."
+                        "Failed to verify integrity for archived event with id '{event_id}'."
                     )),
+                )?;
+            }
+            Some((unique_time, document, StorageTier::Archive))
+        } else {
+            None
+        };
+        if let Some((unique_time, document, storage_tier)) = ret_opt {
+            let delivery_instance_id = self.unique_timer_stamper.get_instance_id();
+            let descriptor_version = None;
+            let intent_ts_micros = fragtale_client::time::get_timestamp_micros();
+            self.dbp
+                .consumer_delivery_facade()
+                .delivery_intent_insert_done(
+                    topic_id,
+                    consumer_id,
+                    event_id,
+                    unique_time,
+                    delivery_instance_id,
+                    &descriptor_version,
+                    intent_ts_micros,
                 )
-            } else {
-                let delivery_instance_id = self.unique_timer_stamper.get_instance_id();
-                let descriptor_version = None;
-                let intent_ts_micros = fragtale_client::time::get_timestamp_micros();
-                self.dbp
-                    .consumer_delivery_facade()
-                    .delivery_intent_insert_done(
-                        topic_id,
-                        consumer_id,
-                        event_id,
-                        unique_time,
-                        delivery_instance_id,
-                        &descriptor_version,
-                        intent_ts_micros,
-                    )
-                    .await;
-                if let Some(metrics) = &self.metrics {
-                    metrics.inc_delivered_events(topic_id);
-                    metrics.inc_delivered_bytes(topic_id, document.len());
-                }
-                Ok(Some(document))
+                .await;
+            if let Some(metrics) = &self.metrics {
+                metrics.inc_delivered_events(topic_id);
+                metrics.inc_delivered_bytes(topic_id, document.len());
             }
+            Ok(Some((
+                self.apply_masking(Some(identity), topic_id, document).await,
+                storage_tier,
+            )))
         } else {
             Ok(None)
         }
     }
 
+    /// Return the topics that the client is allowed to read and that contain
+    /// an event with the event identifier.
+    ///
+    /// The global lookup is populated at publish time, so each location is
+    /// verified against the topic before it is returned.
+    pub async fn get_event_locations(
+        &self,
+        identity: &ClientIdentity,
+        event_id: &str,
+    ) -> Result<EventLocations, MessageBrokerError> {
+        let candidates = RequestDeadline::within(
+            self.dbp
+                .event_facade()
+                .event_topics_by_event_id(event_id, Self::EVENT_LOCATIONS_MAX),
+        )
+        .await?;
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let mut locations = vec![];
+        for (topic_id, _unique_time) in candidates {
+            if self
+                .access_control
+                .assert_allowed_topic_read(identity, &topic_id)
+                .await
+                .is_err()
+            {
+                continue;
+            }
+            if let Some(event_delivery_gist) = self
+                .dbp
+                .event_facade()
+                .event_by_id(&topic_id, event_id)
+                .await
+                .filter(|event_delivery_gist| !event_delivery_gist.is_expired(now_micros))
+            {
+                locations.push(EventLocation::new(
+                    &topic_id,
+                    event_delivery_gist.get_unique_time().as_encoded(),
+                ));
+            }
+        }
+        Ok(EventLocations::new(event_id, locations))
+    }
+
     /// Return event identifiers that match an indexed query.
     pub async fn get_event_ids_by_indexed_column(
         &self,
@@ -679,17 +5928,26 @@ impl MessageBroker {
             log::trace!("Consumer '{consumer_id}' queried index {topic_id}.{index_column}.");
         }
         // Create topic on the fly, if it did not exist.
-        self.dbp.topic_facade().ensure_topic_setup(topic_id).await?;
+        self.ensure_topic_setup(identity, topic_id, false).await?;
         // Create a Consumer if it did not exist.
         self.consumers
             .by_topic_and_consumer_id(topic_id, consumer_id, None, None)
             .await?;
-
-        let ret = self
-            .dbp
-            .event_facade()
-            .event_ids_by_index(topic_id, index_column, index_key)
-            .await;
+        let cache_key = (
+            topic_id.to_owned(),
+            index_column.to_owned(),
+            index_key.to_owned(),
+        );
+        if let Some(cached) = self.index_read_cache.get(&cache_key) {
+            return Ok(cached);
+        }
+        let ret = RequestDeadline::within(self.dbp.event_facade().event_ids_by_index(
+            topic_id,
+            index_column,
+            index_key,
+        ))
+        .await?;
+        self.index_read_cache.insert(cache_key, ret.clone());
         Ok(ret)
     }
 }
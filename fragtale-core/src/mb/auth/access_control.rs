@@ -25,6 +25,7 @@ pub use self::access_control_cache::*;
 pub use self::policy_engine::*;
 pub use self::policy_engine_local::*;
 use super::ClientIdentity;
+use crate::mb::MessageBroker;
 use fragtale_dbp::dbp::DatabaseProvider;
 use fragtale_dbp::mb::MessageBrokerError;
 use fragtale_dbp::mb::MessageBrokerErrorKind;
@@ -34,17 +35,91 @@ use std::sync::Arc;
 pub struct AccessControl {
     cache: Arc<AccessControlCache>,
     policy_engine: Arc<dyn PolicyEngine>,
+    tenant_claim: Option<String>,
 }
 
 impl AccessControl {
+    /// Max number of owners of a single topic that will be listed.
+    const MAX_TOPIC_OWNERS: usize = 100;
+    /// Max number of explicit grants that will be listed.
+    pub const MAX_RESOURCE_GRANTS: usize = 100_000;
+    /// Separator between the tenant and the rest of a topic identifier.
+    const TENANT_SEPARATOR: char = '_';
+
     /// Return a new instance.
-    pub async fn new(dbp: &Arc<DatabaseProvider>) -> Arc<Self> {
+    ///
+    /// When `tenant_claim` is present, topics are isolated per tenant held by
+    /// this bearer token claim.
+    pub async fn new(dbp: &Arc<DatabaseProvider>, tenant_claim: Option<&str>) -> Arc<Self> {
         Arc::new(Self {
             cache: AccessControlCache::new().await,
             policy_engine: PolicyEngineLocal::new(dbp).await,
+            tenant_claim: tenant_claim.map(str::to_string),
+        })
+    }
+
+    /// Return the tenant of the client identity or `None` if topics are not
+    /// isolated for the identity.
+    ///
+    /// Identities of this Pod are never bound to a tenant, while other
+    /// identities must belong to a tenant when topics are isolated.
+    pub fn get_tenant<'a>(
+        &self,
+        identity: &'a ClientIdentity,
+    ) -> Result<Option<&'a str>, MessageBrokerError> {
+        let Some(tenant_claim) = &self.tenant_claim else {
+            return Ok(None);
+        };
+        if identity.is_local() {
+            return Ok(None);
+        }
+        identity.tenant(tenant_claim)?.map(Some).ok_or_else(|| {
+            let msg = format!("Identity: '{identity}' does not belong to any tenant.");
+            log::info!("{msg}");
+            MessageBrokerErrorKind::Unauthorized.error_with_msg(msg)
         })
     }
 
+    /// Error out with [MessageBrokerErrorKind::Unauthorized] if the topic is
+    /// outside of the namespace of the client identity's tenant.
+    ///
+    /// The topic identifiers of a tenant are prefixed with `{tenant}_`.
+    pub fn assert_tenant_topic(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<(), MessageBrokerError> {
+        if let Some(tenant) = self.get_tenant(identity)?
+            && !Self::is_tenant_topic(tenant, topic_id)
+        {
+            let msg = format!(
+                "Identity: '{identity}' of tenant '{tenant}' is not allowed to access topic '{topic_id}'."
+            );
+            log::info!("{msg}");
+            Err(MessageBrokerErrorKind::Unauthorized.error_with_msg(msg))?;
+        }
+        Ok(())
+    }
+
+    /// Return `true` if the topic is in the namespace of the tenant.
+    ///
+    /// System topics are never in the namespace of a tenant.
+    pub fn is_tenant_topic(tenant: &str, topic_id: &str) -> bool {
+        !Self::is_system_topic(topic_id)
+            && topic_id
+                .strip_prefix(tenant)
+                .and_then(|rest| rest.strip_prefix(Self::TENANT_SEPARATOR))
+                .is_some_and(|rest| !rest.is_empty())
+    }
+
+    /// Return `true` if the topic is in the reserved namespace of system
+    /// topics.
+    pub fn is_system_topic(topic_id: &str) -> bool {
+        topic_id
+            .strip_prefix(MessageBroker::SYSTEM_TOPIC_NAMESPACE)
+            .is_some_and(|rest| rest.starts_with(Self::TENANT_SEPARATOR))
+    }
+
     /// Error out with [MessageBrokerErrorKind::Unauthorized] if the client
     /// identity isn't allowed to write to the specified topic.
     pub async fn assert_allowed_topic_write(
@@ -52,6 +127,12 @@ impl AccessControl {
         identity: &ClientIdentity,
         topic_id: &str,
     ) -> Result<(), MessageBrokerError> {
+        self.assert_tenant_topic(identity, topic_id)?;
+        if Self::is_system_topic(topic_id) && !identity.is_local() {
+            let msg = format!("Identity: '{identity}' is not allowed to write system topics.");
+            log::info!("{msg}");
+            Err(MessageBrokerErrorKind::Unauthorized.error_with_msg(msg))?;
+        }
         let resource = format!("/topic/{topic_id}/write");
         let res = self
             .assert_authorized_to_resource(identity, &resource)
@@ -62,13 +143,190 @@ impl AccessControl {
             .is_any_authorized_to_resource(&resource)
             .await
         {
+            self.grant_access_to_resource_for(identity, &resource, None)
+                .await?;
             return self
-                .grant_access_to_resource_for(identity, &resource, None)
+                .claim_topic_ownership_if_unowned(identity, topic_id)
                 .await;
         }
         res
     }
 
+    /// Error out with [MessageBrokerErrorKind::Unauthorized] if the client
+    /// identity is neither an owner of the specified topic nor allowed to
+    /// administer topics.
+    ///
+    /// Topics without a recorded owner (e.g. created before ownership was
+    /// tracked) are claimed by the first identity that is allowed to write to
+    /// the topic.
+    pub async fn assert_allowed_topic_owner_or_admin(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.assert_tenant_topic(identity, topic_id)?;
+        let resource = format!("/topic/{topic_id}/owner");
+        if self
+            .assert_authorized_to_resource(identity, &resource)
+            .await
+            .is_ok()
+            || self.assert_allowed_admin(identity, "topics").await.is_ok()
+        {
+            return Ok(());
+        }
+        if !self
+            .policy_engine
+            .is_any_authorized_to_resource(&resource)
+            .await
+        {
+            self.assert_allowed_topic_write(identity, topic_id).await?;
+            return self
+                .claim_topic_ownership_if_unowned(identity, topic_id)
+                .await;
+        }
+        let msg = format!("Identity: '{identity}' is not an owner of topic '{topic_id}'.");
+        log::info!("{msg}");
+        Err(MessageBrokerErrorKind::Unauthorized.error_with_msg(msg))
+    }
+
+    /// Record the client identity as owner of the topic unless there already
+    /// is an owner.
+    async fn claim_topic_ownership_if_unowned(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<(), MessageBrokerError> {
+        let resource = format!("/topic/{topic_id}/owner");
+        if identity.is_local()
+            || self
+                .policy_engine
+                .is_any_authorized_to_resource(&resource)
+                .await
+        {
+            return Ok(());
+        }
+        self.grant_access_to_resource_for(identity, &resource, None)
+            .await
+    }
+
+    /// Return the principals (identity or group strings) that own the topic.
+    pub async fn get_topic_owners(&self, topic_id: &str) -> Vec<String> {
+        self.policy_engine
+            .principals_authorized_to_resource(
+                &format!("/topic/{topic_id}/owner"),
+                Self::MAX_TOPIC_OWNERS,
+            )
+            .await
+    }
+
+    /// Make `owner` (identity or group string) the only owner of the topic.
+    ///
+    /// The new owner is also granted write access to the topic, unless it is
+    /// a group.
+    ///
+    /// Return the principals that owned the topic before the transfer.
+    pub async fn transfer_topic_ownership(
+        &self,
+        topic_id: &str,
+        owner: &str,
+    ) -> Result<Vec<String>, MessageBrokerError> {
+        let previous_owners = self.get_topic_owners(topic_id).await;
+        self.grant_topic_access_for_principal(owner, topic_id, "owner")
+            .await?;
+        if !ClientIdentity::is_group_string(owner) {
+            self.grant_topic_access_for_principal(owner, topic_id, "write")
+                .await?;
+        }
+        for previous_owner in previous_owners.iter().filter(|p| p.as_str() != owner) {
+            self.revoke_topic_access_for_principal(previous_owner, topic_id, "owner")
+                .await?;
+        }
+        Ok(previous_owners)
+    }
+
+    /// Grant `principal` (identity or group string) access to the topic
+    /// `operation`.
+    pub async fn grant_topic_access_for_principal(
+        &self,
+        principal: &str,
+        topic_id: &str,
+        operation: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.grant_access_to_resource_for_principal(
+            principal,
+            &format!("/topic/{topic_id}/{operation}"),
+        )
+        .await
+    }
+
+    /// Revoke access to the topic `operation` from `principal` (identity or
+    /// group string).
+    pub async fn revoke_topic_access_for_principal(
+        &self,
+        principal: &str,
+        topic_id: &str,
+        operation: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.revoke_access_to_resource_for_principal(
+            principal,
+            &format!("/topic/{topic_id}/{operation}"),
+        )
+        .await
+    }
+
+    /// Return up to [Self::MAX_RESOURCE_GRANTS] explicit grants as
+    /// `(resource, principal)` tuples.
+    pub async fn get_resource_grants(&self) -> Vec<(String, String)> {
+        self.policy_engine
+            .resource_grants(Self::MAX_RESOURCE_GRANTS)
+            .await
+    }
+
+    /// Return a description of why `resource` can't be explicitly granted or
+    /// revoked if this is the case.
+    pub fn validate_grantable_resource(&self, resource: &str) -> Result<(), String> {
+        self.policy_engine.validate_grantable_resource(resource)
+    }
+
+    /// Grant `principal` (identity or group string) access to `resource`.
+    pub async fn grant_access_to_resource_for_principal(
+        &self,
+        principal: &str,
+        resource: &str,
+    ) -> Result<(), MessageBrokerError> {
+        if !self
+            .policy_engine
+            .grant_access_to_resource_for_principal(principal, resource, None)
+            .await
+        {
+            let msg = format!("Failed to grant '{principal}' access to '{resource}'.");
+            log::warn!("{msg}");
+            Err(MessageBrokerErrorKind::Unspecified.error_with_msg(msg))?;
+        }
+        log::info!("Granted '{principal}' access to '{resource}'.");
+        Ok(())
+    }
+
+    /// Revoke access to `resource` from `principal` (identity or group
+    /// string).
+    pub async fn revoke_access_to_resource_for_principal(
+        &self,
+        principal: &str,
+        resource: &str,
+    ) -> Result<(), MessageBrokerError> {
+        if !self
+            .policy_engine
+            .revoke_access_to_resource_for_principal(principal, resource)
+            .await
+        {
+            let msg = format!("Failed to revoke access to '{resource}' from '{principal}'.");
+            log::warn!("{msg}");
+            Err(MessageBrokerErrorKind::Unspecified.error_with_msg(msg))?;
+        }
+        log::info!("Revoked access to '{resource}' from '{principal}'.");
+        Ok(())
+    }
+
     /// Error out with [MessageBrokerErrorKind::Unauthorized] if the client
     /// identity isn't allowed to read from the specified topic.
     pub async fn assert_allowed_topic_read(
@@ -76,10 +334,61 @@ impl AccessControl {
         identity: &ClientIdentity,
         topic_id: &str,
     ) -> Result<(), MessageBrokerError> {
+        self.assert_tenant_topic(identity, topic_id)?;
         self.assert_authorized_to_resource(identity, &format!("/topic/{topic_id}/read"))
             .await
     }
 
+    /// Error out with [MessageBrokerErrorKind::Unauthorized] if the client
+    /// identity isn't allowed to annotate events in the specified topic.
+    pub async fn assert_allowed_topic_annotate(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.assert_tenant_topic(identity, topic_id)?;
+        self.assert_authorized_to_resource(identity, &format!("/topic/{topic_id}/annotate"))
+            .await
+    }
+
+    /// Return `true` if the client identity is allowed to receive events of
+    /// the specified topic without the topic's masking rules applied.
+    ///
+    /// Denials are expected for most consumers and are neither logged nor
+    /// cached.
+    pub async fn is_allowed_topic_unmasked(
+        &self,
+        identity: &ClientIdentity,
+        topic_id: &str,
+    ) -> bool {
+        if self.assert_tenant_topic(identity, topic_id).is_err() {
+            return false;
+        }
+        let resource = format!("/topic/{topic_id}/unmasked");
+        if self.cache.is_authorized_to_resource(identity, &resource) {
+            return true;
+        }
+        let authorized = self
+            .policy_engine
+            .is_authorized_to_resource(identity, &resource)
+            .await;
+        if authorized {
+            self.cache.insert(identity, &resource);
+        }
+        authorized
+    }
+
+    /// Error out with [MessageBrokerErrorKind::Unauthorized] if the client
+    /// identity isn't allowed to use the specified administrative function.
+    pub async fn assert_allowed_admin(
+        &self,
+        identity: &ClientIdentity,
+        admin_function: &str,
+    ) -> Result<(), MessageBrokerError> {
+        self.assert_authorized_to_resource(identity, &format!("/admin/{admin_function}/execute"))
+            .await
+    }
+
     /// Error out with [MessageBrokerErrorKind::Unauthorized] if the client
     /// identity isn't allowed to read from the specified resource.
     async fn assert_authorized_to_resource(
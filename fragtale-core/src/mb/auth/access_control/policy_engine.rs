@@ -29,6 +29,22 @@ pub trait PolicyEngine: Sync + Send {
     /// Return `true` if any `identity` that is authorized to `resource`.
     async fn is_any_authorized_to_resource(&self, resource: &str) -> bool;
 
+    /// Return up to `max_results` principals (identity or group strings) that
+    /// are explicitly authorized to `resource`.
+    async fn principals_authorized_to_resource(
+        &self,
+        resource: &str,
+        max_results: usize,
+    ) -> Vec<String>;
+
+    /// Return up to `max_results` explicit grants as `(resource, principal)`
+    /// tuples.
+    async fn resource_grants(&self, max_results: usize) -> Vec<(String, String)>;
+
+    /// Return a description of why `resource` can't be explicitly granted or
+    /// revoked if this is the case.
+    fn validate_grantable_resource(&self, resource: &str) -> Result<(), String>;
+
     /// Grant `identity` authorization for `resource`.
     async fn grant_access_to_resource_for(
         &self,
@@ -36,4 +52,21 @@ pub trait PolicyEngine: Sync + Send {
         resource: &str,
         expires: Option<u64>,
     ) -> bool;
+
+    /// Grant `principal` (identity or group string) authorization for
+    /// `resource`.
+    async fn grant_access_to_resource_for_principal(
+        &self,
+        principal: &str,
+        resource: &str,
+        expires: Option<u64>,
+    ) -> bool;
+
+    /// Revoke a previously granted authorization for `resource` from
+    /// `principal` (identity or group string).
+    async fn revoke_access_to_resource_for_principal(
+        &self,
+        principal: &str,
+        resource: &str,
+    ) -> bool;
 }
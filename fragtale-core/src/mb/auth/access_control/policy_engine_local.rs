@@ -31,8 +31,17 @@ use std::sync::Arc;
 /// Writing to topic is only allowed by the identity that first performed an
 /// operation that required write access.
 ///
+/// Annotating events in a topic requires an explicit grant.
+///
 /// This supports a model with a single topic "owner" and access to the topic's
 /// data dont' have to be prevented, but should be auditable.
+///
+/// Ownership of a topic is recorded when it is first claimed and can be held
+/// by an identity or by a group from the bearer token's `groups` claim.
+/// Grants to a group apply to every member of the group.
+///
+/// Administrative functions are only allowed for local identities and
+/// identities that have been explicitly granted access.
 pub struct PolicyEngineLocal {
     dbp: Arc<DatabaseProvider>,
 }
@@ -65,6 +74,31 @@ impl PolicyEngineLocal {
         }
         Ok((resource_type, object_id, operation))
     }
+
+    /// Return `true` if the `identity` or any of its groups has been granted
+    /// access to the `resource`.
+    async fn is_identity_or_group_authorized(
+        &self,
+        identity: &ClientIdentity,
+        resource: &str,
+    ) -> bool {
+        let authorization_facade = self.dbp.authorization_facade();
+        if authorization_facade
+            .is_authorized_to_resource(identity.identity_string(), resource)
+            .await
+        {
+            return true;
+        }
+        for group_string in identity.group_strings() {
+            if authorization_facade
+                .is_authorized_to_resource(&group_string, resource)
+                .await
+            {
+                return true;
+            }
+        }
+        false
+    }
 }
 
 #[async_trait::async_trait]
@@ -89,10 +123,8 @@ impl PolicyEngine for PolicyEngineLocal {
                         // The PolicyEngineLocal policy is to always allow topic reads.
                         true
                     }
-                    "write" => {
-                        self.dbp
-                            .authorization_facade()
-                            .is_authorized_to_resource(identity.identity_string(), resource)
+                    "write" | "annotate" | "unmasked" | "owner" => {
+                        self.is_identity_or_group_authorized(identity, resource)
                             .await
                     }
                     _ => {
@@ -103,6 +135,13 @@ impl PolicyEngine for PolicyEngineLocal {
                     }
                 }
             }
+            "admin" => {
+                // Administrative functions require an explicit grant.
+                self.dbp
+                    .authorization_facade()
+                    .is_authorized_to_resource(identity.identity_string(), resource)
+                    .await
+            }
             _ => {
                 log::info!(
                     "Denied access to '{resource}', since resource type '{resource_type}' is unknown."
@@ -128,7 +167,7 @@ impl PolicyEngine for PolicyEngineLocal {
                         // The PolicyEngineLocal policy is to always allow topic reads.
                         true
                     }
-                    "write" => {
+                    "write" | "annotate" | "unmasked" | "owner" => {
                         self.dbp
                             .authorization_facade()
                             .is_any_authorized_to_resource(resource)
@@ -142,6 +181,12 @@ impl PolicyEngine for PolicyEngineLocal {
                     }
                 }
             }
+            "admin" => {
+                self.dbp
+                    .authorization_facade()
+                    .is_any_authorized_to_resource(resource)
+                    .await
+            }
             _ => {
                 log::info!(
                     "Denied access to '{resource}', since resource type '{resource_type}' is unknown."
@@ -151,6 +196,52 @@ impl PolicyEngine for PolicyEngineLocal {
         }
     }
 
+    async fn principals_authorized_to_resource(
+        &self,
+        resource: &str,
+        max_results: usize,
+    ) -> Vec<String> {
+        match Self::split_resource_into_parts(resource) {
+            Ok(("topic", _, "write" | "annotate" | "unmasked" | "owner")) | Ok(("admin", _, _)) => {
+                self.dbp
+                    .authorization_facade()
+                    .identities_authorized_to_resource(resource, max_results)
+                    .await
+            }
+            Ok(_) => vec![],
+            Err(e) => {
+                log::info!("Unable to list principals of '{resource}': {e}");
+                vec![]
+            }
+        }
+    }
+
+    async fn resource_grants(&self, max_results: usize) -> Vec<(String, String)> {
+        self.dbp
+            .authorization_facade()
+            .resource_grants(max_results)
+            .await
+    }
+
+    fn validate_grantable_resource(&self, resource: &str) -> Result<(), String> {
+        if !resource.starts_with('/') {
+            Err(format!(
+                "Resource '{resource}' must start with '/'. (Format: '/type/object_id/operation')"
+            ))?;
+        }
+        match Self::split_resource_into_parts(resource)? {
+            (_, "", _) => Err(format!(
+                "Resource '{resource}' has an empty object identifier part."
+            )),
+            ("topic", _, "write" | "annotate" | "unmasked" | "owner") | ("admin", _, "execute") => {
+                Ok(())
+            }
+            _ => Err(format!(
+                "Resource '{resource}' cannot be explicitly granted. Expected '/topic/<topic_id>/write', '/topic/<topic_id>/annotate', '/topic/<topic_id>/unmasked', '/topic/<topic_id>/owner' or '/admin/<function>/execute'."
+            )),
+        }
+    }
+
     async fn grant_access_to_resource_for(
         &self,
         identity: &ClientIdentity,
@@ -161,6 +252,16 @@ impl PolicyEngine for PolicyEngineLocal {
             // NOOP: The PolicyEngineLocal policy is to always allow local tokens access to anything.
             return true;
         }
+        self.grant_access_to_resource_for_principal(identity.identity_string(), resource, expires)
+            .await
+    }
+
+    async fn grant_access_to_resource_for_principal(
+        &self,
+        principal: &str,
+        resource: &str,
+        expires: Option<u64>,
+    ) -> bool {
         let (resource_type, _object_id, operation) =
             Self::split_resource_into_parts(resource).unwrap();
         match resource_type {
@@ -170,14 +271,10 @@ impl PolicyEngine for PolicyEngineLocal {
                         // NOOP: The PolicyEngineLocal policy is to always allow topic reads.
                         true
                     }
-                    "write" => {
+                    "write" | "annotate" | "unmasked" | "owner" => {
                         self.dbp
                             .authorization_facade()
-                            .grant_access_to_resource_for(
-                                identity.identity_string(),
-                                resource,
-                                expires,
-                            )
+                            .grant_access_to_resource_for(principal, resource, expires)
                             .await
                     }
                     _ => {
@@ -188,6 +285,12 @@ impl PolicyEngine for PolicyEngineLocal {
                     }
                 }
             }
+            "admin" if operation == "execute" => {
+                self.dbp
+                    .authorization_facade()
+                    .grant_access_to_resource_for(principal, resource, expires)
+                    .await
+            }
             _ => {
                 log::warn!(
                     "Unable to grant access to '{resource}', since resource type '{resource_type}' is unknown."
@@ -196,4 +299,27 @@ impl PolicyEngine for PolicyEngineLocal {
             }
         }
     }
+
+    async fn revoke_access_to_resource_for_principal(
+        &self,
+        principal: &str,
+        resource: &str,
+    ) -> bool {
+        let (resource_type, _object_id, operation) =
+            Self::split_resource_into_parts(resource).unwrap();
+        match (resource_type, operation) {
+            ("topic", "write" | "annotate" | "unmasked" | "owner") | ("admin", "execute") => {
+                self.dbp
+                    .authorization_facade()
+                    .deny_access_to_resource_for(principal, resource, None)
+                    .await
+            }
+            _ => {
+                log::warn!(
+                    "Unable to revoke access to '{resource}', since it is not explicitly granted."
+                );
+                false
+            }
+        }
+    }
 }
@@ -0,0 +1,61 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Accepted ways for API clients to authenticate.
+
+/// Accepted ways for API clients to authenticate.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ClientAuthenticationMode {
+    /// Only accept bearer tokens.
+    #[default]
+    Bearer,
+    /// Require a client certificate during the TLS handshake (mTLS).
+    Certificate,
+    /// Accept a client certificate when presented and fall back to bearer
+    /// tokens otherwise.
+    Any,
+}
+
+impl ClientAuthenticationMode {
+    /// Return the mode with the `name` if supported.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "bearer" => Some(Self::Bearer),
+            "mtls" => Some(Self::Certificate),
+            "any" => Some(Self::Any),
+            _ => None,
+        }
+    }
+
+    /// Return `true` if clients may authenticate with a bearer token.
+    pub fn accepts_bearer_token(&self) -> bool {
+        *self != Self::Certificate
+    }
+
+    /// Return `true` if clients may authenticate with a client certificate.
+    ///
+    /// This requires the API to be served over TLS.
+    pub fn accepts_client_certificate(&self) -> bool {
+        *self != Self::Bearer
+    }
+
+    /// Return `true` if the TLS handshake should fail without a client
+    /// certificate.
+    pub fn requires_client_certificate(&self) -> bool {
+        *self == Self::Certificate
+    }
+}
@@ -17,9 +17,12 @@
 
 //! Verified client identity.
 
+use crate::mb::MessageBroker;
+use fragtale_client::mb::publish_grant::PublishGrant;
 use fragtale_dbp::mb::MessageBrokerError;
 use fragtale_dbp::mb::MessageBrokerErrorKind;
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 /// A client identity verified through authentication.
@@ -36,6 +39,26 @@ pub enum ClientIdentity {
         /// Identity in a format that can be used for matching.
         identity_string: String,
     },
+    /// The identity source is a client certificate presented during the TLS
+    /// handshake.
+    Certificate {
+        /// Distinguished name of the certificate subject.
+        subject: String,
+        /// Organizational units of the certificate subject.
+        organizational_units: Vec<String>,
+        /// Identity in a format that can be used for matching.
+        identity_string: String,
+    },
+    /// The issuer of a verified publish grant that the holder of the grant
+    /// publishes on behalf of.
+    PublishGrantIssuer {
+        /// Identity of the issuer in a format that can be used for matching.
+        identity_string: String,
+        /// Groups of the issuer in the format of [Self::group_strings].
+        group_strings: Vec<String>,
+        /// Tenant of the issuer (if any).
+        tenant: Option<String>,
+    },
 }
 
 impl std::fmt::Display for ClientIdentity {
@@ -45,6 +68,13 @@ impl std::fmt::Display for ClientIdentity {
 }
 
 impl ClientIdentity {
+    /// Bearer token claim holding the groups of the identity.
+    const GROUPS_CLAIM: &str = "groups";
+    /// Prefix of group strings.
+    const GROUP_PREFIX: &str = "group;";
+    /// Max length of a tenant.
+    const TENANT_MAX_LEN: usize = 16;
+
     /// Return a new instance
     pub fn from_bearer_token_claims(
         claims: HashMap<String, Value>,
@@ -67,6 +97,39 @@ impl ClientIdentity {
         })
     }
 
+    /// Return a new instance from the subject of a verified client
+    /// certificate.
+    ///
+    /// The organizational units of the subject are used as groups of the
+    /// identity.
+    pub fn from_certificate_subject(
+        subject: &str,
+        organizational_units: Vec<String>,
+    ) -> Result<Self, MessageBrokerError> {
+        if subject.trim().is_empty() {
+            Err(MessageBrokerErrorKind::MalformedIdentifier
+                .error_with_msg("Missing subject in client certificate."))?;
+        }
+        if log::log_enabled!(log::Level::Trace) {
+            log::trace!("Client identity from certificate. subject: '{subject}'");
+        }
+        let identity_string = format!("certificate;;{subject}");
+        Ok(Self::Certificate {
+            subject: subject.to_owned(),
+            organizational_units,
+            identity_string,
+        })
+    }
+
+    /// Return the issuer of a verified publish grant.
+    pub fn from_publish_grant(publish_grant: &PublishGrant) -> Self {
+        Self::PublishGrantIssuer {
+            identity_string: publish_grant.get_issuer().to_owned(),
+            group_strings: publish_grant.get_issuer_groups().to_vec(),
+            tenant: publish_grant.get_issuer_tenant().map(str::to_owned),
+        }
+    }
+
     /// Return `true` when authentication originated from withing this Pod.
     pub fn is_local(&self) -> bool {
         match self {
@@ -76,6 +139,7 @@ impl ClientIdentity {
                 local,
                 identity_string: _,
             } => *local,
+            Self::Certificate { .. } | Self::PublishGrantIssuer { .. } => false,
         }
     }
 
@@ -90,9 +154,137 @@ impl ClientIdentity {
                 local: _,
                 identity_string,
             } => identity_string,
+            ClientIdentity::Certificate {
+                subject: _,
+                organizational_units: _,
+                identity_string,
+            } => identity_string,
+            ClientIdentity::PublishGrantIssuer {
+                identity_string,
+                group_strings: _,
+                tenant: _,
+            } => identity_string,
         }
     }
 
+    /// Return the claims encoded in an identity string produced by
+    /// [Self::identity_string].
+    ///
+    /// The issuer of a bearer token is only available in its normalized
+    /// form. Other principals, like named webhook consumers, yield no claims.
+    pub fn claims_from_identity_string(identity_string: &str) -> BTreeMap<String, String> {
+        let mut parts = identity_string.splitn(3, ';');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("bearer"), Some(iss), Some(sub)) => BTreeMap::from([
+                ("type".to_string(), "bearer".to_string()),
+                ("iss".to_string(), iss.to_string()),
+                ("sub".to_string(), sub.to_string()),
+            ]),
+            (Some("certificate"), Some(""), Some(subject)) => BTreeMap::from([
+                ("type".to_string(), "certificate".to_string()),
+                ("sub".to_string(), subject.to_string()),
+            ]),
+            (Some("internal"), Some(""), Some("")) => {
+                BTreeMap::from([("type".to_string(), "internal".to_string())])
+            }
+            _ => BTreeMap::new(),
+        }
+    }
+
+    /// Return the groups of the identity in a format that can be used for
+    /// matching.
+    ///
+    /// Groups are taken from the optional `groups` claim of a bearer token
+    /// or the organizational units of a client certificate subject and are
+    /// prefixed with `group;` to never collide with an identity string.
+    pub fn group_strings(&self) -> Vec<String> {
+        match self {
+            ClientIdentity::Internal => vec![],
+            ClientIdentity::Bearer {
+                claims,
+                local: _,
+                identity_string: _,
+            } => match claims.get(Self::GROUPS_CLAIM) {
+                Some(Value::String(group)) => vec![Self::GROUP_PREFIX.to_string() + group],
+                Some(Value::Array(groups)) => groups
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .map(|group| Self::GROUP_PREFIX.to_string() + group)
+                    .collect(),
+                _ => vec![],
+            },
+            ClientIdentity::Certificate {
+                subject: _,
+                organizational_units,
+                identity_string: _,
+            } => organizational_units
+                .iter()
+                .map(|group| Self::GROUP_PREFIX.to_string() + group)
+                .collect(),
+            ClientIdentity::PublishGrantIssuer {
+                identity_string: _,
+                group_strings,
+                tenant: _,
+            } => group_strings.to_owned(),
+        }
+    }
+
+    /// Return `true` if the `principal` is a group in the format produced by
+    /// [Self::group_strings].
+    pub fn is_group_string(principal: &str) -> bool {
+        principal.starts_with(Self::GROUP_PREFIX)
+    }
+
+    /// Return the tenant held by the named bearer token claim or `None` if
+    /// the claim is absent.
+    ///
+    /// Tenants are limited to 1-16 chars of a-z0-9, since they are used as
+    /// prefix of the tenant's topic identifiers. The namespace of system topics
+    /// ([MessageBroker::SYSTEM_TOPIC_NAMESPACE]) is not a valid tenant.
+    pub fn tenant(&self, claim: &str) -> Result<Option<&str>, MessageBrokerError> {
+        if let ClientIdentity::PublishGrantIssuer {
+            identity_string: _,
+            group_strings: _,
+            tenant,
+        } = self
+        {
+            // The tenant was validated when the grant was issued
+            return Ok(tenant.as_deref());
+        }
+        let ClientIdentity::Bearer {
+            claims,
+            local: _,
+            identity_string: _,
+        } = self
+        else {
+            return Ok(None);
+        };
+        let Some(value) = claims.get(claim) else {
+            return Ok(None);
+        };
+        let tenant = value
+            .as_str()
+            .filter(|tenant| {
+                !tenant.is_empty()
+                    && tenant.len() <= Self::TENANT_MAX_LEN
+                    && tenant
+                        .chars()
+                        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+            })
+            .ok_or_else(|| {
+                MessageBrokerErrorKind::MalformedIdentifier.error_with_msg(format!(
+                    "Claim '{claim}' in bearer token is not a valid tenant. Only 1-{} chars of a-z0-9 are allowed.",
+                    Self::TENANT_MAX_LEN
+                ))
+            })?;
+        if tenant == MessageBroker::SYSTEM_TOPIC_NAMESPACE {
+            Err(MessageBrokerErrorKind::Unauthorized.error_with_msg(format!(
+                "Claim '{claim}' in bearer token is the reserved tenant '{tenant}' of system topics."
+            )))?;
+        }
+        Ok(Some(tenant))
+    }
+
     /// Extract a claim from the validated `TokenData`.
     fn extract_claim<'a>(
         claim: &str,
@@ -104,3 +296,103 @@ impl ClientIdentity {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_claim_yields_group_strings() {
+        let mut claims = HashMap::new();
+        claims.insert("iss".to_string(), Value::from("https://issuer.example"));
+        claims.insert("sub".to_string(), Value::from("alice"));
+        claims.insert("groups".to_string(), serde_json::json!(["ops", 42, "dev"]));
+        let identity = ClientIdentity::from_bearer_token_claims(claims, false).unwrap();
+        let group_strings = identity.group_strings();
+        assert_eq!(group_strings, vec!["group;ops", "group;dev"]);
+        assert!(
+            group_strings
+                .iter()
+                .all(|g| ClientIdentity::is_group_string(g))
+        );
+        assert!(!ClientIdentity::is_group_string(identity.identity_string()));
+        assert!(ClientIdentity::Internal.group_strings().is_empty());
+    }
+
+    #[test]
+    fn identity_string_yields_claims() {
+        let mut claims = HashMap::new();
+        claims.insert("iss".to_string(), Value::from("https://issuer.example"));
+        claims.insert("sub".to_string(), Value::from("alice;admin"));
+        let identity = ClientIdentity::from_bearer_token_claims(claims, false).unwrap();
+        let claims = ClientIdentity::claims_from_identity_string(identity.identity_string());
+        assert_eq!(claims.get("type").map(String::as_str), Some("bearer"));
+        assert_eq!(
+            claims.get("iss").map(String::as_str),
+            Some("https_issuer_example")
+        );
+        assert_eq!(claims.get("sub").map(String::as_str), Some("alice;admin"));
+        let claims =
+            ClientIdentity::claims_from_identity_string(ClientIdentity::Internal.identity_string());
+        assert_eq!(claims.get("type").map(String::as_str), Some("internal"));
+        assert!(ClientIdentity::claims_from_identity_string("webhook_orders").is_empty());
+    }
+
+    #[test]
+    fn certificate_subject_yields_identity() {
+        let identity = ClientIdentity::from_certificate_subject(
+            "CN=orders, O=Example",
+            vec!["ops".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            identity.identity_string(),
+            "certificate;;CN=orders, O=Example"
+        );
+        assert!(!identity.is_local());
+        assert_eq!(identity.group_strings(), vec!["group;ops"]);
+        assert_eq!(identity.tenant("tenant").unwrap(), None);
+        let claims = ClientIdentity::claims_from_identity_string(identity.identity_string());
+        assert_eq!(claims.get("type").map(String::as_str), Some("certificate"));
+        assert_eq!(
+            claims.get("sub").map(String::as_str),
+            Some("CN=orders, O=Example")
+        );
+        assert!(ClientIdentity::from_certificate_subject(" ", vec![]).is_err());
+    }
+
+    #[test]
+    fn tenant_claim_is_validated() {
+        let identity_with_tenant = |tenant: Value| {
+            let mut claims = HashMap::new();
+            claims.insert("iss".to_string(), Value::from("https://issuer.example"));
+            claims.insert("sub".to_string(), Value::from("alice"));
+            claims.insert("tenant".to_string(), tenant);
+            ClientIdentity::from_bearer_token_claims(claims, false).unwrap()
+        };
+        let identity = identity_with_tenant(Value::from("team1"));
+        assert_eq!(identity.tenant("tenant").unwrap(), Some("team1"));
+        assert_eq!(identity.tenant("org").unwrap(), None);
+        assert!(
+            identity_with_tenant(Value::from("team_1"))
+                .tenant("tenant")
+                .is_err()
+        );
+        assert!(
+            identity_with_tenant(Value::from(""))
+                .tenant("tenant")
+                .is_err()
+        );
+        assert!(
+            identity_with_tenant(Value::from(1))
+                .tenant("tenant")
+                .is_err()
+        );
+        assert_eq!(ClientIdentity::Internal.tenant("tenant").unwrap(), None);
+        assert!(
+            identity_with_tenant(Value::from(MessageBroker::SYSTEM_TOPIC_NAMESPACE))
+                .tenant("tenant")
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,151 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Issuing and verification of pre-signed publish grants.
+
+use super::ClientIdentity;
+use crate::conf::AppConfig;
+use fragtale_client::mb::publish_grant::PublishGrant;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use std::sync::Arc;
+
+/** Issuing and verification of pre-signed [PublishGrant]s.
+
+Grants are protected with the same master secret as correlation tokens, but
+with a different derived secret per topic.
+*/
+pub struct PublishGrants {
+    dbp: Arc<DatabaseProvider>,
+    oid: Vec<u32>,
+    master_secret: Vec<u8>,
+}
+
+impl PublishGrants {
+    /// Default time to live of a grant.
+    const DEFAULT_TTL_SECONDS: u64 = 15 * 60;
+    /// Max time to live of a grant.
+    const MAX_TTL_SECONDS: u64 = 24 * 60 * 60;
+    /// Max number of events that may be published with a single grant.
+    const MAX_EVENTS: u32 = 1000;
+
+    /// Return a new instance.
+    pub fn new(app_config: &Arc<AppConfig>, dbp: &Arc<DatabaseProvider>) -> Self {
+        let (oid, master_secret) = app_config.integrity.correlation_secret();
+        Self {
+            dbp: Arc::clone(dbp),
+            oid,
+            master_secret,
+        }
+    }
+
+    /// Return a new grant issued by the `issuer` to publish up to
+    /// `max_events` (default 1) events of at most `max_event_bytes` each to
+    /// the topic during `ttl_seconds` (default 15 minutes).
+    ///
+    /// The groups and `tenant` of the `issuer` are kept in the grant, so its
+    /// access can be authorized again each time the grant is used.
+    pub fn issue(
+        &self,
+        issuer: &ClientIdentity,
+        tenant: Option<&str>,
+        topic_id: &str,
+        ttl_seconds: Option<u64>,
+        max_events: Option<u32>,
+        max_event_bytes: Option<u64>,
+    ) -> Result<PublishGrant, MessageBrokerError> {
+        let ttl_seconds = ttl_seconds.unwrap_or(Self::DEFAULT_TTL_SECONDS);
+        if ttl_seconds == 0 || ttl_seconds > Self::MAX_TTL_SECONDS {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "The time to live of a publish grant must be between 1 and {} seconds.",
+                    Self::MAX_TTL_SECONDS
+                )),
+            )?;
+        }
+        let max_events = max_events.unwrap_or(1);
+        if max_events == 0 || max_events > Self::MAX_EVENTS {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "The number of events of a publish grant must be between 1 and {}.",
+                    Self::MAX_EVENTS
+                )),
+            )?;
+        }
+        Ok(PublishGrant::new(
+            &self.oid,
+            &self.master_secret,
+            topic_id,
+            fragtale_client::time::get_timestamp_micros() + ttl_seconds * 1_000_000,
+            max_events,
+            max_event_bytes,
+            issuer.identity_string(),
+            &issuer.group_strings(),
+            tenant,
+        ))
+    }
+
+    /// Verify that the serialized grant allows publishing an event document
+    /// of `event_bytes` to the topic and consume one of its uses.
+    ///
+    /// A consumed use is not given back if the publish fails later on.
+    pub async fn consume(
+        &self,
+        grant: &str,
+        topic_id: &str,
+        event_bytes: usize,
+    ) -> Result<PublishGrant, MessageBrokerError> {
+        let publish_grant = PublishGrant::from_string(grant)
+            .ok()
+            .filter(|publish_grant| publish_grant.verify(&self.oid, &self.master_secret))
+            .filter(|publish_grant| publish_grant.get_topic_id() == topic_id)
+            .ok_or_else(|| {
+                MessageBrokerErrorKind::AuthenticationFailure
+                    .error_with_msg(format!("Invalid publish grant for topic '{topic_id}'."))
+            })?;
+        if publish_grant.get_expires_micros() <= fragtale_client::time::get_timestamp_micros() {
+            Err(MessageBrokerErrorKind::AuthenticationFailure
+                .error_with_msg(format!("Expired publish grant for topic '{topic_id}'.")))?;
+        }
+        if let Some(max_event_bytes) = publish_grant.get_max_event_bytes()
+            && event_bytes as u64 > max_event_bytes
+        {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "The publish grant only allows event documents of up to {max_event_bytes} bytes."
+                )),
+            )?;
+        }
+        if !self
+            .dbp
+            .authorization_facade()
+            .publish_grant_use(
+                publish_grant.get_uid(),
+                publish_grant.get_max_events(),
+                publish_grant.get_expires_micros(),
+            )
+            .await
+        {
+            Err(MessageBrokerErrorKind::Unauthorized.error_with_msg(format!(
+                "All uses of the publish grant for topic '{topic_id}' have been consumed."
+            )))?;
+        }
+        Ok(publish_grant)
+    }
+}
@@ -0,0 +1,299 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Tracking of topic backlogs for signalling congestion to producers.
+
+use crate::conf::AppConfig;
+use crossbeam_skiplist::SkipMap;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// How producers are signalled about the backlog of a topic.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BackpressureMode {
+    /// The backlog is not tracked.
+    #[default]
+    Off,
+    /// Publish responses carry the [BacklogState].
+    Header,
+    /// Like [Self::Header], but low priority publishes are also rejected
+    /// while the backlog is critical.
+    Shed,
+}
+
+impl BackpressureMode {
+    /// Return the mode with the `name` if supported.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "off" => Some(Self::Off),
+            "header" => Some(Self::Header),
+            "shed" => Some(Self::Shed),
+            _ => None,
+        }
+    }
+}
+
+/// Backlog of the primary consumer of a topic.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BacklogState {
+    /// The consumer keeps up.
+    #[default]
+    Ok,
+    /// The consumer is falling behind.
+    Warn,
+    /// The consumer is too far behind and producers should shed load.
+    Critical,
+}
+
+impl BacklogState {
+    /// Return the state name. Example: "warn"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "ok",
+            Self::Warn => "warn",
+            Self::Critical => "critical",
+        }
+    }
+
+    /// Return the state where the oldest unprocessed event is `age_micros`
+    /// old.
+    fn from_age(age_micros: u64, warn_micros: u64, critical_micros: u64) -> Self {
+        if age_micros >= critical_micros {
+            Self::Critical
+        } else if age_micros >= warn_micros {
+            Self::Warn
+        } else {
+            Self::Ok
+        }
+    }
+
+    fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            2 => Self::Critical,
+            1 => Self::Warn,
+            _ => Self::Ok,
+        }
+    }
+}
+
+/// Last known backlog of a topic.
+#[derive(Default)]
+struct TopicBacklog {
+    /// Time of the latest refresh in epoch microseconds.
+    refreshed_ts_micros: AtomicU64,
+    /// [BacklogState] in encoded form.
+    state: AtomicU8,
+}
+
+/** Tracking of topic backlogs for signalling congestion to producers.
+
+The backlog of a topic is the age of the oldest event that the primary
+consumer has not processed yet. It is looked up in the database in the
+background at most once per [Self::REFRESH_INTERVAL_MICROS] and topic, so the
+publish path only reads the last known state.
+*/
+pub struct BacklogMonitor {
+    dbp: Arc<DatabaseProvider>,
+    mode: BackpressureMode,
+    consumer_id: Option<String>,
+    warn_micros: u64,
+    critical_micros: u64,
+    shed_priority: u8,
+    topics: SkipMap<String, Arc<TopicBacklog>>,
+}
+
+impl BacklogMonitor {
+    /// Min time between lookups of a topic's backlog.
+    const REFRESH_INTERVAL_MICROS: u64 = 1_000_000;
+
+    /// Return a new instance.
+    pub fn new(dbp: &Arc<DatabaseProvider>, app_config: &AppConfig) -> Arc<Self> {
+        let mode =
+            BackpressureMode::from_name(app_config.backpressure.mode()).unwrap_or_else(|| {
+                panic!(
+                    "Unknown backpressure mode '{}'.",
+                    app_config.backpressure.mode()
+                )
+            });
+        Arc::new(Self {
+            dbp: Arc::clone(dbp),
+            mode,
+            consumer_id: app_config.backpressure.consumer().map(str::to_owned),
+            warn_micros: app_config.backpressure.warn_micros(),
+            critical_micros: app_config.backpressure.critical_micros(),
+            shed_priority: app_config.backpressure.shed_priority(),
+            topics: SkipMap::default(),
+        })
+    }
+
+    /// Return the last known [BacklogState] of the topic or `None` if
+    /// backlogs are not tracked.
+    ///
+    /// A refresh is started in the background when the state is stale.
+    pub fn get_backlog_state(self: &Arc<Self>, topic_id: &str) -> Option<BacklogState> {
+        if self.mode == BackpressureMode::Off {
+            return None;
+        }
+        let entry = self.topics.get(topic_id).unwrap_or_else(|| {
+            self.topics
+                .get_or_insert_with(topic_id.to_owned(), Arc::default)
+        });
+        let topic_backlog = entry.value();
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let refreshed_ts_micros = topic_backlog.refreshed_ts_micros.load(Ordering::Relaxed);
+        if now_micros.saturating_sub(refreshed_ts_micros) >= Self::REFRESH_INTERVAL_MICROS
+            && topic_backlog
+                .refreshed_ts_micros
+                .compare_exchange(
+                    refreshed_ts_micros,
+                    now_micros,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+        {
+            let self_clone = Arc::clone(self);
+            let topic_id = topic_id.to_owned();
+            let topic_backlog = Arc::clone(topic_backlog);
+            tokio::spawn(async move {
+                let age_micros = self_clone.get_backlog_age_micros(&topic_id).await;
+                let state = BacklogState::from_age(
+                    age_micros,
+                    self_clone.warn_micros,
+                    self_clone.critical_micros,
+                );
+                let previous = BacklogState::from_u8(
+                    topic_backlog.state.swap(state.as_u8(), Ordering::Relaxed),
+                );
+                if previous != state {
+                    log::info!(
+                        "Backlog of topic '{topic_id}' changed from {} to {}.",
+                        previous.as_str(),
+                        state.as_str()
+                    );
+                }
+            });
+        }
+        Some(BacklogState::from_u8(
+            topic_backlog.state.load(Ordering::Relaxed),
+        ))
+    }
+
+    /// Return `true` if a publish with the `priority` should be rejected to
+    /// shed load from the topic.
+    pub fn is_shed(self: &Arc<Self>, topic_id: &str, priority: u8) -> bool {
+        self.mode == BackpressureMode::Shed
+            && priority < self.shed_priority
+            && self.get_backlog_state(topic_id) == Some(BacklogState::Critical)
+    }
+
+    /// Return the age in microseconds of the oldest event that the primary
+    /// consumer of the topic has not processed yet.
+    async fn get_backlog_age_micros(&self, topic_id: &str) -> u64 {
+        let consumer_ids = if let Some(consumer_id) = &self.consumer_id {
+            vec![consumer_id.to_owned()]
+        } else {
+            self.dbp
+                .consumer_delivery_facade()
+                .consumer_ids(topic_id)
+                .await
+        };
+        let mut slowest_done = None;
+        for consumer_id in consumer_ids {
+            let done = self
+                .dbp
+                .consumer_delivery_facade()
+                .consumer_get_done_by_id(topic_id, &consumer_id)
+                .await;
+            slowest_done = match (slowest_done, done) {
+                (Some(slowest), Some(done)) => Some(std::cmp::min(slowest, done)),
+                (slowest, done) => slowest.or(done),
+            };
+        }
+        let Some(done) = slowest_done else {
+            return 0;
+        };
+        self.get_first_event_after(topic_id, done)
+            .await
+            .map_or(0, |unique_time| {
+                fragtale_client::time::get_timestamp_micros()
+                    .saturating_sub(unique_time.get_time_micros())
+            })
+    }
+
+    /// Return the [UniqueTime] of the first event in the topic after (exclusive)
+    /// `from`.
+    async fn get_first_event_after(&self, topic_id: &str, from: UniqueTime) -> Option<UniqueTime> {
+        let (entries, _more) = self
+            .dbp
+            .event_facade()
+            .events_by_bucket(topic_id, from.get_bucket(), Some(from), 1)
+            .await;
+        if let Some((unique_time, _event_id, _descriptor_version)) = entries.first() {
+            return Some(*unique_time);
+        }
+        let now_shelf = UniqueTime::from(UniqueTime::min_encoded_for_micros(
+            fragtale_client::time::get_timestamp_micros(),
+        ))
+        .get_shelf();
+        // Buckets are listed after (exclusive) the current bucket
+        let mut current_bucket = Some(from.get_bucket());
+        for shelf in from.get_shelf()..=now_shelf {
+            let (buckets, _more) = self
+                .dbp
+                .event_facade()
+                .buckets_by_shelf(topic_id, shelf, current_bucket, 1)
+                .await;
+            if let Some(bucket) = buckets.first() {
+                let (entries, _more) = self
+                    .dbp
+                    .event_facade()
+                    .events_by_bucket(topic_id, *bucket, None, 1)
+                    .await;
+                return entries
+                    .first()
+                    .map(|(unique_time, _event_id, _descriptor_version)| *unique_time);
+            }
+            current_bucket = None;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_follows_age_of_oldest_unprocessed_event() {
+        assert_eq!(BacklogState::from_age(0, 10, 20), BacklogState::Ok);
+        assert_eq!(BacklogState::from_age(10, 10, 20), BacklogState::Warn);
+        assert_eq!(BacklogState::from_age(25, 10, 20), BacklogState::Critical);
+        for state in [BacklogState::Ok, BacklogState::Warn, BacklogState::Critical] {
+            assert_eq!(BacklogState::from_u8(state.as_u8()), state);
+        }
+    }
+}
@@ -0,0 +1,374 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Bulk ingest windows that defer work while backfilling topics.
+
+use crate::mb::leader_election::LeaderElection;
+use crate::mb::pre_storage_processor::PreStorageProcessor;
+use crossbeam_skiplist::SkipMap;
+use fragtale_client::mb::event_descriptor::DescriptorVersion;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/** Bulk ingest windows that defer work while backfilling topics.
+
+When millions of historical events are published to a topic, population of
+secondary indices and integrity consolidation dominate the cost. During an
+admin-enabled window, events of the topic are persisted without their indexed
+columns and integrity consolidation of the topic is paused.
+
+After the window has ended, the elected background worker populates the
+indexed columns of all events published during the window. Events are
+delivered to consumers as usual during the window, but lookups by index will
+not find them until reconciliation has completed.
+
+Windows are persisted in the database, so all instances pick up changes
+within the refresh interval.
+*/
+pub struct BulkIngest {
+    dbp: Arc<DatabaseProvider>,
+    pre_storage_processor: Arc<PreStorageProcessor>,
+    /// Time of the last refresh and the latest window (if any) by topic.
+    windows_by_topic: SkipMap<String, (u64, Option<(u64, u64, bool)>)>,
+    /// Last discovery of all topics in epoch microseconds.
+    discovered_ts: AtomicU64,
+}
+
+impl BulkIngest {
+    /// Longest allowed bulk ingest window.
+    pub const MAX_DURATION_MICROS: u64 = 24 * 3_600_000_000;
+    /// Time between reloading the window of a topic.
+    const REFRESH_INTERVAL_MICROS: u64 = 10_000_000;
+    /// Time between discovering windows of all topics.
+    const DISCOVERY_INTERVAL_MICROS: u64 = 60_000_000;
+    /// Time between looking for ended windows to reconcile.
+    const RECONCILIATION_INTERVAL_MICROS: u64 = 10_000_000;
+    /// Tolerated clock difference between instances.
+    const CLOCK_SKEW_TOLERANCE_MICROS: u64 = 1_000_000;
+    /// Number of buckets requested from the database at a time.
+    const BUCKETS_PAGE_SIZE: usize = 32;
+    /// Number of bucket entries requested from the database at a time.
+    const ENTRIES_PAGE_SIZE: usize = 256;
+
+    /// Return a new instance.
+    ///
+    /// Ended windows are only reconciled when `leader_election` is present
+    /// and this instance is elected.
+    pub fn new(
+        dbp: &Arc<DatabaseProvider>,
+        pre_storage_processor: &Arc<PreStorageProcessor>,
+        leader_election: &Option<Arc<LeaderElection>>,
+    ) -> Arc<Self> {
+        let ret = Arc::new(Self {
+            dbp: Arc::clone(dbp),
+            pre_storage_processor: Arc::clone(pre_storage_processor),
+            windows_by_topic: SkipMap::default(),
+            discovered_ts: AtomicU64::new(0),
+        });
+        if let Some(leader_election) = leader_election {
+            let self_clone = Arc::clone(&ret);
+            let leader_election = Arc::clone(leader_election);
+            tokio::spawn(async move { self_clone.run_reconciliation(&leader_election).await });
+        }
+        ret
+    }
+
+    /// Return `true` if publishing to the topic currently defers index
+    /// population and integrity consolidation.
+    pub async fn is_deferring(&self, topic_id: &str) -> bool {
+        let now = fragtale_client::time::get_timestamp_micros();
+        self.window_of_topic(topic_id, now)
+            .await
+            .is_some_and(|window| Self::is_active(&window, now))
+    }
+
+    /// Return the start and end in epoch microseconds and reconciliation
+    /// status of the latest window of the topic (if any).
+    pub async fn get_window(&self, topic_id: &str) -> Option<(u64, u64, bool)> {
+        self.refresh(topic_id).await
+    }
+
+    /// Open a window of the topic that ends `duration_micros` from now.
+    ///
+    /// An active or not yet reconciled window is extended instead, so no
+    /// events are left out of reconciliation.
+    pub async fn open(&self, topic_id: &str, duration_micros: u64) -> Option<(u64, u64, bool)> {
+        let now = fragtale_client::time::get_timestamp_micros();
+        let end_ts_micros = now + std::cmp::min(duration_micros, Self::MAX_DURATION_MICROS);
+        let start_ts_micros = self
+            .refresh(topic_id)
+            .await
+            .filter(|window| Self::is_active(window, now) || !window.2)
+            .map_or(now, |(start_ts_micros, _end_ts_micros, _reconciled)| {
+                start_ts_micros
+            });
+        self.persist(topic_id, start_ts_micros, end_ts_micros, false)
+            .await
+    }
+
+    /// End the active window of the topic now.
+    ///
+    /// Reconciliation of the window starts once all instances have noticed
+    /// the change.
+    pub async fn close(&self, topic_id: &str) -> Option<(u64, u64, bool)> {
+        let now = fragtale_client::time::get_timestamp_micros();
+        let window = self.refresh(topic_id).await;
+        match window {
+            Some((start_ts_micros, end_ts_micros, reconciled)) if end_ts_micros > now => {
+                self.persist(topic_id, start_ts_micros, now, reconciled)
+                    .await
+            }
+            window => window,
+        }
+    }
+
+    /// Return `true` if the window defers work at `now`.
+    pub fn is_active(window: &(u64, u64, bool), now: u64) -> bool {
+        window.0 <= now && now < window.1
+    }
+
+    /// Persist the window and make it visible to this instance right away.
+    async fn persist(
+        &self,
+        topic_id: &str,
+        start_ts_micros: u64,
+        end_ts_micros: u64,
+        reconciled: bool,
+    ) -> Option<(u64, u64, bool)> {
+        if !self
+            .dbp
+            .topic_facade()
+            .bulk_ingest_window_persist(topic_id, start_ts_micros, end_ts_micros, reconciled)
+            .await
+        {
+            log::warn!("Failed to persist bulk ingest window of '{topic_id}'.");
+        }
+        self.refresh(topic_id).await
+    }
+
+    /// Return the cached window of the topic and reload it from the database
+    /// when stale.
+    async fn window_of_topic(&self, topic_id: &str, now: u64) -> Option<(u64, u64, bool)> {
+        match self.windows_by_topic.get(topic_id) {
+            Some(entry) if entry.value().0 + Self::REFRESH_INTERVAL_MICROS > now => entry.value().1,
+            _ => self.refresh(topic_id).await,
+        }
+    }
+
+    /// Reload the window of the topic from the database.
+    async fn refresh(&self, topic_id: &str) -> Option<(u64, u64, bool)> {
+        let window = self
+            .dbp
+            .topic_facade()
+            .bulk_ingest_window_by_topic_id(topic_id)
+            .await;
+        self.windows_by_topic.insert(
+            topic_id.to_owned(),
+            (fragtale_client::time::get_timestamp_micros(), window),
+        );
+        window
+    }
+
+    /// Reconcile ended windows of all topics while this instance is the
+    /// elected background worker.
+    async fn run_reconciliation(&self, leader_election: &LeaderElection) {
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_micros(
+                Self::RECONCILIATION_INTERVAL_MICROS,
+            ))
+            .await;
+            if !leader_election.is_leader().await {
+                continue;
+            }
+            let now = fragtale_client::time::get_timestamp_micros();
+            if self.discovered_ts.load(Ordering::Relaxed) + Self::DISCOVERY_INTERVAL_MICROS < now {
+                self.discovered_ts.store(now, Ordering::Relaxed);
+                let mut from = None;
+                loop {
+                    let (topic_ids, more) = self.dbp.topic_facade().get_topic_ids(&from).await;
+                    from = topic_ids.last().cloned();
+                    for topic_id in &topic_ids {
+                        self.refresh(topic_id).await;
+                    }
+                    if !more {
+                        break;
+                    }
+                }
+            }
+            let topic_ids = self
+                .windows_by_topic
+                .iter()
+                .map(|entry| entry.key().to_owned())
+                .collect::<Vec<_>>();
+            for topic_id in topic_ids {
+                let now = fragtale_client::time::get_timestamp_micros();
+                let Some((start_ts_micros, end_ts_micros, reconciled)) =
+                    self.window_of_topic(&topic_id, now).await
+                else {
+                    continue;
+                };
+                // Wait for all instances to notice that the window has ended
+                if reconciled
+                    || end_ts_micros
+                        + Self::REFRESH_INTERVAL_MICROS
+                        + Self::CLOCK_SKEW_TOLERANCE_MICROS
+                        > now
+                {
+                    continue;
+                }
+                self.reconcile(&topic_id, start_ts_micros, end_ts_micros)
+                    .await;
+                // Don't mark a reopened window as reconciled
+                if self.refresh(&topic_id).await == Some((start_ts_micros, end_ts_micros, false)) {
+                    self.persist(&topic_id, start_ts_micros, end_ts_micros, true)
+                        .await;
+                }
+            }
+        }
+    }
+
+    /// Populate the indexed columns of all events published to the topic
+    /// during the window.
+    async fn reconcile(&self, topic_id: &str, start_ts_micros: u64, end_ts_micros: u64) {
+        let start_ts = fragtale_client::time::get_timestamp_micros();
+        log::info!("Reconciling bulk ingest window of '{topic_id}'.");
+        // Instances may keep deferring for up to the refresh interval after the end
+        let low = UniqueTime::new(
+            start_ts_micros.saturating_sub(Self::CLOCK_SKEW_TOLERANCE_MICROS),
+            0,
+        );
+        let high = UniqueTime::new(
+            end_ts_micros + Self::REFRESH_INTERVAL_MICROS + Self::CLOCK_SKEW_TOLERANCE_MICROS,
+            UniqueTime::MAX_INSTANCE_ID,
+        );
+        let mut reconciled_count = 0u64;
+        'shelves: for shelf in low.get_shelf()..=high.get_shelf() {
+            let mut current_bucket =
+                (shelf == low.get_shelf()).then(|| low.get_bucket().saturating_sub(1));
+            loop {
+                let (buckets, more) = self
+                    .dbp
+                    .event_facade()
+                    .buckets_by_shelf(topic_id, shelf, current_bucket, Self::BUCKETS_PAGE_SIZE)
+                    .await;
+                for bucket in buckets {
+                    if bucket > high.get_bucket() {
+                        break 'shelves;
+                    }
+                    let mut entries = self.dbp.event_facade().events_by_bucket_stream(
+                        topic_id,
+                        bucket,
+                        None,
+                        Self::ENTRIES_PAGE_SIZE,
+                    );
+                    while let Some((unique_time, event_id, descriptor_version)) =
+                        entries.next().await
+                    {
+                        if unique_time < low {
+                            continue;
+                        }
+                        if unique_time > high {
+                            break 'shelves;
+                        }
+                        if self
+                            .reconcile_event(topic_id, &event_id, unique_time, descriptor_version)
+                            .await
+                        {
+                            reconciled_count += 1;
+                        }
+                    }
+                    current_bucket = Some(bucket);
+                }
+                if !more {
+                    break;
+                }
+            }
+        }
+        log::info!(
+            "Reconciled {reconciled_count} events of the bulk ingest window of '{topic_id}' in {} micros.",
+            fragtale_client::time::get_timestamp_micros() - start_ts
+        );
+    }
+
+    /// Populate the indexed columns of a single event.
+    ///
+    /// Return `true` if there was anything to populate.
+    async fn reconcile_event(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        descriptor_version: Option<u64>,
+    ) -> bool {
+        let Some(event_delivery_gist) = self
+            .dbp
+            .event_facade()
+            .event_by_id_and_unique_time(topic_id, event_id, unique_time)
+            .await
+        else {
+            return false;
+        };
+        let additional_columns = match self
+            .pre_storage_processor
+            .validate_and_extract(
+                topic_id,
+                event_delivery_gist.get_document(),
+                descriptor_version.map(DescriptorVersion::from_encoded),
+            )
+            .await
+        {
+            Ok((additional_columns, _descriptor_version)) => additional_columns,
+            Err(e) => {
+                log::warn!(
+                    "Unable to extract indexed columns of event '{event_id}' in '{topic_id}': {e}"
+                );
+                return false;
+            }
+        };
+        if additional_columns.is_empty() {
+            return false;
+        }
+        let applied = self
+            .dbp
+            .event_facade()
+            .event_index_columns_update(topic_id, event_id, unique_time, additional_columns)
+            .await;
+        if !applied {
+            log::warn!("Failed to populate indexed columns of event '{event_id}' in '{topic_id}'.");
+        }
+        applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_is_active_from_start_until_end() {
+        let window = (1_000, 2_000, false);
+        assert!(!BulkIngest::is_active(&window, 999));
+        assert!(BulkIngest::is_active(&window, 1_000));
+        assert!(BulkIngest::is_active(&window, 1_999));
+        assert!(!BulkIngest::is_active(&window, 2_000));
+    }
+}
@@ -0,0 +1,137 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Tracking of synthetic canary events in flight.
+
+use crossbeam_skiplist::SkipMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/** Tracking of synthetic canary events in flight.
+
+Canary events are published by this instance and consumed by a consumer of
+the same instance, so the end-to-end latency is measured with a single clock.
+Canaries that are delivered to the consumer of another instance, or that were
+published before a restart of the instance, are ignored.
+*/
+pub struct CanaryTracker {
+    instance_id: u16,
+    sequence: AtomicU64,
+    /// Publish time of canary events in flight by topic and sequence number.
+    in_flight: SkipMap<(String, u64), u64>,
+}
+
+impl CanaryTracker {
+    /// Return a new instance.
+    pub fn new(instance_id: u16) -> Self {
+        Self {
+            instance_id,
+            sequence: AtomicU64::default(),
+            in_flight: SkipMap::default(),
+        }
+    }
+
+    /// Return the sequence number and document of a new canary event for the
+    /// topic and start tracking it.
+    pub fn next_document(&self, topic_id: &str, now_micros: u64) -> (u64, String) {
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        self.in_flight
+            .insert((topic_id.to_owned(), sequence), now_micros);
+        let document = serde_json::json!({
+            "canary": {
+                "instance_id": self.instance_id,
+                "sequence": sequence,
+                "published_ts_micros": now_micros,
+            }
+        })
+        .to_string();
+        (sequence, document)
+    }
+
+    /// Stop tracking a canary event that was never published.
+    pub fn forget(&self, topic_id: &str, sequence: u64) {
+        self.in_flight.remove(&(topic_id.to_owned(), sequence));
+    }
+
+    /// Stop tracking the canary event and return the time since it was
+    /// published.
+    ///
+    /// Return `None` if the document is not a canary event of this instance
+    /// in flight.
+    pub fn complete(&self, topic_id: &str, document: &str, now_micros: u64) -> Option<u64> {
+        let value = serde_json::from_str::<serde_json::Value>(document).ok()?;
+        let canary = value.get("canary")?;
+        if canary.get("instance_id")?.as_u64()? != u64::from(self.instance_id) {
+            return None;
+        }
+        let sequence = canary.get("sequence")?.as_u64()?;
+        let published_ts_micros = canary.get("published_ts_micros")?.as_u64()?;
+        let key = (topic_id.to_owned(), sequence);
+        // Sequence numbers restart with the instance
+        if *self.in_flight.get(&key)?.value() != published_ts_micros {
+            return None;
+        }
+        self.in_flight.remove(&key);
+        Some(now_micros.saturating_sub(published_ts_micros))
+    }
+
+    /// Stop tracking canary events published before `cutoff_micros` and
+    /// return the topic of each of them.
+    pub fn expire(&self, cutoff_micros: u64) -> Vec<String> {
+        self.in_flight
+            .iter()
+            .filter(|entry| *entry.value() < cutoff_micros)
+            .filter_map(|entry| entry.remove().then(|| entry.key().0.to_owned()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_latency_of_own_canaries() {
+        let canary_tracker = CanaryTracker::new(3);
+        let (_sequence, document) = canary_tracker.next_document("topic", 1_000);
+        // Delivered to the consumer of another topic
+        assert_eq!(canary_tracker.complete("other", &document, 1_500), None);
+        assert_eq!(
+            canary_tracker.complete("topic", &document, 1_500),
+            Some(500)
+        );
+        // Only measured once
+        assert_eq!(canary_tracker.complete("topic", &document, 1_600), None);
+        // Canary of another instance
+        let (_sequence, document) = CanaryTracker::new(4).next_document("topic", 1_000);
+        assert_eq!(canary_tracker.complete("topic", &document, 1_500), None);
+        // Not a canary
+        assert_eq!(canary_tracker.complete("topic", "{}", 1_500), None);
+    }
+
+    #[test]
+    fn expires_lost_canaries() {
+        let canary_tracker = CanaryTracker::new(0);
+        canary_tracker.next_document("a", 1_000);
+        canary_tracker.next_document("b", 2_000);
+        let (sequence, _document) = canary_tracker.next_document("c", 500);
+        canary_tracker.forget("c", sequence);
+        assert_eq!(canary_tracker.expire(1_500), vec!["a".to_owned()]);
+        assert!(canary_tracker.expire(1_500).is_empty());
+        assert_eq!(canary_tracker.expire(2_500), vec!["b".to_owned()]);
+    }
+}
@@ -0,0 +1,134 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Protocol version agreed on by all broker instances sharing a database.
+
+use fragtale_client::protocol;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use serde_json::Value;
+use std::sync::Arc;
+
+/** Protocol version agreed on by all broker instances sharing a database.
+
+The active protocol version of the cluster is persisted in the shared
+database's feature flags, so that instances of different versions can run
+side by side during a rolling upgrade. Each instance keeps writing data using
+the active protocol version even when it speaks a newer one.
+
+The first instance to start against an empty database registers its own
+protocol version. An instance that cannot speak the active protocol version
+refuses to start instead of mis-parsing data written by the other instances.
+*/
+pub struct ClusterProtocol {
+    protocol_version: u32,
+}
+
+impl ClusterProtocol {
+    /// Name of the feature flag holding the active protocol version.
+    const FLAG_PROTOCOL_VERSION: &str = "protocol_version";
+
+    /// Return a new instance after agreeing on the active protocol version of
+    /// the cluster.
+    ///
+    /// Panics if this instance cannot speak the active protocol version.
+    pub async fn new(dbp: &Arc<DatabaseProvider>) -> Self {
+        let facade = dbp.instance_id_facade();
+        let feature_flags = match facade.feature_flags().await {
+            Some(feature_flags) => feature_flags,
+            None => {
+                let initial = serde_json::json!({
+                    Self::FLAG_PROTOCOL_VERSION: protocol::PROTOCOL_VERSION
+                })
+                .to_string();
+                if facade.feature_flags_compare_and_set(None, &initial).await {
+                    initial
+                } else {
+                    // Another instance registered the feature flags first.
+                    facade.feature_flags().await.unwrap_or_else(|| {
+                        panic!("Unable to read or register cluster feature flags.")
+                    })
+                }
+            }
+        };
+        let protocol_version =
+            Self::compatible_protocol_version(&feature_flags).unwrap_or_else(|msg| panic!("{msg}"));
+        log::info!(
+            "Using cluster protocol version {protocol_version} (this instance speaks {}..={}).",
+            protocol::MIN_PROTOCOL_VERSION,
+            protocol::PROTOCOL_VERSION,
+        );
+        Self { protocol_version }
+    }
+
+    /// Return the active protocol version of the cluster.
+    pub fn get_protocol_version(&self) -> u32 {
+        self.protocol_version
+    }
+
+    /// Return the active protocol version of the serialized `feature_flags`
+    /// if this instance can speak it.
+    fn compatible_protocol_version(feature_flags: &str) -> Result<u32, String> {
+        let protocol_version = serde_json::from_str::<Value>(feature_flags)
+            .ok()
+            .and_then(|value| {
+                value
+                    .get(Self::FLAG_PROTOCOL_VERSION)
+                    .and_then(Value::as_u64)
+            })
+            .and_then(|protocol_version| u32::try_from(protocol_version).ok())
+            .ok_or_else(|| format!("Malformed cluster feature flags '{feature_flags}'."))?;
+        if protocol_version > protocol::PROTOCOL_VERSION {
+            Err(format!(
+                "Cluster protocol version {protocol_version} is newer than what this instance speaks ({}). Upgrade this instance.",
+                protocol::PROTOCOL_VERSION
+            ))
+        } else if protocol_version < protocol::MIN_PROTOCOL_VERSION {
+            Err(format!(
+                "Cluster protocol version {protocol_version} is older than what this instance understands ({}). Upgrade through an intermediate version.",
+                protocol::MIN_PROTOCOL_VERSION
+            ))
+        } else {
+            Ok(protocol_version)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_only_spoken_protocol_versions() {
+        assert_eq!(
+            ClusterProtocol::compatible_protocol_version(&format!(
+                "{{\"protocol_version\":{}}}",
+                protocol::PROTOCOL_VERSION
+            )),
+            Ok(protocol::PROTOCOL_VERSION)
+        );
+        assert!(
+            ClusterProtocol::compatible_protocol_version(&format!(
+                "{{\"protocol_version\":{}}}",
+                protocol::PROTOCOL_VERSION + 1
+            ))
+            .is_err()
+        );
+        assert!(ClusterProtocol::compatible_protocol_version("{\"protocol_version\":0}").is_err());
+        assert!(ClusterProtocol::compatible_protocol_version("{}").is_err());
+    }
+}
@@ -17,40 +17,144 @@
 
 //! Track connected consumers.
 
+pub mod consumer_definition_registry;
+pub mod delivery_cache_budget;
+pub mod group_members;
+pub mod sticky_preference;
 pub mod topic_consumer;
+pub mod webhook_sender;
 
+pub use self::consumer_definition_registry::ConsumerDefinitionRegistry;
+pub use self::delivery_cache_budget::DeliveryCacheBudget;
+pub use self::group_members::GroupMembers;
+pub use self::sticky_preference::StickyPreference;
 pub use self::topic_consumer::TopicConsumer;
+pub use self::webhook_sender::WebhookSender;
+use crate::mb::mb_metrics::MessageBrokerMetrics;
 use crate::mb::object_count_tracker::ObjectCountTracker;
+use crate::mb::task_supervisor::TaskSupervisor;
 use crossbeam_skiplist::SkipMap;
 use fragtale_client::mb::event_descriptor::DescriptorVersion;
 use fragtale_dbp::dbp::DatabaseProvider;
 use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
 use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::consumers::RetryBackoff;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 /// Tracks all connected consumers.
 pub struct Consumers {
     dbp: Arc<DatabaseProvider>,
     object_count_tracker: Arc<ObjectCountTracker>,
+    metrics: Option<Arc<MessageBrokerMetrics>>,
+    task_supervisor: Arc<TaskSupervisor>,
     consumers: SkipMap<String, Arc<TopicConsumer>>,
+    group_members: Arc<GroupMembers>,
     instance_id: u16,
+    max_redeliveries: u32,
+    concurrency_limits: HashMap<String, u32>,
+    delivery_cache_budget: Arc<DeliveryCacheBudget>,
+    strict_order: HashSet<String>,
+    retry_backoff: RetryBackoff,
+    /// Lowest and highest visibility timeout a subscription may request.
+    visibility_timeout_bounds_micros: (u64, u64),
 }
 
 impl Consumers {
     /// Return a new instance.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         dbp: &Arc<DatabaseProvider>,
         object_count_tracker: &Arc<ObjectCountTracker>,
+        metrics: &Option<Arc<MessageBrokerMetrics>>,
+        task_supervisor: &Arc<TaskSupervisor>,
         instance_id: u16,
+        max_redeliveries: u32,
+        concurrency_limits: HashMap<String, u32>,
+        delivery_cache_budget_bytes: usize,
+        strict_order: HashSet<String>,
+        retry_backoff: RetryBackoff,
+        visibility_timeout_bounds_micros: (u64, u64),
     ) -> Arc<Self> {
         Arc::new(Self {
             dbp: Arc::clone(dbp),
             object_count_tracker: Arc::clone(object_count_tracker),
+            metrics: metrics.clone(),
+            task_supervisor: Arc::clone(task_supervisor),
             consumers: SkipMap::new(),
+            group_members: GroupMembers::new(metrics),
             instance_id,
+            max_redeliveries,
+            concurrency_limits,
+            delivery_cache_budget: DeliveryCacheBudget::new(delivery_cache_budget_bytes),
+            strict_order,
+            retry_backoff,
+            visibility_timeout_bounds_micros,
         })
     }
 
+    /// Return the tracker of consumer group members connected to this
+    /// instance.
+    pub fn get_group_members(&self) -> &Arc<GroupMembers> {
+        &self.group_members
+    }
+
+    /// Return the maximum number of unconfirmed deliveries the consumer group
+    /// may hold for the topic (if limited).
+    ///
+    /// Consumers with strict publish-order delivery never hold more than one.
+    pub fn get_delivery_concurrency(&self, topic_id: &str, consumer_id: &str) -> Option<u32> {
+        if self.is_strict_order(topic_id, consumer_id) {
+            return Some(1);
+        }
+        self.concurrency_limits.get(topic_id).copied()
+    }
+
+    /// Return `true` if events of the topic are delivered strictly in
+    /// published order to the consumer.
+    pub fn is_strict_order(&self, topic_id: &str, consumer_id: &str) -> bool {
+        self.strict_order.contains(topic_id)
+            || self
+                .strict_order
+                .contains(&(topic_id.to_owned() + "/" + consumer_id))
+    }
+
+    /// Return the visibility timeout in microseconds to use for a
+    /// subscription that requested `visibility_timeout_millis`.
+    ///
+    /// Subscriptions that don't request a visibility timeout get the default
+    /// of [TopicConsumer::DEFAULT_VISIBILITY_TIMEOUT_MICROS]. The result is
+    /// always within the configured bounds.
+    pub fn bounded_visibility_timeout_micros(&self, visibility_timeout_millis: Option<u64>) -> u64 {
+        let (min_micros, max_micros) = self.visibility_timeout_bounds_micros;
+        visibility_timeout_millis
+            .map(|millis| millis.saturating_mul(1000))
+            .unwrap_or(TopicConsumer::DEFAULT_VISIBILITY_TIMEOUT_MICROS)
+            .clamp(min_micros, max_micros)
+    }
+
+    /// Return the [TopicConsumer] if it is tracked by this instance.
+    pub fn get_by_topic_and_consumer_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+    ) -> Option<Arc<TopicConsumer>> {
+        self.consumers
+            .get(&(topic_id.to_owned() + "." + consumer_id))
+            .map(|entry| Arc::clone(entry.value()))
+    }
+
+    /// Return all [TopicConsumer]s of a topic tracked by this instance.
+    pub fn get_by_topic(&self, topic_id: &str) -> Vec<Arc<TopicConsumer>> {
+        let prefix = topic_id.to_owned() + ".";
+        self.consumers
+            .range(prefix.to_owned()..)
+            .take_while(|entry| entry.key().starts_with(&prefix))
+            .map(|entry| Arc::clone(entry.value()))
+            .collect()
+    }
+
     /// Returns an existing [TopicConsumer] or a new persisted.
     pub async fn by_topic_and_consumer_id(
         &self,
@@ -80,9 +184,17 @@ impl Consumers {
                 TopicConsumer::new(
                     &self.dbp,
                     &self.object_count_tracker,
+                    &self.metrics,
+                    &self.task_supervisor,
                     topic_id,
                     consumer_id,
                     self.instance_id,
+                    self.max_redeliveries,
+                    self.get_delivery_concurrency(topic_id, consumer_id),
+                    self.is_strict_order(topic_id, consumer_id),
+                    &self.retry_backoff,
+                    self.bounded_visibility_timeout_micros(None),
+                    &self.delivery_cache_budget,
                 )
             });
             Ok(Arc::clone(entry.value()))
@@ -0,0 +1,268 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Cache of declared consumers and their delivery rate limits.
+
+use crossbeam_skiplist::SkipMap;
+use fragtale_client::mb::consumer_definitions::ConsumerDefinition;
+use fragtale_client::mb::consumer_definitions::ConsumerDefinitionTarget;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// Cache of declared consumers and their delivery rate limits.
+///
+/// Definitions are persisted in the database, so any instance will pick up
+/// changes made through another instance within the refresh interval.
+pub struct ConsumerDefinitionRegistry {
+    dbp: Arc<DatabaseProvider>,
+    /// Definitions by topic identifier and consumer identifier.
+    definitions_by_topic: SkipMap<String, Arc<SkipMap<String, Arc<ConsumerDefinition>>>>,
+    /// Last refresh from the database in epoch microseconds by topic.
+    refreshed_by_topic: SkipMap<String, AtomicU64>,
+    /// Last discovery of all topics in epoch microseconds.
+    discovered_ts: AtomicU64,
+    /// Earliest time of the next delivery by topic and consumer identifier.
+    next_delivery_by_consumer: SkipMap<String, AtomicU64>,
+}
+
+impl ConsumerDefinitionRegistry {
+    /// Time between reloading the definitions of a topic.
+    const REFRESH_INTERVAL_MICROS: u64 = 10_000_000;
+    /// Time between discovering definitions of all topics.
+    const DISCOVERY_INTERVAL_MICROS: u64 = 30_000_000;
+
+    /// Return a new instance.
+    pub fn new(dbp: &Arc<DatabaseProvider>) -> Arc<Self> {
+        Arc::new(Self {
+            dbp: Arc::clone(dbp),
+            definitions_by_topic: SkipMap::default(),
+            refreshed_by_topic: SkipMap::default(),
+            discovered_ts: AtomicU64::new(0),
+            next_delivery_by_consumer: SkipMap::default(),
+        })
+    }
+
+    /// Return all declared consumers of the topic.
+    pub async fn by_topic(&self, topic_id: &str) -> Vec<Arc<ConsumerDefinition>> {
+        self.definitions_of_topic(topic_id)
+            .await
+            .iter()
+            .map(|entry| Arc::clone(entry.value()))
+            .collect()
+    }
+
+    /// Return the declared subscriber of the topic that connects as
+    /// `consumer_id` (if any).
+    pub async fn subscriber_by_topic_and_consumer_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+    ) -> Option<Arc<ConsumerDefinition>> {
+        self.definitions_of_topic(topic_id)
+            .await
+            .get(consumer_id)
+            .map(|entry| Arc::clone(entry.value()))
+            .filter(|definition| {
+                matches!(
+                    definition.get_target(),
+                    ConsumerDefinitionTarget::Subscriber { principal: _ }
+                )
+            })
+    }
+
+    /// Return all declared webhook consumers of all topics as tuples of topic
+    /// identifier and definition.
+    pub async fn webhooks(&self) -> Vec<(String, Arc<ConsumerDefinition>)> {
+        let now = fragtale_client::time::get_timestamp_micros();
+        if self.discovered_ts.load(Ordering::Relaxed) + Self::DISCOVERY_INTERVAL_MICROS < now {
+            self.discovered_ts.store(now, Ordering::Relaxed);
+            let mut from = None;
+            loop {
+                let (topic_ids, more) = self.dbp.topic_facade().get_topic_ids(&from).await;
+                from = topic_ids.last().cloned();
+                for topic_id in &topic_ids {
+                    self.definitions_of_topic(topic_id).await;
+                }
+                if !more {
+                    break;
+                }
+            }
+        }
+        let mut ret = vec![];
+        for entry in self.definitions_by_topic.iter() {
+            for definition in self.definitions_of_topic(entry.key()).await.iter() {
+                if matches!(
+                    definition.value().get_target(),
+                    ConsumerDefinitionTarget::Webhook { url: _ }
+                ) {
+                    ret.push((entry.key().to_owned(), Arc::clone(definition.value())));
+                }
+            }
+        }
+        ret
+    }
+
+    /// Persist the definition and make it visible to this instance right
+    /// away.
+    ///
+    /// Return `true` if the definition was persisted.
+    pub async fn persist(&self, topic_id: &str, definition: &ConsumerDefinition) -> bool {
+        let ret = self
+            .dbp
+            .consumer_delivery_facade()
+            .consumer_definition_persist(
+                topic_id,
+                definition.get_name(),
+                &definition.as_string(),
+                fragtale_client::time::get_timestamp_micros(),
+            )
+            .await;
+        self.refresh(topic_id).await;
+        ret
+    }
+
+    /// Remove the definition and make the change visible to this instance
+    /// right away.
+    pub async fn remove(&self, topic_id: &str, name: &str) {
+        self.dbp
+            .consumer_delivery_facade()
+            .consumer_definition_remove(topic_id, name)
+            .await;
+        self.refresh(topic_id).await;
+    }
+
+    /// Return `true` if the consumer may get another event delivered now
+    /// without exceeding `max_events_per_second`.
+    ///
+    /// This is enforced per instance.
+    pub fn try_acquire_delivery(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        max_events_per_second: u32,
+    ) -> bool {
+        let interval_micros = 1_000_000 / u64::from(max_events_per_second.max(1));
+        let entry = self
+            .next_delivery_by_consumer
+            .get_or_insert_with(topic_id.to_owned() + "." + consumer_id, || {
+                AtomicU64::new(0)
+            });
+        Self::try_advance(
+            entry.value(),
+            fragtale_client::time::get_timestamp_micros(),
+            interval_micros,
+        )
+    }
+
+    /// Move the earliest time of the next delivery `interval_micros` past
+    /// `now` if it is due.
+    ///
+    /// Return `true` if the delivery was due.
+    fn try_advance(next_delivery: &AtomicU64, now: u64, interval_micros: u64) -> bool {
+        let current = next_delivery.load(Ordering::Relaxed);
+        current <= now
+            && next_delivery
+                .compare_exchange(
+                    current,
+                    now + interval_micros,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+    }
+
+    /// Return the cached definitions of the topic by consumer identifier and
+    /// reload them from the database when stale.
+    async fn definitions_of_topic(
+        &self,
+        topic_id: &str,
+    ) -> Arc<SkipMap<String, Arc<ConsumerDefinition>>> {
+        let now = fragtale_client::time::get_timestamp_micros();
+        let refreshed_ts = self
+            .refreshed_by_topic
+            .get(topic_id)
+            .map(|entry| entry.value().load(Ordering::Relaxed))
+            .unwrap_or_default();
+        if refreshed_ts + Self::REFRESH_INTERVAL_MICROS < now {
+            self.refresh(topic_id).await;
+        }
+        self.definitions_by_topic
+            .get(topic_id)
+            .map(|entry| Arc::clone(entry.value()))
+            .unwrap_or_default()
+    }
+
+    /// Reload the definitions of the topic from the database.
+    async fn refresh(&self, topic_id: &str) {
+        self.refreshed_by_topic
+            .get_or_insert_with(topic_id.to_owned(), || AtomicU64::new(0))
+            .value()
+            .store(
+                fragtale_client::time::get_timestamp_micros(),
+                Ordering::Relaxed,
+            );
+        let definitions = self
+            .dbp
+            .consumer_delivery_facade()
+            .consumer_definitions(topic_id)
+            .await
+            .iter()
+            .filter_map(|definition| {
+                ConsumerDefinition::from_string(definition).or_else(|| {
+                    log::warn!("Ignoring malformed consumer definition in '{topic_id}'.");
+                    None
+                })
+            })
+            .map(|definition| (definition.get_consumer_id(), Arc::new(definition)))
+            .collect::<SkipMap<_, _>>();
+        if definitions.is_empty() {
+            self.definitions_by_topic.remove(topic_id);
+        } else {
+            self.definitions_by_topic
+                .insert(topic_id.to_owned(), Arc::new(definitions));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deliveries_are_spaced_by_the_rate_limit() {
+        let next_delivery = AtomicU64::new(0);
+        assert!(ConsumerDefinitionRegistry::try_advance(
+            &next_delivery,
+            1_000,
+            100
+        ));
+        assert!(!ConsumerDefinitionRegistry::try_advance(
+            &next_delivery,
+            1_050,
+            100
+        ));
+        assert!(ConsumerDefinitionRegistry::try_advance(
+            &next_delivery,
+            1_100,
+            100
+        ));
+        assert_eq!(next_delivery.load(Ordering::Relaxed), 1_200);
+    }
+}
@@ -0,0 +1,115 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Memory budget shared by the delivery caches of all consumers.
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+/** Memory budget shared by the delivery caches of all consumers.
+
+Each cache is entitled to a fair share of the budget. A cache may use more than
+its share while the budget as a whole has room to spare, but once the budget is
+exhausted, caches above their share must evict entries until they are back
+within their share.
+
+Memory use is estimated from the size of the cached entries and not measured.
+*/
+pub struct DeliveryCacheBudget {
+    /// Max estimated bytes of all caches combined. `0` means unlimited.
+    max_bytes: usize,
+    used_bytes: AtomicUsize,
+    caches: AtomicUsize,
+}
+
+impl DeliveryCacheBudget {
+    /// Return a new instance.
+    pub fn new(max_bytes: usize) -> Arc<Self> {
+        Arc::new(Self {
+            max_bytes,
+            used_bytes: AtomicUsize::default(),
+            caches: AtomicUsize::default(),
+        })
+    }
+
+    /// Start tracking a cache that shares the budget.
+    pub fn register_cache(&self) {
+        self.caches.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Stop tracking a cache and release all its `used_bytes`.
+    pub fn unregister_cache(&self, used_bytes: usize) {
+        self.release(used_bytes);
+        self.caches.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Account for bytes used by a cache.
+    pub fn reserve(&self, bytes: usize) {
+        self.used_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Account for bytes no longer used by a cache.
+    pub fn release(&self, bytes: usize) {
+        self.used_bytes.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    /// Return the estimated bytes used by all caches.
+    pub fn get_used_bytes(&self) -> usize {
+        self.used_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Return `true` if a cache that uses `used_bytes` must shrink.
+    ///
+    /// This is only the case when the budget is exhausted and the cache uses
+    /// more than its fair share.
+    pub fn is_over_fair_share(&self, used_bytes: usize) -> bool {
+        self.max_bytes > 0
+            && self.get_used_bytes() > self.max_bytes
+            && used_bytes > self.fair_share_bytes()
+    }
+
+    /// Return the share of the budget that each cache is entitled to.
+    fn fair_share_bytes(&self) -> usize {
+        self.max_bytes / self.caches.load(Ordering::Relaxed).max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_caches_above_fair_share_shrink_when_exhausted() {
+        let budget = DeliveryCacheBudget::new(1000);
+        budget.register_cache();
+        budget.register_cache();
+        budget.reserve(900);
+        // Within budget, so even a cache above its share may keep growing
+        assert!(!budget.is_over_fair_share(900));
+        budget.reserve(200);
+        assert!(budget.is_over_fair_share(900));
+        assert!(!budget.is_over_fair_share(200));
+        budget.unregister_cache(900);
+        assert_eq!(budget.get_used_bytes(), 200);
+        // Unlimited budget
+        let budget = DeliveryCacheBudget::new(0);
+        budget.register_cache();
+        budget.reserve(usize::MAX / 2);
+        assert!(!budget.is_over_fair_share(usize::MAX / 2));
+    }
+}
@@ -0,0 +1,253 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Track members of consumer groups.
+
+use crate::mb::mb_metrics::MessageBrokerMetrics;
+use crossbeam_skiplist::SkipMap;
+use fragtale_client::mb::group_members::GroupMember;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// Number of confirmations during a single minute.
+#[derive(Default)]
+struct MinuteCount {
+    minute: AtomicU64,
+    count: AtomicU64,
+}
+
+/// Connection and confirmation state of a single group member.
+struct GroupMemberState {
+    connections: AtomicU64,
+    last_connected_ts_micros: AtomicU64,
+    last_confirmed_ts_micros: AtomicU64,
+    confirmations: Vec<MinuteCount>,
+}
+
+impl GroupMemberState {
+    /// Return a new instance.
+    fn new() -> Self {
+        Self {
+            connections: AtomicU64::default(),
+            last_connected_ts_micros: AtomicU64::default(),
+            last_confirmed_ts_micros: AtomicU64::default(),
+            confirmations: (0..GroupMembers::MAX_WINDOW_MINUTES)
+                .map(|_| MinuteCount::default())
+                .collect(),
+        }
+    }
+
+    /// Count a confirmation at `now_micros`.
+    fn confirmed(&self, now_micros: u64) {
+        self.last_confirmed_ts_micros
+            .store(now_micros, Ordering::Relaxed);
+        let minute = now_micros / 60_000_000;
+        let minute_count = &self.confirmations
+            [usize::try_from(minute % GroupMembers::MAX_WINDOW_MINUTES).unwrap()];
+        // Note: This is _not_ atomic as a whole, but good enough for statistics.
+        if minute_count.minute.swap(minute, Ordering::Relaxed) == minute {
+            minute_count.count.fetch_add(1, Ordering::Relaxed);
+        } else {
+            minute_count.count.store(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Return the number of confirmations within the last `window_minutes`.
+    fn confirmed_within(&self, now_micros: u64, window_minutes: u64) -> u64 {
+        let now_minute = now_micros / 60_000_000;
+        self.confirmations
+            .iter()
+            .filter(|minute_count| {
+                now_minute.saturating_sub(minute_count.minute.load(Ordering::Relaxed))
+                    < window_minutes
+            })
+            .map(|minute_count| minute_count.count.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    /// Return `true` if the member has neither been connected nor confirmed
+    /// any event within the max window.
+    fn is_stale(&self, now_micros: u64) -> bool {
+        let max_window_micros = GroupMembers::MAX_WINDOW_MINUTES * 60_000_000;
+        self.connections.load(Ordering::Relaxed) == 0
+            && std::cmp::max(
+                self.last_connected_ts_micros.load(Ordering::Relaxed),
+                self.last_confirmed_ts_micros.load(Ordering::Relaxed),
+            ) < now_micros.saturating_sub(max_window_micros)
+    }
+}
+
+/** Tracks members of consumer groups connected to this instance.
+
+All clients that share the same consumer identifier form a consumer group and
+each client instance identifies itself as a member of the group when it
+connects. Tracking open connections and recent confirmations of each member
+makes it possible to spot members that are connected but silently failing to
+process events.
+*/
+pub struct GroupMembers {
+    metrics: Option<Arc<MessageBrokerMetrics>>,
+    /// Member state by topic, consumer and member identifier.
+    members: SkipMap<(String, String, String), Arc<GroupMemberState>>,
+}
+
+impl GroupMembers {
+    /// Max window of confirmation statistics in minutes.
+    pub const MAX_WINDOW_MINUTES: u64 = 60;
+    /// Max length of a member identifier.
+    pub const MEMBER_ID_MAX_LEN: usize = 64;
+
+    /// Return a new instance.
+    pub fn new(metrics: &Option<Arc<MessageBrokerMetrics>>) -> Arc<Self> {
+        Arc::new(Self {
+            metrics: metrics.clone(),
+            members: SkipMap::default(),
+        })
+    }
+
+    /// Return `true` if the member identifier is non-empty, not too long and
+    /// only consists of URL safe characters.
+    pub fn is_well_formed_member_id(member_id: &str) -> bool {
+        !member_id.is_empty()
+            && member_id.len() <= Self::MEMBER_ID_MAX_LEN
+            && member_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ['-', '_', '.'].contains(&c))
+    }
+
+    /// Return the state of a member and start tracking it if needed.
+    fn member(&self, topic_id: &str, consumer_id: &str, member_id: &str) -> Arc<GroupMemberState> {
+        let key = (
+            topic_id.to_owned(),
+            consumer_id.to_owned(),
+            member_id.to_owned(),
+        );
+        if let Some(entry) = self.members.get(&key) {
+            return Arc::clone(entry.value());
+        }
+        self.purge_stale();
+        Arc::clone(
+            self.members
+                .get_or_insert_with(key, || Arc::new(GroupMemberState::new()))
+                .value(),
+        )
+    }
+
+    /// Stop tracking members that have been inactive longer than the max
+    /// window.
+    fn purge_stale(&self) {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        for entry in self.members.iter() {
+            if entry.value().is_stale(now_micros) {
+                let (topic_id, consumer_id, member_id) = entry.key();
+                if let Some(metrics) = &self.metrics {
+                    metrics.forget_group_member(topic_id, consumer_id, member_id);
+                }
+                entry.remove();
+            }
+        }
+    }
+
+    /// Track a new subscriber connection of a member.
+    pub fn connected(&self, topic_id: &str, consumer_id: &str, member_id: &str) {
+        let member = self.member(topic_id, consumer_id, member_id);
+        member.connections.fetch_add(1, Ordering::Relaxed);
+        member.last_connected_ts_micros.store(
+            fragtale_client::time::get_timestamp_micros(),
+            Ordering::Relaxed,
+        );
+        if let Some(metrics) = &self.metrics {
+            metrics.report_group_member_connected(topic_id, consumer_id, member_id, true);
+        }
+    }
+
+    /// Track a closed subscriber connection of a member.
+    pub fn disconnected(&self, topic_id: &str, consumer_id: &str, member_id: &str) {
+        let member = self.member(topic_id, consumer_id, member_id);
+        member.connections.fetch_sub(1, Ordering::Relaxed);
+        if let Some(metrics) = &self.metrics {
+            metrics.report_group_member_connected(topic_id, consumer_id, member_id, false);
+        }
+    }
+
+    /// Count a confirmed event delivery by a member.
+    pub fn confirmed(&self, topic_id: &str, consumer_id: &str, member_id: &str) {
+        self.member(topic_id, consumer_id, member_id)
+            .confirmed(fragtale_client::time::get_timestamp_micros());
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_group_member_confirmed_events(topic_id, consumer_id, member_id);
+        }
+    }
+
+    /// Return all tracked members of the consumer groups of a topic with the
+    /// number of confirmations within the last `window_minutes`.
+    pub fn get_by_topic(&self, topic_id: &str, window_minutes: u64) -> Vec<GroupMember> {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        self.members
+            .range((topic_id.to_owned(), String::new(), String::new())..)
+            .take_while(|entry| entry.key().0.eq(topic_id))
+            .filter(|entry| !entry.value().is_stale(now_micros))
+            .map(|entry| {
+                let (_topic_id, consumer_id, member_id) = entry.key();
+                let member = entry.value();
+                GroupMember::new(
+                    consumer_id,
+                    member_id,
+                    member.connections.load(Ordering::Relaxed),
+                    member.last_connected_ts_micros.load(Ordering::Relaxed),
+                    Some(member.last_confirmed_ts_micros.load(Ordering::Relaxed))
+                        .filter(|ts| *ts > 0),
+                    member.confirmed_within(now_micros, window_minutes),
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirmations_outside_window_are_not_counted() {
+        let member = GroupMemberState::new();
+        let minute_micros = 60_000_000;
+        let now_micros = 1000 * minute_micros;
+        member.confirmed(now_micros - 10 * minute_micros);
+        member.confirmed(now_micros - minute_micros);
+        member.confirmed(now_micros);
+        member.confirmed(now_micros);
+        assert_eq!(member.confirmed_within(now_micros, 1), 2);
+        assert_eq!(member.confirmed_within(now_micros, 5), 3);
+        assert_eq!(member.confirmed_within(now_micros, 60), 4);
+        // The slot of the oldest minute is reused
+        member.confirmed(now_micros + 50 * minute_micros);
+        assert_eq!(
+            member.confirmed_within(now_micros + 50 * minute_micros, 60),
+            4
+        );
+    }
+
+    #[test]
+    fn member_ids_are_url_safe() {
+        assert!(GroupMembers::is_well_formed_member_id("pod-1_a.b"));
+        assert!(!GroupMembers::is_well_formed_member_id(""));
+        assert!(!GroupMembers::is_well_formed_member_id("a&b=c"));
+        assert!(!GroupMembers::is_well_formed_member_id(&"x".repeat(65)));
+    }
+}
@@ -0,0 +1,71 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Preference of a consumer group member for events with a routing key.
+
+/** Preference of a consumer group member for events with a routing key.
+
+A member that subscribes with a stickiness key (e.g. a region or shard hint)
+prefers events where the text value at the topic's routing key field matches
+the key. This keeps keyed data on the same members for better cache locality,
+but is only a preference: events nobody prefers are still delivered to any
+member.
+*/
+pub struct StickyPreference {
+    routing_key_field: String,
+    sticky_key: String,
+}
+
+impl StickyPreference {
+    /// Return a new instance.
+    pub fn new(routing_key_field: &str, sticky_key: &str) -> Self {
+        Self {
+            routing_key_field: routing_key_field.to_owned(),
+            sticky_key: sticky_key.to_owned(),
+        }
+    }
+
+    /// Return `true` if the event document is a good match for the member.
+    ///
+    /// Documents without a text routing key are a good match for any member.
+    pub fn is_preferred(&self, document: &str) -> bool {
+        serde_json::from_str::<serde_json::Value>(document)
+            .ok()
+            .and_then(|document| {
+                document
+                    .pointer(&self.routing_key_field)
+                    .and_then(serde_json::Value::as_str)
+                    .map(|routing_key| routing_key == self.sticky_key)
+            })
+            .unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_other_routing_keys_are_not_preferred() {
+        let sticky_preference = StickyPreference::new("/region", "eu");
+        assert!(sticky_preference.is_preferred(r#"{"region":"eu"}"#));
+        assert!(!sticky_preference.is_preferred(r#"{"region":"us"}"#));
+        // Without a routing key, the event belongs to nobody in particular
+        assert!(sticky_preference.is_preferred(r#"{"other":"us"}"#));
+        assert!(sticky_preference.is_preferred(r#"{"region":1}"#));
+    }
+}
@@ -20,18 +20,29 @@
 mod consumer_delivery_cache;
 
 use self::consumer_delivery_cache::ConsumerDeliveryCache;
+use super::DeliveryCacheBudget;
+use super::StickyPreference;
+use crate::mb::mb_metrics::MessageBrokerMetrics;
 use crate::mb::object_count_tracker::ObjectCountTracker;
+use crate::mb::task_supervisor::TaskHeartbeat;
+use crate::mb::task_supervisor::TaskSupervisor;
+use crate::util::TimerWheel;
+use crossbeam_skiplist::SkipMap;
 use fragtale_client::mb::event_descriptor::DescriptorVersion;
+use fragtale_client::mb::subscription_health::SubscriptionHealth;
 use fragtale_dbp::dbp::DatabaseProvider;
 use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
 use fragtale_dbp::mb::ObjectCountType;
 use fragtale_dbp::mb::UniqueTime;
+use fragtale_dbp::mb::consumers::DeliveryIntentTemplate;
 use fragtale_dbp::mb::consumers::DeliveryIntentTemplateInsertable;
 use fragtale_dbp::mb::consumers::EventDeliveryGist;
+use fragtale_dbp::mb::consumers::RetryBackoff;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use tokio::sync::Notify;
 use tokio::time::Duration;
 use tokio::time::sleep;
 
@@ -47,15 +58,60 @@ pub struct TopicConsumer {
     last_reservation_attempt_micros: AtomicU64,
     maintain_fresh_has_run: AtomicBool,
     maintain_other_has_run: AtomicBool,
+    /// Set when the delivery cache has been populated for the first time.
+    ready: AtomicBool,
+    /// Wakes up reservations waiting for the delivery cache to become ready.
+    ready_notify: Notify,
+    metrics: Option<Arc<MessageBrokerMetrics>>,
+    /// Max redeliveries of the same event before delivery is paused. (0 disables.)
+    max_redeliveries: u32,
+    /// Event identifier and number of redeliveries by event unique time.
+    redeliveries: SkipMap<UniqueTime, (String, u32)>,
+    /// Encoded unique time of the event that caused delivery to pause or `0`.
+    paused_unique_time: AtomicU64,
+    delivery_concurrency: Option<u32>,
+    /// Deliver events strictly in published order.
+    strict_order: bool,
+    /// Encoded unique time of the delivered, but unconfirmed, event or `0`
+    /// when delivering strictly in published order.
+    unresolved_unique_time: AtomicU64,
+    /// Epoch microseconds when the unconfirmed delivery is considered failed.
+    unresolved_stale_micros: AtomicU64,
+    /// Start of the latest completed population with fresh events.
+    fresh_populated_micros: AtomicU64,
+    /// Start of the latest completed population with retries.
+    retries_populated_micros: AtomicU64,
+    /// Delay before each retry of a failed delivery of the same event.
+    retry_backoff: RetryBackoff,
+    /// Epoch microseconds when each unconfirmed delivery from this instance
+    /// is due for a retry.
+    pending_retries: SkipMap<UniqueTime, u64>,
+    /// Unique time and due epoch microseconds by retry due time.
+    retry_wheel: TimerWheel<(UniqueTime, u64)>,
+    /// Time to wait for confirmation of a delivery before it is considered
+    /// for redelivery.
+    visibility_timeout_micros: AtomicU64,
+    /// Epoch microseconds when each event was first left for the members of
+    /// the consumer group that prefer it.
+    sticky_deferrals: SkipMap<UniqueTime, u64>,
 }
 impl TopicConsumer {
     /// Return a new instance.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         dbp: &Arc<DatabaseProvider>,
         object_count_tracker: &Arc<ObjectCountTracker>,
+        metrics: &Option<Arc<MessageBrokerMetrics>>,
+        task_supervisor: &Arc<TaskSupervisor>,
         topic_id: &str,
         consumer_id: &str,
         instance_id: u16,
+        max_redeliveries: u32,
+        delivery_concurrency: Option<u32>,
+        strict_order: bool,
+        retry_backoff: &RetryBackoff,
+        visibility_timeout_micros: u64,
+        delivery_cache_budget: &Arc<DeliveryCacheBudget>,
     ) -> Arc<Self> {
         Arc::new(Self {
             topic_id: topic_id.to_owned(),
@@ -63,20 +119,68 @@ impl TopicConsumer {
             instance_id,
             dbp: Arc::clone(dbp),
             object_count_tracker: Arc::clone(object_count_tracker),
-            consumer_delivery_cache: ConsumerDeliveryCache::new(),
+            consumer_delivery_cache: ConsumerDeliveryCache::new(delivery_cache_budget),
             last_reservation_attempt_micros: AtomicU64::new(0),
             maintain_fresh_has_run: AtomicBool::new(false),
             maintain_other_has_run: AtomicBool::new(false),
+            ready: AtomicBool::new(false),
+            ready_notify: Notify::new(),
+            metrics: metrics.clone(),
+            max_redeliveries,
+            redeliveries: SkipMap::default(),
+            paused_unique_time: AtomicU64::new(0),
+            delivery_concurrency,
+            strict_order,
+            unresolved_unique_time: AtomicU64::new(0),
+            unresolved_stale_micros: AtomicU64::new(0),
+            fresh_populated_micros: AtomicU64::new(0),
+            retries_populated_micros: AtomicU64::new(0),
+            retry_backoff: retry_backoff.clone(),
+            pending_retries: SkipMap::default(),
+            retry_wheel: TimerWheel::new(Self::RETRY_TICK_MICROS),
+            visibility_timeout_micros: AtomicU64::new(visibility_timeout_micros),
+            sticky_deferrals: SkipMap::default(),
         })
-        .init()
+        .init(task_supervisor)
     }
 
     /// Initialize
-    fn init(self: Arc<Self>) -> Arc<Self> {
+    fn init(self: Arc<Self>, task_supervisor: &Arc<TaskSupervisor>) -> Arc<Self> {
+        let task_name_suffix = format!("{}_{}", self.topic_id, self.consumer_id);
         let self_clone = Arc::clone(&self);
-        tokio::spawn(async move { self_clone.maintain_delivery_cache_with_fresh().await });
+        task_supervisor.spawn(
+            "delivery_cache_initial",
+            format!("delivery_cache_initial_{task_name_suffix}"),
+            Self::MAX_SILENCE_MICROS,
+            move |_heartbeat| {
+                let self_clone = Arc::clone(&self_clone);
+                async move { self_clone.populate_delivery_cache_initial().await }
+            },
+        );
+        let self_clone = Arc::clone(&self);
+        task_supervisor.spawn(
+            "delivery_cache_fresh",
+            format!("delivery_cache_fresh_{task_name_suffix}"),
+            Self::MAX_SILENCE_MICROS,
+            move |heartbeat| {
+                let self_clone = Arc::clone(&self_clone);
+                async move {
+                    self_clone
+                        .maintain_delivery_cache_with_fresh(&heartbeat)
+                        .await
+                }
+            },
+        );
         let self_clone = Arc::clone(&self);
-        tokio::spawn(async move { self_clone.maintain_delivery_cache_other().await });
+        task_supervisor.spawn(
+            "delivery_cache_other",
+            format!("delivery_cache_other_{task_name_suffix}"),
+            Self::MAX_SILENCE_MICROS,
+            move |heartbeat| {
+                let self_clone = Arc::clone(&self_clone);
+                async move { self_clone.maintain_delivery_cache_other(&heartbeat).await }
+            },
+        );
         self
     }
 
@@ -89,23 +193,165 @@ impl TopicConsumer {
     /// duration, some newly publihsed events will be handled as "old".
     const FRESHNESS_DURATION_MICROS: u64 = 3_000_000;
     const CLOCK_SKEW_TOLERANCE_MICROS: u64 = 100_000;
+    /// Visibility timeout of subscriptions that don't request one.
+    pub const DEFAULT_VISIBILITY_TIMEOUT_MICROS: u64 = Self::FRESHNESS_DURATION_MICROS;
+    /// Max number of events to find in the fast initial population of the
+    /// delivery cache.
+    const INITIAL_POPULATION_MAX_EVENTS: usize = 16;
+    /// Max time between heartbeats of the delivery cache maintenance tasks.
+    const MAX_SILENCE_MICROS: u64 = 300_000_000;
+    /// Max time an event is left for the members of the consumer group that
+    /// prefer it, before it is delivered to any member.
+    pub const STICKY_DEFER_MAX_MICROS: u64 = 1_000_000;
+    /// Max number of events a member passes over in a single reservation.
+    const STICKY_MAX_DEFERRED: usize = 32;
+    /// Resolution of the scheduling of retries.
+    const RETRY_TICK_MICROS: u64 = 100_000;
+
+    /// Return the time to wait for confirmation of a delivery before it is
+    /// considered for redelivery.
+    pub fn get_visibility_timeout_micros(&self) -> u64 {
+        self.visibility_timeout_micros.load(Ordering::Relaxed)
+    }
+
+    /// Set the time to wait for confirmation of a delivery before it is
+    /// considered for redelivery.
+    ///
+    /// The latest subscription request of the consumer decides, so all
+    /// members of a consumer group should request the same value.
+    pub fn set_visibility_timeout_micros(&self, visibility_timeout_micros: u64) {
+        let previous = self
+            .visibility_timeout_micros
+            .swap(visibility_timeout_micros, Ordering::Relaxed);
+        if previous != visibility_timeout_micros && log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "Visibility timeout of '{}' on '{}' changed from {previous} to {visibility_timeout_micros} micros.",
+                self.consumer_id,
+                self.topic_id,
+            );
+        }
+    }
+
+    /// Return `true` while the delivery cache is still catching up, since not
+    /// both fresh events and retries have been populated yet.
+    pub fn is_catching_up(&self) -> bool {
+        !self.maintain_fresh_has_run.load(Ordering::Relaxed)
+            || !self.maintain_other_has_run.load(Ordering::Relaxed)
+    }
+
+    /// Mark the delivery cache as ready for reservations.
+    fn set_ready(&self) {
+        if !self.ready.swap(true, Ordering::Relaxed) {
+            self.ready_notify.notify_waiters();
+        }
+    }
+
+    /// Wait until the delivery cache has been populated for the first time.
+    async fn await_ready(&self) {
+        if self.ready.load(Ordering::Relaxed) {
+            return;
+        }
+        // Notify guarantees wakeup from the point the future is created
+        let notified = self.ready_notify.notified();
+        if !self.ready.load(Ordering::Relaxed) {
+            log::debug!("Waiting for initial cache population to run.");
+            notified.await;
+        }
+    }
+
+    /// Quickly populate the delivery cache with the first few events after
+    /// the attempted baseline, so a newly connected consumer doesn't have to
+    /// wait for the full cache population.
+    async fn populate_delivery_cache_initial(&self) {
+        if let Some(unique_time_attempted) = self
+            .dbp
+            .consumer_delivery_facade()
+            .consumer_get_attempted_by_id(&self.topic_id, &self.consumer_id)
+            .await
+        {
+            let cdc_clone = Arc::clone(&self.consumer_delivery_cache);
+            let diti: Box<Arc<dyn DeliveryIntentTemplateInsertable>> = Box::new(cdc_clone);
+            let any_new_found = self
+                .dbp
+                .consumer_delivery_facade()
+                .populate_delivery_cache_initial(
+                    &self.topic_id,
+                    &self.consumer_id,
+                    diti,
+                    unique_time_attempted,
+                    Self::INITIAL_POPULATION_MAX_EVENTS,
+                )
+                .await;
+            if log::log_enabled!(log::Level::Trace) {
+                log::trace!(
+                    "Initial population of '{}' on '{}' found new events: {any_new_found}",
+                    self.consumer_id,
+                    self.topic_id,
+                );
+            }
+        }
+        self.set_ready();
+    }
 
     /// Reserve a new event to deliver of an acceptable version.
+    ///
+    /// Return the event, the consumer's prepared external transaction
+    /// identifier if this is a redelivery of an event with a prepared, but not
+    /// committed, confirmation and the encoded descriptor version the event
+    /// was published with (if known).
+    ///
+    /// When a `sticky_preference` is present, events that other members of the
+    /// consumer group prefer are left for them for up to
+    /// [Self::STICKY_DEFER_MAX_MICROS] before they are delivered anyway.
+    ///
+    /// When `dead_letter` is `true`, events that exhausted the redelivery
+    /// budget are reserved instead of pausing delivery, so the caller can
+    /// dead-letter them after checking [Self::exhausted_redelivery].
     pub async fn reserve_delivery_intent(
         &self,
         descriptor_version: Option<DescriptorVersion>,
-    ) -> Option<EventDeliveryGist> {
-        while !self.maintain_fresh_has_run.load(Ordering::Relaxed)
-            || !self.maintain_other_has_run.load(Ordering::Relaxed)
-        {
-            // Sleep until this happens for the first time
-            sleep(Duration::from_millis(128)).await;
-            log::debug!("Waiting for fresh and old cache population to run.");
-        }
+        sticky_preference: Option<&StickyPreference>,
+        dead_letter: bool,
+    ) -> Option<(EventDeliveryGist, Option<String>, Option<u64>)> {
+        self.await_ready().await;
         self.last_reservation_attempt_micros.store(
             fragtale_client::time::get_timestamp_micros(),
             Ordering::Relaxed,
         );
+        if self.is_paused() {
+            return None;
+        }
+        if self.strict_order && !self.is_strict_order_resolved() {
+            return None;
+        }
+        let mut deferred = vec![];
+        let ret = self
+            .reserve_next_delivery_intent(
+                descriptor_version,
+                sticky_preference,
+                dead_letter,
+                &mut deferred,
+            )
+            .await;
+        // Put back events left for the members that prefer them
+        for dit in deferred {
+            self.consumer_delivery_cache
+                .return_delivery_intent_template(dit);
+        }
+        ret
+    }
+
+    /// Pull events from the delivery cache until one is reserved.
+    ///
+    /// Events that are left for other members of the consumer group are added
+    /// to `deferred`.
+    async fn reserve_next_delivery_intent(
+        &self,
+        descriptor_version: Option<DescriptorVersion>,
+        sticky_preference: Option<&StickyPreference>,
+        dead_letter: bool,
+        deferred: &mut Vec<DeliveryIntentTemplate>,
+    ) -> Option<(EventDeliveryGist, Option<String>, Option<u64>)> {
         // Pull oldest entry from delivery cache until we are able to reserve a DeliveryIntent
         while let Some(dit) = self
             .consumer_delivery_cache
@@ -114,14 +360,89 @@ impl TopicConsumer {
             if log::log_enabled!(log::Level::Trace) {
                 log::trace!("Pulled item from consumer_delivery_cache!");
             }
+            // Skip events that passed their deadline while waiting in the cache
+            if dit.is_expired(fragtale_client::time::get_timestamp_micros()) {
+                self.retire_expired(&dit).await;
+                continue;
+            }
+            // Halt on gaps where earlier events might still show up
+            if self.strict_order && !self.is_strict_order_next(&dit) {
+                self.consumer_delivery_cache
+                    .return_delivery_intent_template(dit);
+                return None;
+            }
             // Check if this event is of an acceptable version to the consumer
             if let Some(descriptor_version) = &descriptor_version
                 && let Some(event_descriptor_semver) = dit.get_descriptor_version()
                 && *event_descriptor_semver > descriptor_version.as_encoded()
             {
+                if self.strict_order {
+                    // Skipping would break the order, so wait for an upgraded consumer
+                    self.consumer_delivery_cache
+                        .return_delivery_intent_template(dit);
+                    return None;
+                }
                 // Find another event with a compatible version
                 continue;
             }
+            if dit.get_failed_intent_ts().is_some()
+                && !dead_letter
+                && self.pause_if_poisoned(&dit.get_unique_time())
+            {
+                return None;
+            }
+            // Leave events with another routing key to the members that prefer them
+            let mut prefetched_gist = None;
+            if let Some(sticky_preference) = sticky_preference
+                && !self.strict_order
+                && deferred.len() < Self::STICKY_MAX_DEFERRED
+            {
+                prefetched_gist = self
+                    .dbp
+                    .event_facade()
+                    .event_by_id_and_unique_time(
+                        &self.topic_id,
+                        dit.get_event_id(),
+                        dit.get_unique_time(),
+                    )
+                    .await;
+                if prefetched_gist.as_ref().is_some_and(|event_delivery_gist| {
+                    !sticky_preference.is_preferred(event_delivery_gist.get_document())
+                }) && self.defer_for_preferred(dit.get_unique_time())
+                {
+                    deferred.push(dit);
+                    continue;
+                }
+            }
+            // Respect the consumer group's cap on unconfirmed deliveries. The
+            // slot is held until the delivery is due for a retry.
+            if let Some(delivery_concurrency) = self.delivery_concurrency
+                && !self
+                    .dbp
+                    .consumer_delivery_facade()
+                    .delivery_slot_claim(
+                        &self.topic_id,
+                        &self.consumer_id,
+                        delivery_concurrency,
+                        dit.get_unique_time(),
+                        self.get_retry_delay_micros(
+                            self.get_retry_count(&dit.get_unique_time())
+                                + u32::from(dit.get_failed_intent_ts().is_some()),
+                        ),
+                    )
+                    .await
+            {
+                if log::log_enabled!(log::Level::Trace) {
+                    log::trace!(
+                        "All {delivery_concurrency} delivery slots of '{}' on '{}' are in use.",
+                        self.consumer_id,
+                        self.topic_id,
+                    );
+                }
+                self.consumer_delivery_cache
+                    .return_delivery_intent_template(dit);
+                return None;
+            }
             let intent_ts = fragtale_client::time::get_timestamp_micros();
             if log::log_enabled!(log::Level::Trace) {
                 let duration_since_publishing = intent_ts - dit.get_unique_time().get_time_micros();
@@ -142,7 +463,7 @@ impl TopicConsumer {
                     self.instance_id,
                     dit.get_descriptor_version(),
                     intent_ts,
-                    Self::FRESHNESS_DURATION_MICROS,
+                    self.get_visibility_timeout_micros(),
                     *dit.get_failed_intent_ts(),
                 )
                 .await;
@@ -151,16 +472,59 @@ impl TopicConsumer {
                     &self.topic_id.to_owned(),
                     &ObjectCountType::ReservedDeliveryIntents,
                 );
-                return self
-                    .dbp
-                    .event_facade()
-                    .event_by_id_and_unique_time(
-                        &self.topic_id,
-                        dit.get_event_id(),
-                        dit.get_unique_time(),
-                    )
+                if dit.get_failed_intent_ts().is_some() {
+                    self.track_redelivery(dit.get_unique_time(), dit.get_event_id());
+                }
+                self.schedule_retry(dit.get_unique_time(), intent_ts);
+                if self.strict_order {
+                    self.unresolved_stale_micros.store(
+                        intent_ts + self.get_visibility_timeout_micros(),
+                        Ordering::Relaxed,
+                    );
+                    self.unresolved_unique_time
+                        .store(dit.get_unique_time().as_encoded(), Ordering::Relaxed);
+                }
+                self.sticky_deferrals.remove(&dit.get_unique_time());
+                let event_delivery_gist = match prefetched_gist {
+                    Some(event_delivery_gist) => event_delivery_gist,
+                    None => {
+                        self.dbp
+                            .event_facade()
+                            .event_by_id_and_unique_time(
+                                &self.topic_id,
+                                dit.get_event_id(),
+                                dit.get_unique_time(),
+                            )
+                            .await?
+                    }
+                };
+                // Only a redelivery can have a prepared confirmation
+                let prepared_transaction_id = if dit.get_failed_intent_ts().is_some() {
+                    self.dbp
+                        .consumer_delivery_facade()
+                        .delivery_prepared_transaction_id(
+                            &self.topic_id,
+                            &self.consumer_id,
+                            dit.get_unique_time(),
+                        )
+                        .await
+                } else {
+                    None
+                };
+                return Some((
+                    event_delivery_gist,
+                    prepared_transaction_id,
+                    *dit.get_descriptor_version(),
+                ));
+            }
+            if self.delivery_concurrency.is_some() {
+                // Free the slot for another delivery
+                self.dbp
+                    .consumer_delivery_facade()
+                    .delivery_slot_release(&self.topic_id, &self.consumer_id, dit.get_unique_time())
                     .await;
-            } else if log::log_enabled!(log::Level::Trace) {
+            }
+            if log::log_enabled!(log::Level::Trace) {
                 log::trace!(
                     "Failed to reserve DeliveryIntent for '{}' on '{}'.",
                     self.consumer_id,
@@ -171,13 +535,453 @@ impl TopicConsumer {
         None
     }
 
+    /// Return `true` if the event should still be left for the members of the
+    /// consumer group that prefer it.
+    fn defer_for_preferred(&self, unique_time: UniqueTime) -> bool {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        // Forget events that were delivered by another instance meanwhile
+        for entry in self.sticky_deferrals.iter() {
+            if *entry.value() + Self::STICKY_DEFER_MAX_MICROS * 2 < now_micros {
+                entry.remove();
+            }
+        }
+        let first_deferred_micros = *self
+            .sticky_deferrals
+            .get_or_insert(unique_time, now_micros)
+            .value();
+        now_micros < first_deferred_micros + Self::STICKY_DEFER_MAX_MICROS
+    }
+
+    /// Return up to `limit` of the events of an acceptable version next in
+    /// line for delivery without reserving them.
+    ///
+    /// Only events already in the delivery cache are considered. Each event is
+    /// returned with the encoded descriptor version the event was published
+    /// with (if known) and `true` if an earlier delivery of it failed.
+    pub async fn peek_delivery_intents(
+        &self,
+        limit: usize,
+        descriptor_version: Option<DescriptorVersion>,
+    ) -> Vec<(EventDeliveryGist, Option<u64>, bool)> {
+        self.await_ready().await;
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let dits = self
+            .consumer_delivery_cache
+            .peek_delivery_intent_templates(limit, |dit| {
+                !dit.is_expired(now_micros)
+                    && descriptor_version
+                        .as_ref()
+                        .zip(*dit.get_descriptor_version())
+                        .is_none_or(|(descriptor_version, event_descriptor_semver)| {
+                            event_descriptor_semver <= descriptor_version.as_encoded()
+                        })
+            });
+        let mut peeked = Vec::with_capacity(dits.len());
+        for dit in dits {
+            // The event might have been removed by retention since it was cached
+            if let Some(event_delivery_gist) = self
+                .dbp
+                .event_facade()
+                .event_by_id_and_unique_time(
+                    &self.topic_id,
+                    dit.get_event_id(),
+                    dit.get_unique_time(),
+                )
+                .await
+            {
+                peeked.push((
+                    event_delivery_gist,
+                    *dit.get_descriptor_version(),
+                    dit.get_failed_intent_ts().is_some(),
+                ));
+            }
+        }
+        peeked
+    }
+
+    /// Return `true` if no earlier delivery blocks the next delivery in strict
+    /// publish-order.
+    ///
+    /// An unconfirmed delivery is resolved when it is confirmed or, once it
+    /// is considered failed, when the scan for retries has run again. The
+    /// scan puts the failed event back into the cache, where it will be the
+    /// oldest entry and hence delivered next.
+    fn is_strict_order_resolved(&self) -> bool {
+        // Failed deliveries from before this instance started serving the consumer
+        if self.is_catching_up() {
+            return false;
+        }
+        let unresolved = self.unresolved_unique_time.load(Ordering::Relaxed);
+        if unresolved == 0 {
+            return true;
+        }
+        if self.unresolved_stale_micros.load(Ordering::Relaxed)
+            < self.retries_populated_micros.load(Ordering::Relaxed)
+        {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!(
+                    "Delivery of {unresolved} to '{}' on '{}' was not confirmed in time.",
+                    self.consumer_id,
+                    self.topic_id,
+                );
+            }
+            self.unresolved_unique_time.store(0, Ordering::Relaxed);
+            return true;
+        }
+        false
+    }
+
+    /// Return `true` if the event can be delivered next in strict
+    /// publish-order.
+    ///
+    /// Events published on other instances with an earlier [UniqueTime] might
+    /// still show up for as long as clock skew is tolerated, so the event must
+    /// be older than that when the fresh events were last populated.
+    fn is_strict_order_next(&self, dit: &DeliveryIntentTemplate) -> bool {
+        dit.get_unique_time().get_time_micros() + Self::CLOCK_SKEW_TOLERANCE_MICROS
+            < self.fresh_populated_micros.load(Ordering::Relaxed)
+    }
+
+    /// Schedule a check for when the delivery of the event reserved at
+    /// `intent_ts` epoch microseconds is due for a retry, unless confirmed
+    /// before that.
+    fn schedule_retry(&self, unique_time: UniqueTime, intent_ts: u64) {
+        let due_ts_micros = intent_ts
+            .saturating_add(self.get_retry_delay_micros(self.get_retry_count(&unique_time)));
+        self.pending_retries.insert(unique_time, due_ts_micros);
+        self.retry_wheel
+            .schedule(due_ts_micros, (unique_time, due_ts_micros));
+    }
+
+    /// Return the number of times the event has been redelivered by this
+    /// instance.
+    fn get_retry_count(&self, unique_time: &UniqueTime) -> u32 {
+        self.redeliveries
+            .get(unique_time)
+            .map(|entry| entry.value().1)
+            .unwrap_or_default()
+    }
+
+    /// Return the time in microseconds from a delivery that was preceded by
+    /// `retry_count` retries until it is due for another retry.
+    fn get_retry_delay_micros(&self, retry_count: u32) -> u64 {
+        self.retry_backoff
+            .get_due_ts_micros(0, retry_count, self.get_visibility_timeout_micros())
+    }
+
+    /// Return `true` if any unconfirmed delivery from this instance has
+    /// become due for a retry since the last check.
+    fn any_retry_due(&self) -> bool {
+        let now = fragtale_client::time::get_timestamp_micros();
+        let mut any_due = false;
+        for (unique_time, due_ts_micros) in self.retry_wheel.expire(now) {
+            // Skip confirmed deliveries and deliveries that were reserved again
+            if self
+                .pending_retries
+                .get(&unique_time)
+                .is_some_and(|entry| *entry.value() == due_ts_micros)
+            {
+                self.pending_retries.remove(&unique_time);
+                any_due = true;
+            }
+        }
+        any_due
+    }
+
+    /// Sleep for [Self::FRESHNESS_DURATION_MICROS] or until an unconfirmed
+    /// delivery is due for a retry.
+    ///
+    /// Return `true` if a retry is due.
+    async fn sleep_until_retry_due(&self) -> bool {
+        let mut slept_micros = 0;
+        while slept_micros < Self::FRESHNESS_DURATION_MICROS {
+            if self.any_retry_due() {
+                return true;
+            }
+            sleep(Duration::from_micros(Self::RETRY_TICK_MICROS)).await;
+            slept_micros += Self::RETRY_TICK_MICROS;
+        }
+        self.any_retry_due()
+    }
+
+    /// Track a confirmed delivery to the consumer.
+    pub fn report_confirmed(&self, unique_time: &UniqueTime) {
+        self.pending_retries.remove(unique_time);
+        if self.strict_order {
+            let _ = self.unresolved_unique_time.compare_exchange(
+                unique_time.as_encoded(),
+                0,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            );
+        }
+    }
+
+    /// Mark all events that had passed their deadline when they were added to
+    /// the delivery cache as expired.
+    async fn retire_all_expired(&self) {
+        while let Some(dit) = self
+            .consumer_delivery_cache
+            .get_next_expired_delivery_intent_template()
+        {
+            self.retire_expired(&dit).await;
+        }
+    }
+
+    /// Mark an event that passed its deadline as done for the consumer
+    /// without delivering it.
+    async fn retire_expired(&self, dit: &DeliveryIntentTemplate) {
+        // Reserve first, so only a single instance retires the event
+        let reserved = self
+            .dbp
+            .consumer_delivery_facade()
+            .delivery_intent_reserve(
+                &self.topic_id,
+                &self.consumer_id,
+                dit.get_event_id(),
+                dit.get_unique_time(),
+                self.instance_id,
+                dit.get_descriptor_version(),
+                fragtale_client::time::get_timestamp_micros(),
+                self.get_visibility_timeout_micros(),
+                *dit.get_failed_intent_ts(),
+            )
+            .await;
+        if !reserved {
+            return;
+        }
+        self.object_count_tracker
+            .inc(&self.topic_id, &ObjectCountType::ReservedDeliveryIntents);
+        self.dbp
+            .consumer_delivery_facade()
+            .delivery_intent_mark_done(
+                &self.topic_id,
+                &self.consumer_id,
+                dit.get_unique_time(),
+                self.instance_id,
+            )
+            .await;
+        self.object_count_tracker
+            .inc(&self.topic_id, &ObjectCountType::DoneDeliveryIntents);
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_expired_events(&self.topic_id);
+        }
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "Event '{}' in '{}' expired before delivery to '{}'.",
+                dit.get_event_id(),
+                self.topic_id,
+                self.consumer_id,
+            );
+        }
+    }
+
+    /// Return `true` if delivery to the consumer is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused_unique_time.load(Ordering::Relaxed) != 0
+    }
+
+    /// Count a redelivery of the event.
+    fn track_redelivery(&self, unique_time: UniqueTime, event_id: &str) {
+        let redeliveries = self
+            .redeliveries
+            .get(&unique_time)
+            .map(|entry| entry.value().1)
+            .unwrap_or_default();
+        self.redeliveries
+            .insert(unique_time, (event_id.to_owned(), redeliveries + 1));
+    }
+
+    /// Pause delivery if the event has been redelivered too many times.
+    ///
+    /// Unless the topic has a dead-letter topic for events that a consumer is
+    /// unable to process, this prevents the event from being redelivered
+    /// forever.
+    ///
+    /// Return `true` if delivery is paused.
+    fn pause_if_poisoned(&self, unique_time: &UniqueTime) -> bool {
+        if self.max_redeliveries == 0 {
+            return false;
+        }
+        let Some((event_id, redeliveries)) = self
+            .redeliveries
+            .get(unique_time)
+            .map(|entry| entry.value().clone())
+            .filter(|(_event_id, redeliveries)| *redeliveries >= self.max_redeliveries)
+        else {
+            return false;
+        };
+        if self
+            .paused_unique_time
+            .compare_exchange(
+                0,
+                unique_time.as_encoded(),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+        {
+            log::warn!(
+                "Paused delivery to '{}' on '{}' since event '{event_id}' has been redelivered {redeliveries} times.",
+                self.consumer_id,
+                self.topic_id,
+            );
+            if let Some(metrics) = &self.metrics {
+                metrics.report_subscription_paused(&self.topic_id, true);
+            }
+        }
+        true
+    }
+
+    /// Return the event identifier and the number of redeliveries if the
+    /// event has been redelivered more times than allowed.
+    pub fn exhausted_redelivery(&self, unique_time: &UniqueTime) -> Option<(String, u32)> {
+        if self.max_redeliveries == 0 {
+            return None;
+        }
+        self.redeliveries
+            .get(unique_time)
+            .map(|entry| entry.value().clone())
+            .filter(|(_event_id, redeliveries)| *redeliveries > self.max_redeliveries)
+    }
+
+    /// Resume a paused delivery.
+    ///
+    /// The event that caused the pause gets a new budget of redeliveries.
+    ///
+    /// Return `true` if delivery was paused.
+    pub fn resume(&self) -> bool {
+        let paused_unique_time = self.paused_unique_time.swap(0, Ordering::Relaxed);
+        if paused_unique_time == 0 {
+            return false;
+        }
+        self.redeliveries
+            .remove(&UniqueTime::from(paused_unique_time));
+        log::info!(
+            "Resumed delivery to '{}' on '{}'.",
+            self.consumer_id,
+            self.topic_id,
+        );
+        if let Some(metrics) = &self.metrics {
+            metrics.report_subscription_paused(&self.topic_id, false);
+        }
+        true
+    }
+
+    /// Return the health of delivery to the consumer.
+    pub fn get_health(&self) -> SubscriptionHealth {
+        let paused_unique_time = self.paused_unique_time.load(Ordering::Relaxed);
+        let paused = (paused_unique_time != 0)
+            .then(|| {
+                self.redeliveries
+                    .get(&UniqueTime::from(paused_unique_time))
+                    .map(|entry| entry.value().clone())
+            })
+            .flatten();
+        SubscriptionHealth::new(
+            &self.consumer_id,
+            paused_unique_time != 0,
+            paused.as_ref().map(|(event_id, _)| event_id.to_owned()),
+            paused.as_ref().map(|(_, redeliveries)| *redeliveries),
+            self.redeliveries.len(),
+        )
+    }
+
+    /// Forget redeliveries of events at or before `unique_time_done`.
+    fn purge_redeliveries_up_to(&self, unique_time_done: &UniqueTime) {
+        let paused_unique_time = self.paused_unique_time.load(Ordering::Relaxed);
+        while let Some(entry) = self.redeliveries.front() {
+            if entry.key() > unique_time_done || entry.key().as_encoded() == paused_unique_time {
+                break;
+            }
+            entry.remove();
+        }
+    }
+
+    /// Commit the position up to which the consumer has processed all events.
+    ///
+    /// This moves the done (and if needed the attempted) baseline forward, so
+    /// that events at or before `position` will neither be delivered nor
+    /// retried again. Positions behind the current done baseline are ignored.
+    ///
+    /// Return `true` if the position was moved forward.
+    pub async fn commit_position(&self, position: UniqueTime) -> bool {
+        let consumer_delivery_facade = self.dbp.consumer_delivery_facade();
+        let unique_time_done = consumer_delivery_facade
+            .consumer_get_done_by_id(&self.topic_id, &self.consumer_id)
+            .await;
+        if unique_time_done.is_some_and(|done| done >= position) {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!(
+                    "Ignoring commit of position {} for '{}' on '{}' since it is not ahead of the current baseline.",
+                    position.as_encoded(),
+                    self.consumer_id,
+                    self.topic_id
+                );
+            }
+            return false;
+        }
+        if !consumer_delivery_facade
+            .consumer_set_done_by_id(&self.topic_id, &self.consumer_id, position)
+            .await
+        {
+            return false;
+        }
+        let unique_time_attempted = consumer_delivery_facade
+            .consumer_get_attempted_by_id(&self.topic_id, &self.consumer_id)
+            .await;
+        if unique_time_attempted.is_none_or(|attempted| attempted < position) {
+            consumer_delivery_facade
+                .consumer_set_attempted_by_id(&self.topic_id, &self.consumer_id, position)
+                .await;
+        }
+        self.consumer_delivery_cache.purge_up_to(&position);
+        self.purge_redeliveries_up_to(&position);
+        true
+    }
+
+    /// Move the position of the consumer to `position` in either direction.
+    ///
+    /// Unlike [Self::commit_position], this can rewind the consumer to replay
+    /// history. Events after `position` will be delivered (again), while
+    /// events at or before it will neither be delivered nor retried. A paused
+    /// delivery is resumed.
+    ///
+    /// Other instances might still deliver events from their delivery cache
+    /// that was populated before the seek.
+    ///
+    /// Return `true` if the position was moved.
+    pub async fn seek(&self, position: UniqueTime) -> bool {
+        if !self
+            .dbp
+            .consumer_delivery_facade()
+            .consumer_seek(&self.topic_id, &self.consumer_id, position)
+            .await
+        {
+            return false;
+        }
+        self.consumer_delivery_cache.purge_up_to(&position);
+        self.resume();
+        self.redeliveries.clear();
+        self.sticky_deferrals.clear();
+        self.unresolved_unique_time.store(0, Ordering::Relaxed);
+        log::info!(
+            "Moved position of '{}' on '{}' to {}.",
+            self.consumer_id,
+            self.topic_id,
+            position.as_encoded()
+        );
+        true
+    }
+
     /// Populate delivery cache with information about newly arrived events.
     ///
     /// This ensures that the delivery cache for the consumer has sufficient
     /// entries to pull from when delivery is possible/requested.
-    async fn maintain_delivery_cache_with_fresh(&self) {
+    async fn maintain_delivery_cache_with_fresh(&self, heartbeat: &TaskHeartbeat) {
         // Load enough "next" events to keep a descent queue to pull from
         loop {
+            heartbeat.beat();
             // Refresh ConsumerEntity info
             if let Some(unique_time_attempted) = self
                 .dbp
@@ -199,6 +1003,7 @@ impl TopicConsumer {
                         unique_time_attempted,
                     )
                     .await;
+                self.retire_all_expired().await;
                 let last_attempted_ts =
                     std::cmp::min(
                         last_attempted_ts,
@@ -219,7 +1024,10 @@ impl TopicConsumer {
                         log::trace!("Updated done baseline!");
                     }
                 }
+                self.fresh_populated_micros.store(now, Ordering::Relaxed);
                 self.maintain_fresh_has_run.store(true, Ordering::Relaxed);
+                self.set_ready();
+                self.report_delivery_cache_metrics();
                 if log::log_enabled!(log::Level::Trace) {
                     log::trace!(
                         "After getting fresh, the cache now has {} items.",
@@ -251,6 +1059,7 @@ impl TopicConsumer {
                         == last_reservation_attempt_micros
                     {
                         // Sleep until this happens
+                        heartbeat.beat();
                         sleep(Duration::from_millis(128)).await
                     }
                 } else if !any_new_found {
@@ -277,16 +1086,38 @@ impl TopicConsumer {
         }
     }
 
+    /// Report evictions from and residency of the delivery cache.
+    fn report_delivery_cache_metrics(&self) {
+        let evictions = self.consumer_delivery_cache.take_evictions();
+        if evictions > 0 && log::log_enabled!(log::Level::Debug) {
+            log::debug!(
+                "Evicted {evictions} events from the delivery cache of '{}' on '{}' to stay within the memory budget.",
+                self.consumer_id,
+                self.topic_id
+            );
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.inc_delivery_cache_evictions(&self.topic_id, evictions);
+            metrics.report_delivery_cache_residency(
+                &self.topic_id,
+                &self.consumer_id,
+                self.consumer_delivery_cache.len(),
+                self.consumer_delivery_cache.get_used_bytes(),
+            );
+        }
+    }
+
     /// Populate delivery cache with information about events where delivery has
     /// failed or was inconclusive.
     ///
     /// This ensures that the delivery cache for the consumer has sufficient
     /// entries to pull from when delivery is possible/requested.
-    async fn maintain_delivery_cache_other(self: &Arc<Self>) {
+    async fn maintain_delivery_cache_other(self: &Arc<Self>, heartbeat: &TaskHeartbeat) {
         // Load enough "next" events to keep a descent queue to pull from
         let mut glitch_count = 0;
         let mut counter = 0u64;
         loop {
+            heartbeat.beat();
             let now = fragtale_client::time::get_timestamp_micros();
             // Refresh ConsumerEntity info
             if let Some(unique_time_done) = self
@@ -307,11 +1138,14 @@ impl TopicConsumer {
                         &self.consumer_id,
                         diti,
                         unique_time_done,
-                        Self::FRESHNESS_DURATION_MICROS,
+                        self.get_visibility_timeout_micros(),
                         Self::CLOCK_SKEW_TOLERANCE_MICROS,
+                        &self.retry_backoff,
                     )
                     .await
                     - UniqueTime::min_encoded_for_micros(Self::CLOCK_SKEW_TOLERANCE_MICROS);
+                self.retire_all_expired().await;
+                self.purge_redeliveries_up_to(&unique_time_done);
                 // Update ConsumerEntity info if we have newer done
                 if last_done_ts > unique_time_done.as_encoded() {
                     let applied = self
@@ -330,7 +1164,10 @@ impl TopicConsumer {
                         );
                     }
                 }
+                self.retries_populated_micros
+                    .store(start_ts, Ordering::Relaxed);
                 self.maintain_other_has_run.store(true, Ordering::Relaxed);
+                self.report_delivery_cache_metrics();
                 if log::log_enabled!(log::Level::Trace) {
                     log::trace!(
                         "After getting others, the cache now has {} items.",
@@ -346,6 +1183,7 @@ impl TopicConsumer {
                 // Step through and update baseline from time to time even when the system is mostly idle
                 // (since entires might expire this is pretty far from bullet proof, but gets the job done)
                 for i in 0..48 {
+                    heartbeat.beat();
                     let reserved_before = self
                         .object_count_tracker
                         .get_total_object_count(
@@ -353,8 +1191,16 @@ impl TopicConsumer {
                             &ObjectCountType::ReservedDeliveryIntents,
                         )
                         .await;
-                    //sleep(Duration::from_micros(Self::FRESHNESS_DURATION_MICROS)).await;
-                    sleep(Duration::from_micros(Self::FRESHNESS_DURATION_MICROS)).await;
+                    if self.sleep_until_retry_due().await {
+                        if log::log_enabled!(log::Level::Trace) {
+                            log::trace!(
+                                "A delivery to '{}' on '{}' is due for a retry.",
+                                self.consumer_id,
+                                self.topic_id
+                            );
+                        }
+                        break;
+                    }
                     let done_after = self
                         .object_count_tracker
                         .get_total_object_count(
@@ -17,30 +17,55 @@
 
 //! Cache of events to delivery to a connected consumer.
 
+use super::super::DeliveryCacheBudget;
 use crossbeam_skiplist::SkipMap;
 use crossbeam_skiplist::SkipSet;
 use fragtale_dbp::mb::UniqueTime;
 use fragtale_dbp::mb::consumers::DeliveryIntentTemplate;
 use fragtale_dbp::mb::consumers::DeliveryIntentTemplateInsertable;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
 /** A cache of events that should be delivered to a connected consumer.
 
 This cache also tracks recently pulled events, to prevent a race condition where
 the same event might be added again.
+
+Events that have passed their deadline when inserted are kept apart, so they
+can be marked as expired instead of being delivered late.
+
+The estimated memory use of the cache is accounted in a [DeliveryCacheBudget]
+shared with all other consumers. When the cache has to shrink, the oldest
+events are evicted. Evicted events will be re-discovered by the scan for
+failed or inconclusive deliveries.
 */
-#[derive(Default)]
 pub struct ConsumerDeliveryCache {
     events: SkipMap<UniqueTime, DeliveryIntentTemplate>,
     recently_pulled: SkipSet<UniqueTime>,
+    expired: SkipMap<UniqueTime, DeliveryIntentTemplate>,
+    budget: Arc<DeliveryCacheBudget>,
+    used_bytes: AtomicUsize,
+    evictions: AtomicU64,
 }
 
 impl ConsumerDeliveryCache {
     const MAX_CACHE_SIZE: usize = 1024;
+    /// Estimated bytes used by the skip list node of each cached entry.
+    const ENTRY_OVERHEAD_BYTES: usize = 64;
 
     /// Return a new instance.
-    pub fn new() -> Arc<Self> {
-        Arc::new(Self::default())
+    pub fn new(budget: &Arc<DeliveryCacheBudget>) -> Arc<Self> {
+        budget.register_cache();
+        Arc::new(Self {
+            events: SkipMap::default(),
+            recently_pulled: SkipSet::default(),
+            expired: SkipMap::default(),
+            budget: Arc::clone(budget),
+            used_bytes: AtomicUsize::default(),
+            evictions: AtomicU64::default(),
+        })
     }
 
     /// Return a guesstimate of the number of events pending delivery in cache.
@@ -54,17 +79,128 @@ impl ConsumerDeliveryCache {
         self.recently_pulled.len()
     }
 
+    /// Return the estimated bytes used by this cache.
+    pub fn get_used_bytes(&self) -> usize {
+        self.used_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Return the number of events evicted since the last call.
+    pub fn take_evictions(&self) -> u64 {
+        self.evictions.swap(0, Ordering::Relaxed)
+    }
+
     /// Return the next event to delivery ordered by UniqueTime.
     pub fn get_next_delivery_intent_template(&self) -> Option<DeliveryIntentTemplate> {
         // Pull from list until a DeliveryIntent has been successfully reserved
         self.events.pop_front().map(|entry| {
             let delivery_intent_template = entry.value().clone();
+            self.release(&delivery_intent_template);
             // Best effort to prevent some unnessary reservation attemps (small race condition here)
             self.recently_pulled
                 .insert(delivery_intent_template.get_unique_time());
             delivery_intent_template
         })
     }
+
+    /// Return up to `limit` of the next events to deliver that `include`
+    /// accepts ordered by UniqueTime without pulling them from the cache.
+    pub fn peek_delivery_intent_templates(
+        &self,
+        limit: usize,
+        include: impl Fn(&DeliveryIntentTemplate) -> bool,
+    ) -> Vec<DeliveryIntentTemplate> {
+        self.events
+            .iter()
+            .map(|entry| entry.value().clone())
+            .filter(include)
+            .take(limit)
+            .collect()
+    }
+
+    /// Return the next event that had passed its deadline when it was
+    /// inserted.
+    pub fn get_next_expired_delivery_intent_template(&self) -> Option<DeliveryIntentTemplate> {
+        self.expired.pop_front().map(|entry| {
+            self.release(entry.value());
+            entry.value().clone()
+        })
+    }
+
+    /// Put back an event that was pulled, but could not be delivered right now.
+    pub fn return_delivery_intent_template(
+        &self,
+        delivery_intent_template: DeliveryIntentTemplate,
+    ) {
+        self.recently_pulled
+            .remove(&delivery_intent_template.get_unique_time());
+        self.insert_accounted(&self.events, delivery_intent_template);
+    }
+
+    /// Remove all events up to and including `unique_time` from the cache.
+    pub fn purge_up_to(&self, unique_time: &UniqueTime) {
+        while let Some(entry) = self.events.front() {
+            if entry.key() > unique_time {
+                break;
+            }
+            if entry.remove() {
+                self.release(entry.value());
+            }
+        }
+    }
+
+    /// Insert into `map` and account for the estimated memory use.
+    fn insert_accounted(
+        &self,
+        map: &SkipMap<UniqueTime, DeliveryIntentTemplate>,
+        delivery_intent_template: DeliveryIntentTemplate,
+    ) {
+        let unique_time = delivery_intent_template.get_unique_time();
+        // Best effort to avoid counting a replaced entry twice
+        if let Some(entry) = map.get(&unique_time) {
+            self.release(entry.value());
+        }
+        self.reserve(&delivery_intent_template);
+        map.insert(unique_time, delivery_intent_template);
+        self.evict_over_fair_share();
+    }
+
+    /// Evict the oldest events while this cache uses more than its fair share
+    /// of an exhausted budget.
+    fn evict_over_fair_share(&self) {
+        while self.budget.is_over_fair_share(self.get_used_bytes()) {
+            let Some(entry) = self.events.pop_front() else {
+                break;
+            };
+            self.release(entry.value());
+            self.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn reserve(&self, delivery_intent_template: &DeliveryIntentTemplate) {
+        let bytes = Self::estimate_bytes(delivery_intent_template);
+        self.used_bytes.fetch_add(bytes, Ordering::Relaxed);
+        self.budget.reserve(bytes);
+    }
+
+    fn release(&self, delivery_intent_template: &DeliveryIntentTemplate) {
+        let bytes = Self::estimate_bytes(delivery_intent_template);
+        self.used_bytes.fetch_sub(bytes, Ordering::Relaxed);
+        self.budget.release(bytes);
+    }
+
+    /// Return the estimated memory use of a cached entry.
+    fn estimate_bytes(delivery_intent_template: &DeliveryIntentTemplate) -> usize {
+        Self::ENTRY_OVERHEAD_BYTES
+            + std::mem::size_of::<UniqueTime>()
+            + std::mem::size_of::<DeliveryIntentTemplate>()
+            + delivery_intent_template.get_event_id().len()
+    }
+}
+
+impl Drop for ConsumerDeliveryCache {
+    fn drop(&mut self) {
+        self.budget.unregister_cache(self.get_used_bytes());
+    }
 }
 
 impl DeliveryIntentTemplateInsertable for ConsumerDeliveryCache {
@@ -75,15 +211,17 @@ impl DeliveryIntentTemplateInsertable for ConsumerDeliveryCache {
             .remove(&delivery_intent_template.get_unique_time())
             .is_none()
         {
-            self.events.insert(
-                delivery_intent_template.get_unique_time(),
-                delivery_intent_template,
-            );
+            if delivery_intent_template.is_expired(fragtale_client::time::get_timestamp_micros()) {
+                self.insert_accounted(&self.expired, delivery_intent_template);
+                return;
+            }
+            self.insert_accounted(&self.events, delivery_intent_template);
         }
     }
 
     fn is_full(&self) -> bool {
         // Guesstimate
         self.events.len() > Self::MAX_CACHE_SIZE
+            || self.budget.is_over_fair_share(self.get_used_bytes())
     }
 }
@@ -0,0 +1,163 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Delivery of events to declared webhook consumers and delivery receipts to
+//! webhooks of producers.
+
+use fragtale_client::mb::delivery_envelope::DeliveryEnvelope;
+use reqwest::Client;
+use reqwest::ClientBuilder;
+use reqwest::Url;
+use reqwest::header::CONTENT_TYPE;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Delivery of events to declared webhook consumers and delivery receipts to
+/// webhooks of producers.
+pub struct WebhookSender {
+    client: Client,
+    /// Host names that receipt webhooks may point to without further
+    /// authorization.
+    receipt_webhook_hosts: HashSet<String>,
+    /// Limits the number of receipts that are sent concurrently.
+    receipts_in_flight: Arc<Semaphore>,
+}
+
+impl WebhookSender {
+    /// Header holding the topic identifier of the delivered event.
+    const HEADER_TOPIC_ID: &str = "fragtale-topic-id";
+    /// Header holding the encoded UniqueTime of the delivered event.
+    const HEADER_UNIQUE_TIME: &str = "fragtale-unique-time";
+    /// Header holding the correlation token of the delivered event.
+    const HEADER_CORRELATION_TOKEN: &str = "fragtale-correlation-token";
+    /// Header holding the event identifier of the delivered event.
+    const HEADER_EVENT_ID: &str = "fragtale-event-id";
+    /// Header holding the descriptor version the event was published with.
+    const HEADER_DESCRIPTOR_VERSION: &str = "fragtale-descriptor-version";
+    /// Header holding the schema identifier the event was published with.
+    const HEADER_SCHEMA_ID: &str = "fragtale-schema-id";
+    /// Max number of receipts sent concurrently before further receipts are
+    /// dropped.
+    const RECEIPTS_MAX_IN_FLIGHT: usize = 256;
+
+    /// Return a new instance.
+    pub fn new(receipt_webhook_hosts: HashSet<String>) -> Self {
+        let client = ClientBuilder::new()
+            .user_agent("fragtale/0.0.0")
+            .referer(false)
+            .redirect(reqwest::redirect::Policy::none())
+            .pool_max_idle_per_host(1)
+            .timeout(core::time::Duration::from_secs(10))
+            .build()
+            .unwrap();
+        Self {
+            client,
+            receipt_webhook_hosts,
+            receipts_in_flight: Arc::new(Semaphore::new(Self::RECEIPTS_MAX_IN_FLIGHT)),
+        }
+    }
+
+    /// Return `true` if the receipt webhook URL points to one of the
+    /// configured receipt webhook hosts.
+    pub fn is_allowed_receipt_url(&self, url: &str) -> bool {
+        Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+            .is_some_and(|host| self.receipt_webhook_hosts.contains(&host))
+    }
+
+    /// POST the event document to the webhook.
+    ///
+    /// Return `true` if the webhook responded with a success status.
+    pub async fn send(
+        &self,
+        url: &str,
+        delivery_envelope: &DeliveryEnvelope,
+        encoded_unique_time: u64,
+        correlation_token: &str,
+        document: &str,
+    ) -> bool {
+        let topic_id = delivery_envelope.get_topic_id();
+        let mut request = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(Self::HEADER_TOPIC_ID, topic_id)
+            .header(Self::HEADER_UNIQUE_TIME, encoded_unique_time.to_string())
+            .header(Self::HEADER_CORRELATION_TOKEN, correlation_token)
+            .header(Self::HEADER_EVENT_ID, delivery_envelope.get_event_id());
+        if let Some(descriptor_version) = delivery_envelope.get_descriptor_version() {
+            request = request.header(Self::HEADER_DESCRIPTOR_VERSION, descriptor_version);
+        }
+        if let Some(schema_id) = delivery_envelope.get_schema_id() {
+            request = request.header(Self::HEADER_SCHEMA_ID, schema_id);
+        }
+        request
+            .body(document.to_owned())
+            .send()
+            .await
+            .map_err(|e| {
+                log::info!("Failed delivery to webhook of topic '{topic_id}': {e}");
+            })
+            .is_ok_and(|response| {
+                let status = response.status();
+                if !status.is_success() && log::log_enabled!(log::Level::Debug) {
+                    log::debug!(
+                        "Webhook of topic '{topic_id}' rejected delivery with status {status}."
+                    );
+                }
+                status.is_success()
+            })
+    }
+
+    /// POST a delivery receipt document to the webhook in the background.
+    ///
+    /// Receipts are sent at most once and failures are only logged. Receipts
+    /// are dropped while [Self::RECEIPTS_MAX_IN_FLIGHT] receipts are already
+    /// being sent, so slow webhooks can't pile up background tasks.
+    pub fn send_receipt(&self, url: &str, topic_id: &str, receipt: &str) {
+        let Ok(permit) = Arc::clone(&self.receipts_in_flight).try_acquire_owned() else {
+            log::info!(
+                "Dropped receipt to webhook of topic '{topic_id}' since too many are in flight."
+            );
+            return;
+        };
+        let request = self
+            .client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(Self::HEADER_TOPIC_ID, topic_id)
+            .body(receipt.to_owned());
+        let topic_id = topic_id.to_owned();
+        tokio::spawn(async move {
+            let _permit = permit;
+            match request.send().await {
+                Ok(response) if !response.status().is_success() => {
+                    log::info!(
+                        "Receipt webhook of topic '{topic_id}' responded with status {}.",
+                        response.status()
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    log::info!("Failed to send receipt to webhook of topic '{topic_id}': {e}");
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,223 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Signed reports of which consumers retrieved events of a topic.
+
+use crate::mb::auth::ClientIdentity;
+use crate::mb::integrity::common::IntegrityProtection;
+use crate::mb::integrity::common::IntegritySecretsHolder;
+use fragtale_client::mb::consumption_audit::ConsumptionAudit;
+use fragtale_client::mb::consumption_audit::ConsumptionRecord;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use fragtale_dbp::mb::UniqueTime;
+use std::sync::Arc;
+use tyst::Tyst;
+
+/// Signed reports of which consumers retrieved events of a topic.
+///
+/// Reports are built from the delivery intents of all consumers of the topic,
+/// which include the audit records of events retrieved by identifier or
+/// correlation token. Reports are signed with the same shared secrets that
+/// protect the integrity of events.
+pub struct ConsumptionAuditor {
+    dbp: Arc<DatabaseProvider>,
+    ish: Arc<IntegritySecretsHolder>,
+}
+
+impl ConsumptionAuditor {
+    /// Max number of records in a single report.
+    const MAX_RECORDS: usize = 10_000;
+    /// Max number of delivery records of a consumer in a single bucket.
+    const MAX_RECORDS_PER_BUCKET: usize = 4096;
+    /// Number of buckets requested from the database at a time.
+    const BUCKETS_PAGE_SIZE: usize = 32;
+
+    /// Return a new instance.
+    pub fn new(dbp: &Arc<DatabaseProvider>, ish: &Arc<IntegritySecretsHolder>) -> Arc<Self> {
+        Arc::new(Self {
+            dbp: Arc::clone(dbp),
+            ish: Arc::clone(ish),
+        })
+    }
+
+    /// Return a signed report of the consumption of events published in the
+    /// time window `[from_ts_micros..=to_ts_micros]`, optionally limited to a
+    /// single event identifier.
+    pub async fn audit(
+        &self,
+        topic_id: &str,
+        from_ts_micros: u64,
+        to_ts_micros: u64,
+        event_id: Option<&str>,
+    ) -> Result<ConsumptionAudit, MessageBrokerError> {
+        if from_ts_micros > to_ts_micros {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Start of time window {from_ts_micros} is after the end {to_ts_micros}."
+                )),
+            )?;
+        }
+        let from = UniqueTime::from(UniqueTime::min_encoded_for_micros(from_ts_micros));
+        let to = UniqueTime::from(
+            UniqueTime::min_encoded_for_micros(to_ts_micros.saturating_add(1)).saturating_sub(1),
+        );
+        let consumer_ids = self
+            .dbp
+            .consumer_delivery_facade()
+            .consumer_ids(topic_id)
+            .await;
+        let mut records = Vec::new();
+        let mut truncated = false;
+        // The bucket queries are exclusive of the provided bucket
+        let mut current_bucket = from.get_bucket().checked_sub(1);
+        'shelves: for shelf in from.get_shelf()..=to.get_shelf() {
+            loop {
+                let (buckets, more) = self
+                    .dbp
+                    .event_facade()
+                    .buckets_by_shelf(topic_id, shelf, current_bucket, Self::BUCKETS_PAGE_SIZE)
+                    .await;
+                for bucket in buckets {
+                    if bucket > to.get_bucket() {
+                        break 'shelves;
+                    }
+                    let mut bucket_records = Vec::new();
+                    for consumer_id in &consumer_ids {
+                        let (delivery_records, more_records) = self
+                            .dbp
+                            .consumer_delivery_facade()
+                            .delivery_records_by_bucket(
+                                topic_id,
+                                consumer_id,
+                                bucket,
+                                Self::MAX_RECORDS_PER_BUCKET,
+                            )
+                            .await;
+                        truncated |= more_records;
+                        bucket_records.extend(
+                            delivery_records
+                                .iter()
+                                .filter(|delivery_record| {
+                                    delivery_record.get_unique_time() >= from
+                                        && delivery_record.get_unique_time() <= to
+                                        && event_id.is_none_or(|event_id| {
+                                            event_id == delivery_record.get_event_id()
+                                        })
+                                })
+                                .map(|delivery_record| {
+                                    ConsumptionRecord::new(
+                                        consumer_id,
+                                        ClientIdentity::claims_from_identity_string(consumer_id),
+                                        delivery_record.get_event_id(),
+                                        delivery_record.get_unique_time().as_encoded(),
+                                        delivery_record.get_intent_ts_micros(),
+                                        delivery_record.is_done(),
+                                        delivery_record.get_retry_count(),
+                                    )
+                                }),
+                        );
+                    }
+                    bucket_records.sort_by(|a, b| {
+                        (
+                            a.get_unique_time(),
+                            a.get_consumer_id(),
+                            a.get_delivery_ts_micros(),
+                        )
+                            .cmp(&(
+                                b.get_unique_time(),
+                                b.get_consumer_id(),
+                                b.get_delivery_ts_micros(),
+                            ))
+                    });
+                    records.append(&mut bucket_records);
+                    if records.len() >= Self::MAX_RECORDS {
+                        records.truncate(Self::MAX_RECORDS);
+                        truncated = true;
+                        break 'shelves;
+                    }
+                    current_bucket = Some(bucket);
+                }
+                if !more {
+                    break;
+                }
+            }
+            current_bucket = None;
+        }
+        let audit = ConsumptionAudit::new(
+            topic_id,
+            event_id,
+            from_ts_micros,
+            to_ts_micros,
+            fragtale_client::time::get_timestamp_micros(),
+            records,
+            truncated,
+        );
+        self.sign(audit)
+    }
+
+    /// Return a copy of the report signed with the configured secrets.
+    fn sign(&self, audit: ConsumptionAudit) -> Result<ConsumptionAudit, MessageBrokerError> {
+        let ip = IntegrityProtection::protect(
+            &Self::hash(&audit.as_signed_string()),
+            self.ish.get_current_oid(),
+            self.ish.get_current_secret(),
+            self.ish.get_previous_oid(),
+            self.ish.get_previous_secret(),
+        )
+        .map_err(|e| {
+            MessageBrokerErrorKind::IntegrityProtectionError
+                .error_with_msg(format!("Failed to sign consumption audit: {e}"))
+        })?;
+        Ok(audit.with_signature(ip.as_string()))
+    }
+
+    /// Return `true` if the report is unmodified since it was signed with
+    /// the current or the previous secret.
+    pub fn verify(&self, audit: &ConsumptionAudit) -> bool {
+        let Some(ip) = audit
+            .get_signature()
+            .and_then(|signature| IntegrityProtection::from_string(signature).ok())
+        else {
+            return false;
+        };
+        if !Self::hash(&audit.as_signed_string()).eq(ip.get_protected_hash()) {
+            return false;
+        }
+        // The secrets might have been rotated since the report was signed
+        [
+            (self.ish.get_current_oid(), self.ish.get_current_secret()),
+            (self.ish.get_previous_oid(), self.ish.get_previous_secret()),
+        ]
+        .iter()
+        .filter(|(oid, secret)| !oid.is_empty() && !secret.is_empty())
+        .any(|(oid, secret)| {
+            ip.validate_current(oid, secret).is_ok() || ip.validate_previous(oid, secret).is_ok()
+        })
+    }
+
+    /// Return the SHA3-512 digest of the serialized report.
+    fn hash(signed_string: &str) -> Vec<u8> {
+        Tyst::instance()
+            .digests()
+            .by_oid(&tyst::encdec::oid::as_string(tyst::oids::digest::SHA3_512))
+            .unwrap()
+            .hash(signed_string.as_bytes())
+    }
+}
@@ -17,8 +17,11 @@
 
 //! Quickly respond to correlation requests when a matching event is seen.
 
+use super::task_supervisor::TaskHeartbeat;
+use super::task_supervisor::TaskSupervisor;
 use crate::conf::AppConfig;
 use crate::util::LogScopeDuration;
+use crate::util::TimerWheel;
 use crossbeam_skiplist::SkipMap;
 use fragtale_client::mb::correlation_token::CorrelationToken;
 use fragtale_dbp::dbp::DatabaseProvider;
@@ -62,65 +65,99 @@ pub struct CorrelationHotlist {
     dbp: Arc<DatabaseProvider>,
     /// topic_id, correlation_id, data
     hotlist: SkipMap<String, SkipMap<String, HotlistEntry>>,
+    /// topic_id, correlation_id and request_ts by expiry of hotlist entries
+    expiry_wheel: TimerWheel<(String, String, u64)>,
     correlation_oid: Vec<u32>,
+    /// Master secret that per topic correlation secrets are derived from.
     correlation_secret: Vec<u8>,
 }
 impl CorrelationHotlist {
     const HOTLIST_DURATION_MICROS: u64 = 5_000_000 * 2;
+    /// Resolution of hotlist entry expiry.
+    const EXPIRY_TICK_MICROS: u64 = 250_000;
+    /// Max time between heartbeats of background tasks.
+    const MAX_SILENCE_MICROS: u64 = 300_000_000;
 
     /// Return a new instance.
-    pub async fn new(app_config: &Arc<AppConfig>, dbp: &Arc<DatabaseProvider>) -> Arc<Self> {
+    pub async fn new(
+        app_config: &Arc<AppConfig>,
+        dbp: &Arc<DatabaseProvider>,
+        task_supervisor: &Arc<TaskSupervisor>,
+    ) -> Arc<Self> {
         let (correlation_oid, correlation_secret) = app_config.integrity.correlation_secret();
         Arc::new(Self {
             dbp: Arc::clone(dbp),
             hotlist: SkipMap::new(),
+            expiry_wheel: TimerWheel::new(Self::EXPIRY_TICK_MICROS),
             correlation_oid,
             correlation_secret,
         })
-        .initialize()
+        .initialize(task_supervisor)
         .await
     }
 
-    async fn initialize(self: Arc<Self>) -> Arc<Self> {
+    async fn initialize(self: Arc<Self>, task_supervisor: &Arc<TaskSupervisor>) -> Arc<Self> {
         let self_clone = Arc::clone(&self);
-        tokio::spawn(async move { self_clone.wake_up_too_old().await });
+        task_supervisor.spawn(
+            "correlation_hotlist",
+            "correlation_hotlist_wake_up_too_old".to_owned(),
+            Self::MAX_SILENCE_MICROS,
+            move |heartbeat| {
+                let self_clone = Arc::clone(&self_clone);
+                async move { self_clone.wake_up_too_old(&heartbeat).await }
+            },
+        );
         let self_clone = Arc::clone(&self);
-        tokio::spawn(async move { self_clone.track_new_events().await });
+        task_supervisor.spawn(
+            "correlation_hotlist",
+            "correlation_hotlist_track_new_events".to_owned(),
+            Self::MAX_SILENCE_MICROS,
+            move |heartbeat| {
+                let self_clone = Arc::clone(&self_clone);
+                async move { self_clone.track_new_events(&heartbeat).await }
+            },
+        );
         self
     }
 
     /// Remove items from hotlist if they are too old
-    async fn wake_up_too_old(&self) {
+    ///
+    /// Only entries whose expiry has passed are visited, so this is cheap even
+    /// when there are many pending correlation requests.
+    async fn wake_up_too_old(&self, heartbeat: &TaskHeartbeat) {
         loop {
-            sleep(Duration::from_millis(1000)).await;
-            let mut count = 0u64;
+            heartbeat.beat();
+            sleep(Duration::from_micros(Self::EXPIRY_TICK_MICROS)).await;
             let now = fragtale_client::time::get_timestamp_micros();
-            self.hotlist.iter().for_each(|per_topic_entry| {
+            for (topic_id, correlation_token, request_ts) in self.expiry_wheel.expire(now) {
+                let Some(per_topic_entry) = self.hotlist.get(&topic_id) else {
+                    continue;
+                };
                 let per_topic_map = per_topic_entry.value();
-                per_topic_map.iter().for_each(|entry| {
-                    count += 1;
-                    let hotlist_entry = entry.value();
-                    if hotlist_entry.request_ts + Self::HOTLIST_DURATION_MICROS < now
-                        && let Some(entry) = per_topic_map.remove(entry.key())
-                    {
-                        entry.value().semaphore.add_permits(1);
-                        log::info!("Unlocked hotlist entry '{}' due to timeout.", entry.key());
-                    }
-                });
-            });
-            if !self.hotlist.is_empty() && count > 0 && log::log_enabled!(log::Level::Trace) {
+                // The entry might have been notified or replaced by a newer request
+                if per_topic_map
+                    .get(&correlation_token)
+                    .is_some_and(|entry| entry.value().request_ts == request_ts)
+                    && let Some(entry) = per_topic_map.remove(&correlation_token)
+                {
+                    entry.value().semaphore.add_permits(1);
+                    log::info!("Unlocked hotlist entry '{}' due to timeout.", entry.key());
+                }
+            }
+            if !self.expiry_wheel.is_empty() && log::log_enabled!(log::Level::Trace) {
                 log::trace!(
-                    "Topics in hotlist: {}. Total number of items: {}",
+                    "Topics in hotlist: {}. Total number of pending expiries: {}",
                     self.hotlist.len(),
-                    count
+                    self.expiry_wheel.len()
                 );
             }
         }
     }
 
     /// Watch for new events and trigger hot-list items when found
-    async fn track_new_events(self: &Arc<Self>) {
+    async fn track_new_events(self: &Arc<Self>, heartbeat: &TaskHeartbeat) {
         loop {
+            heartbeat.beat();
             let mut any_changes = false;
             let mut any_waiters = false;
             for per_topic_entry in self.hotlist.iter() {
@@ -191,6 +228,14 @@ impl CorrelationHotlist {
                 correlation_token_str.to_owned(),
                 HotlistEntry::new(request_ts),
             );
+            self.expiry_wheel.schedule(
+                request_ts + Self::HOTLIST_DURATION_MICROS,
+                (
+                    topic_id.to_owned(),
+                    correlation_token_str.to_owned(),
+                    request_ts,
+                ),
+            );
             if log::log_enabled!(log::Level::Trace) {
                 log::trace!("Inserted hotlist for correlation token {correlation_token_str}");
             }
@@ -240,23 +285,42 @@ impl CorrelationHotlist {
         ret
     }
 
-    /// Create a new CorrelationToken if none exists or can't be validated.
+    /// Create a new CorrelationToken bound to `topic_id` if none exists or
+    /// can't be validated.
+    ///
+    /// An existing token stays bound to the topic where it was issued.
+    ///
+    /// A new token will carry the requested `priority`, while the priority of
+    /// the original request is returned together with an existing valid
+    /// token.
     pub fn validate_or_protect(
         &self,
+        topic_id: &str,
         correlation_token_opt: Option<String>,
         event_ts: u64,
-    ) -> String {
+        priority: Option<u8>,
+    ) -> (String, Option<u8>) {
         correlation_token_opt
             .and_then(|value| self.parse_and_validate(value.as_str()))
+            .map(|correlation_token| {
+                let request_priority = correlation_token.get_priority();
+                (correlation_token.as_string(), request_priority)
+            })
             .unwrap_or_else(|| {
                 // Generate a new token if none was provided
-                CorrelationToken::new(&self.correlation_oid, &self.correlation_secret, event_ts)
+                let correlation_token = CorrelationToken::new_with_priority(
+                    &self.correlation_oid,
+                    &self.correlation_secret,
+                    topic_id,
+                    event_ts,
+                    priority,
+                );
+                (correlation_token.as_string(), None)
             })
-            .as_string()
     }
 
     /// Return `Some(CorrelationToken)` if the string could be parsed and the
-    /// token is valid.
+    /// token is valid for the topic where it was issued.
     fn parse_and_validate(&self, correlation_token: &str) -> Option<CorrelationToken> {
         CorrelationToken::from_string(correlation_token)
             .map_err(|e| {
@@ -0,0 +1,129 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Listing of events in dead-letter topics.
+
+use fragtale_client::mb::dead_letters::DeadLetter;
+use fragtale_client::mb::dead_letters::DeadLetteredEvent;
+use fragtale_client::mb::dead_letters::DeadLetters;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+use futures::StreamExt;
+use std::sync::Arc;
+
+/// Listing of events in dead-letter topics.
+///
+/// A dead-letter topic is an ordinary topic, so the listing walks its buckets
+/// in order of publication.
+pub struct DeadLetterReader {
+    dbp: Arc<DatabaseProvider>,
+}
+
+impl DeadLetterReader {
+    /// Number of buckets requested from the database at a time.
+    const BUCKETS_PAGE_SIZE: usize = 32;
+    /// Max number of dead-lettered events returned in a single listing.
+    const DEAD_LETTERS_PAGE_SIZE: usize = 100;
+
+    /// Return a new instance.
+    pub fn new(dbp: &Arc<DatabaseProvider>) -> Arc<Self> {
+        Arc::new(Self {
+            dbp: Arc::clone(dbp),
+        })
+    }
+
+    /// Get a page of the events in the dead-letter topic published after the
+    /// encoded [UniqueTime] `from` (exclusive).
+    ///
+    /// Events that are not a [DeadLetter] are skipped.
+    pub async fn list(&self, dead_letter_topic_id: &str, from: Option<UniqueTime>) -> DeadLetters {
+        let mut dead_lettered_events = Vec::new();
+        if !self
+            .dbp
+            .topic_facade()
+            .topic_exists(dead_letter_topic_id)
+            .await
+        {
+            return DeadLetters::new(dead_lettered_events, false);
+        }
+        let now_shelf = UniqueTime::from(UniqueTime::min_encoded_for_micros(
+            fragtale_client::time::get_timestamp_micros(),
+        ))
+        .get_shelf();
+        let first_shelf = from.as_ref().map(UniqueTime::get_shelf).unwrap_or(0);
+        // Buckets are listed after (exclusive) the current bucket
+        let mut current_bucket = from.and_then(|from| from.get_bucket().checked_sub(1));
+        for shelf in first_shelf..=now_shelf {
+            loop {
+                let (buckets, more) = self
+                    .dbp
+                    .event_facade()
+                    .buckets_by_shelf(
+                        dead_letter_topic_id,
+                        shelf,
+                        current_bucket,
+                        Self::BUCKETS_PAGE_SIZE,
+                    )
+                    .await;
+                for bucket in buckets {
+                    let mut entries = self.dbp.event_facade().events_by_bucket_stream(
+                        dead_letter_topic_id,
+                        bucket,
+                        from,
+                        Self::DEAD_LETTERS_PAGE_SIZE,
+                    );
+                    while let Some((unique_time, event_id, _descriptor_version)) =
+                        entries.next().await
+                    {
+                        if dead_lettered_events.len() == Self::DEAD_LETTERS_PAGE_SIZE {
+                            return DeadLetters::new(dead_lettered_events, true);
+                        }
+                        let Some(dead_letter) = self
+                            .dbp
+                            .event_facade()
+                            .event_by_id_and_unique_time(
+                                dead_letter_topic_id,
+                                &event_id,
+                                unique_time,
+                            )
+                            .await
+                            .and_then(|event_delivery_gist| {
+                                serde_json::from_str::<DeadLetter>(
+                                    event_delivery_gist.get_document(),
+                                )
+                                .ok()
+                            })
+                        else {
+                            continue;
+                        };
+                        dead_lettered_events.push(DeadLetteredEvent::new(
+                            &event_id,
+                            unique_time.as_encoded(),
+                            dead_letter,
+                        ));
+                    }
+                    current_bucket = Some(bucket);
+                }
+                if !more {
+                    break;
+                }
+            }
+            current_bucket = None;
+        }
+        DeadLetters::new(dead_lettered_events, false)
+    }
+}
@@ -0,0 +1,53 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Workloads performed by an instance in a deployment.
+
+/// Workloads performed by an instance in a deployment.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DeploymentMode {
+    /// Serve the API and consumers and take part in background work.
+    #[default]
+    Combined,
+    /// Serve the API and consumers only.
+    Broker,
+    /// Perform background work only.
+    Worker,
+}
+
+impl DeploymentMode {
+    /// Return the mode with the `name` if supported.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "combined" => Some(Self::Combined),
+            "broker" => Some(Self::Broker),
+            "worker" => Some(Self::Worker),
+            _ => None,
+        }
+    }
+
+    /// Return `true` if the instance serves the API and consumers.
+    pub fn serves_api(&self) -> bool {
+        *self != Self::Worker
+    }
+
+    /// Return `true` if the instance may be elected to perform background
+    /// work.
+    pub fn runs_background_work(&self) -> bool {
+        *self != Self::Broker
+    }
+}
@@ -0,0 +1,133 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Topics, event descriptors and events to seed a broker with in developer
+//! mode.
+
+use fragtale_client::mb::event_descriptor::EventDescriptor;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use serde::Deserialize;
+use serde_json::Value;
+
+/** Topics, event descriptors and events to seed a broker with in developer
+mode.
+
+Example fixtures file:
+
+```json
+{
+  "topics": [
+    {
+      "topic_id": "orders",
+      "descriptor": { "version": 1 },
+      "events": [ { "order_id": 1 }, { "order_id": 2 } ]
+    }
+  ]
+}
+```
+*/
+#[derive(Debug, Deserialize)]
+pub struct DevFixtures {
+    topics: Vec<TopicFixture>,
+}
+
+/// A topic to seed with an optional event descriptor and events.
+#[derive(Debug, Deserialize)]
+pub struct TopicFixture {
+    topic_id: String,
+    #[serde(default)]
+    descriptor: Option<EventDescriptor>,
+    #[serde(default)]
+    events: Vec<Value>,
+}
+
+impl DevFixtures {
+    /// Return the fixtures read from the JSON file at `path`.
+    pub fn from_file(path: &str) -> Result<Self, MessageBrokerError> {
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            MessageBrokerErrorKind::MalformedRequest
+                .error_with_msg(format!("Unable to read fixtures file '{path}': {e}"))
+        })?;
+        Self::from_json(&json).map_err(|e| {
+            MessageBrokerErrorKind::MalformedRequest
+                .error_with_msg(format!("Malformed fixtures file '{path}': {e}"))
+        })
+    }
+
+    /// Return the fixtures parsed from `json`.
+    fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Topics to seed in order of appearance.
+    pub fn get_topics(&self) -> &[TopicFixture] {
+        &self.topics
+    }
+}
+
+impl TopicFixture {
+    /// Topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Event descriptor to register for the topic (if any).
+    pub fn get_descriptor(&self) -> &Option<EventDescriptor> {
+        &self.descriptor
+    }
+
+    /// Serialized event documents to publish to the topic in order of
+    /// appearance.
+    pub fn get_event_documents(&self) -> Vec<String> {
+        self.events.iter().map(Value::to_string).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_topics_with_and_without_descriptor() {
+        let fixtures = DevFixtures::from_json(
+            r#"{"topics":[{"topic_id":"a","events":[{"x":1},{"x":2}]},{"topic_id":"b","descriptor":{"version":1}}]}"#,
+        )
+        .unwrap();
+        let topics = fixtures.get_topics();
+        assert_eq!(topics.len(), 2);
+        assert_eq!(topics[0].get_topic_id(), "a");
+        assert!(topics[0].get_descriptor().is_none());
+        assert_eq!(
+            topics[0].get_event_documents(),
+            [r#"{"x":1}"#, r#"{"x":2}"#]
+        );
+        assert_eq!(
+            topics[1]
+                .get_descriptor()
+                .as_ref()
+                .map(EventDescriptor::get_version),
+            Some(1)
+        );
+        assert!(topics[1].get_event_documents().is_empty());
+    }
+
+    #[test]
+    fn reject_fixtures_without_topics() {
+        assert!(DevFixtures::from_json("{}").is_err());
+    }
+}
@@ -0,0 +1,92 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Canonicalization of event documents before they are processed.
+
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use std::borrow::Cow;
+
+/// Canonicalization of event documents before the event identifier is derived
+/// and integrity protection is applied.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentCanonicalization {
+    /// Keep documents exactly as published.
+    #[default]
+    None,
+    /// JSON Canonicalization Scheme (RFC 8785).
+    Jcs,
+}
+
+impl DocumentCanonicalization {
+    /// Names of all supported canonicalizations.
+    pub const NAMES: [&str; 2] = ["none", "JCS"];
+
+    /// Return the canonicalization with the `name` if supported.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "NONE" => Some(Self::None),
+            "JCS" => Some(Self::Jcs),
+            _ => None,
+        }
+    }
+
+    /// Return the document in canonical form.
+    pub fn canonicalize<'a>(
+        &self,
+        topic_id: &str,
+        document: &'a str,
+    ) -> Result<Cow<'a, str>, MessageBrokerError> {
+        match self {
+            Self::None => Ok(Cow::Borrowed(document)),
+            Self::Jcs => serde_json::from_str::<serde_json::Value>(document)
+                .ok()
+                .and_then(|value| serde_jcs::to_string(&value).ok())
+                .map(Cow::Owned)
+                .ok_or_else(|| {
+                    MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                        "Unable to canonicalize event document published to '{topic_id}' since it is not valid JSON."
+                    ))
+                }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logical_duplicates_share_canonical_form() {
+        let jcs = DocumentCanonicalization::from_name("jcs").unwrap();
+        let a = jcs
+            .canonicalize(
+                "t",
+                "{ \"b\": [1, 2.50], \"a\": {\"y\": 1e2, \"x\": \"\\u0041\"} }",
+            )
+            .unwrap();
+        let b = jcs
+            .canonicalize("t", "{\"a\":{\"x\":\"A\",\"y\":100},\"b\":[1,2.5]}")
+            .unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a, "{\"a\":{\"x\":\"A\",\"y\":100},\"b\":[1,2.5]}");
+        assert!(jcs.canonicalize("t", "not json").is_err());
+        let none = DocumentCanonicalization::default();
+        assert_eq!(none.canonicalize("t", "{ }").unwrap(), "{ }");
+        assert!(DocumentCanonicalization::from_name("C14N").is_none());
+    }
+}
@@ -0,0 +1,158 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Masking of event documents delivered to lower-privileged consumers.
+
+use fragtale_client::mb::event_descriptor::MaskingAction;
+use fragtale_client::mb::event_descriptor::MaskingRule;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use serde_json::Value;
+use tyst::Tyst;
+
+/// Masking of event documents by redacting or hashing the fields selected by
+/// JSON Pointers (RFC 6901).
+///
+/// Masking is only applied to the delivered copy of a document after
+/// integrity validation, so stored events are never modified.
+pub struct DocumentMasking;
+
+impl DocumentMasking {
+    /// Max number of masking rules of a single event descriptor.
+    pub const MAX_MASKING_RULES: usize = 64;
+
+    /// Error out if the masking rules can't be applied to documents.
+    pub fn assert_valid_masking_rules(
+        masking_rules: &[MaskingRule],
+    ) -> Result<(), MessageBrokerError> {
+        if masking_rules.len() > Self::MAX_MASKING_RULES {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "At most {} masking rules are allowed.",
+                    Self::MAX_MASKING_RULES
+                )),
+            )?;
+        }
+        if let Some(masking_rule) = masking_rules.iter().find(|masking_rule| {
+            masking_rule.get_path().len() < 2 || !masking_rule.get_path().starts_with('/')
+        }) {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Masking rule path '{}' is not a JSON Pointer to a field.",
+                    masking_rule.get_path()
+                )),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Return the masked document or `None` if the document is not a JSON
+    /// object or array.
+    ///
+    /// Redacted array elements are replaced by `null` to keep the position of
+    /// the remaining elements.
+    pub fn mask(document: &str, masking_rules: &[MaskingRule]) -> Option<String> {
+        let mut value = serde_json::from_str::<Value>(document)
+            .ok()
+            .filter(|value| value.is_object() || value.is_array())?;
+        for masking_rule in masking_rules {
+            match masking_rule.get_action() {
+                MaskingAction::Redact => Self::redact(&mut value, masking_rule.get_path()),
+                MaskingAction::Hash => {
+                    if let Some(field) = value.pointer_mut(masking_rule.get_path()) {
+                        *field = Value::String(Self::hash(field));
+                    }
+                }
+            }
+        }
+        serde_json::to_string(&value).ok()
+    }
+
+    /// Remove the value at the location of the `pointer` (if present).
+    fn redact(value: &mut Value, pointer: &str) {
+        let Some((parent_pointer, last)) = pointer.rsplit_once('/') else {
+            return;
+        };
+        let last = last.replace("~1", "/").replace("~0", "~");
+        match value.pointer_mut(parent_pointer) {
+            Some(Value::Object(object)) => {
+                object.remove(&last);
+            }
+            Some(Value::Array(array)) => {
+                if let Some(element) = last
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|index| array.get_mut(index))
+                {
+                    *element = Value::Null;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Return the hex encoded SHA3-256 digest of the JSON serialized value.
+    fn hash(value: &Value) -> String {
+        tyst::encdec::hex::encode(
+            &Tyst::instance()
+                .digests()
+                .by_oid(&tyst::encdec::oid::as_string(tyst::oids::digest::SHA3_256))
+                .unwrap()
+                .hash(value.to_string().as_bytes()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masking_redacts_and_hashes_selected_fields() {
+        let masking_rules = vec![
+            MaskingRule::new("/customer/email", MaskingAction::Redact),
+            MaskingRule::new("/customer/id", MaskingAction::Hash),
+            MaskingRule::new("/cards/0", MaskingAction::Redact),
+            MaskingRule::new("/missing/field", MaskingAction::Hash),
+        ];
+        let document =
+            r#"{"amount":7,"customer":{"id":"c-1","email":"e@example.com"},"cards":["1234",5]}"#;
+        let masked: Value =
+            serde_json::from_str(&DocumentMasking::mask(document, &masking_rules).unwrap())
+                .unwrap();
+        assert_eq!(masked.pointer("/amount"), Some(&Value::from(7)));
+        assert!(masked.pointer("/customer/email").is_none());
+        let hashed = masked
+            .pointer("/customer/id")
+            .and_then(Value::as_str)
+            .unwrap();
+        assert_eq!(hashed.len(), 64);
+        assert_eq!(hashed, DocumentMasking::hash(&Value::from("c-1")));
+        assert_eq!(
+            masked.pointer("/cards"),
+            Some(&serde_json::json!([null, 5]))
+        );
+        assert!(DocumentMasking::mask("not json", &masking_rules).is_none());
+        assert!(
+            DocumentMasking::assert_valid_masking_rules(&[MaskingRule::new(
+                "id",
+                MaskingAction::Hash
+            )])
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,146 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Migration of older event documents to a newer event descriptor version.
+
+use fragtale_client::mb::event_descriptor::DefaultValue;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use serde_json::Map;
+use serde_json::Value;
+
+/// Migration of older event documents to a newer event descriptor version by
+/// adding default values of absent fields.
+pub struct DocumentMigration;
+
+impl DocumentMigration {
+    /// Max number of default values of a single event descriptor.
+    pub const MAX_DEFAULT_VALUES: usize = 64;
+
+    /// Error out if the default values can't be applied to documents.
+    pub fn assert_valid_default_values(
+        default_values: &[DefaultValue],
+    ) -> Result<(), MessageBrokerError> {
+        if default_values.len() > Self::MAX_DEFAULT_VALUES {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "At most {} default values are allowed.",
+                    Self::MAX_DEFAULT_VALUES
+                )),
+            )?;
+        }
+        if let Some(default_value) = default_values.iter().find(|default_value| {
+            default_value.get_path().len() < 2 || !default_value.get_path().starts_with('/')
+        }) {
+            Err(
+                MessageBrokerErrorKind::EvenDescriptorError.error_with_msg(format!(
+                    "Default value path '{}' is not a JSON Pointer to a field.",
+                    default_value.get_path()
+                )),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Return the document with the default value of each absent field or
+    /// `None` if the document is not a JSON object or nothing was added.
+    ///
+    /// Fields where a parent location holds something else than an object
+    /// are left out.
+    pub fn apply_default_values(document: &str, default_values: &[DefaultValue]) -> Option<String> {
+        let mut value = serde_json::from_str::<Value>(document).ok()?;
+        let target = value.as_object_mut()?;
+        let mut added = false;
+        for default_value in default_values {
+            added |=
+                Self::insert_if_absent(target, default_value.get_path(), default_value.get_value());
+        }
+        if !added {
+            return None;
+        }
+        serde_json::to_string(&value).ok()
+    }
+
+    /// Insert `value` at the location of the `pointer` in `target` unless
+    /// present and create any missing parent objects.
+    ///
+    /// Return `true` if the value was inserted.
+    fn insert_if_absent(target: &mut Map<String, Value>, pointer: &str, value: &Value) -> bool {
+        let tokens = pointer
+            .split('/')
+            .skip(1)
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect::<Vec<_>>();
+        let Some((last, parents)) = tokens.split_last() else {
+            return false;
+        };
+        let mut current = target;
+        for token in parents {
+            let entry = current
+                .entry(token.to_owned())
+                .or_insert_with(|| Value::Object(Map::new()));
+            let Some(object) = entry.as_object_mut() else {
+                return false;
+            };
+            current = object;
+        }
+        if current.contains_key(last) {
+            return false;
+        }
+        current.insert(last.to_owned(), value.to_owned());
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_only_absent_fields() {
+        let default_values = vec![
+            DefaultValue::new("/currency", Value::from("EUR")),
+            DefaultValue::new("/amount", Value::from(0)),
+            DefaultValue::new("/customer/tier", Value::from("basic")),
+            DefaultValue::new("/id/nested", Value::from(true)),
+        ];
+        assert_eq!(
+            DocumentMigration::apply_default_values(
+                r#"{"id":7,"amount":5,"customer":{"name":"n"}}"#,
+                &default_values
+            )
+            .unwrap(),
+            r#"{"amount":5,"currency":"EUR","customer":{"name":"n","tier":"basic"},"id":7}"#
+        );
+        assert!(
+            DocumentMigration::apply_default_values(
+                r#"{"id":7,"amount":5,"currency":"SEK","customer":{"tier":"gold"}}"#,
+                &default_values
+            )
+            .is_none()
+        );
+        assert!(DocumentMigration::apply_default_values("[1]", &default_values).is_none());
+        assert!(DocumentMigration::assert_valid_default_values(&default_values).is_ok());
+        assert!(
+            DocumentMigration::assert_valid_default_values(&[DefaultValue::new(
+                "currency",
+                Value::Null
+            )])
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,127 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Projection of event documents to the fields of interest to a consumer.
+
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use serde_json::Map;
+use serde_json::Value;
+
+/// Projection of event documents to a subset of fields selected by JSON
+/// Pointers (RFC 6901).
+///
+/// Selected values are placed at the same location in a new JSON object and
+/// pointers that don't resolve in a document are ignored. Locations in arrays
+/// are represented by objects with the array index as key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentProjection {
+    pointers: Vec<String>,
+}
+
+impl DocumentProjection {
+    /// Max number of JSON Pointers in a single projection.
+    pub const MAX_POINTERS: usize = 64;
+
+    /// Return a new instance or an error if any of the `pointers` is not a
+    /// valid JSON Pointer.
+    pub fn new(pointers: &[String]) -> Result<Self, MessageBrokerError> {
+        if pointers.is_empty() || pointers.len() > Self::MAX_POINTERS {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "A projection must have between 1 and {} fields.",
+                    Self::MAX_POINTERS
+                )),
+            )?;
+        }
+        if let Some(pointer) = pointers.iter().find(|pointer| !pointer.starts_with('/')) {
+            Err(
+                MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                    "Projection field '{pointer}' is not a JSON Pointer starting with '/'."
+                )),
+            )?;
+        }
+        Ok(Self {
+            pointers: pointers.to_vec(),
+        })
+    }
+
+    /// Return the projected document or `None` if the document is not a JSON
+    /// object or array.
+    pub fn project(&self, document: &str) -> Option<String> {
+        let source = serde_json::from_str::<Value>(document)
+            .ok()
+            .filter(|value| value.is_object() || value.is_array())?;
+        let mut projected = Map::new();
+        for pointer in &self.pointers {
+            if let Some(value) = source.pointer(pointer) {
+                Self::insert_at(&mut projected, pointer, value.to_owned());
+            }
+        }
+        serde_json::to_string(&projected).ok()
+    }
+
+    /// Insert `value` at the location of the `pointer` in `target` and create
+    /// any missing parent objects.
+    fn insert_at(target: &mut Map<String, Value>, pointer: &str, value: Value) {
+        let tokens = pointer
+            .split('/')
+            .skip(1)
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect::<Vec<_>>();
+        let Some((last, parents)) = tokens.split_last() else {
+            return;
+        };
+        let mut current = target;
+        for token in parents {
+            let entry = current
+                .entry(token.to_owned())
+                .or_insert_with(|| Value::Object(Map::new()));
+            if !entry.is_object() {
+                // An ancestor has already been selected as a whole
+                return;
+            }
+            current = entry.as_object_mut().unwrap();
+        }
+        current.insert(last.to_owned(), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projection_keeps_only_selected_fields() {
+        let projection = DocumentProjection::new(&[
+            "/id".to_owned(),
+            "/customer/name".to_owned(),
+            "/items/1".to_owned(),
+            "/a~1b".to_owned(),
+            "/missing/field".to_owned(),
+        ])
+        .unwrap();
+        let document = r#"{"id":7,"customer":{"name":"n","address":"x"},"items":[1,2],"a/b":true,"blob":"large"}"#;
+        assert_eq!(
+            projection.project(document).unwrap(),
+            r#"{"a/b":true,"customer":{"name":"n"},"id":7,"items":{"1":2}}"#
+        );
+        assert!(projection.project("not json").is_none());
+        assert!(DocumentProjection::new(&["id".to_owned()]).is_err());
+        assert!(DocumentProjection::new(&[]).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Detection of recently published duplicate events.
+
+use crossbeam_skiplist::SkipMap;
+use fragtale_client::mb::duplicate_events::DuplicatePublisher;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// Duplicate detection state of a single topic.
+#[derive(Default)]
+struct TopicDuplicates {
+    /// Epoch microseconds of the first publish by recently seen event
+    /// identifier.
+    seen: SkipMap<String, u64>,
+    /// Epoch microseconds of the latest removal of expired identifiers.
+    pruned_micros: AtomicU64,
+    /// Number of duplicates since the instance started.
+    total: AtomicU64,
+    /// Epoch microseconds and publisher of recent duplicates, oldest first.
+    recent: Mutex<VecDeque<(u64, String)>>,
+}
+
+/** Detection of recently published duplicate events.
+
+An event is a duplicate when an event with the same event identifier was
+published to the same topic on this instance within the detection window.
+This doesn't change how the event is handled, but makes producer retry storms
+visible and traceable to the publishing identities.
+
+Memory use is bounded by only tracking up to a fixed number of event
+identifiers and recent duplicates per topic.
+*/
+pub struct DuplicateTracker {
+    window_micros: u64,
+    topics: SkipMap<String, Arc<TopicDuplicates>>,
+}
+
+impl DuplicateTracker {
+    /// Max number of event identifiers tracked per topic.
+    const MAX_TRACKED_EVENT_IDS: usize = 100_000;
+    /// Max number of recent duplicates kept per topic for reporting.
+    const MAX_RECENT_DUPLICATES: usize = 10_000;
+
+    /// Return a new instance detecting duplicates within `window_micros`.
+    pub fn new(window_micros: u64) -> Self {
+        Self {
+            window_micros,
+            topics: SkipMap::default(),
+        }
+    }
+
+    /// Return the duration of the detection window in microseconds.
+    pub fn get_window_micros(&self) -> u64 {
+        self.window_micros
+    }
+
+    /// Track the publish of an event and return `true` if it is a duplicate.
+    pub fn record(&self, topic_id: &str, publisher: &str, event_id: &str, now_micros: u64) -> bool {
+        let topic_duplicates = Arc::clone(
+            self.topics
+                .get_or_insert_with(topic_id.to_owned(), Arc::default)
+                .value(),
+        );
+        self.prune_expired(&topic_duplicates, now_micros);
+        if let Some(entry) = topic_duplicates.seen.get(event_id)
+            && *entry.value() + self.window_micros >= now_micros
+        {
+            topic_duplicates.total.fetch_add(1, Ordering::Relaxed);
+            let mut recent = topic_duplicates.recent.lock().unwrap();
+            if recent.len() >= Self::MAX_RECENT_DUPLICATES {
+                recent.pop_front();
+            }
+            recent.push_back((now_micros, publisher.to_owned()));
+            return true;
+        }
+        if topic_duplicates.seen.len() < Self::MAX_TRACKED_EVENT_IDS {
+            topic_duplicates
+                .seen
+                .insert(event_id.to_owned(), now_micros);
+        }
+        false
+    }
+
+    /// Remove expired event identifiers and duplicates at most a few times
+    /// per window.
+    fn prune_expired(&self, topic_duplicates: &TopicDuplicates, now_micros: u64) {
+        let pruned_micros = topic_duplicates.pruned_micros.load(Ordering::Relaxed);
+        if pruned_micros + self.window_micros / 8 > now_micros
+            || topic_duplicates
+                .pruned_micros
+                .compare_exchange(
+                    pruned_micros,
+                    now_micros,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_err()
+        {
+            return;
+        }
+        let oldest_micros = now_micros.saturating_sub(self.window_micros);
+        topic_duplicates
+            .seen
+            .iter()
+            .filter(|entry| *entry.value() < oldest_micros)
+            .for_each(|entry| {
+                entry.remove();
+            });
+        let mut recent = topic_duplicates.recent.lock().unwrap();
+        while recent
+            .front()
+            .is_some_and(|(ts_micros, _)| *ts_micros < oldest_micros)
+        {
+            recent.pop_front();
+        }
+    }
+
+    /// Return the number of tracked event identifiers, the number of
+    /// duplicates since start, the number of duplicates within the window and
+    /// the `top` publishers of the most duplicates within the window.
+    pub fn get_report(
+        &self,
+        topic_id: &str,
+        top: usize,
+        now_micros: u64,
+    ) -> (u64, u64, u64, Vec<DuplicatePublisher>) {
+        let Some(entry) = self.topics.get(topic_id) else {
+            return (0, 0, 0, Vec::new());
+        };
+        let topic_duplicates = entry.value();
+        let oldest_micros = now_micros.saturating_sub(self.window_micros);
+        let mut window_duplicates = 0;
+        let mut by_publisher = HashMap::<&str, u64>::new();
+        let recent = topic_duplicates.recent.lock().unwrap();
+        for (_, publisher) in recent
+            .iter()
+            .filter(|(ts_micros, _)| *ts_micros >= oldest_micros)
+        {
+            window_duplicates += 1;
+            *by_publisher.entry(publisher).or_default() += 1;
+        }
+        let mut top_publishers = by_publisher.into_iter().collect::<Vec<_>>();
+        top_publishers.sort_by(|(a_publisher, a), (b_publisher, b)| {
+            b.cmp(a).then_with(|| a_publisher.cmp(b_publisher))
+        });
+        let top_publishers = top_publishers
+            .into_iter()
+            .take(top)
+            .map(|(publisher, duplicates)| DuplicatePublisher::new(publisher, duplicates))
+            .collect();
+        (
+            u64::try_from(topic_duplicates.seen.len()).unwrap_or(u64::MAX),
+            topic_duplicates.total.load(Ordering::Relaxed),
+            window_duplicates,
+            top_publishers,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicates_within_window_are_reported_by_publisher() {
+        let tracker = DuplicateTracker::new(1_000);
+        assert!(!tracker.record("topic", "a", "e1", 0));
+        assert!(tracker.record("topic", "a", "e1", 10));
+        assert!(tracker.record("topic", "b", "e1", 20));
+        assert!(tracker.record("topic", "a", "e1", 30));
+        let (tracked, total, window, top) = tracker.get_report("topic", 10, 40);
+        assert_eq!((tracked, total, window), (1, 3, 3));
+        assert_eq!(top[0], DuplicatePublisher::new("a", 2));
+        assert_eq!(top[1], DuplicatePublisher::new("b", 1));
+        // Outside of the window, the event is tracked as new again
+        assert!(!tracker.record("topic", "a", "e1", 2_000));
+        let (_, total, window, top) = tracker.get_report("topic", 10, 2_500);
+        assert_eq!((total, window), (3, 0));
+        assert!(top.is_empty());
+    }
+}
@@ -0,0 +1,1052 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Archive tier of published events on storage shared by all instances.
+
+use crate::conf::AppConfig;
+use crossbeam_skiplist::SkipMap;
+use fragtale_dbp::mb::TopicEvent;
+use fragtale_dbp::mb::UniqueTime;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+
+/// A published event as kept in the archive tier.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ArchivedEvent {
+    event_id: String,
+    unique_time: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_ts: Option<u64>,
+    protection_ref: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    descriptor_version: Option<u64>,
+    document: String,
+    /// Time when this record superseded the earlier records of the event.
+    /// `None` for the record written when the event was published.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    revision: Option<u64>,
+    /// `true` if the event has been purged.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    purged: bool,
+}
+
+impl ArchivedEvent {
+    /// Return a new instance from a protected event.
+    pub fn from_topic_event(topic_event: &TopicEvent) -> Self {
+        Self {
+            event_id: topic_event.get_event_id().to_owned(),
+            unique_time: topic_event.get_unique_time().as_encoded(),
+            expires_ts: topic_event.get_expires_ts(),
+            protection_ref: topic_event.get_protection_ref().to_owned(),
+            descriptor_version: topic_event.get_descriptor_version(),
+            document: topic_event.get_document().to_owned(),
+            revision: None,
+            purged: false,
+        }
+    }
+
+    /// Return a record that supersedes the earlier records of the event.
+    ///
+    /// The record replaces the document and protection reference of the
+    /// event with `replacement` or marks the event as purged when
+    /// `replacement` is `None`.
+    pub fn superseding(
+        event_id: &str,
+        unique_time: UniqueTime,
+        replacement: Option<(&str, &str)>,
+    ) -> Self {
+        let (document, protection_ref) = replacement.unwrap_or_default();
+        Self {
+            event_id: event_id.to_owned(),
+            unique_time: unique_time.as_encoded(),
+            expires_ts: None,
+            protection_ref: protection_ref.to_owned(),
+            descriptor_version: None,
+            document: document.to_owned(),
+            revision: Some(fragtale_client::time::get_timestamp_micros()),
+            purged: replacement.is_none(),
+        }
+    }
+
+    /// Return the event identifier.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Return the [UniqueTime] of the event.
+    pub fn get_unique_time(&self) -> UniqueTime {
+        UniqueTime::from(self.unique_time)
+    }
+
+    /// Return the version of the event descriptor the event was validated
+    /// against.
+    pub fn get_descriptor_version(&self) -> Option<u64> {
+        self.descriptor_version
+    }
+
+    /// Return `true` if the event expired before `now_micros`.
+    pub fn is_expired(&self, now_micros: u64) -> bool {
+        self.expires_ts
+            .is_some_and(|expires_ts| expires_ts < now_micros)
+    }
+
+    /// Deconstruct this struct into the [UniqueTime], document and protection
+    /// reference of the event.
+    pub fn into_parts(self) -> (UniqueTime, Arc<str>, String) {
+        (
+            UniqueTime::from(self.unique_time),
+            Arc::from(self.document),
+            self.protection_ref,
+        )
+    }
+}
+
+/// The part of an [ArchivedEvent] needed to index it.
+#[derive(Deserialize)]
+struct ArchivedEventId {
+    event_id: String,
+}
+
+/// Bloom filter of the event identifiers in a segment of an archive file.
+struct SegmentFilter {
+    /// Offset of the first byte of the segment.
+    start: u64,
+    /// Offset of the first byte after the segment.
+    end: u64,
+    bits: Vec<u64>,
+}
+
+impl SegmentFilter {
+    /// Bits per event, which gives less than 0.1% false positives.
+    const BITS_PER_EVENT: usize = 16;
+    /// Number of bits set per event.
+    const HASHES: u64 = 8;
+
+    /// Return a new instance for the event identifiers of the segment.
+    fn new(start: u64, end: u64, event_ids: &[String]) -> Self {
+        let len = (event_ids.len() * Self::BITS_PER_EVENT)
+            .next_power_of_two()
+            .max(64);
+        let mut bits = vec![0u64; len / 64];
+        for event_id in event_ids {
+            for bit in Self::get_bit_indices(event_id, len) {
+                bits[bit / 64] |= 1 << (bit % 64);
+            }
+        }
+        Self { start, end, bits }
+    }
+
+    /// Return the indices of the bits set for the event identifier.
+    fn get_bit_indices(event_id: &str, len: usize) -> impl Iterator<Item = usize> {
+        let mut hasher = DefaultHasher::new();
+        event_id.hash(&mut hasher);
+        let hash = hasher.finish();
+        let (hash1, hash2) = (hash & 0xffff_ffff, (hash >> 32) | 1);
+        let len = u64::try_from(len).unwrap();
+        (0..Self::HASHES)
+            .map(move |i| usize::try_from(hash1.wrapping_add(i.wrapping_mul(hash2)) % len).unwrap())
+    }
+
+    /// Return `true` if the event identifier might be in the segment.
+    fn might_contain(&self, event_id: &str) -> bool {
+        Self::get_bit_indices(event_id, self.bits.len() * 64)
+            .all(|bit| self.bits[bit / 64] & (1 << (bit % 64)) != 0)
+    }
+}
+
+/// Index of the event identifiers in an archive file.
+#[derive(Default)]
+struct FileIndex {
+    /// Offset of the first byte that has not been indexed yet.
+    indexed_len: u64,
+    segments: Vec<SegmentFilter>,
+}
+
+/// Index of the event identifiers in the archive files of a topic.
+#[derive(Default)]
+struct TopicIndex {
+    refreshed_micros: u64,
+    by_file: HashMap<PathBuf, FileIndex>,
+}
+
+/// Work queued for the writer of the archive files of this instance.
+enum ArchiveCommand {
+    /// Append archived events of a topic.
+    Append {
+        topic_id: String,
+        archived_events: Vec<ArchivedEvent>,
+    },
+    /// Apply superseding records to the files of this instance and signal
+    /// `compacted` when done.
+    Compact {
+        topic_id: String,
+        compacted: Option<oneshot::Sender<()>>,
+    },
+}
+
+/** Archive tier of published events on storage shared by all instances.
+
+When enabled, persisted events are also appended to NDJSON files in a
+directory shared by all instances (typically an object storage bucket mounted
+into the container). The archive keeps a copy of events beyond what the
+database retains, for example after the database has been pruned or restored.
+
+Each instance only ever writes to its own files, named after the period of
+the events and the instance identifier. A period is roughly 72 minutes, so
+the events of a time range can be found in a few files.
+
+Reads by event identifier use an in-memory index with a Bloom filter for each
+segment of an archive file (about two bytes per archived event), so only
+segments that probably hold the event are read from shared storage and
+lookups of unknown event identifiers are answered from memory. The index of a
+topic is built on its first lookup and then extended with appended events at
+most every [EventArchive::INDEX_REFRESH_INTERVAL_MICROS].
+
+Events are queued and written by a single background task, so slow shared
+storage never blocks the threads that serve requests. Publishers only wait
+when the queue is full. Failures to write are logged and never affect
+publishing.
+
+Purged and redacted events are never changed in the files of other
+instances. Instead, the purging instance appends a record that supersedes the
+earlier records of the event and reads apply the latest such record. Each
+instance then compacts its own files, so the purged documents are also
+removed from storage.
+*/
+pub struct EventArchive {
+    directory: Option<PathBuf>,
+    sender: Option<mpsc::Sender<ArchiveCommand>>,
+    index_by_topic: SkipMap<String, Arc<Mutex<TopicIndex>>>,
+}
+
+impl EventArchive {
+    /// Number of low bits of the bucket that are shared by all events of a
+    /// file.
+    const BUCKET_BITS_PER_FILE: u32 = 12;
+    /// Max number of commands waiting for the writer before publishers wait.
+    const QUEUED_COMMANDS_MAX: usize = 1024;
+    /// Max number of queued commands executed at once.
+    const COMMANDS_PER_WRITE: usize = 64;
+    /// Max number of events in an indexed segment of a file.
+    const SEGMENT_EVENTS_MAX: usize = 1024;
+    /// Min time between looking for appended events to index.
+    pub const INDEX_REFRESH_INTERVAL_MICROS: u64 = 5_000_000;
+
+    /// Return a new instance.
+    pub fn new(app_config: &AppConfig, instance_id: u16) -> Arc<Self> {
+        let directory = app_config.archive.directory().map(PathBuf::from);
+        let sender = directory.clone().map(|directory| {
+            let (sender, receiver) = mpsc::channel(Self::QUEUED_COMMANDS_MAX);
+            tokio::spawn(Self::execute_commands(
+                ArchiveWriter::new(directory, instance_id),
+                receiver,
+            ));
+            sender
+        });
+        Arc::new(Self {
+            directory,
+            sender,
+            index_by_topic: SkipMap::default(),
+        })
+    }
+
+    /// Return `true` if published events are archived.
+    pub fn is_enabled(&self) -> bool {
+        self.directory.is_some()
+    }
+
+    /// Return the period of files holding the event.
+    fn get_period(unique_time: UniqueTime) -> u64 {
+        unique_time.get_bucket() >> Self::BUCKET_BITS_PER_FILE
+    }
+
+    /// Return the name of an archive file.
+    ///
+    /// The generation is increased each time the owning instance compacts
+    /// the file, so other instances never mistake a compacted file for the
+    /// one they have indexed.
+    fn get_file_name(period: u64, instance_id: u16, generation: u32) -> String {
+        format!("{period:010}-{instance_id:04}-{generation:04}.ndjson")
+    }
+
+    /// Return the period of the events in the archive file.
+    fn get_period_of_file(path: &Path) -> Option<u64> {
+        path.file_name()?.to_str()?.split_once('-')?.0.parse().ok()
+    }
+
+    /// Return the archive files of the topic.
+    fn get_topic_files(directory: &Path, topic_id: &str) -> Vec<PathBuf> {
+        let Ok(read_dir) = std::fs::read_dir(directory.join(topic_id)) else {
+            return vec![];
+        };
+        read_dir
+            .filter_map(|dir_entry| dir_entry.ok().map(|dir_entry| dir_entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "ndjson")
+            })
+            .collect()
+    }
+
+    /// Return the archive files of the topic by period.
+    fn get_topic_files_by_period(directory: &Path, topic_id: &str) -> BTreeMap<u64, Vec<PathBuf>> {
+        let mut ret = BTreeMap::<u64, Vec<PathBuf>>::new();
+        for path in Self::get_topic_files(directory, topic_id) {
+            if let Some(period) = Self::get_period_of_file(&path) {
+                ret.entry(period).or_default().push(path);
+            }
+        }
+        ret
+    }
+
+    /// Return the parsed events in the byte range `[start, end)` of an
+    /// archive file.
+    fn read_file_range(path: &Path, start: u64, end: u64) -> std::io::Result<Vec<ArchivedEvent>> {
+        let mut file = OpenOptions::new().read(true).open(path)?;
+        file.seek(SeekFrom::Start(start))?;
+        let mut ret = vec![];
+        for line in BufReader::new(file.take(end - start)).lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ArchivedEvent>(&line) {
+                Ok(archived_event) => ret.push(archived_event),
+                Err(e) => {
+                    log::warn!(
+                        "Skipping malformed archived event in '{}': {e}",
+                        path.display()
+                    );
+                }
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Return the parsed events of an archive file.
+    fn read_file(path: &Path) -> std::io::Result<Vec<ArchivedEvent>> {
+        Self::read_file_range(path, 0, u64::MAX)
+    }
+
+    /// Run blocking file access without blocking other async tasks.
+    async fn blocking<T, F>(f: F) -> Option<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        tokio::task::spawn_blocking(f)
+            .await
+            .inspect_err(|e| log::warn!("Archive access failed: {e}"))
+            .ok()
+    }
+
+    /// Return the most recent archived event of the topic with the event
+    /// identifier.
+    pub async fn event_by_id(&self, topic_id: &str, event_id: &str) -> Option<ArchivedEvent> {
+        let directory = self.directory.clone()?;
+        let mut topic_index = Arc::clone(
+            self.index_by_topic
+                .get_or_insert_with(topic_id.to_owned(), Arc::default)
+                .value(),
+        )
+        .lock_owned()
+        .await;
+        let topic_id = topic_id.to_owned();
+        let event_id = event_id.to_owned();
+        Self::blocking(move || {
+            let now_micros = fragtale_client::time::get_timestamp_micros();
+            if topic_index.refreshed_micros + Self::INDEX_REFRESH_INTERVAL_MICROS < now_micros {
+                Self::refresh_index(&directory, &topic_id, &mut topic_index);
+                topic_index.refreshed_micros = now_micros;
+            }
+            let candidates = topic_index
+                .by_file
+                .iter()
+                .flat_map(|(path, file_index)| {
+                    file_index
+                        .segments
+                        .iter()
+                        .filter(|segment| segment.might_contain(&event_id))
+                        .map(|segment| (path.to_owned(), segment.start, segment.end))
+                })
+                .collect::<Vec<_>>();
+            drop(topic_index);
+            candidates
+                .iter()
+                .flat_map(|(path, start, end)| {
+                    Self::read_file_range(path, *start, *end)
+                        .inspect_err(|e| log::warn!("Failed to read '{}': {e}", path.display()))
+                        .unwrap_or_default()
+                })
+                .filter(|archived_event| archived_event.event_id == event_id)
+                .collect::<Vec<_>>()
+        })
+        .await
+        .map(Self::apply_superseding)
+        .and_then(|mut archived_events| archived_events.pop())
+    }
+
+    /// Apply the latest superseding record of each event to the record
+    /// written when it was published and drop purged events.
+    ///
+    /// Return the resulting events in order of [UniqueTime].
+    fn apply_superseding(archived_events: Vec<ArchivedEvent>) -> Vec<ArchivedEvent> {
+        let mut by_unique_time =
+            BTreeMap::<u64, (Option<ArchivedEvent>, Option<ArchivedEvent>)>::new();
+        for archived_event in archived_events {
+            let (original, superseding) = by_unique_time
+                .entry(archived_event.unique_time)
+                .or_default();
+            if archived_event.revision.is_none() {
+                original.get_or_insert(archived_event);
+            } else if superseding
+                .as_ref()
+                .is_none_or(|superseding| superseding.revision < archived_event.revision)
+            {
+                *superseding = Some(archived_event);
+            }
+        }
+        by_unique_time
+            .into_values()
+            .filter_map(|(original, superseding)| match (original, superseding) {
+                (original, None) => original,
+                (_, Some(superseding)) if superseding.purged => None,
+                (Some(original), Some(superseding)) => Some(ArchivedEvent {
+                    document: superseding.document,
+                    protection_ref: superseding.protection_ref,
+                    ..original
+                }),
+                (None, Some(superseding)) => Some(superseding),
+            })
+            .collect()
+    }
+
+    /// Return up to `max_results` archived events of the topic in the range
+    /// `(from, to]` in order of [UniqueTime] and `true` if there might be
+    /// more events in the range.
+    ///
+    /// Files are read one period at a time, starting with the oldest, until
+    /// more than `max_results` events have been found. This bounds the amount
+    /// of data read for each page, regardless of how large the range is.
+    pub async fn events_by_unique_time_range(
+        &self,
+        topic_id: &str,
+        from: UniqueTime,
+        to: UniqueTime,
+        max_results: usize,
+    ) -> (Vec<ArchivedEvent>, bool) {
+        let Some(directory) = self.directory.clone() else {
+            return (vec![], false);
+        };
+        let topic_id = topic_id.to_owned();
+        Self::blocking(move || {
+            let paths_by_period = Self::get_topic_files_by_period(&directory, &topic_id);
+            let mut ret = vec![];
+            for paths in paths_by_period
+                .range(Self::get_period(from)..=Self::get_period(to))
+                .map(|(_period, paths)| paths)
+            {
+                if ret.len() > max_results {
+                    break;
+                }
+                // Events of a period are spread over the files of all instances
+                let archived_events = paths
+                    .iter()
+                    .flat_map(|path| {
+                        Self::read_file(path)
+                            .inspect_err(|e| log::warn!("Failed to read '{}': {e}", path.display()))
+                            .unwrap_or_default()
+                    })
+                    .filter(|archived_event| {
+                        let unique_time = archived_event.get_unique_time();
+                        from < unique_time && unique_time <= to
+                    })
+                    .collect::<Vec<_>>();
+                ret.append(&mut Self::apply_superseding(archived_events));
+            }
+            let more = ret.len() > max_results;
+            ret.truncate(max_results);
+            (ret, more)
+        })
+        .await
+        .unwrap_or_default()
+    }
+
+    /// Index events appended to the archive files of the topic since the
+    /// last refresh.
+    fn refresh_index(directory: &Path, topic_id: &str, topic_index: &mut TopicIndex) {
+        let paths = Self::get_topic_files(directory, topic_id);
+        topic_index
+            .by_file
+            .retain(|path, _file_index| paths.contains(path));
+        for path in paths {
+            let file_index = topic_index.by_file.entry(path.to_owned()).or_default();
+            if let Err(e) = Self::refresh_file_index(&path, file_index) {
+                log::warn!("Failed to index '{}': {e}", path.display());
+            }
+        }
+    }
+
+    /// Index complete lines appended to the archive file since the last
+    /// refresh.
+    fn refresh_file_index(path: &Path, file_index: &mut FileIndex) -> std::io::Result<()> {
+        let mut file = OpenOptions::new().read(true).open(path)?;
+        let len = file.metadata()?.len();
+        if len < file_index.indexed_len {
+            // The file has been replaced, so start over.
+            *file_index = FileIndex::default();
+        }
+        file.seek(SeekFrom::Start(file_index.indexed_len))?;
+        let mut reader = BufReader::new(file.take(len - file_index.indexed_len));
+        let mut start = file_index.indexed_len;
+        let mut end = start;
+        let mut event_ids = Vec::with_capacity(Self::SEGMENT_EVENTS_MAX);
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 || !line.ends_with('\n') {
+                // Leave partially written lines for the next refresh.
+                break;
+            }
+            end += u64::try_from(read).unwrap();
+            if let Ok(archived_event_id) = serde_json::from_str::<ArchivedEventId>(&line) {
+                event_ids.push(archived_event_id.event_id);
+            }
+            if event_ids.len() == Self::SEGMENT_EVENTS_MAX {
+                file_index
+                    .segments
+                    .push(SegmentFilter::new(start, end, &event_ids));
+                event_ids.clear();
+                start = end;
+            }
+        }
+        if !event_ids.is_empty() {
+            file_index
+                .segments
+                .push(SegmentFilter::new(start, end, &event_ids));
+        }
+        file_index.indexed_len = end;
+        Ok(())
+    }
+
+    /// Queue persisted events of the topic for archiving if enabled.
+    ///
+    /// This only waits when the archive has fallen too far behind.
+    pub async fn append(&self, topic_id: &str, archived_events: Vec<ArchivedEvent>) {
+        if archived_events.is_empty() {
+            return;
+        }
+        self.send(ArchiveCommand::Append {
+            topic_id: topic_id.to_owned(),
+            archived_events,
+        })
+        .await;
+    }
+
+    /// Archive records that supersede earlier records of purged or redacted
+    /// events and wait until they have been applied to the files of this
+    /// instance.
+    ///
+    /// Other instances apply them to their own files once they get the
+    /// audit event of the purge (see [Self::compact]).
+    pub async fn supersede(&self, topic_id: &str, superseding_events: Vec<ArchivedEvent>) {
+        if superseding_events.is_empty() || !self.is_enabled() {
+            return;
+        }
+        self.append(topic_id, superseding_events).await;
+        let (compacted, wait_for_compacted) = oneshot::channel();
+        self.send(ArchiveCommand::Compact {
+            topic_id: topic_id.to_owned(),
+            compacted: Some(compacted),
+        })
+        .await;
+        wait_for_compacted.await.ok();
+    }
+
+    /// Queue rewriting of the files of this instance for the topic without
+    /// the documents of purged events and with the documents of redacted
+    /// events replaced.
+    ///
+    /// This reads all archive files of the topic, but purges are rare and the
+    /// work is done by the background writer.
+    pub async fn compact(&self, topic_id: &str) {
+        self.send(ArchiveCommand::Compact {
+            topic_id: topic_id.to_owned(),
+            compacted: None,
+        })
+        .await;
+    }
+
+    /// Queue a command for the writer if the archive is enabled.
+    async fn send(&self, archive_command: ArchiveCommand) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+        if sender.send(archive_command).await.is_err() {
+            log::warn!("Failed to queue archive command: Archive writer is gone.");
+        }
+    }
+
+    /// Execute queued commands until the archive is dropped.
+    async fn execute_commands(
+        mut archive_writer: ArchiveWriter,
+        mut receiver: mpsc::Receiver<ArchiveCommand>,
+    ) {
+        let mut archive_commands = Vec::with_capacity(Self::COMMANDS_PER_WRITE);
+        while receiver
+            .recv_many(&mut archive_commands, Self::COMMANDS_PER_WRITE)
+            .await
+            > 0
+        {
+            let archive_commands = std::mem::take(&mut archive_commands);
+            // File access blocks, so keep it off the async worker threads.
+            let (directory, instance_id) = archive_writer.get_directory_and_instance_id();
+            archive_writer = tokio::task::spawn_blocking(move || {
+                archive_writer.execute(archive_commands);
+                archive_writer
+            })
+            .await
+            .unwrap_or_else(|e| {
+                log::warn!("Archive writer failed: {e}");
+                ArchiveWriter::new(directory, instance_id)
+            });
+        }
+    }
+}
+
+/// Writer of the archive files of this instance.
+///
+/// Only a single writer per instance exists, so appends and compaction of the
+/// files of this instance never run concurrently.
+struct ArchiveWriter {
+    directory: PathBuf,
+    instance_id: u16,
+    /// Current file of this instance by topic and period.
+    file_by_topic_and_period: HashMap<(String, u64), PathBuf>,
+}
+
+impl ArchiveWriter {
+    /// Max number of known current files before they are looked up again.
+    const KNOWN_FILES_MAX: usize = 4096;
+
+    /// Return a new instance.
+    fn new(directory: PathBuf, instance_id: u16) -> Self {
+        Self {
+            directory,
+            instance_id,
+            file_by_topic_and_period: HashMap::default(),
+        }
+    }
+
+    /// Return the directory and the identifier of this instance.
+    fn get_directory_and_instance_id(&self) -> (PathBuf, u16) {
+        (self.directory.to_owned(), self.instance_id)
+    }
+
+    /// Return the current archive file of this instance for the period.
+    fn get_file(&mut self, topic_id: &str, period: u64) -> PathBuf {
+        if self.file_by_topic_and_period.len() >= Self::KNOWN_FILES_MAX {
+            self.file_by_topic_and_period.clear();
+        }
+        let directory = &self.directory;
+        let instance_id = self.instance_id;
+        self.file_by_topic_and_period
+            .entry((topic_id.to_owned(), period))
+            .or_insert_with(|| {
+                let prefix = EventArchive::get_file_name(period, instance_id, 0);
+                let prefix = &prefix[..prefix.rfind('-').unwrap() + 1];
+                EventArchive::get_topic_files(directory, topic_id)
+                    .into_iter()
+                    .filter(|path| {
+                        path.file_name()
+                            .and_then(|file_name| file_name.to_str())
+                            .is_some_and(|file_name| file_name.starts_with(prefix))
+                    })
+                    .max()
+                    .unwrap_or_else(|| {
+                        directory.join(topic_id).join(EventArchive::get_file_name(
+                            period,
+                            instance_id,
+                            0,
+                        ))
+                    })
+            })
+            .to_owned()
+    }
+
+    /// Execute the commands in order.
+    fn execute(&mut self, archive_commands: Vec<ArchiveCommand>) {
+        let mut lines_by_path = HashMap::<(String, PathBuf), String>::new();
+        for archive_command in archive_commands {
+            match archive_command {
+                ArchiveCommand::Append {
+                    topic_id,
+                    archived_events,
+                } => {
+                    for archived_event in archived_events {
+                        let period = EventArchive::get_period(archived_event.get_unique_time());
+                        let path = self.get_file(&topic_id, period);
+                        let lines = lines_by_path
+                            .entry((topic_id.to_owned(), path))
+                            .or_default();
+                        lines.push_str(&serde_json::to_string(&archived_event).unwrap());
+                        lines.push('\n');
+                    }
+                }
+                ArchiveCommand::Compact {
+                    topic_id,
+                    compacted,
+                } => {
+                    self.write_lines(std::mem::take(&mut lines_by_path));
+                    self.compact(&topic_id);
+                    if let Some(compacted) = compacted {
+                        compacted.send(()).ok();
+                    }
+                }
+            }
+        }
+        self.write_lines(lines_by_path);
+    }
+
+    /// Append the lines to the files of this instance.
+    fn write_lines(&self, lines_by_path: HashMap<(String, PathBuf), String>) {
+        for ((topic_id, path), lines) in lines_by_path {
+            let appended = std::fs::create_dir_all(self.directory.join(&topic_id)).and_then(|_| {
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&path)?
+                    .write_all(lines.as_bytes())
+            });
+            if let Err(e) = appended {
+                log::warn!("Failed to archive events of topic '{topic_id}': {e}");
+            }
+        }
+    }
+
+    /// Apply superseding records of all instances to the files of this
+    /// instance for the topic.
+    fn compact(&mut self, topic_id: &str) {
+        let paths_by_period = EventArchive::get_topic_files_by_period(&self.directory, topic_id);
+        for (period, paths) in paths_by_period {
+            let path = self.get_file(topic_id, period);
+            if !paths.contains(&path) {
+                continue;
+            }
+            if let Err(e) = self.compact_file(topic_id, period, &path, &paths) {
+                log::warn!("Failed to compact '{}': {e}", path.display());
+            }
+        }
+    }
+
+    /// Rewrite the file of this instance at `path` if any of its events have
+    /// been superseded in any of the files of the period.
+    ///
+    /// Documents of purged events are dropped and documents of redacted
+    /// events are replaced. Superseding records are kept, since the events
+    /// they supersede might still be present in files of other instances.
+    ///
+    /// The compacted file is written to a temporary file first and then
+    /// renamed to the name of the next generation, so readers never see a
+    /// partially written file.
+    fn compact_file(
+        &mut self,
+        topic_id: &str,
+        period: u64,
+        path: &Path,
+        paths: &[PathBuf],
+    ) -> std::io::Result<()> {
+        let mut superseding_by_unique_time = HashMap::<u64, ArchivedEvent>::new();
+        let mut archived_events = vec![];
+        for other_path in paths {
+            for archived_event in EventArchive::read_file(other_path)? {
+                if archived_event.revision.is_some()
+                    && superseding_by_unique_time
+                        .get(&archived_event.unique_time)
+                        .is_none_or(|superseding| superseding.revision < archived_event.revision)
+                {
+                    superseding_by_unique_time
+                        .insert(archived_event.unique_time, archived_event.clone());
+                }
+                if other_path == path {
+                    archived_events.push(archived_event);
+                }
+            }
+        }
+        let mut is_changed = false;
+        let mut lines = String::new();
+        for mut archived_event in archived_events {
+            if archived_event.revision.is_none()
+                && let Some(superseding) =
+                    superseding_by_unique_time.get(&archived_event.unique_time)
+            {
+                if superseding.purged {
+                    is_changed = true;
+                    continue;
+                }
+                if archived_event.document != superseding.document
+                    || archived_event.protection_ref != superseding.protection_ref
+                {
+                    is_changed = true;
+                    archived_event.document = superseding.document.to_owned();
+                    archived_event.protection_ref = superseding.protection_ref.to_owned();
+                }
+            }
+            lines.push_str(&serde_json::to_string(&archived_event).unwrap());
+            lines.push('\n');
+        }
+        if !is_changed {
+            return Ok(());
+        }
+        let generation = path
+            .file_stem()
+            .and_then(|file_stem| file_stem.to_str())
+            .and_then(|file_stem| file_stem.rsplit_once('-'))
+            .and_then(|(_, generation)| generation.parse::<u32>().ok())
+            .unwrap_or_default();
+        let next_path = self
+            .directory
+            .join(topic_id)
+            .join(EventArchive::get_file_name(
+                period,
+                self.instance_id,
+                generation + 1,
+            ));
+        let temp_path = next_path.with_extension("tmp");
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&temp_path)?;
+        file.write_all(lines.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, &next_path)?;
+        std::fs::remove_file(path)?;
+        self.file_by_topic_and_period
+            .insert((topic_id.to_owned(), period), next_path);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_test_directory(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "fragtale-archive-{name}-test-{}",
+            fragtale_client::time::get_timestamp_micros()
+        ))
+    }
+
+    fn get_test_event(i: u64, micros: u64) -> ArchivedEvent {
+        ArchivedEvent {
+            event_id: format!("event{i}"),
+            unique_time: UniqueTime::new(micros, 1).as_encoded(),
+            expires_ts: None,
+            protection_ref: "ref".to_owned(),
+            descriptor_version: None,
+            document: format!("{{\"i\":{i}}}"),
+            revision: None,
+            purged: false,
+        }
+    }
+
+    fn get_test_archive(directory: &Path) -> EventArchive {
+        EventArchive {
+            directory: Some(directory.to_owned()),
+            sender: None,
+            index_by_topic: SkipMap::default(),
+        }
+    }
+
+    fn append(archive_writer: &mut ArchiveWriter, archived_events: Vec<ArchivedEvent>) {
+        archive_writer.execute(vec![ArchiveCommand::Append {
+            topic_id: "topic".to_owned(),
+            archived_events,
+        }]);
+    }
+
+    #[test]
+    fn archived_events_are_appended_to_files_of_the_instance() {
+        let directory = get_test_directory("append");
+        let archived_events = (1..=3)
+            .map(|i| get_test_event(i, 1_000_000 * i))
+            .collect::<Vec<_>>();
+        let period = EventArchive::get_period(archived_events[0].get_unique_time());
+        let mut archive_writer = ArchiveWriter::new(directory.clone(), 7);
+        append(&mut archive_writer, archived_events);
+        let content = std::fs::read_to_string(archive_writer.get_file("topic", period));
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(content.unwrap().lines().count(), 3);
+    }
+
+    #[tokio::test]
+    async fn archived_events_are_read_one_period_at_a_time() {
+        let directory = get_test_directory("range");
+        let event_archive = get_test_archive(&directory);
+        // Two events in each of three periods (~72 minutes apart)
+        let period_micros = 5_000_000_000;
+        let archived_events = (0..6)
+            .map(|i| get_test_event(i, period_micros * (i / 2) + i))
+            .collect::<Vec<_>>();
+        append(
+            &mut ArchiveWriter::new(directory.clone(), 1),
+            archived_events,
+        );
+        let from = UniqueTime::from(0);
+        let to = UniqueTime::new(period_micros * 3, 0);
+        let (first_page, first_more) = event_archive
+            .events_by_unique_time_range("topic", from, to, 3)
+            .await;
+        let (all, all_more) = event_archive
+            .events_by_unique_time_range("topic", from, to, 10)
+            .await;
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(
+            first_page
+                .iter()
+                .map(ArchivedEvent::get_event_id)
+                .collect::<Vec<_>>(),
+            vec!["event0", "event1", "event2"]
+        );
+        assert!(first_more);
+        assert_eq!(all.len(), 6);
+        assert!(!all_more);
+    }
+
+    #[test]
+    fn segment_filter_has_no_false_negatives() {
+        let event_ids = (0..1000).map(|i| format!("event{i}")).collect::<Vec<_>>();
+        let segment_filter = SegmentFilter::new(0, 1, &event_ids);
+        assert!(
+            event_ids
+                .iter()
+                .all(|event_id| segment_filter.might_contain(event_id))
+        );
+        let false_positives = (0..1000)
+            .filter(|i| segment_filter.might_contain(&format!("other{i}")))
+            .count();
+        assert!(false_positives < 10);
+    }
+
+    #[tokio::test]
+    async fn archived_events_are_found_by_id() {
+        let directory = get_test_directory("index");
+        let event_archive = get_test_archive(&directory);
+        let archived_events = (1..=3)
+            .map(|i| get_test_event(i, 1_000_000 * i))
+            .collect::<Vec<_>>();
+        append(
+            &mut ArchiveWriter::new(directory.clone(), 1),
+            archived_events,
+        );
+        let found = event_archive.event_by_id("topic", "event2").await;
+        let missing = event_archive.event_by_id("topic", "event4").await;
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(found.unwrap().document, "{\"i\":2}");
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn superseded_events_are_compacted_by_the_owning_instance() {
+        let directory = get_test_directory("compact");
+        let event_archive = get_test_archive(&directory);
+        let archived_events = (1..=3)
+            .map(|i| get_test_event(i, 1_000_000 * i))
+            .collect::<Vec<_>>();
+        let period = EventArchive::get_period(archived_events[0].get_unique_time());
+        let mut owner = ArchiveWriter::new(directory.clone(), 1);
+        append(&mut owner, archived_events.clone());
+        // Another instance purges event1 and redacts event2
+        let mut other = ArchiveWriter::new(directory.clone(), 2);
+        append(
+            &mut other,
+            vec![
+                ArchivedEvent::superseding("event1", archived_events[0].get_unique_time(), None),
+                ArchivedEvent::superseding(
+                    "event2",
+                    archived_events[1].get_unique_time(),
+                    Some(("{}", "ref2")),
+                ),
+            ],
+        );
+        let owner_file = owner.get_file("topic", period);
+        let purged_before = event_archive.event_by_id("topic", "event1").await;
+        // Owner appends concurrently with the purge and then compacts
+        owner.execute(vec![
+            ArchiveCommand::Append {
+                topic_id: "topic".to_owned(),
+                archived_events: vec![get_test_event(4, 4_000_000)],
+            },
+            ArchiveCommand::Compact {
+                topic_id: "topic".to_owned(),
+                compacted: None,
+            },
+        ]);
+        let compacted_file = owner.get_file("topic", period);
+        let compacted = std::fs::read_to_string(&compacted_file).unwrap_or_default();
+        let (after, _more) = event_archive
+            .events_by_unique_time_range(
+                "topic",
+                UniqueTime::from(0),
+                UniqueTime::from(u64::MAX),
+                10,
+            )
+            .await;
+        let is_owner_file_removed = !owner_file.exists();
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert!(purged_before.is_none());
+        assert_ne!(owner_file, compacted_file);
+        assert!(is_owner_file_removed);
+        assert!(!compacted.contains("{\"i\":1}"));
+        assert!(!compacted.contains("{\"i\":2}"));
+        assert_eq!(
+            after
+                .iter()
+                .map(|archived_event| (
+                    archived_event.get_event_id(),
+                    archived_event.document.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                ("event2", "{}"),
+                ("event3", "{\"i\":3}"),
+                ("event4", "{\"i\":4}")
+            ]
+        );
+    }
+}
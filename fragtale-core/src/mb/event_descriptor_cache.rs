@@ -20,12 +20,17 @@
 mod per_topic_event_descriptor;
 
 use self::per_topic_event_descriptor::PerTopicEventDescriptor;
+use super::document_canonicalization::DocumentCanonicalization;
+use super::event_id_collision_policy::EventIdCollisionPolicy;
 use crossbeam_skiplist::SkipMap;
 use crossbeam_skiplist::map::Entry;
+use fragtale_client::mb::delivery_receipts::DeliveryReceiptTarget;
 use fragtale_client::mb::event_descriptor::DescriptorVersion;
 use fragtale_client::mb::event_descriptor::EventDescriptor;
+use fragtale_client::mb::shared_schemas::SharedSchema;
 use fragtale_dbp::dbp::DatabaseProvider;
 use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::EventIdAlgorithm;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
@@ -37,6 +42,8 @@ use tokio::time::sleep;
 pub struct EventDescriptorCache {
     dbp: Arc<DatabaseProvider>,
     event_descriptors: SkipMap<String, PerTopicEventDescriptor>,
+    /// Latest version of each shared schema component by schema identifier.
+    shared_schemas: SkipMap<String, Arc<SharedSchema>>,
     update_in_progress_map: SkipMap<String, Arc<(Semaphore, u64)>>,
     reload_marker_generator: AtomicU64,
 }
@@ -47,6 +54,7 @@ impl EventDescriptorCache {
         Arc::new(Self {
             dbp: Arc::clone(dbp),
             event_descriptors: SkipMap::default(),
+            shared_schemas: SkipMap::default(),
             update_in_progress_map: SkipMap::default(),
             reload_marker_generator: AtomicU64::default(),
         })
@@ -68,7 +76,11 @@ impl EventDescriptorCache {
         self
     }
 
+    /// Max number of shared schema components that are loaded.
+    const SHARED_SCHEMAS_MAX: usize = 1024;
+
     async fn reload_for_topics(&self) {
+        self.reload_shared_schemas().await;
         let mut from = None;
         loop {
             let (topic_ids, more) = self.dbp.topic_facade().get_topic_ids(&from).await;
@@ -82,6 +94,53 @@ impl EventDescriptorCache {
         }
     }
 
+    /// Reload the latest version of all [SharedSchema] components.
+    pub async fn reload_shared_schemas(&self) {
+        let shared_schemas = self
+            .dbp
+            .topic_facade()
+            .shared_schemas_latest(Self::SHARED_SCHEMAS_MAX)
+            .await
+            .iter()
+            .map(SharedSchema::from_string)
+            .collect::<Vec<_>>();
+        if shared_schemas.len() == Self::SHARED_SCHEMAS_MAX {
+            log::warn!(
+                "Only the first {} shared schemas are available for validation.",
+                Self::SHARED_SCHEMAS_MAX
+            );
+        }
+        for shared_schema in shared_schemas {
+            if self
+                .shared_schemas
+                .get(shared_schema.get_schema_id())
+                .is_some_and(|entry| entry.value().get_version() >= shared_schema.get_version())
+            {
+                continue;
+            }
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!(
+                    "Found version {:?} of shared schema '{}'.",
+                    shared_schema.get_version(),
+                    shared_schema.get_schema_id()
+                );
+            }
+            self.shared_schemas.insert(
+                shared_schema.get_schema_id().to_owned(),
+                Arc::new(shared_schema),
+            );
+        }
+    }
+
+    /// Get the latest version of all shared schema components that event
+    /// schemas can reference.
+    pub fn get_shared_schemas(&self) -> Vec<Arc<SharedSchema>> {
+        self.shared_schemas
+            .iter()
+            .map(|entry| Arc::clone(entry.value()))
+            .collect()
+    }
+
     /// Reload [EventDescriptor] for topic.
     ///
     /// If there are multiple concurrent callers, only the first will make the
@@ -196,6 +255,20 @@ impl EventDescriptorCache {
             .and_then(|pted| pted.get_event_descriptor_by_version(descriptor_version))
     }
 
+    /// Get the newest version of the event description for a topic that is
+    /// not newer than the `descriptor_version`.
+    pub fn get_event_descriptor_by_topic_at_most(
+        &self,
+        topic_id: &str,
+        descriptor_version: &DescriptorVersion,
+    ) -> Option<Arc<EventDescriptor>> {
+        self.event_descriptors
+            .get(topic_id)
+            .as_ref()
+            .map(Entry::value)
+            .and_then(|pted| pted.get_event_descriptor_by_version_at_most(descriptor_version))
+    }
+
     /// Get the latest version of the event description for a topic.
     pub fn get_event_descriptor_by_topic_latest(
         &self,
@@ -207,4 +280,41 @@ impl EventDescriptorCache {
             .map(Entry::value)
             .and_then(PerTopicEventDescriptor::get_event_descriptor_latest)
     }
+
+    /// Get the algorithm used to derive event identifiers of a topic.
+    pub fn get_event_id_algorithm(&self, topic_id: &str) -> EventIdAlgorithm {
+        self.get_event_descriptor_by_topic_latest(topic_id)
+            .as_deref()
+            .and_then(|event_descriptor| event_descriptor.get_event_id_algorithm().as_deref())
+            .and_then(EventIdAlgorithm::from_name)
+            .unwrap_or_default()
+    }
+
+    /// Get the handling of event identifier collisions of a topic.
+    pub fn get_event_id_collision_policy(&self, topic_id: &str) -> EventIdCollisionPolicy {
+        self.get_event_descriptor_by_topic_latest(topic_id)
+            .as_deref()
+            .and_then(|event_descriptor| {
+                event_descriptor.get_event_id_collision_policy().as_deref()
+            })
+            .and_then(EventIdCollisionPolicy::from_name)
+            .unwrap_or_default()
+    }
+
+    /// Get where receipts of confirmed deliveries of a topic's events are
+    /// emitted (if anywhere).
+    pub fn get_delivery_receipts(&self, topic_id: &str) -> Option<DeliveryReceiptTarget> {
+        self.get_event_descriptor_by_topic_latest(topic_id)
+            .as_deref()
+            .and_then(|event_descriptor| event_descriptor.get_delivery_receipts().clone())
+    }
+
+    /// Get the canonicalization of event documents published to a topic.
+    pub fn get_canonicalization(&self, topic_id: &str) -> DocumentCanonicalization {
+        self.get_event_descriptor_by_topic_latest(topic_id)
+            .as_deref()
+            .and_then(|event_descriptor| event_descriptor.get_canonicalization().as_deref())
+            .and_then(DocumentCanonicalization::from_name)
+            .unwrap_or_default()
+    }
 }
@@ -80,4 +80,17 @@ impl PerTopicEventDescriptor {
             .map(Entry::value)
             .map(Arc::clone)
     }
+
+    /// Get the newest event description for this topic that is not newer
+    /// than the `version`.
+    pub fn get_event_descriptor_by_version_at_most(
+        &self,
+        version: &DescriptorVersion,
+    ) -> Option<Arc<EventDescriptor>> {
+        self.event_descriptors
+            .upper_bound(std::ops::Bound::Included(&version.as_encoded()))
+            .as_ref()
+            .map(Entry::value)
+            .map(Arc::clone)
+    }
 }
@@ -0,0 +1,44 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Handling of different event documents that map to the same event
+//! identifier.
+
+/// Handling of different event documents that map to the same event
+/// identifier.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EventIdCollisionPolicy {
+    /// Don't look for collisions.
+    #[default]
+    Ignore,
+    /// Raise an integrity alert, but accept the event.
+    Alert,
+    /// Raise an integrity alert and refuse the event.
+    Reject,
+}
+
+impl EventIdCollisionPolicy {
+    /// Return the policy with the `name` if supported.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ignore" => Some(Self::Ignore),
+            "alert" => Some(Self::Alert),
+            "reject" => Some(Self::Reject),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,204 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Mirroring of published events to rotating files on local disk.
+
+use crate::conf::AppConfig;
+use crossbeam_skiplist::SkipMap;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// The currently written mirror file of a topic.
+struct MirrorFile {
+    file: File,
+    path: PathBuf,
+    created_micros: u64,
+    size: u64,
+}
+
+/** Mirror of published events to rotating NDJSON files on local disk.
+
+This is a debug sink for environments where extra consumers cannot be
+attached. Mirroring is toggled per topic and only applies to events accepted
+by this instance.
+
+Failures to write are logged and never affect publishing.
+*/
+pub struct EventMirror {
+    directory: PathBuf,
+    max_file_size: u64,
+    max_file_age_micros: u64,
+    max_files: usize,
+    by_topic: SkipMap<String, Arc<Mutex<Option<MirrorFile>>>>,
+}
+
+impl EventMirror {
+    /// Return a new instance.
+    pub fn new(app_config: &AppConfig) -> Arc<Self> {
+        Arc::new(Self {
+            directory: PathBuf::from(app_config.mirror.directory()),
+            max_file_size: app_config.mirror.max_file_size(),
+            max_file_age_micros: app_config.mirror.max_file_age_micros(),
+            max_files: app_config.mirror.max_files(),
+            by_topic: SkipMap::default(),
+        })
+    }
+
+    /// Return `true` if published events of the topic are mirrored.
+    pub fn is_enabled(&self, topic_id: &str) -> bool {
+        self.by_topic.contains_key(topic_id)
+    }
+
+    /// Enable or disable mirroring of published events of the topic.
+    ///
+    /// Disabling closes the current mirror file, but keeps it on disk.
+    pub fn set_enabled(&self, topic_id: &str, enabled: bool) {
+        if enabled {
+            self.by_topic
+                .get_or_insert_with(topic_id.to_owned(), Arc::default);
+        } else {
+            self.by_topic.remove(topic_id);
+        }
+    }
+
+    /// Return the directory where mirror files of the topic are written.
+    pub fn get_topic_directory(&self, topic_id: &str) -> PathBuf {
+        self.directory.join(topic_id)
+    }
+
+    /// Append a published event to the current mirror file of the topic if
+    /// mirroring is enabled.
+    pub fn mirror(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        encoded_unique_time: u64,
+        publisher: &str,
+        event_document: &str,
+    ) {
+        let Some(entry) = self.by_topic.get(topic_id) else {
+            return;
+        };
+        let mut line = serde_json::json!({
+            "event_id": event_id,
+            "unique_time": encoded_unique_time,
+            "mirrored_ts": fragtale_client::time::get_timestamp_micros(),
+            "publisher": publisher,
+            "document": serde_json::from_str::<serde_json::Value>(event_document)
+                .unwrap_or_else(|_| serde_json::Value::String(event_document.to_owned())),
+        })
+        .to_string();
+        line.push('\n');
+        let mut mirror_file = entry.value().lock().unwrap();
+        if let Err(e) = self.append(topic_id, &mut mirror_file, line.as_bytes()) {
+            log::warn!("Failed to mirror event '{event_id}' of topic '{topic_id}': {e}");
+            // Start over with a new file on next attempt.
+            mirror_file.take();
+        }
+    }
+
+    /// Append to the current file and rotate it first when needed.
+    fn append(
+        &self,
+        topic_id: &str,
+        mirror_file: &mut Option<MirrorFile>,
+        bytes: &[u8],
+    ) -> std::io::Result<()> {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let len = u64::try_from(bytes.len()).unwrap_or(u64::MAX);
+        let rotate = mirror_file.as_ref().is_none_or(|current| {
+            (current.size > 0 && current.size + len > self.max_file_size)
+                || current.created_micros + self.max_file_age_micros < now_micros
+        });
+        if rotate {
+            let topic_directory = self.get_topic_directory(topic_id);
+            std::fs::create_dir_all(&topic_directory)?;
+            let path = topic_directory.join(format!("{topic_id}-{now_micros}.ndjson"));
+            let file = OpenOptions::new().create(true).append(true).open(&path)?;
+            if let Some(previous) = mirror_file.replace(MirrorFile {
+                file,
+                path,
+                created_micros: now_micros,
+                size: 0,
+            }) && log::log_enabled!(log::Level::Debug)
+            {
+                log::debug!("Rotated mirror file '{}'.", previous.path.display());
+            }
+            Self::remove_oldest_files(&topic_directory, self.max_files)?;
+        }
+        let current = mirror_file.as_mut().unwrap();
+        current.file.write_all(bytes)?;
+        current.size += len;
+        Ok(())
+    }
+
+    /// Remove the oldest mirror files in the directory until at most
+    /// `max_files` remain.
+    fn remove_oldest_files(directory: &Path, max_files: usize) -> std::io::Result<()> {
+        let mut paths = std::fs::read_dir(directory)?
+            .filter_map(|dir_entry| dir_entry.ok().map(|dir_entry| dir_entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "ndjson")
+            })
+            .collect::<Vec<_>>();
+        // File names end with the creation time, so this orders by age.
+        paths.sort();
+        for path in paths.iter().take(paths.len().saturating_sub(max_files)) {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_on_size_and_keeps_newest_files() {
+        let directory = std::env::temp_dir().join(format!(
+            "fragtale-mirror-test-{}",
+            fragtale_client::time::get_timestamp_micros()
+        ));
+        let event_mirror = EventMirror {
+            directory: directory.clone(),
+            max_file_size: 128,
+            max_file_age_micros: 3_600_000_000,
+            max_files: 2,
+            by_topic: SkipMap::default(),
+        };
+        event_mirror.mirror("topic", "ignored", 1, "publisher", "{}");
+        assert!(!directory.exists());
+        event_mirror.set_enabled("topic", true);
+        for i in 0..8 {
+            event_mirror.mirror("topic", &format!("event{i}"), i, "publisher", "{\"a\":1}");
+            // Ensure unique file names
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        let files = std::fs::read_dir(event_mirror.get_topic_directory("topic"))
+            .unwrap()
+            .count();
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(files, 2);
+    }
+}
@@ -0,0 +1,185 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Administrative purge of events matching an indexed key.
+
+use super::document_masking::DocumentMasking;
+use super::event_archive::ArchivedEvent;
+use super::event_archive::EventArchive;
+use crate::mb::integrity::IntegrityProtector;
+use fragtale_client::mb::event_descriptor::MaskingRule;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/** Administrative purge of events matching an indexed key.
+
+Purged events are removed together with their index entries, correlation
+lookups and annotations. Redacted events keep their place in the topic, but the
+document is replaced by the masked version and re-protected, so the redacted
+document can still be integrity validated.
+
+The group level integrity protections only hold digests of protected events
+and are kept, so the integrity of the remaining events can still be verified.
+
+Archived copies of the events are purged or redacted the same way. Events are
+matched using the indexes in the database, so events that are only present in
+the archive tier are never matched.
+*/
+pub struct EventPurger {
+    dbp: Arc<DatabaseProvider>,
+    integrity_protector: Arc<IntegrityProtector>,
+    event_archive: Arc<EventArchive>,
+}
+
+impl EventPurger {
+    /// Max number of events with the same event identifier that are purged.
+    const MAX_EVENTS_PER_EVENT_ID: usize = 1024;
+
+    /// Return a new instance.
+    pub fn new(
+        dbp: &Arc<DatabaseProvider>,
+        integrity_protector: &Arc<IntegrityProtector>,
+        event_archive: &Arc<EventArchive>,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            dbp: Arc::clone(dbp),
+            integrity_protector: Arc::clone(integrity_protector),
+            event_archive: Arc::clone(event_archive),
+        })
+    }
+
+    /// Return the event identifier and [UniqueTime] of every event matching
+    /// the index key.
+    ///
+    /// Events with the same identifier (same document published more than
+    /// once) are all returned.
+    pub async fn matching_events(
+        &self,
+        topic_id: &str,
+        index_column: &str,
+        index_key: &str,
+    ) -> Vec<(String, UniqueTime)> {
+        let mut event_ids = self
+            .dbp
+            .event_facade()
+            .event_ids_by_index(topic_id, index_column, index_key)
+            .await;
+        let mut seen = HashSet::new();
+        event_ids.retain(|event_id| seen.insert(event_id.to_owned()));
+        let mut ret = Vec::with_capacity(event_ids.len());
+        for event_id in event_ids {
+            for unique_time in self
+                .dbp
+                .event_facade()
+                .event_unique_times_by_id(topic_id, &event_id, Self::MAX_EVENTS_PER_EVENT_ID)
+                .await
+            {
+                ret.push((event_id.to_owned(), unique_time));
+            }
+        }
+        ret
+    }
+
+    /// Permanently remove the events.
+    ///
+    /// Return the number of events that were removed.
+    pub async fn purge(&self, topic_id: &str, events: &[(String, UniqueTime)]) -> u64 {
+        let mut purged_count = 0;
+        let mut superseding_events = vec![];
+        for (event_id, unique_time) in events {
+            if self
+                .dbp
+                .event_facade()
+                .event_purge(topic_id, event_id, *unique_time)
+                .await
+            {
+                purged_count += 1;
+            } else {
+                log::warn!("Failed to purge event {unique_time:?} in '{topic_id}'.");
+            }
+            // Purge any archived copy, even if it is no longer in the database
+            if self.event_archive.is_enabled() {
+                superseding_events.push(ArchivedEvent::superseding(event_id, *unique_time, None));
+            }
+        }
+        self.event_archive
+            .supersede(topic_id, superseding_events)
+            .await;
+        purged_count
+    }
+
+    /// Replace the documents of the events by the masked version and protect
+    /// the redacted documents.
+    ///
+    /// Return the number of events that were redacted.
+    pub async fn redact(
+        &self,
+        topic_id: &str,
+        events: &[(String, UniqueTime)],
+        masking_rules: &[MaskingRule],
+    ) -> u64 {
+        let mut redacted_count = 0;
+        let mut superseding_events = vec![];
+        for (event_id, unique_time) in events {
+            let Some(event_delivery_gist) = self
+                .dbp
+                .event_facade()
+                .event_by_id_and_unique_time(topic_id, event_id, *unique_time)
+                .await
+            else {
+                continue;
+            };
+            let Some(document) =
+                DocumentMasking::mask(event_delivery_gist.get_document(), masking_rules)
+            else {
+                log::warn!(
+                    "Event {unique_time:?} in '{topic_id}' is not a JSON object or array and was not redacted."
+                );
+                continue;
+            };
+            let protection_ref = self
+                .integrity_protector
+                .derive_protection(topic_id, &document, unique_time)
+                .await
+                .as_string();
+            if self
+                .dbp
+                .event_facade()
+                .event_redact(topic_id, event_id, *unique_time, &document, &protection_ref)
+                .await
+            {
+                redacted_count += 1;
+                if self.event_archive.is_enabled() {
+                    superseding_events.push(ArchivedEvent::superseding(
+                        event_id,
+                        *unique_time,
+                        Some((&document, &protection_ref)),
+                    ));
+                }
+            } else {
+                log::warn!("Failed to redact event {unique_time:?} in '{topic_id}'.");
+            }
+        }
+        self.event_archive
+            .supersede(topic_id, superseding_events)
+            .await;
+        redacted_count
+    }
+}
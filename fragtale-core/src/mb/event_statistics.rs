@@ -0,0 +1,74 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Per topic event size and field-level statistics.
+
+mod hyper_log_log;
+mod per_topic_statistics;
+
+use self::hyper_log_log::HyperLogLog;
+use self::per_topic_statistics::PerTopicStatistics;
+use crossbeam_skiplist::SkipMap;
+use fragtale_client::mb::topic_statistics::TopicStatistics;
+use fragtale_dbp::mb::ExtractedValue;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/** Rolling statistics of published events for each topic.
+
+Statistics are collected in memory by each instance since it was started and
+are intended to inform schema/extraction design and capacity planning.
+*/
+#[derive(Default)]
+pub struct EventStatistics {
+    by_topic: SkipMap<String, Arc<PerTopicStatistics>>,
+}
+
+impl EventStatistics {
+    /// Return a new instance.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Update statistics with a published event document and its extracted
+    /// values.
+    pub fn record(
+        &self,
+        topic_id: &str,
+        event_document: &str,
+        extracted_values: &HashMap<String, ExtractedValue>,
+    ) {
+        let per_topic_statistics = if let Some(entry) = self.by_topic.get(topic_id) {
+            Arc::clone(entry.value())
+        } else {
+            Arc::clone(
+                self.by_topic
+                    .get_or_insert_with(topic_id.to_owned(), PerTopicStatistics::new)
+                    .value(),
+            )
+        };
+        per_topic_statistics.record(event_document, extracted_values);
+    }
+
+    /// Return a snapshot of the statistics of a topic or `None` if no event
+    /// has been published to the topic since this instance was started.
+    pub fn by_topic(&self, topic_id: &str) -> Option<TopicStatistics> {
+        self.by_topic
+            .get(topic_id)
+            .map(|entry| entry.value().as_topic_statistics(topic_id))
+    }
+}
@@ -0,0 +1,172 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Event statistics of a single topic.
+
+use super::HyperLogLog;
+use crossbeam_skiplist::SkipMap;
+use fragtale_client::mb::topic_statistics::TopicStatistics;
+use fragtale_dbp::mb::ExtractedValue;
+use serde::de::IgnoredAny;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// Event size distribution, top-level field presence frequency and extracted
+/// value cardinality estimates of a single topic.
+pub struct PerTopicStatistics {
+    since_micros: u64,
+    event_count: AtomicU64,
+    size_sum: AtomicU64,
+    size_min: AtomicU64,
+    size_max: AtomicU64,
+    /// Event count by power of two size bucket (one bucket per bit of the
+    /// size).
+    size_buckets: Vec<AtomicU64>,
+    field_presence: SkipMap<String, AtomicU64>,
+    extracted_cardinality: SkipMap<String, Arc<HyperLogLog>>,
+}
+
+impl PerTopicStatistics {
+    /// Limit the number of distinct top-level fields tracked for each topic.
+    ///
+    /// This protects against documents that use object keys as data.
+    const MAX_TRACKED_FIELDS: usize = 256;
+
+    /// Return a new instance.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            since_micros: fragtale_client::time::get_timestamp_micros(),
+            event_count: AtomicU64::default(),
+            size_sum: AtomicU64::default(),
+            size_min: AtomicU64::new(u64::MAX),
+            size_max: AtomicU64::default(),
+            size_buckets: (0..u64::BITS).map(|_| AtomicU64::default()).collect(),
+            field_presence: SkipMap::default(),
+            extracted_cardinality: SkipMap::default(),
+        })
+    }
+
+    /// Update statistics with a published event document and its extracted
+    /// values.
+    pub fn record(&self, event_document: &str, extracted_values: &HashMap<String, ExtractedValue>) {
+        let size = event_document.len() as u64;
+        self.event_count.fetch_add(1, Ordering::Relaxed);
+        self.size_sum.fetch_add(size, Ordering::Relaxed);
+        self.size_min.fetch_min(size, Ordering::Relaxed);
+        self.size_max.fetch_max(size, Ordering::Relaxed);
+        let bucket_index = size.max(1).next_power_of_two().trailing_zeros() as usize;
+        self.size_buckets[bucket_index].fetch_add(1, Ordering::Relaxed);
+        // Only the keys are of interest, so avoid allocating the values
+        if let Ok(top_level) = serde_json::from_str::<HashMap<String, IgnoredAny>>(event_document) {
+            for field_name in top_level.keys() {
+                if let Some(entry) = self.field_presence.get(field_name) {
+                    entry.value().fetch_add(1, Ordering::Relaxed);
+                } else if self.field_presence.len() < Self::MAX_TRACKED_FIELDS {
+                    self.field_presence
+                        .get_or_insert_with(field_name.to_owned(), AtomicU64::default)
+                        .value()
+                        .fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+        for (result_name, extracted_value) in extracted_values {
+            let hll = Arc::clone(
+                self.extracted_cardinality
+                    .get_or_insert_with(result_name.to_owned(), Arc::default)
+                    .value(),
+            );
+            match extracted_value {
+                ExtractedValue::Text(text) => hll.insert(text),
+                ExtractedValue::BigInt(number) => hll.insert(number),
+                ExtractedValue::TextCollection(texts) => {
+                    texts.iter().for_each(|text| hll.insert(text))
+                }
+                ExtractedValue::BigIntCollection(numbers) => {
+                    numbers.iter().for_each(|number| hll.insert(number))
+                }
+            }
+        }
+    }
+
+    /// Return a snapshot of the current statistics.
+    pub fn as_topic_statistics(&self, topic_id: &str) -> TopicStatistics {
+        let event_count = self.event_count.load(Ordering::Relaxed);
+        let size_mean = self
+            .size_sum
+            .load(Ordering::Relaxed)
+            .checked_div(event_count)
+            .unwrap_or_default();
+        let size_min = match self.size_min.load(Ordering::Relaxed) {
+            u64::MAX => 0,
+            size_min => size_min,
+        };
+        let size_distribution = self
+            .size_buckets
+            .iter()
+            .enumerate()
+            .map(|(index, count)| (1u64 << index, count.load(Ordering::Relaxed)))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        let field_presence = self
+            .field_presence
+            .iter()
+            .map(|entry| {
+                (
+                    entry.key().to_owned(),
+                    entry.value().load(Ordering::Relaxed),
+                )
+            })
+            .collect();
+        let extracted_cardinality = self
+            .extracted_cardinality
+            .iter()
+            .map(|entry| (entry.key().to_owned(), entry.value().estimate()))
+            .collect::<BTreeMap<_, _>>();
+        TopicStatistics::new(
+            topic_id,
+            self.since_micros,
+            event_count,
+            size_min,
+            self.size_max.load(Ordering::Relaxed),
+            size_mean,
+            size_distribution,
+            field_presence,
+            extracted_cardinality,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes_are_bucketed_by_power_of_two() {
+        let per_topic_statistics = PerTopicStatistics::new();
+        for event_document in ["{}", "{\"a\":1}", "{\"a\":1}"] {
+            per_topic_statistics.record(event_document, &HashMap::new());
+        }
+        let topic_statistics = per_topic_statistics.as_topic_statistics("topic");
+        let size_distribution = topic_statistics.get_size_distribution();
+        assert_eq!(size_distribution.get(&2), Some(&1));
+        assert_eq!(size_distribution.get(&8), Some(&2));
+        assert_eq!(size_distribution.len(), 2);
+    }
+}
@@ -0,0 +1,264 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Small expression language for predicates over extracted document values.
+
+mod expression_parser;
+
+use self::expression_parser::Function;
+use self::expression_parser::Node;
+use self::expression_parser::Operator;
+use fragtale_dbp::mb::ExtractedValue;
+use fragtale_dbp::mb::MessageBrokerError;
+use std::collections::HashMap;
+
+/// Value of an evaluated (sub-)expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Text(String),
+    List(Vec<Value>),
+}
+
+impl From<&ExtractedValue> for Value {
+    fn from(value: &ExtractedValue) -> Self {
+        match value {
+            ExtractedValue::Text(text) => Self::Text(text.to_owned()),
+            ExtractedValue::BigInt(number) => Self::Int(*number),
+            ExtractedValue::TextCollection(texts) => {
+                Self::List(texts.iter().cloned().map(Self::Text).collect())
+            }
+            ExtractedValue::BigIntCollection(numbers) => {
+                Self::List(numbers.iter().copied().map(Self::Int).collect())
+            }
+        }
+    }
+}
+
+/** Compiled predicate over the values extracted from an event document.
+
+The syntax is a small subset of the Common Expression Language (CEL):
+
+* Literals: `"text"` or `'text'`, integers, `true`, `false`, `null` and lists
+  like `["a", "b"]`.
+* Identifiers reference values extracted by the topic's event descriptor by
+  result name. Values that were not extracted are `null` and values of
+  collection result types are lists.
+* Comparisons: `==`, `!=`, `<`, `<=`, `>`, `>=` and `in` (list membership).
+* Boolean logic: `&&`, `||`, `!` and parentheses.
+* Functions: `has(name)`, `size(value)`, `lower(text)` and `upper(text)`.
+* Methods on text: `startsWith(text)`, `endsWith(text)` and `contains(text)`.
+* Methods on lists: `contains(value)` (same as `value in list`).
+
+Example: `region in ["eu", "us"] && amount >= 1000 && !sku.startsWith("test-")`
+
+For simple filters, comma separated `name=operator:value` conditions that all
+must match are accepted as well. The operators are `eq`, `neq`, `lt`, `lte`,
+`gt`, `gte` and `in` (with `|` separated values). A value that looks like an
+integer matches both integer and text values unless it is quoted.
+
+Example: `region=in:eu|us,amount=gte:1000`
+
+Evaluation never fails: operations on values of unexpected types (like
+comparing text to a number) yield `false`.
+*/
+#[derive(Debug)]
+pub struct FilterExpression {
+    root: Node,
+}
+
+impl FilterExpression {
+    /// Compile the expression `source`.
+    pub fn compile(source: &str) -> Result<Self, MessageBrokerError> {
+        Ok(Self {
+            root: expression_parser::parse(source)?,
+        })
+    }
+
+    /// Return `true` if the expression evaluates to `true` for the extracted
+    /// values.
+    pub fn matches(&self, fields: &HashMap<String, ExtractedValue>) -> bool {
+        Self::evaluate(&self.root, fields) == Value::Bool(true)
+    }
+
+    fn evaluate(node: &Node, fields: &HashMap<String, ExtractedValue>) -> Value {
+        match node {
+            Node::Literal(value) => value.clone(),
+            Node::Field(name) => fields.get(name).map(Value::from).unwrap_or(Value::Null),
+            Node::List(items) => Value::List(
+                items
+                    .iter()
+                    .map(|item| Self::evaluate(item, fields))
+                    .collect(),
+            ),
+            Node::Not(operand) => match Self::evaluate(operand, fields) {
+                Value::Bool(value) => Value::Bool(!value),
+                _ => Value::Null,
+            },
+            Node::And(left, right) => Value::Bool(
+                Self::evaluate(left, fields) == Value::Bool(true)
+                    && Self::evaluate(right, fields) == Value::Bool(true),
+            ),
+            Node::Or(left, right) => Value::Bool(
+                Self::evaluate(left, fields) == Value::Bool(true)
+                    || Self::evaluate(right, fields) == Value::Bool(true),
+            ),
+            Node::Binary(operator, left, right) => Value::Bool(Self::compare(
+                operator,
+                &Self::evaluate(left, fields),
+                &Self::evaluate(right, fields),
+            )),
+            Node::Call(Function::Has, arguments) => Value::Bool(
+                matches!(arguments.as_slice(), [Node::Field(name)] if fields.contains_key(name)),
+            ),
+            Node::Call(function, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| Self::evaluate(argument, fields))
+                    .collect::<Vec<_>>();
+                Self::call(function, &arguments)
+            }
+        }
+    }
+
+    fn compare(operator: &Operator, left: &Value, right: &Value) -> bool {
+        match (operator, left, right) {
+            (Operator::Eq, left, right) => left == right,
+            (Operator::Ne, left, right) => left != right,
+            (Operator::In, left, Value::List(items)) => items.contains(left),
+            (operator, Value::Int(left), Value::Int(right)) => {
+                Self::is_ordered(operator, left.cmp(right))
+            }
+            (operator, Value::Text(left), Value::Text(right)) => {
+                Self::is_ordered(operator, left.cmp(right))
+            }
+            _ => false,
+        }
+    }
+
+    fn is_ordered(operator: &Operator, ordering: std::cmp::Ordering) -> bool {
+        match operator {
+            Operator::Lt => ordering.is_lt(),
+            Operator::Le => ordering.is_le(),
+            Operator::Gt => ordering.is_gt(),
+            Operator::Ge => ordering.is_ge(),
+            _ => false,
+        }
+    }
+
+    fn call(function: &Function, arguments: &[Value]) -> Value {
+        match (function, arguments) {
+            (Function::Size, [Value::Text(text)]) => {
+                Value::Int(i64::try_from(text.chars().count()).unwrap_or(i64::MAX))
+            }
+            (Function::Size, [Value::List(items)]) => {
+                Value::Int(i64::try_from(items.len()).unwrap_or(i64::MAX))
+            }
+            (Function::Lower, [Value::Text(text)]) => Value::Text(text.to_lowercase()),
+            (Function::Upper, [Value::Text(text)]) => Value::Text(text.to_uppercase()),
+            (Function::StartsWith, [Value::Text(text), Value::Text(prefix)]) => {
+                Value::Bool(text.starts_with(prefix.as_str()))
+            }
+            (Function::EndsWith, [Value::Text(text), Value::Text(suffix)]) => {
+                Value::Bool(text.ends_with(suffix.as_str()))
+            }
+            (Function::Contains, [Value::Text(text), Value::Text(part)]) => {
+                Value::Bool(text.contains(part.as_str()))
+            }
+            (Function::Contains, [Value::List(items), item]) => Value::Bool(items.contains(item)),
+            _ => Value::Null,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> HashMap<String, ExtractedValue> {
+        HashMap::from([
+            ("region".to_owned(), ExtractedValue::Text("eu".to_owned())),
+            ("sku".to_owned(), ExtractedValue::Text("test-42".to_owned())),
+            ("amount".to_owned(), ExtractedValue::BigInt(1500)),
+            (
+                "tags".to_owned(),
+                ExtractedValue::TextCollection(vec!["new".to_owned(), "vip".to_owned()]),
+            ),
+        ])
+    }
+
+    fn matches(source: &str) -> bool {
+        FilterExpression::compile(source)
+            .unwrap()
+            .matches(&fields())
+    }
+
+    #[test]
+    fn evaluate_predicates() {
+        assert!(matches(r#"region == "eu""#));
+        assert!(matches(r#"region in ["eu", 'us'] && amount >= 1000"#));
+        assert!(!matches(r#"region in ["us"] || amount < 1000"#));
+        assert!(matches(r#"sku.startsWith("test-") && !sku.endsWith("-1")"#));
+        assert!(matches(r#"upper(region) == "EU" && size(sku) == 7"#));
+        assert!(matches("has(amount) && !has(missing) && missing == null"));
+        assert!(matches("(amount > -1 || false) && true"));
+        assert!(matches(
+            r#""vip" in tags && tags.contains("new") && size(tags) == 2"#
+        ));
+        assert!(!matches(r#"tags.contains("old")"#));
+        // Mismatched types never match
+        assert!(!matches(r#"amount > "1000""#));
+        assert!(!matches("missing > 1"));
+        assert!(!matches("region"));
+    }
+
+    #[test]
+    fn evaluate_shorthand_conditions() {
+        assert!(matches("region=eq:eu"));
+        assert!(matches("region=in:eu|us,amount=gte:1000"));
+        assert!(!matches("region=neq:eu"));
+        assert!(!matches("region=eq:eu,amount=lt:1000"));
+        assert!(matches("amount=eq:1500,tags=neq:'1500'"));
+        assert!(!matches("amount=eq:'1500'"));
+        assert!(matches("sku=gt:test"));
+        assert!(!matches("missing=eq:1"));
+    }
+
+    #[test]
+    fn reject_malformed_expressions() {
+        for source in [
+            "",
+            "region ==",
+            r#"region == "eu"#,
+            "region.unknown()",
+            "has(1)",
+            "size(a, b)",
+            "(amount > 1",
+            "amount > 1 amount",
+            "a & b",
+            "region=like:eu",
+            "region=eq:eu,",
+        ] {
+            assert!(
+                FilterExpression::compile(source).is_err(),
+                "'{source}' should not compile."
+            );
+        }
+    }
+}
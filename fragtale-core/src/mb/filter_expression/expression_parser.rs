@@ -0,0 +1,422 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Parser of filter expressions.
+
+use super::Value;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+
+/// Max length of an expression in bytes.
+const MAX_SOURCE_LENGTH: usize = 4096;
+/// Max nesting of sub-expressions.
+const MAX_DEPTH: usize = 32;
+
+/// Comparison operator.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    In,
+}
+
+/// Built-in function.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Function {
+    Has,
+    Size,
+    Lower,
+    Upper,
+    StartsWith,
+    EndsWith,
+    Contains,
+}
+
+impl Function {
+    /// Return the function callable as `name(value)`.
+    fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "has" => Some(Self::Has),
+            "size" => Some(Self::Size),
+            "lower" => Some(Self::Lower),
+            "upper" => Some(Self::Upper),
+            _ => None,
+        }
+    }
+
+    /// Return the function callable as `value.name(argument)`.
+    fn by_method_name(name: &str) -> Option<Self> {
+        match name {
+            "startsWith" => Some(Self::StartsWith),
+            "endsWith" => Some(Self::EndsWith),
+            "contains" => Some(Self::Contains),
+            _ => None,
+        }
+    }
+}
+
+/// Node of a parsed expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Literal(Value),
+    Field(String),
+    List(Vec<Node>),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Binary(Operator, Box<Node>, Box<Node>),
+    Call(Function, Vec<Node>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Text(String),
+    Int(i64),
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Dot,
+    Not,
+    And,
+    Or,
+    Operator(Operator),
+}
+
+/// Parse the expression `source`.
+pub fn parse(source: &str) -> Result<Node, MessageBrokerError> {
+    if source.len() > MAX_SOURCE_LENGTH {
+        Err(error(&format!("Longer than {MAX_SOURCE_LENGTH} bytes.")))?;
+    }
+    if let Some(node) = parse_shorthand(source) {
+        return Ok(node);
+    }
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        position: 0,
+        depth: 0,
+    };
+    let node = parser.parse_or()?;
+    if let Some(token) = parser.peek() {
+        Err(error(&format!("Unexpected {token:?}.")))?;
+    }
+    Ok(node)
+}
+
+/// Parse comma separated `name=operator:value` conditions that all must match.
+///
+/// The operators are `eq`, `neq`, `lt`, `lte`, `gt`, `gte` and `in` (with `|`
+/// separated values). Values that look like integers match both integer and
+/// text values, unless quoted as text.
+///
+/// Return `None` if `source` is not written in this form.
+fn parse_shorthand(source: &str) -> Option<Node> {
+    source
+        .split(',')
+        .map(parse_shorthand_condition)
+        .reduce(|left, right| Some(Node::And(Box::new(left?), Box::new(right?))))?
+}
+
+fn parse_shorthand_condition(condition: &str) -> Option<Node> {
+    let (name, rest) = condition.trim().split_once('=')?;
+    let (operator, value) = rest.split_once(':')?;
+    let mut chars = name.chars();
+    if !chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+    let field = || Box::new(Node::Field(name.to_owned()));
+    let operator = match operator {
+        "eq" => Operator::Eq,
+        "neq" => Operator::Ne,
+        "lt" => Operator::Lt,
+        "lte" => Operator::Le,
+        "gt" => Operator::Gt,
+        "gte" => Operator::Ge,
+        "in" => {
+            let items = value.split('|').flat_map(shorthand_literals).collect();
+            return Some(Node::Binary(
+                Operator::In,
+                field(),
+                Box::new(Node::List(items)),
+            ));
+        }
+        _ => return None,
+    };
+    let comparisons = shorthand_literals(value)
+        .into_iter()
+        .map(|literal| Node::Binary(operator.clone(), field(), Box::new(literal)));
+    if operator == Operator::Ne {
+        comparisons.reduce(|left, right| Node::And(Box::new(left), Box::new(right)))
+    } else {
+        comparisons.reduce(|left, right| Node::Or(Box::new(left), Box::new(right)))
+    }
+}
+
+/// Return the literals a shorthand `value` could match.
+fn shorthand_literals(value: &str) -> Vec<Node> {
+    let text = Node::Literal(Value::Text(value.to_owned()));
+    for quote in ['"', '\''] {
+        if let Some(quoted) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return vec![Node::Literal(Value::Text(quoted.to_owned()))];
+        }
+    }
+    match value.parse::<i64>() {
+        Ok(number) => vec![Node::Literal(Value::Int(number)), text],
+        Err(_) => vec![text],
+    }
+}
+
+fn error(msg: &str) -> MessageBrokerError {
+    MessageBrokerErrorKind::MalformedRequest
+        .error_with_msg(format!("Invalid filter expression: {msg}"))
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, MessageBrokerError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '[' => Token::LeftBracket,
+            ']' => Token::RightBracket,
+            ',' => Token::Comma,
+            '.' => Token::Dot,
+            '&' if chars.next_if_eq(&'&').is_some() => Token::And,
+            '|' if chars.next_if_eq(&'|').is_some() => Token::Or,
+            '=' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Eq),
+            '!' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Ne),
+            '!' => Token::Not,
+            '<' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Le),
+            '<' => Token::Operator(Operator::Lt),
+            '>' if chars.next_if_eq(&'=').is_some() => Token::Operator(Operator::Ge),
+            '>' => Token::Operator(Operator::Gt),
+            '"' | '\'' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(end) if end == c => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some(escaped @ ('\\' | '"' | '\'')) => text.push(escaped),
+                            _ => Err(error("Unsupported escape sequence in text."))?,
+                        },
+                        Some(other) => text.push(other),
+                        None => Err(error("Unterminated text."))?,
+                    }
+                }
+                Token::Text(text)
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut digits = c.to_string();
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    digits.push(digit);
+                }
+                Token::Int(
+                    digits
+                        .parse()
+                        .map_err(|_| error(&format!("Invalid integer '{digits}'.")))?,
+                )
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut identifier = c.to_string();
+                while let Some(next) =
+                    chars.next_if(|next| next.is_ascii_alphanumeric() || *next == '_')
+                {
+                    identifier.push(next);
+                }
+                if identifier == "in" {
+                    Token::Operator(Operator::In)
+                } else {
+                    Token::Identifier(identifier)
+                }
+            }
+            other => Err(error(&format!("Unexpected character '{other}'.")))?,
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Result<Token, MessageBrokerError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or_else(|| error("Unexpected end of expression."))?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn next_if_eq(&mut self, token: &Token) -> bool {
+        let is_next = self.peek() == Some(token);
+        if is_next {
+            self.position += 1;
+        }
+        is_next
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), MessageBrokerError> {
+        if self.next_if_eq(token) {
+            Ok(())
+        } else {
+            Err(error(&format!("Expected {token:?}.")))
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Node, MessageBrokerError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            Err(error(&format!("Nested deeper than {MAX_DEPTH} levels.")))?;
+        }
+        let mut node = self.parse_and()?;
+        while self.next_if_eq(&Token::Or) {
+            node = Node::Or(Box::new(node), Box::new(self.parse_and()?));
+        }
+        self.depth -= 1;
+        Ok(node)
+    }
+
+    fn parse_and(&mut self) -> Result<Node, MessageBrokerError> {
+        let mut node = self.parse_not()?;
+        while self.next_if_eq(&Token::And) {
+            node = Node::And(Box::new(node), Box::new(self.parse_not()?));
+        }
+        Ok(node)
+    }
+
+    fn parse_not(&mut self) -> Result<Node, MessageBrokerError> {
+        if self.next_if_eq(&Token::Not) {
+            self.depth += 1;
+            if self.depth > MAX_DEPTH {
+                Err(error(&format!("Nested deeper than {MAX_DEPTH} levels.")))?;
+            }
+            let node = Node::Not(Box::new(self.parse_not()?));
+            self.depth -= 1;
+            return Ok(node);
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Node, MessageBrokerError> {
+        let left = self.parse_postfix()?;
+        if let Some(Token::Operator(operator)) = self.peek().cloned() {
+            self.position += 1;
+            let right = self.parse_postfix()?;
+            return Ok(Node::Binary(operator, Box::new(left), Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn parse_postfix(&mut self) -> Result<Node, MessageBrokerError> {
+        let mut node = self.parse_primary()?;
+        while self.next_if_eq(&Token::Dot) {
+            let Token::Identifier(name) = self.next()? else {
+                Err(error("Expected method name after '.'."))?
+            };
+            let function = Function::by_method_name(&name)
+                .ok_or_else(|| error(&format!("Unknown method '{name}'.")))?;
+            let mut arguments = vec![node];
+            arguments.extend(self.parse_arguments()?);
+            if arguments.len() != 2 {
+                Err(error(&format!("Method '{name}' takes one argument.")))?;
+            }
+            node = Node::Call(function, arguments);
+        }
+        Ok(node)
+    }
+
+    fn parse_arguments(&mut self) -> Result<Vec<Node>, MessageBrokerError> {
+        self.expect(&Token::LeftParen)?;
+        self.parse_items(&Token::RightParen)
+    }
+
+    /// Parse comma separated expressions until the `end` token.
+    fn parse_items(&mut self, end: &Token) -> Result<Vec<Node>, MessageBrokerError> {
+        let mut items = Vec::new();
+        if self.next_if_eq(end) {
+            return Ok(items);
+        }
+        loop {
+            items.push(self.parse_or()?);
+            if self.next_if_eq(end) {
+                return Ok(items);
+            }
+            self.expect(&Token::Comma)?;
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Node, MessageBrokerError> {
+        match self.next()? {
+            Token::Text(text) => Ok(Node::Literal(Value::Text(text))),
+            Token::Int(number) => Ok(Node::Literal(Value::Int(number))),
+            Token::LeftParen => {
+                let node = self.parse_or()?;
+                self.expect(&Token::RightParen)?;
+                Ok(node)
+            }
+            Token::LeftBracket => Ok(Node::List(self.parse_items(&Token::RightBracket)?)),
+            Token::Identifier(name) => match name.as_str() {
+                "true" => Ok(Node::Literal(Value::Bool(true))),
+                "false" => Ok(Node::Literal(Value::Bool(false))),
+                "null" => Ok(Node::Literal(Value::Null)),
+                _ if self.peek() == Some(&Token::LeftParen) => {
+                    let function = Function::by_name(&name)
+                        .ok_or_else(|| error(&format!("Unknown function '{name}'.")))?;
+                    let arguments = self.parse_arguments()?;
+                    match (&function, arguments.as_slice()) {
+                        (Function::Has, [Node::Field(_)]) => {}
+                        (Function::Has, _) => {
+                            Err(error("Function 'has' takes the name of a value."))?
+                        }
+                        (_, [_]) => {}
+                        _ => Err(error(&format!("Function '{name}' takes one argument.")))?,
+                    }
+                    Ok(Node::Call(function, arguments))
+                }
+                _ => Ok(Node::Field(name)),
+            },
+            token => Err(error(&format!("Unexpected {token:?}."))),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Compiled filter expressions cached per topic.
+
+use super::filter_expression::FilterExpression;
+use crossbeam_skiplist::SkipMap;
+use fragtale_dbp::mb::MessageBrokerError;
+use std::sync::Arc;
+
+/** Compiled filter expressions cached per topic.
+
+Consumers tend to use the same few expressions for every event, so compiling
+on each delivery would be wasted work. When a topic has more than
+[Self::MAX_EXPRESSIONS_PER_TOPIC] distinct expressions, the cached expressions
+of the topic are dropped and compiled again on demand.
+*/
+#[derive(Default)]
+pub struct FilterExpressionCache {
+    by_topic: SkipMap<String, SkipMap<String, Arc<FilterExpression>>>,
+}
+
+impl FilterExpressionCache {
+    /// Max number of distinct cached expressions per topic.
+    const MAX_EXPRESSIONS_PER_TOPIC: usize = 256;
+
+    /// Return a new instance.
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Return the compiled expression `source` for use in the topic.
+    pub fn get_or_compile(
+        &self,
+        topic_id: &str,
+        source: &str,
+    ) -> Result<Arc<FilterExpression>, MessageBrokerError> {
+        let entry = self
+            .by_topic
+            .get_or_insert_with(topic_id.to_owned(), SkipMap::default);
+        let expressions = entry.value();
+        if let Some(expression_entry) = expressions.get(source) {
+            return Ok(Arc::clone(expression_entry.value()));
+        }
+        let filter_expression = Arc::new(FilterExpression::compile(source)?);
+        if expressions.len() >= Self::MAX_EXPRESSIONS_PER_TOPIC {
+            expressions.clear();
+        }
+        expressions.insert(source.to_owned(), Arc::clone(&filter_expression));
+        Ok(filter_expression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuse_compiled_expressions() {
+        let cache = FilterExpressionCache::new();
+        let first = cache.get_or_compile("topic", "amount > 1").unwrap();
+        let second = cache.get_or_compile("topic", "amount > 1").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        let other_topic = cache.get_or_compile("other", "amount > 1").unwrap();
+        assert!(!Arc::ptr_eq(&first, &other_topic));
+        assert!(cache.get_or_compile("topic", "amount >").is_err());
+        for i in 0..=FilterExpressionCache::MAX_EXPRESSIONS_PER_TOPIC {
+            cache
+                .get_or_compile("topic", &format!("amount > {i}"))
+                .unwrap();
+        }
+        assert!(
+            cache.by_topic.get("topic").unwrap().value().len()
+                <= FilterExpressionCache::MAX_EXPRESSIONS_PER_TOPIC
+        );
+    }
+}
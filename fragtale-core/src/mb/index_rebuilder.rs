@@ -0,0 +1,342 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Online rebuild of the indexed columns of existing events.
+
+use crate::mb::leader_election::LeaderElection;
+use crate::mb::pre_storage_processor::PreStorageProcessor;
+use crossbeam_skiplist::SkipMap;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::IndexRebuildProgress;
+use fragtale_dbp::mb::UniqueTime;
+use futures::StreamExt;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/** Online rebuild of the indexed columns of existing events.
+
+When an extractor is added or its type or semantics change, events that were
+published before the change lack the new or corrected indexed columns. An
+admin-triggered rebuild re-runs the extractors of the latest event descriptor
+over all events of the topic that were published before the request, without
+republishing them.
+
+The elected background worker walks the buckets of the topic and persists its
+position after each bucket, so a rebuild is resumed by the next elected
+instance. Queries over the index keep working during the rebuild and become
+complete once it has finished.
+*/
+pub struct IndexRebuilder {
+    dbp: Arc<DatabaseProvider>,
+    pre_storage_processor: Arc<PreStorageProcessor>,
+    /// Topics with a rebuild that might not have completed.
+    pending_topics: SkipMap<String, ()>,
+    /// Last discovery of all topics in epoch microseconds.
+    discovered_ts: AtomicU64,
+}
+
+impl IndexRebuilder {
+    /// Time between looking for rebuilds to make progress on.
+    const REBUILD_INTERVAL_MICROS: u64 = 10_000_000;
+    /// Time between discovering rebuilds of all topics.
+    const DISCOVERY_INTERVAL_MICROS: u64 = 60_000_000;
+    /// Time after the request where events might still have been published
+    /// using a stale event descriptor on other instances.
+    const SETTLE_MICROS: u64 = 60_000_000;
+    /// Number of buckets requested from the database at a time.
+    const BUCKETS_PAGE_SIZE: usize = 32;
+    /// Number of bucket entries requested from the database at a time.
+    const ENTRIES_PAGE_SIZE: usize = 256;
+
+    /// Return a new instance.
+    ///
+    /// Rebuilds only make progress when `leader_election` is present and
+    /// this instance is elected.
+    pub fn new(
+        dbp: &Arc<DatabaseProvider>,
+        pre_storage_processor: &Arc<PreStorageProcessor>,
+        leader_election: &Option<Arc<LeaderElection>>,
+    ) -> Arc<Self> {
+        let ret = Arc::new(Self {
+            dbp: Arc::clone(dbp),
+            pre_storage_processor: Arc::clone(pre_storage_processor),
+            pending_topics: SkipMap::default(),
+            discovered_ts: AtomicU64::new(0),
+        });
+        if let Some(leader_election) = leader_election {
+            let self_clone = Arc::clone(&ret);
+            let leader_election = Arc::clone(leader_election);
+            tokio::spawn(async move { self_clone.run_rebuilds(&leader_election).await });
+        }
+        ret
+    }
+
+    /// Return the progress of the latest rebuild of the topic (if any).
+    pub async fn get_progress(&self, topic_id: &str) -> Option<IndexRebuildProgress> {
+        self.dbp
+            .topic_facade()
+            .index_rebuild_by_topic_id(topic_id)
+            .await
+    }
+
+    /// Request a rebuild of the indexed columns of all events published to
+    /// the topic until now.
+    ///
+    /// A rebuild in progress is restarted from the oldest event.
+    pub async fn start(&self, topic_id: &str) -> Option<IndexRebuildProgress> {
+        let index_rebuild_progress =
+            IndexRebuildProgress::new(fragtale_client::time::get_timestamp_micros(), 0, 0, None);
+        if !self
+            .dbp
+            .topic_facade()
+            .index_rebuild_persist(topic_id, index_rebuild_progress)
+            .await
+        {
+            log::warn!("Failed to persist index rebuild of '{topic_id}'.");
+            return None;
+        }
+        self.pending_topics.insert(topic_id.to_owned(), ());
+        Some(index_rebuild_progress)
+    }
+
+    /// Return the time in epoch microseconds up to which events are covered
+    /// by the rebuild.
+    pub fn covered_until_ts_micros(index_rebuild_progress: &IndexRebuildProgress) -> u64 {
+        index_rebuild_progress.get_requested_ts_micros() + Self::SETTLE_MICROS
+    }
+
+    /// Return `true` if the rebuild has not completed and all events it
+    /// covers have been published at `now`.
+    fn is_due(index_rebuild_progress: &IndexRebuildProgress, now: u64) -> bool {
+        index_rebuild_progress.get_completed_ts_micros().is_none()
+            && Self::covered_until_ts_micros(index_rebuild_progress) < now
+    }
+
+    /// Make progress on rebuilds of all topics while this instance is the
+    /// elected background worker.
+    async fn run_rebuilds(&self, leader_election: &LeaderElection) {
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_micros(
+                Self::REBUILD_INTERVAL_MICROS,
+            ))
+            .await;
+            if !leader_election.is_leader().await {
+                continue;
+            }
+            let now = fragtale_client::time::get_timestamp_micros();
+            if self.discovered_ts.load(Ordering::Relaxed) + Self::DISCOVERY_INTERVAL_MICROS < now {
+                self.discovered_ts.store(now, Ordering::Relaxed);
+                let mut from = None;
+                loop {
+                    let (topic_ids, more) = self.dbp.topic_facade().get_topic_ids(&from).await;
+                    from = topic_ids.last().cloned();
+                    for topic_id in topic_ids {
+                        if self
+                            .get_progress(&topic_id)
+                            .await
+                            .is_some_and(|progress| progress.get_completed_ts_micros().is_none())
+                        {
+                            self.pending_topics.insert(topic_id, ());
+                        }
+                    }
+                    if !more {
+                        break;
+                    }
+                }
+            }
+            let topic_ids = self
+                .pending_topics
+                .iter()
+                .map(|entry| entry.key().to_owned())
+                .collect::<Vec<_>>();
+            for topic_id in topic_ids {
+                let now = fragtale_client::time::get_timestamp_micros();
+                match self.get_progress(&topic_id).await {
+                    Some(progress) if Self::is_due(&progress, now) => {
+                        if self.rebuild(&topic_id, progress, leader_election).await {
+                            self.pending_topics.remove(&topic_id);
+                        }
+                    }
+                    Some(progress) if progress.get_completed_ts_micros().is_none() => {}
+                    _ => {
+                        self.pending_topics.remove(&topic_id);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Populate the indexed columns of all events covered by the rebuild,
+    /// starting after the persisted position.
+    ///
+    /// Return `true` if the rebuild completed.
+    async fn rebuild(
+        &self,
+        topic_id: &str,
+        index_rebuild_progress: IndexRebuildProgress,
+        leader_election: &LeaderElection,
+    ) -> bool {
+        let start_ts = fragtale_client::time::get_timestamp_micros();
+        let requested_ts_micros = index_rebuild_progress.get_requested_ts_micros();
+        let high = UniqueTime::new(
+            Self::covered_until_ts_micros(&index_rebuild_progress),
+            UniqueTime::MAX_INSTANCE_ID,
+        );
+        let position = UniqueTime::from(index_rebuild_progress.get_position());
+        let mut rebuilt_count = index_rebuild_progress.get_rebuilt_count();
+        log::info!(
+            "Rebuilding indexed columns of '{topic_id}' from position {}.",
+            position.as_encoded()
+        );
+        let mut current_bucket =
+            (index_rebuild_progress.get_position() > 0).then(|| position.get_bucket());
+        'shelves: for shelf in position.get_shelf()..=high.get_shelf() {
+            loop {
+                let (buckets, more) = self
+                    .dbp
+                    .event_facade()
+                    .buckets_by_shelf(topic_id, shelf, current_bucket, Self::BUCKETS_PAGE_SIZE)
+                    .await;
+                for bucket in buckets {
+                    if bucket > high.get_bucket() {
+                        break 'shelves;
+                    }
+                    // Leave the rest to the next elected instance or a restarted rebuild
+                    if !leader_election.is_leader().await
+                        || self
+                            .get_progress(topic_id)
+                            .await
+                            .map(|progress| progress.get_requested_ts_micros())
+                            != Some(requested_ts_micros)
+                    {
+                        return false;
+                    }
+                    let mut entries = self.dbp.event_facade().events_by_bucket_stream(
+                        topic_id,
+                        bucket,
+                        None,
+                        Self::ENTRIES_PAGE_SIZE,
+                    );
+                    while let Some((unique_time, event_id, _descriptor_version)) =
+                        entries.next().await
+                    {
+                        if unique_time > high {
+                            break;
+                        }
+                        if self.rebuild_event(topic_id, &event_id, unique_time).await {
+                            rebuilt_count += 1;
+                        }
+                    }
+                    self.dbp
+                        .topic_facade()
+                        .index_rebuild_persist(
+                            topic_id,
+                            IndexRebuildProgress::new(
+                                requested_ts_micros,
+                                UniqueTime::max_encoded_in_bucket(bucket),
+                                rebuilt_count,
+                                None,
+                            ),
+                        )
+                        .await;
+                    current_bucket = Some(bucket);
+                }
+                if !more {
+                    break;
+                }
+            }
+            current_bucket = None;
+        }
+        let completed = self
+            .dbp
+            .topic_facade()
+            .index_rebuild_persist(
+                topic_id,
+                IndexRebuildProgress::new(
+                    requested_ts_micros,
+                    high.as_encoded(),
+                    rebuilt_count,
+                    Some(fragtale_client::time::get_timestamp_micros()),
+                ),
+            )
+            .await;
+        log::info!(
+            "Rebuilt indexed columns of {rebuilt_count} events of '{topic_id}' in {} micros.",
+            fragtale_client::time::get_timestamp_micros() - start_ts
+        );
+        completed
+    }
+
+    /// Populate the indexed columns of a single event.
+    ///
+    /// Return `true` if there was anything to populate.
+    async fn rebuild_event(&self, topic_id: &str, event_id: &str, unique_time: UniqueTime) -> bool {
+        let Some(event_delivery_gist) = self
+            .dbp
+            .event_facade()
+            .event_by_id_and_unique_time(topic_id, event_id, unique_time)
+            .await
+        else {
+            return false;
+        };
+        let additional_columns = match self
+            .pre_storage_processor
+            .extract_using_latest(topic_id, event_delivery_gist.get_document())
+            .await
+        {
+            Ok(additional_columns) => additional_columns,
+            Err(e) => {
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!(
+                        "Unable to extract indexed columns of event '{event_id}' in '{topic_id}': {e}"
+                    );
+                }
+                return false;
+            }
+        };
+        if additional_columns.is_empty() {
+            return false;
+        }
+        let applied = self
+            .dbp
+            .event_facade()
+            .event_index_columns_update(topic_id, event_id, unique_time, additional_columns)
+            .await;
+        if !applied {
+            log::warn!("Failed to populate indexed columns of event '{event_id}' in '{topic_id}'.");
+        }
+        applied
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebuild_is_due_once_covered_events_are_published() {
+        let requested_ts_micros = 1_000_000;
+        let progress = IndexRebuildProgress::new(requested_ts_micros, 0, 0, None);
+        let covered_until = IndexRebuilder::covered_until_ts_micros(&progress);
+        assert!(!IndexRebuilder::is_due(&progress, covered_until));
+        assert!(IndexRebuilder::is_due(&progress, covered_until + 1));
+        let completed = IndexRebuildProgress::new(requested_ts_micros, 0, 0, Some(covered_until));
+        assert!(!IndexRebuilder::is_due(&completed, covered_until + 1));
+    }
+}
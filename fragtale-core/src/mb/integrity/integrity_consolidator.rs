@@ -21,7 +21,10 @@ use super::IntegrityProtector;
 use super::IntegrityValidator;
 use super::common::IntegrityProtection;
 use super::common::IntegritySecretsHolder;
-use crate::mb::unique_time_stamper::UniqueTimeStamper;
+use crate::mb::bulk_ingest::BulkIngest;
+use crate::mb::leader_election::LeaderElection;
+use crate::mb::task_supervisor::TaskHeartbeat;
+use crate::mb::task_supervisor::TaskSupervisor;
 use fragtale_dbp::dbp::DatabaseProvider;
 use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
 use futures::StreamExt;
@@ -34,36 +37,51 @@ pub struct IntegrityConsolidationService {
     dbp: Arc<DatabaseProvider>,
     protector: Arc<IntegrityProtector>,
     validator: Arc<IntegrityValidator>,
-    unique_timer_stamper: Arc<UniqueTimeStamper>,
+    leader_election: Arc<LeaderElection>,
+    bulk_ingest: Arc<BulkIngest>,
 }
 
 impl IntegrityConsolidationService {
+    /// Max time between heartbeats of the consolidation task.
+    const MAX_SILENCE_MICROS: u64 = 600_000_000;
+
     /// Return a new instance.
     pub async fn new(
         integrity_secrets_holder: &Arc<IntegritySecretsHolder>,
         dbp: &Arc<DatabaseProvider>,
         integrity_protector: &Arc<IntegrityProtector>,
         integrity_validator: &Arc<IntegrityValidator>,
-        unique_timer_stamper: &Arc<UniqueTimeStamper>,
+        leader_election: &Arc<LeaderElection>,
+        bulk_ingest: &Arc<BulkIngest>,
+        task_supervisor: &Arc<TaskSupervisor>,
     ) -> Arc<Self> {
         Arc::new(Self {
             ish: Arc::clone(integrity_secrets_holder),
             dbp: Arc::clone(dbp),
             protector: Arc::clone(integrity_protector),
             validator: Arc::clone(integrity_validator),
-            unique_timer_stamper: Arc::clone(unique_timer_stamper),
+            leader_election: Arc::clone(leader_election),
+            bulk_ingest: Arc::clone(bulk_ingest),
         })
-        .run()
+        .run(task_supervisor)
         .await
     }
 
-    async fn run(self: Arc<Self>) -> Arc<Self> {
+    async fn run(self: Arc<Self>, task_supervisor: &Arc<TaskSupervisor>) -> Arc<Self> {
         let self_clone = Arc::clone(&self);
-        tokio::spawn(async move { self_clone.run_update_and_consolidation().await });
+        task_supervisor.spawn(
+            "integrity_consolidation",
+            "integrity_consolidation".to_owned(),
+            Self::MAX_SILENCE_MICROS,
+            move |heartbeat| {
+                let self_clone = Arc::clone(&self_clone);
+                async move { self_clone.run_update_and_consolidation(&heartbeat).await }
+            },
+        );
         self
     }
 
-    async fn run_update_and_consolidation(&self) {
+    async fn run_update_and_consolidation(&self, heartbeat: &TaskHeartbeat) {
         // If this is the oldest instance
         //  -> all nodes are using the new secret for new events from now on
         //  -> after the current level 1 interval is over, it is safe to regen secret again
@@ -71,8 +89,9 @@ impl IntegrityConsolidationService {
         let mut notified = false;
         let mut has_run_secret_validation = false;
         loop {
-            // Is this the lowest claimed instance id?
-            if self.unique_timer_stamper.is_oldest_instance().await {
+            heartbeat.beat();
+            // Is this the elected instance for background work?
+            if self.leader_election.is_leader().await {
                 if log::log_enabled!(log::Level::Trace) {
                     log::trace!("I will be running consolidation service.");
                 }
@@ -86,13 +105,15 @@ impl IntegrityConsolidationService {
                     // Priority number #1 check if current secret has changed and update all if so
                     if !has_run_secret_validation {
                         has_run_secret_validation = true;
-                        self.run_integrity_protection_update(&topics).await;
+                        self.run_integrity_protection_update(&topics, heartbeat)
+                            .await;
                     }
                     if from.is_none() {
                         pre_consolidation_ts_micros = fragtale_client::time::get_timestamp_micros();
                     }
                     // Priority number #2 Start consolidation
                     for topic_id in &topics {
+                        heartbeat.beat();
                         self.run_consolidation_for_topic(topic_id).await
                     }
                     if !more {
@@ -114,102 +135,101 @@ impl IntegrityConsolidationService {
         }
     }
 
-    async fn run_integrity_protection_update(&self, topics: &[String]) {
+    async fn run_integrity_protection_update(&self, topics: &[String], heartbeat: &TaskHeartbeat) {
         // Even if a switch is used in the Helm deploy to regen, there should still
         // be a time limit safety of 5 minutes to avoid having to rewrite level L0.
         //let (current_oid, current_secret, _ts) = self.app_config.integrity.current_secret();
         //let (previous_oid, previous_secret) = self.app_config.integrity.previous_secret();
         let mut update_count = 0;
         for topic_id in topics {
+            heartbeat.beat();
             // for L2, check all buckets
             // for L1, only check buckets not covered by L2
             let mut from_protections_ts_micros = 0;
             for level in (1..=2).rev() {
-                let mut result_count = usize::MAX;
-                while result_count > 0 {
-                    let results = self
-                        .dbp
-                        .integrity_protection_facade()
-                        .integrity_batch_in_interval_by_level_and_time(
-                            topic_id,
-                            level,
-                            from_protections_ts_micros,
-                            1024,
-                        )
-                        .await;
-                    result_count = results.len();
-                    for (protection_id, protection_ts_micros, protection_data, protection_ref) in
-                        results
-                    {
-                        from_protections_ts_micros = protection_ts_micros;
-                        if protection_ref.is_some() {
-                            // Skip this, since it apparently had another layer of protection
-                            continue;
-                        }
-                        // Be crash tolerant and don't make assumptions here based on when something was protected
-                        if let Ok(ip) = IntegrityProtection::from_string(&protection_data) {
-                            if ip.get_protected_hash().to_hex().eq(&protection_id) {
-                                // Validate protection_data using current secret
+                let mut results = self
+                    .dbp
+                    .integrity_protection_facade()
+                    .integrity_stream_in_interval_by_level_and_time(
+                        topic_id,
+                        level,
+                        from_protections_ts_micros,
+                        1024,
+                    );
+                while let Some((
+                    protection_id,
+                    protection_ts_micros,
+                    protection_data,
+                    protection_ref,
+                )) = results.next().await
+                {
+                    from_protections_ts_micros = protection_ts_micros + 1;
+                    if protection_ref.is_some() {
+                        // Skip this, since it apparently had another layer of protection
+                        continue;
+                    }
+                    // Be crash tolerant and don't make assumptions here based on when something was protected
+                    if let Ok(ip) = IntegrityProtection::from_string(&protection_data) {
+                        if ip.get_protected_hash().to_hex().eq(&protection_id) {
+                            // Validate protection_data using current secret
+                            if ip
+                                .validate_current(
+                                    self.ish.get_current_oid(),
+                                    self.ish.get_current_secret(),
+                                )
+                                .is_err()
+                            {
+                                //  if validation fails, check with previous secret
                                 if ip
                                     .validate_current(
+                                        self.ish.get_previous_oid(),
+                                        self.ish.get_previous_secret(),
+                                    )
+                                    .is_ok()
+                                {
+                                    // Reprotect with current secret (only)
+                                    let previous_oid = [];
+                                    let previous_secret = [];
+                                    let new_protection_data = IntegrityProtection::protect(
+                                        ip.get_protected_hash(),
                                         self.ish.get_current_oid(),
                                         self.ish.get_current_secret(),
+                                        &previous_oid,
+                                        &previous_secret,
                                     )
-                                    .is_err()
-                                {
-                                    //  if validation fails, check with previous secret
-                                    if ip
-                                        .validate_current(
-                                            self.ish.get_previous_oid(),
-                                            self.ish.get_previous_secret(),
-                                        )
-                                        .is_ok()
-                                    {
-                                        // Reprotect with current secret (only)
-                                        let previous_oid = [];
-                                        let previous_secret = [];
-                                        let new_protection_data = IntegrityProtection::protect(
-                                            ip.get_protected_hash(),
-                                            self.ish.get_current_oid(),
-                                            self.ish.get_current_secret(),
-                                            &previous_oid,
-                                            &previous_secret,
-                                        )
-                                        .unwrap()
-                                        .as_string();
-                                        // Persist
-                                        // Keep original protection_ts_micros since it used for bucketing during consolidation.
-                                        self.dbp
-                                            .integrity_protection_facade()
-                                            .integrity_protection_persist(
-                                                topic_id,
-                                                &protection_id,
-                                                &new_protection_data,
-                                                protection_ts_micros,
-                                                level,
-                                            )
-                                            .await;
-                                        update_count += 1;
-                                    } else {
-                                        // Err: Not able to verify
-                                        log::error!(
-                                            "Unable to verify event using current or previous secret. level: {level}, protection_id: '{protection_id}', protection_ts_micros: {protection_ts_micros}"
+                                    .unwrap()
+                                    .as_string();
+                                    // Persist
+                                    // Keep original protection_ts_micros since it used for bucketing during consolidation.
+                                    self.dbp
+                                        .integrity_protection_facade()
+                                        .integrity_protection_persist(
+                                            topic_id,
+                                            &protection_id,
+                                            &new_protection_data,
+                                            protection_ts_micros,
+                                            level,
                                         )
-                                    }
+                                        .await;
+                                    update_count += 1;
+                                } else {
+                                    // Err: Not able to verify
+                                    log::error!(
+                                        "Unable to verify event using current or previous secret. level: {level}, protection_id: '{protection_id}', protection_ts_micros: {protection_ts_micros}"
+                                    )
                                 }
-                            } else {
-                                // Err
-                                log::error!(
-                                    "Found protection data is for another protection_id. level: {level}, protection_id: '{protection_id}', protection_ts_micros: {protection_ts_micros}"
-                                )
                             }
                         } else {
+                            // Err
                             log::error!(
-                                "Unable to parse protection data. level: {level}, protection_id: '{protection_id}', protection_ts_micros: {protection_ts_micros}"
+                                "Found protection data is for another protection_id. level: {level}, protection_id: '{protection_id}', protection_ts_micros: {protection_ts_micros}"
                             )
                         }
+                    } else {
+                        log::error!(
+                            "Unable to parse protection data. level: {level}, protection_id: '{protection_id}', protection_ts_micros: {protection_ts_micros}"
+                        )
                     }
-                    from_protections_ts_micros += 1;
                 }
             }
         }
@@ -217,6 +237,13 @@ impl IntegrityConsolidationService {
     }
 
     async fn run_consolidation_for_topic(&self, topic_id: &str) {
+        // Consolidate once the bulk ingest is over instead of competing with it
+        if self.bulk_ingest.is_deferring(topic_id).await {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!("Deferring consolidation of '{topic_id}' during bulk ingest.");
+            }
+            return;
+        }
         // This grabs stuff in at least 4 min intervals.. → no microsec race condition..
         for level_in in 0..=1 {
             // Grab latest protection of `level_out` by doing lookup on `topic.integrity_blat_lookup` and `topic.integrity_by_level_and_time`.
@@ -238,61 +265,52 @@ impl IntegrityConsolidationService {
                 // Batch grab all (protection_id, protections_ts_micros) into mem
                 //  (IntegrityByLevelAndTimeEntity is ordered by `protections_ts_micros`)
                 let mut members: Vec<(String, u64)> = vec![];
-                let mut from_protections_ts_micros = lookup_ts_bucket;
-                let mut result_count = usize::MAX;
-                while result_count > 0 {
-                    let results = self
-                        .dbp
-                        .integrity_protection_facade()
-                        .integrity_batch_in_interval_by_level_and_time(
-                            topic_id,
-                            level_in,
-                            from_protections_ts_micros,
-                            1024,
-                        )
-                        .await;
-                    result_count = results.len();
-                    if log::log_enabled!(log::Level::Trace) {
-                        log::trace!(
-                            "Found {result_count} result in topic_id: {topic_id}, level_in: {level_in}, lookup_ts_bucket: {lookup_ts_bucket}, from_protections_ts_micros: {from_protections_ts_micros}."
+                let mut results = self
+                    .dbp
+                    .integrity_protection_facade()
+                    .integrity_stream_in_interval_by_level_and_time(
+                        topic_id,
+                        level_in,
+                        lookup_ts_bucket,
+                        1024,
+                    );
+                while let Some((
+                    protection_id,
+                    protection_ts_micros,
+                    protection_data,
+                    protection_ref,
+                )) = results.next().await
+                {
+                    if protection_ref.is_some() {
+                        log::warn!(
+                            "Trying to consolidate something that already has a integrirty protection reference. (Unexpected)"
                         );
+                        // Skip inclusion
+                        continue;
                     }
-                    for (protection_id, protection_ts_micros, protection_data, protection_ref) in
-                        results
+                    // Validate protection before including it
+                    if let Ok(ip) =
+                        IntegrityProtection::from_string(&protection_data).map_err(|e| {
+                            log::warn!("IntegrityProtection::from_string: {e}");
+                        })
                     {
-                        if protection_ref.is_some() {
+                        if self
+                            .validator
+                            .is_valid_integrity_protection(
+                                protection_ts_micros,
+                                &tyst::encdec::hex::decode(&protection_id).unwrap(),
+                                &ip,
+                            )
+                            .await
+                        {
+                            members.push((protection_id, protection_ts_micros));
+                        } else {
+                            // Skip inclusion
                             log::warn!(
-                                "Trying to consolidate something that already has a integrirty protection reference. (Unexpected)"
+                                "Validation failed for topic_id: {topic_id}, level_in: {level_in}, lookup_ts_bucket: {lookup_ts_bucket}, protection_ts_micros: {protection_ts_micros}."
                             );
-                            // Skip inclusion
-                            continue;
-                        }
-                        // Validate protection before including it
-                        if let Ok(ip) =
-                            IntegrityProtection::from_string(&protection_data).map_err(|e| {
-                                log::warn!("IntegrityProtection::from_string: {e}");
-                            })
-                        {
-                            if self
-                                .validator
-                                .is_valid_integrity_protection(
-                                    protection_ts_micros,
-                                    &tyst::encdec::hex::decode(&protection_id).unwrap(),
-                                    &ip,
-                                )
-                                .await
-                            {
-                                members.push((protection_id, protection_ts_micros));
-                            } else {
-                                // Skip inclusion
-                                log::warn!(
-                                    "Validation failed for topic_id: {topic_id}, level_in: {level_in}, lookup_ts_bucket: {lookup_ts_bucket}, from_protections_ts_micros: {from_protections_ts_micros}."
-                                );
-                            }
                         }
-                        from_protections_ts_micros = protection_ts_micros;
                     }
-                    from_protections_ts_micros += 1;
                 }
                 if log::log_enabled!(log::Level::Trace) {
                     log::trace!(
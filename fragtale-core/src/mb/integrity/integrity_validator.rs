@@ -39,18 +39,24 @@ protected using 1 or more shared secrets ([IntegrityProtection]).
 Validation will ensure that the protected data belongs to the group and that
 the group protection is valid.
 
-To improve performance, valid group protections will be cached.
+To improve performance, each validated group protection (on every level) is
+cached by its protection identifier and timestamp. Repeated validations of the
+same event, or of other members of an already validated group, will then not
+require any protection lookups.
 */
 pub struct IntegrityValidator {
     dbp: Arc<DatabaseProvider>,
     unique_timer_stamper: Arc<UniqueTimeStamper>,
-    caching_filter: Arc<LocklessCachingFilter>,
+    validated_protections: Arc<LocklessCachingFilter>,
     instance_start_ts: u64,
     allowed_digest_algorithm_oids: Vec<Vec<u32>>,
     ish: Arc<IntegritySecretsHolder>,
 }
 
 impl IntegrityValidator {
+    /// Target maximum number of cached validated group protections.
+    const VALIDATED_PROTECTIONS_CACHE_SIZE: u64 = 8192;
+
     /// Return a new instance.
     pub fn new(
         integrity_secrets_holder: &Arc<IntegritySecretsHolder>,
@@ -61,7 +67,9 @@ impl IntegrityValidator {
         Arc::new(Self {
             dbp: Arc::clone(dbp),
             unique_timer_stamper: Arc::clone(unique_timer_stamper),
-            caching_filter: LocklessCachingFilter::new(100),
+            validated_protections: LocklessCachingFilter::new(
+                Self::VALIDATED_PROTECTIONS_CACHE_SIZE,
+            ),
             instance_start_ts,
             allowed_digest_algorithm_oids: Self::derive_allowed_digest_algos_from_protection(&[
                 integrity_secrets_holder.get_current_oid(),
@@ -115,6 +123,11 @@ impl IntegrityValidator {
         result
     }
 
+    /// Return the validated protections cache key of a group protection.
+    fn validated_protection_key(root_hash: &[u8], protection_ts_micros: u64) -> Vec<u8> {
+        [root_hash, &protection_ts_micros.to_be_bytes()].concat()
+    }
+
     /// Validate the integrity of the member using configured shared secret(s).
     async fn validate_protection_of_member(
         &self,
@@ -124,15 +137,20 @@ impl IntegrityValidator {
     ) -> bool {
         let mut member = member.to_vec();
         let mut ipr_opt = Some(ipr);
+        // Group protections on the path that are valid if the final level is
+        let mut visited_keys = vec![];
         while let Some(ipr) = ipr_opt {
             if let Ok((protection_ts_micros, root_hash)) =
                 ipr.get_integrity_protection_reference(&member).map_err(|e|{
                     log::info!("Failed to get integrity protection reference (root hash) from BDT member (hash over protected): {e}");
                 })
             {
-                if self.caching_filter.contains(&root_hash) {
+                let key = Self::validated_protection_key(&root_hash, protection_ts_micros);
+                if self.validated_protections.contains(&key) {
+                    self.insert_validated_protections(&visited_keys).await;
                     return true;
                 }
+                visited_keys.push(key);
                 let protecion_id = root_hash.to_hex();
                 if let Some((protection_data, protection_ref)) = self
                     .dbp
@@ -158,7 +176,7 @@ impl IntegrityValidator {
                             if log::log_enabled!(log::Level::Trace) {
                                 log::trace!("Validation of event in '{topic_id}' was successful.");
                             }
-                            self.caching_filter.insert(&root_hash).await;
+                            self.insert_validated_protections(&visited_keys).await;
                             return true;
                         } else {
                             log::info!("Validation of event in '{topic_id}' failed.");
@@ -175,6 +193,13 @@ impl IntegrityValidator {
         false
     }
 
+    /// Remember the group protections as validated.
+    async fn insert_validated_protections(&self, keys: &[Vec<u8>]) {
+        for key in keys {
+            self.validated_protections.insert(key).await;
+        }
+    }
+
     /// Validate [IntegrityProtection] using currently deployed secrets.
     pub async fn is_valid_integrity_protection(
         &self,
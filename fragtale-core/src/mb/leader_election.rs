@@ -0,0 +1,131 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Election of a single instance to perform a role.
+
+use crate::mb::unique_time_stamper::UniqueTimeStamper;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use tokio::time::Duration;
+use tokio::time::sleep;
+
+/** Election of a single instance to perform a role.
+
+Each instance that takes part registers its claimed instance identifier as a
+candidate for the role. The candidate that registered first and is still
+alive is the leader.
+
+Registrations expire unless refreshed, so a crashed leader is replaced
+within [LeaderElection::CLAIM_TIME_TO_LIVE_SECONDS].
+*/
+pub struct LeaderElection {
+    dbp: Arc<DatabaseProvider>,
+    role: &'static str,
+    instance_id: u16,
+    resigned: AtomicBool,
+}
+
+impl LeaderElection {
+    /// Role of the instance performing background work like integrity
+    /// consolidation.
+    pub const ROLE_BACKGROUND_WORK: &str = "background";
+    /// Time that a candidate registration is valid without a refresh.
+    const CLAIM_TIME_TO_LIVE_SECONDS: u32 = 60;
+    /// Interval between refreshes of the candidate registration.
+    const REFRESH_INTERVAL_MICROS: u64 = 20_000_000;
+
+    /// Return a new instance that takes part in the election for the `role`.
+    pub async fn new(
+        dbp: &Arc<DatabaseProvider>,
+        unique_timer_stamper: &Arc<UniqueTimeStamper>,
+        role: &'static str,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            dbp: Arc::clone(dbp),
+            role,
+            instance_id: unique_timer_stamper.get_instance_id(),
+            resigned: AtomicBool::new(false),
+        })
+        .initialize()
+        .await
+    }
+
+    /// Register as a candidate and keep the registration alive.
+    async fn initialize(self: Arc<Self>) -> Arc<Self> {
+        self.refresh_candidacy().await;
+        let self_clone = Arc::clone(&self);
+        tokio::spawn(async move {
+            loop {
+                sleep(Duration::from_micros(Self::REFRESH_INTERVAL_MICROS)).await;
+                if self_clone.resigned.load(Ordering::Relaxed) {
+                    break;
+                }
+                self_clone.refresh_candidacy().await;
+            }
+        });
+        self
+    }
+
+    /// Register or refresh the candidate registration of this instance.
+    async fn refresh_candidacy(&self) {
+        let successful_claim = self
+            .dbp
+            .instance_id_facade()
+            .claim_role(
+                self.role,
+                Self::CLAIM_TIME_TO_LIVE_SECONDS,
+                self.instance_id,
+            )
+            .await;
+        if !successful_claim {
+            log::warn!(
+                "Failed to register instance {} as a candidate for the '{}' role.",
+                self.instance_id,
+                self.role
+            );
+        }
+    }
+
+    /// Return `true` if this instance is the oldest alive candidate.
+    pub async fn is_leader(&self) -> bool {
+        !self.resigned.load(Ordering::Relaxed)
+            && self
+                .dbp
+                .instance_id_facade()
+                .get_oldest_instance_id_by_role(self.role)
+                .await
+                .is_some_and(|instance_id| instance_id == self.instance_id)
+    }
+
+    /// Stop taking part in the election, so another candidate can take over
+    /// right away.
+    pub async fn resign(&self) {
+        self.resigned.store(true, Ordering::Relaxed);
+        self.dbp
+            .instance_id_facade()
+            .free_role(self.role, self.instance_id)
+            .await;
+        log::debug!(
+            "Instance {} resigned from the '{}' role.",
+            self.instance_id,
+            self.role
+        );
+    }
+}
@@ -27,6 +27,7 @@ use fragtale_metrics::registry::MetricsProviderRegistry;
 use fragtale_metrics::registry::MetricsResult;
 use fragtale_metrics::registry::MetricsResultFuture;
 use fragtale_metrics::util::AtomicMetricAverage;
+use fragtale_metrics::util::AtomicMetricHistogram;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
@@ -40,8 +41,33 @@ pub struct MessageBrokerMetrics {
     delivered_bytes: SkipMap<String, AtomicU64>,
     correlated_wait_by_topic_max: SkipMap<String, Arc<AtomicU64>>,
     correlated_wait_by_topic_avg: SkipMap<String, AtomicMetricAverage>,
+    correlated_wait_by_topic_histogram: SkipMap<String, AtomicMetricHistogram>,
     delivery_latency_by_topic_max: SkipMap<String, Arc<AtomicU64>>,
     delivery_latency_by_topic_avg: SkipMap<String, AtomicMetricAverage>,
+    delivery_latency_by_topic_histogram: SkipMap<String, AtomicMetricHistogram>,
+    event_id_collisions: SkipMap<String, AtomicU64>,
+    duplicate_events: SkipMap<String, AtomicU64>,
+    unique_time_fallbacks: SkipMap<String, AtomicU64>,
+    unique_time_refusals: SkipMap<String, AtomicU64>,
+    publish_rejections: SkipMap<(String, String), AtomicU64>,
+    event_persists: SkipMap<(String, String), AtomicU64>,
+    expired_events: SkipMap<String, AtomicU64>,
+    dead_lettered_events: SkipMap<(String, String), AtomicU64>,
+    auto_created_topics: SkipMap<String, AtomicU64>,
+    refused_topic_creations: SkipMap<String, AtomicU64>,
+    paused_subscriptions: SkipMap<String, AtomicU64>,
+    group_member_connections: SkipMap<(String, String, String), AtomicU64>,
+    group_member_confirmed_events: SkipMap<(String, String, String), AtomicU64>,
+    delivery_cache_evictions: SkipMap<String, AtomicU64>,
+    delivery_cache_entries: SkipMap<(String, String), AtomicU64>,
+    delivery_cache_bytes: SkipMap<(String, String), AtomicU64>,
+    deadline_exceeded: SkipMap<String, AtomicU64>,
+    canary_events: SkipMap<String, AtomicU64>,
+    canary_errors: SkipMap<(String, String), AtomicU64>,
+    canary_latency_by_topic_max: SkipMap<String, Arc<AtomicU64>>,
+    canary_latency_by_topic_avg: SkipMap<String, AtomicMetricAverage>,
+    background_task_restarts: SkipMap<String, AtomicU64>,
+    background_tasks_stuck: SkipMap<String, AtomicU64>,
 }
 
 impl MessageBrokerMetrics {
@@ -52,11 +78,42 @@ impl MessageBrokerMetrics {
     const METRIC_NAME_PUBLISHED_BYTES: &str = "published_bytes_count";
     const METRIC_NAME_CORRELATED_WAIT_MAX: &str = "correlated_wait_max_micros";
     const METRIC_NAME_CORRELATED_WAIT_AVG: &str = "correlated_wait_avg_millis";
+    const METRIC_NAME_CORRELATED_WAIT: &str = "correlated_wait_micros";
     const METRIC_NAME_DELIVERY_LATENCY_MAX: &str = "delivery_latency_max_micros";
     const METRIC_NAME_DELIVERY_LATENCY_AVG: &str = "delivery_latency_avg_millis";
+    const METRIC_NAME_DELIVERY_LATENCY: &str = "delivery_latency_micros";
+    const METRIC_NAME_EVENT_ID_COLLISIONS: &str = "event_id_collisions_count";
+    const METRIC_NAME_DUPLICATE_EVENTS: &str = "duplicate_events_count";
+    const METRIC_NAME_UNIQUE_TIME_FALLBACKS: &str = "unique_time_fallbacks_count";
+    const METRIC_NAME_UNIQUE_TIME_REFUSALS: &str = "unique_time_refusals_count";
+    const METRIC_NAME_PUBLISH_REJECTIONS: &str = "publish_rejections_count";
+    const METRIC_NAME_EVENT_PERSISTS: &str = "event_persists_count";
+    const METRIC_NAME_EXPIRED_EVENTS: &str = "expired_events_count";
+    const METRIC_NAME_DEAD_LETTERED_EVENTS: &str = "dead_lettered_events_count";
+    const METRIC_NAME_AUTO_CREATED_TOPICS: &str = "auto_created_topics_count";
+    const METRIC_NAME_REFUSED_TOPIC_CREATIONS: &str = "refused_topic_creations_count";
+    const METRIC_NAME_PAUSED_SUBSCRIPTIONS: &str = "paused_subscriptions";
+    const METRIC_NAME_GROUP_MEMBER_CONNECTIONS: &str = "group_member_connections";
+    const METRIC_NAME_GROUP_MEMBER_CONFIRMED_EVENTS: &str = "group_member_confirmed_events_count";
+    const METRIC_NAME_DELIVERY_CACHE_EVICTIONS: &str = "delivery_cache_evictions_count";
+    const METRIC_NAME_DELIVERY_CACHE_ENTRIES: &str = "delivery_cache_entries";
+    const METRIC_NAME_DELIVERY_CACHE_BYTES: &str = "delivery_cache_bytes";
+    const METRIC_NAME_DEADLINE_EXCEEDED: &str = "deadline_exceeded_count";
+    const METRIC_NAME_CANARY_EVENTS: &str = "canary_events_count";
+    const METRIC_NAME_CANARY_ERRORS: &str = "canary_errors_count";
+    const METRIC_NAME_CANARY_LATENCY_MAX: &str = "canary_latency_max_micros";
+    const METRIC_NAME_CANARY_LATENCY_AVG: &str = "canary_latency_avg_millis";
+    const METRIC_NAME_BACKGROUND_TASK_RESTARTS: &str = "background_task_restarts_count";
+    const METRIC_NAME_BACKGROUND_TASKS_STUCK: &str = "background_tasks_stuck_count";
     const METRIC_NAME_VERSION: &str = "appname_build_info";
     const METRIC_LABEL_TOPIC: &str = "topic";
+    const METRIC_LABEL_CONSUMER: &str = "consumer";
+    const METRIC_LABEL_MEMBER: &str = "member";
+    const METRIC_LABEL_REASON: &str = "reason";
+    const METRIC_LABEL_OUTCOME: &str = "outcome";
+    const METRIC_LABEL_ENDPOINT: &str = "endpoint";
     const METRIC_LABEL_VERSION: &str = "version";
+    const METRIC_LABEL_TASK: &str = "task";
 
     /// Return a new instance.
     pub(super) fn new(app_config: &AppConfig) -> Arc<Self> {
@@ -68,8 +125,33 @@ impl MessageBrokerMetrics {
             delivered_bytes: SkipMap::default(),
             correlated_wait_by_topic_max: SkipMap::default(),
             correlated_wait_by_topic_avg: SkipMap::default(),
+            correlated_wait_by_topic_histogram: SkipMap::default(),
             delivery_latency_by_topic_max: SkipMap::default(),
             delivery_latency_by_topic_avg: SkipMap::default(),
+            delivery_latency_by_topic_histogram: SkipMap::default(),
+            event_id_collisions: SkipMap::default(),
+            duplicate_events: SkipMap::default(),
+            unique_time_fallbacks: SkipMap::default(),
+            unique_time_refusals: SkipMap::default(),
+            publish_rejections: SkipMap::default(),
+            event_persists: SkipMap::default(),
+            expired_events: SkipMap::default(),
+            dead_lettered_events: SkipMap::default(),
+            auto_created_topics: SkipMap::default(),
+            refused_topic_creations: SkipMap::default(),
+            paused_subscriptions: SkipMap::default(),
+            group_member_connections: SkipMap::default(),
+            group_member_confirmed_events: SkipMap::default(),
+            delivery_cache_evictions: SkipMap::default(),
+            delivery_cache_entries: SkipMap::default(),
+            delivery_cache_bytes: SkipMap::default(),
+            deadline_exceeded: SkipMap::default(),
+            canary_events: SkipMap::default(),
+            canary_errors: SkipMap::default(),
+            canary_latency_by_topic_max: SkipMap::default(),
+            canary_latency_by_topic_avg: SkipMap::default(),
+            background_task_restarts: SkipMap::default(),
+            background_tasks_stuck: SkipMap::default(),
         });
         MetricsProviderRegistry::register_metrics(
             app_config.app_name_lowercase(),
@@ -123,6 +205,252 @@ impl MessageBrokerMetrics {
             );
     }
 
+    /// Increase counter for detected event identifier collisions per topic.
+    pub(super) fn inc_event_id_collisions(&self, topic_id: &str) {
+        self.event_id_collisions
+            .get_or_insert_with(topic_id.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for events published again with an event identifier
+    /// that was recently published to the same topic.
+    pub(super) fn inc_duplicate_events(&self, topic_id: &str) {
+        self.duplicate_events
+            .get_or_insert_with(topic_id.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for unique time stamping that used the monotonic
+    /// fallback since the local clock had not advanced.
+    pub(super) fn inc_unique_time_fallbacks(&self, topic_id: &str) {
+        self.unique_time_fallbacks
+            .get_or_insert_with(topic_id.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for refused unique time stamping since the local
+    /// clock was too far behind.
+    pub(super) fn inc_unique_time_refusals(&self, topic_id: &str) {
+        self.unique_time_refusals
+            .get_or_insert_with(topic_id.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for rejected publishes per topic and reason code.
+    pub(super) fn inc_publish_rejections(&self, topic_id: &str, reason: &str) {
+        self.publish_rejections
+            .get_or_insert_with((topic_id.to_owned(), reason.to_owned()), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for attempts to persist a published event by outcome.
+    ///
+    /// `retries` is the number of retries a successful write needed or `None`
+    /// if the event could not be persisted.
+    pub(super) fn inc_event_persists(&self, topic_id: &str, retries: Option<u32>) {
+        let outcome = match retries {
+            Some(0) => "first_try",
+            Some(_) => "retried",
+            None => "failed",
+        };
+        self.event_persists
+            .get_or_insert_with(
+                (topic_id.to_owned(), outcome.to_owned()),
+                AtomicU64::default,
+            )
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for events published to the dead-letter topic per
+    /// topic and reason code.
+    pub(super) fn inc_dead_lettered_events(&self, topic_id: &str, reason: &str) {
+        self.dead_lettered_events
+            .get_or_insert_with((topic_id.to_owned(), reason.to_owned()), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for events that passed their deadline before they
+    /// were delivered.
+    pub(super) fn inc_expired_events(&self, topic_id: &str) {
+        self.expired_events
+            .get_or_insert_with(topic_id.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for topics created on first use instead of by
+    /// explicit registration.
+    pub(super) fn inc_auto_created_topics(&self, topic_id: &str) {
+        self.auto_created_topics
+            .get_or_insert_with(topic_id.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for requests refused since they would have created a
+    /// topic against the topic creation policy.
+    pub(super) fn inc_refused_topic_creations(&self, topic_id: &str) {
+        self.refused_topic_creations
+            .get_or_insert_with(topic_id.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Track the number of paused subscriptions per topic.
+    pub(super) fn report_subscription_paused(&self, topic_id: &str, paused: bool) {
+        let entry = self
+            .paused_subscriptions
+            .get_or_insert_with(topic_id.to_string(), AtomicU64::default);
+        if paused {
+            entry.value().fetch_add(1, Ordering::Relaxed);
+        } else {
+            entry.value().fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Track the number of subscriber connections per consumer group member.
+    pub(super) fn report_group_member_connected(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        member_id: &str,
+        connected: bool,
+    ) {
+        let entry = self.group_member_connections.get_or_insert_with(
+            (
+                topic_id.to_owned(),
+                consumer_id.to_owned(),
+                member_id.to_owned(),
+            ),
+            AtomicU64::default,
+        );
+        if connected {
+            entry.value().fetch_add(1, Ordering::Relaxed);
+        } else {
+            entry.value().fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Increase counter for confirmed events per consumer group member.
+    pub(super) fn inc_group_member_confirmed_events(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        member_id: &str,
+    ) {
+        self.group_member_confirmed_events
+            .get_or_insert_with(
+                (
+                    topic_id.to_owned(),
+                    consumer_id.to_owned(),
+                    member_id.to_owned(),
+                ),
+                AtomicU64::default,
+            )
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Stop reporting metrics of a consumer group member that is no longer
+    /// tracked.
+    pub(super) fn forget_group_member(&self, topic_id: &str, consumer_id: &str, member_id: &str) {
+        let key = (
+            topic_id.to_owned(),
+            consumer_id.to_owned(),
+            member_id.to_owned(),
+        );
+        self.group_member_connections.remove(&key);
+        self.group_member_confirmed_events.remove(&key);
+    }
+
+    /// Increase counter for events evicted from delivery caches to stay
+    /// within the memory budget.
+    pub(super) fn inc_delivery_cache_evictions(&self, topic_id: &str, evictions: u64) {
+        self.delivery_cache_evictions
+            .get_or_insert_with(topic_id.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(evictions, Ordering::Relaxed);
+    }
+
+    /// Track the number of cached events and their estimated memory use in
+    /// the delivery cache of a consumer.
+    pub(super) fn report_delivery_cache_residency(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        entries: usize,
+        bytes: usize,
+    ) {
+        let key = (topic_id.to_owned(), consumer_id.to_owned());
+        self.delivery_cache_entries
+            .get_or_insert_with(key.clone(), AtomicU64::default)
+            .value()
+            .store(
+                u64::try_from(entries).unwrap_or_default(),
+                Ordering::Relaxed,
+            );
+        self.delivery_cache_bytes
+            .get_or_insert_with(key, AtomicU64::default)
+            .value()
+            .store(u64::try_from(bytes).unwrap_or_default(), Ordering::Relaxed);
+    }
+
+    /// Increase counter for requests aborted since their deadline passed.
+    pub(super) fn inc_deadline_exceeded(&self, endpoint: &str) {
+        self.deadline_exceeded
+            .get(endpoint)
+            .unwrap_or_else(|| {
+                self.deadline_exceeded
+                    .get_or_insert_with(endpoint.to_string(), AtomicU64::default)
+            })
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for published synthetic canary events per topic.
+    pub(super) fn inc_canary_events(&self, topic_id: &str) {
+        self.canary_events
+            .get_or_insert_with(topic_id.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for synthetic canary events that failed to make the
+    /// round trip per topic and reason.
+    pub(super) fn inc_canary_errors(&self, topic_id: &str, reason: &str) {
+        self.canary_errors
+            .get_or_insert_with((topic_id.to_owned(), reason.to_owned()), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Track how long it takes from publishing of a synthetic canary event to
+    /// the confirmed delivery by the built-in canary consumer.
+    pub(super) fn report_canary_latency_micros(&self, topic_id: &str, latency_micros: u64) {
+        self.canary_latency_by_topic_avg
+            .get_or_insert_with(topic_id.to_string(), AtomicMetricAverage::default)
+            .value()
+            // Convert latency to millis
+            .append_with_cap(latency_micros / 1000);
+        let value = self
+            .canary_latency_by_topic_max
+            .get_or_insert_with(topic_id.to_string(), Arc::default)
+            .value()
+            .clone();
+        // Note: This is _not_ atomic as a whole, but good enough for metrics.
+        let current = value.load(Ordering::Relaxed);
+        if current < latency_micros {
+            value.store(latency_micros, Ordering::Relaxed);
+        }
+    }
+
     /// Track how long the caller has waiting for a result of a correlated
     /// query.
     pub(super) fn report_correlated_wait(&self, topic_id: &str, duration_micros: u64) {
@@ -138,6 +466,14 @@ impl MessageBrokerMetrics {
                 // Convert latency to millis
                 .append_with_cap(duration_micros / 1000);
         }
+        self.correlated_wait_by_topic_histogram
+            .get(topic_id)
+            .unwrap_or_else(|| {
+                self.correlated_wait_by_topic_histogram
+                    .get_or_insert_with(topic_id.to_string(), Self::latency_histogram)
+            })
+            .value()
+            .observe(duration_micros);
         {
             let value = self
                 .correlated_wait_by_topic_max
@@ -175,6 +511,14 @@ impl MessageBrokerMetrics {
                 // Convert latency to millis
                 .append_with_cap(latency_micros / 1000);
         }
+        self.delivery_latency_by_topic_histogram
+            .get(topic_id)
+            .unwrap_or_else(|| {
+                self.delivery_latency_by_topic_histogram
+                    .get_or_insert_with(topic_id.to_string(), Self::latency_histogram)
+            })
+            .value()
+            .observe(latency_micros);
         {
             let value = self
                 .delivery_latency_by_topic_max
@@ -193,6 +537,24 @@ impl MessageBrokerMetrics {
         }
     }
 
+    /// Increase counter for restarts of supervised background tasks that
+    /// died or got stuck.
+    pub(super) fn inc_background_task_restarts(&self, task_kind: &str) {
+        self.background_task_restarts
+            .get_or_insert_with(task_kind.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increase counter for supervised background tasks that stopped sending
+    /// heartbeats.
+    pub(super) fn inc_background_tasks_stuck(&self, task_kind: &str) {
+        self.background_tasks_stuck
+            .get_or_insert_with(task_kind.to_string(), AtomicU64::default)
+            .value()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
     fn mlvs_from_by_topic_count(map: &SkipMap<String, AtomicU64>) -> Vec<MetricLabeledValue> {
         let mut mlvs = vec![];
         for entry in map.iter() {
@@ -208,6 +570,100 @@ impl MessageBrokerMetrics {
         mlvs
     }
 
+    fn mlvs_from_by_endpoint(map: &SkipMap<String, AtomicU64>) -> Vec<MetricLabeledValue> {
+        let mut mlvs = vec![];
+        for entry in map.iter() {
+            let endpoint = entry.key().to_string();
+            let metric_value = entry.value().load(Ordering::Relaxed) as f64;
+            mlvs.push(
+                MetricLabeledValue::new(metric_value)
+                    .add_label(Self::METRIC_LABEL_ENDPOINT, endpoint),
+            )
+        }
+        if mlvs.is_empty() {
+            mlvs.push(MetricLabeledValue::new(0f64));
+        }
+        mlvs
+    }
+
+    fn mlvs_from_by_task(map: &SkipMap<String, AtomicU64>) -> Vec<MetricLabeledValue> {
+        let mut mlvs = vec![];
+        for entry in map.iter() {
+            let task_kind = entry.key().to_string();
+            let metric_value = entry.value().load(Ordering::Relaxed) as f64;
+            mlvs.push(
+                MetricLabeledValue::new(metric_value).add_label(Self::METRIC_LABEL_TASK, task_kind),
+            )
+        }
+        if mlvs.is_empty() {
+            mlvs.push(MetricLabeledValue::new(0f64));
+        }
+        mlvs
+    }
+
+    fn mlvs_from_by_group_member(
+        map: &SkipMap<(String, String, String), AtomicU64>,
+    ) -> Vec<MetricLabeledValue> {
+        let mut mlvs = vec![];
+        for entry in map.iter() {
+            let (topic_id, consumer_id, member_id) = entry.key();
+            let metric_value = entry.value().load(Ordering::Relaxed) as f64;
+            mlvs.push(
+                MetricLabeledValue::new(metric_value)
+                    .add_label(Self::METRIC_LABEL_TOPIC, topic_id.to_owned())
+                    .add_label(Self::METRIC_LABEL_CONSUMER, consumer_id.to_owned())
+                    .add_label(Self::METRIC_LABEL_MEMBER, member_id.to_owned()),
+            )
+        }
+        if mlvs.is_empty() {
+            mlvs.push(MetricLabeledValue::new(0f64));
+        }
+        mlvs
+    }
+
+    fn mlvs_from_by_consumer(
+        map: &SkipMap<(String, String), AtomicU64>,
+    ) -> Vec<MetricLabeledValue> {
+        let mut mlvs = vec![];
+        for entry in map.iter() {
+            let (topic_id, consumer_id) = entry.key();
+            let metric_value = entry.value().load(Ordering::Relaxed) as f64;
+            mlvs.push(
+                MetricLabeledValue::new(metric_value)
+                    .add_label(Self::METRIC_LABEL_TOPIC, topic_id.to_owned())
+                    .add_label(Self::METRIC_LABEL_CONSUMER, consumer_id.to_owned()),
+            )
+        }
+        if mlvs.is_empty() {
+            mlvs.push(MetricLabeledValue::new(0f64));
+        }
+        mlvs
+    }
+
+    fn mlvs_from_by_reason(map: &SkipMap<(String, String), AtomicU64>) -> Vec<MetricLabeledValue> {
+        Self::mlvs_from_by_topic_and_label(map, Self::METRIC_LABEL_REASON)
+    }
+
+    fn mlvs_from_by_topic_and_label(
+        map: &SkipMap<(String, String), AtomicU64>,
+        label: &'static str,
+    ) -> Vec<MetricLabeledValue> {
+        let mut mlvs = vec![];
+        for entry in map.iter() {
+            let (topic_id, label_value) = entry.key();
+            let metric_value = entry.value().load(Ordering::Relaxed) as f64;
+            mlvs.push(
+                MetricLabeledValue::new(metric_value)
+                    .add_label(Self::METRIC_LABEL_TOPIC, topic_id.to_owned())
+                    .add_label(label, label_value.to_owned()),
+            )
+        }
+        if mlvs.is_empty() {
+            mlvs.push(MetricLabeledValue::new(0f64));
+        }
+        mlvs
+    }
+
     fn mlvs_from_by_topic_gauge_max(
         map: &SkipMap<String, Arc<AtomicU64>>,
     ) -> Vec<MetricLabeledValue> {
@@ -242,6 +698,29 @@ impl MessageBrokerMetrics {
         }
         mlvs
     }
+
+    /// Return a new histogram for latencies in microseconds.
+    fn latency_histogram() -> AtomicMetricHistogram {
+        AtomicMetricHistogram::new(AtomicMetricHistogram::LATENCY_MICROS_BOUNDS)
+    }
+
+    fn mlvs_from_by_topic_histogram(
+        map: &SkipMap<String, AtomicMetricHistogram>,
+    ) -> Vec<MetricLabeledValue> {
+        let mut mlvs = vec![];
+        for entry in map.iter() {
+            let topic_id = entry.key().to_string();
+            mlvs.extend(
+                entry
+                    .value()
+                    .as_metric_labeled_values(&[(Self::METRIC_LABEL_TOPIC, topic_id)]),
+            );
+        }
+        if mlvs.is_empty() {
+            mlvs.extend(Self::latency_histogram().as_metric_labeled_values(&[]));
+        }
+        mlvs
+    }
 }
 
 impl MetricsProvider for MessageBrokerMetrics {
@@ -309,6 +788,14 @@ impl MetricsProvider for MessageBrokerMetrics {
                 .set_help("Average wait between publishing of an event the correlated event response delivery.")
                 .set_type(MetricType::Gauge),
             )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_CORRELATED_WAIT,
+                    &Self::mlvs_from_by_topic_histogram(&self_clone.correlated_wait_by_topic_histogram),
+                )
+                .set_help("Distribution of wait between publishing of an event and the correlated event response delivery.")
+                .set_type(MetricType::Histogram),
+            )
             .add_metric(
                 Metric::from_metric_labeled_values(
                     Self::METRIC_NAME_DELIVERY_LATENCY_MAX,
@@ -327,6 +814,201 @@ impl MetricsProvider for MessageBrokerMetrics {
                 .set_help("Average latency between publishing of an event and start of delivery of the event to a waiting consumer.")
                 .set_type(MetricType::Gauge),
             )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_DELIVERY_LATENCY,
+                    &Self::mlvs_from_by_topic_histogram(&self_clone.delivery_latency_by_topic_histogram),
+                )
+                .set_help("Distribution of latency between publishing of an event and start of delivery of the event to a waiting consumer.")
+                .set_type(MetricType::Histogram),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_EVENT_ID_COLLISIONS,
+                    &Self::mlvs_from_by_topic_count(&self_clone.event_id_collisions)
+                )
+                .set_help("Different event documents detected with the same event_id.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_DUPLICATE_EVENTS,
+                    &Self::mlvs_from_by_topic_count(&self_clone.duplicate_events)
+                )
+                .set_help("Events published with the same event_id as a recently published event.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_UNIQUE_TIME_FALLBACKS,
+                    &Self::mlvs_from_by_topic_count(&self_clone.unique_time_fallbacks)
+                )
+                .set_help("Unique time stamping that ran ahead of a stalled local clock.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_UNIQUE_TIME_REFUSALS,
+                    &Self::mlvs_from_by_topic_count(&self_clone.unique_time_refusals)
+                )
+                .set_help("Refused unique time stamping due to a local clock too far behind.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_PUBLISH_REJECTIONS,
+                    &Self::mlvs_from_by_reason(&self_clone.publish_rejections)
+                )
+                .set_help("Rejected publishes by reason.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_EVENT_PERSISTS,
+                    &Self::mlvs_from_by_topic_and_label(
+                        &self_clone.event_persists,
+                        Self::METRIC_LABEL_OUTCOME
+                    )
+                )
+                .set_help("Writes of published events by outcome (first_try, retried or failed).")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_EXPIRED_EVENTS,
+                    &Self::mlvs_from_by_topic_count(&self_clone.expired_events)
+                )
+                .set_help("Event deliveries skipped since the event deadline had passed.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_DEAD_LETTERED_EVENTS,
+                    &Self::mlvs_from_by_reason(&self_clone.dead_lettered_events)
+                )
+                .set_help("Events published to the dead-letter topic by reason.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_AUTO_CREATED_TOPICS,
+                    &Self::mlvs_from_by_topic_count(&self_clone.auto_created_topics)
+                )
+                .set_help("Topics created on first use instead of by explicit registration.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_REFUSED_TOPIC_CREATIONS,
+                    &Self::mlvs_from_by_topic_count(&self_clone.refused_topic_creations)
+                )
+                .set_help("Requests refused since they would have created a topic against policy.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_PAUSED_SUBSCRIPTIONS,
+                    &Self::mlvs_from_by_topic_count(&self_clone.paused_subscriptions)
+                )
+                .set_help("Subscriptions paused due to repeated redelivery of the same event.")
+                .set_type(MetricType::Gauge),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_GROUP_MEMBER_CONNECTIONS,
+                    &Self::mlvs_from_by_group_member(&self_clone.group_member_connections)
+                )
+                .set_help("Open subscriber connections of each consumer group member.")
+                .set_type(MetricType::Gauge),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_GROUP_MEMBER_CONFIRMED_EVENTS,
+                    &Self::mlvs_from_by_group_member(&self_clone.group_member_confirmed_events)
+                )
+                .set_help("Confirmed events of each consumer group member.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_DELIVERY_CACHE_EVICTIONS,
+                    &Self::mlvs_from_by_topic_count(&self_clone.delivery_cache_evictions)
+                )
+                .set_help("Events evicted from delivery caches to stay within the memory budget.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_DELIVERY_CACHE_ENTRIES,
+                    &Self::mlvs_from_by_consumer(&self_clone.delivery_cache_entries)
+                )
+                .set_help("Events pending delivery in the delivery cache of each consumer.")
+                .set_type(MetricType::Gauge),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_DELIVERY_CACHE_BYTES,
+                    &Self::mlvs_from_by_consumer(&self_clone.delivery_cache_bytes)
+                )
+                .set_help("Estimated memory use of the delivery cache of each consumer.")
+                .set_type(MetricType::Gauge),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_DEADLINE_EXCEEDED,
+                    &Self::mlvs_from_by_endpoint(&self_clone.deadline_exceeded)
+                )
+                .set_help("Requests aborted since their deadline passed.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_CANARY_EVENTS,
+                    &Self::mlvs_from_by_topic_count(&self_clone.canary_events)
+                )
+                .set_help("Synthetic canary events published by this instance.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_CANARY_ERRORS,
+                    &Self::mlvs_from_by_reason(&self_clone.canary_errors)
+                )
+                .set_help("Synthetic canary events that failed to be published, delivered or confirmed in time.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_CANARY_LATENCY_MAX,
+                    &Self::mlvs_from_by_topic_gauge_max(&self_clone.canary_latency_by_topic_max),
+                )
+                .set_help("Max latency between publishing of a synthetic canary event and confirmed delivery to the built-in canary consumer.")
+                .set_type(MetricType::Gauge),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_CANARY_LATENCY_AVG,
+                    &Self::mlvs_from_by_topic_gauge_avg(&self_clone.canary_latency_by_topic_avg),
+                )
+                .set_help("Average latency between publishing of a synthetic canary event and confirmed delivery to the built-in canary consumer.")
+                .set_type(MetricType::Gauge),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_BACKGROUND_TASK_RESTARTS,
+                    &Self::mlvs_from_by_task(&self_clone.background_task_restarts)
+                )
+                .set_help("Restarts of supervised background tasks that died or got stuck.")
+                .set_type(MetricType::Counter),
+            )
+            .add_metric(
+                Metric::from_metric_labeled_values(
+                    Self::METRIC_NAME_BACKGROUND_TASKS_STUCK,
+                    &Self::mlvs_from_by_task(&self_clone.background_tasks_stuck)
+                )
+                .set_help("Supervised background tasks that stopped sending heartbeats.")
+                .set_type(MetricType::Counter),
+            )
         })
     }
 }
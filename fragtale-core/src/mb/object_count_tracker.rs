@@ -33,6 +33,10 @@ use tokio::time::sleep;
 /// Track number of events of different types in topics to detect changes.
 ///
 /// This is an optmization to avoid having to query the DB for the actual objects.
+///
+/// The local counts are also snapshotted at regular intervals, so an instance
+/// that claims the same instance identifier after a restart continues counting
+/// from where the previous holder of the identifier left off.
 pub struct ObjectCountTracker {
     // Database provider.
     dbp: Arc<DatabaseProvider>,
@@ -43,6 +47,9 @@ pub struct ObjectCountTracker {
 }
 
 impl ObjectCountTracker {
+    /// Interval between snapshots of the local counts.
+    const SNAPSHOT_INTERVAL_MICROS: u64 = 30_000_000;
+
     /// Return a new instance.
     pub async fn new(dbp: &Arc<DatabaseProvider>, instance_id: u16) -> Arc<Self> {
         Arc::new(Self {
@@ -65,6 +72,15 @@ impl ObjectCountTracker {
             }
         });
         let self_clone = Arc::clone(&self);
+        tokio::spawn(async move {
+            // Continue from the counts of the previous holder of the instance id
+            self_clone.restore_snapshots().await;
+            loop {
+                sleep(Duration::from_micros(Self::SNAPSHOT_INTERVAL_MICROS)).await;
+                self_clone.snapshot_local_counts().await;
+            }
+        });
+        let self_clone = Arc::clone(&self);
         tokio::spawn(async move {
             // Detect changes in object counts for all topics and signal any awaiter.
             loop {
@@ -130,6 +146,98 @@ impl ObjectCountTracker {
         }
     }
 
+    /// Restore the local counts of all topics from the snapshots taken by the
+    /// previous holder of the local instance id.
+    async fn restore_snapshots(&self) {
+        let mut from = None;
+        loop {
+            let (topic_ids, more) = self.dbp.topic_facade().get_topic_ids(&from).await;
+            from = topic_ids.last().cloned();
+            for topic_id in &topic_ids {
+                let per_topic_tracker = self.tracking_by_topic(topic_id);
+                for (object_count_type, value) in self
+                    .dbp
+                    .event_tracking_facade()
+                    .object_count_snapshots_by_instance(topic_id, self.instance_id)
+                    .await
+                {
+                    per_topic_tracker
+                        .local_count_by_type(&object_count_type)
+                        .restore(value);
+                }
+            }
+            if !more {
+                break;
+            }
+        }
+    }
+
+    /// Persist snapshots of all local counts that changed since the last
+    /// snapshot.
+    ///
+    /// A removed snapshot means that the counts were reset by any instance,
+    /// so the snapshotted part of the local count is dropped.
+    async fn snapshot_local_counts(&self) {
+        for entry in self.per_topic_tracker.iter() {
+            let topic_id = entry.key();
+            if entry
+                .value()
+                .local_counts()
+                .any(|entry| entry.value().get_snapshotted() > 0)
+            {
+                let snapshots = self
+                    .dbp
+                    .event_tracking_facade()
+                    .object_count_snapshots_by_instance(topic_id, self.instance_id)
+                    .await;
+                for entry in entry.value().local_counts() {
+                    let object_count = entry.value().as_ref();
+                    if object_count.get_snapshotted() > 0
+                        && !snapshots
+                            .iter()
+                            .any(|(object_count_type, _value)| object_count_type == entry.key())
+                    {
+                        object_count.sub_current(object_count.get_snapshotted());
+                        object_count.set_snapshotted(0);
+                    }
+                }
+            }
+            for entry in entry.value().local_counts() {
+                let object_count_type = entry.key();
+                let object_count = entry.value().as_ref();
+                let current = object_count.get_current();
+                if current != object_count.get_snapshotted() {
+                    self.dbp
+                        .event_tracking_facade()
+                        .object_count_snapshot_upsert(
+                            topic_id,
+                            object_count_type,
+                            self.instance_id,
+                            current,
+                        )
+                        .await;
+                    object_count.set_snapshotted(current);
+                }
+            }
+        }
+    }
+
+    /// Reset the counts of a topic on all instances.
+    ///
+    /// The local counts are reset right away, while other instances notice
+    /// the removed snapshots when they take their next snapshot.
+    pub async fn reset(&self, topic_id: &str) {
+        self.dbp
+            .event_tracking_facade()
+            .object_count_snapshots_delete(topic_id)
+            .await;
+        for entry in self.tracking_by_topic(topic_id).local_counts() {
+            let object_count = entry.value().as_ref();
+            object_count.sub_current(object_count.get_current());
+            object_count.set_snapshotted(0);
+        }
+    }
+
     /// Detect changes in object counts and signal any awaiter.
     async fn detect_changes(&self) {
         // for each topic with waiters
@@ -26,6 +26,7 @@ use std::sync::atomic::Ordering;
 pub struct LocalObjectCount {
     current: AtomicU64,
     persisted: AtomicU64,
+    snapshotted: AtomicU64,
 }
 
 impl LocalObjectCount {
@@ -34,6 +35,7 @@ impl LocalObjectCount {
         Arc::new(Self {
             current: AtomicU64::default(),
             persisted: AtomicU64::default(),
+            snapshotted: AtomicU64::default(),
         })
     }
 
@@ -47,6 +49,31 @@ impl LocalObjectCount {
         self.current.fetch_add(1, Ordering::Relaxed)
     }
 
+    /// Continue counting from a count restored from a snapshot.
+    pub fn restore(&self, value: u64) {
+        self.current.fetch_add(value, Ordering::Relaxed);
+        self.snapshotted.store(value, Ordering::Relaxed);
+    }
+
+    /// Decrease the current local count by `value` (without going below 0).
+    pub fn sub_current(&self, value: u64) {
+        self.current
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                Some(current.saturating_sub(value))
+            })
+            .ok();
+    }
+
+    /// Return the last persisted snapshot of the local count.
+    pub fn get_snapshotted(&self) -> u64 {
+        self.snapshotted.load(Ordering::Relaxed)
+    }
+
+    /// Set the last persisted snapshot of the local count.
+    pub fn set_snapshotted(&self, value: u64) {
+        self.snapshotted.store(value, Ordering::Relaxed)
+    }
+
     /// Return the last known peristed local count.
     pub fn get_persisted(&self) -> u64 {
         self.persisted.load(Ordering::Relaxed)
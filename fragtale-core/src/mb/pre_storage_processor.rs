@@ -19,26 +19,42 @@
 
 mod jsonpointer_extraction;
 mod jsonschema_validation;
+mod sample_documents;
 
+use self::sample_documents::SampleDocuments;
 use super::event_descriptor_cache::EventDescriptorCache;
+use super::filter_expression::FilterExpression;
+use super::filter_expression_cache::FilterExpressionCache;
+use fragtale_client::mb::contract_fixtures::ContractFixture;
+use fragtale_client::mb::contract_fixtures::ContractFixtures;
 use fragtale_client::mb::event_descriptor::DescriptorVersion;
 use fragtale_client::mb::event_descriptor::EventDescriptor;
+use fragtale_client::mb::event_descriptor::EventSchema;
+use fragtale_client::mb::event_descriptor::Extractor;
+use fragtale_client::mb::shared_schemas::SharedSchema;
 use fragtale_dbp::mb::ExtractedValue;
 use fragtale_dbp::mb::MessageBrokerError;
 use fragtale_dbp::mb::MessageBrokerErrorKind;
+use serde_json::Value;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Validates schema and extracts indexed column(s) from document.
 pub struct PreStorageProcessor {
     event_descriptor_cache: Arc<EventDescriptorCache>,
+    filter_expression_cache: Arc<FilterExpressionCache>,
 }
 
 impl PreStorageProcessor {
     /// Return a new instance.
-    pub fn new(event_descriptor_cache: &Arc<EventDescriptorCache>) -> Arc<Self> {
+    pub fn new(
+        event_descriptor_cache: &Arc<EventDescriptorCache>,
+        filter_expression_cache: &Arc<FilterExpressionCache>,
+    ) -> Arc<Self> {
         Arc::new(Self {
             event_descriptor_cache: Arc::clone(event_descriptor_cache),
+            filter_expression_cache: Arc::clone(filter_expression_cache),
         })
     }
 
@@ -58,10 +74,46 @@ impl PreStorageProcessor {
             .get_event_descriptor(topic_id, &descriptor_version)
             .await?;
         let column_to_value_map = if let Some(event_descriptor) = &event_descriptor_opt {
+            // Shared schema components that the schemas may reference
+            let shared_schemas = self.event_descriptor_cache.get_shared_schemas();
             // Validate document against schema, if present
-            Self::assert_event_schema_compliance(event_descriptor, event_document)?;
+            Self::assert_event_schema_compliance(
+                event_descriptor.get_event_schema(),
+                event_document,
+                &shared_schemas,
+            )?;
             // Extract values of interest from the document
-            Self::extract_values_from_document(event_descriptor, event_document)?
+            let mut column_to_value_map = HashMap::new();
+            self.extract_values_from_document(
+                topic_id,
+                event_descriptor.get_extractors(),
+                event_document,
+                &mut column_to_value_map,
+            )?;
+            // Validate and extract per kind of event in multi-type topics
+            if let Some(event_type) = Self::extract_event_type(event_descriptor, event_document)? {
+                let Some(event_type_descriptor) =
+                    event_descriptor.get_event_type_descriptor(&event_type)
+                else {
+                    Err(
+                        MessageBrokerErrorKind::PreStorageProcessorError.error_with_msg(format!(
+                            "The event type '{event_type}' is not described for topic '{topic_id}'."
+                        )),
+                    )?
+                };
+                Self::assert_event_schema_compliance(
+                    event_type_descriptor.get_event_schema(),
+                    event_document,
+                    &shared_schemas,
+                )?;
+                self.extract_values_from_document(
+                    topic_id,
+                    event_type_descriptor.get_extractors(),
+                    event_document,
+                    &mut column_to_value_map,
+                )?;
+            }
+            column_to_value_map
         } else {
             HashMap::new()
         };
@@ -74,6 +126,43 @@ impl PreStorageProcessor {
         ))
     }
 
+    /// Extract any indexed column found in the document using the extractors
+    /// of the latest [EventDescriptor] of the topic.
+    ///
+    /// The document is not validated, since it might have been published
+    /// using an older schema.
+    pub async fn extract_using_latest(
+        &self,
+        topic_id: &str,
+        event_document: &str,
+    ) -> Result<HashMap<String, ExtractedValue>, MessageBrokerError> {
+        let mut column_to_value_map = HashMap::new();
+        let Some(event_descriptor) = self
+            .event_descriptor_cache
+            .get_event_descriptor_by_topic_latest(topic_id)
+        else {
+            return Ok(column_to_value_map);
+        };
+        self.extract_values_from_document(
+            topic_id,
+            event_descriptor.get_extractors(),
+            event_document,
+            &mut column_to_value_map,
+        )?;
+        if let Some(event_type) = Self::extract_event_type(&event_descriptor, event_document)?
+            && let Some(event_type_descriptor) =
+                event_descriptor.get_event_type_descriptor(&event_type)
+        {
+            self.extract_values_from_document(
+                topic_id,
+                event_type_descriptor.get_extractors(),
+                event_document,
+                &mut column_to_value_map,
+            )?;
+        }
+        Ok(column_to_value_map)
+    }
+
     /// Check if "descriptor_version" is still allowed → Error if not
     fn assert_allowed_descriptor_version(
         &self,
@@ -122,17 +211,40 @@ impl PreStorageProcessor {
         }
     }
 
+    /// Return the kind of event for documents in multi-type topics.
+    ///
+    /// Returns `None` if the [EventDescriptor] does not declare an event type
+    /// field and fails if the document lacks a text value at the declared
+    /// location.
+    pub fn extract_event_type(
+        event_descriptor: &EventDescriptor,
+        event_document: &str,
+    ) -> Result<Option<String>, MessageBrokerError> {
+        let Some(event_type_field) = event_descriptor.get_event_type_field() else {
+            return Ok(None);
+        };
+        jsonpointer_extraction::extract_jsonpointer_text(event_document, event_type_field)?
+            .map(Some)
+            .ok_or_else(|| {
+                MessageBrokerErrorKind::PreStorageProcessorError.error_with_msg(format!(
+                    "The document has no text event type at '{event_type_field}'."
+                ))
+            })
+    }
+
     /// Validate document against schema, if present
     fn assert_event_schema_compliance(
-        event_descriptor: &EventDescriptor,
+        event_schema_opt: &Option<EventSchema>,
         event_document: &str,
+        shared_schemas: &[Arc<SharedSchema>],
     ) -> Result<(), MessageBrokerError> {
-        if let Some(event_schema) = event_descriptor.get_event_schema() {
+        if let Some(event_schema) = event_schema_opt {
             match event_schema.get_schema_type() {
-                "https://json-schema.org/draft/2020-12/schema" => {
+                jsonschema_validation::SCHEMA_TYPE_DRAFT202012 => {
                     jsonschema_validation::validate_draft202012(
                         event_schema.get_schema_data(),
                         event_document,
+                        shared_schemas,
                     )?
                 }
                 schema_type => {
@@ -144,31 +256,320 @@ impl PreStorageProcessor {
         Ok(())
     }
 
-    /// Extract indexed values from the document
-    fn extract_values_from_document(
+    /// Ensure that a new version of a shared schema component compiles
+    /// together with the other registered components.
+    pub fn assert_shared_schema_compliance(
+        &self,
+        shared_schema: &SharedSchema,
+    ) -> Result<(), MessageBrokerError> {
+        if shared_schema.get_schema_type() != jsonschema_validation::SCHEMA_TYPE_DRAFT202012 {
+            Err(
+                MessageBrokerErrorKind::PreStorageProcessorError.error_with_msg(format!(
+                    "Unsupported schema type: '{}'",
+                    shared_schema.get_schema_type()
+                )),
+            )?;
+        }
+        let contents = jsonschema_validation::parse_shared_schema(shared_schema)?;
+        let shared_schemas = self
+            .event_descriptor_cache
+            .get_shared_schemas()
+            .into_iter()
+            .filter(|other| other.get_schema_id() != shared_schema.get_schema_id())
+            .chain(std::iter::once(Arc::new(shared_schema.to_owned())))
+            .collect::<Vec<_>>();
+        jsonschema_validation::compile_draft202012(&contents, &shared_schemas).map(|_| ())
+    }
+
+    /// Generate language-agnostic contract test fixtures from the
+    /// [EventDescriptor] of a topic.
+    ///
+    /// A valid sample is generated from the event schema(s) of each kind of
+    /// event and completed with values at the locations of the extractors.
+    /// Invalid samples are derived from the valid ones. Every sample is run
+    /// through the same validation and extraction as published documents, so
+    /// samples that don't behave as intended are left out and the expected
+    /// extraction results are accurate.
+    pub fn generate_contract_fixtures(
+        &self,
+        topic_id: &str,
+        event_descriptor: &EventDescriptor,
+    ) -> Result<ContractFixtures, MessageBrokerError> {
+        let shared_schemas = self.event_descriptor_cache.get_shared_schemas();
+        let sample_documents = SampleDocuments::new(&shared_schemas);
+        let parse_schema = |event_schema_opt: &Option<EventSchema>| {
+            event_schema_opt.as_ref().and_then(|event_schema| {
+                serde_json::from_str::<Value>(event_schema.get_schema_data()).ok()
+            })
+        };
+        let topic_schema = parse_schema(event_descriptor.get_event_schema());
+        let event_types = event_descriptor
+            .get_event_type_field()
+            .as_ref()
+            .and_then(|_| event_descriptor.get_event_types().as_ref())
+            .map(|event_type_descriptors| {
+                event_type_descriptors
+                    .iter()
+                    .map(|event_type_descriptor| Some(event_type_descriptor.get_event_type()))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_else(|| vec![None]);
+        let mut fixtures = vec![];
+        for event_type in event_types {
+            let event_type_descriptor = event_type
+                .and_then(|event_type| event_descriptor.get_event_type_descriptor(event_type));
+            let event_type_schema = event_type_descriptor.and_then(|event_type_descriptor| {
+                parse_schema(event_type_descriptor.get_event_schema())
+            });
+            let mut document = topic_schema
+                .as_ref()
+                .map(|schema| sample_documents.valid_sample(schema))
+                .unwrap_or_else(|| Value::Object(Default::default()));
+            if let Some(schema) = &event_type_schema {
+                sample_documents::merge(&mut document, sample_documents.valid_sample(schema));
+            }
+            if let Some(event_type) = event_type
+                && let Some(event_type_field) = event_descriptor.get_event_type_field()
+            {
+                let value = Value::String(event_type.to_owned());
+                if let Some(existing) = document.pointer_mut(event_type_field) {
+                    *existing = value;
+                } else {
+                    sample_documents::insert_at_pointer(&mut document, event_type_field, value);
+                }
+            }
+            // Provide values for the extractors that the schema didn't cover
+            for extractor in event_descriptor.get_extractors().iter().flatten().chain(
+                event_type_descriptor
+                    .and_then(|event_type_descriptor| {
+                        event_type_descriptor.get_extractors().as_ref()
+                    })
+                    .into_iter()
+                    .flatten(),
+            ) {
+                let value = match extractor.get_result_type() {
+                    "text" => Value::String("example".to_owned()),
+                    "bigint" => Value::from(1),
+                    "set<text>" | "list<text>" => Value::from(vec!["example"]),
+                    "set<bigint>" | "list<bigint>" => Value::from(vec![1]),
+                    _ => continue,
+                };
+                let mut candidate = document.to_owned();
+                if sample_documents::insert_at_pointer(
+                    &mut candidate,
+                    extractor.get_extraction_path(),
+                    value,
+                ) && Self::assert_contract_compliance(
+                    event_descriptor,
+                    &candidate.to_string(),
+                    &shared_schemas,
+                )
+                .is_ok()
+                {
+                    document = candidate;
+                }
+            }
+            let event_document = document.to_string();
+            if let Err(e) =
+                Self::assert_contract_compliance(event_descriptor, &event_document, &shared_schemas)
+            {
+                log::debug!(
+                    "Unable to generate a valid sample for topic '{topic_id}' and event type {event_type:?}: {e}"
+                );
+                continue;
+            }
+            let mut column_to_value_map = HashMap::new();
+            self.extract_values_from_document(
+                topic_id,
+                event_descriptor.get_extractors(),
+                &event_document,
+                &mut column_to_value_map,
+            )?;
+            if let Some(event_type_descriptor) = event_type_descriptor {
+                self.extract_values_from_document(
+                    topic_id,
+                    event_type_descriptor.get_extractors(),
+                    &event_document,
+                    &mut column_to_value_map,
+                )?;
+            }
+            let extractions = column_to_value_map
+                .into_iter()
+                .map(|(result_name, value)| {
+                    let value = match value {
+                        ExtractedValue::Text(text) => Value::String(text),
+                        ExtractedValue::BigInt(number) => Value::from(number),
+                        ExtractedValue::TextCollection(texts) => Value::from(texts),
+                        ExtractedValue::BigIntCollection(numbers) => Value::from(numbers),
+                    };
+                    (result_name, value)
+                })
+                .collect::<BTreeMap<_, _>>();
+            let name = event_type.map_or_else(
+                || "valid sample".to_owned(),
+                |event_type| format!("valid sample of event type '{event_type}'"),
+            );
+            fixtures.push(ContractFixture::valid(
+                &name,
+                event_type,
+                document.to_owned(),
+                extractions,
+            ));
+            let mut invalid_samples = vec![];
+            for schema in topic_schema.iter().chain(event_type_schema.iter()) {
+                invalid_samples.extend(sample_documents.invalid_samples(schema, &document));
+            }
+            if let Some(event_type_field) = event_descriptor.get_event_type_field()
+                && let Some(existing) = document.pointer(event_type_field)
+            {
+                let mut invalid_document = document.to_owned();
+                let value = Value::String(format!(
+                    "{}-undescribed",
+                    existing.as_str().unwrap_or("event-type")
+                ));
+                *invalid_document.pointer_mut(event_type_field).unwrap() = value;
+                invalid_samples.push(("undescribed event type".to_owned(), invalid_document));
+            }
+            for (name, invalid_document) in invalid_samples {
+                if Self::assert_contract_compliance(
+                    event_descriptor,
+                    &invalid_document.to_string(),
+                    &shared_schemas,
+                )
+                .is_err()
+                {
+                    fixtures.push(ContractFixture::invalid(
+                        &name,
+                        event_type,
+                        invalid_document,
+                    ));
+                }
+            }
+        }
+        Ok(ContractFixtures::new(
+            topic_id,
+            event_descriptor.get_version(),
+            fixtures,
+        ))
+    }
+
+    /// Validate the document like [Self::validate_and_extract] would.
+    fn assert_contract_compliance(
         event_descriptor: &EventDescriptor,
         event_document: &str,
-    ) -> Result<HashMap<String, ExtractedValue>, MessageBrokerError> {
+        shared_schemas: &[Arc<SharedSchema>],
+    ) -> Result<(), MessageBrokerError> {
+        Self::assert_event_schema_compliance(
+            event_descriptor.get_event_schema(),
+            event_document,
+            shared_schemas,
+        )?;
+        if let Some(event_type) = Self::extract_event_type(event_descriptor, event_document)? {
+            let Some(event_type_descriptor) =
+                event_descriptor.get_event_type_descriptor(&event_type)
+            else {
+                Err(MessageBrokerErrorKind::PreStorageProcessorError
+                    .error_with_msg(format!("The event type '{event_type}' is not described.")))?
+            };
+            Self::assert_event_schema_compliance(
+                event_type_descriptor.get_event_schema(),
+                event_document,
+                shared_schemas,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Return the compiled filter expression for use in the topic.
+    pub fn compile_filter(
+        &self,
+        topic_id: &str,
+        source: &str,
+    ) -> Result<Arc<FilterExpression>, MessageBrokerError> {
+        self.filter_expression_cache
+            .get_or_compile(topic_id, source)
+    }
+
+    /// Return `true` if the filter expression matches the values extracted
+    /// from the document with the latest [EventDescriptor] of the topic.
+    ///
+    /// Values that fail to be extracted are absent, since the document might
+    /// have been published with an older version of the descriptor.
+    pub fn matches_filter(
+        &self,
+        topic_id: &str,
+        event_document: &str,
+        filter_expression: &FilterExpression,
+    ) -> bool {
         let mut column_to_value_map = HashMap::new();
-        if let Some(extractors) = event_descriptor.get_extractors() {
-            for extractor in extractors {
-                if let Some(value) = match extractor.get_extraction_type() {
-                    "jsonpointer" => jsonpointer_extraction::extract_jsonpointer(
-                        event_document,
-                        extractor.get_extraction_path(),
-                        extractor.get_result_type(),
-                    )?,
-                    extraction_type => {
-                        return Err(MessageBrokerErrorKind::PreStorageProcessorError
-                            .error_with_msg(format!(
-                                "Unsupported extraction type: '{extraction_type}'"
-                            )));
-                    }
-                } {
-                    column_to_value_map.insert(extractor.get_result_name().to_owned(), value);
+        if let Some(event_descriptor) = self
+            .event_descriptor_cache
+            .get_event_descriptor_by_topic_latest(topic_id)
+        {
+            let _ = self.extract_values_from_document(
+                topic_id,
+                event_descriptor.get_extractors(),
+                event_document,
+                &mut column_to_value_map,
+            );
+            if let Ok(Some(event_type)) =
+                Self::extract_event_type(&event_descriptor, event_document)
+                && let Some(event_type_descriptor) =
+                    event_descriptor.get_event_type_descriptor(&event_type)
+            {
+                let _ = self.extract_values_from_document(
+                    topic_id,
+                    event_type_descriptor.get_extractors(),
+                    event_document,
+                    &mut column_to_value_map,
+                );
+            }
+        }
+        filter_expression.matches(&column_to_value_map)
+    }
+
+    /// Extract indexed values from the document
+    ///
+    /// Extractors with a condition are applied after the unconditional ones
+    /// and only when the condition matches the values extracted so far.
+    fn extract_values_from_document(
+        &self,
+        topic_id: &str,
+        extractors_opt: &Option<Vec<Extractor>>,
+        event_document: &str,
+        column_to_value_map: &mut HashMap<String, ExtractedValue>,
+    ) -> Result<(), MessageBrokerError> {
+        let Some(extractors) = extractors_opt else {
+            return Ok(());
+        };
+        let (unconditional, conditional): (Vec<_>, Vec<_>) = extractors
+            .iter()
+            .partition(|extractor| extractor.get_condition().is_none());
+        for extractor in unconditional.into_iter().chain(conditional) {
+            if let Some(condition) = extractor.get_condition()
+                && !self
+                    .compile_filter(topic_id, condition)?
+                    .matches(column_to_value_map)
+            {
+                continue;
+            }
+            if let Some(value) = match extractor.get_extraction_type() {
+                "jsonpointer" => jsonpointer_extraction::extract_jsonpointer(
+                    event_document,
+                    extractor.get_extraction_path(),
+                    extractor.get_result_type(),
+                )?,
+                extraction_type => {
+                    return Err(
+                        MessageBrokerErrorKind::PreStorageProcessorError.error_with_msg(format!(
+                            "Unsupported extraction type: '{extraction_type}'"
+                        )),
+                    );
                 }
+            } {
+                column_to_value_map.insert(extractor.get_result_name().to_owned(), value);
             }
         }
-        Ok(column_to_value_map)
+        Ok(())
     }
 }
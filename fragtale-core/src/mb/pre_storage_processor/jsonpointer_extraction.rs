@@ -38,3 +38,20 @@ pub fn extract_jsonpointer(
         Ok(None)
     }
 }
+
+/// Return the text value that the JSON Pointer references (if any).
+///
+/// See [RFC 6901](https://www.rfc-editor.org/rfc/rfc6901)
+pub fn extract_jsonpointer_text(
+    document: &str,
+    pointer: &str,
+) -> Result<Option<String>, MessageBrokerError> {
+    let document: serde_json::Value = serde_json::from_str(document).map_err(|e| {
+        MessageBrokerErrorKind::PreStorageProcessorError
+            .error_with_msg(format!("Failed to parse document as JSON: {e:?}"))
+    })?;
+    Ok(document
+        .pointer(pointer)
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_owned))
+}
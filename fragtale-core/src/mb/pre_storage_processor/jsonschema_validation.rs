@@ -14,15 +14,28 @@
     See the License for the specific language governing permissions and
     limitations under the License.
 */
-
 //! JSON Schema validation.
 
+use fragtale_client::mb::shared_schemas::SharedSchema;
 use fragtale_dbp::mb::MessageBrokerError;
 use fragtale_dbp::mb::MessageBrokerErrorKind;
 use jsonschema::Draft;
+use jsonschema::Validator;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Schema type of JSON Schema draft 2020-12.
+pub const SCHEMA_TYPE_DRAFT202012: &str = "https://json-schema.org/draft/2020-12/schema";
 
 /// [JSON Schema](https://json-schema.org/) validation.
-pub fn validate_draft202012(schema: &str, document: &str) -> Result<(), MessageBrokerError> {
+///
+/// `$ref`erences to the `shared_schemas` are resolved by their schema
+/// identifier when the schema is compiled.
+pub fn validate_draft202012(
+    schema: &str,
+    document: &str,
+    shared_schemas: &[Arc<SharedSchema>],
+) -> Result<(), MessageBrokerError> {
     let schema = serde_json::from_str(schema).map_err(|e| {
         MessageBrokerErrorKind::PreStorageProcessorError
             .error_with_msg(format!("Failed to parse schema as JSON: {e:?}"))
@@ -31,16 +44,71 @@ pub fn validate_draft202012(schema: &str, document: &str) -> Result<(), MessageB
         MessageBrokerErrorKind::PreStorageProcessorError
             .error_with_msg(format!("Failed to parse document as JSON: {e:?}"))
     })?;
-    let compiled = jsonschema::options()
-        .with_draft(Draft::Draft202012)
-        .build(&schema)
-        .map_err(|e| {
-            MessageBrokerErrorKind::PreStorageProcessorError
-                .error_with_msg(format!("Failed to compile JSONSchema: {e:?}"))
-        })?;
+    let compiled = compile_draft202012(&schema, shared_schemas)?;
     compiled.validate(&document).map_err(|e| {
         log::debug!("Validation error at '{}': {}", e.instance_path, e);
         MessageBrokerErrorKind::PreStorageProcessorError
             .error_with_msg("Failed to validate document")
     })
 }
+
+/// Compile a [JSON Schema](https://json-schema.org/) where `$ref`erences to
+/// the `shared_schemas` are resolved by their schema identifier.
+pub fn compile_draft202012(
+    schema: &Value,
+    shared_schemas: &[Arc<SharedSchema>],
+) -> Result<Validator, MessageBrokerError> {
+    shared_schemas
+        .iter()
+        .filter(|shared_schema| shared_schema.get_schema_type() == SCHEMA_TYPE_DRAFT202012)
+        .try_fold(
+            jsonschema::options().with_draft(Draft::Draft202012),
+            |options, shared_schema| {
+                parse_shared_schema(shared_schema).map(|contents| {
+                    options.with_resource(
+                        shared_schema.get_schema_id(),
+                        Draft::Draft202012.create_resource(contents),
+                    )
+                })
+            },
+        )?
+        .build(schema)
+        .map_err(|e| {
+            MessageBrokerErrorKind::PreStorageProcessorError
+                .error_with_msg(format!("Failed to compile JSONSchema: {e:?}"))
+        })
+}
+
+/// Parse a shared schema component as JSON.
+pub fn parse_shared_schema(shared_schema: &SharedSchema) -> Result<Value, MessageBrokerError> {
+    serde_json::from_str(shared_schema.get_schema_data()).map_err(|e| {
+        MessageBrokerErrorKind::PreStorageProcessorError.error_with_msg(format!(
+            "Failed to parse shared schema '{}' as JSON: {e:?}",
+            shared_schema.get_schema_id()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shared_schema_references_are_resolved() {
+        let shared_schemas = vec![Arc::new(SharedSchema::new(
+            "https://example.com/schemas/address.json",
+            SCHEMA_TYPE_DRAFT202012,
+            r#"{"type": "object", "required": ["city"]}"#,
+        ))];
+        let schema = r#"{
+            "type": "object",
+            "properties": { "address": { "$ref": "https://example.com/schemas/address.json" } }
+        }"#;
+        assert!(
+            validate_draft202012(schema, r#"{"address": {"city": "X"}}"#, &shared_schemas).is_ok()
+        );
+        assert!(validate_draft202012(schema, r#"{"address": {}}"#, &shared_schemas).is_err());
+        // Unresolvable without the registered component
+        assert!(validate_draft202012(schema, r#"{"address": {"city": "X"}}"#, &[]).is_err());
+    }
+}
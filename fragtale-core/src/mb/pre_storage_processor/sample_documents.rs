@@ -0,0 +1,421 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Sample document generation from JSON Schemas.
+
+use fragtale_client::mb::shared_schemas::SharedSchema;
+use serde_json::Map;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Maximum depth of nested schemas to follow when generating samples.
+const MAX_DEPTH: usize = 16;
+
+/// Generates sample documents from JSON Schemas.
+///
+/// The samples are best effort: keywords like `pattern` or `multipleOf` are
+/// not honored, so callers should validate the samples before use.
+pub struct SampleDocuments {
+    shared_schemas: HashMap<String, Value>,
+}
+
+impl SampleDocuments {
+    /// Return a new instance where `$ref`erences to the `shared_schemas` are
+    /// resolved by their schema identifier.
+    pub fn new(shared_schemas: &[Arc<SharedSchema>]) -> Self {
+        Self {
+            shared_schemas: shared_schemas
+                .iter()
+                .filter_map(|shared_schema| {
+                    serde_json::from_str(shared_schema.get_schema_data())
+                        .ok()
+                        .map(|contents| (shared_schema.get_schema_id().to_owned(), contents))
+                })
+                .collect(),
+        }
+    }
+
+    /// Return a sample document that is intended to conform to the schema.
+    ///
+    /// Declared `const`, `default`, `examples` and `enum` values are
+    /// preferred. Otherwise a value of the first declared type is generated,
+    /// where objects get all declared properties and arrays get a single item.
+    pub fn valid_sample(&self, schema: &Value) -> Value {
+        self.sample(schema, schema, 0)
+    }
+
+    /// Return documents that are intended to violate the schema together
+    /// with a short description of each violation.
+    ///
+    /// The variants are derived from a `sample` of the schema by removing each
+    /// required property and by replacing each property of a declared type
+    /// with a value of another type.
+    pub fn invalid_samples(&self, schema: &Value, sample: &Value) -> Vec<(String, Value)> {
+        let mut ret = vec![];
+        let (root, schema) = self.follow_refs(schema, schema);
+        let Value::Object(schema) = schema else {
+            return ret;
+        };
+        if let Some(schema_type) = Self::schema_type(schema) {
+            ret.push((
+                format!("document is not of type '{schema_type}'"),
+                Self::mismatched_value(schema_type),
+            ));
+        }
+        let Value::Object(properties) = sample else {
+            return ret;
+        };
+        for required in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if properties.contains_key(required) {
+                let mut document = properties.to_owned();
+                document.remove(required);
+                ret.push((
+                    format!("missing required property '{required}'"),
+                    Value::Object(document),
+                ));
+            }
+        }
+        for (name, property_schema) in schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            let (_, property_schema) = self.follow_refs(root, property_schema);
+            if let Value::Object(property_schema) = property_schema
+                && let Some(property_type) = Self::schema_type(property_schema)
+                && properties.contains_key(name)
+            {
+                let mut document = properties.to_owned();
+                document.insert(name.to_owned(), Self::mismatched_value(property_type));
+                ret.push((
+                    format!("property '{name}' is not of type '{property_type}'"),
+                    Value::Object(document),
+                ));
+            }
+        }
+        ret
+    }
+
+    fn sample(&self, root: &Value, schema: &Value, depth: usize) -> Value {
+        let Value::Object(schema) = schema else {
+            // `true` accepts anything
+            return Value::Null;
+        };
+        if depth > MAX_DEPTH {
+            return Value::Null;
+        }
+        for keyword in ["const", "default"] {
+            if let Some(value) = schema.get(keyword) {
+                return value.to_owned();
+            }
+        }
+        for keyword in ["examples", "enum"] {
+            if let Some(value) = schema
+                .get(keyword)
+                .and_then(Value::as_array)
+                .and_then(|values| values.first())
+            {
+                return value.to_owned();
+            }
+        }
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            return self
+                .resolve_ref(root, reference)
+                .map(|(root, target)| self.sample(root, target, depth + 1))
+                .unwrap_or(Value::Null);
+        }
+        let mut ret = match Self::schema_type(schema) {
+            Some("object") => self.sample_object(root, schema, depth),
+            Some("array") => self.sample_array(root, schema, depth),
+            Some("string") => Self::sample_string(schema),
+            Some("integer") => Self::sample_number(schema, true),
+            Some("number") => Self::sample_number(schema, false),
+            Some("boolean") => Value::Bool(true),
+            Some(_) => Value::Null,
+            None if schema.contains_key("properties") => self.sample_object(root, schema, depth),
+            None => ["anyOf", "oneOf"]
+                .iter()
+                .find_map(|keyword| {
+                    schema
+                        .get(*keyword)
+                        .and_then(Value::as_array)
+                        .and_then(|alternatives| alternatives.first())
+                })
+                .map(|alternative| self.sample(root, alternative, depth + 1))
+                .unwrap_or(Value::Null),
+        };
+        for sub_schema in schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            merge(&mut ret, self.sample(root, sub_schema, depth + 1));
+        }
+        ret
+    }
+
+    fn sample_object(&self, root: &Value, schema: &Map<String, Value>, depth: usize) -> Value {
+        let mut ret = Map::new();
+        for (name, property_schema) in schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            ret.insert(
+                name.to_owned(),
+                self.sample(root, property_schema, depth + 1),
+            );
+        }
+        // Required properties without a definition accept any value
+        for required in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            ret.entry(required.to_owned()).or_insert(Value::Null);
+        }
+        Value::Object(ret)
+    }
+
+    fn sample_array(&self, root: &Value, schema: &Map<String, Value>, depth: usize) -> Value {
+        let mut ret = schema
+            .get("prefixItems")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|item_schema| self.sample(root, item_schema, depth + 1))
+            .collect::<Vec<_>>();
+        let min_items = schema
+            .get("minItems")
+            .and_then(Value::as_u64)
+            .and_then(|min_items| usize::try_from(min_items).ok())
+            .unwrap_or(0);
+        if let Some(item_schema) = schema.get("items").filter(|items| items.is_object()) {
+            let item = self.sample(root, item_schema, depth + 1);
+            while ret.len() < min_items.max(1) {
+                ret.push(item.to_owned());
+            }
+        }
+        Value::Array(ret)
+    }
+
+    fn sample_string(schema: &Map<String, Value>) -> Value {
+        let sample = match schema.get("format").and_then(Value::as_str) {
+            Some("date-time") => "1970-01-01T00:00:00Z",
+            Some("date") => "1970-01-01",
+            Some("time") => "00:00:00Z",
+            Some("email") => "user@example.com",
+            Some("hostname") => "example.com",
+            Some("ipv4") => "127.0.0.1",
+            Some("ipv6") => "::1",
+            Some("uri") => "https://example.com/",
+            Some("uuid") => "00000000-0000-0000-0000-000000000000",
+            _ => "example",
+        };
+        let length = |keyword| {
+            schema
+                .get(keyword)
+                .and_then(Value::as_u64)
+                .and_then(|length| usize::try_from(length).ok())
+        };
+        let mut ret = sample.to_owned();
+        if let Some(min_length) = length("minLength") {
+            while ret.chars().count() < min_length {
+                ret.push('x');
+            }
+        }
+        if let Some(max_length) = length("maxLength") {
+            ret = ret.chars().take(max_length).collect();
+        }
+        Value::String(ret)
+    }
+
+    fn sample_number(schema: &Map<String, Value>, integer: bool) -> Value {
+        let bound = |keyword| schema.get(keyword).and_then(Value::as_f64);
+        let step = if integer { 1.0 } else { 0.5 };
+        let number = bound("minimum")
+            .or_else(|| bound("exclusiveMinimum").map(|minimum| minimum + step))
+            .or_else(|| bound("maximum"))
+            .or_else(|| bound("exclusiveMaximum").map(|maximum| maximum - step))
+            .unwrap_or(0.0);
+        let number = if integer { number.ceil() } else { number };
+        if number.fract() == 0.0 && number.abs() < 2f64.powi(53) {
+            Value::from(number as i64)
+        } else {
+            Value::from(number)
+        }
+    }
+
+    /// Return the first declared type that isn't `null`.
+    fn schema_type(schema: &Map<String, Value>) -> Option<&str> {
+        match schema.get("type")? {
+            Value::String(schema_type) => Some(schema_type),
+            Value::Array(schema_types) => schema_types
+                .iter()
+                .filter_map(Value::as_str)
+                .find(|schema_type| *schema_type != "null"),
+            _ => None,
+        }
+    }
+
+    /// Return a value that does not conform to the schema type.
+    fn mismatched_value(schema_type: &str) -> Value {
+        match schema_type {
+            "string" => Value::from(0),
+            _ => Value::String("unexpected".to_owned()),
+        }
+    }
+
+    /// Follow top level `$ref`erences of the schema.
+    fn follow_refs<'a>(&'a self, root: &'a Value, schema: &'a Value) -> (&'a Value, &'a Value) {
+        let (mut root, mut schema) = (root, schema);
+        for _ in 0..MAX_DEPTH {
+            let Some((next_root, next_schema)) = schema
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|reference| self.resolve_ref(root, reference))
+            else {
+                break;
+            };
+            (root, schema) = (next_root, next_schema);
+        }
+        (root, schema)
+    }
+
+    /// Return the root and target schema of a reference to a location in the
+    /// current root or in a shared schema component.
+    fn resolve_ref<'a>(
+        &'a self,
+        root: &'a Value,
+        reference: &str,
+    ) -> Option<(&'a Value, &'a Value)> {
+        let (base, fragment) = reference.split_once('#').unwrap_or((reference, ""));
+        let root = if base.is_empty() {
+            root
+        } else {
+            self.shared_schemas.get(base)?
+        };
+        root.pointer(fragment).map(|target| (root, target))
+    }
+}
+
+/// Recursively add object properties of `other` that are absent in
+/// `target`.
+pub fn merge(target: &mut Value, other: Value) {
+    match (target, other) {
+        (Value::Object(target), Value::Object(other)) => {
+            for (name, value) in other {
+                if let Some(existing) = target.get_mut(&name) {
+                    merge(existing, value);
+                } else {
+                    target.insert(name, value);
+                }
+            }
+        }
+        (target, other) if target.is_null() => *target = other,
+        _ => {}
+    }
+}
+
+/// Insert the value at the JSON Pointer location unless something is already
+/// present there, creating intermediate objects as needed.
+///
+/// Return `true` if the document was modified.
+pub fn insert_at_pointer(document: &mut Value, pointer: &str, value: Value) -> bool {
+    if document.pointer(pointer).is_some() {
+        return false;
+    }
+    let Some(tokens) = pointer.strip_prefix('/') else {
+        return false;
+    };
+    let mut current = document;
+    for token in tokens.split('/') {
+        if current.is_null() {
+            *current = Value::Object(Map::new());
+        }
+        let Value::Object(properties) = current else {
+            return false;
+        };
+        // Unescape the reference token as described in RFC 6901
+        let token = token.replace("~1", "/").replace("~0", "~");
+        current = properties.entry(token).or_insert(Value::Null);
+    }
+    *current = value;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn samples_follow_the_schema() {
+        let shared_schemas = vec![Arc::new(SharedSchema::new(
+            "https://example.com/schemas/address.json",
+            "https://json-schema.org/draft/2020-12/schema",
+            r#"{"type": "object", "properties": {"city": {"type": "string", "minLength": 9}}}"#,
+        ))];
+        let schema = json!({
+            "type": "object",
+            "required": ["id", "amount"],
+            "properties": {
+                "id": { "type": "string", "format": "uuid" },
+                "amount": { "type": "integer", "exclusiveMinimum": 0 },
+                "currency": { "enum": ["EUR", "SEK"] },
+                "tags": { "type": "array", "items": { "$ref": "#/$defs/tag" } },
+                "address": { "$ref": "https://example.com/schemas/address.json" }
+            },
+            "$defs": { "tag": { "type": "string", "maxLength": 3 } }
+        });
+        let sample_documents = SampleDocuments::new(&shared_schemas);
+        let sample = sample_documents.valid_sample(&schema);
+        assert_eq!(
+            sample,
+            json!({
+                "id": "00000000-0000-0000-0000-000000000000",
+                "amount": 1,
+                "currency": "EUR",
+                "tags": ["exa"],
+                "address": { "city": "examplexx" }
+            })
+        );
+        let invalid_samples = sample_documents.invalid_samples(&schema, &sample);
+        let names = invalid_samples
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert!(names.contains(&"missing required property 'amount'"));
+        assert!(names.contains(&"property 'tags' is not of type 'array'"));
+        assert!(names.contains(&"document is not of type 'object'"));
+        let mut document = json!({});
+        assert!(insert_at_pointer(&mut document, "/a/b~1c", json!(1)));
+        assert!(!insert_at_pointer(&mut document, "/a/b~1c", json!(2)));
+        assert_eq!(document, json!({"a": {"b/c": 1}}));
+    }
+}
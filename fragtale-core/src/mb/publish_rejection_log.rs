@@ -0,0 +1,98 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Ring buffer of recently rejected publishes.
+
+use fragtale_client::mb::publish_rejections::PublishRejection;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/** Ring buffer of the most recently rejected publishes on this instance.
+
+This makes producer integration failures diagnosable without enabling debug
+logging. The oldest rejection is dropped when the buffer is full.
+*/
+pub struct PublishRejectionLog {
+    capacity: usize,
+    rejections: Mutex<VecDeque<PublishRejection>>,
+}
+
+impl PublishRejectionLog {
+    /// Return a new instance that keeps up to `capacity` rejections.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            rejections: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Keep the rejection and drop the oldest if the buffer is full.
+    pub fn record(&self, rejection: PublishRejection) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut rejections = self.rejections.lock().unwrap();
+        if rejections.len() >= self.capacity {
+            rejections.pop_front();
+        }
+        rejections.push_back(rejection);
+    }
+
+    /// Return the kept rejections with the most recent first, optionally
+    /// limited to a single topic.
+    pub fn get_recent(&self, topic_id: Option<&str>) -> Vec<PublishRejection> {
+        self.rejections
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|rejection| {
+                topic_id.is_none_or(|topic_id| rejection.get_topic_id() == topic_id)
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fragtale_client::mb::publish_rejections::PublishRejectionReason;
+
+    #[test]
+    fn oldest_rejection_is_dropped_when_full() {
+        let log = PublishRejectionLog::new(2);
+        for (topic_id, ts) in [("a", 1), ("b", 2), ("a", 3)] {
+            log.record(PublishRejection::new(
+                topic_id,
+                PublishRejectionReason::Schema,
+                ts,
+                "publisher",
+                "error",
+            ));
+        }
+        let recent = log
+            .get_recent(None)
+            .iter()
+            .map(PublishRejection::get_rejected_ts_micros)
+            .collect::<Vec<_>>();
+        assert_eq!(recent, vec![3, 2]);
+        let recent = log.get_recent(Some("a"));
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].get_rejected_ts_micros(), 3);
+    }
+}
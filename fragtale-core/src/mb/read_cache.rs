@@ -0,0 +1,150 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Bounded in-process cache of read-mostly query results.
+
+use crossbeam_skiplist::SkipMap;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/// Cached value and when it was cached.
+struct ReadCacheEntry<V> {
+    value: V,
+    cached_ts_micros: u64,
+    sequence: u64,
+}
+
+/** Bounded in-process cache of read-mostly query results.
+
+Entries expire after the configured time to live and the oldest entries are
+evicted when the cache is full. Expired entries are removed lazily on lookup
+or eviction.
+*/
+pub struct ReadCache<K, V> {
+    time_to_live_micros: u64,
+    max_entries: usize,
+    entries: SkipMap<K, ReadCacheEntry<V>>,
+    /// Insertion sequence to key for eviction of the oldest entries.
+    by_age: SkipMap<u64, K>,
+    sequence: AtomicU64,
+}
+
+impl<K, V> ReadCache<K, V>
+where
+    K: Ord + Clone + Send + 'static,
+    V: Clone + Send + 'static,
+{
+    /// Return a new instance.
+    ///
+    /// The cache is disabled if `time_to_live_micros` or `max_entries` is `0`.
+    pub fn new(time_to_live_micros: u64, max_entries: usize) -> Arc<Self> {
+        Arc::new(Self {
+            time_to_live_micros,
+            max_entries,
+            entries: SkipMap::default(),
+            by_age: SkipMap::default(),
+            sequence: AtomicU64::default(),
+        })
+    }
+
+    /// Return `true` if values will be cached.
+    pub fn is_enabled(&self) -> bool {
+        self.time_to_live_micros > 0 && self.max_entries > 0
+    }
+
+    /// Return a cached value unless missing or expired.
+    pub fn get(&self, key: &K) -> Option<V> {
+        if !self.is_enabled() {
+            return None;
+        }
+        let entry = self.entries.get(key)?;
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        if entry.value().cached_ts_micros + self.time_to_live_micros < now_micros {
+            entry.remove();
+            return None;
+        }
+        Some(entry.value().value.clone())
+    }
+
+    /// Cache a value and evict the oldest entries if the cache is full.
+    pub fn insert(&self, key: K, value: V) {
+        if !self.is_enabled() {
+            return;
+        }
+        let sequence = self.sequence.fetch_add(1, Ordering::Relaxed);
+        self.entries.insert(
+            key.clone(),
+            ReadCacheEntry {
+                value,
+                cached_ts_micros: fragtale_client::time::get_timestamp_micros(),
+                sequence,
+            },
+        );
+        self.by_age.insert(sequence, key);
+        while self.by_age.len() > self.max_entries {
+            let Some(oldest) = self.by_age.pop_front() else {
+                break;
+            };
+            // Only remove the entry if it has not been replaced since
+            if let Some(entry) = self.entries.get(oldest.value())
+                && entry.value().sequence == *oldest.key()
+            {
+                entry.remove();
+            }
+        }
+    }
+
+    /// Remove a cached value (if present).
+    pub fn remove(&self, key: &K) {
+        self.entries.remove(key);
+    }
+
+    /// Remove all cached values with a key matching `predicate`.
+    pub fn remove_matching<P: Fn(&K) -> bool>(&self, predicate: P) {
+        self.entries
+            .iter()
+            .filter(|entry| predicate(entry.key()))
+            .for_each(|entry| {
+                entry.remove();
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_oldest_and_expires() {
+        let read_cache = ReadCache::new(60_000_000, 2);
+        read_cache.insert("a", 1);
+        read_cache.insert("b", 2);
+        read_cache.insert("a", 3);
+        read_cache.insert("c", 4);
+        assert_eq!(read_cache.get(&"a"), Some(3));
+        assert_eq!(read_cache.get(&"b"), None);
+        assert_eq!(read_cache.get(&"c"), Some(4));
+        read_cache.remove_matching(|key| key.starts_with('c'));
+        assert_eq!(read_cache.get(&"a"), Some(3));
+        assert_eq!(read_cache.get(&"c"), None);
+        let read_cache = ReadCache::new(1, 2);
+        read_cache.insert("a", 1);
+        std::thread::sleep(std::time::Duration::from_millis(1));
+        assert_eq!(read_cache.get(&"a"), None);
+    }
+}
@@ -0,0 +1,256 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Dry-run preview of what a proposed retention would delete.
+
+use fragtale_client::mb::retention_preview::ConsumerRetentionPreview;
+use fragtale_client::mb::retention_preview::RetentionPreview;
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+use futures::StreamExt;
+use std::sync::Arc;
+
+/// Dry-run preview of what a proposed retention would delete.
+///
+/// Retention is assumed to delete whole buckets of events that are older than
+/// the bucket where the retention cutoff falls. Nothing is ever deleted here.
+pub struct RetentionPreviewer {
+    dbp: Arc<DatabaseProvider>,
+}
+
+impl RetentionPreviewer {
+    /// Max number of buckets previewed in a single report.
+    const MAX_BUCKETS: u64 = 1024;
+    /// Number of buckets requested from the database at a time.
+    const BUCKETS_PAGE_SIZE: usize = 32;
+    /// Number of bucket entries requested from the database at a time.
+    const ENTRIES_PAGE_SIZE: usize = 256;
+    /// Number of event documents sampled per bucket for the size estimate.
+    const SIZE_SAMPLES_PER_BUCKET: usize = 4;
+
+    /// Return a new instance.
+    pub fn new(dbp: &Arc<DatabaseProvider>) -> Arc<Self> {
+        Arc::new(Self {
+            dbp: Arc::clone(dbp),
+        })
+    }
+
+    /// Walk the buckets that would be deleted by a retention of
+    /// `max_age_seconds` starting at `from_bucket` and report the outcome.
+    pub async fn preview(
+        &self,
+        topic_id: &str,
+        max_age_seconds: u64,
+        from_bucket: Option<u64>,
+    ) -> RetentionPreview {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let cutoff_bucket = UniqueTime::from(UniqueTime::min_encoded_for_micros(
+            now_micros.saturating_sub(max_age_seconds.saturating_mul(1_000_000)),
+        ))
+        .get_bucket();
+        let cutoff = UniqueTime::from(UniqueTime::min_encoded_in_bucket(cutoff_bucket));
+        let mut consumers = Vec::new();
+        for consumer_id in self
+            .dbp
+            .consumer_delivery_facade()
+            .consumer_ids(topic_id)
+            .await
+        {
+            let watermark = self
+                .dbp
+                .consumer_delivery_facade()
+                .consumer_get_done_by_id(topic_id, &consumer_id)
+                .await;
+            consumers.push((consumer_id, watermark, 0u64));
+        }
+        let mut bucket_count = 0u64;
+        let mut event_count = 0u64;
+        let mut estimated_bytes = 0u64;
+        let mut next_bucket = None;
+        let start_shelf = from_bucket.map_or(0, |from_bucket| {
+            UniqueTime::from(UniqueTime::min_encoded_in_bucket(from_bucket)).get_shelf()
+        });
+        // The bucket queries are exclusive of the provided bucket
+        let mut current_bucket = from_bucket.and_then(|from_bucket| from_bucket.checked_sub(1));
+        'shelves: for shelf in start_shelf..=cutoff.get_shelf() {
+            loop {
+                let (buckets, more) = self
+                    .dbp
+                    .event_facade()
+                    .buckets_by_shelf(topic_id, shelf, current_bucket, Self::BUCKETS_PAGE_SIZE)
+                    .await;
+                for bucket in buckets {
+                    if bucket >= cutoff_bucket {
+                        break 'shelves;
+                    }
+                    if bucket_count == Self::MAX_BUCKETS {
+                        next_bucket = Some(bucket);
+                        break 'shelves;
+                    }
+                    let count = self
+                        .dbp
+                        .event_facade()
+                        .event_count_by_bucket(topic_id, bucket)
+                        .await;
+                    bucket_count += 1;
+                    event_count += count;
+                    estimated_bytes += self.estimate_bucket_bytes(topic_id, bucket, count).await;
+                    for (_consumer_id, watermark, undelivered_event_count) in &mut consumers {
+                        *undelivered_event_count += match watermark {
+                            Some(watermark) if watermark.get_bucket() > bucket => 0,
+                            Some(watermark) if watermark.get_bucket() == bucket => {
+                                self.count_bucket_events_after(topic_id, bucket, *watermark)
+                                    .await
+                            }
+                            _ => count,
+                        };
+                    }
+                    current_bucket = Some(bucket);
+                }
+                if !more {
+                    break;
+                }
+            }
+            current_bucket = None;
+        }
+        let oldest_surviving_unique_time = self
+            .first_event_from(topic_id, cutoff, now_micros)
+            .await
+            .as_ref()
+            .map(UniqueTime::as_encoded);
+        let mut consumer_previews = Vec::with_capacity(consumers.len());
+        for (consumer_id, watermark, undelivered_event_count) in consumers {
+            let from = watermark
+                .map(|watermark| UniqueTime::from(watermark.as_encoded().saturating_add(1)))
+                .filter(|from| from.as_encoded() > cutoff.as_encoded())
+                .unwrap_or(cutoff);
+            consumer_previews.push(ConsumerRetentionPreview::new(
+                &consumer_id,
+                watermark.as_ref().map(UniqueTime::as_encoded),
+                undelivered_event_count,
+                self.first_event_from(topic_id, from, now_micros)
+                    .await
+                    .as_ref()
+                    .map(UniqueTime::as_encoded),
+            ));
+        }
+        RetentionPreview::new(
+            topic_id,
+            max_age_seconds,
+            cutoff.get_time_micros(),
+            bucket_count,
+            event_count,
+            estimated_bytes,
+            oldest_surviving_unique_time,
+            consumer_previews,
+            next_bucket,
+        )
+    }
+
+    /// Estimate the size of the event documents in a bucket from a sample of
+    /// the first events in the bucket.
+    async fn estimate_bucket_bytes(&self, topic_id: &str, bucket: u64, event_count: u64) -> u64 {
+        let (entries, _more) = self
+            .dbp
+            .event_facade()
+            .events_by_bucket(topic_id, bucket, None, Self::SIZE_SAMPLES_PER_BUCKET)
+            .await;
+        let mut samples = 0u64;
+        let mut sampled_bytes = 0u64;
+        for (unique_time, event_id, _descriptor_version) in entries {
+            if let Some(event_delivery_gist) = self
+                .dbp
+                .event_facade()
+                .event_by_id_and_unique_time(topic_id, &event_id, unique_time)
+                .await
+            {
+                samples += 1;
+                sampled_bytes +=
+                    u64::try_from(event_delivery_gist.get_document().len()).unwrap_or_default();
+            }
+        }
+        if samples == 0 {
+            return 0;
+        }
+        sampled_bytes / samples * event_count
+    }
+
+    /// Count the events in a bucket that are newer than `after`.
+    async fn count_bucket_events_after(
+        &self,
+        topic_id: &str,
+        bucket: u64,
+        after: UniqueTime,
+    ) -> u64 {
+        let count = self
+            .dbp
+            .event_facade()
+            .events_by_bucket_stream(topic_id, bucket, Some(after), Self::ENTRIES_PAGE_SIZE)
+            .count()
+            .await;
+        u64::try_from(count).unwrap_or_default()
+    }
+
+    /// Return the UniqueTime of the oldest event at or after `from`.
+    async fn first_event_from(
+        &self,
+        topic_id: &str,
+        from: UniqueTime,
+        now_micros: u64,
+    ) -> Option<UniqueTime> {
+        // The entry query is exclusive of the provided UniqueTime
+        let exclusive_from = from.as_encoded().checked_sub(1).map(UniqueTime::from);
+        let first_bucket = from.get_bucket();
+        let (entries, _more) = self
+            .dbp
+            .event_facade()
+            .events_by_bucket(topic_id, first_bucket, exclusive_from, 1)
+            .await;
+        if let Some((unique_time, _event_id, _descriptor_version)) = entries.first() {
+            return Some(*unique_time);
+        }
+        let now_shelf =
+            UniqueTime::from(UniqueTime::min_encoded_for_micros(now_micros)).get_shelf();
+        let mut current_bucket = Some(first_bucket);
+        for shelf in from.get_shelf()..=now_shelf {
+            loop {
+                let (buckets, more) = self
+                    .dbp
+                    .event_facade()
+                    .buckets_by_shelf(topic_id, shelf, current_bucket, Self::BUCKETS_PAGE_SIZE)
+                    .await;
+                for bucket in buckets {
+                    let (entries, _more) = self
+                        .dbp
+                        .event_facade()
+                        .events_by_bucket(topic_id, bucket, None, 1)
+                        .await;
+                    if let Some((unique_time, _event_id, _descriptor_version)) = entries.first() {
+                        return Some(*unique_time);
+                    }
+                    current_bucket = Some(bucket);
+                }
+                if !more {
+                    break;
+                }
+            }
+            current_bucket = None;
+        }
+        None
+    }
+}
@@ -0,0 +1,234 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Supervision of long running background tasks.
+
+use super::mb_metrics::MessageBrokerMetrics;
+use crossbeam_skiplist::SkipMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use tokio::task::AbortHandle;
+use tokio::time::Duration;
+use tokio::time::sleep;
+
+/// State of a supervised task.
+struct SupervisedTask {
+    /// Kind of task used to group tasks in metrics.
+    kind: &'static str,
+    /// Max time between heartbeats before the task is considered stuck.
+    max_silence_micros: u64,
+    /// Epoch microseconds of the latest heartbeat or (re)start.
+    heartbeat_micros: AtomicU64,
+    /// Number of restarts since the latest heartbeat.
+    failed_restarts: AtomicU64,
+    /// Set when the task was aborted since it was considered stuck.
+    stuck: AtomicBool,
+    /// Handle to abort the current run of the task.
+    abort_handle: Mutex<Option<AbortHandle>>,
+}
+
+/// Handle used by a supervised task to signal that it is making progress.
+#[derive(Clone)]
+pub struct TaskHeartbeat {
+    task: Arc<SupervisedTask>,
+}
+
+impl TaskHeartbeat {
+    /// Signal that the task is still making progress.
+    pub fn beat(&self) {
+        self.task.heartbeat_micros.store(
+            fragtale_client::time::get_timestamp_micros(),
+            Ordering::Relaxed,
+        );
+        self.task.failed_restarts.store(0, Ordering::Relaxed);
+    }
+}
+
+/** Supervision of long running background tasks.
+
+Each supervised task sends heartbeats while it is making progress. A task that
+dies from a panic is restarted with an exponential backoff and a task that has
+not sent a heartbeat within its max silence is aborted and restarted.
+
+Tasks that keep failing without ever sending a heartbeat in between restarts
+make the instance unhealthy, so the platform can replace it.
+*/
+pub struct TaskSupervisor {
+    metrics: Option<Arc<MessageBrokerMetrics>>,
+    tasks: SkipMap<String, Arc<SupervisedTask>>,
+}
+
+impl TaskSupervisor {
+    /// Interval between checks for tasks that stopped sending heartbeats.
+    const WATCH_INTERVAL_MICROS: u64 = 5_000_000;
+    /// Delay before the first restart of a failed task.
+    const RESTART_BACKOFF_MIN_MICROS: u64 = 1_000_000;
+    /// Max delay between restarts of a failed task.
+    const RESTART_BACKOFF_MAX_MICROS: u64 = 60_000_000;
+    /// Restarts without a heartbeat in between before the instance is
+    /// considered unhealthy.
+    const UNHEALTHY_FAILED_RESTARTS: u64 = 3;
+
+    /// Return a new instance.
+    pub fn new(metrics: &Option<Arc<MessageBrokerMetrics>>) -> Arc<Self> {
+        Arc::new(Self {
+            metrics: metrics.clone(),
+            tasks: SkipMap::default(),
+        })
+        .initialize()
+    }
+
+    /// Kick off background watch of heartbeats.
+    fn initialize(self: Arc<Self>) -> Arc<Self> {
+        let self_clone = Arc::clone(&self);
+        tokio::spawn(async move {
+            loop {
+                sleep(Duration::from_micros(Self::WATCH_INTERVAL_MICROS)).await;
+                self_clone.abort_stuck_tasks();
+            }
+        });
+        self
+    }
+
+    /// Spawn a supervised task named `name` that is restarted using
+    /// `task_fn` whenever it dies or stops sending heartbeats for longer
+    /// than `max_silence_micros`.
+    ///
+    /// Supervision ends when the task returns.
+    pub fn spawn<F, Fut>(
+        self: &Arc<Self>,
+        kind: &'static str,
+        name: String,
+        max_silence_micros: u64,
+        task_fn: F,
+    ) where
+        F: Fn(TaskHeartbeat) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let task = Arc::new(SupervisedTask {
+            kind,
+            max_silence_micros,
+            heartbeat_micros: AtomicU64::new(fragtale_client::time::get_timestamp_micros()),
+            failed_restarts: AtomicU64::new(0),
+            stuck: AtomicBool::new(false),
+            abort_handle: Mutex::new(None),
+        });
+        self.tasks.insert(name.clone(), Arc::clone(&task));
+        let self_clone = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                task.heartbeat_micros.store(
+                    fragtale_client::time::get_timestamp_micros(),
+                    Ordering::Relaxed,
+                );
+                let join_handle = tokio::spawn(task_fn(TaskHeartbeat {
+                    task: Arc::clone(&task),
+                }));
+                if let Ok(mut abort_handle) = task.abort_handle.lock() {
+                    abort_handle.replace(join_handle.abort_handle());
+                }
+                match join_handle.await {
+                    Ok(()) => break,
+                    Err(e) if e.is_panic() => {
+                        log::error!("Background task '{name}' died and will be restarted.");
+                    }
+                    Err(_) if task.stuck.swap(false, Ordering::Relaxed) => {
+                        log::warn!("Background task '{name}' got stuck and will be restarted.");
+                    }
+                    Err(_) => break,
+                }
+                if let Some(metrics) = &self_clone.metrics {
+                    metrics.inc_background_task_restarts(task.kind);
+                }
+                let failed_restarts = task.failed_restarts.fetch_add(1, Ordering::Relaxed);
+                let backoff_micros = Self::RESTART_BACKOFF_MIN_MICROS
+                    .saturating_mul(1 << failed_restarts.min(16))
+                    .min(Self::RESTART_BACKOFF_MAX_MICROS);
+                sleep(Duration::from_micros(backoff_micros)).await;
+            }
+            self_clone.tasks.remove(&name);
+        });
+    }
+
+    /// Abort tasks that have not sent a heartbeat within their max silence.
+    fn abort_stuck_tasks(&self) {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        for entry in self.tasks.iter() {
+            let task = entry.value();
+            let heartbeat_micros = task.heartbeat_micros.load(Ordering::Relaxed);
+            if heartbeat_micros + task.max_silence_micros < now_micros
+                && !task.stuck.swap(true, Ordering::Relaxed)
+            {
+                log::warn!(
+                    "Background task '{}' has not sent a heartbeat for {} micros.",
+                    entry.key(),
+                    now_micros - heartbeat_micros
+                );
+                if let Some(metrics) = &self.metrics {
+                    metrics.inc_background_tasks_stuck(task.kind);
+                }
+                // Takes effect when the task awaits the next time
+                if let Ok(abort_handle) = task.abort_handle.lock()
+                    && let Some(abort_handle) = abort_handle.as_ref()
+                {
+                    abort_handle.abort();
+                }
+            }
+        }
+    }
+
+    /// Return `true` unless a task keeps failing without making any progress.
+    pub fn is_healthy(&self) -> bool {
+        self.tasks.iter().all(|entry| {
+            entry.value().failed_restarts.load(Ordering::Relaxed) < Self::UNHEALTHY_FAILED_RESTARTS
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn task_that_dies_is_restarted() {
+        let task_supervisor = TaskSupervisor::new(&None);
+        let runs = Arc::new(AtomicU64::new(0));
+        let runs_clone = Arc::clone(&runs);
+        task_supervisor.spawn("test", "test".to_owned(), 60_000_000, move |heartbeat| {
+            let runs = Arc::clone(&runs_clone);
+            async move {
+                heartbeat.beat();
+                if runs.fetch_add(1, Ordering::Relaxed) == 0 {
+                    panic!("Expected failure of the first run.");
+                }
+            }
+        });
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while task_supervisor.tasks.contains_key("test") || runs.load(Ordering::Relaxed) < 2 {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(runs.load(Ordering::Relaxed), 2);
+        assert!(task_supervisor.is_healthy());
+    }
+}
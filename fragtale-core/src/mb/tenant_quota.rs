@@ -0,0 +1,93 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+//! Quotas of tenants.
+
+use crossbeam_skiplist::SkipMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/** Per-tenant limit of published events per second.
+
+The limit is enforced per instance over fixed one second windows. The window
+and the number of events published in it are packed into a single atomic with
+the second in the upper 32 bits and the count in the lower 32 bits.
+*/
+pub struct TenantQuota {
+    max_events_per_second: Option<u64>,
+    windows: SkipMap<String, AtomicU64>,
+}
+
+impl TenantQuota {
+    /// Return a new instance. No limit is enforced when
+    /// `max_events_per_second` is `None`.
+    pub fn new(max_events_per_second: Option<u64>) -> Self {
+        Self {
+            // The count must fit in the lower 32 bits
+            max_events_per_second: max_events_per_second.map(|max| max.min(0xffff_ffff)),
+            windows: SkipMap::default(),
+        }
+    }
+
+    /// Return `true` if the tenant may publish another `count` events at
+    /// `now_micros` and account for them.
+    pub fn try_acquire(&self, tenant: &str, count: u64, now_micros: u64) -> bool {
+        let Some(max_events_per_second) = self.max_events_per_second else {
+            return true;
+        };
+        let second = (now_micros / 1_000_000) & 0xffff_ffff;
+        let entry = self.windows.get(tenant).unwrap_or_else(|| {
+            self.windows
+                .get_or_insert_with(tenant.to_owned(), AtomicU64::default)
+        });
+        entry
+            .value()
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |packed| {
+                let used = if packed >> 32 == second {
+                    packed & 0xffff_ffff
+                } else {
+                    0
+                };
+                (used + count <= max_events_per_second).then_some((second << 32) | (used + count))
+            })
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_resets_with_each_second() {
+        let tenant_quota = TenantQuota::new(Some(2));
+        assert!(tenant_quota.try_acquire("a", 1, 1_000_000));
+        assert!(tenant_quota.try_acquire("a", 1, 1_500_000));
+        assert!(!tenant_quota.try_acquire("a", 1, 1_999_999));
+        assert!(tenant_quota.try_acquire("b", 1, 1_999_999));
+        assert!(tenant_quota.try_acquire("a", 1, 2_000_000));
+        assert!(TenantQuota::new(None).try_acquire("a", 1, 0));
+    }
+
+    #[test]
+    fn batches_are_accounted_as_a_whole() {
+        let tenant_quota = TenantQuota::new(Some(5));
+        assert!(tenant_quota.try_acquire("a", 3, 1_000_000));
+        assert!(!tenant_quota.try_acquire("a", 3, 1_000_001));
+        assert!(tenant_quota.try_acquire("a", 2, 1_000_002));
+        assert!(!tenant_quota.try_acquire("a", 6, 2_000_000));
+    }
+}
@@ -0,0 +1,44 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Handling of requests that would create a topic that has not been set up
+//! yet.
+
+/// Handling of requests that would create a topic that has not been set up
+/// yet.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TopicCreationPolicy {
+    /// Create topics on first use.
+    #[default]
+    Auto,
+    /// Only create topics for identities with an explicit grant.
+    Restricted,
+    /// Only create topics when explicitly registered.
+    Disabled,
+}
+
+impl TopicCreationPolicy {
+    /// Return the policy with the `name` if supported.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "auto" => Some(Self::Auto),
+            "restricted" => Some(Self::Restricted),
+            "disabled" => Some(Self::Disabled),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,191 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Consistent export of a topic as of a point in time.
+
+use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+use futures::StreamExt;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::mpsc::Receiver;
+use tokio::sync::mpsc::Sender;
+
+/// Consistent export of a topic as of a point in time.
+///
+/// Events are immutable once persisted, so walking the buckets up to a
+/// [UniqueTime] yields the same result every time (until retention removes
+/// the oldest buckets).
+pub struct TopicSnapshotter {
+    dbp: Arc<DatabaseProvider>,
+}
+
+impl TopicSnapshotter {
+    /// Number of buckets requested from the database at a time.
+    const BUCKETS_PAGE_SIZE: usize = 32;
+    /// Number of bucket entries requested from the database at a time.
+    const ENTRIES_PAGE_SIZE: usize = 256;
+    /// Number of serialized events buffered ahead of the reader.
+    const CHANNEL_CAPACITY: usize = 256;
+
+    /// Return a new instance.
+    pub fn new(dbp: &Arc<DatabaseProvider>) -> Arc<Self> {
+        Arc::new(Self {
+            dbp: Arc::clone(dbp),
+        })
+    }
+
+    /// Stream the events of the topic up to and including `as_of` as NDJSON
+    /// lines.
+    ///
+    /// When `key_pointer` (a JSON Pointer) is present, only the latest event
+    /// for each distinct value at this location is returned. Events without a
+    /// value at the location are then left out.
+    ///
+    /// The walk stops when the returned [Receiver] is dropped.
+    pub fn snapshot(
+        self: &Arc<Self>,
+        topic_id: &str,
+        as_of: UniqueTime,
+        key_pointer: Option<String>,
+    ) -> Receiver<String> {
+        let (tx, rx) = tokio::sync::mpsc::channel(Self::CHANNEL_CAPACITY);
+        let self_clone = Arc::clone(self);
+        let topic_id = topic_id.to_owned();
+        tokio::spawn(async move {
+            self_clone
+                .walk(&topic_id, as_of, key_pointer.as_deref(), &tx)
+                .await;
+        });
+        rx
+    }
+
+    /// Walk all buckets up to `as_of` and send serialized events to `tx`.
+    async fn walk(
+        &self,
+        topic_id: &str,
+        as_of: UniqueTime,
+        key_pointer: Option<&str>,
+        tx: &Sender<String>,
+    ) {
+        // Latest (encoded UniqueTime, line) by extracted key.
+        let mut latest_by_key = HashMap::<String, (u64, String)>::new();
+        let mut current_bucket = None;
+        'shelves: for shelf in 0..=as_of.get_shelf() {
+            loop {
+                let (buckets, more) = self
+                    .dbp
+                    .event_facade()
+                    .buckets_by_shelf(topic_id, shelf, current_bucket, Self::BUCKETS_PAGE_SIZE)
+                    .await;
+                for bucket in buckets {
+                    if bucket > as_of.get_bucket() {
+                        break 'shelves;
+                    }
+                    let mut entries = self.dbp.event_facade().events_by_bucket_stream(
+                        topic_id,
+                        bucket,
+                        None,
+                        Self::ENTRIES_PAGE_SIZE,
+                    );
+                    while let Some((unique_time, event_id, _descriptor_version)) =
+                        entries.next().await
+                    {
+                        if unique_time.as_encoded() > as_of.as_encoded() {
+                            break 'shelves;
+                        }
+                        let Some(event_delivery_gist) = self
+                            .dbp
+                            .event_facade()
+                            .event_by_id_and_unique_time(topic_id, &event_id, unique_time)
+                            .await
+                        else {
+                            continue;
+                        };
+                        let document = event_delivery_gist.get_document();
+                        let line = Self::as_line(&event_id, &unique_time, document);
+                        if let Some(key_pointer) = key_pointer {
+                            if let Some(key) = Self::extract_key(document, key_pointer) {
+                                latest_by_key.insert(key, (unique_time.as_encoded(), line));
+                            }
+                        } else if tx.send(line).await.is_err() {
+                            // The reader is gone
+                            return;
+                        }
+                    }
+                    current_bucket = Some(bucket);
+                }
+                if !more {
+                    break;
+                }
+            }
+            current_bucket = None;
+        }
+        let mut latest = latest_by_key.into_values().collect::<Vec<_>>();
+        latest.sort_unstable_by_key(|(encoded_unique_time, _line)| *encoded_unique_time);
+        for (_encoded_unique_time, line) in latest {
+            if tx.send(line).await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Serialize an event as a single line of JSON.
+    fn as_line(event_id: &str, unique_time: &UniqueTime, document: &str) -> String {
+        let mut line = serde_json::json!({
+            "event_id": event_id,
+            "unique_time": unique_time.as_encoded(),
+            "document": serde_json::from_str::<serde_json::Value>(document)
+                .unwrap_or_else(|_| serde_json::Value::String(document.to_owned())),
+        })
+        .to_string();
+        line.push('\n');
+        line
+    }
+
+    /// Return the value at `key_pointer` in the document as a String.
+    fn extract_key(document: &str, key_pointer: &str) -> Option<String> {
+        serde_json::from_str::<serde_json::Value>(document)
+            .ok()?
+            .pointer(key_pointer)
+            .map(|value| match value {
+                serde_json::Value::String(value) => value.to_owned(),
+                value => value.to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_text_and_non_text_keys() {
+        let document = r#"{"id":"a","nested":{"n":42},"flag":true}"#;
+        assert_eq!(
+            TopicSnapshotter::extract_key(document, "/id").as_deref(),
+            Some("a")
+        );
+        assert_eq!(
+            TopicSnapshotter::extract_key(document, "/nested/n").as_deref(),
+            Some("42")
+        );
+        assert_eq!(TopicSnapshotter::extract_key(document, "/missing"), None);
+        assert_eq!(TopicSnapshotter::extract_key("not json", "/id"), None);
+    }
+}
@@ -20,6 +20,8 @@
 use crossbeam_skiplist::SkipMap;
 use fragtale_dbp::dbp::DatabaseProvider;
 use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
 use fragtale_dbp::mb::UniqueTime;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
@@ -32,6 +34,18 @@ conflict for the local node.
 
 By leveraging an instance identifier that unique across all instances, we can
 construct a combined [UniqueTime] that is also unique across the cluster.
+
+The event time used for the local instance is strictly increasing. If the
+local clock stalls or steps backwards, the event time will run ahead of the
+clock up to a tolerable limit, before further stamping is refused.
+
+Consumers get events ordered by [UniqueTime]. To favor important events,
+the time of lower priority events is delayed by up to the priority aging
+duration. This is equivalent to an effective priority that improves with age
+at a fixed rate: an event of priority `0` that has waited for the full
+duration is ordered before any fresh event regardless of priority, so no
+priority can be starved. Events of the highest priority `100` (the default)
+are not delayed.
 */
 pub struct UniqueTimeStamper {
     /// See [DatabaseProvider].
@@ -48,6 +62,12 @@ pub struct UniqueTimeStamper {
     oldest_instance_claim_ts_cache: AtomicU64,
     /// Cache update time. See [Self::get_oldest_first_claim_ts_micros].
     oldest_instance_claim_ts_check: AtomicU64,
+    /// Latest event epoch micros time used by this instance.
+    latest_event_ts_micros: AtomicU64,
+    /// How far ahead of the local clock event time may run.
+    tolerable_clock_stall_micros: u64,
+    /// Time for the effective priority of an event to improve from 0 to 100.
+    priority_aging_micros: u64,
 }
 
 impl UniqueTimeStamper {
@@ -56,7 +76,11 @@ impl UniqueTimeStamper {
     pub const CLAIM_TIME_TO_LIVE_SECONDS: u32 = 900;
 
     /// Return a new instance.
-    pub async fn new(dbp: &Arc<DatabaseProvider>) -> Arc<Self> {
+    pub async fn new(
+        dbp: &Arc<DatabaseProvider>,
+        tolerable_clock_stall_micros: u64,
+        priority_aging_micros: u64,
+    ) -> Arc<Self> {
         let latest_claim_success_micros = fragtale_client::time::get_timestamp_micros();
         let instance_id = Self::claim_instance_id(dbp).await;
         Arc::new(Self {
@@ -67,6 +91,9 @@ impl UniqueTimeStamper {
             used_timestamps: SkipMap::default(),
             oldest_instance_claim_ts_cache: AtomicU64::default(),
             oldest_instance_claim_ts_check: AtomicU64::default(),
+            latest_event_ts_micros: AtomicU64::default(),
+            tolerable_clock_stall_micros,
+            priority_aging_micros,
         })
         .initialize()
         .await
@@ -189,29 +216,146 @@ impl UniqueTimeStamper {
         }
     }
 
-    /// Transform event time into a unique timestamp.
-    pub fn get_unique_timestamp(&self, event_ts_micros: u64, priority: u8) -> UniqueTime {
+    /// Transform the current local time into a unique timestamp.
+    ///
+    /// The clock is read at stamping time, so time spent by the caller before
+    /// stamping can't make the clock appear to stall.
+    ///
+    /// Return the unique timestamp and `true` if the local clock had not
+    /// advanced since the last stamping, so the monotonic fallback was used.
+    ///
+    /// Errors out with [MessageBrokerErrorKind::TrustedTimeError] when the
+    /// fallback would run further ahead of the local clock than tolerated.
+    pub fn get_unique_timestamp(
+        &self,
+        priority: u8,
+    ) -> Result<(UniqueTime, bool), MessageBrokerError> {
+        let event_ts_micros = fragtale_client::time::get_timestamp_micros();
+        let monotonic_ts = Self::next_monotonic_ts(
+            &self.latest_event_ts_micros,
+            event_ts_micros,
+            self.tolerable_clock_stall_micros,
+        )
+        .ok_or_else(|| self.clock_stall_error())?;
+        let is_fallback = monotonic_ts != event_ts_micros;
+        self.claim_unique_timestamp(monotonic_ts, priority)
+            .map(|unique_time| (unique_time, is_fallback))
+    }
+
+    /// Transform the current local time into `count` consecutive unique
+    /// timestamps that are reserved at once.
+    ///
+    /// See [Self::get_unique_timestamp].
+    pub fn get_unique_timestamps(
+        &self,
+        priority: u8,
+        count: usize,
+    ) -> Result<(Vec<UniqueTime>, bool), MessageBrokerError> {
+        let event_ts_micros = fragtale_client::time::get_timestamp_micros();
+        let monotonic_ts = Self::next_monotonic_range(
+            &self.latest_event_ts_micros,
+            event_ts_micros,
+            count as u64,
+            self.tolerable_clock_stall_micros,
+        )
+        .ok_or_else(|| self.clock_stall_error())?;
+        let is_fallback = monotonic_ts != event_ts_micros;
+        (0..count as u64)
+            .map(|offset| self.claim_unique_timestamp(monotonic_ts + offset, priority))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|unique_times| (unique_times, is_fallback))
+    }
+
+    /// Return the error used when stamping would run too far ahead of the
+    /// local clock.
+    fn clock_stall_error(&self) -> MessageBrokerError {
+        MessageBrokerErrorKind::TrustedTimeError.error_with_msg(format!(
+            "Refusing unique time stamping since the local clock is more than {} micros behind previously used time.",
+            self.tolerable_clock_stall_micros
+        ))
+    }
+
+    /// Claim the first unused priority adjusted time at or after
+    /// `monotonic_ts` for the local instance.
+    fn claim_unique_timestamp(
+        &self,
+        monotonic_ts: u64,
+        priority: u8,
+    ) -> Result<UniqueTime, MessageBrokerError> {
         let marker = self
             .marker_generator
             .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
         for i in 0..100 {
             // Account for priority so thet we check for priority_ts uniqueness in this instance.
-            let priority_ts = Self::get_priority_ts(event_ts_micros + i, priority);
+            let priority_ts =
+                Self::get_priority_ts(monotonic_ts + i, priority, self.priority_aging_micros);
             let entry = self.used_timestamps.get_or_insert(priority_ts, marker);
             if &marker == entry.value() {
                 // Successful claim of unique time
-                return UniqueTime::new(priority_ts, self.instance_id);
+                return Ok(UniqueTime::new(priority_ts, self.instance_id));
+            }
+        }
+        Err(MessageBrokerErrorKind::Unspecified
+            .error_with_msg("Failed to generate unique timestamp after 100 attempts."))
+    }
+
+    /// Return a strictly increasing event time that is `event_ts_micros` when
+    /// the clock has advanced since `latest_event_ts_micros` and otherwise
+    /// the next micro after the latest.
+    ///
+    /// Return `None` if this would be more than `tolerable_clock_stall_micros`
+    /// ahead of `event_ts_micros`.
+    fn next_monotonic_ts(
+        latest_event_ts_micros: &AtomicU64,
+        event_ts_micros: u64,
+        tolerable_clock_stall_micros: u64,
+    ) -> Option<u64> {
+        Self::next_monotonic_range(
+            latest_event_ts_micros,
+            event_ts_micros,
+            1,
+            tolerable_clock_stall_micros,
+        )
+    }
+
+    /// Return the first of `count` strictly increasing event times that
+    /// start at `event_ts_micros` when the clock has advanced since
+    /// `latest_event_ts_micros` and otherwise at the next micro after the
+    /// latest.
+    ///
+    /// Return `None` if the last of them would be more than
+    /// `tolerable_clock_stall_micros` ahead of `event_ts_micros`.
+    fn next_monotonic_range(
+        latest_event_ts_micros: &AtomicU64,
+        event_ts_micros: u64,
+        count: u64,
+        tolerable_clock_stall_micros: u64,
+    ) -> Option<u64> {
+        let count = std::cmp::max(count, 1);
+        let mut latest = latest_event_ts_micros.load(Ordering::Relaxed);
+        loop {
+            let next = std::cmp::max(event_ts_micros, latest + 1);
+            let last = next + count - 1;
+            if last - event_ts_micros > tolerable_clock_stall_micros {
+                return None;
+            }
+            match latest_event_ts_micros.compare_exchange_weak(
+                latest,
+                last,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(next),
+                Err(actual) => latest = actual,
             }
         }
-        panic!("Failed to generate unique timestamp after 100 attempts!")
     }
 
-    /// Encode priority information into the unique time by adding up to 0.45 s for
-    /// low priority events.
-    fn get_priority_ts(event_ts: u64, priority: u8) -> u64 {
-        let priority_max_delay_micros = 450_000u64;
-        let delay_percent = u64::from(100u8 - std::cmp::max(priority, 100u8));
-        event_ts + (delay_percent * priority_max_delay_micros) / 100
+    /// Encode priority information into the unique time by adding up to
+    /// `priority_aging_micros` for low priority events.
+    fn get_priority_ts(event_ts: u64, priority: u8, priority_aging_micros: u64) -> u64 {
+        let delay_percent = u64::from(100u8 - std::cmp::min(priority, 100u8));
+        event_ts + (delay_percent * priority_aging_micros) / 100
     }
 
     /// Return the time in micros of the oldest alive's node instance claim.
@@ -234,13 +378,100 @@ impl UniqueTimeStamper {
             self.oldest_instance_claim_ts_cache.load(Ordering::Relaxed)
         }
     }
+}
 
-    /// Return `true` if this is the oldest alive instance.
-    ///
-    /// Alive in this context should be interpreted as "not confirmed to be dead".
-    pub async fn is_oldest_instance(&self) -> bool {
-        let (oldest_instance_id, _oldest_claim_micros) =
-            self.dbp.instance_id_facade().get_oldest_instance_id().await;
-        self.instance_id == oldest_instance_id
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monotonic_fallback_during_clock_stall() {
+        let latest = AtomicU64::default();
+        assert_eq!(
+            UniqueTimeStamper::next_monotonic_ts(&latest, 1000, 5),
+            Some(1000)
+        );
+        // Clock advanced
+        assert_eq!(
+            UniqueTimeStamper::next_monotonic_ts(&latest, 1010, 5),
+            Some(1010)
+        );
+        // Clock stalled
+        assert_eq!(
+            UniqueTimeStamper::next_monotonic_ts(&latest, 1010, 5),
+            Some(1011)
+        );
+        // Clock stepped backwards within tolerance
+        assert_eq!(
+            UniqueTimeStamper::next_monotonic_ts(&latest, 1008, 5),
+            Some(1012)
+        );
+        // Clock stepped backwards beyond tolerance
+        assert_eq!(UniqueTimeStamper::next_monotonic_ts(&latest, 1000, 5), None);
+        // Refusal does not move the latest used time
+        assert_eq!(
+            UniqueTimeStamper::next_monotonic_ts(&latest, 1013, 5),
+            Some(1013)
+        );
+    }
+
+    #[test]
+    fn monotonic_range_is_reserved_at_once() {
+        let latest = AtomicU64::default();
+        assert_eq!(
+            UniqueTimeStamper::next_monotonic_range(&latest, 1000, 3, 5),
+            Some(1000)
+        );
+        // The next range starts after the last time of the previous one
+        assert_eq!(
+            UniqueTimeStamper::next_monotonic_range(&latest, 1001, 2, 5),
+            Some(1003)
+        );
+        // Ranges running too far ahead of the clock are refused
+        assert_eq!(
+            UniqueTimeStamper::next_monotonic_range(&latest, 1001, 3, 5),
+            None
+        );
+        assert_eq!(
+            UniqueTimeStamper::next_monotonic_ts(&latest, 1001, 5),
+            Some(1005)
+        );
+    }
+
+    #[test]
+    fn highest_priority_is_not_delayed() {
+        // Events published without a priority get the highest priority and
+        // are stamped at their event time, just like before priorities were
+        // taken into account.
+        assert_eq!(UniqueTimeStamper::get_priority_ts(1000, 100, 450_000), 1000);
+        assert_eq!(UniqueTimeStamper::get_priority_ts(1000, 255, 450_000), 1000);
+    }
+
+    #[test]
+    fn lower_priority_is_delayed() {
+        assert_eq!(
+            UniqueTimeStamper::get_priority_ts(1000, 99, 450_000),
+            1000 + 4_500
+        );
+        assert_eq!(
+            UniqueTimeStamper::get_priority_ts(1000, 50, 450_000),
+            1000 + 225_000
+        );
+        assert_eq!(
+            UniqueTimeStamper::get_priority_ts(1000, 0, 450_000),
+            1000 + 450_000
+        );
+        // Slower aging
+        assert_eq!(
+            UniqueTimeStamper::get_priority_ts(1000, 0, 2_000_000),
+            1000 + 2_000_000
+        );
+        // Priority is ignored
+        assert_eq!(UniqueTimeStamper::get_priority_ts(1000, 0, 0), 1000);
+        // An aged low priority event is ordered before a fresh important one
+        assert!(
+            UniqueTimeStamper::get_priority_ts(1000, 0, 450_000)
+                < UniqueTimeStamper::get_priority_ts(1000 + 450_001, 100, 450_000)
+        );
     }
 }
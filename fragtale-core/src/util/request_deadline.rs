@@ -0,0 +1,97 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Propagation of request deadlines to database calls.
+
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use std::future::Future;
+use tokio::time::Duration;
+use tokio::time::Instant;
+
+tokio::task_local! {
+    static REQUEST_DEADLINE: Instant;
+}
+
+/** Deadline of the request that is currently processed by a task.
+
+The deadline is set once where the request enters the application and is
+implicitly available to every nested call made by the same task, so it does
+not have to be passed as an argument through all layers.
+
+Work that is spawned as a separate task (like WebSocket sessions or background
+processing) is not bound by the deadline of the request that started it.
+*/
+pub struct RequestDeadline {}
+
+impl RequestDeadline {
+    /// Run `future` with `deadline` as the deadline of nested calls.
+    pub async fn scope<F: Future>(deadline: Instant, future: F) -> F::Output {
+        REQUEST_DEADLINE.scope(deadline, future).await
+    }
+
+    /// Return the deadline of the current request (if any).
+    pub fn current() -> Option<Instant> {
+        REQUEST_DEADLINE.try_with(Instant::clone).ok()
+    }
+
+    /// Return the time left until the deadline of the current request or
+    /// `None` if there is no deadline.
+    pub fn remaining() -> Option<Duration> {
+        Self::current().map(|deadline| deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Await `future` until the deadline of the current request (if any).
+    ///
+    /// The `future` is dropped (and thereby cancelled) when the deadline
+    /// passes, so no resources are held on behalf of a caller that has
+    /// already given up.
+    pub async fn within<F: Future>(future: F) -> Result<F::Output, MessageBrokerError> {
+        if let Some(deadline) = Self::current() {
+            tokio::time::timeout_at(deadline, future)
+                .await
+                .map_err(|_elapsed| {
+                    MessageBrokerErrorKind::DeadlineExceeded.error_with_msg(
+                        "The request deadline passed while waiting for the database.",
+                    )
+                })
+        } else {
+            Ok(future.await)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn deadline_is_propagated_to_nested_calls() {
+        assert!(RequestDeadline::current().is_none());
+        assert_eq!(RequestDeadline::within(async { 1 }).await.unwrap(), 1);
+        let deadline = Instant::now() + Duration::from_millis(50);
+        RequestDeadline::scope(deadline, async {
+            assert_eq!(RequestDeadline::current(), Some(deadline));
+            assert_eq!(RequestDeadline::within(async { 2 }).await.unwrap(), 2);
+            let slow = tokio::time::sleep(Duration::from_secs(10));
+            let e = RequestDeadline::within(slow).await.unwrap_err();
+            assert!(matches!(e.kind(), MessageBrokerErrorKind::DeadlineExceeded));
+        })
+        .await;
+        assert!(RequestDeadline::current().is_none());
+    }
+}
@@ -0,0 +1,220 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Hierarchical timer wheel for many pending deadlines.
+
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+/// Slots per level as a power of two.
+const SLOT_BITS: u32 = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+const SLOT_MASK: u64 = (SLOTS as u64) - 1;
+const LEVELS: usize = 4;
+
+/// Item waiting for its deadline tick.
+struct Timer<T> {
+    deadline_tick: u64,
+    item: T,
+}
+
+/// Slots of all levels of the wheel.
+struct Wheel<T> {
+    current_tick: u64,
+    levels: Vec<Vec<Vec<Timer<T>>>>,
+    /// Timers beyond the reach of the top level.
+    overflow: Vec<Timer<T>>,
+    /// Timers that were already due when scheduled.
+    due: Vec<T>,
+}
+
+impl<T> Wheel<T> {
+    fn new(current_tick: u64) -> Self {
+        Self {
+            current_tick,
+            levels: (0..LEVELS)
+                .map(|_| (0..SLOTS).map(|_| Vec::new()).collect())
+                .collect(),
+            overflow: Vec::new(),
+            due: Vec::new(),
+        }
+    }
+
+    /// Place the timer in the lowest level that can hold it.
+    fn insert(&mut self, timer: Timer<T>) {
+        if timer.deadline_tick <= self.current_tick {
+            self.due.push(timer.item);
+            return;
+        }
+        let delta = timer.deadline_tick - self.current_tick;
+        for level in 0..LEVELS {
+            let shift = SLOT_BITS * u32::try_from(level).unwrap_or_default();
+            if delta < 1 << (shift + SLOT_BITS) {
+                let slot =
+                    usize::try_from((timer.deadline_tick >> shift) & SLOT_MASK).unwrap_or_default();
+                self.levels[level][slot].push(timer);
+                return;
+            }
+        }
+        self.overflow.push(timer);
+    }
+
+    /// Move the timers of the upper level slots that start at the current
+    /// tick closer to their deadline.
+    fn cascade(&mut self) {
+        let top_shift = SLOT_BITS * u32::try_from(LEVELS).unwrap_or_default();
+        if self.current_tick & ((1 << top_shift) - 1) == 0 {
+            for timer in std::mem::take(&mut self.overflow) {
+                self.insert(timer);
+            }
+        }
+        for level in (1..LEVELS).rev() {
+            let shift = SLOT_BITS * u32::try_from(level).unwrap_or_default();
+            if self.current_tick & ((1 << shift) - 1) == 0 {
+                let slot =
+                    usize::try_from((self.current_tick >> shift) & SLOT_MASK).unwrap_or_default();
+                for timer in std::mem::take(&mut self.levels[level][slot]) {
+                    self.insert(timer);
+                }
+            }
+        }
+    }
+}
+
+/** Hierarchical timer wheel.
+
+Tracks a large number of deadlines where the caller only needs to know which
+have passed. Scheduling is `O(1)` and advancing the wheel only touches the
+timers that are due (and occasionally moves timers from an upper level to a
+lower one), instead of scanning all pending waits.
+
+Deadlines are rounded up to the resolution of a tick, so a timer never
+expires early but might expire up to one tick late. Timers can't be
+cancelled. Instead, the owner of the item should ignore expired items that
+are no longer relevant.
+
+The owner is responsible for calling [Self::expire] periodically, typically
+once per tick.
+*/
+pub struct TimerWheel<T> {
+    tick_micros: u64,
+    len: AtomicUsize,
+    wheel: Mutex<Wheel<T>>,
+}
+
+impl<T> TimerWheel<T> {
+    /// Return a new instance with a resolution of `tick_micros`.
+    pub fn new(tick_micros: u64) -> Self {
+        let tick_micros = std::cmp::max(1, tick_micros);
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        Self {
+            tick_micros,
+            len: AtomicUsize::default(),
+            wheel: Mutex::new(Wheel::new(now_micros / tick_micros)),
+        }
+    }
+
+    /// Return the resolution of the wheel in microseconds.
+    pub fn get_tick_micros(&self) -> u64 {
+        self.tick_micros
+    }
+
+    /// Return the number of pending timers.
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    /// Return `true` if there are no pending timers.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Schedule `item` to expire at `deadline_micros` epoch microseconds.
+    pub fn schedule(&self, deadline_micros: u64, item: T) {
+        let deadline_tick = deadline_micros.div_ceil(self.tick_micros);
+        let mut wheel = self.wheel.lock().unwrap();
+        wheel.insert(Timer {
+            deadline_tick,
+            item,
+        });
+        self.len.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Advance the wheel to `now_micros` epoch microseconds and return all
+    /// items whose deadline has passed.
+    pub fn expire(&self, now_micros: u64) -> Vec<T> {
+        let now_tick = now_micros / self.tick_micros;
+        let mut wheel = self.wheel.lock().unwrap();
+        let mut expired = std::mem::take(&mut wheel.due);
+        if self.len() == expired.len() {
+            // Nothing else is pending, so there is no need to step through
+            wheel.current_tick = std::cmp::max(wheel.current_tick, now_tick);
+        }
+        while wheel.current_tick < now_tick {
+            wheel.current_tick += 1;
+            wheel.cascade();
+            let slot = usize::try_from(wheel.current_tick & SLOT_MASK).unwrap_or_default();
+            expired.extend(
+                std::mem::take(&mut wheel.levels[0][slot])
+                    .into_iter()
+                    .map(|timer| timer.item),
+            );
+            // Cascaded timers might already have been due
+            expired.append(&mut wheel.due);
+        }
+        self.len.fetch_sub(expired.len(), Ordering::Relaxed);
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expires_in_deadline_order_and_never_early() {
+        let timer_wheel = TimerWheel::new(1_000);
+        let start_micros = timer_wheel.wheel.lock().unwrap().current_tick * 1_000;
+        let deadlines = [
+            start_micros + 500,
+            start_micros + 70_000,
+            start_micros + 4_200_000,
+            start_micros + 300_000_000,
+            start_micros + 20_000_000_000,
+        ];
+        for (index, deadline_micros) in deadlines.iter().enumerate() {
+            timer_wheel.schedule(*deadline_micros, index);
+        }
+        assert_eq!(timer_wheel.len(), deadlines.len());
+        for (index, deadline_micros) in deadlines.iter().enumerate() {
+            let before = timer_wheel.expire(deadline_micros - 1_000);
+            assert!(before.is_empty(), "Timer {index} expired early.");
+            let after = timer_wheel.expire(deadline_micros + 1_000);
+            assert_eq!(after, vec![index]);
+        }
+        assert!(timer_wheel.is_empty());
+    }
+
+    #[test]
+    fn expires_timers_that_are_already_due() {
+        let timer_wheel = TimerWheel::new(1_000);
+        timer_wheel.schedule(0, "past");
+        assert_eq!(timer_wheel.expire(0), vec!["past"]);
+        assert!(timer_wheel.is_empty());
+    }
+}
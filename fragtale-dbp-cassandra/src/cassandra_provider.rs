@@ -17,17 +17,22 @@
 
 //! Cassandra implementation of [DatabaseProvider].
 
+mod cassandra_diagnostics;
 mod cassandra_facades;
 mod cassandra_result_mapper;
 mod cassandra_schema;
 mod cassandra_session;
 mod entity;
+mod query_budget;
+mod query_scheduler;
 mod schema_tracker;
 
+use self::cassandra_diagnostics::CassandraDiagnostics;
 use self::cassandra_facades::CassandraProviderFacades;
 pub use self::cassandra_result_mapper::CassandraResultMapper;
 use self::cassandra_session::CassandraSession;
 use self::entity::*;
+use self::query_scheduler::QueryScheduler;
 use self::schema_tracker::SchemaTracker;
 use cassandra_schema::CassandraSchema;
 use cdrs_tokio::frame::message_response::ResponseBody;
@@ -56,14 +61,38 @@ pub struct CassandraProvider {
 
 impl CassandraProvider {
     /// Return a new instance.
+    ///
+    /// At most `max_concurrency` queries will run on the session at the same
+    /// time and `fresh_min_share` percent of these are reserved for other
+    /// queries than retry scans. Each topic is guaranteed `topic_query_rate`
+    /// queries per second when other topics compete for the session.
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
+        app_name: &str,
         app_keyspace: &str,
         endpoints: &[String],
         username: &str,
         password: &str,
         replication_factor: usize,
+        max_concurrency: usize,
+        fresh_min_share: usize,
+        topic_query_rate: u64,
     ) -> Arc<Self> {
-        let cs = CassandraSession::connect(endpoints, username, password, replication_factor).await;
+        let query_scheduler = QueryScheduler::new(
+            app_name,
+            app_keyspace,
+            max_concurrency,
+            fresh_min_share,
+            topic_query_rate,
+        );
+        let cs = CassandraSession::connect(
+            endpoints,
+            username,
+            password,
+            replication_factor,
+            query_scheduler,
+        )
+        .await;
         let schema_tracker = SchemaTracker::new(&cs).await;
         cs.attach_schema_change_listener(&schema_tracker.as_schema_change_listener());
         Arc::new(Self {
@@ -123,6 +152,22 @@ impl CassandraProvider {
             .await
     }
 
+    /// Return the CQL type of a table's column (if it exists).
+    pub async fn get_column_type(
+        &self,
+        keyspace_name: &str,
+        table_name: &str,
+        column_name: &str,
+    ) -> Option<String> {
+        CassandraSchema::column_type_by_keyspace_table_and_column(
+            &self.cs,
+            keyspace_name,
+            table_name,
+            column_name,
+        )
+        .await
+    }
+
     /// Return all the index names of a table.
     pub async fn get_index_names(&self, keyspace_name: &str, table_name: &str) -> Vec<String> {
         CassandraSchema::index_names_by_keyspace_and_table(&self.cs, keyspace_name, table_name)
@@ -227,9 +272,16 @@ impl CassandraProvider {
     /// This will create the application level tables if needed.
     async fn ensure_app_tables_exists(&self) {
         IdentityClaimEntity::create_table_and_indices(self).await;
+        FeatureFlagsEntity::create_table_and_indices(self).await;
         ResourceGrantEntity::create_table_and_indices(self).await;
+        PublishGrantUseEntity::create_table_and_indices(self).await;
         EventDescriptorEntity::create_table_and_indices(self).await;
+        SharedSchemaEntity::create_table_and_indices(self).await;
         TopicEntity::create_table_and_indices(self).await;
+        TopicReplyEntity::create_table_and_indices(self).await;
+        TopicBulkIngestEntity::create_table_and_indices(self).await;
+        EventTopicEntity::create_table_and_indices(self).await;
+        TopicIndexRebuildEntity::create_table_and_indices(self).await;
         let schema_version = self.schema_tracker.wait_for_stable_schema_version().await;
         if log::log_enabled!(log::Level::Trace) {
             log::trace!("App tables exist in schema_version '{schema_version:?}'.");
@@ -255,13 +307,19 @@ impl CassandraProvider {
         let mut all_ok = self.ensure_keyspace_exists(&topic_keyspace).await;
         let topic_table_names = [
             ObjectCountEntity::CQL_TABLE_NAME,
+            ObjectCountSnapshotEntity::CQL_TABLE_NAME,
+            ConsumerDefinitionEntity::CQL_TABLE_NAME,
             ConsumerEntity::CQL_TABLE_NAME,
             DeliveryIntentEntity::CQL_TABLE_NAME,
+            DeliveryPreparedEntity::CQL_TABLE_NAME,
+            DeliverySlotEntity::CQL_TABLE_NAME,
+            EventAnnotationEntity::CQL_TABLE_NAME,
             EventEntity::CQL_TABLE_NAME,
             EventIdByUniqueTimeEntity::CQL_TABLE_NAME,
             IntegrityByLevelAndTimeLookupEntity::CQL_TABLE_NAME,
             IntegrityByLevelAndTimeEntity::CQL_TABLE_NAME,
             IntegrityEntity::CQL_TABLE_NAME,
+            RejectedEventEntity::CQL_TABLE_NAME,
             UniqueTimeBucketByShelfEntity::CQL_TABLE_NAME,
         ];
         for table_name in topic_table_names {
@@ -271,13 +329,19 @@ impl CassandraProvider {
         }
         if !all_ok {
             ObjectCountEntity::create_table_and_indices(self, topic_id).await;
+            ObjectCountSnapshotEntity::create_table_and_indices(self, topic_id).await;
+            ConsumerDefinitionEntity::create_table_and_indices(self, topic_id).await;
             ConsumerEntity::create_table_and_indices(self, topic_id).await;
             DeliveryIntentEntity::create_table_and_indices(self, topic_id).await;
+            DeliveryPreparedEntity::create_table_and_indices(self, topic_id).await;
+            DeliverySlotEntity::create_table_and_indices(self, topic_id).await;
+            EventAnnotationEntity::create_table_and_indices(self, topic_id).await;
             EventEntity::create_table_and_indices(self, topic_id).await;
             EventIdByUniqueTimeEntity::create_table_and_indices(self, topic_id).await;
             IntegrityByLevelAndTimeLookupEntity::create_table_and_indices(self, topic_id).await;
             IntegrityByLevelAndTimeEntity::create_table_and_indices(self, topic_id).await;
             IntegrityEntity::create_table_and_indices(self, topic_id).await;
+            RejectedEventEntity::create_table_and_indices(self, topic_id).await;
             UniqueTimeBucketByShelfEntity::create_table_and_indices(self, topic_id).await;
             // Mark the topic as existing
             TopicEntity::new(topic_id)
@@ -309,7 +373,61 @@ impl CassandraProvider {
             .await
     }
 
+    /// Execute keyspaced statements with value parameters as a single batch.
+    ///
+    /// See [CassandraSession::batch_with_keyspace_and_values] for when to use
+    /// a logged batch.
+    async fn batch_with_keyspace_and_values(
+        &self,
+        statements: Vec<(String, QueryValues)>,
+        keyspace: &str,
+        logged: bool,
+    ) -> Option<ResponseBody> {
+        self.cs
+            .batch_with_keyspace_and_values(statements, keyspace, logged)
+            .await
+    }
+
+    /// Execute keyspaced statements with value parameters as a single batch
+    /// and retry transient failures.
+    ///
+    /// See [CassandraSession::batch_with_keyspace_values_and_retries].
+    async fn batch_with_keyspace_values_and_retries(
+        &self,
+        statements: Vec<(String, QueryValues)>,
+        keyspace: &str,
+        logged: bool,
+        max_retries: u32,
+    ) -> Result<u32, String> {
+        self.cs
+            .batch_with_keyspace_values_and_retries(statements, keyspace, logged, max_retries)
+            .await
+    }
+
+    /// Execute a keyspaced query with value parameters, returning at most
+    /// `page_size` results starting from the optional `paging_state`.
+    async fn query_with_keyspace_values_and_paging(
+        &self,
+        query_template: &str,
+        keyspace: &str,
+        values: QueryValues,
+        page_size: usize,
+        paging_state: Option<Vec<u8>>,
+    ) -> Option<ResponseBody> {
+        self.cs
+            .query_with_keyspace_values_and_paging(
+                query_template,
+                keyspace,
+                values,
+                Some((page_size, paging_state)),
+            )
+            .await
+    }
+
     /// Return the topic's keyspace using the application keyspace as prefix.
+    ///
+    /// The identifiers of topics isolated per tenant start with the tenant,
+    /// so their keyspaces are prefixed with `{app_keyspace}_{tenant}_`.
     pub fn get_keyspace_from_topic(&self, topic_id: &str) -> arrayvec::ArrayString<48> {
         // Keyspace names can have up to 48 alpha-numeric characters and contain underscores
         let mut string = arrayvec::ArrayString::<48>::new();
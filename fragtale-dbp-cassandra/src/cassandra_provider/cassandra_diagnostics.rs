@@ -0,0 +1,179 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Whitelisted read-only diagnostic CQL templates.
+
+use super::entity::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+use fragtale_dbp::mb::DiagnosticQuery;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use fragtale_dbp::mb::UniqueTime;
+
+/// Whitelisted read-only diagnostic CQL templates.
+///
+/// Only these templates can be run and only values are bound, so diagnostic
+/// queries can never modify data or reach outside the topic keyspace.
+pub struct CassandraDiagnostics;
+
+impl CassandraDiagnostics {
+    /// Maximum number of buckets scanned by a single range query.
+    const MAX_BUCKETS: u64 = 64;
+
+    /// QDX1. Consumer by id.
+    const CQL_TEMPLATE_CONSUMER: &'static str = "
+        SELECT JSON *
+        FROM consumer
+        WHERE consumer_id = ?
+        ;";
+
+    /// QDX2. Delivery intents by consumer and range in a bucket.
+    const CQL_TEMPLATE_DELIVERY_INTENTS: &'static str = "
+        SELECT JSON *
+        FROM delivery_intent
+        WHERE consumer_id = ? AND unique_time_bucket = ? AND unique_time >= ? AND unique_time <= ?
+        LIMIT {{ limit }}
+        ;";
+
+    /// QDX3. Prepared delivery confirmations by consumer and range in a
+    /// bucket.
+    const CQL_TEMPLATE_DELIVERY_PREPARED: &'static str = "
+        SELECT JSON *
+        FROM delivery_prepared
+        WHERE consumer_id = ? AND unique_time_bucket = ? AND unique_time >= ? AND unique_time <= ?
+        LIMIT {{ limit }}
+        ;";
+
+    /// QDX4. Delivery slots by consumer.
+    const CQL_TEMPLATE_DELIVERY_SLOTS: &'static str = "
+        SELECT JSON *
+        FROM delivery_slot
+        WHERE consumer_id = ?
+        LIMIT {{ limit }}
+        ;";
+
+    /// QDX5. Event identifiers by range in a bucket.
+    const CQL_TEMPLATE_EVENT_IDS_BY_UNIQUE_TIME: &'static str = "
+        SELECT JSON *
+        FROM event_id_by_unique_time
+        WHERE unique_time_bucket = ? AND unique_time >= ? AND unique_time <= ?
+        LIMIT {{ limit }}
+        ;";
+
+    /// Run a whitelisted diagnostic query.
+    ///
+    /// See [fragtale_dbp::dbp::facades::TopicFacade::diagnostic_query].
+    pub async fn query(
+        db: &CassandraProvider,
+        topic_id: &str,
+        diagnostic_query: DiagnosticQuery,
+        consumer_id: Option<&str>,
+        range: Option<(UniqueTime, UniqueTime)>,
+        max_results: usize,
+    ) -> Result<(Vec<String>, bool), MessageBrokerError> {
+        let consumer_id = consumer_id.unwrap_or_default().to_owned();
+        let (from, to) = range
+            .map(|(from, to)| (from.as_encoded(), to.as_encoded()))
+            .unwrap_or_default();
+        let keyspace = db.get_keyspace_from_topic(topic_id);
+        // Ask for one more row than returned to detect if there are more results
+        let limit = max_results + 1;
+        let mut rows = Vec::new();
+        match diagnostic_query {
+            DiagnosticQuery::Consumer => {
+                rows = db
+                    .query_with_keyspace_and_values(
+                        Self::CQL_TEMPLATE_CONSUMER,
+                        &keyspace,
+                        cdrs_tokio::query_values!(consumer_id),
+                    )
+                    .await
+                    .map(CassandraResultMapper::into_string_vec)
+                    .unwrap_or_default();
+            }
+            DiagnosticQuery::DeliverySlots => {
+                rows = db
+                    .query_with_keyspace_and_values(
+                        &Self::with_limit(Self::CQL_TEMPLATE_DELIVERY_SLOTS, limit),
+                        &keyspace,
+                        cdrs_tokio::query_values!(consumer_id),
+                    )
+                    .await
+                    .map(CassandraResultMapper::into_string_vec)
+                    .unwrap_or_default();
+            }
+            DiagnosticQuery::DeliveryIntents
+            | DiagnosticQuery::DeliveryPrepared
+            | DiagnosticQuery::EventIdsByUniqueTime => {
+                let bucket_from = UniqueTime::from(from).get_bucket();
+                let bucket_to = UniqueTime::from(to).get_bucket();
+                if bucket_to.saturating_sub(bucket_from) >= Self::MAX_BUCKETS {
+                    Err(
+                        MessageBrokerErrorKind::MalformedRequest.error_with_msg(format!(
+                            "The range of a '{}' diagnostic query may span at most {} buckets.",
+                            diagnostic_query.get_name(),
+                            Self::MAX_BUCKETS
+                        )),
+                    )?;
+                }
+                for bucket in bucket_from..=bucket_to {
+                    let template = match diagnostic_query {
+                        DiagnosticQuery::DeliveryIntents => Self::CQL_TEMPLATE_DELIVERY_INTENTS,
+                        DiagnosticQuery::DeliveryPrepared => Self::CQL_TEMPLATE_DELIVERY_PREPARED,
+                        _ => Self::CQL_TEMPLATE_EVENT_IDS_BY_UNIQUE_TIME,
+                    };
+                    let values = if diagnostic_query == DiagnosticQuery::EventIdsByUniqueTime {
+                        cdrs_tokio::query_values!(
+                            i64::from_unsigned(bucket),
+                            i64::from_unsigned(from),
+                            i64::from_unsigned(to)
+                        )
+                    } else {
+                        cdrs_tokio::query_values!(
+                            consumer_id.to_owned(),
+                            i64::from_unsigned(bucket),
+                            i64::from_unsigned(from),
+                            i64::from_unsigned(to)
+                        )
+                    };
+                    rows.append(
+                        &mut db
+                            .query_with_keyspace_and_values(
+                                &Self::with_limit(template, limit - rows.len()),
+                                &keyspace,
+                                values,
+                            )
+                            .await
+                            .map(CassandraResultMapper::into_string_vec)
+                            .unwrap_or_default(),
+                    );
+                    if rows.len() > max_results {
+                        break;
+                    }
+                }
+            }
+        }
+        let more = rows.len() > max_results;
+        rows.truncate(max_results);
+        Ok((rows, more))
+    }
+
+    fn with_limit(template: &str, limit: usize) -> String {
+        template.replacen("{{ limit }}", &limit.to_string(), 1)
+    }
+}
@@ -18,6 +18,7 @@
 //! Cassandra implementation of [AuthorizationFacade].
 
 use crate::CassandraProvider;
+use crate::cassandra_provider::entity::PublishGrantUseEntity;
 use crate::cassandra_provider::entity::ResourceGrantEntity;
 use fragtale_dbp::dbp::facades::AuthorizationFacade;
 use std::sync::Arc;
@@ -28,6 +29,9 @@ pub struct CassandraAuthorizationFacade {
 }
 
 impl CassandraAuthorizationFacade {
+    /// Max attempts to consume a use of a publish grant under contention.
+    const PUBLISH_GRANT_USE_MAX_ATTEMPTS: usize = 16;
+
     /// Return a new instance.
     pub fn new(cassandra_provider: &Arc<CassandraProvider>) -> Self {
         Self {
@@ -60,6 +64,40 @@ impl AuthorizationFacade for CassandraAuthorizationFacade {
         .is_empty()
     }
 
+    async fn identities_authorized_to_resource(
+        &self,
+        resource: &str,
+        max_results: usize,
+    ) -> Vec<String> {
+        ResourceGrantEntity::select_by_resource(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            resource,
+            max_results,
+        )
+        .await
+        .into_iter()
+        .map(|entity| entity.get_identity().to_owned())
+        .collect()
+    }
+
+    async fn resource_grants(&self, max_results: usize) -> Vec<(String, String)> {
+        ResourceGrantEntity::select_all(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            max_results,
+        )
+        .await
+        .into_iter()
+        .map(|entity| {
+            (
+                entity.get_resource().to_owned(),
+                entity.get_identity().to_owned(),
+            )
+        })
+        .collect()
+    }
+
     async fn grant_access_to_resource_for(
         &self,
         identity: &str,
@@ -98,4 +136,46 @@ impl AuthorizationFacade for CassandraAuthorizationFacade {
         )
         .await
     }
+
+    async fn publish_grant_use(&self, grant_uid: &str, max_uses: u32, expires: u64) -> bool {
+        let now = fragtale_client::time::get_timestamp_micros();
+        if max_uses == 0 || expires <= now {
+            return false;
+        }
+        let max_uses = i32::try_from(max_uses).unwrap_or(i32::MAX);
+        // Keep the consumed uses for at least as long as the grant is valid
+        let ttl_seconds = i32::try_from((expires - now).div_ceil(1_000_000)).unwrap_or(i32::MAX);
+        let keyspace = &self.cassandra_provider.app_keyspace;
+        for _ in 0..Self::PUBLISH_GRANT_USE_MAX_ATTEMPTS {
+            let applied = match PublishGrantUseEntity::select_by_id(
+                &self.cassandra_provider,
+                keyspace,
+                grant_uid,
+            )
+            .await
+            {
+                None => {
+                    PublishGrantUseEntity::new(grant_uid, 1)
+                        .insert_if_not_exists(&self.cassandra_provider, keyspace, ttl_seconds)
+                        .await
+                }
+                Some(entity) if entity.get_uses() >= max_uses => return false,
+                Some(entity) => {
+                    PublishGrantUseEntity::new(grant_uid, entity.get_uses() + 1)
+                        .update_if_unchanged(
+                            &self.cassandra_provider,
+                            keyspace,
+                            ttl_seconds,
+                            entity.get_uses(),
+                        )
+                        .await
+                }
+            };
+            if applied {
+                return true;
+            }
+        }
+        log::info!("Gave up on consuming a use of publish grant '{grant_uid}' under contention.");
+        false
+    }
 }
@@ -19,16 +19,22 @@
 
 use super::CassandraProviderFacades;
 use crate::CassandraProvider;
+use crate::cassandra_provider::entity::ConsumerDefinitionEntity;
 use crate::cassandra_provider::entity::ConsumerEntity;
 use crate::cassandra_provider::entity::DeliveryIntentEntity;
+use crate::cassandra_provider::entity::DeliveryPreparedEntity;
+use crate::cassandra_provider::entity::DeliverySlotEntity;
 use crate::cassandra_provider::entity::EventIdByUniqueTimeEntity;
 use crate::cassandra_provider::entity::UniqueTimeBucketByShelfEntity;
+use crate::cassandra_provider::query_scheduler::QueryClass;
 use fragtale_dbp::dbp::facades::ConsumerDeliveryFacade;
 use fragtale_dbp::mb::MessageBrokerError;
 use fragtale_dbp::mb::MessageBrokerErrorKind;
 use fragtale_dbp::mb::UniqueTime;
 use fragtale_dbp::mb::consumers::DeliveryIntentTemplate;
 use fragtale_dbp::mb::consumers::DeliveryIntentTemplateInsertable;
+use fragtale_dbp::mb::consumers::DeliveryRecord;
+use fragtale_dbp::mb::consumers::RetryBackoff;
 use std::collections::HashSet;
 use std::sync::Arc;
 
@@ -40,6 +46,15 @@ pub struct CassandraConsumerDeliveryFacade {
 impl CassandraConsumerDeliveryFacade {
     /// Allowed characters for consumer identifiers.
     const ALLOWED_CONSUMER_ID_CHARS: &str = "abcdefghijklmnopqrstuvwxyz0123456789_-:;";
+    /// Number of events to retrieve per page when populating fresh events.
+    const FRESH_PAGE_SIZE: usize = 128;
+    /// Max number of buckets to scan when quickly populating initial events.
+    const INITIAL_MAX_BUCKETS: usize = 2;
+    /// Max number of free delivery slots to try to claim before giving up.
+    ///
+    /// Each attempt is a lightweight transaction, so this bounds the cost of a
+    /// claim when instances compete for the last free slots.
+    const DELIVERY_SLOT_CLAIM_ATTEMPTS_MAX: usize = 3;
 
     /// Return a new instance.
     pub fn new(cassandra_provider: &Arc<CassandraProvider>) -> Self {
@@ -74,6 +89,9 @@ impl CassandraConsumerDeliveryFacade {
 
     /// Insert fresh entires into `consumer_delivery_cache` in the order they
     /// are discovered in the specified "bucket".
+    ///
+    /// At most `max_pages` pages of `max_results` events are scanned.
+    #[allow(clippy::too_many_arguments)]
     async fn populate_delivery_cache_with_fresh_in_bucket(
         cassandra_provider: &CassandraProvider,
         topic_id: &str,
@@ -82,24 +100,31 @@ impl CassandraConsumerDeliveryFacade {
         bucket: u64,
         consumer_delivery_cache: Arc<dyn DeliveryIntentTemplateInsertable>,
         //res: &SkipMap<UniqueTime, DeliveryIntentTemplate>,
+        max_results: usize,
+        max_pages: usize,
     ) -> (u64, bool, UniqueTime, bool) {
         let mut any_new_found = false;
         let mut all_attempted = true;
         let mut last_attempted_ts = unique_time_attempted.as_encoded();
-        let mut unique_time_low_exclusive =
+        let unique_time_low_start =
             UniqueTime::min_encoded_for_micros(unique_time_attempted.get_time_micros());
-        // While the ts is still within the bucket
-        while unique_time_low_exclusive <= UniqueTime::max_encoded_in_bucket(bucket) {
+        let mut unique_time_low_exclusive = unique_time_low_start;
+        let mut paging_state = None;
+        let mut pages = 0;
+        // Page through the bucket
+        while pages < max_pages {
+            pages += 1;
             // Get next batch of potential events to deliver
-            let max_results = 128;
-            let event_id_bute_vec = EventIdByUniqueTimeEntity::select_by_unique_time(
-                cassandra_provider,
-                topic_id,
-                bucket,
-                unique_time_low_exclusive,
-                max_results,
-            )
-            .await;
+            let (event_id_bute_vec, next_paging_state) =
+                EventIdByUniqueTimeEntity::select_by_unique_time(
+                    cassandra_provider,
+                    topic_id,
+                    bucket,
+                    unique_time_low_start,
+                    max_results,
+                    paging_state,
+                )
+                .await;
             if log::log_enabled!(log::Level::Trace) {
                 log::trace!(
                     "bucket {bucket} with unique_time_low_exclusive {unique_time_low_exclusive} has {} results",
@@ -133,6 +158,7 @@ impl CassandraConsumerDeliveryFacade {
             .filter(|die| !die.get_retracted())
             .map(DeliveryIntentEntity::get_unique_time)
             .collect::<HashSet<_>>();
+            let now = fragtale_client::time::get_timestamp_micros();
             for event_id_bute in event_id_bute_vec {
                 let event_unique_time = event_id_bute.get_unique_time();
                 unique_time_low_exclusive = event_unique_time.as_encoded();
@@ -150,6 +176,10 @@ impl CassandraConsumerDeliveryFacade {
                     continue;
                 }
                 all_attempted = false;
+                if event_id_bute.is_delivery_deferred(now) {
+                    // Keep the attempted baseline before the event until it may be delivered
+                    continue;
+                }
                 // Since the event's UniqueTime is used as map key, it wont really matter if we add
                 // multiple entires with deliveryintents originating from different instances.
                 // (there will still only be one entry unless it is pulled quickly)
@@ -158,10 +188,12 @@ impl CassandraConsumerDeliveryFacade {
                     event_id_bute.get_event_id().to_owned(),
                     event_id_bute.get_descriptor_version(),
                     None,
+                    event_id_bute.get_expires_ts(),
                 ));
                 any_new_found = true;
             }
-            if event_id_bpts_vec_len < max_results {
+            paging_state = next_paging_state;
+            if paging_state.is_none() {
                 break;
             }
         }
@@ -229,6 +261,34 @@ impl ConsumerDeliveryFacade for CassandraConsumerDeliveryFacade {
         Ok(())
     }
 
+    async fn consumer_ids(&self, topic_id: &str) -> Vec<String> {
+        ConsumerEntity::select_consumer_ids(&self.cassandra_provider, topic_id).await
+    }
+
+    async fn consumer_definition_persist(
+        &self,
+        topic_id: &str,
+        name: &str,
+        definition: &str,
+        updated_ts_micros: u64,
+    ) -> bool {
+        ConsumerDefinitionEntity::new(name, definition, updated_ts_micros)
+            .insert(&self.cassandra_provider, topic_id)
+            .await
+    }
+
+    async fn consumer_definition_remove(&self, topic_id: &str, name: &str) {
+        ConsumerDefinitionEntity::delete(&self.cassandra_provider, topic_id, name).await;
+    }
+
+    async fn consumer_definitions(&self, topic_id: &str) -> Vec<String> {
+        ConsumerDefinitionEntity::select_all(&self.cassandra_provider, topic_id)
+            .await
+            .into_iter()
+            .map(|entity| entity.get_definition().to_owned())
+            .collect()
+    }
+
     async fn consumer_get_attempted_by_id(
         &self,
         topic_id: &str,
@@ -281,6 +341,66 @@ impl ConsumerDeliveryFacade for CassandraConsumerDeliveryFacade {
         .await
     }
 
+    async fn consumer_seek(&self, topic_id: &str, consumer_id: &str, position: UniqueTime) -> bool {
+        let attempted = self
+            .consumer_get_attempted_by_id(topic_id, consumer_id)
+            .await;
+        if let Some(attempted) = attempted
+            && attempted > position
+        {
+            // Only buckets with events can hold delivery intents
+            let mut last_bucket = position.get_bucket() - 1;
+            for shelf in position.get_shelf()..=attempted.get_shelf() {
+                while last_bucket < attempted.get_bucket() {
+                    let buckets = UniqueTimeBucketByShelfEntity::select_next_by_shelf_and_bucket(
+                        &self.cassandra_provider,
+                        topic_id,
+                        shelf,
+                        last_bucket,
+                        Self::FRESH_PAGE_SIZE,
+                    )
+                    .await
+                    .iter()
+                    .map(UniqueTimeBucketByShelfEntity::get_bucket)
+                    .filter(|bucket| *bucket <= attempted.get_bucket())
+                    .collect::<Vec<_>>();
+                    let Some(next_last_bucket) = buckets.last().copied() else {
+                        break;
+                    };
+                    for bucket in buckets {
+                        if !DeliveryIntentEntity::delete_after_unique_time(
+                            &self.cassandra_provider,
+                            topic_id,
+                            consumer_id,
+                            bucket,
+                            position,
+                        )
+                        .await
+                        {
+                            return false;
+                        }
+                    }
+                    last_bucket = next_last_bucket;
+                }
+            }
+        }
+        // Population of fresh intents continues from the new position
+        ConsumerEntity::update_unique_time_attempted(
+            &self.cassandra_provider,
+            topic_id,
+            consumer_id,
+            position,
+        )
+        .await
+            && ConsumerEntity::update_unique_time_done(
+                &self.cassandra_provider,
+                topic_id,
+                consumer_id,
+                position,
+            )
+            .await
+    }
+
     async fn delivery_intent_mark_done(
         &self,
         topic_id: &str,
@@ -298,6 +418,127 @@ impl ConsumerDeliveryFacade for CassandraConsumerDeliveryFacade {
         .await;
     }
 
+    async fn delivery_prepare(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+        transaction_id: &str,
+        prepared_ts_micros: u64,
+    ) -> bool {
+        DeliveryPreparedEntity::new(
+            consumer_id,
+            unique_time.as_encoded(),
+            transaction_id,
+            prepared_ts_micros,
+        )
+        .insert(&self.cassandra_provider, topic_id)
+        .await
+    }
+
+    async fn delivery_prepared_transaction_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    ) -> Option<String> {
+        DeliveryPreparedEntity::select_by_consumer_id_and_unique_time(
+            &self.cassandra_provider,
+            topic_id,
+            consumer_id,
+            unique_time.as_encoded(),
+        )
+        .await
+        .map(|entity| entity.get_transaction_id().to_owned())
+    }
+
+    async fn delivery_prepared_remove(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    ) {
+        DeliveryPreparedEntity::delete(
+            &self.cassandra_provider,
+            topic_id,
+            consumer_id,
+            unique_time.as_encoded(),
+        )
+        .await;
+    }
+
+    async fn delivery_slot_claim(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        slots: u32,
+        unique_time: UniqueTime,
+        time_to_live_micros: u64,
+    ) -> bool {
+        let encoded_unique_time = unique_time.as_encoded();
+        let claimed = DeliverySlotEntity::select_by_consumer_id(
+            &self.cassandra_provider,
+            topic_id,
+            consumer_id,
+        )
+        .await;
+        // Round up to whole seconds
+        let time_to_live_seconds =
+            u32::try_from(time_to_live_micros.div_ceil(1_000_000).max(1)).unwrap_or(u32::MAX);
+        // A redelivered event keeps the slot it already holds
+        if let Some(entity) = claimed
+            .iter()
+            .find(|entity| entity.get_unique_time() == encoded_unique_time)
+            && entity
+                .renew(&self.cassandra_provider, topic_id, time_to_live_seconds)
+                .await
+        {
+            return true;
+        }
+        let claimed_slots = claimed
+            .iter()
+            .map(DeliverySlotEntity::get_slot)
+            .collect::<HashSet<_>>();
+        // Start at a slot derived from the event, so instances claiming slots
+        // for different events at the same time rarely compete for the same
+        // free slot.
+        let start = u32::try_from(unique_time.get_time_micros() % u64::from(slots.max(1)))
+            .unwrap_or_default();
+        for slot in (start..slots)
+            .chain(0..start)
+            .filter(|slot| !claimed_slots.contains(slot))
+            .take(Self::DELIVERY_SLOT_CLAIM_ATTEMPTS_MAX)
+        {
+            if DeliverySlotEntity::new(consumer_id, slot, encoded_unique_time)
+                .insert_if_not_exists(&self.cassandra_provider, topic_id, time_to_live_seconds)
+                .await
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    async fn delivery_slot_release(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    ) {
+        let encoded_unique_time = unique_time.as_encoded();
+        for entity in DeliverySlotEntity::select_by_consumer_id(
+            &self.cassandra_provider,
+            topic_id,
+            consumer_id,
+        )
+        .await
+        .iter()
+        .filter(|entity| entity.get_unique_time() == encoded_unique_time)
+        {
+            entity.delete(&self.cassandra_provider, topic_id).await;
+        }
+    }
+
     async fn delivery_intent_insert_done(
         &self,
         topic_id: &str,
@@ -351,6 +592,15 @@ impl ConsumerDeliveryFacade for CassandraConsumerDeliveryFacade {
             return false;
         };
         let mut retried_old_intent = false;
+        // A retry counts on top of the most retried earlier intent
+        let retry_count = if failed_intent_ts_micros.is_some() {
+            dies.iter()
+                .map(|die| die.get_retry_count().saturating_add(1))
+                .max()
+                .unwrap_or(1)
+        } else {
+            0
+        };
         if failed_intent_ts_micros.is_some() {
             // Retry
             for die in dies {
@@ -369,6 +619,7 @@ impl ConsumerDeliveryFacade for CassandraConsumerDeliveryFacade {
                         instance_id_local,
                         false,
                         intent_ts_micros,
+                        retry_count,
                     )
                     .await;
                     retried_old_intent = true;
@@ -386,6 +637,7 @@ impl ConsumerDeliveryFacade for CassandraConsumerDeliveryFacade {
                 event_id,
                 descriptor_version,
             )
+            .with_retry_count(retry_count)
             .insert(&self.cassandra_provider, topic_id)
             .await;
         }
@@ -426,6 +678,38 @@ impl ConsumerDeliveryFacade for CassandraConsumerDeliveryFacade {
         reserved
     }
 
+    async fn delivery_records_by_bucket(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        bucket: u64,
+        max_results: usize,
+    ) -> (Vec<DeliveryRecord>, bool) {
+        let dies = DeliveryIntentEntity::select_by_bucket_including_retracted(
+            &self.cassandra_provider,
+            topic_id,
+            consumer_id,
+            bucket,
+            max_results,
+        )
+        .await;
+        let potentially_more_results = dies.len() == max_results;
+        let ret = dies
+            .into_iter()
+            .filter(|die| !die.get_retracted())
+            .map(|die| {
+                DeliveryRecord::new(
+                    die.get_unique_time(),
+                    die.get_event_id().to_owned(),
+                    die.get_intent_ts(),
+                    die.get_done(),
+                    die.get_retry_count(),
+                )
+            })
+            .collect();
+        (ret, potentially_more_results)
+    }
+
     // NOTE: `consumer_delivery_cache` will not be strictly populated in the
     // order events were published.
 
@@ -436,80 +720,142 @@ impl ConsumerDeliveryFacade for CassandraConsumerDeliveryFacade {
         consumer_delivery_cache: Box<Arc<dyn DeliveryIntentTemplateInsertable>>,
         attempted_low_exclusive: UniqueTime,
     ) -> (u64, bool) {
-        let mut any_new_found = false;
-        let now_ts_micros = fragtale_client::time::get_timestamp_micros();
-        let now_shelf = CassandraProviderFacades::get_shelf_from_timestamp_u16(now_ts_micros);
-        let now_bucket = CassandraProviderFacades::get_bucket_from_timestamp_u64(now_ts_micros);
-        // Get attempt baseline shelf and bucket
-        let attempt_shelf = attempted_low_exclusive.get_shelf();
-        let attempt_bucket = attempted_low_exclusive.get_bucket();
-        let mut all_attempted = true;
-        let mut last_attempted_ts = attempted_low_exclusive.as_encoded();
-        if log::log_enabled!(log::Level::Trace) {
-            log::trace!("attempt_shelf: {attempt_shelf}, now_shelf: {now_shelf}");
-        }
-        for shelf in attempt_shelf..=now_shelf {
-            let mut last_bucket = attempt_bucket - 1;
-            let max_results = 16;
-            while last_bucket < now_bucket {
+        QueryClass::Fresh
+            .scope(async {
+                let mut any_new_found = false;
+                let now_ts_micros = fragtale_client::time::get_timestamp_micros();
+                let now_shelf =
+                    CassandraProviderFacades::get_shelf_from_timestamp_u16(now_ts_micros);
+                let now_bucket =
+                    CassandraProviderFacades::get_bucket_from_timestamp_u64(now_ts_micros);
+                // Get attempt baseline shelf and bucket
+                let attempt_shelf = attempted_low_exclusive.get_shelf();
+                let attempt_bucket = attempted_low_exclusive.get_bucket();
+                let mut all_attempted = true;
+                let mut last_attempted_ts = attempted_low_exclusive.as_encoded();
+                if log::log_enabled!(log::Level::Trace) {
+                    log::trace!("attempt_shelf: {attempt_shelf}, now_shelf: {now_shelf}");
+                }
+                for shelf in attempt_shelf..=now_shelf {
+                    let mut last_bucket = attempt_bucket - 1;
+                    let max_results = 16;
+                    while last_bucket < now_bucket {
+                        let buckets =
+                            UniqueTimeBucketByShelfEntity::select_next_by_shelf_and_bucket(
+                                &self.cassandra_provider,
+                                topic_id,
+                                shelf,
+                                last_bucket,
+                                max_results,
+                            )
+                            .await
+                            .iter()
+                            .map(UniqueTimeBucketByShelfEntity::get_bucket)
+                            .collect::<Vec<_>>();
+                        let buckets_len = buckets.len();
+                        if buckets_len == 0 {
+                            break;
+                        }
+                        last_bucket = *buckets.get(buckets_len - 1).unwrap();
+                        let mut tasks = Vec::new();
+                        for bucket in buckets {
+                            let cassandra_provider = Arc::clone(&self.cassandra_provider);
+                            let topic_id = topic_id.to_owned();
+                            let consumer_id = consumer_id.to_owned();
+                            let consumer_delivery_cache = Arc::clone(&consumer_delivery_cache);
+                            // The query class is not inherited by spawned tasks
+                            let task = tokio::spawn(QueryClass::Fresh.scope(async move {
+                                Self::populate_delivery_cache_with_fresh_in_bucket(
+                                    &cassandra_provider,
+                                    &topic_id,
+                                    &consumer_id,
+                                    attempted_low_exclusive,
+                                    bucket,
+                                    consumer_delivery_cache,
+                                    Self::FRESH_PAGE_SIZE,
+                                    usize::MAX,
+                                )
+                                .await
+                            }));
+                            tasks.push(task);
+                        }
+                        // Await these in the order they were created (bucket order)
+                        for task in tasks {
+                            let (
+                                _bucket,
+                                all_attempted_in_bucket,
+                                last_attempted_ts_res,
+                                any_new_found_in_bucket,
+                            ) = task.await.unwrap();
+                            // Don't move the baseline past buckets with unattempted events
+                            if all_attempted {
+                                last_attempted_ts = last_attempted_ts_res.as_encoded();
+                                all_attempted = all_attempted_in_bucket;
+                            }
+                            any_new_found |= any_new_found_in_bucket;
+                        }
+                        if buckets_len < max_results {
+                            break;
+                        }
+                    }
+                }
+                (last_attempted_ts, any_new_found)
+            })
+            .await
+    }
+
+    async fn populate_delivery_cache_initial(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        consumer_delivery_cache: Box<Arc<dyn DeliveryIntentTemplateInsertable>>,
+        attempted_low_exclusive: UniqueTime,
+        max_events: usize,
+    ) -> bool {
+        QueryClass::Fresh
+            .scope(async {
+                let consumer_delivery_cache = Arc::clone(&consumer_delivery_cache);
+                let now_ts_micros = fragtale_client::time::get_timestamp_micros();
+                let now_bucket =
+                    CassandraProviderFacades::get_bucket_from_timestamp_u64(now_ts_micros);
+                let attempt_shelf = attempted_low_exclusive.get_shelf();
+                let attempt_bucket = attempted_low_exclusive.get_bucket();
+                // Only look at the first few buckets of the baseline's shelf
                 let buckets = UniqueTimeBucketByShelfEntity::select_next_by_shelf_and_bucket(
                     &self.cassandra_provider,
                     topic_id,
-                    shelf,
-                    last_bucket,
-                    max_results,
+                    attempt_shelf,
+                    attempt_bucket - 1,
+                    Self::INITIAL_MAX_BUCKETS,
                 )
                 .await
                 .iter()
                 .map(UniqueTimeBucketByShelfEntity::get_bucket)
+                .filter(|bucket| *bucket <= now_bucket)
                 .collect::<Vec<_>>();
-                let buckets_len = buckets.len();
-                if buckets_len == 0 {
-                    break;
-                }
-                last_bucket = *buckets.get(buckets_len - 1).unwrap();
-                let mut tasks = Vec::new();
                 for bucket in buckets {
-                    let cassandra_provider = Arc::clone(&self.cassandra_provider);
-                    let topic_id = topic_id.to_owned();
-                    let consumer_id = consumer_id.to_owned();
-                    let consumer_delivery_cache = Arc::clone(&consumer_delivery_cache);
-                    let task = tokio::spawn(async move {
+                    let (_bucket, _all_attempted, _last_attempted_ts, any_new_found) =
                         Self::populate_delivery_cache_with_fresh_in_bucket(
-                            &cassandra_provider,
-                            &topic_id,
-                            &consumer_id,
+                            &self.cassandra_provider,
+                            topic_id,
+                            consumer_id,
                             attempted_low_exclusive,
                             bucket,
-                            consumer_delivery_cache,
+                            Arc::clone(&consumer_delivery_cache),
+                            max_events,
+                            1,
                         )
-                        .await
-                    });
-                    tasks.push(task);
-                }
-                // Await these in the order they were created (bucket order)
-                for task in tasks {
-                    let (
-                        _bucket,
-                        all_attempted_in_bucket,
-                        last_attempted_ts_res,
-                        any_new_found_in_bucket,
-                    ) = task.await.unwrap();
-                    if all_attempted {
-                        last_attempted_ts = last_attempted_ts_res.as_encoded();
-                    } else if !all_attempted_in_bucket {
-                        all_attempted = false;
+                        .await;
+                    if any_new_found {
+                        return true;
                     }
-                    any_new_found |= any_new_found_in_bucket;
-                }
-                if buckets_len < max_results {
-                    break;
                 }
-            }
-        }
-        (last_attempted_ts, any_new_found)
+                false
+            })
+            .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn populate_delivery_cache_with_retries(
         &self,
         topic_id: &str,
@@ -518,117 +864,137 @@ impl ConsumerDeliveryFacade for CassandraConsumerDeliveryFacade {
         done_low_exclusive: UniqueTime,
         freshness_duration_micros: u64,
         clock_skew_tolerance_micros: u64,
+        retry_backoff: &RetryBackoff,
     ) -> u64 {
-        let mut done_count = 0;
-        let mut total_count = 0;
-        let timeout_ts = fragtale_client::time::get_timestamp_micros() - freshness_duration_micros;
-        let timeout_shelf = CassandraProviderFacades::get_shelf_from_timestamp_u16(timeout_ts);
-        // Get attempt baseline shelf and bucket
-        let done_shelf = done_low_exclusive.get_shelf();
-        let done_bucket = done_low_exclusive.get_bucket();
-        let mut all_done = true;
-        let mut last_done_ts = done_low_exclusive;
-        if log::log_enabled!(log::Level::Trace) {
-            log::trace!("Checking shelf {done_shelf}..={timeout_shelf} for redelivery");
-        }
-        for shelf in done_shelf..=timeout_shelf {
-            let mut bucket = Some(done_bucket);
-            // Keep going while there are more buckets
-            while bucket.is_some() {
-                tokio::task::yield_now().await;
+        QueryClass::Retry
+            .scope(async {
+                let mut done_count = 0;
+                let mut total_count = 0;
+                let now = fragtale_client::time::get_timestamp_micros();
+                let timeout_ts = now - freshness_duration_micros;
+                let timeout_shelf = CassandraProviderFacades::get_shelf_from_timestamp_u16(timeout_ts);
+                // Get attempt baseline shelf and bucket
+                let done_shelf = done_low_exclusive.get_shelf();
+                let done_bucket = done_low_exclusive.get_bucket();
+                let mut all_done = true;
+                let mut last_done_ts = done_low_exclusive;
                 if log::log_enabled!(log::Level::Trace) {
-                    log::trace!(
-                        "Checking shelf {shelf} bucket {} for redelivery",
-                        bucket.unwrap()
-                    );
+                    log::trace!("Checking shelf {done_shelf}..={timeout_shelf} for redelivery");
                 }
-                let mut unique_time_low_exclusive = done_low_exclusive.as_encoded();
-                // While the ts is still within the bucket
-                while unique_time_low_exclusive
-                    <= std::cmp::min(
-                        //CassandraClient::get_max_timestamp_in_bucket(bucket.unwrap()),
-                        UniqueTime::max_encoded_in_bucket(bucket.unwrap()),
-                        UniqueTime::min_encoded_for_micros(timeout_ts),
-                    )
-                {
-                    tokio::task::yield_now().await;
-                    let unique_time_high_inclusive = UniqueTime::min_encoded_for_micros(timeout_ts);
-                    let delivery_intent_vec = DeliveryIntentEntity::select_by_unique_time(
-                        &self.cassandra_provider,
-                        topic_id,
-                        consumer_id,
-                        bucket.unwrap(),
-                        unique_time_low_exclusive,
-                        unique_time_high_inclusive,
-                        1000,
-                    )
-                    .await;
-                    if log::log_enabled!(log::Level::Trace) {
-                        log::trace!(
-                            "topic_id '{topic_id}': shelf {shelf} bucket {} with ({unique_time_low_exclusive}..{unique_time_high_inclusive}] has {} results",
-                            bucket.unwrap(),
-                            delivery_intent_vec.len(),
-                        );
-                    }
-                    // if there are no more results in this bucket
-                    if delivery_intent_vec.is_empty() {
-                        // Don't update the done baseline, since there might
-                        // exist events that have not even been tried yet.
-                        break;
-                    }
-                    total_count += delivery_intent_vec.len();
-                    for delivery_intent in delivery_intent_vec {
-                        unique_time_low_exclusive = delivery_intent.get_unique_time().as_encoded();
-                        // Track if all events are done (or if we have to retry deliveries again later)
-                        if delivery_intent.get_done() {
-                            if all_done {
-                                last_done_ts = delivery_intent.get_unique_time();
-                            }
-                            done_count += 1;
-                            continue;
-                        }
-                        all_done = false;
-                        if delivery_intent.get_intent_ts() >= timeout_ts {
-                            continue;
+                for shelf in done_shelf..=timeout_shelf {
+                    let mut bucket = Some(done_bucket);
+                    // Keep going while there are more buckets
+                    while bucket.is_some() {
+                        tokio::task::yield_now().await;
+                        if log::log_enabled!(log::Level::Trace) {
+                            log::trace!(
+                                "Checking shelf {shelf} bucket {} for redelivery",
+                                bucket.unwrap()
+                            );
                         }
-                        consumer_delivery_cache.insert(DeliveryIntentTemplate::new(
-                            delivery_intent.get_unique_time(),
-                            delivery_intent.get_event_id().to_owned(),
-                            delivery_intent.get_descriptor_version(),
-                            Some(delivery_intent.get_intent_ts()),
-                        ));
-                        if consumer_delivery_cache.is_full() {
-                            if done_count > 0 || total_count > 0 {
-                                log::debug!("done_count: {done_count}, total_count: {total_count}");
+                        let mut unique_time_low_exclusive = done_low_exclusive.as_encoded();
+                        // While the ts is still within the bucket
+                        while unique_time_low_exclusive
+                            <= std::cmp::min(
+                                //CassandraClient::get_max_timestamp_in_bucket(bucket.unwrap()),
+                                UniqueTime::max_encoded_in_bucket(bucket.unwrap()),
+                                UniqueTime::min_encoded_for_micros(timeout_ts),
+                            )
+                        {
+                            tokio::task::yield_now().await;
+                            let unique_time_high_inclusive = UniqueTime::min_encoded_for_micros(timeout_ts);
+                            let delivery_intent_vec = DeliveryIntentEntity::select_by_unique_time(
+                                &self.cassandra_provider,
+                                topic_id,
+                                consumer_id,
+                                bucket.unwrap(),
+                                unique_time_low_exclusive,
+                                unique_time_high_inclusive,
+                                1000,
+                            )
+                            .await;
+                            if log::log_enabled!(log::Level::Trace) {
+                                log::trace!(
+                                    "topic_id '{topic_id}': shelf {shelf} bucket {} with ({unique_time_low_exclusive}..{unique_time_high_inclusive}] has {} results",
+                                    bucket.unwrap(),
+                                    delivery_intent_vec.len(),
+                                );
+                            }
+                            // if there are no more results in this bucket
+                            if delivery_intent_vec.is_empty() {
+                                // Don't update the done baseline, since there might
+                                // exist events that have not even been tried yet.
+                                break;
+                            }
+                            total_count += delivery_intent_vec.len();
+                            // Back off from events that have failed repeatedly. Any
+                            // intent of an event that is not due postpones the retry.
+                            let not_due = delivery_intent_vec
+                                .iter()
+                                .filter(|delivery_intent| {
+                                    retry_backoff.get_due_ts_micros(
+                                        delivery_intent.get_intent_ts(),
+                                        delivery_intent.get_retry_count(),
+                                        freshness_duration_micros,
+                                    ) > now
+                                })
+                                .map(|delivery_intent| delivery_intent.get_unique_time().as_encoded())
+                                .collect::<HashSet<_>>();
+                            for delivery_intent in delivery_intent_vec {
+                                unique_time_low_exclusive = delivery_intent.get_unique_time().as_encoded();
+                                // Track if all events are done (or if we have to retry deliveries again later)
+                                if delivery_intent.get_done() {
+                                    if all_done {
+                                        last_done_ts = delivery_intent.get_unique_time();
+                                    }
+                                    done_count += 1;
+                                    continue;
+                                }
+                                all_done = false;
+                                if not_due.contains(&delivery_intent.get_unique_time().as_encoded()) {
+                                    continue;
+                                }
+                                consumer_delivery_cache.insert(DeliveryIntentTemplate::new(
+                                    delivery_intent.get_unique_time(),
+                                    delivery_intent.get_event_id().to_owned(),
+                                    delivery_intent.get_descriptor_version(),
+                                    Some(delivery_intent.get_intent_ts()),
+                                    None,
+                                ));
+                                if consumer_delivery_cache.is_full() {
+                                    if done_count > 0 || total_count > 0 {
+                                        log::debug!("done_count: {done_count}, total_count: {total_count}");
+                                    }
+                                    return std::cmp::min(
+                                        last_done_ts.as_encoded(),
+                                        UniqueTime::min_encoded_for_micros(
+                                            timeout_ts - clock_skew_tolerance_micros,
+                                        ),
+                                    );
+                                }
                             }
-                            return std::cmp::min(
-                                last_done_ts.as_encoded(),
-                                UniqueTime::min_encoded_for_micros(
-                                    timeout_ts - clock_skew_tolerance_micros,
-                                ),
-                            );
                         }
+                        // Get next bucket in shelf
+                        bucket = UniqueTimeBucketByShelfEntity::select_next_by_shelf_and_bucket(
+                            &self.cassandra_provider,
+                            topic_id,
+                            shelf,
+                            bucket.unwrap(),
+                            1,
+                        )
+                        .await
+                        .first()
+                        .map(UniqueTimeBucketByShelfEntity::get_bucket);
                     }
                 }
-                // Get next bucket in shelf
-                bucket = UniqueTimeBucketByShelfEntity::select_next_by_shelf_and_bucket(
-                    &self.cassandra_provider,
-                    topic_id,
-                    shelf,
-                    bucket.unwrap(),
-                    1,
+                if log::log_enabled!(log::Level::Debug) && (done_count > 0 || total_count > 0) {
+                    log::debug!("done_count: {done_count}, total_count: {total_count}");
+                }
+                std::cmp::min(
+                    last_done_ts.as_encoded(),
+                    UniqueTime::min_encoded_for_micros(timeout_ts - clock_skew_tolerance_micros),
                 )
-                .await
-                .first()
-                .map(UniqueTimeBucketByShelfEntity::get_bucket);
-            }
-        }
-        if log::log_enabled!(log::Level::Debug) && (done_count > 0 || total_count > 0) {
-            log::debug!("done_count: {done_count}, total_count: {total_count}");
-        }
-        std::cmp::min(
-            last_done_ts.as_encoded(),
-            UniqueTime::min_encoded_for_micros(timeout_ts - clock_skew_tolerance_micros),
-        )
+            })
+            .await
     }
 }
@@ -18,14 +18,23 @@
 //! Cassandra implementation of [EventFacade].
 
 use crate::CassandraProvider;
+use crate::cassandra_provider::entity::EventAnnotationEntity;
 use crate::cassandra_provider::entity::EventEntity;
 use crate::cassandra_provider::entity::EventIdByUniqueTimeEntity;
+use crate::cassandra_provider::entity::EventTopicEntity;
+use crate::cassandra_provider::entity::RejectedEventEntity;
 use crate::cassandra_provider::entity::UniqueTimeBucketByShelfEntity;
 use crossbeam_skiplist::SkipMap;
 use fragtale_dbp::dbp::facades::EventFacade;
+use fragtale_dbp::mb::EventAnnotation;
+use fragtale_dbp::mb::ExtractedValue;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use fragtale_dbp::mb::RejectedEvent;
 use fragtale_dbp::mb::TopicEvent;
 use fragtale_dbp::mb::UniqueTime;
 use fragtale_dbp::mb::consumers::EventDeliveryGist;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
@@ -34,18 +43,188 @@ use std::sync::atomic::Ordering;
 pub struct CassandraEventFacade {
     cassandra_provider: Arc<CassandraProvider>,
     per_topic_persisted_bucket: SkipMap<String, AtomicU64>,
+    index_column_types: SkipMap<(String, String), String>,
 }
 
 impl CassandraEventFacade {
+    /// Max number of retries of a transient failure to persist an event.
+    const EVENT_PERSIST_MAX_RETRIES: u32 = 3;
+    /// Max combined size of the event documents in a single logged batch.
+    ///
+    /// This keeps batches of several events below the default
+    /// `batch_size_fail_threshold` (50 KiB) of the cluster.
+    const EVENTS_BATCH_MAX_BYTES: usize = 40 * 1024;
+
     /// Return a new instance.
     pub fn new(cassandra_provider: &Arc<CassandraProvider>) -> Self {
         Self {
             cassandra_provider: Arc::clone(cassandra_provider),
             per_topic_persisted_bucket: SkipMap::default(),
+            index_column_types: SkipMap::default(),
         }
     }
 }
 
+impl CassandraEventFacade {
+    /// Return the CQL type of the column that holds the extracted values.
+    ///
+    /// The type of a column never changes once created, so it is cached.
+    async fn index_column_type(&self, topic_id: &str, column_name: &str) -> Option<String> {
+        let cache_key = (topic_id.to_owned(), column_name.to_owned());
+        if let Some(entry) = self.index_column_types.get(&cache_key) {
+            return Some(entry.value().to_owned());
+        }
+        let column_type = self
+            .cassandra_provider
+            .get_column_type(
+                &self.cassandra_provider.get_keyspace_from_topic(topic_id),
+                EventEntity::CQL_TABLE_NAME,
+                column_name,
+            )
+            .await?;
+        self.index_column_types
+            .insert(cache_key, column_type.to_owned());
+        Some(column_type)
+    }
+
+    /// Write the rows of a published event that are missing after a batch
+    /// with an unknown outcome.
+    ///
+    /// All the writes are idempotent, so rows that made it are left as is.
+    ///
+    /// Return `true` if all rows are known to be present afterwards.
+    async fn repair_partial_persist(&self, topic_id: &str, topic_event: &TopicEvent) -> bool {
+        let unique_time = topic_event.get_unique_time();
+        let event_missing = EventEntity::select_by_event_id_and_unique_time(
+            &self.cassandra_provider,
+            topic_id,
+            topic_event.get_event_id(),
+            unique_time,
+        )
+        .await
+        .is_none();
+        let lookup_missing = EventIdByUniqueTimeEntity::select_by_exact_unique_time(
+            &self.cassandra_provider,
+            topic_id,
+            unique_time,
+        )
+        .await
+        .is_none();
+        if !event_missing && !lookup_missing {
+            return true;
+        }
+        log::info!(
+            "Repairing partial write of event '{}' in '{topic_id}' (event missing: {event_missing}, lookup missing: {lookup_missing}).",
+            topic_event.get_event_id()
+        );
+        let event_repaired = !event_missing
+            || EventEntity::from(topic_event)
+                .insert(
+                    &self.cassandra_provider,
+                    topic_id,
+                    topic_event.get_additional_columns().to_owned(),
+                )
+                .await;
+        let lookup_repaired = !lookup_missing
+            || EventIdByUniqueTimeEntity::from(topic_event)
+                .insert(&self.cassandra_provider, topic_id)
+                .await;
+        // The bucket might be missing as well
+        let bucket_repaired = UniqueTimeBucketByShelfEntity::new(unique_time)
+            .insert(&self.cassandra_provider, topic_id)
+            .await;
+        event_repaired && lookup_repaired && bucket_repaired
+    }
+
+    /// Split events into consecutive parts that each fit in a single logged
+    /// batch.
+    ///
+    /// An event that is larger than [Self::EVENTS_BATCH_MAX_BYTES] on its own
+    /// gets a part of its own.
+    fn split_by_batch_size(topic_events: &[TopicEvent]) -> Vec<&[TopicEvent]> {
+        let mut parts = Vec::new();
+        let mut start = 0;
+        let mut part_bytes = 0;
+        for (index, topic_event) in topic_events.iter().enumerate() {
+            let event_bytes = topic_event.get_document().len();
+            if index > start && part_bytes + event_bytes > Self::EVENTS_BATCH_MAX_BYTES {
+                parts.push(&topic_events[start..index]);
+                start = index;
+                part_bytes = 0;
+            }
+            part_bytes += event_bytes;
+        }
+        if start < topic_events.len() {
+            parts.push(&topic_events[start..]);
+        }
+        parts
+    }
+
+    /// Persist events that fit in a single logged batch.
+    ///
+    /// Return the number of retries needed to persist the events.
+    async fn events_persist_batch(
+        &self,
+        topic_id: &str,
+        topic_events: &[TopicEvent],
+    ) -> Result<u32, MessageBrokerError> {
+        let mut statements = Vec::with_capacity(topic_events.len() * 2 + 1);
+        let mut buckets = Vec::new();
+        for topic_event in topic_events {
+            statements.push(
+                EventEntity::from(topic_event)
+                    .insert_statement(topic_event.get_additional_columns().to_owned()),
+            );
+            statements.push(EventIdByUniqueTimeEntity::from(topic_event).insert_statement());
+            // A batch rarely spans more than a single bucket, so always write them
+            let unique_time = topic_event.get_unique_time();
+            if !buckets.contains(&unique_time.get_bucket()) {
+                buckets.push(unique_time.get_bucket());
+                statements.push(UniqueTimeBucketByShelfEntity::new(unique_time).insert_statement());
+            }
+        }
+        // Same reasoning as for a single event.
+        let retries = match self
+            .cassandra_provider
+            .batch_with_keyspace_values_and_retries(
+                statements,
+                &self.cassandra_provider.get_keyspace_from_topic(topic_id),
+                true,
+                Self::EVENT_PERSIST_MAX_RETRIES,
+            )
+            .await
+        {
+            Ok(retries) => retries,
+            Err(msg) => {
+                for topic_event in topic_events {
+                    if !self.repair_partial_persist(topic_id, topic_event).await {
+                        return Err(MessageBrokerErrorKind::PersistenceFailure.error_with_msg(
+                            format!(
+                                "Failed to persist batch of {} events in topic '{topic_id}': {msg}",
+                                topic_events.len()
+                            ),
+                        ));
+                    }
+                }
+                Self::EVENT_PERSIST_MAX_RETRIES
+            }
+        };
+        for topic_event in topic_events {
+            EventTopicEntity::new(
+                topic_event.get_event_id(),
+                topic_id,
+                topic_event.get_unique_time(),
+            )
+            .insert(
+                &self.cassandra_provider,
+                &self.cassandra_provider.app_keyspace,
+            )
+            .await;
+        }
+        Ok(retries)
+    }
+}
+
 #[async_trait::async_trait]
 impl EventFacade for CassandraEventFacade {
     async fn event_by_id(&self, topic_id: &str, event_id: &str) -> Option<EventDeliveryGist> {
@@ -83,10 +262,16 @@ impl EventFacade for CassandraEventFacade {
         // Use something like 524288 (~68 MiB) to stay clear of this limit.
         // This would still be pretty bad index design, but this is not the
         // right place to enfore suhc limit..
+        let column_name = EventEntity::EXTRACTED_COLUMN_PREFIX.to_owned() + index_column;
+        let Some(column_type) = self.index_column_type(topic_id, &column_name).await else {
+            // No extractor with this result name has been registered
+            return vec![];
+        };
         let mut ret = EventEntity::select_ids_and_unique_time_by_index(
             &self.cassandra_provider,
             topic_id,
-            index_column,
+            &column_name,
+            &column_type,
             index_key,
             524_288,
         )
@@ -99,6 +284,21 @@ impl EventFacade for CassandraEventFacade {
             .collect()
     }
 
+    async fn event_unique_times_by_id(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<UniqueTime> {
+        EventEntity::select_unique_times_by_event_id(
+            &self.cassandra_provider,
+            topic_id,
+            event_id,
+            max_results,
+        )
+        .await
+    }
+
     async fn event_document_by_correlation_token(
         &self,
         topic_id: &str,
@@ -113,19 +313,18 @@ impl EventFacade for CassandraEventFacade {
         .map(EventEntity::into_event_delivery_gist)
     }
 
-    async fn event_persist(&self, topic_id: &str, topic_event: TopicEvent) -> String {
-        EventEntity::from(&topic_event)
-            .insert(
-                &self.cassandra_provider,
-                topic_id,
-                topic_event.get_additional_columns().to_owned(),
-            )
-            .await;
-        EventIdByUniqueTimeEntity::from(&topic_event)
-            .insert(&self.cassandra_provider, topic_id)
-            .await;
+    async fn event_persist(
+        &self,
+        topic_id: &str,
+        topic_event: TopicEvent,
+    ) -> Result<(String, u32), MessageBrokerError> {
         let unique_time = topic_event.get_unique_time();
-        // Avoid this query/insert if its already known to be there
+        let mut statements = vec![
+            EventEntity::from(&topic_event)
+                .insert_statement(topic_event.get_additional_columns().to_owned()),
+            EventIdByUniqueTimeEntity::from(&topic_event).insert_statement(),
+        ];
+        // Avoid this insert if its already known to be there
         // (This is full of glitches, but lightweight and prevents several db ops.)
         let persisted_bucket_entry = self
             .per_topic_persisted_bucket
@@ -142,13 +341,280 @@ impl EventFacade for CassandraEventFacade {
         if old_value != unique_time.get_bucket() {
             // Be optimistic
             persisted_bucket.store(unique_time.get_bucket(), Ordering::Relaxed);
-            UniqueTimeBucketByShelfEntity::new(unique_time)
-                .insert(&self.cassandra_provider, topic_id)
-                .await;
+            statements.push(UniqueTimeBucketByShelfEntity::new(unique_time).insert_statement());
             if log::log_enabled!(log::Level::Trace) {
                 log::trace!("persisted_bucket.store {}", unique_time.get_bucket());
             }
         }
-        topic_event.get_correlation_token().to_owned()
+        // The rows are in different partitions, so only a logged batch
+        // prevents an event that is never delivered or an index entry without
+        // an event.
+        // All statements are idempotent, so retrying a batch with an unknown
+        // outcome is safe.
+        let retries = match self
+            .cassandra_provider
+            .batch_with_keyspace_values_and_retries(
+                statements,
+                &self.cassandra_provider.get_keyspace_from_topic(topic_id),
+                true,
+                Self::EVENT_PERSIST_MAX_RETRIES,
+            )
+            .await
+        {
+            Ok(retries) => retries,
+            Err(msg) => {
+                if !self.repair_partial_persist(topic_id, &topic_event).await {
+                    // Ensure that the bucket is written with the next event
+                    persisted_bucket.store(0, Ordering::Relaxed);
+                    return Err(MessageBrokerErrorKind::PersistenceFailure.error_with_msg(
+                        format!(
+                            "Failed to persist event '{}' in topic '{topic_id}': {msg}",
+                            topic_event.get_event_id()
+                        ),
+                    ));
+                }
+                Self::EVENT_PERSIST_MAX_RETRIES
+            }
+        };
+        // The lookup lives in the app keyspace and is verified when read, so
+        // it is kept out of the topic's batch.
+        EventTopicEntity::new(topic_event.get_event_id(), topic_id, unique_time)
+            .insert(
+                &self.cassandra_provider,
+                &self.cassandra_provider.app_keyspace,
+            )
+            .await;
+        Ok((topic_event.get_correlation_token().to_owned(), retries))
+    }
+
+    async fn events_persist(
+        &self,
+        topic_id: &str,
+        topic_events: Vec<TopicEvent>,
+    ) -> Result<(Vec<String>, u32), MessageBrokerError> {
+        let mut correlation_tokens = Vec::with_capacity(topic_events.len());
+        let mut retries = 0;
+        for topic_events in Self::split_by_batch_size(&topic_events) {
+            retries += self.events_persist_batch(topic_id, topic_events).await?;
+            correlation_tokens.extend(
+                topic_events
+                    .iter()
+                    .map(|topic_event| topic_event.get_correlation_token().to_owned()),
+            );
+        }
+        Ok((correlation_tokens, retries))
+    }
+
+    async fn event_index_columns_update(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        additional_columns: HashMap<String, ExtractedValue>,
+    ) -> bool {
+        EventEntity::update_additional_columns(
+            &self.cassandra_provider,
+            topic_id,
+            event_id,
+            unique_time,
+            additional_columns,
+        )
+        .await
+    }
+
+    async fn event_topics_by_event_id(
+        &self,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<(String, UniqueTime)> {
+        EventTopicEntity::select_by_event_id(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            event_id,
+            max_results,
+        )
+        .await
+        .into_iter()
+        .map(|entity| (entity.get_topic_id().to_owned(), entity.get_unique_time()))
+        .collect()
+    }
+
+    async fn event_purge(&self, topic_id: &str, event_id: &str, unique_time: UniqueTime) -> bool {
+        let db = &self.cassandra_provider;
+        if EventEntity::select_by_event_id_and_unique_time(db, topic_id, event_id, unique_time)
+            .await
+            .is_none()
+        {
+            return false;
+        }
+        // Remove the event first, so a failure further down at most leaves
+        // lookups pointing to a missing event, which readers already tolerate.
+        if !EventEntity::delete(db, topic_id, event_id, unique_time).await {
+            return false;
+        }
+        EventIdByUniqueTimeEntity::delete_by_exact_unique_time(db, topic_id, unique_time).await;
+        if EventEntity::select_unique_times_by_event_id(db, topic_id, event_id, 1)
+            .await
+            .is_empty()
+        {
+            EventAnnotationEntity::delete_by_event_id(db, topic_id, event_id).await;
+            EventTopicEntity::delete(db, &db.app_keyspace, event_id, topic_id).await;
+        }
+        true
+    }
+
+    async fn event_redact(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        document: &str,
+        protection_ref: &str,
+    ) -> bool {
+        let db = &self.cassandra_provider;
+        if EventEntity::select_by_event_id_and_unique_time(db, topic_id, event_id, unique_time)
+            .await
+            .is_none()
+        {
+            return false;
+        }
+        if !EventEntity::update_document(
+            db,
+            topic_id,
+            event_id,
+            unique_time,
+            document,
+            protection_ref,
+        )
+        .await
+        {
+            return false;
+        }
+        EventAnnotationEntity::delete_by_event_id(db, topic_id, event_id).await;
+        true
+    }
+
+    async fn rejected_event_persist(&self, topic_id: &str, rejected_event: RejectedEvent) -> bool {
+        RejectedEventEntity::from(&rejected_event)
+            .insert(&self.cassandra_provider, topic_id)
+            .await
+    }
+
+    async fn rejected_events(
+        &self,
+        topic_id: &str,
+        from: &Option<String>,
+        max_results: usize,
+    ) -> (Vec<RejectedEvent>, bool) {
+        let ret =
+            RejectedEventEntity::select_all(&self.cassandra_provider, topic_id, from, max_results)
+                .await
+                .into_iter()
+                .map(RejectedEvent::from)
+                .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+
+    async fn rejected_event_by_id(&self, topic_id: &str, event_id: &str) -> Option<RejectedEvent> {
+        RejectedEventEntity::select_by_event_id(&self.cassandra_provider, topic_id, event_id)
+            .await
+            .map(RejectedEvent::from)
+    }
+
+    async fn rejected_event_delete(&self, topic_id: &str, event_id: &str) -> bool {
+        RejectedEventEntity::delete(&self.cassandra_provider, topic_id, event_id).await
+    }
+
+    async fn event_annotation_persist(
+        &self,
+        topic_id: &str,
+        event_annotation: EventAnnotation,
+    ) -> bool {
+        EventAnnotationEntity::from(&event_annotation)
+            .insert(&self.cassandra_provider, topic_id)
+            .await
+    }
+
+    async fn event_annotations_by_event_id(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<EventAnnotation> {
+        EventAnnotationEntity::select_by_event_id(
+            &self.cassandra_provider,
+            topic_id,
+            event_id,
+            max_results,
+        )
+        .await
+        .into_iter()
+        .map(EventAnnotation::from)
+        .collect()
+    }
+
+    async fn buckets_by_shelf(
+        &self,
+        topic_id: &str,
+        shelf: u16,
+        from_bucket: Option<u64>,
+        max_results: usize,
+    ) -> (Vec<u64>, bool) {
+        // The query is exclusive of the current bucket
+        let current_bucket = from_bucket
+            .unwrap_or_else(|| UniqueTime::first_bucket_in_shelf(shelf).saturating_sub(1));
+        let ret = UniqueTimeBucketByShelfEntity::select_next_by_shelf_and_bucket(
+            &self.cassandra_provider,
+            topic_id,
+            shelf,
+            current_bucket,
+            max_results,
+        )
+        .await
+        .iter()
+        .map(UniqueTimeBucketByShelfEntity::get_bucket)
+        .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+
+    async fn event_count_by_bucket(&self, topic_id: &str, bucket: u64) -> u64 {
+        EventIdByUniqueTimeEntity::count_by_bucket(&self.cassandra_provider, topic_id, bucket).await
+    }
+
+    async fn events_by_bucket(
+        &self,
+        topic_id: &str,
+        bucket: u64,
+        from: Option<UniqueTime>,
+        max_results: usize,
+    ) -> (Vec<(UniqueTime, String, Option<u64>)>, bool) {
+        let unique_time_low_exclusive = from.map_or_else(
+            || UniqueTime::min_encoded_in_bucket(bucket).saturating_sub(1),
+            |from| from.as_encoded(),
+        );
+        let (entities, paging_state) = EventIdByUniqueTimeEntity::select_by_unique_time(
+            &self.cassandra_provider,
+            topic_id,
+            bucket,
+            unique_time_low_exclusive,
+            max_results,
+            None,
+        )
+        .await;
+        let ret = entities
+            .iter()
+            .take(max_results)
+            .map(|entity| {
+                (
+                    entity.get_unique_time(),
+                    entity.get_event_id().to_owned(),
+                    entity.get_descriptor_version(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let potentially_more_results = paging_state.is_some() || ret.len() == max_results;
+        (ret, potentially_more_results)
     }
 }
@@ -21,6 +21,7 @@ use super::CassandraProviderFacades;
 use crate::CassandraProvider;
 use crate::cassandra_provider::entity::EventIdByUniqueTimeEntity;
 use crate::cassandra_provider::entity::ObjectCountEntity;
+use crate::cassandra_provider::entity::ObjectCountSnapshotEntity;
 use crate::cassandra_provider::entity::UniqueTimeBucketByShelfEntity;
 use fragtale_dbp::dbp::facades::EventTrackingFacade;
 use fragtale_dbp::mb::ObjectCount;
@@ -55,20 +56,23 @@ impl CassandraEventTrackingFacade {
         unique_time_start: UniqueTime,
         any_change: &AtomicBool,
     ) {
-        let mut unique_time_low_exclusive =
+        let unique_time_low_exclusive =
             UniqueTime::min_encoded_for_micros(unique_time_start.get_time_micros());
-        // While the ts is still within the bucket
-        while unique_time_low_exclusive <= UniqueTime::max_encoded_in_bucket(bucket) {
+        let mut paging_state = None;
+        // Page through the bucket
+        loop {
             let max_results = 128;
             // Get next batch of potential events
-            let event_id_bute_vec = EventIdByUniqueTimeEntity::select_by_unique_time(
-                cassandra_provider,
-                topic_id,
-                bucket,
-                unique_time_low_exclusive,
-                max_results,
-            )
-            .await;
+            let (event_id_bute_vec, next_paging_state) =
+                EventIdByUniqueTimeEntity::select_by_unique_time(
+                    cassandra_provider,
+                    topic_id,
+                    bucket,
+                    unique_time_low_exclusive,
+                    max_results,
+                    paging_state,
+                )
+                .await;
             if log::log_enabled!(log::Level::Trace) {
                 log::trace!(
                     "topic_id '{topic_id}' bucket {bucket} with {unique_time_low_exclusive} has {} results",
@@ -86,14 +90,14 @@ impl CassandraEventTrackingFacade {
                 break;
             }
             for event_id_bute in event_id_bute_vec {
-                unique_time_low_exclusive = event_id_bute.get_unique_time().as_encoded();
                 if correlation_hotlist
                     .notify_hotlist_entry(topic_id, event_id_bute.get_correlation_token())
                 {
                     any_change.store(true, Ordering::Relaxed);
                 }
             }
-            if event_id_bute_vec_len < max_results {
+            paging_state = next_paging_state;
+            if paging_state.is_none() {
                 if log::log_enabled!(log::Level::Trace) {
                     log::trace!(
                         "topic_id '{topic_id}' bucket {bucket} hos no more results. -> Break!",
@@ -138,6 +142,39 @@ impl EventTrackingFacade for CassandraEventTrackingFacade {
         .collect::<Vec<_>>()
     }
 
+    async fn object_count_snapshot_upsert(
+        &self,
+        topic_id: &str,
+        object_count_type: &ObjectCountType,
+        instance_id: u16,
+        value: u64,
+    ) {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        ObjectCountSnapshotEntity::new(topic_id, instance_id, object_count_type, value, now_micros)
+            .insert(&self.cassandra_provider)
+            .await;
+    }
+
+    async fn object_count_snapshots_by_instance(
+        &self,
+        topic_id: &str,
+        instance_id: u16,
+    ) -> Vec<(ObjectCountType, u64)> {
+        ObjectCountSnapshotEntity::select_by_topic_id_and_instance_id(
+            &self.cassandra_provider,
+            topic_id,
+            instance_id,
+        )
+        .await
+        .iter()
+        .map(|entity| (entity.get_object_type(), entity.get_object_count()))
+        .collect::<Vec<_>>()
+    }
+
+    async fn object_count_snapshots_delete(&self, topic_id: &str) {
+        ObjectCountSnapshotEntity::delete_by_topic_id(&self.cassandra_provider, topic_id).await;
+    }
+
     async fn track_new_events_in_topic(
         &self,
         topic_id: &str,
@@ -18,6 +18,7 @@
 //! Cassandra implementation of [InstanceIdFacade].
 
 use crate::CassandraProvider;
+use crate::cassandra_provider::entity::FeatureFlagsEntity;
 use crate::cassandra_provider::entity::IdentityClaimEntity;
 use fragtale_dbp::dbp::facades::InstanceIdFacade;
 use fragtale_dbp::mb::UniqueTime;
@@ -29,6 +30,9 @@ pub struct CassandraInstanceIdFacade {
 }
 
 impl CassandraInstanceIdFacade {
+    /// Identifier of the row with the cluster wide feature flags.
+    const FEATURE_FLAGS_ID: &'static str = "cluster";
+
     /// Return a new insatace.
     pub fn new(cassandra_provider: &Arc<CassandraProvider>) -> Self {
         Self {
@@ -121,4 +125,89 @@ impl InstanceIdFacade for CassandraInstanceIdFacade {
         .map(|ice| (ice.get_identity_claim(), ice.get_first_claim_ts()))
         .unwrap()
     }
+
+    async fn claim_role(
+        &self,
+        role: &str,
+        time_to_live_seconds: u32,
+        claimed_instance_id: u16,
+    ) -> bool {
+        // Keep the time of the first registration
+        let ice = IdentityClaimEntity::select_role(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            role,
+            claimed_instance_id,
+        )
+        .await
+        .unwrap_or_else(|| {
+            IdentityClaimEntity::new_role(
+                role,
+                claimed_instance_id,
+                fragtale_client::time::get_timestamp_micros(),
+            )
+        });
+        ice.insert(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            time_to_live_seconds,
+        )
+        .await
+    }
+
+    async fn free_role(&self, role: &str, claimed_instance_id: u16) {
+        IdentityClaimEntity::delete_role(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            role,
+            claimed_instance_id,
+        )
+        .await;
+    }
+
+    async fn get_oldest_instance_id_by_role(&self, role: &str) -> Option<u16> {
+        IdentityClaimEntity::select_all_role(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            role,
+        )
+        .await
+        .into_iter()
+        .min_by_key(IdentityClaimEntity::get_first_claim_ts)
+        .map(|ice| ice.get_identity_claim())
+    }
+
+    async fn feature_flags(&self) -> Option<String> {
+        FeatureFlagsEntity::select_by_id(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            Self::FEATURE_FLAGS_ID,
+        )
+        .await
+        .map(|entity| entity.get_feature_flags().to_owned())
+    }
+
+    async fn feature_flags_compare_and_set(
+        &self,
+        expected: Option<&str>,
+        feature_flags: &str,
+    ) -> bool {
+        let entity = FeatureFlagsEntity::new(Self::FEATURE_FLAGS_ID, feature_flags);
+        if let Some(expected) = expected {
+            entity
+                .update_if_unchanged(
+                    &self.cassandra_provider,
+                    &self.cassandra_provider.app_keyspace,
+                    expected,
+                )
+                .await
+        } else {
+            entity
+                .insert_if_not_exists(
+                    &self.cassandra_provider,
+                    &self.cassandra_provider.app_keyspace,
+                )
+                .await
+        }
+    }
 }
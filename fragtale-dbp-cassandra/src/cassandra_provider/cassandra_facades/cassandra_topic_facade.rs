@@ -18,12 +18,20 @@
 //! Topic facade implementation for Cassandra.
 
 use crate::CassandraProvider;
+use crate::cassandra_provider::CassandraDiagnostics;
 use crate::cassandra_provider::EventDescriptorEntity;
 use crate::cassandra_provider::EventEntity;
+use crate::cassandra_provider::entity::SharedSchemaEntity;
+use crate::cassandra_provider::entity::TopicBulkIngestEntity;
 use crate::cassandra_provider::entity::TopicEntity;
+use crate::cassandra_provider::entity::TopicIndexRebuildEntity;
+use crate::cassandra_provider::entity::TopicReplyEntity;
 use fragtale_dbp::dbp::facades::TopicFacade;
+use fragtale_dbp::mb::DiagnosticQuery;
+use fragtale_dbp::mb::IndexRebuildProgress;
 use fragtale_dbp::mb::MessageBrokerError;
 use fragtale_dbp::mb::MessageBrokerErrorKind;
+use fragtale_dbp::mb::UniqueTime;
 use std::sync::Arc;
 
 /// Topic facade implementation for Cassandra.
@@ -73,6 +81,19 @@ impl TopicFacade for CassandraTopicFacade {
         Ok(())
     }
 
+    async fn topic_exists(&self, topic_id: &str) -> bool {
+        self.cassandra_provider
+            .topic_exists_check
+            .contains(topic_id)
+            || TopicEntity::select_by_topic_id(
+                &self.cassandra_provider,
+                &self.cassandra_provider.app_keyspace,
+                topic_id,
+            )
+            .await
+            .is_some()
+    }
+
     /// Return an ordered list of existing topic indentifiers and an indicator
     /// if there might be additional results.
     async fn get_topic_ids(&self, from: &Option<String>) -> (Vec<String>, bool) {
@@ -133,6 +154,43 @@ impl TopicFacade for CassandraTopicFacade {
         .collect::<Vec<_>>()
     }
 
+    async fn shared_schema_persist(
+        &self,
+        schema_id: &str,
+        version: u64,
+        shared_schema: &str,
+    ) -> bool {
+        SharedSchemaEntity::new(schema_id, version, shared_schema)
+            .insert_if_not_exists(
+                &self.cassandra_provider,
+                &self.cassandra_provider.app_keyspace,
+            )
+            .await
+    }
+
+    async fn shared_schema_latest_by_id(&self, schema_id: &str) -> Option<(u64, String)> {
+        SharedSchemaEntity::select_latest_by_id(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            schema_id,
+        )
+        .await
+        .map(|entity| (entity.get_version(), entity.get_shared_schema().to_owned()))
+    }
+
+    async fn shared_schemas_latest(&self, max_results: usize) -> Vec<String> {
+        SharedSchemaEntity::select_latest_all(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            max_results,
+        )
+        .await
+        .iter()
+        .map(SharedSchemaEntity::get_shared_schema)
+        .map(|value| value.to_owned())
+        .collect::<Vec<_>>()
+    }
+
     async fn extraction_setup_searchable(
         &self,
         topic_id: &str,
@@ -183,4 +241,112 @@ impl TopicFacade for CassandraTopicFacade {
             }
         }
     }
+
+    async fn reply_topic_register(
+        &self,
+        topic_id: &str,
+        reply_topic_id: &str,
+        registered_by: &str,
+    ) -> bool {
+        TopicReplyEntity::new(topic_id, reply_topic_id, registered_by)
+            .insert(
+                &self.cassandra_provider,
+                &self.cassandra_provider.app_keyspace,
+            )
+            .await
+    }
+
+    async fn reply_topic_by_topic_id(&self, topic_id: &str) -> Option<String> {
+        TopicReplyEntity::select_by_topic_id(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            topic_id,
+        )
+        .await
+        .map(|entity| entity.get_reply_topic_id().to_owned())
+    }
+
+    async fn reply_topic_registered_by(&self, topic_id: &str) -> Option<String> {
+        TopicReplyEntity::select_by_topic_id(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            topic_id,
+        )
+        .await
+        .map(|entity| entity.get_registered_by().to_owned())
+    }
+
+    async fn bulk_ingest_window_persist(
+        &self,
+        topic_id: &str,
+        start_ts_micros: u64,
+        end_ts_micros: u64,
+        reconciled: bool,
+    ) -> bool {
+        TopicBulkIngestEntity::new(topic_id, start_ts_micros, end_ts_micros, reconciled)
+            .insert(
+                &self.cassandra_provider,
+                &self.cassandra_provider.app_keyspace,
+            )
+            .await
+    }
+
+    async fn bulk_ingest_window_by_topic_id(&self, topic_id: &str) -> Option<(u64, u64, bool)> {
+        TopicBulkIngestEntity::select_by_topic_id(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            topic_id,
+        )
+        .await
+        .map(|entity| {
+            (
+                entity.get_start_ts_micros(),
+                entity.get_end_ts_micros(),
+                entity.is_reconciled(),
+            )
+        })
+    }
+
+    async fn index_rebuild_persist(
+        &self,
+        topic_id: &str,
+        index_rebuild_progress: IndexRebuildProgress,
+    ) -> bool {
+        TopicIndexRebuildEntity::new(topic_id, &index_rebuild_progress)
+            .insert(
+                &self.cassandra_provider,
+                &self.cassandra_provider.app_keyspace,
+            )
+            .await
+    }
+
+    async fn index_rebuild_by_topic_id(&self, topic_id: &str) -> Option<IndexRebuildProgress> {
+        TopicIndexRebuildEntity::select_by_topic_id(
+            &self.cassandra_provider,
+            &self.cassandra_provider.app_keyspace,
+            topic_id,
+        )
+        .await
+        .as_ref()
+        .map(TopicIndexRebuildEntity::as_index_rebuild_progress)
+    }
+
+    async fn diagnostic_query(
+        &self,
+        topic_id: &str,
+        diagnostic_query: DiagnosticQuery,
+        consumer_id: Option<&str>,
+        range: Option<(UniqueTime, UniqueTime)>,
+        max_results: usize,
+    ) -> Result<(Vec<String>, bool), MessageBrokerError> {
+        CassandraDiagnostics::query(
+            &self.cassandra_provider,
+            topic_id,
+            diagnostic_query,
+            consumer_id,
+            range,
+            max_results,
+        )
+        .await
+    }
 }
@@ -18,6 +18,7 @@
 //! Cassandra query result mapping.
 
 use cdrs_tokio::frame::message_response::ResponseBody;
+use cdrs_tokio::types::CBytes;
 use cdrs_tokio::types::{ByName, IntoRustByIndex};
 
 /// Cassandra query result mapper.
@@ -40,6 +41,18 @@ impl CassandraResultMapper {
             .collect()
     }
 
+    /// Map rows into entities and return the paging state of the next page
+    /// (if there might be more results).
+    pub fn into_entities_and_paging_state<T: cdrs_tokio::frame::TryFromRow>(
+        response_body: ResponseBody,
+    ) -> (Vec<T>, Option<Vec<u8>>) {
+        let paging_state = response_body
+            .as_rows_metadata()
+            .and_then(|rows_metadata| rows_metadata.paging_state.clone())
+            .and_then(CBytes::into_bytes);
+        (Self::into_entities(response_body), paging_state)
+    }
+
     /// Map first column of each rows into a String.
     pub fn into_string_vec(response_body: ResponseBody) -> Vec<String> {
         response_body
@@ -111,6 +124,24 @@ impl CassandraResultMapper {
             .collect()
     }
 
+    /// Map first column of the first row into a count.
+    pub fn into_count(response_body: ResponseBody) -> u64 {
+        response_body
+            .into_rows()
+            .unwrap_or_default()
+            .first()
+            .and_then(|row| {
+                row.get_by_index(0)
+                    .map_err(|e| {
+                        log::debug!("get_by_index(0): {e}");
+                    })
+                    .ok()
+                    .and_then(|column_opt: Option<i64>| column_opt)
+            })
+            .map(|count| u64::try_from(count).unwrap_or_default())
+            .unwrap_or_default()
+    }
+
     /// Conditional statement have a special result named `[applied]`.
     ///
     /// This will return `true` if the `[applied]` result is missing which
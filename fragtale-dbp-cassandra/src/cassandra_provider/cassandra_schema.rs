@@ -180,6 +180,35 @@ impl CassandraSchema {
         .unwrap_or_default()
     }
 
+    const CQL_TEMPLATE_SELECT_COLUMN_TYPE: &'static str = "
+        SELECT type FROM columns
+        WHERE keyspace_name = ? AND table_name = ? AND column_name = ?
+        ;";
+
+    /// Return the CQL type of a table's column in the keyspace (if it exists).
+    pub async fn column_type_by_keyspace_table_and_column(
+        cs: &CassandraSession,
+        keyspace_name: &str,
+        table_name: &str,
+        column_name: &str,
+    ) -> Option<String> {
+        let values = cdrs_tokio::query_values!(
+        "keyspace_name" => keyspace_name.to_owned(),
+        "table_name" => table_name.to_owned(),
+        "column_name" => column_name.to_owned()
+        );
+        cs.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_COLUMN_TYPE,
+            Self::KEYSPACE_SYSTEM_SCHEMA,
+            values,
+        )
+        .await
+        .map(CassandraResultMapper::into_string_vec)
+        .unwrap_or_default()
+        .into_iter()
+        .next()
+    }
+
     const CQL_TEMPLATE_SELECT_INDEX_NAMES: &'static str = "
         SELECT index_name FROM indexes
         WHERE keyspace_name = ? AND table_name = ?
@@ -17,6 +17,7 @@
 
 //! Session (connection) to the Cassandra database.
 
+use super::query_scheduler::QueryScheduler;
 use cdrs_tokio::authenticators::StaticPasswordAuthenticatorProvider;
 use cdrs_tokio::cluster::NodeAddress;
 use cdrs_tokio::cluster::NodeTcpConfigBuilder;
@@ -27,11 +28,14 @@ use cdrs_tokio::cluster::session::SessionBuilder;
 use cdrs_tokio::cluster::session::TcpSessionBuilder;
 use cdrs_tokio::frame::events::SchemaChange;
 use cdrs_tokio::frame::events::ServerEvent;
+use cdrs_tokio::frame::message_batch::BatchType;
 use cdrs_tokio::frame::message_response::ResponseBody;
 use cdrs_tokio::load_balancing::RoundRobinLoadBalancingStrategy;
+use cdrs_tokio::query::BatchQueryBuilder;
 use cdrs_tokio::query::QueryValues;
 use cdrs_tokio::statement::StatementParamsBuilder;
 use cdrs_tokio::transport::TransportTcp;
+use cdrs_tokio::types::CBytes;
 use crossbeam_skiplist::SkipMap;
 use std::sync::Arc;
 use std::sync::atomic::AtomicUsize;
@@ -57,9 +61,16 @@ pub struct CassandraSession {
     schema_change_listener_count: AtomicUsize,
     schema_change_listeners: Arc<SkipMap<usize, Arc<dyn CassandraSchemaChangeListener>>>,
     replication_factor: usize,
+    /// Fair sharing of the session between query classes.
+    query_scheduler: Arc<QueryScheduler>,
 }
 
 impl CassandraSession {
+    /// Backoff before the first retry of a failed batch.
+    const BATCH_RETRY_INITIAL_BACKOFF_MICROS: u64 = 20_000;
+    /// Upper bound of the backoff between retries of a failed batch.
+    const BATCH_RETRY_MAX_BACKOFF_MICROS: u64 = 500_000;
+
     /// Open up a new session to the Cassandra database service and initialize
     /// server side event dispatch.
     pub async fn connect(
@@ -67,6 +78,7 @@ impl CassandraSession {
         username: &str,
         password: &str,
         replication_factor: usize,
+        query_scheduler: Arc<QueryScheduler>,
     ) -> Arc<Self> {
         let session = Arc::new(
             Self::create_session(endpoints, username, password)
@@ -81,6 +93,7 @@ impl CassandraSession {
             schema_change_listener_count: AtomicUsize::default(),
             schema_change_listeners: Arc::new(SkipMap::default()),
             replication_factor,
+            query_scheduler,
         })
         .init()
         .await
@@ -173,8 +186,12 @@ impl CassandraSession {
     /// Execute raw keyspaced query using this session.
     pub async fn query_raw(&self, query_template: &str, keyspace: &str) -> ResponseBody {
         log::debug!("Running '{query_template}' with keyspace '{keyspace}'.");
-        Arc::clone(&self.session)
-            .query(&query_template.replace("{{ keyspace }}", keyspace))
+        self.query_scheduler
+            .run(
+                keyspace,
+                Arc::clone(&self.session)
+                    .query(&query_template.replace("{{ keyspace }}", keyspace)),
+            )
             .await
             .unwrap_or_else(|e| {
                 panic!("Failed to execute query '{query_template}' in keyspace '{keyspace}': {e:?}")
@@ -183,12 +200,172 @@ impl CassandraSession {
             .expect("get body")
     }
 
+    /// Execute keyspaced statements with value parameters as a single batch
+    /// using this session.
+    ///
+    /// A logged batch is eventually applied as a whole, even when the
+    /// statements touch different partitions. An unlogged batch only saves
+    /// round trips and should be limited to statements of the same partition.
+    ///
+    /// Return `None` if the outcome of the batch is unknown.
+    pub async fn batch_with_keyspace_and_values(
+        &self,
+        statements: Vec<(String, QueryValues)>,
+        keyspace: &str,
+        logged: bool,
+    ) -> Option<ResponseBody> {
+        self.execute_batch(statements, keyspace, logged)
+            .await
+            .map_err(|(_transient, msg)| {
+                log::info!("{msg}");
+            })
+            .ok()
+    }
+
+    /// Execute keyspaced statements with value parameters as a single batch
+    /// and retry up to `max_retries` times when the failure is transient.
+    ///
+    /// All statements must be idempotent, since a batch that timed out might
+    /// still be applied.
+    ///
+    /// Return the number of retries needed to apply the batch or a
+    /// description of the last failure.
+    pub async fn batch_with_keyspace_values_and_retries(
+        &self,
+        statements: Vec<(String, QueryValues)>,
+        keyspace: &str,
+        logged: bool,
+        max_retries: u32,
+    ) -> Result<u32, String> {
+        let mut retries = 0;
+        loop {
+            match self
+                .execute_batch(statements.clone(), keyspace, logged)
+                .await
+            {
+                Ok(_response_body) => return Ok(retries),
+                Err((true, msg)) if retries < max_retries => {
+                    retries += 1;
+                    let delay = Self::retry_backoff(retries);
+                    log::debug!("Retry {retries}/{max_retries} of batch in {delay:?}: {msg}");
+                    sleep(delay).await;
+                }
+                Err((_transient, msg)) => {
+                    log::info!("{msg}");
+                    return Err(msg);
+                }
+            }
+        }
+    }
+
+    /// Delay before retry number `retry` with jitter between half and the
+    /// full exponential backoff.
+    fn retry_backoff(retry: u32) -> Duration {
+        let full_micros = Self::BATCH_RETRY_INITIAL_BACKOFF_MICROS
+            .saturating_mul(2u64.saturating_pow(retry.saturating_sub(1)))
+            .min(Self::BATCH_RETRY_MAX_BACKOFF_MICROS);
+        let half_micros = full_micros / 2;
+        // Sub-second clock noise is random enough to spread out retries
+        let noise = u64::from(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .subsec_nanos(),
+        );
+        Duration::from_micros(half_micros + noise % (half_micros + 1))
+    }
+
+    /// Execute the batch and on failure return if the failure is transient
+    /// and a description of it.
+    async fn execute_batch(
+        &self,
+        statements: Vec<(String, QueryValues)>,
+        keyspace: &str,
+        logged: bool,
+    ) -> Result<ResponseBody, (bool, String)> {
+        if log::log_enabled!(log::Level::Trace) {
+            log::trace!(
+                "Running batch of {} statements in keyspace '{keyspace}'.",
+                statements.len()
+            );
+        }
+        let consistency = match self.replication_factor {
+            1 => cdrs_tokio::consistency::Consistency::One,
+            2 => cdrs_tokio::consistency::Consistency::Two,
+            _ => cdrs_tokio::consistency::Consistency::Quorum,
+        };
+        let batch_type = if logged {
+            BatchType::Logged
+        } else {
+            BatchType::Unlogged
+        };
+        let batch = statements
+            .into_iter()
+            .fold(
+                BatchQueryBuilder::new()
+                    .with_batch_type(batch_type)
+                    .with_consistency(consistency)
+                    .with_keyspace(keyspace.to_string()),
+                |batch_query_builder, (query_template, values)| {
+                    batch_query_builder
+                        .add_query(query_template.replace("{{ keyspace }}", keyspace), values)
+                },
+            )
+            .build()
+            .map_err(|e| {
+                (
+                    false,
+                    format!("Failed to build batch in keyspace '{keyspace}': {e:?}"),
+                )
+            })?;
+        self.query_scheduler
+            .run(keyspace, Arc::clone(&self.session).batch(batch))
+            .await
+            .and_then(|envelope| envelope.response_body())
+            .map_err(|e| {
+                (
+                    Self::is_transient_write_error(&e),
+                    format!("Failed to execute batch in keyspace '{keyspace}': {e:?}"),
+                )
+            })
+    }
+
+    /// Return `true` if a write that failed with the error is likely to
+    /// succeed if tried again.
+    fn is_transient_write_error(e: &cdrs_tokio::error::Error) -> bool {
+        match e {
+            // 0x1000 Unavailable, 0x1001 Overloaded, 0x1002 Is_bootstrapping
+            // and 0x1100 Write_timeout from the native protocol spec.
+            cdrs_tokio::error::Error::Server { body, .. } => {
+                matches!(body.ty.to_error_code(), 0x1000 | 0x1001 | 0x1002 | 0x1100)
+            }
+            cdrs_tokio::error::Error::Io(_) | cdrs_tokio::error::Error::Timeout(_) => true,
+            _ => false,
+        }
+    }
+
     /// Execute keyspaced query with value parameters using this session.
     pub async fn query_with_keyspace_and_values(
         &self,
         query_template: &str,
         keyspace: &str,
         values: QueryValues,
+    ) -> Option<ResponseBody> {
+        self.query_with_keyspace_values_and_paging(query_template, keyspace, values, None)
+            .await
+    }
+
+    /// Execute keyspaced query with value parameters using this session.
+    ///
+    /// When `paging` is present, the result will be limited to the page size
+    /// and resumed from the optional paging state returned by the previous
+    /// page.
+    pub async fn query_with_keyspace_values_and_paging(
+        &self,
+        query_template: &str,
+        keyspace: &str,
+        values: QueryValues,
+        paging: Option<(usize, Option<Vec<u8>>)>,
     ) -> Option<ResponseBody> {
         if log::log_enabled!(log::Level::Trace) {
             log::trace!("Running '{query_template}' in keyspace '{keyspace}'.");
@@ -216,12 +393,23 @@ impl CassandraSession {
                 parameters = parameters.with_keyspace(keyspace.to_string());
                 query_template
             };
+            if let Some((page_size, paging_state_opt)) = &paging {
+                parameters =
+                    parameters.with_page_size(i32::try_from(*page_size).unwrap_or(i32::MAX));
+                if let Some(paging_state) = paging_state_opt {
+                    parameters = parameters.with_paging_state(CBytes::new(paging_state.to_owned()));
+                }
+            }
             //let parameters = StatementParamsBuilder::new()
             parameters = parameters
                 //.with_consistency(cdrs_tokio::consistency::Consistency::Quorum)
                 .with_values(values.clone());
-            let result = Arc::clone(&self.session)
-                .query_with_params(query_template, parameters.build())
+            let result = self
+                .query_scheduler
+                .run(
+                    keyspace,
+                    Arc::clone(&self.session).query_with_params(query_template, parameters.build()),
+                )
                 .await;
             if let Err(ref e) = result {
                 match e {
@@ -17,32 +17,58 @@
 
 //! Entities for Cassandra implementation.
 
+mod consumer_definition_entity;
 mod consumer_entity;
 mod delivery_intent_entity;
+mod delivery_prepared_entity;
+mod delivery_slot_entity;
+mod event_annotation_entity;
 mod event_descriptor_entity;
 mod event_entity;
 mod event_id_by_unique_time_entity;
+mod event_topic_entity;
+mod feature_flags_entity;
 mod identity_claim_entity;
 mod integrity_by_level_and_time_entity;
 mod integrity_by_level_and_time_lookup_entity;
 mod integrity_entity;
 mod object_count_entity;
+mod object_count_snapshot_entity;
+mod publish_grant_use_entity;
+mod rejected_event_entity;
 mod resource_grant_entity;
+mod shared_schema_entity;
+mod topic_bulk_ingest_entity;
 mod topic_entity;
+mod topic_index_rebuild_entity;
+mod topic_reply_entity;
 mod unique_time_bucket_by_shelf;
 
+pub use self::consumer_definition_entity::ConsumerDefinitionEntity;
 pub use self::consumer_entity::ConsumerEntity;
 pub use self::delivery_intent_entity::DeliveryIntentEntity;
+pub use self::delivery_prepared_entity::DeliveryPreparedEntity;
+pub use self::delivery_slot_entity::DeliverySlotEntity;
+pub use self::event_annotation_entity::EventAnnotationEntity;
 pub use self::event_descriptor_entity::EventDescriptorEntity;
 pub use self::event_entity::EventEntity;
 pub use self::event_id_by_unique_time_entity::EventIdByUniqueTimeEntity;
+pub use self::event_topic_entity::EventTopicEntity;
+pub use self::feature_flags_entity::FeatureFlagsEntity;
 pub use self::identity_claim_entity::IdentityClaimEntity;
 pub use self::integrity_by_level_and_time_entity::IntegrityByLevelAndTimeEntity;
 pub use self::integrity_by_level_and_time_lookup_entity::IntegrityByLevelAndTimeLookupEntity;
 pub use self::integrity_entity::IntegrityEntity;
 pub use self::object_count_entity::ObjectCountEntity;
+pub use self::object_count_snapshot_entity::ObjectCountSnapshotEntity;
+pub use self::publish_grant_use_entity::PublishGrantUseEntity;
+pub use self::rejected_event_entity::RejectedEventEntity;
 pub use self::resource_grant_entity::ResourceGrantEntity;
+pub use self::shared_schema_entity::SharedSchemaEntity;
+pub use self::topic_bulk_ingest_entity::TopicBulkIngestEntity;
 pub use self::topic_entity::TopicEntity;
+pub use self::topic_index_rebuild_entity::TopicIndexRebuildEntity;
+pub use self::topic_reply_entity::TopicReplyEntity;
 pub use self::unique_time_bucket_by_shelf::UniqueTimeBucketByShelfEntity;
 
 /// Conversion from unsigned to signed primitive.
@@ -0,0 +1,141 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Consumer definition entity and persistence.
+
+use super::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+
+/// Consumer definition entity and persistence.
+///
+/// Holds the serialized declaration of a named consumer of the topic, so the
+/// consumer survives client restarts and redeployments.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct ConsumerDefinitionEntity {
+    /// Name of the consumer definition.
+    name: String,
+    /// Serialized consumer definition.
+    definition: String,
+    /// Time of last update in epoch microseconds.
+    updated_ts: i64,
+}
+
+impl ConsumerDefinitionEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "consumer_definition";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS consumer_definition (
+            name            text,
+            definition      text,
+            updated_ts      bigint,
+            PRIMARY KEY (name)
+        );
+        ";
+
+    /// QCD1. Unconditional upsert
+    const CQL_TEMPLATE_INSERT: &'static str = "
+        INSERT INTO consumer_definition
+        (name, definition, updated_ts)
+        VALUES (?,?,?)
+        ;";
+
+    /// QCD2. Get all entities.
+    const CQL_TEMPLATE_SELECT_ALL: &'static str = "
+        SELECT name, definition, updated_ts
+        FROM consumer_definition
+        ;";
+
+    /// QCD3. Delete/tombstone entity.
+    const CQL_TEMPLATE_DELETE: &'static str = "
+        DELETE
+        FROM consumer_definition
+        WHERE name = ?
+        ;";
+
+    /// Return a new instance.
+    pub fn new(name: &str, definition: &str, updated_ts_micros: u64) -> Self {
+        Self {
+            name: name.to_owned(),
+            definition: definition.to_owned(),
+            updated_ts: i64::from_unsigned(updated_ts_micros),
+        }
+    }
+
+    /// Return the serialized consumer definition.
+    pub fn get_definition(&self) -> &str {
+        &self.definition
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider, topic_id: &str) {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        db.create_table(
+            keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Unconditional upsert
+    pub async fn insert(&self, db: &CassandraProvider, topic_id: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(
+                self.name.to_owned(),
+                self.definition.to_owned(),
+                self.updated_ts
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_else(|| {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!("Failed insert of consumer definition '{}'.", self.name);
+            }
+            false
+        })
+    }
+
+    /// Return all consumer definitions of the topic.
+    pub async fn select_all(db: &CassandraProvider, topic_id: &str) -> Vec<Self> {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_ALL,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+    }
+
+    /// Delete the consumer definition.
+    pub async fn delete(db: &CassandraProvider, topic_id: &str, name: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_DELETE,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(name.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_default()
+    }
+}
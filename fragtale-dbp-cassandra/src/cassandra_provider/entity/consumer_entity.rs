@@ -103,6 +103,12 @@ impl ConsumerEntity {
         WHERE consumer_id=?
         ";
 
+    /// QC7. Get all consumer identifiers
+    const CQL_TEMPLATE_SELECT_IDS: &'static str = "
+        SELECT consumer_id
+        FROM consumer
+        ";
+
     const MICROS_SINCE_EPOCH_20240101: u64 = 1_702_944_000_000_000;
 
     /**
@@ -203,6 +209,19 @@ impl ConsumerEntity {
             .cloned()
     }
 
+    /// Select the identifiers of all consumers.
+    pub async fn select_consumer_ids(db: &CassandraProvider, topic_id: &str) -> Vec<String> {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_IDS,
+            keyspace,
+            cdrs_tokio::query_values!(),
+        )
+        .await
+        .map(CassandraResultMapper::into_string_vec)
+        .unwrap_or_default()
+    }
+
     /// Update last time a client from the consumer group connected.
     pub async fn update_last_update_ts(
         db: &CassandraProvider,
@@ -58,6 +58,10 @@ pub struct DeliveryIntentEntity {
     done: bool,
     /// Optional event descriptor version.
     descriptor_version: Option<i64>,
+    /// Number of retries of the event's delivery before this intent.
+    ///
+    /// Absent for intents written by older versions.
+    retry_count: Option<i32>,
     /// Database time in microseconds of when the `retracted` column was last
     /// written to.
     retracted_write_time: i64,
@@ -77,6 +81,7 @@ impl DeliveryIntentEntity {
             retracted               boolean,
             done                    boolean,
             descriptor_version      bigint,
+            retry_count             int,
             PRIMARY KEY ((consumer_id, unique_time_bucket), unique_time, delivering_instance_id)
         ) WITH CLUSTERING ORDER BY (unique_time ASC);
         ";
@@ -84,13 +89,13 @@ impl DeliveryIntentEntity {
     /// QDI1. Create intent of delivery
     const CQL_TEMPLATE_INSERT: &'static str = "
         INSERT INTO {{ keyspace }}.delivery_intent
-        (consumer_id, unique_time_bucket, unique_time, delivering_instance_id, intent_ts, event_id, retracted, done, descriptor_version)
-        VALUES (?,?,?,?,?,?,?,?,?)
+        (consumer_id, unique_time_bucket, unique_time, delivering_instance_id, intent_ts, event_id, retracted, done, descriptor_version, retry_count)
+        VALUES (?,?,?,?,?,?,?,?,?,?)
         ";
 
     /// QDI2. Find intents by UniqueTime
     const CQL_TEMPLATE_SELECT_BY_UNIQUE_TIME: &'static str = "
-        SELECT consumer_id, unique_time_bucket, unique_time, delivering_instance_id, intent_ts, event_id, retracted, done, descriptor_version, WRITETIME (retracted) AS retracted_write_time
+        SELECT consumer_id, unique_time_bucket, unique_time, delivering_instance_id, intent_ts, event_id, retracted, done, descriptor_version, retry_count, WRITETIME (retracted) AS retracted_write_time
         FROM delivery_intent
         WHERE consumer_id = ? AND unique_time_bucket = ? AND unique_time > ? AND unique_time <= ?
         LIMIT {{ limit }}
@@ -98,7 +103,7 @@ impl DeliveryIntentEntity {
 
     /// QDIx. Find intents by exact UniqueTime
     const CQL_TEMPLATE_SELECT_BY_UNIQUE_TIME_EXACT: &'static str = "
-        SELECT consumer_id, unique_time_bucket, unique_time, delivering_instance_id, intent_ts, event_id, retracted, done, descriptor_version, WRITETIME (retracted) AS retracted_write_time
+        SELECT consumer_id, unique_time_bucket, unique_time, delivering_instance_id, intent_ts, event_id, retracted, done, descriptor_version, retry_count, WRITETIME (retracted) AS retracted_write_time
         FROM delivery_intent
         WHERE consumer_id= ? AND unique_time_bucket = ? AND unique_time = ?
         LIMIT 1024
@@ -112,7 +117,7 @@ impl DeliveryIntentEntity {
 
     const CQL_TEMPLATE_UPDATE_RETRACTED_AND_TS: &'static str = "
         UPDATE delivery_intent
-        SET retracted = ?, intent_ts = ?
+        SET retracted = ?, intent_ts = ?, retry_count = ?
         WHERE consumer_id=? AND unique_time_bucket = ? AND unique_time = ? AND delivering_instance_id = ?
         ";
 
@@ -131,6 +136,13 @@ impl DeliveryIntentEntity {
         WHERE consumer_id = ? AND unique_time_bucket = ? AND unique_time = ? AND delivering_instance_id = ?
         ";
 
+    /// QDE5. Remove intents after a UniqueTime in a bucket (when rewinding a consumer)
+    const CQL_TEMPLATE_DELETE_AFTER_UNIQUE_TIME: &'static str = "
+        DELETE
+        FROM delivery_intent
+        WHERE consumer_id = ? AND unique_time_bucket = ? AND unique_time > ?
+        ";
+
     /// Create a new instance.
     ///
     /// By default, the [DeliveryIntentEntity] is not done nor retracted.
@@ -152,10 +164,18 @@ impl DeliveryIntentEntity {
             retracted: false,
             done: false,
             descriptor_version: descriptor_version.map(i64::from_unsigned),
+            retry_count: None,
             retracted_write_time: 0,
         }
     }
 
+    /// Return this instance with the number of retries of the event's
+    /// delivery before this intent.
+    pub fn with_retry_count(mut self, retry_count: u32) -> Self {
+        self.retry_count = Some(i32::from_unsigned(retry_count));
+        self
+    }
+
     /// Create a new instance that will be delivered by other means.
     ///
     /// The entry will be marked as done from the start to avoid additinal
@@ -181,6 +201,7 @@ impl DeliveryIntentEntity {
             retracted: false,
             done: true,
             descriptor_version: descriptor_version.map(i64::from_unsigned),
+            retry_count: None,
             retracted_write_time: 0,
         }
     }
@@ -232,6 +253,12 @@ impl DeliveryIntentEntity {
         self.descriptor_version.map(u64::from_signed)
     }
 
+    /// Return the number of retries of the event's delivery before this
+    /// intent.
+    pub fn get_retry_count(&self) -> u32 {
+        self.retry_count.map(u32::from_signed).unwrap_or_default()
+    }
+
     /// Database time in microseconds of when the `retracted` column was last
     /// written to.
     pub fn get_retracted_write_time(&self) -> u64 {
@@ -247,6 +274,16 @@ impl DeliveryIntentEntity {
             Self::CQL_TEMPLATE_CREATE_TABLE,
         )
         .await;
+        // Tables created by older versions lack the retry_count column
+        if !db
+            .get_column_names(keyspace, Self::CQL_TABLE_NAME)
+            .await
+            .iter()
+            .any(|column_name| column_name == "retry_count")
+        {
+            db.add_column(keyspace, Self::CQL_TABLE_NAME, "retry_count", "int")
+                .await;
+        }
     }
 
     /// Insert entity (unconditional).
@@ -263,7 +300,8 @@ impl DeliveryIntentEntity {
                 self.event_id.to_owned(),
                 self.retracted,
                 self.done,
-                self.descriptor_version
+                self.descriptor_version,
+                self.retry_count
             ),
         )
         .await
@@ -316,6 +354,39 @@ impl DeliveryIntentEntity {
         .unwrap_or_default()
     }
 
+    /// Return entities from a `bucket` ordered by `unique_time` including
+    /// retracted ones.
+    ///
+    /// The end of the bucket might not be reached if there are more results
+    /// than `max_results` in the bucket.
+    pub async fn select_by_bucket_including_retracted(
+        db: &CassandraProvider,
+        topic_id: &str,
+        consumer_id: &str,
+        bucket: u64,
+        max_results: usize,
+    ) -> Vec<Self> {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        let values = cdrs_tokio::query_values!(
+            consumer_id.to_owned(),
+            i64::from_unsigned(bucket),
+            i64::from_unsigned(UniqueTime::min_encoded_in_bucket(bucket)) - 1,
+            i64::from_unsigned(UniqueTime::max_encoded_in_bucket(bucket))
+        );
+        db.query_with_keyspace_and_values(
+            &Self::CQL_TEMPLATE_SELECT_BY_UNIQUE_TIME.replacen(
+                "{{ limit }}",
+                &max_results.to_string(),
+                1,
+            ),
+            keyspace,
+            values,
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+    }
+
     /// Return all entities for a unique_time.
     ///
     /// Multiple instances might have attempted the delivery for the same event.
@@ -371,7 +442,8 @@ impl DeliveryIntentEntity {
         .unwrap_or(false)
     }
 
-    /// Update retracted and time of intent.
+    /// Update retracted, time of intent and number of previous retries.
+    #[allow(clippy::too_many_arguments)]
     pub async fn update_retracted_and_intent_ts(
         db: &CassandraProvider,
         topic_id: &str,
@@ -380,6 +452,7 @@ impl DeliveryIntentEntity {
         delivering_instance_id: u16,
         retracted: bool,
         intent_ts: u64,
+        retry_count: u32,
     ) -> bool {
         db.query_with_keyspace_and_values(
             Self::CQL_TEMPLATE_UPDATE_RETRACTED_AND_TS,
@@ -387,6 +460,7 @@ impl DeliveryIntentEntity {
             cdrs_tokio::query_values!(
                 retracted,
                 i64::from_unsigned(intent_ts),
+                i32::from_unsigned(retry_count),
                 consumer_id.to_owned(),
                 unique_time.get_bucket_i64(),
                 unique_time.as_encoded_i64(),
@@ -447,4 +521,27 @@ impl DeliveryIntentEntity {
         .map(CassandraResultMapper::into_applied)
         .unwrap_or(false)
     }
+
+    /// Remove all delivery intents of the consumer in the `bucket` that are
+    /// after `unique_time_low_exclusive`.
+    pub async fn delete_after_unique_time(
+        db: &CassandraProvider,
+        topic_id: &str,
+        consumer_id: &str,
+        bucket: u64,
+        unique_time_low_exclusive: UniqueTime,
+    ) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_DELETE_AFTER_UNIQUE_TIME,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(
+                consumer_id.to_owned(),
+                i64::from_unsigned(bucket),
+                unique_time_low_exclusive.as_encoded_i64()
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or(false)
+    }
 }
@@ -0,0 +1,188 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Prepared delivery confirmation entity and persistence.
+
+use super::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+use fragtale_dbp::mb::UniqueTime;
+
+/// Prepared delivery confirmation entity and persistence.
+///
+/// Ties the consumer's external transaction identifier to the delivery of an
+/// event until the confirmation is committed.
+///
+/// Partitioned by the bucket of the event like delivery intents, so
+/// confirmations that are prepared but never committed can't grow a single
+/// partition of the consumer without bounds.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct DeliveryPreparedEntity {
+    /// Consumer identifier.
+    consumer_id: String,
+    /// Bucket of the delivered event.
+    unique_time_bucket: i64,
+    /// Encoded UniqueTime of the delivered event.
+    unique_time: i64,
+    /// The consumer's external transaction identifier.
+    transaction_id: String,
+    /// Time of preparation in epoch microseconds.
+    prepared_ts: i64,
+}
+
+impl DeliveryPreparedEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "delivery_prepared";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS delivery_prepared (
+            consumer_id         text,
+            unique_time_bucket  bigint,
+            unique_time         bigint,
+            transaction_id      text,
+            prepared_ts         bigint,
+            PRIMARY KEY ((consumer_id, unique_time_bucket), unique_time)
+        ) WITH CLUSTERING ORDER BY (unique_time ASC);
+        ";
+
+    /// QDP1. Unconditional insert
+    const CQL_TEMPLATE_INSERT: &'static str = "
+        INSERT INTO delivery_prepared
+        (consumer_id, unique_time_bucket, unique_time, transaction_id, prepared_ts)
+        VALUES (?,?,?,?,?)
+        ;";
+
+    /// QDP2. Get entity by consumer and event.
+    const CQL_TEMPLATE_SELECT_BY_ID: &'static str = "
+        SELECT consumer_id, unique_time_bucket, unique_time, transaction_id, prepared_ts
+        FROM delivery_prepared
+        WHERE consumer_id = ? AND unique_time_bucket = ? AND unique_time = ?
+        ;";
+
+    /// QDP3. Delete/tombstone entity.
+    const CQL_TEMPLATE_DELETE: &'static str = "
+        DELETE
+        FROM delivery_prepared
+        WHERE consumer_id = ? AND unique_time_bucket = ? AND unique_time = ?
+        ;";
+
+    /// Return a new instance.
+    pub fn new(
+        consumer_id: &str,
+        encoded_unique_time: u64,
+        transaction_id: &str,
+        prepared_ts_micros: u64,
+    ) -> Self {
+        Self {
+            consumer_id: consumer_id.to_owned(),
+            unique_time_bucket: UniqueTime::from(encoded_unique_time).get_bucket_i64(),
+            unique_time: i64::from_unsigned(encoded_unique_time),
+            transaction_id: transaction_id.to_owned(),
+            prepared_ts: i64::from_unsigned(prepared_ts_micros),
+        }
+    }
+
+    /// Return the consumer's external transaction identifier.
+    pub fn get_transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider, topic_id: &str) {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        db.create_table(
+            keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Unconditional insert
+    pub async fn insert(&self, db: &CassandraProvider, topic_id: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(
+                self.consumer_id.to_owned(),
+                self.unique_time_bucket,
+                self.unique_time,
+                self.transaction_id.to_owned(),
+                self.prepared_ts
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_else(|| {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!(
+                    "Failed insert of prepared delivery '{}' for '{}'.",
+                    self.unique_time,
+                    self.consumer_id
+                );
+            }
+            false
+        })
+    }
+
+    /// Return the entity for a specific consumer and event if it exists.
+    pub async fn select_by_consumer_id_and_unique_time(
+        db: &CassandraProvider,
+        topic_id: &str,
+        consumer_id: &str,
+        encoded_unique_time: u64,
+    ) -> Option<Self> {
+        let values = cdrs_tokio::query_values!(
+            consumer_id.to_owned(),
+            UniqueTime::from(encoded_unique_time).get_bucket_i64(),
+            i64::from_unsigned(encoded_unique_time)
+        );
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_BY_ID,
+            &db.get_keyspace_from_topic(topic_id),
+            values,
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+        .first()
+        .cloned()
+    }
+
+    /// Delete the entity for a specific consumer and event.
+    pub async fn delete(
+        db: &CassandraProvider,
+        topic_id: &str,
+        consumer_id: &str,
+        encoded_unique_time: u64,
+    ) -> bool {
+        let values = cdrs_tokio::query_values!(
+            consumer_id.to_owned(),
+            UniqueTime::from(encoded_unique_time).get_bucket_i64(),
+            i64::from_unsigned(encoded_unique_time)
+        );
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_DELETE,
+            &db.get_keyspace_from_topic(topic_id),
+            values,
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_default()
+    }
+}
@@ -0,0 +1,201 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Shared delivery slot entity and persistence.
+
+use super::FromSignedOrDefault;
+use super::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+
+/// Shared delivery slot entity and persistence.
+///
+/// Caps the number of unconfirmed deliveries a consumer group may hold across
+/// all instances. Each unconfirmed delivery occupies one slot until it is
+/// confirmed or the slot's TTL expires.
+///
+/// Claiming, renewing and freeing a slot are lightweight transactions (LWT),
+/// so each of them costs a Paxos round of about four round trips to the
+/// replicas of the consumer's partition. Claiming a slot costs one partition
+/// read plus one LWT in the common case and a few LWTs at most when instances
+/// compete for the last free slots, regardless of the number of slots.
+/// Freeing a slot costs one partition read plus one LWT.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct DeliverySlotEntity {
+    /// Consumer identifier.
+    consumer_id: String,
+    /// Slot number.
+    slot: i32,
+    /// Encoded UniqueTime of the event that is being delivered.
+    unique_time: i64,
+}
+
+impl DeliverySlotEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "delivery_slot";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS delivery_slot (
+            consumer_id     text,
+            slot            int,
+            unique_time     bigint,
+            PRIMARY KEY ((consumer_id), slot)
+        ) WITH CLUSTERING ORDER BY (slot ASC);
+        ";
+
+    /// QDS1. Claim a slot for `ttl` seconds.
+    const CQL_TEMPLATE_INSERT: &'static str = "
+        INSERT INTO delivery_slot
+        (consumer_id, slot, unique_time)
+        VALUES (?,?,?)
+        IF NOT EXISTS
+        USING TTL {{ ttl }}
+        ;";
+
+    /// QDS2. Get all claimed slots of a consumer.
+    const CQL_TEMPLATE_SELECT_BY_CONSUMER_ID: &'static str = "
+        SELECT consumer_id, slot, unique_time
+        FROM delivery_slot
+        WHERE consumer_id = ?
+        ;";
+
+    /// QDS3. Free a slot if it is still claimed for the same event.
+    const CQL_TEMPLATE_DELETE: &'static str = "
+        DELETE
+        FROM delivery_slot
+        WHERE consumer_id = ? AND slot = ?
+        IF unique_time = ?
+        ;";
+
+    /// QDS4. Extend the claim of a slot for `ttl` seconds if it is still
+    /// claimed for the same event.
+    const CQL_TEMPLATE_UPDATE_TTL: &'static str = "
+        UPDATE delivery_slot
+        USING TTL {{ ttl }}
+        SET unique_time = ?
+        WHERE consumer_id = ? AND slot = ?
+        IF unique_time = ?
+        ;";
+
+    /// Return a new instance.
+    pub fn new(consumer_id: &str, slot: u32, encoded_unique_time: u64) -> Self {
+        Self {
+            consumer_id: consumer_id.to_owned(),
+            slot: i32::from_unsigned(slot),
+            unique_time: i64::from_unsigned(encoded_unique_time),
+        }
+    }
+
+    /// Return the slot number.
+    pub fn get_slot(&self) -> u32 {
+        u32::from_signed(self.slot)
+    }
+
+    /// Return the encoded UniqueTime of the event that is being delivered.
+    pub fn get_unique_time(&self) -> u64 {
+        u64::from_signed(self.unique_time)
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider, topic_id: &str) {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        db.create_table(
+            keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Insert the entity unless the slot is already claimed.
+    pub async fn insert_if_not_exists(
+        &self,
+        db: &CassandraProvider,
+        topic_id: &str,
+        time_to_live_seconds: u32,
+    ) -> bool {
+        db.query_with_keyspace_and_values(
+            &Self::CQL_TEMPLATE_INSERT.replacen("{{ ttl }}", &time_to_live_seconds.to_string(), 1),
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(self.consumer_id.to_owned(), self.slot, self.unique_time),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_else(|| {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!("Failed insert of {self:?}");
+            }
+            false
+        })
+    }
+
+    /// Extend the claim of the slot unless it has been claimed for another
+    /// event.
+    pub async fn renew(
+        &self,
+        db: &CassandraProvider,
+        topic_id: &str,
+        time_to_live_seconds: u32,
+    ) -> bool {
+        db.query_with_keyspace_and_values(
+            &Self::CQL_TEMPLATE_UPDATE_TTL.replacen(
+                "{{ ttl }}",
+                &time_to_live_seconds.to_string(),
+                1,
+            ),
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(
+                self.unique_time,
+                self.consumer_id.to_owned(),
+                self.slot,
+                self.unique_time
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_default()
+    }
+
+    /// Return all currently claimed slots of a consumer.
+    pub async fn select_by_consumer_id(
+        db: &CassandraProvider,
+        topic_id: &str,
+        consumer_id: &str,
+    ) -> Vec<Self> {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_BY_CONSUMER_ID,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(consumer_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+    }
+
+    /// Free the slot unless it has been claimed for another event.
+    pub async fn delete(&self, db: &CassandraProvider, topic_id: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_DELETE,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(self.consumer_id.to_owned(), self.slot, self.unique_time),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_default()
+    }
+}
@@ -0,0 +1,172 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Event annotation entity and persistence.
+
+use super::FromSignedOrDefault;
+use super::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+use fragtale_dbp::mb::EventAnnotation;
+
+/// Event annotation entity and persistence.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct EventAnnotationEntity {
+    /// The annotated event's document fingerprint.
+    event_id: String,
+    /// Time of annotation in epoch microseconds.
+    annotated_ts: i64,
+    /// Identity of the author in serialized form.
+    author: String,
+    /// Kind of annotation.
+    kind: String,
+    /// Value of the annotation.
+    value: String,
+}
+
+impl From<&EventAnnotation> for EventAnnotationEntity {
+    fn from(value: &EventAnnotation) -> Self {
+        Self {
+            event_id: value.get_event_id().to_owned(),
+            annotated_ts: i64::from_unsigned(value.get_annotated_ts_micros()),
+            author: value.get_author().to_owned(),
+            kind: value.get_kind().to_owned(),
+            value: value.get_value().to_owned(),
+        }
+    }
+}
+
+impl From<EventAnnotationEntity> for EventAnnotation {
+    fn from(value: EventAnnotationEntity) -> Self {
+        EventAnnotation::new(
+            &value.event_id,
+            u64::from_signed(value.annotated_ts),
+            &value.author,
+            &value.kind,
+            &value.value,
+        )
+    }
+}
+
+impl EventAnnotationEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "event_annotation";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS event_annotation (
+            event_id            text,
+            annotated_ts        bigint,
+            author              text,
+            kind                text,
+            value               text,
+            PRIMARY KEY ((event_id), annotated_ts, author)
+        ) WITH CLUSTERING ORDER BY (annotated_ts ASC, author ASC);
+        ";
+
+    /// QEA1. Unconditional insert
+    const CQL_TEMPLATE_INSERT: &'static str = "
+        INSERT INTO event_annotation
+        (event_id, annotated_ts, author, kind, value)
+        VALUES (?,?,?,?,?)
+        ;";
+
+    /// QEA2. Get entities of an event with limit.
+    const CQL_TEMPLATE_SELECT_BY_EVENT_ID: &'static str = "
+        SELECT event_id, annotated_ts, author, kind, value
+        FROM event_annotation
+        WHERE event_id = ?
+        LIMIT {{ limit }}
+        ;";
+
+    /// QEA3. Delete all entities of an event.
+    const CQL_TEMPLATE_DELETE_BY_EVENT_ID: &'static str = "
+        DELETE FROM event_annotation
+        WHERE event_id = ?
+        ;";
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider, topic_id: &str) {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        db.create_table(
+            keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Unconditional insert
+    pub async fn insert(&self, db: &CassandraProvider, topic_id: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(
+                self.event_id.to_owned(),
+                self.annotated_ts,
+                self.author.to_owned(),
+                self.kind.to_owned(),
+                self.value.to_owned()
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_else(|| {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!("Failed insert of annotation of event '{}'.", self.event_id);
+            }
+            false
+        })
+    }
+
+    /// Retrieve annotations of an event up to a max number of results.
+    pub async fn select_by_event_id(
+        db: &CassandraProvider,
+        topic_id: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<Self> {
+        db.query_with_keyspace_and_values(
+            &Self::CQL_TEMPLATE_SELECT_BY_EVENT_ID.replacen(
+                "{{ limit }}",
+                &max_results.to_string(),
+                1,
+            ),
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(event_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+    }
+
+    /// Delete all annotations of an event.
+    pub async fn delete_by_event_id(
+        db: &CassandraProvider,
+        topic_id: &str,
+        event_id: &str,
+    ) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_DELETE_BY_EVENT_ID,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(event_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_default()
+    }
+}
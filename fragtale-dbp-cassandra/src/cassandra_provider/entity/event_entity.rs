@@ -18,6 +18,7 @@
 //! Event entity and persistence.
 
 use super::FromSignedOrDefault;
+use super::FromUnsignedOrDefault;
 use crate::CassandraProvider;
 use crate::CassandraResultMapper;
 use cdrs_tokio::query::QueryValues;
@@ -27,6 +28,7 @@ use fragtale_dbp::mb::TopicEvent;
 use fragtale_dbp::mb::UniqueTime;
 use fragtale_dbp::mb::consumers::EventDeliveryGist;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Event entity and persistence.
 #[derive(
@@ -46,6 +48,13 @@ pub struct EventEntity {
     protection_ref: String,
     /// Unique identifier that clients can propagate through the system.
     correlation_token: String,
+    /// The priority the event was published with.
+    ///
+    /// This is `None` for events persisted before the priority was stored.
+    priority: Option<i32>,
+    /// Deadline in epoch microseconds after which the event should no longer
+    /// be delivered.
+    expires_ts: Option<i64>,
 }
 
 impl From<&TopicEvent> for EventEntity {
@@ -56,6 +65,8 @@ impl From<&TopicEvent> for EventEntity {
             value.get_document(),
             value.get_protection_ref(),
             value.get_correlation_token(),
+            value.get_priority(),
+            value.get_expires_ts(),
         )
     }
 }
@@ -72,6 +83,8 @@ impl EventEntity {
             document            text,
             protection_ref      text,
             correlation_token   text,
+            priority            int,
+            expires_ts          bigint,
             PRIMARY KEY ((event_id), unique_time)
         ) WITH CLUSTERING ORDER BY (unique_time DESC);
         ";
@@ -79,13 +92,13 @@ impl EventEntity {
     /// QE1. Persist new event
     const CQL_TEMPLATE_INSERT: &'static str = "
         INSERT INTO event
-        (event_id, unique_time, document, protection_ref, correlation_token {{ column_names }})
-        VALUES (?,?,?,?,? {{ column_placeholders }})
+        (event_id, unique_time, document, protection_ref, correlation_token, priority, expires_ts {{ column_names }})
+        VALUES (?,?,?,?,?,?,? {{ column_placeholders }})
         ;";
 
     /// QE2. Get full entities by event (document) identifier.
     const CQL_TEMPLATE_SELECT: &'static str = "
-        SELECT event_id, unique_time, document, protection_ref, correlation_token
+        SELECT event_id, unique_time, document, protection_ref, correlation_token, priority, expires_ts
         FROM event
         WHERE event_id=?
         LIMIT {{ limit }}
@@ -93,14 +106,14 @@ impl EventEntity {
 
     /// QE3. Get full entity by event (document) identifier and UniqueTime.
     const CQL_TEMPLATE_SELECT_BY_ID_AND_UNIQUE: &'static str = "
-        SELECT event_id, unique_time, document, protection_ref, correlation_token
+        SELECT event_id, unique_time, document, protection_ref, correlation_token, priority, expires_ts
         FROM event
         WHERE event_id = ? AND unique_time = ?
         ";
 
     /// QE4. Get full entity by correlation token.
     const CQL_TEMPLATE_SELECT_BY_CID: &'static str = "
-        SELECT event_id, unique_time, document, protection_ref, correlation_token
+        SELECT event_id, unique_time, document, protection_ref, correlation_token, priority, expires_ts
         FROM event
         WHERE correlation_token=?
         ";
@@ -113,6 +126,44 @@ impl EventEntity {
         LIMIT {{ limit }}
         ";
 
+    /// QE5b. Get event identifiers by a member of an extracted collection.
+    /// (Columns might vary for each topic.)
+    const CQL_TEMPLATE_SELECT_IDS_BY_COLLECTION_COLUMN: &'static str = "
+        SELECT event_id, unique_time
+        FROM event
+        WHERE {{ column_name }} CONTAINS ?
+        LIMIT {{ limit }}
+        ";
+
+    /// QE6. Set extracted values of an existing event. (Columns might vary for each topic.)
+    const CQL_TEMPLATE_UPDATE_COLUMNS: &'static str = "
+        UPDATE event
+        SET {{ column_assignments }}
+        WHERE event_id = ? AND unique_time = ?
+        ";
+
+    /// QE7. Get event identifiers and UniqueTime by event (document) identifier.
+    const CQL_TEMPLATE_SELECT_UNIQUE_TIMES: &'static str = "
+        SELECT event_id, unique_time
+        FROM event
+        WHERE event_id = ?
+        LIMIT {{ limit }}
+        ";
+
+    /// QE8. Delete event by event (document) identifier and UniqueTime.
+    const CQL_TEMPLATE_DELETE: &'static str = "
+        DELETE FROM event
+        WHERE event_id = ? AND unique_time = ?
+        ";
+
+    /// QE9. Replace the document of an existing event and clear extracted
+    /// values. (Columns might vary for each topic.)
+    const CQL_TEMPLATE_UPDATE_DOCUMENT: &'static str = "
+        UPDATE event
+        SET document = ?, protection_ref = ? {{ column_assignments }}
+        WHERE event_id = ? AND unique_time = ?
+        ";
+
     /// Return a new instance.
     pub fn new(
         event_id: &str,
@@ -120,6 +171,8 @@ impl EventEntity {
         document: &str,
         protection_ref: &str,
         correlation_token: &str,
+        priority: u8,
+        expires_ts: Option<u64>,
     ) -> Self {
         Self {
             event_id: event_id.to_owned(),
@@ -127,6 +180,8 @@ impl EventEntity {
             document: document.to_owned(),
             protection_ref: protection_ref.to_owned(),
             correlation_token: correlation_token.to_owned(),
+            priority: Some(i32::from(priority)),
+            expires_ts: expires_ts.map(i64::from_unsigned),
         }
     }
 
@@ -157,13 +212,29 @@ impl EventEntity {
         &self.correlation_token
     }
 
+    /// Return the priority the event was published with (if known).
+    pub fn get_priority(&self) -> Option<u8> {
+        self.priority
+            .and_then(|priority| u8::try_from(priority).ok())
+    }
+
+    /// Return the deadline in epoch microseconds after which the event should
+    /// no longer be delivered (if any).
+    pub fn get_expires_ts(&self) -> Option<u64> {
+        self.expires_ts.map(u64::from_signed)
+    }
+
     /// Consume this instance into parts for delivery.
     pub fn into_event_delivery_gist(self) -> EventDeliveryGist {
+        let priority = self.get_priority();
+        let expires_ts = self.get_expires_ts();
         EventDeliveryGist::new(
             UniqueTime::from(u64::from_signed(self.unique_time)),
-            self.document,
+            Arc::from(self.document),
             self.protection_ref,
             self.correlation_token,
+            priority,
+            expires_ts,
         )
     }
 
@@ -176,6 +247,22 @@ impl EventEntity {
             Self::CQL_TEMPLATE_CREATE_TABLE,
         )
         .await;
+        // Events persisted by older versions lack the priority and expires_ts columns
+        let column_names = db.get_column_names(keyspace, Self::CQL_TABLE_NAME).await;
+        if !column_names
+            .iter()
+            .any(|column_name| column_name == "priority")
+        {
+            db.add_column(keyspace, Self::CQL_TABLE_NAME, "priority", "int")
+                .await;
+        }
+        if !column_names
+            .iter()
+            .any(|column_name| column_name == "expires_ts")
+        {
+            db.add_column(keyspace, Self::CQL_TABLE_NAME, "expires_ts", "bigint")
+                .await;
+        }
         db.add_index(
             keyspace,
             Self::CQL_TABLE_NAME,
@@ -192,26 +279,38 @@ impl EventEntity {
         topic_id: &str,
         additional_columns: HashMap<String, ExtractedValue>,
     ) -> bool {
+        let (query_template, query_values) = self.insert_statement(additional_columns);
+        db.query_with_keyspace_and_values(
+            &query_template,
+            &db.get_keyspace_from_topic(topic_id),
+            query_values,
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or(false)
+    }
+
+    /// Return the statement and values that inserts the entity
+    /// (unconditional), for use in a batch.
+    pub fn insert_statement(
+        &self,
+        additional_columns: HashMap<String, ExtractedValue>,
+    ) -> (String, QueryValues) {
         let mut simple_values = vec![
             Value::from(self.event_id.to_owned()),
             Value::from(self.unique_time),
             Value::from(self.document.to_owned()),
             Value::from(self.protection_ref.to_owned()),
             Value::from(self.correlation_token.to_owned()),
+            Value::from(self.priority),
+            Value::from(self.expires_ts),
         ];
         let mut column_names = String::new();
         let mut column_placeholders = String::new();
         for (key, value) in additional_columns {
             column_names = column_names + ", " + Self::EXTRACTED_COLUMN_PREFIX + &key;
             column_placeholders += ",?";
-            match value {
-                ExtractedValue::Text(value) => {
-                    simple_values.push(cdrs_tokio::types::prelude::Value::from(value));
-                }
-                ExtractedValue::BigInt(value) => {
-                    simple_values.push(cdrs_tokio::types::prelude::Value::from(value));
-                }
-            }
+            simple_values.push(Self::extracted_value_to_value(value));
         }
         let query_values = QueryValues::SimpleValues(simple_values);
         if log::log_enabled!(log::Level::Trace) {
@@ -220,10 +319,46 @@ impl EventEntity {
         let query_template = Self::CQL_TEMPLATE_INSERT
             .replace("{{ column_names }}", &column_names)
             .replace("{{ column_placeholders }}", &column_placeholders);
+        (query_template, query_values)
+    }
+
+    /// Return the extracted value as a column value.
+    ///
+    /// Collections are serialized the same way for both `set` and `list`
+    /// columns.
+    fn extracted_value_to_value(value: ExtractedValue) -> Value {
+        match value {
+            ExtractedValue::Text(value) => Value::from(value),
+            ExtractedValue::BigInt(value) => Value::from(value),
+            ExtractedValue::TextCollection(values) => Value::from(values),
+            ExtractedValue::BigIntCollection(values) => Value::from(values),
+        }
+    }
+
+    /// Set the extracted values of an existing event.
+    pub async fn update_additional_columns(
+        db: &CassandraProvider,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        additional_columns: HashMap<String, ExtractedValue>,
+    ) -> bool {
+        if additional_columns.is_empty() {
+            return true;
+        }
+        let mut column_assignments = vec![];
+        let mut simple_values = vec![];
+        for (key, value) in additional_columns {
+            column_assignments.push(Self::EXTRACTED_COLUMN_PREFIX.to_owned() + &key + " = ?");
+            simple_values.push(Self::extracted_value_to_value(value));
+        }
+        simple_values.push(Value::from(event_id.to_owned()));
+        simple_values.push(Value::from(unique_time.as_encoded_i64()));
         db.query_with_keyspace_and_values(
-            &query_template,
+            &Self::CQL_TEMPLATE_UPDATE_COLUMNS
+                .replace("{{ column_assignments }}", &column_assignments.join(", ")),
             &db.get_keyspace_from_topic(topic_id),
-            query_values,
+            QueryValues::SimpleValues(simple_values),
         )
         .await
         .map(CassandraResultMapper::into_applied)
@@ -296,16 +431,38 @@ impl EventEntity {
     ///
     /// The results are sorted by Cassandra token order which is stable, but
     /// the order depends on how the Cassandra cluster is setup.
+    ///
+    /// Columns of a collection type match when any member equals the index
+    /// key.
     pub async fn select_ids_and_unique_time_by_index(
         db: &CassandraProvider,
         topic_id: &str,
         index_column: &str,
+        index_column_type: &str,
         index_key: &str,
         max_results: usize,
     ) -> Vec<(String, u64)> {
         let keyspace = &db.get_keyspace_from_topic(topic_id);
-        let values = cdrs_tokio::query_values!(index_key.to_owned());
-        let query_template = Self::CQL_TEMPLATE_SELECT_IDS_BY_COLUMN
+        let (query_template, member_type) = match index_column_type
+            .strip_prefix("set<")
+            .or_else(|| index_column_type.strip_prefix("list<"))
+            .and_then(|member_type| member_type.strip_suffix('>'))
+        {
+            Some(member_type) => (
+                Self::CQL_TEMPLATE_SELECT_IDS_BY_COLLECTION_COLUMN,
+                member_type,
+            ),
+            None => (Self::CQL_TEMPLATE_SELECT_IDS_BY_COLUMN, index_column_type),
+        };
+        let values = if member_type == "bigint" {
+            let Ok(index_key) = index_key.parse::<i64>() else {
+                return vec![];
+            };
+            cdrs_tokio::query_values!(index_key)
+        } else {
+            cdrs_tokio::query_values!(index_key.to_owned())
+        };
+        let query_template = query_template
             .replacen("{{ column_name }}", index_column, 1)
             .replacen("{{ limit }}", &max_results.to_string(), 1);
         db.query_with_keyspace_and_values(&query_template, keyspace, values)
@@ -313,4 +470,85 @@ impl EventEntity {
             .map(CassandraResultMapper::into_string_u64_tuplet_vec)
             .unwrap_or_default()
     }
+
+    /// Return the UniqueTime of all events for a event document identifier.
+    ///
+    /// The largest UniqueTime (newest) is returned first.
+    pub async fn select_unique_times_by_event_id(
+        db: &CassandraProvider,
+        topic_id: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<UniqueTime> {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        db.query_with_keyspace_and_values(
+            &Self::CQL_TEMPLATE_SELECT_UNIQUE_TIMES.replacen(
+                "{{ limit }}",
+                &max_results.to_string(),
+                1,
+            ),
+            keyspace,
+            cdrs_tokio::query_values!(event_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_string_u64_tuplet_vec)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(_event_id, unique_time)| UniqueTime::from(unique_time))
+        .collect()
+    }
+
+    /// Delete the event (unconditional).
+    ///
+    /// Extracted values and the correlation token are stored in the same row,
+    /// so the secondary indices are cleared as well.
+    pub async fn delete(
+        db: &CassandraProvider,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+    ) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_DELETE,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(event_id.to_owned(), unique_time.as_encoded_i64()),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_default()
+    }
+
+    /// Replace the document and protection reference of an existing event and
+    /// clear all extracted values.
+    pub async fn update_document(
+        db: &CassandraProvider,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        document: &str,
+        protection_ref: &str,
+    ) -> bool {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        let column_assignments = db
+            .get_column_names(keyspace, Self::CQL_TABLE_NAME)
+            .await
+            .into_iter()
+            .filter(|column_name| column_name.starts_with(Self::EXTRACTED_COLUMN_PREFIX))
+            .map(|column_name| ", ".to_owned() + &column_name + " = null")
+            .collect::<String>();
+        db.query_with_keyspace_and_values(
+            &Self::CQL_TEMPLATE_UPDATE_DOCUMENT
+                .replace("{{ column_assignments }}", &column_assignments),
+            keyspace,
+            cdrs_tokio::query_values!(
+                document.to_owned(),
+                protection_ref.to_owned(),
+                event_id.to_owned(),
+                unique_time.as_encoded_i64()
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_default()
+    }
 }
@@ -21,6 +21,7 @@ use super::FromSignedOrDefault;
 use super::FromUnsignedOrDefault;
 use crate::CassandraProvider;
 use crate::CassandraResultMapper;
+use cdrs_tokio::query::QueryValues;
 use fragtale_dbp::mb::TopicEvent;
 use fragtale_dbp::mb::UniqueTime;
 
@@ -39,6 +40,12 @@ pub struct EventIdByUniqueTimeEntity {
     descriptor_version: Option<i64>,
     /// Unique identifier that clients can propagate through the system
     correlation_token: String,
+    /// Deadline in epoch microseconds after which the event should no longer
+    /// be delivered.
+    expires_ts: Option<i64>,
+    /// Time in epoch microseconds before which the event should not be
+    /// delivered.
+    deliver_after_ts: Option<i64>,
 }
 
 impl From<&TopicEvent> for EventIdByUniqueTimeEntity {
@@ -48,6 +55,8 @@ impl From<&TopicEvent> for EventIdByUniqueTimeEntity {
             value.get_event_id(),
             &value.get_descriptor_version(),
             value.get_correlation_token(),
+            value.get_expires_ts(),
+            value.get_deliver_after_ts(),
         )
     }
 }
@@ -62,6 +71,8 @@ impl EventIdByUniqueTimeEntity {
             event_id            text,
             descriptor_version  bigint,
             correlation_token   text,
+            expires_ts          bigint,
+            deliver_after_ts    bigint,
             PRIMARY KEY ((unique_time_bucket), unique_time)
         ) WITH CLUSTERING ORDER BY (unique_time ASC);
         ";
@@ -69,16 +80,35 @@ impl EventIdByUniqueTimeEntity {
     /// QEBU1. Insert event by unique time lookup entity.
     const CQL_TEMPLATE_INSERT: &'static str = "
         INSERT INTO {{ keyspace }}.event_id_by_unique_time
-        (unique_time_bucket, unique_time, event_id, descriptor_version, correlation_token)
-        VALUES (?,?,?,?,?)
+        (unique_time_bucket, unique_time, event_id, descriptor_version, correlation_token, expires_ts, deliver_after_ts)
+        VALUES (?,?,?,?,?,?,?)
         ;";
 
     /// QEBU2. Get event identifiers (full entity) in UniqueTime range.
     const CQL_TEMPLATE_SELECT_BY_UNIQUE_TIME: &'static str = "
-        SELECT unique_time_bucket, unique_time, event_id, descriptor_version, correlation_token
+        SELECT unique_time_bucket, unique_time, event_id, descriptor_version, correlation_token, expires_ts, deliver_after_ts
         FROM {{ keyspace }}.event_id_by_unique_time
         WHERE unique_time_bucket = ? AND unique_time > ?
-        LIMIT {{ limit }}
+        ";
+
+    /// QEBU3. Count events in a bucket.
+    const CQL_TEMPLATE_COUNT_BY_BUCKET: &'static str = "
+        SELECT COUNT(*)
+        FROM {{ keyspace }}.event_id_by_unique_time
+        WHERE unique_time_bucket = ?
+        ";
+
+    /// QEBU4. Get event identifier (full entity) by UniqueTime.
+    const CQL_TEMPLATE_SELECT_BY_EXACT_UNIQUE_TIME: &'static str = "
+        SELECT unique_time_bucket, unique_time, event_id, descriptor_version, correlation_token, expires_ts, deliver_after_ts
+        FROM {{ keyspace }}.event_id_by_unique_time
+        WHERE unique_time_bucket = ? AND unique_time = ?
+        ";
+
+    /// QEBU5. Delete event identifier lookup by UniqueTime.
+    const CQL_TEMPLATE_DELETE_BY_EXACT_UNIQUE_TIME: &'static str = "
+        DELETE FROM {{ keyspace }}.event_id_by_unique_time
+        WHERE unique_time_bucket = ? AND unique_time = ?
         ";
 
     //// Return a new instance.
@@ -87,6 +117,8 @@ impl EventIdByUniqueTimeEntity {
         event_id: &str,
         descriptor_version: &Option<u64>,
         correlation_token: &str,
+        expires_ts: Option<u64>,
+        deliver_after_ts: Option<u64>,
     ) -> Self {
         Self {
             unique_time_bucket: unique_time.get_bucket_i64(),
@@ -94,6 +126,8 @@ impl EventIdByUniqueTimeEntity {
             event_id: event_id.to_owned(),
             descriptor_version: descriptor_version.map(i64::from_unsigned),
             correlation_token: correlation_token.to_owned(),
+            expires_ts: expires_ts.map(i64::from_unsigned),
+            deliver_after_ts: deliver_after_ts.map(i64::from_unsigned),
         }
     }
 
@@ -117,6 +151,19 @@ impl EventIdByUniqueTimeEntity {
         &self.correlation_token
     }
 
+    /// Return the deadline in epoch microseconds after which the event should
+    /// no longer be delivered (if any).
+    pub fn get_expires_ts(&self) -> Option<u64> {
+        self.expires_ts.map(u64::from_signed)
+    }
+
+    /// Return `true` if the event may not be delivered yet.
+    pub fn is_delivery_deferred(&self, now_micros: u64) -> bool {
+        self.deliver_after_ts
+            .map(u64::from_signed)
+            .is_some_and(|deliver_after_ts| now_micros < deliver_after_ts)
+    }
+
     /// Create entity table and indices.
     pub async fn create_table_and_indices(db: &CassandraProvider, topic_id: &str) {
         let keyspace = &db.get_keyspace_from_topic(topic_id);
@@ -126,49 +173,130 @@ impl EventIdByUniqueTimeEntity {
             Self::CQL_TEMPLATE_CREATE_TABLE,
         )
         .await;
+        // Tables created by older versions lack the expires_ts and deliver_after_ts columns
+        let column_names = db.get_column_names(keyspace, Self::CQL_TABLE_NAME).await;
+        if !column_names
+            .iter()
+            .any(|column_name| column_name == "expires_ts")
+        {
+            db.add_column(keyspace, Self::CQL_TABLE_NAME, "expires_ts", "bigint")
+                .await;
+        }
+        if !column_names
+            .iter()
+            .any(|column_name| column_name == "deliver_after_ts")
+        {
+            db.add_column(keyspace, Self::CQL_TABLE_NAME, "deliver_after_ts", "bigint")
+                .await;
+        }
     }
 
     /// Insert entity (uncondictional).
     pub async fn insert(&self, db: &CassandraProvider, topic_id: &str) -> bool {
+        let (query_template, query_values) = self.insert_statement();
         db.query_with_keyspace_and_values(
-            Self::CQL_TEMPLATE_INSERT,
+            &query_template,
             &db.get_keyspace_from_topic(topic_id),
+            query_values,
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or(false)
+    }
+
+    /// Return the statement and values that inserts the entity
+    /// (unconditional), for use in a batch.
+    pub fn insert_statement(&self) -> (String, QueryValues) {
+        (
+            Self::CQL_TEMPLATE_INSERT.to_owned(),
             cdrs_tokio::query_values!(
                 self.unique_time_bucket,
                 self.unique_time,
                 self.event_id.to_owned(),
                 self.descriptor_version,
-                self.correlation_token.to_owned()
+                self.correlation_token.to_owned(),
+                self.expires_ts,
+                self.deliver_after_ts
             ),
         )
+    }
+
+    /// Select the entity of an exact UniqueTime.
+    pub async fn select_by_exact_unique_time(
+        db: &CassandraProvider,
+        topic_id: &str,
+        unique_time: UniqueTime,
+    ) -> Option<Self> {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        let values =
+            cdrs_tokio::query_values!(unique_time.get_bucket_i64(), unique_time.as_encoded_i64());
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_BY_EXACT_UNIQUE_TIME,
+            keyspace,
+            values,
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+        .into_iter()
+        .next()
+    }
+
+    /// Delete the entity of an exact UniqueTime.
+    pub async fn delete_by_exact_unique_time(
+        db: &CassandraProvider,
+        topic_id: &str,
+        unique_time: UniqueTime,
+    ) -> bool {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        let values =
+            cdrs_tokio::query_values!(unique_time.get_bucket_i64(), unique_time.as_encoded_i64());
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_DELETE_BY_EXACT_UNIQUE_TIME,
+            keyspace,
+            values,
+        )
         .await
         .map(CassandraResultMapper::into_applied)
-        .unwrap_or(false)
+        .unwrap_or_default()
     }
 
-    /// Select all entities with a encoded UniqueTime greater than
+    /// Select a page of entities with a encoded UniqueTime greater than
     /// `unique_time_low_exclusive`.
+    ///
+    /// Continue the query from where the previous page ended by passing the
+    /// returned paging state. A missing paging state in the result means that
+    /// there are no more results.
     pub async fn select_by_unique_time(
         db: &CassandraProvider,
         topic_id: &str,
         bucket: u64,
         unique_time_low_exclusive: u64,
-        max_results: usize,
-    ) -> Vec<Self> {
+        page_size: usize,
+        paging_state: Option<Vec<u8>>,
+    ) -> (Vec<Self>, Option<Vec<u8>>) {
         let unique_time_low_exclusive = i64::from_unsigned(unique_time_low_exclusive);
         let keyspace = &db.get_keyspace_from_topic(topic_id);
         let values = cdrs_tokio::query_values!(bucket, unique_time_low_exclusive);
-        db.query_with_keyspace_and_values(
-            &Self::CQL_TEMPLATE_SELECT_BY_UNIQUE_TIME.replacen(
-                "{{ limit }}",
-                &max_results.to_string(),
-                1,
-            ),
+        db.query_with_keyspace_values_and_paging(
+            Self::CQL_TEMPLATE_SELECT_BY_UNIQUE_TIME,
             keyspace,
             values,
+            page_size,
+            paging_state,
         )
         .await
-        .map(CassandraResultMapper::into_entities)
+        .map(CassandraResultMapper::into_entities_and_paging_state)
         .unwrap_or_default()
     }
+
+    /// Count the entities in a bucket.
+    pub async fn count_by_bucket(db: &CassandraProvider, topic_id: &str, bucket: u64) -> u64 {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        let values = cdrs_tokio::query_values!(i64::from_unsigned(bucket));
+        db.query_with_keyspace_and_values(Self::CQL_TEMPLATE_COUNT_BY_BUCKET, keyspace, values)
+            .await
+            .map(CassandraResultMapper::into_count)
+            .unwrap_or_default()
+    }
 }
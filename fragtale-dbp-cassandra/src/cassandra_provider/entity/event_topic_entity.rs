@@ -0,0 +1,157 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Global event identifier to topic lookup entity and persistence.
+
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+use fragtale_dbp::mb::UniqueTime;
+
+/// Global event identifier to topic lookup entity and persistence.
+///
+/// Tracks which topics contain an event with a specific event identifier, so
+/// an event can be located without querying every topic.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct EventTopicEntity {
+    /// The event document fingerprint.
+    event_id: String,
+    /// Topic identifier.
+    topic_id: String,
+    /// Latest unique time of the event in the topic.
+    unique_time: i64,
+}
+
+impl EventTopicEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "event_topic";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS event_topic (
+            event_id        text,
+            topic_id        text,
+            unique_time     bigint,
+            PRIMARY KEY ((event_id), topic_id)
+        );
+        ";
+
+    /// QET1. Unconditional insert
+    const CQL_TEMPLATE_INSERT: &'static str = "
+        INSERT INTO event_topic
+        (event_id, topic_id, unique_time)
+        VALUES (?,?,?)
+        ;";
+
+    /// QET2. Get entities by event identifier.
+    const CQL_TEMPLATE_SELECT_BY_ID: &'static str = "
+        SELECT event_id, topic_id, unique_time
+        FROM event_topic
+        WHERE event_id = ?
+        LIMIT {{ limit }}
+        ;";
+
+    /// QET3. Delete entity by event identifier and topic identifier.
+    const CQL_TEMPLATE_DELETE: &'static str = "
+        DELETE FROM event_topic
+        WHERE event_id = ? AND topic_id = ?
+        ;";
+
+    /// Return a new instance.
+    pub fn new(event_id: &str, topic_id: &str, unique_time: UniqueTime) -> Self {
+        Self {
+            event_id: event_id.to_owned(),
+            topic_id: topic_id.to_owned(),
+            unique_time: unique_time.as_encoded_i64(),
+        }
+    }
+
+    /// Return the topic identifier.
+    pub fn get_topic_id(&self) -> &str {
+        &self.topic_id
+    }
+
+    /// Return the latest unique time of the event in the topic.
+    pub fn get_unique_time(&self) -> UniqueTime {
+        UniqueTime::from(self.unique_time)
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider) {
+        db.create_table(
+            &db.app_keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Unconditional insert
+    pub async fn insert(&self, db: &CassandraProvider, keyspace: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT,
+            keyspace,
+            cdrs_tokio::query_values!(
+                self.event_id.to_owned(),
+                self.topic_id.to_owned(),
+                self.unique_time
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_else(|| {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!("Failed insert of {self:?}");
+            }
+            false
+        })
+    }
+
+    /// Return the entities for a specific event identifier ordered by topic
+    /// identifier.
+    pub async fn select_by_event_id(
+        db: &CassandraProvider,
+        keyspace: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<Self> {
+        db.query_with_keyspace_and_values(
+            &Self::CQL_TEMPLATE_SELECT_BY_ID.replacen("{{ limit }}", &max_results.to_string(), 1),
+            keyspace,
+            cdrs_tokio::query_values!(event_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+    }
+
+    /// Delete the entity of a specific event identifier and topic.
+    pub async fn delete(
+        db: &CassandraProvider,
+        keyspace: &str,
+        event_id: &str,
+        topic_id: &str,
+    ) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_DELETE,
+            keyspace,
+            cdrs_tokio::query_values!(event_id.to_owned(), topic_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_default()
+    }
+}
@@ -0,0 +1,142 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Cluster wide feature flags entity and persistence.
+
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+
+/// Cluster wide feature flags entity and persistence.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct FeatureFlagsEntity {
+    /// Identifier of the set of feature flags.
+    flags_id: String,
+    /// The feature flags in serialized form.
+    feature_flags: String,
+}
+
+impl FeatureFlagsEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "feature_flags";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS feature_flags (
+            flags_id        text,
+            feature_flags   text,
+            PRIMARY KEY ((flags_id))
+        );
+        ";
+
+    /// QFF1. Get feature flags by identifier.
+    const CQL_TEMPLATE_SELECT_BY_ID: &'static str = "
+        SELECT flags_id, feature_flags
+        FROM feature_flags
+        WHERE flags_id = ?
+        ;";
+
+    /// QFF2. Conditional insert.
+    const CQL_TEMPLATE_INSERT_IF_NOT_EXISTS: &'static str = "
+        INSERT INTO feature_flags
+        (flags_id, feature_flags)
+        VALUES (?,?)
+        IF NOT EXISTS
+        ;";
+
+    /// QFF3. Conditional replace of unchanged feature flags.
+    const CQL_TEMPLATE_UPDATE_IF_UNCHANGED: &'static str = "
+        UPDATE feature_flags
+        SET feature_flags = ?
+        WHERE flags_id = ?
+        IF feature_flags = ?
+        ;";
+
+    /// Return a new instance.
+    pub fn new(flags_id: &str, feature_flags: &str) -> Self {
+        Self {
+            flags_id: flags_id.to_owned(),
+            feature_flags: feature_flags.to_owned(),
+        }
+    }
+
+    /// Return the feature flags in serialized form.
+    pub fn get_feature_flags(&self) -> &str {
+        &self.feature_flags
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider) {
+        db.create_table(
+            &db.app_keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Return the feature flags by identifier if they exist.
+    pub async fn select_by_id(
+        db: &CassandraProvider,
+        keyspace: &str,
+        flags_id: &str,
+    ) -> Option<Self> {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_BY_ID,
+            keyspace,
+            cdrs_tokio::query_values!(flags_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+        .first()
+        .cloned()
+    }
+
+    /// Conditional insert.
+    pub async fn insert_if_not_exists(&self, db: &CassandraProvider, keyspace: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT_IF_NOT_EXISTS,
+            keyspace,
+            cdrs_tokio::query_values!(self.flags_id.to_owned(), self.feature_flags.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or(false)
+    }
+
+    /// Conditional update if the persisted feature flags are still
+    /// `expected`.
+    pub async fn update_if_unchanged(
+        &self,
+        db: &CassandraProvider,
+        keyspace: &str,
+        expected: &str,
+    ) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_UPDATE_IF_UNCHANGED,
+            keyspace,
+            cdrs_tokio::query_values!(
+                self.feature_flags.to_owned(),
+                self.flags_id.to_owned(),
+                expected.to_owned()
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or(false)
+    }
+}
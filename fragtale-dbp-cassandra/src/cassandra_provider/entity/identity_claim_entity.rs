@@ -94,15 +94,43 @@ impl IdentityClaimEntity {
     /// partition.
     const ID_CLAIM_TYPE_INSTANCE: &'static str = "_instance";
 
+    /// Prefix of the type of candidate registrations for a role.
+    ///
+    /// Each role is kept in a separate partition of claimed instance ids.
+    const ID_CLAIM_TYPE_ROLE_PREFIX: &'static str = "_role:";
+
     /// Return a new instance.
     pub fn new(identity_claim: u16, first_claim_ts_micros: u64) -> Self {
+        Self::with_type(
+            Self::ID_CLAIM_TYPE_INSTANCE,
+            identity_claim,
+            first_claim_ts_micros,
+        )
+    }
+
+    /// Return a new candidate registration for the `role`.
+    pub fn new_role(role: &str, identity_claim: u16, first_claim_ts_micros: u64) -> Self {
+        Self::with_type(
+            &Self::role_identity_type(role),
+            identity_claim,
+            first_claim_ts_micros,
+        )
+    }
+
+    fn with_type(identity_type: &str, identity_claim: u16, first_claim_ts_micros: u64) -> Self {
         Self {
-            identity_type: Self::ID_CLAIM_TYPE_INSTANCE.to_owned(),
+            identity_type: identity_type.to_owned(),
             identity_claim: i16::from_unsigned(identity_claim),
             first_claim_ts: i64::from_unsigned(first_claim_ts_micros),
         }
     }
 
+    /// Return the identity type used for candidate registrations of the
+    /// `role`.
+    fn role_identity_type(role: &str) -> String {
+        Self::ID_CLAIM_TYPE_ROLE_PREFIX.to_owned() + role
+    }
+
     /// Get identity claim.
     pub fn get_identity_claim(&self) -> u16 {
         u16::from_signed(self.identity_claim)
@@ -181,10 +209,33 @@ impl IdentityClaimEntity {
 
     /// Delete the entity.
     pub async fn delete(db: &CassandraProvider, keyspace: &str, identity_claim: u16) -> bool {
-        let values = cdrs_tokio::query_values!(
-            Self::ID_CLAIM_TYPE_INSTANCE.to_owned(),
-            i16::from_unsigned(identity_claim)
-        );
+        Self::delete_by_type(db, keyspace, Self::ID_CLAIM_TYPE_INSTANCE, identity_claim).await
+    }
+
+    /// Delete the candidate registration for the `role`.
+    pub async fn delete_role(
+        db: &CassandraProvider,
+        keyspace: &str,
+        role: &str,
+        identity_claim: u16,
+    ) -> bool {
+        Self::delete_by_type(
+            db,
+            keyspace,
+            &Self::role_identity_type(role),
+            identity_claim,
+        )
+        .await
+    }
+
+    async fn delete_by_type(
+        db: &CassandraProvider,
+        keyspace: &str,
+        identity_type: &str,
+        identity_claim: u16,
+    ) -> bool {
+        let values =
+            cdrs_tokio::query_values!(identity_type.to_owned(), i16::from_unsigned(identity_claim));
         db.query_with_keyspace_and_values(Self::CQL_TEMPLATE_DELETE, keyspace, values)
             .await
             .map(CassandraResultMapper::into_applied)
@@ -202,10 +253,33 @@ impl IdentityClaimEntity {
         keyspace: &str,
         identity_claim: u16,
     ) -> Option<Self> {
-        let values = cdrs_tokio::query_values!(
-            Self::ID_CLAIM_TYPE_INSTANCE.to_owned(),
-            i16::from_unsigned(identity_claim)
-        );
+        Self::select_by_type(db, keyspace, Self::ID_CLAIM_TYPE_INSTANCE, identity_claim).await
+    }
+
+    /// Return the candidate registration for the `role` if it exists.
+    pub async fn select_role(
+        db: &CassandraProvider,
+        keyspace: &str,
+        role: &str,
+        identity_claim: u16,
+    ) -> Option<Self> {
+        Self::select_by_type(
+            db,
+            keyspace,
+            &Self::role_identity_type(role),
+            identity_claim,
+        )
+        .await
+    }
+
+    async fn select_by_type(
+        db: &CassandraProvider,
+        keyspace: &str,
+        identity_type: &str,
+        identity_claim: u16,
+    ) -> Option<Self> {
+        let values =
+            cdrs_tokio::query_values!(identity_type.to_owned(), i16::from_unsigned(identity_claim));
         db.query_with_keyspace_and_values(Self::CQL_TEMPLATE_SELECT, keyspace, values)
             .await
             .map(CassandraResultMapper::into_entities)
@@ -231,7 +305,20 @@ impl IdentityClaimEntity {
     /// The TTL set on all claims will ensure that old and crashed nodes will
     /// stop showing up after TTL seconds.
     pub async fn select_all(db: &CassandraProvider, keyspace: &str) -> Vec<Self> {
-        let values = cdrs_tokio::query_values!(Self::ID_CLAIM_TYPE_INSTANCE.to_owned());
+        Self::select_all_by_type(db, keyspace, Self::ID_CLAIM_TYPE_INSTANCE).await
+    }
+
+    /// Return all candidate registrations for the `role`.
+    pub async fn select_all_role(db: &CassandraProvider, keyspace: &str, role: &str) -> Vec<Self> {
+        Self::select_all_by_type(db, keyspace, &Self::role_identity_type(role)).await
+    }
+
+    async fn select_all_by_type(
+        db: &CassandraProvider,
+        keyspace: &str,
+        identity_type: &str,
+    ) -> Vec<Self> {
+        let values = cdrs_tokio::query_values!(identity_type.to_owned());
         db.query_with_keyspace_and_values(Self::CQL_TEMPLATE_SELECT_ALL_CLAIMS, keyspace, values)
             .await
             .map(CassandraResultMapper::into_entities)
@@ -0,0 +1,172 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Object count snapshot entity and persistence
+
+use super::FromSignedOrDefault;
+use super::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+use fragtale_dbp::mb::ObjectCountType;
+
+/**
+Snapshot of the local object counts of an instance.
+
+Unlike [super::ObjectCountEntity] this is kept without TTL, so an instance that
+claims the same instance identifier after a restart can continue counting from
+where the previous holder of the identifier left off.
+
+All snapshots of a topic share a single partition, since there is only one row
+per instance and type of counted object.
+ */
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct ObjectCountSnapshotEntity {
+    /// Topic identifier.
+    topic_id: String,
+    /// Instance identifier the count is for.
+    instance_id: i16,
+    /// The type of counted object.
+    object_type: String,
+    /// The count.
+    object_count: i64,
+    /// Time of the snapshot in epoch microseconds.
+    snapshot_ts: i64,
+}
+
+impl ObjectCountSnapshotEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "object_count_snapshot";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS object_count_snapshot (
+            topic_id        text,
+            instance_id     smallint,
+            object_type     text,
+            object_count    bigint,
+            snapshot_ts     bigint,
+            PRIMARY KEY ((topic_id), instance_id, object_type)
+        ) WITH CLUSTERING ORDER BY (instance_id ASC, object_type ASC)
+        ";
+
+    /// QOCS1: Upsert object count snapshot
+    const CQL_TEMPLATE_INSERT: &'static str = "
+        INSERT INTO object_count_snapshot
+        (topic_id, instance_id, object_type, object_count, snapshot_ts)
+        VALUES (?,?,?,?,?)
+        ";
+
+    /// QOCS2: Get all snapshots of an instance
+    const CQL_TEMPLATE_SELECT_BY_INSTANCE_ID: &'static str = "
+        SELECT topic_id, instance_id, object_type, object_count, snapshot_ts
+        FROM object_count_snapshot
+        WHERE topic_id = ? AND instance_id = ?
+        ";
+
+    /// QOCS3: Delete the snapshots of all instances
+    const CQL_TEMPLATE_DELETE_BY_TOPIC_ID: &'static str = "
+        DELETE FROM object_count_snapshot
+        WHERE topic_id = ?
+        ";
+
+    /// Create a new ObjectCountSnapshotEntity
+    pub fn new(
+        topic_id: &str,
+        instance_id: u16,
+        object_type: &ObjectCountType,
+        object_count: u64,
+        snapshot_ts_micros: u64,
+    ) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            instance_id: i16::from_unsigned(instance_id),
+            object_type: object_type.name().to_owned(),
+            object_count: i64::try_from(object_count).unwrap_or(i64::MAX),
+            snapshot_ts: i64::from_unsigned(snapshot_ts_micros),
+        }
+    }
+
+    /// The type of counted object.
+    pub fn get_object_type(&self) -> ObjectCountType {
+        ObjectCountType::by_name(&self.object_type)
+    }
+
+    /// Return the count.
+    pub fn get_object_count(&self) -> u64 {
+        u64::from_signed(self.object_count)
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider, topic_id: &str) {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        db.create_table(
+            keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Unconditional insert.
+    pub async fn insert(&self, db: &CassandraProvider) -> bool {
+        let keyspace = &db.get_keyspace_from_topic(&self.topic_id);
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT,
+            keyspace,
+            cdrs_tokio::query_values!(
+                self.topic_id.to_owned(),
+                self.instance_id,
+                self.object_type.to_owned(),
+                self.object_count,
+                self.snapshot_ts
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or(false)
+    }
+
+    /// Get all snapshots of the instance.
+    pub async fn select_by_topic_id_and_instance_id(
+        db: &CassandraProvider,
+        topic_id: &str,
+        instance_id: u16,
+    ) -> Vec<Self> {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_BY_INSTANCE_ID,
+            keyspace,
+            cdrs_tokio::query_values!(topic_id.to_owned(), i16::from_unsigned(instance_id)),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+    }
+
+    /// Delete the snapshots of all instances.
+    pub async fn delete_by_topic_id(db: &CassandraProvider, topic_id: &str) -> bool {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_DELETE_BY_TOPIC_ID,
+            keyspace,
+            cdrs_tokio::query_values!(topic_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_default()
+    }
+}
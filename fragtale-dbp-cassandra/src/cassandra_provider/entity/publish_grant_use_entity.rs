@@ -0,0 +1,146 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Consumed uses of publish grants entity and persistence.
+
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+
+/// Consumed uses of publish grants entity and persistence.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct PublishGrantUseEntity {
+    /// Unique identifier of the publish grant.
+    grant_uid: String,
+    /// Number of consumed uses.
+    uses: i32,
+}
+
+impl PublishGrantUseEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "publish_grant_use";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS publish_grant_use (
+            grant_uid       text,
+            uses            int,
+            PRIMARY KEY ((grant_uid))
+        );
+        ";
+
+    /// QPGU1. Get consumed uses by grant identifier.
+    const CQL_TEMPLATE_SELECT_BY_ID: &'static str = "
+        SELECT grant_uid, uses
+        FROM publish_grant_use
+        WHERE grant_uid = ?
+        ;";
+
+    /// QPGU2. Conditional insert of the first use.
+    const CQL_TEMPLATE_INSERT_IF_NOT_EXISTS: &'static str = "
+        INSERT INTO publish_grant_use
+        (grant_uid, uses)
+        VALUES (?,?)
+        IF NOT EXISTS
+        USING TTL ?
+        ;";
+
+    /// QPGU3. Conditional increase of unchanged uses.
+    const CQL_TEMPLATE_UPDATE_IF_UNCHANGED: &'static str = "
+        UPDATE publish_grant_use
+        USING TTL ?
+        SET uses = ?
+        WHERE grant_uid = ?
+        IF uses = ?
+        ;";
+
+    /// Return a new instance.
+    pub fn new(grant_uid: &str, uses: i32) -> Self {
+        Self {
+            grant_uid: grant_uid.to_owned(),
+            uses,
+        }
+    }
+
+    /// Return the number of consumed uses.
+    pub fn get_uses(&self) -> i32 {
+        self.uses
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider) {
+        db.create_table(
+            &db.app_keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Return the consumed uses by grant identifier if any use exists.
+    pub async fn select_by_id(
+        db: &CassandraProvider,
+        keyspace: &str,
+        grant_uid: &str,
+    ) -> Option<Self> {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_BY_ID,
+            keyspace,
+            cdrs_tokio::query_values!(grant_uid.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+        .first()
+        .cloned()
+    }
+
+    /// Conditional insert that expires after `ttl_seconds`.
+    pub async fn insert_if_not_exists(
+        &self,
+        db: &CassandraProvider,
+        keyspace: &str,
+        ttl_seconds: i32,
+    ) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT_IF_NOT_EXISTS,
+            keyspace,
+            cdrs_tokio::query_values!(self.grant_uid.to_owned(), self.uses, ttl_seconds),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or(false)
+    }
+
+    /// Conditional update that expires after `ttl_seconds` if the persisted
+    /// uses are still `expected`.
+    pub async fn update_if_unchanged(
+        &self,
+        db: &CassandraProvider,
+        keyspace: &str,
+        ttl_seconds: i32,
+        expected: i32,
+    ) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_UPDATE_IF_UNCHANGED,
+            keyspace,
+            cdrs_tokio::query_values!(ttl_seconds, self.uses, self.grant_uid.to_owned(), expected),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or(false)
+    }
+}
@@ -0,0 +1,243 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Rejected event entity and persistence.
+
+use super::FromSignedOrDefault;
+use super::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+use fragtale_dbp::mb::RejectedEvent;
+
+/// Rejected event entity and persistence.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct RejectedEventEntity {
+    /// Group all rejected events in single partition by using a common
+    /// `reject_type`.
+    reject_type: String,
+    /// The event document fingerprint.
+    event_id: String,
+    /// Time of rejection in epoch microseconds.
+    rejected_ts: i64,
+    /// The rejected event document.
+    document: String,
+    /// Requested event priority.
+    priority: Option<i32>,
+    /// Requested event descriptor version.
+    descriptor_version: Option<i64>,
+    /// Correlation token provided by the publisher.
+    correlation_token: Option<String>,
+    /// Identity of the publisher in serialized form.
+    publisher: String,
+    /// Reason for the rejection.
+    error_message: String,
+}
+
+impl From<&RejectedEvent> for RejectedEventEntity {
+    fn from(value: &RejectedEvent) -> Self {
+        Self {
+            reject_type: Self::REJECT_TYPE_DEFAULT.to_owned(),
+            event_id: value.get_event_id().to_owned(),
+            rejected_ts: i64::from_unsigned(value.get_rejected_ts_micros()),
+            document: value.get_document().to_owned(),
+            priority: value.get_priority().map(i32::from),
+            descriptor_version: value.get_descriptor_version().map(i64::from_unsigned),
+            correlation_token: value.get_correlation_token().to_owned(),
+            publisher: value.get_publisher().to_owned(),
+            error_message: value.get_error_message().to_owned(),
+        }
+    }
+}
+
+impl From<RejectedEventEntity> for RejectedEvent {
+    fn from(value: RejectedEventEntity) -> Self {
+        RejectedEvent::new(
+            &value.event_id,
+            u64::from_signed(value.rejected_ts),
+            &value.document,
+            value
+                .priority
+                .and_then(|priority| u8::try_from(priority).ok()),
+            value.descriptor_version.map(u64::from_signed),
+            value.correlation_token,
+            &value.publisher,
+            &value.error_message,
+        )
+    }
+}
+
+impl RejectedEventEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "rejected_event";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS rejected_event (
+            reject_type         text,
+            event_id            text,
+            rejected_ts         bigint,
+            document            text,
+            priority            int,
+            descriptor_version  bigint,
+            correlation_token   text,
+            publisher           text,
+            error_message       text,
+            PRIMARY KEY ((reject_type), event_id)
+        ) WITH CLUSTERING ORDER BY (event_id ASC);
+        ";
+
+    /// QRE1. Unconditional insert
+    const CQL_TEMPLATE_INSERT: &'static str = "
+        INSERT INTO rejected_event
+        (reject_type, event_id, rejected_ts, document, priority, descriptor_version, correlation_token, publisher, error_message)
+        VALUES (?,?,?,?,?,?,?,?,?)
+        ;";
+
+    /// QRE2. Get entities with limit.
+    const CQL_TEMPLATE_SELECT_ALL: &'static str = "
+        SELECT reject_type, event_id, rejected_ts, document, priority, descriptor_version, correlation_token, publisher, error_message
+        FROM rejected_event
+        WHERE reject_type = ?
+        LIMIT {{ limit }}
+        ;";
+
+    /// QRE3. Get entities with limit and event_id is greater than.
+    const CQL_TEMPLATE_SELECT_ALL_FROM: &'static str = "
+        SELECT reject_type, event_id, rejected_ts, document, priority, descriptor_version, correlation_token, publisher, error_message
+        FROM rejected_event
+        WHERE reject_type = ? AND event_id > ?
+        LIMIT {{ limit }}
+        ;";
+
+    /// QRE4. Get entity by event identifier.
+    const CQL_TEMPLATE_SELECT_BY_ID: &'static str = "
+        SELECT reject_type, event_id, rejected_ts, document, priority, descriptor_version, correlation_token, publisher, error_message
+        FROM rejected_event
+        WHERE reject_type = ? AND event_id = ?
+        ;";
+
+    /// QRE5. Delete/tombstone entity.
+    const CQL_TEMPLATE_DELETE: &'static str = "
+        DELETE
+        FROM rejected_event
+        WHERE reject_type = ? AND event_id = ?
+        ;";
+
+    /// Keep all rejected events of a topic in a single ordered partition.
+    const REJECT_TYPE_DEFAULT: &'static str = "_rejected";
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider, topic_id: &str) {
+        let keyspace = &db.get_keyspace_from_topic(topic_id);
+        db.create_table(
+            keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Unconditional insert
+    pub async fn insert(&self, db: &CassandraProvider, topic_id: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT,
+            &db.get_keyspace_from_topic(topic_id),
+            cdrs_tokio::query_values!(
+                self.reject_type.to_owned(),
+                self.event_id.to_owned(),
+                self.rejected_ts,
+                self.document.to_owned(),
+                self.priority,
+                self.descriptor_version,
+                self.correlation_token.to_owned(),
+                self.publisher.to_owned(),
+                self.error_message.to_owned()
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_else(|| {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!("Failed insert of rejected event '{}'.", self.event_id);
+            }
+            false
+        })
+    }
+
+    /// Retrieve rejected events with an event identifier greater than `from`
+    /// up to a max number of results.
+    pub async fn select_all(
+        db: &CassandraProvider,
+        topic_id: &str,
+        from: &Option<String>,
+        max_results: usize,
+    ) -> Vec<Self> {
+        let (query_template, values) = if let Some(from) = from {
+            (
+                Self::CQL_TEMPLATE_SELECT_ALL_FROM,
+                cdrs_tokio::query_values!(Self::REJECT_TYPE_DEFAULT.to_owned(), from.to_owned()),
+            )
+        } else {
+            (
+                Self::CQL_TEMPLATE_SELECT_ALL,
+                cdrs_tokio::query_values!(Self::REJECT_TYPE_DEFAULT.to_owned()),
+            )
+        };
+        db.query_with_keyspace_and_values(
+            &query_template.replacen("{{ limit }}", &max_results.to_string(), 1),
+            &db.get_keyspace_from_topic(topic_id),
+            values,
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+    }
+
+    /// Return the entity for a specific event identifier if it exists.
+    pub async fn select_by_event_id(
+        db: &CassandraProvider,
+        topic_id: &str,
+        event_id: &str,
+    ) -> Option<Self> {
+        let values =
+            cdrs_tokio::query_values!(Self::REJECT_TYPE_DEFAULT.to_owned(), event_id.to_owned());
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_BY_ID,
+            &db.get_keyspace_from_topic(topic_id),
+            values,
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+        .first()
+        .cloned()
+    }
+
+    /// Delete the entity for a specific event identifier.
+    pub async fn delete(db: &CassandraProvider, topic_id: &str, event_id: &str) -> bool {
+        let values =
+            cdrs_tokio::query_values!(Self::REJECT_TYPE_DEFAULT.to_owned(), event_id.to_owned());
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_DELETE,
+            &db.get_keyspace_from_topic(topic_id),
+            values,
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_default()
+    }
+}
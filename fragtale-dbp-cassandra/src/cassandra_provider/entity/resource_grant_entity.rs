@@ -65,7 +65,14 @@ impl ResourceGrantEntity {
         LIMIT {{ limit }}
         ;";
 
-    /// QRG4. Delete/tombstone entity.
+    /// QRG4. Get all entities.
+    const CQL_TEMPLATE_SELECT_ALL: &'static str = "
+        SELECT resource, identity
+        FROM {{ keyspace }}.resource_grant
+        LIMIT {{ limit }}
+        ;";
+
+    /// QRG5. Delete/tombstone entity.
     const CQL_TEMPLATE_DELETE: &'static str = "
         DELETE
         FROM {{ keyspace }}.resource_grant
@@ -81,6 +88,16 @@ impl ResourceGrantEntity {
         }
     }
 
+    /// The resource where authorization is granted.
+    pub fn get_resource(&self) -> &str {
+        &self.resource
+    }
+
+    /// The identity that is granted access in serialized form.
+    pub fn get_identity(&self) -> &str {
+        &self.identity
+    }
+
     /// Create table and indices for this entity.
     pub async fn create_table_and_indices(db: &CassandraProvider) {
         db.create_table(
@@ -137,7 +154,7 @@ impl ResourceGrantEntity {
             .cloned()
     }
 
-    /// Return the entity for a specific resource and identity if it exists.
+    /// Return up to `max_results` entities for a specific resource.
     pub async fn select_by_resource(
         db: &CassandraProvider,
         keyspace: &str,
@@ -159,6 +176,22 @@ impl ResourceGrantEntity {
         .unwrap_or_default()
     }
 
+    /// Return up to `max_results` entities for all resources.
+    pub async fn select_all(
+        db: &CassandraProvider,
+        keyspace: &str,
+        max_results: usize,
+    ) -> Vec<Self> {
+        db.query_with_keyspace_and_values(
+            &Self::CQL_TEMPLATE_SELECT_ALL.replacen("{{ limit }}", &max_results.to_string(), 1),
+            keyspace,
+            cdrs_tokio::query_values!(),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+    }
+
     /// Delete the entity for a specific resource and identity.
     pub async fn delete(
         db: &CassandraProvider,
@@ -0,0 +1,154 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Shared schema component entity and persistence.
+
+use super::FromSignedOrDefault;
+use super::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+
+/// Versioned shared schema component in the schema registry.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct SharedSchemaEntity {
+    /// Schema identifier that event schemas use as `$ref` target.
+    schema_id: String,
+    /// Version of the shared schema component.
+    version: i64,
+    /// The shared schema component in serialized form.
+    shared_schema: String,
+}
+
+impl SharedSchemaEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "shared_schema";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS shared_schema (
+            schema_id       text,
+            version         bigint,
+            shared_schema   text,
+            PRIMARY KEY ((schema_id), version)
+        ) WITH CLUSTERING ORDER BY (version DESC);
+        ";
+
+    /// QSS1. Append new version
+    const CQL_TEMPLATE_INSERT_IF_NOT_EXISTS: &'static str = "
+        INSERT INTO shared_schema
+        (schema_id, version, shared_schema)
+        VALUES (?,?,?)
+        IF NOT EXISTS
+        ;";
+
+    /// QSS2. Get latest version of a shared schema component.
+    const CQL_TEMPLATE_SELECT_LATEST_BY_ID: &'static str = "
+        SELECT schema_id, version, shared_schema
+        FROM shared_schema
+        WHERE schema_id = ?
+        LIMIT 1
+        ;";
+
+    /// QSS3. Get latest version of all shared schema components.
+    const CQL_TEMPLATE_SELECT_LATEST_ALL: &'static str = "
+        SELECT schema_id, version, shared_schema
+        FROM shared_schema
+        PER PARTITION LIMIT 1
+        LIMIT {{ limit }}
+        ;";
+
+    /// Return a new instance.
+    pub fn new(schema_id: &str, version: u64, shared_schema: &str) -> Self {
+        Self {
+            schema_id: schema_id.to_owned(),
+            version: i64::from_unsigned(version),
+            shared_schema: shared_schema.to_owned(),
+        }
+    }
+
+    /// Return the version of the shared schema component.
+    pub fn get_version(&self) -> u64 {
+        u64::from_signed(self.version)
+    }
+
+    /// Return the serialized shared schema component.
+    pub fn get_shared_schema(&self) -> &str {
+        &self.shared_schema
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider) {
+        db.create_table(
+            &db.app_keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Conditional insert.
+    pub async fn insert_if_not_exists(&self, db: &CassandraProvider, keyspace: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT_IF_NOT_EXISTS,
+            keyspace,
+            cdrs_tokio::query_values!(
+                self.schema_id.to_owned(),
+                self.version,
+                self.shared_schema.to_owned()
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or(false)
+    }
+
+    /// Return the latest version of a shared schema component.
+    pub async fn select_latest_by_id(
+        db: &CassandraProvider,
+        keyspace: &str,
+        schema_id: &str,
+    ) -> Option<Self> {
+        let values = cdrs_tokio::query_values!(schema_id.to_owned());
+        db.query_with_keyspace_and_values(Self::CQL_TEMPLATE_SELECT_LATEST_BY_ID, keyspace, values)
+            .await
+            .map(CassandraResultMapper::into_entities)
+            .unwrap_or_default()
+            .first()
+            .cloned()
+    }
+
+    /// Return the latest version of up to `max_results` shared schema
+    /// components.
+    pub async fn select_latest_all(
+        db: &CassandraProvider,
+        keyspace: &str,
+        max_results: usize,
+    ) -> Vec<Self> {
+        db.query_with_keyspace_and_values(
+            &Self::CQL_TEMPLATE_SELECT_LATEST_ALL.replacen(
+                "{{ limit }}",
+                &max_results.to_string(),
+                1,
+            ),
+            keyspace,
+            cdrs_tokio::query_values!(),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+    }
+}
@@ -0,0 +1,144 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Topic bulk ingest window entity and persistence.
+
+use super::FromSignedOrDefault;
+use super::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+
+/// Topic bulk ingest window entity and persistence.
+///
+/// Tracks the latest window where publishing to the topic defers index
+/// population and integrity consolidation.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct TopicBulkIngestEntity {
+    /// Topic identifier.
+    topic_id: String,
+    /// Start of the window in epoch microseconds.
+    start_ts: i64,
+    /// End of the window in epoch microseconds.
+    end_ts: i64,
+    /// `true` once deferred work of the window has been completed.
+    reconciled: bool,
+}
+
+impl TopicBulkIngestEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "topic_bulk_ingest";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS topic_bulk_ingest (
+            topic_id    text,
+            start_ts    bigint,
+            end_ts      bigint,
+            reconciled  boolean,
+            PRIMARY KEY ((topic_id))
+        );
+        ";
+
+    /// QTB1. Unconditional insert
+    const CQL_TEMPLATE_INSERT: &'static str = "
+        INSERT INTO topic_bulk_ingest
+        (topic_id, start_ts, end_ts, reconciled)
+        VALUES (?,?,?,?)
+        ;";
+
+    /// QTB2. Get entity by topic.
+    const CQL_TEMPLATE_SELECT_BY_ID: &'static str = "
+        SELECT topic_id, start_ts, end_ts, reconciled
+        FROM topic_bulk_ingest
+        WHERE topic_id = ?
+        ;";
+
+    /// Return a new instance.
+    pub fn new(topic_id: &str, start_ts_micros: u64, end_ts_micros: u64, reconciled: bool) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            start_ts: i64::from_unsigned(start_ts_micros),
+            end_ts: i64::from_unsigned(end_ts_micros),
+            reconciled,
+        }
+    }
+
+    /// Return the start of the window in epoch microseconds.
+    pub fn get_start_ts_micros(&self) -> u64 {
+        u64::from_signed(self.start_ts)
+    }
+
+    /// Return the end of the window in epoch microseconds.
+    pub fn get_end_ts_micros(&self) -> u64 {
+        u64::from_signed(self.end_ts)
+    }
+
+    /// Return `true` once deferred work of the window has been completed.
+    pub fn is_reconciled(&self) -> bool {
+        self.reconciled
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider) {
+        db.create_table(
+            &db.app_keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Unconditional insert
+    pub async fn insert(&self, db: &CassandraProvider, keyspace: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT,
+            keyspace,
+            cdrs_tokio::query_values!(
+                self.topic_id.to_owned(),
+                self.start_ts,
+                self.end_ts,
+                self.reconciled
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_else(|| {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!("Failed insert of {self:?}");
+            }
+            false
+        })
+    }
+
+    /// Return the entity for a specific topic if it exists.
+    pub async fn select_by_topic_id(
+        db: &CassandraProvider,
+        keyspace: &str,
+        topic_id: &str,
+    ) -> Option<Self> {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_BY_ID,
+            keyspace,
+            cdrs_tokio::query_values!(topic_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+        .first()
+        .cloned()
+    }
+}
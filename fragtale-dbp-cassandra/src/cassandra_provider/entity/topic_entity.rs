@@ -73,6 +73,13 @@ impl TopicEntity {
         LIMIT {{ limit }}
         ;";
 
+    /// QT4. Get entity by topic identifier.
+    const CQL_TEMPLATE_SELECT_BY_ID: &'static str = "
+        SELECT topic_type, topic_id, last_update_ts
+        FROM {{ keyspace }}.topic
+        WHERE topic_type = ? AND topic_id = ?
+        ;";
+
     /// Keep all topics in a single ordered partition..
     const TOPIC_TYPE_DEFAULT: &'static str = "_topic";
 
@@ -121,6 +128,22 @@ impl TopicEntity {
         })
     }
 
+    /// Retrieve the entity by topic identifier if it exists.
+    pub async fn select_by_topic_id(
+        db: &CassandraProvider,
+        keyspace: &str,
+        topic_id: &str,
+    ) -> Option<Self> {
+        let values =
+            cdrs_tokio::query_values!(Self::TOPIC_TYPE_DEFAULT.to_owned(), topic_id.to_string());
+        db.query_with_keyspace_and_values(Self::CQL_TEMPLATE_SELECT_BY_ID, keyspace, values)
+            .await
+            .map(CassandraResultMapper::into_entities)
+            .unwrap_or_default()
+            .first()
+            .cloned()
+    }
+
     /// Retrieve all topic identifiers up to a max number of results.
     pub async fn select_all_topic_id(
         db: &CassandraProvider,
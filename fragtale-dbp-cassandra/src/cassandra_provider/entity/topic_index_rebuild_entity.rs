@@ -0,0 +1,147 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Topic index rebuild progress entity and persistence.
+
+use super::FromSignedOrDefault;
+use super::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+use fragtale_dbp::mb::IndexRebuildProgress;
+
+/// Topic index rebuild progress entity and persistence.
+///
+/// Tracks the progress of the latest rebuild of the indexed columns of the
+/// topic, so it can be resumed by another instance.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct TopicIndexRebuildEntity {
+    /// Topic identifier.
+    topic_id: String,
+    /// Time the rebuild was requested in epoch microseconds.
+    requested_ts: i64,
+    /// Encoded unique time up to which all events have been processed.
+    position: i64,
+    /// Number of events where indexed columns were populated.
+    rebuilt_count: i64,
+    /// Time the rebuild completed in epoch microseconds.
+    completed_ts: Option<i64>,
+}
+
+impl TopicIndexRebuildEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "topic_index_rebuild";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS topic_index_rebuild (
+            topic_id        text,
+            requested_ts    bigint,
+            position        bigint,
+            rebuilt_count   bigint,
+            completed_ts    bigint,
+            PRIMARY KEY ((topic_id))
+        );
+        ";
+
+    /// QTI1. Unconditional insert
+    const CQL_TEMPLATE_INSERT: &'static str = "
+        INSERT INTO topic_index_rebuild
+        (topic_id, requested_ts, position, rebuilt_count, completed_ts)
+        VALUES (?,?,?,?,?)
+        ;";
+
+    /// QTI2. Get entity by topic.
+    const CQL_TEMPLATE_SELECT_BY_ID: &'static str = "
+        SELECT topic_id, requested_ts, position, rebuilt_count, completed_ts
+        FROM topic_index_rebuild
+        WHERE topic_id = ?
+        ;";
+
+    /// Return a new instance.
+    pub fn new(topic_id: &str, index_rebuild_progress: &IndexRebuildProgress) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            requested_ts: i64::from_unsigned(index_rebuild_progress.get_requested_ts_micros()),
+            position: i64::from_unsigned(index_rebuild_progress.get_position()),
+            rebuilt_count: i64::from_unsigned(index_rebuild_progress.get_rebuilt_count()),
+            completed_ts: index_rebuild_progress
+                .get_completed_ts_micros()
+                .map(i64::from_unsigned),
+        }
+    }
+
+    /// Return the progress of the rebuild.
+    pub fn as_index_rebuild_progress(&self) -> IndexRebuildProgress {
+        IndexRebuildProgress::new(
+            u64::from_signed(self.requested_ts),
+            u64::from_signed(self.position),
+            u64::from_signed(self.rebuilt_count),
+            self.completed_ts.map(u64::from_signed),
+        )
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider) {
+        db.create_table(
+            &db.app_keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Unconditional insert
+    pub async fn insert(&self, db: &CassandraProvider, keyspace: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT,
+            keyspace,
+            cdrs_tokio::query_values!(
+                self.topic_id.to_owned(),
+                self.requested_ts,
+                self.position,
+                self.rebuilt_count,
+                self.completed_ts
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_else(|| {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!("Failed insert of {self:?}");
+            }
+            false
+        })
+    }
+
+    /// Return the entity for a specific topic if it exists.
+    pub async fn select_by_topic_id(
+        db: &CassandraProvider,
+        keyspace: &str,
+        topic_id: &str,
+    ) -> Option<Self> {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_BY_ID,
+            keyspace,
+            cdrs_tokio::query_values!(topic_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+        .first()
+        .cloned()
+    }
+}
@@ -0,0 +1,138 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Topic reply registration entity and persistence.
+
+use super::FromUnsignedOrDefault;
+use crate::CassandraProvider;
+use crate::CassandraResultMapper;
+
+/// Topic reply registration entity and persistence.
+///
+/// Tracks which topic the responding service publishes correlated results of
+/// events in a topic to.
+#[derive(
+    Clone, Debug, cdrs_tokio::IntoCdrsValue, cdrs_tokio::TryFromRow, cdrs_tokio::TryFromUdt,
+)]
+pub struct TopicReplyEntity {
+    /// Topic identifier of requests.
+    topic_id: String,
+    /// Topic identifier of correlated results.
+    reply_topic_id: String,
+    /// Identity of the responding service that made the registration.
+    registered_by: String,
+    /// Time of registration in epoch microseconds.
+    last_update_ts: i64,
+}
+
+impl TopicReplyEntity {
+    pub(crate) const CQL_TABLE_NAME: &'static str = "topic_reply";
+
+    const CQL_TEMPLATE_CREATE_TABLE: &'static str = "
+        CREATE TABLE IF NOT EXISTS topic_reply (
+            topic_id        text,
+            reply_topic_id  text,
+            registered_by   text,
+            last_update_ts  bigint,
+            PRIMARY KEY ((topic_id))
+        );
+        ";
+
+    /// QTR1. Unconditional insert
+    const CQL_TEMPLATE_INSERT: &'static str = "
+        INSERT INTO topic_reply
+        (topic_id, reply_topic_id, registered_by, last_update_ts)
+        VALUES (?,?,?,?)
+        ;";
+
+    /// QTR2. Get entity by topic.
+    const CQL_TEMPLATE_SELECT_BY_ID: &'static str = "
+        SELECT topic_id, reply_topic_id, registered_by, last_update_ts
+        FROM topic_reply
+        WHERE topic_id = ?
+        ;";
+
+    /// Return a new instance.
+    pub fn new(topic_id: &str, reply_topic_id: &str, registered_by: &str) -> Self {
+        Self {
+            topic_id: topic_id.to_owned(),
+            reply_topic_id: reply_topic_id.to_owned(),
+            registered_by: registered_by.to_owned(),
+            last_update_ts: i64::from_unsigned(fragtale_client::time::get_timestamp_micros()),
+        }
+    }
+
+    /// Return the topic identifier of correlated results.
+    pub fn get_reply_topic_id(&self) -> &str {
+        &self.reply_topic_id
+    }
+
+    /// Return the identity that registered the reply topic.
+    pub fn get_registered_by(&self) -> &str {
+        &self.registered_by
+    }
+
+    /// Create table and indices for this entity.
+    pub async fn create_table_and_indices(db: &CassandraProvider) {
+        db.create_table(
+            &db.app_keyspace,
+            Self::CQL_TABLE_NAME,
+            Self::CQL_TEMPLATE_CREATE_TABLE,
+        )
+        .await;
+    }
+
+    /// Unconditional insert
+    pub async fn insert(&self, db: &CassandraProvider, keyspace: &str) -> bool {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_INSERT,
+            keyspace,
+            cdrs_tokio::query_values!(
+                self.topic_id.to_owned(),
+                self.reply_topic_id.to_owned(),
+                self.registered_by.to_owned(),
+                self.last_update_ts
+            ),
+        )
+        .await
+        .map(CassandraResultMapper::into_applied)
+        .unwrap_or_else(|| {
+            if log::log_enabled!(log::Level::Debug) {
+                log::debug!("Failed insert of {self:?}");
+            }
+            false
+        })
+    }
+
+    /// Return the entity for a specific topic if it exists.
+    pub async fn select_by_topic_id(
+        db: &CassandraProvider,
+        keyspace: &str,
+        topic_id: &str,
+    ) -> Option<Self> {
+        db.query_with_keyspace_and_values(
+            Self::CQL_TEMPLATE_SELECT_BY_ID,
+            keyspace,
+            cdrs_tokio::query_values!(topic_id.to_owned()),
+        )
+        .await
+        .map(CassandraResultMapper::into_entities)
+        .unwrap_or_default()
+        .first()
+        .cloned()
+    }
+}
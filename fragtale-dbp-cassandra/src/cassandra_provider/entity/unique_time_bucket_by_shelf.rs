@@ -21,6 +21,7 @@ use super::FromSignedOrDefault;
 use super::FromUnsignedOrDefault;
 use crate::CassandraProvider;
 use crate::CassandraResultMapper;
+use cdrs_tokio::query::QueryValues;
 use fragtale_dbp::mb::UniqueTime;
 
 /// UniqueTime bucket event by shelf entity and persistence
@@ -97,16 +98,26 @@ impl UniqueTimeBucketByShelfEntity {
 
     /// Unconditional insert.
     pub async fn insert(&self, db: &CassandraProvider, topic_id: &str) -> bool {
+        let (query_template, query_values) = self.insert_statement();
         db.query_with_keyspace_and_values(
-            Self::CQL_TEMPLATE_INSERT,
+            &query_template,
             &db.get_keyspace_from_topic(topic_id),
-            cdrs_tokio::query_values!(self.shelf, self.bucket),
+            query_values,
         )
         .await
         .map(CassandraResultMapper::into_applied)
         .unwrap_or(false)
     }
 
+    /// Return the statement and values that inserts the entity
+    /// (unconditional), for use in a batch.
+    pub fn insert_statement(&self) -> (String, QueryValues) {
+        (
+            Self::CQL_TEMPLATE_INSERT.to_owned(),
+            cdrs_tokio::query_values!(self.shelf, self.bucket),
+        )
+    }
+
     /// Get the next entity (to get the bucket) for a shelf.
     pub async fn select_next_by_shelf_and_bucket(
         db: &CassandraProvider,
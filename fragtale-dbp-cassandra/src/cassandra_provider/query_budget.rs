@@ -0,0 +1,178 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Per topic token bucket budget of queries on a shared session.
+
+use crossbeam_skiplist::SkipMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use tokio::time::Duration;
+use tokio::time::Instant;
+
+/// Token bucket state of a single topic.
+struct TokenBucket {
+    /// Available tokens and when they were last refilled.
+    state: Mutex<(f64, Instant)>,
+    /// Queries of the topic that currently wait for a session slot.
+    queued: AtomicU64,
+    /// Number of times a query of the topic had to wait for a token.
+    throttled: AtomicU64,
+    /// Total time queries of the topic have waited for tokens.
+    throttled_micros: AtomicU64,
+}
+
+impl TokenBucket {
+    fn new(burst: f64) -> Self {
+        Self {
+            state: Mutex::new((burst, Instant::now())),
+            queued: AtomicU64::default(),
+            throttled: AtomicU64::default(),
+            throttled_micros: AtomicU64::default(),
+        }
+    }
+}
+
+/** Fair budget of queries per topic.
+
+Each topic has a token bucket that is refilled with `rate` tokens per second
+up to `2 * rate` tokens. Every query of the topic takes a token.
+
+A topic that has run out of tokens only has to wait for a new token while
+queries of other topics are waiting for a slot on the session. A single topic
+catching up on a large backlog can thus use the full session while the system
+is otherwise idle, but can't starve other topics during catch-up storms.
+*/
+pub struct QueryBudget {
+    /// Tokens per second refilled into each bucket. (0 disables the budget.)
+    rate: f64,
+    /// Max tokens in each bucket.
+    burst: f64,
+    buckets: SkipMap<String, Arc<TokenBucket>>,
+    /// Queries of all topics that currently wait for a session slot.
+    queued: AtomicU64,
+}
+
+impl QueryBudget {
+    /// Return a new instance where each topic is allowed `rate` queries per
+    /// second under contention.
+    pub fn new(rate: u64) -> Self {
+        Self {
+            rate: rate as f64,
+            burst: rate.saturating_mul(2) as f64,
+            buckets: SkipMap::default(),
+            queued: AtomicU64::default(),
+        }
+    }
+
+    /// Wait until the topic is allowed to run another query.
+    ///
+    /// The returned guard must be held until the query has been assigned a
+    /// slot on the session.
+    pub async fn acquire(&self, topic_id: &str) -> Option<QueryBudgetGuard<'_>> {
+        if self.rate <= 0.0 {
+            return None;
+        }
+        let bucket = Arc::clone(
+            self.buckets
+                .get_or_insert_with(topic_id.to_owned(), || {
+                    Arc::new(TokenBucket::new(self.burst))
+                })
+                .value(),
+        );
+        let mut throttled_since = None;
+        while let Some(wait) = self.take_token(&bucket) {
+            if !self.is_contended_by_others(&bucket) {
+                break;
+            }
+            throttled_since.get_or_insert_with(Instant::now);
+            tokio::time::sleep(wait).await;
+        }
+        if let Some(throttled_since) = throttled_since {
+            bucket.throttled.fetch_add(1, Ordering::Relaxed);
+            bucket.throttled_micros.fetch_add(
+                u64::try_from(throttled_since.elapsed().as_micros()).unwrap_or(u64::MAX),
+                Ordering::Relaxed,
+            );
+        }
+        bucket.queued.fetch_add(1, Ordering::Relaxed);
+        self.queued.fetch_add(1, Ordering::Relaxed);
+        Some(QueryBudgetGuard {
+            query_budget: self,
+            bucket,
+        })
+    }
+
+    /// Take a token from the bucket or return the time until the next token
+    /// is available.
+    ///
+    /// The bucket is never drained below zero, so a topic that was allowed to
+    /// run without tokens doesn't build up a debt.
+    fn take_token(&self, bucket: &TokenBucket) -> Option<Duration> {
+        let Ok(mut state) = bucket.state.lock() else {
+            return None;
+        };
+        let now = Instant::now();
+        let (tokens, refilled) = *state;
+        let tokens =
+            (tokens + now.duration_since(refilled).as_secs_f64() * self.rate).min(self.burst);
+        if tokens >= 1.0 {
+            *state = (tokens - 1.0, now);
+            None
+        } else {
+            *state = (tokens, now);
+            Some(Duration::from_secs_f64((1.0 - tokens) / self.rate))
+        }
+    }
+
+    /// Return `true` if queries of other topics are waiting for a slot on the
+    /// session.
+    fn is_contended_by_others(&self, bucket: &TokenBucket) -> bool {
+        self.queued.load(Ordering::Relaxed) > bucket.queued.load(Ordering::Relaxed)
+    }
+
+    /// Return the number of throttled queries and the total time they waited
+    /// in microseconds by topic.
+    pub fn get_throttled_by_topic(&self) -> Vec<(String, u64, u64)> {
+        self.buckets
+            .iter()
+            .map(|entry| {
+                let bucket = entry.value();
+                (
+                    entry.key().to_owned(),
+                    bucket.throttled.load(Ordering::Relaxed),
+                    bucket.throttled_micros.load(Ordering::Relaxed),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Tracks a query of a topic that waits for a slot on the session until
+/// dropped.
+pub struct QueryBudgetGuard<'a> {
+    query_budget: &'a QueryBudget,
+    bucket: Arc<TokenBucket>,
+}
+
+impl Drop for QueryBudgetGuard<'_> {
+    fn drop(&mut self) {
+        self.bucket.queued.fetch_sub(1, Ordering::Relaxed);
+        self.query_budget.queued.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,293 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Time-sliced fairness between query classes on a shared session.
+
+use super::query_budget::QueryBudget;
+use fragtale_metrics::metric::Metric;
+use fragtale_metrics::metric::MetricLabeledValue;
+use fragtale_metrics::metric::MetricType;
+use fragtale_metrics::registry::MetricsProvider;
+use fragtale_metrics::registry::MetricsProviderRegistry;
+use fragtale_metrics::registry::MetricsResult;
+use fragtale_metrics::registry::MetricsResultFuture;
+use fragtale_metrics::util::AtomicMetricAverage;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use tokio::sync::Semaphore;
+use tokio::sync::SemaphorePermit;
+use tokio::time::Instant;
+
+tokio::task_local! {
+    static QUERY_CLASS: QueryClass;
+}
+
+/** Classification of the queries issued by a task.
+
+The class is set where work enters the provider and is implicitly available
+to every query made by the same task. Work that is spawned as a separate task
+must be scoped again.
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueryClass {
+    /// Reads of newly published events for delivery.
+    Fresh,
+    /// Scans for events where delivery needs to be retried.
+    Retry,
+    /// Everything else.
+    Other,
+}
+
+impl QueryClass {
+    /// All classes in metrics order.
+    const ALL: [Self; 3] = [Self::Fresh, Self::Retry, Self::Other];
+
+    /// Run `future` with all nested queries classified as `self`.
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        QUERY_CLASS.scope(self, future).await
+    }
+
+    /// Return the class of the current task or [Self::Other] if unclassified.
+    pub fn current() -> Self {
+        QUERY_CLASS.try_with(Self::clone).unwrap_or(Self::Other)
+    }
+
+    /// Return the metrics label of this class.
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fresh => "fresh",
+            Self::Retry => "retry",
+            Self::Other => "other",
+        }
+    }
+
+    /// Return the position of this class in [Self::ALL].
+    fn index(&self) -> usize {
+        match self {
+            Self::Fresh => 0,
+            Self::Retry => 1,
+            Self::Other => 2,
+        }
+    }
+}
+
+/// Latency tracking of a single [QueryClass].
+#[derive(Default)]
+struct QueryClassStats {
+    queries: AtomicU64,
+    queued_avg: AtomicMetricAverage,
+    latency_avg: AtomicMetricAverage,
+    latency_max: AtomicU64,
+}
+
+/** Scheduler of concurrent queries on a shared session.
+
+The session can run at most `max_concurrency` queries at the same time.
+[QueryClass::Retry] queries are additionally limited so that they never hold
+more than `100 - fresh_min_share` percent of these slots. A burst of retry
+scans will therefore queue up behind each other, while fresh delivery queries
+always have their minimum share of the session throughput available.
+
+Queries in the keyspace of a topic are additionally subject to the topic's
+[QueryBudget], so a single topic can't monopolize the session.
+
+Per class latency (including time spent waiting for a slot) is exposed as
+metrics to make the isolation observable.
+*/
+pub struct QueryScheduler {
+    session_permits: Semaphore,
+    retry_permits: Semaphore,
+    stats: [QueryClassStats; 3],
+    /// Prefix of the keyspace of every topic.
+    topic_keyspace_prefix: String,
+    query_budget: QueryBudget,
+}
+
+impl QueryScheduler {
+    const METRIC_COMPONENT_NAME: &str = "cassandra";
+    const METRIC_NAME_QUERIES: &str = "query_count";
+    const METRIC_NAME_QUEUED_AVG: &str = "query_queued_avg_millis";
+    const METRIC_NAME_LATENCY_AVG: &str = "query_latency_avg_millis";
+    const METRIC_NAME_LATENCY_MAX: &str = "query_latency_max_micros";
+    const METRIC_NAME_BUDGET_THROTTLED: &str = "query_budget_throttled_count";
+    const METRIC_NAME_BUDGET_THROTTLED_MILLIS: &str = "query_budget_throttled_millis";
+    const METRIC_LABEL_CLASS: &str = "class";
+    const METRIC_LABEL_TOPIC: &str = "topic";
+
+    /// Return a new instance and register its metrics.
+    ///
+    /// `fresh_min_share` is the percentage (1-100) of `max_concurrency` that
+    /// can't be used by retry scans.
+    ///
+    /// `topic_query_rate` is the number of queries per second each topic is
+    /// guaranteed while other topics are waiting for the session. (0 disables
+    /// the per topic budget.)
+    pub fn new(
+        app_name: &str,
+        app_keyspace: &str,
+        max_concurrency: usize,
+        fresh_min_share: usize,
+        topic_query_rate: u64,
+    ) -> Arc<Self> {
+        let max_concurrency = max_concurrency.max(1);
+        let fresh_reserved = (max_concurrency * fresh_min_share.clamp(1, 100)).div_ceil(100);
+        let max_retry_concurrency = max_concurrency.saturating_sub(fresh_reserved).max(1);
+        log::debug!(
+            "Cassandra session concurrency is {max_concurrency} with at most {max_retry_concurrency} for retry scans."
+        );
+        let instance = Arc::new(Self {
+            session_permits: Semaphore::new(max_concurrency),
+            retry_permits: Semaphore::new(max_retry_concurrency),
+            stats: Default::default(),
+            topic_keyspace_prefix: app_keyspace.to_owned() + "_",
+            query_budget: QueryBudget::new(topic_query_rate),
+        });
+        MetricsProviderRegistry::register_metrics(
+            app_name,
+            Self::METRIC_COMPONENT_NAME,
+            Arc::clone(&instance) as Arc<dyn MetricsProvider>,
+        );
+        instance
+    }
+
+    /// Run `query` in `keyspace` once the [QueryClass] of the current task
+    /// and the topic of the keyspace are allowed to use the session.
+    pub async fn run<F: Future>(&self, keyspace: &str, query: F) -> F::Output {
+        let query_class = QueryClass::current();
+        let start = Instant::now();
+        let query_budget_guard = match keyspace.strip_prefix(&self.topic_keyspace_prefix) {
+            Some(topic_id) if !topic_id.is_empty() => self.query_budget.acquire(topic_id).await,
+            _ => None,
+        };
+        let _retry_permit: Option<SemaphorePermit> = if query_class == QueryClass::Retry {
+            self.retry_permits.acquire().await.ok()
+        } else {
+            None
+        };
+        let _session_permit = self.session_permits.acquire().await.ok();
+        drop(query_budget_guard);
+        let queued_micros = Self::micros_since(start);
+        let ret = query.await;
+        self.report(query_class, queued_micros, Self::micros_since(start));
+        ret
+    }
+
+    fn micros_since(start: Instant) -> u64 {
+        u64::try_from(start.elapsed().as_micros()).unwrap_or(u64::MAX)
+    }
+
+    /// Track a completed query.
+    fn report(&self, query_class: QueryClass, queued_micros: u64, latency_micros: u64) {
+        let stats = &self.stats[query_class.index()];
+        stats.queries.fetch_add(1, Ordering::Relaxed);
+        // Convert latency to millis
+        stats.queued_avg.append_with_cap(queued_micros / 1000);
+        stats.latency_avg.append_with_cap(latency_micros / 1000);
+        stats
+            .latency_max
+            .fetch_max(latency_micros, Ordering::Relaxed);
+    }
+
+    fn mlvs_by_class(&self, value_fn: impl Fn(&QueryClassStats) -> f64) -> Vec<MetricLabeledValue> {
+        QueryClass::ALL
+            .iter()
+            .map(|query_class| {
+                MetricLabeledValue::new(value_fn(&self.stats[query_class.index()]))
+                    .add_label(Self::METRIC_LABEL_CLASS, query_class.as_str().to_owned())
+            })
+            .collect()
+    }
+
+    fn mlvs_by_topic(
+        throttled_by_topic: &[(String, u64, u64)],
+        value_fn: impl Fn(u64, u64) -> f64,
+    ) -> Vec<MetricLabeledValue> {
+        throttled_by_topic
+            .iter()
+            .map(|(topic_id, throttled, throttled_micros)| {
+                MetricLabeledValue::new(value_fn(*throttled, *throttled_micros))
+                    .add_label(Self::METRIC_LABEL_TOPIC, topic_id.to_owned())
+            })
+            .collect()
+    }
+}
+
+impl MetricsProvider for QueryScheduler {
+    fn metrics(self: Arc<Self>, template: MetricsResult) -> MetricsResultFuture {
+        let self_clone = Arc::clone(&self);
+        MetricsResultFuture::from_future(async move {
+            let throttled_by_topic = self_clone.query_budget.get_throttled_by_topic();
+            template
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_QUERIES,
+                        &self_clone
+                            .mlvs_by_class(|stats| stats.queries.load(Ordering::Relaxed) as f64),
+                    )
+                    .set_help("Queries executed on the shared database session.")
+                    .set_type(MetricType::Counter),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_QUEUED_AVG,
+                        // Reset value when read
+                        &self_clone.mlvs_by_class(|stats| stats.queued_avg.get_and_reset() as f64),
+                    )
+                    .set_help("Average time queries waited for a session slot since last scrape.")
+                    .set_type(MetricType::Gauge),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_LATENCY_AVG,
+                        &self_clone.mlvs_by_class(|stats| stats.latency_avg.get_and_reset() as f64),
+                    )
+                    .set_help("Average query latency including queueing since last scrape.")
+                    .set_type(MetricType::Gauge),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_LATENCY_MAX,
+                        &self_clone.mlvs_by_class(|stats| {
+                            stats.latency_max.swap(0, Ordering::Relaxed) as f64
+                        }),
+                    )
+                    .set_help("Max query latency including queueing since last scrape.")
+                    .set_type(MetricType::Gauge),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_BUDGET_THROTTLED,
+                        &Self::mlvs_by_topic(&throttled_by_topic, |throttled, _| throttled as f64),
+                    )
+                    .set_help("Queries that waited for the query budget of their topic.")
+                    .set_type(MetricType::Counter),
+                )
+                .add_metric(
+                    Metric::from_metric_labeled_values(
+                        Self::METRIC_NAME_BUDGET_THROTTLED_MILLIS,
+                        &Self::mlvs_by_topic(&throttled_by_topic, |_, throttled_micros| {
+                            (throttled_micros / 1000) as f64
+                        }),
+                    )
+                    .set_help("Total time queries waited for the query budget of their topic.")
+                    .set_type(MetricType::Counter),
+                )
+        })
+    }
+}
@@ -0,0 +1,245 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Checks of expected database provider behavior.
+
+mod batch_persistence;
+mod bucket_boundaries;
+mod deferred_delivery;
+mod delivery_intent_race;
+mod delivery_intents;
+mod delivery_slots;
+mod event_ordering;
+mod event_purge;
+mod integrity_persistence;
+mod object_count_snapshots;
+mod publish_grant_uses;
+mod topic_descriptors;
+
+use crate::ConformanceTarget;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::TopicEvent;
+use fragtale_dbp::mb::UniqueTime;
+use fragtale_dbp::mb::consumers::DeliveryIntentTemplate;
+use fragtale_dbp::mb::consumers::DeliveryIntentTemplateInsertable;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// Check of expected database provider behavior.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConformanceCheck {
+    /// Topics are set up and event descriptor versions are never replaced.
+    TopicDescriptors,
+    /// Events are returned ordered by unique time and paging resumes after
+    /// the last returned event.
+    EventOrdering,
+    /// Events at the edges of a bucket are counted and listed in the right
+    /// bucket.
+    BucketBoundaries,
+    /// Events are offered for delivery until the delivery is marked as done,
+    /// consumer progress is persisted and can be rewound.
+    DeliveryIntents,
+    /// Only one instance may deliver an event when instances race to reserve
+    /// the delivery.
+    DeliveryIntentRace,
+    /// Integrity protection data and references are persisted and listed in
+    /// order of protection time.
+    IntegrityPersistence,
+    /// All events of a batch are persisted and listed in the bucket of their
+    /// unique time.
+    BatchPersistence,
+    /// Publish grants can be used up to their max uses before they expire.
+    PublishGrantUses,
+    /// Events are not offered for delivery before their delivery time.
+    DeferredDelivery,
+    /// Purged events are removed together with their lookups and redacted
+    /// events keep their place with the replaced document.
+    EventPurge,
+    /// No more than the available delivery slots are claimed and a
+    /// redelivered event keeps the slot it holds.
+    DeliverySlots,
+    /// Snapshots of local object counts are kept per instance and type until
+    /// they are reset.
+    ObjectCountSnapshots,
+}
+
+impl ConformanceCheck {
+    /// Return all checks.
+    pub fn all() -> &'static [Self] {
+        &[
+            Self::TopicDescriptors,
+            Self::EventOrdering,
+            Self::BucketBoundaries,
+            Self::DeliveryIntents,
+            Self::DeliveryIntentRace,
+            Self::IntegrityPersistence,
+            Self::BatchPersistence,
+            Self::PublishGrantUses,
+            Self::DeferredDelivery,
+            Self::EventPurge,
+            Self::DeliverySlots,
+            Self::ObjectCountSnapshots,
+        ]
+    }
+
+    /// Return the name of the check. Example: "event_ordering"
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::TopicDescriptors => "topic_descriptors",
+            Self::EventOrdering => "event_ordering",
+            Self::BucketBoundaries => "bucket_boundaries",
+            Self::DeliveryIntents => "delivery_intents",
+            Self::DeliveryIntentRace => "delivery_intent_race",
+            Self::IntegrityPersistence => "integrity_persistence",
+            Self::BatchPersistence => "batch_persistence",
+            Self::PublishGrantUses => "publish_grant_uses",
+            Self::DeferredDelivery => "deferred_delivery",
+            Self::EventPurge => "event_purge",
+            Self::DeliverySlots => "delivery_slots",
+            Self::ObjectCountSnapshots => "object_count_snapshots",
+        }
+    }
+
+    /// Return `true` if the check applies to the target.
+    pub fn is_applicable(&self, target: &dyn ConformanceTarget) -> bool {
+        match self {
+            Self::DeliveryIntentRace => target.is_shared_by_instances(),
+            _ => true,
+        }
+    }
+
+    /// Run the check against the database provider.
+    ///
+    /// Return a description of the first unexpected behavior on failure.
+    pub async fn run(&self, dbp: &dyn DatabaseProviderFacades) -> Result<(), String> {
+        let topic_id = Self::unique_topic_id(self.as_str());
+        match self {
+            Self::TopicDescriptors => topic_descriptors::check(dbp, &topic_id).await,
+            Self::EventOrdering => event_ordering::check(dbp, &topic_id).await,
+            Self::BucketBoundaries => bucket_boundaries::check(dbp, &topic_id).await,
+            Self::DeliveryIntents => delivery_intents::check(dbp, &topic_id).await,
+            Self::DeliveryIntentRace => delivery_intent_race::check(dbp, &topic_id).await,
+            Self::IntegrityPersistence => integrity_persistence::check(dbp, &topic_id).await,
+            Self::BatchPersistence => batch_persistence::check(dbp, &topic_id).await,
+            Self::PublishGrantUses => publish_grant_uses::check(dbp, &topic_id).await,
+            Self::DeferredDelivery => deferred_delivery::check(dbp, &topic_id).await,
+            Self::EventPurge => event_purge::check(dbp, &topic_id).await,
+            Self::DeliverySlots => delivery_slots::check(dbp, &topic_id).await,
+            Self::ObjectCountSnapshots => object_count_snapshots::check(dbp, &topic_id).await,
+        }
+    }
+
+    /// Return a topic identifier that no other check run has used.
+    ///
+    /// This allows the checks to run against a database with existing data.
+    fn unique_topic_id(name: &str) -> String {
+        static RUN_COUNTER: AtomicU32 = AtomicU32::new(0);
+        let run = RUN_COUNTER.fetch_add(1, Ordering::Relaxed);
+        // Keep it short, since some databases limit the length of names
+        let prefix = name.split('_').next().unwrap_or(name);
+        format!("conf_{prefix}_{}_{run}", now_micros() % 1_000_000_000)
+    }
+}
+
+/// Return the current time in epoch microseconds.
+fn now_micros() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| u64::try_from(duration.as_micros()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+/// Fail with `msg` unless the `condition` holds.
+fn ensure(condition: bool, msg: &str) -> Result<(), String> {
+    if condition {
+        Ok(())
+    } else {
+        Err(msg.to_owned())
+    }
+}
+
+/// Set up the topic in the database.
+async fn ensure_topic(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    dbp.topic_facade()
+        .ensure_topic_setup(topic_id)
+        .await
+        .map_err(|e| format!("Failed to set up topic '{topic_id}': {e}"))
+}
+
+/// Persist a minimal event at the unique time.
+async fn persist_event(
+    dbp: &dyn DatabaseProviderFacades,
+    topic_id: &str,
+    event_id: &str,
+    unique_time: UniqueTime,
+) -> Result<(), String> {
+    let document = format!("{{\"event\":\"{event_id}\"}}");
+    dbp.event_facade()
+        .event_persist(
+            topic_id,
+            TopicEvent::new(
+                event_id,
+                &document,
+                0,
+                None,
+                None,
+                "",
+                &format!("correlation_{event_id}"),
+                HashMap::new(),
+                None,
+                unique_time,
+            ),
+        )
+        .await
+        .map(|_| ())
+        .map_err(|e| format!("Failed to persist event '{event_id}': {e}"))
+}
+
+/// Collects the delivery intent templates that a provider populates.
+#[derive(Default)]
+struct CollectingDeliveryCache {
+    delivery_intent_templates: Mutex<Vec<DeliveryIntentTemplate>>,
+}
+
+impl CollectingDeliveryCache {
+    /// Return the unique times of the collected delivery intent templates.
+    fn unique_times(&self) -> Vec<UniqueTime> {
+        self.delivery_intent_templates
+            .lock()
+            .unwrap()
+            .iter()
+            .map(DeliveryIntentTemplate::get_unique_time)
+            .collect()
+    }
+}
+
+impl DeliveryIntentTemplateInsertable for CollectingDeliveryCache {
+    fn insert(&self, delivery_intent_template: DeliveryIntentTemplate) {
+        self.delivery_intent_templates
+            .lock()
+            .unwrap()
+            .push(delivery_intent_template);
+    }
+
+    fn is_full(&self) -> bool {
+        false
+    }
+}
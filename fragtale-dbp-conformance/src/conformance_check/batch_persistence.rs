@@ -0,0 +1,110 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Persisting events in batches.
+
+use super::ensure;
+use super::ensure_topic;
+use super::now_micros;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::TopicEvent;
+use fragtale_dbp::mb::UniqueTime;
+use std::collections::HashMap;
+
+/// Check that all events of a batch are persisted and listed in the right
+/// bucket, also when the batch spans two buckets.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let event_facade = dbp.event_facade();
+    ensure_topic(dbp, topic_id).await?;
+    // End the batch in the first micros of the next bucket
+    let bucket = UniqueTime::new(now_micros(), 0).get_bucket() - 2;
+    let end_micros =
+        UniqueTime::from(UniqueTime::min_encoded_in_bucket(bucket + 1)).get_time_micros() + 2;
+    let unique_times = (0..5u64)
+        .map(|i| UniqueTime::new(end_micros - 4 + i, 1))
+        .collect::<Vec<_>>();
+    let topic_events = unique_times
+        .iter()
+        .enumerate()
+        .map(|(i, unique_time)| {
+            TopicEvent::new(
+                &format!("event_{i}"),
+                &format!("{{\"event\":{i}}}"),
+                0,
+                None,
+                None,
+                "",
+                &format!("correlation_event_{i}"),
+                HashMap::new(),
+                None,
+                *unique_time,
+            )
+        })
+        .collect::<Vec<_>>();
+    let (correlation_tokens, _retries) = event_facade
+        .events_persist(topic_id, topic_events)
+        .await
+        .map_err(|e| format!("Failed to persist batch of events: {e}"))?;
+    ensure(
+        correlation_tokens
+            .iter()
+            .enumerate()
+            .all(|(i, correlation_token)| correlation_token == &format!("correlation_event_{i}")),
+        "Correlation tokens must be returned in the order of the batch.",
+    )?;
+    let (first_entries, _more) = event_facade
+        .events_by_bucket(topic_id, bucket, None, 10)
+        .await;
+    let (second_entries, _more) = event_facade
+        .events_by_bucket(topic_id, bucket + 1, None, 10)
+        .await;
+    ensure(
+        first_entries
+            .iter()
+            .chain(second_entries.iter())
+            .map(|(unique_time, _event_id, _descriptor_version)| *unique_time)
+            .eq(unique_times.iter().copied())
+            && !first_entries.is_empty()
+            && !second_entries.is_empty(),
+        "Events of a batch must be listed in the bucket of their unique time.",
+    )?;
+    let (range_entries, _more) = event_facade
+        .events_by_unique_time_range(
+            topic_id,
+            UniqueTime::from(unique_times[0].as_encoded() - 1),
+            unique_times[4],
+            10,
+        )
+        .await;
+    ensure(
+        range_entries
+            .iter()
+            .map(|(unique_time, _event_id, _descriptor_version)| *unique_time)
+            .eq(unique_times.iter().copied()),
+        "A range spanning two buckets must list the events of both buckets.",
+    )?;
+    for (i, unique_time) in unique_times.iter().enumerate() {
+        ensure(
+            event_facade
+                .event_by_id(topic_id, &format!("event_{i}"))
+                .await
+                .is_some_and(|gist| gist.get_unique_time() == *unique_time),
+            "Each event of a batch must be returned by its event identifier.",
+        )?;
+    }
+    Ok(())
+}
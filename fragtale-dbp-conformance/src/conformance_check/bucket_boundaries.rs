@@ -0,0 +1,79 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Counting and listing of events at the edges of buckets.
+
+use super::ensure;
+use super::ensure_topic;
+use super::now_micros;
+use super::persist_event;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+
+/// Check that events at the edges of a bucket are counted and listed in the
+/// right bucket.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let event_facade = dbp.event_facade();
+    ensure_topic(dbp, topic_id).await?;
+    // Use two recent buckets of the same shelf
+    let mut bucket = UniqueTime::new(now_micros(), 0).get_bucket() - 2;
+    let shelf = UniqueTime::from(UniqueTime::min_encoded_in_bucket(bucket)).get_shelf();
+    if UniqueTime::first_bucket_in_shelf(shelf + 1) == bucket + 1 {
+        bucket -= 1;
+    }
+    let first_in_bucket = UniqueTime::from(UniqueTime::min_encoded_in_bucket(bucket));
+    let last_in_bucket = UniqueTime::from(UniqueTime::max_encoded_in_bucket(bucket));
+    let first_in_next = UniqueTime::from(UniqueTime::min_encoded_in_bucket(bucket + 1));
+    persist_event(dbp, topic_id, "last_in_bucket", last_in_bucket).await?;
+    persist_event(dbp, topic_id, "first_in_next", first_in_next).await?;
+    persist_event(dbp, topic_id, "first_in_bucket", first_in_bucket).await?;
+    ensure(
+        event_facade.event_count_by_bucket(topic_id, bucket).await == 2,
+        "Events at both edges of a bucket must be counted in the bucket.",
+    )?;
+    ensure(
+        event_facade
+            .event_count_by_bucket(topic_id, bucket + 1)
+            .await
+            == 1,
+        "The first event of the next bucket must be counted in the next bucket.",
+    )?;
+    let (entries, _more) = event_facade
+        .events_by_bucket(topic_id, bucket, None, 10)
+        .await;
+    ensure(
+        entries
+            .iter()
+            .map(|(unique_time, _event_id, _descriptor_version)| *unique_time)
+            .eq([first_in_bucket, last_in_bucket]),
+        "Only the events within the bucket must be listed for the bucket.",
+    )?;
+    let (buckets, _more) = event_facade
+        .buckets_by_shelf(topic_id, shelf, None, 10)
+        .await;
+    ensure(
+        buckets == [bucket, bucket + 1],
+        "All used buckets of the shelf must be listed in ascending order.",
+    )?;
+    let (buckets, _more) = event_facade
+        .buckets_by_shelf(topic_id, shelf, Some(bucket), 10)
+        .await;
+    ensure(
+        buckets == [bucket + 1],
+        "Listed buckets must start after the provided bucket.",
+    )
+}
@@ -0,0 +1,85 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Deferring delivery of events until their delivery time.
+
+use super::CollectingDeliveryCache;
+use super::ensure;
+use super::ensure_topic;
+use super::now_micros;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::TopicEvent;
+use fragtale_dbp::mb::UniqueTime;
+use fragtale_dbp::mb::consumers::DeliveryIntentTemplateInsertable;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Check that an event is not offered for delivery before its delivery time
+/// and that the attempted baseline is not moved past it.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let consumer_delivery_facade = dbp.consumer_delivery_facade();
+    let consumer_id = "conformance";
+    ensure_topic(dbp, topic_id).await?;
+    consumer_delivery_facade
+        .ensure_consumer_setup(topic_id, consumer_id, None, None)
+        .await
+        .map_err(|e| format!("Failed to set up consumer: {e}"))?;
+    let start_micros = now_micros() - 5_000_000;
+    let unique_times = (0..3u64)
+        .map(|i| UniqueTime::new(start_micros + i * 10, 1))
+        .collect::<Vec<_>>();
+    for (i, unique_time) in unique_times.iter().enumerate() {
+        let event_id = format!("event_{i}");
+        // Defer delivery of the middle event by an hour
+        let deliver_after_ts = (i == 1).then(|| now_micros() + 3_600_000_000);
+        dbp.event_facade()
+            .event_persist(
+                topic_id,
+                TopicEvent::new(
+                    &event_id,
+                    &format!("{{\"event\":\"{event_id}\"}}"),
+                    0,
+                    None,
+                    deliver_after_ts,
+                    "",
+                    &format!("correlation_{event_id}"),
+                    HashMap::new(),
+                    None,
+                    *unique_time,
+                ),
+            )
+            .await
+            .map_err(|e| format!("Failed to persist event '{event_id}': {e}"))?;
+    }
+    let attempted_low_exclusive = UniqueTime::from(unique_times[0].as_encoded() - 1);
+    let delivery_cache = Arc::new(CollectingDeliveryCache::default());
+    let diti: Box<Arc<dyn DeliveryIntentTemplateInsertable>> =
+        Box::new(Arc::clone(&delivery_cache));
+    let (last_attempted_ts, _any_new_found) = consumer_delivery_facade
+        .populate_delivery_cache_with_fresh(topic_id, consumer_id, diti, attempted_low_exclusive)
+        .await;
+    let mut offered = delivery_cache.unique_times();
+    offered.sort();
+    ensure(
+        offered == [unique_times[0], unique_times[2]],
+        "An event must not be offered for delivery before its delivery time.",
+    )?;
+    ensure(
+        last_attempted_ts < unique_times[1].as_encoded(),
+        "The attempted unique time must not move past an event that is not yet deliverable.",
+    )
+}
@@ -0,0 +1,85 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Resolution of instances racing to deliver the same event.
+
+use super::ensure;
+use super::ensure_topic;
+use super::now_micros;
+use super::persist_event;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+
+/// Freshness of delivery intents used by the check.
+const FRESHNESS_DURATION_MICROS: u64 = 10_000_000;
+
+/// Check that only one instance may deliver an event when instances race to
+/// reserve the delivery.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let consumer_delivery_facade = dbp.consumer_delivery_facade();
+    let consumer_id = "conformance";
+    ensure_topic(dbp, topic_id).await?;
+    consumer_delivery_facade
+        .ensure_consumer_setup(topic_id, consumer_id, None, None)
+        .await
+        .map_err(|e| format!("Failed to set up consumer: {e}"))?;
+    let sequential = UniqueTime::new(now_micros() - 5_000_000, 1);
+    let concurrent = UniqueTime::new(now_micros() - 5_000_000, 2);
+    persist_event(dbp, topic_id, "sequential", sequential).await?;
+    persist_event(dbp, topic_id, "concurrent", concurrent).await?;
+    let reserve = move |event_id: &'static str,
+                        unique_time: UniqueTime,
+                        instance_id: u16,
+                        failed_intent_ts_micros: Option<u64>| async move {
+        consumer_delivery_facade
+            .delivery_intent_reserve(
+                topic_id,
+                consumer_id,
+                event_id,
+                unique_time,
+                instance_id,
+                &None,
+                now_micros(),
+                FRESHNESS_DURATION_MICROS,
+                failed_intent_ts_micros,
+            )
+            .await
+    };
+    ensure(
+        reserve("sequential", sequential, 1, None).await,
+        "An uncontested delivery intent must be reserved.",
+    )?;
+    ensure(
+        !reserve("sequential", sequential, 2, None).await,
+        "A fresh delivery intent of another instance must not be taken over.",
+    )?;
+    consumer_delivery_facade
+        .delivery_intent_mark_done(topic_id, consumer_id, sequential, 1)
+        .await;
+    ensure(
+        !reserve("sequential", sequential, 2, Some(now_micros())).await,
+        "A delivery that is marked as done must never be reserved again.",
+    )?;
+    let (first, second) = futures::join!(
+        reserve("concurrent", concurrent, 3, None),
+        reserve("concurrent", concurrent, 4, None),
+    );
+    ensure(
+        first ^ second,
+        "Exactly one of two concurrent delivery intents must be reserved.",
+    )
+}
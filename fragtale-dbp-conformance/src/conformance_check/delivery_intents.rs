@@ -0,0 +1,159 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Offering of events for delivery and consumer progress.
+
+use super::CollectingDeliveryCache;
+use super::ensure;
+use super::ensure_topic;
+use super::now_micros;
+use super::persist_event;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+use fragtale_dbp::mb::consumers::DeliveryIntentTemplateInsertable;
+use std::sync::Arc;
+
+/// Check that events are offered for delivery until the delivery is marked
+/// as done, that the delivery is recorded, that consumer progress is persisted and that a rewound consumer
+/// is offered the events again.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let consumer_delivery_facade = dbp.consumer_delivery_facade();
+    let consumer_id = "conformance";
+    let instance_id = 1;
+    ensure_topic(dbp, topic_id).await?;
+    consumer_delivery_facade
+        .ensure_consumer_setup(topic_id, consumer_id, None, None)
+        .await
+        .map_err(|e| format!("Failed to set up consumer: {e}"))?;
+    ensure(
+        consumer_delivery_facade
+            .consumer_ids(topic_id)
+            .await
+            .iter()
+            .any(|id| id == consumer_id),
+        "A consumer that has been set up must be listed.",
+    )?;
+    let start_micros = now_micros() - 5_000_000;
+    let unique_times = (0..3u64)
+        .map(|i| UniqueTime::new(start_micros + i * 10, instance_id))
+        .collect::<Vec<_>>();
+    for (i, unique_time) in unique_times.iter().enumerate() {
+        persist_event(dbp, topic_id, &format!("event_{i}"), *unique_time).await?;
+    }
+    // Offered events are not required to be strictly ordered
+    let delivery_cache = Arc::new(CollectingDeliveryCache::default());
+    let diti: Box<Arc<dyn DeliveryIntentTemplateInsertable>> =
+        Box::new(Arc::clone(&delivery_cache));
+    consumer_delivery_facade
+        .populate_delivery_cache_with_fresh(topic_id, consumer_id, diti, unique_times[0])
+        .await;
+    let mut offered = delivery_cache.unique_times();
+    offered.sort();
+    ensure(
+        offered == unique_times[1..],
+        "All events after the attempted unique time must be offered for delivery.",
+    )?;
+    ensure(
+        consumer_delivery_facade
+            .delivery_intent_reserve(
+                topic_id,
+                consumer_id,
+                "event_1",
+                unique_times[1],
+                instance_id,
+                &None,
+                now_micros(),
+                10_000_000,
+                None,
+            )
+            .await,
+        "An uncontested delivery intent must be reserved.",
+    )?;
+    consumer_delivery_facade
+        .delivery_intent_mark_done(topic_id, consumer_id, unique_times[1], instance_id)
+        .await;
+    let delivery_cache = Arc::new(CollectingDeliveryCache::default());
+    let diti: Box<Arc<dyn DeliveryIntentTemplateInsertable>> =
+        Box::new(Arc::clone(&delivery_cache));
+    consumer_delivery_facade
+        .populate_delivery_cache_with_fresh(topic_id, consumer_id, diti, unique_times[0])
+        .await;
+    ensure(
+        delivery_cache.unique_times() == unique_times[2..],
+        "An event that is marked as done must not be offered for delivery again.",
+    )?;
+    let (delivery_records, _more) = consumer_delivery_facade
+        .delivery_records_by_bucket(topic_id, consumer_id, unique_times[1].get_bucket(), 100)
+        .await;
+    ensure(
+        delivery_records.iter().any(|delivery_record| {
+            delivery_record.get_unique_time() == unique_times[1]
+                && delivery_record.get_event_id() == "event_1"
+                && delivery_record.is_done()
+        }),
+        "A delivery that is marked as done must be listed as a done delivery record.",
+    )?;
+    consumer_delivery_facade
+        .consumer_set_attempted_by_id(topic_id, consumer_id, unique_times[2])
+        .await;
+    ensure(
+        consumer_delivery_facade
+            .consumer_get_attempted_by_id(topic_id, consumer_id)
+            .await
+            == Some(unique_times[2]),
+        "The attempted unique time of a consumer must be persisted.",
+    )?;
+    consumer_delivery_facade
+        .consumer_set_done_by_id(topic_id, consumer_id, unique_times[1])
+        .await;
+    ensure(
+        consumer_delivery_facade
+            .consumer_get_done_by_id(topic_id, consumer_id)
+            .await
+            == Some(unique_times[1]),
+        "The done unique time of a consumer must be persisted.",
+    )?;
+    ensure(
+        consumer_delivery_facade
+            .consumer_seek(topic_id, consumer_id, unique_times[0])
+            .await,
+        "A consumer must be possible to rewind.",
+    )?;
+    ensure(
+        consumer_delivery_facade
+            .consumer_get_attempted_by_id(topic_id, consumer_id)
+            .await
+            == Some(unique_times[0])
+            && consumer_delivery_facade
+                .consumer_get_done_by_id(topic_id, consumer_id)
+                .await
+                == Some(unique_times[0]),
+        "A rewound consumer must have both unique times at the new position.",
+    )?;
+    let delivery_cache = Arc::new(CollectingDeliveryCache::default());
+    let diti: Box<Arc<dyn DeliveryIntentTemplateInsertable>> =
+        Box::new(Arc::clone(&delivery_cache));
+    consumer_delivery_facade
+        .populate_delivery_cache_with_fresh(topic_id, consumer_id, diti, unique_times[0])
+        .await;
+    let mut offered = delivery_cache.unique_times();
+    offered.sort();
+    ensure(
+        offered == unique_times[1..],
+        "Events after the position of a rewound consumer must be offered for delivery again.",
+    )
+}
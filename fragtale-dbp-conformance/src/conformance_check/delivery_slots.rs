@@ -0,0 +1,75 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Bounding concurrent deliveries with shared delivery slots.
+
+use super::ensure;
+use super::ensure_topic;
+use super::now_micros;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+
+/// Check that no more than the available slots are claimed, that a
+/// redelivered event keeps the slot it holds and that a released slot can be
+/// claimed again.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let consumer_delivery_facade = dbp.consumer_delivery_facade();
+    let consumer_id = "conformance";
+    ensure_topic(dbp, topic_id).await?;
+    consumer_delivery_facade
+        .ensure_consumer_setup(topic_id, consumer_id, None, None)
+        .await
+        .map_err(|e| format!("Failed to set up consumer: {e}"))?;
+    let slots = 2;
+    let time_to_live_micros = 60_000_000;
+    let start_micros = now_micros();
+    let unique_times = (0..3u64)
+        .map(|i| UniqueTime::new(start_micros + i * 10, 1))
+        .collect::<Vec<_>>();
+    let claim = move |unique_time: UniqueTime| {
+        consumer_delivery_facade.delivery_slot_claim(
+            topic_id,
+            consumer_id,
+            slots,
+            unique_time,
+            time_to_live_micros,
+        )
+    };
+    ensure(
+        claim(unique_times[0]).await,
+        "A free delivery slot must be claimable.",
+    )?;
+    ensure(
+        claim(unique_times[0]).await,
+        "A redelivered event must keep the delivery slot it holds.",
+    )?;
+    ensure(
+        claim(unique_times[1]).await,
+        "A redelivered event must not claim a second delivery slot.",
+    )?;
+    ensure(
+        !claim(unique_times[2]).await,
+        "No more than the available delivery slots may be claimed.",
+    )?;
+    consumer_delivery_facade
+        .delivery_slot_release(topic_id, consumer_id, unique_times[0])
+        .await;
+    ensure(
+        claim(unique_times[2]).await,
+        "A released delivery slot must be claimable again.",
+    )
+}
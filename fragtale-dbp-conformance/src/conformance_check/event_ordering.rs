@@ -0,0 +1,137 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Ordering and paging of events.
+
+use super::ensure;
+use super::ensure_topic;
+use super::now_micros;
+use super::persist_event;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::UniqueTime;
+use futures::StreamExt;
+
+/// Check that events are returned ordered by unique time and that paging
+/// resumes after the last returned event.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let event_facade = dbp.event_facade();
+    ensure_topic(dbp, topic_id).await?;
+    // Start a bit into the current bucket, so all events end up in it
+    let bucket = UniqueTime::new(now_micros(), 0).get_bucket();
+    let start_micros =
+        UniqueTime::from(UniqueTime::min_encoded_in_bucket(bucket)).get_time_micros() + 1_000;
+    let unique_times = (0..5u64)
+        .map(|i| UniqueTime::new(start_micros + i * 10, 1))
+        .collect::<Vec<_>>();
+    // Persist out of order
+    for i in [3, 1, 4, 0, 2] {
+        persist_event(dbp, topic_id, &format!("event_{i}"), unique_times[i]).await?;
+    }
+    let (entries, more) = event_facade
+        .events_by_bucket(topic_id, bucket, None, 100)
+        .await;
+    ensure(
+        entries
+            .iter()
+            .map(|(unique_time, _event_id, _descriptor_version)| *unique_time)
+            .eq(unique_times.iter().copied()),
+        "Events in a bucket must be ordered by unique time.",
+    )?;
+    ensure(
+        entries
+            .iter()
+            .enumerate()
+            .all(|(i, (_unique_time, event_id, _))| event_id == &format!("event_{i}")),
+        "Events in a bucket must be returned with their event identifier.",
+    )?;
+    ensure(
+        !more,
+        "No more events must be indicated for a partial page.",
+    )?;
+    let (first_page, more) = event_facade
+        .events_by_bucket(topic_id, bucket, None, 2)
+        .await;
+    ensure(
+        first_page.len() == 2 && first_page[1].0 == unique_times[1] && more,
+        "A full page must hold the first events and indicate more events.",
+    )?;
+    let (second_page, _more) = event_facade
+        .events_by_bucket(topic_id, bucket, Some(unique_times[1]), 2)
+        .await;
+    ensure(
+        second_page
+            .iter()
+            .map(|(unique_time, _event_id, _descriptor_version)| *unique_time)
+            .eq(unique_times[2..4].iter().copied()),
+        "A page must start right after the provided unique time.",
+    )?;
+    let streamed = event_facade
+        .events_by_bucket_stream(topic_id, bucket, None, 2)
+        .map(|(unique_time, _event_id, _descriptor_version)| unique_time)
+        .collect::<Vec<_>>()
+        .await;
+    ensure(
+        streamed == unique_times,
+        "Streamed events must be complete and ordered by unique time.",
+    )?;
+    let (range_page, more) = event_facade
+        .events_by_unique_time_range(topic_id, unique_times[0], unique_times[3], 2)
+        .await;
+    ensure(
+        range_page
+            .iter()
+            .map(|(unique_time, _event_id, _descriptor_version)| *unique_time)
+            .eq(unique_times[1..3].iter().copied())
+            && more,
+        "A full page of a range must start right after the lower bound and indicate more events.",
+    )?;
+    let (range_page, more) = event_facade
+        .events_by_unique_time_range(topic_id, unique_times[2], unique_times[3], 2)
+        .await;
+    ensure(
+        range_page
+            .iter()
+            .map(|(unique_time, _event_id, _descriptor_version)| *unique_time)
+            .eq(unique_times[3..4].iter().copied())
+            && !more,
+        "A range must include events at the upper bound, but none after it.",
+    )?;
+    // The same document published again
+    let republished = UniqueTime::new(start_micros + 100, 1);
+    persist_event(dbp, topic_id, "event_0", republished).await?;
+    ensure(
+        event_facade
+            .event_by_id(topic_id, "event_0")
+            .await
+            .is_some_and(|gist| gist.get_unique_time() == republished),
+        "The latest event must be returned for an event identifier.",
+    )?;
+    ensure(
+        event_facade
+            .event_by_id_and_unique_time(topic_id, "event_0", unique_times[0])
+            .await
+            .is_some_and(|gist| gist.get_unique_time() == unique_times[0]),
+        "An earlier event must be returned by its unique time.",
+    )?;
+    ensure(
+        event_facade
+            .event_document_by_correlation_token(topic_id, "correlation_event_3")
+            .await
+            .is_some_and(|gist| gist.get_unique_time() == unique_times[3]),
+        "An event must be returned by its correlation token.",
+    )
+}
@@ -0,0 +1,129 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Purging and redacting persisted events.
+
+use super::ensure;
+use super::ensure_topic;
+use super::now_micros;
+use super::persist_event;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::EventAnnotation;
+use fragtale_dbp::mb::UniqueTime;
+
+/// Check that a purged event and its lookups are removed while annotations
+/// remain until the last event with the identifier is gone, and that a
+/// redacted event keeps its place with the replaced document.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let event_facade = dbp.event_facade();
+    ensure_topic(dbp, topic_id).await?;
+    let micros = now_micros();
+    let first = UniqueTime::new(micros - 3, 0);
+    let second = UniqueTime::new(micros - 2, 0);
+    let redacted = UniqueTime::new(micros - 1, 0);
+    // The same document published twice shares the event identifier
+    persist_event(dbp, topic_id, "purged", first).await?;
+    persist_event(dbp, topic_id, "purged", second).await?;
+    persist_event(dbp, topic_id, "redacted", redacted).await?;
+    for event_id in ["purged", "redacted"] {
+        event_facade
+            .event_annotation_persist(
+                topic_id,
+                EventAnnotation::new(event_id, micros, "conformance", "note", "value"),
+            )
+            .await;
+    }
+    ensure(
+        event_facade
+            .event_unique_times_by_id(topic_id, "purged", 10)
+            .await
+            == vec![second, first],
+        "Unique times of an event identifier must be returned newest first.",
+    )?;
+    ensure(
+        event_facade.event_purge(topic_id, "purged", second).await
+            && !event_facade.event_purge(topic_id, "purged", second).await,
+        "Only an existing event must be reported as purged.",
+    )?;
+    ensure(
+        event_facade
+            .event_by_id_and_unique_time(topic_id, "purged", second)
+            .await
+            .is_none()
+            && event_facade
+                .event_unique_times_by_id(topic_id, "purged", 10)
+                .await
+                == vec![first],
+        "A purged event must no longer be returned.",
+    )?;
+    ensure(
+        !event_facade
+            .event_annotations_by_event_id(topic_id, "purged", 10)
+            .await
+            .is_empty(),
+        "Annotations must remain while an event with the identifier remains.",
+    )?;
+    ensure(
+        event_facade.event_purge(topic_id, "purged", first).await,
+        "The last event with the identifier must be purged.",
+    )?;
+    ensure(
+        event_facade
+            .event_document_by_correlation_token(topic_id, "correlation_purged")
+            .await
+            .is_none(),
+        "A purged event must not be returned by its correlation token.",
+    )?;
+    ensure(
+        event_facade
+            .event_annotations_by_event_id(topic_id, "purged", 10)
+            .await
+            .is_empty(),
+        "Annotations must be purged with the last event with the identifier.",
+    )?;
+    ensure(
+        !event_facade
+            .event_topics_by_event_id("purged", 1024)
+            .await
+            .iter()
+            .any(|(event_topic_id, _unique_time)| event_topic_id == topic_id),
+        "The topic lookup must be purged with the last event with the identifier.",
+    )?;
+    ensure(
+        event_facade
+            .event_redact(topic_id, "redacted", redacted, "{}", "protection")
+            .await,
+        "An existing event must be reported as redacted.",
+    )?;
+    ensure(
+        event_facade
+            .event_by_id_and_unique_time(topic_id, "redacted", redacted)
+            .await
+            .is_some_and(|gist| {
+                gist.get_document() == "{}" && gist.get_protection_ref() == "protection"
+            }),
+        "A redacted event must be returned with the replaced document.",
+    )?;
+    ensure(
+        event_facade
+            .event_annotations_by_event_id(topic_id, "redacted", 10)
+            .await
+            .is_empty(),
+        "Annotations must be removed from a redacted event.",
+    )?;
+    Ok(())
+}
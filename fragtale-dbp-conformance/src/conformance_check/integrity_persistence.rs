@@ -0,0 +1,105 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Persistence of integrity protection data.
+
+use super::ensure;
+use super::ensure_topic;
+use super::now_micros;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use futures::StreamExt;
+
+/// Check that integrity protection data and references are persisted and
+/// listed in order of protection time.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let integrity_protection_facade = dbp.integrity_protection_facade();
+    let level = 0;
+    ensure_topic(dbp, topic_id).await?;
+    // Stay well within a single level 0 interval
+    let start_ts_micros = now_micros() - now_micros() % 60_000_000;
+    let protections = (0..3u64)
+        .map(|i| {
+            (
+                format!("protection_{i}"),
+                start_ts_micros + i * 1_000,
+                format!("data_{i}"),
+            )
+        })
+        .collect::<Vec<_>>();
+    for (id, protection_ts_micros, protection_data) in protections.iter().rev() {
+        integrity_protection_facade
+            .integrity_protection_persist(
+                topic_id,
+                id,
+                protection_data,
+                *protection_ts_micros,
+                level,
+            )
+            .await;
+    }
+    let (id, protection_ts_micros, protection_data) = &protections[1];
+    ensure(
+        integrity_protection_facade
+            .integrity_protection_by_id_and_ts(topic_id, id, *protection_ts_micros)
+            .await
+            == Some((protection_data.to_owned(), None)),
+        "Persisted protection data must be returned without a reference.",
+    )?;
+    integrity_protection_facade
+        .integrity_protection_set_protection_ref(
+            topic_id,
+            id,
+            *protection_ts_micros,
+            "protection_ref",
+        )
+        .await;
+    ensure(
+        integrity_protection_facade
+            .integrity_protection_by_id_and_ts(topic_id, id, *protection_ts_micros)
+            .await
+            == Some((
+                protection_data.to_owned(),
+                Some("protection_ref".to_owned()),
+            )),
+        "The protection reference must be persisted with the protection data.",
+    )?;
+    let batch = integrity_protection_facade
+        .integrity_batch_in_interval_by_level_and_time(topic_id, level, start_ts_micros, 10)
+        .await;
+    ensure(
+        batch
+            .iter()
+            .map(
+                |(id, protection_ts_micros, _protection_data, _protection_ref)| {
+                    (id.as_str(), *protection_ts_micros)
+                },
+            )
+            .eq(protections
+                .iter()
+                .map(|(id, protection_ts_micros, _)| (id.as_str(), *protection_ts_micros))),
+        "Protections must be listed in order of protection time.",
+    )?;
+    let streamed = integrity_protection_facade
+        .integrity_stream_in_interval_by_level_and_time(topic_id, level, protections[1].1, 1)
+        .map(|(id, _protection_ts_micros, _protection_data, _protection_ref)| id)
+        .collect::<Vec<_>>()
+        .await;
+    ensure(
+        streamed == ["protection_1", "protection_2"],
+        "Streamed protections must start at the provided protection time.",
+    )
+}
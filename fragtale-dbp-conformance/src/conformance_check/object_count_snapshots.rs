@@ -0,0 +1,76 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Persisted snapshots of local object counts.
+
+use super::ensure;
+use super::ensure_topic;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::ObjectCountType;
+
+/// Check that the latest snapshot of each object count type is returned for
+/// the instance it was taken by and that a reset removes all snapshots.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let event_tracking_facade = dbp.event_tracking_facade();
+    ensure_topic(dbp, topic_id).await?;
+    ensure(
+        event_tracking_facade
+            .object_count_snapshots_by_instance(topic_id, 0)
+            .await
+            .is_empty(),
+        "A new topic must not have any object count snapshots.",
+    )?;
+    for (object_count_type, value) in [
+        (ObjectCountType::Events, 5),
+        (ObjectCountType::Events, 7),
+        (ObjectCountType::DoneDeliveryIntents, 3),
+    ] {
+        event_tracking_facade
+            .object_count_snapshot_upsert(topic_id, &object_count_type, 0, value)
+            .await;
+    }
+    event_tracking_facade
+        .object_count_snapshot_upsert(topic_id, &ObjectCountType::Events, 1, 11)
+        .await;
+    let mut snapshots = event_tracking_facade
+        .object_count_snapshots_by_instance(topic_id, 0)
+        .await;
+    snapshots.sort();
+    ensure(
+        snapshots
+            == vec![
+                (ObjectCountType::Events, 7),
+                (ObjectCountType::DoneDeliveryIntents, 3),
+            ],
+        "Only the latest snapshot of the instance must be returned for each type.",
+    )?;
+    event_tracking_facade
+        .object_count_snapshots_delete(topic_id)
+        .await;
+    ensure(
+        event_tracking_facade
+            .object_count_snapshots_by_instance(topic_id, 0)
+            .await
+            .is_empty()
+            && event_tracking_facade
+                .object_count_snapshots_by_instance(topic_id, 1)
+                .await
+                .is_empty(),
+        "Snapshots of all instances must be removed on reset.",
+    )?;
+    Ok(())
+}
@@ -0,0 +1,53 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Limited uses of publish grants.
+
+use super::ensure;
+use super::now_micros;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+
+/// Check that a publish grant can't be used more than its max uses and that
+/// expired grants can't be used at all.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let authorization_facade = dbp.authorization_facade();
+    // The topic identifier is unique per run and is reused as grant identifier
+    let grant_uid = topic_id;
+    let expires = now_micros() + 60_000_000;
+    for use_number in 1..=2 {
+        ensure(
+            authorization_facade
+                .publish_grant_use(grant_uid, 2, expires)
+                .await,
+            &format!("Use {use_number} of 2 of the publish grant must be allowed."),
+        )?;
+    }
+    ensure(
+        !authorization_facade
+            .publish_grant_use(grant_uid, 2, expires)
+            .await,
+        "A publish grant must not be used more than its max uses.",
+    )?;
+    let expired_grant_uid = topic_id.to_owned() + "_expired";
+    ensure(
+        !authorization_facade
+            .publish_grant_use(&expired_grant_uid, 2, now_micros() - 1)
+            .await,
+        "An expired publish grant must not be used.",
+    )?;
+    Ok(())
+}
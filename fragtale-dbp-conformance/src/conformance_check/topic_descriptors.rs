@@ -0,0 +1,64 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Topic setup and event descriptor persistence.
+
+use super::ensure;
+use super::ensure_topic;
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+
+/// Check that topics are set up and event descriptor versions are never
+/// replaced.
+pub async fn check(dbp: &dyn DatabaseProviderFacades, topic_id: &str) -> Result<(), String> {
+    let topic_facade = dbp.topic_facade();
+    ensure_topic(dbp, topic_id).await?;
+    ensure(
+        topic_facade.topic_exists(topic_id).await,
+        "A topic that has been set up must exist.",
+    )?;
+    let v1 = r#"{"version":1}"#;
+    let v2 = r#"{"version":2}"#;
+    ensure(
+        topic_facade
+            .event_descriptor_persists(topic_id, 1, None, &None, v1)
+            .await,
+        "A new event descriptor version must be persisted.",
+    )?;
+    ensure(
+        !topic_facade
+            .event_descriptor_persists(topic_id, 1, None, &None, r#"{"version":1,"other":1}"#)
+            .await,
+        "An existing event descriptor version must not be replaced.",
+    )?;
+    topic_facade
+        .event_descriptor_persists(topic_id, 2, Some(1), &None, v2)
+        .await;
+    let all = topic_facade
+        .event_descriptors_by_topic_id(topic_id, None)
+        .await;
+    ensure(
+        all.len() == 2 && all.iter().any(|ed| ed == v1) && all.iter().any(|ed| ed == v2),
+        "All persisted event descriptor versions must be returned unmodified.",
+    )?;
+    let newer = topic_facade
+        .event_descriptors_by_topic_id(topic_id, Some(1))
+        .await;
+    ensure(
+        newer == [v2],
+        "Only event descriptor versions above the minimum version must be returned.",
+    )
+}
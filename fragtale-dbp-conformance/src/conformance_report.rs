@@ -0,0 +1,63 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Outcome of running the conformance test suite.
+
+use crate::ConformanceCheck;
+use std::fmt;
+
+/// Outcome of running the conformance test suite.
+pub struct ConformanceReport {
+    outcomes: Vec<(ConformanceCheck, Result<(), String>)>,
+}
+
+impl ConformanceReport {
+    /// Return a new instance.
+    pub(crate) fn new(outcomes: Vec<(ConformanceCheck, Result<(), String>)>) -> Self {
+        Self { outcomes }
+    }
+
+    /// Return the checks that were run and why they failed (if they did).
+    pub fn get_outcomes(&self) -> &[(ConformanceCheck, Result<(), String>)] {
+        &self.outcomes
+    }
+
+    /// Return `true` if the provider passed all checks.
+    pub fn is_conformant(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|(_check, outcome)| outcome.is_ok())
+    }
+
+    /// Panic with a description of each failed check unless the provider
+    /// passed all checks.
+    pub fn assert_conformant(&self) {
+        assert!(self.is_conformant(), "{self}");
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (check, outcome) in &self.outcomes {
+            match outcome {
+                Ok(()) => writeln!(f, "{}: ok", check.as_str())?,
+                Err(msg) => writeln!(f, "{}: FAILED: {msg}", check.as_str())?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Runner of the conformance checks.
+
+use crate::ConformanceCheck;
+use crate::ConformanceReport;
+use crate::ConformanceTarget;
+
+/// Runner of the conformance checks.
+pub struct ConformanceSuite {}
+
+impl ConformanceSuite {
+    /// Run all applicable [ConformanceCheck]s against the target.
+    pub async fn run(target: &dyn ConformanceTarget) -> ConformanceReport {
+        Self::run_checks(target, ConformanceCheck::all()).await
+    }
+
+    /// Run the provided [ConformanceCheck]s against the target.
+    ///
+    /// Checks that don't apply to the target are skipped.
+    pub async fn run_checks(
+        target: &dyn ConformanceTarget,
+        checks: &[ConformanceCheck],
+    ) -> ConformanceReport {
+        let mut outcomes = Vec::with_capacity(checks.len());
+        for check in checks {
+            if !check.is_applicable(target) {
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!("Skipping '{}'. Not applicable.", check.as_str());
+                }
+                continue;
+            }
+            let dbp = target.database_provider().await;
+            let outcome = check.run(dbp.as_ref()).await;
+            if let Err(msg) = &outcome {
+                log::info!("Conformance check '{}' failed: {msg}", check.as_str());
+            }
+            outcomes.push((*check, outcome));
+        }
+        ConformanceReport::new(outcomes)
+    }
+}
@@ -0,0 +1,40 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Database provider implementation under test.
+
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use std::sync::Arc;
+
+/// Database provider implementation under test.
+#[async_trait::async_trait]
+pub trait ConformanceTarget: Send + Sync {
+    /// Return a database provider to run a check against.
+    ///
+    /// Each check uses topics of its own, so the same database may be
+    /// returned for all checks.
+    async fn database_provider(&self) -> Arc<dyn DatabaseProviderFacades>;
+
+    /// Return `true` if the database is shared by multiple message broker
+    /// instances.
+    ///
+    /// Checks of how concurrent delivery attempts from different instances
+    /// are resolved are only run when this is the case.
+    fn is_shared_by_instances(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,96 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Benchmark of reading event documents for delivery.
+//!
+//! Run with `cargo bench -p fragtale_dbp_mem`.
+
+use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+use fragtale_dbp::mb::TopicEvent;
+use fragtale_dbp::mb::UniqueTime;
+use fragtale_dbp_mem::InMemoryDatabaseProvider;
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::Instant;
+
+const TOPIC_ID: &str = "bench";
+const ITERATIONS: u32 = 10_000;
+
+fn main() {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    for document_size in [1_024, 64 * 1_024, 1_024 * 1_024] {
+        runtime.block_on(bench_document_size(document_size));
+    }
+}
+
+/// Measure reading an event for delivery and handing the document on to a
+/// cache and a response.
+async fn bench_document_size(document_size: usize) {
+    let dbp = InMemoryDatabaseProvider::new().await.as_database_provider();
+    let unique_time = UniqueTime::new(1_750_000_000_000_000, 1);
+    let document = format!("{{\"data\":\"{}\"}}", "x".repeat(document_size));
+    dbp.event_facade()
+        .event_persist(
+            TOPIC_ID,
+            TopicEvent::new(
+                "event",
+                &document,
+                0,
+                None,
+                None,
+                "",
+                "",
+                HashMap::new(),
+                None,
+                unique_time,
+            ),
+        )
+        .await
+        .unwrap();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let gist = dbp
+            .event_facade()
+            .event_by_id_and_unique_time(TOPIC_ID, "event", unique_time)
+            .await
+            .unwrap();
+        let (_unique_time, document, _protection_ref, _correlation_token, _priority) =
+            gist.into_parts();
+        // Shared by the read cache and the response
+        let cached = black_box(std::sync::Arc::clone(&document));
+        black_box((cached, document));
+    }
+    let shared_nanos = start.elapsed().as_nanos() / u128::from(ITERATIONS);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let gist = dbp
+            .event_facade()
+            .event_by_id_and_unique_time(TOPIC_ID, "event", unique_time)
+            .await
+            .unwrap();
+        // Baseline: Owned copies for the read cache and the response
+        let cached = black_box(gist.get_document().to_owned());
+        let response = black_box(gist.get_document().to_owned());
+        black_box((cached, response));
+    }
+    let copied_nanos = start.elapsed().as_nanos() / u128::from(ITERATIONS);
+    println!(
+        "delivery_path document_size={document_size}: shared {shared_nanos} ns/op, copied {copied_nanos} ns/op"
+    );
+}
@@ -25,12 +25,18 @@ use self::inmem_topic::InMemConsumer;
 use self::inmem_topic::InMemTopic;
 use crossbeam_skiplist::SkipMap;
 use fragtale_dbp::dbp::DatabaseProvider;
+use fragtale_dbp::mb::IndexRebuildProgress;
 use std::sync::Arc;
 
 /// Ephemeral in-memory implementation of [DatabaseProvider].
 pub struct InMemoryDatabaseProvider {
     topics: SkipMap<String, InMemTopic>,
     topic_descriptors: SkipMap<String, SkipMap<u64, String>>,
+    topic_replies: SkipMap<String, (String, String)>,
+    bulk_ingest_windows: SkipMap<String, (u64, u64, bool)>,
+    index_rebuilds: SkipMap<String, IndexRebuildProgress>,
+    event_topics: SkipMap<String, SkipMap<String, u64>>,
+    shared_schemas: SkipMap<String, SkipMap<u64, String>>,
 }
 
 impl InMemoryDatabaseProvider {
@@ -42,6 +48,11 @@ impl InMemoryDatabaseProvider {
         Arc::new(Self {
             topics: SkipMap::default(),
             topic_descriptors: SkipMap::default(),
+            topic_replies: SkipMap::default(),
+            bulk_ingest_windows: SkipMap::default(),
+            index_rebuilds: SkipMap::default(),
+            event_topics: SkipMap::default(),
+            shared_schemas: SkipMap::default(),
         })
     }
 
@@ -62,3 +73,32 @@ impl InMemoryDatabaseProvider {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+    use fragtale_dbp_conformance::ConformanceSuite;
+    use fragtale_dbp_conformance::ConformanceTarget;
+
+    struct InMemConformanceTarget {}
+
+    #[async_trait::async_trait]
+    impl ConformanceTarget for InMemConformanceTarget {
+        async fn database_provider(&self) -> Arc<dyn DatabaseProviderFacades> {
+            Arc::new(InMemoryDatabaseProvider::new().await.as_database_provider())
+        }
+
+        fn is_shared_by_instances(&self) -> bool {
+            // Each broker instance has storage of its own
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn conforms_to_provider_semantics() {
+        ConformanceSuite::run(&InMemConformanceTarget {})
+            .await
+            .assert_conformant();
+    }
+}
@@ -20,12 +20,16 @@
 use crate::InMemoryDatabaseProvider;
 use crossbeam_skiplist::SkipSet;
 use fragtale_dbp::dbp::facades::AuthorizationFacade;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 /// Ephemeral in-memory specific database code
 pub struct InMemAuthorizationFacade {
     //inmem_provider: Arc<InMemoryDatabaseProvider>,
     authorizations: SkipSet<String>,
+    /// Consumed uses and expiration of publish grants by grant identifier.
+    publish_grant_uses: Mutex<HashMap<String, (u32, u64)>>,
 }
 
 impl InMemAuthorizationFacade {
@@ -34,6 +38,7 @@ impl InMemAuthorizationFacade {
         Self {
             //inmem_provider: Arc::clone(inmem_provider),
             authorizations: SkipSet::default(),
+            publish_grant_uses: Mutex::default(),
         }
     }
 
@@ -52,12 +57,38 @@ impl AuthorizationFacade for InMemAuthorizationFacade {
     }
 
     async fn is_any_authorized_to_resource(&self, resource: &str) -> bool {
-        let pat = ";".to_string() + resource;
+        let pat = "|".to_string() + resource;
         self.authorizations
             .iter()
             .any(|entry| entry.value().ends_with(&pat))
     }
 
+    async fn identities_authorized_to_resource(
+        &self,
+        resource: &str,
+        max_results: usize,
+    ) -> Vec<String> {
+        let pat = "|".to_string() + resource;
+        self.authorizations
+            .iter()
+            .filter_map(|entry| entry.value().strip_suffix(&pat).map(str::to_owned))
+            .take(max_results)
+            .collect()
+    }
+
+    async fn resource_grants(&self, max_results: usize) -> Vec<(String, String)> {
+        self.authorizations
+            .iter()
+            .filter_map(|entry| {
+                entry
+                    .value()
+                    .split_once('|')
+                    .map(|(identity, resource)| (resource.to_owned(), identity.to_owned()))
+            })
+            .take(max_results)
+            .collect()
+    }
+
     async fn grant_access_to_resource_for(
         &self,
         identity: &str,
@@ -78,4 +109,21 @@ impl AuthorizationFacade for InMemAuthorizationFacade {
             .remove(&Self::to_key(identity, resource));
         true
     }
+
+    async fn publish_grant_use(&self, grant_uid: &str, max_uses: u32, expires: u64) -> bool {
+        let now = fragtale_client::time::get_timestamp_micros();
+        if expires <= now {
+            return false;
+        }
+        let mut publish_grant_uses = self.publish_grant_uses.lock().unwrap();
+        publish_grant_uses.retain(|_, (_, expires)| *expires > now);
+        let (uses, _) = publish_grant_uses
+            .entry(grant_uid.to_owned())
+            .or_insert((0, expires));
+        if *uses >= max_uses {
+            return false;
+        }
+        *uses += 1;
+        true
+    }
 }
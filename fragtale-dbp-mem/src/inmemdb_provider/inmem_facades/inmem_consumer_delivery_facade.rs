@@ -23,6 +23,8 @@ use fragtale_dbp::dbp::facades::ConsumerDeliveryFacade;
 use fragtale_dbp::mb::MessageBrokerError;
 use fragtale_dbp::mb::UniqueTime;
 use fragtale_dbp::mb::consumers::DeliveryIntentTemplateInsertable;
+use fragtale_dbp::mb::consumers::DeliveryRecord;
+use fragtale_dbp::mb::consumers::RetryBackoff;
 use std::sync::Arc;
 
 /// Ephemeral in-memory specific database code
@@ -43,15 +45,68 @@ impl InMemConsumerDeliveryFacade {
 impl ConsumerDeliveryFacade for InMemConsumerDeliveryFacade {
     async fn ensure_consumer_setup(
         &self,
-        _topic_id: &str,
-        _consumer_id: &str,
+        topic_id: &str,
+        consumer_id: &str,
         _baseline_ts: Option<u64>,
         _encoded_descriptor_version: Option<u64>,
     ) -> Result<(), MessageBrokerError> {
-        // NOOP
+        // Register the consumer, so it is listed for the topic
+        self.inmem_provider.consumer_by_id(topic_id, consumer_id);
         Ok(())
     }
 
+    async fn consumer_ids(&self, topic_id: &str) -> Vec<String> {
+        self.inmem_provider
+            .topics
+            .get(topic_id)
+            .map(|topic_entry| {
+                topic_entry
+                    .value()
+                    .consumers
+                    .iter()
+                    .map(|entry| entry.key().to_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    async fn consumer_definition_persist(
+        &self,
+        topic_id: &str,
+        name: &str,
+        definition: &str,
+        _updated_ts_micros: u64,
+    ) -> bool {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .consumer_definitions
+            .insert(name.to_owned(), definition.to_owned());
+        true
+    }
+
+    async fn consumer_definition_remove(&self, topic_id: &str, name: &str) {
+        if let Some(topic_entry) = self.inmem_provider.topics.get(topic_id) {
+            topic_entry.value().consumer_definitions.remove(name);
+        }
+    }
+
+    async fn consumer_definitions(&self, topic_id: &str) -> Vec<String> {
+        self.inmem_provider
+            .topics
+            .get(topic_id)
+            .map(|topic_entry| {
+                topic_entry
+                    .value()
+                    .consumer_definitions
+                    .iter()
+                    .map(|entry| entry.value().to_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     async fn consumer_get_attempted_by_id(
         &self,
         topic_id: &str,
@@ -96,6 +151,13 @@ impl ConsumerDeliveryFacade for InMemConsumerDeliveryFacade {
         true
     }
 
+    async fn consumer_seek(&self, topic_id: &str, consumer_id: &str, position: UniqueTime) -> bool {
+        self.inmem_provider
+            .consumer_by_id(topic_id, consumer_id)
+            .seek(position);
+        true
+    }
+
     async fn delivery_intent_mark_done(
         &self,
         topic_id: &str,
@@ -112,6 +174,84 @@ impl ConsumerDeliveryFacade for InMemConsumerDeliveryFacade {
         }
     }
 
+    async fn delivery_prepare(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+        transaction_id: &str,
+        _prepared_ts_micros: u64,
+    ) -> bool {
+        self.inmem_provider
+            .consumer_by_id(topic_id, consumer_id)
+            .prepared_transaction_ids
+            .insert(unique_time, transaction_id.to_owned());
+        true
+    }
+
+    async fn delivery_prepared_transaction_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    ) -> Option<String> {
+        self.inmem_provider
+            .consumer_by_id(topic_id, consumer_id)
+            .prepared_transaction_ids
+            .get(&unique_time)
+            .map(|entry| entry.value().to_owned())
+    }
+
+    async fn delivery_prepared_remove(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    ) {
+        self.inmem_provider
+            .consumer_by_id(topic_id, consumer_id)
+            .prepared_transaction_ids
+            .remove(&unique_time);
+    }
+
+    async fn delivery_slot_claim(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        slots: u32,
+        unique_time: UniqueTime,
+        time_to_live_micros: u64,
+    ) -> bool {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let consumer = self.inmem_provider.consumer_by_id(topic_id, consumer_id);
+        let mut delivery_slots = consumer.delivery_slots.lock().unwrap();
+        delivery_slots.retain(|_, expires_micros| *expires_micros > now_micros);
+        if let Some(expires_micros) = delivery_slots.get_mut(&unique_time) {
+            // A redelivered event keeps the slot it already holds
+            *expires_micros = now_micros + time_to_live_micros;
+            return true;
+        }
+        if delivery_slots.len() >= usize::try_from(slots).unwrap_or(usize::MAX) {
+            return false;
+        }
+        delivery_slots.insert(unique_time, now_micros + time_to_live_micros);
+        true
+    }
+
+    async fn delivery_slot_release(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    ) {
+        self.inmem_provider
+            .consumer_by_id(topic_id, consumer_id)
+            .delivery_slots
+            .lock()
+            .unwrap()
+            .remove(&unique_time);
+    }
+
     async fn delivery_intent_insert_done(
         &self,
         _topic_id: &str,
@@ -144,6 +284,51 @@ impl ConsumerDeliveryFacade for InMemConsumerDeliveryFacade {
         true
     }
 
+    async fn delivery_records_by_bucket(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        bucket: u64,
+        max_results: usize,
+    ) -> (Vec<DeliveryRecord>, bool) {
+        let consumer = self.inmem_provider.consumer_by_id(topic_id, consumer_id);
+        let topic_entry = self
+            .inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default);
+        let events = &topic_entry.value().events;
+        let lower_bound = UniqueTime::from(UniqueTime::min_encoded_in_bucket(bucket));
+        let upper_bound = UniqueTime::from(UniqueTime::max_encoded_in_bucket(bucket));
+        let ret = consumer
+            .delivery_intents
+            .range(lower_bound..=upper_bound)
+            .flat_map(|entry| {
+                let unique_time = *entry.key();
+                let event_id = events
+                    .get(&unique_time)
+                    .map(|event| event.value().event_id.to_owned())
+                    .unwrap_or_default();
+                entry
+                    .value()
+                    .iter()
+                    .map(|intent| {
+                        let intent = intent.value();
+                        DeliveryRecord::new(
+                            unique_time,
+                            event_id.to_owned(),
+                            intent.get_intent_ts_micros(),
+                            intent.is_done(),
+                            intent.get_retry_count(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .take(max_results)
+            .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+
     async fn populate_delivery_cache_with_fresh(
         &self,
         topic_id: &str,
@@ -160,10 +345,34 @@ impl ConsumerDeliveryFacade for InMemConsumerDeliveryFacade {
                 consumer_id,
                 consumer_delivery_cache.as_ref().as_ref(),
                 attempted_low_exclusive,
+                usize::MAX,
             );
         (last_attempted_ts, any_new_found)
     }
 
+    async fn populate_delivery_cache_initial(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        consumer_delivery_cache: Box<Arc<dyn DeliveryIntentTemplateInsertable>>,
+        attempted_low_exclusive: UniqueTime,
+        max_events: usize,
+    ) -> bool {
+        let (_last_attempted_ts, any_new_found) = self
+            .inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .populate_delivery_cache_with_fresh(
+                consumer_id,
+                consumer_delivery_cache.as_ref().as_ref(),
+                attempted_low_exclusive,
+                max_events,
+            );
+        any_new_found
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn populate_delivery_cache_with_retries(
         &self,
         topic_id: &str,
@@ -172,6 +381,7 @@ impl ConsumerDeliveryFacade for InMemConsumerDeliveryFacade {
         done_low_exclusive: UniqueTime,
         freshness_duration_micros: u64,
         _clock_skew_tolerance_micros: u64,
+        retry_backoff: &RetryBackoff,
     ) -> u64 {
         self.inmem_provider
             .topics
@@ -182,6 +392,7 @@ impl ConsumerDeliveryFacade for InMemConsumerDeliveryFacade {
                 consumer_delivery_cache.as_ref().as_ref(),
                 done_low_exclusive,
                 freshness_duration_micros,
+                retry_backoff,
             )
     }
 }
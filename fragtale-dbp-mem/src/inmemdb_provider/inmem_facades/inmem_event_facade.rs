@@ -19,10 +19,17 @@
 
 use crate::InMemoryDatabaseProvider;
 use crate::inmemdb_provider::inmem_topic::InMemTopic;
+use crossbeam_skiplist::SkipMap;
 use fragtale_dbp::dbp::facades::EventFacade;
+use fragtale_dbp::mb::EventAnnotation;
+use fragtale_dbp::mb::ExtractedValue;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::RejectedEvent;
 use fragtale_dbp::mb::TopicEvent;
 use fragtale_dbp::mb::UniqueTime;
 use fragtale_dbp::mb::consumers::EventDeliveryGist;
+use std::collections::HashMap;
+use std::ops::Bound;
 use std::sync::Arc;
 
 /// Ephemeral in-memory implementation of [EventFacade].
@@ -50,9 +57,11 @@ impl EventFacade for InMemEventFacade {
             .map(|event| {
                 EventDeliveryGist::new(
                     event.unique_time,
-                    event.document.to_owned(),
+                    Arc::clone(&event.document),
                     event.protection_ref.to_owned(),
                     event.correlation_token.to_owned(),
+                    Some(event.priority),
+                    event.expires_ts,
                 )
             })
     }
@@ -71,9 +80,11 @@ impl EventFacade for InMemEventFacade {
             .map(|event| {
                 EventDeliveryGist::new(
                     event.unique_time,
-                    event.document.to_owned(),
+                    Arc::clone(&event.document),
                     event.protection_ref.to_owned(),
                     event.correlation_token.to_owned(),
+                    Some(event.priority),
+                    event.expires_ts,
                 )
             })
     }
@@ -91,6 +102,30 @@ impl EventFacade for InMemEventFacade {
             .event_ids_by_index(index_column, index_key)
     }
 
+    async fn event_unique_times_by_id(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<UniqueTime> {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .event_unique_time_by_id
+            .get(event_id)
+            .map(|entry| {
+                entry
+                    .value()
+                    .iter()
+                    .rev()
+                    .take(max_results)
+                    .map(|entry| *entry.value())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     async fn event_document_by_correlation_token(
         &self,
         topic_id: &str,
@@ -112,19 +147,332 @@ impl EventFacade for InMemEventFacade {
                     .map(|event| {
                         EventDeliveryGist::new(
                             event.unique_time,
-                            event.document.to_owned(),
+                            Arc::clone(&event.document),
                             event.protection_ref.to_owned(),
                             event.correlation_token.to_owned(),
+                            Some(event.priority),
+                            event.expires_ts,
                         )
                     })
             })
     }
 
-    async fn event_persist(&self, topic_id: &str, topic_event: TopicEvent) -> String {
+    async fn event_persist(
+        &self,
+        topic_id: &str,
+        topic_event: TopicEvent,
+    ) -> Result<(String, u32), MessageBrokerError> {
+        self.inmem_provider
+            .event_topics
+            .get_or_insert_with(topic_event.get_event_id().to_owned(), SkipMap::default)
+            .value()
+            .insert(
+                topic_id.to_owned(),
+                topic_event.get_unique_time().as_encoded(),
+            );
+        let correlation_token = self
+            .inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .event_persist(topic_event);
+        Ok((correlation_token, 0))
+    }
+
+    async fn events_persist(
+        &self,
+        topic_id: &str,
+        topic_events: Vec<TopicEvent>,
+    ) -> Result<(Vec<String>, u32), MessageBrokerError> {
+        let mut correlation_tokens = Vec::with_capacity(topic_events.len());
+        for topic_event in topic_events {
+            let (correlation_token, _retries) = self.event_persist(topic_id, topic_event).await?;
+            correlation_tokens.push(correlation_token);
+        }
+        Ok((correlation_tokens, 0))
+    }
+
+    async fn event_index_columns_update(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        additional_columns: HashMap<String, ExtractedValue>,
+    ) -> bool {
+        let topic_entry = self
+            .inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default);
+        let topic = topic_entry.value();
+        if topic
+            .event_by_id_and_unique_time(event_id, Some(unique_time))
+            .is_none()
+        {
+            return false;
+        }
+        topic.index_columns_insert(event_id, unique_time, &additional_columns);
+        true
+    }
+
+    async fn event_topics_by_event_id(
+        &self,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<(String, UniqueTime)> {
+        self.inmem_provider
+            .event_topics
+            .get(event_id)
+            .map(|entry| {
+                entry
+                    .value()
+                    .iter()
+                    .take(max_results)
+                    .map(|entry| (entry.key().to_owned(), UniqueTime::from(*entry.value())))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    async fn event_purge(&self, topic_id: &str, event_id: &str, unique_time: UniqueTime) -> bool {
+        let (existed, none_remaining) = self
+            .inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .event_purge(event_id, unique_time);
+        if none_remaining && let Some(entry) = self.inmem_provider.event_topics.get(event_id) {
+            entry.value().remove(topic_id);
+        }
+        existed
+    }
+
+    async fn event_redact(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        document: &str,
+        protection_ref: &str,
+    ) -> bool {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .event_redact(event_id, unique_time, document, protection_ref)
+    }
+
+    async fn rejected_event_persist(&self, topic_id: &str, rejected_event: RejectedEvent) -> bool {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .rejected_events
+            .insert(rejected_event.get_event_id().to_owned(), rejected_event);
+        true
+    }
+
+    async fn rejected_events(
+        &self,
+        topic_id: &str,
+        from: &Option<String>,
+        max_results: usize,
+    ) -> (Vec<RejectedEvent>, bool) {
+        let topic_entry = self
+            .inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default);
+        let rejected_events = &topic_entry.value().rejected_events;
+        let lower_bound = from
+            .as_ref()
+            .map_or(Bound::Unbounded, |from| Bound::Excluded(from.to_owned()));
+        let ret = rejected_events
+            .range((lower_bound, Bound::Unbounded))
+            .take(max_results)
+            .map(|entry| entry.value().to_owned())
+            .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+
+    async fn rejected_event_by_id(&self, topic_id: &str, event_id: &str) -> Option<RejectedEvent> {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .rejected_events
+            .get(event_id)
+            .map(|entry| entry.value().to_owned())
+    }
+
+    async fn rejected_event_delete(&self, topic_id: &str, event_id: &str) -> bool {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .rejected_events
+            .remove(event_id)
+            .is_some()
+    }
+
+    async fn event_annotation_persist(
+        &self,
+        topic_id: &str,
+        event_annotation: EventAnnotation,
+    ) -> bool {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .event_annotations
+            .get_or_insert_with(event_annotation.get_event_id().to_owned(), SkipMap::default)
+            .value()
+            .insert(
+                (
+                    event_annotation.get_annotated_ts_micros(),
+                    event_annotation.get_author().to_owned(),
+                ),
+                event_annotation,
+            );
+        true
+    }
+
+    async fn event_annotations_by_event_id(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<EventAnnotation> {
         self.inmem_provider
             .topics
             .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
             .value()
-            .event_persist(topic_event)
+            .event_annotations
+            .get(event_id)
+            .map(|entry| {
+                entry
+                    .value()
+                    .iter()
+                    .take(max_results)
+                    .map(|entry| entry.value().to_owned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    async fn buckets_by_shelf(
+        &self,
+        topic_id: &str,
+        shelf: u16,
+        from_bucket: Option<u64>,
+        max_results: usize,
+    ) -> (Vec<u64>, bool) {
+        let topic_entry = self
+            .inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default);
+        let events = &topic_entry.value().events;
+        let mut next_bucket = from_bucket.map_or_else(
+            || UniqueTime::first_bucket_in_shelf(shelf),
+            |from_bucket| from_bucket + 1,
+        );
+        let mut ret = Vec::new();
+        while ret.len() < max_results {
+            let lower_bound = UniqueTime::from(UniqueTime::min_encoded_in_bucket(next_bucket));
+            let Some(entry) = events.lower_bound(Bound::Included(&lower_bound)) else {
+                break;
+            };
+            if entry.key().get_shelf() != shelf {
+                break;
+            }
+            let bucket = entry.key().get_bucket();
+            ret.push(bucket);
+            next_bucket = bucket + 1;
+        }
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+
+    async fn event_count_by_bucket(&self, topic_id: &str, bucket: u64) -> u64 {
+        let topic_entry = self
+            .inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default);
+        let lower_bound = UniqueTime::from(UniqueTime::min_encoded_in_bucket(bucket));
+        let upper_bound = UniqueTime::from(UniqueTime::max_encoded_in_bucket(bucket));
+        u64::try_from(
+            topic_entry
+                .value()
+                .events
+                .range(lower_bound..=upper_bound)
+                .count(),
+        )
+        .unwrap_or(u64::MAX)
+    }
+
+    async fn events_by_bucket(
+        &self,
+        topic_id: &str,
+        bucket: u64,
+        from: Option<UniqueTime>,
+        max_results: usize,
+    ) -> (Vec<(UniqueTime, String, Option<u64>)>, bool) {
+        let topic_entry = self
+            .inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default);
+        let lower_bound = from.map_or_else(
+            || Bound::Included(UniqueTime::from(UniqueTime::min_encoded_in_bucket(bucket))),
+            Bound::Excluded,
+        );
+        let upper_bound =
+            Bound::Included(UniqueTime::from(UniqueTime::max_encoded_in_bucket(bucket)));
+        let ret = topic_entry
+            .value()
+            .events
+            .range((lower_bound, upper_bound))
+            .take(max_results)
+            .map(|entry| {
+                let event = entry.value();
+                (
+                    event.unique_time,
+                    event.event_id.to_owned(),
+                    event.descriptor_version,
+                )
+            })
+            .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+
+    async fn events_by_unique_time_range(
+        &self,
+        topic_id: &str,
+        from: UniqueTime,
+        to: UniqueTime,
+        max_results: usize,
+    ) -> (Vec<(UniqueTime, String, Option<u64>)>, bool) {
+        if from.as_encoded() >= to.as_encoded() || max_results == 0 {
+            return (vec![], false);
+        }
+        let topic_entry = self
+            .inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default);
+        let ret = topic_entry
+            .value()
+            .events
+            .range((Bound::Excluded(from), Bound::Included(to)))
+            .take(max_results)
+            .map(|entry| {
+                let event = entry.value();
+                (
+                    event.unique_time,
+                    event.event_id.to_owned(),
+                    event.descriptor_version,
+                )
+            })
+            .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
     }
 }
@@ -79,6 +79,46 @@ impl EventTrackingFacade for InMemEventTrackingFacade {
         vec![ObjectCount::new(0, i64::try_from(count).unwrap())]
     }
 
+    async fn object_count_snapshot_upsert(
+        &self,
+        topic_id: &str,
+        object_count_type: &ObjectCountType,
+        instance_id: u16,
+        value: u64,
+    ) {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .object_count_snapshots
+            .insert((instance_id, object_count_type.name().to_owned()), value);
+    }
+
+    async fn object_count_snapshots_by_instance(
+        &self,
+        topic_id: &str,
+        instance_id: u16,
+    ) -> Vec<(ObjectCountType, u64)> {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .object_count_snapshots
+            .iter()
+            .filter(|entry| entry.key().0 == instance_id)
+            .map(|entry| (ObjectCountType::by_name(&entry.key().1), *entry.value()))
+            .collect::<Vec<_>>()
+    }
+
+    async fn object_count_snapshots_delete(&self, topic_id: &str) {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default)
+            .value()
+            .object_count_snapshots
+            .clear();
+    }
+
     async fn track_new_events_in_topic(
         &self,
         topic_id: &str,
@@ -17,7 +17,9 @@
 
 //! Ephemeral in-memory implementation of [InstanceIdFacade].
 
+use crossbeam_skiplist::SkipSet;
 use fragtale_dbp::dbp::facades::InstanceIdFacade;
+use std::sync::Mutex;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
 
@@ -25,6 +27,8 @@ use std::sync::atomic::Ordering;
 #[derive(Default)]
 pub struct InMemInstanceIdFacade {
     first_claim: AtomicU64,
+    roles: SkipSet<String>,
+    feature_flags: Mutex<Option<String>>,
 }
 
 #[async_trait::async_trait]
@@ -49,4 +53,41 @@ impl InstanceIdFacade for InMemInstanceIdFacade {
         // NOOP: In-mem instance lives forever
         true
     }
+
+    async fn claim_role(
+        &self,
+        role: &str,
+        _time_to_live_seconds: u32,
+        _claimed_instance_id: u16,
+    ) -> bool {
+        if !self.roles.contains(role) {
+            self.roles.insert(role.to_owned());
+        }
+        true
+    }
+
+    async fn free_role(&self, role: &str, _claimed_instance_id: u16) {
+        self.roles.remove(role);
+    }
+
+    async fn get_oldest_instance_id_by_role(&self, role: &str) -> Option<u16> {
+        self.roles.contains(role).then_some(0)
+    }
+
+    async fn feature_flags(&self) -> Option<String> {
+        self.feature_flags.lock().unwrap().to_owned()
+    }
+
+    async fn feature_flags_compare_and_set(
+        &self,
+        expected: Option<&str>,
+        feature_flags: &str,
+    ) -> bool {
+        let mut current = self.feature_flags.lock().unwrap();
+        if current.as_deref() != expected {
+            return false;
+        }
+        *current = Some(feature_flags.to_owned());
+        true
+    }
 }
@@ -18,9 +18,14 @@
 //! Ephemeral in-memory implementation of [TopicFacade].
 
 use crate::InMemoryDatabaseProvider;
+use crate::inmemdb_provider::inmem_topic::InMemTopic;
 use crossbeam_skiplist::SkipMap;
 use fragtale_dbp::dbp::facades::TopicFacade;
+use fragtale_dbp::mb::DiagnosticQuery;
+use fragtale_dbp::mb::IndexRebuildProgress;
 use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use fragtale_dbp::mb::UniqueTime;
 use std::sync::Arc;
 
 /// Ephemeral in-memory implementation of [TopicFacade].
@@ -39,11 +44,17 @@ impl InMemTopicFacade {
 
 #[async_trait::async_trait]
 impl TopicFacade for InMemTopicFacade {
-    async fn ensure_topic_setup(&self, _topic_id: &str) -> Result<(), MessageBrokerError> {
-        // NOOP
+    async fn ensure_topic_setup(&self, topic_id: &str) -> Result<(), MessageBrokerError> {
+        self.inmem_provider
+            .topics
+            .get_or_insert_with(topic_id.to_owned(), InMemTopic::default);
         Ok(())
     }
 
+    async fn topic_exists(&self, topic_id: &str) -> bool {
+        self.inmem_provider.topics.contains_key(topic_id)
+    }
+
     async fn get_topic_ids(&self, from: &Option<String>) -> (Vec<String>, bool) {
         if from.is_some() {
             log::debug!("Getting batches with 'from' is not implemented from the in-mem provider.");
@@ -100,6 +111,50 @@ impl TopicFacade for InMemTopicFacade {
             .collect()
     }
 
+    async fn shared_schema_persist(
+        &self,
+        schema_id: &str,
+        version: u64,
+        shared_schema: &str,
+    ) -> bool {
+        let versions = self
+            .inmem_provider
+            .shared_schemas
+            .get_or_insert_with(schema_id.to_owned(), SkipMap::default);
+        if versions.value().contains_key(&version) {
+            false
+        } else {
+            versions.value().insert(version, shared_schema.to_owned());
+            true
+        }
+    }
+
+    async fn shared_schema_latest_by_id(&self, schema_id: &str) -> Option<(u64, String)> {
+        self.inmem_provider
+            .shared_schemas
+            .get(schema_id)
+            .and_then(|versions| {
+                versions
+                    .value()
+                    .back()
+                    .map(|entry| (*entry.key(), entry.value().to_owned()))
+            })
+    }
+
+    async fn shared_schemas_latest(&self, max_results: usize) -> Vec<String> {
+        self.inmem_provider
+            .shared_schemas
+            .iter()
+            .filter_map(|versions| {
+                versions
+                    .value()
+                    .back()
+                    .map(|entry| entry.value().to_owned())
+            })
+            .take(max_results)
+            .collect()
+    }
+
     async fn extraction_setup_searchable(
         &self,
         _topic_id: &str,
@@ -107,4 +162,84 @@ impl TopicFacade for InMemTopicFacade {
     ) {
         // In-mem impl sets things up lazily / when used
     }
+
+    async fn reply_topic_register(
+        &self,
+        topic_id: &str,
+        reply_topic_id: &str,
+        registered_by: &str,
+    ) -> bool {
+        self.inmem_provider.topic_replies.insert(
+            topic_id.to_owned(),
+            (reply_topic_id.to_owned(), registered_by.to_owned()),
+        );
+        true
+    }
+
+    async fn reply_topic_by_topic_id(&self, topic_id: &str) -> Option<String> {
+        self.inmem_provider
+            .topic_replies
+            .get(topic_id)
+            .map(|entry| entry.value().0.to_owned())
+    }
+
+    async fn reply_topic_registered_by(&self, topic_id: &str) -> Option<String> {
+        self.inmem_provider
+            .topic_replies
+            .get(topic_id)
+            .map(|entry| entry.value().1.to_owned())
+    }
+
+    async fn bulk_ingest_window_persist(
+        &self,
+        topic_id: &str,
+        start_ts_micros: u64,
+        end_ts_micros: u64,
+        reconciled: bool,
+    ) -> bool {
+        self.inmem_provider.bulk_ingest_windows.insert(
+            topic_id.to_owned(),
+            (start_ts_micros, end_ts_micros, reconciled),
+        );
+        true
+    }
+
+    async fn bulk_ingest_window_by_topic_id(&self, topic_id: &str) -> Option<(u64, u64, bool)> {
+        self.inmem_provider
+            .bulk_ingest_windows
+            .get(topic_id)
+            .map(|entry| *entry.value())
+    }
+
+    async fn index_rebuild_persist(
+        &self,
+        topic_id: &str,
+        index_rebuild_progress: IndexRebuildProgress,
+    ) -> bool {
+        self.inmem_provider
+            .index_rebuilds
+            .insert(topic_id.to_owned(), index_rebuild_progress);
+        true
+    }
+
+    async fn index_rebuild_by_topic_id(&self, topic_id: &str) -> Option<IndexRebuildProgress> {
+        self.inmem_provider
+            .index_rebuilds
+            .get(topic_id)
+            .map(|entry| *entry.value())
+    }
+
+    async fn diagnostic_query(
+        &self,
+        _topic_id: &str,
+        diagnostic_query: DiagnosticQuery,
+        _consumer_id: Option<&str>,
+        _range: Option<(UniqueTime, UniqueTime)>,
+        _max_results: usize,
+    ) -> Result<(Vec<String>, bool), MessageBrokerError> {
+        Err(MessageBrokerErrorKind::Unspecified.error_with_msg(format!(
+            "Diagnostic query '{}' is not supported by the in-memory database.",
+            diagnostic_query.get_name()
+        )))
+    }
 }
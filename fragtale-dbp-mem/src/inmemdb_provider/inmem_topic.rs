@@ -24,24 +24,33 @@ pub use self::inmem_consumer::*;
 pub use self::inmem_event::*;
 use crossbeam_skiplist::SkipMap;
 use crossbeam_skiplist::SkipSet;
+use fragtale_dbp::mb::EventAnnotation;
 use fragtale_dbp::mb::ExtractedValue;
+use fragtale_dbp::mb::RejectedEvent;
 use fragtale_dbp::mb::TopicEvent;
 use fragtale_dbp::mb::UniqueTime;
 use fragtale_dbp::mb::consumers::DeliveryIntentTemplate;
 use fragtale_dbp::mb::consumers::DeliveryIntentTemplateInsertable;
+use fragtale_dbp::mb::consumers::RetryBackoff;
 use fragtale_dbp::mb::correlation::CorrelationResultListener;
+use std::collections::HashMap;
+use std::ops::Bound;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
 
 /// Ephemeral in-memory representation of a topic.
 #[derive(Default)]
 pub struct InMemTopic {
+    pub consumer_definitions: SkipMap<String, String>,
     pub consumers: SkipMap<String, Arc<InMemConsumer>>,
     pub events: SkipMap<UniqueTime, Arc<InMemEvent>>,
     pub event_unique_time_by_id: SkipMap<String, Arc<SkipSet<UniqueTime>>>,
     pub event_unique_time_by_corrolation: SkipMap<String, (String, UniqueTime)>,
     pub object_count: SkipMap<String, AtomicU64>,
+    pub object_count_snapshots: SkipMap<(u16, String), u64>,
     pub indices: SkipMap<String, SkipMap<String, SkipSet<(String, UniqueTime)>>>,
+    pub rejected_events: SkipMap<String, RejectedEvent>,
+    pub event_annotations: SkipMap<String, SkipMap<(u64, String), EventAnnotation>>,
 }
 
 impl InMemTopic {
@@ -89,10 +98,13 @@ impl InMemTopic {
             Arc::new(InMemEvent {
                 event_id: topic_event.get_event_id().to_owned(),
                 unique_time: topic_event.get_unique_time(),
-                document: topic_event.get_document().to_owned(),
+                document: Arc::from(topic_event.get_document()),
                 protection_ref: topic_event.get_protection_ref().to_owned(),
                 correlation_token: topic_event.get_correlation_token().to_owned(),
                 descriptor_version: topic_event.get_descriptor_version(),
+                priority: topic_event.get_priority(),
+                expires_ts: topic_event.get_expires_ts(),
+                deliver_after_ts: topic_event.get_deliver_after_ts(),
             }),
         );
         Arc::clone(
@@ -110,31 +122,128 @@ impl InMemTopic {
                 topic_event.get_unique_time(),
             ),
         );
-        // Indexed columns...
-        for (index_column, value) in topic_event.get_additional_columns() {
-            let index_key = match value {
-                ExtractedValue::Text(value) => value,
-                ExtractedValue::BigInt(value) => &value.to_string(),
-            };
-            self.indices
-                .get_or_insert_with(index_column.to_owned(), SkipMap::default)
-                .value()
-                .get_or_insert_with(index_key.to_owned(), SkipSet::default)
-                .value()
-                .insert((
-                    topic_event.get_event_id().to_owned(),
-                    topic_event.get_unique_time(),
-                ));
-        }
+        self.index_columns_insert(
+            topic_event.get_event_id(),
+            topic_event.get_unique_time(),
+            topic_event.get_additional_columns(),
+        );
         topic_event.get_correlation_token().to_owned()
     }
 
-    /// Add new events to the delivery cache of the consumer.
+    /// Add the event to the indices of the indexed columns.
+    pub fn index_columns_insert(
+        &self,
+        event_id: &str,
+        unique_time: UniqueTime,
+        additional_columns: &HashMap<String, ExtractedValue>,
+    ) {
+        for (index_column, value) in additional_columns {
+            let index_entry = self
+                .indices
+                .get_or_insert_with(index_column.to_owned(), SkipMap::default);
+            // Collections are indexed by each member
+            for index_key in value.as_index_keys() {
+                index_entry
+                    .value()
+                    .get_or_insert_with(index_key, SkipSet::default)
+                    .value()
+                    .insert((event_id.to_owned(), unique_time));
+            }
+        }
+    }
+
+    /// Remove the event from the indices of all indexed columns.
+    pub fn index_columns_remove(&self, event_id: &str, unique_time: UniqueTime) {
+        let index_entry = (event_id.to_owned(), unique_time);
+        for index_column_entry in self.indices.iter() {
+            for index_key_entry in index_column_entry.value().iter() {
+                index_key_entry.value().remove(&index_entry);
+            }
+        }
+    }
+
+    /// Remove the event, its index entries and its correlation lookup.
+    ///
+    /// Annotations of the event identifier are removed once no other event
+    /// with the same identifier remains.
+    ///
+    /// Return `true` if the event existed and `false` if it didn't. The
+    /// second value is `true` if no event with the identifier remains.
+    pub fn event_purge(&self, event_id: &str, unique_time: UniqueTime) -> (bool, bool) {
+        let Some(event) = self
+            .event_by_id_and_unique_time(event_id, Some(unique_time))
+            .filter(|event| event.event_id == event_id)
+        else {
+            return (false, false);
+        };
+        self.index_columns_remove(event_id, unique_time);
+        if self
+            .event_unique_time_by_corrolation
+            .get(&event.correlation_token)
+            .is_some_and(|entry| entry.value().1 == unique_time)
+        {
+            self.event_unique_time_by_corrolation
+                .remove(&event.correlation_token);
+        }
+        self.events.remove(&unique_time);
+        let mut none_remaining = true;
+        if let Some(entry) = self.event_unique_time_by_id.get(event_id) {
+            entry.value().remove(&unique_time);
+            none_remaining = entry.value().is_empty();
+            if none_remaining {
+                entry.remove();
+            }
+        }
+        if none_remaining {
+            self.event_annotations.remove(event_id);
+        }
+        (true, none_remaining)
+    }
+
+    /// Replace the document and protection reference of the event and remove
+    /// its index entries and the annotations of the event identifier.
+    ///
+    /// Return `true` if the event existed.
+    pub fn event_redact(
+        &self,
+        event_id: &str,
+        unique_time: UniqueTime,
+        document: &str,
+        protection_ref: &str,
+    ) -> bool {
+        let Some(event) = self
+            .event_by_id_and_unique_time(event_id, Some(unique_time))
+            .filter(|event| event.event_id == event_id)
+        else {
+            return false;
+        };
+        self.index_columns_remove(event_id, unique_time);
+        self.events.insert(
+            unique_time,
+            Arc::new(InMemEvent {
+                event_id: event.event_id.to_owned(),
+                unique_time,
+                document: Arc::from(document),
+                protection_ref: protection_ref.to_owned(),
+                correlation_token: event.correlation_token.to_owned(),
+                descriptor_version: event.descriptor_version,
+                priority: event.priority,
+                expires_ts: event.expires_ts,
+                deliver_after_ts: event.deliver_after_ts,
+            }),
+        );
+        self.event_annotations.remove(event_id);
+        true
+    }
+
+    /// Add up to `max_events` new events to the delivery cache of the
+    /// consumer.
     pub fn populate_delivery_cache_with_fresh(
         &self,
         consumer_id: &str,
         consumer_delivery_cache: &dyn DeliveryIntentTemplateInsertable,
         attempted_low_exclusive: UniqueTime,
+        max_events: usize,
     ) -> (u64, bool) {
         let consumer = Arc::clone(
             self.consumers
@@ -144,12 +253,14 @@ impl InMemTopic {
                 )
                 .value(),
         );
-        let mut next = self.events.front();
-        if let Some(first_entry) = self.events.get(&attempted_low_exclusive) {
-            next = first_entry.next();
-        }
+        let mut next = self
+            .events
+            .lower_bound(Bound::Excluded(&attempted_low_exclusive));
         let mut last_attempted_ts = attempted_low_exclusive.as_encoded();
         let mut any_new_found = false;
+        let mut any_deferred = false;
+        let mut count = 0;
+        let now = fragtale_client::time::get_timestamp_micros();
         while let Some(event_entry) = next {
             // Skip intents marked as done
             let no_done =
@@ -164,16 +275,25 @@ impl InMemTopic {
                     });
             if no_done && let Some(event) = self.events.get(event_entry.key()) {
                 let event = Arc::clone(event.value());
-                consumer_delivery_cache.insert(DeliveryIntentTemplate::new(
-                    event.unique_time,
-                    event.event_id.to_owned(),
-                    event.descriptor_version,
-                    None,
-                ));
-                last_attempted_ts = event.unique_time.as_encoded();
-                any_new_found = true;
+                if event.is_delivery_deferred(now) {
+                    // Look at this event again until it may be delivered
+                    any_deferred = true;
+                } else {
+                    consumer_delivery_cache.insert(DeliveryIntentTemplate::new(
+                        event.unique_time,
+                        event.event_id.to_owned(),
+                        event.descriptor_version,
+                        None,
+                        event.expires_ts,
+                    ));
+                    if !any_deferred {
+                        last_attempted_ts = event.unique_time.as_encoded();
+                    }
+                    any_new_found = true;
+                    count += 1;
+                }
             }
-            if consumer_delivery_cache.is_full() {
+            if consumer_delivery_cache.is_full() || count >= max_events {
                 break;
             }
             next = event_entry.next();
@@ -181,13 +301,15 @@ impl InMemTopic {
         (last_attempted_ts, any_new_found)
     }
 
-    /// Add failed deliveries to the delivery cache of the consumer.
+    /// Add failed deliveries to the delivery cache of the consumer once they
+    /// are due for retry.
     pub fn populate_delivery_cache_with_retries(
         &self,
         consumer_id: &str,
         consumer_delivery_cache: &dyn DeliveryIntentTemplateInsertable,
         done_low_exclusive: UniqueTime,
         freshness_duration_micros: u64,
+        retry_backoff: &RetryBackoff,
     ) -> u64 {
         let consumer = Arc::clone(
             self.consumers
@@ -203,7 +325,8 @@ impl InMemTopic {
         }
         let mut all_done = true;
         let mut confirmed_done_ts = done_low_exclusive.as_encoded();
-        let timeout_ts = fragtale_client::time::get_timestamp_micros() - freshness_duration_micros;
+        let now = fragtale_client::time::get_timestamp_micros();
+        let timeout_ts = now - freshness_duration_micros;
         while let Some(event_entry) = next {
             if consumer_delivery_cache.is_full() || event_entry.key().as_encoded() >= timeout_ts {
                 break;
@@ -214,19 +337,28 @@ impl InMemTopic {
                     .get(event_entry.key())
                     .is_none_or(|dis_entry| {
                         !dis_entry.value().iter().any(|dis_entry| {
-                            dis_entry.value().is_done()
-                                || dis_entry.value().get_intent_ts_micros() > timeout_ts
+                            let intent = dis_entry.value();
+                            intent.is_done()
+                                || retry_backoff.get_due_ts_micros(
+                                    intent.get_intent_ts_micros(),
+                                    intent.get_retry_count(),
+                                    freshness_duration_micros,
+                                ) > now
                         })
                     });
             if no_done {
                 if let Some(event) = self.events.get(event_entry.key()) {
                     let event = Arc::clone(event.value());
-                    consumer_delivery_cache.insert(DeliveryIntentTemplate::new(
-                        event.unique_time,
-                        event.event_id.to_owned(),
-                        event.descriptor_version,
-                        None,
-                    ));
+                    // Events that may not be delivered yet are not done either
+                    if !event.is_delivery_deferred(now) {
+                        consumer_delivery_cache.insert(DeliveryIntentTemplate::new(
+                            event.unique_time,
+                            event.event_id.to_owned(),
+                            event.descriptor_version,
+                            None,
+                            event.expires_ts,
+                        ));
+                    }
                     all_done = false;
                 }
             } else if all_done {
@@ -22,7 +22,9 @@ mod inmem_delivery_intent;
 pub use self::inmem_delivery_intent::InMemDeliveryIntent;
 use crossbeam_skiplist::SkipMap;
 use fragtale_dbp::mb::UniqueTime;
+use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering::Relaxed;
 
@@ -32,6 +34,9 @@ pub struct InMemConsumer {
     attempted: AtomicU64,
     done: AtomicU64,
     pub delivery_intents: SkipMap<UniqueTime, SkipMap<u64, Arc<InMemDeliveryIntent>>>,
+    pub prepared_transaction_ids: SkipMap<UniqueTime, String>,
+    /// Expiration timestamp of each claimed delivery slot by event.
+    pub delivery_slots: Mutex<BTreeMap<UniqueTime, u64>>,
 }
 
 impl InMemConsumer {
@@ -41,10 +46,10 @@ impl InMemConsumer {
     pub fn get_attempted(&self) -> Option<UniqueTime> {
         let mut value = self.attempted.load(Relaxed);
         if value == 0 {
-            value = Self::MICROS_SINCE_EPOCH_20240101;
+            value = UniqueTime::new(Self::MICROS_SINCE_EPOCH_20240101, 0).as_encoded();
             self.attempted.store(value, Relaxed);
         }
-        Some(UniqueTime::new(value, 0))
+        Some(UniqueTime::from(value))
     }
 
     /// Set the time of the event that was last attempted intent for delivery.
@@ -58,10 +63,10 @@ impl InMemConsumer {
     pub fn get_done(&self) -> Option<UniqueTime> {
         let mut value = self.done.load(Relaxed);
         if value == 0 {
-            value = Self::MICROS_SINCE_EPOCH_20240101;
+            value = UniqueTime::new(Self::MICROS_SINCE_EPOCH_20240101, 0).as_encoded();
             self.done.store(value, Relaxed);
         }
-        Some(UniqueTime::new(value, 0))
+        Some(UniqueTime::from(value))
     }
 
     /// Set the time of the event that was confirmed to be delivered.
@@ -71,6 +76,19 @@ impl InMemConsumer {
         self.done.store(value.as_encoded(), Relaxed);
     }
 
+    /// Move both the attempted and done time to `position` and forget about
+    /// all delivery intents after it.
+    pub fn seek(&self, position: UniqueTime) {
+        while let Some(entry) = self.delivery_intents.back() {
+            if *entry.key() <= position {
+                break;
+            }
+            entry.remove();
+        }
+        self.set_attempted(position);
+        self.set_done(position);
+    }
+
     /// Retrieve delivery intent by [UniqueTime].
     pub fn delivery_intent_by_unique_time(
         &self,
@@ -84,12 +102,16 @@ impl InMemConsumer {
     }
 
     /// Reserve a delivery intent.
+    ///
+    /// Every earlier intent to deliver the same event counts as a retry.
     pub fn delivery_intent_reserve(&self, unique_time: &UniqueTime, intent_ts_micros: u64) {
-        self.delivery_intents
-            .get_or_insert_with(unique_time.to_owned(), SkipMap::default)
-            .value()
-            .get_or_insert_with(intent_ts_micros, || {
-                Arc::new(InMemDeliveryIntent::new(intent_ts_micros))
-            });
+        let entry = self
+            .delivery_intents
+            .get_or_insert_with(unique_time.to_owned(), SkipMap::default);
+        let intents = entry.value();
+        let retry_count = u32::try_from(intents.len()).unwrap_or(u32::MAX);
+        intents.get_or_insert_with(intent_ts_micros, || {
+            Arc::new(InMemDeliveryIntent::new(intent_ts_micros, retry_count))
+        });
     }
 }
@@ -24,14 +24,16 @@ use std::sync::atomic::Ordering;
 #[derive(Debug, Default)]
 pub struct InMemDeliveryIntent {
     intent_ts_micros: u64,
+    retry_count: u32,
     done: AtomicBool,
 }
 
 impl InMemDeliveryIntent {
     /// Return a new instance.
-    pub fn new(intent_ts_micros: u64) -> Self {
+    pub fn new(intent_ts_micros: u64, retry_count: u32) -> Self {
         Self {
             intent_ts_micros,
+            retry_count,
             done: AtomicBool::default(),
         }
     }
@@ -41,6 +43,12 @@ impl InMemDeliveryIntent {
         self.intent_ts_micros
     }
 
+    /// Return the number of retries of the event's delivery before this
+    /// intent.
+    pub fn get_retry_count(&self) -> u32 {
+        self.retry_count
+    }
+
     /// Return `true` if no more processing of this event should happen.
     pub fn is_done(&self) -> bool {
         self.done.load(Ordering::Relaxed)
@@ -18,14 +18,26 @@
 //! Ephemeral in-memory implementation an event.
 
 use fragtale_dbp::mb::UniqueTime;
+use std::sync::Arc;
 
 /// Ephemeral in-memory implementation an event.
 #[derive(Debug)]
 pub struct InMemEvent {
     pub event_id: String,
     pub unique_time: UniqueTime,
-    pub document: String,
+    pub document: Arc<str>,
     pub protection_ref: String,
     pub correlation_token: String,
     pub descriptor_version: Option<u64>,
+    pub priority: u8,
+    pub expires_ts: Option<u64>,
+    pub deliver_after_ts: Option<u64>,
+}
+
+impl InMemEvent {
+    /// Return `true` if the event may not be delivered yet.
+    pub fn is_delivery_deferred(&self, now_micros: u64) -> bool {
+        self.deliver_after_ts
+            .is_some_and(|deliver_after_ts| now_micros < deliver_after_ts)
+    }
 }
@@ -0,0 +1,334 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PostgreSQL implementation of [DatabaseProvider].
+
+mod postgres_diagnostics;
+mod postgres_facades;
+mod postgres_schema;
+mod postgres_types;
+
+use self::postgres_facades::PostgresProviderFacades;
+use self::postgres_schema::PostgresSchema;
+use deadpool_postgres::ManagerConfig;
+use deadpool_postgres::Object;
+use deadpool_postgres::Pool;
+use deadpool_postgres::RecyclingMethod;
+use deadpool_postgres::Runtime;
+use deadpool_postgres::tokio_postgres::NoTls;
+use deadpool_postgres::tokio_postgres::Row;
+use deadpool_postgres::tokio_postgres::error::SqlState;
+use deadpool_postgres::tokio_postgres::types::ToSql;
+use fragtale_dbp::dbp::DatabaseProvider;
+use std::sync::Arc;
+use tokio::time::{Duration, sleep};
+
+/// A statement and the values bound to its parameters.
+type StatementWithValues = (&'static str, Vec<Box<dyn ToSql + Sync + Send>>);
+
+/// PostgreSQL [DatabaseProvider] implementation.
+pub struct PostgresProvider {
+    /// Schema holding all the application's tables.
+    schema: String,
+    /// Pool of connections to PostgreSQL.
+    pool: Pool,
+}
+
+impl PostgresProvider {
+    /// Default PostgreSQL port.
+    const DEFAULT_PORT: u16 = 5432;
+    /// Allowed characters in the schema name.
+    const ALLOWED_SCHEMA_CHARS: &str = "abcdefghijklmnopqrstuvwxyz0123456789_";
+    /// Backoff before the first retry of a failed transaction.
+    const RETRY_INITIAL_BACKOFF_MICROS: u64 = 20_000;
+    /// Upper bound of the backoff between retries of a failed transaction.
+    const RETRY_MAX_BACKOFF_MICROS: u64 = 500_000;
+
+    /// Return a new instance.
+    ///
+    /// Each of the `endpoints` is in the form `host:port` (or just `host` for
+    /// the default port) and `schema` is the name of the database schema that
+    /// will hold all the tables.
+    pub async fn new(
+        schema: &str,
+        endpoints: &[String],
+        username: &str,
+        password: &str,
+    ) -> Arc<Self> {
+        if schema.is_empty()
+            || schema
+                .chars()
+                .any(|c| !Self::ALLOWED_SCHEMA_CHARS.contains(c))
+        {
+            panic!(
+                "Invalid PostgreSQL schema name '{schema}'. Only '{}' are allowed.",
+                Self::ALLOWED_SCHEMA_CHARS
+            );
+        }
+        let (hosts, ports) = endpoints
+            .iter()
+            .map(|endpoint| {
+                endpoint
+                    .rsplit_once(':')
+                    .and_then(|(host, port)| port.parse::<u16>().ok().map(|port| (host, port)))
+                    .map_or_else(
+                        || (endpoint.to_owned(), Self::DEFAULT_PORT),
+                        |(host, port)| (host.to_owned(), port),
+                    )
+            })
+            .unzip::<_, _, Vec<_>, Vec<_>>();
+        let mut config = deadpool_postgres::Config::new();
+        config.hosts = Some(hosts);
+        config.ports = Some(ports);
+        config.user = Some(username.to_owned()).filter(|username| !username.is_empty());
+        config.password = Some(password.to_owned()).filter(|password| !password.is_empty());
+        // Resolve all unqualified table names in the application's schema
+        config.options = Some(format!("-c search_path={schema}"));
+        config.manager = Some(ManagerConfig {
+            recycling_method: RecyclingMethod::Fast,
+        });
+        let pool = config
+            .create_pool(Some(Runtime::Tokio1), NoTls)
+            .map_err(|e| {
+                log::info!("Failed to create connection pool to {endpoints:?}: {e:?}");
+            })
+            .unwrap();
+        Arc::new(Self {
+            schema: schema.to_owned(),
+            pool,
+        })
+        .init()
+        .await
+    }
+
+    /// Initialize
+    async fn init(self: Arc<Self>) -> Arc<Self> {
+        // Wait for the database to become available
+        while let Err(e) = self.pool.get().await {
+            log::warn!("Unable to connect to PostgreSQL. Will retry. Cause: {e}");
+            sleep(Duration::from_secs(1)).await;
+        }
+        PostgresSchema::ensure_tables_exists(&self).await;
+        self
+    }
+
+    /// Get [DatabaseProvider] instance.
+    pub fn as_database_provider(self: &Arc<Self>) -> DatabaseProvider {
+        DatabaseProvider::new(Arc::new(PostgresProviderFacades::new(self)))
+    }
+
+    /// Get a pooled connection to the database.
+    async fn client(&self) -> Option<Object> {
+        self.pool
+            .get()
+            .await
+            .map_err(|e| log::info!("Failed to get database connection: {e}"))
+            .ok()
+    }
+
+    /// Execute a statement with value parameters.
+    ///
+    /// Return the number of modified rows or `None` if the statement failed.
+    async fn execute(&self, statement: &str, values: &[&(dyn ToSql + Sync)]) -> Option<u64> {
+        let client = self.client().await?;
+        let prepared = client
+            .prepare_cached(statement)
+            .await
+            .map_err(|e| log::info!("Failed to prepare '{statement}': {e}"))
+            .ok()?;
+        client
+            .execute(&prepared, values)
+            .await
+            .map_err(|e| log::debug!("Failed to execute '{statement}': {e}"))
+            .ok()
+    }
+
+    /// Execute a query with value parameters.
+    ///
+    /// Return the resulting rows or an empty result if the query failed.
+    async fn query(&self, statement: &str, values: &[&(dyn ToSql + Sync)]) -> Vec<Row> {
+        let Some(client) = self.client().await else {
+            return vec![];
+        };
+        let prepared = match client.prepare_cached(statement).await {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                log::info!("Failed to prepare '{statement}': {e}");
+                return vec![];
+            }
+        };
+        client
+            .query(&prepared, values)
+            .await
+            .map_err(|e| log::debug!("Failed to query '{statement}': {e}"))
+            .unwrap_or_default()
+    }
+
+    /// Execute a query with value parameters and return the first row if
+    /// any.
+    async fn query_first(&self, statement: &str, values: &[&(dyn ToSql + Sync)]) -> Option<Row> {
+        self.query(statement, values).await.into_iter().next()
+    }
+
+    /// Execute statements with value parameters as a single transaction and
+    /// retry transient failures.
+    ///
+    /// Return the number of retries needed or a description of the failure.
+    async fn transaction_with_retries(
+        &self,
+        statements: &[StatementWithValues],
+        max_retries: u32,
+    ) -> Result<u32, String> {
+        let mut retries = 0;
+        loop {
+            match self.transaction(statements).await {
+                Ok(()) => return Ok(retries),
+                Err((true, msg)) if retries < max_retries => {
+                    retries += 1;
+                    let delay = Self::retry_backoff(retries);
+                    log::debug!("Retry {retries}/{max_retries} of transaction in {delay:?}: {msg}");
+                    sleep(delay).await;
+                }
+                Err((_transient, msg)) => {
+                    log::info!("{msg}");
+                    return Err(msg);
+                }
+            }
+        }
+    }
+
+    /// Execute statements as a single transaction and on failure return if
+    /// the failure is transient and a description of it.
+    async fn transaction(&self, statements: &[StatementWithValues]) -> Result<(), (bool, String)> {
+        let mut client = self.pool.get().await.map_err(|e| {
+            (
+                true,
+                format!("Failed to get database connection for transaction: {e}"),
+            )
+        })?;
+        let transaction = client.transaction().await.map_err(|e| {
+            (
+                Self::is_transient(&e),
+                format!("Failed to start transaction: {e}"),
+            )
+        })?;
+        for (statement, values) in statements {
+            let values = values
+                .iter()
+                .map(|value| value.as_ref() as &(dyn ToSql + Sync))
+                .collect::<Vec<_>>();
+            let prepared = transaction.prepare_cached(statement).await.map_err(|e| {
+                (
+                    Self::is_transient(&e),
+                    format!("Failed to prepare '{statement}': {e}"),
+                )
+            })?;
+            transaction.execute(&prepared, &values).await.map_err(|e| {
+                (
+                    Self::is_transient(&e),
+                    format!("Failed to execute '{statement}' in transaction: {e}"),
+                )
+            })?;
+        }
+        transaction.commit().await.map_err(|e| {
+            (
+                Self::is_transient(&e),
+                format!("Failed to commit transaction: {e}"),
+            )
+        })
+    }
+
+    /// Return `true` if retrying the operation that failed with `e` might
+    /// succeed.
+    fn is_transient(e: &deadpool_postgres::tokio_postgres::Error) -> bool {
+        e.code().is_none_or(|code| {
+            code == &SqlState::T_R_SERIALIZATION_FAILURE || code == &SqlState::T_R_DEADLOCK_DETECTED
+        })
+    }
+
+    /// Delay before retry number `retry` with jitter between half and the
+    /// full exponential backoff.
+    fn retry_backoff(retry: u32) -> Duration {
+        let full_micros = Self::RETRY_INITIAL_BACKOFF_MICROS
+            .saturating_mul(2u64.saturating_pow(retry.saturating_sub(1)))
+            .min(Self::RETRY_MAX_BACKOFF_MICROS);
+        let half_micros = full_micros / 2;
+        // Sub-second clock noise is random enough to spread out retries
+        let noise = u64::from(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .subsec_nanos(),
+        );
+        Duration::from_micros(half_micros + noise % (half_micros + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fragtale_dbp::dbp::facades::DatabaseProviderFacades;
+    use fragtale_dbp_conformance::ConformanceSuite;
+    use fragtale_dbp_conformance::ConformanceTarget;
+
+    /// Conformance target using the PostgreSQL database at the endpoint in
+    /// `FRAGTALE_TEST_POSTGRES_ENDPOINT` (default `localhost:5432`) with the
+    /// credentials in `FRAGTALE_TEST_POSTGRES_USERNAME` and
+    /// `FRAGTALE_TEST_POSTGRES_PASSWORD`.
+    struct PostgresConformanceTarget {
+        postgres_provider: Arc<PostgresProvider>,
+    }
+
+    impl PostgresConformanceTarget {
+        async fn new() -> Self {
+            let env_or = |name: &str, default: &str| {
+                std::env::var(name).unwrap_or_else(|_| default.to_owned())
+            };
+            // A schema of its own for each run leaves no state between runs
+            let schema = format!(
+                "conformance_{}",
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_micros()
+            );
+            let postgres_provider = PostgresProvider::new(
+                &schema,
+                &[env_or("FRAGTALE_TEST_POSTGRES_ENDPOINT", "localhost:5432")],
+                &env_or("FRAGTALE_TEST_POSTGRES_USERNAME", "postgres"),
+                &env_or("FRAGTALE_TEST_POSTGRES_PASSWORD", ""),
+            )
+            .await;
+            Self { postgres_provider }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ConformanceTarget for PostgresConformanceTarget {
+        async fn database_provider(&self) -> Arc<dyn DatabaseProviderFacades> {
+            Arc::new(self.postgres_provider.as_database_provider())
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a PostgreSQL database, see PostgresConformanceTarget"]
+    async fn conforms_to_provider_semantics() {
+        ConformanceSuite::run(&PostgresConformanceTarget::new().await)
+            .await
+            .assert_conformant();
+    }
+}
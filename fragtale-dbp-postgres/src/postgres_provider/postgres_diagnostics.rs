@@ -0,0 +1,143 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Whitelisted read-only diagnostic SQL queries.
+
+use crate::PostgresProvider;
+use fragtale_dbp::mb::DiagnosticQuery;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::UniqueTime;
+
+/// Whitelisted read-only diagnostic SQL queries.
+///
+/// Only these queries can be run and only values are bound, so diagnostic
+/// queries can never modify data or reach outside the topic.
+pub struct PostgresDiagnostics;
+
+impl PostgresDiagnostics {
+    /// QDX1. Consumer by id.
+    const SQL_CONSUMER: &'static str = "
+        SELECT row_to_json(t)::text
+        FROM (
+            SELECT * FROM consumer
+            WHERE topic_id = $1 AND consumer_id = $2
+        ) t
+        ";
+
+    /// QDX2. Delivery intents by consumer and range.
+    const SQL_DELIVERY_INTENTS: &'static str = "
+        SELECT row_to_json(t)::text
+        FROM (
+            SELECT * FROM delivery_intent
+            WHERE topic_id = $1 AND consumer_id = $2 AND unique_time >= $3 AND unique_time <= $4
+            ORDER BY unique_time ASC, delivering_instance_id ASC
+            LIMIT $5
+        ) t
+        ";
+
+    /// QDX3. Prepared delivery confirmations by consumer and range.
+    const SQL_DELIVERY_PREPARED: &'static str = "
+        SELECT row_to_json(t)::text
+        FROM (
+            SELECT * FROM delivery_prepared
+            WHERE topic_id = $1 AND consumer_id = $2 AND unique_time >= $3 AND unique_time <= $4
+            ORDER BY unique_time ASC
+            LIMIT $5
+        ) t
+        ";
+
+    /// QDX4. Delivery slots by consumer.
+    const SQL_DELIVERY_SLOTS: &'static str = "
+        SELECT row_to_json(t)::text
+        FROM (
+            SELECT * FROM delivery_slot
+            WHERE topic_id = $1 AND consumer_id = $2
+            ORDER BY slot ASC
+            LIMIT $3
+        ) t
+        ";
+
+    /// QDX5. Event identifiers by range.
+    const SQL_EVENT_IDS_BY_UNIQUE_TIME: &'static str = "
+        SELECT row_to_json(t)::text
+        FROM (
+            SELECT unique_time, event_id, descriptor_version FROM event
+            WHERE topic_id = $1 AND unique_time >= $2 AND unique_time <= $3
+            ORDER BY unique_time ASC
+            LIMIT $4
+        ) t
+        ";
+
+    /// Run a whitelisted diagnostic query.
+    ///
+    /// See [fragtale_dbp::dbp::facades::TopicFacade::diagnostic_query].
+    pub async fn query(
+        db: &PostgresProvider,
+        topic_id: &str,
+        diagnostic_query: DiagnosticQuery,
+        consumer_id: Option<&str>,
+        range: Option<(UniqueTime, UniqueTime)>,
+        max_results: usize,
+    ) -> Result<(Vec<String>, bool), MessageBrokerError> {
+        let consumer_id = consumer_id.unwrap_or_default();
+        let (from, to) = range
+            .map(|(from, to)| (from.as_encoded_i64(), to.as_encoded_i64()))
+            .unwrap_or_default();
+        // Ask for one more row than returned to detect if there are more results
+        let limit = i64::try_from(max_results)
+            .unwrap_or(i64::MAX)
+            .saturating_add(1);
+        let rows = match diagnostic_query {
+            DiagnosticQuery::Consumer => {
+                db.query(Self::SQL_CONSUMER, &[&topic_id, &consumer_id])
+                    .await
+            }
+            DiagnosticQuery::DeliveryIntents => {
+                db.query(
+                    Self::SQL_DELIVERY_INTENTS,
+                    &[&topic_id, &consumer_id, &from, &to, &limit],
+                )
+                .await
+            }
+            DiagnosticQuery::DeliveryPrepared => {
+                db.query(
+                    Self::SQL_DELIVERY_PREPARED,
+                    &[&topic_id, &consumer_id, &from, &to, &limit],
+                )
+                .await
+            }
+            DiagnosticQuery::DeliverySlots => {
+                db.query(Self::SQL_DELIVERY_SLOTS, &[&topic_id, &consumer_id, &limit])
+                    .await
+            }
+            DiagnosticQuery::EventIdsByUniqueTime => {
+                db.query(
+                    Self::SQL_EVENT_IDS_BY_UNIQUE_TIME,
+                    &[&topic_id, &from, &to, &limit],
+                )
+                .await
+            }
+        };
+        let mut rows = rows
+            .iter()
+            .map(|row| row.get::<_, String>(0))
+            .collect::<Vec<_>>();
+        let more = rows.len() > max_results;
+        rows.truncate(max_results);
+        Ok((rows, more))
+    }
+}
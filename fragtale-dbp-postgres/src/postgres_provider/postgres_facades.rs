@@ -0,0 +1,93 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PostgreSQL specific database code.
+
+mod postgres_authorization_facade;
+mod postgres_consumer_delivery_facade;
+mod postgres_event_facade;
+mod postgres_event_tracking_facade;
+mod postgres_instance_id_facade;
+mod postgres_integrity_protection_facade;
+mod postgres_topic_facade;
+
+pub use self::postgres_authorization_facade::*;
+pub use self::postgres_consumer_delivery_facade::*;
+pub use self::postgres_event_facade::*;
+pub use self::postgres_event_tracking_facade::*;
+pub use self::postgres_instance_id_facade::*;
+pub use self::postgres_integrity_protection_facade::*;
+pub use self::postgres_topic_facade::*;
+use crate::PostgresProvider;
+use fragtale_dbp::dbp::facades::*;
+use std::sync::Arc;
+
+/// PostgreSQL specific database code.
+pub struct PostgresProviderFacades {
+    authorization_facade: PostgresAuthorizationFacade,
+    consumer_delivery_facade: PostgresConsumerDeliveryFacade,
+    event_tracking_facade: PostgresEventTrackingFacade,
+    event_facade: PostgresEventFacade,
+    instance_id_facade: PostgresInstanceIdFacade,
+    integrity_protection_facade: PostgresIntegrityProtectionFacade,
+    topic_facade: PostgresTopicFacade,
+}
+
+impl PostgresProviderFacades {
+    /// Return a new instance.
+    pub fn new(postgres_provider: &Arc<PostgresProvider>) -> Self {
+        Self {
+            authorization_facade: PostgresAuthorizationFacade::new(postgres_provider),
+            consumer_delivery_facade: PostgresConsumerDeliveryFacade::new(postgres_provider),
+            event_tracking_facade: PostgresEventTrackingFacade::new(postgres_provider),
+            event_facade: PostgresEventFacade::new(postgres_provider),
+            instance_id_facade: PostgresInstanceIdFacade::new(postgres_provider),
+            integrity_protection_facade: PostgresIntegrityProtectionFacade::new(postgres_provider),
+            topic_facade: PostgresTopicFacade::new(postgres_provider),
+        }
+    }
+}
+
+impl DatabaseProviderFacades for PostgresProviderFacades {
+    fn authorization_facade(&self) -> &dyn AuthorizationFacade {
+        &self.authorization_facade
+    }
+
+    fn consumer_delivery_facade(&self) -> &dyn ConsumerDeliveryFacade {
+        &self.consumer_delivery_facade
+    }
+
+    fn event_tracking_facade(&self) -> &dyn EventTrackingFacade {
+        &self.event_tracking_facade
+    }
+
+    fn event_facade(&self) -> &dyn EventFacade {
+        &self.event_facade
+    }
+
+    fn instance_id_facade(&self) -> &dyn InstanceIdFacade {
+        &self.instance_id_facade
+    }
+
+    fn integrity_protection_facade(&self) -> &dyn IntegrityProtectionFacade {
+        &self.integrity_protection_facade
+    }
+
+    fn topic_facade(&self) -> &dyn TopicFacade {
+        &self.topic_facade
+    }
+}
@@ -0,0 +1,182 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PostgreSQL implementation of [AuthorizationFacade].
+
+use crate::PostgresProvider;
+use crate::postgres_provider::postgres_types::FromUnsignedOrDefault;
+use fragtale_dbp::dbp::facades::AuthorizationFacade;
+use std::sync::Arc;
+
+/// PostgreSQL implementation of [AuthorizationFacade].
+pub struct PostgresAuthorizationFacade {
+    postgres_provider: Arc<PostgresProvider>,
+}
+
+impl PostgresAuthorizationFacade {
+    /// QRG1. Check if a non-expired grant exists.
+    const SQL_SELECT: &'static str = "
+        SELECT identity
+        FROM resource_grant
+        WHERE resource = $1 AND identity = $2 AND (expires_ts IS NULL OR expires_ts > $3)
+        ";
+
+    /// QRG2. Get identities with a non-expired grant to a resource.
+    const SQL_SELECT_BY_RESOURCE: &'static str = "
+        SELECT identity
+        FROM resource_grant
+        WHERE resource = $1 AND (expires_ts IS NULL OR expires_ts > $2)
+        ORDER BY identity ASC
+        LIMIT $3
+        ";
+
+    /// QRG3. Get all non-expired grants.
+    const SQL_SELECT_ALL: &'static str = "
+        SELECT resource, identity
+        FROM resource_grant
+        WHERE expires_ts IS NULL OR expires_ts > $1
+        ORDER BY resource ASC, identity ASC
+        LIMIT $2
+        ";
+
+    /// QRG4. Upsert grant.
+    const SQL_UPSERT: &'static str = "
+        INSERT INTO resource_grant (resource, identity, expires_ts)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (resource, identity) DO UPDATE SET expires_ts = EXCLUDED.expires_ts
+        ";
+
+    /// QRG5. Delete grant.
+    const SQL_DELETE: &'static str = "
+        DELETE FROM resource_grant
+        WHERE resource = $1 AND identity = $2
+        ";
+
+    /// QPGU1. Consume a use unless all are consumed.
+    const SQL_UPSERT_PUBLISH_GRANT_USE: &'static str = "
+        INSERT INTO publish_grant_use (grant_uid, uses, expires_ts)
+        VALUES ($1, 1, $3)
+        ON CONFLICT (grant_uid) DO UPDATE SET uses = publish_grant_use.uses + 1
+        WHERE publish_grant_use.uses < $2
+        ";
+
+    /// QPGU2. Delete uses of expired grants.
+    const SQL_DELETE_EXPIRED_PUBLISH_GRANT_USES: &'static str = "
+        DELETE FROM publish_grant_use
+        WHERE expires_ts < $1
+        ";
+
+    /// Return a new instance.
+    pub fn new(postgres_provider: &Arc<PostgresProvider>) -> Self {
+        Self {
+            postgres_provider: Arc::clone(postgres_provider),
+        }
+    }
+
+    /// Return the current time in microseconds as a SQL `bigint`.
+    fn now() -> i64 {
+        i64::from_unsigned(fragtale_client::time::get_timestamp_micros())
+    }
+}
+
+#[async_trait::async_trait]
+impl AuthorizationFacade for PostgresAuthorizationFacade {
+    async fn is_authorized_to_resource(&self, identity: &str, resource: &str) -> bool {
+        self.postgres_provider
+            .query_first(Self::SQL_SELECT, &[&resource, &identity, &Self::now()])
+            .await
+            .is_some()
+    }
+
+    async fn is_any_authorized_to_resource(&self, resource: &str) -> bool {
+        !self
+            .identities_authorized_to_resource(resource, 1)
+            .await
+            .is_empty()
+    }
+
+    async fn identities_authorized_to_resource(
+        &self,
+        resource: &str,
+        max_results: usize,
+    ) -> Vec<String> {
+        let limit = i64::try_from(max_results).unwrap_or(i64::MAX);
+        self.postgres_provider
+            .query(
+                Self::SQL_SELECT_BY_RESOURCE,
+                &[&resource, &Self::now(), &limit],
+            )
+            .await
+            .iter()
+            .map(|row| row.get(0))
+            .collect()
+    }
+
+    async fn resource_grants(&self, max_results: usize) -> Vec<(String, String)> {
+        let limit = i64::try_from(max_results).unwrap_or(i64::MAX);
+        self.postgres_provider
+            .query(Self::SQL_SELECT_ALL, &[&Self::now(), &limit])
+            .await
+            .iter()
+            .map(|row| (row.get(0), row.get(1)))
+            .collect()
+    }
+
+    async fn grant_access_to_resource_for(
+        &self,
+        identity: &str,
+        resource: &str,
+        expires: Option<u64>,
+    ) -> bool {
+        let expires_ts = expires.map(i64::from_unsigned);
+        self.postgres_provider
+            .execute(Self::SQL_UPSERT, &[&resource, &identity, &expires_ts])
+            .await
+            .is_some()
+    }
+
+    async fn deny_access_to_resource_for(
+        &self,
+        identity: &str,
+        resource: &str,
+        _expires: Option<u64>,
+    ) -> bool {
+        self.postgres_provider
+            .execute(Self::SQL_DELETE, &[&resource, &identity])
+            .await
+            .is_some()
+    }
+
+    async fn publish_grant_use(&self, grant_uid: &str, max_uses: u32, expires: u64) -> bool {
+        let now = Self::now();
+        let expires_ts = i64::from_unsigned(expires);
+        if max_uses == 0 || expires_ts <= now {
+            return false;
+        }
+        self.postgres_provider
+            .execute(Self::SQL_DELETE_EXPIRED_PUBLISH_GRANT_USES, &[&now])
+            .await;
+        let max_uses = i32::try_from(max_uses).unwrap_or(i32::MAX);
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_PUBLISH_GRANT_USE,
+                &[&grant_uid, &max_uses, &expires_ts],
+            )
+            .await
+            .is_some_and(|modified| modified > 0)
+    }
+}
@@ -0,0 +1,960 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PostgreSQL implementation of [ConsumerDeliveryFacade].
+
+use crate::PostgresProvider;
+use crate::postgres_provider::StatementWithValues;
+use crate::postgres_provider::postgres_types::FromSignedOrDefault;
+use crate::postgres_provider::postgres_types::FromUnsignedOrDefault;
+use fragtale_dbp::dbp::facades::ConsumerDeliveryFacade;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use fragtale_dbp::mb::UniqueTime;
+use fragtale_dbp::mb::consumers::DeliveryIntentTemplate;
+use fragtale_dbp::mb::consumers::DeliveryIntentTemplateInsertable;
+use fragtale_dbp::mb::consumers::DeliveryRecord;
+use fragtale_dbp::mb::consumers::RetryBackoff;
+use std::sync::Arc;
+
+/// PostgreSQL implementation of [ConsumerDeliveryFacade].
+pub struct PostgresConsumerDeliveryFacade {
+    postgres_provider: Arc<PostgresProvider>,
+}
+
+impl PostgresConsumerDeliveryFacade {
+    /// Allowed characters for consumer identifiers.
+    const ALLOWED_CONSUMER_ID_CHARS: &str = "abcdefghijklmnopqrstuvwxyz0123456789_-:;";
+    /// Number of events to retrieve per page when populating fresh events.
+    const FRESH_PAGE_SIZE: usize = 128;
+    /// Number of events to retrieve per page when populating retries.
+    const RETRIES_PAGE_SIZE: usize = 1000;
+    /// Don't go looking for events before this software ever existed.
+    const MICROS_SINCE_EPOCH_20240101: u64 = 1_702_944_000_000_000;
+
+    /// QC1. Insert consumer or update the existing consumer.
+    const SQL_UPSERT_CONSUMER: &'static str = "
+        INSERT INTO consumer (topic_id, consumer_id, last_update_ts, latest_descriptor_version, unique_time_attempted, unique_time_done)
+        VALUES ($1, $2, $3, $4, $5, $5)
+        ON CONFLICT (topic_id, consumer_id) DO UPDATE
+        SET last_update_ts = EXCLUDED.last_update_ts,
+            latest_descriptor_version = GREATEST(consumer.latest_descriptor_version, EXCLUDED.latest_descriptor_version)
+        ";
+
+    /// QC2. Get consumer identifiers of topic.
+    const SQL_SELECT_CONSUMER_IDS: &'static str = "
+        SELECT consumer_id
+        FROM consumer
+        WHERE topic_id = $1
+        ORDER BY consumer_id ASC
+        ";
+
+    /// QC3. Get delivery positions of consumer.
+    const SQL_SELECT_POSITIONS: &'static str = "
+        SELECT unique_time_attempted, unique_time_done
+        FROM consumer
+        WHERE topic_id = $1 AND consumer_id = $2
+        ";
+
+    /// QC4. Set attempted position of consumer.
+    const SQL_UPDATE_ATTEMPTED: &'static str = "
+        UPDATE consumer
+        SET unique_time_attempted = $3
+        WHERE topic_id = $1 AND consumer_id = $2
+        ";
+
+    /// QC5. Set done position of consumer.
+    const SQL_UPDATE_DONE: &'static str = "
+        UPDATE consumer
+        SET unique_time_done = $3
+        WHERE topic_id = $1 AND consumer_id = $2
+        ";
+
+    /// QC6. Set both positions of consumer.
+    const SQL_UPDATE_POSITIONS: &'static str = "
+        UPDATE consumer
+        SET unique_time_attempted = $3, unique_time_done = $3
+        WHERE topic_id = $1 AND consumer_id = $2
+        ";
+
+    /// QCD1. Upsert consumer definition.
+    const SQL_UPSERT_DEFINITION: &'static str = "
+        INSERT INTO consumer_definition (topic_id, name, definition, updated_ts)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (topic_id, name) DO UPDATE
+        SET definition = EXCLUDED.definition, updated_ts = EXCLUDED.updated_ts
+        ";
+
+    /// QCD2. Delete consumer definition.
+    const SQL_DELETE_DEFINITION: &'static str = "
+        DELETE FROM consumer_definition
+        WHERE topic_id = $1 AND name = $2
+        ";
+
+    /// QCD3. Get consumer definitions of topic.
+    const SQL_SELECT_DEFINITIONS: &'static str = "
+        SELECT definition
+        FROM consumer_definition
+        WHERE topic_id = $1
+        ORDER BY name ASC
+        ";
+
+    /// QDI1. Serialize reservations of the delivery of an event.
+    const SQL_LOCK_INTENT: &'static str = "SELECT pg_advisory_xact_lock(hashtext($1), $2)";
+
+    /// QDI2. Get all delivery intents of an event.
+    const SQL_SELECT_INTENTS: &'static str = "
+        SELECT done, intent_ts, retry_count
+        FROM delivery_intent
+        WHERE topic_id = $1 AND consumer_id = $2 AND unique_time = $3
+        ";
+
+    /// QDI3. Upsert delivery intent.
+    const SQL_UPSERT_INTENT: &'static str = "
+        INSERT INTO delivery_intent (topic_id, consumer_id, unique_time, delivering_instance_id, intent_ts, event_id, descriptor_version, retry_count, done)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (topic_id, consumer_id, unique_time, delivering_instance_id) DO UPDATE
+        SET intent_ts = EXCLUDED.intent_ts, retry_count = EXCLUDED.retry_count, done = EXCLUDED.done
+        ";
+
+    /// QDI4. Mark delivery intent as done.
+    const SQL_UPDATE_INTENT_DONE: &'static str = "
+        UPDATE delivery_intent
+        SET done = true
+        WHERE topic_id = $1 AND consumer_id = $2 AND unique_time = $3 AND delivering_instance_id = $4
+        ";
+
+    /// QDI5. Delete delivery intents after a unique time.
+    const SQL_DELETE_INTENTS_AFTER: &'static str = "
+        DELETE FROM delivery_intent
+        WHERE topic_id = $1 AND consumer_id = $2 AND unique_time > $3
+        ";
+
+    /// QDI6. Get events after a unique time and if the consumer has any
+    /// delivery intent for them.
+    const SQL_SELECT_EVENTS_WITH_INTENT: &'static str = "
+        SELECT e.unique_time, e.event_id, e.descriptor_version, e.expires_ts, e.deliver_after_ts,
+            EXISTS (
+                SELECT 1 FROM delivery_intent d
+                WHERE d.topic_id = e.topic_id AND d.consumer_id = $2 AND d.unique_time = e.unique_time
+            )
+        FROM event e
+        WHERE e.topic_id = $1 AND e.unique_time > $3
+        ORDER BY e.unique_time ASC
+        LIMIT $4
+        ";
+
+    /// QDI7. Get delivery intents in a range of unique times grouped by
+    /// event.
+    const SQL_SELECT_INTENTS_BY_EVENT: &'static str = "
+        SELECT unique_time, bool_or(done), array_agg(intent_ts), array_agg(retry_count),
+            min(event_id), max(descriptor_version), max(intent_ts)
+        FROM delivery_intent
+        WHERE topic_id = $1 AND consumer_id = $2 AND unique_time > $3 AND unique_time <= $4
+        GROUP BY unique_time
+        ORDER BY unique_time ASC
+        LIMIT $5
+        ";
+
+    /// QDI8. Get delivery intents in a range of unique times.
+    const SQL_SELECT_INTENTS_IN_RANGE: &'static str = "
+        SELECT unique_time, event_id, intent_ts, done, retry_count
+        FROM delivery_intent
+        WHERE topic_id = $1 AND consumer_id = $2 AND unique_time >= $3 AND unique_time <= $4
+        ORDER BY unique_time ASC, delivering_instance_id ASC
+        LIMIT $5
+        ";
+
+    /// QDP1. Upsert prepared delivery.
+    const SQL_UPSERT_PREPARED: &'static str = "
+        INSERT INTO delivery_prepared (topic_id, consumer_id, unique_time, transaction_id, prepared_ts)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (topic_id, consumer_id, unique_time) DO UPDATE
+        SET transaction_id = EXCLUDED.transaction_id, prepared_ts = EXCLUDED.prepared_ts
+        ";
+
+    /// QDP2. Get prepared delivery.
+    const SQL_SELECT_PREPARED: &'static str = "
+        SELECT transaction_id
+        FROM delivery_prepared
+        WHERE topic_id = $1 AND consumer_id = $2 AND unique_time = $3
+        ";
+
+    /// QDP3. Delete prepared delivery.
+    const SQL_DELETE_PREPARED: &'static str = "
+        DELETE FROM delivery_prepared
+        WHERE topic_id = $1 AND consumer_id = $2 AND unique_time = $3
+        ";
+
+    /// QDS1. Delete expired delivery slots.
+    const SQL_DELETE_EXPIRED_SLOTS: &'static str = "
+        DELETE FROM delivery_slot
+        WHERE topic_id = $1 AND consumer_id = $2 AND expires_ts <= $3
+        ";
+
+    /// QDS2. Get claimed delivery slots.
+    const SQL_SELECT_SLOTS: &'static str = "
+        SELECT slot
+        FROM delivery_slot
+        WHERE topic_id = $1 AND consumer_id = $2
+        ";
+
+    /// QDS3. Claim delivery slot if it is free.
+    const SQL_INSERT_SLOT: &'static str = "
+        INSERT INTO delivery_slot (topic_id, consumer_id, slot, unique_time, expires_ts)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (topic_id, consumer_id, slot) DO NOTHING
+        ";
+
+    /// QDS4. Free delivery slot claimed for an event.
+    const SQL_DELETE_SLOT: &'static str = "
+        DELETE FROM delivery_slot
+        WHERE topic_id = $1 AND consumer_id = $2 AND unique_time = $3
+        ";
+
+    /// QDS5. Extend the claim of the delivery slot held for an event.
+    const SQL_UPDATE_SLOT_EXPIRES: &'static str = "
+        UPDATE delivery_slot
+        SET expires_ts = $4
+        WHERE topic_id = $1 AND consumer_id = $2 AND unique_time = $3
+        ";
+
+    /// Return a new instance.
+    pub fn new(postgres_provider: &Arc<PostgresProvider>) -> Self {
+        Self {
+            postgres_provider: Arc::clone(postgres_provider),
+        }
+    }
+
+    /// Assert that `consumer_id` uses allowed characters and is of the right
+    /// lenght.
+    fn assert_consumer_id_well_formed(consumer_id: &str) -> Result<(), MessageBrokerError> {
+        if consumer_id
+            .chars()
+            .any(|c| !Self::ALLOWED_CONSUMER_ID_CHARS.contains(c))
+        {
+            Err(
+                MessageBrokerErrorKind::MalformedIdentifier.error_with_msg(format!(
+                    "Invalid chars in consumer id '{consumer_id}'. Only '{}' are allowed.",
+                    Self::ALLOWED_CONSUMER_ID_CHARS
+                )),
+            )?;
+        }
+        if consumer_id.is_empty() || consumer_id.len() > 255 {
+            Err(
+                MessageBrokerErrorKind::MalformedIdentifier.error_with_msg(format!(
+                    "Invalid length of consumer id '{consumer_id}'. Must be of length 1-255."
+                )),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Return the attempted and done positions of the consumer.
+    async fn positions(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+    ) -> Option<(UniqueTime, UniqueTime)> {
+        self.postgres_provider
+            .query_first(Self::SQL_SELECT_POSITIONS, &[&topic_id, &consumer_id])
+            .await
+            .map(|row| {
+                (
+                    UniqueTime::from(row.get::<_, i64>(0)),
+                    UniqueTime::from(row.get::<_, i64>(1)),
+                )
+            })
+    }
+
+    /// Insert fresh entries into `consumer_delivery_cache` in the order they
+    /// were published.
+    ///
+    /// At most `max_pages` pages of `page_size` events are scanned.
+    ///
+    /// Return the last attempted [UniqueTime] of the contiguous range of
+    /// events with delivery intents after `attempted_low_exclusive` and if
+    /// any new events were found.
+    async fn populate_delivery_cache_with_fresh_pages(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        consumer_delivery_cache: &dyn DeliveryIntentTemplateInsertable,
+        attempted_low_exclusive: UniqueTime,
+        page_size: usize,
+        max_pages: usize,
+    ) -> (u64, bool) {
+        let mut any_new_found = false;
+        let mut all_attempted = true;
+        let mut last_attempted_ts = attempted_low_exclusive.as_encoded();
+        let mut unique_time_low_exclusive = attempted_low_exclusive.as_encoded_i64();
+        let limit = i64::try_from(page_size).unwrap_or(i64::MAX);
+        let now = fragtale_client::time::get_timestamp_micros();
+        for _page in 0..max_pages {
+            let rows = self
+                .postgres_provider
+                .query(
+                    Self::SQL_SELECT_EVENTS_WITH_INTENT,
+                    &[&topic_id, &consumer_id, &unique_time_low_exclusive, &limit],
+                )
+                .await;
+            for row in &rows {
+                let event_unique_time = UniqueTime::from(row.get::<_, i64>(0));
+                unique_time_low_exclusive = event_unique_time.as_encoded_i64();
+                if row.get::<_, bool>(5) {
+                    // Don't bother adding this to the queue if there is an intent already
+                    if all_attempted {
+                        last_attempted_ts = event_unique_time.as_encoded();
+                    }
+                    continue;
+                }
+                all_attempted = false;
+                if row
+                    .get::<_, Option<i64>>(4)
+                    .map(u64::from_signed)
+                    .is_some_and(|deliver_after_ts| now < deliver_after_ts)
+                {
+                    // Keep the attempted baseline before the event until it may be delivered
+                    continue;
+                }
+                consumer_delivery_cache.insert(DeliveryIntentTemplate::new(
+                    event_unique_time,
+                    row.get(1),
+                    row.get::<_, Option<i64>>(2).map(u64::from_signed),
+                    None,
+                    row.get::<_, Option<i64>>(3).map(u64::from_signed),
+                ));
+                any_new_found = true;
+            }
+            if rows.len() < page_size || consumer_delivery_cache.is_full() {
+                break;
+            }
+        }
+        (last_attempted_ts, any_new_found)
+    }
+
+    /// Reserve the delivery of an event in a single transaction.
+    ///
+    /// Return `Ok(true)` if the delivery intent was persisted.
+    #[allow(clippy::too_many_arguments)]
+    async fn delivery_intent_reserve_in_transaction(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        event_id: &str,
+        event_unique_time: UniqueTime,
+        instance_id_local: u16,
+        descriptor_version: &Option<u64>,
+        intent_ts_micros: u64,
+        freshness_duration_micros: u64,
+        failed_intent_ts_micros: Option<u64>,
+    ) -> Result<bool, deadpool_postgres::tokio_postgres::Error> {
+        let Some(mut client) = self.postgres_provider.client().await else {
+            return Ok(false);
+        };
+        let transaction = client.transaction().await?;
+        // Other instances reserving the same event will wait for this
+        // transaction to complete, so there is no need to retract intents.
+        let lock_key = format!("{topic_id}/{consumer_id}");
+        let lock_sub_key =
+            i32::try_from(event_unique_time.as_encoded() & 0x7fff_ffff).unwrap_or_default();
+        transaction
+            .execute(Self::SQL_LOCK_INTENT, &[&lock_key, &lock_sub_key])
+            .await?;
+        let unique_time = event_unique_time.as_encoded_i64();
+        let timeout_ts = intent_ts_micros - freshness_duration_micros;
+        let intents = transaction
+            .query(
+                Self::SQL_SELECT_INTENTS,
+                &[&topic_id, &consumer_id, &unique_time],
+            )
+            .await?
+            .iter()
+            .map(|row| {
+                (
+                    row.get::<_, bool>(0),
+                    u64::from_signed(row.get::<_, i64>(1)),
+                    u32::from_signed(row.get::<_, i32>(2)),
+                )
+            })
+            .collect::<Vec<_>>();
+        if intents.iter().any(|(done, intent_ts, _retry_count)| {
+            // Another node has taken care of this
+            *done ||
+            // Another node is about to take care of this
+            *intent_ts > timeout_ts
+        }) {
+            return Ok(false);
+        }
+        // A retry counts on top of the most retried earlier intent
+        let retry_count = if failed_intent_ts_micros.is_some() {
+            intents
+                .iter()
+                .map(|(_done, _intent_ts, retry_count)| retry_count.saturating_add(1))
+                .max()
+                .unwrap_or(1)
+        } else {
+            0
+        };
+        transaction
+            .execute(
+                Self::SQL_UPSERT_INTENT,
+                &[
+                    &topic_id,
+                    &consumer_id,
+                    &unique_time,
+                    &i16::from_unsigned(instance_id_local),
+                    &i64::from_unsigned(intent_ts_micros),
+                    &event_id,
+                    &descriptor_version.map(i64::from_unsigned),
+                    &i32::from_unsigned(retry_count),
+                    &false,
+                ],
+            )
+            .await?;
+        transaction.commit().await?;
+        Ok(true)
+    }
+}
+
+#[async_trait::async_trait]
+impl ConsumerDeliveryFacade for PostgresConsumerDeliveryFacade {
+    async fn ensure_consumer_setup(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        baseline_ts: Option<u64>,
+        encoded_descriptor_version: Option<u64>,
+    ) -> Result<(), MessageBrokerError> {
+        Self::assert_consumer_id_well_formed(consumer_id)?;
+        let now_ts = fragtale_client::time::get_timestamp_micros();
+        // Only used when the consumer does not exist yet
+        let baseline_ts = UniqueTime::min_encoded_for_micros(std::cmp::max(
+            Self::MICROS_SINCE_EPOCH_20240101,
+            baseline_ts.unwrap_or(now_ts),
+        ));
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_CONSUMER,
+                &[
+                    &topic_id,
+                    &consumer_id,
+                    &i64::from_unsigned(now_ts),
+                    &encoded_descriptor_version.map(i64::from_unsigned),
+                    &i64::from_unsigned(baseline_ts),
+                ],
+            )
+            .await
+            .ok_or_else(|| {
+                MessageBrokerErrorKind::PersistenceFailure.error_with_msg(format!(
+                    "Failed to set up consumer '{consumer_id}' of topic '{topic_id}'."
+                ))
+            })?;
+        Ok(())
+    }
+
+    async fn consumer_ids(&self, topic_id: &str) -> Vec<String> {
+        self.postgres_provider
+            .query(Self::SQL_SELECT_CONSUMER_IDS, &[&topic_id])
+            .await
+            .iter()
+            .map(|row| row.get(0))
+            .collect()
+    }
+
+    async fn consumer_definition_persist(
+        &self,
+        topic_id: &str,
+        name: &str,
+        definition: &str,
+        updated_ts_micros: u64,
+    ) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_DEFINITION,
+                &[
+                    &topic_id,
+                    &name,
+                    &definition,
+                    &i64::from_unsigned(updated_ts_micros),
+                ],
+            )
+            .await
+            .is_some()
+    }
+
+    async fn consumer_definition_remove(&self, topic_id: &str, name: &str) {
+        self.postgres_provider
+            .execute(Self::SQL_DELETE_DEFINITION, &[&topic_id, &name])
+            .await;
+    }
+
+    async fn consumer_definitions(&self, topic_id: &str) -> Vec<String> {
+        self.postgres_provider
+            .query(Self::SQL_SELECT_DEFINITIONS, &[&topic_id])
+            .await
+            .iter()
+            .map(|row| row.get(0))
+            .collect()
+    }
+
+    async fn consumer_get_attempted_by_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+    ) -> Option<UniqueTime> {
+        self.positions(topic_id, consumer_id)
+            .await
+            .map(|(attempted, _done)| attempted)
+    }
+
+    async fn consumer_get_done_by_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+    ) -> Option<UniqueTime> {
+        self.positions(topic_id, consumer_id)
+            .await
+            .map(|(_attempted, done)| done)
+    }
+
+    async fn consumer_set_attempted_by_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        attempted: UniqueTime,
+    ) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPDATE_ATTEMPTED,
+                &[&topic_id, &consumer_id, &attempted.as_encoded_i64()],
+            )
+            .await
+            .is_some_and(|updated| updated > 0)
+    }
+
+    async fn consumer_set_done_by_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        done: UniqueTime,
+    ) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPDATE_DONE,
+                &[&topic_id, &consumer_id, &done.as_encoded_i64()],
+            )
+            .await
+            .is_some_and(|updated| updated > 0)
+    }
+
+    async fn consumer_seek(&self, topic_id: &str, consumer_id: &str, position: UniqueTime) -> bool {
+        let mut statements: Vec<StatementWithValues> = Vec::with_capacity(2);
+        let attempted = self
+            .consumer_get_attempted_by_id(topic_id, consumer_id)
+            .await;
+        if let Some(attempted) = attempted
+            && attempted > position
+        {
+            statements.push((
+                Self::SQL_DELETE_INTENTS_AFTER,
+                vec![
+                    Box::new(topic_id.to_owned()),
+                    Box::new(consumer_id.to_owned()),
+                    Box::new(position.as_encoded_i64()),
+                ],
+            ));
+        }
+        statements.push((
+            Self::SQL_UPDATE_POSITIONS,
+            vec![
+                Box::new(topic_id.to_owned()),
+                Box::new(consumer_id.to_owned()),
+                Box::new(position.as_encoded_i64()),
+            ],
+        ));
+        self.postgres_provider
+            .transaction_with_retries(&statements, 0)
+            .await
+            .is_ok()
+    }
+
+    async fn delivery_intent_mark_done(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+        delivery_instance_id: u16,
+    ) {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPDATE_INTENT_DONE,
+                &[
+                    &topic_id,
+                    &consumer_id,
+                    &unique_time.as_encoded_i64(),
+                    &i16::from_unsigned(delivery_instance_id),
+                ],
+            )
+            .await;
+    }
+
+    async fn delivery_prepare(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+        transaction_id: &str,
+        prepared_ts_micros: u64,
+    ) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_PREPARED,
+                &[
+                    &topic_id,
+                    &consumer_id,
+                    &unique_time.as_encoded_i64(),
+                    &transaction_id,
+                    &i64::from_unsigned(prepared_ts_micros),
+                ],
+            )
+            .await
+            .is_some()
+    }
+
+    async fn delivery_prepared_transaction_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    ) -> Option<String> {
+        self.postgres_provider
+            .query_first(
+                Self::SQL_SELECT_PREPARED,
+                &[&topic_id, &consumer_id, &unique_time.as_encoded_i64()],
+            )
+            .await
+            .map(|row| row.get(0))
+    }
+
+    async fn delivery_prepared_remove(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    ) {
+        self.postgres_provider
+            .execute(
+                Self::SQL_DELETE_PREPARED,
+                &[&topic_id, &consumer_id, &unique_time.as_encoded_i64()],
+            )
+            .await;
+    }
+
+    async fn delivery_slot_claim(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        slots: u32,
+        unique_time: UniqueTime,
+        time_to_live_micros: u64,
+    ) -> bool {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        self.postgres_provider
+            .execute(
+                Self::SQL_DELETE_EXPIRED_SLOTS,
+                &[&topic_id, &consumer_id, &i64::from_unsigned(now_micros)],
+            )
+            .await;
+        let expires_ts = i64::from_unsigned(now_micros + time_to_live_micros);
+        // A redelivered event keeps the slot it already holds
+        if self
+            .postgres_provider
+            .execute(
+                Self::SQL_UPDATE_SLOT_EXPIRES,
+                &[
+                    &topic_id,
+                    &consumer_id,
+                    &unique_time.as_encoded_i64(),
+                    &expires_ts,
+                ],
+            )
+            .await
+            .is_some_and(|updated| updated > 0)
+        {
+            return true;
+        }
+        let claimed_slots = self
+            .postgres_provider
+            .query(Self::SQL_SELECT_SLOTS, &[&topic_id, &consumer_id])
+            .await
+            .iter()
+            .map(|row| u32::from_signed(row.get::<_, i32>(0)))
+            .collect::<Vec<_>>();
+        for slot in (0..slots).filter(|slot| !claimed_slots.contains(slot)) {
+            // Another instance might claim the same free slot first
+            if self
+                .postgres_provider
+                .execute(
+                    Self::SQL_INSERT_SLOT,
+                    &[
+                        &topic_id,
+                        &consumer_id,
+                        &i32::from_unsigned(slot),
+                        &unique_time.as_encoded_i64(),
+                        &expires_ts,
+                    ],
+                )
+                .await
+                .is_some_and(|inserted| inserted == 1)
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    async fn delivery_slot_release(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    ) {
+        self.postgres_provider
+            .execute(
+                Self::SQL_DELETE_SLOT,
+                &[&topic_id, &consumer_id, &unique_time.as_encoded_i64()],
+            )
+            .await;
+    }
+
+    async fn delivery_intent_insert_done(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        event_id: &str,
+        event_unique_time: UniqueTime,
+        instance_id_local: u16,
+        descriptor_version: &Option<u64>,
+        intent_ts_micros: u64,
+    ) {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_INTENT,
+                &[
+                    &topic_id,
+                    &consumer_id,
+                    &event_unique_time.as_encoded_i64(),
+                    &i16::from_unsigned(instance_id_local),
+                    &i64::from_unsigned(intent_ts_micros),
+                    &event_id,
+                    &descriptor_version.map(i64::from_unsigned),
+                    &0i32,
+                    &true,
+                ],
+            )
+            .await;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn delivery_intent_reserve(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        event_id: &str,
+        event_unique_time: UniqueTime,
+        instance_id_local: u16,
+        descriptor_version: &Option<u64>,
+        intent_ts_micros: u64,
+        freshness_duration_micros: u64,
+        failed_intent_ts_micros: Option<u64>,
+    ) -> bool {
+        self.delivery_intent_reserve_in_transaction(
+            topic_id,
+            consumer_id,
+            event_id,
+            event_unique_time,
+            instance_id_local,
+            descriptor_version,
+            intent_ts_micros,
+            freshness_duration_micros,
+            failed_intent_ts_micros,
+        )
+        .await
+        .map_err(|e| {
+            log::debug!(
+                "Failed to reserve delivery of event {event_unique_time:?} in topic '{topic_id}' to '{consumer_id}': {e}"
+            )
+        })
+        .unwrap_or(false)
+    }
+
+    async fn delivery_records_by_bucket(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        bucket: u64,
+        max_results: usize,
+    ) -> (Vec<DeliveryRecord>, bool) {
+        let ret = self
+            .postgres_provider
+            .query(
+                Self::SQL_SELECT_INTENTS_IN_RANGE,
+                &[
+                    &topic_id,
+                    &consumer_id,
+                    &i64::from_unsigned(UniqueTime::min_encoded_in_bucket(bucket)),
+                    &i64::from_unsigned(UniqueTime::max_encoded_in_bucket(bucket)),
+                    &i64::try_from(max_results).unwrap_or(i64::MAX),
+                ],
+            )
+            .await
+            .iter()
+            .map(|row| {
+                DeliveryRecord::new(
+                    UniqueTime::from(row.get::<_, i64>(0)),
+                    row.get(1),
+                    u64::from_signed(row.get::<_, i64>(2)),
+                    row.get(3),
+                    u32::from_signed(row.get::<_, i32>(4)),
+                )
+            })
+            .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+
+    async fn populate_delivery_cache_with_fresh(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        consumer_delivery_cache: Box<Arc<dyn DeliveryIntentTemplateInsertable>>,
+        attempted_low_exclusive: UniqueTime,
+    ) -> (u64, bool) {
+        self.populate_delivery_cache_with_fresh_pages(
+            topic_id,
+            consumer_id,
+            consumer_delivery_cache.as_ref().as_ref(),
+            attempted_low_exclusive,
+            Self::FRESH_PAGE_SIZE,
+            usize::MAX,
+        )
+        .await
+    }
+
+    async fn populate_delivery_cache_initial(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        consumer_delivery_cache: Box<Arc<dyn DeliveryIntentTemplateInsertable>>,
+        attempted_low_exclusive: UniqueTime,
+        max_events: usize,
+    ) -> bool {
+        let (_last_attempted_ts, any_new_found) = self
+            .populate_delivery_cache_with_fresh_pages(
+                topic_id,
+                consumer_id,
+                consumer_delivery_cache.as_ref().as_ref(),
+                attempted_low_exclusive,
+                max_events,
+                1,
+            )
+            .await;
+        any_new_found
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn populate_delivery_cache_with_retries(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        consumer_delivery_cache: Box<Arc<dyn DeliveryIntentTemplateInsertable>>,
+        done_low_exclusive: UniqueTime,
+        freshness_duration_micros: u64,
+        clock_skew_tolerance_micros: u64,
+        retry_backoff: &RetryBackoff,
+    ) -> u64 {
+        let now = fragtale_client::time::get_timestamp_micros();
+        let timeout_ts = now - freshness_duration_micros;
+        let unique_time_high_inclusive =
+            i64::from_unsigned(UniqueTime::min_encoded_for_micros(timeout_ts));
+        let mut unique_time_low_exclusive = done_low_exclusive.as_encoded_i64();
+        let limit = i64::try_from(Self::RETRIES_PAGE_SIZE).unwrap_or(i64::MAX);
+        let mut all_done = true;
+        let mut last_done_ts = done_low_exclusive;
+        loop {
+            let rows = self
+                .postgres_provider
+                .query(
+                    Self::SQL_SELECT_INTENTS_BY_EVENT,
+                    &[
+                        &topic_id,
+                        &consumer_id,
+                        &unique_time_low_exclusive,
+                        &unique_time_high_inclusive,
+                        &limit,
+                    ],
+                )
+                .await;
+            for row in &rows {
+                let unique_time = UniqueTime::from(row.get::<_, i64>(0));
+                unique_time_low_exclusive = unique_time.as_encoded_i64();
+                // Track if all events are done (or if we have to retry deliveries again later)
+                if row.get::<_, bool>(1) {
+                    if all_done {
+                        last_done_ts = unique_time;
+                    }
+                    continue;
+                }
+                all_done = false;
+                // Back off from events that have failed repeatedly. Any
+                // intent of the event that is not due postpones the retry.
+                let not_due = row
+                    .get::<_, Vec<i64>>(2)
+                    .into_iter()
+                    .zip(row.get::<_, Vec<i32>>(3))
+                    .any(|(intent_ts, retry_count)| {
+                        retry_backoff.get_due_ts_micros(
+                            u64::from_signed(intent_ts),
+                            u32::from_signed(retry_count),
+                            freshness_duration_micros,
+                        ) > now
+                    });
+                if not_due {
+                    continue;
+                }
+                consumer_delivery_cache.insert(DeliveryIntentTemplate::new(
+                    unique_time,
+                    row.get(4),
+                    row.get::<_, Option<i64>>(5).map(u64::from_signed),
+                    Some(u64::from_signed(row.get::<_, i64>(6))),
+                    None,
+                ));
+                if consumer_delivery_cache.is_full() {
+                    break;
+                }
+            }
+            if rows.len() < Self::RETRIES_PAGE_SIZE || consumer_delivery_cache.is_full() {
+                break;
+            }
+        }
+        std::cmp::min(
+            last_done_ts.as_encoded(),
+            UniqueTime::min_encoded_for_micros(timeout_ts - clock_skew_tolerance_micros),
+        )
+    }
+}
@@ -0,0 +1,822 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PostgreSQL implementation of [EventFacade].
+
+use crate::PostgresProvider;
+use crate::postgres_provider::StatementWithValues;
+use crate::postgres_provider::postgres_types::FromSignedOrDefault;
+use crate::postgres_provider::postgres_types::FromUnsignedOrDefault;
+use deadpool_postgres::tokio_postgres::Row;
+use fragtale_dbp::dbp::facades::EventFacade;
+use fragtale_dbp::mb::EventAnnotation;
+use fragtale_dbp::mb::ExtractedValue;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use fragtale_dbp::mb::RejectedEvent;
+use fragtale_dbp::mb::TopicEvent;
+use fragtale_dbp::mb::UniqueTime;
+use fragtale_dbp::mb::consumers::EventDeliveryGist;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// PostgreSQL implementation of [EventFacade].
+pub struct PostgresEventFacade {
+    postgres_provider: Arc<PostgresProvider>,
+}
+
+impl PostgresEventFacade {
+    /// Number of retries of transient failures when persisting events.
+    const EVENT_PERSIST_MAX_RETRIES: u32 = 3;
+
+    /// Upper bound of the number of event identifiers returned from an index
+    /// lookup.
+    const EVENT_IDS_BY_INDEX_MAX_RESULTS: i64 = 512 * 1024;
+
+    /// QE1. Get the newest event by event identifier.
+    const SQL_SELECT_BY_ID: &'static str = "
+        SELECT unique_time, document, protection_ref, correlation_token, priority, expires_ts
+        FROM event
+        WHERE topic_id = $1 AND event_id = $2
+        ORDER BY unique_time DESC
+        LIMIT 1
+        ";
+
+    /// QE2. Get event by event identifier and unique time.
+    const SQL_SELECT_BY_ID_AND_UNIQUE_TIME: &'static str = "
+        SELECT unique_time, document, protection_ref, correlation_token, priority, expires_ts
+        FROM event
+        WHERE topic_id = $1 AND unique_time = $2 AND event_id = $3
+        ";
+
+    /// QE3. Get the newest event by correlation token.
+    const SQL_SELECT_BY_CORRELATION_TOKEN: &'static str = "
+        SELECT unique_time, document, protection_ref, correlation_token, priority, expires_ts
+        FROM event
+        WHERE topic_id = $1 AND correlation_token = $2
+        ORDER BY unique_time DESC
+        LIMIT 1
+        ";
+
+    /// QE4. Insert event.
+    const SQL_INSERT: &'static str = "
+        INSERT INTO event (topic_id, unique_time, event_id, document, protection_ref, correlation_token, descriptor_version, priority, expires_ts, deliver_after_ts)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (topic_id, unique_time) DO NOTHING
+        ";
+
+    /// QE5. Insert index entry of event.
+    const SQL_INSERT_INDEX: &'static str = "
+        INSERT INTO event_index (topic_id, index_column, index_key, unique_time, event_id)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (topic_id, index_column, index_key, unique_time) DO NOTHING
+        ";
+
+    /// QE6. Get event identifiers by index (newest first).
+    const SQL_SELECT_BY_INDEX: &'static str = "
+        SELECT event_id
+        FROM event_index
+        WHERE topic_id = $1 AND index_column = $2 AND index_key = $3
+        ORDER BY unique_time DESC
+        LIMIT $4
+        ";
+
+    /// QE7. Upsert topic of event identifier and keep the latest unique time.
+    const SQL_UPSERT_EVENT_TOPIC: &'static str = "
+        INSERT INTO event_topic (event_id, topic_id, unique_time)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (event_id, topic_id) DO UPDATE
+        SET unique_time = GREATEST(event_topic.unique_time, EXCLUDED.unique_time)
+        ";
+
+    /// QE8. Get topics of event identifier.
+    const SQL_SELECT_EVENT_TOPICS: &'static str = "
+        SELECT topic_id, unique_time
+        FROM event_topic
+        WHERE event_id = $1
+        ORDER BY topic_id ASC
+        LIMIT $2
+        ";
+
+    /// QE9. Upsert rejected event.
+    const SQL_UPSERT_REJECTED: &'static str = "
+        INSERT INTO rejected_event (topic_id, event_id, rejected_ts, document, priority, descriptor_version, correlation_token, publisher, error_message)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        ON CONFLICT (topic_id, event_id) DO UPDATE
+        SET rejected_ts = EXCLUDED.rejected_ts, document = EXCLUDED.document,
+            priority = EXCLUDED.priority, descriptor_version = EXCLUDED.descriptor_version,
+            correlation_token = EXCLUDED.correlation_token, publisher = EXCLUDED.publisher,
+            error_message = EXCLUDED.error_message
+        ";
+
+    /// QE10. Get rejected events after an event identifier.
+    const SQL_SELECT_REJECTED: &'static str = "
+        SELECT event_id, rejected_ts, document, priority, descriptor_version, correlation_token, publisher, error_message
+        FROM rejected_event
+        WHERE topic_id = $1 AND event_id > $2
+        ORDER BY event_id ASC
+        LIMIT $3
+        ";
+
+    /// QE11. Get rejected event by event identifier.
+    const SQL_SELECT_REJECTED_BY_ID: &'static str = "
+        SELECT event_id, rejected_ts, document, priority, descriptor_version, correlation_token, publisher, error_message
+        FROM rejected_event
+        WHERE topic_id = $1 AND event_id = $2
+        ";
+
+    /// QE12. Delete rejected event.
+    const SQL_DELETE_REJECTED: &'static str = "
+        DELETE FROM rejected_event
+        WHERE topic_id = $1 AND event_id = $2
+        ";
+
+    /// QE13. Upsert event annotation.
+    const SQL_UPSERT_ANNOTATION: &'static str = "
+        INSERT INTO event_annotation (topic_id, event_id, annotated_ts, author, kind, value)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (topic_id, event_id, annotated_ts, author) DO UPDATE
+        SET kind = EXCLUDED.kind, value = EXCLUDED.value
+        ";
+
+    /// QE14. Get event annotations.
+    const SQL_SELECT_ANNOTATIONS: &'static str = "
+        SELECT event_id, annotated_ts, author, kind, value
+        FROM event_annotation
+        WHERE topic_id = $1 AND event_id = $2
+        ORDER BY annotated_ts ASC, author ASC
+        LIMIT $3
+        ";
+
+    /// QE15. Get used buckets in a range of unique times.
+    const SQL_SELECT_BUCKETS: &'static str = "
+        SELECT DISTINCT unique_time >> 30 AS bucket
+        FROM event
+        WHERE topic_id = $1 AND unique_time >= $2 AND unique_time <= $3
+        ORDER BY bucket ASC
+        LIMIT $4
+        ";
+
+    /// QE16. Count events in a range of unique times.
+    const SQL_COUNT_IN_RANGE: &'static str = "
+        SELECT count(*)
+        FROM event
+        WHERE topic_id = $1 AND unique_time >= $2 AND unique_time <= $3
+        ";
+
+    /// QE17. Get events in a range of unique times.
+    const SQL_SELECT_IN_RANGE: &'static str = "
+        SELECT unique_time, event_id, descriptor_version
+        FROM event
+        WHERE topic_id = $1 AND unique_time >= $2 AND unique_time <= $3
+        ORDER BY unique_time ASC
+        LIMIT $4
+        ";
+
+    /// QE18. Get unique times of event identifier (newest first).
+    const SQL_SELECT_UNIQUE_TIMES_BY_ID: &'static str = "
+        SELECT unique_time
+        FROM event
+        WHERE topic_id = $1 AND event_id = $2
+        ORDER BY unique_time DESC
+        LIMIT $3
+        ";
+
+    /// QE19. Delete event.
+    const SQL_DELETE: &'static str = "
+        DELETE FROM event
+        WHERE topic_id = $1 AND unique_time = $2 AND event_id = $3
+        ";
+
+    /// QE20. Delete index entries of event.
+    const SQL_DELETE_INDEX: &'static str = "
+        DELETE FROM event_index
+        WHERE topic_id = $1 AND unique_time = $2 AND event_id = $3
+        ";
+
+    /// QE21. Delete event annotations once no event with the identifier
+    /// remains.
+    const SQL_DELETE_ANNOTATIONS_IF_UNUSED: &'static str = "
+        DELETE FROM event_annotation
+        WHERE topic_id = $1 AND event_id = $2
+        AND NOT EXISTS (SELECT 1 FROM event WHERE topic_id = $1 AND event_id = $2)
+        ";
+
+    /// QE22. Delete topic of event identifier once no event with the
+    /// identifier remains.
+    const SQL_DELETE_EVENT_TOPIC_IF_UNUSED: &'static str = "
+        DELETE FROM event_topic
+        WHERE topic_id = $1 AND event_id = $2
+        AND NOT EXISTS (SELECT 1 FROM event WHERE topic_id = $1 AND event_id = $2)
+        ";
+
+    /// QE23. Replace the document of event.
+    const SQL_UPDATE_DOCUMENT: &'static str = "
+        UPDATE event
+        SET document = $4, protection_ref = $5
+        WHERE topic_id = $1 AND unique_time = $2 AND event_id = $3
+        ";
+
+    /// QE24. Delete event annotations.
+    const SQL_DELETE_ANNOTATIONS: &'static str = "
+        DELETE FROM event_annotation
+        WHERE topic_id = $1 AND event_id = $2
+        ";
+
+    /// Return a new instance.
+    pub fn new(postgres_provider: &Arc<PostgresProvider>) -> Self {
+        Self {
+            postgres_provider: Arc::clone(postgres_provider),
+        }
+    }
+
+    /// Convert `max_results` to a SQL `LIMIT` value.
+    fn limit(max_results: usize) -> i64 {
+        i64::try_from(max_results).unwrap_or(i64::MAX)
+    }
+
+    /// Map a row from QE1-QE3 to an [EventDeliveryGist].
+    fn event_delivery_gist_from_row(row: &Row) -> EventDeliveryGist {
+        EventDeliveryGist::new(
+            UniqueTime::from(row.get::<_, i64>(0)),
+            Arc::from(row.get::<_, String>(1)),
+            row.get(2),
+            row.get(3),
+            Some(u8::from_signed(row.get::<_, i16>(4))),
+            row.get::<_, Option<i64>>(5).map(u64::from_signed),
+        )
+    }
+
+    /// Map a row from QE10-QE11 to a [RejectedEvent].
+    fn rejected_event_from_row(row: &Row) -> RejectedEvent {
+        RejectedEvent::new(
+            row.get(0),
+            u64::from_signed(row.get::<_, i64>(1)),
+            row.get(2),
+            row.get::<_, Option<i16>>(3).map(u8::from_signed),
+            row.get::<_, Option<i64>>(4).map(u64::from_signed),
+            row.get(5),
+            row.get(6),
+            row.get(7),
+        )
+    }
+
+    /// Append the statements for inserting index entries of an event.
+    fn push_index_statements(
+        statements: &mut Vec<StatementWithValues>,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        additional_columns: &HashMap<String, ExtractedValue>,
+    ) {
+        for (index_column, value) in additional_columns {
+            // Collections are indexed by each member
+            for index_key in value.as_index_keys() {
+                statements.push((
+                    Self::SQL_INSERT_INDEX,
+                    vec![
+                        Box::new(topic_id.to_owned()),
+                        Box::new(index_column.to_owned()),
+                        Box::new(index_key),
+                        Box::new(unique_time.as_encoded_i64()),
+                        Box::new(event_id.to_owned()),
+                    ],
+                ));
+            }
+        }
+    }
+
+    /// Append all statements for persisting an event.
+    fn push_event_statements(
+        statements: &mut Vec<StatementWithValues>,
+        topic_id: &str,
+        topic_event: &TopicEvent,
+    ) {
+        let unique_time = topic_event.get_unique_time();
+        statements.push((
+            Self::SQL_INSERT,
+            vec![
+                Box::new(topic_id.to_owned()),
+                Box::new(unique_time.as_encoded_i64()),
+                Box::new(topic_event.get_event_id().to_owned()),
+                Box::new(topic_event.get_document().to_owned()),
+                Box::new(topic_event.get_protection_ref().to_owned()),
+                Box::new(topic_event.get_correlation_token().to_owned()),
+                Box::new(topic_event.get_descriptor_version().map(i64::from_unsigned)),
+                Box::new(i16::from_unsigned(topic_event.get_priority())),
+                Box::new(topic_event.get_expires_ts().map(i64::from_unsigned)),
+                Box::new(topic_event.get_deliver_after_ts().map(i64::from_unsigned)),
+            ],
+        ));
+        Self::push_index_statements(
+            statements,
+            topic_id,
+            topic_event.get_event_id(),
+            unique_time,
+            topic_event.get_additional_columns(),
+        );
+        statements.push((
+            Self::SQL_UPSERT_EVENT_TOPIC,
+            vec![
+                Box::new(topic_event.get_event_id().to_owned()),
+                Box::new(topic_id.to_owned()),
+                Box::new(unique_time.as_encoded_i64()),
+            ],
+        ));
+    }
+}
+
+#[async_trait::async_trait]
+impl EventFacade for PostgresEventFacade {
+    async fn event_by_id(&self, topic_id: &str, event_id: &str) -> Option<EventDeliveryGist> {
+        self.postgres_provider
+            .query_first(Self::SQL_SELECT_BY_ID, &[&topic_id, &event_id])
+            .await
+            .as_ref()
+            .map(Self::event_delivery_gist_from_row)
+    }
+
+    async fn event_by_id_and_unique_time(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+    ) -> Option<EventDeliveryGist> {
+        self.postgres_provider
+            .query_first(
+                Self::SQL_SELECT_BY_ID_AND_UNIQUE_TIME,
+                &[&topic_id, &unique_time.as_encoded_i64(), &event_id],
+            )
+            .await
+            .as_ref()
+            .map(Self::event_delivery_gist_from_row)
+    }
+
+    async fn event_ids_by_index(
+        &self,
+        topic_id: &str,
+        index_column: &str,
+        index_key: &str,
+    ) -> Vec<String> {
+        self.postgres_provider
+            .query(
+                Self::SQL_SELECT_BY_INDEX,
+                &[
+                    &topic_id,
+                    &index_column,
+                    &index_key,
+                    &Self::EVENT_IDS_BY_INDEX_MAX_RESULTS,
+                ],
+            )
+            .await
+            .iter()
+            .map(|row| row.get(0))
+            .collect()
+    }
+
+    async fn event_unique_times_by_id(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<UniqueTime> {
+        self.postgres_provider
+            .query(
+                Self::SQL_SELECT_UNIQUE_TIMES_BY_ID,
+                &[&topic_id, &event_id, &Self::limit(max_results)],
+            )
+            .await
+            .iter()
+            .map(|row| UniqueTime::from(row.get::<_, i64>(0)))
+            .collect()
+    }
+
+    async fn event_document_by_correlation_token(
+        &self,
+        topic_id: &str,
+        correlation_token: &str,
+    ) -> Option<EventDeliveryGist> {
+        self.postgres_provider
+            .query_first(
+                Self::SQL_SELECT_BY_CORRELATION_TOKEN,
+                &[&topic_id, &correlation_token],
+            )
+            .await
+            .as_ref()
+            .map(Self::event_delivery_gist_from_row)
+    }
+
+    async fn event_persist(
+        &self,
+        topic_id: &str,
+        topic_event: TopicEvent,
+    ) -> Result<(String, u32), MessageBrokerError> {
+        let mut statements = Vec::with_capacity(topic_event.get_additional_columns().len() + 2);
+        Self::push_event_statements(&mut statements, topic_id, &topic_event);
+        // The event, its index entries and the event identifier lookup are
+        // written in a single transaction, so there are no partial writes
+        // to repair.
+        let retries = self
+            .postgres_provider
+            .transaction_with_retries(&statements, Self::EVENT_PERSIST_MAX_RETRIES)
+            .await
+            .map_err(|msg| {
+                MessageBrokerErrorKind::PersistenceFailure.error_with_msg(format!(
+                    "Failed to persist event '{}' in topic '{topic_id}': {msg}",
+                    topic_event.get_event_id()
+                ))
+            })?;
+        Ok((topic_event.get_correlation_token().to_owned(), retries))
+    }
+
+    async fn events_persist(
+        &self,
+        topic_id: &str,
+        topic_events: Vec<TopicEvent>,
+    ) -> Result<(Vec<String>, u32), MessageBrokerError> {
+        let mut statements = Vec::with_capacity(topic_events.len() * 2);
+        for topic_event in &topic_events {
+            Self::push_event_statements(&mut statements, topic_id, topic_event);
+        }
+        let retries = self
+            .postgres_provider
+            .transaction_with_retries(&statements, Self::EVENT_PERSIST_MAX_RETRIES)
+            .await
+            .map_err(|msg| {
+                MessageBrokerErrorKind::PersistenceFailure.error_with_msg(format!(
+                    "Failed to persist batch of {} events in topic '{topic_id}': {msg}",
+                    topic_events.len()
+                ))
+            })?;
+        let correlation_tokens = topic_events
+            .iter()
+            .map(|topic_event| topic_event.get_correlation_token().to_owned())
+            .collect();
+        Ok((correlation_tokens, retries))
+    }
+
+    async fn event_index_columns_update(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        additional_columns: HashMap<String, ExtractedValue>,
+    ) -> bool {
+        if self
+            .event_by_id_and_unique_time(topic_id, event_id, unique_time)
+            .await
+            .is_none()
+        {
+            return false;
+        }
+        let mut statements = Vec::with_capacity(additional_columns.len());
+        Self::push_index_statements(
+            &mut statements,
+            topic_id,
+            event_id,
+            unique_time,
+            &additional_columns,
+        );
+        self.postgres_provider
+            .transaction_with_retries(&statements, Self::EVENT_PERSIST_MAX_RETRIES)
+            .await
+            .is_ok()
+    }
+
+    async fn event_topics_by_event_id(
+        &self,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<(String, UniqueTime)> {
+        self.postgres_provider
+            .query(
+                Self::SQL_SELECT_EVENT_TOPICS,
+                &[&event_id, &Self::limit(max_results)],
+            )
+            .await
+            .iter()
+            .map(|row| (row.get(0), UniqueTime::from(row.get::<_, i64>(1))))
+            .collect()
+    }
+
+    async fn event_purge(&self, topic_id: &str, event_id: &str, unique_time: UniqueTime) -> bool {
+        if self
+            .event_by_id_and_unique_time(topic_id, event_id, unique_time)
+            .await
+            .is_none()
+        {
+            return false;
+        }
+        let mut statements: Vec<StatementWithValues> = Vec::with_capacity(4);
+        for statement in [Self::SQL_DELETE_INDEX, Self::SQL_DELETE] {
+            statements.push((
+                statement,
+                vec![
+                    Box::new(topic_id.to_owned()),
+                    Box::new(unique_time.as_encoded_i64()),
+                    Box::new(event_id.to_owned()),
+                ],
+            ));
+        }
+        // The lookups are only removed in the same transaction if this was
+        // the last event with the identifier.
+        for statement in [
+            Self::SQL_DELETE_ANNOTATIONS_IF_UNUSED,
+            Self::SQL_DELETE_EVENT_TOPIC_IF_UNUSED,
+        ] {
+            statements.push((
+                statement,
+                vec![Box::new(topic_id.to_owned()), Box::new(event_id.to_owned())],
+            ));
+        }
+        self.postgres_provider
+            .transaction_with_retries(&statements, Self::EVENT_PERSIST_MAX_RETRIES)
+            .await
+            .map_err(|msg| {
+                log::warn!("Failed to purge event '{event_id}' in topic '{topic_id}': {msg}");
+            })
+            .is_ok()
+    }
+
+    async fn event_redact(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        document: &str,
+        protection_ref: &str,
+    ) -> bool {
+        if self
+            .event_by_id_and_unique_time(topic_id, event_id, unique_time)
+            .await
+            .is_none()
+        {
+            return false;
+        }
+        let statements: Vec<StatementWithValues> = vec![
+            (
+                Self::SQL_UPDATE_DOCUMENT,
+                vec![
+                    Box::new(topic_id.to_owned()),
+                    Box::new(unique_time.as_encoded_i64()),
+                    Box::new(event_id.to_owned()),
+                    Box::new(document.to_owned()),
+                    Box::new(protection_ref.to_owned()),
+                ],
+            ),
+            (
+                Self::SQL_DELETE_INDEX,
+                vec![
+                    Box::new(topic_id.to_owned()),
+                    Box::new(unique_time.as_encoded_i64()),
+                    Box::new(event_id.to_owned()),
+                ],
+            ),
+            (
+                Self::SQL_DELETE_ANNOTATIONS,
+                vec![Box::new(topic_id.to_owned()), Box::new(event_id.to_owned())],
+            ),
+        ];
+        self.postgres_provider
+            .transaction_with_retries(&statements, Self::EVENT_PERSIST_MAX_RETRIES)
+            .await
+            .map_err(|msg| {
+                log::warn!("Failed to redact event '{event_id}' in topic '{topic_id}': {msg}");
+            })
+            .is_ok()
+    }
+
+    async fn rejected_event_persist(&self, topic_id: &str, rejected_event: RejectedEvent) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_REJECTED,
+                &[
+                    &topic_id,
+                    &rejected_event.get_event_id(),
+                    &i64::from_unsigned(rejected_event.get_rejected_ts_micros()),
+                    &rejected_event.get_document(),
+                    &rejected_event.get_priority().map(i16::from_unsigned),
+                    &rejected_event
+                        .get_descriptor_version()
+                        .map(i64::from_unsigned),
+                    rejected_event.get_correlation_token(),
+                    &rejected_event.get_publisher(),
+                    &rejected_event.get_error_message(),
+                ],
+            )
+            .await
+            .is_some()
+    }
+
+    async fn rejected_events(
+        &self,
+        topic_id: &str,
+        from: &Option<String>,
+        max_results: usize,
+    ) -> (Vec<RejectedEvent>, bool) {
+        let from = from.as_deref().unwrap_or_default();
+        let ret = self
+            .postgres_provider
+            .query(
+                Self::SQL_SELECT_REJECTED,
+                &[&topic_id, &from, &Self::limit(max_results)],
+            )
+            .await
+            .iter()
+            .map(Self::rejected_event_from_row)
+            .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+
+    async fn rejected_event_by_id(&self, topic_id: &str, event_id: &str) -> Option<RejectedEvent> {
+        self.postgres_provider
+            .query_first(Self::SQL_SELECT_REJECTED_BY_ID, &[&topic_id, &event_id])
+            .await
+            .as_ref()
+            .map(Self::rejected_event_from_row)
+    }
+
+    async fn rejected_event_delete(&self, topic_id: &str, event_id: &str) -> bool {
+        self.postgres_provider
+            .execute(Self::SQL_DELETE_REJECTED, &[&topic_id, &event_id])
+            .await
+            .is_some_and(|deleted| deleted > 0)
+    }
+
+    async fn event_annotation_persist(
+        &self,
+        topic_id: &str,
+        event_annotation: EventAnnotation,
+    ) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_ANNOTATION,
+                &[
+                    &topic_id,
+                    &event_annotation.get_event_id(),
+                    &i64::from_unsigned(event_annotation.get_annotated_ts_micros()),
+                    &event_annotation.get_author(),
+                    &event_annotation.get_kind(),
+                    &event_annotation.get_value(),
+                ],
+            )
+            .await
+            .is_some()
+    }
+
+    async fn event_annotations_by_event_id(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<EventAnnotation> {
+        self.postgres_provider
+            .query(
+                Self::SQL_SELECT_ANNOTATIONS,
+                &[&topic_id, &event_id, &Self::limit(max_results)],
+            )
+            .await
+            .iter()
+            .map(|row| {
+                EventAnnotation::new(
+                    row.get(0),
+                    u64::from_signed(row.get::<_, i64>(1)),
+                    row.get(2),
+                    row.get(3),
+                    row.get(4),
+                )
+            })
+            .collect()
+    }
+
+    async fn buckets_by_shelf(
+        &self,
+        topic_id: &str,
+        shelf: u16,
+        from_bucket: Option<u64>,
+        max_results: usize,
+    ) -> (Vec<u64>, bool) {
+        let first_bucket = UniqueTime::first_bucket_in_shelf(shelf);
+        let next_bucket = from_bucket.map_or(first_bucket, |from_bucket| from_bucket + 1);
+        // Each shelf holds 2^25 buckets
+        let last_bucket = first_bucket + (1 << 25) - 1;
+        let ret = self
+            .postgres_provider
+            .query(
+                Self::SQL_SELECT_BUCKETS,
+                &[
+                    &topic_id,
+                    &i64::from_unsigned(UniqueTime::min_encoded_in_bucket(next_bucket)),
+                    &i64::from_unsigned(UniqueTime::max_encoded_in_bucket(last_bucket)),
+                    &Self::limit(max_results),
+                ],
+            )
+            .await
+            .iter()
+            .map(|row| u64::from_signed(row.get::<_, i64>(0)))
+            .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+
+    async fn event_count_by_bucket(&self, topic_id: &str, bucket: u64) -> u64 {
+        self.postgres_provider
+            .query_first(
+                Self::SQL_COUNT_IN_RANGE,
+                &[
+                    &topic_id,
+                    &i64::from_unsigned(UniqueTime::min_encoded_in_bucket(bucket)),
+                    &i64::from_unsigned(UniqueTime::max_encoded_in_bucket(bucket)),
+                ],
+            )
+            .await
+            .map(|row| u64::from_signed(row.get::<_, i64>(0)))
+            .unwrap_or_default()
+    }
+
+    async fn events_by_bucket(
+        &self,
+        topic_id: &str,
+        bucket: u64,
+        from: Option<UniqueTime>,
+        max_results: usize,
+    ) -> (Vec<(UniqueTime, String, Option<u64>)>, bool) {
+        let lower_bound = from.map_or_else(
+            || i64::from_unsigned(UniqueTime::min_encoded_in_bucket(bucket)),
+            |from| from.as_encoded_i64() + 1,
+        );
+        let ret = self
+            .postgres_provider
+            .query(
+                Self::SQL_SELECT_IN_RANGE,
+                &[
+                    &topic_id,
+                    &lower_bound,
+                    &i64::from_unsigned(UniqueTime::max_encoded_in_bucket(bucket)),
+                    &Self::limit(max_results),
+                ],
+            )
+            .await
+            .iter()
+            .map(|row| {
+                (
+                    UniqueTime::from(row.get::<_, i64>(0)),
+                    row.get(1),
+                    row.get::<_, Option<i64>>(2).map(u64::from_signed),
+                )
+            })
+            .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+
+    async fn events_by_unique_time_range(
+        &self,
+        topic_id: &str,
+        from: UniqueTime,
+        to: UniqueTime,
+        max_results: usize,
+    ) -> (Vec<(UniqueTime, String, Option<u64>)>, bool) {
+        if from.as_encoded() >= to.as_encoded() || max_results == 0 {
+            return (vec![], false);
+        }
+        let ret = self
+            .postgres_provider
+            .query(
+                Self::SQL_SELECT_IN_RANGE,
+                &[
+                    &topic_id,
+                    &(from.as_encoded_i64() + 1),
+                    &to.as_encoded_i64(),
+                    &Self::limit(max_results),
+                ],
+            )
+            .await
+            .iter()
+            .map(|row| {
+                (
+                    UniqueTime::from(row.get::<_, i64>(0)),
+                    row.get(1),
+                    row.get::<_, Option<i64>>(2).map(u64::from_signed),
+                )
+            })
+            .collect::<Vec<_>>();
+        let potentially_more_results = ret.len() == max_results;
+        (ret, potentially_more_results)
+    }
+}
@@ -0,0 +1,207 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PostgreSQL implementation of [EventTrackingFacade].
+
+use crate::PostgresProvider;
+use crate::postgres_provider::postgres_types::FromSignedOrDefault;
+use crate::postgres_provider::postgres_types::FromUnsignedOrDefault;
+use fragtale_dbp::dbp::facades::EventTrackingFacade;
+use fragtale_dbp::mb::ObjectCount;
+use fragtale_dbp::mb::ObjectCountType;
+use fragtale_dbp::mb::UniqueTime;
+use fragtale_dbp::mb::correlation::CorrelationResultListener;
+use std::sync::Arc;
+
+/// PostgreSQL implementation of [EventTrackingFacade].
+pub struct PostgresEventTrackingFacade {
+    postgres_provider: Arc<PostgresProvider>,
+}
+
+impl PostgresEventTrackingFacade {
+    /// Object counts that have not been updated within this time are
+    /// considered stale (e.g. from an instance that is gone).
+    const OBJECT_COUNT_MAX_AGE_MICROS: u64 = 600_000_000;
+
+    /// QOC1. Upsert local object count.
+    const SQL_UPSERT_OBJECT_COUNT: &'static str = "
+        INSERT INTO object_count (topic_id, object_type, instance_id, object_count, updated_ts)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (topic_id, object_type, instance_id) DO UPDATE
+        SET object_count = EXCLUDED.object_count, updated_ts = EXCLUDED.updated_ts
+        ";
+
+    /// QOC2. Get recent object counts.
+    const SQL_SELECT_OBJECT_COUNTS: &'static str = "
+        SELECT instance_id, object_count
+        FROM object_count
+        WHERE topic_id = $1 AND object_type = $2 AND updated_ts >= $3
+        ";
+
+    /// QOC3. Get correlation tokens of recent events.
+    const SQL_SELECT_CORRELATION_TOKENS: &'static str = "
+        SELECT correlation_token
+        FROM event
+        WHERE topic_id = $1 AND unique_time > $2
+        ";
+
+    /// QOC4. Upsert snapshot of local object count.
+    const SQL_UPSERT_OBJECT_COUNT_SNAPSHOT: &'static str = "
+        INSERT INTO object_count_snapshot (topic_id, instance_id, object_type, object_count, snapshot_ts)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (topic_id, instance_id, object_type) DO UPDATE
+        SET object_count = EXCLUDED.object_count, snapshot_ts = EXCLUDED.snapshot_ts
+        ";
+
+    /// QOC5. Get snapshots of the local object counts of an instance.
+    const SQL_SELECT_OBJECT_COUNT_SNAPSHOTS: &'static str = "
+        SELECT object_type, object_count
+        FROM object_count_snapshot
+        WHERE topic_id = $1 AND instance_id = $2
+        ";
+
+    /// QOC6. Delete snapshots of the local object counts of all instances.
+    const SQL_DELETE_OBJECT_COUNT_SNAPSHOTS: &'static str = "
+        DELETE FROM object_count_snapshot
+        WHERE topic_id = $1
+        ";
+
+    /// Return a new instance.
+    pub fn new(postgres_provider: &Arc<PostgresProvider>) -> Self {
+        Self {
+            postgres_provider: Arc::clone(postgres_provider),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventTrackingFacade for PostgresEventTrackingFacade {
+    async fn object_count_insert(
+        &self,
+        topic_id: &str,
+        object_count_type: &ObjectCountType,
+        instance_id: u16,
+        value: u64,
+    ) {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_OBJECT_COUNT,
+                &[
+                    &topic_id,
+                    &object_count_type.name(),
+                    &i16::from_unsigned(instance_id),
+                    &i64::try_from(value).unwrap_or(i64::MAX),
+                    &i64::from_unsigned(now_micros),
+                ],
+            )
+            .await;
+    }
+
+    async fn object_count_by_topic_and_type(
+        &self,
+        topic_id: &str,
+        object_count_type: &ObjectCountType,
+    ) -> Vec<ObjectCount> {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        let oldest_ts = i64::from_unsigned(now_micros - Self::OBJECT_COUNT_MAX_AGE_MICROS);
+        self.postgres_provider
+            .query(
+                Self::SQL_SELECT_OBJECT_COUNTS,
+                &[&topic_id, &object_count_type.name(), &oldest_ts],
+            )
+            .await
+            .iter()
+            .map(|row| ObjectCount::new(row.get(0), row.get(1)))
+            .collect()
+    }
+
+    async fn object_count_snapshot_upsert(
+        &self,
+        topic_id: &str,
+        object_count_type: &ObjectCountType,
+        instance_id: u16,
+        value: u64,
+    ) {
+        let now_micros = fragtale_client::time::get_timestamp_micros();
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_OBJECT_COUNT_SNAPSHOT,
+                &[
+                    &topic_id,
+                    &i16::from_unsigned(instance_id),
+                    &object_count_type.name(),
+                    &i64::try_from(value).unwrap_or(i64::MAX),
+                    &i64::from_unsigned(now_micros),
+                ],
+            )
+            .await;
+    }
+
+    async fn object_count_snapshots_by_instance(
+        &self,
+        topic_id: &str,
+        instance_id: u16,
+    ) -> Vec<(ObjectCountType, u64)> {
+        self.postgres_provider
+            .query(
+                Self::SQL_SELECT_OBJECT_COUNT_SNAPSHOTS,
+                &[&topic_id, &i16::from_unsigned(instance_id)],
+            )
+            .await
+            .iter()
+            .map(|row| {
+                (
+                    ObjectCountType::by_name(row.get(0)),
+                    u64::from_signed(row.get::<_, i64>(1)),
+                )
+            })
+            .collect()
+    }
+
+    async fn object_count_snapshots_delete(&self, topic_id: &str) {
+        self.postgres_provider
+            .execute(Self::SQL_DELETE_OBJECT_COUNT_SNAPSHOTS, &[&topic_id])
+            .await;
+    }
+
+    async fn track_new_events_in_topic(
+        &self,
+        topic_id: &str,
+        correlation_hotlist: Box<Arc<dyn CorrelationResultListener>>,
+        hotlist_duration_micros: u64,
+    ) -> bool {
+        let now_ts_micros = fragtale_client::time::get_timestamp_micros();
+        let unique_time_low_exclusive = i64::from_unsigned(UniqueTime::min_encoded_for_micros(
+            now_ts_micros - hotlist_duration_micros,
+        ));
+        let mut any_change = false;
+        for row in self
+            .postgres_provider
+            .query(
+                Self::SQL_SELECT_CORRELATION_TOKENS,
+                &[&topic_id, &unique_time_low_exclusive],
+            )
+            .await
+        {
+            if correlation_hotlist.notify_hotlist_entry(topic_id, row.get(0)) {
+                any_change = true;
+            }
+        }
+        any_change
+    }
+}
@@ -0,0 +1,324 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PostgreSQL implementation of [InstanceIdFacade].
+
+use crate::PostgresProvider;
+use crate::postgres_provider::postgres_types::FromSignedOrDefault;
+use crate::postgres_provider::postgres_types::FromUnsignedOrDefault;
+use fragtale_dbp::dbp::facades::InstanceIdFacade;
+use fragtale_dbp::mb::UniqueTime;
+use std::sync::Arc;
+
+/// PostgreSQL implementation of [InstanceIdFacade].
+///
+/// An expiration time on each claim is used to ensure that old and crashed
+/// instances are automatically excluded.
+pub struct PostgresInstanceIdFacade {
+    postgres_provider: Arc<PostgresProvider>,
+}
+
+impl PostgresInstanceIdFacade {
+    /// Default type.
+    ///
+    /// Using this as identity type groups all of the instance claims.
+    const ID_CLAIM_TYPE_INSTANCE: &'static str = "_instance";
+
+    /// Prefix of the type of candidate registrations for a role.
+    const ID_CLAIM_TYPE_ROLE_PREFIX: &'static str = "_role:";
+
+    /// QIC1. Claim an identity unless it is claimed and not expired.
+    ///
+    /// The time of the first claim is kept when a non-expired claim is
+    /// renewed.
+    const SQL_CLAIM: &'static str = "
+        INSERT INTO identity_claim (identity_type, identity_claim, first_claim_ts, expires_ts)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (identity_type, identity_claim) DO UPDATE
+        SET first_claim_ts = EXCLUDED.first_claim_ts, expires_ts = EXCLUDED.expires_ts
+        WHERE identity_claim.expires_ts <= $3
+        ";
+
+    /// QIC2. Claim an identity regardless of any previous claim.
+    const SQL_CLAIM_UNCONDITIONAL: &'static str = "
+        INSERT INTO identity_claim (identity_type, identity_claim, first_claim_ts, expires_ts)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (identity_type, identity_claim) DO UPDATE
+        SET first_claim_ts = CASE
+                WHEN identity_claim.expires_ts <= $3 THEN EXCLUDED.first_claim_ts
+                ELSE identity_claim.first_claim_ts
+            END,
+            expires_ts = EXCLUDED.expires_ts
+        ";
+
+    /// QIC3. Re-claim a non-expired identity for another period.
+    const SQL_REFRESH: &'static str = "
+        UPDATE identity_claim
+        SET expires_ts = $4
+        WHERE identity_type = $1 AND identity_claim = $2 AND expires_ts > $3
+        ";
+
+    /// QIC4. Free an identity.
+    const SQL_DELETE: &'static str = "
+        DELETE FROM identity_claim
+        WHERE identity_type = $1 AND identity_claim = $2
+        ";
+
+    /// QIC5. Retrieve all non-expired claims of a type by age.
+    const SQL_SELECT_ALL: &'static str = "
+        SELECT identity_claim, first_claim_ts
+        FROM identity_claim
+        WHERE identity_type = $1 AND expires_ts > $2
+        ORDER BY first_claim_ts ASC, identity_claim ASC
+        LIMIT 1024
+        ";
+
+    /// Identifier of the row with the cluster wide feature flags.
+    const FEATURE_FLAGS_ID: &'static str = "cluster";
+
+    /// QFF1. Retrieve feature flags.
+    const SQL_SELECT_FEATURE_FLAGS: &'static str = "
+        SELECT feature_flags
+        FROM feature_flags
+        WHERE flags_id = $1
+        ";
+
+    /// QFF2. Insert feature flags unless they exist.
+    const SQL_INSERT_FEATURE_FLAGS: &'static str = "
+        INSERT INTO feature_flags (flags_id, feature_flags)
+        VALUES ($1, $2)
+        ON CONFLICT (flags_id) DO NOTHING
+        ";
+
+    /// QFF3. Replace feature flags if they are unchanged.
+    const SQL_UPDATE_FEATURE_FLAGS: &'static str = "
+        UPDATE feature_flags
+        SET feature_flags = $2
+        WHERE flags_id = $1 AND feature_flags = $3
+        ";
+
+    /// Return a new instance.
+    pub fn new(postgres_provider: &Arc<PostgresProvider>) -> Self {
+        Self {
+            postgres_provider: Arc::clone(postgres_provider),
+        }
+    }
+
+    /// Return the identity type used for candidate registrations of the
+    /// `role`.
+    fn role_identity_type(role: &str) -> String {
+        Self::ID_CLAIM_TYPE_ROLE_PREFIX.to_owned() + role
+    }
+
+    /// Return the current time and the expiration time of a claim as SQL
+    /// `bigint`s.
+    fn now_and_expires(time_to_live_seconds: u32) -> (i64, i64) {
+        let now = fragtale_client::time::get_timestamp_micros();
+        (
+            i64::from_unsigned(now),
+            i64::from_unsigned(now + u64::from(time_to_live_seconds) * 1_000_000),
+        )
+    }
+
+    /// Claim an identity of the type unless it is already claimed.
+    async fn insert_if_not_exists(
+        &self,
+        identity_type: &str,
+        identity_claim: u16,
+        time_to_live_seconds: u32,
+    ) -> bool {
+        let (now, expires_ts) = Self::now_and_expires(time_to_live_seconds);
+        self.postgres_provider
+            .execute(
+                Self::SQL_CLAIM,
+                &[
+                    &identity_type,
+                    &i16::from_unsigned(identity_claim),
+                    &now,
+                    &expires_ts,
+                ],
+            )
+            .await
+            .is_some_and(|modified| modified > 0)
+    }
+
+    /// Delete the claim of an identity of the type.
+    async fn delete(&self, identity_type: &str, identity_claim: u16) {
+        self.postgres_provider
+            .execute(
+                Self::SQL_DELETE,
+                &[&identity_type, &i16::from_unsigned(identity_claim)],
+            )
+            .await;
+    }
+
+    /// Return all non-expired claims of the type ordered by the time of the
+    /// first claim.
+    async fn select_all(&self, identity_type: &str) -> Vec<(u16, u64)> {
+        let (now, _) = Self::now_and_expires(0);
+        self.postgres_provider
+            .query(Self::SQL_SELECT_ALL, &[&identity_type, &now])
+            .await
+            .iter()
+            .map(|row| {
+                (
+                    u16::from_signed(row.get::<_, i16>(0)),
+                    u64::from_signed(row.get::<_, i64>(1)),
+                )
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl InstanceIdFacade for PostgresInstanceIdFacade {
+    async fn claim(&self, time_to_live_seconds: u32) -> u16 {
+        loop {
+            // Get all claimed instance id from DB
+            let claimed_identities = self
+                .select_all(Self::ID_CLAIM_TYPE_INSTANCE)
+                .await
+                .into_iter()
+                .map(|(identity_claim, _first_claim_ts)| identity_claim)
+                .collect::<Vec<_>>();
+            if claimed_identities.len() < usize::from(UniqueTime::MAX_INSTANCE_ID) {
+                for identity_claim in 0..UniqueTime::MAX_INSTANCE_ID {
+                    if !claimed_identities.contains(&identity_claim)
+                        && self
+                            .insert_if_not_exists(
+                                Self::ID_CLAIM_TYPE_INSTANCE,
+                                identity_claim,
+                                time_to_live_seconds,
+                            )
+                            .await
+                    {
+                        return identity_claim;
+                    }
+                }
+            }
+            // Back off a little before trying again
+            tokio::task::yield_now().await;
+        }
+    }
+
+    async fn free(&self, claimed_instance_id: u16) {
+        self.delete(Self::ID_CLAIM_TYPE_INSTANCE, claimed_instance_id)
+            .await;
+    }
+
+    async fn refresh(&self, time_to_live_seconds: u32, claimed_instance_id: u16) -> bool {
+        let (now, expires_ts) = Self::now_and_expires(time_to_live_seconds);
+        let refreshed = self
+            .postgres_provider
+            .execute(
+                Self::SQL_REFRESH,
+                &[
+                    &Self::ID_CLAIM_TYPE_INSTANCE,
+                    &i16::from_unsigned(claimed_instance_id),
+                    &now,
+                    &expires_ts,
+                ],
+            )
+            .await;
+        match refreshed {
+            Some(0) => {
+                log::warn!(
+                    "Failed to reclaim instance id, but it seems to be unused. Claiming it now."
+                );
+                self.insert_if_not_exists(
+                    Self::ID_CLAIM_TYPE_INSTANCE,
+                    claimed_instance_id,
+                    time_to_live_seconds,
+                )
+                .await
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+
+    async fn get_oldest_instance_id(&self) -> (u16, u64) {
+        self.select_all(Self::ID_CLAIM_TYPE_INSTANCE)
+            .await
+            .first()
+            .copied()
+            .unwrap()
+    }
+
+    async fn claim_role(
+        &self,
+        role: &str,
+        time_to_live_seconds: u32,
+        claimed_instance_id: u16,
+    ) -> bool {
+        let (now, expires_ts) = Self::now_and_expires(time_to_live_seconds);
+        self.postgres_provider
+            .execute(
+                Self::SQL_CLAIM_UNCONDITIONAL,
+                &[
+                    &Self::role_identity_type(role),
+                    &i16::from_unsigned(claimed_instance_id),
+                    &now,
+                    &expires_ts,
+                ],
+            )
+            .await
+            .is_some()
+    }
+
+    async fn feature_flags(&self) -> Option<String> {
+        self.postgres_provider
+            .query_first(Self::SQL_SELECT_FEATURE_FLAGS, &[&Self::FEATURE_FLAGS_ID])
+            .await
+            .map(|row| row.get(0))
+    }
+
+    async fn feature_flags_compare_and_set(
+        &self,
+        expected: Option<&str>,
+        feature_flags: &str,
+    ) -> bool {
+        if let Some(expected) = expected {
+            self.postgres_provider
+                .execute(
+                    Self::SQL_UPDATE_FEATURE_FLAGS,
+                    &[&Self::FEATURE_FLAGS_ID, &feature_flags, &expected],
+                )
+                .await
+        } else {
+            self.postgres_provider
+                .execute(
+                    Self::SQL_INSERT_FEATURE_FLAGS,
+                    &[&Self::FEATURE_FLAGS_ID, &feature_flags],
+                )
+                .await
+        }
+        .is_some_and(|modified| modified > 0)
+    }
+
+    async fn free_role(&self, role: &str, claimed_instance_id: u16) {
+        self.delete(&Self::role_identity_type(role), claimed_instance_id)
+            .await;
+    }
+
+    async fn get_oldest_instance_id_by_role(&self, role: &str) -> Option<u16> {
+        self.select_all(&Self::role_identity_type(role))
+            .await
+            .first()
+            .map(|(identity_claim, _first_claim_ts)| *identity_claim)
+    }
+}
@@ -0,0 +1,329 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PostgreSQL implementation of [IntegrityProtectionFacade].
+
+use crate::PostgresProvider;
+use crate::postgres_provider::postgres_types::FromSignedOrDefault;
+use crate::postgres_provider::postgres_types::FromUnsignedOrDefault;
+use fragtale_dbp::dbp::facades::IntegrityProtectionFacade;
+use std::sync::Arc;
+
+/// PostgreSQL implementation of [IntegrityProtectionFacade].
+///
+/// Protections are bucketed by level in the same way as for the other
+/// database providers, but buckets are derived from the protection time
+/// instead of being stored.
+pub struct PostgresIntegrityProtectionFacade {
+    postgres_provider: Arc<PostgresProvider>,
+}
+
+impl PostgresIntegrityProtectionFacade {
+    /// QI1. Upsert protection.
+    const SQL_UPSERT: &'static str = "
+        INSERT INTO integrity (topic_id, protection_ts, protection_id, level, protection_data)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (topic_id, protection_ts, protection_id) DO UPDATE
+        SET level = EXCLUDED.level, protection_data = EXCLUDED.protection_data
+        ";
+
+    /// QI2. Set protection reference.
+    const SQL_UPDATE_PROTECTION_REF: &'static str = "
+        UPDATE integrity
+        SET protection_ref = $4
+        WHERE topic_id = $1 AND protection_ts = $2 AND protection_id = $3
+        ";
+
+    /// QI3. Get protection by identifier and time.
+    const SQL_SELECT: &'static str = "
+        SELECT protection_data, protection_ref
+        FROM integrity
+        WHERE topic_id = $1 AND protection_ts = $2 AND protection_id = $3
+        ";
+
+    /// QI4. Get the latest protection time at a level.
+    const SQL_SELECT_LATEST_TS: &'static str = "
+        SELECT max(protection_ts)
+        FROM integrity
+        WHERE topic_id = $1 AND level = $2
+        ";
+
+    /// QI5. Get the first protection time at a level from a time.
+    const SQL_SELECT_FIRST_TS_FROM: &'static str = "
+        SELECT min(protection_ts)
+        FROM integrity
+        WHERE topic_id = $1 AND level = $2 AND protection_ts >= $3
+        ";
+
+    /// QI6. Get protections at a level in an interval.
+    const SQL_SELECT_IN_INTERVAL: &'static str = "
+        SELECT protection_id, protection_ts, protection_data, protection_ref
+        FROM integrity
+        WHERE topic_id = $1 AND level = $2 AND protection_ts >= $3 AND protection_ts < $4
+        ORDER BY protection_ts ASC, protection_id ASC
+        LIMIT $5
+        ";
+
+    /// Return a new instance.
+    pub fn new(postgres_provider: &Arc<PostgresProvider>) -> Self {
+        Self {
+            postgres_provider: Arc::clone(postgres_provider),
+        }
+    }
+
+    /// Length of the lookup bucket interval at each level in the protection
+    /// hierarchy.
+    fn lookup_interval_micros(level: u8) -> u64 {
+        match level {
+            // Level 0: Bucket into 4 minute intevals
+            0 => 1_000_000 * 240,
+            // Level 1: Bucket into 7 day intevals
+            1 => 1_000_000 * 3_600 * 24 * 7,
+            // Level 2: Bucket into 365 day intevals
+            2 => 1_000_000 * 3_600 * 24 * 365,
+            unsupported_level => {
+                panic!("Bucketing for level {unsupported_level} is not implemented.")
+            }
+        }
+    }
+
+    /// Bucket protections based on level in protection hierarchy.
+    fn to_lookup_ts_bucket(level: u8, protection_ts_micros: u64) -> u64 {
+        protection_ts_micros - protection_ts_micros % Self::lookup_interval_micros(level)
+    }
+
+    /// Return the first protection time at the `level` that is equal to or
+    /// later than `from_ts_micros`.
+    async fn first_protection_ts_from(
+        &self,
+        topic_id: &str,
+        level: u8,
+        from_ts_micros: u64,
+    ) -> Option<u64> {
+        self.postgres_provider
+            .query_first(
+                Self::SQL_SELECT_FIRST_TS_FROM,
+                &[
+                    &topic_id,
+                    &i16::from_unsigned(level),
+                    &i64::from_unsigned(from_ts_micros),
+                ],
+            )
+            .await
+            .and_then(|row| row.get::<_, Option<i64>>(0))
+            .map(u64::from_signed)
+    }
+
+    /// Return protections at the `level` from `from_ts_micros` to the end
+    /// of its lookup bucket.
+    async fn select_in_bucket_from(
+        &self,
+        topic_id: &str,
+        level: u8,
+        from_ts_micros: u64,
+        max_results: usize,
+    ) -> Vec<(String, u64, String, Option<String>)> {
+        let bucket_end =
+            Self::to_lookup_ts_bucket(level, from_ts_micros) + Self::lookup_interval_micros(level);
+        self.postgres_provider
+            .query(
+                Self::SQL_SELECT_IN_INTERVAL,
+                &[
+                    &topic_id,
+                    &i16::from_unsigned(level),
+                    &i64::from_unsigned(from_ts_micros),
+                    &i64::from_unsigned(bucket_end),
+                    &i64::try_from(max_results).unwrap_or(i64::MAX),
+                ],
+            )
+            .await
+            .iter()
+            .map(|row| {
+                (
+                    row.get(0),
+                    u64::from_signed(row.get::<_, i64>(1)),
+                    row.get(2),
+                    row.get(3),
+                )
+            })
+            .collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl IntegrityProtectionFacade for PostgresIntegrityProtectionFacade {
+    async fn integrity_protection_persist(
+        &self,
+        topic_id: &str,
+        id: &str,
+        protection_data: &str,
+        protection_ts_micros: u64,
+        level: u8,
+    ) {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT,
+                &[
+                    &topic_id,
+                    &i64::from_unsigned(protection_ts_micros),
+                    &id,
+                    &i16::from_unsigned(level),
+                    &protection_data,
+                ],
+            )
+            .await;
+    }
+
+    async fn integrity_protection_set_protection_ref(
+        &self,
+        topic_id: &str,
+        id: &str,
+        protection_ts_micros: u64,
+        protection_ref: &str,
+    ) {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPDATE_PROTECTION_REF,
+                &[
+                    &topic_id,
+                    &i64::from_unsigned(protection_ts_micros),
+                    &id,
+                    &protection_ref,
+                ],
+            )
+            .await;
+    }
+
+    async fn integrity_protection_by_id_and_ts(
+        &self,
+        topic_id: &str,
+        id: &str,
+        protection_ts_micros: u64,
+    ) -> Option<(String, Option<String>)> {
+        self.postgres_provider
+            .query_first(
+                Self::SQL_SELECT,
+                &[&topic_id, &i64::from_unsigned(protection_ts_micros), &id],
+            )
+            .await
+            .map(|row| (row.get(0), row.get(1)))
+    }
+
+    /// Return the next buckets starting point
+    async fn integrity_protection_next_starting_point_to_process(
+        &self,
+        topic_id: &str,
+        level_in: u8,
+        now_micros: u64,
+    ) -> Option<u64> {
+        let level_out = level_in + 1;
+        // Get the latest protection_ts from the output level
+        let latest_protection_ts_in_level = self
+            .postgres_provider
+            .query_first(
+                Self::SQL_SELECT_LATEST_TS,
+                &[&topic_id, &i16::from_unsigned(level_out)],
+            )
+            .await
+            .and_then(|row| row.get::<_, Option<i64>>(0))
+            .map(u64::from_signed);
+        let candidate_ts_bucket = if let Some(latest_protection_ts_in_level) =
+            latest_protection_ts_in_level
+        {
+            // Transform output levels protection_ts into this levels bucket
+            let lookup_ts_bucket_latest =
+                Self::to_lookup_ts_bucket(level_in, latest_protection_ts_in_level);
+            // Get the next bucket at the input level that has entires
+            let candidate_ts_bucket = self
+                .first_protection_ts_from(
+                    topic_id,
+                    level_in,
+                    lookup_ts_bucket_latest + Self::lookup_interval_micros(level_in),
+                )
+                .await
+                .map(|protection_ts| Self::to_lookup_ts_bucket(level_in, protection_ts));
+            if candidate_ts_bucket.is_none() && log::log_enabled!(log::Level::Trace) {
+                log::trace!(
+                    "No newer candidate bucket with entries at level {level_in} in '{topic_id}'. (Already covered lookup_ts_bucket_latest: {lookup_ts_bucket_latest}.)"
+                );
+            }
+            candidate_ts_bucket
+        } else {
+            // There is no protection yet at the output level.. find the
+            // earliest protection on the input level
+            if log::log_enabled!(log::Level::Trace) {
+                log::trace!("Topic '{topic_id}' has no bucket yet at level {level_out}.");
+            }
+            let candidate_ts_bucket = self
+                .first_protection_ts_from(topic_id, level_in, 0)
+                .await
+                .map(|protection_ts| Self::to_lookup_ts_bucket(level_in, protection_ts));
+            if candidate_ts_bucket.is_none() && log::log_enabled!(log::Level::Trace) {
+                log::trace!("Topic '{topic_id}' has no bucket yet at level {level_in}.");
+            }
+            candidate_ts_bucket
+        };
+        let candidate_ts_bucket = candidate_ts_bucket?;
+        // Transform now into this levels bucket
+        let lookup_ts_bucket_now = Self::to_lookup_ts_bucket(
+            level_in,
+            // Ensure that there is some time to persist the last protections
+            now_micros + 10_000_000,
+        );
+        if lookup_ts_bucket_now > candidate_ts_bucket {
+            // There is something to handle.
+            return Some(candidate_ts_bucket);
+        }
+        if log::log_enabled!(log::Level::Trace) {
+            log::trace!(
+                "Candidate bucket {candidate_ts_bucket} at level {level_in} in '{topic_id}' was not old enough yet to be consolidated. (now bucket: {lookup_ts_bucket_now})"
+            );
+        }
+        None
+    }
+
+    async fn integrity_batch_in_interval_by_level_and_time(
+        &self,
+        topic_id: &str,
+        level: u8,
+        from_protections_ts_micros: u64,
+        max_results: usize,
+    ) -> Vec<(String, u64, String, Option<String>)> {
+        let ret = self
+            .select_in_bucket_from(topic_id, level, from_protections_ts_micros, max_results)
+            .await;
+        if !ret.is_empty() {
+            return ret;
+        }
+        // If we have exhausted the bucket, go for the next populated one
+        let next_bucket_start = Self::to_lookup_ts_bucket(level, from_protections_ts_micros)
+            + Self::lookup_interval_micros(level);
+        let Some(first_protection_ts) = self
+            .first_protection_ts_from(topic_id, level, next_bucket_start)
+            .await
+        else {
+            return ret;
+        };
+        self.select_in_bucket_from(
+            topic_id,
+            level,
+            Self::to_lookup_ts_bucket(level, first_protection_ts),
+            max_results,
+        )
+        .await
+    }
+}
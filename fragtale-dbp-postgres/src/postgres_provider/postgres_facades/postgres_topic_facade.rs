@@ -0,0 +1,433 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! PostgreSQL implementation of [TopicFacade].
+
+use crate::PostgresProvider;
+use crate::postgres_provider::postgres_diagnostics::PostgresDiagnostics;
+use crate::postgres_provider::postgres_types::FromSignedOrDefault;
+use crate::postgres_provider::postgres_types::FromUnsignedOrDefault;
+use crossbeam_skiplist::SkipSet;
+use fragtale_dbp::dbp::facades::TopicFacade;
+use fragtale_dbp::mb::DiagnosticQuery;
+use fragtale_dbp::mb::IndexRebuildProgress;
+use fragtale_dbp::mb::MessageBrokerError;
+use fragtale_dbp::mb::MessageBrokerErrorKind;
+use fragtale_dbp::mb::UniqueTime;
+use std::sync::Arc;
+
+/// PostgreSQL implementation of [TopicFacade].
+pub struct PostgresTopicFacade {
+    postgres_provider: Arc<PostgresProvider>,
+    /// Topics that are known to exist in the database.
+    topic_exists_check: SkipSet<String>,
+}
+
+impl PostgresTopicFacade {
+    const ALLOWED_TOPIC_ID_CHARS: &str = "abcdefghijklmnopqrstuvwxyz0123456789_";
+
+    /// Maximum length of a topic identifier.
+    ///
+    /// PostgreSQL does not need a limit, but keeping topic identifiers short
+    /// allows moving them between database providers.
+    const MAX_TOPIC_ID_LEN: usize = 48;
+
+    /// QT1. Insert topic.
+    const SQL_INSERT_TOPIC: &'static str = "
+        INSERT INTO topic (topic_id)
+        VALUES ($1)
+        ON CONFLICT (topic_id) DO NOTHING
+        ";
+
+    /// QT2. Check if topic exists.
+    const SQL_SELECT_TOPIC: &'static str = "
+        SELECT topic_id
+        FROM topic
+        WHERE topic_id = $1
+        ";
+
+    /// QT3. Get topic identifiers after a topic identifier.
+    const SQL_SELECT_TOPICS: &'static str = "
+        SELECT topic_id
+        FROM topic
+        WHERE topic_id > $1
+        ORDER BY topic_id ASC
+        LIMIT $2
+        ";
+
+    /// QT4. Insert event descriptor if it does not exist.
+    const SQL_INSERT_EVENT_DESCRIPTOR: &'static str = "
+        INSERT INTO event_descriptor (topic_id, version, version_min, schema_id, event_descriptor)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (topic_id, version) DO NOTHING
+        ";
+
+    /// QT5. Get event descriptors of topic (newest first).
+    const SQL_SELECT_EVENT_DESCRIPTORS: &'static str = "
+        SELECT event_descriptor
+        FROM event_descriptor
+        WHERE topic_id = $1 AND version >= $2
+        ORDER BY version DESC
+        ";
+
+    /// QT6. Insert shared schema if it does not exist.
+    const SQL_INSERT_SHARED_SCHEMA: &'static str = "
+        INSERT INTO shared_schema (schema_id, version, shared_schema)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (schema_id, version) DO NOTHING
+        ";
+
+    /// QT7. Get latest version of shared schema.
+    const SQL_SELECT_SHARED_SCHEMA_LATEST: &'static str = "
+        SELECT version, shared_schema
+        FROM shared_schema
+        WHERE schema_id = $1
+        ORDER BY version DESC
+        LIMIT 1
+        ";
+
+    /// QT8. Get latest version of all shared schemas.
+    const SQL_SELECT_SHARED_SCHEMAS_LATEST: &'static str = "
+        SELECT DISTINCT ON (schema_id) shared_schema
+        FROM shared_schema
+        ORDER BY schema_id ASC, version DESC
+        LIMIT $1
+        ";
+
+    /// QT9. Upsert reply topic.
+    const SQL_UPSERT_REPLY_TOPIC: &'static str = "
+        INSERT INTO topic_reply (topic_id, reply_topic_id, registered_by)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (topic_id) DO UPDATE
+        SET reply_topic_id = EXCLUDED.reply_topic_id, registered_by = EXCLUDED.registered_by
+        ";
+
+    /// QT10. Get reply topic.
+    const SQL_SELECT_REPLY_TOPIC: &'static str = "
+        SELECT reply_topic_id, registered_by
+        FROM topic_reply
+        WHERE topic_id = $1
+        ";
+
+    /// QT11. Upsert bulk ingest window.
+    const SQL_UPSERT_BULK_INGEST: &'static str = "
+        INSERT INTO topic_bulk_ingest (topic_id, start_ts, end_ts, reconciled)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (topic_id) DO UPDATE
+        SET start_ts = EXCLUDED.start_ts, end_ts = EXCLUDED.end_ts, reconciled = EXCLUDED.reconciled
+        ";
+
+    /// QT12. Get bulk ingest window.
+    const SQL_SELECT_BULK_INGEST: &'static str = "
+        SELECT start_ts, end_ts, reconciled
+        FROM topic_bulk_ingest
+        WHERE topic_id = $1
+        ";
+
+    /// QT13. Upsert index rebuild progress.
+    const SQL_UPSERT_INDEX_REBUILD: &'static str = "
+        INSERT INTO topic_index_rebuild (topic_id, requested_ts, position, rebuilt_count, completed_ts)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (topic_id) DO UPDATE
+        SET requested_ts = EXCLUDED.requested_ts, position = EXCLUDED.position,
+            rebuilt_count = EXCLUDED.rebuilt_count, completed_ts = EXCLUDED.completed_ts
+        ";
+
+    /// QT14. Get index rebuild progress.
+    const SQL_SELECT_INDEX_REBUILD: &'static str = "
+        SELECT requested_ts, position, rebuilt_count, completed_ts
+        FROM topic_index_rebuild
+        WHERE topic_id = $1
+        ";
+
+    /// Return a new instance.
+    pub fn new(postgres_provider: &Arc<PostgresProvider>) -> Self {
+        Self {
+            postgres_provider: Arc::clone(postgres_provider),
+            topic_exists_check: SkipSet::default(),
+        }
+    }
+
+    fn assert_topic_id_well_formed(topic_id: &str) -> Result<(), MessageBrokerError> {
+        if topic_id
+            .chars()
+            .any(|c| !Self::ALLOWED_TOPIC_ID_CHARS.contains(c))
+        {
+            Err(
+                MessageBrokerErrorKind::MalformedIdentifier.error_with_msg(format!(
+                    "Invalid chars in topic id '{topic_id}'. Only a-z0-9_ are allowed."
+                )),
+            )?;
+        }
+        if topic_id.is_empty() || topic_id.len() > Self::MAX_TOPIC_ID_LEN {
+            Err(
+                MessageBrokerErrorKind::MalformedIdentifier.error_with_msg(format!(
+                    "Invalid length of topic id '{topic_id}'. Must be of length 1-{}.",
+                    Self::MAX_TOPIC_ID_LEN
+                )),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl TopicFacade for PostgresTopicFacade {
+    async fn ensure_topic_setup(&self, topic_id: &str) -> Result<(), MessageBrokerError> {
+        Self::assert_topic_id_well_formed(topic_id)?;
+        if self.topic_exists_check.contains(topic_id) {
+            return Ok(());
+        }
+        // Topic level tables are shared, so only the topic needs to be registered
+        self.postgres_provider
+            .execute(Self::SQL_INSERT_TOPIC, &[&topic_id])
+            .await
+            .ok_or_else(|| {
+                MessageBrokerErrorKind::PersistenceFailure
+                    .error_with_msg(format!("Failed to register topic '{topic_id}'."))
+            })?;
+        self.topic_exists_check.insert(topic_id.to_owned());
+        Ok(())
+    }
+
+    async fn topic_exists(&self, topic_id: &str) -> bool {
+        self.topic_exists_check.contains(topic_id)
+            || self
+                .postgres_provider
+                .query_first(Self::SQL_SELECT_TOPIC, &[&topic_id])
+                .await
+                .is_some()
+    }
+
+    async fn get_topic_ids(&self, from: &Option<String>) -> (Vec<String>, bool) {
+        let limit = 256 * 1024 / Self::MAX_TOPIC_ID_LEN;
+        let from = from.as_deref().unwrap_or_default();
+        let res = self
+            .postgres_provider
+            .query(
+                Self::SQL_SELECT_TOPICS,
+                &[&from, &i64::try_from(limit).unwrap_or(i64::MAX)],
+            )
+            .await
+            .iter()
+            .map(|row| row.get(0))
+            .collect::<Vec<String>>();
+        let potentially_more_results = res.len() == limit;
+        (res, potentially_more_results)
+    }
+
+    async fn event_descriptor_persists(
+        &self,
+        topic_id: &str,
+        version: u64,
+        version_min: Option<u64>,
+        schema_id: &Option<String>,
+        event_descriptor: &str,
+    ) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_INSERT_EVENT_DESCRIPTOR,
+                &[
+                    &topic_id,
+                    &i64::from_unsigned(version),
+                    &version_min.map(i64::from_unsigned),
+                    schema_id,
+                    &event_descriptor,
+                ],
+            )
+            .await
+            .is_some_and(|inserted| inserted == 1)
+    }
+
+    async fn event_descriptors_by_topic_id(
+        &self,
+        topic_id: &str,
+        min_descriptor_version: Option<u64>,
+    ) -> Vec<String> {
+        let min_version = min_descriptor_version.map_or(0, i64::from_unsigned);
+        self.postgres_provider
+            .query(
+                Self::SQL_SELECT_EVENT_DESCRIPTORS,
+                &[&topic_id, &min_version],
+            )
+            .await
+            .iter()
+            .map(|row| row.get(0))
+            .collect()
+    }
+
+    async fn shared_schema_persist(
+        &self,
+        schema_id: &str,
+        version: u64,
+        shared_schema: &str,
+    ) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_INSERT_SHARED_SCHEMA,
+                &[&schema_id, &i64::from_unsigned(version), &shared_schema],
+            )
+            .await
+            .is_some_and(|inserted| inserted == 1)
+    }
+
+    async fn shared_schema_latest_by_id(&self, schema_id: &str) -> Option<(u64, String)> {
+        self.postgres_provider
+            .query_first(Self::SQL_SELECT_SHARED_SCHEMA_LATEST, &[&schema_id])
+            .await
+            .map(|row| (u64::from_signed(row.get::<_, i64>(0)), row.get(1)))
+    }
+
+    async fn shared_schemas_latest(&self, max_results: usize) -> Vec<String> {
+        self.postgres_provider
+            .query(
+                Self::SQL_SELECT_SHARED_SCHEMAS_LATEST,
+                &[&i64::try_from(max_results).unwrap_or(i64::MAX)],
+            )
+            .await
+            .iter()
+            .map(|row| row.get(0))
+            .collect()
+    }
+
+    async fn extraction_setup_searchable(
+        &self,
+        _topic_id: &str,
+        _name_and_type_slice: &[(String, String)],
+    ) {
+        // Extracted values are stored in the shared event_index table that
+        // is always searchable, so there is nothing to set up.
+    }
+
+    async fn reply_topic_register(
+        &self,
+        topic_id: &str,
+        reply_topic_id: &str,
+        registered_by: &str,
+    ) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_REPLY_TOPIC,
+                &[&topic_id, &reply_topic_id, &registered_by],
+            )
+            .await
+            .is_some()
+    }
+
+    async fn reply_topic_by_topic_id(&self, topic_id: &str) -> Option<String> {
+        self.postgres_provider
+            .query_first(Self::SQL_SELECT_REPLY_TOPIC, &[&topic_id])
+            .await
+            .map(|row| row.get(0))
+    }
+
+    async fn reply_topic_registered_by(&self, topic_id: &str) -> Option<String> {
+        self.postgres_provider
+            .query_first(Self::SQL_SELECT_REPLY_TOPIC, &[&topic_id])
+            .await
+            .map(|row| row.get(1))
+    }
+
+    async fn bulk_ingest_window_persist(
+        &self,
+        topic_id: &str,
+        start_ts_micros: u64,
+        end_ts_micros: u64,
+        reconciled: bool,
+    ) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_BULK_INGEST,
+                &[
+                    &topic_id,
+                    &i64::from_unsigned(start_ts_micros),
+                    &i64::from_unsigned(end_ts_micros),
+                    &reconciled,
+                ],
+            )
+            .await
+            .is_some()
+    }
+
+    async fn bulk_ingest_window_by_topic_id(&self, topic_id: &str) -> Option<(u64, u64, bool)> {
+        self.postgres_provider
+            .query_first(Self::SQL_SELECT_BULK_INGEST, &[&topic_id])
+            .await
+            .map(|row| {
+                (
+                    u64::from_signed(row.get::<_, i64>(0)),
+                    u64::from_signed(row.get::<_, i64>(1)),
+                    row.get(2),
+                )
+            })
+    }
+
+    async fn index_rebuild_persist(
+        &self,
+        topic_id: &str,
+        index_rebuild_progress: IndexRebuildProgress,
+    ) -> bool {
+        self.postgres_provider
+            .execute(
+                Self::SQL_UPSERT_INDEX_REBUILD,
+                &[
+                    &topic_id,
+                    &i64::from_unsigned(index_rebuild_progress.get_requested_ts_micros()),
+                    &i64::from_unsigned(index_rebuild_progress.get_position()),
+                    &i64::from_unsigned(index_rebuild_progress.get_rebuilt_count()),
+                    &index_rebuild_progress
+                        .get_completed_ts_micros()
+                        .map(i64::from_unsigned),
+                ],
+            )
+            .await
+            .is_some()
+    }
+
+    async fn index_rebuild_by_topic_id(&self, topic_id: &str) -> Option<IndexRebuildProgress> {
+        self.postgres_provider
+            .query_first(Self::SQL_SELECT_INDEX_REBUILD, &[&topic_id])
+            .await
+            .map(|row| {
+                IndexRebuildProgress::new(
+                    u64::from_signed(row.get::<_, i64>(0)),
+                    u64::from_signed(row.get::<_, i64>(1)),
+                    u64::from_signed(row.get::<_, i64>(2)),
+                    row.get::<_, Option<i64>>(3).map(u64::from_signed),
+                )
+            })
+    }
+
+    async fn diagnostic_query(
+        &self,
+        topic_id: &str,
+        diagnostic_query: DiagnosticQuery,
+        consumer_id: Option<&str>,
+        range: Option<(UniqueTime, UniqueTime)>,
+        max_results: usize,
+    ) -> Result<(Vec<String>, bool), MessageBrokerError> {
+        PostgresDiagnostics::query(
+            &self.postgres_provider,
+            topic_id,
+            diagnostic_query,
+            consumer_id,
+            range,
+            max_results,
+        )
+        .await
+    }
+}
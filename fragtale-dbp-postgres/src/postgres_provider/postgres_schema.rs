@@ -0,0 +1,268 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Creation of the database schema and tables.
+
+use crate::PostgresProvider;
+
+/// Creation of the database schema and tables.
+///
+/// Topic level tables are shared by all topics and use the topic identifier
+/// as the first part of the primary key.
+pub struct PostgresSchema;
+
+impl PostgresSchema {
+    /// Key of the advisory lock that serializes schema changes between
+    /// instances that start at the same time.
+    const SCHEMA_LOCK_KEY: i64 = 0x6672_6167_7461_6c65;
+
+    /// Serialize concurrent schema changes.
+    const SQL_LOCK: &'static str = "SELECT pg_advisory_xact_lock($1)";
+
+    /// Application level tables.
+    const SQL_CREATE_APP_TABLES: &'static str = "
+        CREATE TABLE IF NOT EXISTS identity_claim (
+            identity_type       text        NOT NULL,
+            identity_claim      smallint    NOT NULL,
+            first_claim_ts      bigint      NOT NULL,
+            expires_ts          bigint      NOT NULL,
+            PRIMARY KEY (identity_type, identity_claim)
+        );
+        CREATE TABLE IF NOT EXISTS feature_flags (
+            flags_id            text        NOT NULL,
+            feature_flags       text        NOT NULL,
+            PRIMARY KEY (flags_id)
+        );
+        CREATE TABLE IF NOT EXISTS resource_grant (
+            resource            text        NOT NULL,
+            identity            text        NOT NULL,
+            expires_ts          bigint,
+            PRIMARY KEY (resource, identity)
+        );
+        CREATE TABLE IF NOT EXISTS publish_grant_use (
+            grant_uid           text        NOT NULL,
+            uses                integer     NOT NULL,
+            expires_ts          bigint      NOT NULL,
+            PRIMARY KEY (grant_uid)
+        );
+        CREATE TABLE IF NOT EXISTS topic (
+            topic_id            text        NOT NULL,
+            PRIMARY KEY (topic_id)
+        );
+        CREATE TABLE IF NOT EXISTS event_descriptor (
+            topic_id            text        NOT NULL,
+            version             bigint      NOT NULL,
+            version_min         bigint,
+            schema_id           text,
+            event_descriptor    text        NOT NULL,
+            PRIMARY KEY (topic_id, version)
+        );
+        CREATE TABLE IF NOT EXISTS shared_schema (
+            schema_id           text        NOT NULL,
+            version             bigint      NOT NULL,
+            shared_schema       text        NOT NULL,
+            PRIMARY KEY (schema_id, version)
+        );
+        CREATE TABLE IF NOT EXISTS topic_reply (
+            topic_id            text        NOT NULL,
+            reply_topic_id      text        NOT NULL,
+            registered_by       text        NOT NULL,
+            PRIMARY KEY (topic_id)
+        );
+        CREATE TABLE IF NOT EXISTS topic_bulk_ingest (
+            topic_id            text        NOT NULL,
+            start_ts            bigint      NOT NULL,
+            end_ts              bigint      NOT NULL,
+            reconciled          boolean     NOT NULL,
+            PRIMARY KEY (topic_id)
+        );
+        CREATE TABLE IF NOT EXISTS topic_index_rebuild (
+            topic_id            text        NOT NULL,
+            requested_ts        bigint      NOT NULL,
+            position            bigint      NOT NULL,
+            rebuilt_count       bigint      NOT NULL,
+            completed_ts        bigint,
+            PRIMARY KEY (topic_id)
+        );
+        CREATE TABLE IF NOT EXISTS event_topic (
+            event_id            text        NOT NULL,
+            topic_id            text        NOT NULL,
+            unique_time         bigint      NOT NULL,
+            PRIMARY KEY (event_id, topic_id)
+        );
+        ";
+
+    /// Topic level tables.
+    const SQL_CREATE_TOPIC_TABLES: &'static str = "
+        CREATE TABLE IF NOT EXISTS event (
+            topic_id            text        NOT NULL,
+            unique_time         bigint      NOT NULL,
+            event_id            text        NOT NULL,
+            document            text        NOT NULL,
+            protection_ref      text        NOT NULL,
+            correlation_token   text        NOT NULL,
+            descriptor_version  bigint,
+            priority            smallint    NOT NULL,
+            expires_ts          bigint,
+            deliver_after_ts    bigint,
+            PRIMARY KEY (topic_id, unique_time)
+        );
+        ALTER TABLE event ADD COLUMN IF NOT EXISTS deliver_after_ts bigint;
+        CREATE INDEX IF NOT EXISTS event_by_event_id
+            ON event (topic_id, event_id, unique_time);
+        CREATE INDEX IF NOT EXISTS event_by_correlation_token
+            ON event (topic_id, correlation_token);
+        CREATE TABLE IF NOT EXISTS event_index (
+            topic_id            text        NOT NULL,
+            index_column        text        NOT NULL,
+            index_key           text        NOT NULL,
+            unique_time         bigint      NOT NULL,
+            event_id            text        NOT NULL,
+            PRIMARY KEY (topic_id, index_column, index_key, unique_time)
+        );
+        CREATE TABLE IF NOT EXISTS rejected_event (
+            topic_id            text        NOT NULL,
+            event_id            text        NOT NULL,
+            rejected_ts         bigint      NOT NULL,
+            document            text        NOT NULL,
+            priority            smallint,
+            descriptor_version  bigint,
+            correlation_token   text,
+            publisher           text        NOT NULL,
+            error_message       text        NOT NULL,
+            PRIMARY KEY (topic_id, event_id)
+        );
+        CREATE TABLE IF NOT EXISTS event_annotation (
+            topic_id            text        NOT NULL,
+            event_id            text        NOT NULL,
+            annotated_ts        bigint      NOT NULL,
+            author              text        NOT NULL,
+            kind                text        NOT NULL,
+            value               text        NOT NULL,
+            PRIMARY KEY (topic_id, event_id, annotated_ts, author)
+        );
+        CREATE TABLE IF NOT EXISTS object_count (
+            topic_id            text        NOT NULL,
+            object_type         text        NOT NULL,
+            instance_id         smallint    NOT NULL,
+            object_count        bigint      NOT NULL,
+            updated_ts          bigint      NOT NULL,
+            PRIMARY KEY (topic_id, object_type, instance_id)
+        );
+        CREATE TABLE IF NOT EXISTS object_count_snapshot (
+            topic_id            text        NOT NULL,
+            instance_id         smallint    NOT NULL,
+            object_type         text        NOT NULL,
+            object_count        bigint      NOT NULL,
+            snapshot_ts         bigint      NOT NULL,
+            PRIMARY KEY (topic_id, instance_id, object_type)
+        );
+        CREATE TABLE IF NOT EXISTS consumer (
+            topic_id                    text        NOT NULL,
+            consumer_id                 text        NOT NULL,
+            last_update_ts              bigint      NOT NULL,
+            latest_descriptor_version   bigint,
+            unique_time_attempted       bigint      NOT NULL,
+            unique_time_done            bigint      NOT NULL,
+            PRIMARY KEY (topic_id, consumer_id)
+        );
+        CREATE TABLE IF NOT EXISTS consumer_definition (
+            topic_id            text        NOT NULL,
+            name                text        NOT NULL,
+            definition          text        NOT NULL,
+            updated_ts          bigint      NOT NULL,
+            PRIMARY KEY (topic_id, name)
+        );
+        CREATE TABLE IF NOT EXISTS delivery_intent (
+            topic_id                text        NOT NULL,
+            consumer_id             text        NOT NULL,
+            unique_time             bigint      NOT NULL,
+            delivering_instance_id  smallint    NOT NULL,
+            intent_ts               bigint      NOT NULL,
+            event_id                text        NOT NULL,
+            descriptor_version      bigint,
+            retry_count             integer     NOT NULL,
+            done                    boolean     NOT NULL,
+            PRIMARY KEY (topic_id, consumer_id, unique_time, delivering_instance_id)
+        );
+        CREATE TABLE IF NOT EXISTS delivery_prepared (
+            topic_id            text        NOT NULL,
+            consumer_id         text        NOT NULL,
+            unique_time         bigint      NOT NULL,
+            transaction_id      text        NOT NULL,
+            prepared_ts         bigint      NOT NULL,
+            PRIMARY KEY (topic_id, consumer_id, unique_time)
+        );
+        CREATE TABLE IF NOT EXISTS delivery_slot (
+            topic_id            text        NOT NULL,
+            consumer_id         text        NOT NULL,
+            slot                integer     NOT NULL,
+            unique_time         bigint      NOT NULL,
+            expires_ts          bigint      NOT NULL,
+            PRIMARY KEY (topic_id, consumer_id, slot)
+        );
+        CREATE TABLE IF NOT EXISTS integrity (
+            topic_id            text        NOT NULL,
+            protection_ts       bigint      NOT NULL,
+            protection_id       text        NOT NULL,
+            level               smallint    NOT NULL,
+            protection_data     text        NOT NULL,
+            protection_ref      text,
+            PRIMARY KEY (topic_id, protection_ts, protection_id)
+        );
+        CREATE INDEX IF NOT EXISTS integrity_by_level_and_time
+            ON integrity (topic_id, level, protection_ts);
+        ";
+
+    /// Ensure that the schema and all tables exist.
+    ///
+    /// This will create the schema and tables if needed.
+    pub async fn ensure_tables_exists(db: &PostgresProvider) {
+        let schema = &db.schema;
+        let mut client = db
+            .pool
+            .get()
+            .await
+            .unwrap_or_else(|e| panic!("Failed to get database connection: {e}"));
+        let transaction = client
+            .transaction()
+            .await
+            .unwrap_or_else(|e| panic!("Failed to start schema transaction: {e}"));
+        transaction
+            .execute(Self::SQL_LOCK, &[&Self::SCHEMA_LOCK_KEY])
+            .await
+            .unwrap_or_else(|e| panic!("Failed to lock schema: {e}"));
+        let sql_create_schema = format!("CREATE SCHEMA IF NOT EXISTS {schema}");
+        for sql in [
+            sql_create_schema.as_str(),
+            Self::SQL_CREATE_APP_TABLES,
+            Self::SQL_CREATE_TOPIC_TABLES,
+        ] {
+            transaction
+                .batch_execute(sql)
+                .await
+                .unwrap_or_else(|e| panic!("Failed to create tables in schema '{schema}': {e}"));
+        }
+        transaction
+            .commit()
+            .await
+            .unwrap_or_else(|e| panic!("Failed to commit schema '{schema}': {e}"));
+        if log::log_enabled!(log::Level::Trace) {
+            log::trace!("App and topic tables exist in schema '{schema}'.");
+        }
+    }
+}
@@ -27,6 +27,18 @@ pub trait AuthorizationFacade: Send + Sync {
     /// the `resource`.
     async fn is_any_authorized_to_resource(&self, resource: &str) -> bool;
 
+    /// Return up to `max_results` identities that are authorized to the
+    /// `resource`.
+    async fn identities_authorized_to_resource(
+        &self,
+        resource: &str,
+        max_results: usize,
+    ) -> Vec<String>;
+
+    /// Return up to `max_results` of all granted authorizations as
+    /// `(resource, identity)` tuples.
+    async fn resource_grants(&self, max_results: usize) -> Vec<(String, String)>;
+
     /// Grant the `identity` authorization for the `resource`.
     async fn grant_access_to_resource_for(
         &self,
@@ -42,4 +54,13 @@ pub trait AuthorizationFacade: Send + Sync {
         resource: &str,
         expires: Option<u64>,
     ) -> bool;
+
+    /// Consume one of the `max_uses` uses of the publish grant identified by
+    /// `grant_uid` that expires at `expires` (epoch microseconds).
+    ///
+    /// Uses are counted across all instances, so each use can only be
+    /// consumed once.
+    ///
+    /// Return `false` if all uses of the grant have already been consumed.
+    async fn publish_grant_use(&self, grant_uid: &str, max_uses: u32, expires: u64) -> bool;
 }
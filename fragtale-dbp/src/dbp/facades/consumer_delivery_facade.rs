@@ -20,6 +20,8 @@
 use crate::mb::MessageBrokerError;
 use crate::mb::UniqueTime;
 use crate::mb::consumers::DeliveryIntentTemplateInsertable;
+use crate::mb::consumers::DeliveryRecord;
+use crate::mb::consumers::RetryBackoff;
 use std::sync::Arc;
 
 /// Database facade for operation related to delivery of events to consumers.
@@ -34,6 +36,30 @@ pub trait ConsumerDeliveryFacade: Send + Sync {
         encoded_descriptor_version: Option<u64>,
     ) -> Result<(), MessageBrokerError>;
 
+    /// Get the identifiers of all consumers of the topic.
+    async fn consumer_ids(&self, topic_id: &str) -> Vec<String>;
+
+    /**
+    Persist the serialized definition of a named consumer of the topic.
+
+    An existing definition by the same name is replaced.
+
+    Return `true` if the definition was persisted.
+    */
+    async fn consumer_definition_persist(
+        &self,
+        topic_id: &str,
+        name: &str,
+        definition: &str,
+        updated_ts_micros: u64,
+    ) -> bool;
+
+    /// Remove the definition of a named consumer of the topic.
+    async fn consumer_definition_remove(&self, topic_id: &str, name: &str);
+
+    /// Get the serialized definitions of all named consumers of the topic.
+    async fn consumer_definitions(&self, topic_id: &str) -> Vec<String>;
+
     /// Get latest [UniqueTime] that is confirmed to be attempted for delivery
     async fn consumer_get_attempted_by_id(
         &self,
@@ -72,6 +98,17 @@ pub trait ConsumerDeliveryFacade: Send + Sync {
         done: UniqueTime,
     ) -> bool;
 
+    /**
+    Move the consumer's position in the topic to `position`.
+
+    Both the attempted and done [UniqueTime] of the consumer are set to
+    `position`. When moving backwards, the consumer's delivery intents after
+    `position` are removed first, so that the events will be delivered again.
+
+    Return `true` if the change was applied.
+    */
+    async fn consumer_seek(&self, topic_id: &str, consumer_id: &str, position: UniqueTime) -> bool;
+
     /// Mark a delivery to never be considered again (due to success or fail)
     async fn delivery_intent_mark_done(
         &self,
@@ -81,6 +118,67 @@ pub trait ConsumerDeliveryFacade: Send + Sync {
         delivery_instance_id: u16,
     );
 
+    /**
+    Record the consumer's external transaction identifier for the delivery of
+    an event before the delivery is confirmed.
+
+    Return `true` if the transaction identifier was persisted.
+    */
+    async fn delivery_prepare(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+        transaction_id: &str,
+        prepared_ts_micros: u64,
+    ) -> bool;
+
+    /// Get the consumer's prepared external transaction identifier for the
+    /// delivery of an event (if any).
+    async fn delivery_prepared_transaction_id(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    ) -> Option<String>;
+
+    /// Remove the consumer's prepared external transaction identifier for the
+    /// delivery of an event.
+    async fn delivery_prepared_remove(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    );
+
+    /**
+    Claim one of `slots` shared delivery slots of the consumer group for the
+    delivery of an event.
+
+    A claimed slot is automatically freed after `time_to_live_micros` to not
+    leak capacity when a delivery is never confirmed. A redelivered event keeps
+    the slot it already holds and the time to live of the slot is renewed.
+
+    Return `true` if a slot was claimed.
+    */
+    async fn delivery_slot_claim(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        slots: u32,
+        unique_time: UniqueTime,
+        time_to_live_micros: u64,
+    ) -> bool;
+
+    /// Free the consumer group's shared delivery slot claimed for the delivery
+    /// of an event (if any).
+    async fn delivery_slot_release(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        unique_time: UniqueTime,
+    );
+
     /**
     Insert a delivery intent as an audit record tying the consumer_id to the
     retrieval of an event.
@@ -103,6 +201,9 @@ pub trait ConsumerDeliveryFacade: Send + Sync {
     /**
     Attempt to reserve an intent to deliver an event from this instance.
 
+    A retry of a failed delivery (`failed_intent_ts_micros`) increases the
+    retry count of the delivery intent.
+
     Return `true` if the attempt was successful.
     */
     #[allow(clippy::too_many_arguments)]
@@ -119,8 +220,26 @@ pub trait ConsumerDeliveryFacade: Send + Sync {
         failed_intent_ts_micros: Option<u64>,
     ) -> bool;
 
+    /**
+    Get the (non-retracted) delivery intents of the consumer for events in a
+    bucket ordered by the event's [UniqueTime] (ascending) and an indicator if
+    there were more than `max_results` intents in the bucket.
+
+    This includes the audit records of [Self::delivery_intent_insert_done].
+    */
+    async fn delivery_records_by_bucket(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        bucket: u64,
+        max_results: usize,
+    ) -> (Vec<DeliveryRecord>, bool);
+
     /// Populate [DeliveryIntentTemplateInsertable] implementation with fresh
     /// intents to deliver events.
+    ///
+    /// Events with a delivery time in the future are skipped and the returned
+    /// attempted baseline is kept before them.
     async fn populate_delivery_cache_with_fresh(
         &self,
         topic_id: &str,
@@ -129,8 +248,31 @@ pub trait ConsumerDeliveryFacade: Send + Sync {
         attempted_low_exclusive: UniqueTime,
     ) -> (u64, bool);
 
+    /// Quickly populate [DeliveryIntentTemplateInsertable] implementation with
+    /// the first few fresh intents to deliver events after
+    /// `attempted_low_exclusive`.
+    ///
+    /// This scans a much smaller window than
+    /// [Self::populate_delivery_cache_with_fresh] and never moves any
+    /// baseline, so a newly connected consumer gets something to deliver
+    /// before the full cache population has run.
+    ///
+    /// Return `true` if any new intents were found.
+    async fn populate_delivery_cache_initial(
+        &self,
+        topic_id: &str,
+        consumer_id: &str,
+        consumer_delivery_cache: Box<Arc<dyn DeliveryIntentTemplateInsertable>>,
+        attempted_low_exclusive: UniqueTime,
+        max_events: usize,
+    ) -> bool;
+
     /// Populate [DeliveryIntentTemplateInsertable] implementation with failed
     /// intents to deliver events for retry.
+    ///
+    /// Failed intents are only added once they are due according to the
+    /// [RetryBackoff] and the number of previous retries of the event.
+    #[allow(clippy::too_many_arguments)]
     async fn populate_delivery_cache_with_retries(
         &self,
         topic_id: &str,
@@ -139,5 +281,6 @@ pub trait ConsumerDeliveryFacade: Send + Sync {
         done_low_exclusive: UniqueTime,
         freshness_duration_micros: u64,
         clock_skew_tolerance_micros: u64,
+        retry_backoff: &RetryBackoff,
     ) -> u64;
 }
@@ -17,9 +17,16 @@
 
 //! Database facade for operation related to events.
 
+use crate::mb::EventAnnotation;
+use crate::mb::ExtractedValue;
+use crate::mb::MessageBrokerError;
+use crate::mb::RejectedEvent;
 use crate::mb::TopicEvent;
 use crate::mb::UniqueTime;
 use crate::mb::consumers::EventDeliveryGist;
+use futures::StreamExt;
+use futures::stream::BoxStream;
+use std::collections::HashMap;
 
 /// Database facade for operation related to events.
 #[async_trait::async_trait]
@@ -51,6 +58,17 @@ pub trait EventFacade: Send + Sync {
         index_key: &str,
     ) -> Vec<String>;
 
+    /// Get the [UniqueTime] of every event with the event identifier in the
+    /// topic.
+    ///
+    /// Ordered by newest event first.
+    async fn event_unique_times_by_id(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<UniqueTime>;
+
     /// Get event's document by the provided correlation token.
     async fn event_document_by_correlation_token(
         &self,
@@ -59,5 +77,230 @@ pub trait EventFacade: Send + Sync {
     ) -> Option<EventDeliveryGist>;
 
     /// Persist an event.
-    async fn event_persist(&self, topic_id: &str, topic_event: TopicEvent) -> String;
+    ///
+    /// The topic of the event is also registered in the global event
+    /// identifier lookup used by [Self::event_topics_by_event_id].
+    ///
+    /// Return the correlation token and the number of retries needed to
+    /// persist the event, or an error when the event could not be persisted.
+    async fn event_persist(
+        &self,
+        topic_id: &str,
+        topic_event: TopicEvent,
+    ) -> Result<(String, u32), MessageBrokerError>;
+
+    /// Persist a batch of events with as few database writes as possible.
+    ///
+    /// Like [Self::event_persist], but an error is returned unless all events
+    /// are persisted.
+    ///
+    /// The batch is not guaranteed to be atomic. Providers may persist it in
+    /// several consecutive parts (e.g. to respect batch size limits of the
+    /// database), so when an error is returned, any leading part of the batch
+    /// might already be persisted and delivered to consumers.
+    ///
+    /// Return the correlation tokens in the order of `topic_events` and the
+    /// number of retries needed to persist the batch.
+    async fn events_persist(
+        &self,
+        topic_id: &str,
+        topic_events: Vec<TopicEvent>,
+    ) -> Result<(Vec<String>, u32), MessageBrokerError>;
+
+    /// Populate the indexed columns of an already persisted event.
+    ///
+    /// Return `true` if the update was applied.
+    async fn event_index_columns_update(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        additional_columns: HashMap<String, ExtractedValue>,
+    ) -> bool;
+
+    /// Permanently remove an event together with its index entries and
+    /// correlation lookup.
+    ///
+    /// Annotations of the event identifier and the entry in the global event
+    /// identifier lookup are also removed once no other event with the same
+    /// identifier remains in the topic.
+    ///
+    /// Return `true` if the event existed.
+    async fn event_purge(&self, topic_id: &str, event_id: &str, unique_time: UniqueTime) -> bool;
+
+    /// Replace the document of an event with a redacted version and the
+    /// matching integrity protection reference.
+    ///
+    /// The index entries of the event and the annotations of the event
+    /// identifier are removed, while the correlation lookup is kept.
+    ///
+    /// Return `true` if the event existed.
+    async fn event_redact(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        unique_time: UniqueTime,
+        document: &str,
+        protection_ref: &str,
+    ) -> bool;
+
+    /// Get the identifiers of topics that have had an event with the event
+    /// identifier published and the latest [UniqueTime] of the event in each
+    /// topic.
+    ///
+    /// Ordered by topic identifier (ascending). The lookup is maintained at
+    /// publish time and is not cleaned up when events expire, so callers
+    /// should verify that the event still exists.
+    async fn event_topics_by_event_id(
+        &self,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<(String, UniqueTime)>;
+
+    /// Persist an event that was rejected by validation in the topic's reject
+    /// store.
+    ///
+    /// A previously rejected event with the same event identifier will be
+    /// replaced.
+    async fn rejected_event_persist(&self, topic_id: &str, rejected_event: RejectedEvent) -> bool;
+
+    /// Get rejected events ordered by event identifier (ascending) that are
+    /// greater than `from` and an indicator if there might be more results
+    /// than what was returned.
+    async fn rejected_events(
+        &self,
+        topic_id: &str,
+        from: &Option<String>,
+        max_results: usize,
+    ) -> (Vec<RejectedEvent>, bool);
+
+    /// Get a rejected event by the event identifier.
+    async fn rejected_event_by_id(&self, topic_id: &str, event_id: &str) -> Option<RejectedEvent>;
+
+    /// Remove a rejected event from the topic's reject store.
+    async fn rejected_event_delete(&self, topic_id: &str, event_id: &str) -> bool;
+
+    /// Append an annotation to an event.
+    async fn event_annotation_persist(
+        &self,
+        topic_id: &str,
+        event_annotation: EventAnnotation,
+    ) -> bool;
+
+    /// Get annotations of an event ordered by time of annotation (ascending).
+    async fn event_annotations_by_event_id(
+        &self,
+        topic_id: &str,
+        event_id: &str,
+        max_results: usize,
+    ) -> Vec<EventAnnotation>;
+
+    /// Get the used buckets of a shelf (ascending) that are greater than
+    /// `from_bucket` and an indicator if there might be more results than
+    /// what was returned.
+    async fn buckets_by_shelf(
+        &self,
+        topic_id: &str,
+        shelf: u16,
+        from_bucket: Option<u64>,
+        max_results: usize,
+    ) -> (Vec<u64>, bool);
+
+    /// Get the number of events in a bucket.
+    async fn event_count_by_bucket(&self, topic_id: &str, bucket: u64) -> u64;
+
+    /// Get the [UniqueTime], event identifier and descriptor version of events
+    /// in a bucket ordered by [UniqueTime] (ascending) that are greater than
+    /// `from` and an indicator if there might be more results than what was
+    /// returned.
+    async fn events_by_bucket(
+        &self,
+        topic_id: &str,
+        bucket: u64,
+        from: Option<UniqueTime>,
+        max_results: usize,
+    ) -> (Vec<(UniqueTime, String, Option<u64>)>, bool);
+
+    /// Stream the [UniqueTime], event identifier and descriptor version of
+    /// events in a bucket ordered by [UniqueTime] (ascending) that are greater
+    /// than `from`.
+    ///
+    /// Each page of at most `page_size` results is only fetched using
+    /// [Self::events_by_bucket] when the previous page has been consumed.
+    fn events_by_bucket_stream<'a>(
+        &'a self,
+        topic_id: &'a str,
+        bucket: u64,
+        from: Option<UniqueTime>,
+        page_size: usize,
+    ) -> BoxStream<'a, (UniqueTime, String, Option<u64>)> {
+        futures::stream::unfold(Some(from), move |from| async move {
+            let from = from?;
+            let (entries, more) = self
+                .events_by_bucket(topic_id, bucket, from, page_size)
+                .await;
+            if entries.is_empty() {
+                return None;
+            }
+            let next = entries
+                .last()
+                .filter(|_| more)
+                .map(|(unique_time, _event_id, _descriptor_version)| Some(*unique_time));
+            Some((futures::stream::iter(entries), next))
+        })
+        .flatten()
+        .boxed()
+    }
+
+    /// Get the [UniqueTime], event identifier and descriptor version of events
+    /// with a [UniqueTime] greater than `from` and up to and including `to`
+    /// ordered by [UniqueTime] (ascending) and an indicator if there might be
+    /// more results than what was returned.
+    ///
+    /// The default implementation walks the used buckets of each shelf in the
+    /// range using [Self::buckets_by_shelf] and [Self::events_by_bucket_stream].
+    async fn events_by_unique_time_range(
+        &self,
+        topic_id: &str,
+        from: UniqueTime,
+        to: UniqueTime,
+        max_results: usize,
+    ) -> (Vec<(UniqueTime, String, Option<u64>)>, bool) {
+        const BUCKETS_PAGE_SIZE: usize = 32;
+        let mut ret = Vec::new();
+        if from.as_encoded() >= to.as_encoded() || max_results == 0 {
+            return (ret, false);
+        }
+        // Buckets are listed after (exclusive) the provided bucket
+        let mut current_bucket = from.get_bucket().checked_sub(1);
+        for shelf in from.get_shelf()..=to.get_shelf() {
+            loop {
+                let (buckets, more) = self
+                    .buckets_by_shelf(topic_id, shelf, current_bucket, BUCKETS_PAGE_SIZE)
+                    .await;
+                for bucket in buckets {
+                    if bucket > to.get_bucket() {
+                        return (ret, false);
+                    }
+                    let mut entries =
+                        self.events_by_bucket_stream(topic_id, bucket, Some(from), max_results);
+                    while let Some(entry) = entries.next().await {
+                        if entry.0.as_encoded() > to.as_encoded() {
+                            return (ret, false);
+                        }
+                        ret.push(entry);
+                        if ret.len() == max_results {
+                            return (ret, true);
+                        }
+                    }
+                    current_bucket = Some(bucket);
+                }
+                if !more {
+                    break;
+                }
+            }
+            current_bucket = None;
+        }
+        (ret, false)
+    }
 }
@@ -41,6 +41,26 @@ pub trait EventTrackingFacade: Send + Sync {
         object_count_type: &ObjectCountType,
     ) -> Vec<ObjectCount>;
 
+    /// Persist a snapshot of the local count of the [ObjectCountType] that
+    /// is kept across restarts.
+    async fn object_count_snapshot_upsert(
+        &self,
+        topic_id: &str,
+        object_count_type: &ObjectCountType,
+        instance_id: u16,
+        value: u64,
+    );
+
+    /// Get the persisted snapshots of the local counts of an instance.
+    async fn object_count_snapshots_by_instance(
+        &self,
+        topic_id: &str,
+        instance_id: u16,
+    ) -> Vec<(ObjectCountType, u64)>;
+
+    /// Remove the persisted snapshots of the local counts of all instances.
+    async fn object_count_snapshots_delete(&self, topic_id: &str);
+
     /// Notify [CorrelationResultListener] of existing correlation results.
     ///
     /// Return `true` if at least on notification was made.
@@ -37,4 +37,44 @@ pub trait InstanceIdFacade: Send + Sync {
     /// out or to ensure that a task is only performed at a single instance
     /// (the oldest one).
     async fn get_oldest_instance_id(&self) -> (u16, u64);
+
+    /// Register the claimed instance id as a candidate for the `role` for
+    /// `time_to_live_seconds`.
+    ///
+    /// The time of the first registration is kept when the registration is
+    /// refreshed, so the oldest candidate can be elected to perform a task
+    /// that only some instances take part in.
+    ///
+    /// Returns `false` if the registration failed.
+    async fn claim_role(
+        &self,
+        role: &str,
+        time_to_live_seconds: u32,
+        claimed_instance_id: u16,
+    ) -> bool;
+
+    /// Remove the instance id as a candidate for the `role`.
+    async fn free_role(&self, role: &str, claimed_instance_id: u16);
+
+    /// Return the oldest alive candidate instance id for the `role` (if
+    /// any).
+    async fn get_oldest_instance_id_by_role(&self, role: &str) -> Option<u16>;
+
+    /// Return the serialized cluster wide feature flags (if any have been
+    /// stored).
+    ///
+    /// The feature flags allow instances of different versions to agree on
+    /// the encodings used in the shared database during rolling upgrades.
+    async fn feature_flags(&self) -> Option<String>;
+
+    /// Store the serialized cluster wide feature flags unless another
+    /// instance has changed them since they were read as `expected` (`None`
+    /// when no feature flags had been stored).
+    ///
+    /// Returns `false` if the feature flags were not stored.
+    async fn feature_flags_compare_and_set(
+        &self,
+        expected: Option<&str>,
+        feature_flags: &str,
+    ) -> bool;
 }
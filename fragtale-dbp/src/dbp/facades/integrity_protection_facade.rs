@@ -17,6 +17,9 @@
 
 //! Database facade for operation related to event integrity protection.
 
+use futures::StreamExt;
+use futures::stream::BoxStream;
+
 /// Database facade for operation related to event integrity protection.
 #[async_trait::async_trait]
 pub trait IntegrityProtectionFacade: Send + Sync {
@@ -64,4 +67,39 @@ pub trait IntegrityProtectionFacade: Send + Sync {
         from_protections_ts_micros: u64,
         max_results: usize,
     ) -> Vec<(String, u64, String, Option<String>)>;
+
+    /// Stream (`protection_id`, `protection_ts_micros`, `protection_data`,
+    /// `protection_ref`) from `from_protections_ts_micros` and onwards.
+    ///
+    /// Each page of at most `page_size` results is only fetched using
+    /// [Self::integrity_batch_in_interval_by_level_and_time] when the previous
+    /// page has been consumed.
+    fn integrity_stream_in_interval_by_level_and_time<'a>(
+        &'a self,
+        topic_id: &'a str,
+        level: u8,
+        from_protections_ts_micros: u64,
+        page_size: usize,
+    ) -> BoxStream<'a, (String, u64, String, Option<String>)> {
+        futures::stream::unfold(
+            Some(from_protections_ts_micros),
+            move |from_protections_ts_micros| async move {
+                let from_protections_ts_micros = from_protections_ts_micros?;
+                let results = self
+                    .integrity_batch_in_interval_by_level_and_time(
+                        topic_id,
+                        level,
+                        from_protections_ts_micros,
+                        page_size,
+                    )
+                    .await;
+                let next = results
+                    .last()
+                    .map(|(_protection_id, protection_ts_micros, _, _)| protection_ts_micros + 1);
+                next.map(|next| (futures::stream::iter(results), Some(next)))
+            },
+        )
+        .flatten()
+        .boxed()
+    }
 }
@@ -17,7 +17,10 @@
 
 //! Database facade for operation related to topics and event descriptor.
 
+use crate::mb::DiagnosticQuery;
+use crate::mb::IndexRebuildProgress;
 use crate::mb::MessageBrokerError;
+use crate::mb::UniqueTime;
 
 /// Database facade for operation related to topics and event descriptor.
 #[async_trait::async_trait]
@@ -26,6 +29,9 @@ pub trait TopicFacade: Send + Sync {
     /// on name conformance.
     async fn ensure_topic_setup(&self, topic_id: &str) -> Result<(), MessageBrokerError>;
 
+    /// Return `true` if the topic has already been set up in the database.
+    async fn topic_exists(&self, topic_id: &str) -> bool;
+
     /// Get all topics (ascending) and an indicator if there might be more
     /// results than what was returned.
     async fn get_topic_ids(&self, from: &Option<String>) -> (Vec<String>, bool);
@@ -47,6 +53,24 @@ pub trait TopicFacade: Send + Sync {
         min_descriptor_version: Option<u64>,
     ) -> Vec<String>;
 
+    /// Persist a version of a shared schema component in the schema registry.
+    ///
+    /// Return `true` if the version did not already exist.
+    async fn shared_schema_persist(
+        &self,
+        schema_id: &str,
+        version: u64,
+        shared_schema: &str,
+    ) -> bool;
+
+    /// Return the latest version of the shared schema component with the
+    /// identifier (if any) in serialized form.
+    async fn shared_schema_latest_by_id(&self, schema_id: &str) -> Option<(u64, String)>;
+
+    /// Return the latest version of up to `max_results` shared schema
+    /// components in serialized form.
+    async fn shared_schemas_latest(&self, max_results: usize) -> Vec<String>;
+
     /// Setup indexes or similar backend functionality to enable queries of
     /// extracted document values.
     async fn extraction_setup_searchable(
@@ -54,4 +78,73 @@ pub trait TopicFacade: Send + Sync {
         topic_id: &str,
         name_and_type_slice: &[(String, String)],
     );
+
+    /// Register the topic where the responding service publishes correlated
+    /// results of events in `topic_id`.
+    ///
+    /// Return `true` if the registration was persisted.
+    async fn reply_topic_register(
+        &self,
+        topic_id: &str,
+        reply_topic_id: &str,
+        registered_by: &str,
+    ) -> bool;
+
+    /// Return the registered topic for correlated results of events in
+    /// `topic_id`, if any.
+    async fn reply_topic_by_topic_id(&self, topic_id: &str) -> Option<String>;
+
+    /// Return the identity that registered the topic for correlated results
+    /// of events in `topic_id`, if any.
+    async fn reply_topic_registered_by(&self, topic_id: &str) -> Option<String>;
+
+    /// Persist the bulk ingest window of a topic, replacing any previous
+    /// window.
+    ///
+    /// `reconciled` is `true` once deferred work of events published during
+    /// the window has been completed.
+    ///
+    /// Return `true` if the window was persisted.
+    async fn bulk_ingest_window_persist(
+        &self,
+        topic_id: &str,
+        start_ts_micros: u64,
+        end_ts_micros: u64,
+        reconciled: bool,
+    ) -> bool;
+
+    /// Return the start and end in epoch microseconds and reconciliation
+    /// status of the latest bulk ingest window of a topic, if any.
+    async fn bulk_ingest_window_by_topic_id(&self, topic_id: &str) -> Option<(u64, u64, bool)>;
+
+    /// Persist the progress of the rebuild of the indexed columns of a topic,
+    /// replacing any previous progress.
+    ///
+    /// Return `true` if the progress was persisted.
+    async fn index_rebuild_persist(
+        &self,
+        topic_id: &str,
+        index_rebuild_progress: IndexRebuildProgress,
+    ) -> bool;
+
+    /// Return the progress of the latest rebuild of the indexed columns of a
+    /// topic, if any.
+    async fn index_rebuild_by_topic_id(&self, topic_id: &str) -> Option<IndexRebuildProgress>;
+
+    /**
+    Run a whitelisted read-only diagnostic query against the storage of the
+    topic.
+
+    The range of unique times is inclusive. Return each result row as a JSON
+    document and an indicator if there might be more results than what was
+    returned.
+    */
+    async fn diagnostic_query(
+        &self,
+        topic_id: &str,
+        diagnostic_query: DiagnosticQuery,
+        consumer_id: Option<&str>,
+        range: Option<(UniqueTime, UniqueTime)>,
+        max_results: usize,
+    ) -> Result<(Vec<String>, bool), MessageBrokerError>;
 }
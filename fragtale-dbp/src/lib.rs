@@ -28,11 +28,15 @@ pub mod mb {
 
         mod delivery_intent_template;
         mod delivery_intent_template_insertable;
+        mod delivery_record;
         mod event_delivery_gist;
+        mod retry_backoff;
 
         pub use self::delivery_intent_template::DeliveryIntentTemplate;
         pub use self::delivery_intent_template_insertable::DeliveryIntentTemplateInsertable;
+        pub use self::delivery_record::DeliveryRecord;
         pub use self::event_delivery_gist::EventDeliveryGist;
+        pub use self::retry_backoff::RetryBackoff;
     }
     pub mod correlation {
         //! Tracking outcomes of a request event.
@@ -50,16 +54,26 @@ pub mod mb {
         pub use self::object_count::ObjectCount;
         pub use self::object_count_type::ObjectCountType;
     }
+    mod diagnostic_query;
+    mod event_annotation;
+    mod event_id_algorithm;
     mod extracted_value;
+    mod index_rebuild_progress;
     mod message_broker_error;
+    mod rejected_event;
     mod topic_event;
     mod unique_time;
 
+    pub use self::diagnostic_query::DiagnosticQuery;
+    pub use self::event_annotation::EventAnnotation;
+    pub use self::event_id_algorithm::EventIdAlgorithm;
     pub use self::extracted_value::ExtractedValue;
+    pub use self::index_rebuild_progress::IndexRebuildProgress;
     pub use self::message_broker_error::MessageBrokerError;
     pub use self::message_broker_error::MessageBrokerErrorKind;
     pub use self::object_count_tracker::ObjectCount;
     pub use self::object_count_tracker::ObjectCountType;
+    pub use self::rejected_event::RejectedEvent;
     pub use self::topic_event::TopicEvent;
     pub use self::unique_time::UniqueTime;
 }
@@ -26,6 +26,7 @@ pub struct DeliveryIntentTemplate {
     event_id: String,
     descriptor_version: Option<u64>,
     failed_intent_ts: Option<u64>,
+    expires_ts: Option<u64>,
 }
 impl DeliveryIntentTemplate {
     /// Return a new instance.
@@ -34,12 +35,14 @@ impl DeliveryIntentTemplate {
         event_id: String,
         descriptor_version: Option<u64>,
         failed_intent_ts: Option<u64>,
+        expires_ts: Option<u64>,
     ) -> Self {
         Self {
             unique_time,
             event_id,
             descriptor_version,
             failed_intent_ts,
+            expires_ts,
         }
     }
 
@@ -62,4 +65,16 @@ impl DeliveryIntentTemplate {
     pub fn get_failed_intent_ts(&self) -> &Option<u64> {
         &self.failed_intent_ts
     }
+
+    /// Return the deadline in epoch microseconds after which the event should
+    /// no longer be delivered (if known).
+    pub fn get_expires_ts(&self) -> Option<u64> {
+        self.expires_ts
+    }
+
+    /// Return `true` if the event has a known deadline that has passed.
+    pub fn is_expired(&self, now_micros: u64) -> bool {
+        self.expires_ts
+            .is_some_and(|expires_ts| expires_ts < now_micros)
+    }
 }
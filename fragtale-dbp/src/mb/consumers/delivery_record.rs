@@ -0,0 +1,75 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Record of an attempted or completed delivery of an event to a consumer.
+
+use crate::mb::UniqueTime;
+
+/// Record of an attempted or completed delivery of an event to a consumer.
+#[derive(Clone, Debug)]
+pub struct DeliveryRecord {
+    unique_time: UniqueTime,
+    event_id: String,
+    intent_ts_micros: u64,
+    done: bool,
+    retry_count: u32,
+}
+
+impl DeliveryRecord {
+    /// Return a new instance.
+    pub fn new(
+        unique_time: UniqueTime,
+        event_id: String,
+        intent_ts_micros: u64,
+        done: bool,
+        retry_count: u32,
+    ) -> Self {
+        Self {
+            unique_time,
+            event_id,
+            intent_ts_micros,
+            done,
+            retry_count,
+        }
+    }
+
+    /// Return the delivered event's `UniqueTime`.
+    pub fn get_unique_time(&self) -> UniqueTime {
+        self.unique_time
+    }
+
+    /// Return the delivered event's identifier.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Return the time of the (latest) delivery attempt in epoch
+    /// microseconds.
+    pub fn get_intent_ts_micros(&self) -> u64 {
+        self.intent_ts_micros
+    }
+
+    /// Return `true` if the consumer confirmed the delivery.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Return the number of times delivery has been retried.
+    pub fn get_retry_count(&self) -> u32 {
+        self.retry_count
+    }
+}
@@ -18,28 +18,38 @@
 //! The core information that makes up an event.
 
 use crate::mb::UniqueTime;
+use std::sync::Arc;
 
 /// The core information that makes up an event.
+///
+/// The document is shared, so it can be handed on from the database provider
+/// to the consumer without copying (potentially large) payloads.
 pub struct EventDeliveryGist {
     unique_time: UniqueTime,
-    document: String,
+    document: Arc<str>,
     protection_ref: String,
     correlation_token: String,
+    priority: Option<u8>,
+    expires_ts: Option<u64>,
 }
 
 impl EventDeliveryGist {
     /// Return a new instance.
     pub fn new(
         unique_time: UniqueTime,
-        document: String,
+        document: Arc<str>,
         protection_ref: String,
         correlation_token: String,
+        priority: Option<u8>,
+        expires_ts: Option<u64>,
     ) -> Self {
         Self {
             unique_time,
             document,
             protection_ref,
             correlation_token,
+            priority,
+            expires_ts,
         }
     }
 
@@ -63,13 +73,33 @@ impl EventDeliveryGist {
         &self.correlation_token
     }
 
+    /// Return the priority the event was published with.
+    ///
+    /// This is `None` for events persisted before the priority was stored.
+    pub fn get_priority(&self) -> Option<u8> {
+        self.priority
+    }
+
+    /// Return the deadline in epoch microseconds after which the event should
+    /// no longer be delivered (if any).
+    pub fn get_expires_ts(&self) -> Option<u64> {
+        self.expires_ts
+    }
+
+    /// Return `true` if the event has a deadline that has passed.
+    pub fn is_expired(&self, now_micros: u64) -> bool {
+        self.expires_ts
+            .is_some_and(|expires_ts| expires_ts < now_micros)
+    }
+
     /// Deconstruct this struct into its parts.
-    pub fn into_parts(self) -> (UniqueTime, String, String, String) {
+    pub fn into_parts(self) -> (UniqueTime, Arc<str>, String, String, Option<u8>) {
         (
             self.unique_time,
             self.document,
             self.protection_ref,
             self.correlation_token,
+            self.priority,
         )
     }
 }
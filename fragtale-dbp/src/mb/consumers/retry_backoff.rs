@@ -0,0 +1,95 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Tiered backoff of retries of failed deliveries.
+
+/** Tiered backoff of retries of failed deliveries.
+
+Each retry of the same event waits for the next tier, so persistent failures
+don't keep consuming bandwidth at the same rate as fresh traffic. The last
+tier is used for all following retries.
+
+A retry is never due before the intent to deliver has timed out.
+*/
+#[derive(Clone, Debug, Default)]
+pub struct RetryBackoff {
+    tiers_micros: Vec<u64>,
+}
+
+impl RetryBackoff {
+    /// Return a new instance with the delay before each retry in
+    /// microseconds.
+    ///
+    /// Without any tiers, a failed delivery is retried as soon as the intent
+    /// to deliver has timed out.
+    pub fn new(tiers_micros: Vec<u64>) -> Self {
+        Self { tiers_micros }
+    }
+
+    /// Return the delay before each retry in microseconds.
+    pub fn get_tiers_micros(&self) -> &[u64] {
+        &self.tiers_micros
+    }
+
+    /// Return the epoch microseconds when an intent to deliver from
+    /// `intent_ts_micros` that was preceded by `retry_count` retries is due
+    /// for another retry.
+    pub fn get_due_ts_micros(
+        &self,
+        intent_ts_micros: u64,
+        retry_count: u32,
+        freshness_duration_micros: u64,
+    ) -> u64 {
+        let delay_micros = self
+            .tiers_micros
+            .get(usize::try_from(retry_count).unwrap_or(usize::MAX))
+            .or(self.tiers_micros.last())
+            .copied()
+            .unwrap_or_default();
+        intent_ts_micros.saturating_add(std::cmp::max(delay_micros, freshness_duration_micros))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_back_off_in_tiers() {
+        let retry_backoff = RetryBackoff::new(vec![3_000_000, 30_000_000, 300_000_000]);
+        assert_eq!(retry_backoff.get_due_ts_micros(10, 0, 3_000_000), 3_000_010);
+        assert_eq!(
+            retry_backoff.get_due_ts_micros(10, 1, 3_000_000),
+            30_000_010
+        );
+        assert_eq!(
+            retry_backoff.get_due_ts_micros(10, 2, 3_000_000),
+            300_000_010
+        );
+        assert_eq!(
+            retry_backoff.get_due_ts_micros(10, 7, 3_000_000),
+            300_000_010
+        );
+        // Never before the intent has timed out
+        let retry_backoff = RetryBackoff::new(vec![1_000]);
+        assert_eq!(retry_backoff.get_due_ts_micros(10, 0, 3_000_000), 3_000_010);
+        assert_eq!(
+            RetryBackoff::default().get_due_ts_micros(10, 5, 3_000_000),
+            3_000_010
+        );
+    }
+}
@@ -0,0 +1,92 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Whitelisted read-only diagnostic queries.
+
+/// Whitelisted read-only diagnostic queries against the storage of a topic.
+///
+/// These exist for emergencies, so operators rarely need direct database
+/// access to production.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticQuery {
+    /// Persisted delivery state of a consumer.
+    Consumer,
+    /// Delivery intents of a consumer in a range of unique times.
+    DeliveryIntents,
+    /// Prepared delivery confirmations of a consumer in a range of unique
+    /// times.
+    DeliveryPrepared,
+    /// Claimed shared delivery slots of a consumer.
+    DeliverySlots,
+    /// Event identifiers in a range of unique times.
+    EventIdsByUniqueTime,
+}
+
+impl DiagnosticQuery {
+    /// All available diagnostic queries.
+    pub const ALL: [Self; 5] = [
+        Self::Consumer,
+        Self::DeliveryIntents,
+        Self::DeliveryPrepared,
+        Self::DeliverySlots,
+        Self::EventIdsByUniqueTime,
+    ];
+
+    /// Return the diagnostic query by name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|diagnostic_query| diagnostic_query.get_name() == name)
+    }
+
+    /// Return the name of the diagnostic query.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Consumer => "consumer",
+            Self::DeliveryIntents => "delivery_intents",
+            Self::DeliveryPrepared => "delivery_prepared",
+            Self::DeliverySlots => "delivery_slots",
+            Self::EventIdsByUniqueTime => "event_ids_by_unique_time",
+        }
+    }
+
+    /// Return a human readable description of the diagnostic query.
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            Self::Consumer => "Persisted delivery state of a consumer.",
+            Self::DeliveryIntents => "Delivery intents of a consumer in a range of unique times.",
+            Self::DeliveryPrepared => {
+                "Prepared delivery confirmations of a consumer in a range of unique times."
+            }
+            Self::DeliverySlots => "Claimed shared delivery slots of a consumer.",
+            Self::EventIdsByUniqueTime => "Event identifiers in a range of unique times.",
+        }
+    }
+
+    /// Return `true` if the query requires a consumer identifier.
+    pub fn requires_consumer_id(&self) -> bool {
+        !matches!(self, Self::EventIdsByUniqueTime)
+    }
+
+    /// Return `true` if the query requires a range of unique times.
+    pub fn requires_range(&self) -> bool {
+        matches!(
+            self,
+            Self::DeliveryIntents | Self::DeliveryPrepared | Self::EventIdsByUniqueTime
+        )
+    }
+}
@@ -0,0 +1,73 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Event annotation model.
+
+/// Append-only annotation of an existing event, stored separately from the
+/// event itself.
+#[derive(Debug, Clone)]
+pub struct EventAnnotation {
+    event_id: String,
+    annotated_ts_micros: u64,
+    author: String,
+    kind: String,
+    value: String,
+}
+
+impl EventAnnotation {
+    /// Return a new instance.
+    pub fn new(
+        event_id: &str,
+        annotated_ts_micros: u64,
+        author: &str,
+        kind: &str,
+        value: &str,
+    ) -> Self {
+        Self {
+            event_id: event_id.to_owned(),
+            annotated_ts_micros,
+            author: author.to_owned(),
+            kind: kind.to_owned(),
+            value: value.to_owned(),
+        }
+    }
+
+    /// Return the identifier of the annotated event.
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Return the time of the annotation in epoch microseconds.
+    pub fn get_annotated_ts_micros(&self) -> u64 {
+        self.annotated_ts_micros
+    }
+
+    /// Return the identity of the author in serialized form.
+    pub fn get_author(&self) -> &str {
+        &self.author
+    }
+
+    /// Return the kind of annotation.
+    pub fn get_kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Return the value of the annotation.
+    pub fn get_value(&self) -> &str {
+        &self.value
+    }
+}
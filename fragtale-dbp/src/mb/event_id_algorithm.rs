@@ -0,0 +1,94 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Algorithm used to derive the event identifier from the event document.
+
+use tyst::Tyst;
+
+/// Message digest algorithm used to derive the event identifier (fingerprint)
+/// from the event document.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum EventIdAlgorithm {
+    /// SHA3-256
+    Sha3_256,
+    /// SHA3-384
+    Sha3_384,
+    /// SHA3-512
+    #[default]
+    Sha3_512,
+}
+
+impl EventIdAlgorithm {
+    /// All supported algorithms.
+    pub const ALL: [Self; 3] = [Self::Sha3_256, Self::Sha3_384, Self::Sha3_512];
+
+    /// Return the algorithm with the `name` (e.g. "SHA3-512") if supported.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "SHA3-256" => Some(Self::Sha3_256),
+            "SHA3-384" => Some(Self::Sha3_384),
+            "SHA3-512" => Some(Self::Sha3_512),
+            _ => None,
+        }
+    }
+
+    /// Return the name of the algorithm.
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Sha3_256 => "SHA3-256",
+            Self::Sha3_384 => "SHA3-384",
+            Self::Sha3_512 => "SHA3-512",
+        }
+    }
+
+    fn get_oid(&self) -> &'static [u32] {
+        match self {
+            Self::Sha3_256 => tyst::oids::digest::SHA3_256,
+            Self::Sha3_384 => tyst::oids::digest::SHA3_384,
+            Self::Sha3_512 => tyst::oids::digest::SHA3_512,
+        }
+    }
+
+    /// Return the event_id (fingerprint) of the document.
+    pub fn event_id_from_document(&self, document: &str) -> String {
+        tyst::encdec::hex::encode(
+            &Tyst::instance()
+                .digests()
+                .by_oid(&tyst::encdec::oid::as_string(self.get_oid()))
+                .unwrap()
+                .hash(document.as_bytes()),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_id_length_by_algorithm() {
+        let document = "{\"key\":\"value\"}";
+        for (name, hex_len) in [("SHA3-256", 64), ("sha3-384", 96), ("SHA3-512", 128)] {
+            let event_id_algorithm = EventIdAlgorithm::from_name(name).unwrap();
+            assert_eq!(
+                event_id_algorithm.event_id_from_document(document).len(),
+                hex_len
+            );
+        }
+        assert!(EventIdAlgorithm::from_name("MD5").is_none());
+    }
+}
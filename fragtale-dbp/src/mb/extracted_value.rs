@@ -24,6 +24,10 @@ pub enum ExtractedValue {
     Text(String),
     /// Document value in numeric format.
     BigInt(i64),
+    /// Members of a document array in String format.
+    TextCollection(Vec<String>),
+    /// Members of a document array in numeric format.
+    BigIntCollection(Vec<i64>),
 }
 
 impl ExtractedValue {
@@ -50,10 +54,98 @@ impl ExtractedValue {
                     None
                 }
             }
+            "set<text>" | "list<text>" => {
+                Self::members(result_type, value, serde_json::Value::as_str).map(|members| {
+                    ExtractedValue::TextCollection(members.into_iter().map(str::to_owned).collect())
+                })
+            }
+            "set<bigint>" | "list<bigint>" => {
+                Self::members(result_type, value, serde_json::Value::as_i64)
+                    .map(ExtractedValue::BigIntCollection)
+            }
             result_type => {
                 log::debug!("Ignoring unsupported result_type {result_type}.");
                 None
             }
         }
     }
+
+    /// Return the members of a JSON array that are of the expected type.
+    ///
+    /// Members of other types are ignored. Members of a `set` result type are
+    /// sorted and deduplicated.
+    fn members<'a, T: Ord>(
+        result_type: &str,
+        value: &'a serde_json::Value,
+        member_fn: impl Fn(&'a serde_json::Value) -> Option<T>,
+    ) -> Option<Vec<T>> {
+        let Some(array) = value.as_array() else {
+            log::debug!(
+                "Failed to parse json value '{value:?}' as result_type '{result_type}'. Ignoring."
+            );
+            return None;
+        };
+        let mut members = array
+            .iter()
+            .filter_map(|member| {
+                let ret = member_fn(member);
+                if ret.is_none() {
+                    log::debug!(
+                        "Failed to parse json value '{member:?}' as member of result_type '{result_type}'. Ignoring."
+                    );
+                }
+                ret
+            })
+            .collect::<Vec<_>>();
+        if result_type.starts_with("set<") {
+            members.sort_unstable();
+            members.dedup();
+        }
+        Some(members)
+    }
+
+    /// Return `true` if the result type holds multiple values per document.
+    pub fn is_collection_type(result_type: &str) -> bool {
+        result_type.starts_with("set<") || result_type.starts_with("list<")
+    }
+
+    /// Return the index key of each value in String format.
+    ///
+    /// Scalar values have a single index key, while collections have one per
+    /// member so the event can be found by any of them.
+    pub fn as_index_keys(&self) -> Vec<String> {
+        match self {
+            ExtractedValue::Text(text) => vec![text.to_owned()],
+            ExtractedValue::BigInt(number) => vec![number.to_string()],
+            ExtractedValue::TextCollection(texts) => texts.to_owned(),
+            ExtractedValue::BigIntCollection(numbers) => {
+                numbers.iter().map(i64::to_string).collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arrays_are_extracted_as_collections() {
+        let value = serde_json::json!(["b", "a", 1, "b"]);
+        assert!(matches!(
+            ExtractedValue::new("set<text>", &value),
+            Some(ExtractedValue::TextCollection(members)) if members == ["a", "b"]
+        ));
+        assert!(matches!(
+            ExtractedValue::new("list<text>", &value),
+            Some(ExtractedValue::TextCollection(members)) if members == ["b", "a", "b"]
+        ));
+        let value = serde_json::json!([3, 1, 3]);
+        assert_eq!(
+            ExtractedValue::new("set<bigint>", &value)
+                .map(|extracted_value| extracted_value.as_index_keys()),
+            Some(vec!["1".to_owned(), "3".to_owned()])
+        );
+        assert!(ExtractedValue::new("set<bigint>", &serde_json::json!(3)).is_none());
+    }
 }
@@ -0,0 +1,66 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Progress of an online rebuild of the indexed columns of a topic.
+
+/// Progress of an online rebuild of the indexed columns of a topic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexRebuildProgress {
+    requested_ts_micros: u64,
+    position: u64,
+    rebuilt_count: u64,
+    completed_ts_micros: Option<u64>,
+}
+
+impl IndexRebuildProgress {
+    /// Return a new instance.
+    pub fn new(
+        requested_ts_micros: u64,
+        position: u64,
+        rebuilt_count: u64,
+        completed_ts_micros: Option<u64>,
+    ) -> Self {
+        Self {
+            requested_ts_micros,
+            position,
+            rebuilt_count,
+            completed_ts_micros,
+        }
+    }
+
+    /// Return the time the rebuild was requested in epoch microseconds.
+    pub fn get_requested_ts_micros(&self) -> u64 {
+        self.requested_ts_micros
+    }
+
+    /// Return the encoded [crate::mb::UniqueTime] up to which all events
+    /// have been processed or `0` if none have.
+    pub fn get_position(&self) -> u64 {
+        self.position
+    }
+
+    /// Return the number of events where indexed columns were populated.
+    pub fn get_rebuilt_count(&self) -> u64 {
+        self.rebuilt_count
+    }
+
+    /// Return the time the rebuild completed in epoch microseconds (if
+    /// completed).
+    pub fn get_completed_ts_micros(&self) -> Option<u64> {
+        self.completed_ts_micros
+    }
+}
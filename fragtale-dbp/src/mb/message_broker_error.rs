@@ -29,6 +29,8 @@ pub enum MessageBrokerErrorKind {
     MalformedIdentifier,
     /// Event descriptor error.
     EvenDescriptorError,
+    /// Malformed request content. E.g. too large or of an unknown kind.
+    MalformedRequest,
     /// Time could be trusted.
     TrustedTimeError,
     /// Failure during processing before storing event, like schema validation
@@ -40,6 +42,12 @@ pub enum MessageBrokerErrorKind {
     AuthenticationFailure,
     /// Unauthorized.
     Unauthorized,
+    /// The deadline of the request passed before the operation completed.
+    DeadlineExceeded,
+    /// The database failed to persist the data, even after retries.
+    PersistenceFailure,
+    /// A quota was exceeded.
+    QuotaExceeded,
 }
 
 impl MessageBrokerErrorKind {
@@ -0,0 +1,97 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Rejected event model.
+
+/// An event document that was rejected by validation during publishing.
+#[derive(Debug, Clone)]
+pub struct RejectedEvent {
+    event_id: String,
+    rejected_ts_micros: u64,
+    document: String,
+    priority: Option<u8>,
+    descriptor_version: Option<u64>,
+    correlation_token: Option<String>,
+    publisher: String,
+    error_message: String,
+}
+
+impl RejectedEvent {
+    /// Return a new instance.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        event_id: &str,
+        rejected_ts_micros: u64,
+        document: &str,
+        priority: Option<u8>,
+        descriptor_version: Option<u64>,
+        correlation_token: Option<String>,
+        publisher: &str,
+        error_message: &str,
+    ) -> Self {
+        Self {
+            event_id: event_id.to_owned(),
+            rejected_ts_micros,
+            document: document.to_owned(),
+            priority,
+            descriptor_version,
+            correlation_token,
+            publisher: publisher.to_owned(),
+            error_message: error_message.to_owned(),
+        }
+    }
+
+    /// Return the event identifier (message digest of the document).
+    pub fn get_event_id(&self) -> &str {
+        &self.event_id
+    }
+
+    /// Return the time of the rejection in epoch microseconds.
+    pub fn get_rejected_ts_micros(&self) -> u64 {
+        self.rejected_ts_micros
+    }
+
+    /// Return the rejected event document.
+    pub fn get_document(&self) -> &str {
+        &self.document
+    }
+
+    /// Return the requested event priority.
+    pub fn get_priority(&self) -> Option<u8> {
+        self.priority
+    }
+
+    /// Return the requested version of the event's descriptor.
+    pub fn get_descriptor_version(&self) -> Option<u64> {
+        self.descriptor_version
+    }
+
+    /// Return the correlation token provided by the publisher.
+    pub fn get_correlation_token(&self) -> &Option<String> {
+        &self.correlation_token
+    }
+
+    /// Return the identity of the publisher in serialized form.
+    pub fn get_publisher(&self) -> &str {
+        &self.publisher
+    }
+
+    /// Return the reason for the rejection.
+    pub fn get_error_message(&self) -> &str {
+        &self.error_message
+    }
+}
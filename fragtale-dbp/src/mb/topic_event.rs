@@ -20,13 +20,14 @@
 use crate::mb::ExtractedValue;
 use crate::mb::UniqueTime;
 use std::collections::HashMap;
-use tyst::Tyst;
 
 /// Event model.
 pub struct TopicEvent {
     event_id: String,
     document: String,
     priority: u8,
+    expires_ts: Option<u64>,
+    deliver_after_ts: Option<u64>,
     protection_ref: String,
     correlation_token: String,
     additional_columns: HashMap<String, ExtractedValue>,
@@ -36,9 +37,13 @@ pub struct TopicEvent {
 
 impl TopicEvent {
     /// Return a new instance.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
+        event_id: &str,
         document: &str,
         priority: u8,
+        expires_ts: Option<u64>,
+        deliver_after_ts: Option<u64>,
         protection_ref: &str,
         correlation_token: &str,
         additional_columns: HashMap<String, ExtractedValue>,
@@ -46,9 +51,11 @@ impl TopicEvent {
         unique_time: UniqueTime,
     ) -> Self {
         Self {
-            event_id: Self::event_id_from_document(document),
+            event_id: event_id.to_owned(),
             document: document.to_owned(),
             priority,
+            expires_ts,
+            deliver_after_ts,
             protection_ref: protection_ref.to_owned(),
             correlation_token: correlation_token.to_owned(),
             additional_columns,
@@ -57,17 +64,6 @@ impl TopicEvent {
         }
     }
 
-    /// Return the event_id (fingerprint) of the document.
-    pub fn event_id_from_document(document: &str) -> String {
-        tyst::encdec::hex::encode(
-            &Tyst::instance()
-                .digests()
-                .by_oid(&tyst::encdec::oid::as_string(tyst::oids::digest::SHA3_512))
-                .unwrap()
-                .hash(document.as_bytes()),
-        )
-    }
-
     /// Return the event identifier (message digest of the document).
     pub fn get_event_id(&self) -> &str {
         &self.event_id
@@ -83,6 +79,18 @@ impl TopicEvent {
         self.priority
     }
 
+    /// Return the deadline in epoch microseconds after which the event should
+    /// no longer be delivered (if any).
+    pub fn get_expires_ts(&self) -> Option<u64> {
+        self.expires_ts
+    }
+
+    /// Return the time in epoch microseconds before which the event should not
+    /// be delivered (if any).
+    pub fn get_deliver_after_ts(&self) -> Option<u64> {
+        self.deliver_after_ts
+    }
+
     /// Return the event integrity protection reference.
     pub fn get_protection_ref(&self) -> &str {
         &self.protection_ref
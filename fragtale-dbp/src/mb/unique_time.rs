@@ -159,6 +159,16 @@ impl UniqueTime {
         u16::try_from(self.0 & Self::BITMASK_10_BITS).unwrap()
     }
 
+    /// Return smallest value in bucket.
+    pub fn min_encoded_in_bucket(bucket: u64) -> u64 {
+        (bucket & Self::BITMASK_33_BITS) << 30
+    }
+
+    /// Return the first bucket of the shelf.
+    pub fn first_bucket_in_shelf(shelf: u16) -> u64 {
+        (u64::from(shelf) & Self::BITMASK_08_BITS) << 25
+    }
+
     /// Return largest value in bucket.
     pub fn max_encoded_in_bucket(bucket: u64) -> u64 {
         ((bucket & Self::BITMASK_33_BITS) << 30) | Self::BITMASK_30_BITS
@@ -23,8 +23,10 @@ pub mod util {
     //! Metric utilities.
 
     mod atomic_metric_average;
+    mod atomic_metric_histogram;
 
     pub use self::atomic_metric_average::AtomicMetricAverage;
+    pub use self::atomic_metric_histogram::AtomicMetricHistogram;
 }
 pub mod http_metrics_resource;
 pub mod metric;
@@ -99,6 +99,9 @@ impl Metric {
         }
         for metric_labeled_value in &self.metric_labeled_values {
             ret.push_str(&metric_name);
+            if let Some(metric_name_suffix) = metric_labeled_value.get_metric_name_suffix() {
+                ret.push_str(metric_name_suffix);
+            }
             if let Some(metric_lables) = metric_labeled_value.get_metric_labels() {
                 ret.push('{');
                 for (i, metric_label) in metric_lables.iter().enumerate() {
@@ -23,6 +23,7 @@ pub use super::MetricLabel;
 #[derive(Clone, Debug)]
 pub struct MetricLabeledValue {
     metric_labels: Option<Vec<MetricLabel>>,
+    metric_name_suffix: Option<&'static str>,
     metric_value: f64,
     metric_ts: Option<i64>,
 }
@@ -32,6 +33,7 @@ impl MetricLabeledValue {
     pub fn new(metric_value: f64) -> Self {
         Self {
             metric_labels: None,
+            metric_name_suffix: None,
             metric_value,
             metric_ts: None,
         }
@@ -56,6 +58,20 @@ impl MetricLabeledValue {
         self
     }
 
+    /// Builder style setting of an optional suffix to the metric name.
+    ///
+    /// This is used for the `_bucket`, `_sum` and `_count` series of a
+    /// histogram.
+    pub fn set_name_suffix(mut self, metric_name_suffix: &'static str) -> Self {
+        self.metric_name_suffix = Some(metric_name_suffix);
+        self
+    }
+
+    /// Get the optional suffix to the metric name.
+    pub fn get_metric_name_suffix(&self) -> Option<&'static str> {
+        self.metric_name_suffix
+    }
+
     /// Get the list of optional labeles.
     pub fn get_metric_labels(&self) -> &Option<Vec<MetricLabel>> {
         &self.metric_labels
@@ -27,8 +27,9 @@ pub enum MetricType {
     Counter,
     /// A single numerical value that can arbitrarily go up and down.
     Gauge,
-    // Unused
-    //Histogram,
+    /// Samples counted in configurable buckets with `_bucket`, `_sum` and
+    /// `_count` series.
+    Histogram,
     // Unused
     //Summary,
     /// No type for the metric is provided.
@@ -41,7 +42,7 @@ impl MetricType {
         match *self {
             Self::Counter => "counter",
             Self::Gauge => "gauge",
-            //Self::Histogram => "histogram",
+            Self::Histogram => "histogram",
             //Self::Summary => "summary",
             Self::Untyped => "untyped",
         }
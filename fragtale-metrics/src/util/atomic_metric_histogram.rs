@@ -0,0 +1,148 @@
+/*
+    Copyright 2025 MydriaTech AB
+
+    Licensed under the Apache License 2.0 with Free world makers exception
+    1.0.0 (the "License"); you may not use this file except in compliance with
+    the License. You should have obtained a copy of the License with the source
+    or binary distribution in file named
+
+        LICENSE-Apache-2.0-with-FWM-Exception-1.0.0
+
+    Unless required by applicable law or agreed to in writing, software
+    distributed under the License is distributed on an "AS IS" BASIS,
+    WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+    See the License for the specific language governing permissions and
+    limitations under the License.
+*/
+
+//! Atomic histogram tailored for metrics.
+
+use crate::metric::MetricLabeledValue;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+/** Atomic histogram of observed values tailored for metrics.
+
+Observations are counted in the first bucket with an upper bound larger than
+or equal to the value. Values above the largest upper bound are only counted
+in the implicit `+Inf` bucket.
+
+Unlike [super::AtomicMetricAverage], the histogram is never reset when read,
+since the buckets, sum and count are expected to be monotonically increasing
+counters in the `PrometheusText0.0.4` format.
+*/
+pub struct AtomicMetricHistogram {
+    upper_bounds: &'static [u64],
+    bucket_counts: Vec<AtomicU64>,
+    sum: AtomicU64,
+    count: AtomicU64,
+}
+
+impl AtomicMetricHistogram {
+    /// Upper bounds suitable for latencies in microseconds from 1 ms to 30 s.
+    pub const LATENCY_MICROS_BOUNDS: &'static [u64] = &[
+        1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 500_000, 1_000_000,
+        2_500_000, 5_000_000, 10_000_000, 30_000_000,
+    ];
+
+    const METRIC_LABEL_LE: &'static str = "le";
+
+    /// Return a new instance with the provided ascending bucket upper bounds.
+    pub fn new(upper_bounds: &'static [u64]) -> Self {
+        debug_assert!(upper_bounds.windows(2).all(|pair| pair[0] < pair[1]));
+        Self {
+            upper_bounds,
+            bucket_counts: upper_bounds.iter().map(|_| AtomicU64::default()).collect(),
+            sum: AtomicU64::default(),
+            count: AtomicU64::default(),
+        }
+    }
+
+    /// Count the observed value in its bucket.
+    pub fn observe(&self, value: u64) {
+        let index = self.upper_bounds.partition_point(|bound| *bound < value);
+        if let Some(bucket_count) = self.bucket_counts.get(index) {
+            bucket_count.fetch_add(1, Ordering::Relaxed);
+        }
+        self.sum.fetch_add(value, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Get the number of observed values.
+    pub fn get_count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Get the sum of all observed values.
+    pub fn get_sum(&self) -> u64 {
+        self.sum.load(Ordering::Relaxed)
+    }
+
+    /** Return the `_bucket`, `_sum` and `_count` series of the histogram.
+
+    Each series gets the provided `labels` and the buckets are cumulative with
+    an additional `le` label as expected for a
+    [crate::metric::MetricType::Histogram].
+    */
+    pub fn as_metric_labeled_values(
+        &self,
+        labels: &[(&'static str, String)],
+    ) -> Vec<MetricLabeledValue> {
+        let with_labels = |mut mlv: MetricLabeledValue| {
+            for (name, value) in labels {
+                mlv = mlv.add_label(*name, value.to_owned());
+            }
+            mlv
+        };
+        // Read count first, so the `+Inf` bucket is never lower than the others
+        let count = self.get_count();
+        let mut cumulative = 0;
+        let mut mlvs = Vec::with_capacity(self.upper_bounds.len() + 3);
+        for (upper_bound, bucket_count) in self.upper_bounds.iter().zip(&self.bucket_counts) {
+            cumulative += bucket_count.load(Ordering::Relaxed);
+            mlvs.push(
+                with_labels(MetricLabeledValue::new(cumulative as f64))
+                    .add_label(Self::METRIC_LABEL_LE, upper_bound.to_string())
+                    .set_name_suffix("_bucket"),
+            );
+        }
+        mlvs.push(
+            with_labels(MetricLabeledValue::new(count.max(cumulative) as f64))
+                .add_label(Self::METRIC_LABEL_LE, "+Inf".to_owned())
+                .set_name_suffix("_bucket"),
+        );
+        mlvs.push(
+            with_labels(MetricLabeledValue::new(self.get_sum() as f64)).set_name_suffix("_sum"),
+        );
+        mlvs.push(with_labels(MetricLabeledValue::new(count as f64)).set_name_suffix("_count"));
+        mlvs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_are_cumulative() {
+        let histogram = AtomicMetricHistogram::new(&[10, 100]);
+        for value in [5, 10, 11, 1000] {
+            histogram.observe(value);
+        }
+        let values = histogram
+            .as_metric_labeled_values(&[])
+            .iter()
+            .map(|mlv| (mlv.get_metric_name_suffix(), mlv.get_metric_value()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            values,
+            vec![
+                (Some("_bucket"), 2.0),
+                (Some("_bucket"), 3.0),
+                (Some("_bucket"), 4.0),
+                (Some("_sum"), 1026.0),
+                (Some("_count"), 4.0),
+            ]
+        );
+    }
+}
@@ -53,7 +53,19 @@ fn main() -> ExitCode {
             .with_writer(non_blocking)
             .init();
     }
-    let app_config = Arc::new(AppConfig::new(env!("CARGO_PKG_NAME"), startup_ts_micros));
+    let mut app_config = AppConfig::new(env!("CARGO_PKG_NAME"), startup_ts_micros)
+        .with_tracing_enabled(cfg!(feature = "tracing"));
+    match parse_dev_args(std::env::args().skip(1)) {
+        Ok(None) => {}
+        Ok(Some(fixtures_file)) => {
+            app_config = app_config.with_dev_mode(fixtures_file.as_deref());
+        }
+        Err(e) => {
+            log::error!("{e}");
+            return ExitCode::FAILURE;
+        }
+    }
+    let app_config = Arc::new(app_config);
     if app_config.limits.cpus() > 0.0 {
         // Defaults to using one thread per core when no limit is set.
         tokio::runtime::Builder::new_multi_thread()
@@ -71,6 +83,42 @@ fn main() -> ExitCode {
     }
 }
 
+/// Parse command line arguments for developer mode.
+///
+/// `--dev` runs a local broker with the in-memory backend and the static bearer
+/// token configured by `FRAGTALE_DEV_TOKEN` (if any). `--fixtures <file>`
+/// seeds it with topics and events.
+///
+/// Return `None` unless developer mode was requested and the optional
+/// fixtures file otherwise.
+fn parse_dev_args(
+    mut args: impl Iterator<Item = String>,
+) -> Result<Option<Option<String>>, String> {
+    let mut dev = false;
+    let mut fixtures_file = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dev" => dev = true,
+            "--fixtures" => {
+                fixtures_file = Some(args.next().ok_or("Missing file name after '--fixtures'.")?);
+            }
+            unknown => {
+                if let Some(value) = unknown.strip_prefix("--fixtures=") {
+                    fixtures_file = Some(value.to_owned());
+                } else {
+                    Err(format!(
+                        "Unknown argument '{unknown}'. Usage: fragtale [--dev [--fixtures <file>]]"
+                    ))?;
+                }
+            }
+        }
+    }
+    if !dev && fixtures_file.is_some() {
+        Err("'--fixtures' requires '--dev'.")?;
+    }
+    Ok(dev.then_some(fixtures_file))
+}
+
 /// Initialize the logging system and apply filters.
 fn init_logger() -> Result<(), log::SetLoggerError> {
     env_logger::builder()